@@ -251,16 +251,24 @@ fn stress_trail_request(root_id: NodeId, depth: u32) -> TrailConfigDto {
         root_id,
         mode: TrailMode::Neighborhood,
         target_id: None,
-        depth,
-        direction: TrailDirection::Outgoing,
-        caller_scope: TrailCallerScope::ProductionOnly,
+        depth: Some(depth),
+        direction: Some(TrailDirection::Outgoing),
+        caller_scope: Some(TrailCallerScope::ProductionOnly),
         edge_filter: Vec::new(),
         show_utility_calls: true,
         hide_speculative: false,
         story: false,
         node_filter: Vec::new(),
-        max_nodes: 2_000,
+        hide_trivial_accessors: false,
+        hide_generated: false,
+        entry_reachable_only: false,
+        max_paths: None,
+        edge_weight: Default::default(),
+        max_nodes: Some(2_000),
         layout_direction: LayoutDirection::Horizontal,
+        graph_session: None,
+        timeout_ms: None,
+        filter_preset: None,
     }
 }
 
@@ -131,6 +131,8 @@ fn build_cleanup_storage() -> anyhow::Result<CleanupFixture> {
                     end_line,
                     end_col: 20,
                 },
+                edge_id: None,
+                notebook_cell: None,
             });
 
             for call_idx in 0..CALLS_PER_CALLER {
@@ -173,6 +175,8 @@ fn build_cleanup_storage() -> anyhow::Result<CleanupFixture> {
                         end_line: start_line + call_idx as u32,
                         end_col: 14,
                     },
+                    edge_id: None,
+                    notebook_cell: None,
                 });
             }
         }
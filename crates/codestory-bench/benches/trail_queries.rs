@@ -0,0 +1,160 @@
+use codestory_contracts::events::EventBus;
+use codestory_contracts::graph::{NodeId, NodeKind, TrailConfig, TrailDirection, TrailMode};
+use codestory_indexer::WorkspaceIndexer;
+use codestory_store::Store as Storage;
+use codestory_workspace::{BuildMode, RefreshExecutionPlan};
+use criterion::{BatchSize, BenchmarkId, Criterion, criterion_group, criterion_main};
+use std::hint::black_box;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+use codestory_bench::util;
+
+const PROJECT_SIZES: &[usize] = &[64, 256, 1024];
+const CALL_FANOUT: usize = 3;
+const BFS_DEPTH: u32 = 4;
+const TRAIL_MAX_NODES: usize = 500;
+
+struct IndexedChainFixture {
+    _temp_dir: TempDir,
+    storage: Storage,
+    root_id: NodeId,
+    target_id: NodeId,
+}
+
+fn full_refresh(root: &Path, storage: &mut Storage, files: Vec<PathBuf>) {
+    let indexer = WorkspaceIndexer::new(root.to_path_buf());
+    let event_bus = EventBus::new();
+    let plan = RefreshExecutionPlan {
+        mode: BuildMode::FullRefresh,
+        files_to_index: files,
+        files_to_remove: Vec::new(),
+        existing_file_ids: Default::default(),
+    };
+    indexer
+        .run(storage, &plan, &event_bus, None)
+        .expect("index call chain fixture");
+}
+
+fn find_step(nodes: &[codestory_contracts::graph::Node], step: usize) -> NodeId {
+    let name = format!("step_{step}");
+    nodes
+        .iter()
+        .find(|node| {
+            node.kind == NodeKind::FUNCTION
+                && node
+                    .serialized_name
+                    .rsplit("::")
+                    .next()
+                    .and_then(|tail| tail.split('(').next())
+                    .is_some_and(|tail| tail == name)
+        })
+        .map(|node| node.id)
+        .unwrap_or_else(|| panic!("call chain fixture should define {name}"))
+}
+
+fn build_indexed_fixture(file_count: usize) -> IndexedChainFixture {
+    let temp_dir = util::generate_call_chain_project(file_count, CALL_FANOUT)
+        .expect("generate call chain fixture");
+    let root = temp_dir.path().to_path_buf();
+    let files = util::collect_files_with_extension(&root, "cpp");
+    let mut storage = Storage::open(root.join("codestory.db")).expect("open fixture storage");
+    full_refresh(&root, &mut storage, files);
+
+    let nodes = storage.get_nodes().expect("load fixture nodes");
+    let root_id = find_step(&nodes, 0);
+    let target_id = find_step(&nodes, file_count / 2);
+    IndexedChainFixture {
+        _temp_dir: temp_dir,
+        storage,
+        root_id,
+        target_id,
+    }
+}
+
+fn bench_call_chain_indexing(c: &mut Criterion) {
+    let mut group = c.benchmark_group("trail_fixture_indexing");
+    group.sample_size(10);
+    for &file_count in PROJECT_SIZES {
+        let temp_dir = util::generate_call_chain_project(file_count, CALL_FANOUT)
+            .expect("generate call chain fixture");
+        let root = temp_dir.path().to_path_buf();
+        let files = util::collect_files_with_extension(&root, "cpp");
+        group.bench_with_input(
+            BenchmarkId::from_parameter(file_count),
+            &files,
+            |b, files| {
+                // Only indexing is timed: each batch gets fresh storage and
+                // its own file list, and both are dropped outside the timing.
+                b.iter_batched(
+                    || {
+                        let storage_temp = tempfile::tempdir().expect("create storage tempdir");
+                        let storage = Storage::open(storage_temp.path().join("codestory.db"))
+                            .expect("open benchmark storage");
+                        (storage_temp, storage, files.clone())
+                    },
+                    |(storage_temp, mut storage, files)| {
+                        full_refresh(&root, &mut storage, files);
+                        (storage_temp, storage)
+                    },
+                    BatchSize::PerIteration,
+                )
+            },
+        );
+    }
+    group.finish();
+}
+
+fn bench_trail_queries(c: &mut Criterion) {
+    let fixtures = PROJECT_SIZES
+        .iter()
+        .map(|&file_count| (file_count, build_indexed_fixture(file_count)))
+        .collect::<Vec<_>>();
+
+    let mut bfs = c.benchmark_group("trail_bfs");
+    for (file_count, fixture) in &fixtures {
+        let config = TrailConfig {
+            root_id: fixture.root_id,
+            mode: TrailMode::Neighborhood,
+            depth: BFS_DEPTH,
+            direction: TrailDirection::Outgoing,
+            max_nodes: TRAIL_MAX_NODES,
+            ..TrailConfig::default()
+        };
+        bfs.bench_with_input(
+            BenchmarkId::from_parameter(file_count),
+            &config,
+            |b, config| b.iter(|| black_box(fixture.storage.get_trail(config).expect("bfs trail"))),
+        );
+    }
+    bfs.finish();
+
+    let mut to_target = c.benchmark_group("trail_to_target_symbol");
+    for (file_count, fixture) in &fixtures {
+        let config = TrailConfig {
+            root_id: fixture.root_id,
+            mode: TrailMode::ToTargetSymbol,
+            target_id: Some(fixture.target_id),
+            depth: 0,
+            direction: TrailDirection::Outgoing,
+            max_nodes: TRAIL_MAX_NODES,
+            ..TrailConfig::default()
+        };
+        let result = fixture.storage.get_trail(&config).expect("to-target trail");
+        assert!(
+            result.nodes.iter().any(|node| node.id == fixture.target_id),
+            "to-target fixture should reach its target"
+        );
+        to_target.bench_with_input(
+            BenchmarkId::from_parameter(file_count),
+            &config,
+            |b, config| {
+                b.iter(|| black_box(fixture.storage.get_trail(config).expect("to-target trail")))
+            },
+        );
+    }
+    to_target.finish();
+}
+
+criterion_group!(benches, bench_call_chain_indexing, bench_trail_queries);
+criterion_main!(benches);
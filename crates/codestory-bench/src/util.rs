@@ -94,6 +94,34 @@ pub fn generate_compile_commands(root: &Path, file_count: usize) -> anyhow::Resu
     Ok(())
 }
 
+/// Generate `step_{i}` C++ functions, one per file, where each step calls the
+/// next step plus `fanout - 1` strided ones. The result is a connected call
+/// graph whose depth grows with `file_count`, which keeps trail traversals
+/// from bottoming out after one hop the way the repo-scale shapes do.
+pub fn generate_call_chain_project(file_count: usize, fanout: usize) -> anyhow::Result<TempDir> {
+    let temp_dir = tempfile::tempdir()?;
+    let root = temp_dir.path();
+    let file_count = file_count.max(1);
+
+    for index in 0..file_count {
+        let callees = (0..fanout.max(1))
+            .map(|hop| (index + 1 + hop * 7) % file_count)
+            .collect::<Vec<_>>();
+        let mut content = String::new();
+        for callee in &callees {
+            content.push_str(&format!("void step_{callee}();\n"));
+        }
+        content.push_str(&format!("\nvoid step_{index}() {{\n"));
+        for callee in &callees {
+            content.push_str(&format!("    step_{callee}();\n"));
+        }
+        content.push_str("}\n");
+        fs::write(root.join(format!("file_{index}.cpp")), content)?;
+    }
+
+    Ok(temp_dir)
+}
+
 pub fn collect_files_with_extension(root: &Path, extension: &str) -> Vec<PathBuf> {
     let mut files = fs::read_dir(root)
         .unwrap()
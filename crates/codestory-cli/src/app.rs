@@ -92,6 +92,8 @@ pub(crate) mod resolution;
 mod search_command;
 mod server;
 mod source_commands;
+mod trail_defaults;
+mod trail_presets;
 
 pub(crate) use agent_context::packet_sufficiency_label;
 #[cfg(test)]
@@ -179,6 +181,12 @@ pub async fn run() -> ExitCode {
 }
 
 async fn run_cli(cli: Cli) -> Result<()> {
+    let db_key_env = match &cli.command {
+        Command::Serve(cmd) => cmd.db_key_env.as_deref(),
+        _ => None,
+    };
+    crate::config::install_storage_encryption_key(db_key_env)?;
+    crate::config::configure_storage_integrity_check();
     if let Some(mode) = lifecycle::embedding_client_transport_mode(&cli.command) {
         embedding_server_transport::install_client_transport(mode)
             .context("install native embedding server transport")?;
@@ -215,6 +223,11 @@ async fn run_cli(cli: Cli) -> Result<()> {
         Command::Explore(cmd) => explore::run_explore(cmd),
         Command::Files(cmd) => source_commands::run_files(cmd),
         Command::Affected(cmd) => source_commands::run_affected(cmd),
+        Command::Review(cmd) => source_commands::run_review(cmd),
+        Command::Literals(cmd) => source_commands::run_literals(cmd),
+        Command::History(cmd) => source_commands::run_history(cmd),
+        Command::TrailPreset(cmd) => trail_presets::run_trail_preset(cmd),
+        Command::TrailDefaults(cmd) => trail_defaults::run_trail_defaults(cmd),
         Command::Bookmark(cmd) => bookmarks::run_bookmark(cmd),
         Command::Serve(cmd) => server::run_serve(cmd).await,
         Command::GenerateCompletions(cmd) => server::run_generate_completions(cmd),
@@ -62,6 +62,7 @@ pub(in crate::app) fn run_context(cmd: ContextCommand) -> Result<()> {
             latency_budget_ms: None,
             include_evidence: !cmd.no_evidence,
             hybrid_weights: None,
+            intent: None,
         };
 
         let mut answer = runtime.browser.ask(request).map_err(map_api_error)?;
@@ -11,7 +11,7 @@ use anyhow::Context;
 use anyhow::{Result, bail};
 use codestory_contracts::api::{
     BookmarkCategoryDto, BookmarkDto, CreateBookmarkCategoryRequest, CreateBookmarkRequest,
-    NodeKind,
+    ListBookmarksRequest, NodeKind,
 };
 
 pub(super) fn run_bookmark(cmd: BookmarkCommand) -> Result<()> {
@@ -68,16 +68,19 @@ fn run_bookmark_list(cmd: BookmarkListCommand) -> Result<()> {
         .as_deref()
         .map(|category| resolve_bookmark_category_id(&categories, category))
         .transpose()?;
-    let bookmarks = runtime
+    let page = runtime
         .bookmarks
-        .list_bookmarks(category_id)
-        .map_err(map_api_error)?
-        .into_iter()
-        .map(bookmark_output)
-        .collect::<Vec<_>>();
+        .list_bookmarks_page(ListBookmarksRequest {
+            category_id: category_id.map(|id| id.to_string()),
+            offset: cmd.offset,
+            limit: cmd.limit,
+        })
+        .map_err(map_api_error)?;
     let output = BookmarkListOutput {
         categories,
-        bookmarks,
+        bookmarks: page.items.into_iter().map(bookmark_output).collect(),
+        total: page.total,
+        next_offset: page.next_offset,
     };
     emit(
         cmd.format,
@@ -215,6 +218,12 @@ fn render_bookmark_list_markdown(output: &BookmarkListOutput) -> String {
     for bookmark in &output.bookmarks {
         markdown.push_str(&render_bookmark_row(bookmark));
     }
+    if let Some(next_offset) = output.next_offset {
+        markdown.push_str(&format!(
+            "next_offset: {next_offset} of {} bookmarks\n",
+            output.total
+        ));
+    }
     markdown
 }
 
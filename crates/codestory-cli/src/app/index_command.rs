@@ -102,6 +102,37 @@ fn run_index_once(cmd: &IndexCommand) -> Result<()> {
         return emit(cmd.format, &output, markdown, cmd.output_file.as_deref());
     }
 
+    runtime
+        .index
+        .set_indexing_max_threads(cmd.max_threads.map(|threads| threads as usize));
+    if let Some(dependencies) = cmd.dependencies {
+        runtime.open_project_summary()?;
+        runtime
+            .index
+            .set_dependency_resolution(dependencies.enabled())
+            .map_err(map_api_error)?;
+    }
+    if let Some(iterations) = cmd.centrality_iterations {
+        runtime.open_project_summary()?;
+        runtime
+            .index
+            .set_centrality_iterations(iterations as usize)
+            .map_err(map_api_error)?;
+    }
+    if let Some(string_literals) = cmd.string_literals {
+        runtime.open_project_summary()?;
+        runtime
+            .index
+            .set_string_literal_indexing(string_literals.enabled())
+            .map_err(map_api_error)?;
+    }
+    if !cmd.open_files.is_empty() {
+        runtime.open_project_summary()?;
+        runtime
+            .index
+            .set_open_files(&cmd.open_files)
+            .map_err(map_api_error)?;
+    }
     let progress = if cmd.progress {
         Some(spawn_progress_printer(runtime.events.clone()))
     } else {
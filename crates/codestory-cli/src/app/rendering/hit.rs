@@ -153,6 +153,9 @@ pub(in crate::app) fn collect_search_hit_occurrences<'a>(
         }
         if let Ok(occurrences) = runtime.browser.node_occurrences(NodeOccurrencesRequest {
             id: hit.node_id.clone(),
+            kind: None,
+            offset: None,
+            limit: None,
         }) {
             occurrences_by_node.insert(hit.node_id.clone(), occurrences);
         }
@@ -1,7 +1,10 @@
 use anyhow::{Context, Result, bail};
 use clap::CommandFactory;
 use clap_complete::{Shell, generate};
-use std::net::{TcpListener, ToSocketAddrs};
+use std::net::ToSocketAddrs;
+use std::sync::Arc;
+use tokio::net::TcpListener;
+use tokio::sync::Semaphore;
 
 use crate::runtime::ensure_index_ready;
 use crate::{
@@ -25,20 +28,83 @@ pub(super) async fn run_serve(cmd: ServeCommand) -> Result<()> {
     let opened = runtime.ensure_open(cmd.refresh)?;
     ensure_index_ready(&opened, "serve")?;
     let listener = TcpListener::bind(&cmd.addr)
+        .await
         .with_context(|| format!("Failed to bind server to {}", cmd.addr))?;
     eprintln!("codestory serve listening on http://{}", cmd.addr);
-    let policy = http_transport::HttpServePolicy::new(cmd.allow_non_loopback);
-    for stream in listener.incoming() {
-        match stream {
-            Ok(stream) => {
-                if let Err(error) = http_transport::handle_http_request(&runtime, stream, policy) {
-                    eprintln!("serve request failed: {error:#}");
+    let tokens = match crate::config::read_serve_tokens(cmd.tokens_env.as_deref())? {
+        Some(raw) => http_transport::parse_http_serve_tokens(&raw)?,
+        None => Vec::new(),
+    };
+    if !tokens.is_empty() {
+        eprintln!(
+            "codestory serve requires bearer tokens ({} configured)",
+            tokens.len()
+        );
+    }
+    let mut policy = http_transport::HttpServePolicy::new(cmd.allow_non_loopback, tokens);
+    if let Some(per_minute) = cmd.rate_limit {
+        eprintln!(
+            "codestory serve limits graph and search routes to {per_minute} requests per minute (burst {})",
+            cmd.rate_burst
+        );
+        policy = policy.with_rate_limit(per_minute, cmd.rate_burst);
+    }
+    let policy = policy.with_trail_limit(cmd.max_trails);
+    // Storage reads are synchronous SQLite calls, so each request runs on a
+    // blocking thread. Requests share one runtime, and so its caches and
+    // the index it reopens after `/review?reindex=1`; the worker permits
+    // only bound how many run at once, and a request waiting for one never
+    // holds up the accept loop. At most `--max-queued` connections wait for
+    // a worker; the accept loop answers the rest with 503 straight away.
+    let runtime = Arc::new(runtime);
+    let policy = Arc::new(policy);
+    let permits = Arc::new(Semaphore::new(cmd.workers as usize));
+    let admitted = Arc::new(Semaphore::new((cmd.workers + cmd.max_queued) as usize));
+    loop {
+        let stream = match listener.accept().await {
+            Ok((stream, _)) => stream,
+            Err(error) => {
+                eprintln!("serve accept failed: {error}");
+                continue;
+            }
+        };
+        let stream = match stream
+            .into_std()
+            .and_then(|stream| stream.set_nonblocking(false).map(|()| stream))
+        {
+            Ok(stream) => stream,
+            Err(error) => {
+                eprintln!("serve accept failed: {error}");
+                continue;
+            }
+        };
+        let Ok(admission) = Arc::clone(&admitted).try_acquire_owned() else {
+            tokio::task::spawn_blocking(move || {
+                if let Err(error) = http_transport::reject_http_request_busy(stream) {
+                    eprintln!("serve busy response failed: {error:#}");
                 }
+            });
+            continue;
+        };
+        let runtime = Arc::clone(&runtime);
+        let policy = Arc::clone(&policy);
+        let permits = Arc::clone(&permits);
+        tokio::spawn(async move {
+            let _admission = admission;
+            let Ok(_permit) = permits.acquire_owned().await else {
+                return;
+            };
+            let handled = tokio::task::spawn_blocking(move || {
+                http_transport::handle_http_request(&runtime, stream, &policy)
+            })
+            .await;
+            match handled {
+                Ok(Ok(())) => {}
+                Ok(Err(error)) => eprintln!("serve request failed: {error:#}"),
+                Err(error) => eprintln!("serve request panicked: {error}"),
             }
-            Err(error) => eprintln!("serve accept failed: {error}"),
-        }
+        });
     }
-    Ok(())
 }
 
 pub(super) fn ensure_http_serve_bind_allowed(addr: &str, allow_non_loopback: bool) -> Result<()> {
@@ -62,7 +128,7 @@ pub(super) fn ensure_http_serve_bind_allowed(addr: &str, allow_non_loopback: boo
 
     bail!(
         "Refusing to bind HTTP serve to non-loopback address `{addr}` without --allow-non-loopback. \
-serve exposes local graph/search endpoints without request authentication; bind to 127.0.0.1/localhost \
+serve exposes local graph/search endpoints and only authenticates requests when serve tokens are configured; bind to 127.0.0.1/localhost \
 or rerun with --allow-non-loopback only behind an intentional network boundary."
     )
 }
@@ -1,10 +1,16 @@
 mod affected;
 mod affected_rendering;
+mod history;
+mod literals;
+mod review;
 mod source_read;
 mod symbol;
 mod trail;
 
 pub(super) use affected::{affected_path_record, run_affected};
+pub(super) use history::run_history;
+pub(super) use literals::run_literals;
+pub(super) use review::run_review;
 pub(super) use source_read::{run_files, run_query, run_snippet};
 pub(super) use symbol::{run_symbol, run_symbol_workflow};
 pub(super) use trail::{run_callees, run_callers, run_trace, run_trail};
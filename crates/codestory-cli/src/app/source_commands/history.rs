@@ -0,0 +1,107 @@
+use super::super::artifacts::{ensure_dot_only_for_trail, preflight_output_file};
+use crate::args::HistoryCommand;
+use crate::output::{RenderedPublicOutput, emit_public_operation};
+use crate::runtime::{RuntimeContext, ensure_index_ready, map_api_error};
+use anyhow::Result;
+use codestory_contracts::api::{
+    GraphAtDto, GraphAtRequest, IndexHistoryRunsDto, IndexHistorySubjectDto, NodeHistoryDto,
+    NodeHistoryRequest, NodeId,
+};
+use std::fmt::Write as _;
+
+pub(in crate::app) fn run_history(cmd: HistoryCommand) -> Result<()> {
+    ensure_dot_only_for_trail(cmd.format, "history")?;
+    preflight_output_file(cmd.output_file.as_deref())?;
+    let runtime = RuntimeContext::new(&cmd.project)?;
+    let opened = runtime.ensure_open(cmd.refresh)?;
+    ensure_index_ready(&opened, "history")?;
+    let operation = runtime.run_public_operation("history", || {
+        if let Some(id) = cmd.id.as_deref() {
+            let output = runtime
+                .browser
+                .node_history(NodeHistoryRequest {
+                    id: NodeId(id.trim().to_string()),
+                })
+                .map_err(map_api_error)?;
+            return RenderedPublicOutput::structured(&output, render_node_history(&output));
+        }
+        if let Some(run_id) = cmd.run.as_deref() {
+            let output = runtime
+                .browser
+                .graph_at(GraphAtRequest {
+                    run_id: run_id.to_string(),
+                })
+                .map_err(map_api_error)?;
+            return RenderedPublicOutput::structured(&output, render_graph_at(&output));
+        }
+        let output = runtime
+            .browser
+            .index_history_runs()
+            .map_err(map_api_error)?;
+        RenderedPublicOutput::structured(&output, render_history_runs(&output))
+    })?;
+    emit_public_operation(cmd.format, operation, cmd.output_file.as_deref())
+}
+
+fn render_history_runs(output: &IndexHistoryRunsDto) -> String {
+    let mut markdown = String::from("# history\n\n");
+    if output.runs.is_empty() {
+        markdown.push_str("No indexing run is recorded yet.\n");
+        return markdown;
+    }
+    for run in &output.runs {
+        let _ = writeln!(
+            markdown,
+            "- `{}` recorded at {} ms",
+            run.run_id, run.recorded_at_epoch_ms
+        );
+    }
+    markdown
+}
+
+fn render_node_history(output: &NodeHistoryDto) -> String {
+    let mut markdown = String::from("# history\n\n");
+    let _ = writeln!(
+        markdown,
+        "- node: `{}`; events: {}",
+        output.node_id.0,
+        output.events.len()
+    );
+    if output.events.is_empty() {
+        markdown.push_str("\nNo retained run added or removed this node or its edges.\n");
+        return markdown;
+    }
+    markdown.push('\n');
+    for event in &output.events {
+        let change = if event.added { "added" } else { "removed" };
+        let subject = match &event.subject {
+            IndexHistorySubjectDto::Node { id, kind, name } => {
+                format!("{kind:?} `{name}` (id `{}`)", id.0)
+            }
+            IndexHistorySubjectDto::Edge {
+                id,
+                kind,
+                source,
+                target,
+            } => format!(
+                "{kind:?} edge `{}` -> `{}` (id `{}`)",
+                source.0, target.0, id.0
+            ),
+        };
+        let _ = writeln!(markdown, "- `{}`: {change} {subject}", event.run_id);
+    }
+    markdown
+}
+
+fn render_graph_at(output: &GraphAtDto) -> String {
+    let mut markdown = String::from("# history\n\n");
+    let _ = writeln!(
+        markdown,
+        "- run: `{}` recorded at {} ms; nodes: {}; edges: {}",
+        output.run.run_id,
+        output.run.recorded_at_epoch_ms,
+        output.node_ids.len(),
+        output.edge_ids.len()
+    );
+    markdown
+}
@@ -0,0 +1,66 @@
+use super::super::artifacts::{ensure_dot_only_for_trail, preflight_output_file};
+use crate::args::LiteralsCommand;
+use crate::output::{RenderedPublicOutput, emit_public_operation};
+use crate::runtime::{RuntimeContext, ensure_index_ready, map_api_error};
+use anyhow::Result;
+use codestory_contracts::api::{StringLiteralSearchDto, StringLiteralSearchRequest};
+use std::fmt::Write as _;
+
+pub(in crate::app) fn run_literals(cmd: LiteralsCommand) -> Result<()> {
+    ensure_dot_only_for_trail(cmd.format, "literals")?;
+    preflight_output_file(cmd.output_file.as_deref())?;
+    let runtime = RuntimeContext::new(&cmd.project)?;
+    let opened = runtime.ensure_open(cmd.refresh)?;
+    ensure_index_ready(&opened, "literals")?;
+    let operation = runtime.run_public_operation("literals", || {
+        let output = runtime
+            .browser
+            .search_string_literals(StringLiteralSearchRequest {
+                query: cmd.query.clone(),
+                limit: Some(cmd.limit),
+            })
+            .map_err(map_api_error)?;
+        let markdown = render_literals_markdown(&cmd.query, &output);
+        RenderedPublicOutput::structured(&output, markdown)
+    })?;
+    emit_public_operation(cmd.format, operation, cmd.output_file.as_deref())
+}
+
+fn render_literals_markdown(query: &str, output: &StringLiteralSearchDto) -> String {
+    let mut markdown = String::new();
+    markdown.push_str("# literals\n\n");
+    let _ = writeln!(
+        markdown,
+        "- query: `{query}`; matches: {}; truncated: {}",
+        output.matches.len(),
+        output.truncated
+    );
+    if output.matches.is_empty() {
+        markdown.push_str(
+            "\nNo literal matched. Literals are only indexed after \
+             `index --string-literals on`.\n",
+        );
+        return markdown;
+    }
+    markdown.push('\n');
+    for found in &output.matches {
+        let _ = write!(
+            markdown,
+            "- `{}:{}:{}` ",
+            found.file_path, found.line, found.column
+        );
+        match &found.name {
+            Some(name) => {
+                let _ = write!(markdown, "`{name} = {}`", found.value);
+            }
+            None => {
+                let _ = write!(markdown, "{:?}", found.value);
+            }
+        }
+        if let (Some(id), Some(symbol)) = (&found.symbol_id, &found.symbol_name) {
+            let _ = write!(markdown, " in `{symbol}` (id `{}`)", id.0);
+        }
+        markdown.push('\n');
+    }
+    markdown
+}
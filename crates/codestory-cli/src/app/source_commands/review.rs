@@ -0,0 +1,201 @@
+use super::super::artifacts::{ensure_dot_only_for_trail, preflight_output_file};
+use crate::args::ReviewCommand;
+use crate::output::{RenderedPublicOutput, emit_public_operation};
+use crate::runtime::{RuntimeContext, ensure_index_ready, map_api_error};
+use anyhow::{Context, Result, bail};
+use codestory_contracts::api::{ReviewGraphDto, ReviewGraphRequest, ReviewSymbolDto};
+use std::fmt::Write as _;
+use std::io::Read;
+use std::path::Path;
+
+pub(in crate::app) fn run_review(cmd: ReviewCommand) -> Result<()> {
+    ensure_dot_only_for_trail(cmd.format, "review")?;
+    preflight_output_file(cmd.output_file.as_deref())?;
+    if cmd.reindex
+        && let Some(head) = &cmd.head
+    {
+        ensure_head_checked_out(&cmd.project.project, head)?;
+    }
+    let diff = review_diff_text(&cmd)?;
+    let runtime = RuntimeContext::new(&cmd.project)?;
+    let opened = runtime.ensure_open(cmd.refresh)?;
+    ensure_index_ready(&opened, "review")?;
+    let reindexed_files = if cmd.reindex {
+        runtime
+            .index
+            .reindex_review_diff(&diff)
+            .map_err(map_api_error)?
+    } else {
+        Vec::new()
+    };
+    let operation = runtime.run_public_operation("review", || {
+        let mut output = runtime
+            .browser
+            .review_graph(ReviewGraphRequest {
+                diff: diff.clone(),
+                depth: Some(cmd.depth),
+            })
+            .map_err(map_api_error)?;
+        output.reindexed_files = reindexed_files.clone();
+        let markdown = render_review_markdown(&output);
+        RenderedPublicOutput::structured(&output, markdown)
+    })?;
+    emit_public_operation(cmd.format, operation, cmd.output_file.as_deref())
+}
+
+fn review_diff_text(cmd: &ReviewCommand) -> Result<String> {
+    if let Some(path) = &cmd.diff {
+        if path == Path::new("-") {
+            let mut diff = String::new();
+            std::io::stdin()
+                .read_to_string(&mut diff)
+                .context("Failed to read the diff from stdin")?;
+            return Ok(diff);
+        }
+        return std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read diff {}", path.display()));
+    }
+    let mut command = std::process::Command::new("git");
+    command
+        .arg("-C")
+        .arg(&cmd.project.project)
+        .arg("diff")
+        .arg("--no-color")
+        .arg("--no-ext-diff")
+        .arg("--unified=0");
+    match &cmd.base {
+        Some(base) => {
+            command.arg(base);
+            if let Some(head) = &cmd.head {
+                command.arg(head);
+            }
+        }
+        None => {
+            command.arg("HEAD");
+        }
+    }
+    let output = command.output().context("Failed to run git diff")?;
+    if !output.status.success() {
+        bail!(
+            "git diff failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    String::from_utf8(output.stdout).context("git diff output is not UTF-8")
+}
+
+/// `--reindex` indexes the working tree, so it only matches the diff's new
+/// side when `head` is the commit checked out.
+fn ensure_head_checked_out(project: &Path, head: &str) -> Result<()> {
+    let resolve = |reference: &str| -> Result<String> {
+        let output = std::process::Command::new("git")
+            .arg("-C")
+            .arg(project)
+            .args(["rev-parse", "--verify", "--quiet"])
+            .arg(format!("{reference}^{{commit}}"))
+            .output()
+            .context("Failed to run git rev-parse")?;
+        if !output.status.success() {
+            bail!(
+                "`{reference}` does not name a commit in {}",
+                project.display()
+            );
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+    if resolve(head)? != resolve("HEAD")? {
+        bail!(
+            "--reindex indexes the working tree, but --head `{head}` is not checked out. Check it out first, or drop --head to review against the working tree."
+        );
+    }
+    Ok(())
+}
+
+fn render_review_markdown(output: &ReviewGraphDto) -> String {
+    let mut markdown = String::new();
+    markdown.push_str("# review\n\n");
+    let _ = writeln!(
+        markdown,
+        "- changed files: {}; changed symbols: {}; callers: {}; tests: {}; truncated: {}",
+        output.changed_files.len(),
+        output.changed_symbols.len(),
+        output.callers.len(),
+        output.tests.len(),
+        output.truncated
+    );
+    if !output.reindexed_files.is_empty() {
+        let _ = writeln!(
+            markdown,
+            "- reindexed: {}",
+            output.reindexed_files.join(", ")
+        );
+    }
+
+    markdown.push_str("\n## Changed Files\n\n");
+    for file in &output.changed_files {
+        let kind = format!("{:?}", file.kind).to_lowercase();
+        let _ = write!(markdown, "- `{}` ({kind}", file.path);
+        if let Some(previous_path) = &file.previous_path {
+            let _ = write!(markdown, " from `{previous_path}`");
+        }
+        let ranges = file
+            .changed_lines
+            .iter()
+            .map(|range| {
+                if range.start_line == range.end_line {
+                    range.start_line.to_string()
+                } else {
+                    format!("{}-{}", range.start_line, range.end_line)
+                }
+            })
+            .collect::<Vec<_>>();
+        if !ranges.is_empty() {
+            let _ = write!(markdown, "; lines {}", ranges.join(", "));
+        }
+        markdown.push_str(")\n");
+    }
+
+    append_review_symbols(&mut markdown, "Changed Symbols", &output.changed_symbols);
+    append_review_symbols(&mut markdown, "Callers", &output.callers);
+    if !output.tests.is_empty() {
+        markdown.push_str("\n## Tests Reaching Changes\n\n");
+        for test in &output.tests {
+            let _ = writeln!(
+                markdown,
+                "- `{}` ({}) distance {}",
+                test.display_name,
+                symbol_location(test.file_path.as_deref(), test.line),
+                test.distance
+            );
+        }
+    }
+    markdown
+}
+
+fn append_review_symbols(markdown: &mut String, heading: &str, symbols: &[ReviewSymbolDto]) {
+    if symbols.is_empty() {
+        return;
+    }
+    let _ = writeln!(markdown, "\n## {heading}\n");
+    for symbol in symbols {
+        let kind = format!("{:?}", symbol.kind).to_lowercase();
+        let _ = write!(
+            markdown,
+            "- `{}` {kind} ({})",
+            symbol.display_name,
+            symbol_location(symbol.file_path.as_deref(), symbol.line)
+        );
+        if symbol.distance > 0 {
+            let _ = write!(markdown, " distance {}", symbol.distance);
+        }
+        markdown.push('\n');
+    }
+}
+
+fn symbol_location(file_path: Option<&str>, line: Option<u32>) -> String {
+    match (file_path, line) {
+        (Some(path), Some(line)) => format!("{path}:{line}"),
+        (Some(path), None) => path.to_string(),
+        _ => "unknown location".to_string(),
+    }
+}
@@ -100,10 +100,9 @@ pub(in crate::app) fn run_query(cmd: QueryCommand) -> Result<()> {
     let opened = runtime.ensure_open(cmd.refresh)?;
     ensure_index_ready(&opened, "query")?;
     let operation = runtime.run_public_operation("graph", || {
-        let items = runtime
-            .browser
-            .query(&ast)
-            .map_err(map_api_error)?
+        let found = runtime.browser.query(&ast).map_err(map_api_error)?;
+        let items = found
+            .items
             .iter()
             .map(|item| explore::browser_query_item_to_output(&runtime.project_root, item))
             .collect();
@@ -111,6 +110,7 @@ pub(in crate::app) fn run_query(cmd: QueryCommand) -> Result<()> {
             query: query.to_string(),
             ast: ast.clone(),
             items,
+            truncated: found.truncated,
         };
         let markdown = render_query_markdown(&output);
         RenderedPublicOutput::structured(&output, markdown)
@@ -13,7 +13,7 @@ use crate::output::{
 use crate::runtime;
 use crate::runtime::{AmbiguousTargetError, RuntimeContext, ensure_index_ready, map_api_error};
 use anyhow::{Result, bail};
-use codestory_contracts::api::TrailContextDto;
+use codestory_contracts::api::{OpenDefinitionRequest, TrailContextDto};
 use std::fmt::Write as _;
 
 pub(in crate::app) fn run_symbol(cmd: SymbolCommand) -> Result<()> {
@@ -29,6 +29,7 @@ pub(in crate::app) fn run_symbol(cmd: SymbolCommand) -> Result<()> {
     } else {
         "graph"
     };
+    let mut resolved_id = None;
     let operation = runtime.run_public_operation(operation, || {
         let target = resolve_target_or_emit_ambiguity(
             &runtime,
@@ -37,6 +38,7 @@ pub(in crate::app) fn run_symbol(cmd: SymbolCommand) -> Result<()> {
             cmd.format,
             cmd.output_file.as_deref(),
         )?;
+        resolved_id = Some(target.selected.node_id.clone());
         let context = runtime
             .browser
             .symbol_context(target.selected.node_id.clone())
@@ -59,7 +61,18 @@ pub(in crate::app) fn run_symbol(cmd: SymbolCommand) -> Result<()> {
         };
         RenderedPublicOutput::structured(&output, markdown)
     })?;
-    emit_public_operation(cmd.format, operation, cmd.output_file.as_deref())
+    emit_public_operation(cmd.format, operation, cmd.output_file.as_deref())?;
+    if let (true, Some(node_id)) = (cmd.open, resolved_id) {
+        let response = runtime
+            .grounding
+            .open_definition(OpenDefinitionRequest {
+                node_id,
+                definition_index: cmd.definition,
+            })
+            .map_err(map_api_error)?;
+        eprintln!("{}", response.message);
+    }
+    Ok(())
 }
 
 #[derive(serde::Serialize)]
@@ -9,6 +9,7 @@ use crate::output::{
 };
 use crate::runtime::{RuntimeContext, ensure_index_ready, map_api_error};
 use anyhow::{Result, bail};
+use codestory_contracts::api::NodeId;
 use std::fmt::Write as _;
 
 pub(in crate::app) fn run_trail(cmd: TrailCommand) -> Result<()> {
@@ -19,6 +20,9 @@ pub(in crate::app) fn run_trail(cmd: TrailCommand) -> Result<()> {
     if cmd.story && cmd.format == args::OutputFormat::Dot {
         bail!("--story cannot be combined with --format dot; use markdown or json output");
     }
+    if cmd.html && (cmd.story || cmd.mermaid || cmd.format != args::OutputFormat::Markdown) {
+        bail!("--html cannot be combined with --story, --mermaid, or --format json/dot");
+    }
     let runtime = RuntimeContext::new(&cmd.project)?;
     let opened = runtime.ensure_open(cmd.refresh)?;
     ensure_index_ready(&opened, "trail")?;
@@ -37,7 +41,16 @@ pub(in crate::app) fn run_trail(cmd: TrailCommand) -> Result<()> {
             cmd.format,
             cmd.output_file.as_deref(),
         )?;
-        let request = build_trail_request(&target.selected.node_id, &cmd);
+        let mut request = build_trail_request(&target.selected.node_id, &cmd);
+        request.graph_session =
+            runtime.request_graph_session(&node_ids(&cmd.hide), &node_ids(&cmd.collapse))?;
+        if cmd.html {
+            let html = runtime
+                .browser
+                .export_trail_html(request)
+                .map_err(map_api_error)?;
+            return Ok(RenderedPublicOutput::text(html));
+        }
         let context = runtime
             .browser
             .trail_context(request)
@@ -87,12 +100,16 @@ pub(in crate::app) fn run_callees(mut cmd: TrailCommand) -> Result<()> {
 }
 
 pub(in crate::app) fn run_trace(mut cmd: TrailCommand) -> Result<()> {
-    if !cmd.mermaid && cmd.format != args::OutputFormat::Dot {
+    if !cmd.mermaid && !cmd.html && cmd.format != args::OutputFormat::Dot {
         cmd.story = true;
     }
     run_trail(cmd)
 }
 
+fn node_ids(ids: &[String]) -> Vec<NodeId> {
+    ids.iter().map(|id| NodeId(id.clone())).collect()
+}
+
 pub(super) fn trail_guidance_notes(
     context: &codestory_contracts::api::TrailContextDto,
 ) -> Vec<String> {
@@ -127,6 +127,11 @@ fn index_watch_rejects_output_file_inside_project_tree() {
         summarize: false,
         progress: false,
         watch: true,
+        max_threads: None,
+        dependencies: None,
+        centrality_iterations: None,
+        string_literals: None,
+        open_files: Vec::new(),
     };
 
     let error =
@@ -171,7 +176,14 @@ fn hide_speculative_trail_edges_prunes_disconnected_nodes() {
                 sample_graph_edge("e3", "c", "d", Some("certain")),
             ],
             truncated: false,
+            truncated_by_timeout: false,
             omitted_edge_count: 0,
+            pruned_test_node_count: 0,
+            pruned_accessor_node_count: 0,
+            pruned_generated_node_count: 0,
+            omitted_node_count: 0,
+            node_limit: None,
+            paths: Vec::new(),
             canonical_layout: None,
         },
         story: None,
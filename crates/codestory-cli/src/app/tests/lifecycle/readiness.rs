@@ -169,6 +169,10 @@ fn agent_preflight_allows_full_surfaces_from_full_agent_lane() {
         file_count: 1,
         error_count: 0,
         fatal_error_count: 0,
+        occurrence_count: 0,
+        database_bytes: 0,
+        last_indexed_at_epoch_ms: None,
+        tables: Vec::new(),
     };
     let verdicts = build_summary_readiness("C:/repo", &stats, None, &agent_status);
     let agent_verdict = verdicts
@@ -83,6 +83,9 @@ fn build_search_output_adds_occurrence_quality_and_verification_targets() {
                 start_col: 1,
                 end_line: 12,
                 end_col: 20,
+                notebook_cell_index: None,
+                notebook_cell_line: None,
+                stale: false,
             },
             SourceOccurrenceDto {
                 element_id: "1".to_string(),
@@ -92,6 +95,9 @@ fn build_search_output_adds_occurrence_quality_and_verification_targets() {
                 start_col: 1,
                 end_line: 60,
                 end_col: 1,
+                notebook_cell_index: None,
+                notebook_cell_line: None,
+                stale: false,
             },
         ],
     );
@@ -206,11 +206,16 @@ pub(super) fn summary_with_files(file_count: u32) -> ProjectSummary {
             file_count,
             error_count: 0,
             fatal_error_count: 0,
+            occurrence_count: 0,
+            database_bytes: 0,
+            last_indexed_at_epoch_ms: None,
+            tables: Vec::new(),
         },
         members: Vec::new(),
         retrieval: None,
         freshness: None,
         publication: None,
+        storage_generation: None,
     }
 }
 
@@ -325,6 +330,7 @@ pub(super) fn sample_phase_timings() -> IndexingPhaseTimings {
         deferred_indexes_ms: Some(7),
         summary_snapshot_ms: Some(8),
         detail_snapshot_ms: Some(9),
+        analyze_ms: None,
         publish_ms: Some(10),
         staged_sqlite_wal_autocheckpoint_bytes: Some(67_108_864),
         staged_sqlite_checkpoint_ms: Some(11),
@@ -401,6 +407,7 @@ pub(super) fn sample_phase_timings() -> IndexingPhaseTimings {
         resolved_imports_global_unique: Some(53),
         resolved_imports_fuzzy: Some(54),
         resolved_imports_semantic: Some(55),
+        run_delta: None,
     }
 }
 
@@ -419,6 +426,7 @@ pub(super) fn sample_node_details(id: &str, display_name: &str) -> NodeDetailsDt
         end_col: None,
         member_access: None,
         route_endpoint: None,
+        definitions: Vec::new(),
         evidence_tier: None,
         evidence_producer: None,
         resolution_status: None,
@@ -438,6 +446,9 @@ pub(super) fn sample_graph_node(id: &str, label: &str) -> GraphNodeDto {
         file_path: None,
         qualified_name: None,
         member_access: None,
+        edge_counts: None,
+        badges: Default::default(),
+        centrality: None,
     }
 }
 
@@ -458,14 +469,13 @@ pub(super) fn sample_graph_edge_with_kind(
     certainty: Option<&str>,
 ) -> GraphEdgeDto {
     GraphEdgeDto {
-        id: EdgeId(id.to_string()),
-        source: NodeId(source.to_string()),
-        target: NodeId(target.to_string()),
-        kind,
-        confidence: None,
         certainty: certainty.map(ToOwned::to_owned),
-        callsite_identity: None,
-        candidate_targets: Vec::new(),
+        ..GraphEdgeDto::new(
+            EdgeId(id.to_string()),
+            NodeId(source.to_string()),
+            NodeId(target.to_string()),
+            kind,
+        )
     }
 }
 
@@ -489,5 +499,6 @@ pub(super) fn test_search_hit_defaults() -> SearchHit {
         source_excerpt: None,
         verification_targets: Vec::new(),
         score_breakdown: None,
+        highlight: None,
     }
 }
@@ -0,0 +1,104 @@
+use super::artifacts::{ensure_dot_only_for_trail, preflight_output_file};
+use crate::args::{
+    TrailDefaultsAction, TrailDefaultsCommand, TrailDefaultsSetCommand, TrailDefaultsShowCommand,
+};
+use crate::display::format_direction;
+use crate::output::emit;
+use crate::runtime::{RuntimeContext, map_api_error};
+use anyhow::Result;
+use codestory_contracts::api::{TrailCallerScope, TrailDefaultsDto};
+use std::fmt::Write as _;
+
+pub(super) fn run_trail_defaults(cmd: TrailDefaultsCommand) -> Result<()> {
+    match cmd.action {
+        TrailDefaultsAction::Show(cmd) => run_trail_defaults_show(cmd),
+        TrailDefaultsAction::Set(cmd) => run_trail_defaults_set(cmd),
+    }
+}
+
+fn run_trail_defaults_show(cmd: TrailDefaultsShowCommand) -> Result<()> {
+    ensure_dot_only_for_trail(cmd.format, "trail-defaults show")?;
+    preflight_output_file(cmd.output_file.as_deref())?;
+    let runtime = RuntimeContext::new(&cmd.project)?;
+    let _summary = runtime.open_project_summary()?;
+    let output = runtime.index.trail_defaults().map_err(map_api_error)?;
+    emit(
+        cmd.format,
+        &output,
+        render_trail_defaults_markdown(&output),
+        cmd.output_file.as_deref(),
+    )
+}
+
+fn run_trail_defaults_set(cmd: TrailDefaultsSetCommand) -> Result<()> {
+    ensure_dot_only_for_trail(cmd.format, "trail-defaults set")?;
+    preflight_output_file(cmd.output_file.as_deref())?;
+    let runtime = RuntimeContext::new(&cmd.project)?;
+    let _summary = runtime.open_project_summary()?;
+    let output = runtime
+        .index
+        .set_trail_defaults(TrailDefaultsDto {
+            depth: cmd.depth,
+            direction: cmd.direction.map(Into::into),
+            caller_scope: cmd.caller_scope.map(Into::into),
+            max_nodes: cmd.max_nodes,
+            edge_filter: cmd.edge_kinds.clone(),
+        })
+        .map_err(map_api_error)?;
+    emit(
+        cmd.format,
+        &output,
+        render_trail_defaults_markdown(&output),
+        cmd.output_file.as_deref(),
+    )
+}
+
+/// Shown for a field the project leaves to the built-in default.
+const BUILT_IN: &str = "built-in";
+
+fn render_trail_defaults_markdown(output: &TrailDefaultsDto) -> String {
+    let mut markdown = String::from("# Trail Defaults\n");
+    let _ = writeln!(
+        markdown,
+        "- depth: {}",
+        output
+            .depth
+            .map_or_else(|| BUILT_IN.to_string(), |depth| depth.to_string())
+    );
+    let _ = writeln!(
+        markdown,
+        "- direction: {}",
+        output.direction.map_or(BUILT_IN, format_direction)
+    );
+    let _ = writeln!(
+        markdown,
+        "- caller scope: {}",
+        match output.caller_scope {
+            Some(TrailCallerScope::ProductionOnly) => "production-only",
+            Some(TrailCallerScope::IncludeTestsAndBenches) => "include-tests",
+            None => BUILT_IN,
+        }
+    );
+    let _ = writeln!(
+        markdown,
+        "- max nodes: {}",
+        output
+            .max_nodes
+            .map_or_else(|| BUILT_IN.to_string(), |max_nodes| max_nodes.to_string())
+    );
+    let edge_kinds = output
+        .edge_filter
+        .iter()
+        .map(|kind| format!("{kind:?}"))
+        .collect::<Vec<_>>();
+    let _ = writeln!(
+        markdown,
+        "- edge kinds: {}",
+        if edge_kinds.is_empty() {
+            "any".to_string()
+        } else {
+            edge_kinds.join(", ")
+        }
+    );
+    markdown
+}
@@ -0,0 +1,120 @@
+use super::artifacts::{ensure_dot_only_for_trail, preflight_output_file};
+use crate::args::{
+    TrailPresetAction, TrailPresetCommand, TrailPresetDeleteCommand, TrailPresetDeleteOutput,
+    TrailPresetListCommand, TrailPresetListOutput, TrailPresetSaveCommand,
+};
+use crate::output::emit;
+use crate::runtime::{RuntimeContext, map_api_error};
+use anyhow::{Result, bail};
+use codestory_contracts::api::TrailFilterPresetDto;
+use std::fmt::Write as _;
+
+pub(super) fn run_trail_preset(cmd: TrailPresetCommand) -> Result<()> {
+    match cmd.action {
+        TrailPresetAction::List(cmd) => run_trail_preset_list(cmd),
+        TrailPresetAction::Save(cmd) => run_trail_preset_save(cmd),
+        TrailPresetAction::Delete(cmd) => run_trail_preset_delete(cmd),
+    }
+}
+
+fn run_trail_preset_list(cmd: TrailPresetListCommand) -> Result<()> {
+    ensure_dot_only_for_trail(cmd.format, "trail-preset list")?;
+    preflight_output_file(cmd.output_file.as_deref())?;
+    let runtime = RuntimeContext::new(&cmd.project)?;
+    let _summary = runtime.open_project_summary()?;
+    let output = TrailPresetListOutput {
+        presets: runtime
+            .index
+            .trail_filter_presets()
+            .map_err(map_api_error)?,
+    };
+    emit(
+        cmd.format,
+        &output,
+        render_trail_presets_markdown(&output),
+        cmd.output_file.as_deref(),
+    )
+}
+
+fn run_trail_preset_save(cmd: TrailPresetSaveCommand) -> Result<()> {
+    ensure_dot_only_for_trail(cmd.format, "trail-preset save")?;
+    preflight_output_file(cmd.output_file.as_deref())?;
+    let runtime = RuntimeContext::new(&cmd.project)?;
+    let _summary = runtime.open_project_summary()?;
+    let output = TrailPresetListOutput {
+        presets: runtime
+            .index
+            .save_trail_filter_preset(TrailFilterPresetDto {
+                name: cmd.name.clone(),
+                edge_kinds: cmd.edge_kinds.clone(),
+                node_kinds: cmd.node_kinds.clone(),
+            })
+            .map_err(map_api_error)?,
+    };
+    emit(
+        cmd.format,
+        &output,
+        render_trail_presets_markdown(&output),
+        cmd.output_file.as_deref(),
+    )
+}
+
+fn run_trail_preset_delete(cmd: TrailPresetDeleteCommand) -> Result<()> {
+    ensure_dot_only_for_trail(cmd.format, "trail-preset delete")?;
+    preflight_output_file(cmd.output_file.as_deref())?;
+    let runtime = RuntimeContext::new(&cmd.project)?;
+    let _summary = runtime.open_project_summary()?;
+    let name = cmd.name.trim();
+    if !runtime
+        .index
+        .delete_trail_filter_preset(name)
+        .map_err(map_api_error)?
+    {
+        bail!("Trail filter preset not found: `{name}`");
+    }
+    let output = TrailPresetDeleteOutput {
+        deleted: name.to_string(),
+    };
+    emit(
+        cmd.format,
+        &output,
+        format!("# Trail Preset Deleted\n- name: {}\n", output.deleted),
+        cmd.output_file.as_deref(),
+    )
+}
+
+fn render_trail_presets_markdown(output: &TrailPresetListOutput) -> String {
+    let mut markdown = String::from("# Trail Presets\n");
+    if output.presets.is_empty() {
+        markdown.push_str("- none\n");
+    }
+    for preset in &output.presets {
+        let kinds = |kinds: Vec<String>| {
+            if kinds.is_empty() {
+                "any".to_string()
+            } else {
+                kinds.join(", ")
+            }
+        };
+        let _ = writeln!(
+            markdown,
+            "- `{}` edges: {}; nodes: {}",
+            preset.name,
+            kinds(
+                preset
+                    .edge_kinds
+                    .iter()
+                    .map(|kind| format!("{kind:?}"))
+                    .collect()
+            ),
+            kinds(
+                preset
+                    .node_kinds
+                    .iter()
+                    .map(|kind| format!("{kind:?}"))
+                    .collect()
+            ),
+        );
+    }
+    markdown
+}
@@ -8,14 +8,15 @@
 
 use clap::{ArgGroup, Args, Parser, Subcommand, ValueEnum};
 use codestory_contracts::api::{
-    AgentPacketDto, BookmarkCategoryDto, BookmarkDto, ClaimReadinessDto, GroundingBudgetDto,
-    IndexDryRunDto, IndexFreshnessDto, IndexedFileRoleDto, IndexingPhaseTimings, LayoutDirection,
-    NodeId, NodeKind, PacketBudgetModeDto, PacketEvidenceResolutionDto, PacketEvidenceTierDto,
-    PacketProbeDto, PacketTaskClassDto, ProjectSummary, ReadinessGoalDto, ReadinessStatusDto,
-    ReadinessVerdictDto, RepoTextScanStatsDto, RetrievalScoreBreakdownDto, RetrievalShadowDto,
-    RetrievalStateDto, SearchHitOrigin, SearchMatchQualityDto, SearchPlanDto,
-    SearchQueryAssessmentDto, SnippetContextDto, SummaryGenerationDto, SymbolContextDto,
-    TrailCallerScope, TrailContextDto, TrailDirection, TrailMode, validate_packet_probe,
+    AgentPacketDto, BookmarkCategoryDto, BookmarkDto, ClaimReadinessDto, EdgeKind,
+    GroundingBudgetDto, IndexDryRunDto, IndexFreshnessDto, IndexedFileRoleDto,
+    IndexingPhaseTimings, LayoutDirection, NodeId, NodeKind, PacketBudgetModeDto,
+    PacketEvidenceResolutionDto, PacketEvidenceTierDto, PacketProbeDto, PacketTaskClassDto,
+    ProjectSummary, ReadinessGoalDto, ReadinessStatusDto, ReadinessVerdictDto,
+    RepoTextScanStatsDto, RetrievalScoreBreakdownDto, RetrievalShadowDto, RetrievalStateDto,
+    SearchHitOrigin, SearchMatchQualityDto, SearchPlanDto, SearchQueryAssessmentDto,
+    SnippetContextDto, SummaryGenerationDto, SymbolContextDto, TrailCallerScope, TrailContextDto,
+    TrailDirection, TrailFilterPresetDto, TrailMode, validate_packet_probe,
 };
 use serde::Serialize;
 use std::{collections::BTreeMap, path::PathBuf};
@@ -108,6 +109,16 @@ pub(crate) enum Command {
     Files(FilesCommand),
     #[command(about = "Explain impact from changed files.")]
     Affected(AffectedCommand),
+    #[command(about = "Show the symbols a diff changes, their callers, and reaching tests.")]
+    Review(ReviewCommand),
+    #[command(about = "Find where a string literal or constant value is defined.")]
+    Literals(LiteralsCommand),
+    #[command(about = "Show when nodes and edges were added or removed by indexing runs.")]
+    History(HistoryCommand),
+    #[command(about = "Save, list, and delete named trail filter presets.")]
+    TrailPreset(TrailPresetCommand),
+    #[command(about = "Show or set the trail settings used where a trail leaves a field unset.")]
+    TrailDefaults(TrailDefaultsCommand),
     #[command(about = "Save and reuse investigation targets.")]
     Bookmark(BookmarkCommand),
     #[command(about = "Start the local integration surface.")]
@@ -182,6 +193,14 @@ fn parse_read_output_format(value: &str) -> Result<OutputFormat, String> {
     }
 }
 
+/// An edge or node kind named as in JSON output, case-insensitively and with
+/// `-` for `_`, such as `call` or `type-usage`.
+pub(crate) fn parse_graph_kind<T: serde::de::DeserializeOwned>(value: &str) -> Result<T, String> {
+    let normalized = value.trim().replace('-', "_").to_ascii_uppercase();
+    serde_json::from_value(serde_json::Value::String(normalized))
+        .map_err(|_| format!("unknown kind `{value}`"))
+}
+
 fn parse_packet_probe(value: &str) -> Result<PacketProbeDto, String> {
     let probe: PacketProbeDto = serde_json::from_str(value).map_err(|error| {
         format!(
@@ -236,6 +255,19 @@ pub(crate) enum RepoTextMode {
     Off,
 }
 
+/// A project setting a command turns on or off.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub(crate) enum CliToggle {
+    On,
+    Off,
+}
+
+impl CliToggle {
+    pub(crate) fn enabled(self) -> bool {
+        self == Self::On
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
 pub(crate) enum CliGroundingBudget {
     Strict,
@@ -276,6 +308,21 @@ pub(crate) enum CliDirection {
     Both,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub(crate) enum CliCallerScope {
+    ProductionOnly,
+    IncludeTests,
+}
+
+impl From<CliCallerScope> for TrailCallerScope {
+    fn from(value: CliCallerScope) -> Self {
+        match value {
+            CliCallerScope::ProductionOnly => Self::ProductionOnly,
+            CliCallerScope::IncludeTests => Self::IncludeTestsAndBenches,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
 pub(crate) enum CliLayout {
     Horizontal,
@@ -332,6 +379,40 @@ pub(crate) struct IndexCommand {
         help = "Keep running and incrementally re-index after file changes."
     )]
     pub(crate) watch: bool,
+    #[arg(
+        long,
+        value_name = "N",
+        value_parser = clap::value_parser!(u32).range(1..),
+        help = "Parse on at most N threads. Defaults to one less than the available cores."
+    )]
+    pub(crate) max_threads: Option<u32>,
+    #[arg(
+        long,
+        value_enum,
+        value_name = "MODE",
+        help = "Resolve calls into the sources of declared dependencies from this run on, or stop. The choice is saved with the project."
+    )]
+    pub(crate) dependencies: Option<CliToggle>,
+    #[arg(
+        long,
+        value_name = "N",
+        value_parser = clap::value_parser!(u32).range(1..=200),
+        help = "Score symbol centrality with N PageRank iterations from this run on. The choice is saved with the project; the default is 20."
+    )]
+    pub(crate) centrality_iterations: Option<u32>,
+    #[arg(
+        long,
+        value_enum,
+        value_name = "MODE",
+        help = "Record string literals and constant values for `literals` from this run on, or stop and drop them. The choice is saved with the project."
+    )]
+    pub(crate) string_literals: Option<CliToggle>,
+    #[arg(
+        long = "open",
+        value_name = "PATH",
+        help = "A file open in the editor. Open files are parsed first, in the order given; repeat for several files."
+    )]
+    pub(crate) open_files: Vec<String>,
 }
 
 #[derive(Args, Debug)]
@@ -1073,6 +1154,18 @@ pub(crate) struct SymbolCommand {
     pub(crate) output_file: Option<PathBuf>,
     #[arg(long, help = "Render a Mermaid graph instead of Markdown/JSON output.")]
     pub(crate) mermaid: bool,
+    #[arg(
+        long,
+        help = "Also open the symbol's definition in the IDE (`CODESTORY_IDE_COMMAND`, else `code --goto`)."
+    )]
+    pub(crate) open: bool,
+    #[arg(
+        long,
+        value_name = "INDEX",
+        requires = "open",
+        help = "With --open, open this 0-based entry of the symbol's definitions instead of its primary location."
+    )]
+    pub(crate) definition: Option<u32>,
 }
 
 #[derive(Args, Debug)]
@@ -1087,8 +1180,11 @@ pub(crate) struct TrailCommand {
     pub(crate) depth: Option<u32>,
     #[arg(long, value_enum)]
     pub(crate) direction: Option<CliDirection>,
-    #[arg(long, default_value_t = 120)]
-    pub(crate) max_nodes: u32,
+    #[arg(
+        long,
+        help = "Most nodes the trail returns; defaults to the project's trail default, else 120."
+    )]
+    pub(crate) max_nodes: Option<u32>,
     #[arg(long)]
     pub(crate) include_tests: bool,
     #[arg(long)]
@@ -1098,6 +1194,39 @@ pub(crate) struct TrailCommand {
         help = "Hide uncertain/speculative edges plus probable or low-confidence runtime bridge edges, then remove nodes disconnected from the trail focus."
     )]
     pub(crate) hide_speculative: bool,
+    #[arg(
+        long,
+        help = "Drop short getter/setter style functions and methods while building the trail."
+    )]
+    pub(crate) hide_accessors: bool,
+    #[arg(
+        long,
+        help = "Drop symbols from generated files (generated paths or @generated/DO NOT EDIT headers) while building the trail."
+    )]
+    pub(crate) hide_generated: bool,
+    #[arg(
+        long,
+        help = "Drop symbols that no entry point (main functions, route handlers, tests) reaches through calls while building the trail."
+    )]
+    pub(crate) entry_reachable_only: bool,
+    #[arg(
+        long,
+        value_name = "NAME",
+        help = "Restrict the trail to the edge and node kinds of a saved filter preset."
+    )]
+    pub(crate) filter_preset: Option<String>,
+    #[arg(
+        long,
+        value_name = "ID",
+        help = "Drop this node from the trail graph; repeat to hide several."
+    )]
+    pub(crate) hide: Vec<String>,
+    #[arg(
+        long,
+        value_name = "ID",
+        help = "Fold this node's members into it in the trail graph; repeat to collapse several."
+    )]
+    pub(crate) collapse: Vec<String>,
     #[arg(long, help = "Render a readable narrative of the trail graph.")]
     pub(crate) story: bool,
     #[arg(long, value_enum, default_value_t = CliLayout::Horizontal)]
@@ -1119,6 +1248,11 @@ pub(crate) struct TrailCommand {
     pub(crate) output_file: Option<PathBuf>,
     #[arg(long, help = "Render a Mermaid graph instead of Markdown/JSON output.")]
     pub(crate) mermaid: bool,
+    #[arg(
+        long,
+        help = "Render a self-contained interactive HTML page instead of Markdown/JSON output."
+    )]
+    pub(crate) html: bool,
 }
 
 #[derive(Args, Debug)]
@@ -1240,10 +1374,16 @@ pub(crate) struct ExploreCommand {
         help = "Opt into an investigation preset without changing default explore behavior."
     )]
     pub(crate) profile: Option<ExploreProfile>,
-    #[arg(long, default_value_t = 2)]
-    pub(crate) depth: u32,
-    #[arg(long, default_value_t = 18)]
-    pub(crate) max_nodes: u32,
+    #[arg(
+        long,
+        help = "Trail depth; defaults to the project's trail default, else 2."
+    )]
+    pub(crate) depth: Option<u32>,
+    #[arg(
+        long,
+        help = "Most trail nodes; defaults to the project's trail default, else 18."
+    )]
+    pub(crate) max_nodes: Option<u32>,
     #[arg(
         long,
         help = "Print plain Markdown instead of opening the terminal explorer when stdout is interactive."
@@ -1312,6 +1452,116 @@ pub(crate) struct FilesCommand {
     pub(crate) output_file: Option<PathBuf>,
 }
 
+#[derive(Args, Debug)]
+#[command(group(ArgGroup::new("review_diff_source").args(["diff", "base"]).multiple(false)))]
+pub(crate) struct ReviewCommand {
+    #[command(flatten)]
+    pub(crate) project: ProjectArgs,
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Unified diff to review; `-` reads stdin. If neither --diff nor --base is given, CodeStory reads git diff HEAD."
+    )]
+    pub(crate) diff: Option<PathBuf>,
+    #[arg(long, value_name = "REF", help = "Review git diff <base> [<head>].")]
+    pub(crate) base: Option<String>,
+    #[arg(
+        long,
+        value_name = "REF",
+        requires = "base",
+        help = "Head ref to diff against --base. Defaults to the working tree."
+    )]
+    pub(crate) head: Option<String>,
+    #[arg(
+        long,
+        default_value_t = 1,
+        help = "Caller hops to follow from changed symbols (1-4)."
+    )]
+    pub(crate) depth: u32,
+    #[arg(
+        long,
+        help = "Reindex the changed files and the files of their direct callers before reviewing. With --head, that commit must be checked out."
+    )]
+    pub(crate) reindex: bool,
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = RefreshMode::None,
+        long_help = READ_REFRESH_HELP
+    )]
+    pub(crate) refresh: RefreshMode,
+    #[arg(long, value_name = "FORMAT", value_parser = parse_read_output_format, default_value = "markdown")]
+    pub(crate) format: OutputFormat,
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Write command output to this file instead of stdout. The parent directory must already exist."
+    )]
+    pub(crate) output_file: Option<PathBuf>,
+}
+
+#[derive(Args, Debug)]
+pub(crate) struct LiteralsCommand {
+    #[command(flatten)]
+    pub(crate) project: ProjectArgs,
+    #[arg(
+        long,
+        help = "Words the literal, or a constant's name or value, must contain. Needs an index built with `index --string-literals on`."
+    )]
+    pub(crate) query: String,
+    #[arg(long, default_value_t = 50)]
+    pub(crate) limit: u32,
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = RefreshMode::None,
+        long_help = READ_REFRESH_HELP
+    )]
+    pub(crate) refresh: RefreshMode,
+    #[arg(long, value_name = "FORMAT", value_parser = parse_read_output_format, default_value = "markdown")]
+    pub(crate) format: OutputFormat,
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Write command output to this file instead of stdout. The parent directory must already exist."
+    )]
+    pub(crate) output_file: Option<PathBuf>,
+}
+
+#[derive(Args, Debug)]
+#[command(group(ArgGroup::new("history_target").args(["id", "run"]).multiple(false)))]
+pub(crate) struct HistoryCommand {
+    #[command(flatten)]
+    pub(crate) project: ProjectArgs,
+    #[arg(
+        long,
+        value_name = "NODE_ID",
+        help = "List when this node and the edges touching it were added or removed. Without --id or --run, list the retained indexing runs."
+    )]
+    pub(crate) id: Option<String>,
+    #[arg(
+        long,
+        value_name = "RUN_ID",
+        help = "List the node and edge ids present right after this indexing run."
+    )]
+    pub(crate) run: Option<String>,
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = RefreshMode::None,
+        long_help = READ_REFRESH_HELP
+    )]
+    pub(crate) refresh: RefreshMode,
+    #[arg(long, value_name = "FORMAT", value_parser = parse_read_output_format, default_value = "markdown")]
+    pub(crate) format: OutputFormat,
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Write command output to this file instead of stdout. The parent directory must already exist."
+    )]
+    pub(crate) output_file: Option<PathBuf>,
+}
+
 #[derive(Debug, Clone, Copy, ValueEnum)]
 pub(crate) enum AffectedChangeSource {
     Head,
@@ -1375,6 +1625,139 @@ pub(crate) struct AffectedCommand {
     pub(crate) output_file: Option<PathBuf>,
 }
 
+#[derive(Args, Debug)]
+pub(crate) struct TrailPresetCommand {
+    #[command(subcommand)]
+    pub(crate) action: TrailPresetAction,
+}
+
+#[derive(Subcommand, Debug)]
+pub(crate) enum TrailPresetAction {
+    List(TrailPresetListCommand),
+    Save(TrailPresetSaveCommand),
+    Delete(TrailPresetDeleteCommand),
+}
+
+#[derive(Args, Debug)]
+pub(crate) struct TrailPresetListCommand {
+    #[command(flatten)]
+    pub(crate) project: ProjectArgs,
+    #[arg(long, value_name = "FORMAT", value_parser = parse_read_output_format, default_value = "markdown")]
+    pub(crate) format: OutputFormat,
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Write command output to this file instead of stdout. The parent directory must already exist."
+    )]
+    pub(crate) output_file: Option<PathBuf>,
+}
+
+#[derive(Args, Debug)]
+pub(crate) struct TrailPresetSaveCommand {
+    #[command(flatten)]
+    pub(crate) project: ProjectArgs,
+    #[arg(
+        value_name = "NAME",
+        help = "Preset name; saving an existing name replaces it."
+    )]
+    pub(crate) name: String,
+    #[arg(
+        long = "edge-kind",
+        value_name = "KIND",
+        value_parser = parse_graph_kind::<EdgeKind>,
+        help = "Edge kind the preset keeps, such as call or type-usage; repeat for several."
+    )]
+    pub(crate) edge_kinds: Vec<EdgeKind>,
+    #[arg(
+        long = "node-kind",
+        value_name = "KIND",
+        value_parser = parse_graph_kind::<NodeKind>,
+        help = "Node kind the preset keeps, such as function or class; repeat for several."
+    )]
+    pub(crate) node_kinds: Vec<NodeKind>,
+    #[arg(long, value_name = "FORMAT", value_parser = parse_read_output_format, default_value = "markdown")]
+    pub(crate) format: OutputFormat,
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Write command output to this file instead of stdout. The parent directory must already exist."
+    )]
+    pub(crate) output_file: Option<PathBuf>,
+}
+
+#[derive(Args, Debug)]
+pub(crate) struct TrailPresetDeleteCommand {
+    #[command(flatten)]
+    pub(crate) project: ProjectArgs,
+    #[arg(value_name = "NAME")]
+    pub(crate) name: String,
+    #[arg(long, value_name = "FORMAT", value_parser = parse_read_output_format, default_value = "markdown")]
+    pub(crate) format: OutputFormat,
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Write command output to this file instead of stdout. The parent directory must already exist."
+    )]
+    pub(crate) output_file: Option<PathBuf>,
+}
+
+#[derive(Args, Debug)]
+pub(crate) struct TrailDefaultsCommand {
+    #[command(subcommand)]
+    pub(crate) action: TrailDefaultsAction,
+}
+
+#[derive(Subcommand, Debug)]
+pub(crate) enum TrailDefaultsAction {
+    Show(TrailDefaultsShowCommand),
+    Set(TrailDefaultsSetCommand),
+}
+
+#[derive(Args, Debug)]
+pub(crate) struct TrailDefaultsShowCommand {
+    #[command(flatten)]
+    pub(crate) project: ProjectArgs,
+    #[arg(long, value_name = "FORMAT", value_parser = parse_read_output_format, default_value = "markdown")]
+    pub(crate) format: OutputFormat,
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Write command output to this file instead of stdout. The parent directory must already exist."
+    )]
+    pub(crate) output_file: Option<PathBuf>,
+}
+
+/// Replace every trail default; flags left out fall back to the built-in
+/// defaults, so `set` with no flags restores them all.
+#[derive(Args, Debug)]
+pub(crate) struct TrailDefaultsSetCommand {
+    #[command(flatten)]
+    pub(crate) project: ProjectArgs,
+    #[arg(long)]
+    pub(crate) depth: Option<u32>,
+    #[arg(long, value_enum)]
+    pub(crate) direction: Option<CliDirection>,
+    #[arg(long, value_enum)]
+    pub(crate) caller_scope: Option<CliCallerScope>,
+    #[arg(long, value_parser = clap::value_parser!(u32).range(10..=100_000))]
+    pub(crate) max_nodes: Option<u32>,
+    #[arg(
+        long = "edge-kind",
+        value_name = "KIND",
+        value_parser = parse_graph_kind::<EdgeKind>,
+        help = "Edge kind trails keep when a request names none; repeat for several."
+    )]
+    pub(crate) edge_kinds: Vec<EdgeKind>,
+    #[arg(long, value_name = "FORMAT", value_parser = parse_read_output_format, default_value = "markdown")]
+    pub(crate) format: OutputFormat,
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Write command output to this file instead of stdout. The parent directory must already exist."
+    )]
+    pub(crate) output_file: Option<PathBuf>,
+}
+
 #[derive(Args, Debug)]
 pub(crate) struct BookmarkCommand {
     #[command(subcommand)]
@@ -1421,6 +1804,10 @@ pub(crate) struct BookmarkListCommand {
     pub(crate) project: ProjectArgs,
     #[arg(long)]
     pub(crate) category: Option<String>,
+    #[arg(long, help = "Skip this many bookmarks.")]
+    pub(crate) offset: Option<u32>,
+    #[arg(long, help = "List at most this many bookmarks (default 500).")]
+    pub(crate) limit: Option<u32>,
     #[arg(long, value_name = "FORMAT", value_parser = parse_read_output_format, default_value = "markdown")]
     pub(crate) format: OutputFormat,
     #[arg(
@@ -1476,6 +1863,63 @@ pub(crate) struct ServeCommand {
         long_help = READ_REFRESH_HELP
     )]
     pub(crate) refresh: RefreshMode,
+    #[arg(
+        long,
+        value_name = "VAR",
+        help = "Read the project database encryption key from this environment variable instead of CODESTORY_DB_KEY. Requires a build with the `sqlcipher` feature."
+    )]
+    pub(crate) db_key_env: Option<String>,
+    #[arg(
+        long,
+        value_name = "VAR",
+        conflicts_with = "stdio",
+        help = "Read HTTP bearer tokens from this environment variable instead of CODESTORY_SERVE_TOKENS. Entries are comma-separated `token[:read|:write]`; tokens without a scope are read-only."
+    )]
+    pub(crate) tokens_env: Option<String>,
+    #[arg(
+        long,
+        value_name = "PER_MINUTE",
+        conflicts_with = "stdio",
+        value_parser = clap::value_parser!(u32).range(1..),
+        help = "Limit each graph and search route to this many HTTP requests per minute; excess requests get 429 with Retry-After."
+    )]
+    pub(crate) rate_limit: Option<u32>,
+    #[arg(
+        long,
+        value_name = "N",
+        requires = "rate_limit",
+        default_value_t = 10,
+        value_parser = clap::value_parser!(u32).range(1..),
+        help = "Requests a rate-limited route accepts back to back before --rate-limit spacing applies."
+    )]
+    pub(crate) rate_burst: u32,
+    #[arg(
+        long,
+        value_name = "N",
+        default_value_t = 4,
+        conflicts_with = "stdio",
+        value_parser = clap::value_parser!(u32).range(1..=64),
+        help = "HTTP requests answered at once. Each runs on a blocking thread against the one shared runtime, so slow trails do not stall the rest."
+    )]
+    pub(crate) workers: u32,
+    #[arg(
+        long,
+        value_name = "N",
+        default_value_t = 32,
+        conflicts_with = "stdio",
+        value_parser = clap::value_parser!(u32).range(0..=1024),
+        help = "HTTP connections allowed to wait for a free worker. Connections past --workers plus this get 503 with Retry-After."
+    )]
+    pub(crate) max_queued: u32,
+    #[arg(
+        long,
+        value_name = "N",
+        default_value_t = 2,
+        conflicts_with = "stdio",
+        value_parser = clap::value_parser!(u32).range(1..=64),
+        help = "Trail routes (/trail, /references, /graph/render.png) answered at once. Trails past the limit get 503 with Retry-After instead of waiting."
+    )]
+    pub(crate) max_trails: u32,
 }
 
 #[derive(Args, Debug)]
@@ -2134,6 +2578,10 @@ pub(crate) struct QueryItemOutput {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub(crate) depth: Option<u32>,
     pub(crate) source: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) target_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) target_name: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -2141,6 +2589,8 @@ pub(crate) struct QueryOutput {
     pub(crate) query: String,
     pub(crate) ast: codestory_contracts::query::GraphQueryAst,
     pub(crate) items: Vec<QueryItemOutput>,
+    /// A `match` stopped at its row cap; narrow it to see the rest.
+    pub(crate) truncated: bool,
 }
 
 #[derive(Debug, Serialize)]
@@ -2232,6 +2682,11 @@ pub(crate) struct BookmarkAddOutput {
 pub(crate) struct BookmarkListOutput {
     pub(crate) categories: Vec<BookmarkCategoryDto>,
     pub(crate) bookmarks: Vec<BookmarkOutput>,
+    /// Bookmarks in the whole listing.
+    pub(crate) total: u32,
+    /// Offset of the next page, absent on the last page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) next_offset: Option<u32>,
 }
 
 #[derive(Debug, Serialize)]
@@ -2239,6 +2694,16 @@ pub(crate) struct BookmarkRemoveOutput {
     pub(crate) removed_id: String,
 }
 
+#[derive(Debug, Serialize)]
+pub(crate) struct TrailPresetListOutput {
+    pub(crate) presets: Vec<TrailFilterPresetDto>,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct TrailPresetDeleteOutput {
+    pub(crate) deleted: String,
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub(crate) struct ExploreStatusOutput {
     pub(crate) project: String,
@@ -2449,43 +2914,35 @@ fn build_trail_request_impl(
         CliTrailMode::Referenced => TrailMode::AllReferenced,
         CliTrailMode::Referencing => TrailMode::AllReferencing,
     };
-    let direction = cmd
-        .direction
-        .map(Into::into)
-        .unwrap_or_else(|| default_trail_direction(cmd.mode));
 
+    // Fields the user left unset fall back to the project's trail defaults.
     codestory_contracts::api::TrailConfigDto {
         root_id: root_id.clone(),
         mode,
         target_id: None,
-        depth: cmd.depth.unwrap_or(match cmd.mode {
-            CliTrailMode::Neighborhood => 2,
-            CliTrailMode::Referenced | CliTrailMode::Referencing => 0,
-        }),
-        direction,
-        caller_scope: if cmd.include_tests {
-            TrailCallerScope::IncludeTestsAndBenches
-        } else {
-            TrailCallerScope::ProductionOnly
-        },
+        depth: cmd.depth,
+        direction: cmd.direction.map(Into::into),
+        caller_scope: cmd
+            .include_tests
+            .then_some(TrailCallerScope::IncludeTestsAndBenches),
         edge_filter: Vec::new(),
         show_utility_calls: cmd.show_utility_calls,
         hide_speculative: cmd.hide_speculative,
         story: cmd.story,
         node_filter: Vec::new(),
-        max_nodes: cmd.max_nodes.clamp(1, 200),
+        hide_trivial_accessors: cmd.hide_accessors,
+        hide_generated: cmd.hide_generated,
+        entry_reachable_only: cmd.entry_reachable_only,
+        max_paths: None,
+        edge_weight: Default::default(),
+        max_nodes: cmd.max_nodes.map(|max_nodes| max_nodes.clamp(1, 200)),
         layout_direction: match cmd.layout {
             CliLayout::Horizontal => LayoutDirection::Horizontal,
             CliLayout::Vertical => LayoutDirection::Vertical,
         },
-    }
-}
-
-pub(crate) fn default_trail_direction(mode: CliTrailMode) -> TrailDirection {
-    match mode {
-        CliTrailMode::Neighborhood => TrailDirection::Both,
-        CliTrailMode::Referenced => TrailDirection::Outgoing,
-        CliTrailMode::Referencing => TrailDirection::Incoming,
+        graph_session: None,
+        timeout_ms: None,
+        filter_preset: cmd.filter_preset.clone(),
     }
 }
 
@@ -2769,6 +3226,163 @@ mod tests {
         );
     }
 
+    #[test]
+    fn trail_parses_repeated_hide_and_collapse_ids() {
+        let parsed = Cli::try_parse_from([
+            "codestory-cli",
+            "trail",
+            "--id",
+            "node-1",
+            "--hide",
+            "node-2",
+            "--hide",
+            "node-3",
+            "--collapse",
+            "node-4",
+        ])
+        .expect("trail graph view flags should parse");
+        let Command::Trail(cmd) = parsed.command else {
+            panic!("expected trail command");
+        };
+        assert_eq!(cmd.hide, ["node-2", "node-3"]);
+        assert_eq!(cmd.collapse, ["node-4"]);
+    }
+
+    #[test]
+    fn index_parses_dependency_resolution_toggle() {
+        let parsed = Cli::try_parse_from(["codestory-cli", "index", "--dependencies", "on"])
+            .expect("dependency toggle should parse");
+        let Command::Index(cmd) = parsed.command else {
+            panic!("expected index command");
+        };
+        assert_eq!(cmd.dependencies, Some(CliToggle::On));
+        assert!(
+            Cli::try_parse_from(["codestory-cli", "index", "--dependencies", "maybe"]).is_err()
+        );
+    }
+
+    #[test]
+    fn trail_preset_save_parses_repeated_kinds() {
+        let parsed = Cli::try_parse_from([
+            "codestory-cli",
+            "trail-preset",
+            "save",
+            "calls",
+            "--edge-kind",
+            "call",
+            "--edge-kind",
+            "type-usage",
+            "--node-kind",
+            "FUNCTION",
+        ])
+        .expect("trail preset save should parse");
+        let Command::TrailPreset(TrailPresetCommand {
+            action: TrailPresetAction::Save(cmd),
+        }) = parsed.command
+        else {
+            panic!("expected trail-preset save");
+        };
+        assert_eq!(cmd.name, "calls");
+        assert_eq!(cmd.edge_kinds, [EdgeKind::CALL, EdgeKind::TYPE_USAGE]);
+        assert_eq!(cmd.node_kinds, [NodeKind::FUNCTION]);
+        assert!(
+            Cli::try_parse_from([
+                "codestory-cli",
+                "trail-preset",
+                "save",
+                "calls",
+                "--edge-kind",
+                "teleport"
+            ])
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn trail_defaults_set_parses_every_field_and_bounds_max_nodes() {
+        let parsed = Cli::try_parse_from([
+            "codestory-cli",
+            "trail-defaults",
+            "set",
+            "--depth",
+            "3",
+            "--direction",
+            "incoming",
+            "--caller-scope",
+            "include-tests",
+            "--max-nodes",
+            "40",
+            "--edge-kind",
+            "call",
+        ])
+        .expect("trail defaults set should parse");
+        let Command::TrailDefaults(TrailDefaultsCommand {
+            action: TrailDefaultsAction::Set(cmd),
+        }) = parsed.command
+        else {
+            panic!("expected trail-defaults set");
+        };
+        assert_eq!(cmd.depth, Some(3));
+        assert_eq!(cmd.direction, Some(CliDirection::Incoming));
+        assert_eq!(cmd.caller_scope, Some(CliCallerScope::IncludeTests));
+        assert_eq!(cmd.max_nodes, Some(40));
+        assert_eq!(cmd.edge_kinds, [EdgeKind::CALL]);
+        assert!(
+            Cli::try_parse_from(["codestory-cli", "trail-defaults", "set", "--max-nodes", "5"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn index_parses_string_literal_toggle() {
+        let parsed = Cli::try_parse_from(["codestory-cli", "index", "--string-literals", "off"])
+            .expect("string literal toggle should parse");
+        let Command::Index(cmd) = parsed.command else {
+            panic!("expected index command");
+        };
+        assert_eq!(cmd.string_literals, Some(CliToggle::Off));
+    }
+
+    #[test]
+    fn index_parses_bounded_centrality_iterations() {
+        let parsed =
+            Cli::try_parse_from(["codestory-cli", "index", "--centrality-iterations", "40"])
+                .expect("centrality iterations should parse");
+        let Command::Index(cmd) = parsed.command else {
+            panic!("expected index command");
+        };
+        assert_eq!(cmd.centrality_iterations, Some(40));
+        for rejected in ["0", "201"] {
+            assert!(
+                Cli::try_parse_from([
+                    "codestory-cli",
+                    "index",
+                    "--centrality-iterations",
+                    rejected
+                ])
+                .is_err(),
+                "{rejected} iterations should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn index_parses_repeated_open_files_in_order() {
+        let parsed = Cli::try_parse_from([
+            "codestory-cli",
+            "index",
+            "--open",
+            "src/b.rs",
+            "--open",
+            "src/a.rs",
+        ])
+        .expect("open files should parse");
+        let Command::Index(cmd) = parsed.command else {
+            panic!("expected index command");
+        };
+        assert_eq!(cmd.open_files, vec!["src/b.rs", "src/a.rs"]);
+    }
+
     #[test]
     fn symbol_workflow_commands_parse_target_and_caps() {
         let impact = Cli::try_parse_from([
@@ -2903,6 +3517,31 @@ mod tests {
         assert!(help.contains("without reading source files"));
     }
 
+    #[test]
+    fn symbol_definition_requires_open() {
+        let cli = Cli::try_parse_from([
+            "codestory-cli",
+            "symbol",
+            "--id",
+            "7",
+            "--open",
+            "--definition",
+            "1",
+        ])
+        .expect("symbol --open --definition should parse");
+        match cli.command {
+            Command::Symbol(cmd) => {
+                assert!(cmd.open);
+                assert_eq!(cmd.definition, Some(1));
+            }
+            _ => panic!("expected symbol command"),
+        }
+        assert!(
+            Cli::try_parse_from(["codestory-cli", "symbol", "--id", "7", "--definition", "1"])
+                .is_err()
+        );
+    }
+
     #[test]
     fn negative_node_ids_parse_without_equals_workaround() {
         let cli = Cli::try_parse_from(["codestory-cli", "symbol", "--id", "-3816661223164617416"])
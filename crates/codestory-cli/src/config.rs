@@ -7,6 +7,9 @@ use std::sync::OnceLock;
 
 const PROJECT_NETWORK_CONFIG_OPT_IN_ENV: &str = "CODESTORY_ALLOW_PROJECT_NETWORK_CONFIG";
 const SOURCE_FILE_BYTE_CAP_ENV: &str = "CODESTORY_INDEX_SOURCE_FILE_BYTE_CAP";
+const STORAGE_KEY_ENV: &str = "CODESTORY_DB_KEY";
+const STORAGE_INTEGRITY_CHECK_ENV: &str = "CODESTORY_DB_INTEGRITY_CHECK";
+const SERVE_TOKENS_ENV: &str = "CODESTORY_SERVE_TOKENS";
 
 #[derive(Debug, Clone)]
 pub(crate) struct CliStartupConfig {
@@ -36,6 +39,53 @@ impl CliStartupConfig {
     }
 }
 
+/// Install the database encryption key once, before any project is opened.
+///
+/// `key_env` names an explicit variable (from `serve --db-key-env`) that must
+/// be set; otherwise `CODESTORY_DB_KEY` is used when present. The key is never
+/// copied into `CliStartupConfig`, which is cloned and debug-printed freely.
+pub(crate) fn install_storage_encryption_key(key_env: Option<&str>) -> Result<()> {
+    let passphrase = match key_env {
+        Some(name) => Some(std::env::var(name).with_context(|| {
+            format!("--db-key-env names `{name}`, but that environment variable is not set")
+        })?),
+        None => std::env::var(STORAGE_KEY_ENV)
+            .ok()
+            .filter(|value| !value.is_empty()),
+    };
+    let Some(passphrase) = passphrase else {
+        return Ok(());
+    };
+    codestory_runtime::install_storage_encryption_key(passphrase)
+        .map_err(|error| anyhow::anyhow!("{}", error.message))
+        .context("install project database encryption key")
+}
+
+/// Raw HTTP serve token list, read like the database key: `tokens_env` (from
+/// `serve --tokens-env`) must be set, otherwise `CODESTORY_SERVE_TOKENS` is
+/// used when present and non-empty.
+pub(crate) fn read_serve_tokens(tokens_env: Option<&str>) -> Result<Option<String>> {
+    match tokens_env {
+        Some(name) => std::env::var(name).map(Some).with_context(|| {
+            format!("--tokens-env names `{name}`, but that environment variable is not set")
+        }),
+        None => Ok(std::env::var(SERVE_TOKENS_ENV)
+            .ok()
+            .filter(|value| !value.trim().is_empty())),
+    }
+}
+
+/// Turn on the open-time database integrity check when
+/// `CODESTORY_DB_INTEGRITY_CHECK` is set to a truthy value.
+pub(crate) fn configure_storage_integrity_check() {
+    let enabled = std::env::var(STORAGE_INTEGRITY_CHECK_ENV)
+        .map(|value| matches!(value.trim(), "1" | "true" | "TRUE" | "yes" | "YES"))
+        .unwrap_or(false);
+    if enabled {
+        codestory_runtime::enable_storage_integrity_check();
+    }
+}
+
 fn source_index_policy_from_env_value(raw: Option<&str>) -> SourceIndexPolicy {
     let byte_cap = raw
         .and_then(|raw| raw.trim().parse::<u64>().ok())
@@ -78,11 +78,3 @@ pub(crate) fn format_direction(direction: TrailDirection) -> &'static str {
         TrailDirection::Both => "both",
     }
 }
-
-pub(crate) fn default_trail_direction(mode: CliTrailMode) -> TrailDirection {
-    match mode {
-        CliTrailMode::Neighborhood => TrailDirection::Both,
-        CliTrailMode::Referenced => TrailDirection::Outgoing,
-        CliTrailMode::Referencing => TrailDirection::Incoming,
-    }
-}
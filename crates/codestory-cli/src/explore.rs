@@ -2,7 +2,7 @@ use anyhow::Result;
 use codestory_contracts::api::{
     GraphNodeDto, IndexFreshnessDto, IndexFreshnessStatusDto, LayoutDirection, NodeDetailsRequest,
     SnippetContextDto, SymbolContextDto, TrailCallerScope, TrailConfigDto, TrailContextDto,
-    TrailDirection, TrailMode,
+    TrailDefaultsDto, TrailMode,
 };
 use std::{
     collections::{BTreeMap, HashMap, HashSet},
@@ -50,7 +50,8 @@ pub(crate) fn run_explore(cmd: ExploreCommand) -> Result<()> {
     let runtime = RuntimeContext::new(&cmd.project)?;
     let opened = runtime.ensure_open(cmd.refresh)?;
     ensure_index_ready(&opened, "explore")?;
-    let profile = resolve_explore_profile(cmd.profile, cmd.depth, cmd.max_nodes);
+    let trail_defaults = runtime.index.trail_defaults().map_err(map_api_error)?;
+    let profile = resolve_explore_profile(cmd.profile, cmd.depth, cmd.max_nodes, &trail_defaults);
     let file_filter = cmd.target.file_filter();
     let operation = if cmd.target.query.is_some() {
         "graph_assisted"
@@ -75,16 +76,24 @@ pub(crate) fn run_explore(cmd: ExploreCommand) -> Result<()> {
                 root_id: target.selected.node_id.clone(),
                 mode: TrailMode::Neighborhood,
                 target_id: None,
-                depth: profile.output.depth,
-                direction: TrailDirection::Both,
-                caller_scope: profile.caller_scope,
+                depth: Some(profile.output.depth),
+                direction: None,
+                caller_scope: Some(profile.caller_scope),
                 edge_filter: Vec::new(),
                 show_utility_calls: false,
                 hide_speculative: false,
                 story: false,
                 node_filter: Vec::new(),
-                max_nodes: profile.output.max_nodes.clamp(1, 120),
+                hide_trivial_accessors: false,
+                hide_generated: false,
+                entry_reachable_only: false,
+                max_paths: None,
+                edge_weight: Default::default(),
+                max_nodes: Some(profile.output.max_nodes.clamp(1, 120)),
                 layout_direction: LayoutDirection::Horizontal,
+                graph_session: None,
+                timeout_ms: None,
+                filter_preset: None,
             })
             .map_err(map_api_error)?;
         let snippet_result = runtime
@@ -234,12 +243,23 @@ struct ExploreProfileSpec {
     notes: &'static [&'static str],
 }
 
+/// Explore's trail budget when neither a flag nor the project's trail
+/// defaults set one; smaller than a plain trail so the packet stays short.
+const EXPLORE_DEFAULT_DEPTH: u32 = 2;
+const EXPLORE_DEFAULT_MAX_NODES: u32 = 18;
+
 fn resolve_explore_profile(
     requested: Option<ExploreProfile>,
-    depth: u32,
-    max_nodes: u32,
+    depth: Option<u32>,
+    max_nodes: Option<u32>,
+    defaults: &TrailDefaultsDto,
 ) -> ResolvedExploreProfile {
-    let spec = explore_profile_spec(requested, depth, max_nodes);
+    let depth = depth.or(defaults.depth).unwrap_or(EXPLORE_DEFAULT_DEPTH);
+    let max_nodes = max_nodes
+        .or(defaults.max_nodes)
+        .unwrap_or(EXPLORE_DEFAULT_MAX_NODES);
+    let default_caller_scope = defaults.caller_scope.unwrap_or_default();
+    let spec = explore_profile_spec(requested, depth, max_nodes, default_caller_scope);
     let resolved_depth = if requested.is_some() {
         depth.max(spec.depth_floor)
     } else {
@@ -268,6 +288,7 @@ fn explore_profile_spec(
     requested: Option<ExploreProfile>,
     depth: u32,
     max_nodes: u32,
+    default_caller_scope: TrailCallerScope,
 ) -> ExploreProfileSpec {
     match requested {
         Some(ExploreProfile::Architecture) => ExploreProfileSpec {
@@ -324,9 +345,9 @@ fn explore_profile_spec(
             requested_name: "default",
             depth_floor: depth,
             node_floor: max_nodes,
-            caller_scope: TrailCallerScope::ProductionOnly,
+            caller_scope: default_caller_scope,
             notes: &[
-                "default profile preserves the normal explore depth, node budget, and production-only caller scope",
+                "default profile uses the project's trail defaults, else the normal explore depth, node budget, and production-only caller scope",
             ],
         },
     }
@@ -357,6 +378,8 @@ fn graph_node_to_query_item(
         line: None,
         depth: Some(node.depth),
         source: source.to_string(),
+        target_id: None,
+        target_name: None,
     }
 }
 
@@ -381,6 +404,8 @@ pub(crate) fn browser_query_item_to_output(
         line: item.line,
         depth: item.depth,
         source: item.source.clone(),
+        target_id: item.target_id.as_ref().map(|id| id.0.clone()),
+        target_name: item.target_name.clone(),
     }
 }
 
@@ -1323,17 +1348,24 @@ fn explore_trail_command(
         },
         mode: args::CliTrailMode::Neighborhood,
         depth: Some(2),
-        direction: Some(args::CliDirection::Both),
-        max_nodes: trail.trail.nodes.len().min(u32::MAX as usize) as u32,
+        direction: None,
+        max_nodes: Some(trail.trail.nodes.len().min(u32::MAX as usize) as u32),
         include_tests: false,
         show_utility_calls: false,
         hide_speculative: false,
+        hide_accessors: false,
+        hide_generated: false,
+        entry_reachable_only: false,
+        filter_preset: None,
+        hide: Vec::new(),
+        collapse: Vec::new(),
         story: false,
         layout: args::CliLayout::Horizontal,
         refresh: args::RefreshMode::None,
         format: args::OutputFormat::Markdown,
         output_file: None,
         mermaid: false,
+        html: false,
     }
 }
 
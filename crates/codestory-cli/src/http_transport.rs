@@ -1,49 +1,237 @@
 use anyhow::{Context, Result, bail};
 use codestory_contracts::api::{
-    LayoutDirection, ListChildrenSymbolsRequest, ListRootSymbolsRequest, NodeId,
-    SearchRepoTextMode, SearchRequest, TrailCallerScope, TrailConfigDto, TrailDirection, TrailMode,
+    ApiError, CodeMarkersRequest, CreateProjectRequest, ExplorerSymbolsRequest, GraphAtRequest,
+    GraphRenderRequest, GraphRenderTheme, LayoutDirection, ListBookmarksRequest,
+    ListChildrenSymbolsRequest, ListIndexErrorsRequest, ListRootSymbolsRequest, NodeHistoryRequest,
+    NodeId, NodeKind, NodeOccurrencesRequest, PageDto, ProjectPresetDto, ReviewGraphRequest,
+    SearchRepoTextMode, SearchRequest, StringLiteralSearchRequest, TrailCallerScope,
+    TrailConfigDto, TrailDefaultsDto, TrailDirection, TrailFilterPresetDto, TrailMode,
 };
 use std::{
     collections::HashMap,
     io::{Read, Write},
     net::{IpAddr, TcpStream},
-    time::Duration,
+    sync::Mutex,
+    time::{Duration, Instant},
 };
+use tokio::sync::{Semaphore, SemaphorePermit, TryAcquireError};
 
 use crate::args;
+use crate::explore;
 use crate::runtime::{self, AmbiguousTargetError, RuntimeContext, map_api_error, resolve_target};
 use crate::{
     build_ambiguous_target_error_output, build_query_resolution_output, build_search_hit_output,
 };
 
-pub(crate) const BROWSER_TRAIL_DEFAULT_DEPTH: u32 = 2;
 pub(crate) const BROWSER_TRAIL_MAX_DEPTH: u32 = 10;
-const BROWSER_TRAIL_MAX_NODES: u32 = 80;
 const BROWSER_REFERENCES_DEPTH: u32 = 0;
-const BROWSER_REFERENCES_MAX_NODES: u32 = 120;
+/// Deadline for an HTTP trail walk unless the request asks for less (or up
+/// to `HTTP_TRAIL_MAX_TIMEOUT_MS`) with `timeout_ms`. Walks that hit it
+/// return the partial trail with `truncated_by_timeout` set.
+const HTTP_TRAIL_DEFAULT_TIMEOUT_MS: u32 = 10_000;
+const HTTP_TRAIL_MAX_TIMEOUT_MS: u32 = 30_000;
 pub(crate) const BROWSER_SYMBOLS_DEFAULT_LIMIT: u32 = 300;
 pub(crate) const BROWSER_SYMBOLS_MAX_LIMIT: u32 = 2_000;
+const BROWSER_AUTOCOMPLETE_DEFAULT_LIMIT: usize = 10;
+const BROWSER_AUTOCOMPLETE_MAX_LIMIT: usize = 50;
+const BROWSER_QUERY_DEFAULT_LIMIT: usize = 100;
+const BROWSER_QUERY_MAX_LIMIT: usize = 500;
+/// Largest diff `POST /review` reads.
+const HTTP_MAX_BODY_BYTES: usize = 2 * 1024 * 1024;
+const QUERY_TABLE_COLUMNS: &[&str] = &[
+    "node_id",
+    "display_name",
+    "kind",
+    "file_path",
+    "line",
+    "depth",
+    "source",
+    "target_id",
+    "target_name",
+];
+/// Routes that run a search or graph walk; `/health` and autocomplete stay
+/// unlimited.
+const RATE_LIMITED_ROUTES: &[&str] = &[
+    "/search",
+    "/symbol",
+    "/definition",
+    "/references",
+    "/symbols",
+    "/symbols/children",
+    "/occurrences",
+    "/explorer/symbols",
+    "/trail",
+    "/graph/render.png",
+    "/query",
+    "/review",
+    "/markers",
+    "/literals",
+    "/history",
+];
+/// Routes that walk the graph from a resolved target. `with_trail_limit`
+/// bounds how many of them run at once.
+const TRAIL_ROUTES: &[&str] = &["/references", "/trail", "/graph/render.png"];
 
-#[derive(Clone, Copy, Debug)]
+/// What a bearer token may do. `GET` routes only read the graph; `POST`
+/// routes such as `/project/create` need a read-write token before they are
+/// even dispatched. Without configured tokens they are served to loopback
+/// peers only.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum HttpServeScope {
+    Read,
+    ReadWrite,
+}
+
+#[derive(Clone)]
+pub(crate) struct HttpServeToken {
+    secret: Box<str>,
+    scope: HttpServeScope,
+}
+
+impl std::fmt::Debug for HttpServeToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HttpServeToken")
+            .field("secret", &"<redacted>")
+            .field("scope", &self.scope)
+            .finish()
+    }
+}
+
+#[derive(Debug)]
 pub(crate) struct HttpServePolicy {
     allow_non_loopback: bool,
+    tokens: Vec<HttpServeToken>,
+    rate_limit: Option<HttpRateLimit>,
+    trail_permits: Option<Semaphore>,
 }
 
 impl HttpServePolicy {
-    pub(crate) fn new(allow_non_loopback: bool) -> Self {
-        Self { allow_non_loopback }
+    pub(crate) fn new(allow_non_loopback: bool, tokens: Vec<HttpServeToken>) -> Self {
+        Self {
+            allow_non_loopback,
+            tokens,
+            rate_limit: None,
+            trail_permits: None,
+        }
+    }
+
+    /// Give each route in `RATE_LIMITED_ROUTES` its own token bucket holding
+    /// `burst` requests and refilling `per_minute` times a minute.
+    pub(crate) fn with_rate_limit(mut self, per_minute: u32, burst: u32) -> Self {
+        self.rate_limit = Some(HttpRateLimit {
+            per_minute: f64::from(per_minute.max(1)),
+            burst: f64::from(burst.max(1)),
+            buckets: Mutex::new(HashMap::new()),
+        });
+        self
+    }
+
+    /// Run at most `limit` requests to `TRAIL_ROUTES` at once. Trails past
+    /// the limit are turned away with 503 rather than queued behind the
+    /// running ones.
+    pub(crate) fn with_trail_limit(mut self, limit: u32) -> Self {
+        self.trail_permits = Some(Semaphore::new(limit.max(1) as usize));
+        self
+    }
+
+    /// The permit a trail route holds while it runs; `Ok(None)` for routes
+    /// and policies without a trail limit.
+    fn trail_permit(
+        &self,
+        path: &str,
+    ) -> std::result::Result<Option<SemaphorePermit<'_>>, TryAcquireError> {
+        match &self.trail_permits {
+            Some(permits) if TRAIL_ROUTES.contains(&path) => permits.try_acquire().map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    /// How long the caller should wait before `path` accepts another request,
+    /// or `None` when this request may proceed.
+    fn rate_limit_retry_after(&self, path: &str, now: Instant) -> Option<Duration> {
+        let limit = self.rate_limit.as_ref()?;
+        let route = RATE_LIMITED_ROUTES
+            .iter()
+            .copied()
+            .find(|route| *route == path)?;
+        let mut buckets = limit
+            .buckets
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let bucket = buckets.entry(route).or_insert(TokenBucket {
+            tokens: limit.burst,
+            refilled_at: now,
+        });
+        let per_second = limit.per_minute / 60.0;
+        let elapsed = now.saturating_duration_since(bucket.refilled_at);
+        bucket.tokens = (bucket.tokens + elapsed.as_secs_f64() * per_second).min(limit.burst);
+        bucket.refilled_at = now;
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            return None;
+        }
+        Some(Duration::from_secs_f64((1.0 - bucket.tokens) / per_second))
     }
 }
 
-pub(crate) fn handle_http_request(
-    runtime: &RuntimeContext,
-    mut stream: TcpStream,
-    policy: HttpServePolicy,
-) -> Result<()> {
-    stream.set_read_timeout(Some(Duration::from_secs(2)))?;
+#[derive(Debug)]
+struct HttpRateLimit {
+    per_minute: f64,
+    burst: f64,
+    buckets: Mutex<HashMap<&'static str, TokenBucket>>,
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+/// Parse `secret[:read|:write]` entries separated by commas. A missing scope
+/// means read-only, so a token never gains write access by accident.
+pub(crate) fn parse_http_serve_tokens(raw: &str) -> Result<Vec<HttpServeToken>> {
+    let mut tokens = Vec::new();
+    for (index, entry) in raw.split(',').map(str::trim).enumerate() {
+        if entry.is_empty() {
+            continue;
+        }
+        let (secret, scope) = match entry.rsplit_once(':') {
+            Some((secret, "read")) => (secret, HttpServeScope::Read),
+            Some((secret, "write")) => (secret, HttpServeScope::ReadWrite),
+            Some((_, scope)) => bail!(
+                "serve token #{} has unknown scope `{scope}`; expected `read` or `write`",
+                index + 1
+            ),
+            None => (entry, HttpServeScope::Read),
+        };
+        if secret.is_empty() {
+            bail!("serve token #{} is empty", index + 1);
+        }
+        tokens.push(HttpServeToken {
+            secret: secret.into(),
+            scope,
+        });
+    }
+    Ok(tokens)
+}
+
+/// Answer a connection the server has no room to queue. The request head is
+/// read first so the client gets the 503 rather than a reset connection.
+pub(crate) fn reject_http_request_busy(mut stream: TcpStream) -> Result<()> {
+    stream.set_read_timeout(Some(Duration::from_millis(500)))?;
+    read_http_request_head(&mut stream)?;
+    write_http_unavailable(
+        &mut stream,
+        "server_busy",
+        "Every worker is busy and the wait queue is full; retry shortly.",
+    )
+}
+
+/// Read up to the blank line ending the request head. `None` when the
+/// client stops sending, times out, or sends more than 8 KiB without one.
+fn read_http_request_head(stream: &mut TcpStream) -> Result<Option<Vec<u8>>> {
     let mut request_bytes = Vec::with_capacity(1024);
     let mut buffer = [0u8; 1024];
-    let mut headers_complete = false;
     loop {
         let read = match stream.read(&mut buffer) {
             Ok(read) => read,
@@ -53,29 +241,36 @@ pub(crate) fn handle_http_request(
                     std::io::ErrorKind::TimedOut | std::io::ErrorKind::WouldBlock
                 ) =>
             {
-                break;
+                return Ok(None);
             }
             Err(error) => return Err(error.into()),
         };
         if read == 0 {
-            break;
+            return Ok(None);
         }
         request_bytes.extend_from_slice(&buffer[..read]);
         if request_bytes.windows(4).any(|window| window == b"\r\n\r\n") {
-            headers_complete = true;
-            break;
+            return Ok(Some(request_bytes));
         }
         if request_bytes.len() >= 8192 {
-            break;
+            return Ok(None);
         }
     }
-    if !headers_complete {
+}
+
+pub(crate) fn handle_http_request(
+    runtime: &RuntimeContext,
+    mut stream: TcpStream,
+    policy: &HttpServePolicy,
+) -> Result<()> {
+    stream.set_read_timeout(Some(Duration::from_secs(2)))?;
+    let Some(request_bytes) = read_http_request_head(&mut stream)? else {
         return write_http_json(
             &mut stream,
             400,
             &serde_json::json!({"error": "bad request"}),
         );
-    }
+    };
     let request = String::from_utf8_lossy(&request_bytes);
     let line = request.lines().next().unwrap_or_default();
     let mut parts = line.split_whitespace();
@@ -85,14 +280,35 @@ pub(crate) fn handle_http_request(
     if let Some(message) = http_boundary_rejection(&headers, policy) {
         return write_http_error_json(&mut stream, 403, "forbidden_http_boundary", message);
     }
-    if method != "GET" {
+    let peer_is_loopback = stream.peer_addr().is_ok_and(|peer| peer.ip().is_loopback());
+    if let Some((status, code, message)) =
+        http_auth_rejection(&headers, method, target, peer_is_loopback, policy)
+    {
+        return write_http_error_json(&mut stream, status, code, message);
+    }
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let method_allowed = match path {
+        "/project/create" | "/review" | "/trail/presets/delete" => method == "POST",
+        "/trail/presets" | "/trail/defaults" => matches!(method, "GET" | "POST"),
+        _ => method == "GET",
+    };
+    if !method_allowed {
         return write_http_json(
             &mut stream,
             405,
             &serde_json::json!({"error": "method not allowed"}),
         );
     }
-    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    if let Some(retry_after) = policy.rate_limit_retry_after(path, Instant::now()) {
+        return write_http_rate_limited(&mut stream, retry_after);
+    }
+    let Ok(_trail_permit) = policy.trail_permit(path) else {
+        return write_http_unavailable(
+            &mut stream,
+            "trails_busy",
+            "Too many trail queries are running; retry shortly.",
+        );
+    };
     let params = parse_query_string(query);
     match path {
         "/health" => write_http_json(&mut stream, 200, &serde_json::json!({"ok": true})),
@@ -136,6 +352,17 @@ pub(crate) fn handle_http_request(
                 &runtime::public_operation_json_value(&operation, &operation.value)?,
             )
         }
+        "/search/autocomplete" => {
+            let prefix = params.get("q").cloned().unwrap_or_default();
+            let limit = params
+                .get("limit")
+                .and_then(|value| value.parse::<usize>().ok())
+                .unwrap_or(BROWSER_AUTOCOMPLETE_DEFAULT_LIMIT)
+                .clamp(1, BROWSER_AUTOCOMPLETE_MAX_LIMIT);
+            write_http_read(&mut stream, runtime, "search", || {
+                runtime.browser.autocomplete(&prefix, limit)
+            })
+        }
         "/symbol" => {
             let Some(selection) = http_target_selection_or_error(&mut stream, &params)? else {
                 return Ok(());
@@ -181,10 +408,15 @@ pub(crate) fn handle_http_request(
             let Some(selection) = http_target_selection_or_error(&mut stream, &params)? else {
                 return Ok(());
             };
+            let (hide, collapse) = browser_graph_view_params(&params);
+            let timeout_ms = http_trail_timeout_ms(params.get("timeout_ms").map(String::as_str));
             match run_http_target_operation(runtime, selection, None, |target| {
+                let mut config = browser_references_config(target.selected.node_id.clone());
+                config.graph_session = runtime.request_graph_session(&hide, &collapse)?;
+                config.timeout_ms = Some(timeout_ms);
                 let context = runtime
                     .browser
-                    .references_context(browser_references_config(target.selected.node_id.clone()))
+                    .references_context(config)
                     .map_err(map_api_error)?;
                 Ok(serde_json::json!({
                     "resolution": build_query_resolution_output(&runtime.project_root, target),
@@ -211,6 +443,8 @@ pub(crate) fn handle_http_request(
                         .browser
                         .list_children_symbols(ListChildrenSymbolsRequest {
                             parent_id: NodeId(parent_id.clone()),
+                            offset: None,
+                            limit: None,
                         })
                         .map_err(map_api_error)
                 } else {
@@ -226,6 +460,26 @@ pub(crate) fn handle_http_request(
                 &runtime::public_operation_json_value(&operation, &operation.value)?,
             )
         }
+        "/explorer/symbols" => {
+            let Some(kinds) = explorer_kinds_param(params.get("kinds").map(String::as_str)) else {
+                return write_http_error_json(
+                    &mut stream,
+                    400,
+                    "invalid_kinds",
+                    "`kinds` must be a comma-separated list of node kinds such as function,struct.",
+                );
+            };
+            let request = ExplorerSymbolsRequest {
+                query: params.get("query").cloned(),
+                kinds,
+                limit: params
+                    .get("limit")
+                    .and_then(|value| value.parse::<u32>().ok()),
+            };
+            write_http_read(&mut stream, runtime, "graph", || {
+                runtime.browser.explorer_symbols(request.clone())
+            })
+        }
         "/trail" => {
             let Some(selection) = http_target_selection_or_error(&mut stream, &params)? else {
                 return Ok(());
@@ -233,16 +487,19 @@ pub(crate) fn handle_http_request(
             let depth = browser_trail_depth(params.get("depth").map(String::as_str));
             let direction = browser_trail_direction(params.get("direction").map(String::as_str));
             let story = browser_bool_param(params.get("story").map(String::as_str));
+            let (hide, collapse) = browser_graph_view_params(&params);
+            let preset = params
+                .get("preset")
+                .map(|value| value.trim())
+                .filter(|value| !value.is_empty());
+            let timeout_ms = http_trail_timeout_ms(params.get("timeout_ms").map(String::as_str));
             match run_http_target_operation(runtime, selection, None, |target| {
-                runtime
-                    .browser
-                    .trail_context(browser_trail_config(
-                        target.selected.node_id.clone(),
-                        depth,
-                        direction,
-                        story,
-                    ))
-                    .map_err(map_api_error)
+                let mut config =
+                    browser_trail_config(target.selected.node_id.clone(), depth, direction, story);
+                config.graph_session = runtime.request_graph_session(&hide, &collapse)?;
+                config.filter_preset = preset.map(str::to_string);
+                config.timeout_ms = Some(timeout_ms);
+                runtime.browser.trail_context(config).map_err(map_api_error)
             }) {
                 Ok(operation) => write_http_json(
                     &mut stream,
@@ -252,10 +509,397 @@ pub(crate) fn handle_http_request(
                 Err(error) => write_http_target_error(&mut stream, runtime, error),
             }
         }
+        "/graph/render.png" => {
+            let Some(selection) = http_target_selection_or_error(&mut stream, &params)? else {
+                return Ok(());
+            };
+            let depth = browser_trail_depth(params.get("depth").map(String::as_str));
+            let direction = browser_trail_direction(params.get("direction").map(String::as_str));
+            let width = params
+                .get("width")
+                .and_then(|value| value.parse::<u32>().ok());
+            let height = params
+                .get("height")
+                .and_then(|value| value.parse::<u32>().ok());
+            let theme = browser_render_theme(params.get("theme").map(String::as_str));
+            let (hide, collapse) = browser_graph_view_params(&params);
+            let preset = params
+                .get("preset")
+                .map(|value| value.trim())
+                .filter(|value| !value.is_empty());
+            let timeout_ms = http_trail_timeout_ms(params.get("timeout_ms").map(String::as_str));
+            match run_http_target_operation(runtime, selection, None, |target| {
+                let mut trail =
+                    browser_trail_config(target.selected.node_id.clone(), depth, direction, false);
+                trail.graph_session = runtime.request_graph_session(&hide, &collapse)?;
+                trail.filter_preset = preset.map(str::to_string);
+                trail.timeout_ms = Some(timeout_ms);
+                runtime
+                    .browser
+                    .render_trail_png(GraphRenderRequest {
+                        trail,
+                        width,
+                        height,
+                        theme,
+                    })
+                    .map_err(map_api_error)
+            }) {
+                Ok(operation) => write_http_bytes(&mut stream, 200, "image/png", &operation.value),
+                Err(error) => write_http_target_error(&mut stream, runtime, error),
+            }
+        }
+        "/query" => {
+            let source = params
+                .get("q")
+                .map(|value| value.trim())
+                .unwrap_or_default();
+            let ast = match codestory_runtime::parse_graph_query(source) {
+                Ok(ast) => ast,
+                Err(error) => {
+                    return write_http_error_json(
+                        &mut stream,
+                        400,
+                        "invalid_query",
+                        error.to_string(),
+                    );
+                }
+            };
+            let limit = params
+                .get("limit")
+                .and_then(|value| value.parse::<usize>().ok())
+                .unwrap_or(BROWSER_QUERY_DEFAULT_LIMIT)
+                .clamp(1, BROWSER_QUERY_MAX_LIMIT);
+            write_http_read(&mut stream, runtime, "graph", || {
+                let found = runtime.browser.query(&ast)?;
+                Ok(query_table_json(&runtime.project_root, &found, limit))
+            })
+        }
+        "/markers" => {
+            let number = |name: &str| params.get(name).and_then(|value| value.parse::<u32>().ok());
+            let request = CodeMarkersRequest {
+                tag: params.get("tag").cloned(),
+                file: params.get("file").cloned(),
+                min_age_days: number("min_age_days"),
+                max_age_days: number("max_age_days"),
+                limit: number("limit"),
+            };
+            write_http_read(&mut stream, runtime, "markers", || {
+                runtime.browser.code_markers(request.clone())
+            })
+        }
+        "/literals" => {
+            let request = StringLiteralSearchRequest {
+                query: params.get("q").cloned().unwrap_or_default(),
+                limit: params
+                    .get("limit")
+                    .and_then(|value| value.parse::<u32>().ok()),
+            };
+            write_http_read(&mut stream, runtime, "literals", || {
+                runtime.browser.search_string_literals(request.clone())
+            })
+        }
+        "/history" => {
+            let node_id = params.get("id").filter(|value| !value.trim().is_empty());
+            let run_id = params.get("run").filter(|value| !value.trim().is_empty());
+            match (node_id, run_id) {
+                (Some(_), Some(_)) => write_http_error_json(
+                    &mut stream,
+                    400,
+                    "invalid_argument",
+                    "Pass `id` or `run`, not both.",
+                ),
+                (Some(id), None) => {
+                    let request = NodeHistoryRequest {
+                        id: NodeId(id.trim().to_string()),
+                    };
+                    write_http_read(&mut stream, runtime, "history", || {
+                        runtime.browser.node_history(request.clone())
+                    })
+                }
+                (None, Some(run_id)) => {
+                    let request = GraphAtRequest {
+                        run_id: run_id.trim().to_string(),
+                    };
+                    write_http_read(&mut stream, runtime, "history", || {
+                        runtime.browser.graph_at(request.clone())
+                    })
+                }
+                (None, None) => write_http_read(&mut stream, runtime, "history", || {
+                    runtime.browser.index_history_runs()
+                }),
+            }
+        }
+        "/occurrences" => {
+            let Some(id) = params.get("id").filter(|value| !value.trim().is_empty()) else {
+                return write_http_error_json(&mut stream, 400, "missing_id", "Pass `id`.");
+            };
+            let (offset, limit) = http_page_params(&params);
+            let request = NodeOccurrencesRequest {
+                id: NodeId(id.trim().to_string()),
+                kind: params.get("kind").cloned(),
+                offset,
+                limit,
+            };
+            write_http_page(&mut stream, runtime, "occurrences", || {
+                runtime.browser.node_occurrences_page(request.clone())
+            })
+        }
+        "/symbols/children" => {
+            let Some(parent_id) = params.get("parent_id").filter(|value| !value.is_empty()) else {
+                return write_http_error_json(
+                    &mut stream,
+                    400,
+                    "missing_parent_id",
+                    "Pass `parent_id`.",
+                );
+            };
+            let (offset, limit) = http_page_params(&params);
+            let request = ListChildrenSymbolsRequest {
+                parent_id: NodeId(parent_id.clone()),
+                offset,
+                limit,
+            };
+            write_http_page(&mut stream, runtime, "graph", || {
+                runtime.browser.list_children_symbols_page(request.clone())
+            })
+        }
+        "/bookmarks" => {
+            let (offset, limit) = http_page_params(&params);
+            let request = ListBookmarksRequest {
+                category_id: params.get("category_id").cloned(),
+                offset,
+                limit,
+            };
+            write_http_page(&mut stream, runtime, "bookmarks", || {
+                runtime.bookmarks.list_bookmarks_page(request.clone())
+            })
+        }
+        "/index/errors" => {
+            let (offset, limit) = http_page_params(&params);
+            let request = ListIndexErrorsRequest {
+                fatal_only: params
+                    .get("fatal_only")
+                    .is_some_and(|value| matches!(value.as_str(), "1" | "true")),
+                offset,
+                limit,
+            };
+            write_http_page(&mut stream, runtime, "errors", || {
+                runtime.browser.list_index_errors(request.clone())
+            })
+        }
+        "/project/manifest" => match runtime.index.index_manifest() {
+            Ok(Some(manifest)) => {
+                write_http_json(&mut stream, 200, &serde_json::to_value(manifest)?)
+            }
+            Ok(None) => write_http_error_json(
+                &mut stream,
+                404,
+                "index_manifest_missing",
+                "No index manifest yet; run an index first.",
+            ),
+            Err(error) => write_http_error_json(
+                &mut stream,
+                500,
+                "index_manifest_unavailable",
+                error.message,
+            ),
+        },
+        "/trail/presets" if method == "POST" => {
+            let body = match read_http_body(&mut stream, &request_bytes, &headers) {
+                Ok(body) => body,
+                Err((status, code, message)) => {
+                    return write_http_error_json(&mut stream, status, code, message);
+                }
+            };
+            let Ok(preset) = serde_json::from_slice::<TrailFilterPresetDto>(&body) else {
+                return write_http_error_json(
+                    &mut stream,
+                    400,
+                    "invalid_body",
+                    "The request body must be a preset: {\"name\", \"edge_kinds\", \"node_kinds\"}.",
+                );
+            };
+            write_http_read(&mut stream, runtime, "trail_presets", || {
+                runtime.index.save_trail_filter_preset(preset.clone())
+            })
+        }
+        "/trail/presets" => write_http_read(&mut stream, runtime, "trail_presets", || {
+            runtime.index.trail_filter_presets()
+        }),
+        "/trail/defaults" if method == "POST" => {
+            let body = match read_http_body(&mut stream, &request_bytes, &headers) {
+                Ok(body) => body,
+                Err((status, code, message)) => {
+                    return write_http_error_json(&mut stream, status, code, message);
+                }
+            };
+            let Ok(defaults) = serde_json::from_slice::<TrailDefaultsDto>(&body) else {
+                return write_http_error_json(
+                    &mut stream,
+                    400,
+                    "invalid_body",
+                    "The request body must be trail defaults: {\"depth\", \"direction\", \"caller_scope\", \"max_nodes\", \"edge_filter\"}.",
+                );
+            };
+            write_http_read(&mut stream, runtime, "trail_defaults", || {
+                runtime.index.set_trail_defaults(defaults.clone())
+            })
+        }
+        "/trail/defaults" => write_http_read(&mut stream, runtime, "trail_defaults", || {
+            runtime.index.trail_defaults()
+        }),
+        "/trail/presets/delete" => {
+            let name = params.get("name").cloned().unwrap_or_default();
+            write_http_read(&mut stream, runtime, "trail_presets", || {
+                if runtime.index.delete_trail_filter_preset(&name)? {
+                    Ok(serde_json::json!({"deleted": name.trim()}))
+                } else {
+                    Err(ApiError::not_found(format!(
+                        "Trail filter preset not found: {}",
+                        name.trim()
+                    )))
+                }
+            })
+        }
+        "/project/create" => {
+            let Some(preset) = params
+                .get("preset")
+                .and_then(|value| project_preset_param(value))
+            else {
+                return write_http_error_json(
+                    &mut stream,
+                    400,
+                    "invalid_preset",
+                    "`preset` must be one of rust_workspace, node_monorepo, cxx_cmake, python_package.",
+                );
+            };
+            match runtime.project.create_project_with_storage_path(
+                CreateProjectRequest {
+                    path: runtime.project_root.to_string_lossy().to_string(),
+                    preset,
+                },
+                runtime.storage_path.clone(),
+            ) {
+                Ok(summary) => write_http_json(&mut stream, 200, &serde_json::to_value(summary)?),
+                Err(error) => {
+                    let (status, code) = http_api_error_status(&error);
+                    write_http_error_json(&mut stream, status, code, error.message)
+                }
+            }
+        }
+        "/review" => {
+            let body = match read_http_body(&mut stream, &request_bytes, &headers) {
+                Ok(body) => body,
+                Err((status, code, message)) => {
+                    return write_http_error_json(&mut stream, status, code, message);
+                }
+            };
+            let Ok(diff) = String::from_utf8(body) else {
+                return write_http_error_json(
+                    &mut stream,
+                    400,
+                    "invalid_body",
+                    "The request body must be a UTF-8 unified diff.",
+                );
+            };
+            let depth = params
+                .get("depth")
+                .and_then(|value| value.parse::<u32>().ok());
+            let reindexed_files = if review_reindex_requested(&params) {
+                match runtime.index.reindex_review_diff(&diff) {
+                    Ok(files) => files,
+                    Err(error) => {
+                        let (status, code) = http_api_error_status(&error);
+                        return write_http_error_json(&mut stream, status, code, error.message);
+                    }
+                }
+            } else {
+                Vec::new()
+            };
+            let operation = match runtime.run_public_operation("review", || {
+                let mut output = runtime
+                    .browser
+                    .review_graph(ReviewGraphRequest {
+                        diff: diff.clone(),
+                        depth,
+                    })
+                    .map_err(map_api_error)?;
+                output.reindexed_files = reindexed_files.clone();
+                Ok(output)
+            }) {
+                Ok(operation) => operation,
+                Err(error) => {
+                    let (status, code) = http_error_status(&error);
+                    return write_http_error_json(&mut stream, status, code, error.to_string());
+                }
+            };
+            write_http_json(
+                &mut stream,
+                200,
+                &runtime::public_operation_json_value(&operation, &operation.value)?,
+            )
+        }
         _ => write_http_json(&mut stream, 404, &serde_json::json!({"error": "not found"})),
     }
 }
 
+/// The body following the headers in `request_bytes`, reading the rest of
+/// its declared `Content-Length` from `stream`.
+fn read_http_body(
+    stream: &mut TcpStream,
+    request_bytes: &[u8],
+    headers: &[(&str, &str)],
+) -> std::result::Result<Vec<u8>, (u16, &'static str, String)> {
+    let length = match http_header_values(headers, "content-length").as_slice() {
+        [value] => value.parse::<usize>().map_err(|_| {
+            (
+                400,
+                "invalid_body",
+                "`Content-Length` must be a byte count.".to_string(),
+            )
+        })?,
+        _ => {
+            return Err((
+                411,
+                "length_required",
+                "The request needs exactly one `Content-Length` header.".to_string(),
+            ));
+        }
+    };
+    if length > HTTP_MAX_BODY_BYTES {
+        return Err((
+            413,
+            "payload_too_large",
+            format!("The request body may be at most {HTTP_MAX_BODY_BYTES} bytes."),
+        ));
+    }
+    let body_start = request_bytes
+        .windows(4)
+        .position(|window| window == b"\r\n\r\n")
+        .map_or(request_bytes.len(), |position| position + 4);
+    let mut body = request_bytes[body_start..].to_vec();
+    body.truncate(length);
+    let mut buffer = [0u8; 8192];
+    while body.len() < length {
+        let read = stream.read(&mut buffer).map_err(|error| {
+            (
+                400,
+                "invalid_body",
+                format!("Failed to read the request body: {error}"),
+            )
+        })?;
+        if read == 0 {
+            return Err((
+                400,
+                "invalid_body",
+                "The request body ended before `Content-Length` bytes.".to_string(),
+            ));
+        }
+        body.extend_from_slice(&buffer[..read.min(length - body.len())]);
+    }
+    Ok(body)
+}
+
 fn parse_http_headers(request: &str) -> Vec<(&str, &str)> {
     request
         .lines()
@@ -268,7 +912,7 @@ fn parse_http_headers(request: &str) -> Vec<(&str, &str)> {
         .collect()
 }
 
-fn http_boundary_rejection(headers: &[(&str, &str)], policy: HttpServePolicy) -> Option<String> {
+fn http_boundary_rejection(headers: &[(&str, &str)], policy: &HttpServePolicy) -> Option<String> {
     if policy.allow_non_loopback {
         return None;
     }
@@ -298,6 +942,98 @@ fn http_boundary_rejection(headers: &[(&str, &str)], policy: HttpServePolicy) ->
     None
 }
 
+/// Check the bearer token when the policy has any, then compare its scope with
+/// what the request method needs. Without tokens, methods that need write
+/// access are refused to peers off the loopback interface.
+fn http_auth_rejection(
+    headers: &[(&str, &str)],
+    method: &str,
+    target: &str,
+    peer_is_loopback: bool,
+    policy: &HttpServePolicy,
+) -> Option<(u16, &'static str, String)> {
+    let required = http_request_scope(method, target);
+    if policy.tokens.is_empty() {
+        if required == HttpServeScope::ReadWrite && !peer_is_loopback {
+            return Some((
+                403,
+                "forbidden_scope",
+                format!(
+                    "{method} requests from non-loopback clients need a read-write serve token; set CODESTORY_SERVE_TOKENS."
+                ),
+            ));
+        }
+        return None;
+    }
+    let authorizations = http_header_values(headers, "authorization");
+    let presented = match authorizations.as_slice() {
+        [value] => value
+            .split_once(' ')
+            .filter(|(scheme, _)| scheme.eq_ignore_ascii_case("bearer"))
+            .map(|(_, token)| token.trim()),
+        _ => None,
+    };
+    let Some(presented) = presented else {
+        return Some((
+            401,
+            "unauthorized",
+            "HTTP serve requires exactly one `Authorization: Bearer <token>` header.".to_string(),
+        ));
+    };
+    let Some(token) = policy
+        .tokens
+        .iter()
+        .find(|token| http_token_matches(&token.secret, presented))
+    else {
+        return Some((401, "unauthorized", "Unknown serve token.".to_string()));
+    };
+    if token.scope < required {
+        return Some((
+            403,
+            "forbidden_scope",
+            format!("{method} requests need a read-write serve token."),
+        ));
+    }
+    None
+}
+
+fn http_method_scope(method: &str) -> HttpServeScope {
+    match method {
+        "GET" | "HEAD" | "OPTIONS" => HttpServeScope::Read,
+        _ => HttpServeScope::ReadWrite,
+    }
+}
+
+/// The scope a request needs. `POST /review` only carries its diff in the
+/// body, so it reads unless it asks to reindex.
+fn http_request_scope(method: &str, target: &str) -> HttpServeScope {
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    if method == "POST"
+        && path == "/review"
+        && !review_reindex_requested(&parse_query_string(query))
+    {
+        return HttpServeScope::Read;
+    }
+    http_method_scope(method)
+}
+
+fn review_reindex_requested(params: &HashMap<String, String>) -> bool {
+    params
+        .get("reindex")
+        .is_some_and(|value| matches!(value.as_str(), "1" | "true"))
+}
+
+/// Compare without returning at the first differing byte.
+fn http_token_matches(expected: &str, presented: &str) -> bool {
+    let (expected, presented) = (expected.as_bytes(), presented.as_bytes());
+    expected.len() == presented.len()
+        && expected
+            .iter()
+            .zip(presented)
+            .fold(0u8, |diff, (left, right)| diff | (left ^ right))
+            == 0
+}
+
 fn http_header_values<'a>(headers: &'a [(&str, &str)], name: &str) -> Vec<&'a str> {
     headers
         .iter()
@@ -374,28 +1110,41 @@ fn http_target_public_operation(selection: &args::TargetSelection) -> &'static s
     }
 }
 
+/// Direct incoming references of `root_id`, test and bench callers
+/// included. The node budget and edge filter come from the project's trail
+/// defaults.
 pub(crate) fn browser_references_config(root_id: NodeId) -> TrailConfigDto {
     TrailConfigDto {
         root_id,
         mode: TrailMode::AllReferencing,
         target_id: None,
-        depth: BROWSER_REFERENCES_DEPTH,
-        direction: TrailDirection::Incoming,
-        caller_scope: TrailCallerScope::IncludeTestsAndBenches,
+        depth: Some(BROWSER_REFERENCES_DEPTH),
+        direction: Some(TrailDirection::Incoming),
+        caller_scope: Some(TrailCallerScope::IncludeTestsAndBenches),
         edge_filter: Vec::new(),
         show_utility_calls: false,
         hide_speculative: false,
         story: false,
         node_filter: Vec::new(),
-        max_nodes: BROWSER_REFERENCES_MAX_NODES,
+        hide_trivial_accessors: false,
+        hide_generated: false,
+        entry_reachable_only: false,
+        max_paths: None,
+        edge_weight: Default::default(),
+        max_nodes: None,
         layout_direction: LayoutDirection::Horizontal,
+        graph_session: None,
+        timeout_ms: None,
+        filter_preset: None,
     }
 }
 
+/// A neighborhood trail around `root_id`. Unset fields, and the caller scope
+/// and node budget, come from the project's trail defaults.
 pub(crate) fn browser_trail_config(
     root_id: NodeId,
-    depth: u32,
-    direction: TrailDirection,
+    depth: Option<u32>,
+    direction: Option<TrailDirection>,
     story: bool,
 ) -> TrailConfigDto {
     TrailConfigDto {
@@ -404,32 +1153,100 @@ pub(crate) fn browser_trail_config(
         target_id: None,
         depth,
         direction,
-        caller_scope: TrailCallerScope::ProductionOnly,
+        caller_scope: None,
         edge_filter: Vec::new(),
         show_utility_calls: false,
         hide_speculative: false,
         story,
         node_filter: Vec::new(),
-        max_nodes: BROWSER_TRAIL_MAX_NODES,
+        hide_trivial_accessors: false,
+        hide_generated: false,
+        entry_reachable_only: false,
+        max_paths: None,
+        edge_weight: Default::default(),
+        max_nodes: None,
         layout_direction: LayoutDirection::Horizontal,
+        graph_session: None,
+        timeout_ms: None,
+        filter_preset: None,
     }
 }
 
-fn browser_trail_depth(value: Option<&str>) -> u32 {
+fn http_trail_timeout_ms(value: Option<&str>) -> u32 {
+    value
+        .and_then(|value| value.parse::<u32>().ok())
+        .map_or(HTTP_TRAIL_DEFAULT_TIMEOUT_MS, |value| {
+            value.min(HTTP_TRAIL_MAX_TIMEOUT_MS)
+        })
+}
+
+fn browser_trail_depth(value: Option<&str>) -> Option<u32> {
     value
         .and_then(|value| value.parse::<u32>().ok())
         .map(|value| value.min(BROWSER_TRAIL_MAX_DEPTH))
-        .unwrap_or(BROWSER_TRAIL_DEFAULT_DEPTH)
 }
 
-fn browser_trail_direction(value: Option<&str>) -> TrailDirection {
+pub(crate) fn browser_trail_direction(value: Option<&str>) -> Option<TrailDirection> {
+    match value {
+        Some("incoming") => Some(TrailDirection::Incoming),
+        Some("outgoing") => Some(TrailDirection::Outgoing),
+        Some("both") => Some(TrailDirection::Both),
+        _ => None,
+    }
+}
+
+fn project_preset_param(value: &str) -> Option<ProjectPresetDto> {
     match value {
-        Some("incoming") => TrailDirection::Incoming,
-        Some("outgoing") => TrailDirection::Outgoing,
-        _ => TrailDirection::Both,
+        "rust_workspace" => Some(ProjectPresetDto::RustWorkspace),
+        "node_monorepo" => Some(ProjectPresetDto::NodeMonorepo),
+        "cxx_cmake" => Some(ProjectPresetDto::CxxCmake),
+        "python_package" => Some(ProjectPresetDto::PythonPackage),
+        _ => None,
     }
 }
 
+/// Comma-separated node kinds, in any case and with `-` for `_`. `None`
+/// when one is not a kind.
+fn explorer_kinds_param(value: Option<&str>) -> Option<Vec<NodeKind>> {
+    value
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|kind| !kind.is_empty())
+        .map(|kind| {
+            serde_json::from_value(serde_json::Value::String(
+                kind.replace('-', "_").to_ascii_uppercase(),
+            ))
+            .ok()
+        })
+        .collect()
+}
+
+fn browser_render_theme(value: Option<&str>) -> GraphRenderTheme {
+    match value {
+        Some("dark") => GraphRenderTheme::Dark,
+        _ => GraphRenderTheme::Light,
+    }
+}
+
+/// Comma-separated node ids of the `hide` and `collapse` parameters.
+fn browser_graph_view_params(params: &HashMap<String, String>) -> (Vec<NodeId>, Vec<NodeId>) {
+    let node_ids = |key: &str| {
+        params
+            .get(key)
+            .map(|value| {
+                value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|id| !id.is_empty())
+                    .map(|id| NodeId(id.to_string()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    (node_ids("hide"), node_ids("collapse"))
+}
+
 fn browser_bool_param(value: Option<&str>) -> bool {
     matches!(
         value.map(|value| value.to_ascii_lowercase()).as_deref(),
@@ -437,6 +1254,39 @@ fn browser_bool_param(value: Option<&str>) -> bool {
     )
 }
 
+/// Query items as rows under `QUERY_TABLE_COLUMNS`, cut to `limit`. The
+/// table is truncated when it was cut or the query's `match` hit its cap.
+fn query_table_json(
+    project_root: &std::path::Path,
+    found: &codestory_runtime::BrowserQueryOutput,
+    limit: usize,
+) -> serde_json::Value {
+    let rows = found
+        .items
+        .iter()
+        .take(limit)
+        .map(|item| {
+            let item = explore::browser_query_item_to_output(project_root, item);
+            serde_json::json!([
+                item.node_id,
+                item.display_name,
+                item.kind,
+                item.file_path,
+                item.line,
+                item.depth,
+                item.source,
+                item.target_id,
+                item.target_name,
+            ])
+        })
+        .collect::<Vec<_>>();
+    serde_json::json!({
+        "columns": QUERY_TABLE_COLUMNS,
+        "rows": rows,
+        "truncated": found.truncated || found.items.len() > limit,
+    })
+}
+
 fn browser_symbols_limit(value: Option<&str>) -> Option<u32> {
     Some(
         value
@@ -459,6 +1309,59 @@ fn http_target_selection_or_error(
     }
 }
 
+/// `offset` and `limit` of a paged route; unparsable values are ignored.
+fn http_page_params(params: &HashMap<String, String>) -> (Option<u32>, Option<u32>) {
+    let number = |name: &str| params.get(name).and_then(|value| value.parse::<u32>().ok());
+    (number("offset"), number("limit"))
+}
+
+/// Write one page of a listing with its publication metadata.
+fn write_http_page<T: serde::Serialize>(
+    stream: &mut TcpStream,
+    runtime: &RuntimeContext,
+    operation: &str,
+    read: impl Fn() -> Result<PageDto<T>, ApiError>,
+) -> Result<()> {
+    write_http_read(stream, runtime, operation, read)
+}
+
+/// Write the result of a read with its publication metadata, or its error
+/// with the status [`http_error_status`] picks.
+fn write_http_read<T: serde::Serialize>(
+    stream: &mut TcpStream,
+    runtime: &RuntimeContext,
+    operation: &str,
+    read: impl Fn() -> Result<T, ApiError>,
+) -> Result<()> {
+    match runtime.run_public_operation(operation, || read().map_err(map_api_error)) {
+        Ok(operation) => write_http_json(
+            stream,
+            200,
+            &runtime::public_operation_json_value(&operation, &operation.value)?,
+        ),
+        Err(error) => {
+            let (status, code) = http_error_status(&error);
+            write_http_error_json(stream, status, code, error.to_string())
+        }
+    }
+}
+
+/// Status and error code for a failed request: `400` for bad arguments,
+/// `404` for unknown ids, `409` for state that already exists, `500` for
+/// everything the caller cannot fix.
+fn http_error_status(error: &anyhow::Error) -> (u16, &'static str) {
+    runtime::api_error_in_chain(error).map_or((500, "internal"), http_api_error_status)
+}
+
+fn http_api_error_status(error: &ApiError) -> (u16, &'static str) {
+    match error.code.as_str() {
+        "invalid_argument" => (400, "invalid_argument"),
+        "not_found" => (404, "not_found"),
+        "already_exists" => (409, "already_exists"),
+        _ => (500, "internal"),
+    }
+}
+
 fn write_http_target_error(
     stream: &mut TcpStream,
     runtime: &RuntimeContext,
@@ -501,23 +1404,94 @@ fn write_http_json<T: serde::Serialize>(
     stream: &mut TcpStream,
     status: u16,
     value: &T,
+) -> Result<()> {
+    write_http_json_with_headers(stream, status, "", value)
+}
+
+/// `extra_headers` is empty or a run of `Name: value\r\n` lines.
+fn write_http_json_with_headers<T: serde::Serialize>(
+    stream: &mut TcpStream,
+    status: u16,
+    extra_headers: &str,
+    value: &T,
 ) -> Result<()> {
     let body = serde_json::to_string_pretty(value)?;
-    let status_text = match status {
+    let status_text = http_status_text(status);
+    write!(
+        stream,
+        "HTTP/1.1 {status} {status_text}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n{extra_headers}Connection: close\r\n\r\n{}",
+        body.len(),
+        body
+    )?;
+    Ok(())
+}
+
+fn write_http_bytes(
+    stream: &mut TcpStream,
+    status: u16,
+    content_type: &str,
+    body: &[u8],
+) -> Result<()> {
+    let status_text = http_status_text(status);
+    write!(
+        stream,
+        "HTTP/1.1 {status} {status_text}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        body.len()
+    )?;
+    stream.write_all(body)?;
+    Ok(())
+}
+
+fn http_status_text(status: u16) -> &'static str {
+    match status {
         200 => "OK",
         400 => "Bad Request",
+        401 => "Unauthorized",
         403 => "Forbidden",
         404 => "Not Found",
         405 => "Method Not Allowed",
+        409 => "Conflict",
+        411 => "Length Required",
+        413 => "Payload Too Large",
+        429 => "Too Many Requests",
+        500 => "Internal Server Error",
+        503 => "Service Unavailable",
         _ => "OK",
-    };
-    write!(
+    }
+}
+
+fn write_http_rate_limited(stream: &mut TcpStream, retry_after: Duration) -> Result<()> {
+    let retry_after_ms = u64::try_from(retry_after.as_millis())
+        .unwrap_or(u64::MAX)
+        .max(1);
+    write_http_json_with_headers(
         stream,
-        "HTTP/1.1 {status} {status_text}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
-        body.len(),
-        body
-    )?;
-    Ok(())
+        429,
+        &format!("Retry-After: {}\r\n", retry_after_ms.div_ceil(1000)),
+        &serde_json::json!({
+            "error": {
+                "code": "rate_limited",
+                "message": "Too many requests for this route; retry after the given delay.",
+                "retry_after_ms": retry_after_ms
+            }
+        }),
+    )
+}
+
+/// Turn a request away while the server is saturated. The work is short,
+/// so callers are asked to retry after a second.
+fn write_http_unavailable(stream: &mut TcpStream, code: &'static str, message: &str) -> Result<()> {
+    write_http_json_with_headers(
+        stream,
+        503,
+        "Retry-After: 1\r\n",
+        &serde_json::json!({
+            "error": {
+                "code": code,
+                "message": message
+            }
+        }),
+    )
 }
 
 fn parse_query_string(query: &str) -> HashMap<String, String> {
@@ -595,4 +1569,221 @@ mod tests {
             "graph"
         );
     }
+
+    #[test]
+    fn graph_view_params_split_comma_separated_node_ids() {
+        let params = parse_query_string("hide=node-1,%20node-2,&collapse=node-3");
+        let (hide, collapse) = browser_graph_view_params(&params);
+        assert_eq!(
+            hide,
+            vec![NodeId("node-1".to_string()), NodeId("node-2".to_string())]
+        );
+        assert_eq!(collapse, vec![NodeId("node-3".to_string())]);
+        assert_eq!(
+            browser_graph_view_params(&HashMap::new()),
+            (Vec::new(), Vec::new())
+        );
+    }
+
+    #[test]
+    fn serve_tokens_default_to_read_and_gate_unsafe_methods() -> Result<()> {
+        let tokens = parse_http_serve_tokens("reader, writer:write ,viewer:read")?;
+        assert_eq!(
+            tokens.iter().map(|token| token.scope).collect::<Vec<_>>(),
+            vec![
+                HttpServeScope::Read,
+                HttpServeScope::ReadWrite,
+                HttpServeScope::Read
+            ]
+        );
+        assert!(parse_http_serve_tokens("reader:admin").is_err());
+        assert!(parse_http_serve_tokens(":write").is_err());
+        assert!(!format!("{tokens:?}").contains("reader"));
+
+        let policy = HttpServePolicy::new(false, tokens);
+        let reject = |authorization: Option<&str>, method: &str| {
+            let mut headers = vec![("Host", "127.0.0.1:3917")];
+            if let Some(value) = authorization {
+                headers.push(("Authorization", value));
+            }
+            http_auth_rejection(&headers, method, "/search", false, &policy)
+                .map(|(status, code, _)| (status, code))
+        };
+        assert_eq!(reject(None, "GET"), Some((401, "unauthorized")));
+        assert_eq!(
+            reject(Some("Bearer nope"), "GET"),
+            Some((401, "unauthorized"))
+        );
+        assert_eq!(
+            reject(Some("Basic reader"), "GET"),
+            Some((401, "unauthorized"))
+        );
+        assert_eq!(reject(Some("Bearer reader"), "GET"), None);
+        assert_eq!(
+            reject(Some("Bearer reader"), "POST"),
+            Some((403, "forbidden_scope"))
+        );
+        assert_eq!(reject(Some("bearer writer"), "POST"), None);
+
+        let open = HttpServePolicy::new(false, Vec::new());
+        assert!(http_auth_rejection(&[], "POST", "/project/create", true, &open).is_none());
+        assert!(http_auth_rejection(&[], "GET", "/search", false, &open).is_none());
+        assert_eq!(
+            http_auth_rejection(&[], "POST", "/project/create", false, &open)
+                .map(|(status, code, _)| (status, code)),
+            Some((403, "forbidden_scope"))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn review_posts_need_write_scope_only_to_reindex() {
+        assert_eq!(
+            http_request_scope("POST", "/review?depth=2"),
+            HttpServeScope::Read
+        );
+        assert_eq!(
+            http_request_scope("POST", "/review?reindex=1"),
+            HttpServeScope::ReadWrite
+        );
+        assert_eq!(
+            http_request_scope("POST", "/project/create"),
+            HttpServeScope::ReadWrite
+        );
+
+        let policy =
+            HttpServePolicy::new(false, parse_http_serve_tokens("reader").expect("tokens"));
+        let headers = [("Authorization", "Bearer reader")];
+        assert!(http_auth_rejection(&headers, "POST", "/review", false, &policy).is_none());
+        assert_eq!(
+            http_auth_rejection(&headers, "POST", "/review?reindex=true", false, &policy)
+                .map(|(status, code, _)| (status, code)),
+            Some((403, "forbidden_scope"))
+        );
+        let open = HttpServePolicy::new(false, Vec::new());
+        assert!(http_auth_rejection(&[], "POST", "/review", false, &open).is_none());
+    }
+
+    #[test]
+    fn query_table_lists_one_row_per_item_under_fixed_columns() {
+        let item = |id: &str| codestory_runtime::BrowserQueryItem {
+            node_id: NodeId(id.to_string()),
+            display_name: format!("app::billing::{id}"),
+            kind: codestory_contracts::api::NodeKind::FUNCTION,
+            file_path: None,
+            line: Some(7),
+            depth: None,
+            source: "match".to_string(),
+            target_id: Some(NodeId("9".to_string())),
+            target_name: Some("app::db::insert".to_string()),
+        };
+        let found = codestory_runtime::BrowserQueryOutput {
+            items: vec![item("1"), item("2"), item("3")],
+            truncated: false,
+        };
+        let table = query_table_json(std::path::Path::new("/repo"), &found, 2);
+
+        assert_eq!(table["columns"].as_array().map(Vec::len), Some(9));
+        assert_eq!(table["rows"].as_array().map(Vec::len), Some(2));
+        assert_eq!(table["rows"][0][0], "1");
+        assert_eq!(table["rows"][0][1], "app::billing::1");
+        assert_eq!(table["rows"][0][4], 7);
+        assert_eq!(table["rows"][1][6], "match");
+        assert_eq!(table["rows"][1][7], "9");
+        assert_eq!(table["rows"][1][8], "app::db::insert");
+        assert_eq!(table["truncated"], true);
+
+        let capped = codestory_runtime::BrowserQueryOutput {
+            items: vec![item("1")],
+            truncated: true,
+        };
+        let table = query_table_json(std::path::Path::new("/repo"), &capped, 2);
+        assert_eq!(table["rows"].as_array().map(Vec::len), Some(1));
+        assert_eq!(table["truncated"], true);
+    }
+
+    #[test]
+    fn rate_limit_spends_burst_per_route_then_asks_callers_to_wait() {
+        let policy = HttpServePolicy::new(false, Vec::new()).with_rate_limit(60, 2);
+        let start = Instant::now();
+        assert!(policy.rate_limit_retry_after("/trail", start).is_none());
+        assert!(policy.rate_limit_retry_after("/trail", start).is_none());
+        let retry_after = policy
+            .rate_limit_retry_after("/trail", start)
+            .expect("burst spent");
+        assert_eq!(retry_after, Duration::from_secs(1));
+
+        assert!(policy.rate_limit_retry_after("/search", start).is_none());
+        assert!(policy.rate_limit_retry_after("/health", start).is_none());
+        assert!(
+            policy
+                .rate_limit_retry_after("/trail", start + Duration::from_secs(1))
+                .is_none()
+        );
+        assert!(
+            HttpServePolicy::new(false, Vec::new())
+                .rate_limit_retry_after("/trail", start)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn trail_limit_turns_away_trails_past_the_limit_until_one_finishes() {
+        let policy = HttpServePolicy::new(false, Vec::new()).with_trail_limit(1);
+        let running = policy
+            .trail_permit("/trail")
+            .expect("first trail admitted")
+            .expect("trail routes hold a permit");
+        assert!(policy.trail_permit("/graph/render.png").is_err());
+        assert!(policy.trail_permit("/references").is_err());
+        assert!(matches!(policy.trail_permit("/search"), Ok(None)));
+        drop(running);
+        assert!(matches!(policy.trail_permit("/references"), Ok(Some(_))));
+        assert!(matches!(
+            HttpServePolicy::new(false, Vec::new()).trail_permit("/trail"),
+            Ok(None)
+        ));
+    }
+
+    #[test]
+    fn unavailable_response_asks_callers_to_retry() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind");
+        let mut client = TcpStream::connect(listener.local_addr().expect("addr")).expect("connect");
+        let (mut server, _) = listener.accept().expect("accept");
+        write_http_unavailable(&mut server, "trails_busy", "busy").expect("write 503");
+        drop(server);
+        let mut response = String::new();
+        client.read_to_string(&mut response).expect("read");
+        assert!(response.starts_with("HTTP/1.1 503 Service Unavailable\r\n"));
+        assert!(response.contains("Retry-After: 1\r\n"));
+        assert!(response.contains("\"code\": \"trails_busy\""));
+    }
+
+    #[test]
+    fn http_trails_default_to_a_deadline_and_cap_requested_ones() {
+        assert_eq!(http_trail_timeout_ms(None), HTTP_TRAIL_DEFAULT_TIMEOUT_MS);
+        assert_eq!(
+            http_trail_timeout_ms(Some("soon")),
+            HTTP_TRAIL_DEFAULT_TIMEOUT_MS
+        );
+        assert_eq!(http_trail_timeout_ms(Some("250")), 250);
+        assert_eq!(
+            http_trail_timeout_ms(Some("900000")),
+            HTTP_TRAIL_MAX_TIMEOUT_MS
+        );
+    }
+
+    #[test]
+    fn explorer_kinds_accept_any_case_and_reject_unknown_kinds() {
+        assert_eq!(
+            explorer_kinds_param(Some("function, Struct,enum-constant")),
+            Some(vec![
+                NodeKind::FUNCTION,
+                NodeKind::STRUCT,
+                NodeKind::ENUM_CONSTANT
+            ])
+        );
+        assert_eq!(explorer_kinds_param(None), Some(Vec::new()));
+        assert_eq!(explorer_kinds_param(Some("function,widget")), None);
+    }
 }
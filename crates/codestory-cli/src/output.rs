@@ -30,13 +30,13 @@ use std::io::{BufWriter, Write};
 use std::path::Path;
 
 use crate::args::{
-    CliTrailMode, DoctorOutput, DrillOutput, IndexDryRunOutput, IndexOutput, OutputFormat,
-    QueryItemOutput, QueryOutput, ReadyOutput, SearchHitOutput, SearchOutput, TrailCommand,
+    DoctorOutput, DrillOutput, IndexDryRunOutput, IndexOutput, OutputFormat, QueryItemOutput,
+    QueryOutput, ReadyOutput, SearchHitOutput, SearchOutput, TrailCommand,
     VerificationTargetOutput,
 };
 use crate::display::{
-    clean_path_string, default_trail_direction, format_budget, format_direction, format_kind,
-    format_trail_mode, relative_path,
+    clean_path_string, format_budget, format_direction, format_kind, format_trail_mode,
+    relative_path,
 };
 use crate::runtime::ResolvedTarget;
 
@@ -527,6 +527,7 @@ fn append_index_cache_timings(markdown: &mut String, timings: &IndexingPhaseTimi
             ("deferred_indexes", timings.deferred_indexes_ms),
             ("summary_snapshot", timings.summary_snapshot_ms),
             ("detail_snapshot", timings.detail_snapshot_ms),
+            ("analyze", timings.analyze_ms),
             ("publish", timings.publish_ms),
         ],
     );
@@ -3126,6 +3127,17 @@ pub(crate) fn render_symbol_markdown(
         "focus: {}",
         render_node(project_root, &context.node)
     );
+    if !context.node.definitions.is_empty() {
+        let _ = writeln!(markdown, "definitions: {}", context.node.definitions.len());
+        for definition in &context.node.definitions {
+            let _ = writeln!(
+                markdown,
+                "- {}:{}",
+                relative_path(project_root, &definition.file_path),
+                definition.start_line
+            );
+        }
+    }
     if let Some(summary) = context.summary.as_deref() {
         let _ = writeln!(markdown, "summary: {summary}");
     }
@@ -3164,6 +3176,12 @@ pub(crate) fn render_query_markdown(output: &QueryOutput) -> String {
     for item in &output.items {
         let _ = writeln!(markdown, "{}", render_query_item_line(item));
     }
+    if output.truncated {
+        let _ = writeln!(
+            markdown,
+            "truncated: the match read stopped at its row cap; narrow `from`, `to`, or kinds"
+        );
+    }
     markdown
 }
 
@@ -3183,6 +3201,9 @@ fn render_query_item_line(item: &QueryItemOutput) -> String {
     if let Some(depth) = item.depth {
         let _ = write!(line, " depth={depth}");
     }
+    if let (Some(target_id), Some(target_name)) = (&item.target_id, &item.target_name) {
+        let _ = write!(line, " -> [{target_id}] {target_name}");
+    }
     if let Some(node_ref) = item.node_ref.as_deref() {
         let _ = write!(line, " ref=`{node_ref}`");
     }
@@ -3258,15 +3279,12 @@ pub(crate) fn render_trail_markdown(
         markdown,
         "mode: {} direction: {} depth: {} nodes: {} edges: {} truncated: {}",
         format_trail_mode(cmd.mode),
-        format_direction(
-            cmd.direction
-                .map(Into::into)
-                .unwrap_or_else(|| default_trail_direction(cmd.mode))
-        ),
-        cmd.depth.unwrap_or(match cmd.mode {
-            CliTrailMode::Neighborhood => 2,
-            CliTrailMode::Referenced | CliTrailMode::Referencing => 0,
-        }),
+        // Unset flags take the project's trail defaults, which only the
+        // runtime resolves.
+        cmd.direction
+            .map_or("default", |direction| format_direction(direction.into())),
+        cmd.depth
+            .map_or_else(|| "default".to_string(), |depth| depth.to_string()),
         context.trail.nodes.len(),
         context.trail.edges.len(),
         context.trail.truncated
@@ -4258,6 +4276,7 @@ mod tests {
             source_excerpt: None,
             verification_targets: Vec::new(),
             score_breakdown: None,
+            highlight: None,
         }
     }
 
@@ -4329,6 +4348,10 @@ mod tests {
             file_count: 1,
             error_count: 0,
             fatal_error_count: 0,
+            occurrence_count: 0,
+            database_bytes: 0,
+            last_indexed_at_epoch_ms: None,
+            tables: Vec::new(),
         }
     }
 
@@ -4386,6 +4409,7 @@ mod tests {
             end_col: None,
             member_access: None,
             route_endpoint: None,
+            definitions: Vec::new(),
             evidence_tier: None,
             evidence_producer: None,
             resolution_status: None,
@@ -4432,6 +4456,9 @@ mod tests {
             file_path: None,
             qualified_name: None,
             member_access: None,
+            edge_counts: None,
+            badges: Default::default(),
+            centrality: None,
         }
     }
 
@@ -4453,20 +4480,19 @@ mod tests {
             file_path: Some(file_path.to_string()),
             qualified_name: None,
             member_access: None,
+            edge_counts: None,
+            badges: Default::default(),
+            centrality: None,
         }
     }
 
     fn sample_graph_edge(id: &str, source: &str, target: &str) -> GraphEdgeDto {
-        GraphEdgeDto {
-            id: EdgeId(id.to_string()),
-            source: NodeId(source.to_string()),
-            target: NodeId(target.to_string()),
-            kind: EdgeKind::CALL,
-            confidence: None,
-            certainty: None,
-            callsite_identity: None,
-            candidate_targets: Vec::new(),
-        }
+        GraphEdgeDto::new(
+            EdgeId(id.to_string()),
+            NodeId(source.to_string()),
+            NodeId(target.to_string()),
+            EdgeKind::CALL,
+        )
     }
 
     fn sample_graph_edge_with_certainty(
@@ -4477,14 +4503,14 @@ mod tests {
         confidence: f32,
     ) -> GraphEdgeDto {
         GraphEdgeDto {
-            id: EdgeId(id.to_string()),
-            source: NodeId(source.to_string()),
-            target: NodeId(target.to_string()),
-            kind: EdgeKind::CALL,
             confidence: Some(confidence),
             certainty: Some(certainty.to_string()),
-            callsite_identity: None,
-            candidate_targets: Vec::new(),
+            ..GraphEdgeDto::new(
+                EdgeId(id.to_string()),
+                NodeId(source.to_string()),
+                NodeId(target.to_string()),
+                EdgeKind::CALL,
+            )
         }
     }
 
@@ -4500,19 +4526,26 @@ mod tests {
                 file: None,
                 choose: None,
             },
-            mode: CliTrailMode::Neighborhood,
+            mode: crate::args::CliTrailMode::Neighborhood,
             depth: Some(2),
             direction: None,
-            max_nodes: 24,
+            max_nodes: Some(24),
             include_tests,
             show_utility_calls: false,
             hide_speculative: false,
+            hide_accessors: false,
+            hide_generated: false,
+            entry_reachable_only: false,
+            filter_preset: None,
+            hide: Vec::new(),
+            collapse: Vec::new(),
             story: true,
             layout: crate::args::CliLayout::Horizontal,
             refresh: crate::args::RefreshMode::None,
             format: OutputFormat::Markdown,
             output_file: None,
             mermaid: false,
+            html: false,
         }
     }
 
@@ -4622,7 +4655,14 @@ mod tests {
                     ),
                 ],
                 truncated: false,
+                truncated_by_timeout: false,
                 omitted_edge_count: 0,
+                pruned_test_node_count: 0,
+                pruned_accessor_node_count: 0,
+                pruned_generated_node_count: 0,
+                omitted_node_count: 0,
+                node_limit: None,
+                paths: Vec::new(),
                 canonical_layout: None,
             },
             story: None,
@@ -5432,6 +5472,10 @@ mod tests {
                 file_count: 4,
                 error_count: 2,
                 fatal_error_count: 0,
+                occurrence_count: 0,
+                database_bytes: 0,
+                last_indexed_at_epoch_ms: None,
+                tables: Vec::new(),
             },
             retrieval: Some(retrieval),
             coverage: GroundingCoverageDto {
@@ -5776,7 +5820,14 @@ legend:
                 nodes: vec![sample_graph_node("a", "A"), sample_graph_node("b", "B")],
                 edges: vec![sample_graph_edge("edge-1", "a", "b")],
                 truncated: false,
+                truncated_by_timeout: false,
                 omitted_edge_count: 0,
+                pruned_test_node_count: 0,
+                pruned_accessor_node_count: 0,
+                pruned_generated_node_count: 0,
+                omitted_node_count: 0,
+                node_limit: None,
+                paths: Vec::new(),
                 canonical_layout: None,
             },
             story: None,
@@ -509,6 +509,10 @@ mod tests {
             file_count: u32::from(node_count > 0),
             error_count: 0,
             fatal_error_count: 0,
+            occurrence_count: 0,
+            database_bytes: 0,
+            last_indexed_at_epoch_ms: None,
+            tables: Vec::new(),
         }
     }
 
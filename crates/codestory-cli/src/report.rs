@@ -88,6 +88,7 @@ fn render_report_markdown(output: &RepoReport, profile: ReportProfile) -> String
         "Bridge / High-Connectivity Nodes",
         &output.bridge_nodes,
     );
+    append_central_symbols(&mut markdown, &output.central_symbols);
     append_follow_ups(&mut markdown, output);
     let _ = writeln!(
         markdown,
@@ -345,6 +346,35 @@ fn append_node_section(
     let _ = writeln!(markdown);
 }
 
+fn append_central_symbols(
+    markdown: &mut String,
+    nodes: &[codestory_runtime::graph_analysis::ReportNodeSummary],
+) {
+    let _ = writeln!(markdown, "## Most Central Symbols");
+    if nodes.is_empty() {
+        let _ = writeln!(
+            markdown,
+            "No centrality scores are stored yet; they are computed on the next index run."
+        );
+        let _ = writeln!(markdown);
+        return;
+    }
+    let _ = writeln!(markdown, "| Node | Kind | Centrality | In | Source |");
+    let _ = writeln!(markdown, "| --- | --- | ---: | ---: | --- |");
+    for node in nodes {
+        let _ = writeln!(
+            markdown,
+            "| `{}` | `{}` | {:.2} | {} | {} |",
+            markdown_escape(&node.name),
+            node.kind,
+            node.centrality.unwrap_or_default(),
+            node.incoming_edges,
+            render_source_location(node.source_location.as_ref())
+        );
+    }
+    let _ = writeln!(markdown);
+}
+
 fn append_follow_ups(
     markdown: &mut String,
     output: &codestory_runtime::graph_analysis::RepoReport,
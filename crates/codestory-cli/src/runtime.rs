@@ -7,7 +7,8 @@
 
 use anyhow::{Context, Result, anyhow, bail};
 use codestory_contracts::api::{
-    ApiError, AppEventPayload, IndexMode, IndexingPhaseTimings, ProjectSummary, SearchHit,
+    ApiError, AppEventPayload, GraphSessionUpdateRequest, IndexMode, IndexingPhaseTimings, NodeId,
+    ProjectSummary, SearchHit,
 };
 use codestory_runtime::{
     ActivationService, BookmarkService, GroundingService, IndexService, ProjectService,
@@ -17,7 +18,7 @@ use codestory_runtime::{
 use serde::Serialize;
 use std::path::{Component, Path, PathBuf};
 use std::sync::Arc;
-use std::sync::atomic::AtomicBool;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 
 use crate::args::{ProjectArgs, QuerySelectorOutput, RefreshMode, TargetSelection};
 use crate::display::{clean_path_string, quote_command_path};
@@ -439,6 +440,35 @@ impl RuntimeContext {
         }
     }
 
+    /// Open a graph session that hides `hide` and folds `collapse` for one
+    /// request, or `None` when both are empty. Each call gets a fresh session
+    /// name, so concurrent requests never share a view.
+    pub(crate) fn request_graph_session(
+        &self,
+        hide: &[NodeId],
+        collapse: &[NodeId],
+    ) -> Result<Option<String>> {
+        static NEXT_REQUEST_SESSION: AtomicU64 = AtomicU64::new(0);
+        if hide.is_empty() && collapse.is_empty() {
+            return Ok(None);
+        }
+        let session = format!(
+            "request-{}",
+            NEXT_REQUEST_SESSION.fetch_add(1, Ordering::Relaxed)
+        );
+        self.browser
+            .update_graph_session(GraphSessionUpdateRequest {
+                session: session.clone(),
+                reset: true,
+                hide: hide.to_vec(),
+                unhide: Vec::new(),
+                collapse: collapse.to_vec(),
+                uncollapse: Vec::new(),
+            })
+            .map_err(|error| map_api_error_for_project(error, &self.project_root))?;
+        Ok(Some(session))
+    }
+
     pub(crate) fn active_project_summary(&self) -> Result<ProjectSummary> {
         self.public_operation
             .active_project_summary()
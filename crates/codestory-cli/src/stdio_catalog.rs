@@ -742,6 +742,7 @@ const PACKET_EVIDENCE_RESOLUTIONS: &[&str] = &[
 const SEARCH_REPO_TEXT_MODES: &[&str] = &["auto", "on", "off"];
 const INDEXED_FILE_ROLES: &[&str] = &["source", "test", "generated", "vendor", "unknown"];
 const SNIPPET_SCOPES: &[&str] = &["line_context", "function_body"];
+const TRAIL_PRESET_ACTIONS: &[&str] = &["list", "save", "delete"];
 const GROUNDING_BUDGETS: &[&str] = &["strict", "balanced", "max"];
 const GROUNDING_ORIENTATION_CONFIDENCE: &[&str] = &["strong", "partial", "weak"];
 const GROUNDING_ORIENTATION_UNCERTAINTY: &[&str] = &[
@@ -892,6 +893,10 @@ static SYMBOL_SUMMARY_SCHEMA: SchemaObject = SchemaObject::object(
         SchemaProperty::string("kind", "Node kind."),
         SchemaProperty::string("file_path", "Project-relative file path.").nullable(),
         SchemaProperty::boolean("has_children", "Whether children can be browsed."),
+        SchemaProperty::boolean(
+            "entry_point",
+            "Curated entry point bookmarked under \"Start here\"; listed first among root symbols.",
+        ),
     ],
     &["id", "label", "kind", "has_children"],
 );
@@ -966,6 +971,26 @@ static SYMBOLS_OUTPUT_SCHEMA: SchemaObject = SchemaObject::object(
     &["symbols", "returned_count", "limit", "truncated"],
 );
 
+static TRAIL_PRESET_SCHEMA: SchemaObject = SchemaObject::object(
+    "Saved trail filter preset.",
+    &[
+        SchemaProperty::string("name", "Preset name."),
+        SchemaProperty::string_array("edge_kinds", "Edge kinds the preset keeps."),
+        SchemaProperty::string_array("node_kinds", "Node kinds the preset keeps."),
+    ],
+    &["name", "edge_kinds", "node_kinds"],
+);
+
+static TRAIL_PRESETS_OUTPUT_SCHEMA: SchemaObject = SchemaObject::object(
+    "Saved trail filter presets after the action.",
+    &[SchemaProperty::array(
+        "presets",
+        "Saved presets, ordered by name.",
+        &TRAIL_PRESET_SCHEMA,
+    )],
+    &["presets"],
+);
+
 static INDEXED_FILE_SCHEMA: SchemaObject = SchemaObject::object(
     "Indexed file coverage row.",
     &[
@@ -1805,6 +1830,20 @@ static SYMBOLS_INPUT_SCHEMA: SchemaObject = SchemaObject::object(
     &[],
 );
 
+static TRAIL_PRESETS_INPUT_SCHEMA: SchemaObject = SchemaObject::object(
+    "List, save, or delete named trail filter presets.",
+    &[
+        SchemaProperty::string("action", "Preset action.")
+            .with_enum(TRAIL_PRESET_ACTIONS)
+            .with_default(ValueLiteral::String("list")),
+        SchemaProperty::string("name", "Preset name; required to save or delete.")
+            .with_min_length(1),
+        SchemaProperty::string_array("edge_kinds", "Edge kinds the preset keeps, e.g. CALL."),
+        SchemaProperty::string_array("node_kinds", "Node kinds the preset keeps, e.g. FUNCTION."),
+    ],
+    &[],
+);
+
 static FILES_INPUT_SCHEMA: SchemaObject = SchemaObject::object(
     "List indexed files from the existing local index.",
     &[
@@ -2145,6 +2184,13 @@ static TOOLS: &[ToolSpec] = &[
         output_schema: Some(SchemaSpec::Object(SYMBOLS_OUTPUT_SCHEMA)),
         safety: SafetyMetadata::managed_activation(),
     },
+    ToolSpec {
+        name: "trail_presets",
+        description: "List, save, or delete named trail filter presets that CLI and HTTP trails apply by name.",
+        input_schema: TRAIL_PRESETS_INPUT_SCHEMA,
+        output_schema: Some(SchemaSpec::Object(TRAIL_PRESETS_OUTPUT_SCHEMA)),
+        safety: SafetyMetadata::managed_activation(),
+    },
     ToolSpec {
         name: "snippet",
         description: "Return a focused source snippet after packet, search, or graph evidence selects a concrete target.",
@@ -15,8 +15,8 @@ use codestory_contracts::api::{
     IndexedFilesRequest, ListChildrenSymbolsRequest, ListRootSymbolsRequest, NodeDetailsDto,
     NodeDetailsRequest, NodeId, NodeKind, PACKET_PROBE_CONTRACT_VERSION, PacketBudgetModeDto,
     PacketProbeDto, PacketTaskClassDto, ProjectSummary, ReadinessGoalDto, ReadinessStatusDto,
-    ReadinessVerdictDto, SearchRepoTextMode, SearchRequest, StorageStatsDto, TrailCallerScope,
-    TrailDirection, TrailMode,
+    ReadinessVerdictDto, SearchRepoTextMode, SearchRequest, StorageStatsDto, TrailDirection,
+    TrailFilterPresetDto, TrailMode,
 };
 use codestory_workspace::project_identity_v3;
 use serde::Deserialize;
@@ -34,8 +34,8 @@ use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncWrite, AsyncWriteExt, BufRea
 
 use crate::args;
 use crate::http_transport::{
-    BROWSER_SYMBOLS_DEFAULT_LIMIT, BROWSER_SYMBOLS_MAX_LIMIT, BROWSER_TRAIL_DEFAULT_DEPTH,
-    BROWSER_TRAIL_MAX_DEPTH, browser_references_config, browser_trail_config,
+    BROWSER_SYMBOLS_DEFAULT_LIMIT, BROWSER_SYMBOLS_MAX_LIMIT, BROWSER_TRAIL_MAX_DEPTH,
+    browser_references_config, browser_trail_config, browser_trail_direction,
 };
 use crate::output::{
     REPO_CONTENT_BOUNDARY_LINE, UNTRUSTED_REPO_EVIDENCE_TRUST, context_packet_json,
@@ -2072,6 +2072,7 @@ fn handle_stdio_tool_call(
         "definition" => handle_stdio_definition(runtime, request),
         "references" => handle_stdio_references(runtime, request),
         "symbols" => handle_stdio_symbols(runtime, request),
+        "trail_presets" => handle_stdio_trail_presets(runtime, request),
         "snippet" => match prepared {
             PreparedStdioToolCall::Snippet(snippet) => {
                 handle_stdio_snippet(runtime, request, *snippet)
@@ -3029,20 +3030,15 @@ fn handle_stdio_trail(
     request: &serde_json::Value,
     default_story: bool,
 ) -> serde_json::Value {
-    let direction = match request
-        .pointer("/params/arguments/direction")
-        .and_then(|value| value.as_str())
-    {
-        Some("incoming") => TrailDirection::Incoming,
-        Some("outgoing") => TrailDirection::Outgoing,
-        _ => TrailDirection::Both,
-    };
-    let depth = request
-        .pointer("/params/arguments/depth")
-        .and_then(|value| value.as_u64())
-        .map(|value| value.min(BROWSER_TRAIL_MAX_DEPTH as u64) as u32)
-        .unwrap_or(BROWSER_TRAIL_DEFAULT_DEPTH);
-    let max_nodes = stdio_graph_u32_arg(request, "max_nodes", 120, 1, 120);
+    // Arguments the caller leaves out fall back to the project's trail
+    // defaults.
+    let direction = browser_trail_direction(
+        request
+            .pointer("/params/arguments/direction")
+            .and_then(|value| value.as_str()),
+    );
+    let depth = stdio_graph_optional_u32_arg(request, "depth", 0, BROWSER_TRAIL_MAX_DEPTH);
+    let max_nodes = stdio_graph_optional_u32_arg(request, "max_nodes", 1, 120);
     let story = request
         .pointer("/params/arguments/story")
         .and_then(|value| value.as_bool())
@@ -3154,9 +3150,13 @@ fn handle_stdio_neighbors(
     let max_nodes = stdio_graph_u32_arg(request, "max_nodes", default_max_nodes, 1, 120);
     resolve_target(runtime, stdio_target_selection(request), None)
         .and_then(|target| {
-            let mut config =
-                browser_trail_config(target.selected.node_id.clone(), depth, direction, false);
-            config.max_nodes = max_nodes;
+            let mut config = browser_trail_config(
+                target.selected.node_id.clone(),
+                Some(depth),
+                Some(direction),
+                false,
+            );
+            config.max_nodes = Some(max_nodes);
             runtime
                 .browser
                 .trail_context(config)
@@ -3218,16 +3218,24 @@ fn handle_stdio_shortest_path(
             root_id: from.clone(),
             mode: TrailMode::ToTargetSymbol,
             target_id: Some(to.clone()),
-            depth: max_depth,
-            direction: TrailDirection::Outgoing,
-            caller_scope: TrailCallerScope::ProductionOnly,
+            depth: Some(max_depth),
+            direction: Some(TrailDirection::Outgoing),
+            caller_scope: None,
             edge_filter: Vec::new(),
             show_utility_calls: false,
             hide_speculative: false,
             story: false,
             node_filter: Vec::new(),
-            max_nodes,
+            hide_trivial_accessors: false,
+            hide_generated: false,
+            entry_reachable_only: false,
+            max_paths: None,
+            edge_weight: Default::default(),
+            max_nodes: Some(max_nodes),
             layout_direction: codestory_contracts::api::LayoutDirection::Horizontal,
+            graph_session: None,
+            timeout_ms: None,
+            filter_preset: None,
         })
         .map(|context| {
             let mut output = stdio_graph_tool_output(
@@ -3267,6 +3275,69 @@ fn handle_stdio_references(
         )
 }
 
+fn handle_stdio_trail_presets(
+    runtime: &RuntimeContext,
+    request: &serde_json::Value,
+) -> serde_json::Value {
+    let action = request
+        .pointer("/params/arguments/action")
+        .and_then(|value| value.as_str())
+        .unwrap_or("list");
+    let name = request
+        .pointer("/params/arguments/name")
+        .and_then(|value| value.as_str())
+        .map(str::trim)
+        .filter(|value| !value.is_empty());
+    let result = match (action, name) {
+        ("list", _) => runtime.index.trail_filter_presets(),
+        ("save", Some(name)) => stdio_trail_preset(request, name)
+            .and_then(|preset| runtime.index.save_trail_filter_preset(preset)),
+        ("delete", Some(name)) => match runtime.index.delete_trail_filter_preset(name) {
+            Ok(true) => runtime.index.trail_filter_presets(),
+            Ok(false) => Err(ApiError::not_found(format!(
+                "Trail filter preset not found: {name}"
+            ))),
+            Err(error) => Err(error),
+        },
+        ("save" | "delete", None) => Err(ApiError::invalid_argument(format!(
+            "trail_presets {action} requires a preset name."
+        ))),
+        _ => Err(ApiError::invalid_argument(format!(
+            "Unknown trail_presets action `{action}`; expected list, save, or delete."
+        ))),
+    };
+    result
+        .map(|presets| serde_json::json!({"result": {"presets": presets}}))
+        .unwrap_or_else(|error| serde_json::json!({"error": stdio_api_error_value(error)}))
+}
+
+fn stdio_trail_preset(
+    request: &serde_json::Value,
+    name: &str,
+) -> std::result::Result<TrailFilterPresetDto, ApiError> {
+    fn kinds<T: serde::de::DeserializeOwned>(
+        request: &serde_json::Value,
+        field: &str,
+    ) -> std::result::Result<Vec<T>, ApiError> {
+        request
+            .pointer(&format!("/params/arguments/{field}"))
+            .and_then(|value| value.as_array())
+            .into_iter()
+            .flatten()
+            .map(|value| {
+                let value = value.as_str().unwrap_or_default();
+                args::parse_graph_kind(value)
+                    .map_err(|error| ApiError::invalid_argument(format!("{field}: {error}")))
+            })
+            .collect()
+    }
+    Ok(TrailFilterPresetDto {
+        name: name.to_string(),
+        edge_kinds: kinds(request, "edge_kinds")?,
+        node_kinds: kinds(request, "node_kinds")?,
+    })
+}
+
 fn handle_stdio_symbols(
     runtime: &RuntimeContext,
     request: &serde_json::Value,
@@ -3285,6 +3356,8 @@ fn handle_stdio_symbols(
             .browser
             .list_children_symbols(ListChildrenSymbolsRequest {
                 parent_id: NodeId(parent_id.to_string()),
+                offset: None,
+                limit: None,
             })
             .map(|symbols| {
                 serde_json::to_value(symbols)
@@ -3384,6 +3457,7 @@ fn handle_stdio_context(
             latency_budget_ms: None,
             include_evidence,
             hybrid_weights: None,
+            intent: None,
         })
         .map(|mut result| {
             result.retrieval_trace.annotations.push(format!(
@@ -3422,11 +3496,19 @@ fn stdio_graph_u32_arg(
     min: u32,
     max: u32,
 ) -> u32 {
+    stdio_graph_optional_u32_arg(request, name, min, max).unwrap_or(default)
+}
+
+fn stdio_graph_optional_u32_arg(
+    request: &serde_json::Value,
+    name: &str,
+    min: u32,
+    max: u32,
+) -> Option<u32> {
     request
         .pointer(&format!("/params/arguments/{name}"))
         .and_then(|value| value.as_u64())
         .map(|value| value.clamp(min as u64, max as u64) as u32)
-        .unwrap_or(default)
 }
 
 fn stdio_graph_string_arg<'a>(request: &'a serde_json::Value, name: &str) -> Option<&'a str> {
@@ -3901,11 +3983,16 @@ fn read_stdio_status_resource_uncached(
                 file_count: 0,
                 error_count: 0,
                 fatal_error_count: 0,
+                occurrence_count: 0,
+                database_bytes: 0,
+                last_indexed_at_epoch_ms: None,
+                tables: Vec::new(),
             },
             members: Vec::new(),
             retrieval: None,
             freshness: None,
             publication: None,
+            storage_generation: None,
         });
     let recent_local_refresh = state.recent_local_refresh.take();
     let local_refresh = crate::local_refresh_status::active_local_refresh_status(
@@ -5620,12 +5707,7 @@ fn read_stdio_template_resource(
             .map_err(map_api_error),
         StdioResource::Trail(node_id) => runtime
             .browser
-            .trail_context(browser_trail_config(
-                node_id.clone(),
-                BROWSER_TRAIL_DEFAULT_DEPTH,
-                TrailDirection::Both,
-                false,
-            ))
+            .trail_context(browser_trail_config(node_id.clone(), None, None, false))
             .map(|value| serde_json::json!(value))
             .map_err(map_api_error),
         _ => bail!("resource is not publication-backed"),
@@ -6349,6 +6431,7 @@ version = "0.11.20"
             source_excerpt: None,
             verification_targets: Vec::new(),
             score_breakdown: None,
+            highlight: None,
         };
         let result = codestory_contracts::api::SearchResultsDto {
             query: "test".to_string(),
@@ -6426,6 +6509,10 @@ version = "0.11.20"
             edge_count: 0,
             error_count: 0,
             fatal_error_count: 0,
+            occurrence_count: 0,
+            database_bytes: 0,
+            last_indexed_at_epoch_ms: None,
+            tables: Vec::new(),
         };
         let readiness =
             crate::readiness::build_readiness_verdicts(crate::readiness::ReadinessInputs {
@@ -297,8 +297,9 @@ fn runtime_exposes_read_only_browser_service_boundary() {
     .join("\n");
 
     assert!(
-        runtime_lib.contains("pub use browser::{BrowserQueryItem, ReadOnlyBrowserService}")
-            && runtime_lib.contains("pub fn browser_service(&self) -> ReadOnlyBrowserService"),
+        runtime_lib.contains(
+            "pub use browser::{BrowserQueryItem, BrowserQueryOutput, ReadOnlyBrowserService}",
+        ) && runtime_lib.contains("pub fn browser_service(&self) -> ReadOnlyBrowserService"),
         "runtime should export a read-only browser service accessor"
     );
     assert!(
@@ -1466,6 +1466,22 @@ fn add_and_assert_bookmark_focus(workspace: &Path, cache_dir: &Path, node_id: &s
             .any(|bookmark| bookmark["bookmark"]["id"] == bookmark_id),
         "bookmark list should include the saved focus"
     );
+    let first_page = run_cli_json(
+        workspace,
+        cache_dir,
+        &["bookmark", "list", "--limit", "1", "--format", "json"],
+    );
+    let total = first_page["total"].as_u64().expect("bookmark total");
+    assert_eq!(
+        first_page["bookmarks"].as_array().map(Vec::len),
+        Some(1),
+        "bookmark list --limit should page: {first_page}"
+    );
+    assert_eq!(
+        first_page.get("next_offset").and_then(Value::as_u64),
+        (total > 1).then_some(1),
+        "bookmark list should point at the next page: {first_page}"
+    );
     bookmark_id
 }
 
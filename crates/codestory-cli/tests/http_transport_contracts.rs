@@ -222,6 +222,21 @@ fn publish_zero_dense_agent_fixture(fixture: &HttpFixture) {
     .expect("publish strict zero-dense HTTP fixture");
 }
 
+/// Delete the published index out from under a running server, so reads
+/// that open storage fail.
+fn remove_index_database(fixture: &HttpFixture) {
+    for entry in fs::read_dir(fixture.cache_dir.path()).expect("list cache dir") {
+        let path = entry.expect("cache dir entry").path();
+        if path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with("codestory.db"))
+        {
+            fs::remove_file(&path).expect("remove index database");
+        }
+    }
+}
+
 fn free_local_addr() -> String {
     let listener = TcpListener::bind("127.0.0.1:0").expect("bind free local port");
     let addr = listener.local_addr().expect("read local addr");
@@ -262,6 +277,10 @@ fn http_serve_rejects_non_loopback_addr_before_opening_runtime_state() {
 }
 
 fn spawn_http_server(fixture: &HttpFixture) -> (HttpServer, String) {
+    spawn_http_server_with_args(fixture, &[])
+}
+
+fn spawn_http_server_with_args(fixture: &HttpFixture, args: &[&str]) -> (HttpServer, String) {
     let addr = free_local_addr();
     let child = test_support::cli_command()
         .arg("serve")
@@ -273,6 +292,7 @@ fn spawn_http_server(fixture: &HttpFixture) -> (HttpServer, String) {
         .arg(fixture.cache_dir.path())
         .arg("--addr")
         .arg(&addr)
+        .args(args)
         .env("CODESTORY_EMBED_ALLOW_CPU", "1")
         .stdin(Stdio::null())
         .stdout(Stdio::null())
@@ -313,13 +333,38 @@ fn http_get_with_headers(
     addr: &str,
     target: &str,
     headers: &[(&str, &str)],
+) -> std::io::Result<HttpResponse> {
+    http_request(addr, "GET", target, headers)
+}
+
+fn http_request(
+    addr: &str,
+    method: &str,
+    target: &str,
+    headers: &[(&str, &str)],
+) -> std::io::Result<HttpResponse> {
+    http_request_with_body(addr, method, target, headers, None)
+}
+
+fn http_request_with_body(
+    addr: &str,
+    method: &str,
+    target: &str,
+    headers: &[(&str, &str)],
+    body: Option<&str>,
 ) -> std::io::Result<HttpResponse> {
     let mut stream = TcpStream::connect(addr)?;
-    write!(stream, "GET {target} HTTP/1.1\r\n")?;
+    write!(stream, "{method} {target} HTTP/1.1\r\n")?;
     for (name, value) in headers {
         write!(stream, "{name}: {value}\r\n")?;
     }
+    if let Some(body) = body {
+        write!(stream, "Content-Length: {}\r\n", body.len())?;
+    }
     write!(stream, "Connection: close\r\n\r\n")?;
+    if let Some(body) = body {
+        stream.write_all(body.as_bytes())?;
+    }
     stream.flush()?;
     stream.shutdown(Shutdown::Write)?;
 
@@ -354,6 +399,436 @@ fn http_get_with_headers(
     Ok(HttpResponse { status, body })
 }
 
+#[test]
+fn http_project_create_reports_bad_presets_and_existing_manifests_by_status() {
+    let fixture = indexed_fixture();
+    let (_server, addr) = spawn_http_server(&fixture);
+    let post = |target: &str| {
+        http_request(&addr, "POST", target, &[("Host", &addr)])
+            .unwrap_or_else(|error| panic!("POST {target}: {error}"))
+    };
+
+    let bad_preset = post("/project/create?preset=cobol");
+    assert_eq!(bad_preset.status, 400, "{}", bad_preset.body);
+
+    let created = post("/project/create?preset=rust_workspace");
+    assert_eq!(created.status, 200, "{}", created.body);
+    assert!(
+        fixture
+            .workspace
+            .path()
+            .join("codestory_project.json")
+            .is_file()
+    );
+
+    let existing = post("/project/create?preset=rust_workspace");
+    assert_eq!(existing.status, 409, "{}", existing.body);
+    assert_eq!(
+        existing.body.pointer("/error/code").and_then(Value::as_str),
+        Some("already_exists"),
+        "{}",
+        existing.body
+    );
+}
+
+#[test]
+fn http_project_manifest_serves_the_last_run_and_reports_a_missing_manifest() {
+    let fixture = indexed_fixture();
+    let (_server, addr) = spawn_http_server(&fixture);
+
+    let manifest = get_json(&addr, "/project/manifest");
+    assert_eq!(manifest["mode"], "Full", "{manifest}");
+    assert!(
+        manifest["file_count"]
+            .as_u64()
+            .is_some_and(|count| count > 0),
+        "{manifest}"
+    );
+    assert_eq!(
+        manifest["settings_hash"].as_str().map(str::len),
+        Some(64),
+        "{manifest}"
+    );
+
+    fs::remove_file(
+        fixture
+            .cache_dir
+            .path()
+            .join("codestory.index_manifest.json"),
+    )
+    .expect("remove manifest");
+    let missing = http_get(&addr, "/project/manifest").expect("manifest response");
+    assert_eq!(missing.status, 404, "{}", missing.body);
+    assert_eq!(
+        missing.body["error"]["code"], "index_manifest_missing",
+        "{}",
+        missing.body
+    );
+}
+
+#[test]
+fn http_explorer_symbols_groups_matches_and_reports_bad_kinds_by_status() {
+    let fixture = indexed_fixture();
+    let (_server, addr) = spawn_http_server(&fixture);
+
+    let symbols = get_json(&addr, "/explorer/symbols?query=configure&kinds=function");
+    let groups = symbols["groups"]
+        .as_array()
+        .unwrap_or_else(|| panic!("symbols should carry groups: {symbols}"));
+    assert_eq!(groups.len(), 1, "{symbols}");
+    assert_eq!(groups[0]["kind"], "FUNCTION", "{symbols}");
+    assert_eq!(groups[0]["count"], 2, "{symbols}");
+    assert_eq!(symbols["total_count"], 2, "{symbols}");
+
+    let limited = get_json(&addr, "/explorer/symbols?query=configure&limit=1");
+    assert_eq!(
+        limited
+            .pointer("/groups/0/symbols")
+            .and_then(Value::as_array)
+            .map(Vec::len),
+        Some(1),
+        "{limited}"
+    );
+
+    let bad_kinds = http_get(&addr, "/explorer/symbols?kinds=function,bogus")
+        .expect("explorer symbols response");
+    assert_eq!(bad_kinds.status, 400, "{}", bad_kinds.body);
+    assert_eq!(
+        bad_kinds.body["error"]["code"], "invalid_kinds",
+        "{}",
+        bad_kinds.body
+    );
+}
+
+#[test]
+fn http_autocomplete_answers_storage_failures_with_a_json_error() {
+    let fixture = indexed_fixture();
+    let (_server, addr) = spawn_http_server(&fixture);
+    let suggestions = get_json(&addr, "/search/autocomplete?q=appcon");
+    assert!(
+        !public_result_array(&suggestions, "/search/autocomplete").is_empty(),
+        "{suggestions}"
+    );
+
+    remove_index_database(&fixture);
+    let failed = http_get(&addr, "/search/autocomplete?q=appcon")
+        .expect("autocomplete should answer instead of dropping the connection");
+    assert_eq!(failed.status, 500, "{}", failed.body);
+    assert!(failed.body["error"]["code"].is_string(), "{}", failed.body);
+}
+
+#[test]
+fn http_query_reports_bad_queries_and_storage_failures_as_json_errors() {
+    let fixture = indexed_fixture();
+    let (_server, addr) = spawn_http_server(&fixture);
+    let table = get_json(&addr, "/query?q=trail(symbol:+'step0')");
+    assert!(table["rows"].is_array(), "{table}");
+
+    let unclosed = http_get(&addr, "/query?q=trail(symbol:+'step0'").expect("unclosed query");
+    assert_eq!(unclosed.status, 400, "{}", unclosed.body);
+    assert_eq!(unclosed.body["error"]["code"], "invalid_query");
+
+    remove_index_database(&fixture);
+    let failed = http_get(&addr, "/query?q=trail(symbol:+'step0')")
+        .expect("query should answer instead of dropping the connection");
+    assert_eq!(failed.status, 500, "{}", failed.body);
+    assert!(failed.body["error"]["code"].is_string(), "{}", failed.body);
+}
+
+#[test]
+fn http_history_lists_runs_rebuilds_graphs_and_reports_errors_by_status() {
+    let fixture = indexed_fixture();
+    let (_server, addr) = spawn_http_server(&fixture);
+
+    let runs = get_json(&addr, "/history");
+    assert_nonempty_array(&runs, "/runs");
+    let run_id = runs
+        .pointer("/runs/0/run_id")
+        .and_then(Value::as_str)
+        .unwrap_or_else(|| panic!("runs should carry run ids: {runs}"))
+        .to_string();
+
+    let graph = get_json(&addr, &format!("/history?run={run_id}"));
+    assert_nonempty_array(&graph, "/node_ids");
+    let node_id = graph
+        .pointer("/node_ids/0")
+        .and_then(Value::as_str)
+        .expect("node id")
+        .to_string();
+    let history = get_json(&addr, &format!("/history?id={node_id}"));
+    assert!(
+        history.pointer("/events").is_some_and(Value::is_array),
+        "{history}"
+    );
+
+    for (target, status) in [
+        ("/history?run=unknown-run".to_string(), 404),
+        ("/history?id=not-a-node".to_string(), 400),
+        (format!("/history?id={node_id}&run={run_id}"), 400),
+    ] {
+        let response = http_get(&addr, &target).expect("history response");
+        assert_eq!(response.status, status, "{target}: {}", response.body);
+    }
+}
+
+#[test]
+fn http_markers_list_markers_and_report_bad_filters_by_status() {
+    let fixture = indexed_fixture();
+    let (_server, addr) = spawn_http_server(&fixture);
+
+    let markers = get_json(&addr, "/markers?tag=todo");
+    assert!(
+        markers.pointer("/markers").is_some_and(Value::is_array),
+        "{markers}"
+    );
+
+    for target in [
+        "/markers?tag=NOTE",
+        "/markers?min_age_days=9&max_age_days=1",
+    ] {
+        let response = http_get(&addr, target).expect("markers response");
+        assert_eq!(response.status, 400, "{target}: {}", response.body);
+        assert_eq!(
+            response.body["error"]["code"], "invalid_argument",
+            "{}",
+            response.body
+        );
+    }
+}
+
+#[test]
+fn http_literals_search_saved_literals_and_report_errors_by_status() {
+    let fixture = indexed_fixture();
+    let output = test_support::cli_command()
+        .args(["index", "--refresh", "full", "--string-literals", "on"])
+        .arg("--project")
+        .arg(fixture.workspace.path())
+        .arg("--cache-dir")
+        .arg(fixture.cache_dir.path())
+        .env("CODESTORY_EMBED_ALLOW_CPU", "1")
+        .output()
+        .expect("run index");
+    assert!(
+        output.status.success(),
+        "index failed\nstderr:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let (_server, addr) = spawn_http_server(&fixture);
+
+    let literals = get_json(&addr, "/literals?q=workspace");
+    assert_nonempty_array(&literals, "/matches");
+    assert_eq!(
+        literals.pointer("/matches/0/value").and_then(Value::as_str),
+        Some("workspace:{project_name}"),
+        "{literals}"
+    );
+
+    let response = http_get(&addr, "/literals?q=").expect("literals response");
+    assert_eq!(response.status, 400, "{}", response.body);
+    assert_eq!(
+        response.body["error"]["code"], "invalid_argument",
+        "{}",
+        response.body
+    );
+}
+
+#[test]
+fn http_trail_presets_save_list_apply_and_delete() {
+    let fixture = indexed_fixture();
+    let (_server, addr) = spawn_http_server(&fixture);
+    let post = |target: &str, body: Option<&str>| {
+        http_request_with_body(&addr, "POST", target, &[("Host", &addr)], body)
+            .unwrap_or_else(|error| panic!("POST {target}: {error}"))
+    };
+
+    let saved = post(
+        "/trail/presets",
+        Some(r#"{"name":"inherits","edge_kinds":["INHERITANCE"]}"#),
+    );
+    assert_eq!(saved.status, 200, "{}", saved.body);
+    let listed = get_json(&addr, "/trail/presets");
+    assert_eq!(
+        listed.pointer("/result/0/name").and_then(Value::as_str),
+        Some("inherits"),
+        "{listed}"
+    );
+
+    let trail = get_json(&addr, "/trail?q=step0&preset=inherits");
+    assert_eq!(trail["focus"]["display_name"], "step0");
+    assert!(
+        trail
+            .pointer("/trail/edges")
+            .and_then(Value::as_array)
+            .is_some_and(|edges| edges.iter().all(|edge| edge["kind"] != "CALL")),
+        "/trail?preset should keep only the preset's edge kinds: {trail}"
+    );
+
+    let mut png = TcpStream::connect(&addr).expect("connect render client");
+    write!(
+        png,
+        "GET /graph/render.png?q=step0&preset=inherits HTTP/1.1\r\nHost: {addr}\r\nConnection: close\r\n\r\n"
+    )
+    .expect("write render request");
+    let mut rendered = Vec::new();
+    png.read_to_end(&mut rendered)
+        .expect("read render response");
+    let head = String::from_utf8_lossy(&rendered[..rendered.len().min(256)]);
+    assert!(
+        head.starts_with("HTTP/1.1 200") && head.contains("image/png"),
+        "/graph/render.png?preset should render with the saved preset: {head}"
+    );
+    for route in ["/trail", "/graph/render.png"] {
+        let unknown = http_get(&addr, &format!("{route}?q=step0&preset=missing"))
+            .unwrap_or_else(|error| panic!("GET {route}: {error}"));
+        assert_eq!(unknown.status, 400, "{route}: {}", unknown.body);
+        assert!(
+            unknown.body["error"]["message"]
+                .as_str()
+                .is_some_and(|message| message.contains("missing")),
+            "{route} should name the unknown preset: {}",
+            unknown.body
+        );
+    }
+
+    let bad_body = post("/trail/presets", Some("not json"));
+    assert_eq!(bad_body.status, 400, "{}", bad_body.body);
+    let empty = post("/trail/presets", Some(r#"{"name":"empty"}"#));
+    assert_eq!(empty.status, 400, "{}", empty.body);
+    assert_eq!(
+        empty.body["error"]["code"], "invalid_argument",
+        "{}",
+        empty.body
+    );
+
+    let deleted = post("/trail/presets/delete?name=inherits", None);
+    assert_eq!(deleted.status, 200, "{}", deleted.body);
+    let missing = post("/trail/presets/delete?name=inherits", None);
+    assert_eq!(missing.status, 404, "{}", missing.body);
+    let listed = get_json(&addr, "/trail/presets");
+    assert!(
+        listed["result"].as_array().is_some_and(Vec::is_empty),
+        "{listed}"
+    );
+}
+
+#[test]
+fn http_trail_defaults_save_apply_and_reset() {
+    let fixture = indexed_fixture();
+    let (_server, addr) = spawn_http_server(&fixture);
+    let post = |target: &str, body: &str| {
+        http_request_with_body(&addr, "POST", target, &[("Host", &addr)], Some(body))
+            .unwrap_or_else(|error| panic!("POST {target}: {error}"))
+    };
+
+    let initial = get_json(&addr, "/trail/defaults");
+    assert_eq!(initial["max_nodes"], Value::Null, "{initial}");
+
+    let saved = post(
+        "/trail/defaults",
+        r#"{"max_nodes":10,"edge_filter":["INHERITANCE"]}"#,
+    );
+    assert_eq!(saved.status, 200, "{}", saved.body);
+    let loaded = get_json(&addr, "/trail/defaults");
+    assert_eq!(loaded["max_nodes"], 10, "{loaded}");
+    assert_eq!(loaded["edge_filter"][0], "INHERITANCE", "{loaded}");
+
+    let trail = get_json(&addr, "/trail?q=step0");
+    assert!(
+        trail
+            .pointer("/trail/edges")
+            .and_then(Value::as_array)
+            .is_some_and(|edges| edges.iter().all(|edge| edge["kind"] != "CALL")),
+        "/trail without filters should take the project's default edge filter: {trail}"
+    );
+
+    let invalid = post("/trail/defaults", r#"{"max_nodes":1}"#);
+    assert_eq!(invalid.status, 400, "{}", invalid.body);
+    assert_eq!(
+        invalid.body["error"]["code"], "invalid_argument",
+        "{}",
+        invalid.body
+    );
+    let bad_body = post("/trail/defaults", "not json");
+    assert_eq!(bad_body.status, 400, "{}", bad_body.body);
+
+    let reset = post("/trail/defaults", "{}");
+    assert_eq!(reset.status, 200, "{}", reset.body);
+    let trail = get_json(&addr, "/trail?q=step0");
+    assert!(
+        trail
+            .pointer("/trail/edges")
+            .and_then(Value::as_array)
+            .is_some_and(|edges| edges.iter().any(|edge| edge["kind"] == "CALL")),
+        "/trail should show calls again once the defaults are reset: {trail}"
+    );
+}
+
+#[test]
+fn http_review_reports_changed_symbols_and_errors_by_status() {
+    let fixture = indexed_fixture();
+    let (_server, addr) = spawn_http_server(&fixture);
+    let post = |target: &str, body: &str| {
+        http_request_with_body(&addr, "POST", target, &[("Host", &addr)], Some(body))
+            .unwrap_or_else(|error| panic!("POST {target}: {error}"))
+    };
+    let diff = "\
+--- a/src/runtime.rs
++++ b/src/runtime.rs
+@@ -2 +2 @@
+-    format!(\"workspace:{project_name}\")
++    format!(\"project:{project_name}\")
+";
+    let labels = |body: &Value, pointer: &str| {
+        body.pointer(pointer)
+            .and_then(Value::as_array)
+            .unwrap_or_else(|| panic!("{pointer} should be an array: {body}"))
+            .iter()
+            .filter_map(|symbol| symbol["display_name"].as_str().map(str::to_string))
+            .collect::<Vec<_>>()
+    };
+
+    let review = post("/review", diff);
+    assert_eq!(review.status, 200, "{}", review.body);
+    assert!(
+        labels(&review.body, "/changed_symbols")
+            .iter()
+            .any(|name| name.ends_with("normalize_project")),
+        "{}",
+        review.body
+    );
+    assert!(
+        labels(&review.body, "/callers")
+            .iter()
+            .any(|name| name.ends_with("open_project")),
+        "{}",
+        review.body
+    );
+
+    let reindexed = post("/review?reindex=1", diff);
+    assert_eq!(reindexed.status, 200, "{}", reindexed.body);
+    assert!(
+        reindexed
+            .body
+            .pointer("/reindexed_files")
+            .and_then(Value::as_array)
+            .is_some_and(|files| files.iter().any(|file| file == "src/runtime.rs")),
+        "{}",
+        reindexed.body
+    );
+
+    let not_a_diff = post("/review", "no hunks here");
+    assert_eq!(not_a_diff.status, 400, "{}", not_a_diff.body);
+    assert_eq!(
+        not_a_diff
+            .body
+            .pointer("/error/code")
+            .and_then(Value::as_str),
+        Some("invalid_argument"),
+        "{}",
+        not_a_diff.body
+    );
+}
+
 #[test]
 fn http_serve_rejects_non_loopback_host_and_origin_headers() {
     let fixture = indexed_fixture();
@@ -511,7 +986,7 @@ fn http_routes_and_stdio_tools_keep_aligned_default_contracts() {
     );
     let shared_browser_defaults = source_between(
         &http,
-        "const BROWSER_TRAIL_DEFAULT_DEPTH",
+        "const BROWSER_TRAIL_MAX_DEPTH",
         "fn handle_http_request",
     );
     let http_trail = route_arm(http_handler, "/trail", "_ =>");
@@ -549,11 +1024,14 @@ fn http_routes_and_stdio_tools_keep_aligned_default_contracts() {
         );
     }
 
+    let shared_trail_config =
+        source_between(&http, "fn browser_trail_config", "fn browser_trail_depth");
     assert!(
-        shared_browser_defaults.contains("BROWSER_TRAIL_DEFAULT_DEPTH: u32 = 2")
+        shared_trail_config.contains("caller_scope: None")
+            && shared_trail_config.contains("max_nodes: None")
             && http_trail.contains("browser_trail_depth(")
-            && stdio_trail.contains("BROWSER_TRAIL_DEFAULT_DEPTH"),
-        "HTTP and stdio trail should share the named default depth=2 contract"
+            && stdio_trail.contains("browser_trail_direction("),
+        "HTTP and stdio trail should leave unset fields to the project's trail defaults"
     );
     assert!(
         shared_browser_defaults.contains("BROWSER_TRAIL_MAX_DEPTH: u32 = 10")
@@ -562,10 +1040,9 @@ fn http_routes_and_stdio_tools_keep_aligned_default_contracts() {
         "HTTP /trail and stdio trail should share the named maximum depth=10 contract"
     );
     assert!(
-        shared_browser_defaults.contains("BROWSER_TRAIL_MAX_NODES: u32 = 80")
-            && http_trail.contains("browser_trail_config(")
+        http_trail.contains("browser_trail_config(")
             && stdio_trail.contains("browser_trail_config("),
-        "HTTP and stdio trail should share max_nodes=80 through the common helper"
+        "HTTP and stdio trail should share the common trail helper"
     );
 
     assert!(
@@ -584,10 +1061,13 @@ fn http_routes_and_stdio_tools_keep_aligned_default_contracts() {
         "shared references helper should preserve incoming direction"
     );
     assert!(
-        shared_browser_defaults.contains("BROWSER_REFERENCES_MAX_NODES: u32 = 120")
-            && http_references.contains("browser_references_config(")
-            && stdio_references.contains("browser_references_config("),
-        "HTTP and stdio references should share max_nodes=120 through the common helper"
+        source_between(
+            &http,
+            "fn browser_references_config",
+            "fn browser_trail_config"
+        )
+        .contains("max_nodes: None"),
+        "shared references helper should take its node budget from the project's trail defaults"
     );
 
     assert!(
@@ -760,6 +1240,41 @@ fn http_smoke_keeps_existing_routes_and_default_semantics_against_indexed_repo()
                 .is_string(),
         "/symbols child success must retain its result and complete publication identity: {child_symbols}"
     );
+    let child_count = public_result_array(&child_symbols, "/symbols?parent_id").len() as u64;
+    let child_page = get_json(
+        &addr,
+        &format!("/symbols/children?parent_id={app_controller_id}&offset=1&limit=1"),
+    );
+    assert_eq!(child_page["total"], child_count, "{child_page}");
+    assert_eq!(child_page["offset"], 1, "{child_page}");
+    assert_eq!(
+        child_page["items"].as_array().map(Vec::len),
+        Some(1),
+        "/symbols/children should return only the requested page: {child_page}"
+    );
+    assert_eq!(
+        child_page["items"][0]["id"],
+        public_result_array(&child_symbols, "/symbols?parent_id")[1]["id"],
+        "/symbols/children pages should follow the /symbols child order: {child_page}"
+    );
+    let occurrences = get_json(
+        &addr,
+        &format!("/occurrences?id={app_controller_id}&kind=definition&limit=5"),
+    );
+    assert!(
+        occurrences["total"]
+            .as_u64()
+            .is_some_and(|total| total >= 1)
+            && occurrences["items"][0]["kind"] == "definition",
+        "/occurrences should page the focus's definition occurrences: {occurrences}"
+    );
+    let missing_id = http_get(&addr, "/occurrences").expect("occurrences without id");
+    assert_eq!(missing_id.status, 400, "{}", missing_id.body);
+    let errors = get_json(&addr, "/index/errors?fatal_only=true&limit=10");
+    assert!(
+        errors["total"].is_u64() && errors["items"].is_array(),
+        "/index/errors should return a page of index errors: {errors}"
+    );
 
     let references = get_json(&addr, &format!("/references?id={step10_id}"));
     assert!(
@@ -834,11 +1349,131 @@ fn http_smoke_keeps_existing_routes_and_default_semantics_against_indexed_repo()
         "/trail depth=99 should be clamped to the stdio maximum depth: {trail}"
     );
 
+    let step1_id = symbol_id_by_label(&symbols["result"], "step1");
+    let hidden_trail = get_json(
+        &addr,
+        &format!("/trail?id={step0_id}&depth=2&hide={step1_id}"),
+    );
+    let hidden_labels = graph_node_labels(&hidden_trail, "/trail/nodes");
+    assert!(
+        graph_node_labels(&default_trail, "/trail/nodes").contains(&"step1")
+            && hidden_labels.contains(&"step0")
+            && !hidden_labels.contains(&"step1"),
+        "/trail should drop nodes named by the hide parameter: {hidden_trail}"
+    );
+
     let one_symbol = get_json(&addr, "/symbols?limit=1");
     assert_eq!(
         public_result_array(&one_symbol, "/symbols?limit=1").len(),
         1,
         "/symbols should honor an explicit bounded root limit: {one_symbol}"
     );
+
+    let suggestions = get_json(&addr, "/search/autocomplete?q=appcon&limit=5");
+    let suggestion_items = public_result_array(&suggestions, "/search/autocomplete");
+    assert!(
+        (1..=5).contains(&suggestion_items.len())
+            && suggestion_items[0]["label"] == "AppController"
+            && suggestion_items[0]["kind"].is_string(),
+        "/search/autocomplete should suggest symbols by name prefix with their kinds: {suggestions}"
+    );
 }
+
+#[test]
+fn http_serve_keeps_answering_while_trails_run_concurrently() {
+    let fixture = indexed_fixture();
+    let (_server, addr) = spawn_http_server(&fixture);
+
+    // Three requests that never finish their headers hold three of the four
+    // workers until the two second read timeout.
+    let stalled = (0..3)
+        .map(|_| {
+            let mut stream = TcpStream::connect(&addr).expect("connect stalled client");
+            write!(stream, "GET /trail?q=step0 HTTP/1.1\r\n").expect("write partial request");
+            stream
+        })
+        .collect::<Vec<_>>();
+    let started = Instant::now();
+    let health = http_get(&addr, "/health").expect("health response behind stalled requests");
+    let stalled_latency = started.elapsed();
+    assert_eq!(health.status, 200);
+    assert!(
+        stalled_latency < Duration::from_millis(1500),
+        "/health waited {stalled_latency:?} behind stalled requests"
+    );
+    drop(stalled);
+
+    let trails = (0..8)
+        .map(|_| {
+            let addr = addr.clone();
+            thread::spawn(move || {
+                let started = Instant::now();
+                let response = http_get(&addr, "/trail?q=step0&depth=10").expect("trail response");
+                (response, started.elapsed())
+            })
+        })
+        .collect::<Vec<_>>();
+    let started = Instant::now();
+    let health = http_get(&addr, "/health").expect("health response");
+    let health_latency = started.elapsed();
+    assert_eq!(health.status, 200);
+    let mut answered = 0;
+    for trail in trails {
+        let (response, latency) = trail.join().expect("trail client thread");
+        // Trails past `--max-trails` are turned away instead of queued.
+        match response.status {
+            200 => answered += 1,
+            503 => assert_eq!(response.body["error"]["code"], "trails_busy"),
+            status => panic!("concurrent /trail answered {status}: {}", response.body),
+        }
+        assert!(
+            latency < Duration::from_secs(30),
+            "concurrent /trail request took {latency:?}"
+        );
+    }
+    assert!(
+        health_latency < Duration::from_secs(5),
+        "/health waited {health_latency:?} behind concurrent trails"
+    );
+    assert!(
+        answered > 0,
+        "every concurrent /trail request was turned away"
+    );
+}
+
+#[test]
+fn http_serve_turns_away_connections_past_the_wait_queue() {
+    let fixture = indexed_fixture();
+    let (_server, addr) =
+        spawn_http_server_with_args(&fixture, &["--workers", "1", "--max-queued", "0"]);
+
+    // A request that never finishes its headers holds the only worker.
+    let mut stalled = TcpStream::connect(&addr).expect("connect stalled client");
+    write!(stalled, "GET /health HTTP/1.1\r\n").expect("write partial request");
+    let started = Instant::now();
+    let busy = http_get(&addr, "/health").expect("busy response");
+    let busy_latency = started.elapsed();
+    assert_eq!(busy.status, 503, "{}", busy.body);
+    assert_eq!(busy.body["error"]["code"], "server_busy");
+    assert!(
+        busy_latency < Duration::from_millis(1500),
+        "the busy answer waited {busy_latency:?} behind the stalled request"
+    );
+    drop(stalled);
+
+    let deadline = Instant::now() + Duration::from_secs(5);
+    loop {
+        let health = http_get(&addr, "/health").expect("health response");
+        if health.status == 200 {
+            break;
+        }
+        assert_eq!(health.body["error"]["code"], "server_busy");
+        assert!(
+            Instant::now() < deadline,
+            "/health stayed busy after the stalled request closed"
+        );
+        thread::sleep(Duration::from_millis(50));
+    }
+}
+
 mod test_support;
@@ -277,6 +277,279 @@ fn symbol_query_file_filter_resolves_expected_fixture() {
     );
 }
 
+#[test]
+#[ignore = "builds indexed runtime fixtures; run explicitly when touching CLI/runtime read-command flows"]
+fn literals_command_finds_literals_while_the_saved_setting_is_on() {
+    let workspace = tempdir().expect("create literals workspace");
+    fs::write(
+        workspace.path().join("lib.rs"),
+        "pub const RETRY_LIMIT: u32 = 7;\n\npub fn connect() -> &'static str {\n    \"connection refused by peer\"\n}\n",
+    )
+    .expect("write literals fixture");
+    let literals = |query: &str| {
+        let output = run_cli(
+            workspace.path(),
+            &["literals", "--query", query, "--format", "json"],
+        );
+        assert!(
+            output.status.success(),
+            "literals command failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        let json: Value = serde_json::from_slice(&output.stdout).expect("parse literals json");
+        json["matches"]
+            .as_array()
+            .unwrap_or_else(|| panic!("matches should be an array: {json}"))
+            .iter()
+            .filter_map(|found| found["value"].as_str().map(str::to_string))
+            .collect::<Vec<_>>()
+    };
+
+    index_workspace(workspace.path());
+    assert!(literals("connection refused").is_empty());
+
+    let index = run_cli(
+        workspace.path(),
+        &[
+            "index",
+            "--refresh",
+            "full",
+            "--string-literals",
+            "on",
+            "--format",
+            "json",
+        ],
+    );
+    assert!(
+        index.status.success(),
+        "index command failed: {}",
+        String::from_utf8_lossy(&index.stderr)
+    );
+    assert_eq!(
+        literals("connection refused"),
+        vec!["connection refused by peer"]
+    );
+    index_workspace(workspace.path());
+    assert_eq!(
+        literals("RETRY_LIMIT"),
+        vec!["7"],
+        "a later run without the flag keeps the saved setting"
+    );
+
+    let index = run_cli(
+        workspace.path(),
+        &[
+            "index",
+            "--refresh",
+            "full",
+            "--string-literals",
+            "off",
+            "--format",
+            "json",
+        ],
+    );
+    assert!(index.status.success());
+    assert!(literals("connection refused").is_empty());
+}
+
+#[test]
+#[ignore = "builds indexed runtime fixtures; run explicitly when touching CLI/runtime read-command flows"]
+fn trail_preset_commands_save_list_and_delete_presets() {
+    let workspace = tempdir().expect("create preset workspace");
+    fs::write(
+        workspace.path().join("lib.rs"),
+        "pub fn caller() {\n    callee();\n}\n\npub fn callee() {}\n",
+    )
+    .expect("write preset fixture");
+    index_workspace(workspace.path());
+    let presets = |args: &[&str]| {
+        let output = run_cli(workspace.path(), args);
+        assert!(
+            output.status.success(),
+            "trail-preset command failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        let json: Value = serde_json::from_slice(&output.stdout).expect("parse preset json");
+        json
+    };
+
+    let saved = presets(&[
+        "trail-preset",
+        "save",
+        "calls",
+        "--edge-kind",
+        "call",
+        "--node-kind",
+        "function",
+        "--format",
+        "json",
+    ]);
+    assert_eq!(saved["presets"][0]["name"], "calls", "{saved}");
+    assert_eq!(saved["presets"][0]["edge_kinds"][0], "CALL", "{saved}");
+
+    let trail = run_cli(
+        workspace.path(),
+        &[
+            "trail",
+            "--query",
+            "caller",
+            "--filter-preset",
+            "calls",
+            "--format",
+            "json",
+        ],
+    );
+    assert!(
+        trail.status.success(),
+        "trail with a saved preset failed: {}",
+        String::from_utf8_lossy(&trail.stderr)
+    );
+
+    let deleted = presets(&["trail-preset", "delete", "calls", "--format", "json"]);
+    assert_eq!(deleted["deleted"], "calls");
+    let listed = presets(&["trail-preset", "list", "--format", "json"]);
+    assert!(
+        listed["presets"].as_array().is_some_and(Vec::is_empty),
+        "{listed}"
+    );
+    let missing = run_cli(workspace.path(), &["trail-preset", "delete", "calls"]);
+    assert!(!missing.status.success());
+}
+
+#[test]
+#[ignore = "builds indexed runtime fixtures; run explicitly when touching CLI/runtime read-command flows"]
+fn trail_defaults_commands_set_show_and_apply_to_trails() {
+    let workspace = tempdir().expect("create defaults workspace");
+    fs::write(
+        workspace.path().join("lib.rs"),
+        "pub fn caller() {\n    callee();\n}\n\npub fn callee() {}\n",
+    )
+    .expect("write defaults fixture");
+    index_workspace(workspace.path());
+    let defaults = |args: &[&str]| {
+        let output = run_cli(workspace.path(), args);
+        assert!(
+            output.status.success(),
+            "trail-defaults command failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        let json: Value = serde_json::from_slice(&output.stdout).expect("parse defaults json");
+        json
+    };
+
+    let saved = defaults(&[
+        "trail-defaults",
+        "set",
+        "--max-nodes",
+        "10",
+        "--edge-kind",
+        "inheritance",
+        "--format",
+        "json",
+    ]);
+    assert_eq!(saved["max_nodes"], 10, "{saved}");
+    let shown = defaults(&["trail-defaults", "show", "--format", "json"]);
+    assert_eq!(shown["edge_filter"][0], "INHERITANCE", "{shown}");
+
+    let trail = run_cli(
+        workspace.path(),
+        &["trail", "--query", "caller", "--format", "json"],
+    );
+    assert!(
+        trail.status.success(),
+        "trail with project defaults failed: {}",
+        String::from_utf8_lossy(&trail.stderr)
+    );
+    let trail: Value = serde_json::from_slice(&trail.stdout).expect("parse trail json");
+    assert!(
+        trail
+            .pointer("/trail/trail/edges")
+            .and_then(Value::as_array)
+            .is_some_and(|edges| edges.iter().all(|edge| edge["kind"] != "CALL")),
+        "a trail without --edge flags should take the default edge filter: {trail}"
+    );
+
+    let reset = defaults(&["trail-defaults", "set", "--format", "json"]);
+    assert_eq!(reset["edge_filter"], serde_json::json!([]), "{reset}");
+}
+
+#[test]
+#[ignore = "builds indexed runtime fixtures; run explicitly when touching CLI/runtime read-command flows"]
+fn review_command_reports_changed_symbols_and_callers() {
+    let workspace = tempdir().expect("create review workspace");
+    fs::write(
+        workspace.path().join("lib.rs"),
+        "pub fn helper() -> u32 {\n    1\n}\n\npub fn caller() -> u32 {\n    helper()\n}\n",
+    )
+    .expect("write review fixture");
+    index_workspace(workspace.path());
+    let diff_dir = tempdir().expect("create diff dir");
+    let diff_path = diff_dir.path().join("change.diff");
+    fs::write(
+        &diff_path,
+        "--- a/lib.rs\n+++ b/lib.rs\n@@ -2 +2 @@\n-    1\n+    2\n",
+    )
+    .expect("write diff");
+
+    let review = run_cli(
+        workspace.path(),
+        &[
+            "review",
+            "--diff",
+            diff_path.to_str().expect("utf-8 diff path"),
+            "--format",
+            "json",
+        ],
+    );
+    assert!(
+        review.status.success(),
+        "review command failed: {}",
+        String::from_utf8_lossy(&review.stderr)
+    );
+    let json: Value = serde_json::from_slice(&review.stdout).expect("parse review json");
+    let names = |key: &str| {
+        json[key]
+            .as_array()
+            .unwrap_or_else(|| panic!("{key} should be an array: {json}"))
+            .iter()
+            .filter_map(|symbol| symbol["display_name"].as_str())
+            .collect::<Vec<_>>()
+    };
+    assert!(
+        names("changed_symbols")
+            .iter()
+            .any(|name| name.ends_with("helper")),
+        "{json}"
+    );
+    assert!(
+        names("callers").iter().any(|name| name.ends_with("caller")),
+        "{json}"
+    );
+
+    let unchecked_head = run_cli(
+        workspace.path(),
+        &[
+            "review",
+            "--base",
+            "HEAD~1",
+            "--head",
+            "HEAD",
+            "--reindex",
+            "--format",
+            "json",
+        ],
+    );
+    assert!(
+        !unchecked_head.status.success(),
+        "--reindex with --head outside a git checkout should fail"
+    );
+    assert!(
+        String::from_utf8_lossy(&unchecked_head.stderr).contains("does not name a commit"),
+        "{}",
+        String::from_utf8_lossy(&unchecked_head.stderr)
+    );
+}
+
 #[test]
 #[ignore = "builds indexed runtime fixtures; run explicitly when touching CLI/runtime read-command flows"]
 fn query_command_runs_search_filter_limit_pipeline() {
@@ -1442,6 +1442,7 @@ fn tool_catalog_keeps_stable_product_tool_names() {
             "symbols",
             "trace",
             "trail",
+            "trail_presets",
         ],
         "stdio product tool names should stay stable: {tools}"
     );
@@ -2754,6 +2755,81 @@ fn files_tool_lists_indexed_files_without_sidecars() {
     );
 }
 
+#[test]
+fn trail_presets_tool_saves_lists_and_deletes_presets() {
+    let fixture = indexed_fixture();
+    let mut server = spawn_stdio_server(&fixture);
+    let call = |id: &str, arguments: Value| {
+        json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": "tools/call",
+            "params": {"name": "trail_presets", "arguments": arguments}
+        })
+    };
+
+    let saved = send_json(
+        &mut server,
+        call(
+            "presets-save",
+            json!({
+                "action": "save",
+                "name": "calls",
+                "edge_kinds": ["call"],
+                "node_kinds": ["FUNCTION", "method"]
+            }),
+        ),
+    );
+    let result = assert_tool_success(&saved, json!("presets-save"));
+    assert_eq!(
+        result["presets"],
+        json!([{
+            "name": "calls",
+            "edge_kinds": ["CALL"],
+            "node_kinds": ["FUNCTION", "METHOD"]
+        }]),
+        "saving should return every saved preset: {result}"
+    );
+
+    let listed = send_json(&mut server, call("presets-list", json!({})));
+    let result = assert_tool_success(&listed, json!("presets-list"));
+    assert_eq!(result["presets"][0]["name"], json!("calls"));
+
+    let bad_kind = send_json(
+        &mut server,
+        call(
+            "presets-bad-kind",
+            json!({"action": "save", "name": "bad", "edge_kinds": ["teleport"]}),
+        ),
+    );
+    assert_eq!(
+        assert_tool_error(&bad_kind, json!("presets-bad-kind"))["code"],
+        json!("invalid_argument")
+    );
+
+    let deleted = send_json(
+        &mut server,
+        call(
+            "presets-delete",
+            json!({"action": "delete", "name": "calls"}),
+        ),
+    );
+    let result = assert_tool_success(&deleted, json!("presets-delete"));
+    assert_eq!(result["presets"], json!([]));
+
+    let missing = send_json(
+        &mut server,
+        call(
+            "presets-delete-missing",
+            json!({"action": "delete", "name": "calls"}),
+        ),
+    );
+    assert_eq!(
+        assert_tool_error(&missing, json!("presets-delete-missing"))["code"],
+        json!("not_found")
+    );
+}
+
 #[test]
 fn affected_tool_maps_preferred_paths_without_sidecars() {
     let fixture = indexed_fixture();
@@ -17,44 +17,58 @@ pub use dto::{
     AffectedAnalysisInput, AffectedAnalysisRequest, AffectedChangeKindDto, AffectedChangeRecordDto,
     AffectedFollowUpDto, AffectedFollowUpInvocationDto, AffectedInputClassificationDto,
     AffectedMatchedFileDto, AffectedRouteDto, AffectedSymbolDto, AffectedTestFileDto,
-    AffectedUncoveredInputDto, AffectedUnmatchedPathDto, AgentAnswerDto, AgentAskRequest,
-    AgentCitationDto, AgentCustomRetrievalConfigDto, AgentHybridWeightsDto, AgentPacketDto,
-    AgentPacketRequestDto, AgentResponseBlockDto, AgentResponseModeDto, AgentResponseSectionDto,
-    AgentRetrievalPolicyModeDto, AgentRetrievalPresetDto, AgentRetrievalProfileSelectionDto,
-    AgentRetrievalStepDto, AgentRetrievalStepKindDto, AgentRetrievalStepStatusDto,
-    AgentRetrievalSummaryFieldDto, AgentRetrievalTraceDto, BookmarkCategoryDto, BookmarkDto,
-    CanonicalEdgeDto, CanonicalEdgeFamily, CanonicalLayoutDto, CanonicalMemberDto,
-    CanonicalMemberVisibility, CanonicalNodeDto, CanonicalNodeStyle, CanonicalRouteKind,
-    ClaimReadinessDto, CreateBookmarkCategoryRequest, CreateBookmarkRequest,
-    EMBEDDING_VECTOR_PRODUCER_EVIDENCE_VERSION, EdgeOccurrencesRequest, EmbeddingEngineIdentityDto,
+    AffectedUncoveredInputDto, AffectedUnmatchedPathDto, AgentAnswerDto, AgentAskIntentDto,
+    AgentAskRequest, AgentCitationDto, AgentCustomRetrievalConfigDto, AgentHybridWeightsDto,
+    AgentPacketDto, AgentPacketRequestDto, AgentResponseBlockDto, AgentResponseModeDto,
+    AgentResponseSectionDto, AgentRetrievalPolicyModeDto, AgentRetrievalPresetDto,
+    AgentRetrievalProfileSelectionDto, AgentRetrievalStepDto, AgentRetrievalStepKindDto,
+    AgentRetrievalStepStatusDto, AgentRetrievalSummaryFieldDto, AgentRetrievalTraceDto,
+    AutocompleteSuggestionDto, BookmarkCategoryDto, BookmarkDto, BookmarkHealthDto,
+    BookmarkHealthEntryDto, BookmarkHealthStatusDto, CanonicalEdgeDto, CanonicalEdgeFamily,
+    CanonicalLayoutDto, CanonicalMemberDto, CanonicalMemberVisibility, CanonicalNodeDto,
+    CanonicalNodeStyle, CanonicalRouteKind, ClaimReadinessDto, CodeMarkerDto, CodeMarkersDto,
+    CodeMarkersRequest, CreateBookmarkCategoryRequest, CreateBookmarkRequest, CreateProjectRequest,
+    DEFAULT_PAGE_LIMIT, EMBEDDING_VECTOR_PRODUCER_EVIDENCE_VERSION, ENTRY_POINT_BOOKMARK_CATEGORY,
+    EdgeKindCountDto, EdgeOccurrencesRequest, EdgeResolutionDto, EmbeddingEngineIdentityDto,
     EmbeddingExecutionEvidenceDto, EmbeddingModelIdentityDto, EmbeddingProducerIdentityDto,
     EmbeddingProfileContractDto, EmbeddingVectorEvidenceCompatibilityDto,
     EmbeddingVectorEvidenceMigrationDispositionDto, EmbeddingVectorProducerEvidenceDto,
-    EmbeddingVectorPublicationIdentityDto, EmbeddingVectorSemanticsDto, FileCoverageDiagnosticDto,
-    FrameworkRouteCoverageDto, GraphArtifactDto, GraphEdgeDto, GraphNodeDto, GraphRequest,
-    GraphResponse, GroundingBudgetDto, GroundingCoverageBucketDto, GroundingCoverageDto,
-    GroundingFileDigestDto, GroundingOrientationConfidenceDto, GroundingOrientationDto,
-    GroundingOrientationUncertaintyDto, GroundingSnapshotDto, GroundingSymbolDigestDto,
-    IndexDryRunDto, IndexFreshnessChangeKindDto, IndexFreshnessDto, IndexFreshnessSampleDto,
-    IndexFreshnessStatusDto, IndexPublicationDto, IndexPublicationModeDto, IndexedFileDto,
+    EmbeddingVectorPublicationIdentityDto, EmbeddingVectorSemanticsDto, EntryPointDto,
+    EntryPointOverviewDto, ExplorerSymbolDto, ExplorerSymbolGroupDto, ExplorerSymbolsDto,
+    ExplorerSymbolsRequest, FileCoverageDiagnosticDto, FileStatsDto, FileStatsRequest,
+    FrameworkRouteCoverageDto, GraphArtifactDto, GraphAtDto, GraphAtRequest, GraphCompareDto,
+    GraphCompareEdgeDto, GraphCompareNodeDto, GraphCompareSide, GraphEdgeDto, GraphExpandRequest,
+    GraphNodeDto, GraphRenderRequest, GraphRenderTheme, GraphRequest, GraphResponse,
+    GraphSessionDto, GraphSessionUpdateRequest, GroundingBudgetDto, GroundingCoverageBucketDto,
+    GroundingCoverageDto, GroundingFileDigestDto, GroundingOrientationConfidenceDto,
+    GroundingOrientationDto, GroundingOrientationUncertaintyDto, GroundingSnapshotDto,
+    GroundingSymbolDigestDto, IndexDryRunDto, IndexErrorDto, IndexFreshnessChangeKindDto,
+    IndexFreshnessDto, IndexFreshnessSampleDto, IndexFreshnessStatusDto, IndexHistoryEventDto,
+    IndexHistoryRunDto, IndexHistoryRunsDto, IndexHistorySubjectDto, IndexManifestDto,
+    IndexPublicationDto, IndexPublicationModeDto, IndexSnapshotDto, IndexedFileDto,
     IndexedFileIncompleteReasonCountDto, IndexedFileLanguageCountDto, IndexedFileRoleDto,
-    IndexedFilesDto, IndexedFilesRequest, IndexedFilesSummaryDto, ListChildrenSymbolsRequest,
-    ListRootSymbolsRequest, NodeDetailsDto, NodeDetailsRequest, NodeOccurrencesRequest,
-    OpenContainingFolderRequest, OpenDefinitionRequest, OpenProjectRequest,
-    PACKET_PROBE_CONTRACT_VERSION, PACKET_PROBE_MAX_COUNT, PACKET_PROBE_MAX_TEXT_LENGTH,
-    PacketBudgetDto, PacketBudgetLimitsDto, PacketBudgetModeDto, PacketBudgetUsageDto,
-    PacketClaimDto, PacketCoverageReportDto, PacketEvidenceResolutionDto, PacketEvidenceTierDto,
-    PacketPlanDto, PacketPlanQueryDto, PacketProbeAmbiguityCandidateDto, PacketProbeDto,
-    PacketProbeRejectionCodeDto, PacketProbeRejectionDto, PacketProbeResolutionDto,
+    IndexedFilesDto, IndexedFilesRequest, IndexedFilesSummaryDto, ListBookmarksRequest,
+    ListChildrenSymbolsRequest, ListIndexErrorsRequest, ListRootSymbolsRequest, MAX_PAGE_LIMIT,
+    MergeNodesRequest, NodeAliasDto, NodeBadgesDto, NodeDefinitionDto, NodeDetailsDto,
+    NodeDetailsRequest, NodeEdgeCountsDto, NodeHistoryDto, NodeHistoryRequest, NodeKindCountDto,
+    NodeOccurrencesRequest, NodePreviewDto, OpenContainingFolderRequest, OpenDefinitionRequest,
+    OpenProjectRequest, PACKET_PROBE_CONTRACT_VERSION, PACKET_PROBE_MAX_COUNT,
+    PACKET_PROBE_MAX_TEXT_LENGTH, PacketBudgetDto, PacketBudgetLimitsDto, PacketBudgetModeDto,
+    PacketBudgetUsageDto, PacketClaimDto, PacketCoverageReportDto, PacketEvidenceResolutionDto,
+    PacketEvidenceTierDto, PacketPlanDto, PacketPlanQueryDto, PacketProbeAmbiguityCandidateDto,
+    PacketProbeDto, PacketProbeRejectionCodeDto, PacketProbeRejectionDto, PacketProbeResolutionDto,
     PacketProbeResolutionStatusDto, PacketProofStatusDto, PacketRetrievalTraceSummaryDto,
     PacketSidecarQueryDiagnosticDto, PacketSufficiencyDto, PacketSufficiencyStatusDto,
-    PacketTaskClassDto, ProjectSummary, ReadFileTextRequest, ReadFileTextResponse,
+    PacketTaskClassDto, PageDto, ProjectPresetDto, ProjectSummary, ReachableFileCountDto,
+    ReachableSetDto, ReachableSetRequest, ReadFileTextRequest, ReadFileTextResponse,
     ReadinessGoalDto, ReadinessIndexSnapshotDto, ReadinessSetupSnapshotDto,
     ReadinessSidecarSnapshotDto, ReadinessStatusDto, ReadinessVerdictDto, RepoTextScanStatsDto,
     RetrievalCandidateResolutionCountDto, RetrievalCandidateSummaryDto, RetrievalFallbackReasonDto,
     RetrievalModeDto, RetrievalScoreBreakdownDto, RetrievalShadowDto, RetrievalStageTimingDto,
-    RetrievalStateDto, RouteEndpointHandlerDto, RouteEndpointKindDto, RouteEndpointMetadataDto,
-    SearchHit, SearchHitOrigin, SearchHybridLimitsDto, SearchMatchQualityDto,
+    RetrievalStateDto, ReviewChangedFileDto, ReviewGraphDto, ReviewGraphRequest,
+    ReviewLineRangeDto, ReviewSymbolDto, RouteEndpointHandlerDto, RouteEndpointKindDto,
+    RouteEndpointMetadataDto, SearchHighlightDto, SearchHit, SearchHitOrigin,
+    SearchHybridLimitsDto, SearchMatchFieldDto, SearchMatchQualityDto, SearchMatchRangeDto,
     SearchPlanAnchorGroupDto, SearchPlanBridgeConfidenceDto, SearchPlanBridgeDto,
     SearchPlanBridgeEvidenceKindDto, SearchPlanBridgeStatusDto, SearchPlanCandidateWindowDto,
     SearchPlanChannelDto, SearchPlanDroppedTermDto, SearchPlanDto, SearchPlanNextActionDto,
@@ -62,12 +76,14 @@ pub use dto::{
     SearchPlanTermsDto, SearchQueryAssessmentDto, SearchRepoTextMode, SearchRequest,
     SearchResultsDto, SearchVerificationTargetDto, SemanticFallbackRecordDto, SemanticModeDto,
     SetUiLayoutRequest, SnippetContextDto, SnippetScopeDto, SourceOccurrenceDto,
-    SourcePolicyExclusionDto, StartIndexingRequest, StorageStatsDto, StoredSemanticDocsContractDto,
-    SummaryGenerationDto, SymbolContextDto, SymbolSummaryDto, SystemActionResponse, TrailConfigDto,
-    TrailContextDto, TrailFilterOptionsDto, TrailStoryDto, TrailStoryStepDto,
-    UpdateBookmarkCategoryRequest, UpdateBookmarkRequest, WorkspaceMemberIndexDto,
-    WriteFileDataUrlRequest, WriteFileResponse, WriteFileTextRequest, validate_packet_probe,
-    validate_packet_probe_request,
+    SourcePolicyExclusionDto, StartIndexingRequest, StorageStatsDto, StorageTableStatsDto,
+    StoredSemanticDocsContractDto, StringLiteralMatchDto, StringLiteralSearchDto,
+    StringLiteralSearchRequest, SummaryGenerationDto, SymbolContextDto, SymbolSummaryDto,
+    SystemActionResponse, TRAIL_DEFAULT_MAX_NODES, TestLinkDto, TrailConfigDto, TrailContextDto,
+    TrailDefaultsDto, TrailFilterOptionsDto, TrailFilterPresetDto, TrailPathDto, TrailStoryDto,
+    TrailStoryStepDto, UpdateBookmarkCategoryRequest, UpdateBookmarkRequest,
+    WorkspaceMemberIndexDto, WriteFileDataUrlRequest, WriteFileResponse, WriteFileTextRequest,
+    validate_packet_probe, validate_packet_probe_request,
 };
 pub use errors::{
     ApiError, ApiErrorDetails, COMMAND_FAILURE_SCHEMA_VERSION, CommandFailureEnvelope,
@@ -75,11 +91,11 @@ pub use errors::{
 };
 pub use events::{
     AppEventPayload, ArtifactCacheAccessTimings, ArtifactCachePolicyDto, CorePromotionTimings,
-    DatabaseSnapshotCopyTimings, FullRefreshWallTimings, IndexingPhaseTimings,
-    ProjectionPersistenceFamilyTimings, ProjectionPersistenceTimings,
+    DatabaseSnapshotCopyTimings, FileParseTiming, FullRefreshWallTimings, IndexingPhaseTimings,
+    IndexingRunDelta, ProjectionPersistenceFamilyTimings, ProjectionPersistenceTimings,
 };
 pub use ids::{EdgeId, NodeId};
 pub use types::{
-    EdgeKind, IndexMode, LayoutDirection, MemberAccess, NodeKind, TrailCallerScope, TrailDirection,
-    TrailMode,
+    EdgeKind, IndexMode, IndexingPriority, LayoutDirection, MemberAccess, NodeKind,
+    TrailCallerScope, TrailDirection, TrailEdgeWeight, TrailMode,
 };
@@ -1,7 +1,7 @@
 use super::ids::{EdgeId, NodeId};
 use super::types::{
-    EdgeKind, IndexMode, LayoutDirection, MemberAccess, NodeKind, TrailCallerScope, TrailDirection,
-    TrailMode,
+    EdgeKind, IndexMode, IndexingPriority, LayoutDirection, MemberAccess, NodeKind,
+    TrailCallerScope, TrailDirection, TrailEdgeWeight, TrailMode,
 };
 use crate::graph::FileCoverageReason;
 use serde::{Deserialize, Serialize};
@@ -14,6 +14,24 @@ pub struct OpenProjectRequest {
     pub path: String,
 }
 
+/// Starting source-group layout for a newly created project.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ProjectPresetDto {
+    RustWorkspace,
+    NodeMonorepo,
+    CxxCmake,
+    PythonPackage,
+}
+
+/// Request to write a project manifest from a preset and open the project.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CreateProjectRequest {
+    /// Filesystem path supplied by the caller; producers normalize it before use.
+    pub path: String,
+    pub preset: ProjectPresetDto,
+}
+
 /// Grounding output budget requested by callers.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, Default)]
 #[serde(rename_all = "snake_case")]
@@ -37,6 +55,27 @@ pub struct StorageStatsDto {
     pub error_count: u32,
     #[serde(default)]
     pub fatal_error_count: u32,
+    #[serde(default)]
+    pub occurrence_count: u32,
+    /// Size of the main database file.
+    #[serde(default)]
+    pub database_bytes: u64,
+    /// When the current complete index was published, if it has been.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_indexed_at_epoch_ms: Option<i64>,
+    /// Per-table usage, largest first. Only detailed storage stats fill it.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tables: Vec<StorageTableStatsDto>,
+}
+
+/// Rows and disk use of one storage table.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct StorageTableStatsDto {
+    pub name: String,
+    pub row_count: u32,
+    /// Bytes held by the table and its indexes; absent when the SQLite build
+    /// cannot measure them.
+    pub bytes: Option<u64>,
 }
 
 /// Project-level summary returned after opening or indexing a workspace.
@@ -52,6 +91,10 @@ pub struct ProjectSummary {
     pub freshness: Option<IndexFreshnessDto>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub publication: Option<IndexPublicationDto>,
+    /// Storage write generation; it changes whenever indexed graph data
+    /// changes, so clients can drop caches keyed by an older value.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage_generation: Option<u64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
@@ -63,6 +106,33 @@ pub struct IndexPublicationDto {
     pub published_at_epoch_ms: i64,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct IndexSnapshotDto {
+    pub name: String,
+    pub path: String,
+    pub created_at_epoch_ms: i64,
+    pub size_bytes: u64,
+}
+
+/// How the project index was last produced, written to
+/// `<database>.index_manifest.json` next to the database after each
+/// successful run.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct IndexManifestDto {
+    pub format_version: u32,
+    pub tool_version: String,
+    pub schema_version: u32,
+    /// Grammar release (or parser ABI when a grammar embeds no release) per
+    /// parser-backed language.
+    pub grammar_versions: std::collections::BTreeMap<String, String>,
+    /// SHA-256 over the settings that shape indexed output.
+    pub settings_hash: String,
+    pub mode: IndexMode,
+    pub file_count: u32,
+    pub duration_ms: u32,
+    pub completed_at_epoch_ms: i64,
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum IndexPublicationModeDto {
@@ -74,6 +144,13 @@ pub enum IndexPublicationModeDto {
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub struct StartIndexingRequest {
     pub mode: IndexMode,
+    /// Defaults to `Rebuild` for full runs and `Watcher` for incremental ones.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<IndexingPriority>,
+    /// Cap on indexing worker threads for this and later runs. Defaults to
+    /// one less than the available cores.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_threads: Option<u32>,
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, Default, PartialEq, Eq)]
@@ -716,6 +793,9 @@ pub struct SearchHit {
     pub verification_targets: Vec<SearchVerificationTargetDto>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub score_breakdown: Option<RetrievalScoreBreakdownDto>,
+    /// Characters of the hit that matched the query, for highlighting.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub highlight: Option<SearchHighlightDto>,
 }
 
 impl SearchHit {
@@ -724,6 +804,32 @@ impl SearchHit {
     }
 }
 
+/// Which part of a hit's text its highlight ranges fall in.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchMatchFieldDto {
+    /// The last segment of `display_name`.
+    Name,
+    /// `display_name`, reaching into its qualifying segments.
+    QualifiedName,
+}
+
+/// Half-open range of characters (Unicode scalar values, not bytes).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct SearchMatchRangeDto {
+    pub start: u32,
+    pub end: u32,
+}
+
+/// Matched characters of a hit's `display_name`, as the fuzzy matcher
+/// aligned them with the query.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct SearchHighlightDto {
+    pub field: SearchMatchFieldDto,
+    /// Ascending, disjoint ranges into `display_name`.
+    pub ranges: Vec<SearchMatchRangeDto>,
+}
+
 /// A source location related to a search hit by operation-bound source evidence.
 ///
 /// `role` names the evidence relationship. It must not imply parser-backed
@@ -982,6 +1088,27 @@ pub struct IndexedFilesRequest {
     pub limit: Option<u32>,
 }
 
+/// Files ranked by symbol density for the project overview.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct FileStatsRequest {
+    #[serde(default)]
+    pub limit: Option<u32>,
+}
+
+/// Symbol and edge totals for one indexed file.
+///
+/// `symbol_density` is symbols per source line, with empty files counted as
+/// one line.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct FileStatsDto {
+    pub path: String,
+    pub language: String,
+    pub line_count: u32,
+    pub symbol_count: u32,
+    pub edge_count: u32,
+    pub symbol_density: f32,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub struct IndexedFileDto {
     pub path: String,
@@ -1305,6 +1432,243 @@ pub struct AffectedAnalysisDto {
     pub notes: Vec<String>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ReviewGraphRequest {
+    /// Unified diff of the change under review, as `git diff` prints it. The
+    /// index should already hold the diff's new side.
+    pub diff: String,
+    /// Caller hops to follow from the changed symbols. Defaults to 1.
+    #[serde(default)]
+    pub depth: Option<u32>,
+}
+
+/// Inclusive line range on the new side of a diff.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub struct ReviewLineRangeDto {
+    pub start_line: u32,
+    pub end_line: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub struct ReviewChangedFileDto {
+    pub path: String,
+    pub kind: AffectedChangeKindDto,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_path: Option<String>,
+    /// Added lines, plus the line that follows each removal.
+    #[serde(default)]
+    pub changed_lines: Vec<ReviewLineRangeDto>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ReviewSymbolDto {
+    pub id: NodeId,
+    pub kind: NodeKind,
+    pub display_name: String,
+    pub file_path: Option<String>,
+    pub line: Option<u32>,
+    /// Caller hops from the nearest changed symbol; `0` for changed symbols.
+    pub distance: u32,
+}
+
+/// The part of the graph a change touches: the symbols it edits, their
+/// callers, and the tests that reach them.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ReviewGraphDto {
+    pub changed_files: Vec<ReviewChangedFileDto>,
+    pub changed_symbols: Vec<ReviewSymbolDto>,
+    pub callers: Vec<ReviewSymbolDto>,
+    pub tests: Vec<TestLinkDto>,
+    /// Changed symbols at depth 0, callers at their distance, and tests, with
+    /// the edges between them.
+    pub graph: GraphResponse,
+    /// Files reindexed before the graph was built.
+    #[serde(default)]
+    pub reindexed_files: Vec<String>,
+    /// Callers or tests were left out at the node limit.
+    #[serde(default)]
+    pub truncated: bool,
+}
+
+/// Which `TODO`, `FIXME`, and `HACK` comments to list. Unset fields match
+/// anything.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+pub struct CodeMarkersRequest {
+    #[serde(default)]
+    pub tag: Option<String>,
+    /// Substring of the file path.
+    #[serde(default)]
+    pub file: Option<String>,
+    /// Keep markers whose line was last changed at least this many days ago,
+    /// per `git blame`.
+    #[serde(default)]
+    pub min_age_days: Option<u32>,
+    /// Keep markers whose line was last changed at most this many days ago.
+    #[serde(default)]
+    pub max_age_days: Option<u32>,
+    #[serde(default)]
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CodeMarkerDto {
+    pub file_path: String,
+    pub line: u32,
+    pub column: u32,
+    pub tag: String,
+    pub text: String,
+    /// Author time of the line's last change; unset without blame.
+    #[serde(default)]
+    pub authored_at_epoch_ms: Option<i64>,
+    #[serde(default)]
+    pub age_days: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CodeMarkersDto {
+    pub markers: Vec<CodeMarkerDto>,
+    /// More markers matched than the limit allowed.
+    pub truncated: bool,
+}
+
+/// Full-text search over indexed string literals and constant values.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct StringLiteralSearchRequest {
+    /// Words that must all appear in the literal, or in a constant's name or
+    /// value.
+    pub query: String,
+    #[serde(default)]
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct StringLiteralMatchDto {
+    pub file_path: String,
+    pub line: u32,
+    pub column: u32,
+    /// Set when the match is a named constant.
+    #[serde(default)]
+    pub name: Option<String>,
+    pub value: String,
+    /// Innermost symbol whose span holds the literal.
+    #[serde(default)]
+    pub symbol_id: Option<NodeId>,
+    #[serde(default)]
+    pub symbol_name: Option<String>,
+    #[serde(default)]
+    pub symbol_kind: Option<NodeKind>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct StringLiteralSearchDto {
+    pub matches: Vec<StringLiteralMatchDto>,
+    /// More literals matched than the limit allowed.
+    pub truncated: bool,
+}
+
+/// One indexing run whose node and edge changes are retained.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct IndexHistoryRunDto {
+    pub run_id: String,
+    pub recorded_at_epoch_ms: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct IndexHistoryRunsDto {
+    /// Retained runs, oldest first.
+    pub runs: Vec<IndexHistoryRunDto>,
+}
+
+/// When a node and the edges touching it were added or removed.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct NodeHistoryRequest {
+    pub id: NodeId,
+}
+
+/// What an [`IndexHistoryEventDto`] added or removed.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(tag = "entity", rename_all = "snake_case")]
+pub enum IndexHistorySubjectDto {
+    Node {
+        id: NodeId,
+        kind: NodeKind,
+        name: String,
+    },
+    Edge {
+        id: EdgeId,
+        kind: EdgeKind,
+        source: NodeId,
+        /// The resolved target when the edge had one.
+        target: NodeId,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct IndexHistoryEventDto {
+    pub run_id: String,
+    pub recorded_at_epoch_ms: i64,
+    /// `false` when the run removed the subject.
+    pub added: bool,
+    pub subject: IndexHistorySubjectDto,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct NodeHistoryDto {
+    pub node_id: NodeId,
+    /// Oldest first, within the retained runs.
+    pub events: Vec<IndexHistoryEventDto>,
+}
+
+/// The graph as it stood right after one retained indexing run.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct GraphAtRequest {
+    pub run_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct GraphAtDto {
+    pub run: IndexHistoryRunDto,
+    pub node_ids: Vec<NodeId>,
+    pub edge_ids: Vec<EdgeId>,
+}
+
+/// Workspace symbols for the explorer sidebar, grouped by kind.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ExplorerSymbolsRequest {
+    /// Case-insensitive substring of the symbol's qualified name.
+    #[serde(default)]
+    pub query: Option<String>,
+    /// Kinds to list. Empty lists every kind but files.
+    #[serde(default)]
+    pub kinds: Vec<NodeKind>,
+    /// Most symbols listed per kind.
+    #[serde(default)]
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ExplorerSymbolDto {
+    pub id: NodeId,
+    pub label: String,
+    pub file_path: Option<String>,
+    pub line: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ExplorerSymbolGroupDto {
+    pub kind: NodeKind,
+    /// Every matching symbol of this kind, including those past the limit.
+    pub count: u32,
+    pub symbols: Vec<ExplorerSymbolDto>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ExplorerSymbolsDto {
+    pub groups: Vec<ExplorerSymbolGroupDto>,
+    /// Sum of the group counts.
+    pub total_count: u32,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub struct ListRootSymbolsRequest {
     pub limit: Option<u32>,
@@ -1313,6 +1677,75 @@ pub struct ListRootSymbolsRequest {
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub struct ListChildrenSymbolsRequest {
     pub parent_id: NodeId,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub offset: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+}
+
+/// Items in a page when the request names no `limit`.
+pub const DEFAULT_PAGE_LIMIT: u32 = 500;
+/// Largest `limit` a paged request is granted.
+pub const MAX_PAGE_LIMIT: u32 = 5_000;
+
+/// One page of a listing, with the size of the whole listing.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct PageDto<T> {
+    pub items: Vec<T>,
+    /// Items in the whole listing.
+    pub total: u32,
+    pub offset: u32,
+    /// Offset of the next page, absent on the last page.
+    pub next_offset: Option<u32>,
+}
+
+impl<T> PageDto<T> {
+    /// The page of `items` starting at `offset`, holding at most `limit`
+    /// items ([`DEFAULT_PAGE_LIMIT`] when unset, capped at
+    /// [`MAX_PAGE_LIMIT`]).
+    pub fn from_items(items: Vec<T>, offset: Option<u32>, limit: Option<u32>) -> Self {
+        let total = u32::try_from(items.len()).unwrap_or(u32::MAX);
+        let offset = offset.unwrap_or(0).min(total);
+        let limit = limit.unwrap_or(DEFAULT_PAGE_LIMIT).clamp(1, MAX_PAGE_LIMIT);
+        let end = offset.saturating_add(limit).min(total);
+        let items = items
+            .into_iter()
+            .skip(offset as usize)
+            .take((end - offset) as usize)
+            .collect();
+        Self {
+            items,
+            total,
+            offset,
+            next_offset: (end < total).then_some(end),
+        }
+    }
+
+    /// The page starting at `offset` of a listing of `total` items, bounded
+    /// as in [`Self::from_items`]. `fetch` reads the page's items given the
+    /// bounded offset and item count, and is not called for an empty page.
+    pub fn fetch<E>(
+        total: usize,
+        offset: Option<u32>,
+        limit: Option<u32>,
+        fetch: impl FnOnce(usize, usize) -> Result<Vec<T>, E>,
+    ) -> Result<Self, E> {
+        let total = u32::try_from(total).unwrap_or(u32::MAX);
+        let offset = offset.unwrap_or(0).min(total);
+        let limit = limit.unwrap_or(DEFAULT_PAGE_LIMIT).clamp(1, MAX_PAGE_LIMIT);
+        let end = offset.saturating_add(limit).min(total);
+        let items = if end > offset {
+            fetch(offset as usize, (end - offset) as usize)?
+        } else {
+            Vec::new()
+        };
+        Ok(Self {
+            items,
+            total,
+            offset,
+            next_offset: (end < total).then_some(end),
+        })
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -1322,6 +1755,20 @@ pub struct SymbolSummaryDto {
     pub kind: NodeKind,
     pub file_path: Option<String>,
     pub has_children: bool,
+    /// Bookmarked in the [`ENTRY_POINT_BOOKMARK_CATEGORY`] category; root
+    /// listings pin these first.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub entry_point: bool,
+}
+
+/// Symbol suggested while the user is still typing its name.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct AutocompleteSuggestionDto {
+    pub node_id: NodeId,
+    pub label: String,
+    pub kind: NodeKind,
+    pub file_path: Option<String>,
+    pub score: f32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -1439,6 +1886,64 @@ pub struct GraphRequest {
     pub max_edges: Option<u32>,
 }
 
+/// Expand one node of a graph the caller already shows.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct GraphExpandRequest {
+    pub node_id: NodeId,
+    /// Nodes already on screen; they are not returned again.
+    #[serde(default)]
+    pub existing_node_ids: Vec<NodeId>,
+    /// Edges already on screen; they are not returned again.
+    #[serde(default)]
+    pub existing_edge_ids: Vec<EdgeId>,
+    pub direction: TrailDirection,
+    #[serde(default)]
+    pub caller_scope: TrailCallerScope,
+    #[serde(default)]
+    pub edge_filter: Vec<EdgeKind>,
+    #[serde(default)]
+    pub node_filter: Vec<NodeKind>,
+    #[serde(default = "default_show_utility_calls")]
+    pub show_utility_calls: bool,
+    #[serde(default)]
+    pub hide_speculative: bool,
+    #[serde(default)]
+    pub hide_trivial_accessors: bool,
+    #[serde(default)]
+    pub hide_generated: bool,
+    /// Cap on neighbors loaded around the expanded node.
+    pub max_nodes: u32,
+    /// Graph session whose hidden and collapsed nodes apply to the response.
+    #[serde(default)]
+    pub graph_session: Option<String>,
+}
+
+/// Change the hidden and collapsed nodes of one graph session. Sessions are
+/// created on first update; ids are applied in field order.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct GraphSessionUpdateRequest {
+    pub session: String,
+    /// Forget everything the session hid or collapsed before applying this update.
+    #[serde(default)]
+    pub reset: bool,
+    #[serde(default)]
+    pub hide: Vec<NodeId>,
+    #[serde(default)]
+    pub unhide: Vec<NodeId>,
+    /// Fold members into their host node.
+    #[serde(default)]
+    pub collapse: Vec<NodeId>,
+    #[serde(default)]
+    pub uncollapse: Vec<NodeId>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct GraphSessionDto {
+    pub session: String,
+    pub hidden_node_ids: Vec<NodeId>,
+    pub collapsed_node_ids: Vec<NodeId>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub struct GraphNodeDto {
     pub id: NodeId,
@@ -1459,6 +1964,78 @@ pub struct GraphNodeDto {
     pub qualified_name: Option<String>,
     #[serde(default)]
     pub member_access: Option<MemberAccess>,
+    /// Edge totals across the whole index, including edges outside this response.
+    #[serde(default)]
+    pub edge_counts: Option<NodeEdgeCountsDto>,
+    #[serde(default)]
+    pub badges: NodeBadgesDto,
+    /// PageRank over call and usage edges, scaled so the mean across scored
+    /// nodes is `1.0`. Views can size nodes by it.
+    #[serde(default)]
+    pub centrality: Option<f32>,
+}
+
+impl GraphNodeDto {
+    /// A node with only its id, label, kind, and depth set.
+    pub fn new(id: NodeId, label: impl Into<String>, kind: NodeKind, depth: u32) -> Self {
+        Self {
+            id,
+            label: label.into(),
+            kind,
+            depth,
+            label_policy: None,
+            badge_visible_members: None,
+            badge_total_members: None,
+            merged_symbol_examples: Vec::new(),
+            file_path: None,
+            qualified_name: None,
+            member_access: None,
+            edge_counts: None,
+            badges: NodeBadgesDto::default(),
+            centrality: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub struct NodeEdgeCountsDto {
+    pub outgoing_call_count: u32,
+    pub incoming_call_count: u32,
+    pub member_count: u32,
+}
+
+/// Decorations a graph view can draw on a node without further requests.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub struct NodeBadgesDto {
+    /// The node's file has indexing errors.
+    #[serde(default)]
+    pub has_errors: bool,
+    #[serde(default)]
+    pub is_bookmarked: bool,
+    /// A bookmark on the node carries a comment.
+    #[serde(default)]
+    pub has_annotation: bool,
+    /// `TODO`, `FIXME`, and `HACK` comments in the file, or in the function
+    /// or method body.
+    #[serde(default)]
+    pub marker_count: u32,
+    /// The node stands for a type declared outside the project.
+    #[serde(default)]
+    pub is_external: bool,
+}
+
+/// How an edge's endpoints were bound to graph nodes.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum EdgeResolutionDto {
+    /// Extracted with exact endpoints; nothing needed resolving.
+    #[default]
+    Exact,
+    /// Resolution moved at least one endpoint off the extracted node.
+    Resolved,
+    /// A call or usage extraction could not bind with certainty, still
+    /// pointing at its extracted placeholder or guessed target.
+    Unresolved,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -1476,6 +2053,38 @@ pub struct GraphEdgeDto {
     pub callsite_identity: Option<String>,
     #[serde(default)]
     pub candidate_targets: Vec<NodeId>,
+    /// Edges for the same relationship folded into this one, such as a raw
+    /// edge whose resolved twin is returned instead.
+    #[serde(default)]
+    pub collapsed_edge_ids: Vec<EdgeId>,
+    /// Extracted source before resolution, when resolution moved `source`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw_source: Option<NodeId>,
+    /// Extracted target before resolution, when resolution moved `target`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw_target: Option<NodeId>,
+    #[serde(default)]
+    pub resolution: EdgeResolutionDto,
+}
+
+impl GraphEdgeDto {
+    /// An exact edge with only its id, endpoints, and kind set.
+    pub fn new(id: EdgeId, source: NodeId, target: NodeId, kind: EdgeKind) -> Self {
+        Self {
+            id,
+            source,
+            target,
+            kind,
+            confidence: None,
+            certainty: None,
+            callsite_identity: None,
+            candidate_targets: Vec::new(),
+            collapsed_edge_ids: Vec::new(),
+            raw_source: None,
+            raw_target: None,
+            resolution: EdgeResolutionDto::Exact,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -1484,12 +2093,94 @@ pub struct GraphResponse {
     pub nodes: Vec<GraphNodeDto>,
     pub edges: Vec<GraphEdgeDto>,
     pub truncated: bool,
+    /// The trail hit its deadline or was cancelled before it finished.
+    #[serde(default)]
+    pub truncated_by_timeout: bool,
     #[serde(default)]
     pub omitted_edge_count: u32,
     #[serde(default)]
+    pub pruned_test_node_count: u32,
+    #[serde(default)]
+    pub pruned_accessor_node_count: u32,
+    #[serde(default)]
+    pub pruned_generated_node_count: u32,
+    /// Nodes the trail reached but left out, by the node limit or the node
+    /// filter.
+    #[serde(default)]
+    pub omitted_node_count: u32,
+    /// Node limit the trail ran with; `None` for graphs built without one.
+    #[serde(default)]
+    pub node_limit: Option<u32>,
+    /// Root-to-target paths, filled only for `TrailMode::AllPaths` and
+    /// `TrailMode::WeightedPath`.
+    #[serde(default)]
+    pub paths: Vec<TrailPathDto>,
+    #[serde(default)]
     pub canonical_layout: Option<CanonicalLayoutDto>,
 }
 
+impl GraphResponse {
+    /// A complete graph of `nodes` and `edges` around `center_id`: nothing
+    /// truncated, omitted, or pruned, and no paths or layout.
+    pub fn new(center_id: NodeId, nodes: Vec<GraphNodeDto>, edges: Vec<GraphEdgeDto>) -> Self {
+        Self {
+            center_id,
+            nodes,
+            edges,
+            truncated: false,
+            truncated_by_timeout: false,
+            omitted_edge_count: 0,
+            pruned_test_node_count: 0,
+            pruned_accessor_node_count: 0,
+            pruned_generated_node_count: 0,
+            omitted_node_count: 0,
+            node_limit: None,
+            paths: Vec::new(),
+            canonical_layout: None,
+        }
+    }
+}
+
+/// Which of two compared graphs hold a node or edge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum GraphCompareSide {
+    OnlyA,
+    OnlyB,
+    Both,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct GraphCompareNodeDto {
+    pub node: GraphNodeDto,
+    pub side: GraphCompareSide,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct GraphCompareEdgeDto {
+    pub edge: GraphEdgeDto,
+    pub side: GraphCompareSide,
+}
+
+/// The union of two graphs, each node and edge labeled with the graphs that
+/// hold it. Nodes and edges in both keep graph A's details.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct GraphCompareDto {
+    pub center_a: NodeId,
+    pub center_b: NodeId,
+    pub nodes: Vec<GraphCompareNodeDto>,
+    pub edges: Vec<GraphCompareEdgeDto>,
+    /// Either graph was cut short, so an "only" label may be an artifact
+    /// of the limit rather than a real difference.
+    pub truncated: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct TrailPathDto {
+    pub node_ids: Vec<NodeId>,
+    pub edge_ids: Vec<EdgeId>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub struct CanonicalLayoutDto {
     pub schema_version: u32,
@@ -1583,7 +2274,14 @@ pub enum CanonicalMemberVisibility {
 ///
 /// The shape is serialized API surface. `hide_speculative` filters uncertain
 /// evidence from presentation; it does not alter the graph. `story` asks the
-/// runtime to add narrative grouping when available.
+/// runtime to add narrative grouping when available. Unlike those, test-file
+/// pruning under `caller_scope: ProductionOnly`, `hide_trivial_accessors`,
+/// `hide_generated`, and `entry_reachable_only` remove nodes while the trail
+/// is built; the response reports how many.
+///
+/// `depth`, `direction`, `caller_scope`, `max_nodes`, and an empty
+/// `edge_filter` fall back to the project's [`TrailDefaultsDto`], then to the
+/// built-in defaults of the `*_or_default` accessors.
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub struct TrailConfigDto {
     pub root_id: NodeId,
@@ -1592,10 +2290,13 @@ pub struct TrailConfigDto {
     #[serde(default)]
     pub target_id: Option<NodeId>,
     /// Use `0` to mean "infinite" (bounded by `max_nodes`).
-    pub depth: u32,
-    pub direction: TrailDirection,
     #[serde(default)]
-    pub caller_scope: TrailCallerScope,
+    pub depth: Option<u32>,
+    #[serde(default)]
+    pub direction: Option<TrailDirection>,
+    #[serde(default)]
+    pub caller_scope: Option<TrailCallerScope>,
+    #[serde(default)]
     pub edge_filter: Vec<EdgeKind>,
     #[serde(default = "default_show_utility_calls")]
     pub show_utility_calls: bool,
@@ -1605,9 +2306,92 @@ pub struct TrailConfigDto {
     pub story: bool,
     #[serde(default)]
     pub node_filter: Vec<NodeKind>,
-    pub max_nodes: u32,
+    #[serde(default)]
+    pub hide_trivial_accessors: bool,
+    #[serde(default)]
+    pub hide_generated: bool,
+    #[serde(default)]
+    pub entry_reachable_only: bool,
+    /// Path limit for `TrailMode::AllPaths`.
+    #[serde(default)]
+    pub max_paths: Option<u32>,
+    /// Hop cost for `TrailMode::WeightedPath`.
+    #[serde(default)]
+    pub edge_weight: TrailEdgeWeight,
+    #[serde(default)]
+    pub max_nodes: Option<u32>,
     #[serde(default = "default_layout_direction")]
     pub layout_direction: LayoutDirection,
+    /// Graph session whose hidden and collapsed nodes apply to the response.
+    #[serde(default)]
+    pub graph_session: Option<String>,
+    /// Stop expanding after this long and return the partial trail flagged
+    /// `truncated_by_timeout`; `None` uses the server default.
+    #[serde(default)]
+    pub timeout_ms: Option<u32>,
+    /// Saved preset whose kinds fill `edge_filter` and `node_filter` when
+    /// those are empty.
+    #[serde(default)]
+    pub filter_preset: Option<String>,
+}
+
+/// Node budget of a trail when neither the request nor the project sets one.
+pub const TRAIL_DEFAULT_MAX_NODES: u32 = 120;
+
+impl TrailConfigDto {
+    /// `depth`, else 2 for neighborhoods and unbounded for other modes.
+    pub fn depth_or_default(&self) -> u32 {
+        self.depth.unwrap_or(match self.mode {
+            TrailMode::Neighborhood => 2,
+            _ => 0,
+        })
+    }
+
+    /// `direction`, else the direction `mode` reads in.
+    pub fn direction_or_default(&self) -> TrailDirection {
+        self.direction.unwrap_or(match self.mode {
+            TrailMode::AllReferenced => TrailDirection::Outgoing,
+            TrailMode::AllReferencing => TrailDirection::Incoming,
+            _ => TrailDirection::Both,
+        })
+    }
+
+    pub fn caller_scope_or_default(&self) -> TrailCallerScope {
+        self.caller_scope.unwrap_or_default()
+    }
+
+    pub fn max_nodes_or_default(&self) -> u32 {
+        self.max_nodes.unwrap_or(TRAIL_DEFAULT_MAX_NODES)
+    }
+}
+
+/// Per-project trail settings used where a [`TrailConfigDto`] leaves a field
+/// unset.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub struct TrailDefaultsDto {
+    #[serde(default)]
+    pub depth: Option<u32>,
+    #[serde(default)]
+    pub direction: Option<TrailDirection>,
+    #[serde(default)]
+    pub caller_scope: Option<TrailCallerScope>,
+    #[serde(default)]
+    pub max_nodes: Option<u32>,
+    #[serde(default)]
+    pub edge_filter: Vec<EdgeKind>,
+}
+
+impl TrailDefaultsDto {
+    /// Fill the fields `req` leaves unset from these defaults.
+    pub fn apply_to(&self, req: &mut TrailConfigDto) {
+        req.depth = req.depth.or(self.depth);
+        req.direction = req.direction.or(self.direction);
+        req.caller_scope = req.caller_scope.or(self.caller_scope);
+        req.max_nodes = req.max_nodes.or(self.max_nodes);
+        if req.edge_filter.is_empty() {
+            req.edge_filter.clone_from(&self.edge_filter);
+        }
+    }
 }
 
 const fn default_show_utility_calls() -> bool {
@@ -1618,10 +2402,129 @@ const fn default_layout_direction() -> LayoutDirection {
     LayoutDirection::Horizontal
 }
 
+/// Palette for graph images rendered on the server.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum GraphRenderTheme {
+    #[default]
+    Light,
+    Dark,
+}
+
+/// A trail to render as a PNG image.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct GraphRenderRequest {
+    pub trail: TrailConfigDto,
+    /// Image width in pixels. When only one of `width` and `height` is set
+    /// the other follows the layout's aspect ratio; when neither is, the
+    /// layout is drawn at twice its natural size.
+    #[serde(default)]
+    pub width: Option<u32>,
+    #[serde(default)]
+    pub height: Option<u32>,
+    #[serde(default)]
+    pub theme: GraphRenderTheme,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub struct TrailFilterOptionsDto {
     pub node_kinds: Vec<NodeKind>,
     pub edge_kinds: Vec<EdgeKind>,
+    #[serde(default)]
+    pub node_kind_counts: Vec<NodeKindCountDto>,
+    #[serde(default)]
+    pub edge_kind_counts: Vec<EdgeKindCountDto>,
+    /// Saved filter presets, ordered by name.
+    #[serde(default)]
+    pub presets: Vec<TrailFilterPresetDto>,
+}
+
+/// Named edge and node kind filters a trail request can refer to by name.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct TrailFilterPresetDto {
+    pub name: String,
+    #[serde(default)]
+    pub edge_kinds: Vec<EdgeKind>,
+    #[serde(default)]
+    pub node_kinds: Vec<NodeKind>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct NodeKindCountDto {
+    pub kind: NodeKind,
+    pub count: u32,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct EdgeKindCountDto {
+    pub kind: EdgeKind,
+    pub count: u32,
+}
+
+/// Everything a symbol can eventually reach, without building a trail.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ReachableSetRequest {
+    pub id: NodeId,
+    pub direction: TrailDirection,
+    /// Edge kinds to follow; empty follows every kind.
+    #[serde(default)]
+    pub edge_kinds: Vec<EdgeKind>,
+    #[serde(default)]
+    pub max_nodes: Option<u32>,
+}
+
+/// Nodes reachable from `id`, excluding `id` itself, grouped for impact
+/// summaries.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ReachableSetDto {
+    pub id: NodeId,
+    pub node_ids: Vec<NodeId>,
+    pub kind_counts: Vec<NodeKindCountDto>,
+    pub file_counts: Vec<ReachableFileCountDto>,
+    /// Hops to the farthest reached node.
+    pub max_depth: u32,
+    /// Expansion stopped at `max_nodes` before the set was complete.
+    pub truncated: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+pub struct ReachableFileCountDto {
+    pub path: String,
+    pub count: u32,
+}
+
+/// Entry points and how far the rest of the call graph sits from them.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct EntryPointOverviewDto {
+    /// Main functions, route endpoints, and tests.
+    pub entry_points: Vec<EntryPointDto>,
+    /// Nodes per call distance from the nearest entry point; index `0`
+    /// counts the entry points themselves.
+    pub layer_sizes: Vec<u32>,
+    pub reachable_count: u32,
+    /// The walk stopped at its node budget, so deeper layers are incomplete.
+    pub truncated: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct EntryPointDto {
+    pub id: NodeId,
+    pub kind: NodeKind,
+    pub display_name: String,
+    pub file_path: Option<String>,
+    pub line: Option<u32>,
+}
+
+/// A test, or the production code it exercises, linked through calls.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct TestLinkDto {
+    pub id: NodeId,
+    pub kind: NodeKind,
+    pub display_name: String,
+    pub file_path: Option<String>,
+    pub line: Option<u32>,
+    /// Call hops between the test and the production code.
+    pub distance: u32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -1669,6 +2572,13 @@ pub struct NodeDetailsRequest {
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub struct NodeOccurrencesRequest {
     pub id: NodeId,
+    /// Keep only occurrences with this kind label, e.g. `write_reference`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub offset: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -1685,6 +2595,17 @@ pub struct SourceOccurrenceDto {
     pub start_col: u32,
     pub end_line: u32,
     pub end_col: u32,
+    /// For notebooks, the 0-based cell holding `start_line`. Line numbers above
+    /// count through the notebook's code cells joined in order.
+    #[serde(default)]
+    pub notebook_cell_index: Option<u32>,
+    /// For notebooks, the 1-based line of `start_line` within its cell.
+    #[serde(default)]
+    pub notebook_cell_line: Option<u32>,
+    /// The file changed since it was indexed and the stored range no longer
+    /// fit it, so the range was clamped to the file's current contents.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub stale: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -1710,6 +2631,40 @@ pub struct NodeDetailsDto {
     pub member_access: Option<MemberAccess>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub route_endpoint: Option<RouteEndpointMetadataDto>,
+    /// Every place the symbol is defined, when it has more than one: partial
+    /// classes, overloads sharing a name, or re-exports.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub definitions: Vec<NodeDefinitionDto>,
+}
+
+/// One definition of a symbol defined in several places.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub struct NodeDefinitionDto {
+    pub file_path: String,
+    pub start_line: u32,
+    pub start_col: u32,
+    pub end_line: u32,
+    pub end_col: u32,
+}
+
+/// Hover preview of one node: identity, one-line summary, neighbor counts,
+/// and where it is defined.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct NodePreviewDto {
+    pub id: NodeId,
+    pub kind: NodeKind,
+    pub display_name: String,
+    pub qualified_name: Option<String>,
+    /// First line of the generated symbol summary, when one exists.
+    #[serde(default)]
+    pub doc: Option<String>,
+    #[serde(default)]
+    pub member_access: Option<MemberAccess>,
+    pub caller_count: u32,
+    pub callee_count: u32,
+    pub member_count: u32,
+    pub file_path: Option<String>,
+    pub line: Option<u32>,
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, PartialEq, Eq)]
@@ -1947,6 +2902,19 @@ pub enum AgentResponseModeDto {
     Structured,
 }
 
+/// Common questions about one symbol whose context the app assembles itself
+/// and answers with a fixed set of sections.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AgentAskIntentDto {
+    /// Body, direct callers, and direct callees of the focus symbol.
+    Explain,
+    /// Transitive callers of the focus symbol, grouped by hop.
+    Callers,
+    /// Outgoing call chain from the focus symbol, grouped by hop.
+    TraceRequest,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Type, Default)]
 pub struct AgentHybridWeightsDto {
     #[serde(default)]
@@ -1986,6 +2954,9 @@ pub struct AgentAskRequest {
     pub include_evidence: bool,
     #[serde(default)]
     pub hybrid_weights: Option<AgentHybridWeightsDto>,
+    /// Answer with intent sections instead of routing on prompt keywords.
+    #[serde(default)]
+    pub intent: Option<AgentAskIntentDto>,
 }
 
 const fn default_include_evidence() -> bool {
@@ -2653,6 +3624,11 @@ pub struct AgentPacketDto {
     pub retrieval_trace_summary: PacketRetrievalTraceSummaryDto,
 }
 
+/// Bookmark category whose bookmarks mark curated entry points. Root symbol
+/// listings show them first, so a newcomer sees where to start reading. The
+/// name is matched without regard to ASCII case.
+pub const ENTRY_POINT_BOOKMARK_CATEGORY: &str = "Start here";
+
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub struct BookmarkCategoryDto {
     pub id: String,
@@ -2663,6 +3639,45 @@ pub struct BookmarkCategoryDto {
 mod packet_tests {
     use super::*;
 
+    #[test]
+    fn pages_report_the_whole_listing_and_the_next_offset() {
+        let page = PageDto::from_items((0..12).collect(), Some(5), Some(4));
+        assert_eq!(page.items, vec![5, 6, 7, 8]);
+        assert_eq!(page.total, 12);
+        assert_eq!(page.offset, 5);
+        assert_eq!(page.next_offset, Some(9));
+
+        let last = PageDto::from_items((0..12).collect(), Some(9), None);
+        assert_eq!(last.items, vec![9, 10, 11]);
+        assert_eq!(last.next_offset, None);
+
+        let past_end = PageDto::<u32>::from_items(vec![1, 2], Some(10), Some(0));
+        assert!(past_end.items.is_empty());
+        assert_eq!(past_end.offset, 2);
+        assert_eq!(past_end.next_offset, None);
+
+        let capped = PageDto::from_items(vec![0; 6_000], None, Some(u32::MAX));
+        assert_eq!(capped.items.len(), MAX_PAGE_LIMIT as usize);
+        assert_eq!(capped.next_offset, Some(MAX_PAGE_LIMIT));
+    }
+
+    #[test]
+    fn fetched_pages_read_only_the_bounded_window() {
+        let listing = (0..12).collect::<Vec<u32>>();
+        let read =
+            |offset: usize, limit: usize| Ok::<_, ()>(listing[offset..offset + limit].to_vec());
+        let page = PageDto::fetch(listing.len(), Some(5), Some(4), read).expect("page");
+        assert_eq!(page.items, vec![5, 6, 7, 8]);
+        assert_eq!(page.total, 12);
+        assert_eq!(page.next_offset, Some(9));
+
+        let past_end =
+            PageDto::<u32>::fetch(2, Some(10), None, |_, _| Err("not read")).expect("empty page");
+        assert!(past_end.items.is_empty());
+        assert_eq!(past_end.offset, 2);
+        assert_eq!(past_end.next_offset, None);
+    }
+
     #[test]
     fn structural_text_evidence_tier_serializes_as_a_named_public_contract() {
         assert_eq!(
@@ -3277,6 +4292,88 @@ pub struct BookmarkDto {
     pub file_path: Option<String>,
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BookmarkHealthStatusDto {
+    Valid,
+    /// The bookmarked node is gone but a live node with its kind and name exists.
+    Resolvable,
+    Orphaned,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct BookmarkHealthEntryDto {
+    pub bookmark_id: String,
+    pub category_id: String,
+    pub node_id: NodeId,
+    pub resolved_node_id: Option<NodeId>,
+    pub symbol_name: Option<String>,
+    pub status: BookmarkHealthStatusDto,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct BookmarkHealthDto {
+    pub valid_count: u32,
+    pub resolvable_count: u32,
+    pub orphaned_count: u32,
+    /// Bookmarks rebound by the repair that produced this report.
+    pub repaired_count: u32,
+    pub bookmarks: Vec<BookmarkHealthEntryDto>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct MergeNodesRequest {
+    pub primary_id: NodeId,
+    pub duplicate_ids: Vec<NodeId>,
+}
+
+/// A node merged into another as a duplicate, recorded by kind and
+/// canonical name so it still applies after re-indexing.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct NodeAliasDto {
+    pub kind: NodeKind,
+    pub name: String,
+    pub primary_kind: NodeKind,
+    pub primary_name: String,
+    /// Live node currently carrying `kind` and `name`, if any.
+    pub node_id: Option<NodeId>,
+    /// Live node currently carrying the primary's kind and name, if any.
+    pub primary_node_id: Option<NodeId>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+pub struct ListBookmarksRequest {
+    #[serde(default)]
+    pub category_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub offset: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+pub struct ListIndexErrorsRequest {
+    /// Keep only errors that stopped a file from being indexed.
+    #[serde(default)]
+    pub fatal_only: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub offset: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+}
+
+/// An error recorded while indexing, in the order it was recorded.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct IndexErrorDto {
+    pub message: String,
+    pub file_path: Option<String>,
+    pub line: Option<u32>,
+    pub column: Option<u32>,
+    pub fatal: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coverage_reason: Option<FileCoverageReason>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub struct CreateBookmarkRequest {
     pub category_id: String,
@@ -3296,6 +4393,10 @@ pub struct UpdateBookmarkRequest {
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub struct OpenDefinitionRequest {
     pub node_id: NodeId,
+    /// Index into the node's `definitions` to open. The node's own location
+    /// opens when unset.
+    #[serde(default)]
+    pub definition_index: Option<u32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -3307,6 +4408,10 @@ pub struct OpenContainingFolderRequest {
 pub struct SystemActionResponse {
     pub ok: bool,
     pub message: String,
+    /// Every definition of a node `open_definition` opened, when it has
+    /// several; pass one's index as `definition_index` to open it instead.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub definitions: Vec<NodeDefinitionDto>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
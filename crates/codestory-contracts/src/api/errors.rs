@@ -119,6 +119,22 @@ impl ApiErrorDetails {
         }
     }
 
+    pub fn storage_corrupt(next_commands: Vec<String>) -> Self {
+        let minimum_next = next_commands.iter().take(1).cloned().collect::<Vec<_>>();
+        Self {
+            cause_code: Some("integrity_check_failed".to_string()),
+            failed_layer: Some("storage".to_string()),
+            project: None,
+            minimum_next,
+            full_repair: next_commands.clone(),
+            next_commands,
+            readiness: None,
+            embedding_capacity: None,
+            embedding_retry: None,
+            coverage_gaps: Vec::new(),
+        }
+    }
+
     pub fn with_readiness(mut self, readiness: ReadinessVerdictDto) -> Self {
         if self.minimum_next.is_empty() {
             self.minimum_next = readiness.minimum_next.clone();
@@ -178,6 +194,10 @@ impl ApiError {
         Self::new("not_found", message)
     }
 
+    pub fn already_exists(message: impl Into<String>) -> Self {
+        Self::new("already_exists", message)
+    }
+
     pub fn internal(message: impl Into<String>) -> Self {
         Self::new("internal", message)
     }
@@ -206,6 +226,16 @@ impl ApiError {
         )
     }
 
+    /// The project database failed its integrity check; a full refresh
+    /// rebuilds it from source.
+    pub fn storage_corrupt(message: impl Into<String>, next_commands: Vec<String>) -> Self {
+        Self::with_details(
+            "storage_corrupt",
+            message,
+            ApiErrorDetails::storage_corrupt(next_commands),
+        )
+    }
+
     pub fn embedding_capacity(
         message: impl Into<String>,
         pressure: EmbeddingCapacityPressureDto,
@@ -1,3 +1,4 @@
+use super::types::{IndexMode, IndexingPriority};
 use serde::{Deserialize, Serialize};
 use specta::Type;
 
@@ -241,6 +242,9 @@ pub struct IndexingPhaseTimings {
     pub summary_snapshot_ms: Option<u32>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub detail_snapshot_ms: Option<u32>,
+    /// Set only when the publish refreshed SQLite planner statistics.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub analyze_ms: Option<u32>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub publish_ms: Option<u32>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -343,6 +347,31 @@ pub struct IndexingPhaseTimings {
     pub resolved_imports_fuzzy: Option<u32>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub resolved_imports_semantic: Option<u32>,
+    /// What the run changed in the published index.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub run_delta: Option<IndexingRunDelta>,
+}
+
+/// Node, edge, and file changes one indexing run published, plus the files
+/// that took longest to parse.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub struct IndexingRunDelta {
+    pub nodes_added: u32,
+    pub nodes_removed: u32,
+    pub edges_added: u32,
+    pub edges_removed: u32,
+    pub files_added: u32,
+    pub files_removed: u32,
+    pub files_updated: u32,
+    /// Slowest first; at most five entries.
+    #[serde(default)]
+    pub slowest_files: Vec<FileParseTiming>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub struct FileParseTiming {
+    pub path: String,
+    pub parse_ms: u32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -364,6 +393,28 @@ pub enum AppEventPayload {
     IndexingFailed {
         error: String,
     },
+    /// `reindex_file` published a refresh of one project-relative path.
+    FileReindexed {
+        path: String,
+        duration_ms: u32,
+    },
+    /// Stored occurrence ranges in these project-relative files no longer fit
+    /// the files on disk. `reindex_file` on each path refreshes them.
+    ReindexSuggested {
+        paths: Vec<String>,
+    },
+    /// A request arrived while another run was active and will start after it.
+    /// `pending` counts waiting runs after coalescing.
+    IndexingQueued {
+        mode: IndexMode,
+        priority: IndexingPriority,
+        pending: u32,
+    },
+    /// Symbol names and the search engine finished loading after a lazy open.
+    CachesReady {
+        symbol_count: u32,
+        duration_ms: u32,
+    },
     StatusUpdate {
         message: String,
     },
@@ -458,6 +509,7 @@ mod tests {
             deferred_indexes_ms: None,
             summary_snapshot_ms: None,
             detail_snapshot_ms: None,
+            analyze_ms: None,
             publish_ms: None,
             staged_sqlite_wal_autocheckpoint_bytes: None,
             staged_sqlite_checkpoint_ms: None,
@@ -512,6 +564,7 @@ mod tests {
             resolved_imports_global_unique: None,
             resolved_imports_fuzzy: None,
             resolved_imports_semantic: None,
+            run_delta: None,
         };
 
         let value = serde_json::to_value(timings).expect("serialize timings");
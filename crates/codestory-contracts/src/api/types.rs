@@ -27,6 +27,16 @@ pub enum IndexMode {
     Incremental,
 }
 
+/// Who asked for an indexing run. Queued runs start highest-priority first.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Type,
+)]
+pub enum IndexingPriority {
+    Rebuild,
+    Watcher,
+    Interactive,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Type)]
 #[allow(non_camel_case_types)]
 pub enum NodeKind {
@@ -108,6 +118,7 @@ pub enum EdgeKind {
     MACRO_USAGE,
     ANNOTATION_USAGE,
     UNKNOWN,
+    ASSIGNMENT,
 }
 
 impl_mirrored_enum_conversions!(
@@ -127,6 +138,7 @@ impl_mirrored_enum_conversions!(
         MACRO_USAGE,
         ANNOTATION_USAGE,
         UNKNOWN,
+        ASSIGNMENT,
     ]
 );
 
@@ -137,12 +149,36 @@ pub enum TrailMode {
     AllReferenced,
     AllReferencing,
     ToTargetSymbol,
+    AllPaths,
+    WeightedPath,
+    DataFlow,
 }
 
 impl_mirrored_enum_conversions!(
     TrailMode,
     crate::graph::TrailMode,
-    [Neighborhood, AllReferenced, AllReferencing, ToTargetSymbol,]
+    [
+        Neighborhood,
+        AllReferenced,
+        AllReferencing,
+        ToTargetSymbol,
+        AllPaths,
+        WeightedPath,
+        DataFlow,
+    ]
+);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Type, Default)]
+pub enum TrailEdgeWeight {
+    #[default]
+    InverseConfidence,
+    NegativeLogConfidence,
+}
+
+impl_mirrored_enum_conversions!(
+    TrailEdgeWeight,
+    crate::graph::TrailEdgeWeight,
+    [InverseConfidence, NegativeLogConfidence,]
 );
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Type)]
@@ -18,11 +18,38 @@ pub use telemetry::{
 /// User-visible runtime status event.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Event {
-    IndexingStarted { file_count: usize },
-    IndexingProgress { current: usize, total: usize },
-    IndexingComplete { duration_ms: u64 },
-    ShowWarning { message: String },
-    StatusUpdate { message: String },
+    IndexingStarted {
+        file_count: usize,
+    },
+    IndexingProgress {
+        current: usize,
+        total: usize,
+    },
+    IndexingComplete {
+        /// Wall-clock time of the whole run.
+        duration_ms: u64,
+        /// The run stopped early; only completed work was stored.
+        cancelled: bool,
+        phase_timings: IndexingPhaseDurations,
+    },
+    ShowWarning {
+        message: String,
+    },
+    StatusUpdate {
+        message: String,
+    },
+}
+
+/// Milliseconds an indexing run spent in each of its phases.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexingPhaseDurations {
+    /// Loading existing projections and reading sources.
+    pub prepare_ms: u64,
+    pub parse_ms: u64,
+    /// Writing projections and errors to storage.
+    pub flush_ms: u64,
+    pub resolution_ms: u64,
+    pub cleanup_ms: u64,
 }
 
 /// In-process fanout bus for runtime events.
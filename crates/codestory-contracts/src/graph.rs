@@ -14,6 +14,7 @@ use thiserror::Error;
 pub mod access;
 pub mod definition;
 pub mod error;
+pub mod intern;
 pub mod location_type;
 pub mod node_type;
 pub mod protocol;
@@ -22,6 +23,7 @@ pub mod token_component;
 pub use access::AccessKind;
 pub use definition::DefinitionKind;
 pub use error::{ErrorFilter, ErrorInfo, FileCoverageReason, IndexStep};
+pub use intern::StringInterner;
 pub use location_type::LocationType;
 pub use node_type::{BundleInfo, NodeType};
 pub use token_component::{Token, TokenComponent};
@@ -171,9 +173,12 @@ pub enum EdgeKind {
     ANNOTATION_USAGE,
 
     UNKNOWN,
+
+    // Data flow; declared after UNKNOWN so stored discriminants stay stable.
+    ASSIGNMENT, // function writes a field or variable
 }
 
-const EDGE_KIND_BY_DISCRIMINANT: [EdgeKind; 13] = [
+const EDGE_KIND_BY_DISCRIMINANT: [EdgeKind; 14] = [
     EdgeKind::MEMBER,
     EdgeKind::TYPE_USAGE,
     EdgeKind::USAGE,
@@ -187,6 +192,7 @@ const EDGE_KIND_BY_DISCRIMINANT: [EdgeKind; 13] = [
     EdgeKind::MACRO_USAGE,
     EdgeKind::ANNOTATION_USAGE,
     EdgeKind::UNKNOWN,
+    EdgeKind::ASSIGNMENT,
 ];
 
 impl TryFrom<i32> for EdgeKind {
@@ -366,6 +372,19 @@ pub struct SourceLocation {
     pub end_col: u32,
 }
 
+/// Position of a location inside a Jupyter notebook cell.
+///
+/// Notebooks are indexed as one virtual document built from their code cells;
+/// occurrence locations use that document's lines, and this records where the
+/// start line falls in the notebook itself.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct NotebookCellPosition {
+    /// 0-based index into the notebook's `cells` array, counting every cell.
+    pub cell_index: u32,
+    /// 1-based line within the cell's source.
+    pub line: u32,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct CallableProjectionState {
     pub file_id: i64,
@@ -411,15 +430,23 @@ pub enum OccurrenceKind {
     MACRO_DEFINITION,
     MACRO_REFERENCE,
     UNKNOWN,
+    // Appended after `UNKNOWN` so discriminants already persisted keep their
+    // meaning; older readers map these to `UNKNOWN`.
+    /// A reference that assigns to or updates the symbol.
+    WRITE_REFERENCE,
+    /// A reference that only reads the symbol.
+    READ_REFERENCE,
 }
 
-const OCCURRENCE_KIND_BY_DISCRIMINANT: [OccurrenceKind; 6] = [
+const OCCURRENCE_KIND_BY_DISCRIMINANT: [OccurrenceKind; 8] = [
     OccurrenceKind::DEFINITION,
     OccurrenceKind::REFERENCE,
     OccurrenceKind::DECLARATION,
     OccurrenceKind::MACRO_DEFINITION,
     OccurrenceKind::MACRO_REFERENCE,
     OccurrenceKind::UNKNOWN,
+    OccurrenceKind::WRITE_REFERENCE,
+    OccurrenceKind::READ_REFERENCE,
 ];
 
 impl TryFrom<i32> for OccurrenceKind {
@@ -446,6 +473,13 @@ pub struct Occurrence {
     pub element_id: i64, // Can be a NodeId or EdgeId
     pub kind: OccurrenceKind,
     pub location: SourceLocation,
+    /// Edge this occurrence justifies, set for call sites so that several
+    /// calls to the same symbol within one file stay distinguishable.
+    #[serde(default)]
+    pub edge_id: Option<EdgeId>,
+    /// Notebook cell holding the start line, for occurrences in `.ipynb` files.
+    #[serde(default)]
+    pub notebook_cell: Option<NotebookCellPosition>,
 }
 
 // ============================================================================
@@ -476,6 +510,13 @@ pub struct Bookmark {
 /// - `Neighborhood` respects the chosen `TrailDirection` (incoming/outgoing/both).
 /// - `AllReferenced` / `AllReferencing` force an outgoing/incoming perspective.
 /// - `ToTargetSymbol` finds paths from `root_id` to `target_id`.
+/// - `AllPaths` enumerates the distinct simple paths from `root_id` to
+///   `target_id`, up to `depth` hops and `max_paths` paths.
+/// - `WeightedPath` finds the single route from `root_id` to `target_id` with
+///   the lowest total edge weight, so well-resolved edges win over a shorter
+///   chain of guesses.
+/// - `DataFlow` ignores `TrailDirection` and the edge filter: it leaves code
+///   through `ASSIGNMENT` edges and data through incoming `USAGE` edges.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub enum TrailMode {
     /// Default CodeStory behavior: explore a neighborhood subgraph around the root.
@@ -487,6 +528,27 @@ pub enum TrailMode {
     AllReferencing,
     /// Show paths from the root to a target symbol.
     ToTargetSymbol,
+    /// List every simple path from the root to a target symbol.
+    AllPaths,
+    /// Show the most trustworthy path from the root to a target symbol.
+    WeightedPath,
+    /// Follow write-to-read chains: functions that assign a field or
+    /// variable, then the functions that read it.
+    DataFlow,
+}
+
+/// Cost of one hop for `TrailMode::WeightedPath`.
+///
+/// Edges without a confidence count as fully confident.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum TrailEdgeWeight {
+    /// `1 / confidence`: every hop costs at least one, so among equally
+    /// confident routes the shorter one wins.
+    #[default]
+    InverseConfidence,
+    /// `-ln(confidence)`: the path whose confidences have the highest product
+    /// wins, however many hops it takes.
+    NegativeLogConfidence,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -508,7 +570,8 @@ pub struct TrailConfig {
     pub root_id: NodeId,
     #[serde(default)]
     pub mode: TrailMode,
-    /// Optional target for `TrailMode::ToTargetSymbol`.
+    /// Target for `TrailMode::ToTargetSymbol`, `TrailMode::AllPaths`, and
+    /// `TrailMode::WeightedPath`.
     #[serde(default)]
     pub target_id: Option<NodeId>,
     pub depth: u32,
@@ -522,6 +585,26 @@ pub struct TrailConfig {
     /// nodes of the listed kinds (root/target are always included when present).
     #[serde(default)]
     pub node_filter: Vec<NodeKind>,
+    /// Drop short getter/setter style functions and methods (root/target are
+    /// always kept).
+    #[serde(default)]
+    pub hide_trivial_accessors: bool,
+    /// Drop symbols from files whose role is generated (root/target are
+    /// always kept).
+    #[serde(default)]
+    pub hide_generated: bool,
+    /// Drop symbols that no entry point reaches through calls (root/target
+    /// are always kept). See `Storage::entry_point_distances` for what counts
+    /// as an entry point.
+    #[serde(default)]
+    pub entry_reachable_only: bool,
+    /// Most paths `TrailMode::AllPaths` returns; `None` uses the store
+    /// default.
+    #[serde(default)]
+    pub max_paths: Option<u32>,
+    /// Hop cost used by `TrailMode::WeightedPath`.
+    #[serde(default)]
+    pub edge_weight: TrailEdgeWeight,
     pub max_nodes: usize,
 }
 
@@ -541,6 +624,11 @@ impl Default for TrailConfig {
             edge_filter: vec![],
             show_utility_calls: false,
             node_filter: vec![],
+            hide_trivial_accessors: false,
+            hide_generated: false,
+            entry_reachable_only: false,
+            max_paths: None,
+            edge_weight: TrailEdgeWeight::InverseConfidence,
             max_nodes: 500,
         }
     }
@@ -553,6 +641,36 @@ pub struct TrailResult {
     pub depth_map: std::collections::HashMap<NodeId, u32>,
     pub truncated: bool,
     pub omitted_edge_count: u32,
+    /// Nodes dropped because they live in test or bench files under
+    /// `TrailCallerScope::ProductionOnly`.
+    pub pruned_test_node_count: u32,
+    /// Nodes dropped by `TrailConfig::hide_trivial_accessors`.
+    pub pruned_accessor_node_count: u32,
+    /// Nodes dropped by `TrailConfig::hide_generated`.
+    pub pruned_generated_node_count: u32,
+    /// Nodes reached but left out of the result: cut by
+    /// `TrailConfig::max_nodes`, or dropped by `TrailConfig::node_filter` or
+    /// `TrailConfig::entry_reachable_only`.
+    pub omitted_node_count: u32,
+    /// Paths found by `TrailMode::AllPaths`, shortest first, or the single
+    /// path found by `TrailMode::WeightedPath`.
+    pub paths: Vec<TrailPath>,
+    /// Expansion stopped because the query's deadline passed or it was
+    /// cancelled; `truncated` is also set.
+    pub truncated_by_timeout: bool,
+    /// Edges left out because a returned edge describes the same
+    /// relationship, keyed by the returned edge.
+    pub collapsed_edge_ids: std::collections::HashMap<EdgeId, Vec<EdgeId>>,
+}
+
+/// One root-to-target route found by `TrailMode::AllPaths` or
+/// `TrailMode::WeightedPath`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct TrailPath {
+    /// Nodes in order from the root to the target.
+    pub node_ids: Vec<NodeId>,
+    /// One edge per hop: `edge_ids[i]` joins `node_ids[i]` to `node_ids[i + 1]`.
+    pub edge_ids: Vec<EdgeId>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
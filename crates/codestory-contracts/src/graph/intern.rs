@@ -0,0 +1,83 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// Pool of shared strings for names that repeat across many nodes.
+///
+/// Serialized names, qualified names, and canonical ids recur heavily in large
+/// graphs (`new`, `fmt`, module paths), and a node's qualified name often equals
+/// its canonical id. Interning keeps one allocation per distinct string for as
+/// long as any holder keeps its `Arc`.
+#[derive(Debug, Default)]
+pub struct StringInterner {
+    strings: HashSet<Arc<str>>,
+    bytes: usize,
+}
+
+impl StringInterner {
+    /// The pooled copy of `value`, added to the pool if it is new.
+    pub fn intern(&mut self, value: &str) -> Arc<str> {
+        if let Some(existing) = self.strings.get(value) {
+            return Arc::clone(existing);
+        }
+        let interned: Arc<str> = Arc::from(value);
+        self.bytes += interned.len();
+        self.strings.insert(Arc::clone(&interned));
+        interned
+    }
+
+    /// Drop `value` from the pool when the caller's handle is the last one
+    /// outside it.
+    pub fn release(&mut self, value: &Arc<str>) {
+        if Arc::strong_count(value) == 2 && self.strings.remove(value) {
+            self.bytes -= value.len();
+        }
+    }
+
+    /// Number of distinct strings in the pool.
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+
+    /// Bytes of string data held by the pool, counting each string once.
+    pub fn bytes(&self) -> usize {
+        self.bytes
+    }
+
+    pub fn clear(&mut self) {
+        self.strings.clear();
+        self.bytes = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shares_repeated_strings_until_released() {
+        let mut interner = StringInterner::default();
+        let first = interner.intern("crate::io::Reader::new");
+        let second = interner.intern("crate::io::Reader::new");
+        let other = interner.intern("fmt");
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(interner.len(), 2);
+        assert_eq!(interner.bytes(), "crate::io::Reader::new".len() + 3);
+
+        interner.release(&first);
+        drop(first);
+        assert_eq!(interner.len(), 2);
+        interner.release(&second);
+        drop(second);
+        assert_eq!(interner.len(), 1);
+        assert_eq!(interner.bytes(), 3);
+
+        interner.release(&other);
+        drop(other);
+        assert!(interner.is_empty());
+        assert_eq!(interner.bytes(), 0);
+    }
+}
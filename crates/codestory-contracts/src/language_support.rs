@@ -184,6 +184,18 @@ const CARGO_MANIFEST_UNSUPPORTED_SHAPES: &[&str] = &[
     "Target-scoped dependency tables, workspace dependency tables, dependency subtables, features, patch, and replace tables are not semantic proof.",
     "The collector records exact source anchors for selected manifest keys only; it does not validate Cargo behavior.",
 ];
+const PROTOBUF_NODE_KINDS: &[NodeKind] = &[
+    NodeKind::NAMESPACE,
+    NodeKind::CLASS,
+    NodeKind::ENUM,
+    NodeKind::INTERFACE,
+    NodeKind::METHOD,
+];
+const PROTOBUF_UNSUPPORTED_SHAPES: &[&str] = &[
+    "Imports, options, extensions, and custom code generator plugins are not interpreted.",
+    "Definitions are keyed by unqualified name, so same-named messages or services in different packages share one node.",
+    "Generated code is linked by naming convention only; wire compatibility and runtime transport behavior are not proven.",
+];
 const GENERIC_STRUCTURAL_NODE_KINDS: &[NodeKind] =
     &[NodeKind::MODULE, NodeKind::FUNCTION, NodeKind::ANNOTATION];
 const GENERIC_STRUCTURAL_UNSUPPORTED_SHAPES: &[&str] = &[
@@ -241,6 +253,18 @@ pub const STRUCTURAL_SOURCE_PROOF_CONTRACTS: &[StructuralSourceProofContract] =
         claim_boundary: "structural exact-source proof only; not parser-backed graph parity, typed semantic resolution, not semantic dependency proof, Cargo resolution, or packet semantic-proof admission",
         semantic_proof_allowed: false,
     },
+    StructuralSourceProofContract {
+        collector_name: "protobuf",
+        path_pattern: "**/*.proto",
+        emitted_node_kinds: PROTOBUF_NODE_KINDS,
+        source_span: "1-based exact source span for a package, message, enum, service, or rpc name",
+        evidence_tier: PacketEvidenceTierDto::StructuralText,
+        resolution: PacketEvidenceResolutionDto::SourceRangeOnly,
+        confidence: 1.0,
+        unsupported_shape_notes: PROTOBUF_UNSUPPORTED_SHAPES,
+        claim_boundary: "structural exact-source proof only; not parser-backed graph parity, generated-code correctness, gRPC runtime behavior, or packet semantic-proof admission",
+        semantic_proof_allowed: false,
+    },
     StructuralSourceProofContract {
         collector_name: "markdown",
         path_pattern: "**/*.{md,markdown,mdx}",
@@ -359,6 +383,7 @@ pub const LANGUAGE_SUPPORT_PROFILES: &[LanguageSupportProfile] = &[
     structural_profile("html", &["html", "htm"]),
     structural_profile("css", &["css"]),
     structural_profile("sql", &["sql"]),
+    structural_profile("protobuf", &["proto"]),
     structural_profile("markdown", &["md", "markdown", "mdx"]),
     structural_profile("yaml", &["yml", "yaml"]),
     structural_profile("toml", &["toml"]),
@@ -918,7 +943,15 @@ mod tests {
                 .contains("not semantic dependency proof")
         );
 
-        for collector_name in ["markdown", "yaml", "toml", "json", "shell", "powershell"] {
+        for collector_name in [
+            "protobuf",
+            "markdown",
+            "yaml",
+            "toml",
+            "json",
+            "shell",
+            "powershell",
+        ] {
             let contract = STRUCTURAL_SOURCE_PROOF_CONTRACTS
                 .iter()
                 .find(|contract| contract.collector_name == collector_name)
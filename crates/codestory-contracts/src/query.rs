@@ -4,7 +4,7 @@
 //! remain responsible for resolving symbols, applying defaults, enforcing
 //! limits, and reporting partial evidence when the query cannot be satisfied.
 
-use crate::api::{NodeKind, TrailDirection};
+use crate::api::{EdgeKind, NodeKind, TrailDirection};
 use serde::{Deserialize, Serialize};
 
 /// Ordered pipeline of graph-query operations.
@@ -25,6 +25,7 @@ pub enum GraphQueryOperation {
     Search(SearchQuery),
     Filter(FilterQuery),
     Limit(LimitQuery),
+    Match(MatchQuery),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -55,3 +56,14 @@ pub struct FilterQuery {
 pub struct LimitQuery {
     pub count: u32,
 }
+
+/// Nodes with an edge to another node, both sides narrowed by kind and by a
+/// qualified-name prefix such as a module path.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MatchQuery {
+    pub from: Option<String>,
+    pub from_kind: Option<NodeKind>,
+    pub edge: Option<EdgeKind>,
+    pub to: Option<String>,
+    pub to_kind: Option<NodeKind>,
+}
@@ -5,5 +5,8 @@
 //! evidence: filtering, truncation, and speculative-edge hiding affect what is
 //! shown, not what exists in the underlying graph.
 
-pub use crate::api::{TrailConfigDto, TrailContextDto, TrailFilterOptionsDto};
-pub use crate::graph::{TrailCallerScope, TrailConfig, TrailDirection, TrailMode, TrailResult};
+pub use crate::api::{TrailConfigDto, TrailContextDto, TrailFilterOptionsDto, TrailPathDto};
+pub use crate::graph::{
+    TrailCallerScope, TrailConfig, TrailDirection, TrailEdgeWeight, TrailMode, TrailPath,
+    TrailResult,
+};
@@ -143,6 +143,24 @@ impl WorkspaceInventory {
     }
 }
 
+impl RefreshPlan {
+    /// Move the `priority` files to the front of `files_to_index`, in
+    /// `priority` order. Other files keep their order, and priority files the
+    /// plan does not schedule are ignored.
+    pub fn prioritize(&mut self, priority: &[PathBuf]) {
+        if priority.is_empty() {
+            return;
+        }
+        let rank = priority
+            .iter()
+            .enumerate()
+            .map(|(rank, path)| (path.as_path(), rank))
+            .collect::<HashMap<_, _>>();
+        self.files_to_index
+            .sort_by_key(|path| rank.get(path.as_path()).copied().unwrap_or(usize::MAX));
+    }
+}
+
 pub type BuildMode = RefreshMode;
 pub type RefreshExecutionPlan = RefreshPlan;
 pub type RefreshInfo = RefreshPlan;
@@ -186,6 +186,30 @@
   attr (@target.node -> @decorator.node) kind = "CALL"
 }
 
+;; Command-line command decorators (`@click.command()`, `@app.command()`,
+;; `@cli.command`), recorded as annotations on the function.
+(decorated_definition
+  (decorator
+    [
+      (call function: (attribute attribute: (identifier) @command_name) @callee)
+      (attribute attribute: (identifier) @command_name) @callee
+    ]) @decorator
+  definition: (function_definition name: (identifier) @target)
+  (#eq? @command_name "command"))
+{
+  node @decorator.node
+  attr (@decorator.node) kind = "ANNOTATION"
+  attr (@decorator.node) name = (source-text @callee)
+  attr (@decorator.node) start_row = (start-row @callee)
+  attr (@decorator.node) start_col = (start-column @callee)
+  attr (@decorator.node) end_row = (end-row @callee)
+  attr (@decorator.node) end_col = (end-column @callee)
+
+  edge @target.node -> @decorator.node
+  attr (@target.node -> @decorator.node) kind = "ANNOTATION_USAGE"
+  attr (@target.node -> @decorator.node) line = (start-row @decorator)
+}
+
 ;; Imports
 (import_from_statement
   module_name: (dotted_name) @module)
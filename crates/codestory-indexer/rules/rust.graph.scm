@@ -364,6 +364,52 @@
   attr (@caller.node -> @macro.node) line = (start-row @call)
 }
 
+;; Test attributes (`#[test]`, `#[tokio::test]`, `#[rstest]`) on the
+;; function they precede, directly or past one other attribute.
+(_
+  (attribute_item
+    (attribute [(identifier) (scoped_identifier)] @attr))
+  .
+  (function_item
+    name: (identifier) @fn_name)
+  (#match? @attr "^(.*::)?(test|rstest)$"))
+{
+  node @attr.node
+  attr (@attr.node) kind = "ANNOTATION"
+  attr (@attr.node) name = (source-text @attr)
+  attr (@attr.node) start_row = (start-row @attr)
+  attr (@attr.node) start_col = (start-column @attr)
+  attr (@attr.node) end_row = (end-row @attr)
+  attr (@attr.node) end_col = (end-column @attr)
+
+  edge @fn_name.node -> @attr.node
+  attr (@fn_name.node -> @attr.node) kind = "ANNOTATION_USAGE"
+  attr (@fn_name.node -> @attr.node) line = (start-row @attr)
+}
+
+(_
+  (attribute_item
+    (attribute [(identifier) (scoped_identifier)] @attr))
+  .
+  (attribute_item)
+  .
+  (function_item
+    name: (identifier) @fn_name)
+  (#match? @attr "^(.*::)?(test|rstest)$"))
+{
+  node @attr.node
+  attr (@attr.node) kind = "ANNOTATION"
+  attr (@attr.node) name = (source-text @attr)
+  attr (@attr.node) start_row = (start-row @attr)
+  attr (@attr.node) start_col = (start-column @attr)
+  attr (@attr.node) end_row = (end-row @attr)
+  attr (@attr.node) end_col = (end-column @attr)
+
+  edge @fn_name.node -> @attr.node
+  attr (@fn_name.node -> @attr.node) kind = "ANNOTATION_USAGE"
+  attr (@fn_name.node -> @attr.node) line = (start-row @attr)
+}
+
 ;; Lambda assignment
 (let_declaration
   pattern: (identifier) @name
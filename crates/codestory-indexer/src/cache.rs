@@ -7,7 +7,7 @@ use codestory_store::FileInfo;
 use serde::{Deserialize, Serialize};
 use std::path::{Component, Path, PathBuf};
 
-const INDEX_ARTIFACT_CACHE_VERSION: u32 = 2;
+const INDEX_ARTIFACT_CACHE_VERSION: u32 = 5;
 const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
 const FNV_PRIME: u64 = 0x00000100000001B3;
 
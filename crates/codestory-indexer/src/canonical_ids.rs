@@ -0,0 +1,113 @@
+//! Canonical symbol IDs that name a symbol the same way on every machine and
+//! in every run.
+//!
+//! Node ids and the grouping keys behind them embed the absolute file path, so
+//! they change when the checkout moves. The canonical ID stored for a code
+//! symbol instead follows this scheme:
+//!
+//! - `sym:<language>:<qualified name>` for named symbols;
+//! - `#<hash>` appended for callables, hashing the parameter list so
+//!   overloads stay apart;
+//! - `sym:<language>:<relative path>:<qualified name>` for symbols local to a
+//!   file, using the workspace-relative path with `/` separators.
+
+use crate::{WorkspaceIndexer, generate_id, line_col_to_byte_offset, short_member_name};
+use codestory_contracts::graph::{Node, NodeKind};
+use std::path::Path;
+
+pub(crate) const SYMBOL_CANONICAL_ID_PREFIX: &str = "sym:";
+
+/// Replace the path-bound grouping keys left by canonicalization with
+/// symbol canonical IDs. Framework, impl-anchor, and other tagged canonical
+/// IDs are left alone.
+pub(crate) fn assign_symbol_canonical_ids(
+    nodes: &mut [Node],
+    root: &Path,
+    full_path: &Path,
+    language: &str,
+    source: &str,
+) {
+    let grouping_prefix = format!("{}:", WorkspaceIndexer::file_identity_path(full_path));
+    let relative_path = full_path
+        .strip_prefix(root)
+        .unwrap_or(full_path)
+        .to_string_lossy()
+        .replace('\\', "/");
+    for node in nodes {
+        if matches!(node.kind, NodeKind::FILE | NodeKind::UNKNOWN)
+            || !node
+                .canonical_id
+                .as_deref()
+                .is_some_and(|value| value.starts_with(&grouping_prefix))
+        {
+            continue;
+        }
+        node.canonical_id = Some(symbol_canonical_id(node, language, &relative_path, source));
+    }
+}
+
+pub(crate) fn symbol_canonical_id(
+    node: &Node,
+    language: &str,
+    relative_path: &str,
+    source: &str,
+) -> String {
+    let qualified_name = node
+        .qualified_name
+        .as_deref()
+        .unwrap_or(&node.serialized_name);
+    let mut canonical_id = if matches!(node.kind, NodeKind::VARIABLE | NodeKind::TYPE_PARAMETER) {
+        format!("{SYMBOL_CANONICAL_ID_PREFIX}{language}:{relative_path}:{qualified_name}")
+    } else {
+        format!("{SYMBOL_CANONICAL_ID_PREFIX}{language}:{qualified_name}")
+    };
+    if matches!(
+        node.kind,
+        NodeKind::FUNCTION | NodeKind::METHOD | NodeKind::MACRO
+    ) && let Some(parameters) = declared_parameter_list(node, source)
+    {
+        canonical_id.push_str(&format!("#{:016x}", generate_id(&parameters) as u64));
+    }
+    canonical_id
+}
+
+/// The parameter list following the symbol's name inside its declaration
+/// span, with whitespace collapsed so formatting changes keep the hash.
+fn declared_parameter_list(node: &Node, source: &str) -> Option<String> {
+    let start = line_col_to_byte_offset(source, node.start_line?, node.start_col?)?;
+    let end = line_col_to_byte_offset(source, node.end_line?, node.end_col?)?;
+    let span = source.get(start..end.max(start))?;
+    let name = short_member_name(&node.serialized_name);
+    if name.is_empty() {
+        return None;
+    }
+    let after_name = span.find(name)? + name.len();
+    let mut chars = span[after_name..].char_indices().peekable();
+    let mut generic_depth = 0usize;
+    let open = loop {
+        let (index, ch) = chars.next()?;
+        match ch {
+            '<' => generic_depth += 1,
+            '>' if generic_depth > 0 => generic_depth -= 1,
+            '(' if generic_depth == 0 => break after_name + index,
+            _ if generic_depth > 0 || ch.is_whitespace() => {}
+            _ => return None,
+        }
+    };
+
+    let mut depth = 0usize;
+    for (index, ch) in span[open..].char_indices() {
+        match ch {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    let parameters = &span[open..=open + index];
+                    return Some(parameters.split_whitespace().collect::<Vec<_>>().join(" "));
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
@@ -0,0 +1,245 @@
+//! `TODO`, `FIXME`, and `HACK` comments.
+//!
+//! Files with a tree-sitter grammar are parsed and only their comment nodes
+//! are searched, so identifiers and string contents, even ones that look like
+//! a comment, are never mistaken for markers. Other files are read line by
+//! line: a tag counts only after the line's first comment opener for the
+//! file's language.
+
+use crate::get_language_for_ext;
+use codestory_store::CodeMarker;
+use std::path::Path;
+use tree_sitter::{Node, Parser};
+
+/// Tags recognized as markers.
+pub const CODE_MARKER_TAGS: [&str; 3] = ["TODO", "FIXME", "HACK"];
+
+/// Longest marker text kept; the rest of a long comment is cut.
+const CODE_MARKER_MAX_TEXT_CHARS: usize = 240;
+/// Markers kept per file, so generated files cannot flood the table.
+const CODE_MARKER_MAX_PER_FILE: usize = 500;
+
+/// Markers in `source`, in line order.
+pub fn extract_code_markers(path: &Path, source: &str) -> Vec<CodeMarker> {
+    parsed_comment_markers(path, source).unwrap_or_else(|| line_comment_markers(path, source))
+}
+
+/// Markers in the comment nodes of `source`, or `None` when the file has no
+/// grammar or does not parse.
+fn parsed_comment_markers(path: &Path, source: &str) -> Option<Vec<CodeMarker>> {
+    let config = get_language_for_ext(path.extension()?.to_str()?)?;
+    let mut parser = Parser::new();
+    parser.set_language(&config.language).ok()?;
+    let tree = parser.parse(source, None)?;
+    let mut markers = Vec::new();
+    let mut cursor = tree.walk();
+    'walk: loop {
+        let node = cursor.node();
+        // A comment node is not entered: doc comment children repeat its text.
+        let is_comment = node.kind().contains("comment");
+        if is_comment {
+            push_comment_markers(node, source, &mut markers);
+            if markers.len() >= CODE_MARKER_MAX_PER_FILE {
+                break;
+            }
+        }
+        if !is_comment && cursor.goto_first_child() {
+            continue;
+        }
+        while !cursor.goto_next_sibling() {
+            if !cursor.goto_parent() {
+                break 'walk;
+            }
+        }
+    }
+    markers.truncate(CODE_MARKER_MAX_PER_FILE);
+    Some(markers)
+}
+
+/// One marker for each line of the comment `node` that holds a tag.
+fn push_comment_markers(node: Node<'_>, source: &str, markers: &mut Vec<CodeMarker>) {
+    let mut offset = node.start_byte();
+    let lines = source[node.byte_range()].split_inclusive('\n');
+    for (row, line) in (node.start_position().row..).zip(lines) {
+        if let Some((tag_start, tag)) = find_tag(line) {
+            let line_start = source[..offset + tag_start]
+                .rfind('\n')
+                .map_or(0, |at| at + 1);
+            markers.push(CodeMarker {
+                line: row as u32 + 1,
+                column: source[line_start..offset + tag_start].chars().count() as u32 + 1,
+                tag: tag.to_string(),
+                text: marker_text(line[tag_start + tag.len()..].trim_end_matches(['\r', '\n'])),
+            });
+        }
+        offset += line.len();
+    }
+}
+
+/// Markers found after each line's first comment opener, for files without a
+/// grammar.
+fn line_comment_markers(path: &Path, source: &str) -> Vec<CodeMarker> {
+    let openers = comment_openers(path);
+    let mut markers = Vec::new();
+    let mut in_block_comment = false;
+    for (index, line) in source.lines().enumerate() {
+        let comment_start = if in_block_comment {
+            Some(0)
+        } else {
+            openers.iter().filter_map(|opener| line.find(opener)).min()
+        };
+        let block_open = line.rfind("/*");
+        let block_close = line.rfind("*/");
+        in_block_comment = match (block_open, block_close) {
+            (Some(open), Some(close)) => open > close,
+            (Some(_), None) => openers.contains(&"/*"),
+            (None, Some(_)) => false,
+            (None, None) => in_block_comment,
+        };
+        let Some(comment_start) = comment_start else {
+            continue;
+        };
+        let Some((tag_start, tag)) = find_tag(&line[comment_start..]) else {
+            continue;
+        };
+        let tag_start = comment_start + tag_start;
+        markers.push(CodeMarker {
+            line: index as u32 + 1,
+            column: line[..tag_start].chars().count() as u32 + 1,
+            tag: tag.to_string(),
+            text: marker_text(&line[tag_start + tag.len()..]),
+        });
+        if markers.len() >= CODE_MARKER_MAX_PER_FILE {
+            break;
+        }
+    }
+    markers
+}
+
+pub(crate) fn comment_openers(path: &Path) -> &'static [&'static str] {
+    let ext = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(str::to_ascii_lowercase)
+        .unwrap_or_default();
+    match ext.as_str() {
+        "rs" | "c" | "h" | "cc" | "cpp" | "cxx" | "hpp" | "hh" | "java" | "kt" | "kts" | "js"
+        | "jsx" | "mjs" | "cjs" | "ts" | "tsx" | "go" | "cs" | "swift" | "scala" | "dart" => {
+            &["//", "/*"]
+        }
+        "php" => &["//", "/*", "#"],
+        "py" | "pyi" | "rb" | "sh" | "bash" | "zsh" | "yml" | "yaml" | "toml" | "r" | "pl"
+        | "ps1" | "psm1" | "cmake" | "mk" => &["#"],
+        "sql" | "lua" | "hs" => &["--"],
+        "html" | "htm" | "xml" => &["<!--"],
+        "vue" | "svelte" => &["<!--", "//", "/*"],
+        _ => &["//", "/*", "#", "--", "<!--"],
+    }
+}
+
+/// The first marker tag in `comment` that stands as its own word.
+fn find_tag(comment: &str) -> Option<(usize, &'static str)> {
+    CODE_MARKER_TAGS
+        .iter()
+        .filter_map(|tag| {
+            comment.match_indices(tag).find_map(|(start, _)| {
+                let before = comment[..start].chars().next_back();
+                let after = comment[start + tag.len()..].chars().next();
+                let is_word_char = |ch: char| ch.is_alphanumeric() || ch == '_';
+                (!before.is_some_and(is_word_char) && !after.is_some_and(is_word_char))
+                    .then_some((start, *tag))
+            })
+        })
+        .min_by_key(|(start, _)| *start)
+}
+
+/// Comment text after a tag, without an `(owner)` note, separator, or block
+/// comment closer.
+fn marker_text(rest: &str) -> String {
+    let mut text = rest.trim_start();
+    if text.starts_with('(')
+        && let Some(close) = text.find(')')
+    {
+        text = text[close + 1..].trim_start();
+    }
+    let text = text
+        .trim_start_matches([':', '-', ' '])
+        .trim_end()
+        .trim_end_matches("-->")
+        .trim_end_matches("*/")
+        .trim_end();
+    text.chars().take(CODE_MARKER_MAX_TEXT_CHARS).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(path: &str, source: &str) -> Vec<(u32, u32, String, String)> {
+        extract_code_markers(Path::new(path), source)
+            .into_iter()
+            .map(|marker| (marker.line, marker.column, marker.tag, marker.text))
+            .collect()
+    }
+
+    #[test]
+    fn extracts_tags_after_the_comment_opener() {
+        let source = "\
+fn load() {
+    // TODO(ana): retry on timeout
+    let todo_list = \"TODO not a comment\";
+    /* FIXME - leaks the handle */
+}
+/*
+ * HACK: works around the old parser
+ */
+// TODOS are not markers
+";
+        assert_eq!(
+            summary("src/lib.rs", source),
+            vec![
+                (2, 8, "TODO".to_string(), "retry on timeout".to_string()),
+                (4, 8, "FIXME".to_string(), "leaks the handle".to_string()),
+                (
+                    7,
+                    4,
+                    "HACK".to_string(),
+                    "works around the old parser".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn uses_the_comment_syntax_of_the_file() {
+        assert_eq!(
+            summary("app.py", "x = 1  # FIXME: off by one\ny = '// TODO'\n"),
+            vec![(1, 10, "FIXME".to_string(), "off by one".to_string())]
+        );
+        assert_eq!(
+            summary("schema.sql", "SELECT 1; -- HACK until v2\n"),
+            vec![(1, 14, "HACK".to_string(), "until v2".to_string())]
+        );
+    }
+
+    #[test]
+    fn ignores_comment_openers_inside_strings() {
+        let source = "\
+fn load() {
+    let url = \"https://example.com // TODO not a comment\";
+    let note = \"/* FIXME still a string */\"; /* HACK: real one */
+    let raw = r#\"
+        # TODO in a raw string
+    \"#;
+}
+";
+        assert_eq!(
+            summary("src/lib.rs", source),
+            vec![(3, 49, "HACK".to_string(), "real one".to_string())]
+        );
+        assert_eq!(
+            summary("app.ts", "const path = \"a // TODO b\"; // FIXME: escape\n"),
+            vec![(1, 32, "FIXME".to_string(), "escape".to_string())]
+        );
+    }
+}
@@ -2,7 +2,7 @@ use anyhow::{Context, Result};
 use serde::Deserialize;
 use std::collections::HashMap;
 use std::fs;
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct CompileCommand {
@@ -61,6 +61,77 @@ pub struct CompilationInfo {
     pub other_flags: Vec<String>,
 }
 
+/// Where an `#include` was found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IncludeKind {
+    /// Next to the including file or on a `-I` path.
+    Project,
+    /// On an `-isystem` path.
+    System,
+}
+
+impl IncludeKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Project => "project",
+            Self::System => "system",
+        }
+    }
+}
+
+impl CompilationInfo {
+    /// Find the header an `#include` directive names, searching the way the
+    /// compiler does: the including file's directory for quoted includes,
+    /// then `-I` paths, then `-isystem` paths. The path returned has `.` and
+    /// `..` folded away, so it names the header the way discovery does.
+    pub fn resolve_include(
+        &self,
+        including_file: &Path,
+        header: &str,
+        quoted: bool,
+    ) -> Option<(PathBuf, IncludeKind)> {
+        let local = quoted
+            .then(|| including_file.parent())
+            .flatten()
+            .map(|dir| (dir.to_path_buf(), IncludeKind::Project));
+        local
+            .into_iter()
+            .chain(
+                self.include_paths
+                    .iter()
+                    .map(|dir| (dir.clone(), IncludeKind::Project)),
+            )
+            .chain(
+                self.system_include_paths
+                    .iter()
+                    .map(|dir| (dir.clone(), IncludeKind::System)),
+            )
+            .map(|(dir, kind)| (dir.join(header.replace('\\', "/")), kind))
+            .find(|(path, _)| path.is_file())
+            .map(|(path, kind)| (normalize_lexical_path(&path), kind))
+    }
+}
+
+/// `path` with `.` components dropped and each `..` folded into the
+/// component before it.
+fn normalize_lexical_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !normalized.pop() {
+                    normalized.push(component.as_os_str());
+                }
+            }
+            Component::Prefix(_) | Component::RootDir | Component::Normal(_) => {
+                normalized.push(component.as_os_str());
+            }
+        }
+    }
+    normalized
+}
+
 pub struct CompilationDatabase {
     commands: Vec<CompileCommand>,
     index: HashMap<PathBuf, usize>,
@@ -355,4 +426,69 @@ mod tests {
         );
         assert_eq!(info.defines.get("BAR"), Some(&None));
     }
+
+    #[test]
+    fn test_resolve_include_searches_local_then_project_then_system_paths() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let write = |relative: &str| {
+            let path = dir.path().join(relative);
+            fs::create_dir_all(path.parent().expect("parent")).expect("create dir");
+            fs::write(&path, "").expect("write header");
+            path
+        };
+        let local = write("src/config.h");
+        let project = write("include/widget.h");
+        let system = write("sysroot/vector");
+        write("sysroot/config.h");
+        let info = CompilationInfo {
+            include_paths: vec![dir.path().join("include")],
+            system_include_paths: vec![dir.path().join("sysroot")],
+            ..Default::default()
+        };
+        let main = dir.path().join("src/main.cpp");
+
+        assert_eq!(
+            info.resolve_include(&main, "config.h", true),
+            Some((local, IncludeKind::Project))
+        );
+        assert_eq!(
+            info.resolve_include(&main, "config.h", false),
+            Some((dir.path().join("sysroot/config.h"), IncludeKind::System))
+        );
+        assert_eq!(
+            info.resolve_include(&main, "widget.h", false),
+            Some((project, IncludeKind::Project))
+        );
+        assert_eq!(
+            info.resolve_include(&main, "vector", false),
+            Some((system, IncludeKind::System))
+        );
+        assert_eq!(info.resolve_include(&main, "missing.h", true), None);
+    }
+
+    #[test]
+    fn test_resolve_include_folds_relative_components() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let header = dir.path().join("include/widget.h");
+        fs::create_dir_all(header.parent().expect("parent")).expect("create dir");
+        fs::write(&header, "").expect("write header");
+        let info = CompilationInfo {
+            include_paths: vec![dir.path().join("src/./../include/.")],
+            ..Default::default()
+        };
+        let main = dir.path().join("src/main.cpp");
+
+        for (spelling, quoted) in [
+            ("../include/widget.h", true),
+            ("./../include/./widget.h", true),
+            ("..\\include\\widget.h", true),
+            ("widget.h", false),
+        ] {
+            assert_eq!(
+                info.resolve_include(&main, spelling, quoted),
+                Some((header.clone(), IncludeKind::Project)),
+                "{spelling}"
+            );
+        }
+    }
 }
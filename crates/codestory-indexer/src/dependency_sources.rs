@@ -0,0 +1,433 @@
+//! Dependency sources indexed into a separate read-only store so calls into
+//! third-party code can resolve past the workspace boundary.
+//!
+//! Sources come from what the workspace declares:
+//!
+//! - Cargo registry packages listed in `Cargo.lock`, read from the unpacked
+//!   sources under `$CARGO_HOME/registry/src`;
+//! - `package.json` dependencies, read as `.d.ts` declarations from
+//!   `node_modules`;
+//! - JDK classes from an extracted `src` directory under `$JAVA_HOME`.
+//!   The packed `lib/src.zip` is not read.
+//!
+//! Resolution is one hop: the matched dependency symbol and its file node
+//! are copied into workspace storage, not the rest of the dependency graph.
+
+use crate::{WorkspaceIndexer, short_member_name};
+use anyhow::{Context, Result};
+use codestory_contracts::events::EventBus;
+use codestory_contracts::graph::{EdgeId, EdgeKind, NodeId, NodeKind};
+use codestory_store::{DependencyResolution, Store as Storage};
+use serde::Deserialize;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// Files indexed per dependency before the rest are skipped.
+const DEPENDENCY_FILE_LIMIT: usize = 400;
+/// JDK packages read from an extracted source tree.
+const JDK_PACKAGES: &[&str] = &["java/lang", "java/util", "java/io"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DependencyEcosystem {
+    Cargo,
+    Npm,
+    Jdk,
+}
+
+/// One declared dependency and the source files indexed for it.
+#[derive(Debug, Clone)]
+pub struct DependencySource {
+    pub ecosystem: DependencyEcosystem,
+    pub name: String,
+    pub version: Option<String>,
+    pub root: PathBuf,
+    pub files: Vec<PathBuf>,
+}
+
+/// Toolchain directories dependency sources are read from.
+#[derive(Debug, Clone, Default)]
+pub struct DependencySourceLocations {
+    pub cargo_home: Option<PathBuf>,
+    pub java_home: Option<PathBuf>,
+}
+
+impl DependencySourceLocations {
+    /// `$CARGO_HOME` (or `~/.cargo`) and `$JAVA_HOME`.
+    pub fn from_env() -> Self {
+        let home = std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE"));
+        Self {
+            cargo_home: std::env::var_os("CARGO_HOME")
+                .map(PathBuf::from)
+                .or_else(|| home.map(|home| PathBuf::from(home).join(".cargo"))),
+            java_home: std::env::var_os("JAVA_HOME").map(PathBuf::from),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct CargoLock {
+    #[serde(default)]
+    package: Vec<CargoLockPackage>,
+}
+
+#[derive(Deserialize)]
+struct CargoLockPackage {
+    name: String,
+    version: String,
+    source: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct PackageJson {
+    version: Option<String>,
+    #[serde(default)]
+    dependencies: BTreeMap<String, serde_json::Value>,
+    #[serde(default, rename = "devDependencies")]
+    dev_dependencies: BTreeMap<String, serde_json::Value>,
+}
+
+/// Dependencies declared by the workspace whose sources exist on disk.
+/// Dependencies without readable sources are left out.
+pub fn discover_dependency_sources(
+    workspace_root: &Path,
+    locations: &DependencySourceLocations,
+) -> Result<Vec<DependencySource>> {
+    let mut sources = Vec::new();
+    if let Some(cargo_home) = locations.cargo_home.as_deref() {
+        sources.extend(cargo_dependency_sources(workspace_root, cargo_home)?);
+    }
+    sources.extend(npm_dependency_sources(workspace_root)?);
+    if let Some(java_home) = locations.java_home.as_deref() {
+        sources.extend(jdk_dependency_source(java_home));
+    }
+    Ok(sources)
+}
+
+fn cargo_dependency_sources(
+    workspace_root: &Path,
+    cargo_home: &Path,
+) -> Result<Vec<DependencySource>> {
+    let lock_path = workspace_root.join("Cargo.lock");
+    if !lock_path.is_file() {
+        return Ok(Vec::new());
+    }
+    let lock: CargoLock = toml::from_str(&std::fs::read_to_string(&lock_path)?)
+        .with_context(|| format!("Failed to parse {}", lock_path.display()))?;
+    let registries = match std::fs::read_dir(cargo_home.join("registry").join("src")) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.is_dir())
+            .collect::<Vec<_>>(),
+        Err(_) => return Ok(Vec::new()),
+    };
+
+    let mut sources = Vec::new();
+    for package in lock.package {
+        if !package
+            .source
+            .as_deref()
+            .is_some_and(|source| source.starts_with("registry+") || source.starts_with("sparse+"))
+        {
+            continue;
+        }
+        let dir_name = format!("{}-{}", package.name, package.version);
+        let Some(root) = registries
+            .iter()
+            .map(|registry| registry.join(&dir_name))
+            .find(|root| root.is_dir())
+        else {
+            continue;
+        };
+        let files = collect_source_files(&root.join("src"), |path| {
+            path.extension().is_some_and(|ext| ext == "rs")
+        });
+        if files.is_empty() {
+            continue;
+        }
+        sources.push(DependencySource {
+            ecosystem: DependencyEcosystem::Cargo,
+            name: package.name,
+            version: Some(package.version),
+            root,
+            files,
+        });
+    }
+    Ok(sources)
+}
+
+fn npm_dependency_sources(workspace_root: &Path) -> Result<Vec<DependencySource>> {
+    let manifest_path = workspace_root.join("package.json");
+    if !manifest_path.is_file() {
+        return Ok(Vec::new());
+    }
+    let manifest: PackageJson = serde_json::from_str(&std::fs::read_to_string(&manifest_path)?)
+        .with_context(|| format!("Failed to parse {}", manifest_path.display()))?;
+
+    let mut sources = Vec::new();
+    for name in manifest
+        .dependencies
+        .keys()
+        .chain(manifest.dev_dependencies.keys())
+        .collect::<BTreeSet<_>>()
+    {
+        let root = workspace_root.join("node_modules").join(name);
+        if !root.is_dir() {
+            continue;
+        }
+        let files = collect_source_files(&root, |path| path.to_string_lossy().ends_with(".d.ts"));
+        if files.is_empty() {
+            continue;
+        }
+        let version = std::fs::read_to_string(root.join("package.json"))
+            .ok()
+            .and_then(|manifest| serde_json::from_str::<PackageJson>(&manifest).ok())
+            .and_then(|manifest| manifest.version);
+        sources.push(DependencySource {
+            ecosystem: DependencyEcosystem::Npm,
+            name: name.clone(),
+            version,
+            root,
+            files,
+        });
+    }
+    Ok(sources)
+}
+
+fn jdk_dependency_source(java_home: &Path) -> Option<DependencySource> {
+    let src = [java_home.join("lib").join("src"), java_home.join("src")]
+        .into_iter()
+        .find(|path| path.is_dir())?;
+    // Module layouts keep packages under `java.base/`.
+    let root = if src.join("java.base").is_dir() {
+        src.join("java.base")
+    } else {
+        src
+    };
+    let mut files = Vec::new();
+    for package in JDK_PACKAGES {
+        files.extend(collect_source_files(&root.join(package), |path| {
+            path.extension().is_some_and(|ext| ext == "java")
+        }));
+    }
+    (!files.is_empty()).then(|| DependencySource {
+        ecosystem: DependencyEcosystem::Jdk,
+        name: "jdk".to_string(),
+        version: None,
+        root,
+        files,
+    })
+}
+
+/// Up to [`DEPENDENCY_FILE_LIMIT`] matching files under `root`, in path
+/// order, skipping nested `node_modules` and hidden directories.
+fn collect_source_files(root: &Path, matches: impl Fn(&Path) -> bool) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        let mut entries = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .collect::<Vec<_>>();
+        entries.sort();
+        for path in entries.into_iter().rev() {
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            if path.is_dir() {
+                if name != "node_modules" && !name.starts_with('.') {
+                    pending.push(path);
+                }
+            } else if matches(&path) {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    files.truncate(DEPENDENCY_FILE_LIMIT);
+    files
+}
+
+/// Index `sources` into `storage`, which becomes the dependency storage read
+/// by [`WorkspaceIndexer::with_dependency_storage`]. Returns how many files
+/// were indexed.
+pub fn build_dependency_storage(
+    storage: &mut Storage,
+    sources: &[DependencySource],
+    event_bus: &EventBus,
+) -> Result<usize> {
+    let mut indexed = 0usize;
+    for source in sources {
+        let refresh_info = codestory_workspace::RefreshInfo {
+            mode: codestory_workspace::BuildMode::Incremental,
+            files_to_index: source.files.clone(),
+            files_to_remove: Vec::new(),
+            existing_file_ids: HashMap::new(),
+        };
+        WorkspaceIndexer::new(source.root.clone())
+            .run_incremental(storage, &refresh_info, event_bus, None)
+            .with_context(|| format!("Failed to index dependency {}", source.name))?;
+        indexed += source.files.len();
+    }
+    Ok(indexed)
+}
+
+/// Point unresolved CALL edges in `storage` at callables indexed in
+/// `dependency_storage`. A call resolves only when its name picks out one
+/// dependency callable. Returns how many edges were resolved.
+pub(crate) fn resolve_calls_into_dependencies(
+    storage: &mut Storage,
+    dependency_storage: &Storage,
+) -> Result<usize> {
+    let unresolved = {
+        let mut stmt = storage.get_connection().prepare(
+            "SELECT edge.id, COALESCE(target.qualified_name, target.serialized_name)
+             FROM edge
+             JOIN node target ON target.id = edge.target_node_id
+             WHERE edge.kind = ?1
+               AND edge.resolved_target_node_id IS NULL
+               AND target.kind = ?2",
+        )?;
+        stmt.query_map([EdgeKind::CALL as i32, NodeKind::UNKNOWN as i32], |row| {
+            Ok((EdgeId(row.get(0)?), row.get::<_, String>(1)?))
+        })?
+        .collect::<Result<Vec<_>, _>>()?
+    };
+    if unresolved.is_empty() {
+        return Ok(0);
+    }
+
+    let mut by_short_name = HashMap::<String, Vec<(NodeId, String)>>::new();
+    {
+        let mut stmt = dependency_storage.get_connection().prepare(
+            "SELECT id, COALESCE(qualified_name, serialized_name)
+             FROM node
+             WHERE kind IN (?1, ?2, ?3)",
+        )?;
+        let rows = stmt.query_map(
+            [
+                NodeKind::FUNCTION as i32,
+                NodeKind::METHOD as i32,
+                NodeKind::MACRO as i32,
+            ],
+            |row| Ok((NodeId(row.get(0)?), row.get::<_, String>(1)?)),
+        )?;
+        for row in rows {
+            let (id, name) = row?;
+            by_short_name
+                .entry(short_member_name(&name).to_string())
+                .or_default()
+                .push((id, name));
+        }
+    }
+
+    let mut matched = Vec::new();
+    for (edge_id, callee) in unresolved {
+        let Some(candidates) = by_short_name.get(short_member_name(&callee)) else {
+            continue;
+        };
+        let target = match candidates.as_slice() {
+            [(id, _)] => *id,
+            _ => {
+                let mut qualified = candidates
+                    .iter()
+                    .filter(|(_, name)| name == &callee || name.ends_with(&format!("::{callee}")));
+                match (qualified.next(), qualified.next()) {
+                    (Some((id, _)), None) => *id,
+                    _ => continue,
+                }
+            }
+        };
+        matched.push((edge_id, target));
+    }
+    if matched.is_empty() {
+        return Ok(0);
+    }
+
+    let target_ids = matched
+        .iter()
+        .map(|(_, target)| *target)
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect::<Vec<_>>();
+    let targets = dependency_storage.get_nodes_by_ids(&target_ids)?;
+    let file_ids = targets
+        .values()
+        .filter_map(|node| node.file_node_id)
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect::<Vec<_>>();
+    let files = dependency_storage.get_nodes_by_ids(&file_ids)?;
+
+    let resolutions = matched
+        .into_iter()
+        .filter_map(|(edge_id, target)| {
+            let file = files.get(&targets.get(&target)?.file_node_id?)?;
+            Some(DependencyResolution {
+                edge_id,
+                target,
+                dependency: dependency_label(Path::new(&file.serialized_name)),
+            })
+        })
+        .collect::<Vec<_>>();
+    let nodes = files
+        .into_values()
+        .chain(targets.into_values())
+        .collect::<Vec<_>>();
+    Ok(storage.apply_dependency_resolutions(&nodes, &resolutions)?)
+}
+
+/// `cargo:<name>-<version>`, `npm:<package>`, or `jdk`, read from where the
+/// dependency file sits.
+fn dependency_label(path: &Path) -> String {
+    let components = path
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy().into_owned())
+        .collect::<Vec<_>>();
+    if let Some(index) = components
+        .windows(2)
+        .position(|pair| pair[0] == "registry" && pair[1] == "src")
+        && let Some(package) = components.get(index + 3)
+    {
+        return format!("cargo:{package}");
+    }
+    if let Some(index) = components.iter().rposition(|part| part == "node_modules")
+        && let Some(first) = components.get(index + 1)
+    {
+        return match components.get(index + 2) {
+            Some(second) if first.starts_with('@') => format!("npm:{first}/{second}"),
+            _ => format!("npm:{first}"),
+        };
+    }
+    "jdk".to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dependency_labels_follow_the_package_directory() {
+        assert_eq!(
+            dependency_label(Path::new(
+                "/home/dev/.cargo/registry/src/index.crates.io-6f17d22bba15001f/serde-1.0.200/src/de.rs"
+            )),
+            "cargo:serde-1.0.200"
+        );
+        assert_eq!(
+            dependency_label(Path::new(
+                "/app/node_modules/@types/node/node_modules/undici/index.d.ts"
+            )),
+            "npm:undici"
+        );
+        assert_eq!(
+            dependency_label(Path::new("/app/node_modules/@types/node/fs.d.ts")),
+            "npm:@types/node"
+        );
+        assert_eq!(
+            dependency_label(Path::new(
+                "/opt/jdk/lib/src/java.base/java/lang/String.java"
+            )),
+            "jdk"
+        );
+    }
+}
@@ -0,0 +1,62 @@
+//! Keeps one file's extraction failure from taking down a parse chunk.
+//!
+//! Parse jobs run on rayon workers, where a panic in a tree-sitter query or a
+//! graph rule would otherwise unwind through the whole chunk. Jobs run under
+//! [`catch_index_panic`], which turns the panic into an error naming the
+//! extraction stage the file had reached.
+
+use anyhow::{Result, anyhow};
+use std::any::Any;
+use std::cell::Cell;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+
+thread_local! {
+    static INDEX_STAGE: Cell<&'static str> = const { Cell::new("setup") };
+}
+
+/// Record the extraction stage the current thread's file has entered.
+pub(crate) fn enter_index_stage(stage: &'static str) {
+    INDEX_STAGE.with(|current| current.set(stage));
+}
+
+/// Run `job`, converting a panic into an error that names the last stage
+/// passed to [`enter_index_stage`].
+pub(crate) fn catch_index_panic<T>(job: impl FnOnce() -> Result<T>) -> Result<T> {
+    enter_index_stage("setup");
+    let outcome = catch_unwind(AssertUnwindSafe(job));
+    let stage = INDEX_STAGE.with(Cell::get);
+    outcome.unwrap_or_else(|payload| {
+        Err(anyhow!(
+            "panicked during {stage}: {}",
+            panic_message(payload.as_ref())
+        ))
+    })
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    payload
+        .downcast_ref::<&'static str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("non-string panic payload")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn panics_become_errors_naming_the_stage() {
+        let error = catch_index_panic::<()>(|| {
+            enter_index_stage("edge extraction");
+            panic!("bad capture index {}", 7);
+        })
+        .expect_err("panic is reported");
+        assert_eq!(
+            error.to_string(),
+            "panicked during edge extraction: bad capture index 7"
+        );
+
+        assert_eq!(catch_index_panic(|| Ok(3)).expect("no panic"), 3);
+    }
+}
@@ -0,0 +1,65 @@
+//! Java source roots, read so files without a `package` declaration still get
+//! the package their directory implies.
+//!
+//! Roots come from the project's Java source groups: their source paths,
+//! directory `classpath` entries, and the conventional `src/main/java` and
+//! `src/test/java` directories beside a group's `maven_path` or
+//! `gradle_path` build. The same conventional directories under the project
+//! root are always included.
+
+use codestory_workspace::{Language, LanguageSpecificSettings, WorkspaceManifest};
+use std::path::{Path, PathBuf};
+
+/// Source directories Maven and Gradle builds use by default.
+const CONVENTIONAL_SOURCE_DIRS: &[&str] = &["src/main/java", "src/test/java"];
+
+/// Existing Java source roots for the project at `root`, in manifest order
+/// and without duplicates.
+pub(crate) fn discover_java_source_roots(root: &Path) -> Vec<PathBuf> {
+    let manifest = WorkspaceManifest::open(root.to_path_buf()).ok();
+    let manifest_root = manifest
+        .as_ref()
+        .map(WorkspaceManifest::root_dir)
+        .unwrap_or_else(|| root.to_path_buf());
+    let mut roots = Vec::new();
+    let java_groups = manifest
+        .iter()
+        .flat_map(|manifest| manifest.settings().source_groups.iter())
+        .filter(|group| group.language == Language::Java);
+    for group in java_groups {
+        roots.extend(
+            group
+                .source_paths
+                .iter()
+                .map(|path| manifest_root.join(path)),
+        );
+        if let LanguageSpecificSettings::Java {
+            classpath,
+            maven_path,
+            gradle_path,
+        } = &group.language_specific
+        {
+            roots.extend(classpath.iter().map(|path| manifest_root.join(path)));
+            for build in maven_path.iter().chain(gradle_path) {
+                let build = manifest_root.join(build);
+                // Either the build file itself or the directory holding it.
+                let project = if build.is_file() {
+                    build.parent().map(Path::to_path_buf).unwrap_or(build)
+                } else {
+                    build
+                };
+                roots.extend(CONVENTIONAL_SOURCE_DIRS.iter().map(|dir| project.join(dir)));
+            }
+        }
+    }
+    roots.extend(CONVENTIONAL_SOURCE_DIRS.iter().map(|dir| root.join(dir)));
+    roots.push(root.join("src"));
+
+    let mut unique = Vec::new();
+    for root in roots.into_iter().filter(|root| root.is_dir()) {
+        if !unique.contains(&root) {
+            unique.push(root);
+        }
+    }
+    unique
+}
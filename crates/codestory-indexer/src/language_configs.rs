@@ -3,7 +3,7 @@ use super::{
     GO_GRAPH_QUERY, JAVA_GRAPH_QUERY, JAVASCRIPT_GRAPH_QUERY, KOTLIN_GRAPH_QUERY, LanguageConfig,
     LanguageRuleset, PHP_GRAPH_QUERY, PYTHON_GRAPH_QUERY, RUBY_GRAPH_QUERY, RUST_GRAPH_QUERY,
     RUST_TAGS_QUERY, SWIFT_GRAPH_QUERY, TSX_GRAPH_QUERY, TSX_TAGS_QUERY, TYPESCRIPT_GRAPH_QUERY,
-    TYPESCRIPT_TAGS_QUERY, make_language_config,
+    TYPESCRIPT_TAGS_QUERY, language_plugins, make_language_config,
 };
 use codestory_contracts::language_support::{
     LanguageSupportMode, language_support_profile_for_ext, normalize_extension,
@@ -11,12 +11,16 @@ use codestory_contracts::language_support::{
 
 pub(super) fn get_language_for_ext(ext: &str) -> Option<LanguageConfig> {
     let ext = normalize_extension(ext);
-    let profile = language_support_profile_for_ext(&ext)?;
+    builtin_language_for_ext(&ext).or_else(|| language_plugins::plugin_language_for_ext(&ext))
+}
+
+fn builtin_language_for_ext(ext: &str) -> Option<LanguageConfig> {
+    let profile = language_support_profile_for_ext(ext)?;
     if profile.support_mode != LanguageSupportMode::ParserBackedGraph {
         return None;
     }
 
-    match (profile.language_name, ext.as_str()) {
+    match (profile.language_name, ext) {
         ("python", _) => Some(python()),
         ("java", _) => Some(java()),
         ("rust", _) => Some(rust()),
@@ -37,6 +41,29 @@ pub(super) fn get_language_for_ext(ext: &str) -> Option<LanguageConfig> {
     }
 }
 
+/// Every builtin parser-backed grammar, keyed by the name it is reported
+/// under. TSX is listed apart from TypeScript because it ships its own grammar.
+pub(super) fn builtin_language_configs() -> Vec<(&'static str, LanguageConfig)> {
+    vec![
+        ("python", python()),
+        ("java", java()),
+        ("rust", rust()),
+        ("javascript", javascript()),
+        ("typescript", typescript()),
+        ("tsx", tsx()),
+        ("cpp", cpp()),
+        ("c", c()),
+        ("go", go()),
+        ("ruby", ruby()),
+        ("php", php()),
+        ("csharp", csharp()),
+        ("kotlin", kotlin()),
+        ("swift", swift()),
+        ("dart", dart()),
+        ("bash", bash()),
+    ]
+}
+
 fn python() -> LanguageConfig {
     make_language_config(
         tree_sitter_python::LANGUAGE.into(),
@@ -0,0 +1,260 @@
+//! Tree-sitter grammars loaded at runtime from `languages/<name>/` directories.
+//!
+//! Each plugin directory holds:
+//!
+//! - `plugin.toml` with `extensions = ["ext", ...]` and an optional `symbol`
+//!   naming the grammar constructor (default `tree_sitter_<name>`);
+//! - the compiled grammar as `grammar.so`, `grammar.dylib`, or `grammar.dll`;
+//! - `graph.scm` with tree-sitter-graph rules, and optionally `tags.scm`.
+//!
+//! Plugins register process-wide and only fill extensions that have no
+//! built-in parser. Loaded libraries stay mapped for the life of the process.
+
+use super::{CompiledLanguageRules, LanguageConfig, LanguageRuleset, make_language_config};
+use serde::Deserialize;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::{OnceLock, RwLock};
+use tree_sitter::{Language, Parser};
+
+const PLUGIN_MANIFEST_FILE: &str = "plugin.toml";
+const GRAPH_RULES_FILE: &str = "graph.scm";
+const TAGS_QUERY_FILE: &str = "tags.scm";
+const GRAMMAR_FILES: &[&str] = &["grammar.so", "grammar.dylib", "grammar.dll"];
+
+static LANGUAGE_PLUGINS: RwLock<Vec<&'static LanguagePlugin>> = RwLock::new(Vec::new());
+
+pub(super) struct LanguagePlugin {
+    name: &'static str,
+    extensions: Vec<String>,
+    language: Language,
+    graph_query: &'static str,
+    tags_query: Option<&'static str>,
+    pub(super) rules: OnceLock<Result<CompiledLanguageRules, String>>,
+}
+
+impl fmt::Debug for LanguagePlugin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LanguagePlugin")
+            .field("name", &self.name)
+            .field("extensions", &self.extensions)
+            .finish_non_exhaustive()
+    }
+}
+
+impl LanguagePlugin {
+    pub(super) fn graph_query(&self) -> &'static str {
+        self.graph_query
+    }
+
+    pub(super) fn tags_query(&self) -> Option<&'static str> {
+        self.tags_query
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PluginManifest {
+    extensions: Vec<String>,
+    #[serde(default)]
+    symbol: Option<String>,
+}
+
+/// Outcome of scanning one `languages/` directory.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct LanguagePluginReport {
+    /// Language names registered by this scan.
+    pub loaded: Vec<String>,
+    /// `(plugin directory, reason)` for plugins that could not be loaded.
+    pub failed: Vec<(PathBuf, String)>,
+}
+
+/// Load every plugin under `languages_dir`. A missing directory loads
+/// nothing; plugins whose name is already registered are skipped.
+pub fn register_language_plugins(languages_dir: &Path) -> LanguagePluginReport {
+    let mut report = LanguagePluginReport::default();
+    let Ok(entries) = std::fs::read_dir(languages_dir) else {
+        return report;
+    };
+    let mut plugin_dirs = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.join(PLUGIN_MANIFEST_FILE).is_file())
+        .collect::<Vec<_>>();
+    plugin_dirs.sort();
+    for plugin_dir in plugin_dirs {
+        let Some(name) = plugin_dir
+            .file_name()
+            .and_then(|name| name.to_str())
+            .map(str::to_string)
+        else {
+            continue;
+        };
+        if registered_plugins()
+            .iter()
+            .any(|plugin| plugin.name == name)
+        {
+            continue;
+        }
+        match load_plugin(&plugin_dir, &name) {
+            Ok(plugin) => {
+                LANGUAGE_PLUGINS
+                    .write()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner())
+                    .push(Box::leak(Box::new(plugin)));
+                report.loaded.push(name);
+            }
+            Err(reason) => report.failed.push((plugin_dir, reason)),
+        }
+    }
+    report
+}
+
+/// Parser configuration for `ext` from a registered plugin.
+pub(super) fn plugin_language_for_ext(ext: &str) -> Option<LanguageConfig> {
+    let plugin = registered_plugins().into_iter().find(|plugin| {
+        plugin
+            .extensions
+            .iter()
+            .any(|candidate| candidate.eq_ignore_ascii_case(ext))
+    })?;
+    Some(make_language_config(
+        plugin.language.clone(),
+        plugin.name,
+        plugin.graph_query,
+        plugin.tags_query,
+        LanguageRuleset::Plugin(plugin),
+    ))
+}
+
+/// Extensions claimed by registered plugins.
+pub(super) fn plugin_extensions() -> Vec<String> {
+    registered_plugins()
+        .into_iter()
+        .flat_map(|plugin| plugin.extensions.iter().cloned())
+        .collect()
+}
+
+fn registered_plugins() -> Vec<&'static LanguagePlugin> {
+    LANGUAGE_PLUGINS
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone()
+}
+
+fn load_plugin(plugin_dir: &Path, name: &str) -> Result<LanguagePlugin, String> {
+    let manifest_path = plugin_dir.join(PLUGIN_MANIFEST_FILE);
+    let manifest = std::fs::read_to_string(&manifest_path)
+        .map_err(|error| format!("cannot read {}: {error}", manifest_path.display()))?;
+    let manifest: PluginManifest = toml::from_str(&manifest)
+        .map_err(|error| format!("invalid {}: {error}", manifest_path.display()))?;
+    let extensions = manifest
+        .extensions
+        .iter()
+        .map(|ext| ext.trim().trim_start_matches('.').to_ascii_lowercase())
+        .filter(|ext| !ext.is_empty())
+        .collect::<Vec<_>>();
+    if extensions.is_empty() {
+        return Err(format!("{} lists no extensions", manifest_path.display()));
+    }
+
+    let graph_path = plugin_dir.join(GRAPH_RULES_FILE);
+    let graph_query = std::fs::read_to_string(&graph_path)
+        .map_err(|error| format!("cannot read {}: {error}", graph_path.display()))?;
+    let tags_path = plugin_dir.join(TAGS_QUERY_FILE);
+    let tags_query = if tags_path.is_file() {
+        Some(
+            std::fs::read_to_string(&tags_path)
+                .map_err(|error| format!("cannot read {}: {error}", tags_path.display()))?,
+        )
+    } else {
+        None
+    };
+
+    let grammar_path = GRAMMAR_FILES
+        .iter()
+        .map(|file| plugin_dir.join(file))
+        .find(|path| path.is_file())
+        .ok_or_else(|| format!("no grammar library in {}", plugin_dir.display()))?;
+    let symbol = manifest
+        .symbol
+        .unwrap_or_else(|| format!("tree_sitter_{}", name.replace('-', "_")));
+    let language = load_grammar(&grammar_path, &symbol)?;
+
+    Ok(LanguagePlugin {
+        name: Box::leak(name.to_string().into_boxed_str()),
+        extensions,
+        language,
+        graph_query: Box::leak(graph_query.into_boxed_str()),
+        tags_query: tags_query.map(|query| &*Box::leak(query.into_boxed_str())),
+        rules: OnceLock::new(),
+    })
+}
+
+fn load_grammar(grammar_path: &Path, symbol: &str) -> Result<Language, String> {
+    // SAFETY: the library is a tree-sitter grammar chosen by the workspace
+    // owner; its initializers run like any other native plugin.
+    let library = unsafe { libloading::Library::new(grammar_path) }
+        .map_err(|error| format!("cannot load {}: {error}", grammar_path.display()))?;
+    // SAFETY: `symbol` is assumed to be a tree-sitter grammar constructor,
+    // `const TSLanguage *tree_sitter_<name>(void)`, which takes no arguments
+    // and returns either null or a pointer to static tables inside the
+    // library. A symbol with another signature cannot be detected here.
+    let raw = unsafe {
+        let constructor = library
+            .get::<unsafe extern "C" fn() -> *const tree_sitter::ffi::TSLanguage>(symbol.as_bytes())
+            .map_err(|error| {
+                format!(
+                    "{} does not export {symbol}: {error}",
+                    grammar_path.display()
+                )
+            })?;
+        constructor()
+    };
+    if raw.is_null() {
+        return Err(format!(
+            "{} returned no grammar from {symbol}",
+            grammar_path.display()
+        ));
+    }
+    // SAFETY: `raw` is non-null and points at the grammar tables, which stay
+    // loaded because the library is never unloaded below.
+    let language = unsafe { Language::from_raw(raw) };
+    Parser::new().set_language(&language).map_err(|error| {
+        format!(
+            "{} has an incompatible grammar: {error}",
+            grammar_path.display()
+        )
+    })?;
+    // The grammar's tables live in the library, so it must never unload.
+    std::mem::forget(library);
+    Ok(language)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plugins_without_a_grammar_library_are_reported_and_skipped() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let plugin_dir = dir.path().join("toy");
+        std::fs::create_dir_all(&plugin_dir).expect("plugin dir");
+        std::fs::write(
+            plugin_dir.join(PLUGIN_MANIFEST_FILE),
+            "extensions = [\"toy\"]\n",
+        )
+        .expect("manifest");
+        std::fs::write(plugin_dir.join(GRAPH_RULES_FILE), "").expect("graph rules");
+
+        let report = register_language_plugins(dir.path());
+
+        assert!(report.loaded.is_empty());
+        assert_eq!(report.failed.len(), 1);
+        assert!(report.failed[0].1.contains("no grammar library"));
+        assert!(plugin_language_for_ext("toy").is_none());
+        assert_eq!(
+            register_language_plugins(&dir.path().join("missing")),
+            LanguagePluginReport::default()
+        );
+    }
+}
@@ -25,7 +25,7 @@ use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
 
-use codestory_contracts::events::{Event, EventBus};
+use codestory_contracts::events::{Event, EventBus, IndexingPhaseDurations};
 use rayon::prelude::*;
 use sha2::{Digest, Sha256};
 use std::sync::Arc;
@@ -39,12 +39,21 @@ use tree_sitter_graph::{ExecutionConfig, NoCancellation, Variables};
 
 mod cache;
 pub mod cancellation;
+mod canonical_ids;
+mod code_markers;
 pub mod compilation_database;
+pub mod dependency_sources;
+mod file_isolation;
 mod framework_routes;
 pub mod intermediate_storage;
+mod java_source_roots;
 mod language_configs;
+mod language_plugins;
+mod manifest_packages;
+mod notebook;
 pub mod resolution;
 pub mod semantic;
+mod string_literals;
 pub mod structural;
 pub mod symbol_table;
 pub mod template_pipeline;
@@ -53,7 +62,10 @@ use cache::{
     build_structural_artifact_cache_key, index_artifact_cache_path,
 };
 pub use cancellation::CancellationToken;
+pub use code_markers::{CODE_MARKER_TAGS, extract_code_markers};
 use intermediate_storage::IntermediateStorage;
+pub use language_plugins::{LanguagePluginReport, register_language_plugins};
+pub use string_literals::extract_string_literals;
 use symbol_table::SymbolTable;
 
 pub(crate) const PYTHON_ATTRIBUTE_CALLSITE_MARKER: &str = "syntax:python-attribute-call";
@@ -70,6 +82,9 @@ pub(crate) const DART_MEMBER_CALLSITE_MARKER: &str = "syntax:dart-member-call";
 pub(crate) const SWIFT_MEMBER_CALLSITE_MARKER: &str = "syntax:swift-member-call";
 pub(crate) const RECEIVER_OWNER_CALLSITE_PREFIX: &str = "receiver-owner:";
 pub(crate) const RECEIVER_MODULE_CALLSITE_PREFIX: &str = "receiver-module:";
+/// Marks an `#include` edge resolved through compilation database search
+/// paths, followed by `project` or `system`.
+pub const INCLUDE_CALLSITE_PREFIX: &str = "include:";
 
 #[derive(Debug, Clone, Copy)]
 struct IndexFeatureFlags {
@@ -121,6 +136,7 @@ fn parser_direct_structural_certainty(kind: EdgeKind) -> Option<ResolutionCertai
         | EdgeKind::TYPE_USAGE
         | EdgeKind::MACRO_USAGE
         | EdgeKind::ANNOTATION_USAGE
+        | EdgeKind::ASSIGNMENT
         | EdgeKind::UNKNOWN => None,
     }
 }
@@ -147,6 +163,55 @@ const SWIFT_GRAPH_QUERY: &str = include_str!("../rules/swift.scm");
 const DART_GRAPH_QUERY: &str = include_str!("../rules/dart.scm");
 const BASH_GRAPH_QUERY: &str = include_str!("../rules/bash.scm");
 
+/// Embedded rule files by asset name; the name is what an override file in
+/// [`RULES_OVERRIDE_DIR_ENV`] must be called.
+const RULE_FILES: &[(&str, &str)] = &[
+    ("python.scm", PYTHON_GRAPH_QUERY),
+    ("java.scm", JAVA_GRAPH_QUERY),
+    ("rust.graph.scm", RUST_GRAPH_QUERY),
+    ("rust.tags.scm", RUST_TAGS_QUERY),
+    ("javascript.scm", JAVASCRIPT_GRAPH_QUERY),
+    ("typescript.graph.scm", TYPESCRIPT_GRAPH_QUERY),
+    ("typescript.tags.scm", TYPESCRIPT_TAGS_QUERY),
+    ("tsx.graph.scm", TSX_GRAPH_QUERY),
+    ("cpp.scm", CPP_GRAPH_QUERY),
+    ("c.scm", C_GRAPH_QUERY),
+    ("go.scm", GO_GRAPH_QUERY),
+    ("ruby.scm", RUBY_GRAPH_QUERY),
+    ("php.scm", PHP_GRAPH_QUERY),
+    ("csharp.scm", CSHARP_GRAPH_QUERY),
+    ("kotlin.scm", KOTLIN_GRAPH_QUERY),
+    ("swift.scm", SWIFT_GRAPH_QUERY),
+    ("dart.scm", DART_GRAPH_QUERY),
+    ("bash.scm", BASH_GRAPH_QUERY),
+];
+
+/// Environment variable naming a directory of rule files that replace the
+/// embedded ones of the same name, such as `rust.graph.scm`, without a
+/// rebuild. Overrides are read when a language's rules first compile.
+pub const RULES_OVERRIDE_DIR_ENV: &str = "CODESTORY_RULES_DIR";
+
+/// Rule text to compile for an embedded asset: its override file when one
+/// exists in `override_dir`, otherwise the embedded text.
+fn rule_text(
+    override_dir: Option<&Path>,
+    embedded: &'static str,
+) -> std::result::Result<std::borrow::Cow<'static, str>, String> {
+    let override_path = override_dir.and_then(|dir| {
+        RULE_FILES
+            .iter()
+            .find(|(_, text)| *text == embedded)
+            .map(|(file_name, _)| dir.join(file_name))
+            .filter(|path| path.is_file())
+    });
+    match override_path {
+        Some(path) => std::fs::read_to_string(&path)
+            .map(std::borrow::Cow::Owned)
+            .map_err(|error| format!("Cannot read rule override {}: {error}", path.display())),
+        None => Ok(std::borrow::Cow::Borrowed(embedded)),
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 enum LanguageRuleset {
     Python,
@@ -165,6 +230,7 @@ enum LanguageRuleset {
     Swift,
     Dart,
     Bash,
+    Plugin(&'static language_plugins::LanguagePlugin),
 }
 
 /// Tree-sitter language plus graph/tag rules used for parser-backed indexing.
@@ -188,6 +254,8 @@ pub use codestory_contracts::language_support::{
 struct CompiledLanguageRules {
     graph_file: GraphFile,
     tags_query: Option<Query>,
+    /// Tag query patterns left out because they failed to compile.
+    skipped_tag_patterns: Vec<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -330,6 +398,12 @@ impl LanguageRuleset {
             LanguageRuleset::Bash => {
                 compiled_rules_cache(language, BASH_GRAPH_QUERY, None, &BASH_RULES)
             }
+            LanguageRuleset::Plugin(plugin) => compiled_rules_cache(
+                language,
+                plugin.graph_query(),
+                plugin.tags_query(),
+                &plugin.rules,
+            ),
         }
     }
 }
@@ -341,17 +415,24 @@ fn compiled_rules_cache(
     cache: &'static OnceLock<Result<CompiledLanguageRules, String>>,
 ) -> Result<&'static CompiledLanguageRules> {
     let compiled = cache.get_or_init(|| {
-        let graph_file = GraphFile::from_str(language.clone(), graph_query)
-            .map_err(|e| format!("Graph DSL error: {:?}", e))?;
+        let override_dir = std::env::var_os(RULES_OVERRIDE_DIR_ENV).map(PathBuf::from);
+        let graph_query = rule_text(override_dir.as_deref(), graph_query)?;
         let tags_query = tags_query
-            .filter(|query| !query.trim().is_empty())
-            .map(|query| {
-                Query::new(&language, query).map_err(|e| format!("Tag query error: {:?}", e))
-            })
+            .map(|query| rule_text(override_dir.as_deref(), query))
             .transpose()?;
+        let graph_file = GraphFile::from_str(language.clone(), &graph_query)
+            .map_err(|e| format!("Graph DSL error: {:?}", e))?;
+        let (tags_query, skipped_tag_patterns) = match tags_query
+            .as_deref()
+            .filter(|query| !query.trim().is_empty())
+        {
+            Some(query) => compile_tags_query(&language, query),
+            None => (None, Vec::new()),
+        };
         Ok::<CompiledLanguageRules, String>(CompiledLanguageRules {
             graph_file,
             tags_query,
+            skipped_tag_patterns,
         })
     });
 
@@ -360,6 +441,119 @@ fn compiled_rules_cache(
         .map_err(|message| anyhow!(message.clone()))
 }
 
+/// Compile a tag query, dropping only the patterns that fail to compile.
+///
+/// Returns the query built from the remaining patterns plus one message per
+/// dropped pattern, naming its index in the source query.
+fn compile_tags_query(language: &Language, query: &str) -> (Option<Query>, Vec<String>) {
+    let full_error = match Query::new(language, query) {
+        Ok(query) => return (Some(query), Vec::new()),
+        Err(error) => error,
+    };
+    let mut kept = Vec::new();
+    let mut skipped = Vec::new();
+    for (index, pattern) in split_query_patterns(query).into_iter().enumerate() {
+        match Query::new(language, pattern) {
+            Ok(_) => kept.push(pattern),
+            Err(error) => skipped.push(format!("Tag query pattern {index} skipped: {error:?}")),
+        }
+    }
+    if skipped.is_empty() {
+        return (None, vec![format!("Tag query error: {full_error:?}")]);
+    }
+    if kept.is_empty() {
+        return (None, skipped);
+    }
+    match Query::new(language, &kept.join("\n")) {
+        Ok(query) => (Some(query), skipped),
+        Err(_) => (None, vec![format!("Tag query error: {full_error:?}")]),
+    }
+}
+
+/// Split query source into top-level patterns, keeping the captures,
+/// quantifiers, and predicates that follow each pattern with it.
+fn split_query_patterns(query: &str) -> Vec<&str> {
+    let mut patterns = Vec::new();
+    let mut start = None::<usize>;
+    let mut depth = 0usize;
+    let mut chars = query.char_indices().peekable();
+    while let Some((offset, ch)) = chars.next() {
+        match ch {
+            ';' => while chars.next_if(|(_, next)| *next != '\n').is_some() {},
+            '"' => {
+                while let Some((_, next)) = chars.next() {
+                    match next {
+                        '\\' => {
+                            chars.next();
+                        }
+                        '"' => break,
+                        _ => {}
+                    }
+                }
+            }
+            '(' | '[' => {
+                let is_predicate = ch == '(' && query[offset + 1..].trim_start().starts_with('#');
+                if depth == 0 && !is_predicate {
+                    if let Some(start) = start {
+                        patterns.push(query[start..offset].trim());
+                    }
+                    start = Some(offset);
+                }
+                depth += 1;
+            }
+            ')' | ']' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+    if let Some(start) = start {
+        patterns.push(query[start..].trim());
+    }
+    patterns
+}
+
+/// Compile the rules of every registered language and describe each failure
+/// as a fatal, file-less indexing error.
+fn language_rule_errors() -> Vec<codestory_contracts::graph::ErrorInfo> {
+    let extensions = codestory_contracts::language_support::LANGUAGE_SUPPORT_PROFILES
+        .iter()
+        .filter(|profile| profile.support_mode == LanguageSupportMode::ParserBackedGraph)
+        .flat_map(|profile| profile.extensions.iter().map(|ext| ext.to_string()))
+        .chain(language_plugins::plugin_extensions());
+    let mut seen = HashSet::new();
+    let mut messages = Vec::new();
+    for ext in extensions {
+        let Some(config) = get_language_for_ext(&ext) else {
+            continue;
+        };
+        if !seen.insert((config.language_name, config.graph_query)) {
+            continue;
+        }
+        match config.compiled_rules() {
+            Ok(rules) => messages.extend(
+                rules
+                    .skipped_tag_patterns
+                    .iter()
+                    .map(|message| (config.language_name, message.clone())),
+            ),
+            Err(error) => messages.push((config.language_name, error.to_string())),
+        }
+    }
+    messages
+        .into_iter()
+        .map(
+            |(language, message)| codestory_contracts::graph::ErrorInfo {
+                message: format!("{language} rules: {message}"),
+                file_id: None,
+                line: None,
+                column: None,
+                is_fatal: true,
+                index_step: codestory_contracts::graph::IndexStep::Indexing,
+                coverage_reason: None,
+            },
+        )
+        .collect()
+}
+
 static PYTHON_RULES: OnceLock<Result<CompiledLanguageRules, String>> = OnceLock::new();
 static JAVA_RULES: OnceLock<Result<CompiledLanguageRules, String>> = OnceLock::new();
 static RUST_RULES: OnceLock<Result<CompiledLanguageRules, String>> = OnceLock::new();
@@ -854,6 +1048,37 @@ impl ArtifactCacheFamilyStats {
     }
 }
 
+/// Number of files [`SlowestParsedFiles`] keeps.
+pub const SLOWEST_PARSED_FILES_LIMIT: usize = 5;
+
+/// The files whose parse took longest during one run, slowest first.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SlowestParsedFiles {
+    entries: [(i64, u64); SLOWEST_PARSED_FILES_LIMIT],
+    len: usize,
+}
+
+impl SlowestParsedFiles {
+    /// Offer one file's parse duration, in microseconds.
+    pub fn record(&mut self, file_id: i64, parse_us: u64) {
+        let position = self.entries[..self.len]
+            .iter()
+            .position(|&(_, existing_us)| parse_us > existing_us)
+            .unwrap_or(self.len);
+        if position >= SLOWEST_PARSED_FILES_LIMIT {
+            return;
+        }
+        self.len = (self.len + 1).min(SLOWEST_PARSED_FILES_LIMIT);
+        self.entries[position..self.len].rotate_right(1);
+        self.entries[position] = (file_id, parse_us);
+    }
+
+    /// `(file node id, parse microseconds)` pairs, slowest first.
+    pub fn iter(&self) -> impl Iterator<Item = (i64, u64)> + '_ {
+        self.entries[..self.len].iter().copied()
+    }
+}
+
 /// Timings and counters collected during a workspace indexing run.
 #[derive(Debug, Clone, Copy, Default)]
 pub struct IncrementalIndexingStats {
@@ -886,6 +1111,7 @@ pub struct IncrementalIndexingStats {
     pub full_refresh_chunk_budget_overruns: usize,
     pub full_refresh_chunk_planning_ms: u64,
     pub parse_index_ms: u64,
+    pub slowest_parsed_files: SlowestParsedFiles,
     pub projection_flush_ms: u64,
     pub projection_batch_wall_ms: u64,
     pub projection_batch_transactions: usize,
@@ -939,11 +1165,17 @@ pub struct IncrementalIndexingStats {
     pub resolved_calls_same_module: usize,
     pub resolved_calls_global_unique: usize,
     pub resolved_calls_semantic: usize,
+    pub resolved_calls_dependency: usize,
     pub resolved_imports_same_file: usize,
     pub resolved_imports_same_module: usize,
     pub resolved_imports_global_unique: usize,
     pub resolved_imports_fuzzy: usize,
     pub resolved_imports_semantic: usize,
+    pub unresolved_imports_external: usize,
+    /// Java imports and supertypes linked to a project type by qualified name.
+    pub resolved_java_types_project: usize,
+    /// Java imports and supertypes linked to a shared node for an external type.
+    pub resolved_java_types_external: usize,
 }
 
 /// Indexing statistics plus verified bounded-source exclusions discovered by collectors.
@@ -962,6 +1194,8 @@ struct PreparedIndexInput {
     language_config: LanguageConfig,
     artifact_cache_key: Option<String>,
     content_hash: String,
+    /// Invalid UTF-8 bytes replaced while decoding `source`.
+    replaced_invalid_bytes: usize,
 }
 
 #[derive(Debug)]
@@ -1473,21 +1707,33 @@ pub struct WorkspaceIndexer {
     root: PathBuf,
     compilation_db: Option<compilation_database::CompilationDatabase>,
     compilation_db_warning: Option<String>,
+    language_plugin_warnings: Vec<String>,
     batch_config: IncrementalIndexingConfig,
     full_refresh_chunk_budget: FullRefreshChunkBudget,
     source_file_byte_cap: u64,
     source_index_policy: Option<SourceIndexPolicy>,
     artifact_cache_policies: ArtifactCachePolicies,
+    package_roots: Vec<String>,
+    dependency_storage: Option<PathBuf>,
     #[cfg(test)]
     pipeline_test_hooks: FullRefreshPipelineTestHooks,
 }
 
+/// Environment variable naming the `languages/` directory scanned for
+/// runtime language plugins. Plugins load native code, so they are never
+/// picked up from the indexed workspace itself.
+pub const LANGUAGE_PLUGIN_DIR_ENV: &str = "CODESTORY_LANGUAGE_PLUGINS_DIR";
+
+/// Source directories that do not become directory packages themselves.
+pub const DEFAULT_PACKAGE_ROOTS: &[&str] = &["src"];
+
 impl WorkspaceIndexer {
     /// Create an indexer rooted at a workspace directory.
     ///
     /// If a compilation database is present, C/C++ header routing can use it;
     /// load failures are reported later through the event bus and indexing
-    /// continues without that metadata.
+    /// continues without that metadata. Language plugins named by
+    /// [`LANGUAGE_PLUGIN_DIR_ENV`] are registered the same way.
     pub fn new(root: PathBuf) -> Self {
         let (compilation_db, compilation_db_warning) = if let Some(path) =
             compilation_database::CompilationDatabase::find_in_directory(&root)
@@ -1506,15 +1752,29 @@ impl WorkspaceIndexer {
         } else {
             (None, None)
         };
+        let language_plugin_warnings = std::env::var_os(LANGUAGE_PLUGIN_DIR_ENV)
+            .map(|dir| register_language_plugins(Path::new(&dir)).failed)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(plugin_dir, reason)| {
+                format!("Skipped language plugin {}: {reason}", plugin_dir.display())
+            })
+            .collect();
         Self {
             root,
             compilation_db,
             compilation_db_warning,
+            language_plugin_warnings,
             batch_config: IncrementalIndexingConfig::default(),
             full_refresh_chunk_budget: FullRefreshChunkBudget::default(),
             source_file_byte_cap: SourceIndexPolicy::default().byte_cap,
             source_index_policy: None,
             artifact_cache_policies: ArtifactCachePolicies::default(),
+            package_roots: DEFAULT_PACKAGE_ROOTS
+                .iter()
+                .map(|root| root.to_string())
+                .collect(),
+            dependency_storage: None,
             #[cfg(test)]
             pipeline_test_hooks: FullRefreshPipelineTestHooks::default(),
         }
@@ -1555,6 +1815,21 @@ impl WorkspaceIndexer {
         self
     }
 
+    /// Override the leading directories (such as `src`) that are skipped when
+    /// grouping JavaScript and TypeScript files into directory packages.
+    pub fn with_package_roots(mut self, package_roots: Vec<String>) -> Self {
+        self.package_roots = package_roots;
+        self
+    }
+
+    /// Resolve calls the resolution pass leaves unresolved against the
+    /// dependency storage at `path`, built by
+    /// [`dependency_sources::build_dependency_storage`].
+    pub fn with_dependency_storage(mut self, path: PathBuf) -> Self {
+        self.dependency_storage = Some(path);
+        self
+    }
+
     #[cfg(test)]
     fn with_pipeline_test_hooks(mut self, hooks: FullRefreshPipelineTestHooks) -> Self {
         self.pipeline_test_hooks = hooks;
@@ -1611,6 +1886,7 @@ impl WorkspaceIndexer {
         event_bus: &EventBus,
         cancel_token: Option<&CancellationToken>,
     ) -> Result<WorkspaceIndexingOutcome> {
+        let run_started = Instant::now();
         let plan = plan.clone();
         event_bus.publish(Event::IndexingStarted {
             file_count: plan.files_to_index.len(),
@@ -1620,6 +1896,20 @@ impl WorkspaceIndexer {
                 message: message.clone(),
             });
         }
+        for message in &self.language_plugin_warnings {
+            event_bus.publish(Event::ShowWarning {
+                message: message.clone(),
+            });
+        }
+        let rule_errors = language_rule_errors();
+        for error in &rule_errors {
+            event_bus.publish(Event::ShowWarning {
+                message: error.message.clone(),
+            });
+        }
+        storage
+            .replace_run_errors(&rule_errors)
+            .map_err(|e| anyhow!("Storage error: {:?}", e))?;
         let mut stats = IncrementalIndexingStats {
             parser_artifact_cache: ArtifactCacheFamilyStats::new(
                 self.artifact_cache_policies.parser,
@@ -1779,7 +2069,7 @@ impl WorkspaceIndexer {
         let policy_exclusions = writer_output.policy_exclusions;
 
         if cancelled.load(Ordering::Relaxed) {
-            event_bus.publish(Event::IndexingComplete { duration_ms: 0 });
+            publish_indexing_complete(event_bus, run_started, &stats, true);
             return Ok(WorkspaceIndexingOutcome {
                 stats,
                 policy_exclusions,
@@ -1799,7 +2089,7 @@ impl WorkspaceIndexer {
         }
 
         if Self::is_cancelled(cancel_token) {
-            event_bus.publish(Event::IndexingComplete { duration_ms: 0 });
+            publish_indexing_complete(event_bus, run_started, &stats, true);
             return Ok(WorkspaceIndexingOutcome {
                 stats,
                 policy_exclusions,
@@ -1818,8 +2108,14 @@ impl WorkspaceIndexer {
             } else {
                 (HashSet::new(), 0)
             };
+        let discovered_files = Self::discovered_files(&root, &plan);
         if had_edges || expanded_resolution_scope_files > 0 {
-            let resolver = resolution::ResolutionPass::new();
+            let resolver = resolution::ResolutionPass::new()
+                .with_path_aliases(resolution::TypeScriptPathAliases::discover(
+                    &root,
+                    &discovered_files,
+                ))
+                .with_python_roots(resolution::PythonImportRoots::discover(&root));
             let resolution_scope = if plan.mode == codestory_workspace::BuildMode::Incremental {
                 (!resolution_scope_file_ids.is_empty()).then_some(&resolution_scope_file_ids)
             } else {
@@ -1933,8 +2229,54 @@ impl WorkspaceIndexer {
             stats.resolved_imports_fuzzy = resolution_stats.strategy_counters.import_fuzzy;
             stats.resolved_imports_semantic =
                 resolution_stats.strategy_counters.import_semantic_fallback;
+            stats.unresolved_imports_external = resolution_stats.strategy_counters.import_external;
+        }
+
+        // Resolve remaining calls into indexed dependency sources. Earlier
+        // dependency resolutions are undone first, so nodes copied from a
+        // dependency that is gone, or from storage that is now disabled, do
+        // not linger.
+        let released = storage
+            .reset_dependency_resolutions()
+            .map_err(|e| anyhow!("Dependency resolution reset error: {:?}", e))?;
+        stats.unresolved_calls_end = stats.unresolved_calls_end.saturating_add(released);
+        if let Some(path) = self.dependency_storage.as_deref()
+            && stats.unresolved_calls_end > 0
+        {
+            let dependency_storage = Storage::open_read_only(path)
+                .map_err(|e| anyhow!("Dependency storage error: {:?}", e))?;
+            let resolved =
+                dependency_sources::resolve_calls_into_dependencies(storage, &dependency_storage)?;
+            stats.resolved_calls_dependency = resolved;
+            stats.resolved_calls = stats.resolved_calls.saturating_add(resolved);
+            stats.unresolved_calls_end = stats.unresolved_calls_end.saturating_sub(resolved);
         }
 
+        // 3.6 Group script files without module declarations into directory packages
+        storage
+            .rebuild_directory_packages(&root, &self.package_roots)
+            .map_err(|e| anyhow!("Directory package error: {:?}", e))?;
+
+        // 3.7 Group files under build manifests into the packages they declare
+        storage
+            .rebuild_manifest_packages(&manifest_packages::discover_manifest_packages(
+                &root,
+                &discovered_files,
+            ))
+            .map_err(|e| anyhow!("Manifest package error: {:?}", e))?;
+
+        // 3.8 Link Java imports and supertypes to types by qualified name
+        let java_scope = (plan.mode == codestory_workspace::BuildMode::Incremental)
+            .then_some(&resolution_scope_file_ids);
+        let java_links = storage
+            .link_java_types(
+                &java_source_roots::discover_java_source_roots(&root),
+                java_scope,
+            )
+            .map_err(|e| anyhow!("Java type linking error: {:?}", e))?;
+        stats.resolved_java_types_project = java_links.project;
+        stats.resolved_java_types_external = java_links.external;
+
         // Write errors
         while !all_errors.is_empty() {
             let error_flush_started = Instant::now();
@@ -1944,7 +2286,7 @@ impl WorkspaceIndexer {
                 .saturating_add(duration_ms_u64(error_flush_started.elapsed()));
         }
 
-        event_bus.publish(Event::IndexingComplete { duration_ms: 0 });
+        publish_indexing_complete(event_bus, run_started, &stats, false);
         Ok(WorkspaceIndexingOutcome {
             stats,
             policy_exclusions,
@@ -2022,9 +2364,10 @@ impl WorkspaceIndexer {
             .saturating_add(source_prepare_ms);
 
         let parse_started = Instant::now();
-        let parse_results: Vec<PreparedIndexJobResult> = parse_jobs
+        let timed_results: Vec<(PreparedIndexJobResult, i64, u64)> = parse_jobs
             .par_iter()
             .map(|prepared_input| {
+                let job_started = Instant::now();
                 #[cfg(test)]
                 if let Some(hook) = &self.pipeline_test_hooks.before_parse_job {
                     hook(_chunk_index);
@@ -2033,25 +2376,66 @@ impl WorkspaceIndexer {
                     && token.is_cancelled()
                 {
                     cancelled.store(true, Ordering::Relaxed);
-                    return PreparedIndexJobResult {
-                        local_storage: IntermediateStorage::default(),
-                        cache_write: None,
-                        policy_exclusion: None,
-                    };
-                }
-                match prepared_input {
-                    PreparedIndexJob::Parse(prepared_input) => {
-                        self.execute_prepared_index(prepared_input, symbol_table)
-                    }
-                    PreparedIndexJob::Structural(prepared_input) => {
-                        self.execute_prepared_structural_index(prepared_input)
-                    }
+                    return (
+                        PreparedIndexJobResult {
+                            local_storage: IntermediateStorage::default(),
+                            cache_write: None,
+                            policy_exclusion: None,
+                        },
+                        0,
+                        0,
+                    );
                 }
+                let (result, full_path) = match prepared_input {
+                    PreparedIndexJob::Parse(prepared_input) => (
+                        self.execute_prepared_index(prepared_input, symbol_table),
+                        &prepared_input.full_path,
+                    ),
+                    PreparedIndexJob::Structural(prepared_input) => (
+                        file_isolation::catch_index_panic(|| {
+                            Ok(self.execute_prepared_structural_index(prepared_input))
+                        })
+                        .unwrap_or_else(|error| PreparedIndexJobResult {
+                            local_storage: incomplete_file_storage(
+                                &prepared_input.full_path,
+                                Some(&prepared_input.source),
+                                structural::structural_language_name(&prepared_input.full_path),
+                                codestory_contracts::graph::ErrorInfo {
+                                    message: format!(
+                                        "Failed to index structural file {:?}: {}",
+                                        prepared_input.full_path, error
+                                    ),
+                                    file_id: None,
+                                    line: None,
+                                    column: None,
+                                    is_fatal: false,
+                                    index_step: codestory_contracts::graph::IndexStep::Indexing,
+                                    coverage_reason: Some(FileCoverageReason::CollectorFailure),
+                                },
+                            ),
+                            cache_write: None,
+                            policy_exclusion: None,
+                        }),
+                        &prepared_input.full_path,
+                    ),
+                };
+                (
+                    result,
+                    Self::canonical_file_node_id_for_path(full_path),
+                    duration_us_u64(job_started.elapsed()),
+                )
             })
             .collect();
         stats.parse_index_ms = stats
             .parse_index_ms
             .saturating_add(duration_ms_u64(parse_started.elapsed()));
+        let mut parse_results = Vec::with_capacity(timed_results.len());
+        for (result, file_id, parse_us) in timed_results {
+            if parse_us > 0 {
+                stats.slowest_parsed_files.record(file_id, parse_us);
+            }
+            parse_results.push(result);
+        }
         // Parsed projections and serialized cache artifacts own everything the
         // writer needs. Release source strings before a capacity wait.
         drop(parse_jobs);
@@ -2529,6 +2913,25 @@ impl WorkspaceIndexer {
             .collect())
     }
 
+    /// Every workspace file the plan's discovery pass found: those scheduled
+    /// for indexing and those kept from the previous run. Auxiliary readers
+    /// such as manifest discovery use it rather than walking the workspace
+    /// again.
+    fn discovered_files(
+        root: &Path,
+        plan: &codestory_workspace::RefreshExecutionPlan,
+    ) -> Vec<PathBuf> {
+        let mut files = plan
+            .files_to_index
+            .iter()
+            .chain(plan.existing_file_ids.keys())
+            .map(|path| Self::normalize_index_path(root, path))
+            .collect::<Vec<_>>();
+        files.sort();
+        files.dedup();
+        files
+    }
+
     fn collect_touched_file_ids(root: &Path, files_to_index: &[PathBuf]) -> HashSet<i64> {
         let mut file_ids = HashSet::new();
         for path in files_to_index {
@@ -2807,6 +3210,9 @@ impl WorkspaceIndexer {
             .as_ref()
             .map(|config| config.language_name)
             .or_else(|| template_pipeline::template_surface_language(&full_path))
+            .or_else(|| {
+                notebook::is_notebook_path(&full_path).then_some(notebook::NOTEBOOK_LANGUAGE)
+            })
             .or_else(|| openapi_path_language_hint(&full_path).then_some("openapi"))
             .or_else(|| {
                 structural::is_structural_candidate_path(&full_path)
@@ -2867,6 +3273,28 @@ impl WorkspaceIndexer {
                 Ok(None) => {}
                 Err(err_storage) => return Err(err_storage),
             }
+            if notebook::is_notebook_path(&full_path) {
+                return match prepare_notebook_index_work(&full_path) {
+                    Ok(local_storage) => Ok(PreparedIndexWork::Immediate(local_storage)),
+                    Err(error) => {
+                        let local_storage = incomplete_file_storage(
+                            &full_path,
+                            None,
+                            notebook::NOTEBOOK_LANGUAGE,
+                            codestory_contracts::graph::ErrorInfo {
+                                message: format!("Failed to index notebook {:?}: {}", path, error),
+                                file_id: None,
+                                line: None,
+                                column: None,
+                                is_fatal: false,
+                                index_step: codestory_contracts::graph::IndexStep::Indexing,
+                                coverage_reason: Some(FileCoverageReason::CollectorFailure),
+                            },
+                        );
+                        Err(local_storage)
+                    }
+                };
+            }
             if let Some(template_kind) = template_pipeline::template_kind_for_path(&full_path) {
                 return match prepare_template_index_work(&full_path, template_kind) {
                     Ok(local_storage) => Ok(PreparedIndexWork::Immediate(local_storage)),
@@ -2950,20 +3378,39 @@ impl WorkspaceIndexer {
                 return Err(local_storage);
             }
         };
+        if looks_binary(&bytes) {
+            return Err(incomplete_file_storage(
+                &full_path,
+                None,
+                language_config.language_name,
+                codestory_contracts::graph::ErrorInfo {
+                    message: format!(
+                        "Skipped binary source file {:?}: NUL byte in the first {} bytes",
+                        path, BINARY_SNIFF_BYTES
+                    ),
+                    file_id: None,
+                    line: None,
+                    column: None,
+                    is_fatal: false,
+                    index_step: codestory_contracts::graph::IndexStep::Collection,
+                    coverage_reason: Some(FileCoverageReason::Binary),
+                },
+            ));
+        }
         let content_hash = source_content_hash(&bytes);
         // Decode before building the cache key so source-aware header detection can choose
         // the same parser that will be used for indexing.
-        let source = match String::from_utf8(bytes) {
-            Ok(source) => source,
-            Err(error) => String::from_utf8_lossy(&error.into_bytes()).into_owned(),
-        };
+        let (source, replaced_invalid_bytes) = decode_source_preserving_offsets(bytes);
         if let Some(upgraded) =
             maybe_upgrade_header_language_from_source(&full_path, &source, &language_config)
         {
             language_config = upgraded;
         }
         let flags = index_feature_flags();
-        let artifact_cache_path = index_artifact_cache_path(root, &full_path);
+        // Cached artifacts do not carry file errors, so lossily decoded files
+        // are always reparsed to keep their decoding warning.
+        let artifact_cache_path =
+            index_artifact_cache_path(root, &full_path).filter(|_| replaced_invalid_bytes == 0);
         let artifact_cache_key = artifact_cache_path.as_ref().and_then(|cache_path| {
             build_index_artifact_cache_key(
                 root,
@@ -2988,6 +3435,7 @@ impl WorkspaceIndexer {
                 language_config,
                 artifact_cache_key,
                 content_hash,
+                replaced_invalid_bytes,
             }));
         };
         let Some(cache_key) = artifact_cache_key.as_ref() else {
@@ -3001,6 +3449,7 @@ impl WorkspaceIndexer {
                 language_config,
                 artifact_cache_key,
                 content_hash,
+                replaced_invalid_bytes,
             }));
         };
 
@@ -3014,6 +3463,13 @@ impl WorkspaceIndexer {
                         language_config.language_name,
                         flags,
                     );
+                    canonical_ids::assign_symbol_canonical_ids(
+                        &mut artifact.nodes,
+                        root,
+                        &full_path,
+                        language_config.language_name,
+                        &source,
+                    );
                     verify_cached_artifact_source(
                         &mut artifact,
                         &full_path,
@@ -3104,6 +3560,7 @@ impl WorkspaceIndexer {
                         language_config,
                         artifact_cache_key,
                         content_hash,
+                        replaced_invalid_bytes,
                     }))
                 }
             },
@@ -3118,6 +3575,7 @@ impl WorkspaceIndexer {
                     language_config,
                     artifact_cache_key,
                     content_hash,
+                    replaced_invalid_bytes,
                 }))
             }
             Err(_) => {
@@ -3131,6 +3589,7 @@ impl WorkspaceIndexer {
                     language_config,
                     artifact_cache_key,
                     content_hash,
+                    replaced_invalid_bytes,
                 }))
             }
         }
@@ -3488,13 +3947,15 @@ impl WorkspaceIndexer {
         prepared_input: &PreparedIndexInput,
         symbol_table: &Arc<SymbolTable>,
     ) -> PreparedIndexJobResult {
-        let index_result = index_file(
-            &prepared_input.full_path,
-            &prepared_input.source,
-            &prepared_input.language_config,
-            prepared_input.compilation_info.clone(),
-            Some(Arc::clone(symbol_table)),
-        );
+        let index_result = file_isolation::catch_index_panic(|| {
+            index_file(
+                &prepared_input.full_path,
+                &prepared_input.source,
+                &prepared_input.language_config,
+                prepared_input.compilation_info.clone(),
+                Some(Arc::clone(symbol_table)),
+            )
+        });
         let modification_time =
             match verify_source_snapshot(&prepared_input.full_path, &prepared_input.content_hash) {
                 Ok(modification_time) => modification_time,
@@ -3516,6 +3977,13 @@ impl WorkspaceIndexer {
                 if let Some(file_info) = index_result.files.first_mut() {
                     file_info.modification_time = modification_time;
                 }
+                canonical_ids::assign_symbol_canonical_ids(
+                    &mut index_result.nodes,
+                    &self.root,
+                    &prepared_input.full_path,
+                    prepared_input.language_config.language_name,
+                    &prepared_input.source,
+                );
                 let artifact = CachedIndexArtifact::from_index_result(index_result);
                 let cache_write = prepared_input
                     .artifact_cache_path
@@ -3532,12 +4000,30 @@ impl WorkspaceIndexer {
                     });
                 let mut local_storage = artifact.into_intermediate_storage();
                 if let Some(file_info) = local_storage.files.first() {
+                    let file_id = file_info.id;
                     local_storage
                         .file_content_hashes
                         .push(codestory_store::FileContentHash {
-                            file_id: file_info.id,
+                            file_id,
                             content_hash: prepared_input.content_hash.clone(),
                         });
+                    if prepared_input.replaced_invalid_bytes > 0 {
+                        local_storage.add_error(codestory_contracts::graph::ErrorInfo {
+                            message: format!(
+                                "Decoded {:?} with {} invalid UTF-8 byte(s) replaced by `_`; \
+                                 symbol positions still match the file's bytes",
+                                prepared_input.full_path, prepared_input.replaced_invalid_bytes
+                            ),
+                            file_id: Some(NodeId(file_id)),
+                            line: None,
+                            column: None,
+                            is_fatal: false,
+                            index_step: codestory_contracts::graph::IndexStep::Indexing,
+                            // The file is fully indexed; this is a warning,
+                            // not a coverage gap.
+                            coverage_reason: None,
+                        });
+                    }
                 }
                 PreparedIndexJobResult {
                     local_storage,
@@ -3546,20 +4032,52 @@ impl WorkspaceIndexer {
                 }
             }
             Err(e) => {
-                let mut local_storage = incomplete_file_storage(
-                    &prepared_input.full_path,
-                    Some(&prepared_input.source),
-                    prepared_input.language_config.language_name,
-                    codestory_contracts::graph::ErrorInfo {
-                        message: format!("Failed to index {:?}: {}", prepared_input.full_path, e),
-                        file_id: None,
-                        line: None,
-                        column: None,
-                        is_fatal: false,
-                        index_step: codestory_contracts::graph::IndexStep::Indexing,
-                        coverage_reason: Some(FileCoverageReason::CollectorFailure),
-                    },
-                );
+                let mut error = codestory_contracts::graph::ErrorInfo {
+                    message: format!(
+                        "Failed to index {:?} with {} rules: {}",
+                        prepared_input.full_path, prepared_input.language_config.language_name, e
+                    ),
+                    file_id: None,
+                    line: None,
+                    column: None,
+                    is_fatal: false,
+                    index_step: codestory_contracts::graph::IndexStep::Indexing,
+                    coverage_reason: Some(FileCoverageReason::CollectorFailure),
+                };
+                // Keep the symbols the tags query can still find, so one bad
+                // graph rule doesn't empty the file.
+                let partial = file_isolation::catch_index_panic(|| {
+                    index_file_definitions(
+                        &prepared_input.full_path,
+                        &prepared_input.source,
+                        &prepared_input.language_config,
+                    )
+                });
+                let mut local_storage = match partial {
+                    Ok(mut partial) => {
+                        canonical_ids::assign_symbol_canonical_ids(
+                            &mut partial.nodes,
+                            &self.root,
+                            &prepared_input.full_path,
+                            prepared_input.language_config.language_name,
+                            &prepared_input.source,
+                        );
+                        let mut local_storage = CachedIndexArtifact::from_index_result(partial)
+                            .into_intermediate_storage();
+                        error
+                            .message
+                            .push_str("; kept definitions from the tags query");
+                        error.file_id = local_storage.files.first().map(|file| NodeId(file.id));
+                        local_storage.add_error(error);
+                        local_storage
+                    }
+                    Err(_) => incomplete_file_storage(
+                        &prepared_input.full_path,
+                        Some(&prepared_input.source),
+                        prepared_input.language_config.language_name,
+                        error,
+                    ),
+                };
                 if let Some(file_info) = local_storage.files.first_mut() {
                     file_info.modification_time = modification_time;
                     local_storage
@@ -3582,6 +4100,7 @@ impl WorkspaceIndexer {
         &self,
         prepared_input: &PreparedStructuralInput,
     ) -> PreparedIndexJobResult {
+        file_isolation::enter_index_stage("structural collection");
         let language = structural::structural_language_name(&prepared_input.full_path);
         let structural_unit_cap = self
             .source_index_policy
@@ -3777,6 +4296,59 @@ impl WorkspaceIndexer {
     }
 }
 
+/// Decode source bytes, replacing each byte of an invalid UTF-8 sequence with
+/// `_` so every byte offset, and therefore every line and column, still
+/// matches the file. `_` keeps Latin-1 or Shift-JIS identifiers in one piece.
+/// Returns the text and the number of replaced bytes.
+fn decode_source_preserving_offsets(bytes: Vec<u8>) -> (String, usize) {
+    let bytes = match String::from_utf8(bytes) {
+        Ok(source) => return (source, 0),
+        Err(error) => error.into_bytes(),
+    };
+    let mut source = String::with_capacity(bytes.len());
+    let mut replaced = 0;
+    for chunk in bytes.utf8_chunks() {
+        source.push_str(chunk.valid());
+        let invalid = chunk.invalid().len();
+        source.extend(std::iter::repeat_n('_', invalid));
+        replaced += invalid;
+    }
+    (source, replaced)
+}
+
+fn publish_indexing_complete(
+    event_bus: &EventBus,
+    run_started: Instant,
+    stats: &IncrementalIndexingStats,
+    cancelled: bool,
+) {
+    event_bus.publish(Event::IndexingComplete {
+        duration_ms: duration_ms_u64(run_started.elapsed()),
+        cancelled,
+        phase_timings: IndexingPhaseDurations {
+            prepare_ms: stats
+                .setup_existing_projection_ids_ms
+                .saturating_add(stats.setup_seed_symbol_table_ms)
+                .saturating_add(stats.source_prepare_ms),
+            parse_ms: stats.parse_index_ms,
+            flush_ms: stats
+                .projection_flush_ms
+                .saturating_add(stats.error_flush_ms),
+            resolution_ms: stats.edge_resolution_ms,
+            cleanup_ms: stats.cleanup_ms,
+        },
+    });
+}
+
+/// Leading bytes searched for NUL when deciding whether a parser source is
+/// binary.
+const BINARY_SNIFF_BYTES: usize = 8 * 1024;
+
+/// Git's heuristic: a NUL byte near the start means the file is not text.
+fn looks_binary(bytes: &[u8]) -> bool {
+    bytes[..bytes.len().min(BINARY_SNIFF_BYTES)].contains(&0)
+}
+
 fn source_content_hash(bytes: &[u8]) -> String {
     format!("{:x}", Sha256::digest(bytes))
 }
@@ -3896,6 +4468,10 @@ fn duration_ms_u64(duration: std::time::Duration) -> u64 {
     duration.as_millis().min(u64::MAX as u128) as u64
 }
 
+fn duration_us_u64(duration: std::time::Duration) -> u64 {
+    duration.as_micros().min(u64::MAX as u128) as u64
+}
+
 fn projection_batch_has_rows(storage: &IntermediateStorage) -> bool {
     !storage.files.is_empty()
         || !storage.file_content_hashes.is_empty()
@@ -4105,7 +4681,7 @@ fn incomplete_file_storage(
         indexed: true,
         complete: false,
         line_count: source.lines().count() as u32,
-        file_role: codestory_store::FileRole::classify_path(path),
+        file_role: codestory_store::FileRole::classify_source(path, source),
     });
     local_storage.nodes.push(file_node);
     error.file_id = Some(file_id);
@@ -4208,6 +4784,32 @@ fn classify_rust_visibility(text: &str) -> Option<AccessKind> {
     None
 }
 
+/// Python has no access keywords: a leading `__` (without a trailing `__`)
+/// marks a name-mangled private member and a single leading `_` marks an
+/// internal one, reported as protected. Everything else is public.
+fn classify_python_name_access(name: &str, kind: NodeKind) -> Option<AccessKind> {
+    if !matches!(
+        kind,
+        NodeKind::FUNCTION
+            | NodeKind::METHOD
+            | NodeKind::CLASS
+            | NodeKind::FIELD
+            | NodeKind::VARIABLE
+            | NodeKind::GLOBAL_VARIABLE
+            | NodeKind::CONSTANT
+    ) {
+        return None;
+    }
+    let name = name.rsplit('.').next().unwrap_or(name);
+    if name.starts_with("__") && !name.ends_with("__") {
+        Some(AccessKind::Private)
+    } else if name.starts_with('_') && !name.starts_with("__") {
+        Some(AccessKind::Protected)
+    } else {
+        Some(AccessKind::Public)
+    }
+}
+
 fn point_for_line_start(line: u32) -> Point {
     Point {
         row: line.saturating_sub(1) as usize,
@@ -6549,17 +7151,67 @@ fn collect_rust_macro_call_edges(tree: &Tree, source: &str) -> Vec<ManualEdgeSpe
     edges
 }
 
-fn python_decorator_target_name(node: TsNode<'_>, source: &str) -> Option<String> {
-    match node.kind() {
-        "decorator" => {
-            let mut cursor = node.walk();
-            node.named_children(&mut cursor)
-                .find_map(|child| python_decorator_target_name(child, source))
+/// `self.field` accesses inside Rust functions: writes become `ASSIGNMENT`
+/// edges and reads become `USAGE` edges, which is what `TrailMode::DataFlow`
+/// walks. Method calls through `self` are left to the call rules.
+fn collect_rust_field_access_edges(tree: &Tree, source: &str) -> Vec<ManualEdgeSpec> {
+    let mut edges = Vec::new();
+    walk_tree_nodes(tree.root_node(), &mut |node| {
+        if node.kind() != "field_expression"
+            || node.child_by_field_name("value").map(|value| value.kind()) != Some("self")
+        {
+            return;
         }
-        "call" => node
-            .child_by_field_name("function")
-            .and_then(|function| python_decorator_target_name(function, source)),
-        "attribute" => node
+        let parent = node.parent();
+        let is_method_call = parent.is_some_and(|parent| {
+            parent.kind() == "call_expression"
+                && parent.child_by_field_name("function") == Some(node)
+        });
+        if is_method_call {
+            return;
+        }
+        let Some(target_name) = node
+            .child_by_field_name("field")
+            .and_then(|field| node_source_text(field, source))
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty())
+        else {
+            return;
+        };
+        let Some(source_name) = rust_macro_owner_name(node, source) else {
+            return;
+        };
+        let is_write = parent.is_some_and(|parent| {
+            matches!(
+                parent.kind(),
+                "assignment_expression" | "compound_assignment_expr"
+            ) && parent.child_by_field_name("left") == Some(node)
+        });
+        edges.push(ManualEdgeSpec {
+            source_name,
+            target_name,
+            kind: if is_write {
+                EdgeKind::ASSIGNMENT
+            } else {
+                EdgeKind::USAGE
+            },
+            line: Some(node.start_position().row as u32 + 1),
+        });
+    });
+    edges
+}
+
+fn python_decorator_target_name(node: TsNode<'_>, source: &str) -> Option<String> {
+    match node.kind() {
+        "decorator" => {
+            let mut cursor = node.walk();
+            node.named_children(&mut cursor)
+                .find_map(|child| python_decorator_target_name(child, source))
+        }
+        "call" => node
+            .child_by_field_name("function")
+            .and_then(|function| python_decorator_target_name(function, source)),
+        "attribute" => node
             .child_by_field_name("attribute")
             .and_then(|attribute| node_source_text(attribute, source))
             .map(|name| name.trim().to_string())
@@ -7260,11 +7912,21 @@ fn append_manual_usage_edges(
     if language_name == "ruby" {
         specs.extend(collect_ruby_bare_call_edges(tree, source));
     }
-    if specs.is_empty() {
+    // Field writes and reads are only extracted for Rust so far.
+    let field_specs = if language_name == "rust" {
+        collect_rust_field_access_edges(tree, source)
+    } else {
+        Vec::new()
+    };
+    if specs.is_empty() && field_specs.is_empty() {
         return;
     }
 
-    for spec in specs {
+    let specs = specs
+        .into_iter()
+        .map(|spec| (spec, false))
+        .chain(field_specs.into_iter().map(|spec| (spec, true)));
+    for (spec, is_field_access) in specs {
         let Some(source_id) = unique_node_id_by_name(unique_nodes, &spec.source_name, |kind| {
             if language_name == "python" {
                 matches!(
@@ -7278,6 +7940,14 @@ fn append_manual_usage_edges(
             continue;
         };
         let target_id = match spec.kind {
+            _ if is_field_access => {
+                unique_node_id_by_name(unique_nodes, &spec.target_name, |kind| {
+                    matches!(
+                        kind,
+                        NodeKind::FIELD | NodeKind::VARIABLE | NodeKind::GLOBAL_VARIABLE
+                    )
+                })
+            }
             EdgeKind::CALL => unique_node_id_by_name(unique_nodes, &spec.target_name, |kind| {
                 if is_tsx_file
                     || language_name == "python"
@@ -15429,9 +16099,13 @@ fn infer_access_from_source(
     language_name: &str,
     tree: &Tree,
     source: &str,
+    name: &str,
     start_line: u32,
     kind: NodeKind,
 ) -> Option<AccessKind> {
+    if language_name == "python" {
+        return classify_python_name_access(name, kind);
+    }
     if !matches!(
         kind,
         NodeKind::METHOD
@@ -15527,13 +16201,255 @@ fn definition_occurrences(
                     end_line,
                     end_col,
                 },
+                edge_id: None,
+                notebook_cell: None,
+            });
+        }
+    }
+
+    occurrences
+}
+
+/// Reference occurrences for CALL edges, each linked back to the edge it
+/// justifies so repeated calls to one symbol in a file stay distinguishable.
+///
+/// The span covers the callee's short name starting at the call-site column
+/// recorded in the edge's callsite identity.
+fn callsite_occurrences(nodes: &[Node], edges: &[Edge]) -> Vec<Occurrence> {
+    let names = nodes
+        .iter()
+        .map(|node| (node.id, short_member_name(&node.serialized_name)))
+        .collect::<HashMap<_, _>>();
+    edges
+        .iter()
+        .filter(|edge| edge.kind == EdgeKind::CALL)
+        .filter_map(|edge| {
+            let file_node_id = edge.file_node_id?;
+            let line = edge.line.filter(|line| *line > 0)?;
+            let start_col = edge
+                .callsite_identity
+                .as_deref()
+                .and_then(callsite_identity_start_col)?;
+            let name_len = names
+                .get(&edge.target)
+                .map_or(0, |name| name.chars().count() as u32);
+            Some(Occurrence {
+                element_id: edge.target.0,
+                kind: OccurrenceKind::REFERENCE,
+                location: SourceLocation {
+                    file_node_id,
+                    start_line: line,
+                    start_col,
+                    end_line: line,
+                    end_col: start_col.saturating_add(name_len),
+                },
+                edge_id: Some(edge.id),
+                notebook_cell: None,
+            })
+        })
+        .collect()
+}
+
+/// Point `#include` edges at the FILE node of the header the compilation
+/// database's search paths find, instead of a MODULE node named after the
+/// directive. Each resolved edge is marked as a project or system include;
+/// system headers get a FILE node of their own since they are not indexed.
+/// Includes no search path finds keep their MODULE node.
+fn resolve_compiled_includes(
+    path: &Path,
+    info: &compilation_database::CompilationInfo,
+    nodes: &mut Vec<Node>,
+    edges: &mut [Edge],
+    occurrences: &mut [Occurrence],
+) {
+    let headers = nodes
+        .iter()
+        .filter(|node| node.kind == NodeKind::MODULE)
+        .filter_map(|node| {
+            let name = node.serialized_name.trim();
+            let (header, quoted) = if let Some(header) = name
+                .strip_prefix('<')
+                .and_then(|rest| rest.strip_suffix('>'))
+            {
+                (header, false)
+            } else {
+                (name.trim_matches('"'), true)
+            };
+            let (header_path, kind) = info.resolve_include(path, header, quoted)?;
+            Some((node.id, (header_path, kind)))
+        })
+        .collect::<HashMap<_, _>>();
+    if headers.is_empty() {
+        return;
+    }
+
+    let mut retargeted = HashMap::new();
+    for edge in edges.iter_mut() {
+        if edge.kind != EdgeKind::IMPORT {
+            continue;
+        }
+        let Some((header_path, kind)) = headers.get(&edge.effective_target()) else {
+            continue;
+        };
+        let file_id = NodeId(WorkspaceIndexer::canonical_file_node_id_for_path(
+            header_path,
+        ));
+        retargeted.insert(edge.effective_target(), file_id);
+        edge.target = file_id;
+        edge.resolved_target = Some(file_id);
+        edge.confidence = Some(1.0);
+        edge.certainty = Some(ResolutionCertainty::Certain);
+        edge.callsite_identity = Some(format!("{INCLUDE_CALLSITE_PREFIX}{}", kind.as_str()));
+    }
+    for occurrence in occurrences.iter_mut() {
+        if let Some(file_id) = retargeted.get(&NodeId(occurrence.element_id)) {
+            occurrence.element_id = file_id.0;
+        }
+    }
+
+    nodes.retain(|node| !retargeted.contains_key(&node.id));
+    let mut retargeted = retargeted.into_iter().collect::<Vec<_>>();
+    retargeted.sort_by_key(|(_, file_id)| file_id.0);
+    retargeted.dedup_by_key(|(_, file_id)| *file_id);
+    for (module_id, file_id) in retargeted {
+        let (header_path, kind) = &headers[&module_id];
+        if *kind == compilation_database::IncludeKind::System {
+            nodes.push(Node {
+                id: file_id,
+                kind: NodeKind::FILE,
+                serialized_name: header_path.to_string_lossy().to_string(),
+                ..Default::default()
             });
         }
     }
+}
+
+/// Write and read occurrences for accesses to fields declared in this file.
+///
+/// An access that is the target of an assignment, compound assignment, or
+/// increment becomes a `WRITE_REFERENCE`; any other access is a
+/// `READ_REFERENCE`. Field names declared more than once in the file are
+/// skipped so an access never lands on the wrong field.
+fn field_access_occurrences(
+    tree: &Tree,
+    source: &str,
+    nodes: &[Node],
+    file_id: NodeId,
+) -> Vec<Occurrence> {
+    let mut fields = HashMap::<&str, Option<&Node>>::new();
+    for node in nodes
+        .iter()
+        .filter(|node| node.kind == NodeKind::FIELD && node.file_node_id == Some(file_id))
+    {
+        fields
+            .entry(short_member_name(&node.serialized_name))
+            .and_modify(|field| *field = None)
+            .or_insert(Some(node));
+    }
+    if fields.is_empty() {
+        return Vec::new();
+    }
 
+    let mut occurrences = Vec::new();
+    walk_tree_nodes(tree.root_node(), &mut |access| {
+        let name_field = match access.kind() {
+            "field_expression" | "field_access" | "selector_expression" => "field",
+            "member_expression" => "property",
+            "attribute" => "attribute",
+            "member_access_expression" => "name",
+            _ => return,
+        };
+        let Some(name) = access.child_by_field_name(name_field) else {
+            return;
+        };
+        // `value.len()` names a method, not a field.
+        if access.parent().is_some_and(|parent| {
+            parent
+                .child_by_field_name("function")
+                .is_some_and(|function| same_ts_span(function, access))
+        }) {
+            return;
+        }
+        let Some(field) = source
+            .get(name.byte_range())
+            .and_then(|text| fields.get(text.trim_start_matches('#')))
+            .copied()
+            .flatten()
+        else {
+            return;
+        };
+        let start_line = name.start_position().row as u32 + 1;
+        let start_col = name.start_position().column as u32 + 1;
+        if span_contains(field, start_line, start_col) {
+            return;
+        }
+        occurrences.push(Occurrence {
+            element_id: field.id.0,
+            kind: if field_access_is_write(access) {
+                OccurrenceKind::WRITE_REFERENCE
+            } else {
+                OccurrenceKind::READ_REFERENCE
+            },
+            location: SourceLocation {
+                file_node_id: file_id,
+                start_line,
+                start_col,
+                end_line: name.end_position().row as u32 + 1,
+                end_col: name.end_position().column as u32 + 1,
+            },
+            edge_id: None,
+            notebook_cell: None,
+        });
+    });
     occurrences
 }
 
+/// Whether `access` is assigned to, looking through tuple and list targets
+/// such as `a.x, a.y = 1, 2`.
+fn field_access_is_write(access: TsNode<'_>) -> bool {
+    let mut target = access;
+    let mut parent = access.parent();
+    while let Some(node) = parent {
+        if !matches!(
+            node.kind(),
+            "parenthesized_expression"
+                | "expression_list"
+                | "pattern_list"
+                | "tuple"
+                | "tuple_pattern"
+                | "tuple_expression"
+        ) {
+            break;
+        }
+        target = node;
+        parent = node.parent();
+    }
+    let Some(parent) = parent else {
+        return false;
+    };
+    match parent.kind() {
+        "assignment_expression"
+        | "assignment"
+        | "assignment_statement"
+        | "augmented_assignment"
+        | "augmented_assignment_expression"
+        | "compound_assignment_expr" => parent
+            .child_by_field_name("left")
+            .is_some_and(|left| same_ts_span(left, target)),
+        "update_expression" | "inc_statement" | "dec_statement" => true,
+        _ => false,
+    }
+}
+
+fn span_contains(node: &Node, line: u32, col: u32) -> bool {
+    let (Some(start_line), Some(start_col), Some(end_line), Some(end_col)) =
+        (node.start_line, node.start_col, node.end_line, node.end_col)
+    else {
+        return false;
+    };
+    (start_line, start_col) <= (line, col) && (line, col) <= (end_line, end_col)
+}
+
 fn apply_qualified_names(nodes: Vec<Node>, edges: &[Edge], language_name: &str) -> Vec<Node> {
     let mut parent_map: HashMap<NodeId, Vec<NodeId>> = HashMap::new();
     let mut has_parent: HashMap<NodeId, bool> = HashMap::new();
@@ -15776,6 +16692,8 @@ fn canonicalize_nodes_with_file_identity(
                     || value.starts_with("route_endpoint:")
                     || value.starts_with("tauri:command:")
                     || value.starts_with("payload:collection:")
+                    || value.starts_with("sql:table:")
+                    || value.starts_with("proto:")
             })
             .map(str::to_string)
             .unwrap_or_else(|| {
@@ -16745,6 +17663,41 @@ fn index_template_file(
     Ok(local_storage)
 }
 
+fn prepare_notebook_index_work(path: &Path) -> Result<IntermediateStorage> {
+    let source = std::fs::read_to_string(path)?;
+    index_notebook_file(path, &source)
+}
+
+/// Index the Python code cells of a Jupyter notebook.
+///
+/// Cells are parsed as one virtual document, so graph locations use that
+/// document's lines; each occurrence also records its notebook cell position.
+/// Notebooks for other kernels produce no rows.
+fn index_notebook_file(path: &Path, source: &str) -> Result<IntermediateStorage> {
+    let Some(document) = notebook::NotebookDocument::parse_python(source)? else {
+        return Ok(IntermediateStorage::default());
+    };
+    let language_config = get_language_for_ext("py")
+        .ok_or_else(|| anyhow!("missing tree-sitter config for notebook code cells"))?;
+
+    let mut index_result = index_file(path, &document.source, &language_config, None, None)?;
+    if let Some(file_info) = index_result.files.first_mut() {
+        file_info.language = notebook::NOTEBOOK_LANGUAGE.to_string();
+    }
+    document.annotate_occurrences(&mut index_result.occurrences);
+
+    Ok(IntermediateStorage {
+        files: index_result.files,
+        nodes: index_result.nodes,
+        edges: index_result.edges,
+        occurrences: index_result.occurrences,
+        component_access: index_result.component_access,
+        callable_projection_states: index_result.callable_projection_states,
+        impl_anchor_node_ids: index_result.impl_anchor_node_ids,
+        ..Default::default()
+    })
+}
+
 fn index_text_only_file(path: &Path) -> Result<IntermediateStorage> {
     let source = std::fs::read_to_string(path)?;
     let mut local_storage = IntermediateStorage::default();
@@ -16757,7 +17710,7 @@ fn index_text_only_file(path: &Path) -> Result<IntermediateStorage> {
         indexed: true,
         complete: true,
         line_count: source.lines().count() as u32,
-        file_role: codestory_store::FileRole::classify_path(path),
+        file_role: codestory_store::FileRole::classify_source(path, &source),
     });
     local_storage.nodes.push(file_node);
     if text_only_language_name(path) == "go" {
@@ -16840,6 +17793,8 @@ fn append_text_only_go_symbols(
                 end_line: symbol.line,
                 end_col: symbol.col + symbol.name.len().max(1) as u32,
             },
+            edge_id: None,
+            notebook_cell: None,
         });
     }
 }
@@ -17016,6 +17971,8 @@ fn append_text_only_tauri_invocations(
                     .col
                     .saturating_add(invocation.command.len() as u32),
             },
+            edge_id: None,
+            notebook_cell: None,
         });
     }
 }
@@ -17046,7 +18003,7 @@ fn index_openapi_schema_file(path: &Path, source: &str) -> Result<Option<Interme
         indexed: true,
         complete: true,
         line_count: source.lines().count() as u32,
-        file_role: codestory_store::FileRole::classify_path(path),
+        file_role: codestory_store::FileRole::classify_source(path, source),
     });
     local_storage.nodes.push(file_node);
 
@@ -17092,6 +18049,8 @@ fn index_openapi_schema_file(path: &Path, source: &str) -> Result<Option<Interme
                 end_line: endpoint.line,
                 end_col: label.len().max(1) as u32,
             },
+            edge_id: None,
+            notebook_cell: None,
         });
     }
 
@@ -17342,6 +18301,10 @@ fn collect_framework_routes(path: &Path, language_name: &str, source: &str) -> V
         collect_remix_file_route(path, &code_source, &mut routes);
         collect_nestjs_routes(&code_source, &mut routes);
     }
+    attach_declared_route_handlers(language_name, &code_lines, &mut routes);
+    if language_name == "java" {
+        apply_spring_class_prefixes(&code_lines, &mut routes);
+    }
     if language_name == "svelte" {
         collect_sveltekit_page_route(path, 1, &mut routes);
     }
@@ -18018,21 +18981,153 @@ fn collect_spring_route(line: &str, line_number: u32, routes: &mut Vec<Framework
         ("@RequestMapping", "ROUTE"),
     ];
     for (annotation, method) in mappings {
-        if line.contains(annotation)
-            && let Some(path) = first_quoted_string(line)
+        let Some(arguments) = line
+            .split_once(annotation)
+            .map(|(_, rest)| rest.trim_start())
+        else {
+            continue;
+        };
+        if arguments.starts_with(|ch: char| ch.is_alphanumeric() || ch == '_') {
+            continue;
+        }
+        // A bare mapping inherits the class-level path; see `apply_spring_class_prefixes`.
+        let path = first_quoted_string(arguments).unwrap_or_default();
+        let method = if method == "ROUTE" {
+            spring_request_method(arguments).unwrap_or(method)
+        } else {
+            method
+        };
+        routes.push(FrameworkRoute::new(
+            "spring",
+            method.to_string(),
+            path,
+            None,
+            line_number,
+            "annotation",
+        ));
+    }
+}
+
+fn spring_request_method(arguments: &str) -> Option<&'static str> {
+    let (_, rest) = arguments.split_once("RequestMethod.")?;
+    ["GET", "POST", "PUT", "PATCH", "DELETE"]
+        .into_iter()
+        .find(|method| rest.starts_with(method))
+}
+
+/// Scope Spring method mappings under the `@RequestMapping` of their class.
+///
+/// The class-level mapping only contributes a prefix and is not itself an
+/// endpoint.
+fn apply_spring_class_prefixes(code_lines: &[String], routes: &mut Vec<FrameworkRoute>) {
+    let class_mapping_lines = routes
+        .iter()
+        .filter(|route| {
+            route.framework == "spring"
+                && route.handler.is_none()
+                && next_route_declaration_line(code_lines, route.line)
+                    .is_some_and(declares_route_container)
+        })
+        .map(|route| (route.line, route.raw_path.clone()))
+        .collect::<Vec<_>>();
+    if class_mapping_lines.is_empty() {
+        return;
+    }
+    routes.retain(|route| {
+        route.framework != "spring"
+            || !class_mapping_lines
+                .iter()
+                .any(|(line, _)| *line == route.line)
+    });
+    for route in routes.iter_mut() {
+        if route.framework != "spring" {
+            continue;
+        }
+        let Some((_, prefix)) = class_mapping_lines
+            .iter()
+            .rev()
+            .find(|(line, _)| *line < route.line)
+        else {
+            continue;
+        };
+        *route = FrameworkRoute::new(
+            "spring",
+            route.method.clone(),
+            join_route_paths(prefix, &route.raw_path),
+            route.handler.take(),
+            route.line,
+            "annotation",
+        );
+    }
+}
+
+/// Name decorator, annotation, and attribute routes after the function they
+/// sit on, so the route gets a CALL edge to its handler.
+fn attach_declared_route_handlers(
+    language_name: &str,
+    code_lines: &[String],
+    routes: &mut [FrameworkRoute],
+) {
+    for route in routes.iter_mut() {
+        if route.handler.is_some()
+            || !matches!(
+                route.source_convention,
+                "decorator" | "annotation" | "attribute"
+            )
         {
-            routes.push(FrameworkRoute::new(
-                "spring",
-                method.to_string(),
-                path,
-                None,
-                line_number,
-                "annotation",
-            ));
+            continue;
         }
+        route.handler = next_route_declaration_line(code_lines, route.line)
+            .and_then(|line| declared_route_handler_name(language_name, line));
     }
 }
 
+/// First line after `route_line` that is not blank, a decorator, or an attribute.
+fn next_route_declaration_line(code_lines: &[String], route_line: u32) -> Option<&str> {
+    code_lines
+        .iter()
+        .skip(route_line as usize)
+        .take(8)
+        .map(|line| line.trim())
+        .find(|line| {
+            !line.is_empty()
+                && !line.starts_with('@')
+                && !line.starts_with("#[")
+                && !line.starts_with('[')
+        })
+}
+
+fn declares_route_container(line: &str) -> bool {
+    line.split_whitespace()
+        .any(|token| matches!(token, "class" | "interface" | "record" | "object"))
+}
+
+fn declared_route_handler_name(language_name: &str, line: &str) -> Option<String> {
+    if declares_route_container(line) {
+        return None;
+    }
+    let name = match language_name {
+        "python" => line
+            .trim_start_matches("async ")
+            .strip_prefix("def ")?
+            .split('(')
+            .next()?
+            .trim()
+            .to_string(),
+        "rust" => {
+            let (_, rest) = line.split_once("fn ")?;
+            rest.split(['(', '<']).next()?.trim().to_string()
+        }
+        _ => {
+            if !line.contains('(') {
+                return None;
+            }
+            typescript_method_name(line)?
+        }
+    };
+    (!name.is_empty() && name.chars().all(|ch| ch.is_alphanumeric() || ch == '_')).then_some(name)
+}
+
 fn collect_rust_web_route(line: &str, line_number: u32, routes: &mut Vec<FrameworkRoute>) {
     if line.contains(".route(")
         && let Some(path) = first_quoted_string(line)
@@ -18735,6 +19830,8 @@ fn framework_route_occurrence(
             end_line: route.line,
             end_col: framework_route_label(route).len().max(1) as u32,
         },
+        edge_id: None,
+        notebook_cell: None,
     }
 }
 
@@ -18872,6 +19969,8 @@ fn payload_collection_occurrence(
             end_line: line,
             end_col: col.saturating_add(slug.len() as u32),
         },
+        edge_id: None,
+        notebook_cell: None,
     }
 }
 
@@ -19399,29 +20498,236 @@ fn append_payload_collection_symbols(
     }
 }
 
-#[derive(Debug, Clone)]
-struct ApiEndpointCall {
-    method: String,
-    path: String,
-    line: u32,
-    col: u32,
-}
+/// Link SQL statements held in host-language string literals to the tables
+/// they name.
+///
+/// Each referenced table becomes a shared `sql:table:` node, so every query
+/// site across the project lands on the same node, with a USAGE edge from the
+/// enclosing callable and a reference occurrence on the table name inside the
+/// literal.
+fn append_embedded_sql_references(
+    language_name: &str,
+    source: &str,
+    file_id: NodeId,
+    flags: IndexFeatureFlags,
+    sinks: &mut FrameworkSymbolSinks<'_>,
+) {
+    if language_name == "sql" || !has_embedded_sql_evidence(source) {
+        return;
+    }
 
-fn collect_api_endpoint_calls(source: &str) -> Vec<ApiEndpointCall> {
-    let mut calls = Vec::new();
     for (line_index, line) in source.lines().enumerate() {
-        for (literal, col) in quoted_string_literals(line) {
-            if !is_api_path_literal(&literal) || !is_api_endpoint_call_context(line, col) {
+        let line_number = line_index as u32 + 1;
+        for (literal, literal_col) in quoted_string_literals(code_before_line_comment(line)) {
+            if !crate::structural::looks_like_embedded_sql(&literal) {
                 continue;
             }
-            calls.push(ApiEndpointCall {
-                method: infer_http_method(line),
-                path: normalize_api_path(&literal),
-                line: line_index as u32 + 1,
-                col,
-            });
-        }
-    }
+            for table in crate::structural::embedded_sql_table_references(&literal) {
+                let col = embedded_sql_table_col(&literal, literal_col, &table);
+                let table_node = embedded_sql_table_node(file_id, &table, line_number, col);
+                let table_node_id = table_node.id;
+                sinks
+                    .unique_nodes
+                    .entry(table_node_id)
+                    .or_insert(table_node);
+                sinks.result_occurrences.push(payload_collection_occurrence(
+                    file_id,
+                    table_node_id,
+                    embedded_sql_table_short_name(&table),
+                    line_number,
+                    col,
+                    OccurrenceKind::REFERENCE,
+                ));
+
+                let source_id =
+                    enclosing_callable_node_id(sinks.unique_nodes, line_number).unwrap_or(file_id);
+                let mut edge = Edge {
+                    id: EdgeId(0),
+                    source: source_id,
+                    target: table_node_id,
+                    kind: EdgeKind::USAGE,
+                    file_node_id: Some(file_id),
+                    line: Some(line_number),
+                    certainty: Some(ResolutionCertainty::Probable),
+                    confidence: Some(0.60),
+                    callsite_identity: Some(format!("sql:{table}:{line_number}:{col}")),
+                    ..Default::default()
+                };
+                if !sinks.edge_keys.insert(edge_dedup_key(&edge, flags)) {
+                    continue;
+                }
+                edge.id = EdgeId(generate_edge_id_for_edge(&edge, flags));
+                sinks.result_edges.push(edge);
+            }
+        }
+    }
+}
+
+fn has_embedded_sql_evidence(source: &str) -> bool {
+    let upper = source.to_ascii_uppercase();
+    ["SELECT ", "INSERT ", "UPDATE ", "DELETE "]
+        .iter()
+        .any(|keyword| upper.contains(keyword))
+}
+
+fn embedded_sql_table_short_name(table: &str) -> &str {
+    table.rsplit('.').next().unwrap_or(table)
+}
+
+/// 1-based column of the table name inside the literal starting at
+/// `literal_col` (the opening quote), falling back to the literal itself.
+fn embedded_sql_table_col(literal: &str, literal_col: u32, table: &str) -> u32 {
+    let name = embedded_sql_table_short_name(table).to_ascii_lowercase();
+    let offset = literal
+        .to_ascii_lowercase()
+        .find(&name)
+        .map_or(0, |index| index as u32);
+    literal_col.saturating_add(1).saturating_add(offset)
+}
+
+fn embedded_sql_table_node(file_id: NodeId, table: &str, line: u32, col: u32) -> Node {
+    let canonical_id = format!("sql:table:{table}");
+    Node {
+        id: NodeId(generate_id(&canonical_id)),
+        kind: NodeKind::CLASS,
+        serialized_name: table.to_string(),
+        qualified_name: Some(table.to_string()),
+        canonical_id: Some(canonical_id),
+        file_node_id: Some(file_id),
+        start_line: Some(line),
+        start_col: Some(col),
+        end_line: Some(line),
+        end_col: Some(col.saturating_add(embedded_sql_table_short_name(table).len() as u32)),
+    }
+}
+
+/// Link `protoc` output back to the `.proto` definitions it was generated
+/// from, so trails can continue across the codegen boundary.
+///
+/// Generated clients, servers, and stubs point at the proto service, their
+/// members at the matching rpc, and generated message types at the proto
+/// message. Matching is by naming convention only.
+fn append_protobuf_codegen_links(
+    path: &Path,
+    source: &str,
+    file_id: NodeId,
+    flags: IndexFeatureFlags,
+    sinks: &mut FrameworkSymbolSinks<'_>,
+) {
+    if !crate::structural::is_protobuf_generated_source(path, source) {
+        return;
+    }
+
+    let mut links = sinks
+        .unique_nodes
+        .values()
+        .filter(|node| {
+            !node
+                .canonical_id
+                .as_deref()
+                .is_some_and(|canonical_id| canonical_id.starts_with("proto:"))
+        })
+        .filter_map(|node| {
+            let line = node.start_line?;
+            let (definition_kind, name) = protobuf_definition_for_generated_node(node)?;
+            Some((line, node.id, definition_kind, name))
+        })
+        .collect::<Vec<_>>();
+    links.sort_by_key(|(line, node_id, _, _)| (*line, *node_id));
+
+    for (line, source_id, definition_kind, name) in links {
+        let canonical_id = definition_kind.canonical_id(&name);
+        let target_id = NodeId(generate_id(&canonical_id));
+        sinks.unique_nodes.entry(target_id).or_insert_with(|| Node {
+            id: target_id,
+            kind: definition_kind.node_kind(),
+            serialized_name: name.clone(),
+            qualified_name: Some(name.clone()),
+            canonical_id: Some(canonical_id),
+            file_node_id: Some(file_id),
+            ..Default::default()
+        });
+        let kind = if definition_kind == crate::structural::ProtobufDefinitionKind::Rpc {
+            EdgeKind::USAGE
+        } else {
+            EdgeKind::TYPE_USAGE
+        };
+        let mut edge = Edge {
+            id: EdgeId(0),
+            source: source_id,
+            target: target_id,
+            kind,
+            file_node_id: Some(file_id),
+            line: Some(line),
+            certainty: Some(ResolutionCertainty::Probable),
+            confidence: Some(0.70),
+            ..Default::default()
+        };
+        if !sinks.edge_keys.insert(edge_dedup_key(&edge, flags)) {
+            continue;
+        }
+        edge.id = EdgeId(generate_edge_id_for_edge(&edge, flags));
+        sinks.result_edges.push(edge);
+    }
+}
+
+fn protobuf_definition_for_generated_node(
+    node: &Node,
+) -> Option<(crate::structural::ProtobufDefinitionKind, String)> {
+    use crate::structural::{
+        ProtobufDefinitionKind, protobuf_generated_message_name, protobuf_generated_rpc_name,
+        protobuf_generated_service_name,
+    };
+
+    let (owner, name) = match node.serialized_name.rsplit_once(['.', ':']) {
+        Some((owner, name)) => {
+            let owner = owner.trim_end_matches(':');
+            (Some(owner.rsplit(['.', ':']).next().unwrap_or(owner)), name)
+        }
+        None => (None, node.serialized_name.as_str()),
+    };
+    match node.kind {
+        NodeKind::METHOD | NodeKind::FUNCTION => {
+            let service = protobuf_generated_service_name(owner?)?;
+            let rpc = protobuf_generated_rpc_name(name)?;
+            Some((ProtobufDefinitionKind::Rpc, format!("{service}.{rpc}")))
+        }
+        NodeKind::CLASS | NodeKind::STRUCT | NodeKind::INTERFACE => {
+            if let Some(service) = protobuf_generated_service_name(name) {
+                return Some((ProtobufDefinitionKind::Service, service));
+            }
+            protobuf_generated_message_name(name)
+                .map(|message| (ProtobufDefinitionKind::Message, message.to_string()))
+        }
+        NodeKind::ENUM => protobuf_generated_message_name(name)
+            .map(|name| (ProtobufDefinitionKind::Enum, name.to_string())),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ApiEndpointCall {
+    method: String,
+    path: String,
+    line: u32,
+    col: u32,
+}
+
+fn collect_api_endpoint_calls(source: &str) -> Vec<ApiEndpointCall> {
+    let mut calls = Vec::new();
+    for (line_index, line) in source.lines().enumerate() {
+        for (literal, col) in quoted_string_literals(line) {
+            if !is_api_path_literal(&literal) || !is_api_endpoint_call_context(line, col) {
+                continue;
+            }
+            calls.push(ApiEndpointCall {
+                method: infer_http_method(line),
+                path: normalize_api_path(&literal),
+                line: line_index as u32 + 1,
+                col,
+            });
+        }
+    }
     calls
 }
 
@@ -20187,6 +21493,7 @@ pub fn index_file(
         .map(|ext| ext.eq_ignore_ascii_case("tsx") || ext.eq_ignore_ascii_case("jsx"))
         .unwrap_or(false);
 
+    file_isolation::enter_index_stage("parse");
     let mut parser = Parser::new();
     parser
         .set_language(&language_config.language)
@@ -20196,6 +21503,7 @@ pub fn index_file(
     let tree = parser
         .parse(source, None)
         .ok_or_else(|| anyhow!("Failed to parse source"))?;
+    file_isolation::enter_index_stage("tags query");
     let mut tag_definitions = extract_tag_definitions(compiled_rules, &tree, source)?;
     let declaration_span_overrides =
         collect_declaration_span_overrides(language_config.language_name, &tree, source);
@@ -20212,6 +21520,7 @@ pub fn index_file(
     let functions = Functions::stdlib();
     let config = ExecutionConfig::new(&functions, &variables).lazy(flags.lazy_graph_execution);
 
+    file_isolation::enter_index_stage("graph rules");
     let graph = compiled_rules
         .graph_file
         .execute(&tree, source, &config, &NoCancellation)
@@ -20244,10 +21553,11 @@ pub fn index_file(
         indexed: true,
         complete: !tree.root_node().has_error(),
         line_count: source.lines().count() as u32,
-        file_role: codestory_store::FileRole::classify_path(path),
+        file_role: codestory_store::FileRole::classify_source(path, source),
     });
 
     // 1. First pass: Create nodes and a temporary mapping from GraphNodeId -> OurNodeId
+    file_isolation::enter_index_stage("node extraction");
     let mut graph_to_node_id = HashMap::new();
     let mut unique_nodes: HashMap<NodeId, Node> = HashMap::new();
     let mut component_access_by_node_id: HashMap<NodeId, AccessKind> = HashMap::new();
@@ -20407,6 +21717,7 @@ pub fn index_file(
                     language_config.language_name,
                     &tree,
                     source,
+                    &name_str,
                     start_line,
                     kind,
                 )
@@ -20476,6 +21787,7 @@ pub fn index_file(
     );
 
     // 2. Second pass: Create edges using tree-sitter-graph output
+    file_isolation::enter_index_stage("edge extraction");
     let mut edge_keys: HashSet<EdgeDedupKey> = HashSet::new();
     let mut callsite_ordinals: HashMap<(NodeId, Option<u32>), u32> = HashMap::new();
 
@@ -20696,6 +22008,34 @@ pub fn index_file(
             callsite_ordinals: &mut callsite_ordinals,
         },
     );
+    append_embedded_sql_references(
+        language_config.language_name,
+        source,
+        file_id,
+        flags,
+        &mut FrameworkSymbolSinks {
+            unique_nodes: &mut unique_nodes,
+            result_edges: &mut result_edges,
+            result_occurrences: &mut result_occurrences,
+            component_access_by_node_id: &mut component_access_by_node_id,
+            edge_keys: &mut edge_keys,
+            callsite_ordinals: &mut callsite_ordinals,
+        },
+    );
+    append_protobuf_codegen_links(
+        path,
+        source,
+        file_id,
+        flags,
+        &mut FrameworkSymbolSinks {
+            unique_nodes: &mut unique_nodes,
+            result_edges: &mut result_edges,
+            result_occurrences: &mut result_occurrences,
+            component_access_by_node_id: &mut component_access_by_node_id,
+            edge_keys: &mut edge_keys,
+            callsite_ordinals: &mut callsite_ordinals,
+        },
+    );
     append_framework_routes(
         path,
         language_config,
@@ -20727,6 +22067,7 @@ pub fn index_file(
     ));
 
     // 3. Resolve qualified names, canonicalize IDs, and remap projections.
+    file_isolation::enter_index_stage("projection remapping");
     let post_processed = post_process_index_results(
         result_nodes,
         &mut result_edges,
@@ -20771,8 +22112,27 @@ pub fn index_file(
         &mut impl_anchor_node_ids,
     );
 
+    if matches!(language_config.language_name, "c" | "cpp")
+        && let Some(info) = &compilation_info
+    {
+        resolve_compiled_includes(
+            path,
+            info,
+            &mut final_nodes,
+            &mut result_edges,
+            &mut result_occurrences,
+        );
+    }
+
     let callable_projection_states =
         build_callable_projection_states(&final_nodes, &result_edges, &result_occurrences);
+    result_occurrences.extend(callsite_occurrences(&final_nodes, &result_edges));
+    result_occurrences.extend(field_access_occurrences(
+        &tree,
+        source,
+        &final_nodes,
+        file_id,
+    ));
 
     if let Some(st) = &symbol_table {
         for node in &final_nodes {
@@ -20791,6 +22151,93 @@ pub fn index_file(
     })
 }
 
+/// Definitions a file's tags query finds, without graph rules or edges.
+///
+/// Used to keep a file's symbols in the graph when [`index_file`] fails or
+/// panics partway. The file row is marked incomplete.
+fn index_file_definitions(
+    path: &Path,
+    source: &str,
+    language_config: &LanguageConfig,
+) -> Result<IndexResult> {
+    let mut parser = Parser::new();
+    parser
+        .set_language(&language_config.language)
+        .map_err(|e| anyhow!("Language error: {:?}", e))?;
+    let compiled_rules = language_config.compiled_rules()?;
+    let tree = parser
+        .parse(source, None)
+        .ok_or_else(|| anyhow!("Failed to parse source"))?;
+    let tag_definitions = extract_tag_definitions(compiled_rules, &tree, source)?;
+
+    let (file_node, file_name, file_id) = file_node_from_source(path, source);
+    let mut nodes = vec![file_node];
+    let mut occurrences = Vec::new();
+    let mut seen = HashSet::new();
+    let mut definitions = tag_definitions.into_remaining();
+    definitions.sort_by(|left, right| {
+        (left.key.start_line, left.key.start_col, &left.key.name).cmp(&(
+            right.key.start_line,
+            right.key.start_col,
+            &right.key.name,
+        ))
+    });
+    for definition in definitions {
+        let canonical_seed = format!(
+            "{}:{}:{}",
+            file_name, definition.key.name, definition.key.start_line
+        );
+        let node_id = NodeId(generate_id(&canonical_seed));
+        if !seen.insert(node_id) {
+            continue;
+        }
+        let location = SourceLocation {
+            file_node_id: file_id,
+            start_line: definition.key.start_line,
+            start_col: definition.key.start_col,
+            end_line: definition.end_line,
+            end_col: definition.end_col,
+        };
+        nodes.push(Node {
+            id: node_id,
+            kind: definition.kind,
+            serialized_name: definition.key.name,
+            file_node_id: Some(file_id),
+            start_line: Some(location.start_line),
+            start_col: Some(location.start_col),
+            end_line: Some(location.end_line),
+            end_col: Some(location.end_col),
+            ..Default::default()
+        });
+        occurrences.push(Occurrence {
+            element_id: node_id.0,
+            kind: OccurrenceKind::DEFINITION,
+            location,
+            edge_id: None,
+            notebook_cell: None,
+        });
+    }
+
+    Ok(IndexResult {
+        files: vec![codestory_store::FileInfo {
+            id: file_id.0,
+            path: path.to_path_buf(),
+            language: language_config.language_name.to_string(),
+            modification_time: file_modification_time(path),
+            indexed: true,
+            complete: false,
+            line_count: source.lines().count() as u32,
+            file_role: codestory_store::FileRole::classify_source(path, source),
+        }],
+        nodes,
+        edges: Vec::new(),
+        occurrences,
+        component_access: Vec::new(),
+        callable_projection_states: Vec::new(),
+        impl_anchor_node_ids: Vec::new(),
+    })
+}
+
 /// Return the public language-support profile for a file extension.
 pub fn language_support_profile_for_ext(ext: &str) -> Option<LanguageSupportProfile> {
     codestory_contracts::language_support::language_support_profile_for_ext(ext).copied()
@@ -20812,6 +22259,26 @@ pub fn get_language_for_ext(ext: &str) -> Option<LanguageConfig> {
     language_configs::get_language_for_ext(ext)
 }
 
+/// Version of each builtin tree-sitter grammar, keyed by language name.
+///
+/// Grammars that embed no release metadata report their parser ABI instead,
+/// as `abi <n>`.
+pub fn grammar_versions() -> BTreeMap<String, String> {
+    language_configs::builtin_language_configs()
+        .into_iter()
+        .map(|(name, config)| {
+            let version = match config.language.metadata() {
+                Some(metadata) => format!(
+                    "{}.{}.{}",
+                    metadata.major_version, metadata.minor_version, metadata.patch_version
+                ),
+                None => format!("abi {}", config.language.abi_version()),
+            };
+            (name.to_string(), version)
+        })
+        .collect()
+}
+
 /// Generate a stable deterministic id from a canonical graph name.
 pub fn generate_id(name: &str) -> i64 {
     let mut h: u64 = 0xcbf29ce484222325;
@@ -21301,6 +22768,7 @@ fn edge_kind_from_str(kind: &str) -> Option<EdgeKind> {
         "IMPORT" => Some(EdgeKind::IMPORT),
         "MACRO_USAGE" => Some(EdgeKind::MACRO_USAGE),
         "ANNOTATION_USAGE" => Some(EdgeKind::ANNOTATION_USAGE),
+        "ASSIGNMENT" => Some(EdgeKind::ASSIGNMENT),
         "UNKNOWN" => Some(EdgeKind::UNKNOWN),
         _ => None,
     }
@@ -21619,6 +23087,32 @@ impl MyStruct {
         Ok(())
     }
 
+    #[test]
+    fn test_definitions_fallback_keeps_tagged_symbols_of_a_failed_file() -> Result<()> {
+        let rust_code = "struct Config { retries: u32 }\n\nfn load() -> Config {\n    todo!()\n}\n";
+        let language_config = get_language_for_ext("rs").unwrap();
+
+        let partial = index_file_definitions(Path::new("config.rs"), rust_code, &language_config)?;
+
+        assert!(!partial.files[0].complete);
+        assert!(partial.edges.is_empty());
+        let definitions = partial
+            .occurrences
+            .iter()
+            .filter(|occurrence| occurrence.kind == OccurrenceKind::DEFINITION)
+            .filter_map(|occurrence| {
+                partial
+                    .nodes
+                    .iter()
+                    .find(|node| node.id.0 == occurrence.element_id)
+                    .map(|node| node.serialized_name.as_str())
+            })
+            .collect::<Vec<_>>();
+        assert!(definitions.contains(&"Config"), "{definitions:?}");
+        assert!(definitions.contains(&"load"), "{definitions:?}");
+        Ok(())
+    }
+
     #[test]
     fn test_rust_type_anchor_prefers_declaration_over_impl_anchor() -> Result<()> {
         let rust_code = r#"
@@ -21674,6 +23168,116 @@ impl AppController {
         Ok(())
     }
 
+    #[test]
+    fn test_rust_test_attributes_are_recorded_as_annotation_usages() -> Result<()> {
+        let rust_code = r#"
+fn parse() {}
+
+#[test]
+fn parses_header() {
+    parse();
+}
+
+#[tokio::test]
+#[ignore]
+async fn parses_async() {}
+
+#[inline]
+fn helper() {}
+"#;
+        let language_config = get_language_for_ext("rs").unwrap();
+        let result = index_file(
+            Path::new("main.rs"),
+            rust_code,
+            &language_config,
+            None,
+            None,
+        )?;
+
+        let name_of = |id: NodeId| {
+            result
+                .nodes
+                .iter()
+                .find(|node| node.id == id)
+                .map(|node| node.serialized_name.clone())
+                .unwrap_or_default()
+        };
+        let mut annotated = result
+            .edges
+            .iter()
+            .filter(|edge| edge.kind == EdgeKind::ANNOTATION_USAGE)
+            .map(|edge| {
+                let source = name_of(edge.source);
+                let function = source.rsplit("::").next().unwrap_or_default().to_string();
+                (function, name_of(edge.target))
+            })
+            .collect::<Vec<_>>();
+        annotated.sort();
+        assert_eq!(
+            annotated,
+            vec![
+                ("parses_async".to_string(), "tokio::test".to_string()),
+                ("parses_header".to_string(), "test".to_string()),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_python_command_decorators_are_recorded_as_annotation_usages() -> Result<()> {
+        let python_code = r#"
+import click
+
+@click.command()
+def deploy():
+    pass
+
+@cli.command
+def status():
+    pass
+
+@click.option("--force")
+def helper():
+    pass
+"#;
+        let language_config = get_language_for_ext("py").unwrap();
+        let result = index_file(
+            Path::new("cli.py"),
+            python_code,
+            &language_config,
+            None,
+            None,
+        )?;
+
+        let name_of = |id: NodeId| {
+            result
+                .nodes
+                .iter()
+                .find(|node| node.id == id)
+                .map(|node| node.serialized_name.clone())
+                .unwrap_or_default()
+        };
+        let mut annotated = result
+            .edges
+            .iter()
+            .filter(|edge| edge.kind == EdgeKind::ANNOTATION_USAGE)
+            .map(|edge| {
+                let source = name_of(edge.source);
+                let function = source.rsplit('.').next().unwrap_or_default().to_string();
+                (function, name_of(edge.target))
+            })
+            .collect::<Vec<_>>();
+        annotated.sort();
+        assert_eq!(
+            annotated,
+            vec![
+                ("deploy".to_string(), "click.command".to_string()),
+                ("status".to_string(), "cli.command".to_string()),
+            ]
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_rust_impl_queries_normalize_plain_scoped_and_generic_type_expressions() -> Result<()> {
         let rust_code = r#"
@@ -22082,31 +23686,110 @@ public:
 "#;
         assert!(header_source_has_cpp_signals(cpp_header));
 
-        let base_config = get_language_for_ext("h").expect("header extension should resolve");
-        let upgraded = maybe_upgrade_header_language_from_source(
-            Path::new("StorageAccess.h"),
-            cpp_header,
-            &base_config,
-        )
-        .expect("C++ header signals should upgrade parser");
-        assert_eq!(upgraded.language_name, "cpp");
+        let base_config = get_language_for_ext("h").expect("header extension should resolve");
+        let upgraded = maybe_upgrade_header_language_from_source(
+            Path::new("StorageAccess.h"),
+            cpp_header,
+            &base_config,
+        )
+        .expect("C++ header signals should upgrade parser");
+        assert_eq!(upgraded.language_name, "cpp");
+    }
+
+    #[test]
+    fn test_file_completeness_tracks_parse_errors() -> Result<()> {
+        let language_config = get_language_for_ext("rs").unwrap();
+        let result = index_file(
+            Path::new("broken.rs"),
+            "fn broken( {",
+            &language_config,
+            None,
+            None,
+        )?;
+
+        assert_eq!(result.files.len(), 1);
+        assert!(
+            !result.files[0].complete,
+            "malformed Rust source should be incomplete"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_binary_parser_sources_are_skipped_as_non_fatal_collection_errors() -> Result<()> {
+        use codestory_store::Store as Storage;
+        use tempfile::tempdir;
+
+        let dir = tempdir()?;
+        let binary = dir.path().join("blob.rs");
+        std::fs::write(&binary, b"fn main() {}\0\x01\x02")?;
+        let text = dir.path().join("text.rs");
+        std::fs::write(&text, "fn kept() {}\n")?;
+        let plan = codestory_workspace::RefreshExecutionPlan {
+            mode: codestory_workspace::BuildMode::FullRefresh,
+            files_to_index: vec![binary, text],
+            files_to_remove: Vec::new(),
+            existing_file_ids: HashMap::new(),
+        };
+        let mut storage = Storage::new_in_memory()?;
+        WorkspaceIndexer::new(dir.path().to_path_buf()).run(
+            &mut storage,
+            &plan,
+            &EventBus::new(),
+            None,
+        )?;
+
+        let errors = storage.get_errors(None)?;
+        assert_eq!(errors.len(), 1);
+        assert!(!errors[0].is_fatal);
+        assert_eq!(errors[0].coverage_reason, Some(FileCoverageReason::Binary));
+        assert!(
+            storage
+                .get_nodes()?
+                .iter()
+                .any(|node| node.serialized_name == "kept")
+        );
+        Ok(())
     }
 
     #[test]
-    fn test_file_completeness_tracks_parse_errors() -> Result<()> {
-        let language_config = get_language_for_ext("rs").unwrap();
-        let result = index_file(
-            Path::new("broken.rs"),
-            "fn broken( {",
-            &language_config,
-            None,
+    fn test_invalid_utf8_sources_are_indexed_with_a_decoding_warning() -> Result<()> {
+        use codestory_store::Store as Storage;
+        use tempfile::tempdir;
+
+        let source = b"/* caf\xe9 */ fn after_latin1() {}\n".to_vec();
+        let (decoded, replaced) = decode_source_preserving_offsets(source.clone());
+        assert_eq!(replaced, 1);
+        assert_eq!(decoded.len(), source.len());
+        assert_eq!(decoded.find("fn after_latin1"), Some(11));
+
+        let dir = tempdir()?;
+        let path = dir.path().join("latin1.rs");
+        std::fs::write(&path, &source)?;
+        let plan = codestory_workspace::RefreshExecutionPlan {
+            mode: codestory_workspace::BuildMode::FullRefresh,
+            files_to_index: vec![path],
+            files_to_remove: Vec::new(),
+            existing_file_ids: HashMap::new(),
+        };
+        let mut storage = Storage::new_in_memory()?;
+        WorkspaceIndexer::new(dir.path().to_path_buf()).run(
+            &mut storage,
+            &plan,
+            &EventBus::new(),
             None,
         )?;
 
-        assert_eq!(result.files.len(), 1);
+        let errors = storage.get_errors(None)?;
+        assert_eq!(errors.len(), 1);
+        assert!(!errors[0].is_fatal);
+        assert!(errors[0].message.contains("1 invalid UTF-8 byte"));
+        assert_eq!(errors[0].coverage_reason, None);
         assert!(
-            !result.files[0].complete,
-            "malformed Rust source should be incomplete"
+            storage
+                .get_nodes()?
+                .iter()
+                .any(|node| node.serialized_name == "after_latin1")
         );
         Ok(())
     }
@@ -22369,7 +24052,15 @@ fn checked_foreign(value: Option<i32>) -> Option<i32> {
         {
             match rx.recv_timeout(Duration::from_millis(100)) {
                 Ok(Event::IndexingStarted { .. }) => saw_started = true,
-                Ok(Event::IndexingComplete { .. }) => saw_complete = true,
+                Ok(Event::IndexingComplete {
+                    duration_ms,
+                    cancelled,
+                    phase_timings,
+                }) => {
+                    assert!(!cancelled);
+                    assert!(duration_ms >= phase_timings.parse_ms);
+                    saw_complete = true;
+                }
                 Ok(_) => {}
                 Err(crossbeam_channel::RecvTimeoutError::Timeout) => {}
                 Err(crossbeam_channel::RecvTimeoutError::Disconnected) => break,
@@ -22454,6 +24145,7 @@ fn checked_foreign(value: Option<i32>) -> Option<i32> {
             language_config: get_language_for_ext("rs").expect("rust config"),
             artifact_cache_key: Some("old-source".to_string()),
             content_hash: source_content_hash(original.as_bytes()),
+            replaced_invalid_bytes: 0,
         };
         overwrite_preserving_mtime(&path, "fn replaced() {}\n")?;
 
@@ -25518,6 +27210,83 @@ class Test {
         assert_eq!(config.language_name, "cpp");
     }
 
+    #[test]
+    fn test_cpp_includes_resolve_to_header_files_through_compile_info() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let include_dir = dir.path().join("include");
+        let system_dir = dir.path().join("sysroot");
+        std::fs::create_dir_all(&include_dir)?;
+        std::fs::create_dir_all(&system_dir)?;
+        let widget = include_dir.join("widget.h");
+        let vector = system_dir.join("vector");
+        std::fs::write(&widget, "struct Widget {};\n")?;
+        std::fs::write(&vector, "")?;
+        let main = dir.path().join("main.cpp");
+        let source = "#include \"widget.h\"\n#include <vector>\n#include <missing.h>\n";
+        std::fs::write(&main, source)?;
+
+        let info = compilation_database::CompilationInfo {
+            include_paths: vec![include_dir],
+            system_include_paths: vec![system_dir],
+            ..Default::default()
+        };
+        let config = get_language_for_ext("cpp").expect("cpp config");
+        let result = index_file(&main, source, &config, Some(info), None)?;
+
+        let include_kinds = result
+            .edges
+            .iter()
+            .filter(|edge| edge.kind == EdgeKind::IMPORT)
+            .filter_map(|edge| Some((edge.target, edge.callsite_identity.clone()?)))
+            .collect::<HashMap<_, _>>();
+        let widget_id = NodeId(WorkspaceIndexer::canonical_file_node_id_for_path(&widget));
+        let vector_id = NodeId(WorkspaceIndexer::canonical_file_node_id_for_path(&vector));
+        assert_eq!(
+            include_kinds.get(&widget_id).map(String::as_str),
+            Some("include:project")
+        );
+        assert_eq!(
+            include_kinds.get(&vector_id).map(String::as_str),
+            Some("include:system")
+        );
+        assert!(
+            result
+                .nodes
+                .iter()
+                .any(|node| node.id == vector_id && node.kind == NodeKind::FILE)
+        );
+        assert!(result.nodes.iter().any(
+            |node| node.kind == NodeKind::MODULE && node.serialized_name.contains("missing.h")
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_cpp_relative_includes_target_the_discovered_header_file() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let widget = dir.path().join("include/widget.h");
+        std::fs::create_dir_all(widget.parent().expect("header parent"))?;
+        std::fs::write(&widget, "struct Widget {};\n")?;
+        let main = dir.path().join("src/main.cpp");
+        std::fs::create_dir_all(main.parent().expect("source parent"))?;
+        let source = "#include \"../include/widget.h\"\n";
+        std::fs::write(&main, source)?;
+
+        let config = get_language_for_ext("cpp").expect("cpp config");
+        let info = compilation_database::CompilationInfo::default();
+        let result = index_file(&main, source, &config, Some(info), None)?;
+
+        let widget_id = NodeId(WorkspaceIndexer::canonical_file_node_id_for_path(&widget));
+        assert!(
+            result
+                .edges
+                .iter()
+                .any(|edge| edge.kind == EdgeKind::IMPORT && edge.target == widget_id),
+            "the include should point at the id discovery gives include/widget.h"
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_live_rule_registry_uses_split_rule_assets() {
         let rust = get_language_for_ext("rs").expect("rust config");
@@ -25602,6 +27371,50 @@ class Test {
         Ok(())
     }
 
+    #[test]
+    fn test_tag_queries_skip_only_broken_patterns() {
+        let language: Language = tree_sitter_rust::LANGUAGE.into();
+        let query = "; functions\n\
+                     ((function_item name: (identifier) @name) (#eq? @name \"main\"))\n\
+                     (not_a_rust_node) @broken\n\
+                     (struct_item name: (type_identifier) @name) @definition.struct\n";
+        assert_eq!(split_query_patterns(query).len(), 3);
+
+        let (compiled, skipped) = compile_tags_query(&language, query);
+
+        assert_eq!(compiled.expect("salvaged query").pattern_count(), 2);
+        assert_eq!(skipped.len(), 1);
+        assert!(
+            skipped[0].starts_with("Tag query pattern 1 skipped"),
+            "{skipped:?}"
+        );
+        assert!(language_rule_errors().is_empty());
+    }
+
+    #[test]
+    fn test_rule_overrides_replace_embedded_rule_files_by_name() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        std::fs::write(dir.path().join("rust.tags.scm"), "(identifier) @name\n")?;
+
+        assert_eq!(
+            rule_text(Some(dir.path()), RUST_TAGS_QUERY).map_err(|e| anyhow!(e))?,
+            "(identifier) @name\n"
+        );
+        assert_eq!(
+            rule_text(Some(dir.path()), RUST_GRAPH_QUERY).map_err(|e| anyhow!(e))?,
+            RUST_GRAPH_QUERY
+        );
+        assert_eq!(
+            rule_text(None, RUST_TAGS_QUERY).map_err(|e| anyhow!(e))?,
+            RUST_TAGS_QUERY
+        );
+        assert_eq!(
+            RULE_FILES.len(),
+            std::fs::read_dir(Path::new(env!("CARGO_MANIFEST_DIR")).join("rules"))?.count()
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_dart_graph_query_tracks_grammar_0_4_call_shapes_without_duplicates() -> Result<()> {
         let config = get_language_for_ext("dart").expect("dart config");
@@ -27493,6 +29306,108 @@ func (r *Route) Get(name string) interface{} { return r.namedRoutes[name] }
         );
     }
 
+    #[test]
+    fn test_declared_routes_link_to_the_function_they_decorate() -> Result<()> {
+        let cases = [
+            (
+                "java",
+                "src/UserController.java",
+                r#"
+@RestController
+@RequestMapping("/users")
+public class UserController {
+    @GetMapping("/{id}")
+    public String show(@PathVariable String id) {
+        return id;
+    }
+
+    @RequestMapping(method = RequestMethod.POST)
+    @ResponseBody
+    public String create(String body) {
+        return body;
+    }
+}
+"#,
+                vec![
+                    (
+                        "GET /users/:id (spring route; confidence=annotation)",
+                        "show",
+                    ),
+                    (
+                        "POST /users (spring route; confidence=annotation)",
+                        "create",
+                    ),
+                ],
+            ),
+            (
+                "py",
+                "app/views.py",
+                r#"
+@app.route("/health")
+def health():
+    return "ok"
+
+@app.post("/items")
+@requires_auth
+async def create_item(item):
+    return item
+"#,
+                vec![
+                    ("GET /health (flask route; confidence=decorator)", "health"),
+                    (
+                        "POST /items (fastapi route; confidence=decorator)",
+                        "create_item",
+                    ),
+                ],
+            ),
+            (
+                "rs",
+                "src/routes.rs",
+                r#"
+#[get("/ping")]
+#[allow(dead_code)]
+pub async fn ping() -> &'static str {
+    "pong"
+}
+"#,
+                vec![("GET /ping (rocket route; confidence=attribute)", "ping")],
+            ),
+        ];
+
+        for (extension, path, code, expected) in cases {
+            let language_config = get_language_for_ext(extension).expect("language config");
+            let result = index_file(Path::new(path), code, &language_config, None, None)?;
+            for (route_label, handler_name) in expected {
+                let route = result
+                    .nodes
+                    .iter()
+                    .find(|node| node.serialized_name == route_label)
+                    .unwrap_or_else(|| panic!("route {route_label} in {path}"));
+                assert!(
+                    result.edges.iter().any(|edge| {
+                        edge.kind == EdgeKind::CALL
+                            && edge.source == route.id
+                            && result.nodes.iter().any(|node| {
+                                node.id == edge.target && node_matches_name(node, handler_name)
+                            })
+                    }),
+                    "expected {route_label} to call {handler_name}"
+                );
+            }
+        }
+        assert!(
+            !collect_framework_routes(
+                Path::new("UserController.java"),
+                "java",
+                "@RequestMapping(\"/users\")\npublic class UserController {}\n",
+            )
+            .iter()
+            .any(|route| route.path == "/users"),
+            "class-level mappings only prefix method routes"
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_nextjs_file_route_metadata_preserves_raw_path_params_and_convention() {
         let routes = collect_framework_routes(
@@ -27661,6 +29576,209 @@ export async function loadWriting(payload: any) {
         );
     }
 
+    #[test]
+    fn test_protobuf_generated_go_code_links_to_proto_definitions() -> Result<()> {
+        let code = r#"// Code generated by protoc-gen-go-grpc. DO NOT EDIT.
+// source: greeter.proto
+
+package greeter
+
+type HelloRequest struct {
+	Name string
+}
+
+type GreeterClient interface {
+	SayHello(ctx context.Context, in *HelloRequest) (*HelloRequest, error)
+}
+
+type greeterClient struct {
+	cc grpc.ClientConnInterface
+}
+
+func (c *greeterClient) SayHello(ctx context.Context, in *HelloRequest) (*HelloRequest, error) {
+	return nil, nil
+}
+"#;
+        let language_config = get_language_for_ext("go").expect("go config");
+        let result = index_file(
+            Path::new("gen/greeter_grpc.pb.go"),
+            code,
+            &language_config,
+            None,
+            None,
+        )?;
+        let proto = |canonical: &str| {
+            result
+                .nodes
+                .iter()
+                .find(|node| node.canonical_id.as_deref() == Some(canonical))
+                .unwrap_or_else(|| panic!("missing {canonical}"))
+        };
+        let linked_sources = |kind: EdgeKind, target: NodeId| {
+            result
+                .edges
+                .iter()
+                .filter(|edge| edge.kind == kind && edge.target == target)
+                .filter_map(|edge| result.nodes.iter().find(|node| node.id == edge.source))
+                .map(|node| node.serialized_name.clone())
+                .collect::<Vec<_>>()
+        };
+
+        let service = proto("proto:service:Greeter");
+        assert_eq!(service.id, NodeId(generate_id("proto:service:Greeter")));
+        assert_eq!(service.start_line, None);
+        let service_sources = linked_sources(EdgeKind::TYPE_USAGE, service.id);
+        assert!(
+            service_sources
+                .iter()
+                .any(|name| name.ends_with("GreeterClient"))
+        );
+        assert!(
+            service_sources
+                .iter()
+                .any(|name| name.ends_with("greeterClient"))
+        );
+        let rpc = proto("proto:rpc:Greeter.SayHello");
+        assert!(
+            linked_sources(EdgeKind::USAGE, rpc.id)
+                .iter()
+                .any(|name| name.ends_with("SayHello"))
+        );
+        let message = proto("proto:message:HelloRequest");
+        assert!(
+            linked_sources(EdgeKind::TYPE_USAGE, message.id)
+                .iter()
+                .any(|name| name.ends_with("HelloRequest"))
+        );
+        assert!(result.nodes.iter().all(|node| {
+            !node
+                .canonical_id
+                .as_deref()
+                .is_some_and(|id| id.starts_with("proto:message:") && id.ends_with("Client"))
+        }));
+        Ok(())
+    }
+
+    #[test]
+    fn test_notebook_code_cells_index_as_python_with_cell_positions() -> Result<()> {
+        let notebook = r##"{
+  "metadata": {"kernelspec": {"language": "python"}},
+  "cells": [
+    {"cell_type": "markdown", "source": "# Analysis"},
+    {"cell_type": "code", "source": ["!pip install numpy\n", "def load():\n", "    return 1\n"]},
+    {"cell_type": "code", "source": ["def summarize():\n", "    return load()\n"]}
+  ]
+}"##;
+        let storage = index_notebook_file(Path::new("analysis.ipynb"), notebook)?;
+        assert_eq!(storage.files[0].language, "jupyter");
+
+        let definition = |name: &str| {
+            storage
+                .occurrences
+                .iter()
+                .filter(|occurrence| occurrence.kind == OccurrenceKind::DEFINITION)
+                .find(|occurrence| {
+                    storage.nodes.iter().any(|node| {
+                        node.id.0 == occurrence.element_id
+                            && node.kind == NodeKind::FUNCTION
+                            && node.serialized_name == name
+                    })
+                })
+                .unwrap_or_else(|| panic!("missing definition of {name}"))
+        };
+        let summarize = NodeId(definition("summarize").element_id);
+        assert!(storage.edges.iter().any(|edge| {
+            edge.kind == EdgeKind::CALL
+                && edge.source == summarize
+                && storage
+                    .nodes
+                    .iter()
+                    .any(|node| node.id == edge.target && node.serialized_name.ends_with("load"))
+        }));
+
+        let definition_cell = |name: &str| definition(name).notebook_cell;
+        assert_eq!(
+            definition_cell("load"),
+            Some(codestory_contracts::graph::NotebookCellPosition {
+                cell_index: 1,
+                line: 2
+            })
+        );
+        assert_eq!(
+            definition_cell("summarize"),
+            Some(codestory_contracts::graph::NotebookCellPosition {
+                cell_index: 2,
+                line: 1
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_embedded_sql_literals_link_callables_to_shared_table_nodes() -> Result<()> {
+        let code = r#"
+def load_user(conn, user_id):
+    return conn.execute("SELECT id, email FROM users WHERE id = ?", (user_id,))
+
+def archive(conn):
+    label = "Select a user to archive"
+    conn.execute("INSERT INTO audit.user_log (id) VALUES (?)")
+"#;
+        let language_config = get_language_for_ext("py").expect("python config");
+        let result = index_file(Path::new("app/db.py"), code, &language_config, None, None)?;
+        let table = |canonical: &str| {
+            result
+                .nodes
+                .iter()
+                .find(|node| node.canonical_id.as_deref() == Some(canonical))
+                .unwrap_or_else(|| panic!("missing {canonical}"))
+        };
+        let function = |name: &str| {
+            result
+                .nodes
+                .iter()
+                .find(|node| node.serialized_name == name && node.kind == NodeKind::FUNCTION)
+                .unwrap_or_else(|| panic!("missing {name}"))
+        };
+        let users = table("sql:table:public.users");
+        let user_log = table("sql:table:audit.user_log");
+        assert_eq!(users.id, NodeId(generate_id("sql:table:public.users")));
+
+        for (source, target) in [
+            (function("load_user"), users),
+            (function("archive"), user_log),
+        ] {
+            assert!(result.edges.iter().any(|edge| {
+                edge.kind == EdgeKind::USAGE && edge.source == source.id && edge.target == target.id
+            }));
+        }
+        let users_reference = result
+            .occurrences
+            .iter()
+            .find(|occurrence| {
+                occurrence.element_id == users.id.0 && occurrence.kind == OccurrenceKind::REFERENCE
+            })
+            .expect("users reference occurrence");
+        let line = code.lines().nth(2).expect("query line");
+        assert_eq!(
+            &line[users_reference.location.start_col as usize - 1
+                ..users_reference.location.end_col as usize - 1],
+            "users"
+        );
+        assert_eq!(
+            result
+                .nodes
+                .iter()
+                .filter(|node| node
+                    .canonical_id
+                    .as_deref()
+                    .is_some_and(|id| id.starts_with("sql:")))
+                .count(),
+            2
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_typescript_api_literal_creates_schema_endpoint_call_edge() -> Result<()> {
         let code = r#"
@@ -27757,4 +29875,16 @@ export function handler() {
         );
         Ok(())
     }
+
+    #[test]
+    fn test_slowest_parsed_files_keeps_the_five_longest_in_order() {
+        let mut slowest = SlowestParsedFiles::default();
+        for (file_id, parse_us) in [(1, 30), (2, 10), (3, 50), (4, 20), (5, 40), (6, 5), (7, 60)] {
+            slowest.record(file_id, parse_us);
+        }
+        assert_eq!(
+            slowest.iter().collect::<Vec<_>>(),
+            vec![(7, 60), (3, 50), (5, 40), (1, 30), (4, 20)]
+        );
+    }
 }
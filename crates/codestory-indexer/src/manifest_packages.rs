@@ -0,0 +1,239 @@
+//! Packages declared by build manifests, read so the graph's package
+//! boundaries match `Cargo.toml`, `package.json`, and `pom.xml`.
+//!
+//! Only the package name and the names of its dependencies are read. Which
+//! dependencies live in the workspace is decided by the store when it links
+//! packages together.
+
+use codestory_store::ManifestPackage;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+/// Directories whose manifests are never read.
+const SKIPPED_DIRECTORIES: &[&str] = &["node_modules", "target", "build", "dist", "vendor"];
+
+/// Every package declared by a manifest among `discovered_files`, the
+/// workspace files discovery found under `root`, in path order. Manifests
+/// that fail to parse or declare no package name are skipped.
+pub(crate) fn discover_manifest_packages(
+    root: &Path,
+    discovered_files: &[PathBuf],
+) -> Vec<ManifestPackage> {
+    let mut manifests = discovered_files
+        .iter()
+        .filter(|path| {
+            !path
+                .strip_prefix(root)
+                .unwrap_or(path)
+                .components()
+                .any(|component| {
+                    component
+                        .as_os_str()
+                        .to_str()
+                        .is_some_and(|name| SKIPPED_DIRECTORIES.contains(&name))
+                })
+        })
+        .collect::<Vec<_>>();
+    manifests.sort();
+
+    manifests
+        .into_iter()
+        .filter_map(|path| {
+            let file_name = path.file_name()?.to_str()?;
+            let parse: fn(&str) -> Option<ParsedManifest> = match file_name {
+                "Cargo.toml" => parse_cargo_manifest,
+                "package.json" => parse_npm_manifest,
+                "pom.xml" => parse_maven_manifest,
+                _ => return None,
+            };
+            let source = std::fs::read_to_string(path).ok()?;
+            let (ecosystem, name, dependencies) = parse(&source)?;
+            Some(ManifestPackage {
+                ecosystem: ecosystem.to_string(),
+                name,
+                root: path.parent()?.to_path_buf(),
+                dependencies: dependencies.into_iter().collect(),
+            })
+        })
+        .collect()
+}
+
+type ParsedManifest = (&'static str, String, BTreeSet<String>);
+
+fn parse_cargo_manifest(source: &str) -> Option<ParsedManifest> {
+    let manifest = source.parse::<toml::Table>().ok()?;
+    let name = manifest.get("package")?.get("name")?.as_str()?.to_string();
+    let mut tables = ["dependencies", "dev-dependencies", "build-dependencies"]
+        .into_iter()
+        .filter_map(|key| manifest.get(key)?.as_table())
+        .collect::<Vec<_>>();
+    // `[target.'cfg(...)'.dependencies]` tables count as well.
+    if let Some(targets) = manifest.get("target").and_then(|value| value.as_table()) {
+        for target in targets.values().filter_map(|value| value.as_table()) {
+            tables.extend(
+                ["dependencies", "dev-dependencies", "build-dependencies"]
+                    .into_iter()
+                    .filter_map(|key| target.get(key)?.as_table()),
+            );
+        }
+    }
+    let dependencies = tables
+        .into_iter()
+        .flat_map(|table| table.iter())
+        .map(|(key, value)| {
+            // A renamed dependency names the real package in `package`.
+            value
+                .get("package")
+                .and_then(|package| package.as_str())
+                .unwrap_or(key)
+                .to_string()
+        })
+        .collect();
+    Some(("cargo", name, dependencies))
+}
+
+fn parse_npm_manifest(source: &str) -> Option<ParsedManifest> {
+    let manifest = serde_json::from_str::<serde_json::Value>(source).ok()?;
+    let name = manifest.get("name")?.as_str()?.to_string();
+    let dependencies = [
+        "dependencies",
+        "devDependencies",
+        "peerDependencies",
+        "optionalDependencies",
+    ]
+    .into_iter()
+    .filter_map(|key| manifest.get(key)?.as_object())
+    .flat_map(|table| table.keys().cloned())
+    .collect();
+    Some(("npm", name, dependencies))
+}
+
+/// Reads `artifactId`s without a full XML parser: the project's own is the
+/// first one outside `<parent>`, `<dependencies>`, and `<build>`, and each
+/// `<dependency>` contributes its own.
+fn parse_maven_manifest(source: &str) -> Option<ParsedManifest> {
+    let project = strip_elements(&strip_elements(source, "parent"), "dependencyManagement");
+    let own = strip_elements(&strip_elements(&project, "dependencies"), "build");
+    let name = element_texts(&own, "artifactId").into_iter().next()?;
+    let dependencies = element_texts(&project, "dependency")
+        .iter()
+        .flat_map(|dependency| element_texts(dependency, "artifactId"))
+        .collect();
+    Some(("maven", name, dependencies))
+}
+
+/// `source` with every `<tag>...</tag>` element removed.
+fn strip_elements(source: &str, tag: &str) -> String {
+    let open = format!("<{tag}>");
+    let close = format!("</{tag}>");
+    let mut stripped = String::with_capacity(source.len());
+    let mut rest = source;
+    while let Some(start) = rest.find(&open) {
+        stripped.push_str(&rest[..start]);
+        match rest[start..].find(&close) {
+            Some(end) => rest = &rest[start + end + close.len()..],
+            None => return stripped,
+        }
+    }
+    stripped.push_str(rest);
+    stripped
+}
+
+/// Trimmed text of each `<tag>...</tag>` element in `source`.
+fn element_texts(source: &str, tag: &str) -> Vec<String> {
+    let open = format!("<{tag}>");
+    let close = format!("</{tag}>");
+    let mut texts = Vec::new();
+    let mut rest = source;
+    while let Some(start) = rest.find(&open) {
+        let body = &rest[start + open.len()..];
+        let Some(end) = body.find(&close) else {
+            break;
+        };
+        texts.push(body[..end].trim().to_string());
+        rest = &body[end + close.len()..];
+    }
+    texts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn manifests_name_their_package_and_dependencies() {
+        let (_, name, dependencies) = parse_cargo_manifest(
+            r#"
+[package]
+name = "app"
+
+[dependencies]
+core = { path = "../core" }
+json = { package = "serde_json", version = "1" }
+
+[target.'cfg(unix)'.dev-dependencies]
+libc = "0.2"
+"#,
+        )
+        .expect("cargo manifest");
+        assert_eq!(name, "app");
+        assert_eq!(
+            dependencies.into_iter().collect::<Vec<_>>(),
+            vec!["core", "libc", "serde_json"]
+        );
+        assert!(parse_cargo_manifest("[workspace]\nmembers = [\"crates/*\"]\n").is_none());
+
+        let (_, name, dependencies) = parse_maven_manifest(
+            r#"<project>
+  <parent><artifactId>platform</artifactId></parent>
+  <artifactId>orders</artifactId>
+  <dependencies>
+    <dependency><groupId>com.acme</groupId><artifactId>billing</artifactId></dependency>
+  </dependencies>
+</project>"#,
+        )
+        .expect("maven manifest");
+        assert_eq!(name, "orders");
+        assert_eq!(
+            dependencies.into_iter().collect::<Vec<_>>(),
+            vec!["billing"]
+        );
+    }
+
+    #[test]
+    fn packages_come_from_discovered_manifests_outside_skipped_directories() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let root = dir.path();
+        let files = [
+            (
+                "app/package.json",
+                r#"{"name":"app","dependencies":{"lib":"1"}}"#,
+            ),
+            ("app/src/main.ts", "export {};\n"),
+            ("node_modules/lib/package.json", r#"{"name":"lib"}"#),
+        ]
+        .into_iter()
+        .map(|(relative, source)| {
+            let path = root.join(relative);
+            std::fs::create_dir_all(path.parent().expect("fixture parent"))?;
+            std::fs::write(&path, source)?;
+            Ok(path)
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+        std::fs::write(
+            root.join("Cargo.toml"),
+            "[package]\nname = \"undiscovered\"\n",
+        )?;
+
+        let packages = discover_manifest_packages(root, &files);
+        assert_eq!(
+            packages
+                .iter()
+                .map(|package| (package.name.as_str(), package.root.clone()))
+                .collect::<Vec<_>>(),
+            vec![("app", root.join("app"))]
+        );
+        assert_eq!(packages[0].dependencies, vec!["lib".to_string()]);
+        Ok(())
+    }
+}
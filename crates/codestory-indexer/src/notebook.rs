@@ -0,0 +1,180 @@
+//! Jupyter notebook support for Python code cells.
+//!
+//! A notebook is indexed as one virtual Python document: code cells are
+//! concatenated in order and everything else (markdown, raw cells, outputs) is
+//! dropped. Locations keep the virtual document's line numbers, and a cell
+//! table translates each line back to `(cell index, line in cell)`.
+
+use codestory_contracts::graph::{NotebookCellPosition, Occurrence};
+use serde_json::Value;
+use std::path::Path;
+
+/// Language label stored on file rows for indexed notebooks.
+pub(crate) const NOTEBOOK_LANGUAGE: &str = "jupyter";
+
+pub(crate) fn is_notebook_path(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("ipynb"))
+}
+
+/// Code cells of one notebook joined into a single Python source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct NotebookDocument {
+    pub(crate) source: String,
+    cells: Vec<NotebookCellSpan>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct NotebookCellSpan {
+    cell_index: u32,
+    /// 1-based line in the virtual document where the cell starts.
+    first_line: u32,
+    line_count: u32,
+}
+
+impl NotebookDocument {
+    /// Build the virtual document for a Python notebook.
+    ///
+    /// Returns `Ok(None)` when the notebook declares a kernel language other
+    /// than Python. Notebooks without language metadata are treated as Python.
+    pub(crate) fn parse_python(raw: &str) -> serde_json::Result<Option<Self>> {
+        let notebook: Value = serde_json::from_str(raw)?;
+        if !is_python_notebook(&notebook) {
+            return Ok(None);
+        }
+
+        let mut source = String::new();
+        let mut cells = Vec::new();
+        let mut next_line = 1u32;
+        let notebook_cells = notebook
+            .get("cells")
+            .and_then(Value::as_array)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        for (cell_index, cell) in notebook_cells.iter().enumerate() {
+            if cell.get("cell_type").and_then(Value::as_str) != Some("code") {
+                continue;
+            }
+            let text = cell_source(cell);
+            let mut line_count = 0u32;
+            for line in text.lines() {
+                source.push_str(&neutralize_magic(line));
+                source.push('\n');
+                line_count += 1;
+            }
+            if line_count == 0 {
+                continue;
+            }
+            cells.push(NotebookCellSpan {
+                cell_index: cell_index as u32,
+                first_line: next_line,
+                line_count,
+            });
+            next_line += line_count;
+        }
+
+        Ok(Some(Self { source, cells }))
+    }
+
+    /// Cell position of a 1-based line in the virtual document.
+    pub(crate) fn cell_position(&self, line: u32) -> Option<NotebookCellPosition> {
+        let index = self
+            .cells
+            .partition_point(|cell| cell.first_line <= line)
+            .checked_sub(1)?;
+        let cell = self.cells[index];
+        let offset = line - cell.first_line;
+        (offset < cell.line_count).then_some(NotebookCellPosition {
+            cell_index: cell.cell_index,
+            line: offset + 1,
+        })
+    }
+
+    /// Record the notebook cell of each occurrence's start line.
+    pub(crate) fn annotate_occurrences(&self, occurrences: &mut [Occurrence]) {
+        for occurrence in occurrences {
+            occurrence.notebook_cell = self.cell_position(occurrence.location.start_line);
+        }
+    }
+}
+
+fn is_python_notebook(notebook: &Value) -> bool {
+    let metadata = notebook.get("metadata");
+    let language = metadata
+        .and_then(|metadata| metadata.pointer("/kernelspec/language"))
+        .or_else(|| metadata.and_then(|metadata| metadata.pointer("/language_info/name")))
+        .and_then(Value::as_str);
+    language.is_none_or(|language| language.eq_ignore_ascii_case("python"))
+}
+
+/// Cell source is either one string or a list of line strings.
+fn cell_source(cell: &Value) -> String {
+    match cell.get("source") {
+        Some(Value::String(text)) => text.clone(),
+        Some(Value::Array(parts)) => parts.iter().filter_map(Value::as_str).collect(),
+        _ => String::new(),
+    }
+}
+
+/// Turn IPython magics and shell escapes into comments so the cell still
+/// parses as Python. Column positions are unchanged.
+fn neutralize_magic(line: &str) -> String {
+    let trimmed = line.trim_start();
+    if trimmed.starts_with('%') || trimmed.starts_with('!') {
+        let indent = line.len() - trimmed.len();
+        format!("{}#{}", &line[..indent], &trimmed[1..])
+    } else {
+        line.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOTEBOOK: &str = r##"{
+  "metadata": {"kernelspec": {"language": "python", "name": "python3"}},
+  "nbformat": 4,
+  "cells": [
+    {"cell_type": "markdown", "source": ["# Title\n"]},
+    {"cell_type": "code", "source": ["%matplotlib inline\n", "import math\n"], "outputs": []},
+    {"cell_type": "code", "source": [], "outputs": []},
+    {"cell_type": "code", "source": "def area(r):\n    return math.pi * r * r\n", "outputs": []}
+  ]
+}"##;
+
+    #[test]
+    fn code_cells_join_into_virtual_python_document() {
+        let document = NotebookDocument::parse_python(NOTEBOOK)
+            .expect("valid notebook")
+            .expect("python notebook");
+        assert_eq!(
+            document.source,
+            "#matplotlib inline\nimport math\ndef area(r):\n    return math.pi * r * r\n"
+        );
+        assert_eq!(
+            document.cell_position(2),
+            Some(NotebookCellPosition {
+                cell_index: 1,
+                line: 2
+            })
+        );
+        assert_eq!(
+            document.cell_position(4),
+            Some(NotebookCellPosition {
+                cell_index: 3,
+                line: 2
+            })
+        );
+        assert_eq!(document.cell_position(5), None);
+        assert_eq!(document.cell_position(0), None);
+    }
+
+    #[test]
+    fn non_python_kernels_are_skipped() {
+        let raw = r#"{"metadata": {"kernelspec": {"language": "R"}}, "cells": []}"#;
+        assert_eq!(NotebookDocument::parse_python(raw).expect("valid"), None);
+        assert!(NotebookDocument::parse_python("not json").is_err());
+    }
+}
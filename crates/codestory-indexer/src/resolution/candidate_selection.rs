@@ -400,6 +400,65 @@ pub(super) fn compute_import_resolution(
         {
             candidate_ids.push(candidate);
         }
+    } else if !pass.python_roots.is_empty()
+        && semantic_language_bucket(caller_file_path.as_deref()) == Some("python")
+    {
+        // `import pkg.mod` binds the module itself; `from pkg.mod import name`
+        // binds a symbol in it or a submodule.
+        relative_file_selected = if source_name == target_name {
+            candidate_index.find_python_module_file_readonly(&pass.python_roots, target_name)
+        } else if is_import_binding_name(source_name) {
+            let alias_name = PreparedName::new(source_name.clone());
+            candidate_index.find_python_package_import_readonly(
+                &pass.python_roots,
+                caller_file_path.as_deref(),
+                target_name,
+                &alias_name.original,
+                &alias_name.ascii_lower,
+            )
+        } else {
+            None
+        };
+        match relative_file_selected {
+            Some(candidate) if pass.flags.store_candidates => candidate_ids.push(candidate),
+            Some(_) => {}
+            None if pass.python_roots.is_external(target_name)
+                && candidate_index
+                    .find_python_module_file_readonly(&pass.python_roots, target_name)
+                    .is_none() =>
+            {
+                // A same-named project symbol is never what the import binds.
+                let update = build_resolved_edge_update(*edge_id, None, candidate_ids.as_slice())?;
+                return Ok(ComputedResolution {
+                    update,
+                    strategy: Some(ResolutionStrategy::ImportExternal),
+                });
+            }
+            None => {}
+        }
+    } else if !pass.path_aliases.is_empty()
+        && is_import_binding_name(source_name)
+        && matches!(
+            semantic_language_bucket(caller_file_path.as_deref()),
+            Some("javascript" | "typescript" | "vue" | "svelte" | "astro")
+        )
+    {
+        // A `tsconfig.json` alias that lands on a project file is as certain
+        // as a relative import; one that does not falls through to the name
+        // lookups below, since it may still name an installed package.
+        let alias_name = PreparedName::new(source_name.clone());
+        relative_file_selected = candidate_index.find_aliased_import_readonly(
+            &pass.path_aliases,
+            caller_file_path.as_deref(),
+            target_name,
+            &alias_name.original,
+            &alias_name.ascii_lower,
+        );
+        if let Some(candidate) = relative_file_selected
+            && pass.flags.store_candidates
+        {
+            candidate_ids.push(candidate);
+        }
     }
 
     for name in &name_candidates {
@@ -635,6 +694,8 @@ mod tests {
             flags,
             policy: ResolutionPolicy::for_flags(flags),
             semantic_resolvers: SemanticResolverRegistry::new(flags.enable_semantic),
+            path_aliases: TypeScriptPathAliases::default(),
+            python_roots: PythonImportRoots::default(),
         }
     }
 
@@ -27,10 +27,14 @@ use std::time::Instant;
 
 mod candidate_selection;
 mod pipeline;
+mod python_roots;
 mod query_helpers;
 mod sql;
+mod tsconfig;
 
+pub use python_roots::PythonImportRoots;
 use query_helpers::{import_alias_mismatch, sorted_scope_file_ids};
+pub use tsconfig::TypeScriptPathAliases;
 
 type UnresolvedEdgeRow = (
     i64,
@@ -488,6 +492,9 @@ pub struct ResolutionStrategyCounters {
     pub import_global_unique: usize,
     pub import_fuzzy: usize,
     pub import_semantic_fallback: usize,
+    /// Python imports of standard library or installed modules, left
+    /// unresolved.
+    pub import_external: usize,
 }
 
 impl ResolutionStrategyCounters {
@@ -502,6 +509,7 @@ impl ResolutionStrategyCounters {
             Some(ResolutionStrategy::ImportGlobalUnique) => self.import_global_unique += 1,
             Some(ResolutionStrategy::ImportFuzzy) => self.import_fuzzy += 1,
             Some(ResolutionStrategy::ImportSemanticFallback) => self.import_semantic_fallback += 1,
+            Some(ResolutionStrategy::ImportExternal) => self.import_external += 1,
             None => {}
         }
     }
@@ -518,6 +526,7 @@ enum ResolutionStrategy {
     ImportGlobalUnique,
     ImportFuzzy,
     ImportSemanticFallback,
+    ImportExternal,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -713,6 +722,8 @@ pub struct ResolutionPass {
     flags: ResolutionFlags,
     policy: ResolutionPolicy,
     semantic_resolvers: SemanticResolverRegistry,
+    path_aliases: TypeScriptPathAliases,
+    python_roots: PythonImportRoots,
 }
 
 impl Default for ResolutionPass {
@@ -730,9 +741,25 @@ impl ResolutionPass {
             flags,
             policy,
             semantic_resolvers: SemanticResolverRegistry::new(flags.enable_semantic),
+            path_aliases: TypeScriptPathAliases::default(),
+            python_roots: PythonImportRoots::default(),
         }
     }
 
+    /// Resolve non-relative JavaScript and TypeScript imports through the
+    /// project's `tsconfig.json` `paths` and `baseUrl`.
+    pub fn with_path_aliases(mut self, path_aliases: TypeScriptPathAliases) -> Self {
+        self.path_aliases = path_aliases;
+        self
+    }
+
+    /// Resolve absolute Python imports to files under the project's package
+    /// roots, and leave standard library and installed imports unresolved.
+    pub fn with_python_roots(mut self, python_roots: PythonImportRoots) -> Self {
+        self.python_roots = python_roots;
+        self
+    }
+
     /// Resolve all eligible unresolved edges in the store.
     pub fn run(&self, storage: &mut Storage) -> Result<ResolutionStats> {
         self.run_with_scope(storage, None)
@@ -1931,6 +1958,98 @@ impl CandidateIndex {
     ) -> Option<i64> {
         let caller_file_path = caller_file_path?;
         let candidates = relative_import_path_candidates(caller_file_path, module_name);
+        self.find_import_in_files_readonly(
+            caller_file_path,
+            module_name,
+            candidates,
+            imported_name,
+            imported_name_ascii_lower,
+        )
+    }
+
+    /// The symbol `imported_name` from the absolute Python module
+    /// `module_name`, or the file of submodule `module_name.imported_name`.
+    fn find_python_package_import_readonly(
+        &self,
+        python_roots: &PythonImportRoots,
+        caller_file_path: Option<&str>,
+        module_name: &str,
+        imported_name: &str,
+        imported_name_ascii_lower: &str,
+    ) -> Option<i64> {
+        let caller_file_path = caller_file_path?;
+        let mut candidates = Vec::new();
+        for base in python_roots.module_bases(module_name) {
+            push_python_module_file_candidates(&mut candidates, &base);
+        }
+        self.find_import_in_files_readonly(
+            caller_file_path,
+            module_name,
+            candidates,
+            imported_name,
+            imported_name_ascii_lower,
+        )
+        .or_else(|| {
+            self.find_python_module_file_readonly(
+                python_roots,
+                &format!("{}.{imported_name}", module_name.trim()),
+            )
+        })
+    }
+
+    /// The file node of the project file the absolute Python module
+    /// `module_name` names.
+    fn find_python_module_file_readonly(
+        &self,
+        python_roots: &PythonImportRoots,
+        module_name: &str,
+    ) -> Option<i64> {
+        let mut candidates = Vec::new();
+        for base in python_roots.module_bases(module_name) {
+            push_python_module_file_candidates(&mut candidates, &base);
+        }
+        candidates.iter().find_map(|candidate| {
+            self.relative_file_path_map
+                .get(candidate)?
+                .iter()
+                .find_map(|offset| self.relative_import_nodes.get(*offset)?.file_node_id)
+        })
+    }
+
+    /// Like [`Self::find_relative_import_readonly`], for a non-relative
+    /// specifier mapped to project files by `tsconfig.json`.
+    fn find_aliased_import_readonly(
+        &self,
+        path_aliases: &TypeScriptPathAliases,
+        caller_file_path: Option<&str>,
+        module_name: &str,
+        imported_name: &str,
+        imported_name_ascii_lower: &str,
+    ) -> Option<i64> {
+        let caller_file_path = caller_file_path?;
+        let mut candidates = Vec::new();
+        for base in path_aliases.module_bases(caller_file_path, module_name) {
+            for candidate in module_file_candidates(base) {
+                push_unique(&mut candidates, candidate);
+            }
+        }
+        self.find_import_in_files_readonly(
+            caller_file_path,
+            module_name,
+            candidates,
+            imported_name,
+            imported_name_ascii_lower,
+        )
+    }
+
+    fn find_import_in_files_readonly(
+        &self,
+        caller_file_path: &str,
+        module_name: &str,
+        candidates: Vec<String>,
+        imported_name: &str,
+        imported_name_ascii_lower: &str,
+    ) -> Option<i64> {
         if candidates.is_empty() {
             return None;
         }
@@ -2660,7 +2779,12 @@ fn relative_import_path_candidates(caller_file_path: &str, module_name: &str) ->
     let Some(base) = normalize_resolution_path(&format!("{parent}/{module_name}")) else {
         return Vec::new();
     };
+    module_file_candidates(base)
+}
 
+/// `base` followed by the files a JavaScript resolver probes for it when it
+/// has no extension.
+fn module_file_candidates(base: String) -> Vec<String> {
     let mut candidates = Vec::new();
     push_unique(&mut candidates, base.clone());
     if !path_has_extension(&base) {
@@ -3690,6 +3814,8 @@ mod tests {
             flags,
             policy: ResolutionPolicy::for_flags(flags),
             semantic_resolvers: SemanticResolverRegistry::new(true),
+            path_aliases: TypeScriptPathAliases::default(),
+            python_roots: PythonImportRoots::default(),
         };
         let rows = vec![
             (
@@ -3738,6 +3864,8 @@ mod tests {
             flags,
             policy: ResolutionPolicy::for_flags(flags),
             semantic_resolvers: SemanticResolverRegistry::new(true),
+            path_aliases: TypeScriptPathAliases::default(),
+            python_roots: PythonImportRoots::default(),
         };
         let rows = vec![(
             1_i64,
@@ -3788,6 +3916,8 @@ mod tests {
             flags,
             policy: ResolutionPolicy::for_flags(flags),
             semantic_resolvers: SemanticResolverRegistry::new(false),
+            path_aliases: TypeScriptPathAliases::default(),
+            python_roots: PythonImportRoots::default(),
         };
         let row = (
             1_i64,
@@ -3828,6 +3958,8 @@ mod tests {
             flags,
             policy: ResolutionPolicy::for_flags(flags),
             semantic_resolvers: SemanticResolverRegistry::new(true),
+            path_aliases: TypeScriptPathAliases::default(),
+            python_roots: PythonImportRoots::default(),
         };
         let row = (
             2_i64,
@@ -342,7 +342,7 @@ where
     let mut updates = Vec::with_capacity(rows.len());
     for computed in computed_results {
         let computed = computed?;
-        if computed.strategy.is_some() {
+        if computed.update.resolved_target_node_id.is_some() {
             resolved += 1;
         }
         job.strategy_counters.record(computed.strategy);
@@ -0,0 +1,436 @@
+//! Package roots and installed modules for absolute Python imports.
+//!
+//! `import pkg.mod` names a file relative to whichever `sys.path` entry holds
+//! `pkg`. The project's Python source groups supply those entries: each
+//! group's `python_path` and source directories, then the project root and a
+//! `src/` layout directory. Modules that live in none of them are classified
+//! as standard library or, when a group's `virtual_env` has them installed,
+//! third-party, so they are never matched to a same-named project symbol.
+
+use super::{normalize_import_module_name, normalize_resolution_path};
+use codestory_workspace::{Language, LanguageSpecificSettings, WorkspaceManifest};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// Virtual environment directories looked for when no Python source group
+/// names one.
+const DEFAULT_VIRTUAL_ENVS: &[&str] = &[".venv", "venv"];
+
+/// Top-level standard library modules, lowercased to match normalized
+/// import names.
+const STDLIB_MODULES: &[&str] = &[
+    "__future__",
+    "_thread",
+    "abc",
+    "aifc",
+    "argparse",
+    "array",
+    "ast",
+    "asynchat",
+    "asyncio",
+    "asyncore",
+    "atexit",
+    "audioop",
+    "base64",
+    "bdb",
+    "binascii",
+    "bisect",
+    "builtins",
+    "bz2",
+    "calendar",
+    "cgi",
+    "cgitb",
+    "chunk",
+    "cmath",
+    "cmd",
+    "code",
+    "codecs",
+    "codeop",
+    "collections",
+    "colorsys",
+    "compileall",
+    "concurrent",
+    "configparser",
+    "contextlib",
+    "contextvars",
+    "copy",
+    "copyreg",
+    "cprofile",
+    "crypt",
+    "csv",
+    "ctypes",
+    "curses",
+    "dataclasses",
+    "datetime",
+    "dbm",
+    "decimal",
+    "difflib",
+    "dis",
+    "distutils",
+    "doctest",
+    "email",
+    "encodings",
+    "ensurepip",
+    "enum",
+    "errno",
+    "faulthandler",
+    "fcntl",
+    "filecmp",
+    "fileinput",
+    "fnmatch",
+    "fractions",
+    "ftplib",
+    "functools",
+    "gc",
+    "getopt",
+    "getpass",
+    "gettext",
+    "glob",
+    "graphlib",
+    "grp",
+    "gzip",
+    "hashlib",
+    "heapq",
+    "hmac",
+    "html",
+    "http",
+    "idlelib",
+    "imaplib",
+    "imghdr",
+    "imp",
+    "importlib",
+    "inspect",
+    "io",
+    "ipaddress",
+    "itertools",
+    "json",
+    "keyword",
+    "lib2to3",
+    "linecache",
+    "locale",
+    "logging",
+    "lzma",
+    "mailbox",
+    "mailcap",
+    "marshal",
+    "math",
+    "mimetypes",
+    "mmap",
+    "modulefinder",
+    "msilib",
+    "msvcrt",
+    "multiprocessing",
+    "netrc",
+    "nis",
+    "nntplib",
+    "ntpath",
+    "numbers",
+    "opcode",
+    "operator",
+    "optparse",
+    "os",
+    "ossaudiodev",
+    "pathlib",
+    "pdb",
+    "pickle",
+    "pickletools",
+    "pipes",
+    "pkgutil",
+    "platform",
+    "plistlib",
+    "poplib",
+    "posix",
+    "posixpath",
+    "pprint",
+    "profile",
+    "pstats",
+    "pty",
+    "pwd",
+    "py_compile",
+    "pyclbr",
+    "pydoc",
+    "queue",
+    "quopri",
+    "random",
+    "re",
+    "readline",
+    "reprlib",
+    "resource",
+    "rlcompleter",
+    "runpy",
+    "sched",
+    "secrets",
+    "select",
+    "selectors",
+    "shelve",
+    "shlex",
+    "shutil",
+    "signal",
+    "site",
+    "smtpd",
+    "smtplib",
+    "sndhdr",
+    "socket",
+    "socketserver",
+    "spwd",
+    "sqlite3",
+    "sre_compile",
+    "sre_constants",
+    "sre_parse",
+    "ssl",
+    "stat",
+    "statistics",
+    "string",
+    "stringprep",
+    "struct",
+    "subprocess",
+    "sunau",
+    "symtable",
+    "sys",
+    "sysconfig",
+    "syslog",
+    "tabnanny",
+    "tarfile",
+    "telnetlib",
+    "tempfile",
+    "termios",
+    "textwrap",
+    "threading",
+    "time",
+    "timeit",
+    "tkinter",
+    "token",
+    "tokenize",
+    "tomllib",
+    "trace",
+    "traceback",
+    "tracemalloc",
+    "tty",
+    "turtle",
+    "turtledemo",
+    "types",
+    "typing",
+    "unicodedata",
+    "unittest",
+    "urllib",
+    "uu",
+    "uuid",
+    "venv",
+    "warnings",
+    "wave",
+    "weakref",
+    "webbrowser",
+    "winreg",
+    "winsound",
+    "wsgiref",
+    "xdrlib",
+    "xml",
+    "xmlrpc",
+    "zipapp",
+    "zipfile",
+    "zipimport",
+    "zlib",
+    "zoneinfo",
+];
+
+/// Where absolute Python imports are looked up for one project.
+#[derive(Debug, Clone, Default)]
+pub struct PythonImportRoots {
+    /// Normalized directories searched in order, like `sys.path`.
+    package_roots: Vec<String>,
+    /// Lowercased top-level modules installed in the virtual environments.
+    installed_modules: HashSet<String>,
+}
+
+impl PythonImportRoots {
+    /// Import roots for the project at `root`, read from the Python source
+    /// groups of its manifest.
+    pub fn discover(root: &Path) -> Self {
+        let manifest = WorkspaceManifest::open(root.to_path_buf()).ok();
+        let manifest_root = manifest
+            .as_ref()
+            .map(WorkspaceManifest::root_dir)
+            .unwrap_or_else(|| root.to_path_buf());
+        let mut package_roots = Vec::new();
+        let mut virtual_envs = Vec::new();
+        let python_groups = manifest
+            .iter()
+            .flat_map(|manifest| manifest.settings().source_groups.iter())
+            .filter(|group| group.language == Language::Python);
+        for group in python_groups {
+            if let LanguageSpecificSettings::Python {
+                python_path,
+                virtual_env,
+            } = &group.language_specific
+            {
+                package_roots.extend(python_path.iter().map(|path| manifest_root.join(path)));
+                virtual_envs.extend(virtual_env.iter().map(|path| manifest_root.join(path)));
+            }
+            package_roots.extend(
+                group
+                    .source_paths
+                    .iter()
+                    .map(|path| manifest_root.join(path))
+                    .filter(|path| path.is_dir()),
+            );
+        }
+        package_roots.push(root.to_path_buf());
+        package_roots.push(root.join("src"));
+        if virtual_envs.is_empty() {
+            virtual_envs.extend(
+                DEFAULT_VIRTUAL_ENVS
+                    .iter()
+                    .map(|dir| root.join(dir))
+                    .filter(|dir| dir.join("pyvenv.cfg").is_file()),
+            );
+        }
+        Self::new(package_roots, &virtual_envs)
+    }
+
+    /// Import roots searched in the order given, with the modules installed
+    /// in `virtual_envs` treated as third-party.
+    pub fn new(package_roots: impl IntoIterator<Item = PathBuf>, virtual_envs: &[PathBuf]) -> Self {
+        let mut roots = Vec::new();
+        for root in package_roots.into_iter().filter(|root| root.is_dir()) {
+            if let Some(root) = normalize_resolution_path(&root.to_string_lossy())
+                && !roots.contains(&root)
+            {
+                roots.push(root);
+            }
+        }
+        Self {
+            package_roots: roots,
+            installed_modules: virtual_envs
+                .iter()
+                .flat_map(|env| installed_top_level_modules(env))
+                .collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.package_roots.is_empty()
+    }
+
+    /// Normalized paths, without the `.py` or `__init__.py` suffix, that the
+    /// absolute `module_name` may name under each package root.
+    pub(super) fn module_bases(&self, module_name: &str) -> Vec<String> {
+        let Some(module_name) = normalize_import_module_name(module_name) else {
+            return Vec::new();
+        };
+        if module_name.starts_with('.') {
+            return Vec::new();
+        }
+        let module_path = module_name.replace('.', "/");
+        self.package_roots
+            .iter()
+            .filter_map(|root| normalize_resolution_path(&format!("{root}/{module_path}")))
+            .collect()
+    }
+
+    /// Whether the absolute `module_name` belongs to the standard library or
+    /// an installed package.
+    pub(super) fn is_external(&self, module_name: &str) -> bool {
+        let Some(module_name) = normalize_import_module_name(module_name) else {
+            return false;
+        };
+        let Some(top_level) = module_name.split('.').next().filter(|top| !top.is_empty()) else {
+            return false;
+        };
+        STDLIB_MODULES.contains(&top_level) || self.installed_modules.contains(top_level)
+    }
+}
+
+/// Top-level module names under the `site-packages` directories of
+/// `virtual_env`, for both the POSIX and Windows layouts.
+fn installed_top_level_modules(virtual_env: &Path) -> Vec<String> {
+    let mut site_packages = vec![virtual_env.join("Lib").join("site-packages")];
+    if let Ok(entries) = std::fs::read_dir(virtual_env.join("lib")) {
+        site_packages.extend(
+            entries
+                .filter_map(Result::ok)
+                .filter(|entry| entry.file_name().to_string_lossy().starts_with("python"))
+                .map(|entry| entry.path().join("site-packages")),
+        );
+    }
+    site_packages
+        .iter()
+        .filter_map(|dir| std::fs::read_dir(dir).ok())
+        .flatten()
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_ascii_lowercase();
+            let is_dir = entry.file_type().is_ok_and(|kind| kind.is_dir());
+            if name == "__pycache__" || name.ends_with(".dist-info") || name.ends_with(".egg-info")
+            {
+                return None;
+            }
+            if is_dir {
+                return Some(name);
+            }
+            // `six.py`, `_cffi_backend.cpython-312-x86_64-linux-gnu.so`, `yaml.pyd`.
+            let (stem, _) = name.split_once('.')?;
+            [".py", ".so", ".pyd"]
+                .iter()
+                .any(|extension| name.ends_with(extension))
+                .then(|| stem.to_string())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn package_roots_map_modules_and_virtualenv_marks_installed_packages() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let root = dir.path();
+        fs::create_dir_all(root.join("src/app"))?;
+        fs::create_dir_all(root.join("libs"))?;
+        let site_packages = root.join(".venv/lib/python3.12/site-packages");
+        fs::create_dir_all(site_packages.join("requests"))?;
+        fs::create_dir_all(site_packages.join("requests-2.32.0.dist-info"))?;
+        fs::write(site_packages.join("six.py"), "")?;
+        fs::write(root.join(".venv/pyvenv.cfg"), "home = /usr/bin\n")?;
+        fs::write(
+            root.join("codestory_project.json"),
+            serde_json::json!({
+                "name": "app",
+                "version": 1,
+                "source_groups": [{
+                    "id": "00000000-0000-0000-0000-000000000001",
+                    "language": "Python",
+                    "standard": "Default",
+                    "source_paths": ["src"],
+                    "exclude_patterns": [],
+                    "include_paths": [],
+                    "defines": {},
+                    "language_specific": {
+                        "Python": { "python_path": "libs", "virtual_env": ".venv" }
+                    }
+                }]
+            })
+            .to_string(),
+        )?;
+
+        let roots = PythonImportRoots::discover(root);
+        let root_path = normalize_resolution_path(&root.to_string_lossy()).expect("root");
+        assert_eq!(
+            roots.module_bases("app.models"),
+            vec![
+                format!("{root_path}/libs/app/models"),
+                format!("{root_path}/src/app/models"),
+                format!("{root_path}/app/models"),
+            ]
+        );
+        assert!(roots.module_bases(".models").is_empty());
+
+        assert!(roots.is_external("os.path"));
+        assert!(roots.is_external("requests.adapters"));
+        assert!(roots.is_external("six"));
+        assert!(!roots.is_external("app.models"));
+        assert!(!roots.is_external("requests_extra"));
+        Ok(())
+    }
+}
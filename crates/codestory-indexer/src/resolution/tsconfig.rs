@@ -0,0 +1,420 @@
+//! Module path mapping from `tsconfig.json` and `jsconfig.json`.
+//!
+//! TypeScript projects import through `compilerOptions.paths` aliases such as
+//! `@app/*` and through specifiers rooted at `compilerOptions.baseUrl`. Every
+//! config workspace discovery found is read once per indexing run, and a file
+//! uses the config in its nearest enclosing directory, the same project an
+//! editor would pick for it.
+
+use super::{normalize_import_module_name, normalize_resolution_path};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Config files read in preference order: a `tsconfig.json` wins over a
+/// `jsconfig.json` in the same directory.
+const CONFIG_FILE_NAMES: &[&str] = &["tsconfig.json", "jsconfig.json"];
+
+/// Directories whose configs are never read.
+const SKIPPED_DIRECTORIES: &[&str] = &["node_modules", "dist", "build"];
+
+/// Longest `extends` chain followed before giving up on a cycle.
+const MAX_EXTENDS_DEPTH: usize = 8;
+
+/// Path mappings from the TypeScript and JavaScript configs of a project.
+#[derive(Debug, Clone, Default)]
+pub struct TypeScriptPathAliases {
+    /// Deepest scope first, so the first match is the nearest config.
+    configs: Vec<PathMappingConfig>,
+}
+
+#[derive(Debug, Clone)]
+struct PathMappingConfig {
+    /// Normalized directory holding the config.
+    scope: String,
+    /// Normalized `baseUrl`, when the config or one it extends sets it.
+    base_url: Option<String>,
+    paths: Vec<PathPattern>,
+}
+
+#[derive(Debug, Clone)]
+struct PathPattern {
+    prefix: String,
+    /// Text after the `*`, or `None` for a pattern without a wildcard.
+    suffix: Option<String>,
+    /// Substitution targets joined to the directory they are relative to.
+    targets: Vec<String>,
+}
+
+impl TypeScriptPathAliases {
+    /// Read every `tsconfig.json` and `jsconfig.json` among
+    /// `discovered_files`, the workspace files discovery found under `root`.
+    /// Configs that fail to parse or set neither `baseUrl` nor `paths` are
+    /// skipped. Configs they extend are read even when not discovered.
+    pub fn discover(root: &Path, discovered_files: &[PathBuf]) -> Self {
+        let mut config_paths = discovered_files
+            .iter()
+            .filter(|path| {
+                path.file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| CONFIG_FILE_NAMES.contains(&name))
+                    && !path
+                        .strip_prefix(root)
+                        .unwrap_or(path)
+                        .components()
+                        .any(|component| {
+                            component
+                                .as_os_str()
+                                .to_str()
+                                .is_some_and(|name| SKIPPED_DIRECTORIES.contains(&name))
+                        })
+            })
+            .collect::<Vec<_>>();
+        config_paths.sort_by_key(|path| {
+            let preference = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| CONFIG_FILE_NAMES.iter().position(|known| *known == name));
+            (path.parent().map(|parent| parent.to_path_buf()), preference)
+        });
+
+        let mut by_scope = BTreeMap::new();
+        for path in config_paths {
+            if let Some(config) = PathMappingConfig::load(path) {
+                by_scope.entry(config.scope.clone()).or_insert(config);
+            }
+        }
+        let mut configs = by_scope.into_values().collect::<Vec<_>>();
+        configs.sort_by_key(|config| std::cmp::Reverse(config.scope.len()));
+        Self { configs }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.configs.is_empty()
+    }
+
+    /// Normalized file paths, without extension probing, that the
+    /// non-relative `module_name` maps to when imported from
+    /// `caller_file_path`. Matching `paths` targets come first, then the
+    /// specifier under `baseUrl`.
+    pub(super) fn module_bases(&self, caller_file_path: &str, module_name: &str) -> Vec<String> {
+        let Some(module_name) = normalize_import_module_name(module_name) else {
+            return Vec::new();
+        };
+        if module_name.starts_with("./")
+            || module_name.starts_with("../")
+            || module_name.starts_with('/')
+        {
+            return Vec::new();
+        }
+        let Some(caller_path) = normalize_resolution_path(caller_file_path) else {
+            return Vec::new();
+        };
+        let Some(config) = self.configs.iter().find(|config| {
+            caller_path
+                .strip_prefix(config.scope.as_str())
+                .is_some_and(|rest| rest.starts_with('/'))
+        }) else {
+            return Vec::new();
+        };
+
+        let mut bases = Vec::new();
+        if let Some((pattern, captured)) = config.matching_pattern(&module_name) {
+            bases.extend(pattern.targets.iter().filter_map(|target| {
+                normalize_resolution_path(&target.replacen('*', captured, 1))
+            }));
+        }
+        if let Some(base) = config
+            .base_url
+            .as_ref()
+            .and_then(|base_url| normalize_resolution_path(&format!("{base_url}/{module_name}")))
+        {
+            bases.push(base);
+        }
+        bases.dedup();
+        bases
+    }
+}
+
+impl PathMappingConfig {
+    fn load(path: &Path) -> Option<Self> {
+        let scope = normalize_resolution_path(&path.parent()?.to_string_lossy())?;
+        let mut base_url = None;
+        let mut paths = None;
+        let mut current = path.to_path_buf();
+        for _ in 0..MAX_EXTENDS_DEPTH {
+            // An `extends` target that cannot be read ends the chain; the
+            // config itself must parse.
+            let Some(config) = read_jsonc(&current) else {
+                if current == path {
+                    return None;
+                }
+                break;
+            };
+            let dir = current.parent()?.to_path_buf();
+            let options = config.get("compilerOptions");
+            if base_url.is_none()
+                && let Some(value) = options
+                    .and_then(|options| options.get("baseUrl"))
+                    .and_then(|value| value.as_str())
+            {
+                base_url = normalize_resolution_path(&dir.join(value).to_string_lossy());
+            }
+            if paths.is_none()
+                && let Some(value) = options
+                    .and_then(|options| options.get("paths"))
+                    .and_then(|value| value.as_object())
+            {
+                paths = Some((value.clone(), dir.clone()));
+            }
+            match extended_config_path(&dir, config.get("extends")) {
+                Some(parent) => current = parent,
+                None => break,
+            }
+        }
+
+        // `paths` targets are relative to `baseUrl` when one is set, and to
+        // the config that declares them otherwise.
+        let paths = paths
+            .map(|(entries, declaring_dir)| {
+                let relative_to = base_url
+                    .clone()
+                    .or_else(|| normalize_resolution_path(&declaring_dir.to_string_lossy()));
+                entries
+                    .iter()
+                    .filter_map(|(pattern, targets)| {
+                        PathPattern::new(pattern, targets, relative_to.as_deref()?)
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        (base_url.is_some() || !paths.is_empty()).then_some(Self {
+            scope,
+            base_url,
+            paths,
+        })
+    }
+
+    /// The pattern TypeScript would apply to `module_name` and the text its
+    /// `*` captured: an exact pattern wins, then the longest prefix.
+    fn matching_pattern<'a>(&self, module_name: &'a str) -> Option<(&PathPattern, &'a str)> {
+        if let Some(exact) = self
+            .paths
+            .iter()
+            .find(|pattern| pattern.suffix.is_none() && pattern.prefix == module_name)
+        {
+            return Some((exact, ""));
+        }
+        self.paths
+            .iter()
+            .filter_map(|pattern| {
+                let suffix = pattern.suffix.as_deref()?;
+                let captured = module_name
+                    .strip_prefix(pattern.prefix.as_str())?
+                    .strip_suffix(suffix)?;
+                Some((pattern, captured))
+            })
+            .max_by_key(|(pattern, _)| pattern.prefix.len())
+    }
+}
+
+impl PathPattern {
+    fn new(pattern: &str, targets: &serde_json::Value, relative_to: &str) -> Option<Self> {
+        let pattern = pattern.to_ascii_lowercase();
+        let (prefix, suffix) = match pattern.split_once('*') {
+            Some((prefix, suffix)) => (prefix.to_string(), Some(suffix.to_string())),
+            None => (pattern, None),
+        };
+        let targets = targets
+            .as_array()?
+            .iter()
+            .filter_map(|target| target.as_str())
+            .map(|target| {
+                format!("{relative_to}/{}", target.replace('\\', "/")).to_ascii_lowercase()
+            })
+            .collect::<Vec<_>>();
+        (!targets.is_empty()).then_some(Self {
+            prefix,
+            suffix,
+            targets,
+        })
+    }
+}
+
+/// The config named by `extends`, when it is a file path. Package
+/// references such as `@tsconfig/node20` are not followed; for an array, the
+/// last entry is used since it overrides the others.
+fn extended_config_path(dir: &Path, extends: Option<&serde_json::Value>) -> Option<PathBuf> {
+    let extends = match extends? {
+        serde_json::Value::String(value) => value.as_str(),
+        serde_json::Value::Array(values) => values.last()?.as_str()?,
+        _ => return None,
+    };
+    let path = Path::new(extends);
+    if !(extends.starts_with("./") || extends.starts_with("../") || path.is_absolute()) {
+        return None;
+    }
+    let path = dir.join(path);
+    if extends.ends_with(".json") {
+        return Some(path);
+    }
+    let mut with_extension = path.into_os_string();
+    with_extension.push(".json");
+    Some(PathBuf::from(with_extension))
+}
+
+/// Parse a config file, allowing the comments and trailing commas that
+/// `tsc` accepts.
+fn read_jsonc(path: &Path) -> Option<serde_json::Value> {
+    let source = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&strip_trailing_commas(&strip_comments(&source))).ok()
+}
+
+fn strip_comments(source: &str) -> String {
+    let mut stripped = String::with_capacity(source.len());
+    let mut chars = source.chars().peekable();
+    let mut in_string = false;
+    while let Some(ch) = chars.next() {
+        if in_string {
+            stripped.push(ch);
+            match ch {
+                '\\' => stripped.extend(chars.next()),
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match (ch, chars.peek()) {
+            ('"', _) => {
+                in_string = true;
+                stripped.push(ch);
+            }
+            ('/', Some('/')) => while chars.next_if(|next| *next != '\n').is_some() {},
+            ('/', Some('*')) => {
+                chars.next();
+                let mut previous = '\0';
+                for next in chars.by_ref() {
+                    if previous == '*' && next == '/' {
+                        break;
+                    }
+                    previous = next;
+                }
+                stripped.push(' ');
+            }
+            _ => stripped.push(ch),
+        }
+    }
+    stripped
+}
+
+fn strip_trailing_commas(source: &str) -> String {
+    let mut stripped = String::with_capacity(source.len());
+    let mut in_string = false;
+    let mut escaped = false;
+    for (offset, ch) in source.char_indices() {
+        if in_string {
+            match ch {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+        } else if ch == '"' {
+            in_string = true;
+        } else if ch == ',' && source[offset + 1..].trim_start().starts_with(['}', ']']) {
+            continue;
+        }
+        stripped.push(ch);
+    }
+    stripped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn nearest_config_maps_aliases_and_base_url() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let root = dir.path();
+        fs::create_dir_all(root.join("packages/web/src"))?;
+        fs::write(
+            root.join("tsconfig.base.json"),
+            r#"{
+                // Shared by every package.
+                "compilerOptions": {
+                    "baseUrl": ".",
+                    "paths": {
+                        "@shared/*": ["libs/shared/*", "libs/fallback/*"],
+                        "@config": ["config/index.ts"], /* exact */
+                    },
+                },
+            }"#,
+        )?;
+        fs::write(
+            root.join("tsconfig.json"),
+            r#"{ "extends": "./tsconfig.base" }"#,
+        )?;
+        fs::write(
+            root.join("packages/web/tsconfig.json"),
+            r#"{
+                "compilerOptions": {
+                    "paths": { "@/*": ["src/*"], "@/components/*": ["src/ui/*"] }
+                }
+            }"#,
+        )?;
+
+        let aliases = TypeScriptPathAliases::discover(
+            root,
+            &[
+                root.join("tsconfig.base.json"),
+                root.join("tsconfig.json"),
+                root.join("packages/web/tsconfig.json"),
+            ],
+        );
+        let root_path = normalize_resolution_path(&root.to_string_lossy()).expect("root");
+        let app = root.join("apps/app.ts");
+        let app = app.to_string_lossy();
+        assert_eq!(
+            aliases.module_bases(&app, "\"@shared/util\""),
+            vec![
+                format!("{root_path}/libs/shared/util"),
+                format!("{root_path}/libs/fallback/util"),
+                format!("{root_path}/@shared/util"),
+            ]
+        );
+        assert_eq!(
+            aliases.module_bases(&app, "'@config'"),
+            vec![
+                format!("{root_path}/config/index.ts"),
+                format!("{root_path}/@config"),
+            ]
+        );
+        assert!(aliases.module_bases(&app, "\"./local\"").is_empty());
+
+        let web = root.join("packages/web/src/main.ts");
+        let web = web.to_string_lossy();
+        assert_eq!(
+            aliases.module_bases(&web, "\"@/components/button\""),
+            vec![format!("{root_path}/packages/web/src/ui/button")]
+        );
+        assert_eq!(
+            aliases.module_bases(&web, "\"@/store\""),
+            vec![format!("{root_path}/packages/web/src/store")]
+        );
+        assert!(aliases.module_bases(&web, "\"@shared/util\"").is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn jsonc_comments_and_trailing_commas_are_removed_outside_strings() {
+        let source = r#"{ "a": "http://x/*y*/", // note
+            "b": [1, 2,], /* gone */ "c": "d,}", }"#;
+        let value: serde_json::Value =
+            serde_json::from_str(&strip_trailing_commas(&strip_comments(source)))
+                .expect("valid json");
+        assert_eq!(value["a"], "http://x/*y*/");
+        assert_eq!(value["b"], serde_json::json!([1, 2]));
+        assert_eq!(value["c"], "d,}");
+    }
+}
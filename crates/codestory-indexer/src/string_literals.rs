@@ -0,0 +1,397 @@
+//! String literals and named constant values.
+//!
+//! Literals are read line by line, and anything after the line's first comment
+//! opener is skipped. Rust raw strings (`r#"..."#`) and triple-quoted strings
+//! are closed by their own delimiter. A literal must open and close on one
+//! line to be kept; a string that runs onto later lines, such as a docstring
+//! or a multi-line template, is skipped up to its closing delimiter so its
+//! contents are not read as code. A constant is kept only when its initializer
+//! starts as a number or a string, which is what someone hunting a magic value
+//! searches for.
+
+use crate::code_markers::comment_openers;
+use codestory_store::StringLiteral;
+use std::path::Path;
+
+/// Longest literal or constant value kept; the rest is cut.
+const STRING_LITERAL_MAX_CHARS: usize = 200;
+/// Literals kept per file, so generated tables cannot flood the index.
+const STRING_LITERAL_MAX_PER_FILE: usize = 2_000;
+
+/// String literals and named constants in `source`, in line order.
+pub fn extract_string_literals(path: &Path, source: &str) -> Vec<StringLiteral> {
+    let openers = comment_openers(path);
+    let quotes = quote_chars(path);
+    let is_rust = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("rs"));
+    let mut literals = Vec::new();
+    let mut in_block_comment = false;
+    // The closing delimiter of a string that ran past the end of a line.
+    let mut open_string = None::<StringDelimiter>;
+    for (index, line) in source.lines().enumerate() {
+        let line_number = index as u32 + 1;
+        let mut code_end = line.len();
+        let mut start = 0;
+        if let Some(delimiter) = &open_string {
+            match delimiter.find_close(line) {
+                Some(close) => {
+                    start = close + delimiter.close.len();
+                    open_string = None;
+                }
+                None => continue,
+            }
+        } else if in_block_comment {
+            match line.find("*/") {
+                Some(close) => {
+                    in_block_comment = false;
+                    start = close + 2;
+                }
+                None => continue,
+            }
+        }
+        for (offset, ch) in line.char_indices() {
+            if offset < start {
+                continue;
+            }
+            let rest = &line[offset..];
+            if let Some(opener) = openers.iter().find(|opener| rest.starts_with(**opener)) {
+                if *opener == "/*" {
+                    if let Some(close) = rest[2..].find("*/") {
+                        start = offset + 2 + close + 2;
+                        continue;
+                    }
+                    in_block_comment = true;
+                }
+                code_end = offset;
+                break;
+            }
+            if !quotes.contains(&ch) || (is_rust && is_char_literal_quote(line, offset)) {
+                continue;
+            }
+            let delimiter = StringDelimiter::at(line, offset, ch, is_rust);
+            let body_start = offset + delimiter.open_len;
+            let Some(close) = delimiter
+                .find_close(&line[body_start..])
+                .map(|close| body_start + close)
+            else {
+                code_end = offset;
+                if delimiter.spans_lines {
+                    open_string = Some(delimiter);
+                }
+                break;
+            };
+            let value = &line[body_start..close];
+            if is_searchable(value) && literals.len() < STRING_LITERAL_MAX_PER_FILE {
+                literals.push(StringLiteral {
+                    line: line_number,
+                    column: line[..offset].chars().count() as u32 + 1,
+                    name: None,
+                    value: truncate(value),
+                });
+            }
+            start = close + delimiter.close.len();
+        }
+        if literals.len() < STRING_LITERAL_MAX_PER_FILE
+            && let Some(constant) = named_constant(&line[..code_end], line_number)
+        {
+            literals.push(constant);
+        }
+    }
+    literals.sort_by_key(|literal| (literal.line, literal.column));
+    literals
+}
+
+/// How a string literal ends.
+struct StringDelimiter {
+    /// Length of the opening quote, after any raw-string prefix.
+    open_len: usize,
+    close: String,
+    /// Whether a backslash escapes the next character.
+    escapes: bool,
+    /// Whether the string may continue on later lines.
+    spans_lines: bool,
+}
+
+impl StringDelimiter {
+    /// The delimiter of the string whose quote `quote` sits at `offset`.
+    fn at(line: &str, offset: usize, quote: char, is_rust: bool) -> Self {
+        let triple = quote.to_string().repeat(3);
+        if !is_rust && line[offset..].starts_with(&triple) {
+            return Self {
+                open_len: triple.len(),
+                close: triple,
+                escapes: true,
+                spans_lines: true,
+            };
+        }
+        if is_rust {
+            let before = &line[..offset];
+            let prefix = before.trim_end_matches('#');
+            let word_start = prefix
+                .rfind(|ch: char| !is_identifier_char(ch))
+                .map_or(0, |position| position + 1);
+            if matches!(&prefix[word_start..], "r" | "br" | "cr") {
+                return Self {
+                    open_len: 1,
+                    close: format!("\"{}", &before[prefix.len()..]),
+                    escapes: false,
+                    spans_lines: true,
+                };
+            }
+        }
+        Self {
+            open_len: quote.len_utf8(),
+            close: quote.to_string(),
+            escapes: true,
+            // Rust strings and template literals may hold newlines; other
+            // single-quoted strings end with their line.
+            spans_lines: is_rust || quote == '`',
+        }
+    }
+
+    /// Byte offset of the closing delimiter in `text`.
+    fn find_close(&self, text: &str) -> Option<usize> {
+        if !self.escapes {
+            return text.find(&self.close);
+        }
+        let mut escaped = false;
+        for (index, ch) in text.char_indices() {
+            if escaped {
+                escaped = false;
+            } else if ch == '\\' {
+                escaped = true;
+            } else if text[index..].starts_with(&self.close) {
+                return Some(index);
+            }
+        }
+        None
+    }
+}
+
+/// Whether the `"` at `offset` is a Rust character literal, `'"'` or `'\"'`.
+fn is_char_literal_quote(line: &str, offset: usize) -> bool {
+    let before = &line[..offset];
+    (before.ends_with('\'') || before.ends_with("'\\")) && line[offset + 1..].starts_with('\'')
+}
+
+fn quote_chars(path: &Path) -> &'static [char] {
+    let ext = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(str::to_ascii_lowercase)
+        .unwrap_or_default();
+    match ext.as_str() {
+        // Single quotes delimit characters or lifetimes here.
+        "rs" | "c" | "h" | "cc" | "cpp" | "cxx" | "hpp" | "hh" | "java" | "kt" | "kts" | "cs"
+        | "swift" | "scala" => &['"'],
+        "go" | "js" | "jsx" | "mjs" | "cjs" | "ts" | "tsx" | "vue" | "svelte" => &['"', '\'', '`'],
+        _ => &['"', '\''],
+    }
+}
+
+/// A literal worth indexing holds at least two characters, one of them a
+/// letter or digit.
+fn is_searchable(value: &str) -> bool {
+    value.chars().nth(1).is_some() && value.chars().any(char::is_alphanumeric)
+}
+
+fn truncate(value: &str) -> String {
+    value.chars().take(STRING_LITERAL_MAX_CHARS).collect()
+}
+
+/// A constant declared on `code`: `const`, `final`, or `readonly` bindings,
+/// upper-case `static`s, `#define`s, and upper-case module-level assignments.
+fn named_constant(code: &str, line: u32) -> Option<StringLiteral> {
+    let trimmed = code.trim_start();
+    let indent = code.len() - trimmed.len();
+    let (name, value) = if let Some(define) = trimmed.strip_prefix("#define ") {
+        let define = define.trim_start();
+        let name_len = define
+            .find(|ch: char| !is_identifier_char(ch))
+            .unwrap_or(define.len());
+        (&define[..name_len], define[name_len..].trim())
+    } else {
+        let equals = assignment_position(trimmed)?;
+        let binding = &trimmed[..equals];
+        let binding = binding.split(':').next().unwrap_or(binding).trim_end();
+        let name_start = binding
+            .rfind(|ch: char| !is_identifier_char(ch))
+            .map_or(0, |position| position + 1);
+        let name = &binding[name_start..];
+        let keywords = binding[..name_start].split_whitespace().collect::<Vec<_>>();
+        let is_upper_case = name.chars().any(|ch| ch.is_ascii_uppercase())
+            && !name.chars().any(|ch| ch.is_ascii_lowercase());
+        let declared = keywords
+            .iter()
+            .any(|word| matches!(*word, "const" | "final" | "readonly" | "constexpr"))
+            || (is_upper_case
+                && (keywords.contains(&"static") || (indent == 0 && keywords.is_empty())));
+        if !declared || binding.contains('(') {
+            return None;
+        }
+        let value = trimmed[equals + 1..].trim();
+        (name, value.trim_end_matches([';', ',']).trim_end())
+    };
+    let starts_as_literal = value
+        .trim_start_matches(['-', '+'])
+        .chars()
+        .next()
+        .is_some_and(|ch| ch.is_ascii_digit() || matches!(ch, '"' | '\'' | '`'))
+        || value.starts_with("b\"")
+        || value.starts_with("r\"")
+        || value.starts_with("r#\"");
+    if name.is_empty() || name.starts_with(|ch: char| ch.is_ascii_digit()) || !starts_as_literal {
+        return None;
+    }
+    Some(StringLiteral {
+        line,
+        column: (indent + trimmed.find(name)?) as u32 + 1,
+        name: Some(name.to_string()),
+        value: truncate(value),
+    })
+}
+
+/// Byte offset of the first `=` that assigns rather than compares.
+fn assignment_position(code: &str) -> Option<usize> {
+    let bytes = code.as_bytes();
+    (0..bytes.len()).find(|&index| {
+        bytes[index] == b'='
+            && bytes
+                .get(index + 1)
+                .is_none_or(|next| !matches!(next, b'=' | b'>'))
+            && index
+                .checked_sub(1)
+                .is_none_or(|previous| !matches!(bytes[previous], b'=' | b'!' | b'<' | b'>'))
+    })
+}
+
+fn is_identifier_char(ch: char) -> bool {
+    ch.is_alphanumeric() || ch == '_' || ch == '$'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(path: &str, source: &str) -> Vec<(u32, u32, Option<String>, String)> {
+        extract_string_literals(Path::new(path), source)
+            .into_iter()
+            .map(|literal| (literal.line, literal.column, literal.name, literal.value))
+            .collect()
+    }
+
+    #[test]
+    fn records_literals_and_constants_outside_comments() {
+        let source = "\
+pub const CONNECT_TIMEOUT_MS: u64 = 4_096;
+static DEFAULT_HOST: &str = \"localhost\";
+fn connect(retries: u32) {
+    // \"commented out\"
+    let message = format!(\"connection refused by {}\", \"peer\\\"s host\");
+    let ch = 'x'; /* \"inline block\" */ let unit = \"ms\";
+    const fn_like: fn() = run;
+}
+";
+        assert_eq!(
+            summary("src/net.rs", source),
+            vec![
+                (
+                    1,
+                    11,
+                    Some("CONNECT_TIMEOUT_MS".to_string()),
+                    "4_096".to_string()
+                ),
+                (
+                    2,
+                    8,
+                    Some("DEFAULT_HOST".to_string()),
+                    "\"localhost\"".to_string()
+                ),
+                (2, 29, None, "localhost".to_string()),
+                (5, 27, None, "connection refused by {}".to_string()),
+                (5, 55, None, "peer\\\"s host".to_string()),
+                (6, 51, None, "ms".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn follows_the_conventions_of_the_file_language() {
+        let source = "\
+MAX_RETRIES = 5
+retries = 3
+def fail():
+    LOCAL = 'not module level'
+    raise ValueError('bad token')  # 'comment'
+";
+        assert_eq!(
+            summary("app.py", source),
+            vec![
+                (1, 1, Some("MAX_RETRIES".to_string()), "5".to_string()),
+                (4, 13, None, "not module level".to_string()),
+                (5, 22, None, "bad token".to_string()),
+            ]
+        );
+        assert_eq!(
+            summary("limits.h", "#define BUFFER_SIZE 512\n#define GUARD\n"),
+            vec![(1, 9, Some("BUFFER_SIZE".to_string()), "512".to_string())]
+        );
+        assert_eq!(
+            summary(
+                "app.ts",
+                "export const API_URL = `https://example.test`;\nif (a == 'on') {}\n"
+            ),
+            vec![
+                (
+                    1,
+                    14,
+                    Some("API_URL".to_string()),
+                    "`https://example.test`".to_string()
+                ),
+                (1, 24, None, "https://example.test".to_string()),
+                (2, 10, None, "on".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn reads_raw_strings_and_skips_strings_spanning_lines() {
+        let source = r##"const QUERY: &str = r#"SELECT "name" FROM t"#;
+let help = "first line
+    \"quoted\" second line";
+let quote = '"'; let after = "after";
+"##;
+        assert_eq!(
+            summary("src/sql.rs", source),
+            vec![
+                (
+                    1,
+                    7,
+                    Some("QUERY".to_string()),
+                    r##"r#"SELECT "name" FROM t"#"##.to_string()
+                ),
+                (1, 23, None, r#"SELECT "name" FROM t"#.to_string()),
+                (4, 30, None, "after".to_string()),
+            ]
+        );
+        assert_eq!(
+            summary(
+                "app.py",
+                "DOC = \"\"\"Start\nit's \"quoted\" here\n\"\"\"\nNAME = \"svc\"\n"
+            ),
+            vec![
+                (4, 1, Some("NAME".to_string()), "\"svc\"".to_string()),
+                (4, 8, None, "svc".to_string()),
+            ]
+        );
+        assert_eq!(
+            summary(
+                "page.ts",
+                "const page = `<a href=\"x\">\n</a>`; let label = 'done';\n"
+            ),
+            vec![(2, 20, None, "done".to_string())]
+        );
+    }
+}
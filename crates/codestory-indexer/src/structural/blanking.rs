@@ -83,6 +83,40 @@ pub fn blank_non_script_regions(source: &str) -> String {
     blank_outside_regions(source, &regions)
 }
 
+/// Language of a `<script>` block from its opening tag: `"typescript"` for
+/// `lang="ts"` or a TypeScript `type`, `"javascript"` otherwise. Data and
+/// template blocks such as `type="application/json"`, `importmap`, or
+/// `text/x-template` are not code and give `None`.
+pub(crate) fn script_block_language(opening_tag: &str) -> Option<&'static str> {
+    let lower = opening_tag.to_ascii_lowercase();
+    let attribute = |name: &str| {
+        let (start, _) = lower
+            .match_indices(&format!("{name}="))
+            .find(|(index, _)| lower[..*index].ends_with(char::is_whitespace))?;
+        let start = start + name.len() + 1;
+        let value = lower[start..].trim_start_matches(['"', '\'']);
+        let end = value
+            .find(|ch: char| ch == '"' || ch == '\'' || ch == '>' || ch.is_whitespace())
+            .unwrap_or(value.len());
+        Some(value[..end].to_string())
+    };
+    if let Some(lang) = attribute("lang") {
+        return match lang.as_str() {
+            "ts" | "typescript" | "tsx" => Some("typescript"),
+            "js" | "javascript" | "jsx" => Some("javascript"),
+            _ => None,
+        };
+    }
+    match attribute("type").as_deref() {
+        None
+        | Some("" | "module" | "text/javascript" | "application/javascript" | "text/babel") => {
+            Some("javascript")
+        }
+        Some("ts" | "text/typescript" | "application/typescript") => Some("typescript"),
+        Some(_) => None,
+    }
+}
+
 /// Extract inner text for each `<style>` block.
 pub fn extract_style_block_sources(source: &str) -> Vec<(u32, u32, String)> {
     extract_embedded_regions(source)
@@ -145,6 +179,28 @@ mod tests {
         assert!(blanked.as_bytes()[4] == b' ');
     }
 
+    #[test]
+    fn script_blocks_take_their_language_from_lang_or_type() {
+        assert_eq!(script_block_language("<script>"), Some("javascript"));
+        assert_eq!(
+            script_block_language("<script type=\"module\">"),
+            Some("javascript")
+        );
+        assert_eq!(
+            script_block_language("<script setup lang='ts'>"),
+            Some("typescript")
+        );
+        assert_eq!(
+            script_block_language("<script type=\"text/typescript\">"),
+            Some("typescript")
+        );
+        assert_eq!(
+            script_block_language("<script type=\"application/json\">"),
+            None
+        );
+        assert_eq!(script_block_language("<script type=importmap>"), None);
+    }
+
     #[test]
     fn extracts_script_and_style_regions() {
         let source = "<style>.a{}</style><script>const a=1</script>";
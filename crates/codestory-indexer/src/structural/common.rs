@@ -174,6 +174,34 @@ pub(crate) fn push_structural_node(
     span: StructuralSourceSpan,
 ) -> NodeId {
     let node_id = structural_node_id(file_id, canonical_id, span.start_line, span.start_col);
+    push_located_structural_node(storage, node_id, file_id, kind, name, canonical_id, span);
+    node_id
+}
+
+/// Push a structural node whose id depends on `canonical_id` alone, so every
+/// file that declares or links the same definition lands on one node.
+pub(crate) fn push_shared_structural_node(
+    storage: &mut IntermediateStorage,
+    file_id: NodeId,
+    kind: NodeKind,
+    name: &str,
+    canonical_id: &str,
+    span: StructuralSourceSpan,
+) -> NodeId {
+    let node_id = NodeId(crate::generate_id(canonical_id));
+    push_located_structural_node(storage, node_id, file_id, kind, name, canonical_id, span);
+    node_id
+}
+
+fn push_located_structural_node(
+    storage: &mut IntermediateStorage,
+    node_id: NodeId,
+    file_id: NodeId,
+    kind: NodeKind,
+    name: &str,
+    canonical_id: &str,
+    span: StructuralSourceSpan,
+) {
     storage.nodes.push(Node {
         id: node_id,
         kind,
@@ -198,8 +226,9 @@ pub(crate) fn push_structural_node(
             end_line: span.end_line,
             end_col: span.end_col,
         },
+        edge_id: None,
+        notebook_cell: None,
     });
-    node_id
 }
 
 pub(crate) fn push_synthetic_structural_node(
@@ -1,11 +1,11 @@
 use crate::intermediate_storage::IntermediateStorage;
 use crate::structural::blanking::{
-    EmbeddedRegionKind, blank_non_script_regions, byte_offset_line_col, extract_embedded_regions,
-    extract_style_block_sources,
+    EmbeddedRegion, EmbeddedRegionKind, blank_outside_regions, byte_offset_line_col,
+    extract_embedded_regions, extract_style_block_sources, script_block_language,
 };
 use crate::{get_language_for_ext, index_file};
 use codestory_contracts::graph::{EdgeId, EdgeKind, NodeId, NodeKind};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::Path;
 
 use super::common::{
@@ -123,23 +123,45 @@ fn maybe_region_node(
     ))
 }
 
+/// Parse `<script>` blocks with the grammar each one declares. Blocks of one
+/// language are parsed together, with everything else blanked, so their
+/// locations stay those of the HTML file.
 fn delegate_script_blocks(
     path: &Path,
     source: &str,
     file_id: NodeId,
     storage: &mut IntermediateStorage,
 ) {
-    let script_regions: Vec<_> = extract_embedded_regions(source)
-        .into_iter()
-        .filter(|region| region.kind == EmbeddedRegionKind::Script)
-        .collect();
-    if script_regions.is_empty() {
-        return;
+    let mut regions_by_language = BTreeMap::<&'static str, Vec<EmbeddedRegion>>::new();
+    for region in extract_embedded_regions(source) {
+        if region.kind != EmbeddedRegionKind::Script {
+            continue;
+        }
+        let opening_tag = source
+            .get(region.open_start_byte..region.start_byte)
+            .unwrap_or_default();
+        if let Some(language) = script_block_language(opening_tag) {
+            regions_by_language
+                .entry(language)
+                .or_default()
+                .push(region);
+        }
     }
+    for (language, script_regions) in regions_by_language {
+        delegate_script_language_blocks(path, source, file_id, storage, language, script_regions);
+    }
+}
 
-    let blanked = blank_non_script_regions(source);
-    let lang = script_language_for_source(source);
-    let ext = if lang == "typescript" { "ts" } else { "js" };
+fn delegate_script_language_blocks(
+    path: &Path,
+    source: &str,
+    file_id: NodeId,
+    storage: &mut IntermediateStorage,
+    language: &str,
+    script_regions: Vec<EmbeddedRegion>,
+) {
+    let blanked = blank_outside_regions(source, &script_regions);
+    let ext = if language == "typescript" { "ts" } else { "js" };
     let delegate_path = path.with_extension(ext);
     let Some(language_config) = get_language_for_ext(ext) else {
         for region in script_regions {
@@ -171,23 +193,11 @@ fn delegate_script_blocks(
     }
 }
 
-fn script_language_for_source(source: &str) -> &'static str {
-    let lower = source.to_ascii_lowercase();
-    if lower.contains("lang=\"ts\"")
-        || lower.contains("lang='ts'")
-        || lower.contains("lang=\"typescript\"")
-    {
-        "typescript"
-    } else {
-        "javascript"
-    }
-}
-
 fn merge_delegated_script_graph(
     storage: &mut IntermediateStorage,
     host_file_id: NodeId,
     index_result: crate::IndexResult,
-    script_regions: &[super::blanking::EmbeddedRegion],
+    script_regions: &[EmbeddedRegion],
     source: &str,
 ) {
     let delegated_file_id = index_result
@@ -212,6 +222,17 @@ fn merge_delegated_script_graph(
         )
     });
 
+    let first_symbol = index_result
+        .nodes
+        .iter()
+        .find(|node| {
+            matches!(
+                node.kind,
+                NodeKind::FUNCTION | NodeKind::CLASS | NodeKind::METHOD
+            )
+        })
+        .map(|node| node.id);
+
     for mut node in index_result.nodes {
         if node.kind == NodeKind::FILE {
             continue;
@@ -265,20 +286,7 @@ fn merge_delegated_script_graph(
         .component_access
         .extend(index_result.component_access);
 
-    if let (Some(module_id), Some(first_symbol)) = (
-        script_module,
-        storage
-            .nodes
-            .iter()
-            .find(|node| {
-                node.file_node_id == Some(host_file_id)
-                    && matches!(
-                        node.kind,
-                        NodeKind::FUNCTION | NodeKind::CLASS | NodeKind::METHOD
-                    )
-            })
-            .map(|node| node.id),
-    ) {
+    if let (Some(module_id), Some(first_symbol)) = (script_module, first_symbol) {
         push_import_edge(
             storage,
             host_file_id,
@@ -392,4 +400,31 @@ mod tests {
                 .any(|n| n.canonical_id.as_deref() == Some("css:class:layout"))
         );
     }
+
+    #[test]
+    fn script_blocks_are_parsed_with_their_own_language() {
+        let html = r#"<html>
+<script>function boot() { return 1; }</script>
+<script lang="ts">interface Shape { sides: number }</script>
+<script type="application/json">function fromJson() {}</script>
+</html>"#;
+        let mut storage = IntermediateStorage::default();
+        collect_html_entities(Path::new("index.html"), html, NodeId(99), &mut storage);
+        let node = |name: &str| {
+            storage
+                .nodes
+                .iter()
+                .find(|node| node.serialized_name == name)
+        };
+        let boot = node("boot").expect("javascript block");
+        assert_eq!(boot.kind, NodeKind::FUNCTION);
+        assert_eq!(
+            (boot.file_node_id, boot.start_line),
+            (Some(NodeId(99)), Some(2))
+        );
+        let shape = node("Shape").expect("typescript block");
+        assert_eq!(shape.kind, NodeKind::INTERFACE);
+        assert_eq!(shape.start_line, Some(3));
+        assert!(node("fromJson").is_none(), "data blocks are not code");
+    }
 }
@@ -14,13 +14,19 @@ mod docker_compose;
 mod generic;
 mod github_actions;
 mod html;
+mod protobuf;
 mod sql;
 
-pub(crate) use blanking::byte_offset_line_col;
 pub use blanking::{
     EmbeddedRegion, EmbeddedRegionKind, blank_non_script_regions, blank_outside_regions,
     extract_embedded_regions,
 };
+pub(crate) use blanking::{byte_offset_line_col, script_block_language};
+pub(crate) use protobuf::{
+    ProtobufDefinitionKind, is_protobuf_generated_source, protobuf_generated_message_name,
+    protobuf_generated_rpc_name, protobuf_generated_service_name,
+};
+pub(crate) use sql::{embedded_sql_table_references, looks_like_embedded_sql};
 /// Return the structural language label stored for `path`.
 pub fn structural_language_name(path: &Path) -> &'static str {
     common::structural_language_name(path)
@@ -109,6 +115,7 @@ pub(crate) fn structural_producer(path: &Path) -> Option<&'static str> {
         Some("html" | "htm") => Some("structural_html_collector"),
         Some("css") => Some("structural_css_collector"),
         Some("sql") => Some("structural_sql_collector"),
+        Some("proto") => Some("structural_protobuf_collector"),
         Some("md" | "markdown" | "mdx") => Some("structural_markdown_collector"),
         Some("yml" | "yaml") => Some("structural_yaml_collector"),
         Some("toml") => Some("structural_toml_collector"),
@@ -337,7 +344,7 @@ pub(crate) fn index_structural_source_with_unit_cap(
         indexed: true,
         complete: true,
         line_count: source.lines().count() as u32,
-        file_role: codestory_store::FileRole::classify_path(path),
+        file_role: codestory_store::FileRole::classify_source(path, source),
     });
     storage.nodes.push(file_node);
 
@@ -360,6 +367,9 @@ pub(crate) fn index_structural_source_with_unit_cap(
             }
             Some("css") => css::collect_css_entities(path, source, file_id, &mut storage, 1, 0),
             Some("sql") => sql::collect_sql_entities(path, source, file_id, &mut storage),
+            Some("proto") => {
+                protobuf::collect_protobuf_entities(path, source, file_id, &mut storage)
+            }
             Some("md" | "markdown" | "mdx") => {
                 generic::collect_markdown_entities(path, source, file_id, &mut storage)?
             }
@@ -492,6 +502,12 @@ mod tests {
                     "app.touch_users",
                 ],
             ),
+            (
+                "proto/health.proto",
+                "syntax = \"proto3\";\npackage demo.health;\nmessage Ping {\n  string id = 1;\n}\nservice Health {\n  rpc Check (Ping) returns (Ping);\n}\n",
+                "structural_protobuf_collector",
+                &["demo.health", "Ping", "Health", "Check"],
+            ),
             (
                 "docs/guide.mdx",
                 "# Guide\n\n```rust\n# Hidden\n[hidden]: ./hidden.md\n```\n\n[api]: ./api.md\n",
@@ -0,0 +1,555 @@
+use crate::intermediate_storage::IntermediateStorage;
+use codestory_contracts::graph::{NodeId, NodeKind};
+use std::collections::HashMap;
+use std::path::Path;
+
+use super::common::{
+    StructuralSourceSpan, push_member_edge, push_shared_structural_node, push_structural_node,
+    push_type_usage_edge,
+};
+
+/// Protobuf definitions that generated code can be linked back to.
+///
+/// Canonical ids omit the package so generated classes, which rarely spell the
+/// proto package, can name the same node. Files without a `package` share no
+/// namespace with anything, so their ids are scoped to the file instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ProtobufDefinitionKind {
+    Message,
+    Enum,
+    Service,
+    Rpc,
+}
+
+impl ProtobufDefinitionKind {
+    pub(crate) fn canonical_id(self, name: &str) -> String {
+        let tag = match self {
+            Self::Message => "message",
+            Self::Enum => "enum",
+            Self::Service => "service",
+            Self::Rpc => "rpc",
+        };
+        format!("proto:{tag}:{name}")
+    }
+
+    /// Canonical id of `name` declared in the package-less file at `path`.
+    pub(crate) fn file_scoped_canonical_id(self, path: &str, name: &str) -> String {
+        self.canonical_id(&format!("{path}#{name}"))
+    }
+
+    pub(crate) fn node_kind(self) -> NodeKind {
+        match self {
+            Self::Message => NodeKind::CLASS,
+            Self::Enum => NodeKind::ENUM,
+            Self::Service => NodeKind::INTERFACE,
+            Self::Rpc => NodeKind::METHOD,
+        }
+    }
+}
+
+struct ProtoScope {
+    kind: ProtobufDefinitionKind,
+    name: String,
+    node_id: NodeId,
+    depth: usize,
+}
+
+struct PendingTypeUsage {
+    source_id: NodeId,
+    scope: String,
+    type_name: String,
+    line: u32,
+}
+
+pub(crate) fn collect_protobuf_entities(
+    path: &Path,
+    source: &str,
+    file_id: NodeId,
+    storage: &mut IntermediateStorage,
+) {
+    let mut package_id = None;
+    let mut scopes: Vec<ProtoScope> = Vec::new();
+    let mut messages: HashMap<String, NodeId> = HashMap::new();
+    let mut pending = Vec::new();
+    let mut depth = 0usize;
+    let mut in_block_comment = false;
+    let file_scope = (!declares_package(source)).then(|| path.to_string_lossy().replace('\\', "/"));
+
+    for (line_idx, raw_line) in source.lines().enumerate() {
+        let line_number = line_idx as u32 + 1;
+        let line = blank_comments_and_strings(raw_line, &mut in_block_comment);
+        let trimmed = line.trim_start();
+        let indent = line.len() - trimmed.len();
+        let (keyword, rest) = trimmed
+            .split_once(char::is_whitespace)
+            .unwrap_or((trimmed, ""));
+        // `split_once` consumed one separator between the keyword and `rest`.
+        let name_start = indent + keyword.len() + 1 + (rest.len() - rest.trim_start().len());
+
+        if keyword == "package"
+            && let Some(name) = leading_identifier(rest.trim_start(), true)
+        {
+            let node_id = push_structural_node(
+                storage,
+                file_id,
+                NodeKind::NAMESPACE,
+                name,
+                &format!("proto:package:{name}"),
+                StructuralSourceSpan::token(line_number, name_start, name.len()),
+            );
+            push_member_edge(storage, file_id, file_id, node_id, line_number);
+            package_id = Some(node_id);
+        } else if let Some(kind) = declaration_kind(keyword, scopes.last())
+            && let Some(name) = leading_identifier(rest.trim_start(), false)
+        {
+            let parent = scopes.last();
+            let full_name = match parent {
+                Some(parent) => format!("{}.{name}", parent.name),
+                None => name.to_string(),
+            };
+            let node_id = push_shared_structural_node(
+                storage,
+                file_id,
+                kind.node_kind(),
+                &full_name,
+                &match file_scope.as_deref() {
+                    Some(path) => kind.file_scoped_canonical_id(path, &full_name),
+                    None => kind.canonical_id(&full_name),
+                },
+                StructuralSourceSpan::token(line_number, name_start, name.len()),
+            );
+            let parent_id = parent
+                .map(|parent| parent.node_id)
+                .or(package_id)
+                .unwrap_or(file_id);
+            push_member_edge(storage, file_id, parent_id, node_id, line_number);
+
+            if kind == ProtobufDefinitionKind::Rpc {
+                for type_name in rpc_type_names(rest) {
+                    pending.push(PendingTypeUsage {
+                        source_id: node_id,
+                        scope: String::new(),
+                        type_name,
+                        line: line_number,
+                    });
+                }
+            } else {
+                if kind == ProtobufDefinitionKind::Message {
+                    messages.insert(full_name.clone(), node_id);
+                }
+                scopes.push(ProtoScope {
+                    kind,
+                    name: full_name,
+                    node_id,
+                    depth: depth + 1,
+                });
+            }
+        } else if let Some(scope) = scopes
+            .iter()
+            .rev()
+            .find(|scope| scope.kind == ProtobufDefinitionKind::Message)
+            && let Some(type_name) = field_type_name(trimmed)
+        {
+            pending.push(PendingTypeUsage {
+                source_id: scope.node_id,
+                scope: scope.name.clone(),
+                type_name,
+                line: line_number,
+            });
+        }
+
+        let opens = line.matches('{').count();
+        let closes = line.matches('}').count();
+        depth = (depth + opens).saturating_sub(closes);
+        while scopes.last().is_some_and(|scope| scope.depth > depth) {
+            scopes.pop();
+        }
+    }
+
+    for usage in pending {
+        if let Some(target_id) = resolve_message(&messages, &usage.scope, &usage.type_name)
+            && target_id != usage.source_id
+        {
+            push_type_usage_edge(storage, file_id, usage.source_id, target_id, usage.line);
+        }
+    }
+}
+
+fn declares_package(source: &str) -> bool {
+    let mut in_block_comment = false;
+    source.lines().any(|line| {
+        let line = blank_comments_and_strings(line, &mut in_block_comment);
+        line.trim_start()
+            .strip_prefix("package")
+            .is_some_and(|rest| rest.starts_with(char::is_whitespace))
+    })
+}
+
+fn declaration_kind(keyword: &str, parent: Option<&ProtoScope>) -> Option<ProtobufDefinitionKind> {
+    let parent_kind = parent.map(|parent| parent.kind);
+    match keyword {
+        "message" if parent_kind.is_none_or(|kind| kind == ProtobufDefinitionKind::Message) => {
+            Some(ProtobufDefinitionKind::Message)
+        }
+        "enum" if parent_kind.is_none_or(|kind| kind == ProtobufDefinitionKind::Message) => {
+            Some(ProtobufDefinitionKind::Enum)
+        }
+        "service" if parent_kind.is_none() => Some(ProtobufDefinitionKind::Service),
+        "rpc" if parent_kind == Some(ProtobufDefinitionKind::Service) => {
+            Some(ProtobufDefinitionKind::Rpc)
+        }
+        _ => None,
+    }
+}
+
+/// Replace comments and string literal contents with spaces, keeping columns.
+fn blank_comments_and_strings(line: &str, in_block_comment: &mut bool) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut chars = line.chars().peekable();
+    let mut quote = None;
+    while let Some(ch) = chars.next() {
+        if *in_block_comment {
+            if ch == '*' && chars.peek() == Some(&'/') {
+                chars.next();
+                *in_block_comment = false;
+                out.push_str("  ");
+            } else {
+                out.push_str(&" ".repeat(ch.len_utf8()));
+            }
+            continue;
+        }
+        if let Some(open) = quote {
+            if ch == '\\' {
+                if let Some(escaped) = chars.next() {
+                    out.push_str(&" ".repeat(1 + escaped.len_utf8()));
+                }
+                continue;
+            }
+            if ch == open {
+                quote = None;
+                out.push(ch);
+            } else {
+                out.push_str(&" ".repeat(ch.len_utf8()));
+            }
+            continue;
+        }
+        match ch {
+            '/' if chars.peek() == Some(&'/') => break,
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                *in_block_comment = true;
+                out.push_str("  ");
+            }
+            '"' | '\'' => {
+                quote = Some(ch);
+                out.push(ch);
+            }
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+fn leading_identifier(text: &str, allow_dots: bool) -> Option<&str> {
+    let end = text
+        .find(|ch: char| !(ch.is_ascii_alphanumeric() || ch == '_' || (allow_dots && ch == '.')))
+        .unwrap_or(text.len());
+    let ident = &text[..end];
+    ident
+        .starts_with(|ch: char| ch.is_ascii_alphabetic() || ch == '_')
+        .then_some(ident)
+}
+
+/// Request and response type names of an `rpc Name (Req) returns (Resp)` line.
+fn rpc_type_names(rest: &str) -> Vec<String> {
+    rest.split('(')
+        .skip(1)
+        .filter_map(|group| group.split(')').next())
+        .filter_map(|group| {
+            let group = group.trim();
+            let group = group.strip_prefix("stream ").unwrap_or(group).trim();
+            leading_identifier(group.trim_start_matches('.'), true).map(str::to_string)
+        })
+        .collect()
+}
+
+/// Message-typed field declarations: `[label] Type name = N;` and map values.
+fn field_type_name(line: &str) -> Option<String> {
+    if !line.contains('=') || !line.trim_end().ends_with(';') {
+        return None;
+    }
+    if let Some(map_args) = line.strip_prefix("map<") {
+        let value = map_args.split('>').next()?.split(',').nth(1)?.trim();
+        return leading_identifier(value.trim_start_matches('.'), true).map(str::to_string);
+    }
+    let mut words = line.split_whitespace();
+    let mut type_name = words.next()?;
+    if matches!(type_name, "repeated" | "optional" | "required") {
+        type_name = words.next()?;
+    }
+    if matches!(
+        type_name,
+        "option" | "reserved" | "extensions" | "import" | "syntax" | "edition"
+    ) {
+        return None;
+    }
+    leading_identifier(type_name.trim_start_matches('.'), true)
+        .filter(|ident| ident.len() == type_name.trim_start_matches('.').len())
+        .map(str::to_string)
+}
+
+/// Resolve a field or rpc type against messages declared in this file, trying
+/// enclosing message scopes first and then dropping package qualifiers.
+fn resolve_message(
+    messages: &HashMap<String, NodeId>,
+    scope: &str,
+    type_name: &str,
+) -> Option<NodeId> {
+    let mut prefix = scope;
+    while !prefix.is_empty() {
+        if let Some(node_id) = messages.get(&format!("{prefix}.{type_name}")) {
+            return Some(*node_id);
+        }
+        prefix = prefix.rsplit_once('.').map_or("", |(parent, _)| parent);
+    }
+    let mut name = type_name;
+    loop {
+        if let Some(node_id) = messages.get(name) {
+            return Some(*node_id);
+        }
+        name = name.split_once('.')?.1;
+    }
+}
+
+const GENERATED_PATH_SUFFIXES: &[&str] = &[
+    "_pb2.py",
+    "_pb2_grpc.py",
+    "_pb2.pyi",
+    ".pb.go",
+    "_pb.ts",
+    "_pb.js",
+    "_pb.d.ts",
+    "Grpc.java",
+];
+
+/// Whether a source file looks like `protoc` output.
+pub(crate) fn is_protobuf_generated_source(path: &Path, source: &str) -> bool {
+    let path_text = path.to_string_lossy();
+    if GENERATED_PATH_SUFFIXES
+        .iter()
+        .any(|suffix| path_text.ends_with(suffix))
+    {
+        return true;
+    }
+    let header = source.get(..source.len().min(2048)).unwrap_or(source);
+    header.contains(".proto")
+        && (header.contains("protoc") || header.contains("protocol buffer compiler"))
+}
+
+const GENERATED_SERVICE_SUFFIXES: &[&str] = &[
+    "BlockingStub",
+    "FutureStub",
+    "ImplBase",
+    "Servicer",
+    "Stub",
+    "Client",
+    "Server",
+    "Grpc",
+];
+
+/// Proto service behind a generated client, server, or stub type name, e.g.
+/// `GreeterStub`, `GreeterServicer`, `UnimplementedGreeterServer`, or Go's
+/// unexported `greeterClient`.
+pub(crate) fn protobuf_generated_service_name(type_name: &str) -> Option<String> {
+    let name = type_name.strip_prefix("Unimplemented").unwrap_or(type_name);
+    let service = GENERATED_SERVICE_SUFFIXES
+        .iter()
+        .find_map(|suffix| name.strip_suffix(suffix))
+        .filter(|service| !service.is_empty())?;
+    Some(upper_first(service))
+}
+
+/// Proto message behind a generated message type, skipping builder and
+/// descriptor helpers that have no proto counterpart.
+pub(crate) fn protobuf_generated_message_name(type_name: &str) -> Option<&str> {
+    let helper = type_name.ends_with("OrBuilder")
+        || type_name == "Builder"
+        || type_name.ends_with("OuterClass")
+        || type_name.contains('_')
+        || !type_name.starts_with(|ch: char| ch.is_ascii_uppercase());
+    (!helper && protobuf_generated_service_name(type_name).is_none()).then_some(type_name)
+}
+
+/// Proto rpc behind a generated service member, e.g. Java's `sayHello`.
+pub(crate) fn protobuf_generated_rpc_name(member_name: &str) -> Option<String> {
+    let helper = member_name.starts_with('_')
+        || member_name.starts_with("mustEmbed")
+        || (member_name.starts_with("get") && member_name.ends_with("Method"))
+        || matches!(
+            member_name,
+            "bindService"
+                | "build"
+                | "getServiceDescriptor"
+                | "newStub"
+                | "newBlockingStub"
+                | "newFutureStub"
+        );
+    (!helper && !member_name.is_empty()).then(|| upper_first(member_name))
+}
+
+fn upper_first(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codestory_contracts::graph::EdgeKind;
+
+    const GREETER_PROTO: &str = r#"syntax = "proto3";
+package demo.greeter;
+
+/* service { comments do not open scopes */
+message HelloRequest {
+  string name = 1;
+  Locale locale = 2; // trailing comment
+  message Locale {
+    string tag = 1;
+  }
+}
+
+message HelloReply {
+  repeated HelloRequest.Locale locales = 1;
+}
+
+enum Mood { MOOD_UNSPECIFIED = 0; }
+
+service Greeter {
+  option (demo.note) = "rpc Hidden (A) returns (B)";
+  rpc SayHello (HelloRequest) returns (stream .demo.greeter.HelloReply);
+}
+"#;
+
+    #[test]
+    fn collects_messages_services_and_rpcs_with_shared_ids() {
+        let mut storage = IntermediateStorage::default();
+        let file_id = NodeId(7);
+        collect_protobuf_entities(
+            Path::new("api/greeter.proto"),
+            GREETER_PROTO,
+            file_id,
+            &mut storage,
+        );
+
+        let node = |canonical: &str| {
+            storage
+                .nodes
+                .iter()
+                .find(|node| node.canonical_id.as_deref() == Some(canonical))
+                .unwrap_or_else(|| panic!("missing {canonical}"))
+        };
+        let request = node("proto:message:HelloRequest");
+        let locale = node("proto:message:HelloRequest.Locale");
+        let reply = node("proto:message:HelloReply");
+        let service = node("proto:service:Greeter");
+        let rpc = node("proto:rpc:Greeter.SayHello");
+        assert_eq!(node("proto:enum:Mood").kind, NodeKind::ENUM);
+        assert_eq!(service.kind, NodeKind::INTERFACE);
+        assert_eq!(rpc.kind, NodeKind::METHOD);
+        assert_eq!(rpc.serialized_name, "Greeter.SayHello");
+        assert_eq!(
+            service.id,
+            NodeId(crate::generate_id("proto:service:Greeter"))
+        );
+        assert_eq!((rpc.start_line, rpc.start_col), (Some(21), Some(7)));
+        assert!(
+            storage
+                .nodes
+                .iter()
+                .all(|node| !node.serialized_name.contains("Hidden"))
+        );
+
+        let has_edge = |kind: EdgeKind, source: NodeId, target: NodeId| {
+            storage
+                .edges
+                .iter()
+                .any(|edge| edge.kind == kind && edge.source == source && edge.target == target)
+        };
+        assert!(has_edge(EdgeKind::MEMBER, service.id, rpc.id));
+        assert!(has_edge(EdgeKind::MEMBER, request.id, locale.id));
+        assert!(has_edge(EdgeKind::TYPE_USAGE, request.id, locale.id));
+        assert!(has_edge(EdgeKind::TYPE_USAGE, reply.id, locale.id));
+        assert!(has_edge(EdgeKind::TYPE_USAGE, rpc.id, request.id));
+        assert!(has_edge(EdgeKind::TYPE_USAGE, rpc.id, reply.id));
+    }
+
+    #[test]
+    fn package_less_files_scope_their_ids_to_the_file() {
+        let source = "syntax = \"proto3\";\n// package commented.out;\nmessage Ping {}\n";
+        let ids = ["a/ping.proto", "b/ping.proto"].map(|path| {
+            let mut storage = IntermediateStorage::default();
+            collect_protobuf_entities(Path::new(path), source, NodeId(7), &mut storage);
+            let ping = storage
+                .nodes
+                .iter()
+                .find(|node| node.serialized_name == "Ping")
+                .expect("message node");
+            assert_eq!(
+                ping.canonical_id.as_deref(),
+                Some(format!("proto:message:{path}#Ping").as_str())
+            );
+            ping.id
+        });
+        assert_ne!(ids[0], ids[1]);
+    }
+
+    #[test]
+    fn generated_type_names_map_back_to_proto_definitions() {
+        assert!(is_protobuf_generated_source(
+            Path::new("gen/greeter_pb2_grpc.py"),
+            ""
+        ));
+        assert!(is_protobuf_generated_source(
+            Path::new("gen/greeter.go"),
+            "// Code generated by protoc-gen-go. DO NOT EDIT.\n// source: greeter.proto\n"
+        ));
+        assert!(!is_protobuf_generated_source(
+            Path::new("src/greeter.go"),
+            "package greeter\n"
+        ));
+
+        for (generated, service) in [
+            ("GreeterStub", "Greeter"),
+            ("GreeterServicer", "Greeter"),
+            ("UnimplementedGreeterServer", "Greeter"),
+            ("greeterClient", "Greeter"),
+            ("GreeterImplBase", "Greeter"),
+            ("GreeterGrpc", "Greeter"),
+        ] {
+            assert_eq!(
+                protobuf_generated_service_name(generated).as_deref(),
+                Some(service)
+            );
+        }
+        assert_eq!(protobuf_generated_service_name("Stub"), None);
+        assert_eq!(
+            protobuf_generated_message_name("HelloRequest"),
+            Some("HelloRequest")
+        );
+        assert_eq!(
+            protobuf_generated_message_name("HelloRequestOrBuilder"),
+            None
+        );
+        assert_eq!(protobuf_generated_message_name("HelloRequest_Name"), None);
+        assert_eq!(
+            protobuf_generated_rpc_name("sayHello").as_deref(),
+            Some("SayHello")
+        );
+        assert_eq!(protobuf_generated_rpc_name("bindService"), None);
+        assert_eq!(protobuf_generated_rpc_name("getSayHelloMethod"), None);
+    }
+}
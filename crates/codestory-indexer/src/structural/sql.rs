@@ -379,6 +379,34 @@ fn referenced_tables(line: &str, default_schema: &str) -> Vec<String> {
     tables
 }
 
+/// Whether a host-language string literal reads as a SQL statement.
+///
+/// Only statements that name a table (`SELECT ... FROM`, `INSERT INTO`,
+/// `UPDATE`, `DELETE FROM`, or a `WITH` query) qualify, so prose that merely
+/// starts with "Select" or "Update" is not mistaken for a query.
+pub(crate) fn looks_like_embedded_sql(text: &str) -> bool {
+    let upper = text.trim_start().to_ascii_uppercase();
+    let Some(first) = upper.split_whitespace().next() else {
+        return false;
+    };
+    match first {
+        "SELECT" | "WITH" => upper.contains(" FROM "),
+        "INSERT" => upper.contains(" INTO "),
+        "UPDATE" => upper.contains(" SET "),
+        "DELETE" => upper.contains(" FROM "),
+        _ => false,
+    }
+}
+
+/// Tables an embedded SQL statement reads or writes, as `schema.name` keys
+/// matching the `sql:table:` canonical ids of `.sql` schema files.
+pub(crate) fn embedded_sql_table_references(text: &str) -> Vec<String> {
+    let mut tables = referenced_tables(&format!(" {}", text.trim_start()), "public");
+    let mut seen = std::collections::HashSet::new();
+    tables.retain(|table| seen.insert(table.clone()));
+    tables
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -386,6 +414,30 @@ mod tests {
     use codestory_contracts::graph::{EdgeKind, NodeKind};
     use std::path::Path;
 
+    #[test]
+    fn embedded_sql_literals_report_referenced_tables() {
+        assert!(looks_like_embedded_sql(
+            "SELECT id FROM app.users JOIN orders ON 1=1"
+        ));
+        assert!(looks_like_embedded_sql("update users set email = ?"));
+        assert!(!looks_like_embedded_sql("Select a file to open"));
+        assert!(!looks_like_embedded_sql("users"));
+        assert_eq!(
+            embedded_sql_table_references("SELECT id FROM app.users JOIN orders ON 1=1"),
+            vec!["app.users".to_string(), "public.orders".to_string()]
+        );
+        assert_eq!(
+            embedded_sql_table_references(
+                "UPDATE users SET name = ? WHERE id IN (SELECT id FROM users)"
+            ),
+            vec!["public.users".to_string()]
+        );
+        assert_eq!(
+            embedded_sql_table_references("INSERT INTO audit_log (id) VALUES (?)"),
+            vec!["public.audit_log".to_string()]
+        );
+    }
+
     #[test]
     fn collects_schema_table_column_and_index() {
         let sql = r#"
@@ -4,6 +4,7 @@
 //! JavaScript/TypeScript tree-sitter graph rules can run on the full file while keeping
 //! absolute source positions aligned with the original template.
 
+use crate::structural::script_block_language;
 use std::path::Path;
 
 /// Which template dialect applies to a file path.
@@ -128,14 +129,20 @@ fn collect_script_blocks(
         };
         let close_start = open_end + close_rel;
         let close_end = close_start + "</script>".len();
+        search_from = close_end;
+        // Data blocks such as `type="application/json"` stay blanked. The
+        // TypeScript grammar also reads JavaScript, so one TypeScript block
+        // routes the whole component through it.
+        let Some(lang) = script_block_language(&source[start..open_end]) else {
+            continue;
+        };
         keep_ranges.push(ByteRange {
             start: open_end,
             end: close_start.min(bytes.len()),
         });
-        if let Some(lang) = script_lang_from_opening_tag(&source[start..open_end]) {
+        if lang == "typescript" {
             *script_language = lang;
         }
-        search_from = close_end;
     }
 }
 
@@ -264,21 +271,6 @@ fn find_brace_expression_end(source: &str, open_index: usize) -> Option<usize> {
     None
 }
 
-fn script_lang_from_opening_tag(opening_tag: &str) -> Option<&'static str> {
-    let lower = opening_tag.to_ascii_lowercase();
-    if lower.contains("lang=\"ts\"")
-        || lower.contains("lang='ts'")
-        || lower.contains("lang=ts")
-        || lower.contains("lang=\"typescript\"")
-        || lower.contains("lang='typescript'")
-        || lower.contains("lang=typescript")
-    {
-        Some("typescript")
-    } else {
-        None
-    }
-}
-
 fn blank_source(source: &str, keep_ranges: &[ByteRange]) -> String {
     let bytes = source.as_bytes();
     let mut out = vec![b' '; bytes.len()];
@@ -464,4 +456,14 @@ const site = 'codestory'
         let prepared = prepare_template_source(TemplateKind::Vue, source);
         assert_eq!(prepared.script_language, "typescript");
     }
+
+    #[test]
+    fn test_template_data_script_blocks_stay_blanked() {
+        let source = r#"<script type="application/json">{"a": 1}</script>
+<script>export const x = 1</script>"#;
+        let prepared = prepare_template_source(TemplateKind::Vue, source);
+        assert!(!prepared.blanked.contains("\"a\""));
+        assert!(prepared.blanked.contains("export const x = 1"));
+        assert_eq!(prepared.script_language, "javascript");
+    }
 }
@@ -800,3 +800,174 @@ export default function dispatchRequest(config) {
 
     Ok(())
 }
+
+#[test]
+fn test_typescript_path_aliases_resolve_through_tsconfig() -> anyhow::Result<()> {
+    let dir = tempdir()?;
+    let root = dir.path();
+    fs::write(
+        root.join("tsconfig.json"),
+        r#"{
+    // Aliases used by the app.
+    "compilerOptions": {
+        "baseUrl": ".",
+        "paths": { "@app/*": ["src/app/*"] },
+    },
+}"#,
+    )?;
+    let files = [
+        ("src/app/client.ts", "export class Client {}\n"),
+        ("src/legacy/client.ts", "export class Client {}\n"),
+        ("src/shared/format.ts", "export function format() {}\n"),
+        (
+            "src/main.ts",
+            r#"
+import { Client } from "@app/client";
+import { format } from "src/shared/format";
+
+const client = new Client();
+format();
+"#,
+        ),
+    ];
+    // The config is read because discovery found it, like every other file.
+    let mut paths = vec![root.join("tsconfig.json")];
+    for (filename, contents) in files {
+        let file_path = root.join(filename);
+        fs::create_dir_all(file_path.parent().expect("file has a parent"))?;
+        fs::write(&file_path, contents)?;
+        paths.push(file_path);
+    }
+
+    let mut storage = Storage::new_in_memory()?;
+    let refresh_info = codestory_workspace::RefreshInfo {
+        mode: codestory_workspace::BuildMode::Incremental,
+        files_to_index: paths,
+        files_to_remove: vec![],
+        existing_file_ids: std::collections::HashMap::new(),
+    };
+    WorkspaceIndexer::new(root.to_path_buf()).run_incremental(
+        &mut storage,
+        &refresh_info,
+        &EventBus::new(),
+        None,
+    )?;
+    let (nodes, edges) = (storage.get_nodes()?, storage.get_edges()?);
+
+    // `Client` is defined twice, so only the alias can pick the right file.
+    assert_import_resolved_to(&nodes, &edges, "src/main.ts", "src/app/client.ts", "Client");
+    assert_import_resolved_to(
+        &nodes,
+        &edges,
+        "src/main.ts",
+        "src/shared/format.ts",
+        "format",
+    );
+    Ok(())
+}
+
+#[test]
+fn test_python_absolute_imports_resolve_under_package_roots() -> anyhow::Result<()> {
+    let (nodes, edges) = index_workspace(&[
+        ("src/app/models.py", "class User:\n    pass\n"),
+        ("tools/models.py", "class User:\n    pass\n"),
+        ("tools/helpers.py", "def logging():\n    pass\n"),
+        (
+            "src/app/main.py",
+            "import logging\nimport app.models\nfrom app.models import User\n",
+        ),
+    ])?;
+    let nodes_by_id = nodes
+        .iter()
+        .map(|node| (node.id, node))
+        .collect::<std::collections::HashMap<_, _>>();
+
+    // `User` is defined twice; only the `src/` package root picks one.
+    assert_import_resolved_to(
+        &nodes,
+        &edges,
+        "src/app/main.py",
+        "src/app/models.py",
+        "User",
+    );
+
+    let import_from_main = |name: &str| {
+        edges.iter().find(|edge| {
+            edge.kind == EdgeKind::IMPORT
+                && edge_importer_path(&nodes_by_id, edge)
+                    .is_some_and(|path| path_ends_with(path, "src/app/main.py"))
+                && nodes_by_id
+                    .get(&edge.source)
+                    .is_some_and(|source| source.serialized_name == name)
+        })
+    };
+    let module_import = import_from_main("app.models").expect("`import app.models` edge");
+    let module_file = module_import
+        .resolved_target
+        .and_then(|target| nodes_by_id.get(&target))
+        .expect("`import app.models` resolves");
+    assert_eq!(module_file.kind, NodeKind::FILE);
+    assert!(path_ends_with(
+        &module_file.serialized_name,
+        "src/app/models.py"
+    ));
+
+    let stdlib_import = import_from_main("logging").expect("`import logging` edge");
+    assert_eq!(
+        stdlib_import.resolved_target, None,
+        "a standard library import must not land on the project's `logging` function"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_java_supertypes_resolve_by_qualified_name() -> anyhow::Result<()> {
+    let (nodes, edges) = index_workspace(&[
+        (
+            "src/main/java/com/acme/Base.java",
+            "package com.acme;\n\npublic class Base {}\n",
+        ),
+        (
+            "src/main/java/com/other/Base.java",
+            "package com.other;\n\npublic class Base {}\n",
+        ),
+        (
+            "src/main/java/com/acme/shapes/Circle.java",
+            "package com.acme.shapes;\n\nimport com.acme.Base;\nimport java.io.Serializable;\n\npublic class Circle extends Base implements Serializable {}\n",
+        ),
+    ])?;
+    let nodes_by_id = nodes
+        .iter()
+        .map(|node| (node.id, node))
+        .collect::<std::collections::HashMap<_, _>>();
+    let supertype = |name: &str| {
+        edges
+            .iter()
+            .find(|edge| {
+                edge.kind == EdgeKind::INHERITANCE
+                    && nodes_by_id
+                        .get(&edge.target)
+                        .is_some_and(|target| target.serialized_name == name)
+            })
+            .and_then(|edge| edge.resolved_target)
+            .and_then(|target| nodes_by_id.get(&target))
+            .unwrap_or_else(|| panic!("`{name}` supertype resolves"))
+    };
+
+    // Both packages declare `Base`; the import picks `com.acme`.
+    let base = node_in_file(
+        &nodes,
+        &nodes_by_id,
+        "Base",
+        NodeKind::CLASS,
+        "com/acme/Base.java",
+    )
+    .expect("com.acme.Base node");
+    assert_eq!(supertype("Base").id, base.id);
+
+    let serializable = supertype("Serializable");
+    assert_eq!(serializable.serialized_name, "java.io.Serializable");
+    assert_eq!(serializable.kind, NodeKind::INTERFACE);
+    assert_eq!(serializable.file_node_id, None);
+    Ok(())
+}
@@ -112,6 +112,39 @@ int main() {
     Ok(())
 }
 
+#[test]
+fn test_indexed_file_row_records_language_line_count_and_mtime() -> anyhow::Result<()> {
+    let dir = tempdir()?;
+    let root = dir.path();
+    let file_path = root.join("src/lib.rs");
+    fs::create_dir_all(file_path.parent().expect("fixture parent"))?;
+    fs::write(
+        &file_path,
+        "pub fn first() {}\n\npub fn second() {\n    first();\n}\n",
+    )?;
+    let modified_ms = fs::metadata(&file_path)?
+        .modified()?
+        .duration_since(std::time::UNIX_EPOCH)?
+        .as_millis() as i64;
+
+    let mut storage = Storage::new_in_memory()?;
+    run_incremental_indexing(root, &mut storage, vec![file_path.clone()])?;
+
+    let files = storage.get_files()?;
+    assert_eq!(files.len(), 1);
+    let file = &files[0];
+    assert_eq!(file.path, file_path);
+    assert_eq!(file.language, "rust");
+    assert_eq!(file.line_count, 5);
+    assert_eq!(
+        file.modification_time, modified_ms,
+        "refresh planning compares this mtime on the next incremental run"
+    );
+    assert!(file.indexed);
+    assert!(file.complete);
+    Ok(())
+}
+
 #[test]
 fn test_failed_file_attempt_is_recorded_as_incomplete_with_attached_error() -> anyhow::Result<()> {
     let dir = tempdir()?;
@@ -1357,6 +1390,52 @@ function App() {
     Ok(())
 }
 
+#[test]
+fn test_rust_self_field_writes_and_reads_become_data_flow_edges() -> anyhow::Result<()> {
+    let storage = index_project(&[(
+        "counter.rs",
+        r#"
+struct Counter {
+    total: u32,
+}
+
+impl Counter {
+    fn bump(&mut self) {
+        self.total += 1;
+    }
+
+    fn report(&self) -> u32 {
+        self.total
+    }
+}
+"#,
+    )])?;
+
+    let nodes = storage.get_nodes()?;
+    let edges = storage.get_edges()?;
+    let node_id = |name: &str| {
+        nodes
+            .iter()
+            .find(|node| node.serialized_name.ends_with(name))
+            .map(|node| node.id)
+            .unwrap_or_else(|| panic!("{name} node not found"))
+    };
+    let (bump_id, report_id, total_id) = (node_id("bump"), node_id("report"), node_id("total"));
+    assert!(edges.iter().any(|edge| {
+        edge.kind == EdgeKind::ASSIGNMENT && edge.source == bump_id && edge.target == total_id
+    }));
+    assert!(edges.iter().any(|edge| {
+        edge.kind == EdgeKind::USAGE && edge.source == report_id && edge.target == total_id
+    }));
+    assert!(
+        !edges
+            .iter()
+            .any(|edge| edge.kind == EdgeKind::ASSIGNMENT && edge.source == report_id)
+    );
+
+    Ok(())
+}
+
 #[test]
 fn test_cpp_access_specifiers_are_captured_from_rules() -> anyhow::Result<()> {
     let storage = index_project(&[(
@@ -506,6 +506,110 @@ fn run() -> i32 {
     Ok(())
 }
 
+#[test]
+fn test_rust_symbol_canonical_ids_do_not_depend_on_the_checkout_path() -> anyhow::Result<()> {
+    let files = [(
+        "src/counter.rs",
+        r#"
+pub struct Counter {
+    count: u32,
+}
+
+impl Counter {
+    pub fn bump(&mut self, by: u32) {
+        self.count += by;
+    }
+}
+"#,
+    )];
+    let symbol_ids = |nodes: &[Node]| {
+        let mut ids = nodes
+            .iter()
+            .filter_map(|node| node.canonical_id.clone())
+            .filter(|canonical_id| canonical_id.starts_with("sym:"))
+            .collect::<Vec<_>>();
+        ids.sort();
+        ids
+    };
+
+    let (first, _) = index_project(&files)?;
+    let (second, _) = index_project(&files)?;
+    assert!(!symbol_ids(&first).is_empty());
+    assert_eq!(symbol_ids(&first), symbol_ids(&second));
+
+    let counter = find_node_by_name_and_kind(&first, "Counter", NodeKind::STRUCT)
+        .ok_or_else(|| anyhow::anyhow!("expected Counter struct"))?;
+    let counter_id = counter.canonical_id.as_deref().unwrap_or_default();
+    assert!(
+        counter_id.starts_with("sym:rust:") && counter_id.ends_with("Counter"),
+        "{counter:?}"
+    );
+    let bump = find_node_by_name_and_kind(&first, "bump", NodeKind::METHOD)
+        .ok_or_else(|| anyhow::anyhow!("expected bump method"))?;
+    let bump_id = bump.canonical_id.as_deref().unwrap_or_default();
+    assert!(
+        bump_id.starts_with("sym:rust:") && bump_id.contains("bump#"),
+        "{bump:?}"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_rust_field_accesses_split_into_write_and_read_occurrences() -> anyhow::Result<()> {
+    let (nodes, _edges, occurrences) = index_project_with_occurrences(&[(
+        "counter.rs",
+        r#"
+struct Counter {
+    count: u32,
+    limit: u32,
+}
+
+impl Counter {
+    fn bump(&mut self) {
+        self.count += 1;
+        if self.count > self.limit {
+            self.count = 0;
+        }
+    }
+}
+"#,
+    )])?;
+
+    let accesses = |name: &str| -> anyhow::Result<Vec<(OccurrenceKind, u32)>> {
+        let field = find_node_by_name_and_kind(&nodes, name, NodeKind::FIELD)
+            .ok_or_else(|| anyhow::anyhow!("expected field {name}"))?;
+        let mut accesses = occurrences
+            .iter()
+            .filter(|occurrence| occurrence.element_id == field.id.0)
+            .filter(|occurrence| {
+                matches!(
+                    occurrence.kind,
+                    OccurrenceKind::WRITE_REFERENCE | OccurrenceKind::READ_REFERENCE
+                )
+            })
+            .map(|occurrence| (occurrence.kind, occurrence.location.start_line))
+            .collect::<Vec<_>>();
+        accesses.sort_by_key(|(_, line)| *line);
+        Ok(accesses)
+    };
+
+    assert_eq!(
+        accesses("count")?,
+        vec![
+            (OccurrenceKind::WRITE_REFERENCE, 9),
+            (OccurrenceKind::READ_REFERENCE, 10),
+            (OccurrenceKind::WRITE_REFERENCE, 11),
+        ]
+    );
+    assert_eq!(
+        accesses("limit")?,
+        vec![(OccurrenceKind::READ_REFERENCE, 10)]
+    );
+
+    Ok(())
+}
+
 #[test]
 fn test_c_typedef_struct_members_cover_pointer_and_function_pointer_declarators()
 -> anyhow::Result<()> {
@@ -1496,3 +1600,86 @@ record Holder(int value) {
 
     Ok(())
 }
+
+#[test]
+fn test_unresolved_rust_calls_resolve_into_cargo_registry_sources() -> anyhow::Result<()> {
+    use codestory_indexer::dependency_sources::{
+        DependencySourceLocations, build_dependency_storage, discover_dependency_sources,
+    };
+
+    let cargo_home = tempdir()?;
+    let package_src = cargo_home
+        .path()
+        .join("registry/src/index.crates.io-0000000000000000/loudly-1.2.0/src");
+    fs::create_dir_all(&package_src)?;
+    fs::write(
+        package_src.join("lib.rs"),
+        "pub fn shout(text: &str) -> String {\n    text.to_uppercase()\n}\n",
+    )?;
+
+    let workspace = tempdir()?;
+    fs::create_dir_all(workspace.path().join("src"))?;
+    fs::write(
+        workspace.path().join("Cargo.lock"),
+        r#"version = 3
+
+[[package]]
+name = "app"
+version = "0.1.0"
+
+[[package]]
+name = "loudly"
+version = "1.2.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+"#,
+    )?;
+    let main_path = workspace.path().join("src/main.rs");
+    fs::write(&main_path, "fn main() {\n    loudly::shout(\"hi\");\n}\n")?;
+
+    let sources = discover_dependency_sources(
+        workspace.path(),
+        &DependencySourceLocations {
+            cargo_home: Some(cargo_home.path().to_path_buf()),
+            java_home: None,
+        },
+    )?;
+    assert_eq!(sources.len(), 1);
+    assert_eq!(sources[0].name, "loudly");
+
+    let event_bus = EventBus::new();
+    let dependency_path = cargo_home.path().join("dependencies.db");
+    {
+        let mut dependency_storage = Storage::open(&dependency_path)?;
+        build_dependency_storage(&mut dependency_storage, &sources, &event_bus)?;
+    }
+
+    let mut storage = Storage::new_in_memory()?;
+    let refresh_info = codestory_workspace::RefreshInfo {
+        mode: codestory_workspace::BuildMode::Incremental,
+        files_to_index: vec![main_path],
+        files_to_remove: vec![],
+        existing_file_ids: std::collections::HashMap::new(),
+    };
+    let stats = WorkspaceIndexer::new(workspace.path().to_path_buf())
+        .with_dependency_storage(dependency_path)
+        .run_incremental(&mut storage, &refresh_info, &event_bus, None)?;
+    assert_eq!(stats.resolved_calls_dependency, 1);
+
+    let nodes = storage.get_nodes()?;
+    let shout = find_node_by_name_and_kind(&nodes, "shout", NodeKind::FUNCTION)
+        .ok_or_else(|| anyhow::anyhow!("expected dependency shout function"))?;
+    let call = storage
+        .get_edges()?
+        .into_iter()
+        .find(|edge| edge.kind == EdgeKind::CALL && edge.resolved_target == Some(shout.id))
+        .ok_or_else(|| anyhow::anyhow!("expected call resolved into loudly"))?;
+    assert_eq!(
+        storage
+            .dependency_resolutions()?
+            .get(&call.id)
+            .map(String::as_str),
+        Some("cargo:loudly-1.2.0")
+    );
+
+    Ok(())
+}
@@ -951,6 +951,34 @@ private:
     Ok(())
 }
 
+#[test]
+fn test_python_underscore_names_map_to_access() -> Result<()> {
+    let source = r#"
+class Board:
+    def place(self):
+        pass
+
+    def _reset(self):
+        pass
+
+    def __check(self):
+        pass
+
+    def __len__(self):
+        return 9
+"#;
+
+    let language_config = get_language_for_ext("py").expect("python extension should be supported");
+    let result = index_file(Path::new("board.py"), source, &language_config, None, None)?;
+
+    let access = |name| access_for_name(&result.component_access, &result.nodes, name);
+    assert_eq!(access("place"), Some(AccessKind::Public));
+    assert_eq!(access("_reset"), Some(AccessKind::Protected));
+    assert_eq!(access("__check"), Some(AccessKind::Private));
+    assert_eq!(access("__len__"), Some(AccessKind::Public));
+    Ok(())
+}
+
 #[test]
 fn test_tictactoe_fixture_parses_for_all_supported_languages() -> Result<()> {
     for case in fixture_cases() {
@@ -1280,6 +1280,7 @@ fn affected_edge_kind_label(kind: codestory_contracts::graph::EdgeKind) -> &'sta
         codestory_contracts::graph::EdgeKind::IMPORT => "import",
         codestory_contracts::graph::EdgeKind::MACRO_USAGE => "macro_usage",
         codestory_contracts::graph::EdgeKind::ANNOTATION_USAGE => "annotation_usage",
+        codestory_contracts::graph::EdgeKind::ASSIGNMENT => "assignment",
         codestory_contracts::graph::EdgeKind::UNKNOWN => "unknown",
     }
 }
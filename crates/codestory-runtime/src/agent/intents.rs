@@ -0,0 +1,256 @@
+//! Fixed-structure answers for `AgentAskIntentDto` requests.
+//!
+//! An intent replaces prompt-keyword routing: it picks the trail the retrieval
+//! bundle is built from and answers with the same sections every time, so
+//! callers can read "callers" or "body" without parsing free-form markdown.
+
+use crate::AppController;
+use crate::agent::orchestrator::agent_trail_request;
+use crate::agent::profiles::{ResolvedProfile, TrailPlan};
+use codestory_contracts::api::{
+    AgentAskIntentDto, AgentResponseBlockDto, AgentResponseSectionDto, AgentRetrievalPolicyModeDto,
+    AgentRetrievalPresetDto, EdgeKind, GraphResponse, NodeDetailsDto, TrailCallerScope,
+    TrailDirection, TrailMode,
+};
+use std::fmt::Write as _;
+
+const INTENT_TRAIL_MAX_NODES: u32 = 400;
+const INTENT_CALLER_DEPTH: u32 = 3;
+const INTENT_TRACE_DEPTH: u32 = 4;
+/// Symbols listed per section before the rest are summarized as a count.
+const INTENT_LIST_LIMIT: usize = 40;
+
+/// Retrieval profile an intent runs with in place of `resolve_profile`.
+pub(crate) fn intent_profile(intent: AgentAskIntentDto) -> ResolvedProfile {
+    let (preset, trail_plans) = match intent {
+        AgentAskIntentDto::Explain => (
+            AgentRetrievalPresetDto::Investigate,
+            vec![call_plan(TrailMode::Neighborhood, TrailDirection::Both, 1)],
+        ),
+        AgentAskIntentDto::Callers => (
+            AgentRetrievalPresetDto::Impact,
+            vec![caller_plan(INTENT_CALLER_DEPTH)],
+        ),
+        AgentAskIntentDto::TraceRequest => (
+            AgentRetrievalPresetDto::Callflow,
+            vec![callee_plan(INTENT_TRACE_DEPTH)],
+        ),
+    };
+    ResolvedProfile {
+        preset,
+        policy_mode: AgentRetrievalPolicyModeDto::LatencyFirst,
+        trail_plans,
+        include_edge_occurrences: true,
+        enable_source_reads: true,
+        max_search_results: 25,
+        max_source_bytes: 32 * 1024,
+    }
+}
+
+/// Sections for `intent` about the focused symbol. They come before the
+/// generic retrieval sections and keep the same ids whatever the prompt says.
+pub(crate) fn intent_sections(
+    controller: &AppController,
+    intent: AgentAskIntentDto,
+    focus: Option<&NodeDetailsDto>,
+) -> Vec<AgentResponseSectionDto> {
+    let Some(focus) = focus else {
+        return vec![markdown_section(
+            "focus",
+            "Focus Symbol",
+            "No focus symbol resolved. Pass `focus_node_id` or name the symbol in the prompt."
+                .to_string(),
+        )];
+    };
+
+    let mut sections = vec![markdown_section(
+        "focus",
+        "Focus Symbol",
+        focus_markdown(focus),
+    )];
+    match intent {
+        AgentAskIntentDto::Explain => {
+            let body = match controller.snippet_function_body_context(focus.id.clone(), 0) {
+                Ok(snippet) => snippet.snippet,
+                Err(error) => format!("Body unavailable: {}", error.message),
+            };
+            sections.push(markdown_section("body", "Body", body));
+            sections.push(markdown_section(
+                "callers",
+                "Direct Callers",
+                trail_markdown(controller, focus, &caller_plan(1)),
+            ));
+            sections.push(markdown_section(
+                "callees",
+                "Direct Callees",
+                trail_markdown(controller, focus, &callee_plan(1)),
+            ));
+        }
+        AgentAskIntentDto::Callers => sections.push(markdown_section(
+            "callers",
+            "Callers",
+            trail_markdown(controller, focus, &caller_plan(INTENT_CALLER_DEPTH)),
+        )),
+        AgentAskIntentDto::TraceRequest => sections.push(markdown_section(
+            "call-trace",
+            "Call Trace",
+            trail_markdown(controller, focus, &callee_plan(INTENT_TRACE_DEPTH)),
+        )),
+    }
+    sections
+}
+
+fn caller_plan(depth: u32) -> TrailPlan {
+    call_plan(TrailMode::AllReferencing, TrailDirection::Incoming, depth)
+}
+
+fn callee_plan(depth: u32) -> TrailPlan {
+    call_plan(TrailMode::AllReferenced, TrailDirection::Outgoing, depth)
+}
+
+fn call_plan(mode: TrailMode, direction: TrailDirection, depth: u32) -> TrailPlan {
+    TrailPlan {
+        mode,
+        depth,
+        direction,
+        caller_scope: TrailCallerScope::IncludeTestsAndBenches,
+        edge_filter: vec![EdgeKind::CALL],
+        node_filter: vec![],
+        max_nodes: INTENT_TRAIL_MAX_NODES,
+    }
+}
+
+fn markdown_section(id: &str, title: &str, markdown: String) -> AgentResponseSectionDto {
+    AgentResponseSectionDto {
+        id: id.to_string(),
+        title: title.to_string(),
+        blocks: vec![AgentResponseBlockDto::Markdown { markdown }],
+    }
+}
+
+fn focus_markdown(focus: &NodeDetailsDto) -> String {
+    let mut markdown = format!("**{}** (`{:?}`)", focus.display_name, focus.kind);
+    match (focus.file_path.as_deref(), focus.start_line) {
+        (Some(path), Some(line)) => {
+            let _ = write!(markdown, " at `{path}:{line}`");
+        }
+        (Some(path), None) => {
+            let _ = write!(markdown, " in `{path}`");
+        }
+        _ => {}
+    }
+    markdown
+}
+
+fn trail_markdown(controller: &AppController, focus: &NodeDetailsDto, plan: &TrailPlan) -> String {
+    match controller.graph_trail(agent_trail_request(focus.id.clone(), plan)) {
+        Ok(graph) => hop_list_markdown(&graph),
+        Err(error) => format!("Call graph unavailable: {}", error.message),
+    }
+}
+
+/// Non-center nodes of `graph` as a list ordered by hop, then label.
+fn hop_list_markdown(graph: &GraphResponse) -> String {
+    let mut nodes = graph
+        .nodes
+        .iter()
+        .filter(|node| node.id != graph.center_id)
+        .collect::<Vec<_>>();
+    if nodes.is_empty() {
+        return "None found in the index.".to_string();
+    }
+    nodes.sort_by(|left, right| {
+        left.depth
+            .cmp(&right.depth)
+            .then_with(|| left.label.cmp(&right.label))
+            .then_with(|| left.id.0.cmp(&right.id.0))
+    });
+
+    let mut markdown = String::new();
+    for node in nodes.iter().take(INTENT_LIST_LIMIT) {
+        let _ = write!(
+            markdown,
+            "- hop {}: `{}` (`{:?}`)",
+            node.depth, node.label, node.kind
+        );
+        if let Some(path) = node.file_path.as_deref() {
+            let _ = write!(markdown, " in `{path}`");
+        }
+        markdown.push('\n');
+    }
+    if nodes.len() > INTENT_LIST_LIMIT {
+        let _ = writeln!(markdown, "- {} more", nodes.len() - INTENT_LIST_LIMIT);
+    }
+    if graph.truncated {
+        markdown.push_str("\nTrail stopped at its node limit; more symbols may exist.\n");
+    }
+    markdown
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codestory_contracts::api::{GraphNodeDto, NodeId, NodeKind};
+
+    fn node(id: &str, label: &str, depth: u32) -> GraphNodeDto {
+        GraphNodeDto {
+            id: NodeId(id.to_string()),
+            label: label.to_string(),
+            kind: NodeKind::FUNCTION,
+            depth,
+            label_policy: None,
+            badge_visible_members: None,
+            badge_total_members: None,
+            merged_symbol_examples: Vec::new(),
+            file_path: (id == "3").then(|| "src/api.rs".to_string()),
+            qualified_name: None,
+            member_access: None,
+            edge_counts: None,
+            badges: Default::default(),
+            centrality: None,
+        }
+    }
+
+    #[test]
+    fn hop_list_orders_by_hop_then_label_and_skips_the_center() {
+        let graph = GraphResponse {
+            center_id: NodeId("1".to_string()),
+            nodes: vec![
+                node("1", "handle", 0),
+                node("2", "worker", 2),
+                node("3", "route", 1),
+                node("4", "main", 1),
+            ],
+            edges: Vec::new(),
+            truncated: true,
+            truncated_by_timeout: false,
+            omitted_edge_count: 0,
+            pruned_test_node_count: 0,
+            pruned_accessor_node_count: 0,
+            pruned_generated_node_count: 0,
+            omitted_node_count: 0,
+            node_limit: None,
+            paths: Vec::new(),
+            canonical_layout: None,
+        };
+
+        assert_eq!(
+            hop_list_markdown(&graph),
+            "- hop 1: `main` (`FUNCTION`)\n\
+             - hop 1: `route` (`FUNCTION`) in `src/api.rs`\n\
+             - hop 2: `worker` (`FUNCTION`)\n\
+             \nTrail stopped at its node limit; more symbols may exist.\n"
+        );
+    }
+
+    #[test]
+    fn intent_profiles_follow_calls_in_the_intent_direction() {
+        let callers = intent_profile(AgentAskIntentDto::Callers);
+        assert_eq!(callers.trail_plans[0].direction, TrailDirection::Incoming);
+        assert_eq!(callers.trail_plans[0].edge_filter, vec![EdgeKind::CALL]);
+
+        let trace = intent_profile(AgentAskIntentDto::TraceRequest);
+        assert_eq!(trace.preset, AgentRetrievalPresetDto::Callflow);
+        assert_eq!(trace.trail_plans[0].direction, TrailDirection::Outgoing);
+    }
+}
@@ -1,6 +1,7 @@
 pub(crate) mod citation;
 #[cfg(test)]
 pub(crate) mod eval_probes;
+pub(crate) mod intents;
 pub(crate) mod nucleo_policy;
 pub(crate) mod orchestrator;
 pub(crate) mod packet_batch;
@@ -1,4 +1,5 @@
 use crate::agent::citation::{evidence_edge_ids_for_node, to_citation_from_hit};
+use crate::agent::intents;
 use crate::agent::packet_batch::{
     PacketLatencyBudget, packet_anchor_probe_queries, run_packet_anchor_expansion,
     run_packet_planned_subqueries,
@@ -213,7 +214,10 @@ pub(crate) fn agent_ask(
     }
 
     let request_id = next_request_id();
-    let resolved_profile = resolve_profile(&prompt, &req.retrieval_profile);
+    let resolved_profile = match req.intent {
+        Some(intent) => intents::intent_profile(intent),
+        None => resolve_profile(&prompt, &req.retrieval_profile),
+    };
     let sla_target_ms = req
         .latency_budget_ms
         .unwrap_or(DEFAULT_SLA_TARGET_MS)
@@ -286,7 +290,16 @@ pub(crate) fn agent_ask(
         vec![field("citation_count", bundle.citations.len().to_string())],
     );
 
-    let sections = build_sections(&prompt, &resolved_profile, &bundle, source_context.as_ref());
+    let mut sections = match req.intent {
+        Some(intent) => intents::intent_sections(controller, intent, bundle.focused_node.as_ref()),
+        None => Vec::new(),
+    };
+    sections.extend(build_sections(
+        &prompt,
+        &resolved_profile,
+        &bundle,
+        source_context.as_ref(),
+    ));
 
     trace.finish_ok(
         synth_step,
@@ -404,6 +417,7 @@ pub(crate) fn agent_packet(
             latency_budget_ms: req.latency_budget_ms,
             include_evidence: req.include_evidence,
             hybrid_weights: initial_hybrid_weights.clone(),
+            intent: None,
         },
     )?;
     if !exact_probe_citations.is_empty() {
@@ -3767,6 +3781,9 @@ fn execute_retrieval(
             TrailFilterOptionsDto {
                 node_kinds: Vec::new(),
                 edge_kinds: Vec::new(),
+                node_kind_counts: Vec::new(),
+                edge_kind_counts: Vec::new(),
+                presets: Vec::new(),
             }
         }
     };
@@ -3924,6 +3941,9 @@ fn execute_retrieval(
         for hit in hits.iter().take(3) {
             match controller.node_occurrences(NodeOccurrencesRequest {
                 id: hit.node_id.clone(),
+                kind: None,
+                offset: None,
+                limit: None,
             }) {
                 Ok(occurrences) => {
                     occurrence_count += occurrences.len();
@@ -4301,6 +4321,7 @@ fn search_hit_from_grounding_symbol(
             final_rank_reason: None,
             provenance: Vec::new(),
         }),
+        highlight: None,
     }
 }
 
@@ -4393,21 +4414,29 @@ fn trail_truncated_annotation(trail_number: usize, max_nodes: u32) -> String {
     format!("Trail {trail_number} was truncated at max_nodes={max_nodes}.")
 }
 
-fn agent_trail_request(root_id: NodeId, plan: &TrailPlan) -> TrailConfigDto {
+pub(crate) fn agent_trail_request(root_id: NodeId, plan: &TrailPlan) -> TrailConfigDto {
     TrailConfigDto {
         root_id,
         mode: plan.mode,
         target_id: None,
-        depth: plan.depth,
-        direction: plan.direction,
-        caller_scope: plan.caller_scope,
+        depth: Some(plan.depth),
+        direction: Some(plan.direction),
+        caller_scope: Some(plan.caller_scope),
         edge_filter: plan.edge_filter.clone(),
         show_utility_calls: true,
         hide_speculative: true,
         story: false,
         node_filter: plan.node_filter.clone(),
-        max_nodes: plan.max_nodes,
+        hide_trivial_accessors: false,
+        hide_generated: false,
+        entry_reachable_only: false,
+        max_paths: None,
+        edge_weight: Default::default(),
+        max_nodes: Some(plan.max_nodes),
         layout_direction: codestory_contracts::api::LayoutDirection::Horizontal,
+        graph_session: None,
+        timeout_ms: None,
+        filter_preset: None,
     }
 }
 
@@ -5014,7 +5043,7 @@ mod tests {
         assert!(!request.story);
         assert_eq!(request.edge_filter, plan.edge_filter);
         assert_eq!(request.node_filter, plan.node_filter);
-        assert_eq!(request.max_nodes, plan.max_nodes);
+        assert_eq!(request.max_nodes, Some(plan.max_nodes));
     }
 
     fn test_search_hit(node_id: &str, score: f32) -> SearchHit {
@@ -5039,6 +5068,7 @@ mod tests {
             source_excerpt: None,
             verification_targets: Vec::new(),
             score_breakdown: None,
+            highlight: None,
         }
     }
 
@@ -7277,6 +7307,7 @@ mod tests {
             latency_budget_ms: None,
             include_evidence: true,
             hybrid_weights: None,
+            intent: None,
         };
 
         assert_eq!(
@@ -9976,20 +10007,19 @@ mod tests {
                 file_path: None,
                 qualified_name: None,
                 member_access: None,
+                edge_counts: None,
+                badges: Default::default(),
+                centrality: None,
             }
         }
 
         fn edge(id: &str, source: &str, target: &str) -> codestory_contracts::api::GraphEdgeDto {
-            codestory_contracts::api::GraphEdgeDto {
-                id: EdgeId(id.to_string()),
-                source: NodeId(source.to_string()),
-                target: NodeId(target.to_string()),
-                kind: codestory_contracts::api::EdgeKind::CALL,
-                confidence: None,
-                certainty: None,
-                callsite_identity: None,
-                candidate_targets: Vec::new(),
-            }
+            codestory_contracts::api::GraphEdgeDto::new(
+                EdgeId(id.to_string()),
+                NodeId(source.to_string()),
+                NodeId(target.to_string()),
+                codestory_contracts::api::EdgeKind::CALL,
+            )
         }
 
         let question = "Explain public content flow through Payload.";
@@ -10029,7 +10059,14 @@ mod tests {
                 nodes: vec![node("post-page"), node("payload")],
                 edges: vec![edge("edge_1", "post-page", "payload")],
                 truncated: false,
+                truncated_by_timeout: false,
                 omitted_edge_count: 0,
+                pruned_test_node_count: 0,
+                pruned_accessor_node_count: 0,
+                pruned_generated_node_count: 0,
+                omitted_node_count: 0,
+                node_limit: None,
+                paths: Vec::new(),
                 canonical_layout: None,
             },
         });
@@ -10080,20 +10117,19 @@ mod tests {
                 file_path: None,
                 qualified_name: None,
                 member_access: None,
+                edge_counts: None,
+                badges: Default::default(),
+                centrality: None,
             }
         }
 
         fn edge(id: &str, source: &str, target: &str) -> codestory_contracts::api::GraphEdgeDto {
-            codestory_contracts::api::GraphEdgeDto {
-                id: EdgeId(id.to_string()),
-                source: NodeId(source.to_string()),
-                target: NodeId(target.to_string()),
-                kind: codestory_contracts::api::EdgeKind::CALL,
-                confidence: None,
-                certainty: None,
-                callsite_identity: None,
-                candidate_targets: Vec::new(),
-            }
+            codestory_contracts::api::GraphEdgeDto::new(
+                EdgeId(id.to_string()),
+                NodeId(source.to_string()),
+                NodeId(target.to_string()),
+                codestory_contracts::api::EdgeKind::CALL,
+            )
         }
 
         let question = "Explain public content flow through Payload.";
@@ -10116,7 +10152,14 @@ mod tests {
                     edge("edge_2", "session", "adapter"),
                 ],
                 truncated: true,
+                truncated_by_timeout: false,
                 omitted_edge_count: 12,
+                pruned_test_node_count: 0,
+                pruned_accessor_node_count: 0,
+                pruned_generated_node_count: 0,
+                omitted_node_count: 0,
+                node_limit: None,
+                paths: Vec::new(),
                 canonical_layout: None,
             },
         });
@@ -10308,20 +10351,19 @@ mod tests {
                 file_path: None,
                 qualified_name: None,
                 member_access: None,
+                edge_counts: None,
+                badges: Default::default(),
+                centrality: None,
             }
         }
 
         fn edge(id: &str, source: &str, target: &str) -> codestory_contracts::api::GraphEdgeDto {
-            codestory_contracts::api::GraphEdgeDto {
-                id: EdgeId(id.to_string()),
-                source: NodeId(source.to_string()),
-                target: NodeId(target.to_string()),
-                kind: codestory_contracts::api::EdgeKind::CALL,
-                confidence: None,
-                certainty: None,
-                callsite_identity: None,
-                candidate_targets: Vec::new(),
-            }
+            codestory_contracts::api::GraphEdgeDto::new(
+                EdgeId(id.to_string()),
+                NodeId(source.to_string()),
+                NodeId(target.to_string()),
+                codestory_contracts::api::EdgeKind::CALL,
+            )
         }
 
         let mut answer = packet_answer_fixture(
@@ -10345,7 +10387,14 @@ mod tests {
                     edge("edge_3", "dropped_a", "dropped_b"),
                 ],
                 truncated: false,
+                truncated_by_timeout: false,
                 omitted_edge_count: 0,
+                pruned_test_node_count: 0,
+                pruned_accessor_node_count: 0,
+                pruned_generated_node_count: 0,
+                omitted_node_count: 0,
+                node_limit: None,
+                paths: Vec::new(),
                 canonical_layout: None,
             },
         });
@@ -14214,6 +14263,7 @@ mod tests {
                 latency_budget_ms: None,
                 include_evidence: true,
                 hybrid_weights: None,
+                intent: None,
             },
             "inspect this",
             Instant::now(),
@@ -14264,6 +14314,7 @@ mod tests {
             source_excerpt: None,
             verification_targets: Vec::new(),
             score_breakdown: None,
+            highlight: None,
         }];
 
         merge_search_hits(
@@ -14292,6 +14343,7 @@ mod tests {
                     source_excerpt: None,
                     verification_targets: Vec::new(),
                     score_breakdown: None,
+                    highlight: None,
                 },
                 SearchHit {
                     node_id: codestory_contracts::api::NodeId("2".to_string()),
@@ -14316,6 +14368,7 @@ mod tests {
                     source_excerpt: None,
                     verification_targets: Vec::new(),
                     score_breakdown: None,
+                    highlight: None,
                 },
             ],
             10,
@@ -14466,40 +14519,35 @@ mod tests {
             center_id: codestory_contracts::api::NodeId("1".to_string()),
             nodes: Vec::new(),
             edges: vec![
-                codestory_contracts::api::GraphEdgeDto {
-                    id: EdgeId("8".to_string()),
-                    source: codestory_contracts::api::NodeId("2".to_string()),
-                    target: codestory_contracts::api::NodeId("3".to_string()),
-                    kind: codestory_contracts::api::EdgeKind::CALL,
-                    confidence: None,
-                    certainty: None,
-                    callsite_identity: None,
-                    candidate_targets: Vec::new(),
-                },
-                codestory_contracts::api::GraphEdgeDto {
-                    id: EdgeId("3".to_string()),
-                    source: codestory_contracts::api::NodeId("4".to_string()),
-                    target: codestory_contracts::api::NodeId("2".to_string()),
-                    kind: codestory_contracts::api::EdgeKind::CALL,
-                    confidence: None,
-                    certainty: None,
-                    callsite_identity: None,
-                    candidate_targets: Vec::new(),
-                },
-                codestory_contracts::api::GraphEdgeDto {
-                    id: EdgeId("9".to_string()),
-                    source: codestory_contracts::api::NodeId("7".to_string()),
-                    target: codestory_contracts::api::NodeId("8".to_string()),
-                    kind: codestory_contracts::api::EdgeKind::CALL,
-                    confidence: None,
-                    certainty: None,
-                    callsite_identity: None,
-                    candidate_targets: Vec::new(),
-                },
+                codestory_contracts::api::GraphEdgeDto::new(
+                    EdgeId("8".to_string()),
+                    codestory_contracts::api::NodeId("2".to_string()),
+                    codestory_contracts::api::NodeId("3".to_string()),
+                    codestory_contracts::api::EdgeKind::CALL,
+                ),
+                codestory_contracts::api::GraphEdgeDto::new(
+                    EdgeId("3".to_string()),
+                    codestory_contracts::api::NodeId("4".to_string()),
+                    codestory_contracts::api::NodeId("2".to_string()),
+                    codestory_contracts::api::EdgeKind::CALL,
+                ),
+                codestory_contracts::api::GraphEdgeDto::new(
+                    EdgeId("9".to_string()),
+                    codestory_contracts::api::NodeId("7".to_string()),
+                    codestory_contracts::api::NodeId("8".to_string()),
+                    codestory_contracts::api::EdgeKind::CALL,
+                ),
             ],
             truncated: false,
+            truncated_by_timeout: false,
             canonical_layout: None,
             omitted_edge_count: 0,
+            pruned_test_node_count: 0,
+            pruned_accessor_node_count: 0,
+            pruned_generated_node_count: 0,
+            omitted_node_count: 0,
+            node_limit: None,
+            paths: Vec::new(),
         };
 
         let evidence = evidence_edge_ids_for_node(
@@ -308,6 +308,7 @@ mod tests {
                 final_rank_reason: None,
                 provenance: Vec::new(),
             }),
+            highlight: None,
         }
     }
 
@@ -3152,6 +3152,9 @@ mod tests {
             file_path: Some(format!("src/{id}.rs")),
             qualified_name: None,
             member_access: None,
+            edge_counts: None,
+            badges: Default::default(),
+            centrality: None,
         }
     }
 
@@ -3167,14 +3170,14 @@ mod tests {
         confidence: Option<f32>,
     ) -> GraphEdgeDto {
         GraphEdgeDto {
-            id: EdgeId(id.to_string()),
-            source: NodeId(source.to_string()),
-            target: NodeId(target.to_string()),
-            kind: EdgeKind::CALL,
             confidence,
             certainty: certainty.map(str::to_string),
-            callsite_identity: None,
-            candidate_targets: Vec::new(),
+            ..GraphEdgeDto::new(
+                EdgeId(id.to_string()),
+                NodeId(source.to_string()),
+                NodeId(target.to_string()),
+                EdgeKind::CALL,
+            )
         }
     }
 
@@ -3193,7 +3196,14 @@ mod tests {
                     })
                     .collect(),
                 truncated: false,
+                truncated_by_timeout: false,
                 omitted_edge_count: 0,
+                pruned_test_node_count: 0,
+                pruned_accessor_node_count: 0,
+                pruned_generated_node_count: 0,
+                omitted_node_count: 0,
+                node_limit: None,
+                paths: Vec::new(),
                 canonical_layout: None,
             },
         }
@@ -302,6 +302,7 @@ mod golden_tests {
             source_excerpt: None,
             verification_targets: Vec::new(),
             score_breakdown: None,
+            highlight: None,
         };
         let results = vec![("exec_events".to_string(), vec![hit])];
         let diagnostics = vec![PacketSidecarQueryDiagnosticDto {
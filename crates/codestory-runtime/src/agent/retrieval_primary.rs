@@ -2017,6 +2017,7 @@ mod tests {
             source_excerpt: None,
             verification_targets: Vec::new(),
             score_breakdown: Some(score_breakdown_for_candidate(candidate)),
+            highlight: None,
         }
     }
 
@@ -2370,6 +2371,8 @@ mod tests {
                     end_line: 10,
                     end_col: 21,
                 },
+                edge_id: None,
+                notebook_cell: None,
             }])
             .expect("insert occurrence");
         let mut candidate = CandidateHit::with_source(
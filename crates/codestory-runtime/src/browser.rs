@@ -1,14 +1,20 @@
 use codestory_contracts::api::{
     AffectedAnalysisDto, AffectedAnalysisRequest, AgentAnswerDto, AgentAskRequest,
-    AgentHybridWeightsDto, AgentPacketDto, AgentPacketRequestDto, ApiError, GraphResponse,
-    IndexedFilesDto, IndexedFilesRequest, LayoutDirection, ListChildrenSymbolsRequest,
-    ListRootSymbolsRequest, NodeDetailsDto, NodeDetailsRequest, NodeId, NodeKind,
-    NodeOccurrencesRequest, SearchHit, SearchRepoTextMode, SearchRequest, SearchResultsDto,
-    SnippetContextDto, SourceOccurrenceDto, SymbolContextDto, SymbolSummaryDto, TrailCallerScope,
-    TrailConfigDto, TrailContextDto, TrailDirection, TrailMode,
+    AgentHybridWeightsDto, AgentPacketDto, AgentPacketRequestDto, ApiError,
+    AutocompleteSuggestionDto, CodeMarkersDto, CodeMarkersRequest, EntryPointOverviewDto,
+    ExplorerSymbolsDto, ExplorerSymbolsRequest, FileStatsDto, FileStatsRequest, GraphAtDto,
+    GraphAtRequest, GraphExpandRequest, GraphRenderRequest, GraphResponse, GraphSessionDto,
+    GraphSessionUpdateRequest, IndexErrorDto, IndexHistoryRunsDto, IndexedFilesDto,
+    IndexedFilesRequest, LayoutDirection, ListChildrenSymbolsRequest, ListIndexErrorsRequest,
+    ListRootSymbolsRequest, NodeDetailsDto, NodeDetailsRequest, NodeHistoryDto, NodeHistoryRequest,
+    NodeId, NodeKind, NodeOccurrencesRequest, NodePreviewDto, PageDto, ReachableSetDto,
+    ReachableSetRequest, ReviewGraphDto, ReviewGraphRequest, SearchHit, SearchRepoTextMode,
+    SearchRequest, SearchResultsDto, SnippetContextDto, SourceOccurrenceDto,
+    StringLiteralSearchDto, StringLiteralSearchRequest, SymbolContextDto, SymbolSummaryDto,
+    TestLinkDto, TrailConfigDto, TrailContextDto, TrailMode,
 };
 use codestory_contracts::query::{
-    FilterQuery, GraphQueryAst, GraphQueryOperation, SearchQuery as BrowserSearchQuery,
+    FilterQuery, GraphQueryAst, GraphQueryOperation, MatchQuery, SearchQuery as BrowserSearchQuery,
     SymbolQuery, TrailQuery,
 };
 
@@ -19,6 +25,10 @@ use crate::{
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 
+/// Most rows a `match` operation reads from the graph; narrow it with
+/// `from`, `to`, and kinds to see the rest.
+const QUERY_MATCH_MAX_ITEMS: usize = 500;
+
 #[derive(Debug, Clone)]
 pub struct BrowserQueryItem {
     pub node_id: NodeId,
@@ -28,6 +38,17 @@ pub struct BrowserQueryItem {
     pub line: Option<u32>,
     pub depth: Option<u32>,
     pub source: String,
+    /// The node a `match` row's edge leads to.
+    pub target_id: Option<NodeId>,
+    pub target_name: Option<String>,
+}
+
+/// The items a graph query ends with, and whether a `match` read stopped at
+/// its row cap before they were filtered or limited.
+#[derive(Debug, Clone, Default)]
+pub struct BrowserQueryOutput {
+    pub items: Vec<BrowserQueryItem>,
+    pub truncated: bool,
 }
 
 /// Runtime-owned read-only codebase browser boundary.
@@ -149,6 +170,14 @@ impl ReadOnlyBrowserService {
         })
     }
 
+    pub fn autocomplete(
+        &self,
+        prefix: &str,
+        limit: usize,
+    ) -> Result<Vec<AutocompleteSuggestionDto>, ApiError> {
+        self.run_public("search", || self.controller.autocomplete(prefix, limit))
+    }
+
     pub fn resolve_target(
         &self,
         target: TargetSelection,
@@ -191,10 +220,34 @@ impl ReadOnlyBrowserService {
         })
     }
 
+    pub fn export_trail_html(&self, req: TrailConfigDto) -> Result<String, ApiError> {
+        self.run_public("graph", || self.controller.export_trail_html(req.clone()))
+    }
+
+    pub fn render_trail_png(&self, req: GraphRenderRequest) -> Result<Vec<u8>, ApiError> {
+        self.run_public("graph", || self.controller.render_trail_png(req.clone()))
+    }
+
     pub fn indexed_files(&self, req: IndexedFilesRequest) -> Result<IndexedFilesDto, ApiError> {
         self.run_public("graph", || self.controller.indexed_files(req.clone()))
     }
 
+    pub fn list_index_errors(
+        &self,
+        req: ListIndexErrorsRequest,
+    ) -> Result<PageDto<IndexErrorDto>, ApiError> {
+        self.run_public("graph", || self.controller.list_index_errors(req.clone()))
+    }
+
+    pub fn files_by_symbol_density(
+        &self,
+        req: FileStatsRequest,
+    ) -> Result<Vec<FileStatsDto>, ApiError> {
+        self.run_public("graph", || {
+            self.controller.files_by_symbol_density(req.clone())
+        })
+    }
+
     pub fn affected_analysis(
         &self,
         req: AffectedAnalysisRequest,
@@ -233,10 +286,27 @@ impl ReadOnlyBrowserService {
         self.run_public("graph", || self.controller.trail_context(req.clone()))
     }
 
+    pub fn reachable_set(&self, req: ReachableSetRequest) -> Result<ReachableSetDto, ApiError> {
+        self.run_public("graph", || self.controller.reachable_set(req.clone()))
+    }
+
     pub fn references_context(&self, req: TrailConfigDto) -> Result<TrailContextDto, ApiError> {
         self.run_public("graph", || self.controller.trail_context(req.clone()))
     }
 
+    pub fn expand_graph(&self, req: GraphExpandRequest) -> Result<GraphResponse, ApiError> {
+        self.run_public("graph", || self.controller.graph_expand(req.clone()))
+    }
+
+    pub fn update_graph_session(
+        &self,
+        req: GraphSessionUpdateRequest,
+    ) -> Result<GraphSessionDto, ApiError> {
+        self.run_public("graph", || {
+            self.controller.update_graph_session(req.clone())
+        })
+    }
+
     pub fn direct_references_graph(&self, req: TrailConfigDto) -> Result<GraphResponse, ApiError> {
         self.run_public("graph", || {
             self.controller.graph_direct_references(req.clone())
@@ -268,6 +338,58 @@ impl ReadOnlyBrowserService {
         self.run_public("graph", || self.controller.node_details(req.clone()))
     }
 
+    pub fn node_preview(&self, node_id: NodeId) -> Result<NodePreviewDto, ApiError> {
+        self.run_public("graph", || self.controller.node_preview(node_id.clone()))
+    }
+
+    pub fn entry_point_overview(&self) -> Result<EntryPointOverviewDto, ApiError> {
+        self.run_public("graph", || self.controller.entry_point_overview())
+    }
+
+    pub fn review_graph(&self, req: ReviewGraphRequest) -> Result<ReviewGraphDto, ApiError> {
+        self.run_public("review", || self.controller.review_graph(req.clone()))
+    }
+
+    pub fn code_markers(&self, req: CodeMarkersRequest) -> Result<CodeMarkersDto, ApiError> {
+        self.run_public("markers", || self.controller.code_markers(req.clone()))
+    }
+
+    pub fn explorer_symbols(
+        &self,
+        req: ExplorerSymbolsRequest,
+    ) -> Result<ExplorerSymbolsDto, ApiError> {
+        self.run_public("graph", || self.controller.explorer_symbols(req.clone()))
+    }
+
+    pub fn index_history_runs(&self) -> Result<IndexHistoryRunsDto, ApiError> {
+        self.run_public("history", || self.controller.index_history_runs())
+    }
+
+    pub fn node_history(&self, req: NodeHistoryRequest) -> Result<NodeHistoryDto, ApiError> {
+        self.run_public("history", || self.controller.node_history(req.clone()))
+    }
+
+    pub fn graph_at(&self, req: GraphAtRequest) -> Result<GraphAtDto, ApiError> {
+        self.run_public("history", || self.controller.graph_at(req.clone()))
+    }
+
+    pub fn search_string_literals(
+        &self,
+        req: StringLiteralSearchRequest,
+    ) -> Result<StringLiteralSearchDto, ApiError> {
+        self.run_public("literals", || {
+            self.controller.search_string_literals(req.clone())
+        })
+    }
+
+    pub fn tests_covering(&self, node_id: NodeId) -> Result<Vec<TestLinkDto>, ApiError> {
+        self.run_public("graph", || self.controller.tests_covering(node_id.clone()))
+    }
+
+    pub fn code_tested_by(&self, test_id: NodeId) -> Result<Vec<TestLinkDto>, ApiError> {
+        self.run_public("graph", || self.controller.code_tested_by(test_id.clone()))
+    }
+
     pub fn node_occurrences(
         &self,
         req: NodeOccurrencesRequest,
@@ -275,6 +397,15 @@ impl ReadOnlyBrowserService {
         self.run_public("graph", || self.controller.node_occurrences(req.clone()))
     }
 
+    pub fn node_occurrences_page(
+        &self,
+        req: NodeOccurrencesRequest,
+    ) -> Result<PageDto<SourceOccurrenceDto>, ApiError> {
+        self.run_public("graph", || {
+            self.controller.node_occurrences_page(req.clone())
+        })
+    }
+
     pub fn list_root_symbols(
         &self,
         req: ListRootSymbolsRequest,
@@ -291,22 +422,35 @@ impl ReadOnlyBrowserService {
         })
     }
 
-    pub fn query(&self, ast: &GraphQueryAst) -> Result<Vec<BrowserQueryItem>, ApiError> {
+    pub fn list_children_symbols_page(
+        &self,
+        req: ListChildrenSymbolsRequest,
+    ) -> Result<PageDto<SymbolSummaryDto>, ApiError> {
+        self.run_public("graph", || {
+            self.controller.list_children_symbols_page(req.clone())
+        })
+    }
+
+    pub fn query(&self, ast: &GraphQueryAst) -> Result<BrowserQueryOutput, ApiError> {
         self.run_public("graph_assisted", || self.query_once(ast))
     }
 
-    fn query_once(&self, ast: &GraphQueryAst) -> Result<Vec<BrowserQueryItem>, ApiError> {
+    fn query_once(&self, ast: &GraphQueryAst) -> Result<BrowserQueryOutput, ApiError> {
         let mut items = Vec::<BrowserQueryItem>::new();
+        let mut truncated = false;
         for op in &ast.operations {
             match op {
                 GraphQueryOperation::Trail(query) => {
                     items = self.query_trail_items(query)?;
+                    truncated = false;
                 }
                 GraphQueryOperation::Symbol(query) => {
                     items = self.query_symbol_items(query)?;
+                    truncated = false;
                 }
                 GraphQueryOperation::Search(query) => {
                     items = self.query_search_items(query)?;
+                    truncated = false;
                 }
                 GraphQueryOperation::Filter(filter) => {
                     items.retain(|item| query_item_matches_filter(item, filter));
@@ -314,9 +458,12 @@ impl ReadOnlyBrowserService {
                 GraphQueryOperation::Limit(limit) => {
                     items.truncate(limit.count as usize);
                 }
+                GraphQueryOperation::Match(query) => {
+                    (items, truncated) = self.query_match_items(query)?;
+                }
             }
         }
-        Ok(items)
+        Ok(BrowserQueryOutput { items, truncated })
     }
 
     fn query_trail_items(&self, query: &TrailQuery) -> Result<Vec<BrowserQueryItem>, ApiError> {
@@ -325,19 +472,27 @@ impl ReadOnlyBrowserService {
             root_id: target.node_id,
             mode: TrailMode::Neighborhood,
             target_id: None,
-            depth: query.depth.unwrap_or(2),
-            direction: query.direction.unwrap_or(TrailDirection::Both),
-            caller_scope: TrailCallerScope::ProductionOnly,
+            depth: query.depth,
+            direction: query.direction,
+            caller_scope: None,
             edge_filter: Vec::new(),
             show_utility_calls: false,
             hide_speculative: false,
             story: false,
             node_filter: Vec::new(),
-            max_nodes: 120,
+            hide_trivial_accessors: false,
+            hide_generated: false,
+            entry_reachable_only: false,
+            max_paths: None,
+            edge_weight: Default::default(),
+            max_nodes: None,
             layout_direction: LayoutDirection::Horizontal,
+            graph_session: None,
+            timeout_ms: None,
+            filter_preset: None,
         };
-        if request.depth == 0 {
-            request.max_nodes = 200;
+        if request.depth == Some(0) {
+            request.max_nodes = Some(200);
         }
         let context = self.controller.trail_context(request)?;
         Ok(context
@@ -352,6 +507,8 @@ impl ReadOnlyBrowserService {
                 line: None,
                 depth: Some(node.depth),
                 source: "trail".to_string(),
+                target_id: None,
+                target_name: None,
             })
             .collect())
     }
@@ -367,6 +524,8 @@ impl ReadOnlyBrowserService {
             line: context.node.start_line,
             depth: Some(0),
             source: "symbol".to_string(),
+            target_id: None,
+            target_name: None,
         })
         .chain(context.children.into_iter().map(|child| BrowserQueryItem {
             node_id: child.id,
@@ -376,6 +535,8 @@ impl ReadOnlyBrowserService {
             line: None,
             depth: Some(1),
             source: "symbol_child".to_string(),
+            target_id: None,
+            target_name: None,
         }))
         .collect())
     }
@@ -403,10 +564,37 @@ impl ReadOnlyBrowserService {
                 line: hit.line,
                 depth: None,
                 source: "search".to_string(),
+                target_id: None,
+                target_name: None,
             })
             .collect())
     }
 
+    fn query_match_items(
+        &self,
+        query: &MatchQuery,
+    ) -> Result<(Vec<BrowserQueryItem>, bool), ApiError> {
+        let found = self
+            .controller
+            .graph_matches(query, QUERY_MATCH_MAX_ITEMS)?;
+        let items = found
+            .matches
+            .into_iter()
+            .map(|found| BrowserQueryItem {
+                node_id: NodeId::from(found.source),
+                display_name: found.source_name,
+                kind: found.source_kind.into(),
+                file_path: found.source_file,
+                line: found.source_line,
+                depth: None,
+                source: "match".to_string(),
+                target_id: Some(NodeId::from(found.target)),
+                target_name: Some(found.target_name),
+            })
+            .collect();
+        Ok((items, found.truncated))
+    }
+
     fn resolve_query(&self, query: &str) -> Result<SearchHit, ApiError> {
         let mut hits = self
             .controller
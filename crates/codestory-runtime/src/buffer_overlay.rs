@@ -0,0 +1,128 @@
+//! Unsaved editor buffers parsed outside the published index.
+
+use crate::Storage;
+use crate::search::SymbolPrefixIndex;
+use crate::search::engine::search_symbol_list;
+use crate::support::node_display_name;
+use codestory_contracts::api::{self, ApiError, SearchHit, SearchHitOrigin};
+use codestory_contracts::graph::{Node as GraphNode, NodeId, NodeKind};
+use nucleo_matcher::Utf32String;
+use std::path::Path;
+
+/// Symbols parsed from one buffer's unsaved text.
+///
+/// Overlays never touch storage. Callers that honor them hide the published
+/// symbols of the overlaid file and use these instead.
+pub(crate) struct BufferOverlay {
+    nodes: Vec<GraphNode>,
+    names: Vec<(Utf32String, NodeId)>,
+    prefixes: SymbolPrefixIndex,
+}
+
+impl BufferOverlay {
+    pub(crate) fn parse(path: &Path, text: &str) -> Result<Self, ApiError> {
+        let language = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(codestory_indexer::get_language_for_ext)
+            .ok_or_else(|| {
+                ApiError::invalid_argument(format!(
+                    "No parser-backed language for overlay {}",
+                    path.display()
+                ))
+            })?;
+        let result =
+            codestory_indexer::index_file(path, text, &language, None, None).map_err(|error| {
+                ApiError::invalid_argument(format!(
+                    "Failed to parse overlay {}: {error}",
+                    path.display()
+                ))
+            })?;
+        let nodes = result
+            .nodes
+            .into_iter()
+            .filter(|node| !matches!(node.kind, NodeKind::FILE | NodeKind::UNKNOWN))
+            .collect::<Vec<_>>();
+        let names = nodes
+            .iter()
+            .map(|node| (Utf32String::from(node_display_name(node)), node.id))
+            .collect::<Vec<_>>();
+        Ok(Self {
+            prefixes: SymbolPrefixIndex::build(&names),
+            names,
+            nodes,
+        })
+    }
+
+    pub(crate) fn symbol_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Overlay symbols matching `prefix`, scored like published suggestions
+    /// before importance blending.
+    pub(crate) fn autocomplete(&self, prefix: &str) -> Vec<(&GraphNode, f32)> {
+        let prefix = prefix.trim();
+        if prefix.is_empty() {
+            return Vec::new();
+        }
+        self.prefixes
+            .lookup(prefix)
+            .into_iter()
+            .filter_map(|(id, score)| self.node(id).map(|node| (node, score)))
+            .collect()
+    }
+
+    /// Overlay symbols fuzzy-matching `query`, scored like published symbol
+    /// lookups before importance blending.
+    pub(crate) fn search(&self, query: &str) -> Vec<(&GraphNode, f32)> {
+        search_symbol_list(&self.names, query)
+            .into_iter()
+            .filter_map(|(id, score)| self.node(id).map(|node| (node, score)))
+            .collect()
+    }
+
+    fn node(&self, id: NodeId) -> Option<&GraphNode> {
+        self.nodes.iter().find(|node| node.id == id)
+    }
+}
+
+/// Symbol lookup hit for an overlay symbol. It resolves only when the
+/// published index holds a node with the same id.
+pub(crate) fn overlay_search_hit(
+    storage: &Storage,
+    path: &Path,
+    node: &GraphNode,
+    score: f32,
+) -> SearchHit {
+    SearchHit {
+        node_id: api::NodeId::from(node.id),
+        display_name: node_display_name(node),
+        kind: api::NodeKind::from(node.kind),
+        file_path: Some(path.to_string_lossy().into_owned()),
+        line: node.start_line,
+        score,
+        origin: SearchHitOrigin::IndexedSymbol,
+        match_quality: None,
+        resolvable: storage
+            .get_node(node.id)
+            .is_ok_and(|stored| stored.is_some()),
+        evidence_tier: None,
+        evidence_producer: None,
+        resolution_status: None,
+        loss_reason: None,
+        coverage_role: None,
+        eligible_for_sufficiency: None,
+        source_excerpt: None,
+        verification_targets: Vec::new(),
+        score_breakdown: None,
+        highlight: None,
+    }
+}
+
+/// Whether the published `path` names one of the `overlaid` files.
+pub(crate) fn is_overlaid(overlaid: &[String], path: &str) -> bool {
+    overlaid.iter().any(|overlaid| {
+        overlaid == path
+            || codestory_workspace::same_workspace_path(Path::new(overlaid), Path::new(path))
+    })
+}
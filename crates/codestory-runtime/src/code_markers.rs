@@ -0,0 +1,297 @@
+use crate::{AppController, current_epoch_ms, runtime_relative_path};
+use codestory_contracts::api::{ApiError, CodeMarkerDto, CodeMarkersDto, CodeMarkersRequest};
+use codestory_indexer::CODE_MARKER_TAGS;
+use codestory_store::{CodeMarkerFilter, CodeMarkerRecord};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+const CODE_MARKERS_DEFAULT_LIMIT: u32 = 200;
+const CODE_MARKERS_MAX_LIMIT: u32 = 2_000;
+/// Stored markers an age-filtered listing considers before blame narrows
+/// them down.
+const CODE_MARKERS_MAX_AGE_CANDIDATES: usize = 5_000;
+/// Files one age-filtered listing runs `git blame` on; files blamed by an
+/// earlier listing and unchanged since are read from the cache instead.
+const CODE_MARKERS_MAX_BLAMED_FILES: usize = 200;
+/// Files whose blame the controller keeps before it starts over.
+const MARKER_BLAME_CACHE_MAX_FILES: usize = 5_000;
+const MS_PER_DAY: i64 = 24 * 60 * 60 * 1000;
+
+impl AppController {
+    /// Indexed `TODO`, `FIXME`, and `HACK` comments, ordered by file and
+    /// line.
+    ///
+    /// An age bound blames each candidate file once, reusing the blame of
+    /// files unchanged since an earlier listing; markers whose age is unknown,
+    /// such as those outside a git checkout, are then left out.
+    pub fn code_markers(&self, req: CodeMarkersRequest) -> Result<CodeMarkersDto, ApiError> {
+        let tag = match req.tag.as_deref().map(str::trim) {
+            Some(tag) if !tag.is_empty() => {
+                let tag = tag.to_ascii_uppercase();
+                if !CODE_MARKER_TAGS.contains(&tag.as_str()) {
+                    return Err(ApiError::invalid_argument(format!(
+                        "Unknown marker tag `{tag}`; expected one of {}.",
+                        CODE_MARKER_TAGS.join(", ")
+                    )));
+                }
+                Some(tag)
+            }
+            _ => None,
+        };
+        if let (Some(min), Some(max)) = (req.min_age_days, req.max_age_days)
+            && min > max
+        {
+            return Err(ApiError::invalid_argument(
+                "min_age_days must not exceed max_age_days.",
+            ));
+        }
+        let limit = req
+            .limit
+            .unwrap_or(CODE_MARKERS_DEFAULT_LIMIT)
+            .clamp(1, CODE_MARKERS_MAX_LIMIT) as usize;
+        let filter = CodeMarkerFilter {
+            tag,
+            path_contains: req.file.filter(|file| !file.trim().is_empty()),
+        };
+        let age_filtered = req.min_age_days.is_some() || req.max_age_days.is_some();
+        let candidate_limit = if age_filtered {
+            CODE_MARKERS_MAX_AGE_CANDIDATES
+        } else {
+            limit
+        };
+
+        let root = self.require_project_root()?;
+        let records = {
+            let storage = self.open_storage_read_only()?;
+            storage
+                .code_markers(&filter, candidate_limit + 1)
+                .map_err(|e| ApiError::internal(format!("Failed to load code markers: {e}")))?
+        };
+        let mut truncated = records.len() > candidate_limit;
+        let mut markers = Vec::new();
+        let mut blame = MarkerBlame::new(&root, &self.marker_blame_cache);
+        let now_ms = current_epoch_ms();
+        for record in records.into_iter().take(candidate_limit) {
+            let authored_at_epoch_ms = if age_filtered {
+                blame.author_time_ms(&record)
+            } else {
+                None
+            };
+            let age_days = authored_at_epoch_ms
+                .map(|authored| (now_ms.saturating_sub(authored).max(0) / MS_PER_DAY) as u32);
+            if age_filtered {
+                let Some(age) = age_days else {
+                    continue;
+                };
+                if req.min_age_days.is_some_and(|min| age < min)
+                    || req.max_age_days.is_some_and(|max| age > max)
+                {
+                    continue;
+                }
+            }
+            if markers.len() == limit {
+                truncated = true;
+                break;
+            }
+            markers.push(CodeMarkerDto {
+                file_path: runtime_relative_path(&root, &record.path),
+                line: record.marker.line,
+                column: record.marker.column,
+                tag: record.marker.tag,
+                text: record.marker.text,
+                authored_at_epoch_ms,
+                age_days,
+            });
+        }
+        Ok(CodeMarkersDto { markers, truncated })
+    }
+}
+
+/// Per-line author times of a file, keyed by path and kept while the file's
+/// modification time and length are unchanged. `None` records a failed
+/// blame, such as for a file outside a git checkout.
+#[derive(Debug, Default)]
+pub(crate) struct MarkerBlameCache {
+    files: HashMap<PathBuf, (FileStamp, Option<Arc<HashMap<u32, i64>>>)>,
+}
+
+type FileStamp = (Option<SystemTime>, u64);
+
+fn file_stamp(path: &Path) -> Option<FileStamp> {
+    let metadata = std::fs::metadata(path).ok()?;
+    Some((metadata.modified().ok(), metadata.len()))
+}
+
+/// Per-line author times from `git blame`, read once per file.
+struct MarkerBlame<'a> {
+    root: &'a Path,
+    cache: &'a parking_lot::Mutex<MarkerBlameCache>,
+    files: HashMap<i64, Option<Arc<HashMap<u32, i64>>>>,
+    blamed: usize,
+}
+
+impl<'a> MarkerBlame<'a> {
+    fn new(root: &'a Path, cache: &'a parking_lot::Mutex<MarkerBlameCache>) -> Self {
+        Self {
+            root,
+            cache,
+            files: HashMap::new(),
+            blamed: 0,
+        }
+    }
+
+    fn author_time_ms(&mut self, record: &CodeMarkerRecord) -> Option<i64> {
+        if !self.files.contains_key(&record.file_id) {
+            let lines = self.file_author_times(&record.path);
+            self.files.insert(record.file_id, lines);
+        }
+        self.files
+            .get(&record.file_id)?
+            .as_ref()?
+            .get(&record.marker.line)
+            .copied()
+    }
+
+    fn file_author_times(&mut self, path: &Path) -> Option<Arc<HashMap<u32, i64>>> {
+        let stamp = file_stamp(path)?;
+        if let Some((cached_stamp, lines)) = self.cache.lock().files.get(path)
+            && *cached_stamp == stamp
+        {
+            return lines.clone();
+        }
+        if self.blamed >= CODE_MARKERS_MAX_BLAMED_FILES {
+            return None;
+        }
+        self.blamed += 1;
+        let lines = blame_author_times(self.root, path).map(Arc::new);
+        let mut cache = self.cache.lock();
+        if cache.files.len() >= MARKER_BLAME_CACHE_MAX_FILES {
+            cache.files.clear();
+        }
+        cache
+            .files
+            .insert(path.to_path_buf(), (stamp, lines.clone()));
+        lines
+    }
+}
+
+fn blame_author_times(root: &Path, path: &Path) -> Option<HashMap<u32, i64>> {
+    let relative = path.strip_prefix(root).unwrap_or(path);
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(root)
+        .arg("blame")
+        .arg("--line-porcelain")
+        .arg("--")
+        .arg(relative)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        tracing::debug!(
+            "git blame failed for {}: {}",
+            relative.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+        return None;
+    }
+    Some(parse_blame_author_times(&String::from_utf8_lossy(
+        &output.stdout,
+    )))
+}
+
+/// Author time in epoch milliseconds for each final line of
+/// `git blame --line-porcelain` output.
+fn parse_blame_author_times(porcelain: &str) -> HashMap<u32, i64> {
+    let mut times = HashMap::new();
+    let mut line = None::<u32>;
+    for text in porcelain.lines() {
+        if let Some(seconds) = text.strip_prefix("author-time ") {
+            if let (Some(line), Ok(seconds)) = (line, seconds.trim().parse::<i64>()) {
+                times.insert(line, seconds.saturating_mul(1000));
+            }
+            continue;
+        }
+        let mut fields = text.split(' ');
+        let is_header = fields
+            .next()
+            .is_some_and(|sha| sha.len() == 40 && sha.bytes().all(|b| b.is_ascii_hexdigit()));
+        if is_header {
+            line = fields.nth(1).and_then(|final_line| final_line.parse().ok());
+        }
+    }
+    times
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blame_author_times_map_final_lines() {
+        let porcelain = "\
+3f1c2b9a0d4e5f6a7b8c9d0e1f2a3b4c5d6e7f80 1 1 2
+author Ana
+author-time 1700000000
+author-tz +0000
+filename src/lib.rs
+\tfn load() {
+3f1c2b9a0d4e5f6a7b8c9d0e1f2a3b4c5d6e7f80 2 2
+author Ana
+author-time 1700000000
+filename src/lib.rs
+\t    // TODO: retry
+0000000000000000000000000000000000000000 5 3 1
+author Not Committed Yet
+author-time 1800000000
+filename src/lib.rs
+\t}
+";
+        let times = parse_blame_author_times(porcelain);
+        assert_eq!(times.len(), 3);
+        assert_eq!(times[&1], 1_700_000_000_000);
+        assert_eq!(times[&2], 1_700_000_000_000);
+        assert_eq!(times[&3], 1_800_000_000_000);
+    }
+
+    #[test]
+    fn blame_is_reused_until_the_file_changes() {
+        let root = tempfile::tempdir().expect("root");
+        let path = root.path().join("lib.rs");
+        std::fs::write(&path, "// TODO: retry\n").expect("write source");
+        let cache = parking_lot::Mutex::new(MarkerBlameCache::default());
+        cache.lock().files.insert(
+            path.clone(),
+            (
+                file_stamp(&path).expect("stamp"),
+                Some(Arc::new(HashMap::from([(1, 1_700_000_000_000)]))),
+            ),
+        );
+        let record = CodeMarkerRecord {
+            file_id: 1,
+            path: path.clone(),
+            marker: codestory_store::CodeMarker {
+                line: 1,
+                column: 4,
+                tag: "TODO".to_string(),
+                text: "retry".to_string(),
+            },
+        };
+
+        let mut blame = MarkerBlame::new(root.path(), &cache);
+        assert_eq!(blame.author_time_ms(&record), Some(1_700_000_000_000));
+        assert_eq!(blame.blamed, 0, "a cached file is not blamed again");
+
+        std::fs::write(&path, "// TODO: retry later\n").expect("rewrite source");
+        let mut blame = MarkerBlame::new(root.path(), &cache);
+        assert_eq!(
+            blame.author_time_ms(&record),
+            None,
+            "a changed file outside git is blamed again and has no age"
+        );
+        assert_eq!(blame.blamed, 1);
+        assert!(cache.lock().files[&path].1.is_none());
+    }
+}
@@ -0,0 +1,63 @@
+use crate::AppController;
+use codestory_contracts::api::{ApiError, MergeNodesRequest, NodeAliasDto, NodeId, NodeKind};
+use codestory_store::NodeAlias;
+
+fn node_alias_dto(alias: NodeAlias) -> NodeAliasDto {
+    NodeAliasDto {
+        kind: NodeKind::from(alias.kind),
+        name: alias.name,
+        primary_kind: NodeKind::from(alias.primary_kind),
+        primary_name: alias.primary_name,
+        node_id: alias.node_id.map(NodeId::from),
+        primary_node_id: alias.primary_node_id.map(NodeId::from),
+    }
+}
+
+impl AppController {
+    /// Every recorded node merge, bound to the nodes that match it in the
+    /// current index.
+    pub fn list_node_aliases(&self) -> Result<Vec<NodeAliasDto>, ApiError> {
+        let storage = self.open_storage_read_only()?;
+        let aliases = storage
+            .node_aliases()
+            .map_err(|e| ApiError::internal(format!("Failed to load node aliases: {e}")))?;
+        Ok(aliases.into_iter().map(node_alias_dto).collect())
+    }
+
+    /// Merge duplicate nodes into a primary, so the primary's edges and
+    /// occurrences include theirs. Returns every recorded merge.
+    pub fn merge_nodes(&self, req: MergeNodesRequest) -> Result<Vec<NodeAliasDto>, ApiError> {
+        if req.duplicate_ids.is_empty() {
+            return Err(ApiError::invalid_argument(
+                "Merging nodes requires at least one duplicate.",
+            ));
+        }
+        let storage = self.open_storage()?;
+        let mut ids = Vec::with_capacity(req.duplicate_ids.len() + 1);
+        for id in std::iter::once(&req.primary_id).chain(&req.duplicate_ids) {
+            let node_id = id.to_core()?;
+            storage
+                .get_node(node_id)
+                .map_err(|e| ApiError::internal(format!("Failed to load node: {e}")))?
+                .ok_or_else(|| ApiError::not_found(format!("Node not found: {}", id.0)))?;
+            ids.push(node_id);
+        }
+        storage
+            .merge_nodes(ids[0], &ids[1..])
+            .map_err(|e| ApiError::internal(format!("Failed to merge nodes: {e}")))?;
+        let aliases = storage
+            .node_aliases()
+            .map_err(|e| ApiError::internal(format!("Failed to load node aliases: {e}")))?;
+        Ok(aliases.into_iter().map(node_alias_dto).collect())
+    }
+
+    /// Undo the merge of one node into its primary. Returns whether it had
+    /// been merged.
+    pub fn unmerge_node(&self, id: NodeId) -> Result<bool, ApiError> {
+        let node_id = id.to_core()?;
+        let storage = self.open_storage()?;
+        storage
+            .unmerge_node(node_id)
+            .map_err(|e| ApiError::internal(format!("Failed to unmerge node: {e}")))
+    }
+}
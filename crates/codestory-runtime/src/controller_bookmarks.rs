@@ -1,9 +1,14 @@
-use crate::AppController;
-use crate::support::node_display_name;
+use crate::support::{clamp_usize_to_u32, node_display_name};
+use crate::{AppController, Storage};
 use codestory_contracts::api::{
-    ApiError, BookmarkCategoryDto, BookmarkDto, CreateBookmarkCategoryRequest,
-    CreateBookmarkRequest, NodeId, NodeKind, UpdateBookmarkCategoryRequest, UpdateBookmarkRequest,
+    ApiError, BookmarkCategoryDto, BookmarkDto, BookmarkHealthDto, BookmarkHealthEntryDto,
+    BookmarkHealthStatusDto, CreateBookmarkCategoryRequest, CreateBookmarkRequest,
+    ENTRY_POINT_BOOKMARK_CATEGORY, ListBookmarksRequest, NodeId, NodeKind, PageDto,
+    UpdateBookmarkCategoryRequest, UpdateBookmarkRequest,
 };
+use codestory_contracts::graph::{Bookmark, Node};
+use codestory_store::{BookmarkHealth, BookmarkHealthStatus};
+use std::collections::HashSet;
 
 fn parse_db_id(raw: &str, field_name: &str) -> Result<i64, ApiError> {
     raw.trim()
@@ -11,6 +16,59 @@ fn parse_db_id(raw: &str, field_name: &str) -> Result<i64, ApiError> {
         .map_err(|_| ApiError::invalid_argument(format!("Invalid {field_name}: {raw}")))
 }
 
+fn bookmark_health_dto(health: Vec<BookmarkHealth>, repaired: usize) -> BookmarkHealthDto {
+    let count = |status| health.iter().filter(|entry| entry.status == status).count();
+    BookmarkHealthDto {
+        valid_count: clamp_usize_to_u32(count(BookmarkHealthStatus::Valid)),
+        resolvable_count: clamp_usize_to_u32(count(BookmarkHealthStatus::Resolvable)),
+        orphaned_count: clamp_usize_to_u32(count(BookmarkHealthStatus::Orphaned)),
+        repaired_count: clamp_usize_to_u32(repaired),
+        bookmarks: health
+            .into_iter()
+            .map(|entry| BookmarkHealthEntryDto {
+                bookmark_id: entry.bookmark_id.to_string(),
+                category_id: entry.category_id.to_string(),
+                node_id: NodeId::from(entry.stored_node_id),
+                resolved_node_id: entry.resolved_node_id.map(NodeId::from),
+                symbol_name: entry.symbol_name,
+                status: match entry.status {
+                    BookmarkHealthStatus::Valid => BookmarkHealthStatusDto::Valid,
+                    BookmarkHealthStatus::Resolvable => BookmarkHealthStatusDto::Resolvable,
+                    BookmarkHealthStatus::Orphaned => BookmarkHealthStatusDto::Orphaned,
+                },
+            })
+            .collect(),
+    }
+}
+
+/// Live nodes bookmarked in the entry point category, in bookmark order.
+pub(crate) fn entry_point_bookmark_nodes(storage: &Storage) -> Result<Vec<Node>, ApiError> {
+    let categories = storage
+        .get_bookmark_categories()
+        .map_err(|e| ApiError::internal(format!("Failed to load bookmark categories: {e}")))?;
+    let mut seen = HashSet::new();
+    let mut nodes = Vec::new();
+    for category in categories.iter().filter(|category| {
+        category
+            .name
+            .eq_ignore_ascii_case(ENTRY_POINT_BOOKMARK_CATEGORY)
+    }) {
+        let bookmarks = storage
+            .get_bookmarks(Some(category.id))
+            .map_err(|e| ApiError::internal(format!("Failed to load bookmarks: {e}")))?;
+        for bookmark in bookmarks {
+            if !seen.insert(bookmark.node_id) {
+                continue;
+            }
+            let node = storage
+                .get_node(bookmark.node_id)
+                .map_err(|e| ApiError::internal(format!("Failed to load bookmark node: {e}")))?;
+            nodes.extend(node);
+        }
+    }
+    Ok(nodes)
+}
+
 impl AppController {
     pub fn list_bookmark_categories(&self) -> Result<Vec<BookmarkCategoryDto>, ApiError> {
         let storage = self.open_storage_read_only()?;
@@ -86,7 +144,13 @@ impl AppController {
         let bookmarks = storage
             .get_bookmarks(category_id)
             .map_err(|e| ApiError::internal(format!("Failed to load bookmarks: {e}")))?;
+        Self::bookmark_dtos(&storage, bookmarks)
+    }
 
+    fn bookmark_dtos(
+        storage: &Storage,
+        bookmarks: Vec<Bookmark>,
+    ) -> Result<Vec<BookmarkDto>, ApiError> {
         let mut response = Vec::with_capacity(bookmarks.len());
         for bookmark in bookmarks {
             let node = storage
@@ -96,7 +160,7 @@ impl AppController {
                 Some(node) => (
                     node_display_name(&node),
                     NodeKind::from(node.kind),
-                    Self::file_path_for_node(&storage, &node)?,
+                    Self::file_path_for_node(storage, &node)?,
                 ),
                 None => (bookmark.node_id.0.to_string(), NodeKind::UNKNOWN, None),
             };
@@ -113,6 +177,27 @@ impl AppController {
         Ok(response)
     }
 
+    pub fn list_bookmarks_page(
+        &self,
+        req: ListBookmarksRequest,
+    ) -> Result<PageDto<BookmarkDto>, ApiError> {
+        let category_id = req
+            .category_id
+            .as_deref()
+            .map(|raw| parse_db_id(raw, "category_id"))
+            .transpose()?;
+        let storage = self.open_storage_read_only()?;
+        let total = storage
+            .get_bookmark_count(category_id)
+            .map_err(|e| ApiError::internal(format!("Failed to count bookmarks: {e}")))?;
+        PageDto::fetch(total, req.offset, req.limit, |offset, limit| {
+            let bookmarks = storage
+                .get_bookmarks_page(category_id, offset, limit)
+                .map_err(|e| ApiError::internal(format!("Failed to load bookmarks: {e}")))?;
+            Self::bookmark_dtos(&storage, bookmarks)
+        })
+    }
+
     pub fn create_bookmark(&self, req: CreateBookmarkRequest) -> Result<BookmarkDto, ApiError> {
         let node_id = req.node_id.to_core()?;
         let category_id = parse_db_id(&req.category_id, "category_id")?;
@@ -188,4 +273,25 @@ impl AppController {
             .map_err(|e| ApiError::internal(format!("Failed to delete bookmark: {e}")))?;
         Ok(())
     }
+
+    /// Classify bookmarks as valid, re-resolvable by symbol name, or orphaned.
+    pub fn bookmarks_health(&self) -> Result<BookmarkHealthDto, ApiError> {
+        let storage = self.open_storage_read_only()?;
+        let health = storage
+            .bookmark_health()
+            .map_err(|e| ApiError::internal(format!("Failed to check bookmarks: {e}")))?;
+        Ok(bookmark_health_dto(health, 0))
+    }
+
+    /// Rebind re-resolvable bookmarks and report their health afterwards.
+    pub fn repair_bookmarks(&self) -> Result<BookmarkHealthDto, ApiError> {
+        let storage = self.open_storage()?;
+        let repaired = storage
+            .repair_bookmarks()
+            .map_err(|e| ApiError::internal(format!("Failed to repair bookmarks: {e}")))?;
+        let health = storage
+            .bookmark_health()
+            .map_err(|e| ApiError::internal(format!("Failed to check bookmarks: {e}")))?;
+        Ok(bookmark_health_dto(health, repaired))
+    }
 }
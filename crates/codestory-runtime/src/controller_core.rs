@@ -1,6 +1,7 @@
 use crate::browser::ReadOnlyBrowserService;
 use crate::index_freshness::{
-    index_freshness_from_storage_with_policy, open_existing_storage_for_read, open_storage_for_read,
+    index_freshness_from_storage_with_policy, open_existing_storage_for_read,
+    open_storage_for_read, storage_open_error,
 };
 use crate::search_publication::{
     load_persisted_search_state_for_runtime, retrieval_state_from_storage_for_runtime,
@@ -56,15 +57,24 @@ impl AppController {
             state: Arc::new(Mutex::new(AppState {
                 project_root: None,
                 storage_path: None,
-                node_names: HashMap::new(),
+                node_names: Arc::default(),
                 search_engine: None,
                 search_publication: None,
                 is_indexing: false,
+                indexing_queue: Default::default(),
+                buffer_overlays: HashMap::new(),
+                open_files: Vec::new(),
                 index_freshness_cache: None,
+                snapshot_retention: 0,
+                indexing_max_threads: None,
+                canonical_layouts: Default::default(),
+                graph_sessions: Default::default(),
                 #[cfg(test)]
                 last_hybrid_instrumentation: None,
             })),
             sidecar_query_cache: Arc::new(Mutex::new(SidecarQueryCacheState::new())),
+            search_state_load: Arc::new(Mutex::new(())),
+            marker_blame_cache: Arc::default(),
             events_tx,
             events_rx,
             runtime_config: Arc::new(config),
@@ -132,8 +142,7 @@ impl AppController {
 
     pub(crate) fn open_storage(&self) -> Result<Storage, ApiError> {
         let storage_path = self.require_storage_path()?;
-        Storage::open(&storage_path)
-            .map_err(|e| ApiError::internal(format!("Failed to open storage: {e}")))
+        Storage::open(&storage_path).map_err(storage_open_error)
     }
 
     pub(crate) fn open_storage_read_only(&self) -> Result<ReadStorage, ApiError> {
@@ -264,7 +273,7 @@ impl AppController {
 
     pub(crate) fn clear_search_state(&self) {
         let mut s = self.state.lock();
-        s.node_names.clear();
+        s.node_names = Arc::default();
         clear_search_engine(&mut s);
         self.sidecar_query_cache.lock().clear();
     }
@@ -303,6 +312,7 @@ impl AppController {
                 "the pinned core publication is no longer the current lexical search generation",
             ));
         }
+        let _load_guard = self.search_state_load.lock();
         {
             let s = self.state.lock();
             if s.search_engine.is_some() && s.search_publication == current_publication {
@@ -333,8 +343,14 @@ impl AppController {
         }
 
         let mut s = self.state.lock();
+        if s.storage_path.as_deref() != Some(storage_path.as_path()) {
+            return Err(ApiError::new(
+                "cache_busy",
+                "the project changed while its search state was loading",
+            ));
+        }
         if s.search_engine.is_none() || s.search_publication != loaded.publication {
-            s.node_names = loaded.node_names;
+            s.node_names = Arc::new(loaded.node_names);
             publish_search_engine(&mut s, loaded.engine, loaded.publication);
         }
 
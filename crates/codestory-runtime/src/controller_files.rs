@@ -1,17 +1,27 @@
+use crate::buffer_overlay::BufferOverlay;
 use crate::snippets::{
     BoundedSnippet, BoundedSnippetRangeOptions, bounded_markdown_snippet_from_path,
     bounded_markdown_snippet_range_from_path,
 };
-use crate::support::clamp_i64_to_u32;
+use crate::support::{clamp_i64_to_u32, clamp_usize_to_u32};
 use crate::system_actions::status_response;
-use crate::{AppController, path_resolution, system_actions};
+use crate::{AppController, Storage, path_resolution, system_actions};
 use codestory_contracts::api::{
-    ApiError, OpenContainingFolderRequest, OpenDefinitionRequest, ReadFileTextRequest,
-    ReadFileTextResponse, SystemActionResponse, WriteFileResponse, WriteFileTextRequest,
+    ApiError, NodeDefinitionDto, OpenContainingFolderRequest, OpenDefinitionRequest,
+    ReadFileTextRequest, ReadFileTextResponse, SystemActionResponse, WriteFileResponse,
+    WriteFileTextRequest,
 };
 use std::io;
 use std::path::{Path, PathBuf};
 
+/// A file location `open_definition` opens.
+#[derive(Debug, PartialEq, Eq)]
+struct DefinitionTarget {
+    path: PathBuf,
+    line: Option<u32>,
+    col: Option<u32>,
+}
+
 impl AppController {
     pub(crate) fn resolve_project_file_path(
         &self,
@@ -34,10 +44,25 @@ impl AppController {
         system_actions::launch_definition_in_ide(path, line, col)
     }
 
+    /// Open the definition of `req.node_id` in an IDE. A node with several
+    /// definitions opens its primary location unless `req.definition_index`
+    /// picks one, and the response lists all of them.
     pub fn open_definition(
         &self,
         req: OpenDefinitionRequest,
     ) -> Result<SystemActionResponse, ApiError> {
+        let (target, definitions) = self.definition_target(&req)?;
+        let mut response = self.launch_definition_in_ide(&target.path, target.line, target.col)?;
+        response.definitions = definitions;
+        Ok(response)
+    }
+
+    /// Where `open_definition` opens `req`, with the node's definitions when
+    /// it has several.
+    fn definition_target(
+        &self,
+        req: &OpenDefinitionRequest,
+    ) -> Result<(DefinitionTarget, Vec<NodeDefinitionDto>), ApiError> {
         let node_id = req.node_id.to_core()?;
         let storage = self.open_storage_read_only()?;
         let node = storage
@@ -45,6 +70,27 @@ impl AppController {
             .map_err(|e| ApiError::internal(format!("Failed to load node: {e}")))?
             .ok_or_else(|| ApiError::not_found(format!("Node not found: {}", req.node_id.0)))?;
 
+        let definitions = self.node_definitions(&storage, node.id)?;
+        let count = definitions.len().max(1);
+        match req.definition_index {
+            Some(index) if index as usize >= count => {
+                return Err(ApiError::invalid_argument(format!(
+                    "Definition index {index} is out of range; the node has {count} definition{}.",
+                    if count == 1 { "" } else { "s" }
+                )));
+            }
+            Some(index) if !definitions.is_empty() => {
+                let definition = &definitions[index as usize];
+                let target = DefinitionTarget {
+                    path: self.resolve_project_file_path(&definition.file_path, false)?,
+                    line: Some(definition.start_line),
+                    col: Some(definition.start_col),
+                };
+                return Ok((target, definitions));
+            }
+            _ => {}
+        }
+
         let raw_path = if node.kind == codestory_contracts::graph::NodeKind::FILE {
             Some(node.serialized_name.clone())
         } else {
@@ -52,8 +98,36 @@ impl AppController {
         }
         .ok_or_else(|| ApiError::invalid_argument("Node has no file path for definition open."))?;
 
-        let resolved = self.resolve_project_file_path(&raw_path, false)?;
-        self.launch_definition_in_ide(&resolved, node.start_line, node.start_col)
+        let target = DefinitionTarget {
+            path: self.resolve_project_file_path(&raw_path, false)?,
+            line: node.start_line,
+            col: node.start_col,
+        };
+        Ok((target, definitions))
+    }
+
+    /// Each definition of `node_id` when it has more than one, else nothing.
+    pub(crate) fn node_definitions(
+        &self,
+        storage: &Storage,
+        node_id: codestory_contracts::graph::NodeId,
+    ) -> Result<Vec<NodeDefinitionDto>, ApiError> {
+        let definitions = storage
+            .get_node_definitions(node_id)
+            .map_err(|e| ApiError::internal(format!("Failed to load node definitions: {e}")))?;
+        if definitions.len() < 2 {
+            return Ok(Vec::new());
+        }
+        Ok(definitions
+            .into_iter()
+            .map(|definition| NodeDefinitionDto {
+                file_path: definition.file_path,
+                start_line: definition.start_line,
+                start_col: definition.start_col,
+                end_line: definition.end_line,
+                end_col: definition.end_col,
+            })
+            .collect())
     }
 
     pub fn open_containing_folder(
@@ -134,6 +208,52 @@ impl AppController {
         Ok((candidate.to_string_lossy().to_string(), snippet))
     }
 
+    /// Parse unsaved editor text for `path` and return how many symbols it
+    /// holds. Until [`Self::clear_overlay`] or a project switch, symbol
+    /// autocomplete and indexed symbol lookup show the buffer's symbols in
+    /// place of the published ones for that file. Sidecar-backed search,
+    /// trails, and graph queries keep reading the published index.
+    pub fn set_overlay(&self, path: &str, text: &str) -> Result<u32, ApiError> {
+        let candidate = self.resolve_project_file_path(path, true)?;
+        let overlay = BufferOverlay::parse(&candidate, text)?;
+        let symbol_count = clamp_usize_to_u32(overlay.symbol_count());
+        self.state.lock().buffer_overlays.insert(candidate, overlay);
+        Ok(symbol_count)
+    }
+
+    /// Drop the overlay for `path`, returning whether one was set.
+    pub fn clear_overlay(&self, path: &str) -> Result<bool, ApiError> {
+        let candidate = self.resolve_project_file_path(path, true)?;
+        Ok(self
+            .state
+            .lock()
+            .buffer_overlays
+            .remove(&candidate)
+            .is_some())
+    }
+
+    /// Replace the list of files open in the client. Later index runs parse
+    /// these files, in this order, before any other, so their graphs and
+    /// progress events come first. Paths outside the project are rejected.
+    pub fn set_open_files(&self, paths: &[String]) -> Result<(), ApiError> {
+        let root = self
+            .require_project_root()?
+            .canonicalize()
+            .map_err(|e| ApiError::internal(format!("Failed to resolve project root: {e}")))?;
+        let mut open_files = Vec::with_capacity(paths.len());
+        for path in paths {
+            let candidate = self.resolve_project_file_path(path, true)?;
+            let relative = candidate.strip_prefix(&root).map_err(|_| {
+                ApiError::invalid_argument(format!("File is outside the project root: {path}"))
+            })?;
+            if !open_files.iter().any(|open: &PathBuf| open == relative) {
+                open_files.push(relative.to_path_buf());
+            }
+        }
+        self.state.lock().open_files = open_files;
+        Ok(())
+    }
+
     pub fn write_file_text(
         &self,
         req: WriteFileTextRequest,
@@ -148,3 +268,114 @@ impl AppController {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codestory_contracts::api::NodeId;
+    use codestory_contracts::graph::{
+        Node, NodeId as CoreNodeId, NodeKind, Occurrence, OccurrenceKind, SourceLocation,
+    };
+    use codestory_store::Store;
+    use tempfile::tempdir;
+
+    #[test]
+    fn definition_target_picks_among_several_definitions() {
+        let project = tempdir().expect("project");
+        std::fs::create_dir_all(project.path().join("src")).expect("create src");
+        for file in ["src/Widget.cs", "src/Widget.Layout.cs"] {
+            std::fs::write(project.path().join(file), "partial class Widget {}\n")
+                .expect("write source");
+        }
+        let storage_path = project.path().join("codestory.db");
+        {
+            let mut store = Store::open(&storage_path).expect("create store");
+            let file = |id, path: &str| Node {
+                id: CoreNodeId(id),
+                kind: NodeKind::FILE,
+                serialized_name: path.to_string(),
+                ..Default::default()
+            };
+            store
+                .insert_nodes_batch(&[
+                    file(1, "src/Widget.Layout.cs"),
+                    file(2, "src/Widget.cs"),
+                    Node {
+                        id: CoreNodeId(3),
+                        kind: NodeKind::CLASS,
+                        serialized_name: "Widget".to_string(),
+                        file_node_id: Some(CoreNodeId(2)),
+                        start_line: Some(5),
+                        start_col: Some(1),
+                        ..Default::default()
+                    },
+                ])
+                .expect("insert nodes");
+            let definition = |file_id, line| Occurrence {
+                element_id: 3,
+                kind: OccurrenceKind::DEFINITION,
+                location: SourceLocation {
+                    file_node_id: CoreNodeId(file_id),
+                    start_line: line,
+                    start_col: 1,
+                    end_line: line + 10,
+                    end_col: 2,
+                },
+                edge_id: None,
+                notebook_cell: None,
+            };
+            store
+                .insert_occurrences_batch(&[definition(2, 5), definition(1, 3)])
+                .expect("insert occurrences");
+        }
+        let controller = AppController::new();
+        {
+            let mut state = controller.state.lock();
+            state.project_root = Some(project.path().to_path_buf());
+            state.storage_path = Some(storage_path);
+        }
+        let root = project.path().canonicalize().expect("canonical root");
+        let request = |definition_index| OpenDefinitionRequest {
+            node_id: NodeId("3".to_string()),
+            definition_index,
+        };
+
+        let (primary, definitions) = controller
+            .definition_target(&request(None))
+            .expect("primary definition");
+        assert_eq!(
+            primary,
+            DefinitionTarget {
+                path: root.join("src/Widget.cs"),
+                line: Some(5),
+                col: Some(1),
+            },
+            "without an index the node's own location opens"
+        );
+        assert_eq!(
+            definitions
+                .iter()
+                .map(|definition| definition.file_path.as_str())
+                .collect::<Vec<_>>(),
+            vec!["src/Widget.Layout.cs", "src/Widget.cs"]
+        );
+
+        let (picked, _) = controller
+            .definition_target(&request(Some(0)))
+            .expect("picked definition");
+        assert_eq!(
+            picked,
+            DefinitionTarget {
+                path: root.join("src/Widget.Layout.cs"),
+                line: Some(3),
+                col: Some(1),
+            }
+        );
+
+        let out_of_range = controller
+            .definition_target(&request(Some(2)))
+            .expect_err("index past the definitions");
+        assert_eq!(out_of_range.code, "invalid_argument");
+        assert!(out_of_range.message.contains("2 definitions"));
+    }
+}
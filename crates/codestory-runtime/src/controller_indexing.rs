@@ -7,8 +7,10 @@ use crate::index_freshness::{
 };
 use crate::index_full::index_full_for_runtime;
 use crate::index_incremental::{
-    ensure_incremental_refresh_compatible, index_incremental_for_runtime,
+    ensure_incremental_refresh_compatible, index_file_for_runtime, index_incremental_for_runtime,
 };
+use crate::index_queue::{QueuedIndexing, default_indexing_priority};
+use crate::index_snapshots;
 use crate::index_timings::IndexingRunSummary;
 #[cfg(test)]
 use crate::publication::{
@@ -27,25 +29,58 @@ use crate::semantic_projection::{
     apply_cache_refresh_stats, summarize_symbol_doc,
 };
 use crate::semantic_republish::semantic_projection_republish_for_runtime;
-use crate::support::{clamp_i64_to_u32, clamp_u128_to_u32};
+use crate::support::{clamp_u128_to_u32, clamp_usize_to_u32, storage_stats_dto};
 use crate::workspace_state::runtime_workspace_manifest;
 use crate::{
-    AppController, Storage, clear_search_engine, current_epoch_ms,
-    full_refresh_execution_plan_with_coverage, no_project_error, publish_search_engine,
-    runtime_relative_path, validate_source_policy_exclusions,
+    AppController, PublicOperationService, ReadOnlyBrowserService, Storage, clear_search_engine,
+    current_epoch_ms, full_refresh_execution_plan_with_coverage, no_project_error,
+    publish_search_engine, runtime_relative_path, validate_source_policy_exclusions,
 };
 use codestory_contracts::api::{
-    ApiError, AppEventPayload, IndexDryRunDto, IndexFreshnessDto, IndexMode, IndexPublicationDto,
-    IndexedFilesDto, IndexedFilesRequest, IndexingPhaseTimings, OpenProjectRequest, ProjectSummary,
+    ApiError, AppEventPayload, CreateProjectRequest, FileStatsDto, FileStatsRequest,
+    IndexDryRunDto, IndexErrorDto, IndexFreshnessDto, IndexMode, IndexPublicationDto,
+    IndexSnapshotDto, IndexedFilesDto, IndexedFilesRequest, IndexingPhaseTimings,
+    ListIndexErrorsRequest, OpenProjectRequest, PageDto, ProjectPresetDto, ProjectSummary,
     StartIndexingRequest, StorageStatsDto, SummaryGenerationDto,
 };
+use codestory_contracts::graph::{ErrorFilter, ErrorInfo};
 use codestory_indexer::CancellationToken;
 use codestory_store::{CURRENT_SCHEMA_VERSION, IndexPublicationRecord, Store, SymbolSummaryRecord};
-use codestory_workspace::{RefreshInputs, WorkspaceManifest};
-use std::collections::HashSet;
+use codestory_workspace::{RefreshInputs, WorkspaceManifest, WorkspacePreset};
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
+/// Write the manifest for `req` and return the project root.
+fn create_project_manifest(req: &CreateProjectRequest) -> Result<PathBuf, ApiError> {
+    let root = PathBuf::from(&req.path);
+    if !root.is_dir() {
+        return Err(ApiError::invalid_argument(format!(
+            "Project path is not a directory: {}",
+            root.display()
+        )));
+    }
+    let preset = match req.preset {
+        ProjectPresetDto::RustWorkspace => WorkspacePreset::RustWorkspace,
+        ProjectPresetDto::NodeMonorepo => WorkspacePreset::NodeMonorepo,
+        ProjectPresetDto::CxxCmake => WorkspacePreset::CxxCmake,
+        ProjectPresetDto::PythonPackage => WorkspacePreset::PythonPackage,
+    };
+    if let Some(existing) = ["codestory_workspace.json", "codestory_project.json"]
+        .into_iter()
+        .find(|manifest| root.join(manifest).exists())
+    {
+        return Err(ApiError::already_exists(format!(
+            "Project already has a `{existing}` manifest: {}",
+            root.display()
+        )));
+    }
+    WorkspaceManifest::create(root.clone(), preset)
+        .map_err(|e| ApiError::invalid_argument(format!("Failed to create project: {e}")))?;
+    Ok(root)
+}
+
 impl AppController {
     pub(crate) fn project_summary_from_storage(
         &self,
@@ -63,13 +98,7 @@ impl AppController {
                 .get_file_node_count()
                 .map_err(|e| ApiError::internal(format!("Failed to query file nodes: {e}")))?
         };
-        let dto_stats = StorageStatsDto {
-            node_count: clamp_i64_to_u32(stats.node_count),
-            edge_count: clamp_i64_to_u32(stats.edge_count),
-            file_count: clamp_i64_to_u32(derived_file_count),
-            error_count: clamp_i64_to_u32(stats.error_count),
-            fatal_error_count: clamp_i64_to_u32(stats.fatal_error_count),
-        };
+        let dto_stats = storage_stats_dto(&stats, derived_file_count);
         let workspace = runtime_workspace_manifest(root, storage_path)
             .map_err(|e| ApiError::internal(format!("Failed to open project: {e}")))?;
         let members = workspace_member_storage_summaries(root, &workspace, storage)?;
@@ -94,9 +123,20 @@ impl AppController {
             )?),
             freshness: Some(freshness),
             publication,
+            storage_generation: storage.generation().ok(),
         })
     }
 
+    /// Storage counts plus the rows and disk use of every table, for seeing
+    /// what takes up space in the index. This scans every table.
+    pub fn storage_stats(&self) -> Result<StorageStatsDto, ApiError> {
+        let storage = self.open_storage_read_only()?;
+        let stats = storage
+            .get_detailed_stats()
+            .map_err(|e| ApiError::internal(format!("Failed to query stats: {e}")))?;
+        Ok(storage_stats_dto(&stats, stats.file_count))
+    }
+
     pub fn complete_index_publication_at(
         &self,
         storage_path: &Path,
@@ -130,12 +170,13 @@ impl AppController {
                 "Failed to finish project summary snapshot: {error}"
             ))
         })?;
+        self.warn_on_incompatible_index_manifest(&storage_path);
 
         {
             let mut s = self.state.lock();
             s.project_root = Some(root);
             s.storage_path = Some(storage_path);
-            s.node_names.clear();
+            s.node_names = Arc::default();
             clear_search_engine(&mut s);
         }
         self.sidecar_query_cache.lock().clear();
@@ -155,6 +196,7 @@ impl AppController {
             &self.runtime_config,
         )?;
         let mut summary = self.project_summary_from_storage(&root, &storage_path, &storage)?;
+        self.warn_on_incompatible_index_manifest(&storage_path);
         summary.retrieval = Some(retrieval_state_from_storage_for_runtime(
             &storage,
             &self.runtime_config,
@@ -164,7 +206,7 @@ impl AppController {
             let mut s = self.state.lock();
             s.project_root = Some(root);
             s.storage_path = Some(storage_path);
-            s.node_names = loaded.node_names;
+            s.node_names = Arc::new(loaded.node_names);
             publish_search_engine(&mut s, loaded.engine, loaded.publication);
         }
         self.sidecar_query_cache.lock().clear();
@@ -176,6 +218,11 @@ impl AppController {
         Ok(summary)
     }
 
+    /// Open a project and return its summary without waiting for caches.
+    ///
+    /// Symbol names and the search engine load on a background thread that
+    /// emits [`AppEventPayload::CachesReady`] when done. Calls that need them
+    /// before then wait for the warm-up rather than loading a second copy.
     pub fn open_project(&self, req: OpenProjectRequest) -> Result<ProjectSummary, ApiError> {
         let root = PathBuf::from(req.path);
         if !root.exists() {
@@ -192,9 +239,57 @@ impl AppController {
         }
 
         let storage_path = root.join("codestory.db");
+        let summary = self.open_project_summary_with_storage_path(root, storage_path.clone())?;
+        self.spawn_cache_warmup(storage_path);
+        Ok(summary)
+    }
+
+    /// Write a `codestory_project.json` configured by `req.preset` into a
+    /// directory that has no manifest yet, then open it as a project.
+    pub fn create_project(&self, req: CreateProjectRequest) -> Result<ProjectSummary, ApiError> {
+        let root = create_project_manifest(&req)?;
+        self.open_project(OpenProjectRequest {
+            path: root.to_string_lossy().to_string(),
+        })
+    }
+
+    pub fn create_project_with_storage_path(
+        &self,
+        req: CreateProjectRequest,
+        storage_path: PathBuf,
+    ) -> Result<ProjectSummary, ApiError> {
+        let root = create_project_manifest(&req)?;
         self.open_project_with_storage_path(root, storage_path)
     }
 
+    fn spawn_cache_warmup(&self, storage_path: PathBuf) {
+        let controller = self.clone();
+        std::thread::spawn(move || {
+            let started = Instant::now();
+            let project_current = |controller: &AppController| {
+                let state = controller.state.lock();
+                !state.is_indexing && state.storage_path.as_deref() == Some(storage_path.as_path())
+            };
+            if !project_current(&controller) {
+                return;
+            }
+            let result = controller.ensure_search_state();
+            if !project_current(&controller) {
+                return;
+            }
+            let event = match result {
+                Ok(()) => AppEventPayload::CachesReady {
+                    symbol_count: clamp_usize_to_u32(controller.state.lock().node_names.len()),
+                    duration_ms: clamp_u128_to_u32(started.elapsed().as_millis()),
+                },
+                Err(error) => AppEventPayload::StatusUpdate {
+                    message: format!("Search caches are not ready: {}", error.message),
+                },
+            };
+            let _ = controller.events_tx.send(event);
+        });
+    }
+
     pub fn open_project_with_storage_path(
         &self,
         root: PathBuf,
@@ -295,7 +390,10 @@ impl AppController {
                     !codestory_workspace::same_workspace_path(current, &storage_path)
                 });
             if changed {
-                state.node_names.clear();
+                state.node_names = Arc::default();
+                state.indexing_queue.clear();
+                state.buffer_overlays.clear();
+                state.open_files.clear();
                 clear_search_engine(&mut state);
             }
             state.project_root = Some(root);
@@ -308,17 +406,97 @@ impl AppController {
         Ok(Some(summary))
     }
 
+    /// Keep up to `count` archived copies of the database, taken after each
+    /// successful indexing run. Zero disables archival.
+    pub fn set_snapshot_retention(&self, count: usize) {
+        self.state.lock().snapshot_retention = count;
+    }
+
+    /// Cap the threads indexing parses on. `None` leaves one core free.
+    pub fn set_indexing_max_threads(&self, max_threads: Option<usize>) {
+        self.state.lock().indexing_max_threads = max_threads.map(|threads| threads.max(1));
+    }
+
+    /// Run `index` on a dedicated pool so parsing does not take every core;
+    /// all rayon work started inside it stays on that pool.
+    fn with_indexing_threads<R: Send>(&self, index: impl FnOnce() -> R + Send) -> R {
+        let threads = self
+            .state
+            .lock()
+            .indexing_max_threads
+            .unwrap_or_else(default_indexing_threads);
+        match rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .thread_name(|index| format!("codestory-index-{index}"))
+            .build()
+        {
+            Ok(pool) => pool.install(index),
+            Err(_) => index(),
+        }
+    }
+
+    /// Archived index snapshots for the open project, newest first.
+    pub fn list_index_snapshots(&self) -> Result<Vec<IndexSnapshotDto>, ApiError> {
+        let storage_path = self
+            .state
+            .lock()
+            .storage_path
+            .clone()
+            .ok_or_else(no_project_error)?;
+        index_snapshots::list_snapshots(&storage_path)
+    }
+
+    /// Browse an archived snapshot read-only, leaving this controller on the
+    /// live index. Only the read-only browser surface is exposed, so the
+    /// snapshot can never be reindexed or written through it.
+    pub fn open_index_snapshot(&self, name: &str) -> Result<ReadOnlyBrowserService, ApiError> {
+        let (root, storage_path) = {
+            let state = self.state.lock();
+            (
+                state.project_root.clone().ok_or_else(no_project_error)?,
+                state.storage_path.clone().ok_or_else(no_project_error)?,
+            )
+        };
+        let snapshot_path = index_snapshots::resolve_snapshot(&storage_path, name)?;
+        let controller = AppController::new_with_source_index_policy(
+            (*self.runtime_config).clone(),
+            (*self.source_index_policy).clone(),
+        );
+        controller
+            .inspect_project_summary_with_storage_path(root, snapshot_path)?
+            .ok_or_else(|| ApiError::not_found(format!("Index snapshot not found: {name}")))?;
+        Ok(ReadOnlyBrowserService::new(
+            controller.clone(),
+            PublicOperationService::new_archived(controller),
+        ))
+    }
+
+    /// Start indexing on a background thread.
+    ///
+    /// A request that arrives while another run is active is queued rather
+    /// than rejected, and reported with [`AppEventPayload::IndexingQueued`].
+    /// Queued runs coalesce by mode and start highest-priority first once the
+    /// active run finishes.
     pub fn start_indexing(&self, req: StartIndexingRequest) -> Result<(), ApiError> {
+        if let Some(max_threads) = req.max_threads {
+            self.set_indexing_max_threads(Some(max_threads as usize));
+        }
+        let request = QueuedIndexing {
+            mode: req.mode,
+            priority: req
+                .priority
+                .unwrap_or_else(|| default_indexing_priority(req.mode)),
+        };
         let (root, storage_path) = {
             let s = self.state.lock();
-            if s.is_indexing {
-                return Err(ApiError::invalid_argument(
-                    "Indexing already in progress for this controller.",
-                ));
-            }
             let root = s.project_root.clone().ok_or_else(|| {
                 ApiError::invalid_argument("No project open. Call open_project first.")
             })?;
+            if s.is_indexing {
+                drop(s);
+                self.queue_indexing(request);
+                return Ok(());
+            }
             let storage_path = s
                 .storage_path
                 .clone()
@@ -331,68 +509,150 @@ impl AppController {
         {
             let mut s = self.state.lock();
             if s.is_indexing {
-                return Err(ApiError::invalid_argument(
-                    "Indexing already in progress for this controller.",
-                ));
+                drop(s);
+                self.queue_indexing(request);
+                return Ok(());
             }
             s.is_indexing = true;
             s.index_freshness_cache = None;
         }
 
-        let events_tx = self.events_tx.clone();
+        self.spawn_indexing_worker((root, storage_path, req.mode));
+        Ok(())
+    }
+
+    /// Run `first`, then keep claiming queued runs on the same thread.
+    fn spawn_indexing_worker(&self, first: (PathBuf, PathBuf, IndexMode)) {
         let controller = self.clone();
 
         // Use a dedicated thread so callers can keep their runtime responsive.
         std::thread::spawn(move || {
-            let indexing_started = std::time::Instant::now();
-            let result = match IndexWriterGuard::try_acquire(&storage_path) {
-                Ok(_writer_guard) => {
-                    let result = match req.mode {
-                        IndexMode::Full => index_full_for_runtime(
-                            &root,
-                            &storage_path,
-                            &events_tx,
-                            None,
-                            &controller.runtime_config,
-                            &controller.source_index_policy,
-                        ),
-                        IndexMode::Incremental => index_incremental_for_runtime(
-                            &root,
-                            &storage_path,
-                            &events_tx,
-                            None,
-                            &controller.runtime_config,
-                            &controller.source_index_policy,
-                        ),
-                    };
-                    result.and_then(|summary| {
-                        controller.finish_successful_indexing(summary, &storage_path, true, None)
-                    })
-                }
-                Err(error) => Err(error),
-            };
+            let mut next = Some(first);
+            while let Some((root, storage_path, mode)) = next {
+                controller.run_background_indexing(&root, &storage_path, mode);
+                next = controller.claim_queued_indexing();
+            }
+        });
+    }
+
+    fn queue_indexing(&self, request: QueuedIndexing) {
+        let (queued, pending) = self.state.lock().indexing_queue.push(request);
+        let _ = self.events_tx.send(AppEventPayload::IndexingQueued {
+            mode: queued.mode,
+            priority: queued.priority,
+            pending: clamp_usize_to_u32(pending),
+        });
+    }
 
-            match result {
-                Ok(phase_timings) => {
-                    controller.state.lock().is_indexing = false;
-                    let _ = events_tx.send(AppEventPayload::IndexingComplete {
-                        duration_ms: clamp_u128_to_u32(indexing_started.elapsed().as_millis()),
-                        phase_timings,
-                    });
+    /// Take the next queued run unless another run already claimed the
+    /// controller. Queued incremental runs that can no longer reuse the stored
+    /// index report `IndexingFailed`, and the next entry is tried.
+    pub(crate) fn claim_queued_indexing(&self) -> Option<(PathBuf, PathBuf, IndexMode)> {
+        loop {
+            let (root, storage_path, queued) = {
+                let mut s = self.state.lock();
+                if s.is_indexing {
+                    return None;
                 }
-                Err(err) => {
-                    let _ = events_tx.send(AppEventPayload::IndexingFailed { error: err.message });
-                    controller.recover_failed_indexing(&storage_path, true);
+                let queued = s.indexing_queue.pop()?;
+                let root = s.project_root.clone()?;
+                let storage_path = s
+                    .storage_path
+                    .clone()
+                    .unwrap_or_else(|| root.join("codestory.db"));
+                if queued.mode == IndexMode::Full {
+                    s.is_indexing = true;
+                    s.index_freshness_cache = None;
+                    return Some((root, storage_path, queued.mode));
                 }
+                (root, storage_path, queued)
+            };
+            if let Err(error) = ensure_incremental_refresh_compatible(&root, &storage_path) {
+                let _ = self.events_tx.send(AppEventPayload::IndexingFailed {
+                    error: error.message,
+                });
+                continue;
             }
-        });
+            let mut s = self.state.lock();
+            if s.is_indexing {
+                s.indexing_queue.push(queued);
+                return None;
+            }
+            s.is_indexing = true;
+            s.index_freshness_cache = None;
+            return Some((root, storage_path, queued.mode));
+        }
+    }
 
-        Ok(())
+    fn run_background_indexing(&self, root: &Path, storage_path: &Path, mode: IndexMode) {
+        let indexing_started = std::time::Instant::now();
+        let result = match IndexWriterGuard::try_acquire(storage_path) {
+            Ok(_writer_guard) => {
+                let open_files = self.state.lock().open_files.clone();
+                let result = self.with_indexing_threads(|| match mode {
+                    IndexMode::Full => index_full_for_runtime(
+                        root,
+                        storage_path,
+                        &self.events_tx,
+                        None,
+                        &self.runtime_config,
+                        &self.source_index_policy,
+                        &open_files,
+                    ),
+                    IndexMode::Incremental => index_incremental_for_runtime(
+                        root,
+                        storage_path,
+                        &self.events_tx,
+                        None,
+                        &self.runtime_config,
+                        &self.source_index_policy,
+                        &open_files,
+                    ),
+                });
+                result.and_then(|summary| {
+                    self.finish_successful_indexing(summary, storage_path, true, None)
+                })
+            }
+            Err(error) => Err(error),
+        };
+
+        match result {
+            Ok(phase_timings) => {
+                self.state.lock().is_indexing = false;
+                let _ = self.events_tx.send(AppEventPayload::IndexingComplete {
+                    duration_ms: clamp_u128_to_u32(indexing_started.elapsed().as_millis()),
+                    phase_timings,
+                });
+            }
+            Err(err) => {
+                let _ = self
+                    .events_tx
+                    .send(AppEventPayload::IndexingFailed { error: err.message });
+                self.recover_failed_indexing(storage_path, true);
+            }
+        }
     }
 
     fn run_indexing_blocking_inner(
         &self,
         mode: IndexMode,
+        only_file: Option<&Path>,
+        refresh_runtime_caches: bool,
+        cancel_token: Option<&CancellationToken>,
+    ) -> Result<IndexingPhaseTimings, ApiError> {
+        let result =
+            self.run_indexing_blocking_once(mode, only_file, refresh_runtime_caches, cancel_token);
+        // Requests queued by `start_indexing` during this run start now.
+        if let Some(next) = self.claim_queued_indexing() {
+            self.spawn_indexing_worker(next);
+        }
+        result
+    }
+
+    fn run_indexing_blocking_once(
+        &self,
+        mode: IndexMode,
+        only_file: Option<&Path>,
         refresh_runtime_caches: bool,
         cancel_token: Option<&CancellationToken>,
     ) -> Result<IndexingPhaseTimings, ApiError> {
@@ -432,32 +692,49 @@ impl AppController {
             }
         };
 
-        let result = match mode {
-            IndexMode::Full => index_full_for_runtime(
+        let started = Instant::now();
+        let open_files = self.state.lock().open_files.clone();
+        let result = self.with_indexing_threads(|| match (mode, only_file) {
+            (IndexMode::Full, _) => index_full_for_runtime(
                 &root,
                 &storage_path,
                 &self.events_tx,
                 cancel_token,
                 &self.runtime_config,
                 &self.source_index_policy,
+                &open_files,
             ),
-            IndexMode::Incremental => index_incremental_for_runtime(
+            (IndexMode::Incremental, None) => index_incremental_for_runtime(
                 &root,
                 &storage_path,
                 &self.events_tx,
                 cancel_token,
                 &self.runtime_config,
                 &self.source_index_policy,
+                &open_files,
             ),
-        };
-
-        match result {
-            Ok(summary) => self.finish_successful_indexing(
-                summary,
+            (IndexMode::Incremental, Some(file)) => index_file_for_runtime(
+                &root,
                 &storage_path,
-                refresh_runtime_caches,
+                file,
+                &self.events_tx,
                 cancel_token,
+                &self.runtime_config,
+                &self.source_index_policy,
             ),
+        });
+
+        match result {
+            Ok(summary) => {
+                let phase_timings = self.finish_successful_indexing(
+                    summary,
+                    &storage_path,
+                    refresh_runtime_caches,
+                    cancel_token,
+                )?;
+                self.record_index_manifest(&storage_path, mode, started.elapsed());
+                Ok(phase_timings)
+            }
             Err(error) => {
                 self.recover_failed_indexing(&storage_path, refresh_runtime_caches);
                 Err(error)
@@ -547,6 +824,15 @@ impl AppController {
             cache_stats.semantic_stats = summary.staged_semantic_stats;
         }
         apply_cache_refresh_stats(&mut summary.phase_timings, cache_stats);
+        let snapshot_retention = self.state.lock().snapshot_retention;
+        if snapshot_retention > 0
+            && let Err(error) = index_snapshots::archive_snapshot(storage_path, snapshot_retention)
+        {
+            tracing::warn!(
+                error = %error.message,
+                "Failed to archive index snapshot after successful indexing"
+            );
+        }
         Ok(summary.phase_timings)
     }
 
@@ -711,8 +997,25 @@ impl AppController {
         ensure_incremental_refresh_compatible(root, storage_path)
     }
 
+    /// Reindex one file, typically right after an editor save, and publish
+    /// the result. `path` resolves against the project root like the other
+    /// file APIs; a path that no longer exists drops the file's projection.
+    /// Emits [`AppEventPayload::FileReindexed`] on success.
+    pub fn reindex_file(&self, path: &str) -> Result<IndexingPhaseTimings, ApiError> {
+        let file = self.resolve_project_file_path(path, true)?;
+        let started = Instant::now();
+        let phase_timings =
+            self.run_indexing_blocking_inner(IndexMode::Incremental, Some(&file), true, None)?;
+        let root = self.require_project_root()?;
+        let _ = self.events_tx.send(AppEventPayload::FileReindexed {
+            path: runtime_relative_path(&root, &file),
+            duration_ms: clamp_u128_to_u32(started.elapsed().as_millis()),
+        });
+        Ok(phase_timings)
+    }
+
     pub fn run_indexing_blocking(&self, mode: IndexMode) -> Result<IndexingPhaseTimings, ApiError> {
-        self.run_indexing_blocking_inner(mode, true, None)
+        self.run_indexing_blocking_inner(mode, None, true, None)
     }
 
     pub fn run_indexing_blocking_with_cancel(
@@ -720,14 +1023,14 @@ impl AppController {
         mode: IndexMode,
         cancel_token: &CancellationToken,
     ) -> Result<IndexingPhaseTimings, ApiError> {
-        self.run_indexing_blocking_inner(mode, true, Some(cancel_token))
+        self.run_indexing_blocking_inner(mode, None, true, Some(cancel_token))
     }
 
     pub fn run_indexing_blocking_without_runtime_refresh(
         &self,
         mode: IndexMode,
     ) -> Result<IndexingPhaseTimings, ApiError> {
-        self.run_indexing_blocking_inner(mode, false, None)
+        self.run_indexing_blocking_inner(mode, None, false, None)
     }
 
     pub fn run_indexing_blocking_without_runtime_refresh_with_cancel(
@@ -735,7 +1038,7 @@ impl AppController {
         mode: IndexMode,
         cancel_token: &CancellationToken,
     ) -> Result<IndexingPhaseTimings, ApiError> {
-        self.run_indexing_blocking_inner(mode, false, Some(cancel_token))
+        self.run_indexing_blocking_inner(mode, None, false, Some(cancel_token))
     }
 
     pub fn republish_semantic_projections_blocking(
@@ -801,7 +1104,10 @@ impl AppController {
                     !codestory_workspace::same_workspace_path(current, &storage_path)
                 });
             if changed {
-                state.node_names.clear();
+                state.node_names = Arc::default();
+                state.indexing_queue.clear();
+                state.buffer_overlays.clear();
+                state.open_files.clear();
                 clear_search_engine(&mut state);
             }
             state.project_root = Some(root.clone());
@@ -1023,6 +1329,83 @@ impl AppController {
         indexed_files_from_storage(&root, &storage, &self.source_index_policy, req)
     }
 
+    /// Page through the errors recorded by the last index run.
+    pub fn list_index_errors(
+        &self,
+        req: ListIndexErrorsRequest,
+    ) -> Result<PageDto<IndexErrorDto>, ApiError> {
+        let storage = self.open_storage_read_only()?;
+        let filter = ErrorFilter {
+            fatal_only: req.fatal_only,
+            indexed_only: false,
+        };
+        let total = storage
+            .get_error_count(Some(&filter))
+            .map_err(|e| ApiError::internal(format!("Failed to count index errors: {e}")))?;
+        PageDto::fetch(total, req.offset, req.limit, |offset, limit| {
+            let errors = storage
+                .get_errors_page(Some(&filter), offset, limit)
+                .map_err(|e| ApiError::internal(format!("Failed to load index errors: {e}")))?;
+            Self::index_error_dtos(&storage, errors)
+        })
+    }
+
+    fn index_error_dtos(
+        storage: &Storage,
+        errors: Vec<ErrorInfo>,
+    ) -> Result<Vec<IndexErrorDto>, ApiError> {
+        let mut file_paths = HashMap::new();
+        let mut items = Vec::with_capacity(errors.len());
+        for error in errors {
+            let file_path = match error.file_id {
+                Some(file_id) if file_paths.contains_key(&file_id) => file_paths[&file_id].clone(),
+                Some(file_id) => {
+                    let path = storage
+                        .get_node(file_id)
+                        .map_err(|e| ApiError::internal(format!("Failed to load file node: {e}")))?
+                        .map(|file| file.serialized_name);
+                    file_paths.insert(file_id, path.clone());
+                    path
+                }
+                None => None,
+            };
+            items.push(IndexErrorDto {
+                message: error.message,
+                file_path,
+                line: error.line,
+                column: error.column,
+                fatal: error.is_fatal,
+                coverage_reason: error.coverage_reason,
+            });
+        }
+        Ok(items)
+    }
+
+    /// List the files that declare the most symbols per line.
+    pub fn files_by_symbol_density(
+        &self,
+        req: FileStatsRequest,
+    ) -> Result<Vec<FileStatsDto>, ApiError> {
+        self.ensure_consistent_read_state("Files")?;
+        let root = self.require_project_root()?;
+        let storage = self.open_storage_read_only()?;
+        let limit = req.limit.unwrap_or(25).clamp(1, 500) as usize;
+        let stats = storage
+            .get_files_by_symbol_density(limit)
+            .map_err(|e| ApiError::internal(format!("Failed to load file stats: {e}")))?;
+        Ok(stats
+            .into_iter()
+            .map(|stats| FileStatsDto {
+                path: runtime_relative_path(&root, &stats.path),
+                symbol_density: stats.symbol_density() as f32,
+                language: stats.language,
+                line_count: stats.line_count,
+                symbol_count: stats.symbol_count,
+                edge_count: stats.edge_count,
+            })
+            .collect())
+    }
+
     pub(crate) fn index_freshness(&self) -> Result<IndexFreshnessDto, ApiError> {
         let root = self.require_project_root()?;
         let storage_path = self.require_storage_path()?;
@@ -1102,3 +1485,8 @@ impl AppController {
         freshness
     }
 }
+
+/// One less than the available cores, so the machine stays usable.
+fn default_indexing_threads() -> usize {
+    std::thread::available_parallelism().map_or(1, |cores| cores.get().saturating_sub(1).max(1))
+}
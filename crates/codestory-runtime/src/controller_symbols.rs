@@ -1,20 +1,31 @@
+use crate::buffer_overlay::{is_overlaid, overlay_search_hit};
 use crate::route_coverage::{
     RouteHandlerCandidate, compare_route_handler_candidates,
     route_endpoint_metadata_from_canonical, route_endpoint_metadata_from_openapi_label,
 };
+use crate::search::engine::MAX_AUTOCOMPLETE_SUGGESTIONS;
+use crate::search_intent::{apply_search_intent_filters, parse_search_intent_query};
 #[cfg(test)]
 use crate::search_scoring::HybridSearchInstrumentation;
-use crate::support::node_display_name;
+use crate::support::{clamp_u64_to_u32, node_display_name};
 use crate::symbol_query::compare_search_hits_with_project_root;
-use crate::{AppController, Storage, agent, graph_builders, member_access_dto};
+use crate::{
+    AppController, Storage, agent, graph_builders, graph_compare, member_access_dto,
+    runtime_relative_path, trail_html, trail_png,
+};
 use codestory_contracts::api::{
-    AgentAnswerDto, AgentAskRequest, AgentPacketDto, AgentPacketRequestDto, ApiError, EdgeKind,
-    EdgeOccurrencesRequest, GraphRequest, GraphResponse, ListChildrenSymbolsRequest,
-    ListRootSymbolsRequest, NodeDetailsDto, NodeDetailsRequest, NodeId, NodeKind,
-    NodeOccurrencesRequest, RouteEndpointHandlerDto, RouteEndpointMetadataDto, SearchHit,
-    SourceOccurrenceDto, SymbolSummaryDto, TrailConfigDto, TrailFilterOptionsDto,
+    AgentAnswerDto, AgentAskRequest, AgentPacketDto, AgentPacketRequestDto, ApiError,
+    AutocompleteSuggestionDto, EdgeKind, EdgeKindCountDto, EdgeOccurrencesRequest, EntryPointDto,
+    EntryPointOverviewDto, GraphCompareDto, GraphExpandRequest, GraphRenderRequest, GraphRequest,
+    GraphResponse, GraphSessionDto, GraphSessionUpdateRequest, ListChildrenSymbolsRequest,
+    ListRootSymbolsRequest, NodeDetailsDto, NodeDetailsRequest, NodeId, NodeKind, NodeKindCountDto,
+    NodeOccurrencesRequest, NodePreviewDto, PageDto, ReachableFileCountDto, ReachableSetDto,
+    ReachableSetRequest, RouteEndpointHandlerDto, RouteEndpointMetadataDto, SearchHit,
+    SourceOccurrenceDto, SymbolSummaryDto, TestLinkDto, TrailConfigDto, TrailFilterOptionsDto,
 };
 use codestory_contracts::graph::Node as GraphNode;
+use codestory_contracts::query::MatchQuery;
+use codestory_store::{GraphMatchFilter, GraphMatches};
 use std::collections::{HashMap, HashSet};
 
 impl AppController {
@@ -54,6 +65,8 @@ impl AppController {
             codestory_contracts::graph::OccurrenceKind::MACRO_DEFINITION => "macro_definition",
             codestory_contracts::graph::OccurrenceKind::MACRO_REFERENCE => "macro_reference",
             codestory_contracts::graph::OccurrenceKind::UNKNOWN => "unknown",
+            codestory_contracts::graph::OccurrenceKind::WRITE_REFERENCE => "write_reference",
+            codestory_contracts::graph::OccurrenceKind::READ_REFERENCE => "read_reference",
         }
     }
 
@@ -78,6 +91,9 @@ impl AppController {
             start_col: occurrence.location.start_col,
             end_line: occurrence.location.end_line,
             end_col: occurrence.location.end_col,
+            notebook_cell_index: occurrence.notebook_cell.map(|cell| cell.cell_index),
+            notebook_cell_line: occurrence.notebook_cell.map(|cell| cell.line),
+            stale: false,
         }))
     }
 
@@ -102,6 +118,7 @@ impl AppController {
             kind: NodeKind::from(node.kind),
             file_path: Self::file_path_for_node(storage, &node)?,
             has_children,
+            entry_point: false,
         })
     }
 
@@ -132,22 +149,43 @@ impl AppController {
     /// snippet, trail, and graph-query target resolution can work from an
     /// already-open indexed store. Product search and packet evidence must use
     /// the sidecar-primary search paths instead.
+    ///
+    /// `kind:`, `file:`/`path:`, `name:`, and `lang:` atoms in the query
+    /// narrow the candidates; the remaining terms are matched fuzzily. Files
+    /// with a buffer overlay (see [`Self::set_overlay`]) contribute their
+    /// unsaved symbols instead of the published ones.
     pub fn resolve_indexed_symbol_candidates(
         &self,
         query: &str,
         max_results: usize,
     ) -> Result<Vec<SearchHit>, ApiError> {
         self.ensure_search_state()?;
+        let intent_query = parse_search_intent_query(query);
+        let query = intent_query.effective_query.as_str();
         let storage = self.open_storage_read_only()?;
-        let (matches, node_names) = {
+        let (matches, node_names, overlay_hits, overlaid_paths) = {
             let mut s = self.state.lock();
             let engine = s.search_engine.as_mut().ok_or_else(|| {
                 ApiError::invalid_argument("Search engine not initialized. Open a project first.")
             })?;
-            (
-                engine.search_symbol_with_scores(query),
-                s.node_names.clone(),
-            )
+            let matches = engine.search_symbol_with_scores(query);
+            let overlay_hits = s
+                .buffer_overlays
+                .iter()
+                .flat_map(|(path, overlay)| {
+                    overlay
+                        .search(query)
+                        .into_iter()
+                        .map(|(node, score)| overlay_search_hit(&storage, path, node, score))
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>();
+            let overlaid_paths = s
+                .buffer_overlays
+                .keys()
+                .map(|path| path.to_string_lossy().into_owned())
+                .collect::<Vec<_>>();
+            (matches, s.node_names.clone(), overlay_hits, overlaid_paths)
         };
 
         let mut hits = matches
@@ -157,6 +195,15 @@ impl AppController {
             .into_iter()
             .flatten()
             .collect::<Vec<_>>();
+        if !overlaid_paths.is_empty() {
+            hits.retain(|hit| {
+                hit.file_path
+                    .as_deref()
+                    .is_none_or(|path| !is_overlaid(&overlaid_paths, path))
+            });
+            hits.extend(overlay_hits);
+        }
+        apply_search_intent_filters(&mut hits, &intent_query.filters);
         let project_root = self.require_project_root().ok();
         hits.sort_by(|left, right| {
             compare_search_hits_with_project_root(project_root.as_deref(), query, left, right)
@@ -165,6 +212,103 @@ impl AppController {
         Ok(hits)
     }
 
+    /// Suggest symbols whose name, or a trailing path segment of it, starts
+    /// with `prefix`.
+    ///
+    /// Lookups hit the in-memory prefix index and read only the suggested
+    /// nodes from storage, so they are cheap enough to run per keystroke.
+    /// Files with a buffer overlay (see [`Self::set_overlay`]) contribute
+    /// their unsaved symbols instead of the published ones.
+    pub fn autocomplete(
+        &self,
+        prefix: &str,
+        limit: usize,
+    ) -> Result<Vec<AutocompleteSuggestionDto>, ApiError> {
+        self.ensure_search_state()?;
+        let (matches, labels_by_id, overlay_suggestions, overlaid_paths) = {
+            let s = self.state.lock();
+            let engine = s.search_engine.as_ref().ok_or_else(|| {
+                ApiError::invalid_argument("Search engine not initialized. Open a project first.")
+            })?;
+            // Overlaid files hide some published matches, so ask for the
+            // full budget and truncate after merging.
+            let published_limit = if s.buffer_overlays.is_empty() {
+                limit
+            } else {
+                MAX_AUTOCOMPLETE_SUGGESTIONS
+            };
+            let matches = engine.autocomplete_symbols(prefix, published_limit);
+            let labels_by_id = matches
+                .iter()
+                .filter_map(|(id, _)| s.node_names.get(id).map(|label| (*id, label.clone())))
+                .collect::<HashMap<_, _>>();
+            let overlay_suggestions = s
+                .buffer_overlays
+                .iter()
+                .flat_map(|(path, overlay)| {
+                    overlay
+                        .autocomplete(prefix)
+                        .into_iter()
+                        .map(move |(node, score)| AutocompleteSuggestionDto {
+                            node_id: NodeId::from(node.id),
+                            label: node_display_name(node),
+                            kind: NodeKind::from(node.kind),
+                            file_path: Some(path.to_string_lossy().into_owned()),
+                            score,
+                        })
+                })
+                .collect::<Vec<_>>();
+            let overlaid_paths = s
+                .buffer_overlays
+                .keys()
+                .map(|path| path.to_string_lossy().into_owned())
+                .collect::<Vec<_>>();
+            (matches, labels_by_id, overlay_suggestions, overlaid_paths)
+        };
+        if matches.is_empty() && overlay_suggestions.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let storage = self.open_storage_read_only()?;
+        let mut suggestions = Vec::with_capacity(matches.len());
+        for (id, score) in matches {
+            let node = match storage.get_node(id) {
+                Ok(Some(node)) if node.kind != codestory_contracts::graph::NodeKind::UNKNOWN => {
+                    node
+                }
+                Ok(_) => continue,
+                Err(e) => {
+                    return Err(ApiError::internal(format!(
+                        "Failed to load suggested symbol: {e}"
+                    )));
+                }
+            };
+            suggestions.push(AutocompleteSuggestionDto {
+                node_id: NodeId::from(node.id),
+                label: labels_by_id
+                    .get(&node.id)
+                    .cloned()
+                    .unwrap_or_else(|| node_display_name(&node)),
+                kind: NodeKind::from(node.kind),
+                file_path: Self::file_path_for_node(&storage, &node)?,
+                score,
+            });
+        }
+        if overlaid_paths.is_empty() {
+            return Ok(suggestions);
+        }
+        suggestions.retain(|suggestion| {
+            suggestion
+                .file_path
+                .as_deref()
+                .is_none_or(|path| !is_overlaid(&overlaid_paths, path))
+        });
+        suggestions.extend(overlay_suggestions);
+        suggestions.sort_by(|left, right| right.score.total_cmp(&left.score));
+        suggestions.truncate(limit.min(MAX_AUTOCOMPLETE_SUGGESTIONS));
+        Ok(suggestions)
+    }
+
     pub fn list_root_symbols(
         &self,
         req: ListRootSymbolsRequest,
@@ -176,24 +320,71 @@ impl AppController {
             .get_root_symbols()
             .map_err(|e| ApiError::internal(format!("Failed to load root symbols: {e}")))?;
         roots.sort_by_cached_key(node_display_name);
+        let entry_points = crate::controller_bookmarks::entry_point_bookmark_nodes(&storage)?;
+        let entry_point_ids = entry_points
+            .iter()
+            .map(|node| node.id)
+            .collect::<HashSet<_>>();
+        roots.retain(|node| !entry_point_ids.contains(&node.id));
 
-        let labels_by_id = self.cached_labels(roots.iter().map(|node| node.id));
+        let labels_by_id =
+            self.cached_labels(entry_points.iter().chain(roots.iter()).map(|node| node.id));
         roots = Self::dedupe_symbol_nodes(roots, &labels_by_id);
 
         let limit = req.limit.unwrap_or(300).clamp(1, 2_000) as usize;
-        if roots.len() > limit {
-            roots.truncate(limit);
+        let mut symbols = Vec::with_capacity(limit.min(entry_points.len() + roots.len()));
+        for node in entry_points.into_iter().take(limit) {
+            let mut summary = Self::symbol_summary_for_node(&storage, &labels_by_id, node)?;
+            summary.entry_point = true;
+            symbols.push(summary);
         }
-
-        roots
-            .into_iter()
-            .map(|node| Self::symbol_summary_for_node(&storage, &labels_by_id, node))
-            .collect()
+        for node in roots.into_iter().take(limit - symbols.len()) {
+            symbols.push(Self::symbol_summary_for_node(
+                &storage,
+                &labels_by_id,
+                node,
+            )?);
+        }
+        Ok(symbols)
     }
 
+    /// Every child of `req.parent_id`, or only the requested page when
+    /// `offset` or `limit` is set.
     pub fn list_children_symbols(
         &self,
         req: ListChildrenSymbolsRequest,
+    ) -> Result<Vec<SymbolSummaryDto>, ApiError> {
+        if req.offset.is_some() || req.limit.is_some() {
+            return self.list_children_symbols_page(req).map(|page| page.items);
+        }
+        self.all_children_symbols(&req)
+    }
+
+    pub fn list_children_symbols_page(
+        &self,
+        req: ListChildrenSymbolsRequest,
+    ) -> Result<PageDto<SymbolSummaryDto>, ApiError> {
+        self.ensure_search_state()?;
+        let parent_id = req.parent_id.to_core()?;
+        let storage = self.open_storage_read_only()?;
+        let total = storage
+            .get_children_symbol_count(parent_id)
+            .map_err(|e| ApiError::internal(format!("Failed to count child symbols: {e}")))?;
+        PageDto::fetch(total, req.offset, req.limit, |offset, limit| {
+            let children = storage
+                .get_children_symbols_page(parent_id, offset, limit)
+                .map_err(|e| ApiError::internal(format!("Failed to load child symbols: {e}")))?;
+            let labels_by_id = self.cached_labels(children.iter().map(|node| node.id));
+            children
+                .into_iter()
+                .map(|node| Self::symbol_summary_for_node(&storage, &labels_by_id, node))
+                .collect()
+        })
+    }
+
+    fn all_children_symbols(
+        &self,
+        req: &ListChildrenSymbolsRequest,
     ) -> Result<Vec<SymbolSummaryDto>, ApiError> {
         self.ensure_search_state()?;
         let parent_id = req.parent_id.to_core()?;
@@ -251,30 +442,272 @@ impl AppController {
         graph_builders::graph_trail(self, req)
     }
 
+    /// Build two trails and merge them, labeling each node and edge as only
+    /// in `config_a`'s trail, only in `config_b`'s, or in both. Use
+    /// [`crate::compare_trail_graphs`] to compare trails from two snapshots.
+    pub fn graph_compare(
+        &self,
+        config_a: TrailConfigDto,
+        config_b: TrailConfigDto,
+    ) -> Result<GraphCompareDto, ApiError> {
+        let a = self.graph_trail(config_a)?;
+        let b = self.graph_trail(config_b)?;
+        Ok(graph_compare::compare_trail_graphs(&a, &b))
+    }
+
+    /// Neighbors of one node that are not yet part of the caller's graph.
+    pub fn graph_expand(&self, req: GraphExpandRequest) -> Result<GraphResponse, ApiError> {
+        graph_builders::graph_expand(self, req)
+    }
+
+    /// Hide, collapse, or restore nodes for one graph session. Trail, direct
+    /// reference, and expand requests naming the session reflect the change.
+    pub fn update_graph_session(
+        &self,
+        req: GraphSessionUpdateRequest,
+    ) -> Result<GraphSessionDto, ApiError> {
+        self.state.lock().graph_sessions.update(req)
+    }
+
     pub fn graph_direct_references(&self, req: TrailConfigDto) -> Result<GraphResponse, ApiError> {
         graph_builders::graph_direct_references(self, req)
     }
 
+    /// Build a trail and render it as a standalone HTML document that can be
+    /// attached to a review or design doc and explored offline.
+    pub fn export_trail_html(&self, req: TrailConfigDto) -> Result<String, ApiError> {
+        let graph = self.graph_trail(req)?;
+        let title = graph
+            .nodes
+            .iter()
+            .find(|node| node.id == graph.center_id)
+            .map(|node| format!("Trail: {}", node.label))
+            .unwrap_or_else(|| "Trail".to_string());
+        trail_html::render_trail_html(&title, &graph)
+            .map_err(|e| ApiError::internal(format!("Failed to render trail HTML: {e}")))
+    }
+
+    /// Build a trail and rasterize it to a PNG image, for chat messages and
+    /// docs where no browser is available.
+    pub fn render_trail_png(&self, req: GraphRenderRequest) -> Result<Vec<u8>, ApiError> {
+        let graph = self.graph_trail(req.trail)?;
+        trail_png::render_trail_png(&graph, req.width, req.height, req.theme)
+            .map_err(|e| ApiError::internal(format!("Failed to render trail PNG: {e}")))
+    }
+
     pub fn graph_trail_filter_options(&self) -> Result<TrailFilterOptionsDto, ApiError> {
         let storage = self.open_storage_read_only()?;
-        let node_kinds = storage
-            .get_present_node_kinds()
-            .map_err(|e| ApiError::internal(format!("Failed to load node kinds: {e}")))?
+        let metadata = storage
+            .get_graph_metadata()
+            .map_err(|e| ApiError::internal(format!("Failed to load graph kinds: {e}")))?;
+        let node_kind_counts = metadata
+            .node_kind_counts
             .into_iter()
-            .map(NodeKind::from)
+            .map(|(kind, count)| NodeKindCountDto {
+                kind: NodeKind::from(kind),
+                count: clamp_u64_to_u32(count),
+            })
             .collect::<Vec<_>>();
-        let edge_kinds = storage
-            .get_present_edge_kinds()
-            .map_err(|e| ApiError::internal(format!("Failed to load edge kinds: {e}")))?
+        let edge_kind_counts = metadata
+            .edge_kind_counts
             .into_iter()
-            .map(EdgeKind::from)
+            .map(|(kind, count)| EdgeKindCountDto {
+                kind: EdgeKind::from(kind),
+                count: clamp_u64_to_u32(count),
+            })
             .collect::<Vec<_>>();
         Ok(TrailFilterOptionsDto {
-            node_kinds,
-            edge_kinds,
+            node_kinds: node_kind_counts.iter().map(|entry| entry.kind).collect(),
+            edge_kinds: edge_kind_counts.iter().map(|entry| entry.kind).collect(),
+            node_kind_counts,
+            edge_kind_counts,
+            presets: crate::controller_trail_presets::trail_filter_presets(&storage)?,
+        })
+    }
+
+    pub fn reachable_set(&self, req: ReachableSetRequest) -> Result<ReachableSetDto, ApiError> {
+        let id = req.id.to_core()?;
+        let root = self.require_project_root()?;
+        let storage = self.open_storage_read_only()?;
+        let edge_kinds = req
+            .edge_kinds
+            .into_iter()
+            .map(Into::into)
+            .collect::<Vec<_>>();
+        let max_nodes = req.max_nodes.unwrap_or(10_000).clamp(1, 100_000) as usize;
+        let set = storage
+            .reachable_set(id, req.direction.into(), &edge_kinds, max_nodes)
+            .map_err(|e| ApiError::internal(format!("Failed to compute reachable set: {e}")))?;
+        Ok(ReachableSetDto {
+            id: req.id,
+            node_ids: set.node_ids.into_iter().map(NodeId::from).collect(),
+            kind_counts: set
+                .kind_counts
+                .into_iter()
+                .map(|(kind, count)| NodeKindCountDto {
+                    kind: NodeKind::from(kind),
+                    count,
+                })
+                .collect(),
+            file_counts: set
+                .file_counts
+                .into_iter()
+                .map(|entry| ReachableFileCountDto {
+                    path: runtime_relative_path(&root, std::path::Path::new(&entry.path)),
+                    count: entry.count,
+                })
+                .collect(),
+            max_depth: set.max_depth,
+            truncated: set.truncated,
         })
     }
 
+    /// Source nodes with an edge matching `query`, at most `limit` of them,
+    /// with file paths relative to the project root.
+    pub fn graph_matches(
+        &self,
+        query: &MatchQuery,
+        limit: usize,
+    ) -> Result<GraphMatches, ApiError> {
+        let root = self.require_project_root()?;
+        let storage = self.open_storage_read_only()?;
+        let filter = GraphMatchFilter {
+            from_prefix: query.from.clone(),
+            from_kind: query.from_kind.map(Into::into),
+            edge_kind: query.edge.map(Into::into),
+            to_prefix: query.to.clone(),
+            to_kind: query.to_kind.map(Into::into),
+        };
+        let mut matches = storage
+            .match_edges(&filter, limit)
+            .map_err(|e| ApiError::internal(format!("Failed to match graph edges: {e}")))?;
+        for found in &mut matches.matches {
+            found.source_file = found
+                .source_file
+                .take()
+                .map(|path| runtime_relative_path(&root, std::path::Path::new(&path)));
+        }
+        Ok(matches)
+    }
+
+    /// Tests whose calls reach `id`, nearest first.
+    pub fn tests_covering(&self, id: NodeId) -> Result<Vec<TestLinkDto>, ApiError> {
+        let core_id = id.to_core()?;
+        let storage = self.open_storage_read_only()?;
+        let reached = storage
+            .tests_covering(core_id)
+            .map_err(|e| ApiError::internal(format!("Failed to find covering tests: {e}")))?;
+        self.test_links(&storage, reached)
+    }
+
+    /// Production functions and methods the test `id` reaches, nearest first.
+    pub fn code_tested_by(&self, id: NodeId) -> Result<Vec<TestLinkDto>, ApiError> {
+        let core_id = id.to_core()?;
+        let storage = self.open_storage_read_only()?;
+        let reached = storage
+            .code_tested_by(core_id)
+            .map_err(|e| ApiError::internal(format!("Failed to find tested code: {e}")))?;
+        self.test_links(&storage, reached)
+    }
+
+    /// Entry points plus the size of each call-distance layer below them.
+    pub fn entry_point_overview(&self) -> Result<EntryPointOverviewDto, ApiError> {
+        let storage = self.open_storage_read_only()?;
+        let reach = storage
+            .entry_point_distances()
+            .map_err(|e| ApiError::internal(format!("Failed to walk entry points: {e}")))?;
+        let mut layer_sizes = Vec::new();
+        for distance in reach.distances.values() {
+            let layer = *distance as usize;
+            if layer_sizes.len() <= layer {
+                layer_sizes.resize(layer + 1, 0u32);
+            }
+            layer_sizes[layer] += 1;
+        }
+        let nodes = storage
+            .get_nodes_by_ids(&reach.entry_points)
+            .map_err(|e| ApiError::internal(format!("Failed to load entry points: {e}")))?;
+        let file_paths = self.file_paths_for(&storage, nodes.values())?;
+        let labels = self.cached_labels(reach.entry_points.iter().copied());
+        let entry_points = reach
+            .entry_points
+            .iter()
+            .filter_map(|id| nodes.get(id))
+            .map(|node| EntryPointDto {
+                id: NodeId::from(node.id),
+                kind: NodeKind::from(node.kind),
+                display_name: labels
+                    .get(&node.id)
+                    .cloned()
+                    .unwrap_or_else(|| node_display_name(node)),
+                file_path: node
+                    .file_node_id
+                    .and_then(|file_id| file_paths.get(&file_id).cloned()),
+                line: node.start_line,
+            })
+            .collect();
+        Ok(EntryPointOverviewDto {
+            entry_points,
+            layer_sizes,
+            reachable_count: clamp_u64_to_u32(reach.distances.len() as u64),
+            truncated: reach.truncated,
+        })
+    }
+
+    pub(crate) fn file_paths_for<'a>(
+        &self,
+        storage: &Storage,
+        nodes: impl Iterator<Item = &'a GraphNode>,
+    ) -> Result<HashMap<codestory_contracts::graph::NodeId, String>, ApiError> {
+        let file_ids = nodes
+            .filter_map(|node| node.file_node_id)
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect::<Vec<_>>();
+        let files = storage
+            .get_nodes_by_ids(&file_ids)
+            .map_err(|e| ApiError::internal(format!("Failed to load files: {e}")))?;
+        Ok(files
+            .into_iter()
+            .map(|(id, file)| (id, file.serialized_name))
+            .collect())
+    }
+
+    pub(crate) fn test_links(
+        &self,
+        storage: &Storage,
+        reached: Vec<codestory_store::TestReach>,
+    ) -> Result<Vec<TestLinkDto>, ApiError> {
+        let ids = reached
+            .iter()
+            .map(|reach| reach.node_id)
+            .collect::<Vec<_>>();
+        let nodes = storage
+            .get_nodes_by_ids(&ids)
+            .map_err(|e| ApiError::internal(format!("Failed to load linked nodes: {e}")))?;
+        let file_paths = self.file_paths_for(storage, nodes.values())?;
+        let labels = self.cached_labels(ids);
+        Ok(reached
+            .into_iter()
+            .filter_map(|reach| {
+                let node = nodes.get(&reach.node_id)?;
+                Some(TestLinkDto {
+                    id: NodeId::from(node.id),
+                    kind: NodeKind::from(node.kind),
+                    display_name: labels
+                        .get(&node.id)
+                        .cloned()
+                        .unwrap_or_else(|| node_display_name(node)),
+                    file_path: node
+                        .file_node_id
+                        .and_then(|file_id| file_paths.get(&file_id).cloned()),
+                    line: node.start_line,
+                    distance: reach.distance,
+                })
+            })
+            .collect())
+    }
+
     pub fn node_details(&self, req: NodeDetailsRequest) -> Result<NodeDetailsDto, ApiError> {
         let id = req.id.to_core()?;
 
@@ -316,6 +749,7 @@ impl AppController {
             .canonical_id
             .as_deref()
             .is_some_and(|value| value.starts_with("openapi:endpoint:"));
+        let definitions = self.node_definitions(&storage, node.id)?;
 
         Ok(NodeDetailsDto {
             id: NodeId::from(node.id),
@@ -344,6 +778,39 @@ impl AppController {
                 .then_some(codestory_contracts::api::PacketEvidenceResolutionDto::SourceRangeOnly),
             member_access: member_access_dto(storage.get_component_access(node.id).ok().flatten()),
             route_endpoint,
+            definitions,
+        })
+    }
+
+    /// Tooltip summary for `id`, read with a single storage query.
+    pub fn node_preview(&self, id: NodeId) -> Result<NodePreviewDto, ApiError> {
+        let core_id = id.to_core()?;
+        let storage = self.open_storage_read_only()?;
+        let preview = storage
+            .get_node_preview(core_id)
+            .map_err(|e| ApiError::internal(format!("Failed to query node preview: {e}")))?
+            .ok_or_else(|| ApiError::not_found(format!("Node not found: {core_id}")))?;
+        let node = preview.node;
+        let display_name = self
+            .state
+            .lock()
+            .node_names
+            .get(&node.id)
+            .cloned()
+            .unwrap_or_else(|| node_display_name(&node));
+
+        Ok(NodePreviewDto {
+            id,
+            kind: NodeKind::from(node.kind),
+            display_name,
+            qualified_name: node.qualified_name,
+            doc: preview.summary,
+            member_access: member_access_dto(preview.access),
+            caller_count: preview.caller_count,
+            callee_count: preview.callee_count,
+            member_count: preview.member_count,
+            file_path: preview.file_path,
+            line: node.start_line,
         })
     }
 
@@ -449,9 +916,71 @@ impl AppController {
         })
     }
 
+    /// Every occurrence of `req.id`, or only the requested page when
+    /// `offset` or `limit` is set.
     pub fn node_occurrences(
         &self,
         req: NodeOccurrencesRequest,
+    ) -> Result<Vec<SourceOccurrenceDto>, ApiError> {
+        if req.offset.is_some() || req.limit.is_some() {
+            return self.node_occurrences_page(req).map(|page| page.items);
+        }
+        self.all_node_occurrences(&req)
+    }
+
+    pub fn node_occurrences_page(
+        &self,
+        req: NodeOccurrencesRequest,
+    ) -> Result<PageDto<SourceOccurrenceDto>, ApiError> {
+        let id = req.id.to_core()?;
+        let Some(kind) = Self::occurrence_kind_filter(req.kind.as_deref()) else {
+            return Ok(PageDto::from_items(Vec::new(), req.offset, req.limit));
+        };
+        let storage = self.open_storage_read_only()?;
+        let total = storage
+            .get_occurrence_count_for_node(id, kind)
+            .map_err(|e| ApiError::internal(format!("Failed to count node occurrences: {e}")))?;
+        PageDto::fetch(total, req.offset, req.limit, |offset, limit| {
+            let mut occurrences = storage
+                .get_occurrences_for_node_page(id, kind, offset, limit)
+                .map_err(|e| ApiError::internal(format!("Failed to load node occurrences: {e}")))?
+                .into_iter()
+                .filter_map(|occurrence| {
+                    Self::to_source_occurrence_dto(&storage, occurrence).transpose()
+                })
+                .collect::<Result<Vec<_>, ApiError>>()?;
+            self.clamp_stale_occurrences(&storage, &mut occurrences)?;
+            Ok(occurrences)
+        })
+    }
+
+    /// The occurrence kind a `kind` label names: `Some(None)` for no label,
+    /// `None` for a label naming no kind.
+    fn occurrence_kind_filter(
+        label: Option<&str>,
+    ) -> Option<Option<codestory_contracts::graph::OccurrenceKind>> {
+        use codestory_contracts::graph::OccurrenceKind;
+        let Some(label) = label else {
+            return Some(None);
+        };
+        [
+            OccurrenceKind::DEFINITION,
+            OccurrenceKind::REFERENCE,
+            OccurrenceKind::DECLARATION,
+            OccurrenceKind::MACRO_DEFINITION,
+            OccurrenceKind::MACRO_REFERENCE,
+            OccurrenceKind::UNKNOWN,
+            OccurrenceKind::WRITE_REFERENCE,
+            OccurrenceKind::READ_REFERENCE,
+        ]
+        .into_iter()
+        .find(|kind| Self::occurrence_kind_label(*kind).eq_ignore_ascii_case(label))
+        .map(Some)
+    }
+
+    fn all_node_occurrences(
+        &self,
+        req: &NodeOccurrencesRequest,
     ) -> Result<Vec<SourceOccurrenceDto>, ApiError> {
         let id = req.id.to_core()?;
         let storage = self.open_storage_read_only()?;
@@ -459,10 +988,16 @@ impl AppController {
             .get_occurrences_for_node(id)
             .map_err(|e| ApiError::internal(format!("Failed to load node occurrences: {e}")))?
             .into_iter()
+            .filter(|occurrence| {
+                req.kind.as_deref().is_none_or(|kind| {
+                    Self::occurrence_kind_label(occurrence.kind).eq_ignore_ascii_case(kind)
+                })
+            })
             .filter_map(|occurrence| {
                 Self::to_source_occurrence_dto(&storage, occurrence).transpose()
             })
             .collect::<Result<Vec<_>, ApiError>>()?;
+        self.clamp_stale_occurrences(&storage, &mut occurrences)?;
 
         occurrences.sort_by(|left, right| {
             left.file_path
@@ -481,14 +1016,22 @@ impl AppController {
     ) -> Result<Vec<SourceOccurrenceDto>, ApiError> {
         let id = req.id.to_core()?;
         let storage = self.open_storage_read_only()?;
-        let mut occurrences = storage
-            .get_occurrences_for_element(id.0)
-            .map_err(|e| ApiError::internal(format!("Failed to load edge occurrences: {e}")))?
+        let mut callsites = storage
+            .get_callsites_for_edge(id)
+            .map_err(|e| ApiError::internal(format!("Failed to load edge call sites: {e}")))?;
+        if callsites.is_empty() {
+            // Projections written before occurrences carried edge links.
+            callsites = storage
+                .get_occurrences_for_element(id.0)
+                .map_err(|e| ApiError::internal(format!("Failed to load edge occurrences: {e}")))?;
+        }
+        let mut occurrences = callsites
             .into_iter()
             .filter_map(|occurrence| {
                 Self::to_source_occurrence_dto(&storage, occurrence).transpose()
             })
             .collect::<Result<Vec<_>, ApiError>>()?;
+        self.clamp_stale_occurrences(&storage, &mut occurrences)?;
 
         occurrences.sort_by(|left, right| {
             left.file_path
@@ -0,0 +1,60 @@
+use crate::{AppController, Storage};
+use codestory_contracts::api::{ApiError, TrailConfigDto, TrailDefaultsDto};
+
+const TRAIL_DEFAULTS_SETTING: &str = "trail_defaults";
+
+pub(crate) fn load_trail_defaults(storage: &Storage) -> Result<TrailDefaultsDto, ApiError> {
+    let Some(value) = storage
+        .project_setting(TRAIL_DEFAULTS_SETTING)
+        .map_err(|e| ApiError::internal(format!("Failed to load trail defaults: {e}")))?
+    else {
+        return Ok(TrailDefaultsDto::default());
+    };
+    serde_json::from_str(&value)
+        .map_err(|e| ApiError::internal(format!("Stored trail defaults are invalid: {e}")))
+}
+
+/// Fill the fields `req` leaves unset from the project's trail defaults.
+pub(crate) fn apply_trail_defaults(
+    storage: &Storage,
+    req: &mut TrailConfigDto,
+) -> Result<(), ApiError> {
+    load_trail_defaults(storage)?.apply_to(req);
+    Ok(())
+}
+
+impl AppController {
+    /// The project's trail defaults. Unset fields use the built-in defaults.
+    pub fn trail_defaults(&self) -> Result<TrailDefaultsDto, ApiError> {
+        let storage = self.open_storage_read_only()?;
+        load_trail_defaults(&storage)
+    }
+
+    /// Replace the project's trail defaults. Saving the empty defaults
+    /// restores the built-in ones.
+    pub fn set_trail_defaults(
+        &self,
+        defaults: TrailDefaultsDto,
+    ) -> Result<TrailDefaultsDto, ApiError> {
+        if defaults
+            .max_nodes
+            .is_some_and(|max_nodes| !(10..=100_000).contains(&max_nodes))
+        {
+            return Err(ApiError::invalid_argument(
+                "Trail default max_nodes must be between 10 and 100000.",
+            ));
+        }
+        let storage = self.open_storage()?;
+        let result = if defaults == TrailDefaultsDto::default() {
+            storage
+                .delete_project_setting(TRAIL_DEFAULTS_SETTING)
+                .map(|_| ())
+        } else {
+            let value = serde_json::to_string(&defaults)
+                .map_err(|e| ApiError::internal(format!("Failed to encode trail defaults: {e}")))?;
+            storage.set_project_setting(TRAIL_DEFAULTS_SETTING, &value)
+        };
+        result.map_err(|e| ApiError::internal(format!("Failed to save trail defaults: {e}")))?;
+        Ok(defaults)
+    }
+}
@@ -0,0 +1,172 @@
+use crate::{AppController, Storage};
+use codestory_contracts::api::{
+    ApiError, EdgeKind, NodeKind, TrailConfigDto, TrailFilterPresetDto,
+};
+use codestory_store::TrailFilterPreset;
+
+fn trail_filter_preset_dto(preset: TrailFilterPreset) -> TrailFilterPresetDto {
+    TrailFilterPresetDto {
+        name: preset.name,
+        edge_kinds: preset.edge_kinds.into_iter().map(EdgeKind::from).collect(),
+        node_kinds: preset.node_kinds.into_iter().map(NodeKind::from).collect(),
+    }
+}
+
+pub(crate) fn trail_filter_presets(
+    storage: &Storage,
+) -> Result<Vec<TrailFilterPresetDto>, ApiError> {
+    let presets = storage
+        .trail_filter_presets()
+        .map_err(|e| ApiError::internal(format!("Failed to load trail filter presets: {e}")))?;
+    Ok(presets.into_iter().map(trail_filter_preset_dto).collect())
+}
+
+/// Fill the edge and node filters `req` leaves empty from the preset it names.
+pub(crate) fn apply_trail_filter_preset(
+    storage: &Storage,
+    req: &mut TrailConfigDto,
+) -> Result<(), ApiError> {
+    let Some(name) = req.filter_preset.as_deref() else {
+        return Ok(());
+    };
+    let preset = storage
+        .trail_filter_preset(name)
+        .map_err(|e| ApiError::internal(format!("Failed to load trail filter preset: {e}")))?
+        .ok_or_else(|| ApiError::not_found(format!("Trail filter preset not found: {name}")))?;
+    if req.edge_filter.is_empty() {
+        req.edge_filter = preset.edge_kinds.into_iter().map(EdgeKind::from).collect();
+    }
+    if req.node_filter.is_empty() {
+        req.node_filter = preset.node_kinds.into_iter().map(NodeKind::from).collect();
+    }
+    Ok(())
+}
+
+impl AppController {
+    /// Saved trail filter presets, ordered by name.
+    pub fn trail_filter_presets(&self) -> Result<Vec<TrailFilterPresetDto>, ApiError> {
+        let storage = self.open_storage_read_only()?;
+        trail_filter_presets(&storage)
+    }
+
+    /// Save a named trail filter preset, replacing one with the same name.
+    /// Returns every saved preset.
+    pub fn save_trail_filter_preset(
+        &self,
+        preset: TrailFilterPresetDto,
+    ) -> Result<Vec<TrailFilterPresetDto>, ApiError> {
+        let name = preset.name.trim();
+        if name.is_empty() {
+            return Err(ApiError::invalid_argument(
+                "Trail filter preset name is empty.",
+            ));
+        }
+        if preset.edge_kinds.is_empty() && preset.node_kinds.is_empty() {
+            return Err(ApiError::invalid_argument(
+                "A trail filter preset needs at least one edge or node kind.",
+            ));
+        }
+        let storage = self.open_storage()?;
+        storage
+            .save_trail_filter_preset(&TrailFilterPreset {
+                name: name.to_string(),
+                edge_kinds: preset.edge_kinds.into_iter().map(Into::into).collect(),
+                node_kinds: preset.node_kinds.into_iter().map(Into::into).collect(),
+            })
+            .map_err(|e| ApiError::internal(format!("Failed to save trail filter preset: {e}")))?;
+        trail_filter_presets(&storage)
+    }
+
+    /// Delete a saved trail filter preset. Returns whether it existed.
+    pub fn delete_trail_filter_preset(&self, name: &str) -> Result<bool, ApiError> {
+        let storage = self.open_storage()?;
+        storage
+            .delete_trail_filter_preset(name.trim())
+            .map_err(|e| ApiError::internal(format!("Failed to delete trail filter preset: {e}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codestory_contracts::api::NodeId;
+    use codestory_store::Store;
+    use tempfile::tempdir;
+
+    fn trail_request(preset: Option<&str>) -> TrailConfigDto {
+        let mut req: TrailConfigDto =
+            serde_json::from_value(serde_json::json!({"root_id": "1"})).expect("trail request");
+        req.filter_preset = preset.map(str::to_string);
+        req
+    }
+
+    #[test]
+    fn saved_presets_fill_empty_trail_filters() {
+        let project = tempdir().expect("project");
+        let storage_path = project.path().join("codestory.db");
+        drop(Store::open(&storage_path).expect("create store"));
+        let controller = AppController::new();
+        {
+            let mut state = controller.state.lock();
+            state.project_root = Some(project.path().to_path_buf());
+            state.storage_path = Some(storage_path);
+        }
+
+        let saved = controller
+            .save_trail_filter_preset(TrailFilterPresetDto {
+                name: " calls ".to_string(),
+                edge_kinds: vec![EdgeKind::CALL, EdgeKind::OVERRIDE],
+                node_kinds: vec![NodeKind::FUNCTION],
+            })
+            .expect("save preset");
+        assert_eq!(saved.len(), 1);
+        assert_eq!(saved[0].name, "calls");
+        assert_eq!(controller.trail_filter_presets().expect("list"), saved);
+
+        let storage = controller.open_storage_read_only().expect("storage");
+        let mut req = trail_request(Some("calls"));
+        apply_trail_filter_preset(&storage, &mut req).expect("apply preset");
+        assert_eq!(req.edge_filter, vec![EdgeKind::CALL, EdgeKind::OVERRIDE]);
+        assert_eq!(req.node_filter, vec![NodeKind::FUNCTION]);
+        assert_eq!(req.root_id, NodeId("1".to_string()));
+
+        let mut explicit = trail_request(Some("calls"));
+        explicit.edge_filter = vec![EdgeKind::MEMBER];
+        apply_trail_filter_preset(&storage, &mut explicit).expect("apply preset");
+        assert_eq!(
+            explicit.edge_filter,
+            vec![EdgeKind::MEMBER],
+            "explicit filters win"
+        );
+        assert_eq!(explicit.node_filter, vec![NodeKind::FUNCTION]);
+
+        let mut unfiltered = trail_request(None);
+        apply_trail_filter_preset(&storage, &mut unfiltered).expect("no preset");
+        assert!(unfiltered.edge_filter.is_empty() && unfiltered.node_filter.is_empty());
+
+        let missing = apply_trail_filter_preset(&storage, &mut trail_request(Some("gone")))
+            .expect_err("unknown preset");
+        assert_eq!(missing.code, "not_found");
+        drop(storage);
+
+        let empty = controller
+            .save_trail_filter_preset(TrailFilterPresetDto {
+                name: "empty".to_string(),
+                edge_kinds: Vec::new(),
+                node_kinds: Vec::new(),
+            })
+            .expect_err("empty preset");
+        assert_eq!(empty.code, "invalid_argument");
+        assert!(
+            controller
+                .delete_trail_filter_preset("calls")
+                .expect("delete")
+        );
+        assert!(
+            !controller
+                .delete_trail_filter_preset("calls")
+                .expect("delete again")
+        );
+        assert!(controller.trail_filter_presets().expect("list").is_empty());
+    }
+}
@@ -0,0 +1,96 @@
+use crate::{AppController, Storage};
+use codestory_contracts::api::ApiError;
+use codestory_contracts::events::EventBus;
+use codestory_indexer::dependency_sources::{
+    DependencySourceLocations, build_dependency_storage, discover_dependency_sources,
+};
+use std::path::{Path, PathBuf};
+
+const DEPENDENCY_RESOLUTION_SETTING: &str = "dependency_resolution";
+
+/// Where the dependency sources of the project stored at `storage_path` are
+/// indexed, next to the project database.
+pub(crate) fn dependency_storage_path(storage_path: &Path) -> PathBuf {
+    storage_path.with_extension("dependencies.db")
+}
+
+fn dependency_resolution_enabled(storage_path: &Path) -> bool {
+    if !storage_path.is_file() {
+        return false;
+    }
+    Storage::open_read_only(storage_path)
+        .and_then(|storage| storage.project_setting(DEPENDENCY_RESOLUTION_SETTING))
+        .is_ok_and(|value| value.is_some())
+}
+
+/// Dependency storage for an indexing run of the project at `root`, or
+/// `None` when dependency resolution is off. The storage is built when
+/// missing or when `rebuild` is set, as on a full refresh, so dependencies
+/// that changed are picked up. A build that fails only disables resolution
+/// for the run.
+pub(crate) fn prepare_dependency_storage(
+    root: &Path,
+    storage_path: &Path,
+    rebuild: bool,
+) -> Option<PathBuf> {
+    if !dependency_resolution_enabled(storage_path) {
+        return None;
+    }
+    let path = dependency_storage_path(storage_path);
+    if path.is_file() && !rebuild {
+        return Some(path);
+    }
+    match build_dependency_storage_at(root, &path) {
+        Ok(files) => {
+            tracing::debug!(files, path = %path.display(), "Built dependency storage");
+            Some(path)
+        }
+        Err(error) => {
+            tracing::warn!(
+                "Failed to build dependency storage; skipping dependency resolution: {error}"
+            );
+            None
+        }
+    }
+}
+
+fn build_dependency_storage_at(root: &Path, path: &Path) -> anyhow::Result<usize> {
+    let sources = discover_dependency_sources(root, &DependencySourceLocations::from_env())?;
+    let partial = path.with_extension("db.partial");
+    let _ = std::fs::remove_file(&partial);
+    let mut storage = Storage::open(&partial)
+        .map_err(|error| anyhow::anyhow!("Failed to open dependency storage: {error}"))?;
+    let files = build_dependency_storage(&mut storage, &sources, &EventBus::new())?;
+    drop(storage);
+    std::fs::rename(&partial, path)?;
+    Ok(files)
+}
+
+impl AppController {
+    /// Whether indexing resolves calls into the sources of declared
+    /// dependencies.
+    pub fn dependency_resolution(&self) -> Result<bool, ApiError> {
+        let storage = self.open_storage_read_only()?;
+        storage
+            .project_setting(DEPENDENCY_RESOLUTION_SETTING)
+            .map(|value| value.is_some())
+            .map_err(|e| ApiError::internal(format!("Failed to load dependency resolution: {e}")))
+    }
+
+    /// Turn dependency resolution on or off. It applies from the next
+    /// indexing run, which also drops nodes copied in while it was on.
+    pub fn set_dependency_resolution(&self, enabled: bool) -> Result<bool, ApiError> {
+        let storage = self.open_storage()?;
+        let result = if enabled {
+            storage.set_project_setting(DEPENDENCY_RESOLUTION_SETTING, "on")
+        } else {
+            storage
+                .delete_project_setting(DEPENDENCY_RESOLUTION_SETTING)
+                .map(|_| ())
+        };
+        result.map_err(|e| {
+            ApiError::internal(format!("Failed to save dependency resolution: {e}"))
+        })?;
+        Ok(enabled)
+    }
+}
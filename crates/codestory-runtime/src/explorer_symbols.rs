@@ -0,0 +1,136 @@
+use crate::{AppController, runtime_relative_path};
+use codestory_contracts::api::{
+    ApiError, ExplorerSymbolDto, ExplorerSymbolGroupDto, ExplorerSymbolsDto,
+    ExplorerSymbolsRequest, NodeId, NodeKind,
+};
+use codestory_store::WorkspaceSymbolFilter;
+use std::path::Path;
+
+const EXPLORER_SYMBOLS_DEFAULT_LIMIT: u32 = 50;
+const EXPLORER_SYMBOLS_MAX_LIMIT: u32 = 500;
+
+impl AppController {
+    /// Symbols whose qualified name contains `req.query`, grouped by kind
+    /// with the number of matches of each kind.
+    pub fn explorer_symbols(
+        &self,
+        req: ExplorerSymbolsRequest,
+    ) -> Result<ExplorerSymbolsDto, ApiError> {
+        let limit = req
+            .limit
+            .unwrap_or(EXPLORER_SYMBOLS_DEFAULT_LIMIT)
+            .clamp(1, EXPLORER_SYMBOLS_MAX_LIMIT) as usize;
+        let root = self.require_project_root()?;
+        let storage = self.open_storage_read_only()?;
+        let filter = WorkspaceSymbolFilter {
+            name_contains: req.query,
+            kinds: req.kinds.into_iter().map(Into::into).collect(),
+            limit_per_kind: limit,
+        };
+        let groups = storage
+            .workspace_symbols(&filter)
+            .map_err(|e| ApiError::internal(format!("Failed to list workspace symbols: {e}")))?;
+        let labels = self.cached_labels(
+            groups
+                .iter()
+                .flat_map(|group| group.symbols.iter().map(|symbol| symbol.id)),
+        );
+        let groups = groups
+            .into_iter()
+            .map(|group| ExplorerSymbolGroupDto {
+                kind: NodeKind::from(group.kind),
+                count: group.count,
+                symbols: group
+                    .symbols
+                    .into_iter()
+                    .map(|symbol| ExplorerSymbolDto {
+                        id: NodeId::from(symbol.id),
+                        label: labels.get(&symbol.id).cloned().unwrap_or(symbol.name),
+                        file_path: symbol
+                            .file_path
+                            .map(|path| runtime_relative_path(&root, Path::new(&path))),
+                        line: symbol.line,
+                    })
+                    .collect(),
+            })
+            .collect::<Vec<_>>();
+        let total_count = groups
+            .iter()
+            .fold(0u32, |total, group| total.saturating_add(group.count));
+        Ok(ExplorerSymbolsDto {
+            groups,
+            total_count,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codestory_contracts::graph::{Node, NodeId as CoreNodeId, NodeKind as CoreNodeKind};
+    use codestory_store::Store;
+    use tempfile::tempdir;
+
+    #[test]
+    fn explorer_symbols_group_matches_by_kind_and_count_past_the_limit() {
+        let project = tempdir().expect("project");
+        let storage_path = project.path().join("codestory.db");
+        {
+            let mut store = Store::open(&storage_path).expect("create store");
+            let node = |id: i64, kind: CoreNodeKind, name: &str| Node {
+                id: CoreNodeId(id),
+                kind,
+                serialized_name: name.to_string(),
+                ..Default::default()
+            };
+            store
+                .insert_nodes_batch(&[
+                    node(1, CoreNodeKind::FUNCTION, "load_config"),
+                    node(2, CoreNodeKind::FUNCTION, "save_config"),
+                    node(3, CoreNodeKind::STRUCT, "Config"),
+                    node(4, CoreNodeKind::FUNCTION, "unrelated"),
+                ])
+                .expect("insert nodes");
+        }
+        let controller = AppController::new();
+        {
+            let mut state = controller.state.lock();
+            state.project_root = Some(project.path().to_path_buf());
+            state.storage_path = Some(storage_path);
+        }
+
+        let all = controller
+            .explorer_symbols(ExplorerSymbolsRequest {
+                query: Some("config".to_string()),
+                kinds: Vec::new(),
+                limit: None,
+            })
+            .expect("list symbols");
+        assert_eq!(all.total_count, 3);
+        let functions = all
+            .groups
+            .iter()
+            .find(|group| group.kind == NodeKind::FUNCTION)
+            .expect("function group");
+        assert_eq!(
+            functions
+                .symbols
+                .iter()
+                .map(|symbol| symbol.label.as_str())
+                .collect::<Vec<_>>(),
+            vec!["load_config", "save_config"]
+        );
+
+        let limited = controller
+            .explorer_symbols(ExplorerSymbolsRequest {
+                query: Some("config".to_string()),
+                kinds: vec![NodeKind::FUNCTION],
+                limit: Some(1),
+            })
+            .expect("list limited symbols");
+        assert_eq!(limited.groups.len(), 1);
+        assert_eq!(limited.groups[0].count, 2);
+        assert_eq!(limited.groups[0].symbols.len(), 1);
+        assert_eq!(limited.groups[0].symbols[0].id, NodeId("1".to_string()));
+    }
+}
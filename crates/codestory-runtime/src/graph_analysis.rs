@@ -10,6 +10,9 @@ use std::path::Path;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 const DEFAULT_REPORT_LIMIT: usize = 10;
+/// Scored nodes the report reads before dropping files, so the central
+/// symbols section can still fill its limit.
+const CENTRAL_NODE_CANDIDATE_LIMIT: usize = 500;
 
 #[derive(Debug, Clone, Serialize)]
 pub struct RepoReport {
@@ -18,6 +21,8 @@ pub struct RepoReport {
     pub hotspots: Vec<ReportNodeSummary>,
     pub entry_points: Vec<ReportNodeSummary>,
     pub bridge_nodes: Vec<ReportNodeSummary>,
+    /// Highest PageRank over call and usage edges, most central first.
+    pub central_symbols: Vec<ReportNodeSummary>,
     pub follow_up_queries: Vec<ReportFollowUpQuery>,
 }
 
@@ -80,6 +85,8 @@ pub struct ReportNodeSummary {
     pub outgoing_edges: usize,
     pub total_edges: usize,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub centrality: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub source_location: Option<SourceLocation>,
 }
 
@@ -167,6 +174,7 @@ struct ReportSource {
     edges: Vec<Edge>,
     nodes_by_id: HashMap<NodeId, Node>,
     degrees: HashMap<NodeId, NodeDegree>,
+    central_nodes: Vec<(NodeId, f32)>,
 }
 
 pub fn build_report(
@@ -232,12 +240,16 @@ fn load_report_source(storage_path: &Path) -> Result<ReportSource> {
         .map(|node| (node.id, node.clone()))
         .collect::<HashMap<_, _>>();
     let degrees = degree_map(&edges);
+    let central_nodes = storage
+        .most_central_nodes(CENTRAL_NODE_CANDIDATE_LIMIT)
+        .context("Failed to query CodeStory node centrality")?;
     Ok(ReportSource {
         stats,
         nodes,
         edges,
         nodes_by_id,
         degrees,
+        central_nodes,
     })
 }
 
@@ -262,6 +274,7 @@ fn build_report_from_source(
     let hotspots = top_hotspots(&source.nodes, &source.nodes_by_id, &source.degrees, limit);
     let entry_points = top_entry_points(&source.nodes, &source.nodes_by_id, &source.degrees, limit);
     let bridge_nodes = top_bridge_nodes(&source.nodes, &source.nodes_by_id, &source.degrees, limit);
+    let central_symbols = top_central_symbols(source, limit);
     let follow_up_queries =
         follow_up_queries(project_root, &entry_points, &bridge_nodes, &hotspots, limit);
 
@@ -280,6 +293,7 @@ fn build_report_from_source(
         hotspots,
         entry_points,
         bridge_nodes,
+        central_symbols,
         follow_up_queries,
     }
 }
@@ -380,6 +394,24 @@ fn top_bridge_nodes(
         .collect()
 }
 
+fn top_central_symbols(source: &ReportSource, limit: usize) -> Vec<ReportNodeSummary> {
+    source
+        .central_nodes
+        .iter()
+        .filter_map(|(id, centrality)| {
+            let node = source.nodes_by_id.get(id)?;
+            (node.kind != NodeKind::FILE).then(|| {
+                let degree = source.degrees.get(id).copied().unwrap_or_default();
+                ReportNodeSummary {
+                    centrality: Some(*centrality),
+                    ..report_node_summary(node, &source.nodes_by_id, degree)
+                }
+            })
+        })
+        .take(limit)
+        .collect()
+}
+
 fn follow_up_queries(
     project_root: &Path,
     entry_points: &[ReportNodeSummary],
@@ -453,6 +485,7 @@ fn report_node_summary(
         incoming_edges: degree.incoming,
         outgoing_edges: degree.outgoing,
         total_edges: degree.total(),
+        centrality: None,
         source_location: source_location_for_node(node, nodes_by_id),
     }
 }
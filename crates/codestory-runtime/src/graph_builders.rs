@@ -1,9 +1,105 @@
 use super::{
     ApiError, AppController, EdgeKind, GraphEdgeDto, GraphNodeDto, GraphRequest, GraphResponse,
-    NodeId, NodeKind, TrailConfigDto, app_graph_flags, graph_canonical, graph_edge_dto,
+    NodeId, NodeKind, Storage, TrailConfigDto, app_graph_flags, graph_canonical, graph_edge_dto,
     is_structural_kind, member_access_dto, node_display_name,
 };
+use codestory_contracts::api::{
+    CanonicalLayoutDto, EdgeId, GraphExpandRequest, NodeBadgesDto, NodeEdgeCountsDto, TrailMode,
+    TrailPathDto,
+};
 use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::{Duration, Instant};
+
+/// Trail deadline when the request sets none.
+const DEFAULT_TRAIL_TIMEOUT_MS: u32 = 30_000;
+const MIN_TRAIL_TIMEOUT_MS: u32 = 100;
+const MAX_TRAIL_TIMEOUT_MS: u32 = 600_000;
+
+/// Reuse the layout of an identical graph built from the same storage
+/// generation as `storage`.
+fn cached_canonical_layout(
+    controller: &AppController,
+    storage: &Storage,
+    center_id: &NodeId,
+    nodes: &[GraphNodeDto],
+    edges: &[GraphEdgeDto],
+) -> CanonicalLayoutDto {
+    // Without a generation a cached layout cannot be told from a stale one.
+    let Ok(generation) = storage.generation() else {
+        return graph_canonical::build_canonical_layout(center_id, nodes, edges);
+    };
+    let key = graph_canonical::canonical_layout_key(center_id, nodes, edges);
+    if let Some(layout) = controller
+        .state
+        .lock()
+        .canonical_layouts
+        .get(generation, key)
+    {
+        return layout;
+    }
+    let layout = graph_canonical::build_canonical_layout(center_id, nodes, edges);
+    controller
+        .state
+        .lock()
+        .canonical_layouts
+        .insert(generation, key, layout.clone());
+    layout
+}
+
+/// Whole-index call and member counts for every node in a response.
+fn node_edge_counts(
+    storage: &Storage,
+    node_ids: &[codestory_contracts::graph::NodeId],
+) -> Result<HashMap<codestory_contracts::graph::NodeId, NodeEdgeCountsDto>, ApiError> {
+    let counts = storage
+        .node_edge_counts(node_ids)
+        .map_err(|e| ApiError::internal(format!("Failed to count node edges: {e}")))?;
+    Ok(node_ids
+        .iter()
+        .map(|id| {
+            let count = counts.get(id).copied().unwrap_or_default();
+            let dto = NodeEdgeCountsDto {
+                outgoing_call_count: count.outgoing_calls,
+                incoming_call_count: count.incoming_calls,
+                member_count: count.members,
+            };
+            (*id, dto)
+        })
+        .collect())
+}
+
+/// Error and bookmark badges for every node in a response.
+fn node_badges(
+    storage: &Storage,
+    node_ids: &[codestory_contracts::graph::NodeId],
+) -> Result<HashMap<codestory_contracts::graph::NodeId, NodeBadgesDto>, ApiError> {
+    let badges = storage
+        .node_badges(node_ids)
+        .map_err(|e| ApiError::internal(format!("Failed to load node badges: {e}")))?;
+    Ok(badges
+        .into_iter()
+        .map(|(id, badge)| {
+            let dto = NodeBadgesDto {
+                has_errors: badge.has_errors,
+                is_bookmarked: badge.is_bookmarked,
+                has_annotation: badge.has_annotation,
+                marker_count: badge.marker_count,
+                is_external: badge.is_external,
+            };
+            (id, dto)
+        })
+        .collect())
+}
+
+/// Centrality scores for the nodes in a response that have one.
+fn node_centrality(
+    storage: &Storage,
+    node_ids: &[codestory_contracts::graph::NodeId],
+) -> Result<HashMap<codestory_contracts::graph::NodeId, f32>, ApiError> {
+    storage
+        .node_centrality(node_ids)
+        .map_err(|e| ApiError::internal(format!("Failed to load node centrality: {e}")))
+}
 
 pub(super) fn graph_neighborhood(
     controller: &AppController,
@@ -13,6 +109,16 @@ pub(super) fn graph_neighborhood(
     let graph_flags = app_graph_flags();
 
     let storage = controller.open_storage_read_only()?;
+    // A neighborhood is one hop in both directions, so only the edge filter
+    // and node budget of the project's trail defaults apply.
+    let defaults = crate::controller_trail_defaults::load_trail_defaults(&storage)?;
+    let edge_filter = defaults
+        .edge_filter
+        .iter()
+        .copied()
+        .map(codestory_contracts::graph::EdgeKind::from)
+        .collect::<HashSet<_>>();
+    let max_nodes = defaults.max_nodes.map(|max_nodes| max_nodes as usize);
 
     let max_edges = req.max_edges.unwrap_or(400).min(2_000) as usize;
     let mut edges = storage
@@ -50,7 +156,10 @@ pub(super) fn graph_neighborhood(
     }
 
     let mut seen_edge_ids = HashSet::new();
-    edges.retain(|edge| seen_edge_ids.insert(edge.id));
+    edges.retain(|edge| {
+        seen_edge_ids.insert(edge.id)
+            && (edge_filter.is_empty() || edge_filter.contains(&edge.kind))
+    });
     edges.sort_by_key(|e| e.id.0);
     let mut truncated = false;
     if edges.len() > max_edges {
@@ -65,8 +174,13 @@ pub(super) fn graph_neighborhood(
 
     let mut edge_dtos = Vec::with_capacity(edges.len());
     for edge in edges {
-        let edge = edge.with_effective_endpoints();
-        let (source, target) = (edge.source, edge.target);
+        let (source, target) = edge.effective_endpoints();
+        let new_nodes = usize::from(!seen.contains(&source))
+            + usize::from(source != target && !seen.contains(&target));
+        if max_nodes.is_some_and(|max_nodes| ordered_node_ids.len() + new_nodes > max_nodes) {
+            truncated = true;
+            continue;
+        }
 
         edge_dtos.push(graph_edge_dto(edge, graph_flags));
 
@@ -78,6 +192,9 @@ pub(super) fn graph_neighborhood(
         }
     }
 
+    let edge_counts = node_edge_counts(&storage, &ordered_node_ids)?;
+    let badges = node_badges(&storage, &ordered_node_ids)?;
+    let centrality = node_centrality(&storage, &ordered_node_ids)?;
     let mut node_dtos = Vec::with_capacity(ordered_node_ids.len());
     for id in ordered_node_ids {
         let (label, kind, file_path, qualified_name, member_access) = match storage.get_node(id) {
@@ -108,30 +225,44 @@ pub(super) fn graph_neighborhood(
             file_path,
             qualified_name,
             member_access,
+            edge_counts: edge_counts.get(&id).copied(),
+            badges: badges.get(&id).copied().unwrap_or_default(),
+            centrality: centrality.get(&id).copied(),
         });
     }
 
     let center_id = NodeId::from(center);
     let canonical_layout =
-        graph_canonical::build_canonical_layout(&center_id, &node_dtos, &edge_dtos);
+        cached_canonical_layout(controller, &storage, &center_id, &node_dtos, &edge_dtos);
 
     Ok(GraphResponse {
         center_id,
         nodes: node_dtos,
         edges: edge_dtos,
         truncated,
+        truncated_by_timeout: false,
         omitted_edge_count: 0,
+        pruned_test_node_count: 0,
+        pruned_accessor_node_count: 0,
+        pruned_generated_node_count: 0,
+        omitted_node_count: 0,
+        node_limit: None,
+        paths: Vec::new(),
         canonical_layout: Some(canonical_layout),
     })
 }
 
 pub(super) fn graph_trail(
     controller: &AppController,
-    req: TrailConfigDto,
+    mut req: TrailConfigDto,
 ) -> Result<GraphResponse, ApiError> {
     let root_id = req.root_id.to_core()?;
+    let storage = controller.open_storage_read_only()?;
+    crate::controller_trail_presets::apply_trail_filter_preset(&storage, &mut req)?;
+    crate::controller_trail_defaults::apply_trail_defaults(&storage, &mut req)?;
     let graph_flags = app_graph_flags();
     let hide_speculative = req.hide_speculative;
+    let graph_session = req.graph_session;
     let target_id = match req.target_id {
         Some(id) => Some(id.to_core()?),
         None => None,
@@ -141,18 +272,31 @@ pub(super) fn graph_trail(
         root_id,
         mode: req.mode.into(),
         target_id,
-        depth: req.depth,
-        direction: req.direction.into(),
-        caller_scope: req.caller_scope.into(),
+        depth: req.depth_or_default(),
+        direction: req.direction_or_default().into(),
+        caller_scope: req.caller_scope_or_default().into(),
         edge_filter: req.edge_filter.into_iter().map(Into::into).collect(),
         show_utility_calls: req.show_utility_calls,
         node_filter: req.node_filter.into_iter().map(Into::into).collect(),
-        max_nodes: req.max_nodes.clamp(10, 100_000) as usize,
+        hide_trivial_accessors: req.hide_trivial_accessors,
+        hide_generated: req.hide_generated,
+        entry_reachable_only: req.entry_reachable_only,
+        max_paths: req.max_paths,
+        edge_weight: req.edge_weight.into(),
+        max_nodes: req.max_nodes_or_default().clamp(10, 100_000) as usize,
     };
 
-    let storage = controller.open_storage_read_only()?;
+    let timeout_ms = req
+        .timeout_ms
+        .unwrap_or(DEFAULT_TRAIL_TIMEOUT_MS)
+        .clamp(MIN_TRAIL_TIMEOUT_MS, MAX_TRAIL_TIMEOUT_MS);
+    let stop = codestory_store::TrailStop::new(
+        Some(Instant::now() + Duration::from_millis(u64::from(timeout_ms))),
+        crate::services::active_public_operation_cancellation(),
+    );
+
     let result = storage
-        .get_trail(&config)
+        .get_trail_until(&config, &stop)
         .map_err(|e| ApiError::internal(format!("Failed to compute trail: {e}")))?;
 
     let codestory_contracts::graph::TrailResult {
@@ -161,6 +305,13 @@ pub(super) fn graph_trail(
         depth_map,
         truncated,
         omitted_edge_count,
+        pruned_test_node_count,
+        pruned_accessor_node_count,
+        pruned_generated_node_count,
+        omitted_node_count,
+        paths,
+        truncated_by_timeout,
+        mut collapsed_edge_ids,
     } = result;
 
     let node_kind_by_id: HashMap<
@@ -190,6 +341,10 @@ pub(super) fn graph_trail(
         }
     }
 
+    let node_ids = nodes.iter().map(|node| node.id).collect::<Vec<_>>();
+    let edge_counts = node_edge_counts(&storage, &node_ids)?;
+    let badges = node_badges(&storage, &node_ids)?;
+    let centrality = node_centrality(&storage, &node_ids)?;
     let mut node_dtos = Vec::with_capacity(nodes.len());
     for node in nodes {
         let label = node_display_name(&node);
@@ -222,57 +377,157 @@ pub(super) fn graph_trail(
             file_path: AppController::file_path_for_node(&storage, &node)?,
             qualified_name: node.qualified_name.clone(),
             member_access: member_access_dto(member_access),
+            edge_counts: edge_counts.get(&node.id).copied(),
+            badges: badges.get(&node.id).copied().unwrap_or_default(),
+            centrality: centrality.get(&node.id).copied(),
         });
     }
 
     let mut edge_dtos = Vec::with_capacity(edges.len());
     for edge in edges {
-        let edge = edge.with_effective_endpoints();
-        edge_dtos.push(graph_edge_dto(edge, graph_flags));
+        let collapsed = collapsed_edge_ids.remove(&edge.id).unwrap_or_default();
+        let mut dto = graph_edge_dto(edge, graph_flags);
+        dto.collapsed_edge_ids = collapsed.into_iter().map(EdgeId::from).collect();
+        edge_dtos.push(dto);
     }
 
     let center_id = NodeId::from(config.root_id);
     let canonical_layout =
-        graph_canonical::build_canonical_layout(&center_id, &node_dtos, &edge_dtos);
+        cached_canonical_layout(controller, &storage, &center_id, &node_dtos, &edge_dtos);
 
     let mut response = GraphResponse {
         center_id,
         nodes: node_dtos,
         edges: edge_dtos,
         truncated,
+        truncated_by_timeout,
         omitted_edge_count,
+        pruned_test_node_count,
+        pruned_accessor_node_count,
+        pruned_generated_node_count,
+        omitted_node_count,
+        node_limit: Some(config.max_nodes as u32),
+        paths: paths
+            .into_iter()
+            .map(|path| TrailPathDto {
+                node_ids: path.node_ids.into_iter().map(NodeId::from).collect(),
+                edge_ids: path.edge_ids.into_iter().map(EdgeId::from).collect(),
+            })
+            .collect(),
         canonical_layout: Some(canonical_layout),
     };
     if hide_speculative {
         response = hide_speculative_trail_edges(response);
     }
     response = suppress_default_trail_noise(response);
+    apply_graph_session(
+        controller,
+        &storage,
+        graph_session.as_deref(),
+        &mut response,
+    );
+    Ok(response)
+}
+
+/// Apply the session's hidden and collapsed nodes, relaying out the graph
+/// when they change it.
+fn apply_graph_session(
+    controller: &AppController,
+    storage: &Storage,
+    session: Option<&str>,
+    response: &mut GraphResponse,
+) {
+    let Some(session) = session else {
+        return;
+    };
+    let view = controller.state.lock().graph_sessions.view(session);
+    if view.apply(response) && response.canonical_layout.is_some() {
+        response.canonical_layout = Some(cached_canonical_layout(
+            controller,
+            storage,
+            &response.center_id,
+            &response.nodes,
+            &response.edges,
+        ));
+    }
+}
+
+/// One hop of trail around `req.node_id`, minus everything the caller
+/// already shows. Returned edges only touch nodes the caller will have after
+/// merging the response.
+pub(super) fn graph_expand(
+    controller: &AppController,
+    req: GraphExpandRequest,
+) -> Result<GraphResponse, ApiError> {
+    let existing_node_ids = req.existing_node_ids.into_iter().collect::<HashSet<_>>();
+    let existing_edge_ids = req.existing_edge_ids.into_iter().collect::<HashSet<_>>();
+    let mut response = graph_trail(
+        controller,
+        TrailConfigDto {
+            root_id: req.node_id.clone(),
+            mode: TrailMode::Neighborhood,
+            target_id: None,
+            depth: Some(1),
+            direction: Some(req.direction),
+            caller_scope: Some(req.caller_scope),
+            edge_filter: req.edge_filter,
+            show_utility_calls: req.show_utility_calls,
+            hide_speculative: req.hide_speculative,
+            story: false,
+            node_filter: req.node_filter,
+            hide_trivial_accessors: req.hide_trivial_accessors,
+            hide_generated: req.hide_generated,
+            entry_reachable_only: false,
+            max_paths: None,
+            edge_weight: Default::default(),
+            max_nodes: Some(req.max_nodes),
+            layout_direction: Default::default(),
+            graph_session: req.graph_session,
+            timeout_ms: None,
+            filter_preset: None,
+        },
+    )?;
+
+    response
+        .nodes
+        .retain(|node| node.id != req.node_id && !existing_node_ids.contains(&node.id));
+    let mut shown = existing_node_ids;
+    shown.insert(req.node_id);
+    shown.extend(response.nodes.iter().map(|node| node.id.clone()));
+    response.edges.retain(|edge| {
+        !existing_edge_ids.contains(&edge.id)
+            && shown.contains(&edge.source)
+            && shown.contains(&edge.target)
+    });
+    response.paths.clear();
+    response.canonical_layout = None;
     Ok(response)
 }
 
 pub(super) fn graph_direct_references(
     controller: &AppController,
-    req: TrailConfigDto,
+    mut req: TrailConfigDto,
 ) -> Result<GraphResponse, ApiError> {
     let root_id = req.root_id.to_core()?;
     let graph_flags = app_graph_flags();
+    let storage = controller.open_storage_read_only()?;
+    crate::controller_trail_defaults::apply_trail_defaults(&storage, &mut req)?;
     let edge_filter = req
         .edge_filter
         .into_iter()
         .map(Into::into)
         .collect::<Vec<_>>();
-    let storage = controller.open_storage_read_only()?;
     let mut edges = storage
         .get_incoming_edges_for_node_id(
             root_id,
             &edge_filter,
-            req.caller_scope.into(),
+            req.caller_scope_or_default().into(),
             req.show_utility_calls,
         )
         .map_err(|e| ApiError::internal(format!("Failed to load incoming references: {e}")))?;
     edges.sort_by_key(|edge| edge.id.0);
 
-    let max_nodes = req.max_nodes.clamp(10, 100_000) as usize;
+    let max_nodes = req.max_nodes_or_default().clamp(10, 100_000) as usize;
     let mut selected_node_ids = Vec::with_capacity(max_nodes.min(edges.len().saturating_add(1)));
     let mut selected = HashSet::new();
     selected_node_ids.push(root_id);
@@ -282,8 +537,7 @@ pub(super) fn graph_direct_references(
     let mut truncated = false;
     let mut omitted_edge_count = 0u32;
     for edge in edges {
-        let edge = edge.with_effective_endpoints();
-        let (source, target) = (edge.source, edge.target);
+        let (source, target) = edge.effective_endpoints();
         if target != root_id || source == root_id {
             continue;
         }
@@ -299,6 +553,9 @@ pub(super) fn graph_direct_references(
         retained_edges.push(edge);
     }
 
+    let edge_counts = node_edge_counts(&storage, &selected_node_ids)?;
+    let badges = node_badges(&storage, &selected_node_ids)?;
+    let centrality = node_centrality(&storage, &selected_node_ids)?;
     let mut node_dtos = Vec::with_capacity(selected_node_ids.len());
     for id in selected_node_ids {
         let (label, kind, file_path, qualified_name, member_access) = match storage.get_node(id) {
@@ -329,6 +586,9 @@ pub(super) fn graph_direct_references(
             file_path,
             qualified_name,
             member_access,
+            edge_counts: edge_counts.get(&id).copied(),
+            badges: badges.get(&id).copied().unwrap_or_default(),
+            centrality: centrality.get(&id).copied(),
         });
     }
 
@@ -341,13 +601,115 @@ pub(super) fn graph_direct_references(
         nodes: node_dtos,
         edges: edge_dtos,
         truncated,
+        truncated_by_timeout: false,
         omitted_edge_count,
+        pruned_test_node_count: 0,
+        pruned_accessor_node_count: 0,
+        pruned_generated_node_count: 0,
+        omitted_node_count: 0,
+        node_limit: None,
+        paths: Vec::new(),
         canonical_layout: None,
     };
     if req.hide_speculative {
         response = hide_speculative_trail_edges(response);
     }
-    Ok(suppress_default_trail_noise(response))
+    let mut response = suppress_default_trail_noise(response);
+    apply_graph_session(
+        controller,
+        &storage,
+        req.graph_session.as_deref(),
+        &mut response,
+    );
+    Ok(response)
+}
+
+/// A graph of exactly `node_depths` and the non-member edges between them,
+/// centered on the first node.
+pub(super) fn graph_review(
+    controller: &AppController,
+    node_depths: &[(codestory_contracts::graph::NodeId, u32)],
+) -> Result<GraphResponse, ApiError> {
+    let graph_flags = app_graph_flags();
+    let storage = controller.open_storage_read_only()?;
+    let node_ids = node_depths.iter().map(|(id, _)| *id).collect::<Vec<_>>();
+    let included = node_ids.iter().copied().collect::<HashSet<_>>();
+    let mut seen_edges = HashSet::new();
+    let mut retained_edges = storage
+        .get_edges_for_node_ids(&node_ids)
+        .map_err(|e| ApiError::internal(format!("Failed to load review edges: {e}")))?
+        .into_values()
+        .flatten()
+        .filter(|edge| {
+            let (source, target) = edge.effective_endpoints();
+            edge.kind != codestory_contracts::graph::EdgeKind::MEMBER
+                && source != target
+                && included.contains(&source)
+                && included.contains(&target)
+                && seen_edges.insert(edge.id)
+        })
+        .collect::<Vec<_>>();
+    retained_edges.sort_by_key(|edge| edge.id.0);
+
+    let edge_counts = node_edge_counts(&storage, &node_ids)?;
+    let badges = node_badges(&storage, &node_ids)?;
+    let centrality = node_centrality(&storage, &node_ids)?;
+    let mut node_dtos = Vec::with_capacity(node_depths.len());
+    for (id, depth) in node_depths {
+        let (label, kind, file_path, qualified_name, member_access) = match storage.get_node(*id) {
+            Ok(Some(node)) => {
+                let access = storage.get_component_access(node.id).ok().flatten();
+                (
+                    node_display_name(&node),
+                    NodeKind::from(node.kind),
+                    AppController::file_path_for_node(&storage, &node)
+                        .ok()
+                        .flatten(),
+                    node.qualified_name,
+                    member_access_dto(access),
+                )
+            }
+            _ => (id.0.to_string(), NodeKind::UNKNOWN, None, None, None),
+        };
+        node_dtos.push(GraphNodeDto {
+            id: NodeId::from(*id),
+            label,
+            kind,
+            depth: *depth,
+            label_policy: Some("qualified_or_serialized".to_string()),
+            badge_visible_members: None,
+            badge_total_members: None,
+            merged_symbol_examples: Vec::new(),
+            file_path,
+            qualified_name,
+            member_access,
+            edge_counts: edge_counts.get(id).copied(),
+            badges: badges.get(id).copied().unwrap_or_default(),
+            centrality: centrality.get(id).copied(),
+        });
+    }
+
+    Ok(GraphResponse {
+        center_id: node_ids
+            .first()
+            .map(|id| NodeId::from(*id))
+            .unwrap_or_else(|| NodeId(String::new())),
+        nodes: node_dtos,
+        edges: retained_edges
+            .into_iter()
+            .map(|edge| graph_edge_dto(edge, graph_flags))
+            .collect(),
+        truncated: false,
+        truncated_by_timeout: false,
+        omitted_edge_count: 0,
+        pruned_test_node_count: 0,
+        pruned_accessor_node_count: 0,
+        pruned_generated_node_count: 0,
+        omitted_node_count: 0,
+        node_limit: None,
+        paths: Vec::new(),
+        canonical_layout: None,
+    })
 }
 
 fn suppress_default_trail_noise(mut response: GraphResponse) -> GraphResponse {
@@ -420,7 +782,10 @@ fn hide_speculative_trail_edges(mut response: GraphResponse) -> GraphResponse {
         }
     }
 
+    let original_node_count = response.nodes.len();
     response.nodes.retain(|node| reachable.contains(&node.id));
+    let omitted_nodes = original_node_count.saturating_sub(response.nodes.len()) as u32;
+    response.omitted_node_count = response.omitted_node_count.saturating_add(omitted_nodes);
     response.edges = retained_edges
         .into_iter()
         .filter(|edge| reachable.contains(&edge.source) && reachable.contains(&edge.target))
@@ -428,12 +793,17 @@ fn hide_speculative_trail_edges(mut response: GraphResponse) -> GraphResponse {
     let omitted_edges = original_edge_count.saturating_sub(response.edges.len()) as u32;
     response.omitted_edge_count = response.omitted_edge_count.saturating_add(omitted_edges);
 
-    if let Some(layout) = response.canonical_layout.as_mut() {
-        let retained = response
-            .edges
+    let retained = response
+        .edges
+        .iter()
+        .map(|edge| edge.id.clone())
+        .collect::<HashSet<_>>();
+    response.paths.retain(|path| {
+        path.edge_ids
             .iter()
-            .map(|edge| edge.id.clone())
-            .collect::<HashSet<_>>();
+            .all(|edge_id| retained.contains(edge_id))
+    });
+    if let Some(layout) = response.canonical_layout.as_mut() {
         layout.nodes.retain(|node| reachable.contains(&node.id));
         layout.edges.retain(|edge| {
             edge.source_edge_ids
@@ -484,14 +854,15 @@ mod tests {
 
     fn edge(id: i64, source: &str, target: &str) -> GraphEdgeDto {
         GraphEdgeDto {
-            id: codestory_contracts::api::EdgeId(id.to_string()),
-            source: NodeId(source.to_string()),
-            target: NodeId(target.to_string()),
-            kind: EdgeKind::CALL,
             confidence: Some(1.0),
             certainty: Some("certain".to_string()),
             callsite_identity: Some(format!("{source}->{target}")),
-            candidate_targets: Vec::new(),
+            ..GraphEdgeDto::new(
+                codestory_contracts::api::EdgeId(id.to_string()),
+                NodeId(source.to_string()),
+                NodeId(target.to_string()),
+                EdgeKind::CALL,
+            )
         }
     }
 
@@ -541,7 +912,14 @@ mod tests {
                 edge(4, "b", "a"),
             ],
             truncated: false,
+            truncated_by_timeout: false,
             omitted_edge_count: 0,
+            pruned_test_node_count: 0,
+            pruned_accessor_node_count: 0,
+            pruned_generated_node_count: 0,
+            omitted_node_count: 0,
+            node_limit: None,
+            paths: Vec::new(),
             canonical_layout: None,
         };
 
@@ -571,7 +949,14 @@ mod tests {
                 },
             ],
             truncated: false,
+            truncated_by_timeout: false,
             omitted_edge_count: 0,
+            pruned_test_node_count: 0,
+            pruned_accessor_node_count: 0,
+            pruned_generated_node_count: 0,
+            omitted_node_count: 0,
+            node_limit: None,
+            paths: Vec::new(),
             canonical_layout: None,
         };
 
@@ -604,7 +989,14 @@ mod tests {
                 edge_with_certainty(3, "c", "b", Some("certain"), Some(0.85)),
             ],
             truncated: false,
+            truncated_by_timeout: false,
             omitted_edge_count: 0,
+            pruned_test_node_count: 0,
+            pruned_accessor_node_count: 0,
+            pruned_generated_node_count: 0,
+            omitted_node_count: 0,
+            node_limit: None,
+            paths: Vec::new(),
             canonical_layout: Some(CanonicalLayoutDto {
                 schema_version: 1,
                 center_node_id: NodeId("a".to_string()),
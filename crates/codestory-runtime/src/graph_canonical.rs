@@ -4,7 +4,9 @@ use codestory_contracts::api::{
     GraphEdgeDto, GraphNodeDto, MemberAccess, NodeId, NodeKind,
 };
 use std::cmp::Ordering;
-use std::collections::{HashMap, HashSet};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::{Hash, Hasher};
 
 const CARD_WIDTH_MIN: f32 = 228.0;
 const CARD_WIDTH_MAX: f32 = 432.0;
@@ -18,6 +20,84 @@ const PILL_HEIGHT: f32 = 34.0;
 const APPROX_CHAR_WIDTH: f32 = 7.25;
 const SCHEMA_VERSION: u32 = 1;
 const MAX_MERGED_SYMBOL_IDS: usize = 6;
+const LAYOUT_CACHE_CAPACITY: usize = 64;
+
+/// Recently built layouts keyed by [`canonical_layout_key`], least recently
+/// used first, all built from one storage generation.
+///
+/// Labels and kinds are not part of the key because they follow from node and
+/// edge ids within one storage generation. A lookup or insert under a newer
+/// generation drops every cached layout first.
+#[derive(Debug, Default)]
+pub(crate) struct CanonicalLayoutCache {
+    generation: u64,
+    entries: VecDeque<(u64, CanonicalLayoutDto)>,
+}
+
+impl CanonicalLayoutCache {
+    /// Forget layouts from generations before `generation`. Returns false
+    /// when `generation` is older than the cached one.
+    fn advance_to(&mut self, generation: u64) -> bool {
+        if generation < self.generation {
+            return false;
+        }
+        if generation > self.generation {
+            self.generation = generation;
+            self.entries.clear();
+        }
+        true
+    }
+
+    pub(crate) fn get(&mut self, generation: u64, key: u64) -> Option<CanonicalLayoutDto> {
+        if !self.advance_to(generation) {
+            return None;
+        }
+        let index = self.entries.iter().position(|(cached, _)| *cached == key)?;
+        let entry = self.entries.remove(index)?;
+        let layout = entry.1.clone();
+        self.entries.push_back(entry);
+        Some(layout)
+    }
+
+    /// Store `layout` unless the cache already holds a newer generation, so
+    /// a layout built from a superseded index is never kept.
+    pub(crate) fn insert(&mut self, generation: u64, key: u64, layout: CanonicalLayoutDto) -> bool {
+        if !self.advance_to(generation) {
+            return false;
+        }
+        self.entries.retain(|(cached, _)| *cached != key);
+        if self.entries.len() >= LAYOUT_CACHE_CAPACITY {
+            self.entries.pop_front();
+        }
+        self.entries.push_back((key, layout));
+        true
+    }
+}
+
+/// Hash of the center, node ids with their depth and badges, and edge ids
+/// with their endpoints, in response order.
+pub(crate) fn canonical_layout_key(
+    center_id: &NodeId,
+    nodes: &[GraphNodeDto],
+    edges: &[GraphEdgeDto],
+) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    center_id.hash(&mut hasher);
+    nodes.len().hash(&mut hasher);
+    for node in nodes {
+        node.id.hash(&mut hasher);
+        node.depth.hash(&mut hasher);
+        node.badge_visible_members.hash(&mut hasher);
+        node.badge_total_members.hash(&mut hasher);
+    }
+    edges.len().hash(&mut hasher);
+    for edge in edges {
+        edge.id.hash(&mut hasher);
+        edge.source.hash(&mut hasher);
+        edge.target.hash(&mut hasher);
+    }
+    hasher.finish()
+}
 
 #[derive(Debug, Clone)]
 struct NodeLike {
@@ -786,6 +866,7 @@ fn edge_kind_name(kind: EdgeKind) -> &'static str {
         EdgeKind::IMPORT => "IMPORT",
         EdgeKind::MACRO_USAGE => "MACRO_USAGE",
         EdgeKind::ANNOTATION_USAGE => "ANNOTATION_USAGE",
+        EdgeKind::ASSIGNMENT => "ASSIGNMENT",
         EdgeKind::UNKNOWN => "UNKNOWN",
     }
 }
@@ -833,20 +914,19 @@ mod tests {
             file_path: None,
             qualified_name: None,
             member_access: None,
+            edge_counts: None,
+            badges: Default::default(),
+            centrality: None,
         }
     }
 
     fn edge(id: &str, source: &str, target: &str, kind: EdgeKind) -> GraphEdgeDto {
-        GraphEdgeDto {
-            id: codestory_contracts::api::EdgeId(id.to_string()),
-            source: NodeId(source.to_string()),
-            target: NodeId(target.to_string()),
+        GraphEdgeDto::new(
+            codestory_contracts::api::EdgeId(id.to_string()),
+            NodeId(source.to_string()),
+            NodeId(target.to_string()),
             kind,
-            confidence: None,
-            certainty: None,
-            callsite_identity: None,
-            candidate_targets: Vec::new(),
-        }
+        )
     }
 
     #[test]
@@ -989,4 +1069,37 @@ mod tests {
         assert_eq!(first_node_ids, second_node_ids);
         assert_eq!(first_edge_ids, second_edge_ids);
     }
+
+    #[test]
+    fn layout_cache_evicts_least_recently_used_and_drops_stale_generations() {
+        let center = NodeId("run".to_string());
+        let nodes = vec![
+            node("run", "run", NodeKind::FUNCTION, 0),
+            node("helper", "helper", NodeKind::FUNCTION, 1),
+        ];
+        let edges = vec![edge("call-1", "run", "helper", EdgeKind::CALL)];
+        let layout = build_canonical_layout(&center, &nodes, &edges);
+        let key = canonical_layout_key(&center, &nodes, &edges);
+        assert_ne!(key, canonical_layout_key(&center, &nodes[..1], &[]));
+
+        let mut cache = CanonicalLayoutCache::default();
+        let generation = 3;
+        assert!(cache.insert(generation, key, layout.clone()));
+        for filler in 1..LAYOUT_CACHE_CAPACITY as u64 {
+            assert!(cache.insert(generation, key.wrapping_add(filler), layout.clone()));
+            assert!(
+                cache.get(generation, key).is_some(),
+                "recent use keeps the entry"
+            );
+        }
+        assert!(cache.insert(generation, key.wrapping_sub(1), layout.clone()));
+        assert!(cache.get(generation, key).is_some());
+        assert!(cache.get(generation, key.wrapping_add(1)).is_none());
+
+        assert!(cache.get(generation + 1, key).is_none());
+        assert!(!cache.insert(generation, key, layout.clone()));
+        assert!(cache.get(generation + 1, key).is_none());
+        assert!(cache.insert(generation + 1, key, layout));
+        assert!(cache.get(generation + 1, key).is_some());
+    }
 }
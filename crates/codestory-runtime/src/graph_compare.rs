@@ -0,0 +1,138 @@
+use codestory_contracts::api::{
+    GraphCompareDto, GraphCompareEdgeDto, GraphCompareNodeDto, GraphCompareSide, GraphResponse,
+};
+use std::collections::HashSet;
+
+/// Merge two graphs into one, labeling every node and edge as only in `a`,
+/// only in `b`, or in both.
+///
+/// Nodes and edges match by id, so graphs from two snapshots of the same
+/// index compare as long as their ids are stable. The result lists `a`'s
+/// nodes and edges in `a`'s order, followed by those only in `b`.
+pub fn compare_trail_graphs(a: &GraphResponse, b: &GraphResponse) -> GraphCompareDto {
+    let side_in_a = |in_b: bool| {
+        if in_b {
+            GraphCompareSide::Both
+        } else {
+            GraphCompareSide::OnlyA
+        }
+    };
+
+    let b_nodes = b.nodes.iter().map(|node| &node.id).collect::<HashSet<_>>();
+    let a_nodes = a.nodes.iter().map(|node| &node.id).collect::<HashSet<_>>();
+    let nodes = a
+        .nodes
+        .iter()
+        .map(|node| (node, side_in_a(b_nodes.contains(&node.id))))
+        .chain(
+            b.nodes
+                .iter()
+                .filter(|node| !a_nodes.contains(&node.id))
+                .map(|node| (node, GraphCompareSide::OnlyB)),
+        )
+        .map(|(node, side)| GraphCompareNodeDto {
+            node: node.clone(),
+            side,
+        })
+        .collect();
+
+    let b_edges = b.edges.iter().map(|edge| &edge.id).collect::<HashSet<_>>();
+    let a_edges = a.edges.iter().map(|edge| &edge.id).collect::<HashSet<_>>();
+    let edges = a
+        .edges
+        .iter()
+        .map(|edge| (edge, side_in_a(b_edges.contains(&edge.id))))
+        .chain(
+            b.edges
+                .iter()
+                .filter(|edge| !a_edges.contains(&edge.id))
+                .map(|edge| (edge, GraphCompareSide::OnlyB)),
+        )
+        .map(|(edge, side)| GraphCompareEdgeDto {
+            edge: edge.clone(),
+            side,
+        })
+        .collect();
+
+    GraphCompareDto {
+        center_a: a.center_id.clone(),
+        center_b: b.center_id.clone(),
+        nodes,
+        edges,
+        truncated: a.truncated || a.truncated_by_timeout || b.truncated || b.truncated_by_timeout,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codestory_contracts::api::{
+        EdgeId, EdgeKind, GraphEdgeDto, GraphNodeDto, NodeId, NodeKind,
+    };
+
+    fn graph(nodes: &[&str], edges: &[(&str, &str, &str)]) -> GraphResponse {
+        GraphResponse::new(
+            NodeId(nodes[0].to_string()),
+            nodes
+                .iter()
+                .map(|id| {
+                    GraphNodeDto::new(
+                        NodeId(id.to_string()),
+                        format!("node_{id}"),
+                        NodeKind::FUNCTION,
+                        0,
+                    )
+                })
+                .collect(),
+            edges
+                .iter()
+                .map(|(id, source, target)| {
+                    GraphEdgeDto::new(
+                        EdgeId(id.to_string()),
+                        NodeId(source.to_string()),
+                        NodeId(target.to_string()),
+                        EdgeKind::CALL,
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn compared_graphs_label_each_node_and_edge_by_side() {
+        let a = graph(&["1", "2", "3"], &[("12", "1", "2"), ("13", "1", "3")]);
+        let mut b = graph(&["1", "2", "4"], &[("12", "1", "2"), ("14", "1", "4")]);
+        b.truncated = true;
+
+        let compared = compare_trail_graphs(&a, &b);
+
+        let nodes = compared
+            .nodes
+            .iter()
+            .map(|node| (node.node.id.0.as_str(), node.side))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            nodes,
+            vec![
+                ("1", GraphCompareSide::Both),
+                ("2", GraphCompareSide::Both),
+                ("3", GraphCompareSide::OnlyA),
+                ("4", GraphCompareSide::OnlyB),
+            ]
+        );
+        let edges = compared
+            .edges
+            .iter()
+            .map(|edge| (edge.edge.id.0.as_str(), edge.side))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            edges,
+            vec![
+                ("12", GraphCompareSide::Both),
+                ("13", GraphCompareSide::OnlyA),
+                ("14", GraphCompareSide::OnlyB),
+            ]
+        );
+        assert!(compared.truncated);
+    }
+}
@@ -1,4 +1,6 @@
-use codestory_contracts::api::{EdgeId, EdgeKind, GraphEdgeDto, MemberAccess, NodeId};
+use codestory_contracts::api::{
+    EdgeId, EdgeKind, EdgeResolutionDto, GraphEdgeDto, MemberAccess, NodeId,
+};
 use std::sync::OnceLock;
 
 pub(crate) fn edge_certainty_label(
@@ -30,10 +32,31 @@ fn structural_edge_default_certainty(
         | EdgeKind::TYPE_USAGE
         | EdgeKind::MACRO_USAGE
         | EdgeKind::ANNOTATION_USAGE
+        | EdgeKind::ASSIGNMENT
         | EdgeKind::UNKNOWN => None,
     }
 }
 
+/// Edges resolution never moved are exact when extraction bound them with
+/// certainty, as structural edges and calls to known definitions are, and
+/// unresolved when they still point at a guessed or placeholder target.
+fn edge_resolution(edge: &codestory_contracts::graph::Edge) -> EdgeResolutionDto {
+    use codestory_contracts::graph::ResolutionCertainty;
+
+    if edge.resolved_source.is_some() || edge.resolved_target.is_some() {
+        return EdgeResolutionDto::Resolved;
+    }
+    let certainty = edge
+        .certainty
+        .or_else(|| ResolutionCertainty::from_confidence(edge.confidence))
+        .or_else(|| structural_edge_default_certainty(edge.kind));
+    if certainty == Some(ResolutionCertainty::Certain) {
+        EdgeResolutionDto::Exact
+    } else {
+        EdgeResolutionDto::Unresolved
+    }
+}
+
 pub(crate) fn is_structural_kind(kind: codestory_contracts::graph::NodeKind) -> bool {
     matches!(
         kind,
@@ -89,10 +112,11 @@ pub(crate) fn graph_edge_dto(
     edge: codestory_contracts::graph::Edge,
     flags: AppGraphFeatureFlags,
 ) -> GraphEdgeDto {
+    let (source, target) = edge.effective_endpoints();
     GraphEdgeDto {
         id: EdgeId::from(edge.id),
-        source: NodeId::from(edge.source),
-        target: NodeId::from(edge.target),
+        source: NodeId::from(source),
+        target: NodeId::from(target),
         kind: EdgeKind::from(edge.kind),
         confidence: edge.confidence,
         certainty: if flags.include_edge_certainty {
@@ -114,5 +138,9 @@ pub(crate) fn graph_edge_dto(
         } else {
             Vec::new()
         },
+        collapsed_edge_ids: Vec::new(),
+        raw_source: (edge.source != source).then(|| NodeId::from(edge.source)),
+        raw_target: (edge.target != target).then(|| NodeId::from(edge.target)),
+        resolution: edge_resolution(&edge),
     }
 }
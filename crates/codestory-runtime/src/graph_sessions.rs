@@ -0,0 +1,279 @@
+//! Hidden and collapsed nodes kept per client session so every surface that
+//! shares a session token sees the same trail and expand responses.
+
+use codestory_contracts::api::{
+    ApiError, EdgeKind, GraphResponse, GraphSessionDto, GraphSessionUpdateRequest, NodeId,
+};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::time::Instant;
+
+/// Sessions kept before the least recently used one is dropped.
+const GRAPH_SESSION_LIMIT: usize = 64;
+
+#[derive(Debug, Default)]
+pub(crate) struct GraphSessions {
+    sessions: HashMap<String, GraphSession>,
+}
+
+#[derive(Debug, Clone)]
+struct GraphSession {
+    hidden: BTreeSet<NodeId>,
+    collapsed: BTreeSet<NodeId>,
+    last_used: Instant,
+}
+
+/// Snapshot of one session, applied outside the controller state lock.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct GraphSessionView {
+    hidden: HashSet<NodeId>,
+    collapsed: HashSet<NodeId>,
+}
+
+impl GraphSessions {
+    pub(crate) fn update(
+        &mut self,
+        req: GraphSessionUpdateRequest,
+    ) -> Result<GraphSessionDto, ApiError> {
+        let name = req.session.trim();
+        if name.is_empty() {
+            return Err(ApiError::invalid_argument("Graph session name is empty"));
+        }
+        if !self.sessions.contains_key(name) && self.sessions.len() >= GRAPH_SESSION_LIMIT {
+            let oldest = self
+                .sessions
+                .iter()
+                .min_by_key(|(_, session)| session.last_used)
+                .map(|(name, _)| name.clone());
+            if let Some(oldest) = oldest {
+                self.sessions.remove(&oldest);
+            }
+        }
+        let session = self
+            .sessions
+            .entry(name.to_string())
+            .or_insert_with(|| GraphSession {
+                hidden: BTreeSet::new(),
+                collapsed: BTreeSet::new(),
+                last_used: Instant::now(),
+            });
+        session.last_used = Instant::now();
+        if req.reset {
+            session.hidden.clear();
+            session.collapsed.clear();
+        }
+        session.hidden.extend(req.hide);
+        for id in &req.unhide {
+            session.hidden.remove(id);
+        }
+        session.collapsed.extend(req.collapse);
+        for id in &req.uncollapse {
+            session.collapsed.remove(id);
+        }
+        Ok(GraphSessionDto {
+            session: name.to_string(),
+            hidden_node_ids: session.hidden.iter().cloned().collect(),
+            collapsed_node_ids: session.collapsed.iter().cloned().collect(),
+        })
+    }
+
+    /// Unknown sessions have nothing hidden or collapsed.
+    pub(crate) fn view(&mut self, name: &str) -> GraphSessionView {
+        let Some(session) = self.sessions.get_mut(name.trim()) else {
+            return GraphSessionView::default();
+        };
+        session.last_used = Instant::now();
+        GraphSessionView {
+            hidden: session.hidden.iter().cloned().collect(),
+            collapsed: session.collapsed.iter().cloned().collect(),
+        }
+    }
+}
+
+impl GraphSessionView {
+    /// Drop hidden nodes with their edges and fold members of collapsed hosts
+    /// into the host, rerouting member edges onto it. The center node always
+    /// stays. Returns whether the response changed.
+    pub(crate) fn apply(&self, response: &mut GraphResponse) -> bool {
+        if self.hidden.is_empty() && self.collapsed.is_empty() {
+            return false;
+        }
+        let center = response.center_id.clone();
+        let mut host_by_member = HashMap::new();
+        for edge in &response.edges {
+            if edge.kind == EdgeKind::MEMBER
+                && self.collapsed.contains(&edge.source)
+                && edge.target != center
+                && !self.hidden.contains(&edge.source)
+            {
+                host_by_member.insert(edge.target.clone(), edge.source.clone());
+            }
+        }
+        let removed = |id: &NodeId| {
+            *id != center && (self.hidden.contains(id) || host_by_member.contains_key(id))
+        };
+
+        let node_count = response.nodes.len();
+        response.nodes.retain(|node| !removed(&node.id));
+        let mut changed = response.nodes.len() != node_count;
+
+        let edge_count = response.edges.len();
+        let mut seen = HashSet::new();
+        let edges = std::mem::take(&mut response.edges);
+        for mut edge in edges {
+            if edge.kind == EdgeKind::MEMBER && host_by_member.contains_key(&edge.target) {
+                continue;
+            }
+            if (self.hidden.contains(&edge.source) && edge.source != center)
+                || (self.hidden.contains(&edge.target) && edge.target != center)
+            {
+                continue;
+            }
+            if let Some(host) = host_by_member.get(&edge.source) {
+                edge.source = host.clone();
+                changed = true;
+            }
+            if let Some(host) = host_by_member.get(&edge.target) {
+                edge.target = host.clone();
+                changed = true;
+            }
+            if edge.source == edge.target
+                || !seen.insert((edge.source.clone(), edge.target.clone(), edge.kind))
+            {
+                continue;
+            }
+            response.edges.push(edge);
+        }
+        changed |= response.edges.len() != edge_count;
+
+        response
+            .paths
+            .retain(|path| !path.node_ids.iter().any(&removed));
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codestory_contracts::api::{EdgeId, GraphEdgeDto, GraphNodeDto, NodeKind};
+
+    fn id(value: &str) -> NodeId {
+        NodeId(value.to_string())
+    }
+
+    fn node(value: &str) -> GraphNodeDto {
+        GraphNodeDto {
+            id: id(value),
+            label: value.to_string(),
+            kind: NodeKind::FUNCTION,
+            depth: 1,
+            label_policy: None,
+            badge_visible_members: None,
+            badge_total_members: None,
+            merged_symbol_examples: Vec::new(),
+            file_path: None,
+            qualified_name: None,
+            member_access: None,
+            edge_counts: None,
+            badges: Default::default(),
+            centrality: None,
+        }
+    }
+
+    fn edge(value: &str, source: &str, target: &str, kind: EdgeKind) -> GraphEdgeDto {
+        GraphEdgeDto::new(EdgeId(value.to_string()), id(source), id(target), kind)
+    }
+
+    fn update(sessions: &mut GraphSessions, hide: &[&str], collapse: &[&str]) -> GraphSessionDto {
+        sessions
+            .update(GraphSessionUpdateRequest {
+                session: "ui".to_string(),
+                reset: false,
+                hide: hide.iter().map(|value| id(value)).collect(),
+                unhide: Vec::new(),
+                collapse: collapse.iter().map(|value| id(value)).collect(),
+                uncollapse: Vec::new(),
+            })
+            .expect("update session")
+    }
+
+    #[test]
+    fn session_hides_nodes_and_folds_collapsed_members_into_their_host() {
+        let mut sessions = GraphSessions::default();
+        let dto = update(&mut sessions, &["noise"], &["Service"]);
+        assert_eq!(dto.hidden_node_ids, vec![id("noise")]);
+        assert_eq!(dto.collapsed_node_ids, vec![id("Service")]);
+
+        let mut response = GraphResponse {
+            center_id: id("main"),
+            nodes: ["main", "Service", "run", "stop", "noise"]
+                .into_iter()
+                .map(node)
+                .collect(),
+            edges: vec![
+                edge("m1", "Service", "run", EdgeKind::MEMBER),
+                edge("m2", "Service", "stop", EdgeKind::MEMBER),
+                edge("c1", "main", "run", EdgeKind::CALL),
+                edge("c2", "main", "stop", EdgeKind::CALL),
+                edge("c3", "run", "stop", EdgeKind::CALL),
+                edge("c4", "main", "noise", EdgeKind::CALL),
+            ],
+            truncated: false,
+            truncated_by_timeout: false,
+            omitted_edge_count: 0,
+            pruned_test_node_count: 0,
+            pruned_accessor_node_count: 0,
+            pruned_generated_node_count: 0,
+            omitted_node_count: 0,
+            node_limit: None,
+            paths: Vec::new(),
+            canonical_layout: None,
+        };
+
+        assert!(sessions.view("ui").apply(&mut response));
+        let node_ids = response
+            .nodes
+            .iter()
+            .map(|node| node.id.0.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(node_ids, vec!["main", "Service"]);
+        let edges = response
+            .edges
+            .iter()
+            .map(|edge| (edge.source.0.as_str(), edge.target.0.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(edges, vec![("main", "Service")]);
+        assert!(!sessions.view("other").apply(&mut response));
+    }
+
+    #[test]
+    fn session_updates_reject_empty_names_and_reset_state() {
+        let mut sessions = GraphSessions::default();
+        assert!(
+            sessions
+                .update(GraphSessionUpdateRequest {
+                    session: " ".to_string(),
+                    reset: false,
+                    hide: Vec::new(),
+                    unhide: Vec::new(),
+                    collapse: Vec::new(),
+                    uncollapse: Vec::new(),
+                })
+                .is_err()
+        );
+
+        update(&mut sessions, &["a", "b"], &[]);
+        let dto = sessions
+            .update(GraphSessionUpdateRequest {
+                session: "ui".to_string(),
+                reset: true,
+                hide: vec![id("c")],
+                unhide: vec![id("c")],
+                collapse: vec![id("d")],
+                uncollapse: Vec::new(),
+            })
+            .expect("reset session");
+        assert!(dto.hidden_node_ids.is_empty());
+        assert_eq!(dto.collapsed_node_ids, vec![id("d")]);
+    }
+}
@@ -5,10 +5,10 @@ use super::{
     GroundingEdgeKindCount, GroundingFileDigestDto, GroundingNodeRecord,
     GroundingOrientationConfidenceDto, GroundingOrientationDto, GroundingOrientationUncertaintyDto,
     GroundingSnapshotDto, GroundingSymbolDigestDto, NodeDetailsRequest, NodeId, NodeKind,
-    RetrievalScoreBreakdownDto, SearchHit, SnippetContextDto, StorageStatsDto, SymbolContextDto,
-    SymbolSummaryRecord, TrailConfigDto, TrailContextDto, clamp_i64_to_u32, current_epoch_ms,
-    edge_digest_for_node, is_structural_kind, node_display_name, normalize_symbol_query,
-    retrieval_state_from_storage_for_runtime, terminal_symbol_segment,
+    RetrievalScoreBreakdownDto, SearchHit, SnippetContextDto, SymbolContextDto,
+    SymbolSummaryRecord, TrailConfigDto, TrailContextDto, current_epoch_ms, edge_digest_for_node,
+    is_structural_kind, node_display_name, normalize_symbol_query,
+    retrieval_state_from_storage_for_runtime, storage_stats_dto, terminal_symbol_segment,
 };
 use crate::agent::packet_evidence::{decorate_search_hit_evidence, diagnostic_source_evidence};
 use crate::trail_story::build_trail_story;
@@ -1069,6 +1069,7 @@ fn search_hit_from_grounding_recommendation(candidate: &RecommendationCandidate<
             final_rank_reason: None,
             provenance: Vec::new(),
         }),
+        highlight: None,
     };
     decorate_search_hit_evidence(&mut hit);
     hit
@@ -1147,13 +1148,7 @@ impl AppController {
         } else {
             file_summaries.len().min(i64::MAX as usize) as i64
         };
-        let dto_stats = StorageStatsDto {
-            node_count: clamp_i64_to_u32(stats.node_count),
-            edge_count: clamp_i64_to_u32(stats.edge_count),
-            file_count: clamp_i64_to_u32(derived_file_count),
-            error_count: clamp_i64_to_u32(stats.error_count),
-            fatal_error_count: clamp_i64_to_u32(stats.fatal_error_count),
-        };
+        let dto_stats = storage_stats_dto(&stats, derived_file_count);
 
         let mut file_coverages = Vec::with_capacity(file_summaries.len());
         for summary in file_summaries {
@@ -1543,7 +1538,11 @@ impl AppController {
         })
     }
 
-    pub fn trail_context(&self, req: TrailConfigDto) -> Result<TrailContextDto, ApiError> {
+    pub fn trail_context(&self, mut req: TrailConfigDto) -> Result<TrailContextDto, ApiError> {
+        crate::controller_trail_defaults::apply_trail_defaults(
+            &self.open_storage_read_only()?,
+            &mut req,
+        )?;
         let focus = self.node_details(NodeDetailsRequest {
             id: req.root_id.clone(),
         })?;
@@ -3412,6 +3411,8 @@ mod tests {
                         end_line: 3,
                         end_col: 10,
                     },
+                    edge_id: None,
+                    notebook_cell: None,
                 }])
                 .expect("insert occurrences");
         }
@@ -3599,6 +3600,8 @@ mod tests {
                         end_line: 3,
                         end_col: 10,
                     },
+                    edge_id: None,
+                    notebook_cell: None,
                 }])
                 .expect("insert occurrences");
             storage
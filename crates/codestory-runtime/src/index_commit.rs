@@ -1,3 +1,4 @@
+use crate::index_settings::{centrality_iterations_setting, string_literals_setting};
 #[cfg(test)]
 use crate::publication::run_source_policy_before_revalidate_hook;
 #[cfg(test)]
@@ -11,9 +12,10 @@ use crate::{
     current_epoch_ms, publish_source_policy_exclusions, revalidate_source_policy_exclusions,
 };
 use codestory_contracts::api::{ApiError, IndexPublicationDto, IndexPublicationModeDto};
-use codestory_indexer::CancellationToken;
+use codestory_indexer::{CancellationToken, extract_code_markers, extract_string_literals};
 use codestory_store::{
-    IndexPublicationMode, IndexPublicationRecord, StagedSnapshot, StagedSnapshotPublishStats,
+    CodeMarkerScan, IndexPublicationMode, IndexPublicationRecord, StagedSnapshot,
+    StagedSnapshotPublishStats, StringLiteralScan,
 };
 use codestory_workspace::{
     OversizedSourceExclusionCandidate, SourceIndexPolicy, WorkspaceManifest,
@@ -116,6 +118,141 @@ impl Drop for IndexWriterGuard {
     }
 }
 
+/// Record what this run adds and removes relative to the live index, so the
+/// published index can answer when a node or edge appeared. Incremental runs
+/// pass the files they rewrote or removed, so only those are diffed.
+pub(super) fn record_staged_index_history(
+    staged: &mut StagedSnapshot,
+    storage_path: &Path,
+    publication: &IndexPublicationRecord,
+    changed_file_ids: Option<&[i64]>,
+) {
+    if let Err(error) = staged.store_mut().record_index_history(
+        storage_path,
+        &publication.run_id,
+        publication.published_at_epoch_ms,
+        changed_file_ids,
+    ) {
+        tracing::warn!("Failed to record indexing history: {error}");
+    }
+}
+
+/// Incremental runs that change at most this many files keep the previous
+/// centrality scores. PageRank walks the whole graph, and a few edited files
+/// barely move it; their new symbols are scored by the next larger run.
+const CENTRALITY_INCREMENTAL_SKIP_MAX_FILES: usize = 16;
+
+/// Rescore node centrality. Incremental runs pass the files they rewrote or
+/// removed and skip the pass when those are few and scores already exist.
+pub(super) fn refresh_staged_node_centrality(
+    staged: &mut StagedSnapshot,
+    changed_file_ids: Option<&[i64]>,
+) {
+    let started = Instant::now();
+    let store = staged.store_mut();
+    if changed_file_ids.is_some_and(|ids| ids.len() <= CENTRALITY_INCREMENTAL_SKIP_MAX_FILES)
+        && store.has_node_centrality().unwrap_or(false)
+    {
+        tracing::debug!("Kept node centrality from the previous run");
+        return;
+    }
+    let iterations = centrality_iterations_setting(store);
+    match store.refresh_node_centrality(iterations) {
+        Ok(stats) => tracing::debug!(
+            "Scored centrality for {} nodes over {} edges in {} iterations ({:?})",
+            stats.node_count,
+            stats.edge_count,
+            stats.iterations,
+            started.elapsed()
+        ),
+        Err(error) => tracing::warn!("Failed to compute node centrality: {error}"),
+    }
+}
+
+pub(super) fn refresh_staged_entry_distances(staged: &mut StagedSnapshot) {
+    let started = Instant::now();
+    match staged.store_mut().refresh_entry_point_distances() {
+        Ok(reach) => tracing::debug!(
+            "Stored entry-point distances for {} nodes from {} entry points ({:?})",
+            reach.distances.len(),
+            reach.entry_points.len(),
+            started.elapsed()
+        ),
+        Err(error) => tracing::warn!("Failed to compute entry-point distances: {error}"),
+    }
+}
+
+/// Rescan `TODO`, `FIXME`, and `HACK` comments in files added or changed
+/// since their last scan. Files that cannot be read keep no markers.
+pub(super) fn refresh_staged_code_markers(staged: &mut StagedSnapshot) {
+    let started = Instant::now();
+    let sources = match staged.store_mut().code_marker_sources_pending() {
+        Ok(sources) => sources,
+        Err(error) => {
+            tracing::warn!("Failed to list files to scan for code markers: {error}");
+            return;
+        }
+    };
+    let scans = sources
+        .into_iter()
+        .map(|source| CodeMarkerScan {
+            markers: std::fs::read_to_string(&source.path)
+                .map(|text| extract_code_markers(&source.path, &text))
+                .unwrap_or_default(),
+            file_id: source.file_id,
+            modification_time: source.modification_time,
+            content_hash: source.content_hash,
+        })
+        .collect::<Vec<_>>();
+    match staged.store_mut().replace_code_markers(&scans) {
+        Ok(marker_count) => tracing::debug!(
+            "Scanned {} files for code markers, found {marker_count} ({:?})",
+            scans.len(),
+            started.elapsed()
+        ),
+        Err(error) => tracing::warn!("Failed to store code markers: {error}"),
+    }
+}
+
+/// Rescan string literals and constant values in changed files when the
+/// project turned string literal indexing on; otherwise drop any recorded
+/// while it was on.
+pub(super) fn refresh_staged_string_literals(staged: &mut StagedSnapshot) {
+    if !string_literals_setting(staged.store_mut()) {
+        if let Err(error) = staged.store_mut().clear_string_literals() {
+            tracing::warn!("Failed to clear string literals: {error}");
+        }
+        return;
+    }
+    let started = Instant::now();
+    let sources = match staged.store_mut().string_literal_sources_pending() {
+        Ok(sources) => sources,
+        Err(error) => {
+            tracing::warn!("Failed to list files to scan for string literals: {error}");
+            return;
+        }
+    };
+    let scans = sources
+        .into_iter()
+        .map(|source| StringLiteralScan {
+            literals: std::fs::read_to_string(&source.path)
+                .map(|text| extract_string_literals(&source.path, &text))
+                .unwrap_or_default(),
+            file_id: source.file_id,
+            modification_time: source.modification_time,
+            content_hash: source.content_hash,
+        })
+        .collect::<Vec<_>>();
+    match staged.store_mut().replace_string_literals(&scans) {
+        Ok(literal_count) => tracing::debug!(
+            "Scanned {} files for string literals, found {literal_count} ({:?})",
+            scans.len(),
+            started.elapsed()
+        ),
+        Err(error) => tracing::warn!("Failed to store string literals: {error}"),
+    }
+}
+
 pub(super) fn stage_core_publication_identity(
     staged: &mut StagedSnapshot,
     root: &Path,
@@ -8,6 +8,7 @@ use super::{
     validate_structural_text_units,
 };
 #[cfg(test)]
+use codestory_store::StorageError;
 use std::cell::RefCell;
 use std::io;
 use std::time::{Instant, UNIX_EPOCH};
@@ -707,7 +708,19 @@ pub(super) fn open_storage_for_read(path: &Path) -> Result<Storage, ApiError> {
     } else {
         Storage::open_read_only(path)
     };
-    storage.map_err(|error| ApiError::internal(format!("Failed to open storage: {error}")))
+    storage.map_err(storage_open_error)
+}
+
+/// Report a damaged database as recoverable by a full refresh instead of as
+/// an internal failure.
+pub(super) fn storage_open_error(error: StorageError) -> ApiError {
+    match error {
+        StorageError::IntegrityCheckFailed(_) => ApiError::storage_corrupt(
+            format!("Project storage is damaged: {error}"),
+            vec!["codestory-cli index --refresh full".to_string()],
+        ),
+        error => ApiError::internal(format!("Failed to open storage: {error}")),
+    }
 }
 
 pub(super) fn open_existing_storage_for_read(path: &Path) -> Result<Storage, ApiError> {
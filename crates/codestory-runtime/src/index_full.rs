@@ -1,11 +1,15 @@
+use crate::dependency_index::prepare_dependency_storage;
 use crate::index_commit::{
     CoreCommitMode, PreparedCoreCommit, StagedPreparation, next_index_publication,
+    record_staged_index_history, refresh_staged_code_markers, refresh_staged_entry_distances,
+    refresh_staged_node_centrality, refresh_staged_string_literals,
     stage_core_publication_identity,
 };
 use crate::index_incremental::spawn_progress_forwarder;
+use crate::index_queue::prioritize_open_files;
 use crate::index_timings::{
     FullRefreshWallDurations, IndexingRunSummary, apply_full_refresh_pipeline_timings,
-    core_indexing_phase_timings,
+    core_indexing_phase_timings, staged_indexing_run_delta,
 };
 #[cfg(test)]
 use crate::publication::{run_full_refresh_staged_store_hook, run_source_policy_after_plan_hook};
@@ -39,7 +43,7 @@ use codestory_workspace::{
     OversizedSourceExclusionCandidate, RefreshExecutionPlan, SourceIndexPolicy, WorkspaceManifest,
 };
 use crossbeam_channel::Sender;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
 use uuid::Uuid;
 
@@ -236,6 +240,37 @@ fn copy_forward_full_refresh_artifacts(staged: &mut StagedSnapshot, storage_path
             tracing::warn!("Failed to copy dense anchor inputs into staged storage: {error}")
         }
     }
+    match staged.store_mut().copy_node_aliases_from(storage_path) {
+        Ok(copied) => tracing::debug!(copied, "Copied node aliases into staged storage"),
+        Err(error) => tracing::warn!("Failed to copy node aliases into staged storage: {error}"),
+    }
+    match staged
+        .store_mut()
+        .copy_trail_filter_presets_from(storage_path)
+    {
+        Ok(copied) => tracing::debug!(copied, "Copied trail filter presets into staged storage"),
+        Err(error) => {
+            tracing::warn!("Failed to copy trail filter presets into staged storage: {error}")
+        }
+    }
+    match staged.store_mut().copy_project_settings_from(storage_path) {
+        Ok(copied) => tracing::debug!(copied, "Copied project settings into staged storage"),
+        Err(error) => {
+            tracing::warn!("Failed to copy project settings into staged storage: {error}")
+        }
+    }
+    match staged.store_mut().copy_generation_from(storage_path) {
+        Ok(generation) => tracing::debug!(generation, "Carried storage generation forward"),
+        Err(error) => {
+            tracing::warn!("Failed to carry storage generation into staged storage: {error}")
+        }
+    }
+    match staged.store_mut().copy_index_history_from(storage_path) {
+        Ok(copied) => tracing::debug!(copied, "Copied indexing history into staged storage"),
+        Err(error) => {
+            tracing::warn!("Failed to copy indexing history into staged storage: {error}")
+        }
+    }
 }
 
 struct PreparedFullRefreshSnapshots {
@@ -353,7 +388,7 @@ fn run_full_refresh_indexer(
     };
     let bus = EventBus::new();
     let forwarder = spawn_progress_forwarder(bus.receiver(), events_tx.clone());
-    let indexer = V2WorkspaceIndexer::new(root.to_path_buf())
+    let mut indexer = V2WorkspaceIndexer::new(root.to_path_buf())
         .with_source_index_policy(source_index_policy.clone())
         .with_artifact_cache_policies(ArtifactCachePolicies {
             parser: ArtifactCachePolicy::KnownEmpty,
@@ -363,6 +398,9 @@ fn run_full_refresh_indexer(
                 ArtifactCachePolicy::KnownEmpty
             },
         });
+    if let Some(path) = prepare_dependency_storage(root, storage_path, true) {
+        indexer = indexer.with_dependency_storage(path);
+    }
     wall_durations.stage_open = stage_started.elapsed();
     let execution_started = Instant::now();
     let result = indexer.run_with_policy_exclusions(
@@ -410,6 +448,7 @@ fn prepare_full_refresh(
     cancel_token: Option<&CancellationToken>,
     runtime: &codestory_retrieval::SidecarRuntimeConfig,
     source_index_policy: &SourceIndexPolicy,
+    open_files: &[PathBuf],
 ) -> Result<PreparedFullRefresh, ApiError> {
     let core_refresh_started = Instant::now();
     let live_started = Instant::now();
@@ -421,8 +460,9 @@ fn prepare_full_refresh(
     let discovery_started = Instant::now();
     let workspace = runtime_workspace_manifest(root, storage_path)
         .map_err(|error| ApiError::internal(format!("Failed to open project: {error}")))?;
-    let (execution_plan, policy_exclusions) =
+    let (mut execution_plan, policy_exclusions) =
         full_refresh_execution_plan_with_coverage(root, &workspace, source_index_policy)?;
+    prioritize_open_files(&mut execution_plan, root, open_files);
     wall_durations.source_discovery = discovery_started.elapsed();
     let output = run_full_refresh_indexer(
         FullRefreshIndexerContext {
@@ -466,6 +506,7 @@ fn prepare_full_refresh(
     })
 }
 
+/// `open_files`, relative to `root`, are parsed before the rest of the tree.
 pub(super) fn index_full_for_runtime(
     root: &Path,
     storage_path: &Path,
@@ -473,6 +514,7 @@ pub(super) fn index_full_for_runtime(
     cancel_token: Option<&CancellationToken>,
     runtime: &codestory_retrieval::SidecarRuntimeConfig,
     source_index_policy: &SourceIndexPolicy,
+    open_files: &[PathBuf],
 ) -> Result<IndexingRunSummary, ApiError> {
     let PreparedFullRefresh {
         mut staged,
@@ -490,6 +532,7 @@ pub(super) fn index_full_for_runtime(
         cancel_token,
         runtime,
         source_index_policy,
+        open_files,
     )?;
     let publication = &live_state.publication;
     let recovering_incomplete_run = live_state.recovering_incomplete_run;
@@ -537,6 +580,13 @@ pub(super) fn index_full_for_runtime(
         return Err(indexing_cancelled_error());
     }
     wall_durations.search_generation = wall_stage_started.elapsed();
+    let run_delta =
+        staged_indexing_run_delta(root, storage_path, staged.store_mut(), &index_stats, None);
+    record_staged_index_history(&mut staged, storage_path, publication, None);
+    refresh_staged_node_centrality(&mut staged, None);
+    refresh_staged_entry_distances(&mut staged);
+    refresh_staged_code_markers(&mut staged);
+    refresh_staged_string_literals(&mut staged);
     wall_stage_started = Instant::now();
     let prepared_commit =
         PreparedCoreCommit::new(staged, prepared_search_state, storage_path, publication);
@@ -557,6 +607,7 @@ pub(super) fn index_full_for_runtime(
         prepared_snapshots.semantic_stats.semantic_context_index_ms,
     );
     apply_full_refresh_pipeline_timings(&mut phase_timings, &index_stats, full_refresh_wall);
+    phase_timings.run_delta = run_delta;
     Ok(IndexingRunSummary {
         phase_timings,
         staged_semantic_stats: prepared_snapshots.semantic_stats,
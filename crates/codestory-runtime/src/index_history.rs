@@ -0,0 +1,170 @@
+use crate::AppController;
+use codestory_contracts::api::{
+    ApiError, EdgeId, EdgeKind, GraphAtDto, GraphAtRequest, IndexHistoryEventDto,
+    IndexHistoryRunDto, IndexHistoryRunsDto, IndexHistorySubjectDto, NodeHistoryDto,
+    NodeHistoryRequest, NodeId, NodeKind,
+};
+use codestory_store::{IndexHistoryRun, IndexHistorySubject};
+
+fn run_dto(run: IndexHistoryRun) -> IndexHistoryRunDto {
+    IndexHistoryRunDto {
+        run_id: run.run_id,
+        recorded_at_epoch_ms: run.recorded_at_epoch_ms,
+    }
+}
+
+impl AppController {
+    /// Indexing runs whose changes are retained, oldest first.
+    pub fn index_history_runs(&self) -> Result<IndexHistoryRunsDto, ApiError> {
+        let storage = self.open_storage_read_only()?;
+        let runs = storage
+            .index_history_runs()
+            .map_err(|e| ApiError::internal(format!("Failed to load indexing history: {e}")))?;
+        Ok(IndexHistoryRunsDto {
+            runs: runs.into_iter().map(run_dto).collect(),
+        })
+    }
+
+    /// When `req.id` and the edges touching it were added or removed, within
+    /// the retained runs.
+    pub fn node_history(&self, req: NodeHistoryRequest) -> Result<NodeHistoryDto, ApiError> {
+        let node_id = req.id.to_core()?;
+        let storage = self.open_storage_read_only()?;
+        let events = storage
+            .node_history(node_id)
+            .map_err(|e| ApiError::internal(format!("Failed to load node history: {e}")))?;
+        let events = events
+            .into_iter()
+            .map(|event| IndexHistoryEventDto {
+                run_id: event.run_id,
+                recorded_at_epoch_ms: event.recorded_at_epoch_ms,
+                added: event.added,
+                subject: match event.subject {
+                    IndexHistorySubject::Node { id, kind, name } => IndexHistorySubjectDto::Node {
+                        id: NodeId::from(id),
+                        kind: NodeKind::from(kind),
+                        name,
+                    },
+                    IndexHistorySubject::Edge {
+                        id,
+                        kind,
+                        source,
+                        target,
+                    } => IndexHistorySubjectDto::Edge {
+                        id: EdgeId::from(id),
+                        kind: EdgeKind::from(kind),
+                        source: NodeId::from(source),
+                        target: NodeId::from(target),
+                    },
+                },
+            })
+            .collect();
+        Ok(NodeHistoryDto {
+            node_id: req.id,
+            events,
+        })
+    }
+
+    /// The node and edge ids present right after run `req.run_id`.
+    pub fn graph_at(&self, req: GraphAtRequest) -> Result<GraphAtDto, ApiError> {
+        let run_id = req.run_id.trim();
+        if run_id.is_empty() {
+            return Err(ApiError::invalid_argument("Pass an indexing run id."));
+        }
+        let storage = self.open_storage_read_only()?;
+        let graph = storage
+            .graph_at(run_id)
+            .map_err(|e| ApiError::internal(format!("Failed to rebuild graph at run: {e}")))?
+            .ok_or_else(|| {
+                ApiError::not_found(format!(
+                    "Indexing run `{run_id}` is not retained; list runs with `history`."
+                ))
+            })?;
+        Ok(GraphAtDto {
+            run: run_dto(graph.run),
+            node_ids: graph.node_ids.into_iter().map(NodeId::from).collect(),
+            edge_ids: graph.edge_ids.into_iter().map(EdgeId::from).collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codestory_contracts::graph::{Node, NodeId as CoreNodeId, NodeKind as CoreNodeKind};
+    use codestory_store::Store;
+    use tempfile::tempdir;
+
+    #[test]
+    fn node_history_and_graph_at_report_retained_runs() {
+        let project = tempdir().expect("project");
+        let cache = project.path().join(".cache");
+        std::fs::create_dir_all(&cache).expect("create cache");
+        let previous = cache.join("previous.db");
+        let storage_path = cache.join("codestory.db");
+        drop(Store::open(&previous).expect("create previous store"));
+        {
+            let mut store = Store::open(&storage_path).expect("create store");
+            store
+                .record_index_history(&previous, "run-1", 1_000, None)
+                .expect("record baseline");
+            store
+                .insert_nodes_batch(&[Node {
+                    id: CoreNodeId(7),
+                    kind: CoreNodeKind::FUNCTION,
+                    serialized_name: "added_later".to_string(),
+                    ..Default::default()
+                }])
+                .expect("insert node");
+            store
+                .record_index_history(&previous, "run-2", 2_000, None)
+                .expect("record second run");
+        }
+        let controller = AppController::new();
+        {
+            let mut state = controller.state.lock();
+            state.project_root = Some(project.path().to_path_buf());
+            state.storage_path = Some(storage_path);
+        }
+
+        let runs = controller.index_history_runs().expect("runs");
+        assert_eq!(
+            runs.runs
+                .iter()
+                .map(|run| run.run_id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["run-1", "run-2"]
+        );
+        let history = controller
+            .node_history(NodeHistoryRequest {
+                id: NodeId("7".to_string()),
+            })
+            .expect("node history");
+        assert_eq!(history.events.len(), 1);
+        assert_eq!(history.events[0].run_id, "run-2");
+        assert!(history.events[0].added);
+        assert!(matches!(
+            &history.events[0].subject,
+            IndexHistorySubjectDto::Node { name, .. } if name == "added_later"
+        ));
+
+        let before = controller
+            .graph_at(GraphAtRequest {
+                run_id: "run-1".to_string(),
+            })
+            .expect("graph at first run");
+        assert!(before.node_ids.is_empty());
+        let after = controller
+            .graph_at(GraphAtRequest {
+                run_id: "run-2".to_string(),
+            })
+            .expect("graph at second run");
+        assert_eq!(after.node_ids, vec![NodeId("7".to_string())]);
+        let missing = controller
+            .graph_at(GraphAtRequest {
+                run_id: "run-9".to_string(),
+            })
+            .expect_err("unknown run");
+        assert_eq!(missing.code, "not_found");
+    }
+}
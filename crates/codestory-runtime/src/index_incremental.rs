@@ -1,8 +1,14 @@
+use crate::dependency_index::prepare_dependency_storage;
 use crate::index_commit::{
     CoreCommitMode, PreparedCoreCommit, StagedPreparation, next_index_publication,
+    record_staged_index_history, refresh_staged_code_markers, refresh_staged_entry_distances,
+    refresh_staged_node_centrality, refresh_staged_string_literals,
     stage_core_publication_identity,
 };
-use crate::index_timings::{IndexingRunSummary, core_indexing_phase_timings};
+use crate::index_queue::prioritize_open_files;
+use crate::index_timings::{
+    IndexingRunSummary, core_indexing_phase_timings, staged_indexing_run_delta,
+};
 use crate::search_publication::discard_unpublished_search_generation;
 use crate::search_state_cache::{
     ensure_indexing_active, indexing_cancelled_error, is_indexing_cancelled,
@@ -38,7 +44,7 @@ use codestory_workspace::{
 };
 use crossbeam_channel::{Receiver, Sender};
 use std::collections::{HashMap, HashSet};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::time::Instant;
 use uuid::Uuid;
 
@@ -56,9 +62,11 @@ pub(super) fn index_incremental(
         cancel_token,
         &test_sidecar_runtime_from_env(),
         &SourceIndexPolicy::default(),
+        &[],
     )
 }
 
+/// `open_files`, relative to `root`, are parsed before the other changed files.
 pub(super) fn index_incremental_for_runtime(
     root: &Path,
     storage_path: &Path,
@@ -66,14 +74,43 @@ pub(super) fn index_incremental_for_runtime(
     cancel_token: Option<&CancellationToken>,
     runtime: &codestory_retrieval::SidecarRuntimeConfig,
     source_index_policy: &SourceIndexPolicy,
+    open_files: &[PathBuf],
 ) -> Result<IndexingRunSummary, ApiError> {
     run_incremental_indexing_common(
         root,
         storage_path,
+        None,
         events_tx,
         cancel_token,
         runtime,
         source_index_policy,
+        open_files,
+    )
+}
+
+/// Incremental refresh limited to one file.
+///
+/// Only `file` is checked against the workspace scope, exclusion, and
+/// freshness rules, without walking the workspace, and only `file` is parsed
+/// or removed. Other pending changes wait for the next refresh.
+pub(super) fn index_file_for_runtime(
+    root: &Path,
+    storage_path: &Path,
+    file: &Path,
+    events_tx: &Sender<AppEventPayload>,
+    cancel_token: Option<&CancellationToken>,
+    runtime: &codestory_retrieval::SidecarRuntimeConfig,
+    source_index_policy: &SourceIndexPolicy,
+) -> Result<IndexingRunSummary, ApiError> {
+    run_incremental_indexing_common(
+        root,
+        storage_path,
+        Some(file),
+        events_tx,
+        cancel_token,
+        runtime,
+        source_index_policy,
+        &[],
     )
 }
 
@@ -269,6 +306,30 @@ fn incremental_execution_plan(
     ))
 }
 
+fn single_file_execution_plan(
+    staged: &mut StagedSnapshot,
+    root: &Path,
+    storage_path: &Path,
+    source_index_policy: &SourceIndexPolicy,
+    file: &Path,
+) -> Result<(RefreshExecutionPlan, Vec<OversizedSourceExclusionCandidate>), ApiError> {
+    let workspace = runtime_workspace_manifest(root, storage_path)
+        .map_err(|error| ApiError::internal(format!("Failed to open project: {error}")))?;
+    let refresh_inputs = workspace_refresh_inputs(staged.store_mut())?;
+    let policy_refresh = workspace
+        .build_file_execution_outcome_with_policy(&refresh_inputs, file, source_index_policy)
+        .map_err(|error| {
+            ApiError::internal(format!(
+                "Failed to generate refresh info for {}: {error}",
+                file.display()
+            ))
+        })?;
+    Ok((
+        policy_refresh.refresh.plan,
+        policy_refresh.policy_exclusions,
+    ))
+}
+
 struct IncrementalSemanticPlan {
     previous_indexed_file_ids_by_path: HashMap<String, codestory_contracts::graph::NodeId>,
     policy_excluded_seed_file_ids: HashSet<codestory_contracts::graph::NodeId>,
@@ -345,6 +406,7 @@ fn plan_incremental_semantics(
 
 struct IncrementalIndexerContext<'a> {
     root: &'a Path,
+    storage_path: &'a Path,
     events_tx: &'a Sender<AppEventPayload>,
     cancel_token: Option<&'a CancellationToken>,
     source_index_policy: &'a SourceIndexPolicy,
@@ -359,6 +421,7 @@ fn run_incremental_indexer(
 ) -> Result<IncrementalIndexingStats, ApiError> {
     let IncrementalIndexerContext {
         root,
+        storage_path,
         events_tx,
         cancel_token,
         source_index_policy,
@@ -377,9 +440,13 @@ fn run_incremental_indexer(
         let _ = forwarder.join();
         return Err(error);
     }
-    let result = V2WorkspaceIndexer::new(root.to_path_buf())
-        .with_source_index_policy(source_index_policy.clone())
-        .run_with_policy_exclusions(staged.store_mut(), execution_plan, &bus, cancel_token);
+    let mut indexer = V2WorkspaceIndexer::new(root.to_path_buf())
+        .with_source_index_policy(source_index_policy.clone());
+    if let Some(path) = prepare_dependency_storage(root, storage_path, false) {
+        indexer = indexer.with_dependency_storage(path);
+    }
+    let result =
+        indexer.run_with_policy_exclusions(staged.store_mut(), execution_plan, &bus, cancel_token);
     drop(bus);
     let _ = forwarder.join();
     let outcome = match result {
@@ -497,15 +564,18 @@ struct PreparedIncrementalRefresh {
     semantic_stats: SemanticProjectionStats,
     semantic_refresh_scope: HashSet<codestory_contracts::graph::NodeId>,
     policy_exclusions: Vec<OversizedSourceExclusionCandidate>,
+    changed_file_ids: Vec<i64>,
 }
 
 fn prepare_incremental_refresh(
     root: &Path,
     storage_path: &Path,
+    only_file: Option<&Path>,
     events_tx: &Sender<AppEventPayload>,
     cancel_token: Option<&CancellationToken>,
     runtime: &codestory_retrieval::SidecarRuntimeConfig,
     source_index_policy: &SourceIndexPolicy,
+    open_files: &[PathBuf],
 ) -> Result<PreparedIncrementalRefresh, ApiError> {
     ensure_incremental_refresh_compatible(root, storage_path)?;
     ensure_indexing_active(cancel_token)?;
@@ -558,18 +628,29 @@ fn prepare_incremental_refresh(
                 "Failed to invalidate staged derived index snapshots: {error}"
             ))
         })?;
-    let (execution_plan, mut policy_exclusions) = incremental_execution_plan(
-        preparation.staged_mut(),
-        root,
-        storage_path,
-        source_index_policy,
-    )?;
+    let (mut execution_plan, mut policy_exclusions) = match only_file {
+        Some(file) => single_file_execution_plan(
+            preparation.staged_mut(),
+            root,
+            storage_path,
+            source_index_policy,
+            file,
+        )?,
+        None => incremental_execution_plan(
+            preparation.staged_mut(),
+            root,
+            storage_path,
+            source_index_policy,
+        )?,
+    };
+    prioritize_open_files(&mut execution_plan, root, open_files);
     let mut semantic_plan =
         plan_incremental_semantics(preparation.staged_mut(), root, &execution_plan)?;
     let stats = run_incremental_indexer(
         preparation.staged_mut(),
         IncrementalIndexerContext {
             root,
+            storage_path,
             events_tx,
             cancel_token,
             source_index_policy,
@@ -579,6 +660,7 @@ fn prepare_incremental_refresh(
         &mut policy_exclusions,
     )?;
     validate_incremental_refresh_coverage(preparation.staged_mut(), root)?;
+    let changed_file_ids = incremental_changed_file_ids(preparation.staged_mut(), &execution_plan)?;
     let semantic_refresh_scope = incremental_semantic_refresh_scope(
         preparation.staged_mut(),
         root,
@@ -624,16 +706,41 @@ fn prepare_incremental_refresh(
         semantic_stats,
         semantic_refresh_scope,
         policy_exclusions,
+        changed_file_ids,
     })
 }
 
+/// File ids an incremental run rewrote or removed, for scoping its delta.
+/// Files new to the index only have an id once the staged run stored them.
+fn incremental_changed_file_ids(
+    staged: &mut StagedSnapshot,
+    plan: &RefreshExecutionPlan,
+) -> Result<Vec<i64>, ApiError> {
+    let mut file_ids = plan.files_to_remove.clone();
+    let mut new_files = Vec::new();
+    for path in &plan.files_to_index {
+        match plan.existing_file_ids.get(path) {
+            Some(file_id) => file_ids.push(*file_id),
+            None => new_files.push(path.clone()),
+        }
+    }
+    let stored = staged
+        .store_mut()
+        .get_files_by_paths(&new_files)
+        .map_err(|error| ApiError::internal(format!("Failed to read staged file ids: {error}")))?;
+    file_ids.extend(stored.values().map(|file| file.id));
+    Ok(file_ids)
+}
+
 fn run_incremental_indexing_common(
     root: &Path,
     storage_path: &Path,
+    only_file: Option<&Path>,
     events_tx: &Sender<AppEventPayload>,
     cancel_token: Option<&CancellationToken>,
     runtime: &codestory_retrieval::SidecarRuntimeConfig,
     source_index_policy: &SourceIndexPolicy,
+    open_files: &[PathBuf],
 ) -> Result<IndexingRunSummary, ApiError> {
     let PreparedIncrementalRefresh {
         mut staged,
@@ -644,13 +751,16 @@ fn run_incremental_indexing_common(
         semantic_stats: staged_semantic_stats,
         semantic_refresh_scope: llm_refresh_scope,
         policy_exclusions,
+        changed_file_ids,
     } = prepare_incremental_refresh(
         root,
         storage_path,
+        only_file,
         events_tx,
         cancel_token,
         runtime,
         source_index_policy,
+        open_files,
     )?;
     let workspace = match runtime_workspace_manifest(root, storage_path) {
         Ok(workspace) => workspace,
@@ -695,11 +805,28 @@ fn run_incremental_indexing_common(
         discard_unpublished_search_generation(storage_path, &publication);
         return Err(indexing_cancelled_error());
     }
+    let run_delta = staged_indexing_run_delta(
+        root,
+        storage_path,
+        staged.store_mut(),
+        &index_stats,
+        Some(&changed_file_ids),
+    );
+    record_staged_index_history(
+        &mut staged,
+        storage_path,
+        &publication,
+        Some(&changed_file_ids),
+    );
+    refresh_staged_node_centrality(&mut staged, Some(&changed_file_ids));
+    refresh_staged_entry_distances(&mut staged);
+    refresh_staged_code_markers(&mut staged);
+    refresh_staged_string_literals(&mut staged);
     let prepared_commit =
         PreparedCoreCommit::new(staged, prepared_search_state, storage_path, &publication);
     let (prepared_search_state, staged_publish_stats, publish_duration) =
         prepared_commit.commit(CoreCommitMode::Incremental, cancel_token)?;
-    let phase_timings = core_indexing_phase_timings(
+    let mut phase_timings = core_indexing_phase_timings(
         &index_stats,
         staged_finalize_stats,
         detail_snapshot_ms,
@@ -707,6 +834,7 @@ fn run_incremental_indexing_common(
         publish_duration,
         staged_semantic_stats.semantic_context_index_ms,
     );
+    phase_timings.run_delta = run_delta;
     Ok(IndexingRunSummary {
         phase_timings,
         staged_semantic_stats,
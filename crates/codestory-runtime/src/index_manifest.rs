@@ -0,0 +1,262 @@
+//! `<database>.index_manifest.json`: how the index next to it was produced.
+
+use crate::index_coverage::current_epoch_ms;
+use crate::index_settings::{centrality_iterations_setting, string_literals_setting};
+use crate::{AppController, Storage, clamp_u128_to_u32, no_project_error};
+use codestory_contracts::api::{ApiError, AppEventPayload, IndexManifestDto, IndexMode};
+use codestory_retrieval::SidecarRuntimeConfig;
+use codestory_store::CURRENT_SCHEMA_VERSION;
+use codestory_workspace::SourceIndexPolicy;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+const MANIFEST_FORMAT_VERSION: u32 = 1;
+const TOOL_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// The manifest lives next to the live database and is named after it, so
+/// databases sharing a cache directory keep their own manifests.
+fn manifest_path(storage_path: &Path) -> PathBuf {
+    storage_path.with_extension("index_manifest.json")
+}
+
+/// SHA-256 over the policy, retrieval, and project settings that change what
+/// an index run writes.
+fn settings_hash(
+    policy: &SourceIndexPolicy,
+    runtime_config: &SidecarRuntimeConfig,
+    storage: &Storage,
+) -> String {
+    let retrieval = &runtime_config.retrieval;
+    let settings = serde_json::json!({
+        "source_index_policy": policy,
+        "hybrid_enabled": retrieval.hybrid_enabled,
+        "semantic_doc_scope": retrieval.semantic_doc_scope,
+        "semantic_doc_alias_mode": retrieval.semantic_doc_alias_mode,
+        "semantic_doc_max_tokens": retrieval.semantic_doc_max_tokens,
+        "string_literals": string_literals_setting(storage),
+        "centrality_iterations": centrality_iterations_setting(storage),
+    });
+    format!("{:x}", Sha256::digest(settings.to_string().as_bytes()))
+}
+
+fn read_manifest(storage_path: &Path) -> Result<Option<IndexManifestDto>, ApiError> {
+    let path = manifest_path(storage_path);
+    let bytes = match fs::read(&path) {
+        Ok(bytes) => bytes,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(error) => {
+            return Err(ApiError::internal(format!(
+                "Failed to read index manifest {}: {error}",
+                path.display()
+            )));
+        }
+    };
+    serde_json::from_slice(&bytes).map(Some).map_err(|error| {
+        ApiError::internal(format!(
+            "Failed to parse index manifest {}: {error}",
+            path.display()
+        ))
+    })
+}
+
+fn write_manifest(storage_path: &Path, manifest: &IndexManifestDto) -> Result<(), ApiError> {
+    let path = manifest_path(storage_path);
+    let bytes = serde_json::to_vec_pretty(manifest)
+        .map_err(|error| ApiError::internal(format!("Failed to encode index manifest: {error}")))?;
+    fs::write(&path, bytes).map_err(|error| {
+        ApiError::internal(format!(
+            "Failed to write index manifest {}: {error}",
+            path.display()
+        ))
+    })
+}
+
+/// Why an index described by `manifest` may not match what this build would
+/// produce, if it may not.
+///
+/// Releases before 1.0 change index output between minor versions, so the
+/// release line is `major.minor` until then and `major` after.
+fn manifest_incompatibility(manifest: &IndexManifestDto) -> Option<String> {
+    if manifest.format_version > MANIFEST_FORMAT_VERSION {
+        return Some(format!(
+            "index manifest format {} is newer than this build understands ({MANIFEST_FORMAT_VERSION})",
+            manifest.format_version
+        ));
+    }
+    if manifest.schema_version != CURRENT_SCHEMA_VERSION {
+        return Some(format!(
+            "index was written with schema {} by codestory {}; this build uses schema {CURRENT_SCHEMA_VERSION}",
+            manifest.schema_version, manifest.tool_version
+        ));
+    }
+    if release_line(&manifest.tool_version) != release_line(TOOL_VERSION) {
+        return Some(format!(
+            "index was produced by codestory {}; this build is {TOOL_VERSION}",
+            manifest.tool_version
+        ));
+    }
+    None
+}
+
+fn release_line(version: &str) -> Vec<&str> {
+    let mut parts = version.split(['.', '-', '+']);
+    let major = parts.next().unwrap_or_default();
+    if major == "0" {
+        vec![major, parts.next().unwrap_or_default()]
+    } else {
+        vec![major]
+    }
+}
+
+impl AppController {
+    /// The manifest written by the last successful indexing run, if any.
+    pub fn index_manifest(&self) -> Result<Option<IndexManifestDto>, ApiError> {
+        let storage_path = self
+            .state
+            .lock()
+            .storage_path
+            .clone()
+            .ok_or_else(no_project_error)?;
+        read_manifest(&storage_path)
+    }
+
+    /// Record how the index at `storage_path` was just produced. Failures are
+    /// logged; the index itself is already published.
+    pub(crate) fn record_index_manifest(
+        &self,
+        storage_path: &Path,
+        mode: IndexMode,
+        duration: Duration,
+    ) {
+        let storage = match Storage::open_observational(storage_path) {
+            Ok(storage) => storage,
+            Err(error) => {
+                tracing::warn!(%error, "Failed to open the index for its manifest");
+                return;
+            }
+        };
+        let file_count = match storage.get_grounding_file_summary_count() {
+            Ok(file_count) => file_count,
+            Err(error) => {
+                tracing::warn!(%error, "Failed to count indexed files for the index manifest");
+                return;
+            }
+        };
+        let manifest = IndexManifestDto {
+            format_version: MANIFEST_FORMAT_VERSION,
+            tool_version: TOOL_VERSION.to_string(),
+            schema_version: CURRENT_SCHEMA_VERSION,
+            grammar_versions: codestory_indexer::grammar_versions(),
+            settings_hash: settings_hash(&self.source_index_policy, &self.runtime_config, &storage),
+            mode,
+            file_count,
+            duration_ms: clamp_u128_to_u32(duration.as_millis()),
+            completed_at_epoch_ms: current_epoch_ms(),
+        };
+        if let Err(error) = write_manifest(storage_path, &manifest) {
+            tracing::warn!(error = %error.message, "Failed to write index manifest");
+        }
+    }
+
+    /// Warn when the index at `storage_path` was produced by a build whose
+    /// output may differ from this one.
+    pub(crate) fn warn_on_incompatible_index_manifest(&self, storage_path: &Path) {
+        let manifest = match read_manifest(storage_path) {
+            Ok(Some(manifest)) => manifest,
+            Ok(None) => return,
+            Err(error) => {
+                tracing::warn!(error = %error.message, "Ignoring unreadable index manifest");
+                return;
+            }
+        };
+        if let Some(reason) = manifest_incompatibility(&manifest) {
+            tracing::warn!(%reason, "Opened an index produced by an incompatible version");
+            let _ = self.events_tx.send(AppEventPayload::StatusUpdate {
+                message: format!("Reindex recommended: {reason}."),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn manifest(tool_version: &str, schema_version: u32) -> IndexManifestDto {
+        IndexManifestDto {
+            format_version: MANIFEST_FORMAT_VERSION,
+            tool_version: tool_version.to_string(),
+            schema_version,
+            grammar_versions: BTreeMap::new(),
+            settings_hash: String::new(),
+            mode: IndexMode::Full,
+            file_count: 0,
+            duration_ms: 0,
+            completed_at_epoch_ms: 0,
+        }
+    }
+
+    #[test]
+    fn manifests_from_another_release_line_or_schema_are_incompatible() {
+        assert_eq!(
+            manifest_incompatibility(&manifest(TOOL_VERSION, CURRENT_SCHEMA_VERSION)),
+            None
+        );
+        assert!(
+            manifest_incompatibility(&manifest(TOOL_VERSION, CURRENT_SCHEMA_VERSION - 1)).is_some()
+        );
+        assert!(manifest_incompatibility(&manifest("99.0.0", CURRENT_SCHEMA_VERSION)).is_some());
+
+        assert_eq!(release_line("0.16.3"), release_line("0.16.0-rc.1"));
+        assert_ne!(release_line("0.16.3"), release_line("0.17.0"));
+        assert_eq!(release_line("2.1.0"), release_line("2.4.1"));
+    }
+
+    #[test]
+    fn manifests_are_named_after_their_database() {
+        let cache = Path::new("cache");
+        assert_eq!(
+            manifest_path(&cache.join("codestory.db")),
+            cache.join("codestory.index_manifest.json")
+        );
+        assert_ne!(
+            manifest_path(&cache.join("codestory.db")),
+            manifest_path(&cache.join("other.db"))
+        );
+    }
+
+    #[test]
+    fn settings_hash_tracks_string_literals_and_centrality_iterations() {
+        let project = tempfile::tempdir().expect("project");
+        let storage_path = project.path().join("codestory.db");
+        drop(Storage::open(&storage_path).expect("create store"));
+        let controller = AppController::new();
+        {
+            let mut state = controller.state.lock();
+            state.project_root = Some(project.path().to_path_buf());
+            state.storage_path = Some(storage_path.clone());
+        }
+        let hash = || {
+            let storage = Storage::open_read_only(&storage_path).expect("open store");
+            settings_hash(
+                &controller.source_index_policy,
+                &controller.runtime_config,
+                &storage,
+            )
+        };
+
+        let defaults = hash();
+        controller
+            .set_string_literal_indexing(true)
+            .expect("enable string literals");
+        let with_literals = hash();
+        assert_ne!(with_literals, defaults);
+        controller
+            .set_centrality_iterations(7)
+            .expect("set centrality iterations");
+        assert_ne!(hash(), with_literals);
+    }
+}
@@ -0,0 +1,87 @@
+use codestory_contracts::api::{IndexMode, IndexingPriority};
+use codestory_workspace::RefreshExecutionPlan;
+use std::path::{Path, PathBuf};
+
+/// Indexing requests that arrived while another run held the controller.
+///
+/// Requests coalesce by mode: a second incremental request while one is
+/// already waiting only raises the waiting request's priority. The next run
+/// is the highest-priority request, oldest first among equals.
+#[derive(Debug, Default)]
+pub(crate) struct IndexingQueue {
+    pending: Vec<QueuedIndexing>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct QueuedIndexing {
+    pub(crate) mode: IndexMode,
+    pub(crate) priority: IndexingPriority,
+}
+
+impl IndexingQueue {
+    /// Queue `request`, returning the effective entry and the number waiting.
+    pub(crate) fn push(&mut self, request: QueuedIndexing) -> (QueuedIndexing, usize) {
+        let entry = match self
+            .pending
+            .iter_mut()
+            .find(|queued| queued.mode == request.mode)
+        {
+            Some(queued) => {
+                queued.priority = queued.priority.max(request.priority);
+                *queued
+            }
+            None => {
+                self.pending.push(request);
+                request
+            }
+        };
+        (entry, self.pending.len())
+    }
+
+    pub(crate) fn pop(&mut self) -> Option<QueuedIndexing> {
+        let mut next = None::<(usize, IndexingPriority)>;
+        for (index, queued) in self.pending.iter().enumerate() {
+            if next.is_none_or(|(_, priority)| queued.priority > priority) {
+                next = Some((index, queued.priority));
+            }
+        }
+        next.map(|(index, _)| self.pending.remove(index))
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.pending.clear();
+    }
+}
+
+/// Priority used when a caller does not name one: full refreshes are
+/// rebuilds, incremental refreshes are watcher batches.
+pub(crate) fn default_indexing_priority(mode: IndexMode) -> IndexingPriority {
+    match mode {
+        IndexMode::Full => IndexingPriority::Rebuild,
+        IndexMode::Incremental => IndexingPriority::Watcher,
+    }
+}
+
+/// Schedule the files open in the client ahead of the rest of `plan`, so a
+/// refresh parses and reports them first. `open_files` are relative to
+/// `root`; plan paths may be relative too, or joined to `root` as given or
+/// canonicalized.
+pub(crate) fn prioritize_open_files(
+    plan: &mut RefreshExecutionPlan,
+    root: &Path,
+    open_files: &[PathBuf],
+) {
+    let mut roots = vec![root.to_path_buf()];
+    if let Ok(canonical) = root.canonicalize()
+        && canonical != root
+    {
+        roots.push(canonical);
+    }
+    let priority = open_files
+        .iter()
+        .flat_map(|file| {
+            std::iter::once(file.clone()).chain(roots.iter().map(move |root| root.join(file)))
+        })
+        .collect::<Vec<_>>();
+    plan.prioritize(&priority);
+}
@@ -0,0 +1,82 @@
+use crate::{AppController, Storage};
+use codestory_contracts::api::ApiError;
+use codestory_store::CENTRALITY_DEFAULT_ITERATIONS;
+
+const CENTRALITY_ITERATIONS_SETTING: &str = "centrality_iterations";
+const STRING_LITERALS_SETTING: &str = "string_literals";
+
+/// Most PageRank iterations a project may ask for; PageRank has long
+/// converged by then.
+pub(crate) const CENTRALITY_MAX_ITERATIONS: usize = 200;
+
+/// PageRank iterations indexing runs for the project whose settings
+/// `storage` holds.
+pub(crate) fn centrality_iterations_setting(storage: &Storage) -> usize {
+    storage
+        .project_setting(CENTRALITY_ITERATIONS_SETTING)
+        .ok()
+        .flatten()
+        .and_then(|value| value.parse::<usize>().ok())
+        .map(|iterations| iterations.min(CENTRALITY_MAX_ITERATIONS))
+        .unwrap_or(CENTRALITY_DEFAULT_ITERATIONS)
+}
+
+/// Whether indexing records string literals for the project whose settings
+/// `storage` holds.
+pub(crate) fn string_literals_setting(storage: &Storage) -> bool {
+    storage
+        .project_setting(STRING_LITERALS_SETTING)
+        .is_ok_and(|value| value.is_some())
+}
+
+impl AppController {
+    /// PageRank iterations indexing runs when it scores node centrality.
+    pub fn centrality_iterations(&self) -> Result<usize, ApiError> {
+        let storage = self.open_storage_read_only()?;
+        Ok(centrality_iterations_setting(&storage))
+    }
+
+    /// Save the PageRank iteration count. It applies from the next run that
+    /// rescores centrality.
+    pub fn set_centrality_iterations(&self, iterations: usize) -> Result<usize, ApiError> {
+        if !(1..=CENTRALITY_MAX_ITERATIONS).contains(&iterations) {
+            return Err(ApiError::invalid_argument(format!(
+                "Centrality iterations must be between 1 and {CENTRALITY_MAX_ITERATIONS}."
+            )));
+        }
+        let storage = self.open_storage()?;
+        storage
+            .set_project_setting(CENTRALITY_ITERATIONS_SETTING, &iterations.to_string())
+            .map_err(|e| {
+                ApiError::internal(format!("Failed to save centrality iterations: {e}"))
+            })?;
+        Ok(iterations)
+    }
+
+    /// Whether indexing records string literals and constant values.
+    pub fn string_literal_indexing(&self) -> Result<bool, ApiError> {
+        let storage = self.open_storage_read_only()?;
+        storage
+            .project_setting(STRING_LITERALS_SETTING)
+            .map(|value| value.is_some())
+            .map_err(|e| ApiError::internal(format!("Failed to load string literal indexing: {e}")))
+    }
+
+    /// Turn string literal indexing on or off. It applies from the next
+    /// indexing run, which scans every file not yet scanned when it is on and
+    /// drops the recorded literals when it is off.
+    pub fn set_string_literal_indexing(&self, enabled: bool) -> Result<bool, ApiError> {
+        let storage = self.open_storage()?;
+        let result = if enabled {
+            storage.set_project_setting(STRING_LITERALS_SETTING, "on")
+        } else {
+            storage
+                .delete_project_setting(STRING_LITERALS_SETTING)
+                .map(|_| ())
+        };
+        result.map_err(|e| {
+            ApiError::internal(format!("Failed to save string literal indexing: {e}"))
+        })?;
+        Ok(enabled)
+    }
+}
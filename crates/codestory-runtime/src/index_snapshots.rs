@@ -0,0 +1,116 @@
+use crate::index_coverage::current_epoch_ms;
+use codestory_contracts::api::{ApiError, IndexSnapshotDto};
+use codestory_store::Store;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const SNAPSHOT_DIR_NAME: &str = "snapshots";
+const SNAPSHOT_EXTENSION: &str = "db";
+
+/// Archived copies live next to the live database: `.codestory/snapshots/<epoch_ms>.db`.
+pub(crate) fn snapshot_dir(storage_path: &Path) -> PathBuf {
+    storage_path
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join(SNAPSHOT_DIR_NAME)
+}
+
+pub(crate) fn archive_snapshot(
+    storage_path: &Path,
+    retention: usize,
+) -> Result<IndexSnapshotDto, ApiError> {
+    let dir = snapshot_dir(storage_path);
+    let mut created_at = current_epoch_ms();
+    let mut target = dir.join(format!("{created_at}.{SNAPSHOT_EXTENSION}"));
+    while target.exists() {
+        created_at += 1;
+        target = dir.join(format!("{created_at}.{SNAPSHOT_EXTENSION}"));
+    }
+    Store::copy_database_snapshot(storage_path, &target).map_err(|error| {
+        ApiError::internal(format!(
+            "Failed to archive index snapshot {}: {error}",
+            target.display()
+        ))
+    })?;
+    prune_snapshots(&dir, retention)?;
+    snapshot_entry(&target)
+        .ok_or_else(|| ApiError::internal("Archived index snapshot disappeared after pruning"))
+}
+
+pub(crate) fn list_snapshots(storage_path: &Path) -> Result<Vec<IndexSnapshotDto>, ApiError> {
+    list_snapshots_in(&snapshot_dir(storage_path))
+}
+
+fn list_snapshots_in(dir: &Path) -> Result<Vec<IndexSnapshotDto>, ApiError> {
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let entries = fs::read_dir(dir).map_err(|error| {
+        ApiError::internal(format!(
+            "Failed to read snapshot directory {}: {error}",
+            dir.display()
+        ))
+    })?;
+    let mut snapshots = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| snapshot_entry(&entry.path()))
+        .collect::<Vec<_>>();
+    snapshots.sort_by(|left, right| {
+        right
+            .created_at_epoch_ms
+            .cmp(&left.created_at_epoch_ms)
+            .then_with(|| right.name.cmp(&left.name))
+    });
+    Ok(snapshots)
+}
+
+pub(crate) fn resolve_snapshot(storage_path: &Path, name: &str) -> Result<PathBuf, ApiError> {
+    let valid_name = !name.is_empty()
+        && Path::new(name).file_name().and_then(|file| file.to_str()) == Some(name)
+        && parse_snapshot_name(name).is_some();
+    if !valid_name {
+        return Err(ApiError::invalid_argument(format!(
+            "Invalid index snapshot name: {name}"
+        )));
+    }
+    let path = snapshot_dir(storage_path).join(name);
+    if !path.is_file() {
+        return Err(ApiError::not_found(format!(
+            "Index snapshot not found: {name}"
+        )));
+    }
+    Ok(path)
+}
+
+fn prune_snapshots(dir: &Path, retention: usize) -> Result<(), ApiError> {
+    for stale in list_snapshots_in(dir)?.into_iter().skip(retention) {
+        fs::remove_file(&stale.path).map_err(|error| {
+            ApiError::internal(format!(
+                "Failed to prune index snapshot {}: {error}",
+                stale.path
+            ))
+        })?;
+    }
+    Ok(())
+}
+
+fn snapshot_entry(path: &Path) -> Option<IndexSnapshotDto> {
+    let name = path.file_name()?.to_str()?;
+    let created_at_epoch_ms = parse_snapshot_name(name)?;
+    let metadata = fs::metadata(path)
+        .ok()
+        .filter(|metadata| metadata.is_file())?;
+    Some(IndexSnapshotDto {
+        name: name.to_string(),
+        path: path.to_string_lossy().to_string(),
+        created_at_epoch_ms,
+        size_bytes: metadata.len(),
+    })
+}
+
+fn parse_snapshot_name(name: &str) -> Option<i64> {
+    name.strip_suffix(SNAPSHOT_EXTENSION)?
+        .strip_suffix('.')?
+        .parse::<i64>()
+        .ok()
+}
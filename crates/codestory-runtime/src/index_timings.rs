@@ -1,15 +1,21 @@
+use crate::index_coverage::runtime_relative_path;
 use crate::semantic_projection::{SearchStateBuildResult, SemanticProjectionStats};
 use crate::{clamp_u64_to_u32, clamp_u128_to_u32, clamp_usize_to_u32};
 use codestory_contracts::api::{
     ArtifactCacheAccessTimings, ArtifactCachePolicyDto, CorePromotionTimings,
-    DatabaseSnapshotCopyTimings, FullRefreshWallTimings, IndexingPhaseTimings,
-    ProjectionPersistenceFamilyTimings, ProjectionPersistenceTimings,
+    DatabaseSnapshotCopyTimings, FileParseTiming, FullRefreshWallTimings, IndexingPhaseTimings,
+    IndexingRunDelta, ProjectionPersistenceFamilyTimings, ProjectionPersistenceTimings,
 };
+use codestory_contracts::graph::NodeId;
 use codestory_indexer::{ArtifactCacheFamilyStats, ArtifactCachePolicy, IncrementalIndexingStats};
 #[cfg(test)]
 use codestory_store::IndexPublicationRecord;
-use codestory_store::{StagedSnapshotFinalizeStats, StagedSnapshotPublishStats};
+use codestory_store::{
+    IndexContentDigest, StagedSnapshotFinalizeStats, StagedSnapshotPublishStats, StorageError,
+    Store,
+};
 use std::collections::HashSet;
+use std::path::Path;
 use std::time::Duration;
 
 pub(super) struct IndexingRunSummary {
@@ -174,6 +180,7 @@ fn apply_staged_publication_timings(
     );
     timings.summary_snapshot_ms = Some(finalize_stats.summary_snapshot_ms);
     timings.detail_snapshot_ms = Some(detail_snapshot_ms);
+    timings.analyze_ms = finalize_stats.analyze_ms;
     timings.publish_ms = Some(clamp_u128_to_u32(publish_duration.as_millis()));
     timings.staged_sqlite_wal_autocheckpoint_bytes = publish_stats.sqlite_wal_autocheckpoint_bytes;
     timings.staged_sqlite_checkpoint_ms = publish_stats.sqlite_checkpoint_ms;
@@ -184,6 +191,56 @@ fn apply_staged_publication_timings(
     timings.core_promotion = Some(core_promotion_timings(publish_stats.core_promotion));
 }
 
+/// Diff the staged generation against the live one before it is promoted.
+///
+/// `changed_file_ids` limits both sides to the files an incremental run
+/// touched; full runs pass `None` and diff every row. Returns `None` when the
+/// live store cannot be read; a missing live store counts as empty, so a
+/// first run reports everything as added.
+pub(super) fn staged_indexing_run_delta(
+    root: &Path,
+    storage_path: &Path,
+    staged: &Store,
+    stats: &IncrementalIndexingStats,
+    changed_file_ids: Option<&[i64]>,
+) -> Option<IndexingRunDelta> {
+    let delta = (|| -> Result<IndexingRunDelta, StorageError> {
+        let digest = |store: &Store| match changed_file_ids {
+            Some(file_ids) => store.index_content_digest_for_files(file_ids),
+            None => store.index_content_digest(),
+        };
+        let live = if storage_path.is_file() {
+            digest(&Store::open_observational(storage_path)?)?
+        } else {
+            IndexContentDigest::default()
+        };
+        let changes = live.delta_to(&digest(staged)?);
+        let mut slowest_files = Vec::new();
+        for (file_id, parse_us) in stats.slowest_parsed_files.iter() {
+            let Some(file) = staged.get_node(NodeId(file_id))? else {
+                continue;
+            };
+            slowest_files.push(FileParseTiming {
+                path: runtime_relative_path(root, Path::new(&file.serialized_name)),
+                parse_ms: clamp_u64_to_u32(parse_us / 1_000),
+            });
+        }
+        Ok(IndexingRunDelta {
+            nodes_added: clamp_usize_to_u32(changes.nodes_added),
+            nodes_removed: clamp_usize_to_u32(changes.nodes_removed),
+            edges_added: clamp_usize_to_u32(changes.edges_added),
+            edges_removed: clamp_usize_to_u32(changes.edges_removed),
+            files_added: clamp_usize_to_u32(changes.files_added),
+            files_removed: clamp_usize_to_u32(changes.files_removed),
+            files_updated: clamp_usize_to_u32(changes.files_updated),
+            slowest_files,
+        })
+    })();
+    delta
+        .inspect_err(|error| tracing::debug!(%error, "Skipped indexing run delta"))
+        .ok()
+}
+
 pub(super) fn apply_full_refresh_pipeline_timings(
     timings: &mut IndexingPhaseTimings,
     stats: &IncrementalIndexingStats,
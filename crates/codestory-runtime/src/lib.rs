@@ -25,7 +25,7 @@ use codestory_contracts::api::{
     SearchPlanCandidateWindowDto, SearchPlanChannelDto, SearchPlanDroppedTermDto, SearchPlanDto,
     SearchPlanNextActionDto, SearchPlanPromotionStatusDto, SearchPlanRejectedHitDto,
     SearchPlanSubqueryDto, SearchPlanTermsDto, SearchQueryAssessmentDto, SearchRepoTextMode,
-    SearchRequest, SearchResultsDto, SemanticModeDto, SnippetContextDto, StorageStatsDto,
+    SearchRequest, SearchResultsDto, SemanticModeDto, SnippetContextDto,
     StoredSemanticDocsContractDto, SymbolContextDto, SystemActionResponse, TrailConfigDto,
     TrailContextDto, WorkspaceMemberIndexDto,
 };
@@ -66,14 +66,22 @@ use uuid::Uuid;
 
 mod affected;
 mod agent;
+mod dependency_index;
+mod explorer_symbols;
 mod index_commit;
 mod index_coverage;
 mod index_freshness;
 mod index_full;
+mod index_history;
 mod index_incremental;
+mod index_manifest;
+mod index_queue;
+mod index_settings;
+mod index_snapshots;
 mod index_timings;
 mod publication;
 mod repo_text;
+mod review;
 mod route_coverage;
 mod search_evidence;
 mod search_intent;
@@ -86,6 +94,7 @@ mod search_terms;
 mod semantic_projection;
 mod semantic_republish;
 mod snippets;
+mod string_literals;
 mod workspace_state;
 use affected::{AffectedOperationIdentityIndex, IndexFreshnessObservation};
 pub use agent::{packet_step_trace_json, plan_packet};
@@ -158,25 +167,35 @@ pub(crate) use snippets::{
 };
 
 mod browser;
+mod buffer_overlay;
 mod cache_rehydrate;
+mod code_markers;
+mod controller_aliases;
 mod controller_bookmarks;
 mod controller_core;
 mod controller_files;
 mod controller_indexing;
 mod controller_symbols;
+mod controller_trail_defaults;
+mod controller_trail_presets;
 pub(crate) use controller_core::no_project_error;
 pub mod graph_analysis;
 mod graph_builders;
 mod graph_canonical;
+mod graph_compare;
 mod graph_dto;
+mod graph_sessions;
 mod grounding;
 mod mermaid;
+mod occurrence_spans;
 mod path_identity;
 mod path_resolution;
 #[doc(hidden)]
 pub use path_resolution::resolve_project_file_path_from_root;
 mod process_config;
-pub use process_config::RuntimeProcessConfig;
+pub use process_config::{
+    RuntimeProcessConfig, enable_storage_integrity_check, install_storage_encryption_key,
+};
 mod query_language;
 mod repository_identity;
 mod search;
@@ -195,11 +214,14 @@ mod system_actions;
 mod target_resolution;
 #[cfg(test)]
 mod tests;
+mod trail_html;
+mod trail_png;
 mod trail_story;
 
-pub use browser::{BrowserQueryItem, ReadOnlyBrowserService};
+pub use browser::{BrowserQueryItem, BrowserQueryOutput, ReadOnlyBrowserService};
 pub use cache_rehydrate::{CacheRehydrateOutput, CacheRehydrateRequest, rehydrate_cache};
 pub use codestory_contracts as contracts;
+pub use graph_compare::compare_trail_graphs;
 pub(crate) use graph_dto::{
     app_graph_flags, edge_certainty_label, graph_edge_dto, is_structural_kind, member_access_dto,
 };
@@ -266,7 +288,7 @@ pub(crate) use support::{
     clamp_u128_to_u32, clamp_usize_to_u32, extract_symbol_search_terms, file_text_match_line,
     hybrid_retrieval_enabled, looks_like_repo_text_query, node_display_name, preferred_occurrence,
     query_has_symbol_or_literal_signal, read_file_text_limited, read_searchable_file_contents,
-    should_expand_symbol_query,
+    should_expand_symbol_query, storage_stats_dto,
 };
 #[cfg(test)]
 pub(crate) use support::{apply_hybrid_limits, normalized_hybrid_weights};
@@ -289,7 +311,7 @@ type Storage = Store;
 type GraphNodeId = codestory_contracts::graph::NodeId;
 type WeightedGraphMatches = Vec<(GraphNodeId, f32)>;
 type GraphNodeNameMap = HashMap<GraphNodeId, String>;
-type ExpandedSymbolMatches = Option<(WeightedGraphMatches, GraphNodeNameMap)>;
+type ExpandedSymbolMatches = Option<(WeightedGraphMatches, Arc<GraphNodeNameMap>)>;
 
 #[derive(Clone)]
 struct ActiveCoreRead {
@@ -419,11 +441,22 @@ impl Default for Runtime {
 struct AppState {
     project_root: Option<PathBuf>,
     storage_path: Option<PathBuf>,
-    node_names: HashMap<codestory_contracts::graph::NodeId, String>,
+    /// Display names of searchable symbols. Replaced as a whole when the
+    /// search state is rebuilt, so readers clone the `Arc` instead of the map.
+    node_names: Arc<GraphNodeNameMap>,
     search_engine: Option<SearchEngine>,
     search_publication: Option<IndexPublicationRecord>,
     is_indexing: bool,
+    indexing_queue: index_queue::IndexingQueue,
+    buffer_overlays: HashMap<PathBuf, buffer_overlay::BufferOverlay>,
+    /// Files open in the client, relative to the project root, in the order
+    /// index runs should parse them.
+    open_files: Vec<PathBuf>,
     index_freshness_cache: Option<CachedIndexFreshness>,
+    snapshot_retention: usize,
+    indexing_max_threads: Option<usize>,
+    canonical_layouts: graph_canonical::CanonicalLayoutCache,
+    graph_sessions: graph_sessions::GraphSessions,
     #[cfg(test)]
     #[allow(dead_code)]
     last_hybrid_instrumentation: Option<HybridSearchInstrumentation>,
@@ -454,6 +487,11 @@ fn clear_search_engine(state: &mut AppState) {
 pub struct AppController {
     state: Arc<Mutex<AppState>>,
     sidecar_query_cache: Arc<Mutex<SidecarQueryCacheState>>,
+    /// Serializes search state loads so callers arriving during a background
+    /// warm-up wait for it instead of loading the same state again.
+    search_state_load: Arc<Mutex<()>>,
+    /// `git blame` author times kept across marker listings.
+    marker_blame_cache: Arc<Mutex<code_markers::MarkerBlameCache>>,
     events_tx: Sender<AppEventPayload>,
     events_rx: Receiver<AppEventPayload>,
     runtime_config: Arc<codestory_retrieval::SidecarRuntimeConfig>,
@@ -0,0 +1,238 @@
+//! Stored occurrence ranges checked against the files they point into.
+
+use crate::{AppController, Storage, runtime_relative_path};
+use codestory_contracts::api::{ApiError, AppEventPayload, SourceOccurrenceDto};
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+/// What is known about one occurrence file while checking spans.
+enum FileSpanState {
+    /// Unchanged since indexing; ranges are trusted up to `line_count`.
+    Unchanged { line_count: u32 },
+    /// Unchanged since indexing, but a range ran past the stored line count;
+    /// byte length of each current line.
+    Lines(Vec<u32>),
+    /// Edited since indexing, so every range in it is stale; byte length of
+    /// each current line.
+    Edited(Vec<u32>),
+    /// Gone from disk or unreadable.
+    Missing,
+}
+
+/// Clamps occurrence ranges that outlived edits made after indexing.
+///
+/// A file whose modification time still matches the index is only read when
+/// a range runs past its stored line count. Every range in a file edited
+/// since indexing is stale, and only those that no longer fit are clamped.
+/// Files with stale ranges are collected for a reindex suggestion.
+pub(crate) struct OccurrenceSpanCheck<'a> {
+    storage: &'a Storage,
+    root: Option<&'a Path>,
+    files: HashMap<String, FileSpanState>,
+    stale_paths: BTreeSet<String>,
+}
+
+impl<'a> OccurrenceSpanCheck<'a> {
+    pub(crate) fn new(storage: &'a Storage, root: Option<&'a Path>) -> Self {
+        Self {
+            storage,
+            root,
+            files: HashMap::new(),
+            stale_paths: BTreeSet::new(),
+        }
+    }
+
+    /// Clamp `occurrence` to its file's current contents, marking it stale
+    /// when its file was edited since indexing or the stored range no longer
+    /// fits.
+    pub(crate) fn check(&mut self, occurrence: &mut SourceOccurrenceDto) -> Result<(), ApiError> {
+        // Notebook lines count through the joined code cells, not the file.
+        if occurrence.notebook_cell_index.is_some() {
+            return Ok(());
+        }
+        let path = disk_path(self.root, &occurrence.file_path);
+        let state = match self.files.entry(occurrence.file_path.clone()) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(load_state(self.storage, &path)?),
+        };
+        if let FileSpanState::Unchanged { line_count } = *state {
+            if line_count == 0 || occurrence.end_line <= line_count {
+                return Ok(());
+            }
+            *state = read_line_lengths(&path).map_or(FileSpanState::Missing, FileSpanState::Lines);
+        }
+        let stale = match state {
+            FileSpanState::Unchanged { .. } => false,
+            FileSpanState::Lines(lines) => clamp_span(occurrence, lines),
+            FileSpanState::Edited(lines) => {
+                clamp_span(occurrence, lines);
+                true
+            }
+            FileSpanState::Missing => true,
+        };
+        if stale {
+            occurrence.stale = true;
+            self.stale_paths.insert(occurrence.file_path.clone());
+        }
+        Ok(())
+    }
+
+    /// Project-relative paths of the files with stale ranges, in order.
+    pub(crate) fn stale_paths(self) -> Vec<String> {
+        self.stale_paths
+            .into_iter()
+            .map(|path| match self.root {
+                Some(root) => runtime_relative_path(root, Path::new(&path)),
+                None => path,
+            })
+            .collect()
+    }
+}
+
+impl AppController {
+    /// Clamp `occurrences` that no longer fit their files and emit
+    /// [`AppEventPayload::ReindexSuggested`] for the files involved.
+    pub(crate) fn clamp_stale_occurrences(
+        &self,
+        storage: &Storage,
+        occurrences: &mut [SourceOccurrenceDto],
+    ) -> Result<(), ApiError> {
+        let root = self.require_project_root().ok();
+        let mut check = OccurrenceSpanCheck::new(storage, root.as_deref());
+        for occurrence in occurrences.iter_mut() {
+            check.check(occurrence)?;
+        }
+        let paths = check.stale_paths();
+        if !paths.is_empty() {
+            let _ = self
+                .events_tx
+                .send(AppEventPayload::ReindexSuggested { paths });
+        }
+        Ok(())
+    }
+}
+
+fn disk_path(root: Option<&Path>, path: &str) -> PathBuf {
+    match root {
+        Some(root) => root.join(path),
+        None => PathBuf::from(path),
+    }
+}
+
+fn load_state(storage: &Storage, path: &Path) -> Result<FileSpanState, ApiError> {
+    let Some(info) = storage
+        .get_file_by_path(path)
+        .map_err(|e| ApiError::internal(format!("Failed to look up occurrence file: {e}")))?
+    else {
+        return Ok(FileSpanState::Unchanged { line_count: 0 });
+    };
+    let Some(modified_ms) = live_modification_ms(path) else {
+        return Ok(FileSpanState::Missing);
+    };
+    if modified_ms == info.modification_time {
+        return Ok(FileSpanState::Unchanged {
+            line_count: info.line_count,
+        });
+    }
+    Ok(read_line_lengths(path).map_or(FileSpanState::Missing, FileSpanState::Edited))
+}
+
+fn live_modification_ms(path: &Path) -> Option<i64> {
+    let modified = std::fs::metadata(path).ok()?.modified().ok()?;
+    let millis = modified.duration_since(UNIX_EPOCH).ok()?.as_millis();
+    Some(millis.min(i64::MAX as u128) as i64)
+}
+
+/// Byte length of each line of `path`, or `None` when it cannot be read.
+fn read_line_lengths(path: &Path) -> Option<Vec<u32>> {
+    let bytes = std::fs::read(path).ok()?;
+    Some(
+        bytes
+            .split(|byte| *byte == b'\n')
+            .map(|line| line.strip_suffix(b"\r").unwrap_or(line).len() as u32)
+            .collect(),
+    )
+}
+
+/// Clamp a 1-based line and column range to `lines`. Returns whether the
+/// range had to change.
+fn clamp_span(occurrence: &mut SourceOccurrenceDto, lines: &[u32]) -> bool {
+    let last_line = lines.len().max(1) as u32;
+    let line_end_col = |line: u32| lines.get(line as usize - 1).copied().unwrap_or(0) + 1;
+
+    let start_line = occurrence.start_line.clamp(1, last_line);
+    let end_line = occurrence.end_line.clamp(start_line, last_line);
+    let start_col = occurrence.start_col.clamp(1, line_end_col(start_line));
+    let mut end_col = occurrence.end_col.clamp(1, line_end_col(end_line));
+    if end_line == start_line {
+        end_col = end_col.max(start_col);
+    }
+    let clamped = (start_line, start_col, end_line, end_col)
+        != (
+            occurrence.start_line,
+            occurrence.start_col,
+            occurrence.end_line,
+            occurrence.end_col,
+        );
+    occurrence.start_line = start_line;
+    occurrence.start_col = start_col;
+    occurrence.end_line = end_line;
+    occurrence.end_col = end_col;
+    clamped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn occurrence(start: (u32, u32), end: (u32, u32)) -> SourceOccurrenceDto {
+        SourceOccurrenceDto {
+            element_id: "1".to_string(),
+            kind: "definition".to_string(),
+            file_path: "src/lib.rs".to_string(),
+            start_line: start.0,
+            start_col: start.1,
+            end_line: end.0,
+            end_col: end.1,
+            notebook_cell_index: None,
+            notebook_cell_line: None,
+            stale: false,
+        }
+    }
+
+    #[test]
+    fn ranges_inside_the_file_are_left_alone() {
+        let mut inside = occurrence((2, 1), (3, 5));
+        assert!(!clamp_span(&mut inside, &[10, 4, 4]));
+        assert_eq!(
+            (
+                inside.start_line,
+                inside.start_col,
+                inside.end_line,
+                inside.end_col
+            ),
+            (2, 1, 3, 5)
+        );
+    }
+
+    #[test]
+    fn ranges_past_the_end_are_clamped_to_the_last_line() {
+        let mut past_end = occurrence((12, 3), (40, 9));
+        assert!(clamp_span(&mut past_end, &[10, 4, 6]));
+        assert_eq!(
+            (
+                past_end.start_line,
+                past_end.start_col,
+                past_end.end_line,
+                past_end.end_col
+            ),
+            (3, 3, 3, 7)
+        );
+
+        let mut wide = occurrence((1, 30), (1, 44));
+        assert!(clamp_span(&mut wide, &[8]));
+        assert_eq!((wide.start_col, wide.end_col), (9, 9));
+    }
+}
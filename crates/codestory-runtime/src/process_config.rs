@@ -1,3 +1,4 @@
+use codestory_contracts::api::ApiError;
 use codestory_contracts::workspace::SourceIndexPolicy;
 use codestory_retrieval::SidecarRuntimeConfig;
 
@@ -24,3 +25,23 @@ impl RuntimeProcessConfig {
         Self::new(SidecarRuntimeConfig::local(), SourceIndexPolicy::default())
     }
 }
+
+/// Key every project database this process opens with `passphrase`.
+///
+/// Call once at startup, before the first project is opened. Fails unless the
+/// runtime was built with the `sqlcipher` feature, so a configured key can
+/// never silently fall back to a plaintext database.
+pub fn install_storage_encryption_key(passphrase: String) -> Result<(), ApiError> {
+    codestory_store::StorageEncryptionKey::new(passphrase)
+        .and_then(codestory_store::install_process_encryption_key)
+        .map_err(|error| ApiError::invalid_argument(error.to_string()))
+}
+
+/// Check every project database this process opens for writing with
+/// `PRAGMA integrity_check`.
+///
+/// A damaged database then fails with a `storage_corrupt` error that points
+/// at a full refresh. The check reads the whole file, so it is opt-in.
+pub fn enable_storage_integrity_check() {
+    codestory_store::enable_process_integrity_check();
+}
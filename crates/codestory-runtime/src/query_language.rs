@@ -1,7 +1,7 @@
-use codestory_contracts::api::{NodeKind, TrailDirection};
+use codestory_contracts::api::{EdgeKind, NodeKind, TrailDirection};
 use codestory_contracts::query::{
-    FilterQuery, GraphQueryAst, GraphQueryOperation, LimitQuery, SearchQuery, SymbolQuery,
-    TrailQuery,
+    FilterQuery, GraphQueryAst, GraphQueryOperation, LimitQuery, MatchQuery, SearchQuery,
+    SymbolQuery, TrailQuery,
 };
 use std::fmt;
 
@@ -172,6 +172,27 @@ fn parse_operation(
                 count: required_u32_arg(source, &args, "n", 0, args_offset)?,
             }))
         }
+        "match" => {
+            reject_unknown_args(
+                source,
+                &args,
+                &["from", "from_kind", "edge", "to", "to_kind"],
+            )?;
+            if let Some(arg) = args.iter().find(|arg| arg.key.is_none()) {
+                return Err(parse_error(
+                    source,
+                    arg.offset,
+                    "`match` takes only named arguments",
+                ));
+            }
+            Ok(GraphQueryOperation::Match(MatchQuery {
+                from: optional_string_arg(&args, "from"),
+                from_kind: optional_kind_arg(source, &args, "from_kind")?,
+                edge: optional_edge_kind_arg(source, &args, "edge")?,
+                to: optional_string_arg(&args, "to"),
+                to_kind: optional_kind_arg(source, &args, "to_kind")?,
+            }))
+        }
         _ => Err(parse_error(
             source,
             segment_offset,
@@ -449,6 +470,44 @@ fn parse_node_kind(raw: &str) -> Option<NodeKind> {
     }
 }
 
+fn optional_edge_kind_arg(
+    source: &str,
+    args: &[ParsedArg],
+    key: &str,
+) -> Result<Option<EdgeKind>, GraphQueryParseError> {
+    let Some(raw) = optional_string_arg(args, key) else {
+        return Ok(None);
+    };
+    parse_edge_kind(&raw).map(Some).ok_or_else(|| {
+        parse_error(
+            source,
+            arg_offset(args, key).unwrap_or(0),
+            format!("Unknown edge kind `{raw}`"),
+        )
+    })
+}
+
+fn parse_edge_kind(raw: &str) -> Option<EdgeKind> {
+    let normalized = raw.trim().replace('-', "_").to_ascii_uppercase();
+    match normalized.as_str() {
+        "MEMBER" => Some(EdgeKind::MEMBER),
+        "TYPE_USAGE" => Some(EdgeKind::TYPE_USAGE),
+        "USAGE" => Some(EdgeKind::USAGE),
+        "CALL" => Some(EdgeKind::CALL),
+        "INHERITANCE" => Some(EdgeKind::INHERITANCE),
+        "OVERRIDE" => Some(EdgeKind::OVERRIDE),
+        "TYPE_ARGUMENT" => Some(EdgeKind::TYPE_ARGUMENT),
+        "TEMPLATE_SPECIALIZATION" => Some(EdgeKind::TEMPLATE_SPECIALIZATION),
+        "INCLUDE" => Some(EdgeKind::INCLUDE),
+        "IMPORT" => Some(EdgeKind::IMPORT),
+        "MACRO_USAGE" => Some(EdgeKind::MACRO_USAGE),
+        "ANNOTATION_USAGE" => Some(EdgeKind::ANNOTATION_USAGE),
+        "ASSIGNMENT" => Some(EdgeKind::ASSIGNMENT),
+        "UNKNOWN" => Some(EdgeKind::UNKNOWN),
+        _ => None,
+    }
+}
+
 fn arg_offset(args: &[ParsedArg], key: &str) -> Option<usize> {
     args.iter()
         .find(|arg| arg.key.as_deref() == Some(key))
@@ -512,6 +571,27 @@ mod tests {
         assert_eq!(err.offset, "trail(symbol: 'Foo'".len());
     }
 
+    #[test]
+    fn parses_match_with_kinds_and_name_prefixes() {
+        let ast = parse_graph_query(
+            "match(from: 'app::billing', from_kind: function, edge: call, to: 'app::db') | limit(10)",
+        )
+        .expect("parse");
+
+        assert_eq!(
+            ast.operations[0],
+            GraphQueryOperation::Match(MatchQuery {
+                from: Some("app::billing".to_string()),
+                from_kind: Some(NodeKind::FUNCTION),
+                edge: Some(EdgeKind::CALL),
+                to: Some("app::db".to_string()),
+                to_kind: None,
+            })
+        );
+        let err = parse_graph_query("match(edge: calls)").expect_err("unknown edge kind");
+        assert!(err.message.contains("Unknown edge kind `calls`"));
+    }
+
     #[test]
     fn rejects_unknown_named_arguments() {
         let err = parse_graph_query("trail(symbol: 'Foo', deth: 2)").expect_err("unknown arg");
@@ -314,6 +314,7 @@ impl AppController {
             source_excerpt,
             verification_targets: Vec::new(),
             score_breakdown: None,
+            highlight: None,
         }
     }
 
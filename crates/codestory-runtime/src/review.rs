@@ -0,0 +1,712 @@
+use crate::support::node_display_name;
+use crate::{
+    AppController, Storage, graph_builders, indexable_source_path_in_workspace,
+    runtime_relative_path,
+};
+use codestory_contracts::api::{
+    AffectedChangeKindDto, ApiError, NodeId, NodeKind, ReviewChangedFileDto, ReviewGraphDto,
+    ReviewGraphRequest, ReviewLineRangeDto, ReviewSymbolDto,
+};
+use codestory_contracts::graph::{
+    EdgeKind as GraphEdgeKind, Node as GraphNode, NodeId as GraphNodeId, NodeKind as GraphNodeKind,
+};
+use codestory_store::TestReach;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::path::Path;
+
+const REVIEW_DEFAULT_DEPTH: u32 = 1;
+const REVIEW_MAX_DEPTH: u32 = 4;
+/// Callers, and separately tests, one review graph may hold.
+const REVIEW_MAX_RELATED_NODES: usize = 200;
+/// Files holding direct callers that a review reindexes after the changed
+/// files.
+const REVIEW_MAX_DEPENDENT_REINDEX: usize = 50;
+
+impl AppController {
+    /// Symbols a unified diff edits, their callers up to `depth` hops, and
+    /// the tests that reach them, as a graph centered on the first changed
+    /// symbol.
+    ///
+    /// A changed symbol is the innermost symbol whose span holds a changed
+    /// line; every symbol of a deleted file counts as changed while the index
+    /// still holds it.
+    pub fn review_graph(&self, req: ReviewGraphRequest) -> Result<ReviewGraphDto, ApiError> {
+        let changed_files = parse_unified_diff(&req.diff);
+        if changed_files.is_empty() {
+            return Err(ApiError::invalid_argument(
+                "The diff does not name any changed files.",
+            ));
+        }
+        let depth = req
+            .depth
+            .unwrap_or(REVIEW_DEFAULT_DEPTH)
+            .clamp(1, REVIEW_MAX_DEPTH);
+        let storage = self.open_storage_read_only()?;
+        let changed_ids = self
+            .changed_review_nodes(&storage, &changed_files)?
+            .iter()
+            .map(|node| node.id)
+            .collect::<Vec<_>>();
+        let (callers, callers_truncated) = review_callers(&storage, &changed_ids, depth)?;
+
+        let mut test_distances = BTreeMap::<GraphNodeId, u32>::new();
+        for id in &changed_ids {
+            let reached = storage
+                .tests_covering(*id)
+                .map_err(|e| ApiError::internal(format!("Failed to find covering tests: {e}")))?;
+            for reach in reached {
+                let distance = test_distances
+                    .entry(reach.node_id)
+                    .or_insert(reach.distance);
+                *distance = (*distance).min(reach.distance);
+            }
+        }
+        let mut tests = test_distances
+            .into_iter()
+            .map(|(node_id, distance)| TestReach { node_id, distance })
+            .collect::<Vec<_>>();
+        tests.sort_by_key(|reach| (reach.distance, reach.node_id.0));
+        let tests_truncated = tests.len() > REVIEW_MAX_RELATED_NODES;
+        tests.truncate(REVIEW_MAX_RELATED_NODES);
+
+        let mut node_depths = changed_ids
+            .iter()
+            .map(|id| (*id, 0))
+            .chain(callers.iter().copied())
+            .collect::<Vec<_>>();
+        let included = node_depths
+            .iter()
+            .map(|(id, _)| *id)
+            .collect::<HashSet<_>>();
+        node_depths.extend(
+            tests
+                .iter()
+                .filter(|reach| !included.contains(&reach.node_id))
+                .map(|reach| (reach.node_id, reach.distance)),
+        );
+
+        let changed_symbols = self.review_symbols(
+            &storage,
+            &changed_ids.iter().map(|id| (*id, 0)).collect::<Vec<_>>(),
+        )?;
+        let caller_symbols = self.review_symbols(&storage, &callers)?;
+        let tests = self.test_links(&storage, tests)?;
+        drop(storage);
+        let graph = graph_builders::graph_review(self, &node_depths)?;
+        Ok(ReviewGraphDto {
+            changed_files,
+            changed_symbols,
+            callers: caller_symbols,
+            tests,
+            graph,
+            reindexed_files: Vec::new(),
+            truncated: callers_truncated || tests_truncated,
+        })
+    }
+
+    /// Reindex the source files a unified diff changes, then the files holding
+    /// direct callers of the changed symbols, so a review graph reflects the
+    /// diff's new side. Returns the project-relative paths reindexed.
+    pub fn reindex_review_diff(&self, diff: &str) -> Result<Vec<String>, ApiError> {
+        let changed_files = parse_unified_diff(diff);
+        let root = self.require_project_root()?;
+        let mut reindexed = Vec::new();
+        let mut seen = HashSet::new();
+        let mut reindex = |path: &str| -> Result<(), ApiError> {
+            let file = self.resolve_project_file_path(path, true)?;
+            if !seen.insert(file.clone()) || !indexable_source_path_in_workspace(&root, &file) {
+                return Ok(());
+            }
+            self.reindex_file(path)?;
+            reindexed.push(runtime_relative_path(&root, &file));
+            Ok(())
+        };
+        for file in &changed_files {
+            if file.kind == AffectedChangeKindDto::Renamed
+                && let Some(previous_path) = &file.previous_path
+            {
+                reindex(previous_path)?;
+            }
+            reindex(&file.path)?;
+        }
+
+        let storage = self.open_storage_read_only()?;
+        let changed_ids = self
+            .changed_review_nodes(&storage, &changed_files)?
+            .iter()
+            .map(|node| node.id)
+            .collect::<Vec<_>>();
+        let (callers, _) = review_callers(&storage, &changed_ids, 1)?;
+        let caller_ids = callers.iter().map(|(id, _)| *id).collect::<Vec<_>>();
+        let caller_nodes = storage
+            .get_nodes_by_ids(&caller_ids)
+            .map_err(|e| ApiError::internal(format!("Failed to load callers: {e}")))?;
+        let dependent_paths = self
+            .file_paths_for(&storage, caller_nodes.values())?
+            .into_values()
+            .map(|path| runtime_relative_path(&root, Path::new(&path)))
+            .collect::<BTreeSet<_>>();
+        drop(storage);
+        for path in dependent_paths
+            .into_iter()
+            .take(REVIEW_MAX_DEPENDENT_REINDEX)
+        {
+            reindex(&path)?;
+        }
+        Ok(reindexed)
+    }
+
+    fn changed_review_nodes(
+        &self,
+        storage: &Storage,
+        changed_files: &[ReviewChangedFileDto],
+    ) -> Result<Vec<GraphNode>, ApiError> {
+        let mut changed = Vec::new();
+        for file in changed_files {
+            let path = self.resolve_project_file_path(&file.path, true)?;
+            let Some(info) = storage
+                .get_file_by_path(&path)
+                .map_err(|e| ApiError::internal(format!("Failed to look up file: {e}")))?
+            else {
+                continue;
+            };
+            let ids = storage
+                .get_node_kinds_for_files(&[info.id])
+                .map_err(|e| ApiError::internal(format!("Failed to load file symbols: {e}")))?
+                .into_iter()
+                .filter(|(_, kind)| *kind != GraphNodeKind::FILE)
+                .map(|(id, _)| id)
+                .collect::<Vec<_>>();
+            let touched = storage
+                .get_nodes_by_ids(&ids)
+                .map_err(|e| ApiError::internal(format!("Failed to load file symbols: {e}")))?
+                .into_values()
+                .filter(|node| {
+                    file.kind == AffectedChangeKindDto::Deleted
+                        || node_span(node).is_some_and(|(start, end)| {
+                            file.changed_lines
+                                .iter()
+                                .any(|range| range.start_line <= end && start <= range.end_line)
+                        })
+                })
+                .collect::<Vec<_>>();
+            changed.extend(innermost_nodes(touched));
+        }
+        changed.sort_by_key(|node| node.id.0);
+        changed.dedup_by_key(|node| node.id);
+        Ok(changed)
+    }
+
+    fn review_symbols(
+        &self,
+        storage: &Storage,
+        node_distances: &[(GraphNodeId, u32)],
+    ) -> Result<Vec<ReviewSymbolDto>, ApiError> {
+        let ids = node_distances.iter().map(|(id, _)| *id).collect::<Vec<_>>();
+        let nodes = storage
+            .get_nodes_by_ids(&ids)
+            .map_err(|e| ApiError::internal(format!("Failed to load review symbols: {e}")))?;
+        let file_paths = self.file_paths_for(storage, nodes.values())?;
+        let labels = self.cached_labels(ids);
+        Ok(node_distances
+            .iter()
+            .filter_map(|(id, distance)| {
+                let node = nodes.get(id)?;
+                Some(ReviewSymbolDto {
+                    id: NodeId::from(node.id),
+                    kind: NodeKind::from(node.kind),
+                    display_name: labels
+                        .get(&node.id)
+                        .cloned()
+                        .unwrap_or_else(|| node_display_name(node)),
+                    file_path: node
+                        .file_node_id
+                        .and_then(|file_id| file_paths.get(&file_id).cloned()),
+                    line: node.start_line,
+                    distance: *distance,
+                })
+            })
+            .collect())
+    }
+}
+
+/// Nodes that depend on `changed` through calls, usages, or inheritance, up
+/// to `depth` hops, nearest first. Each hop loads the edges of its whole
+/// frontier at once. The flag is set when the walk stopped at the node limit.
+fn review_callers(
+    storage: &Storage,
+    changed: &[GraphNodeId],
+    depth: u32,
+) -> Result<(Vec<(GraphNodeId, u32)>, bool), ApiError> {
+    let mut visited = changed.iter().copied().collect::<HashSet<_>>();
+    let mut callers = Vec::new();
+    let mut frontier = changed.to_vec();
+    let mut truncated = false;
+    for distance in 1..=depth {
+        if frontier.is_empty() {
+            break;
+        }
+        let edges_by_node = storage
+            .get_edges_for_node_ids(&frontier)
+            .map_err(|e| ApiError::internal(format!("Failed to load edges: {e}")))?;
+        let mut next = Vec::new();
+        for id in frontier {
+            for edge in edges_by_node.get(&id).into_iter().flatten() {
+                if !matches!(
+                    edge.kind,
+                    GraphEdgeKind::CALL
+                        | GraphEdgeKind::USAGE
+                        | GraphEdgeKind::TYPE_USAGE
+                        | GraphEdgeKind::INHERITANCE
+                        | GraphEdgeKind::OVERRIDE
+                ) {
+                    continue;
+                }
+                let (source, target) = edge.effective_endpoints();
+                if target != id || visited.contains(&source) {
+                    continue;
+                }
+                if callers.len() >= REVIEW_MAX_RELATED_NODES {
+                    truncated = true;
+                    continue;
+                }
+                visited.insert(source);
+                callers.push((source, distance));
+                next.push(source);
+            }
+        }
+        frontier = next;
+    }
+    callers.sort_by_key(|(id, distance)| (*distance, id.0));
+    Ok((callers, truncated))
+}
+
+fn node_span(node: &GraphNode) -> Option<(u32, u32)> {
+    let start = node.start_line?;
+    Some((start, node.end_line.unwrap_or(start).max(start)))
+}
+
+/// Drop every node whose span strictly contains another node's span, so a
+/// change inside a method names the method rather than its class too.
+fn innermost_nodes(nodes: Vec<GraphNode>) -> Vec<GraphNode> {
+    let spans = nodes.iter().map(node_span).collect::<Vec<_>>();
+    nodes
+        .into_iter()
+        .enumerate()
+        .filter(|(index, _)| {
+            let Some((start, end)) = spans[*index] else {
+                return true;
+            };
+            !spans.iter().flatten().any(|&(inner_start, inner_end)| {
+                (inner_start, inner_end) != (start, end) && start <= inner_start && inner_end <= end
+            })
+        })
+        .map(|(_, node)| node)
+        .collect()
+}
+
+#[derive(Default)]
+struct DiffFile {
+    old_path: Option<String>,
+    new_path: Option<String>,
+    added: bool,
+    deleted: bool,
+    renamed: bool,
+    copied: bool,
+    seen_hunk: bool,
+    lines: Vec<u32>,
+}
+
+impl DiffFile {
+    fn finish(self) -> Option<ReviewChangedFileDto> {
+        let kind = if self.deleted {
+            AffectedChangeKindDto::Deleted
+        } else if self.added {
+            AffectedChangeKindDto::Added
+        } else if self.copied {
+            AffectedChangeKindDto::Copied
+        } else if self.renamed || (self.old_path.is_some() && self.old_path != self.new_path) {
+            AffectedChangeKindDto::Renamed
+        } else {
+            AffectedChangeKindDto::Modified
+        };
+        let (path, previous_path) = match kind {
+            AffectedChangeKindDto::Deleted => (self.old_path?, None),
+            AffectedChangeKindDto::Renamed | AffectedChangeKindDto::Copied => {
+                (self.new_path?, self.old_path)
+            }
+            _ => (self.new_path?, None),
+        };
+        let changed_lines = if kind == AffectedChangeKindDto::Deleted {
+            Vec::new()
+        } else {
+            line_ranges(self.lines)
+        };
+        Some(ReviewChangedFileDto {
+            path,
+            kind,
+            previous_path,
+            changed_lines,
+        })
+    }
+}
+
+/// Files a unified diff changes, in diff order, with the new-side lines each
+/// hunk touches. Reads both `git diff` output and plain `diff -u` output.
+pub(crate) fn parse_unified_diff(diff: &str) -> Vec<ReviewChangedFileDto> {
+    let mut files = Vec::new();
+    let mut current: Option<DiffFile> = None;
+    let mut new_line = 0u32;
+    let mut old_remaining = 0u32;
+    let mut new_remaining = 0u32;
+    for line in diff.lines() {
+        if (old_remaining > 0 || new_remaining > 0)
+            && let Some(file) = current.as_mut()
+        {
+            match line.as_bytes().first() {
+                Some(b'+') => {
+                    file.lines.push(new_line);
+                    new_line += 1;
+                    new_remaining = new_remaining.saturating_sub(1);
+                }
+                Some(b'-') => {
+                    file.lines.push(new_line.max(1));
+                    old_remaining = old_remaining.saturating_sub(1);
+                }
+                Some(b'\\') => {}
+                _ => {
+                    new_line += 1;
+                    old_remaining = old_remaining.saturating_sub(1);
+                    new_remaining = new_remaining.saturating_sub(1);
+                }
+            }
+            continue;
+        }
+        if let Some(rest) = line.strip_prefix("diff --git ") {
+            files.extend(current.take().and_then(DiffFile::finish));
+            let mut file = DiffFile::default();
+            if let Some((old, new)) = rest.split_once(" b/") {
+                file.old_path = Some(old.strip_prefix("a/").unwrap_or(old).to_string());
+                file.new_path = Some(new.to_string());
+            }
+            current = Some(file);
+        } else if let Some(path) = line.strip_prefix("--- ") {
+            // Plain `diff -u` output has no `diff --git` line, so a file
+            // starts at its `---` header.
+            if current.as_ref().is_none_or(|file| file.seen_hunk) {
+                files.extend(current.take().and_then(DiffFile::finish));
+                current = Some(DiffFile::default());
+            }
+            if let Some(file) = current.as_mut() {
+                match diff_header_path(path, "a/") {
+                    Some(path) => file.old_path = Some(path),
+                    None => file.added = true,
+                }
+            }
+        } else if let Some(path) = line.strip_prefix("+++ ") {
+            if let Some(file) = current.as_mut() {
+                match diff_header_path(path, "b/") {
+                    Some(path) => file.new_path = Some(path),
+                    None => file.deleted = true,
+                }
+            }
+        } else if let Some(file) = current.as_mut() {
+            if line.starts_with("new file mode") {
+                file.added = true;
+            } else if line.starts_with("deleted file mode") {
+                file.deleted = true;
+            } else if let Some(path) = line.strip_prefix("rename from ") {
+                file.renamed = true;
+                file.old_path = Some(path.to_string());
+            } else if let Some(path) = line.strip_prefix("rename to ") {
+                file.new_path = Some(path.to_string());
+            } else if let Some(path) = line.strip_prefix("copy from ") {
+                file.copied = true;
+                file.old_path = Some(path.to_string());
+            } else if let Some(path) = line.strip_prefix("copy to ") {
+                file.new_path = Some(path.to_string());
+            } else if let Some((old_count, new_start, new_count)) = parse_hunk_header(line) {
+                file.seen_hunk = true;
+                // An empty new side starts at the line before the removal;
+                // the removal is recorded on the line after it, which now
+                // sits where the removed lines were.
+                new_line = if new_count == 0 {
+                    new_start + 1
+                } else {
+                    new_start
+                };
+                old_remaining = old_count;
+                new_remaining = new_count;
+            }
+        }
+    }
+    files.extend(current.take().and_then(DiffFile::finish));
+    files
+}
+
+/// The path in a `---`/`+++` header, or `None` for `/dev/null`.
+fn diff_header_path(raw: &str, prefix: &str) -> Option<String> {
+    let path = raw.split('\t').next().unwrap_or(raw).trim_end();
+    if path == "/dev/null" {
+        return None;
+    }
+    Some(path.strip_prefix(prefix).unwrap_or(path).to_string())
+}
+
+/// Old line count, new start line, and new line count of an `@@` header.
+fn parse_hunk_header(line: &str) -> Option<(u32, u32, u32)> {
+    let rest = line.strip_prefix("@@ -")?;
+    let (old, rest) = rest.split_once(" +")?;
+    let (new, _) = rest.split_once(" @@")?;
+    let range = |range: &str| -> Option<(u32, u32)> {
+        match range.split_once(',') {
+            Some((start, count)) => Some((start.parse().ok()?, count.parse().ok()?)),
+            None => Some((range.parse().ok()?, 1)),
+        }
+    };
+    let (_, old_count) = range(old)?;
+    let (new_start, new_count) = range(new)?;
+    Some((old_count, new_start, new_count))
+}
+
+fn line_ranges(mut lines: Vec<u32>) -> Vec<ReviewLineRangeDto> {
+    lines.sort_unstable();
+    lines.dedup();
+    let mut ranges = Vec::<ReviewLineRangeDto>::new();
+    for line in lines {
+        match ranges.last_mut() {
+            Some(range) if range.end_line + 1 == line => range.end_line = line,
+            _ => ranges.push(ReviewLineRangeDto {
+                start_line: line,
+                end_line: line,
+            }),
+        }
+    }
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codestory_contracts::api::IndexMode;
+    use std::fs;
+    use tempfile::tempdir;
+
+    const REVIEW_FIXTURE: &str = "\
+pub fn helper() -> u32 {
+    1
+}
+
+pub fn caller() -> u32 {
+    helper()
+}
+";
+
+    const HELPER_BODY_DIFF: &str = "\
+--- a/lib.rs
++++ b/lib.rs
+@@ -2 +2 @@
+-    1
++    2
+";
+
+    fn indexed_review_project() -> (tempfile::TempDir, AppController) {
+        let workspace = tempdir().expect("workspace dir");
+        fs::write(workspace.path().join("lib.rs"), REVIEW_FIXTURE).expect("write lib");
+        let controller = AppController::new();
+        controller
+            .open_project_summary_with_storage_path(
+                workspace.path().to_path_buf(),
+                workspace.path().join(".cache").join("codestory.db"),
+            )
+            .expect("open project");
+        controller
+            .run_indexing_blocking_without_runtime_refresh(IndexMode::Full)
+            .expect("index fixture");
+        (workspace, controller)
+    }
+
+    fn names(symbols: &[ReviewSymbolDto]) -> Vec<&str> {
+        symbols
+            .iter()
+            .map(|symbol| symbol.display_name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn review_graph_reports_changed_symbols_and_their_callers() {
+        let (_workspace, controller) = indexed_review_project();
+
+        let review = controller
+            .review_graph(ReviewGraphRequest {
+                diff: HELPER_BODY_DIFF.to_string(),
+                depth: None,
+            })
+            .expect("review diff");
+
+        assert_eq!(review.changed_files.len(), 1);
+        assert_eq!(review.changed_files[0].path, "lib.rs");
+        assert!(
+            names(&review.changed_symbols)
+                .iter()
+                .any(|name| name.ends_with("helper")),
+            "{:?}",
+            review.changed_symbols
+        );
+        assert!(
+            review
+                .callers
+                .iter()
+                .any(|caller| caller.display_name.ends_with("caller") && caller.distance == 1),
+            "{:?}",
+            review.callers
+        );
+        assert_eq!(
+            review.changed_symbols[0].file_path.as_deref(),
+            Some("lib.rs")
+        );
+        assert!(!review.graph.nodes.is_empty());
+        assert!(!review.truncated);
+
+        let empty = controller
+            .review_graph(ReviewGraphRequest {
+                diff: String::new(),
+                depth: None,
+            })
+            .expect_err("empty diff");
+        assert_eq!(empty.code, "invalid_argument");
+    }
+
+    #[test]
+    fn reindex_review_diff_indexes_the_new_side_of_changed_files() {
+        let (workspace, controller) = indexed_review_project();
+        fs::write(
+            workspace.path().join("lib.rs"),
+            format!("{REVIEW_FIXTURE}\npub fn added() -> u32 {{\n    helper()\n}}\n"),
+        )
+        .expect("edit lib");
+        let diff = "\
+--- a/lib.rs
++++ b/lib.rs
+@@ -7,0 +8,4 @@
++
++pub fn added() -> u32 {
++    helper()
++}
+";
+
+        let reindexed = controller.reindex_review_diff(diff).expect("reindex diff");
+        assert_eq!(reindexed, vec!["lib.rs".to_string()]);
+
+        let review = controller
+            .review_graph(ReviewGraphRequest {
+                diff: diff.to_string(),
+                depth: None,
+            })
+            .expect("review reindexed diff");
+        assert!(
+            names(&review.changed_symbols)
+                .iter()
+                .any(|name| name.ends_with("added")),
+            "{:?}",
+            review.changed_symbols
+        );
+    }
+
+    #[test]
+    fn parses_git_diff_files_and_changed_lines() {
+        let diff = "\
+diff --git a/src/lib.rs b/src/lib.rs
+index 1111111..2222222 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -10,3 +10,4 @@ fn helper() {
+     let a = 1;
+-    let b = 2;
++    let b = 3;
++    let c = 4;
+     a + b
+@@ -40,2 +41,0 @@
+-fn removed() {}
+-
+diff --git a/src/old.rs b/src/new.rs
+similarity index 90%
+rename from src/old.rs
+rename to src/new.rs
+diff --git a/src/gone.rs b/src/gone.rs
+deleted file mode 100644
+--- a/src/gone.rs
++++ /dev/null
+@@ -1 +0,0 @@
+-fn gone() {}
+diff --git a/src/added.rs b/src/added.rs
+new file mode 100644
+--- /dev/null
++++ b/src/added.rs
+@@ -0,0 +1,2 @@
++fn added() {}
++
+";
+        let files = parse_unified_diff(diff);
+        let summary = files
+            .iter()
+            .map(|file| {
+                (
+                    file.path.as_str(),
+                    file.kind.clone(),
+                    file.previous_path.as_deref(),
+                    file.changed_lines
+                        .iter()
+                        .map(|range| (range.start_line, range.end_line))
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    "src/lib.rs",
+                    AffectedChangeKindDto::Modified,
+                    None,
+                    vec![(11, 12), (42, 42)],
+                ),
+                (
+                    "src/new.rs",
+                    AffectedChangeKindDto::Renamed,
+                    Some("src/old.rs"),
+                    vec![],
+                ),
+                ("src/gone.rs", AffectedChangeKindDto::Deleted, None, vec![]),
+                (
+                    "src/added.rs",
+                    AffectedChangeKindDto::Added,
+                    None,
+                    vec![(1, 2)]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_plain_unified_diff_without_git_headers() {
+        let diff = "\
+--- app.py\t2026-01-01 00:00:00
++++ app.py\t2026-01-02 00:00:00
+@@ -3 +3 @@
+-    return 1
++    return 2
+";
+        let files = parse_unified_diff(diff);
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].path, "app.py");
+        assert_eq!(files[0].kind, AffectedChangeKindDto::Modified);
+        assert_eq!(
+            files[0].changed_lines,
+            vec![ReviewLineRangeDto {
+                start_line: 3,
+                end_line: 3,
+            }]
+        );
+    }
+}
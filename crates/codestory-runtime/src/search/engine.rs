@@ -1,3 +1,6 @@
+use super::prefilter::SymbolCharMasks;
+use super::prefix::SymbolPrefixIndex;
+use super::subtokens::{SymbolSubtokenIndex, identifier_subtokens};
 use crate::symbol_query::RetrievalFileRole;
 #[cfg(test)]
 use crate::symbol_query::query_mentions_non_primary_source;
@@ -15,6 +18,7 @@ use std::hash::{Hash, Hasher};
 use std::path::{Path, PathBuf};
 #[cfg(test)]
 use std::sync::Arc;
+use std::sync::OnceLock;
 use std::time::{Duration, Instant};
 use tantivy::collector::TopDocs;
 use tantivy::doc;
@@ -29,6 +33,10 @@ const EMBEDDING_PROFILE: &str = "coderank-embed";
 const EMBEDDING_MODEL_ID: &str = "nomic-ai/CodeRankEmbed";
 pub const STORED_VECTOR_ENCODING_ENV: &str = "CODESTORY_STORED_VECTOR_ENCODING";
 pub const SYMBOL_FULL_TEXT_INDEX_ENV: &str = "CODESTORY_SYMBOL_FULL_TEXT_INDEX";
+pub const SYMBOL_IMPORTANCE_WEIGHT_ENV: &str = "CODESTORY_SYMBOL_IMPORTANCE_WEIGHT";
+const DEFAULT_SYMBOL_IMPORTANCE_WEIGHT: f32 = 0.3;
+const MAX_SYMBOL_IMPORTANCE_WEIGHT: f32 = 4.0;
+pub const MAX_AUTOCOMPLETE_SUGGESTIONS: usize = 50;
 #[cfg(test)]
 const SEMANTIC_QUANTIZED_RESCORE_MULTIPLIER: usize = 4;
 
@@ -106,6 +114,34 @@ fn symbol_full_text_index_enabled_from_env() -> bool {
     env_bool_override(SYMBOL_FULL_TEXT_INDEX_ENV).unwrap_or(true)
 }
 
+fn symbol_importance_weight_from_env() -> f32 {
+    std::env::var(SYMBOL_IMPORTANCE_WEIGHT_ENV)
+        .ok()
+        .and_then(|raw| raw.trim().parse::<f32>().ok())
+        .filter(|weight| weight.is_finite())
+        .map_or(DEFAULT_SYMBOL_IMPORTANCE_WEIGHT, |weight| {
+            weight.clamp(0.0, MAX_SYMBOL_IMPORTANCE_WEIGHT)
+        })
+}
+
+/// Importance boost blended into fuzzy symbol scores.
+#[derive(Clone, Copy)]
+struct SymbolImportanceBlend<'a> {
+    factors: &'a HashMap<NodeId, f32>,
+    weight: f32,
+}
+
+impl SymbolImportanceBlend<'_> {
+    fn blend(&self, node_id: NodeId, score: u32) -> u32 {
+        self.lift(node_id, score as f32).round() as u32
+    }
+
+    fn lift(&self, node_id: NodeId, score: f32) -> f32 {
+        let factor = self.factors.get(&node_id).copied().unwrap_or(0.0);
+        score * (1.0 + self.weight * factor)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct LlmSearchDoc {
     pub node_id: NodeId,
@@ -368,6 +404,12 @@ impl EmbeddingRuntime {
 
 pub struct SearchEngine {
     symbols: Vec<(Utf32String, NodeId)>,
+    /// Built from `symbols` on the first autocomplete after they change.
+    symbol_prefixes: OnceLock<SymbolPrefixIndex>,
+    /// Built from `symbols` on the first fuzzy search after they change.
+    symbol_masks: OnceLock<SymbolCharMasks>,
+    /// Built from `symbols` on the first fuzzy search after they change.
+    symbol_subtokens: OnceLock<SymbolSubtokenIndex>,
     index: Index,
     reader: IndexReader,
     llm_docs: HashMap<NodeId, LlmSearchDoc>,
@@ -377,6 +419,9 @@ pub struct SearchEngine {
     #[cfg(test)]
     stored_vector_encoding: StoredVectorEncoding,
     full_text_index_enabled: bool,
+    /// Fan-in derived importance per symbol, in `0.0..=1.0`.
+    symbol_importance: HashMap<NodeId, f32>,
+    symbol_importance_weight: f32,
     #[cfg(test)]
     query_embedding_cache: HashMap<String, Vec<f32>>,
     _persisted_index_guard: Option<PersistedSearchIndexGuard>,
@@ -396,6 +441,8 @@ pub struct SymbolIndexSession<'a> {
     engine: &'a mut SearchEngine,
     writer: Option<IndexWriter<TantivyDocument>>,
     name_field: Field,
+    /// Absent from indexes persisted before sub-token search.
+    name_tokens_field: Option<Field>,
     id_field: Field,
     symbols_start_len: usize,
     docs_written: usize,
@@ -643,6 +690,7 @@ impl SearchEngine {
     fn build_schema() -> Schema {
         let mut schema_builder = Schema::builder();
         schema_builder.add_text_field("name", TEXT | STORED);
+        schema_builder.add_text_field("name_tokens", TEXT);
         schema_builder.add_i64_field("node_id", INDEXED | STORED | FAST);
         schema_builder.build()
     }
@@ -658,6 +706,9 @@ impl SearchEngine {
 
         Ok(Self {
             symbols: Vec::new(),
+            symbol_prefixes: OnceLock::new(),
+            symbol_masks: OnceLock::new(),
+            symbol_subtokens: OnceLock::new(),
             index,
             reader,
             llm_docs: HashMap::new(),
@@ -667,6 +718,8 @@ impl SearchEngine {
             #[cfg(test)]
             stored_vector_encoding: StoredVectorEncoding::from_env()?,
             full_text_index_enabled: symbol_full_text_index_enabled_from_env(),
+            symbol_importance: HashMap::new(),
+            symbol_importance_weight: symbol_importance_weight_from_env(),
             #[cfg(test)]
             query_embedding_cache: HashMap::new(),
             _persisted_index_guard: persisted_index_guard,
@@ -932,12 +985,14 @@ impl SearchEngine {
             .transpose()?;
         let schema = self.index.schema();
         let name_field = schema.get_field("name")?;
+        let name_tokens_field = schema.get_field("name_tokens").ok();
         let id_field = schema.get_field("node_id")?;
         let symbols_start_len = self.symbols.len();
         Ok(SymbolIndexSession {
             engine: self,
             writer,
             name_field,
+            name_tokens_field,
             id_field,
             symbols_start_len,
             docs_written: 0,
@@ -958,6 +1013,9 @@ impl SearchEngine {
         I: IntoIterator<Item = (NodeId, String)>,
     {
         self.symbols.clear();
+        self.symbol_prefixes.take();
+        self.symbol_masks.take();
+        self.symbol_subtokens.take();
         self.extend_symbol_projection(symbols);
     }
 
@@ -970,6 +1028,9 @@ impl SearchEngine {
                 .into_iter()
                 .map(|(id, name)| (Utf32String::from(name.as_str()), id)),
         );
+        self.symbol_prefixes.take();
+        self.symbol_masks.take();
+        self.symbol_subtokens.take();
     }
 
     #[cfg(test)]
@@ -983,8 +1044,96 @@ impl SearchEngine {
             .collect()
     }
 
+    /// Derive each symbol's importance from how many symbols reference it.
+    ///
+    /// Importance is `ln(1 + fan_in) / ln(1 + max_fan_in)`, so the most
+    /// referenced symbol scores 1.0 and unreferenced symbols score 0.0.
+    pub fn set_symbol_fan_in(&mut self, fan_in: &HashMap<NodeId, u32>) {
+        let max_fan_in = fan_in.values().copied().max().unwrap_or(0);
+        self.symbol_importance.clear();
+        if max_fan_in == 0 {
+            return;
+        }
+        let scale = (1.0 + max_fan_in as f32).ln();
+        self.symbol_importance.extend(
+            fan_in
+                .iter()
+                .filter(|(_, count)| **count > 0)
+                .map(|(id, count)| (*id, (1.0 + *count as f32).ln() / scale)),
+        );
+    }
+
+    /// How strongly importance lifts a fuzzy score: a symbol's score is
+    /// multiplied by `1 + weight * importance`. Zero ranks on fuzzy score
+    /// alone.
+    pub fn set_symbol_importance_weight(&mut self, weight: f32) {
+        self.symbol_importance_weight = if weight.is_finite() {
+            weight.clamp(0.0, MAX_SYMBOL_IMPORTANCE_WEIGHT)
+        } else {
+            0.0
+        };
+    }
+
+    /// Fuzzy symbol matches, best first, with scores lifted by importance.
+    ///
+    /// Names whose identifier sub-tokens cover the query in any order also
+    /// match, so `flushprojection` finds `ProjectionFlusher`. Exact name
+    /// matches still outrank everything else; importance reorders candidates
+    /// whose fuzzy scores are close.
     pub fn search_symbol_with_scores(&self, query: &str) -> Vec<(NodeId, f32)> {
-        search_symbols_with_scores(&self.symbols, query)
+        let masks = self
+            .symbol_masks
+            .get_or_init(|| SymbolCharMasks::build(&self.symbols));
+        let subtoken_scores = self
+            .symbol_subtokens
+            .get_or_init(|| SymbolSubtokenIndex::build(&self.symbols))
+            .lookup(query);
+        search_symbols_with_importance(
+            &self.symbols,
+            query,
+            self.importance_blend(),
+            Some(masks),
+            &subtoken_scores,
+        )
+    }
+
+    /// Symbols whose name, or a trailing `::`, `.`, `/`, or `#` segment of
+    /// it, starts with `prefix`, best first.
+    ///
+    /// Suggestions that complete more of their name rank higher, lifted by
+    /// importance like fuzzy search.
+    pub fn autocomplete_symbols(&self, prefix: &str, limit: usize) -> Vec<(NodeId, f32)> {
+        let prefix = prefix.trim();
+        let limit = limit.min(MAX_AUTOCOMPLETE_SUGGESTIONS);
+        if prefix.is_empty() || limit == 0 {
+            return Vec::new();
+        }
+        let index = self
+            .symbol_prefixes
+            .get_or_init(|| SymbolPrefixIndex::build(&self.symbols));
+        let mut suggestions = index.lookup(prefix);
+        if let Some(blend) = self.importance_blend() {
+            for (node_id, score) in &mut suggestions {
+                *score = blend.lift(*node_id, *score);
+            }
+        }
+        suggestions.sort_by(|left, right| {
+            right
+                .1
+                .total_cmp(&left.1)
+                .then_with(|| left.0.0.cmp(&right.0.0))
+        });
+        suggestions.truncate(limit);
+        suggestions
+    }
+
+    fn importance_blend(&self) -> Option<SymbolImportanceBlend<'_>> {
+        (self.symbol_importance_weight > 0.0 && !self.symbol_importance.is_empty()).then_some(
+            SymbolImportanceBlend {
+                factors: &self.symbol_importance,
+                weight: self.symbol_importance_weight,
+            },
+        )
     }
 
     #[cfg(test)]
@@ -1029,6 +1178,9 @@ impl SearchEngine {
         }
 
         self.symbols.retain(|(_, id)| !remove_ids.contains(&id.0));
+        self.symbol_prefixes.take();
+        self.symbol_masks.take();
+        self.symbol_subtokens.take();
         self.llm_docs.retain(|id, _| !remove_ids.contains(&id.0));
         self.quantized_llm_docs
             .retain(|id, _| !remove_ids.contains(&id.0));
@@ -1059,10 +1211,11 @@ impl SearchEngine {
 
         let searcher = self.reader.searcher();
         let schema = self.index.schema();
-        let name_field = schema.get_field("name")?;
+        let mut fields = vec![schema.get_field("name")?];
+        fields.extend(schema.get_field("name_tokens").ok());
         let id_field = schema.get_field("node_id")?;
 
-        let query_parser = QueryParser::for_index(&self.index, vec![name_field]);
+        let query_parser = QueryParser::for_index(&self.index, fields);
         let query = query_parser
             .parse_query(query_str)
             .context("Failed to parse tantivy query")?;
@@ -1098,12 +1251,22 @@ impl SymbolIndexSession<'_> {
             }
             let fuzzy_name = Utf32String::from(name.as_str());
             if let Some(writer) = self.writer.as_mut() {
-                writer.add_document(doc!(
+                let name_tokens = self
+                    .name_tokens_field
+                    .map(|field| (field, identifier_subtokens(&name).join(" ")));
+                let mut document = doc!(
                     self.name_field => name,
                     self.id_field => id.0
-                ))?;
+                );
+                if let Some((field, tokens)) = name_tokens {
+                    document.add_text(field, tokens);
+                }
+                writer.add_document(document)?;
             }
             self.engine.symbols.push((fuzzy_name, id));
+            self.engine.symbol_prefixes.take();
+            self.engine.symbol_masks.take();
+            self.engine.symbol_subtokens.take();
             self.docs_written = self.docs_written.saturating_add(1);
         }
         Ok(self.docs_written.saturating_sub(start_count))
@@ -1145,6 +1308,9 @@ impl Drop for SymbolIndexSession<'_> {
     fn drop(&mut self) {
         if !self.finished {
             self.engine.symbols.truncate(self.symbols_start_len);
+            self.engine.symbol_prefixes.take();
+            self.engine.symbol_masks.take();
+            self.engine.symbol_subtokens.take();
         }
     }
 }
@@ -1152,6 +1318,26 @@ impl Drop for SymbolIndexSession<'_> {
 pub(crate) fn search_symbols_with_scores(
     symbols: &[(Utf32String, NodeId)],
     query: &str,
+) -> Vec<(NodeId, f32)> {
+    search_symbols_with_importance(symbols, query, None, None, &HashMap::new())
+}
+
+/// Fuzzy and sub-token matches over a standalone symbol list, scored like
+/// [`SearchEngine::search_symbol_with_scores`] without importance.
+pub(crate) fn search_symbol_list(
+    symbols: &[(Utf32String, NodeId)],
+    query: &str,
+) -> Vec<(NodeId, f32)> {
+    let subtoken_scores = SymbolSubtokenIndex::build(symbols).lookup(query);
+    search_symbols_with_importance(symbols, query, None, None, &subtoken_scores)
+}
+
+fn search_symbols_with_importance(
+    symbols: &[(Utf32String, NodeId)],
+    query: &str,
+    importance: Option<SymbolImportanceBlend<'_>>,
+    prefilter: Option<&SymbolCharMasks>,
+    subtoken_scores: &HashMap<usize, u32>,
 ) -> Vec<(NodeId, f32)> {
     if query.is_empty() {
         return Vec::new();
@@ -1167,32 +1353,41 @@ pub(crate) fn search_symbols_with_scores(
         AtomKind::Fuzzy,
     );
 
+    // Symbols are scored in shards of this size, in parallel once there is
+    // more than one.
     const SYMBOL_SCAN_CHUNK: usize = 256;
+    let query_mask = SymbolCharMasks::query_mask(query);
+    let score_shard = |offset: usize, shard: &[(Utf32String, NodeId)]| {
+        let mut matcher = Matcher::new(NucleoConfig::DEFAULT);
+        shard
+            .iter()
+            .enumerate()
+            .filter_map(|(index, (name, id))| {
+                let subtoken_score = subtoken_scores.get(&(offset + index)).copied();
+                let fuzzy_score = prefilter
+                    .is_none_or(|masks| masks.may_match(offset + index, query_mask))
+                    .then(|| pattern.score(name.slice(..), &mut matcher))
+                    .flatten();
+                // A sub-token match lifts a fuzzy match by a quarter of its
+                // own score and stands in for one the fuzzy pattern missed.
+                let score = match (fuzzy_score, subtoken_score) {
+                    (Some(fuzzy), Some(subtoken)) => fuzzy.max(subtoken) + subtoken / 4,
+                    (Some(score), None) | (None, Some(score)) => score,
+                    (None, None) => return None,
+                };
+                let score = importance.map_or(score, |blend| blend.blend(*id, score));
+                Some((*id, score, symbol_candidate_rank(query, name, score)))
+            })
+            .collect::<Vec<_>>()
+    };
     let mut matches = if symbols.len() >= SYMBOL_SCAN_CHUNK {
         symbols
             .par_chunks(SYMBOL_SCAN_CHUNK)
-            .flat_map(|chunk| {
-                let mut matcher = Matcher::new(NucleoConfig::DEFAULT);
-                chunk
-                    .iter()
-                    .filter_map(|(name, id)| {
-                        pattern
-                            .score(name.slice(..), &mut matcher)
-                            .map(|score| (*id, score, symbol_candidate_rank(query, name, score)))
-                    })
-                    .collect::<Vec<_>>()
-            })
+            .enumerate()
+            .flat_map(|(shard, symbols)| score_shard(shard * SYMBOL_SCAN_CHUNK, symbols))
             .collect::<Vec<_>>()
     } else {
-        let mut matcher = Matcher::new(NucleoConfig::DEFAULT);
-        symbols
-            .iter()
-            .filter_map(|(name, id)| {
-                pattern
-                    .score(name.slice(..), &mut matcher)
-                    .map(|score| (*id, score, symbol_candidate_rank(query, name, score)))
-            })
-            .collect::<Vec<_>>()
+        score_shard(0, symbols)
     };
 
     matches.sort_by(|left, right| right.2.cmp(&left.2).then_with(|| right.1.cmp(&left.1)));
@@ -1826,6 +2021,88 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn symbol_importance_lifts_widely_referenced_symbols() -> Result<()> {
+        let mut engine = SearchEngine::new(None)?;
+        engine.index_nodes(vec![
+            (NodeId(1), "render_config_local".to_string()),
+            (NodeId(2), "render_config_local".to_string()),
+            (NodeId(3), "RenderConfigLoader".to_string()),
+        ])?;
+        engine.set_symbol_fan_in(&HashMap::from([(NodeId(2), 40), (NodeId(3), 2)]));
+        engine.set_symbol_importance_weight(0.5);
+
+        let ranked = engine.search_symbol_with_scores("rcl");
+        let position = |id| {
+            ranked
+                .iter()
+                .position(|(node_id, _)| *node_id == NodeId(id))
+        };
+        assert!(position(2) < position(1));
+        let score = |id| {
+            ranked
+                .iter()
+                .find(|(node_id, _)| *node_id == NodeId(id))
+                .map(|hit| hit.1)
+        };
+        assert!(score(2) > score(1));
+
+        engine.set_symbol_importance_weight(0.0);
+        let unweighted = engine.search_symbol_with_scores("rcl");
+        let score = |id| {
+            unweighted
+                .iter()
+                .find(|(node_id, _)| *node_id == NodeId(id))
+                .map(|hit| hit.1)
+        };
+        assert_eq!(score(1), score(2));
+        Ok(())
+    }
+
+    #[test]
+    fn autocomplete_matches_name_and_path_segment_prefixes() -> Result<()> {
+        let mut engine = SearchEngine::new(None)?;
+        engine.index_nodes(vec![
+            (NodeId(1), "Storage::get_node".to_string()),
+            (NodeId(2), "get_nodes_batch".to_string()),
+            (NodeId(3), "StorageCache".to_string()),
+            (NodeId(4), "render".to_string()),
+        ])?;
+
+        let ids = |prefix: &str, limit: usize| {
+            engine
+                .autocomplete_symbols(prefix, limit)
+                .into_iter()
+                .map(|(id, _)| id)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ids("GET_NODE", 10), vec![NodeId(1), NodeId(2)]);
+        assert_eq!(ids("stor", 10), vec![NodeId(3), NodeId(1)]);
+        assert_eq!(ids("stor", 1), vec![NodeId(3)]);
+        assert!(ids("", 10).is_empty());
+        assert!(ids("node", 10).is_empty());
+
+        engine.extend_symbol_projection([(NodeId(5), "node_store".to_string())]);
+        assert_eq!(ids("node", 10), vec![NodeId(5)]);
+        Ok(())
+    }
+
+    #[test]
+    fn run_together_queries_match_identifier_sub_tokens() -> Result<()> {
+        let mut engine = SearchEngine::new(None)?;
+        engine.index_nodes(vec![
+            (NodeId(1), "projection_flush".to_string()),
+            (NodeId(2), "ProjectionFlusher".to_string()),
+            (NodeId(3), "project_loader".to_string()),
+        ])?;
+
+        let mut ids = engine.search_symbol("flushprojection");
+        ids.sort_by_key(|id| id.0);
+        assert_eq!(ids, vec![NodeId(1), NodeId(2)]);
+        assert_eq!(engine.search_full_text("flusher")?, vec![NodeId(2)]);
+        Ok(())
+    }
+
     #[test]
     fn symbol_full_text_index_can_be_disabled_for_projection_only_search() -> Result<()> {
         let _lock = crate::process_env_test_lock();
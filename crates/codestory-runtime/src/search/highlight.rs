@@ -0,0 +1,160 @@
+//! Highlight ranges for search hits, so clients need not re-run matching.
+
+use super::subtokens::subtoken_match_indices;
+use codestory_contracts::api::{
+    SearchHighlightDto, SearchHit, SearchMatchFieldDto, SearchMatchRangeDto,
+};
+use nucleo_matcher::pattern::{AtomKind, CaseMatching, Normalization, Pattern};
+use nucleo_matcher::{Config as NucleoConfig, Matcher, Utf32Str};
+
+/// Attach the characters of each indexed symbol hit's `display_name` that
+/// fuzzy-match `query`, or that its sub-token pieces matched when the query
+/// runs words together out of order. Hits neither aligns with, such as
+/// semantic suggestions, are left without a highlight.
+pub(crate) fn annotate_search_hit_highlights(query: &str, hits: &mut [SearchHit]) {
+    let query = query.trim();
+    if query.is_empty() {
+        return;
+    }
+    let pattern = Pattern::new(
+        query,
+        CaseMatching::Ignore,
+        Normalization::Smart,
+        AtomKind::Fuzzy,
+    );
+    let mut matcher = Matcher::new(NucleoConfig::DEFAULT);
+    let mut buf = Vec::new();
+    let mut indices = Vec::new();
+    for hit in hits {
+        if hit.is_text_match() {
+            continue;
+        }
+        indices.clear();
+        let haystack = Utf32Str::new(&hit.display_name, &mut buf);
+        hit.highlight = if pattern
+            .indices(haystack, &mut matcher, &mut indices)
+            .is_some()
+        {
+            Some(highlight_from_indices(&hit.display_name, &mut indices))
+        } else {
+            subtoken_match_indices(&hit.display_name, query)
+                .map(|mut indices| highlight_from_indices(&hit.display_name, &mut indices))
+        };
+    }
+}
+
+/// Collapse matched character indices into ranges. Indices from several
+/// pattern atoms may repeat or arrive out of order.
+fn highlight_from_indices(display_name: &str, indices: &mut Vec<u32>) -> SearchHighlightDto {
+    indices.sort_unstable();
+    indices.dedup();
+    let mut ranges = Vec::<SearchMatchRangeDto>::new();
+    for index in indices.iter().copied() {
+        match ranges.last_mut() {
+            Some(range) if range.end == index => range.end += 1,
+            _ => ranges.push(SearchMatchRangeDto {
+                start: index,
+                end: index + 1,
+            }),
+        }
+    }
+    let name_start = terminal_segment_start(display_name);
+    let field = if ranges.first().is_none_or(|range| range.start >= name_start) {
+        SearchMatchFieldDto::Name
+    } else {
+        SearchMatchFieldDto::QualifiedName
+    };
+    SearchHighlightDto { field, ranges }
+}
+
+/// Character offset where the last `::`, `.`, `/`, or `\` separated segment
+/// of `display_name` begins.
+fn terminal_segment_start(display_name: &str) -> u32 {
+    let byte_start = display_name
+        .rfind([':', '.', '/', '\\'])
+        .map_or(0, |separator| separator + 1);
+    display_name[..byte_start].chars().count() as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codestory_contracts::api::{NodeId, NodeKind, SearchHitOrigin};
+
+    fn hit(display_name: &str, origin: SearchHitOrigin) -> SearchHit {
+        SearchHit {
+            node_id: NodeId("1".to_string()),
+            display_name: display_name.to_string(),
+            kind: NodeKind::FUNCTION,
+            file_path: None,
+            line: None,
+            score: 1.0,
+            origin,
+            match_quality: None,
+            resolvable: true,
+            evidence_tier: None,
+            evidence_producer: None,
+            resolution_status: None,
+            loss_reason: None,
+            coverage_role: None,
+            eligible_for_sufficiency: None,
+            source_excerpt: None,
+            verification_targets: Vec::new(),
+            score_breakdown: None,
+            highlight: None,
+        }
+    }
+
+    fn ranges(highlight: &SearchHighlightDto) -> Vec<(u32, u32)> {
+        highlight
+            .ranges
+            .iter()
+            .map(|range| (range.start, range.end))
+            .collect()
+    }
+
+    #[test]
+    fn highlights_report_matched_ranges_and_field() {
+        let mut hits = vec![
+            hit("Storage::get_node", SearchHitOrigin::IndexedSymbol),
+            hit("Storage::get_node", SearchHitOrigin::IndexedSymbol),
+            hit("café::get_node", SearchHitOrigin::IndexedSymbol),
+            hit("src/storage.rs", SearchHitOrigin::TextMatch),
+        ];
+        annotate_search_hit_highlights("getnode", &mut hits[..1]);
+        annotate_search_hit_highlights("stor node", &mut hits[1..2]);
+        annotate_search_hit_highlights("node", &mut hits[2..]);
+
+        let name = hits[0].highlight.as_ref().expect("name highlight");
+        assert_eq!(name.field, SearchMatchFieldDto::Name);
+        assert_eq!(ranges(name), vec![(9, 12), (13, 17)]);
+
+        let qualified = hits[1].highlight.as_ref().expect("qualified highlight");
+        assert_eq!(qualified.field, SearchMatchFieldDto::QualifiedName);
+        assert_eq!(ranges(qualified), vec![(0, 4), (13, 17)]);
+
+        let unicode = hits[2].highlight.as_ref().expect("unicode highlight");
+        assert_eq!(ranges(unicode), vec![(10, 14)]);
+        assert!(hits[3].highlight.is_none());
+    }
+
+    #[test]
+    fn sub_token_matches_highlight_the_matched_sub_token_prefixes() {
+        let mut hits = vec![
+            hit("ProjectionFlusher", SearchHitOrigin::IndexedSymbol),
+            hit("flush_queue::Projection", SearchHitOrigin::IndexedSymbol),
+            hit("unrelated", SearchHitOrigin::IndexedSymbol),
+        ];
+        annotate_search_hit_highlights("flushproj", &mut hits[..1]);
+        annotate_search_hit_highlights("projflush", &mut hits[1..]);
+
+        let name = hits[0].highlight.as_ref().expect("sub-token highlight");
+        assert_eq!(name.field, SearchMatchFieldDto::Name);
+        assert_eq!(ranges(name), vec![(0, 4), (10, 15)]);
+
+        let qualified = hits[1].highlight.as_ref().expect("qualified highlight");
+        assert_eq!(qualified.field, SearchMatchFieldDto::QualifiedName);
+        assert_eq!(ranges(qualified), vec![(0, 5), (13, 17)]);
+        assert!(hits[2].highlight.is_none());
+    }
+}
@@ -1,3 +1,10 @@
 pub mod engine;
+mod highlight;
 #[cfg(test)]
 pub mod lexical;
+mod prefilter;
+mod prefix;
+mod subtokens;
+
+pub(crate) use highlight::annotate_search_hit_highlights;
+pub(crate) use prefix::SymbolPrefixIndex;
@@ -0,0 +1,76 @@
+//! Character-set prefilter that skips symbols fuzzy search cannot match.
+//!
+//! A fuzzy match needs every query character somewhere in the name, so a
+//! name missing one of them is rejected before it is scored. Each name is
+//! summarized as a 64-bit set of the ASCII characters it contains.
+
+use nucleo_matcher::{Utf32Str, Utf32String};
+
+/// Bit for one case-folded ASCII byte. Letters and digits get their own
+/// bits; other bytes share the rest, which only lets more names through.
+fn ascii_bit(byte: u8) -> u64 {
+    let slot = match byte.to_ascii_lowercase() {
+        byte @ b'a'..=b'z' => byte - b'a',
+        byte @ b'0'..=b'9' => 26 + (byte - b'0'),
+        byte => 36 + byte % 28,
+    };
+    1 << slot
+}
+
+fn name_mask(name: &Utf32String) -> u64 {
+    match name.slice(..) {
+        Utf32Str::Ascii(bytes) => bytes.iter().fold(0, |mask, byte| mask | ascii_bit(*byte)),
+        // Non-ASCII characters may normalize to ASCII ones while matching.
+        Utf32Str::Unicode(_) => u64::MAX,
+    }
+}
+
+/// One character set per symbol, in symbol order.
+pub(crate) struct SymbolCharMasks {
+    masks: Vec<u64>,
+}
+
+impl SymbolCharMasks {
+    pub(crate) fn build<T>(symbols: &[(Utf32String, T)]) -> Self {
+        Self {
+            masks: symbols.iter().map(|(name, _)| name_mask(name)).collect(),
+        }
+    }
+
+    /// Characters every match of `query` contains. Whitespace separates
+    /// pattern words and `\` escapes, so neither is required; neither are
+    /// non-ASCII characters.
+    pub(crate) fn query_mask(query: &str) -> u64 {
+        query
+            .bytes()
+            .filter(|byte| byte.is_ascii() && !byte.is_ascii_whitespace() && *byte != b'\\')
+            .fold(0, |mask, byte| mask | ascii_bit(byte))
+    }
+
+    /// Whether the symbol at `index` contains every character of
+    /// `query_mask`.
+    pub(crate) fn may_match(&self, index: usize, query_mask: u64) -> bool {
+        self.masks
+            .get(index)
+            .is_none_or(|mask| mask & query_mask == query_mask)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_missing_a_query_character_are_skipped() {
+        let symbols =
+            ["Storage::get_node", "render", "café_latte"].map(|name| (Utf32String::from(name), ()));
+        let masks = SymbolCharMasks::build(&symbols);
+
+        let query = SymbolCharMasks::query_mask("GetNd");
+        assert!(masks.may_match(0, query));
+        assert!(!masks.may_match(1, query));
+        assert!(masks.may_match(2, query), "non-ASCII names are kept");
+        assert!(masks.may_match(1, SymbolCharMasks::query_mask("r nd")));
+        assert!(!masks.may_match(0, SymbolCharMasks::query_mask("get_node2")));
+    }
+}
@@ -0,0 +1,83 @@
+//! Sorted prefix index over symbol names for autocomplete.
+
+use codestory_contracts::graph::NodeId;
+use nucleo_matcher::Utf32String;
+use std::collections::HashMap;
+
+/// Keys examined per lookup, keeping one-letter prefixes fast on large
+/// projects.
+const PREFIX_SCAN_BUDGET: usize = 4_096;
+const PATH_SEPARATORS: [&str; 4] = ["::", ".", "/", "#"];
+
+struct PrefixKey {
+    key: Box<str>,
+    node_id: NodeId,
+    /// Whether the key is the whole name rather than a trailing segment.
+    whole_name: bool,
+}
+
+/// Lowercased symbol names plus their trailing path segments, sorted so that
+/// all keys sharing a prefix form one contiguous run.
+///
+/// `codestory::Storage::get_node` is reachable by typing `codestory`,
+/// `storage`, or `get_n`.
+pub(crate) struct SymbolPrefixIndex {
+    keys: Vec<PrefixKey>,
+}
+
+impl SymbolPrefixIndex {
+    pub(crate) fn build(symbols: &[(Utf32String, NodeId)]) -> Self {
+        let mut keys = Vec::with_capacity(symbols.len() * 2);
+        for (name, node_id) in symbols {
+            let name = name.to_string().to_lowercase();
+            for offset in segment_starts(&name) {
+                keys.push(PrefixKey {
+                    key: name[offset..].into(),
+                    node_id: *node_id,
+                    whole_name: offset == 0,
+                });
+            }
+        }
+        keys.sort_by(|left, right| left.key.cmp(&right.key));
+        Self { keys }
+    }
+
+    /// Symbols with a key starting with `prefix`, scored by how much of the
+    /// key the prefix already covers. Whole-name keys score slightly above
+    /// segment keys of the same length.
+    pub(crate) fn lookup(&self, prefix: &str) -> Vec<(NodeId, f32)> {
+        let prefix = prefix.to_lowercase();
+        let start = self
+            .keys
+            .partition_point(|entry| entry.key.as_ref() < prefix.as_str());
+        let prefix_chars = prefix.chars().count() as f32;
+        let mut best = HashMap::<NodeId, f32>::new();
+        for entry in self.keys[start..]
+            .iter()
+            .take(PREFIX_SCAN_BUDGET)
+            .take_while(|entry| entry.key.starts_with(prefix.as_str()))
+        {
+            let coverage = prefix_chars / entry.key.chars().count() as f32;
+            let score = if entry.whole_name {
+                coverage
+            } else {
+                coverage * 0.95
+            };
+            let slot = best.entry(entry.node_id).or_insert(score);
+            *slot = slot.max(score);
+        }
+        best.into_iter().collect()
+    }
+}
+
+/// Byte offsets where a searchable segment of `name` begins.
+fn segment_starts(name: &str) -> impl Iterator<Item = usize> + '_ {
+    let segment_starts = name.char_indices().filter_map(move |(idx, _)| {
+        PATH_SEPARATORS
+            .iter()
+            .find(|separator| name[idx..].starts_with(**separator))
+            .map(|separator| idx + separator.len())
+            .filter(|start| *start < name.len())
+    });
+    std::iter::once(0).chain(segment_starts)
+}
@@ -0,0 +1,397 @@
+//! Identifier sub-token index for queries that run words together or
+//! reorder them.
+//!
+//! Names are split into lowercase sub-tokens at namespace separators,
+//! underscores, and camel humps, so `ProjectionFlusher` becomes
+//! `projection` and `flusher`. A query is matched by cutting it into pieces
+//! that each start one of a symbol's sub-tokens, in any order: `flushproj`
+//! finds both `projection_flush` and `ProjectionFlusher`.
+
+use nucleo_matcher::Utf32String;
+use std::collections::HashMap;
+use std::ops::Range;
+
+/// Shortest piece that may match a longer sub-token by prefix. Shorter
+/// pieces must equal a whole sub-token.
+const MIN_PREFIX_PIECE_CHARS: usize = 3;
+/// Most pieces one query is cut into.
+const MAX_PIECES: usize = 6;
+/// Most ways of cutting one query that are looked up.
+const MAX_SEGMENTATIONS: usize = 8;
+/// Most partial cuts tried for one query, including those that lead
+/// nowhere, so a query no cut covers cannot explore every branch.
+const MAX_SEGMENT_VISITS: usize = 512;
+/// Longer queries are left to fuzzy search alone.
+const MAX_QUERY_CHARS: usize = 64;
+/// Fuzzy-score points per query character for a sub-token match, a little
+/// under what an in-order contiguous fuzzy match earns.
+const SCORE_PER_QUERY_CHAR: u32 = 14;
+
+/// Lowercase sub-tokens of `name`, in order, without repeats.
+///
+/// `http::HTTPServer::read_all2` gives `http`, `server`, `read`, `all2`.
+pub(crate) fn identifier_subtokens(name: &str) -> Vec<String> {
+    let chars = name.chars().collect::<Vec<_>>();
+    let mut tokens = Vec::<String>::new();
+    for span in subtoken_spans(&chars) {
+        let token = chars[span].iter().collect::<String>().to_lowercase();
+        if !tokens.contains(&token) {
+            tokens.push(token);
+        }
+    }
+    tokens
+}
+
+/// Character ranges of the sub-tokens of `chars`, in order, repeats
+/// included.
+fn subtoken_spans(chars: &[char]) -> Vec<Range<usize>> {
+    let mut spans = Vec::new();
+    let mut start = None;
+    for (index, ch) in chars.iter().copied().enumerate() {
+        if !ch.is_alphanumeric() {
+            spans.extend(start.take().map(|start| start..index));
+            continue;
+        }
+        let previous = index.checked_sub(1).map(|index| chars[index]);
+        let next = chars.get(index + 1).copied();
+        let hump = ch.is_uppercase()
+            && previous.is_some_and(|previous| {
+                previous.is_lowercase()
+                    || previous.is_ascii_digit()
+                    || (previous.is_uppercase() && next.is_some_and(char::is_lowercase))
+            });
+        if hump {
+            spans.extend(start.take().map(|start| start..index));
+        }
+        start.get_or_insert(index);
+    }
+    spans.extend(start.map(|start| start..chars.len()));
+    spans
+}
+
+/// `query` lowercased, without the characters sub-tokens never hold.
+fn normalized_query(query: &str) -> String {
+    query
+        .chars()
+        .filter(|ch| ch.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Whether a query of `query_chars` normalized characters is matched by
+/// sub-tokens rather than left to fuzzy search alone.
+fn subtoken_query_len(query_chars: usize) -> bool {
+    (MIN_PREFIX_PIECE_CHARS + 1..=MAX_QUERY_CHARS).contains(&query_chars)
+}
+
+/// Character indices of `name` that [`SymbolSubtokenIndex::lookup`] matches
+/// `query` against: the starts of the sub-tokens the query's pieces begin,
+/// or `None` when no cut of the query covers `name`.
+pub(crate) fn subtoken_match_indices(name: &str, query: &str) -> Option<Vec<u32>> {
+    let query = normalized_query(query);
+    if !subtoken_query_len(query.chars().count()) {
+        return None;
+    }
+    let chars = name.chars().collect::<Vec<_>>();
+    let tokens = subtoken_spans(&chars)
+        .into_iter()
+        .map(|span| {
+            let token = chars[span.clone()]
+                .iter()
+                .collect::<String>()
+                .to_lowercase();
+            (token, span)
+        })
+        .collect::<Vec<_>>();
+    let mut cover = NameCover {
+        chars: &chars,
+        tokens: &tokens,
+        indices: Vec::new(),
+        visits_left: MAX_SEGMENT_VISITS,
+    };
+    cover.cut(&query, 0).then_some(cover.indices)
+}
+
+/// The first cut of a query over one name's sub-tokens.
+struct NameCover<'a> {
+    chars: &'a [char],
+    /// Lowercase sub-tokens with their character ranges in `chars`.
+    tokens: &'a [(String, Range<usize>)],
+    /// Characters of `chars` matched by the pieces cut so far.
+    indices: Vec<u32>,
+    visits_left: usize,
+}
+
+impl NameCover<'_> {
+    /// Cut `rest` into pieces that each start a sub-token, longest pieces
+    /// first, as [`SymbolSubtokenIndex::segment`] does. Returns whether a
+    /// cut was found, leaving its matched characters in `indices`.
+    fn cut(&mut self, rest: &str, pieces: usize) -> bool {
+        if rest.is_empty() {
+            return pieces >= 2;
+        }
+        if pieces >= MAX_PIECES || self.visits_left == 0 {
+            return false;
+        }
+        self.visits_left -= 1;
+        let ends = rest
+            .char_indices()
+            .map(|(offset, ch)| offset + ch.len_utf8())
+            .collect::<Vec<_>>();
+        for (chars, end) in ends.iter().enumerate().rev() {
+            let piece = &rest[..*end];
+            let Some((_, span)) = self.tokens.iter().find(|(token, _)| {
+                token.starts_with(piece) && (chars + 1 >= MIN_PREFIX_PIECE_CHARS || token == piece)
+            }) else {
+                continue;
+            };
+            let matched = self.indices.len();
+            // Walk the original characters until their lowercase forms
+            // cover the piece.
+            let mut covered = 0;
+            for index in span.clone() {
+                if covered >= piece.len() {
+                    break;
+                }
+                covered += self.chars[index]
+                    .to_lowercase()
+                    .map(char::len_utf8)
+                    .sum::<usize>();
+                self.indices.push(index as u32);
+            }
+            if self.cut(&rest[*end..], pieces + 1) {
+                return true;
+            }
+            self.indices.truncate(matched);
+        }
+        false
+    }
+}
+
+/// Sub-tokens of every symbol, with the symbols that contain each one.
+pub(crate) struct SymbolSubtokenIndex {
+    /// Distinct sub-tokens, sorted so tokens sharing a prefix are adjacent.
+    vocabulary: Vec<Box<str>>,
+    /// Symbol indices containing each vocabulary entry, ascending.
+    postings: Vec<Vec<u32>>,
+    /// Vocabulary indices of each symbol's sub-tokens.
+    symbol_tokens: Vec<Box<[u32]>>,
+}
+
+impl SymbolSubtokenIndex {
+    pub(crate) fn build<T>(symbols: &[(Utf32String, T)]) -> Self {
+        let symbol_subtokens = symbols
+            .iter()
+            .map(|(name, _)| identifier_subtokens(&name.to_string()))
+            .collect::<Vec<_>>();
+        let mut vocabulary = symbol_subtokens
+            .iter()
+            .flatten()
+            .map(|token| Box::<str>::from(token.as_str()))
+            .collect::<Vec<_>>();
+        vocabulary.sort_unstable();
+        vocabulary.dedup();
+        let token_ids = vocabulary
+            .iter()
+            .enumerate()
+            .map(|(id, token)| (token.as_ref(), id as u32))
+            .collect::<HashMap<_, _>>();
+
+        let mut postings = vec![Vec::new(); vocabulary.len()];
+        let mut symbol_tokens = Vec::with_capacity(symbols.len());
+        for (symbol, tokens) in symbol_subtokens.iter().enumerate() {
+            let ids = tokens
+                .iter()
+                .map(|token| token_ids[token.as_str()])
+                .collect::<Box<[u32]>>();
+            for id in ids.iter() {
+                postings[*id as usize].push(symbol as u32);
+            }
+            symbol_tokens.push(ids);
+        }
+        Self {
+            vocabulary,
+            postings,
+            symbol_tokens,
+        }
+    }
+
+    /// Fuzzy-scale scores for symbols whose sub-tokens cover `query` cut
+    /// into two or more pieces, keyed by symbol index.
+    pub(crate) fn lookup(&self, query: &str) -> HashMap<usize, u32> {
+        let query = normalized_query(query);
+        let mut matches = HashMap::new();
+        let query_chars = query.chars().count();
+        if !subtoken_query_len(query_chars) {
+            return matches;
+        }
+        let score = query_chars as u32 * SCORE_PER_QUERY_CHAR;
+        let mut segmentations = Vec::new();
+        let mut visits_left = MAX_SEGMENT_VISITS;
+        self.segment(
+            &query,
+            &mut Vec::new(),
+            &mut segmentations,
+            &mut visits_left,
+        );
+        for pieces in segmentations {
+            for symbol in self.covering_symbols(&pieces) {
+                matches.insert(symbol, score);
+            }
+        }
+        matches
+    }
+
+    /// Collect ways of cutting `rest` into pieces that each start a
+    /// vocabulary token, longest pieces first, giving up once `visits_left`
+    /// partial cuts have been tried.
+    fn segment(
+        &self,
+        rest: &str,
+        pieces: &mut Vec<Range<usize>>,
+        out: &mut Vec<Vec<Range<usize>>>,
+        visits_left: &mut usize,
+    ) {
+        if out.len() >= MAX_SEGMENTATIONS || *visits_left == 0 {
+            return;
+        }
+        *visits_left -= 1;
+        if rest.is_empty() {
+            if pieces.len() >= 2 {
+                out.push(pieces.clone());
+            }
+            return;
+        }
+        if pieces.len() >= MAX_PIECES {
+            return;
+        }
+        let ends = rest
+            .char_indices()
+            .map(|(offset, ch)| offset + ch.len_utf8())
+            .collect::<Vec<_>>();
+        for (chars, end) in ends.iter().enumerate().rev() {
+            let piece = &rest[..*end];
+            let range = self.prefix_range(piece);
+            let usable = !range.is_empty()
+                && (chars + 1 >= MIN_PREFIX_PIECE_CHARS
+                    || self.vocabulary[range.start].as_ref() == piece);
+            if !usable {
+                continue;
+            }
+            pieces.push(range);
+            self.segment(&rest[*end..], pieces, out, visits_left);
+            pieces.pop();
+        }
+    }
+
+    /// Vocabulary indices of the tokens starting with `prefix`.
+    fn prefix_range(&self, prefix: &str) -> Range<usize> {
+        let start = self
+            .vocabulary
+            .partition_point(|token| token.as_ref() < prefix);
+        let len = self.vocabulary[start..]
+            .iter()
+            .take_while(|token| token.starts_with(prefix))
+            .count();
+        start..start + len
+    }
+
+    /// Symbols with a sub-token in every piece's vocabulary range.
+    fn covering_symbols(&self, pieces: &[Range<usize>]) -> Vec<usize> {
+        let posting_len = |range: &Range<usize>| -> usize {
+            self.postings[range.clone()].iter().map(Vec::len).sum()
+        };
+        let Some(narrowest) = pieces.iter().min_by_key(|range| posting_len(range)) else {
+            return Vec::new();
+        };
+        let mut symbols = self.postings[narrowest.clone()]
+            .iter()
+            .flatten()
+            .map(|symbol| *symbol as usize)
+            .filter(|symbol| {
+                let tokens = &self.symbol_tokens[*symbol];
+                pieces.iter().all(|range| {
+                    tokens
+                        .iter()
+                        .any(|token| range.contains(&(*token as usize)))
+                })
+            })
+            .collect::<Vec<_>>();
+        symbols.sort_unstable();
+        symbols.dedup();
+        symbols
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identifiers_split_at_separators_and_camel_humps() {
+        assert_eq!(
+            identifier_subtokens("http::HTTPServer::read_all2"),
+            vec!["http", "server", "read", "all2"]
+        );
+        assert_eq!(
+            identifier_subtokens("ProjectionFlusher"),
+            vec!["projection", "flusher"]
+        );
+        assert_eq!(identifier_subtokens("__init__"), vec!["init"]);
+    }
+
+    #[test]
+    fn run_together_queries_match_sub_tokens_in_any_order() {
+        let symbols = [
+            "projection_flush",
+            "ProjectionFlusher",
+            "flush",
+            "project_loader",
+        ]
+        .map(|name| (Utf32String::from(name), ()));
+        let index = SymbolSubtokenIndex::build(&symbols);
+
+        let mut matched = index
+            .lookup("flushprojection")
+            .into_keys()
+            .collect::<Vec<_>>();
+        matched.sort_unstable();
+        assert_eq!(matched, vec![0, 1]);
+        assert_eq!(
+            index
+                .lookup("Flush Proj")
+                .keys()
+                .copied()
+                .collect::<Vec<_>>()
+                .len(),
+            2
+        );
+        assert!(
+            index.lookup("flu").is_empty(),
+            "short queries are left to fuzzy search"
+        );
+        assert!(index.lookup("loaderflush").is_empty());
+    }
+
+    #[test]
+    fn queries_no_cut_covers_stop_after_the_visit_cap() {
+        let symbols = ["a", "aa", "aaa", "aaaa", "aaaaa"].map(|name| (Utf32String::from(name), ()));
+        let index = SymbolSubtokenIndex::build(&symbols);
+        let query = format!("{}b", "a".repeat(40));
+
+        let mut segmentations = Vec::new();
+        let mut visits_left = MAX_SEGMENT_VISITS;
+        index.segment(
+            &query,
+            &mut Vec::new(),
+            &mut segmentations,
+            &mut visits_left,
+        );
+        assert!(segmentations.is_empty());
+        assert_eq!(
+            visits_left, 0,
+            "every branch fails, so the cap is what stops it"
+        );
+        assert!(index.lookup(&query).is_empty());
+    }
+}
@@ -399,6 +399,7 @@ mod tests {
             source_excerpt: None,
             verification_targets: Vec::new(),
             score_breakdown: None,
+            highlight: None,
         }
     }
 }
@@ -7,6 +7,11 @@ use super::{
     terminal_symbol_segment,
 };
 
+/// Candidates resolved per requested hit when scope atoms will discard some.
+const SCOPED_SEARCH_CANDIDATE_MULTIPLIER: usize = 4;
+/// Most candidates a scoped search resolves before filtering.
+const SCOPED_SEARCH_MAX_CANDIDATES: usize = 200;
+
 #[derive(Debug, Clone)]
 pub(super) struct SearchIntentQuery {
     pub(super) effective_query: String,
@@ -75,6 +80,17 @@ pub(super) fn parse_search_intent_query(query: &str) -> SearchIntentQuery {
     }
 }
 
+/// How many candidates to resolve so that `limit` hits survive the filters.
+pub(super) fn scoped_search_candidate_limit(limit: usize, filters: &[SearchIntentFilter]) -> usize {
+    if filters.is_empty() {
+        return limit;
+    }
+    limit
+        .saturating_mul(SCOPED_SEARCH_CANDIDATE_MULTIPLIER)
+        .min(SCOPED_SEARCH_MAX_CANDIDATES)
+        .max(limit)
+}
+
 pub(super) fn strip_query_value_quotes(value: &str) -> String {
     let value = value.trim();
     if value.len() >= 2 {
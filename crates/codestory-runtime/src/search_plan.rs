@@ -14,8 +14,8 @@ use super::{
 };
 use crate::search_intent::{
     SearchIntentFilter, SearchIntentQuery, annotate_search_hit_match_quality,
-    apply_search_intent_filters, parse_search_intent_query, search_hit_match_quality,
-    search_query_assessment,
+    apply_search_intent_filters, parse_search_intent_query, scoped_search_candidate_limit,
+    search_hit_match_quality, search_query_assessment,
 };
 use crate::search_scoring::{
     ArchitectureCoverage, apply_architecture_cross_source_coverage, architecture_coverage_for_hit,
@@ -999,16 +999,24 @@ pub(super) fn search_plan_bridge_request(from: &NodeId, to: &NodeId) -> TrailCon
         root_id: from.clone(),
         mode: codestory_contracts::api::TrailMode::ToTargetSymbol,
         target_id: Some(to.clone()),
-        depth: 0,
-        direction: codestory_contracts::api::TrailDirection::Outgoing,
-        caller_scope: codestory_contracts::api::TrailCallerScope::ProductionOnly,
+        depth: Some(0),
+        direction: Some(codestory_contracts::api::TrailDirection::Outgoing),
+        caller_scope: Some(codestory_contracts::api::TrailCallerScope::ProductionOnly),
         edge_filter: Vec::new(),
         show_utility_calls: false,
         hide_speculative: true,
         story: false,
         node_filter: Vec::new(),
-        max_nodes: 80,
+        hide_trivial_accessors: false,
+        hide_generated: false,
+        entry_reachable_only: false,
+        max_paths: None,
+        edge_weight: Default::default(),
+        max_nodes: Some(80),
         layout_direction: codestory_contracts::api::LayoutDirection::Horizontal,
+        graph_session: None,
+        timeout_ms: None,
+        filter_preset: None,
     }
 }
 
@@ -1582,7 +1590,7 @@ impl AppController {
         let (query_result, resolution) = agent::retrieval_primary::run_and_resolve_sidecar_query(
             self,
             &query,
-            limit_per_source,
+            scoped_search_candidate_limit(limit_per_source, &intent_query.filters),
             None,
         )?;
         let mut indexed_symbol_hits = resolution.resolved_hits.clone();
@@ -1698,6 +1706,7 @@ impl AppController {
         );
         indexed_symbol_hits.truncate(limit_per_source);
         annotate_search_hit_match_quality(&query, &mut indexed_symbol_hits);
+        crate::search::annotate_search_hit_highlights(&query, &mut indexed_symbol_hits);
         crate::search_evidence::attach_pinned_search_evidence(
             &storage,
             project_root.as_deref(),
@@ -446,6 +446,18 @@ pub(super) fn load_canonical_search_symbols(
     ))
 }
 
+/// Rank widely referenced symbols ahead of obscure ones with similar names.
+pub(super) fn load_symbol_importance(
+    storage: &Storage,
+    engine: &mut SearchEngine,
+) -> Result<(), ApiError> {
+    let fan_in = storage
+        .get_symbol_fan_in_counts()
+        .map_err(|error| ApiError::internal(format!("Failed to load symbol fan-in: {error}")))?;
+    engine.set_symbol_fan_in(&fan_in);
+    Ok(())
+}
+
 pub(super) struct LoadedSearchState {
     pub(super) publication: Option<IndexPublicationRecord>,
     pub(super) node_names: HashMap<codestory_contracts::graph::NodeId, String>,
@@ -497,6 +509,7 @@ pub(super) fn load_persisted_search_state_for_runtime(
             ApiError::internal(format!("Failed to finish legacy symbol index: {error}"))
         })?;
         load_persisted_semantic_docs_for_runtime(storage, &mut engine, false, runtime)?;
+        load_symbol_importance(storage, &mut engine)?;
         return Ok(LoadedSearchState {
             publication: None,
             node_names,
@@ -559,6 +572,7 @@ pub(super) fn load_persisted_search_state_for_runtime(
             ),
         ));
     }
+    load_symbol_importance(storage, &mut engine)?;
     if publication.is_some() {
         engine
             .downgrade_persisted_lock_to_shared()
@@ -237,6 +237,52 @@ pub(super) fn primary_source_retention_threshold(requested_max_results: usize) -
     requested_max_results.clamp(1, 3)
 }
 
+/// Largest share of a hit's score its centrality can add. Hits are ranked by
+/// match quality first, so this only reorders comparable matches.
+const CENTRALITY_SEARCH_BOOST: f32 = 0.05;
+
+/// Raise hit scores by their node's centrality relative to the most central
+/// hit. Hits keep their scores when no centrality has been computed.
+pub(super) fn apply_centrality_boost(storage: &Storage, hits: &mut [HybridSearchScoredHit]) {
+    let node_ids = hits
+        .iter()
+        .filter_map(|scored| scored.hit.node_id.to_core().ok())
+        .collect::<Vec<_>>();
+    let centrality = match storage.node_centrality(&node_ids) {
+        Ok(centrality) => centrality,
+        Err(error) => {
+            tracing::debug!("Search centrality unavailable: {error}");
+            return;
+        }
+    };
+    let Some(max_centrality) = centrality
+        .values()
+        .copied()
+        .reduce(f32::max)
+        .filter(|max| *max > 0.0)
+    else {
+        return;
+    };
+    for scored in hits {
+        let Some(score) = scored
+            .hit
+            .node_id
+            .to_core()
+            .ok()
+            .and_then(|id| centrality.get(&id))
+        else {
+            continue;
+        };
+        let factor = 1.0 + CENTRALITY_SEARCH_BOOST * (score / max_centrality);
+        scored.hit.score *= factor;
+        scored.total_score *= factor;
+        if let Some(breakdown) = scored.hit.score_breakdown.as_mut() {
+            breakdown.total = scored.total_score;
+            breakdown.boosts.push(format!("centrality {score:.2}"));
+        }
+    }
+}
+
 pub(super) fn merge_search_hits_by_node_id(hits: &mut Vec<SearchHit>, additional: Vec<SearchHit>) {
     let mut existing = hits
         .iter()
@@ -1121,6 +1167,7 @@ impl AppController {
             source_excerpt: None,
             verification_targets: Vec::new(),
             score_breakdown: None,
+            highlight: None,
         };
         decorate_search_hit_evidence(&mut hit);
         Ok(Some(hit))
@@ -1406,6 +1453,7 @@ impl AppController {
                 });
             }
         }
+        apply_centrality_boost(&storage, &mut out);
         if should_pretruncate_primary_source_window(
             &req.query,
             prefer_primary_sources,
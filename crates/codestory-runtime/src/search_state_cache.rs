@@ -1,8 +1,9 @@
 use crate::search;
 use crate::search_publication::{
     SearchGenerationCatalogGuard, discard_unpublished_search_generation,
-    load_canonical_search_symbols, prune_search_generations, read_search_generation_completion,
-    search_index_path_for_publication, write_search_generation_completion,
+    load_canonical_search_symbols, load_symbol_importance, prune_search_generations,
+    read_search_generation_completion, search_index_path_for_publication,
+    write_search_generation_completion,
 };
 use crate::semantic_projection::{
     CacheRefreshStats, SEARCH_SYMBOL_STREAM_BATCH_SIZE, SearchStateBuildResult,
@@ -23,6 +24,7 @@ use codestory_store::{IndexPublicationRecord, Store};
 use codestory_workspace::RefreshInputs;
 use std::collections::{HashMap, HashSet};
 use std::path::Path;
+use std::sync::Arc;
 use std::time::Instant;
 use uuid::Uuid;
 
@@ -132,6 +134,7 @@ fn reuse_completed_search_state(
         hydrate_semantic_docs,
         runtime,
     )?;
+    load_symbol_importance(storage, &mut engine)?;
     Ok(Some(SearchStateBuildResult {
         publication: Some(publication.clone()),
         node_names,
@@ -231,6 +234,7 @@ pub(super) fn build_persisted_search_state_from_canonical_symbols(
         hydrate_semantic_docs,
         runtime,
     )?;
+    load_symbol_importance(storage, &mut engine)?;
     Ok(SearchStateBuildResult {
         publication: None,
         node_names,
@@ -410,7 +414,7 @@ pub(super) fn refresh_caches(
                 error.message
             );
             let mut state = controller.state.lock();
-            state.node_names.clear();
+            state.node_names = Arc::default();
             clear_search_engine(&mut state);
             controller.sidecar_query_cache.lock().clear();
             state.is_indexing = false;
@@ -425,7 +429,7 @@ pub(super) fn publish_prepared_search_state(
 ) -> CacheRefreshStats {
     let publish_started = Instant::now();
     let mut state = controller.state.lock();
-    state.node_names = result.node_names;
+    state.node_names = Arc::new(result.node_names);
     publish_search_engine(&mut state, result.engine, result.publication);
     controller.sidecar_query_cache.lock().clear();
     state.is_indexing = false;
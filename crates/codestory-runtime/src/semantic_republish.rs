@@ -377,6 +377,7 @@ fn semantic_projection_phase_timings(
         ),
         summary_snapshot_ms: Some(prepared.finalize_stats.summary_snapshot_ms),
         detail_snapshot_ms: Some(prepared.detail_snapshot_ms),
+        analyze_ms: prepared.finalize_stats.analyze_ms,
         publish_ms: Some(clamp_u128_to_u32(publish_duration.as_millis())),
         staged_sqlite_wal_autocheckpoint_bytes: publish_stats.sqlite_wal_autocheckpoint_bytes,
         staged_sqlite_checkpoint_ms: publish_stats.sqlite_checkpoint_ms,
@@ -1,18 +1,21 @@
 use codestory_contracts::api::{
     AffectedAnalysisDto, AffectedAnalysisRequest, AgentAnswerDto, AgentAskRequest,
     AgentHybridWeightsDto, AgentPacketDto, AgentPacketRequestDto, ApiError, ApiErrorDetails,
-    BookmarkCategoryDto, BookmarkDto, CreateBookmarkCategoryRequest, CreateBookmarkRequest,
+    AutocompleteSuggestionDto, BookmarkCategoryDto, BookmarkDto, BookmarkHealthDto,
+    CreateBookmarkCategoryRequest, CreateBookmarkRequest, CreateProjectRequest,
     EmbeddingCapacityPressureDto, EmbeddingRetryStateDto, EmbeddingVectorPublicationIdentityDto,
-    GroundingBudgetDto, GroundingSnapshotDto, IndexDryRunDto, IndexFreshnessStatusDto, IndexMode,
-    IndexPublicationDto, IndexedFilesDto, IndexedFilesRequest, IndexingPhaseTimings,
-    ListChildrenSymbolsRequest, ListRootSymbolsRequest, NodeDetailsDto, NodeDetailsRequest, NodeId,
-    OpenDefinitionRequest, OpenProjectRequest, ProjectSummary, RetrievalStateDto, SearchHit,
-    SearchRequest, SearchResultsDto, SnippetContextDto, SourceOccurrenceDto, StartIndexingRequest,
-    SummaryGenerationDto, SymbolContextDto, SymbolSummaryDto, SystemActionResponse, TrailConfigDto,
-    TrailContextDto,
+    EntryPointOverviewDto, GroundingBudgetDto, GroundingSnapshotDto, IndexDryRunDto, IndexErrorDto,
+    IndexFreshnessStatusDto, IndexManifestDto, IndexMode, IndexPublicationDto, IndexSnapshotDto,
+    IndexedFilesDto, IndexedFilesRequest, IndexingPhaseTimings, ListBookmarksRequest,
+    ListChildrenSymbolsRequest, ListIndexErrorsRequest, ListRootSymbolsRequest, NodeDetailsDto,
+    NodeDetailsRequest, NodeId, NodePreviewDto, OpenDefinitionRequest, OpenProjectRequest, PageDto,
+    ProjectSummary, RetrievalStateDto, SearchHit, SearchRequest, SearchResultsDto,
+    SnippetContextDto, SourceOccurrenceDto, StartIndexingRequest, SummaryGenerationDto,
+    SymbolContextDto, SymbolSummaryDto, SystemActionResponse, TestLinkDto, TrailConfigDto,
+    TrailContextDto, TrailDefaultsDto, TrailFilterPresetDto,
 };
 
-use crate::AppController;
+use crate::{AppController, ReadOnlyBrowserService};
 use codestory_indexer::CancellationToken;
 use codestory_store::{IndexPublicationRecord, Store};
 use serde::Serialize;
@@ -984,6 +987,9 @@ pub struct ActivePublicOperationPublication {
 pub struct PublicOperationService {
     controller: AppController,
     activation: Option<ActivationService>,
+    /// Serves an archived index snapshot, which is expected to lag the
+    /// workspace.
+    archived: bool,
     next_id: Arc<AtomicU64>,
 }
 
@@ -992,6 +998,7 @@ impl PublicOperationService {
         Self {
             controller,
             activation: None,
+            archived: false,
             next_id: Arc::new(AtomicU64::new(0)),
         }
     }
@@ -1003,11 +1010,26 @@ impl PublicOperationService {
         Self {
             controller,
             activation: Some(activation),
+            archived: false,
+            next_id: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Operations over an archived snapshot. Graph reads run on the
+    /// snapshot's publication however far the workspace has moved on.
+    pub(crate) fn new_archived(controller: AppController) -> Self {
+        Self {
+            controller,
+            activation: None,
+            archived: true,
             next_id: Arc::new(AtomicU64::new(0)),
         }
     }
 
     fn retained_core_allows(&self, operation: &str, publication: &IndexPublicationRecord) -> bool {
+        if self.archived {
+            return !operation_requires_retrieval(operation);
+        }
         !operation_requires_retrieval(operation)
             && self.activation.as_ref().is_some_and(|activation| {
                 let Some(project_root) = self.controller.require_project_root().ok() else {
@@ -1439,6 +1461,19 @@ impl ProjectService {
         self.controller.open_project(req)
     }
 
+    pub fn create_project(&self, req: CreateProjectRequest) -> Result<ProjectSummary, ApiError> {
+        self.controller.create_project(req)
+    }
+
+    pub fn create_project_with_storage_path(
+        &self,
+        req: CreateProjectRequest,
+        storage_path: std::path::PathBuf,
+    ) -> Result<ProjectSummary, ApiError> {
+        self.controller
+            .create_project_with_storage_path(req, storage_path)
+    }
+
     pub fn open_project_with_storage_path(
         &self,
         root: std::path::PathBuf,
@@ -1532,6 +1567,12 @@ impl IndexService {
         self.controller.run_indexing_blocking(mode)
     }
 
+    /// Reindex the files a unified diff changes plus the files of their
+    /// direct callers; see [`AppController::reindex_review_diff`].
+    pub fn reindex_review_diff(&self, diff: &str) -> Result<Vec<String>, ApiError> {
+        self.controller.reindex_review_diff(diff)
+    }
+
     pub fn run_indexing_blocking_with_cancel(
         &self,
         mode: IndexMode,
@@ -1621,6 +1662,80 @@ impl IndexService {
     pub fn summarize_symbols_blocking(&self) -> Result<SummaryGenerationDto, ApiError> {
         self.controller.summarize_symbols_blocking()
     }
+
+    pub fn set_snapshot_retention(&self, count: usize) {
+        self.controller.set_snapshot_retention(count)
+    }
+
+    pub fn set_indexing_max_threads(&self, max_threads: Option<usize>) {
+        self.controller.set_indexing_max_threads(max_threads)
+    }
+
+    pub fn dependency_resolution(&self) -> Result<bool, ApiError> {
+        self.controller.dependency_resolution()
+    }
+
+    pub fn set_dependency_resolution(&self, enabled: bool) -> Result<bool, ApiError> {
+        self.controller.set_dependency_resolution(enabled)
+    }
+
+    pub fn centrality_iterations(&self) -> Result<usize, ApiError> {
+        self.controller.centrality_iterations()
+    }
+
+    pub fn set_centrality_iterations(&self, iterations: usize) -> Result<usize, ApiError> {
+        self.controller.set_centrality_iterations(iterations)
+    }
+
+    pub fn trail_defaults(&self) -> Result<TrailDefaultsDto, ApiError> {
+        self.controller.trail_defaults()
+    }
+
+    pub fn set_trail_defaults(
+        &self,
+        defaults: TrailDefaultsDto,
+    ) -> Result<TrailDefaultsDto, ApiError> {
+        self.controller.set_trail_defaults(defaults)
+    }
+
+    pub fn trail_filter_presets(&self) -> Result<Vec<TrailFilterPresetDto>, ApiError> {
+        self.controller.trail_filter_presets()
+    }
+
+    pub fn save_trail_filter_preset(
+        &self,
+        preset: TrailFilterPresetDto,
+    ) -> Result<Vec<TrailFilterPresetDto>, ApiError> {
+        self.controller.save_trail_filter_preset(preset)
+    }
+
+    pub fn delete_trail_filter_preset(&self, name: &str) -> Result<bool, ApiError> {
+        self.controller.delete_trail_filter_preset(name)
+    }
+
+    pub fn string_literal_indexing(&self) -> Result<bool, ApiError> {
+        self.controller.string_literal_indexing()
+    }
+
+    pub fn set_string_literal_indexing(&self, enabled: bool) -> Result<bool, ApiError> {
+        self.controller.set_string_literal_indexing(enabled)
+    }
+
+    pub fn set_open_files(&self, paths: &[String]) -> Result<(), ApiError> {
+        self.controller.set_open_files(paths)
+    }
+
+    pub fn list_index_snapshots(&self) -> Result<Vec<IndexSnapshotDto>, ApiError> {
+        self.controller.list_index_snapshots()
+    }
+
+    pub fn index_manifest(&self) -> Result<Option<IndexManifestDto>, ApiError> {
+        self.controller.index_manifest()
+    }
+
+    pub fn open_index_snapshot(&self, name: &str) -> Result<ReadOnlyBrowserService, ApiError> {
+        self.controller.open_index_snapshot(name)
+    }
 }
 
 #[derive(Clone)]
@@ -1645,10 +1760,25 @@ impl SearchService {
         self.controller.search_results(req)
     }
 
+    pub fn autocomplete(
+        &self,
+        prefix: &str,
+        limit: usize,
+    ) -> Result<Vec<AutocompleteSuggestionDto>, ApiError> {
+        self.controller.autocomplete(prefix, limit)
+    }
+
     pub fn indexed_files(&self, req: IndexedFilesRequest) -> Result<IndexedFilesDto, ApiError> {
         self.controller.indexed_files(req)
     }
 
+    pub fn list_index_errors(
+        &self,
+        req: ListIndexErrorsRequest,
+    ) -> Result<PageDto<IndexErrorDto>, ApiError> {
+        self.controller.list_index_errors(req)
+    }
+
     pub fn affected_analysis(
         &self,
         req: AffectedAnalysisRequest,
@@ -1714,6 +1844,22 @@ impl GroundingService {
         self.controller.node_details(req)
     }
 
+    pub fn node_preview(&self, node_id: NodeId) -> Result<NodePreviewDto, ApiError> {
+        self.controller.node_preview(node_id)
+    }
+
+    pub fn entry_point_overview(&self) -> Result<EntryPointOverviewDto, ApiError> {
+        self.controller.entry_point_overview()
+    }
+
+    pub fn tests_covering(&self, node_id: NodeId) -> Result<Vec<TestLinkDto>, ApiError> {
+        self.controller.tests_covering(node_id)
+    }
+
+    pub fn code_tested_by(&self, test_id: NodeId) -> Result<Vec<TestLinkDto>, ApiError> {
+        self.controller.code_tested_by(test_id)
+    }
+
     pub fn node_occurrences(
         &self,
         req: codestory_contracts::api::NodeOccurrencesRequest,
@@ -1721,6 +1867,13 @@ impl GroundingService {
         self.controller.node_occurrences(req)
     }
 
+    pub fn node_occurrences_page(
+        &self,
+        req: codestory_contracts::api::NodeOccurrencesRequest,
+    ) -> Result<PageDto<SourceOccurrenceDto>, ApiError> {
+        self.controller.node_occurrences_page(req)
+    }
+
     pub fn list_root_symbols(
         &self,
         req: ListRootSymbolsRequest,
@@ -1735,6 +1888,13 @@ impl GroundingService {
         self.controller.list_children_symbols(req)
     }
 
+    pub fn list_children_symbols_page(
+        &self,
+        req: ListChildrenSymbolsRequest,
+    ) -> Result<PageDto<SymbolSummaryDto>, ApiError> {
+        self.controller.list_children_symbols_page(req)
+    }
+
     pub fn open_definition(
         &self,
         req: OpenDefinitionRequest,
@@ -1802,6 +1962,13 @@ impl BookmarkService {
         self.controller.list_bookmarks(category_id)
     }
 
+    pub fn list_bookmarks_page(
+        &self,
+        req: ListBookmarksRequest,
+    ) -> Result<PageDto<BookmarkDto>, ApiError> {
+        self.controller.list_bookmarks_page(req)
+    }
+
     pub fn create_bookmark(&self, req: CreateBookmarkRequest) -> Result<BookmarkDto, ApiError> {
         self.controller.create_bookmark(req)
     }
@@ -1809,6 +1976,14 @@ impl BookmarkService {
     pub fn delete_bookmark(&self, id: i64) -> Result<(), ApiError> {
         self.controller.delete_bookmark(id)
     }
+
+    pub fn health(&self) -> Result<BookmarkHealthDto, ApiError> {
+        self.controller.bookmarks_health()
+    }
+
+    pub fn repair(&self) -> Result<BookmarkHealthDto, ApiError> {
+        self.controller.repair_bookmarks()
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,69 @@
+use crate::support::node_display_name;
+use crate::{AppController, runtime_relative_path};
+use codestory_contracts::api::{
+    ApiError, NodeId, NodeKind, StringLiteralMatchDto, StringLiteralSearchDto,
+    StringLiteralSearchRequest,
+};
+
+const STRING_LITERAL_DEFAULT_LIMIT: u32 = 50;
+const STRING_LITERAL_MAX_LIMIT: u32 = 500;
+
+impl AppController {
+    /// Indexed string literals and constant values holding every word of the
+    /// query, each with the innermost symbol that defines it.
+    ///
+    /// Nothing matches unless the project turned string literal indexing on
+    /// before its last indexing run.
+    pub fn search_string_literals(
+        &self,
+        req: StringLiteralSearchRequest,
+    ) -> Result<StringLiteralSearchDto, ApiError> {
+        if req.query.trim().is_empty() {
+            return Err(ApiError::invalid_argument(
+                "The literal search query must not be empty.",
+            ));
+        }
+        let limit = req
+            .limit
+            .unwrap_or(STRING_LITERAL_DEFAULT_LIMIT)
+            .clamp(1, STRING_LITERAL_MAX_LIMIT) as usize;
+        let root = self.require_project_root()?;
+        let storage = self.open_storage_read_only()?;
+        let mut records = storage
+            .search_string_literals(&req.query, limit + 1)
+            .map_err(|e| ApiError::internal(format!("Failed to search string literals: {e}")))?;
+        let truncated = records.len() > limit;
+        records.truncate(limit);
+
+        let symbol_ids = records
+            .iter()
+            .filter_map(|record| record.symbol_id)
+            .collect::<Vec<_>>();
+        let symbols = storage
+            .get_nodes_by_ids(&symbol_ids)
+            .map_err(|e| ApiError::internal(format!("Failed to load literal symbols: {e}")))?;
+        let labels = self.cached_labels(symbol_ids);
+        let matches = records
+            .into_iter()
+            .map(|record| {
+                let symbol = record.symbol_id.and_then(|id| symbols.get(&id));
+                StringLiteralMatchDto {
+                    file_path: runtime_relative_path(&root, &record.path),
+                    line: record.literal.line,
+                    column: record.literal.column,
+                    name: record.literal.name,
+                    value: record.literal.value,
+                    symbol_id: symbol.map(|node| NodeId::from(node.id)),
+                    symbol_name: symbol.map(|node| {
+                        labels
+                            .get(&node.id)
+                            .cloned()
+                            .unwrap_or_else(|| node_display_name(node))
+                    }),
+                    symbol_kind: symbol.map(|node| NodeKind::from(node.kind)),
+                }
+            })
+            .collect();
+        Ok(StringLiteralSearchDto { matches, truncated })
+    }
+}
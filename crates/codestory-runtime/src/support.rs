@@ -2,6 +2,8 @@
 use crate::search_runtime::HybridSearchConfig;
 #[cfg(test)]
 use codestory_contracts::api::{AgentHybridWeightsDto, SearchHybridLimitsDto};
+use codestory_contracts::api::{StorageStatsDto, StorageTableStatsDto};
+use codestory_store::StorageStats;
 use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::collections::HashSet;
@@ -82,6 +84,30 @@ pub(crate) fn node_display_name(node: &codestory_contracts::graph::Node) -> Stri
         .unwrap_or_else(|| node.serialized_name.clone())
 }
 
+/// `stats` as the API reports them, with `file_count` in place of the stored
+/// file count.
+pub(crate) fn storage_stats_dto(stats: &StorageStats, file_count: i64) -> StorageStatsDto {
+    StorageStatsDto {
+        node_count: clamp_i64_to_u32(stats.node_count),
+        edge_count: clamp_i64_to_u32(stats.edge_count),
+        file_count: clamp_i64_to_u32(file_count),
+        error_count: clamp_i64_to_u32(stats.error_count),
+        fatal_error_count: clamp_i64_to_u32(stats.fatal_error_count),
+        occurrence_count: clamp_i64_to_u32(stats.occurrence_count),
+        database_bytes: stats.database_bytes,
+        last_indexed_at_epoch_ms: stats.last_indexed_at_epoch_ms,
+        tables: stats
+            .tables
+            .iter()
+            .map(|table| StorageTableStatsDto {
+                name: table.name.clone(),
+                row_count: clamp_i64_to_u32(table.row_count),
+                bytes: table.bytes,
+            })
+            .collect(),
+    }
+}
+
 pub(crate) fn clamp_i64_to_u32(v: i64) -> u32 {
     if v <= 0 {
         0
@@ -415,7 +441,9 @@ pub(crate) fn preferred_occurrence(
             | codestory_contracts::graph::OccurrenceKind::MACRO_DEFINITION => 4,
             codestory_contracts::graph::OccurrenceKind::DECLARATION => 3,
             codestory_contracts::graph::OccurrenceKind::REFERENCE
-            | codestory_contracts::graph::OccurrenceKind::MACRO_REFERENCE => 2,
+            | codestory_contracts::graph::OccurrenceKind::MACRO_REFERENCE
+            | codestory_contracts::graph::OccurrenceKind::WRITE_REFERENCE
+            | codestory_contracts::graph::OccurrenceKind::READ_REFERENCE => 2,
             codestory_contracts::graph::OccurrenceKind::UNKNOWN => 1,
         }
     }
@@ -453,6 +481,8 @@ mod tests {
                 end_line: line,
                 end_col: 10,
             },
+            edge_id: None,
+            notebook_cell: None,
         }
     }
 
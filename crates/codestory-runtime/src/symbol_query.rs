@@ -1330,6 +1330,7 @@ mod tests {
             source_excerpt: None,
             verification_targets: Vec::new(),
             score_breakdown: None,
+            highlight: None,
         }
     }
 
@@ -145,20 +145,28 @@ impl AppController {
             root_id: target.selected.node_id.clone(),
             mode: TrailMode::AllReferencing,
             target_id: None,
-            depth,
-            direction: TrailDirection::Incoming,
-            caller_scope: if include_tests {
+            depth: Some(depth),
+            direction: Some(TrailDirection::Incoming),
+            caller_scope: Some(if include_tests {
                 TrailCallerScope::IncludeTestsAndBenches
             } else {
                 TrailCallerScope::ProductionOnly
-            },
+            }),
             edge_filter: Vec::new(),
             show_utility_calls: false,
             hide_speculative: true,
             story: false,
             node_filter: Vec::new(),
-            max_nodes,
+            hide_trivial_accessors: false,
+            hide_generated: false,
+            entry_reachable_only: false,
+            max_paths: None,
+            edge_weight: Default::default(),
+            max_nodes: Some(max_nodes),
             layout_direction: LayoutDirection::Horizontal,
+            graph_session: None,
+            timeout_ms: None,
+            filter_preset: None,
         })?;
 
         let affected_seed = target
@@ -249,6 +257,9 @@ fn workflow_occurrences(
         }
         if let Ok(occurrences) = controller.node_occurrences(NodeOccurrencesRequest {
             id: hit.node_id.clone(),
+            kind: None,
+            offset: None,
+            limit: None,
         }) {
             by_node.insert(hit.node_id.clone(), occurrences);
         }
@@ -532,6 +543,9 @@ mod tests {
             file_path: Some(format!("src/{label}.rs")),
             qualified_name: None,
             member_access: None,
+            edge_counts: None,
+            badges: Default::default(),
+            centrality: None,
         }
     }
 
@@ -555,6 +569,7 @@ mod tests {
                 resolution_status: None,
                 member_access: None,
                 route_endpoint: None,
+                definitions: Vec::new(),
             },
             trail: GraphResponse {
                 center_id: NodeId("focus".to_string()),
@@ -567,59 +582,46 @@ mod tests {
                     node("reference", "Reference", 2),
                 ],
                 edges: vec![
-                    GraphEdgeDto {
-                        id: EdgeId("direct-b-focus".to_string()),
-                        source: NodeId("direct-b".to_string()),
-                        target: NodeId("focus".to_string()),
-                        kind: EdgeKind::CALL,
-                        confidence: None,
-                        certainty: None,
-                        callsite_identity: None,
-                        candidate_targets: Vec::new(),
-                    },
-                    GraphEdgeDto {
-                        id: EdgeId("direct-a-focus".to_string()),
-                        source: NodeId("direct-a".to_string()),
-                        target: NodeId("focus".to_string()),
-                        kind: EdgeKind::CALL,
-                        confidence: None,
-                        certainty: None,
-                        callsite_identity: None,
-                        candidate_targets: Vec::new(),
-                    },
-                    GraphEdgeDto {
-                        id: EdgeId("transitive-b-direct".to_string()),
-                        source: NodeId("transitive-b".to_string()),
-                        target: NodeId("direct-b".to_string()),
-                        kind: EdgeKind::CALL,
-                        confidence: None,
-                        certainty: None,
-                        callsite_identity: None,
-                        candidate_targets: Vec::new(),
-                    },
-                    GraphEdgeDto {
-                        id: EdgeId("transitive-a-direct".to_string()),
-                        source: NodeId("transitive-a".to_string()),
-                        target: NodeId("direct-a".to_string()),
-                        kind: EdgeKind::CALL,
-                        confidence: None,
-                        certainty: None,
-                        callsite_identity: None,
-                        candidate_targets: Vec::new(),
-                    },
-                    GraphEdgeDto {
-                        id: EdgeId("reference-direct".to_string()),
-                        source: NodeId("reference".to_string()),
-                        target: NodeId("direct-b".to_string()),
-                        kind: EdgeKind::USAGE,
-                        confidence: None,
-                        certainty: None,
-                        callsite_identity: None,
-                        candidate_targets: Vec::new(),
-                    },
+                    GraphEdgeDto::new(
+                        EdgeId("direct-b-focus".to_string()),
+                        NodeId("direct-b".to_string()),
+                        NodeId("focus".to_string()),
+                        EdgeKind::CALL,
+                    ),
+                    GraphEdgeDto::new(
+                        EdgeId("direct-a-focus".to_string()),
+                        NodeId("direct-a".to_string()),
+                        NodeId("focus".to_string()),
+                        EdgeKind::CALL,
+                    ),
+                    GraphEdgeDto::new(
+                        EdgeId("transitive-b-direct".to_string()),
+                        NodeId("transitive-b".to_string()),
+                        NodeId("direct-b".to_string()),
+                        EdgeKind::CALL,
+                    ),
+                    GraphEdgeDto::new(
+                        EdgeId("transitive-a-direct".to_string()),
+                        NodeId("transitive-a".to_string()),
+                        NodeId("direct-a".to_string()),
+                        EdgeKind::CALL,
+                    ),
+                    GraphEdgeDto::new(
+                        EdgeId("reference-direct".to_string()),
+                        NodeId("reference".to_string()),
+                        NodeId("direct-b".to_string()),
+                        EdgeKind::USAGE,
+                    ),
                 ],
                 truncated: false,
+                truncated_by_timeout: false,
                 omitted_edge_count: 0,
+                pruned_test_node_count: 0,
+                pruned_accessor_node_count: 0,
+                pruned_generated_node_count: 0,
+                omitted_node_count: 0,
+                node_limit: None,
+                paths: Vec::new(),
                 canonical_layout: None,
             },
             story: None,
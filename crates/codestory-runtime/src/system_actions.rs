@@ -8,6 +8,7 @@ pub(crate) fn status_response(message: impl Into<String>) -> SystemActionRespons
     SystemActionResponse {
         ok: true,
         message: message.into(),
+        definitions: Vec::new(),
     }
 }
 
@@ -926,6 +926,7 @@ fn search_hit_from_node(node: &NodeDetailsDto) -> SearchHit {
         source_excerpt: None,
         verification_targets: Vec::new(),
         score_breakdown: None,
+        highlight: None,
     };
     decorate_search_hit_evidence(&mut hit);
     hit
@@ -1101,6 +1102,7 @@ mod tests {
             source_excerpt: None,
             verification_targets: Vec::new(),
             score_breakdown: None,
+            highlight: None,
         }
     }
 
@@ -1241,6 +1243,7 @@ mod tests {
             resolution_status: Some(PacketEvidenceResolutionDto::SourceRangeOnly),
             member_access: None,
             route_endpoint: None,
+            definitions: Vec::new(),
         };
         let hit = search_hit_from_node(&details);
 
@@ -1296,6 +1299,7 @@ mod tests {
             resolution_status: Some(PacketEvidenceResolutionDto::SourceRangeOnly),
             member_access: None,
             route_endpoint: None,
+            definitions: Vec::new(),
         };
         let hit = search_hit_from_node(&details);
 
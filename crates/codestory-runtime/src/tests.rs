@@ -47,6 +47,7 @@ use crate::index_incremental::{
     FULL_REFRESH_REQUIRED_ERROR_CODE, full_refresh_required_error, index_incremental,
     spawn_progress_forwarder,
 };
+use crate::index_queue::{IndexingQueue, QueuedIndexing, prioritize_open_files};
 use crate::index_timings::{FullRefreshWallDurations, IndexingRunSummary};
 use crate::repo_text::{
     REPO_TEXT_MAX_FILE_BYTES, REPO_TEXT_SCAN_BYTE_CAP, REPO_TEXT_SCAN_FILE_CAP,
@@ -59,6 +60,7 @@ use crate::search_intent::indexed_file_matches_language_filter;
 use crate::search_intent::{
     SearchIntentFilter, annotate_search_hit_match_quality, apply_search_intent_filters,
     exact_symbol_hit_count, language_filter_matches_path, parse_search_intent_query,
+    scoped_search_candidate_limit,
 };
 use crate::search_plan::{
     SearchPlanActivePathEvidence, search_plan_anchor_groups, search_plan_eligible,
@@ -88,8 +90,8 @@ use crate::semantic_republish::semantic_projection_republish_for_runtime;
 use crate::snippets::bounded_direct_markdown_snippet;
 use crate::snippets::bounded_markdown_snippet_from_path;
 use codestory_contracts::api::{
-    ArtifactCachePolicyDto, CorePromotionTimings, IndexMode, IndexedFilesRequest,
-    ListRootSymbolsRequest, OpenProjectRequest, StartIndexingRequest,
+    ArtifactCachePolicyDto, CorePromotionTimings, IndexMode, IndexedFilesRequest, IndexingPriority,
+    ListRootSymbolsRequest, OpenProjectRequest, StartIndexingRequest, TrailDefaultsDto,
     UpdateBookmarkCategoryRequest, WriteFileTextRequest,
 };
 use codestory_contracts::events::{Event, EventBus};
@@ -385,6 +387,83 @@ fn graph_edge_dto_defaults_structural_member_certainty() {
     assert_eq!(explicit_probable.certainty.as_deref(), Some("probable"));
 }
 
+#[test]
+fn graph_edge_dto_separates_raw_and_resolved_endpoints() {
+    use codestory_contracts::api::EdgeResolutionDto;
+
+    let flags = AppGraphFeatureFlags {
+        include_edge_certainty: true,
+        include_callsite_identity: true,
+        include_candidate_targets: true,
+    };
+    let resolved_call = graph_edge_dto(
+        Edge {
+            id: EdgeId(1),
+            source: CoreNodeId(10),
+            target: CoreNodeId(20),
+            kind: EdgeKind::CALL,
+            resolved_target: Some(CoreNodeId(21)),
+            ..Default::default()
+        },
+        flags,
+    );
+    let unresolved_call = graph_edge_dto(
+        Edge {
+            id: EdgeId(2),
+            source: CoreNodeId(10),
+            target: CoreNodeId(30),
+            kind: EdgeKind::CALL,
+            ..Default::default()
+        },
+        flags,
+    );
+    let member = graph_edge_dto(
+        Edge {
+            id: EdgeId(3),
+            source: CoreNodeId(10),
+            target: CoreNodeId(40),
+            kind: EdgeKind::MEMBER,
+            ..Default::default()
+        },
+        flags,
+    );
+    let exact_call = graph_edge_dto(
+        Edge {
+            id: EdgeId(4),
+            source: CoreNodeId(10),
+            target: CoreNodeId(50),
+            kind: EdgeKind::CALL,
+            certainty: Some(ResolutionCertainty::Certain),
+            ..Default::default()
+        },
+        flags,
+    );
+    let probable_call = graph_edge_dto(
+        Edge {
+            id: EdgeId(5),
+            source: CoreNodeId(10),
+            target: CoreNodeId(60),
+            kind: EdgeKind::CALL,
+            certainty: Some(ResolutionCertainty::Probable),
+            ..Default::default()
+        },
+        flags,
+    );
+
+    assert_eq!(resolved_call.target, NodeId("21".to_string()));
+    assert_eq!(resolved_call.raw_target, Some(NodeId("20".to_string())));
+    assert_eq!(resolved_call.raw_source, None);
+    assert_eq!(resolved_call.resolution, EdgeResolutionDto::Resolved);
+    assert_eq!(unresolved_call.target, NodeId("30".to_string()));
+    assert_eq!(unresolved_call.raw_target, None);
+    assert_eq!(unresolved_call.resolution, EdgeResolutionDto::Unresolved);
+    assert_eq!(member.resolution, EdgeResolutionDto::Exact);
+    assert_eq!(exact_call.target, NodeId("50".to_string()));
+    assert_eq!(exact_call.raw_target, None);
+    assert_eq!(exact_call.resolution, EdgeResolutionDto::Exact);
+    assert_eq!(probable_call.resolution, EdgeResolutionDto::Unresolved);
+}
+
 #[test]
 fn llm_doc_embed_batch_size_uses_throughput_default() {
     let _lock = process_env_test_lock();
@@ -1637,6 +1716,7 @@ fn search_plan_test_hit(
         source_excerpt: None,
         verification_targets: Vec::new(),
         score_breakdown: None,
+        highlight: None,
     }
 }
 
@@ -5135,6 +5215,7 @@ fn embedded_exact_symbol_terms_count_and_annotate_exact_hits() {
         source_excerpt: None,
         verification_targets: Vec::new(),
         score_breakdown: None,
+        highlight: None,
     };
     let query = "exact symbol first semantic ranking search_hybrid_with_scores";
 
@@ -5459,9 +5540,7 @@ fn finalize_indexing_without_runtime_refresh_propagates_rebuild_failure() {
     {
         let mut state = controller.state.lock();
         state.is_indexing = true;
-        state
-            .node_names
-            .insert(CoreNodeId(999), "stale_symbol".to_string());
+        Arc::make_mut(&mut state.node_names).insert(CoreNodeId(999), "stale_symbol".to_string());
         let engine = SearchEngine::new(None).expect("search engine");
         publish_search_engine(&mut state, engine, None);
     }
@@ -5556,6 +5635,8 @@ fn async_incremental_finishes_cache_boundary_before_clearing_marker() {
     controller
         .start_indexing(StartIndexingRequest {
             mode: IndexMode::Incremental,
+            priority: None,
+            max_threads: None,
         })
         .expect("start async incremental");
 
@@ -5588,137 +5669,863 @@ fn async_incremental_finishes_cache_boundary_before_clearing_marker() {
 }
 
 #[test]
-fn empty_full_refresh_reports_adaptive_chunk_config() {
+fn reindex_file_refreshes_only_the_saved_file() {
     let workspace = tempdir().expect("workspace dir");
+    fs::write(workspace.path().join("lib.rs"), "pub fn alpha() {}\n").expect("write lib");
+    fs::write(workspace.path().join("other.rs"), "pub fn beta() {}\n").expect("write other");
     let storage_path = workspace.path().join(".cache").join("codestory.db");
     let controller = AppController::new();
     controller
-        .open_project_summary_with_storage_path(workspace.path().to_path_buf(), storage_path)
+        .open_project_summary_with_storage_path(
+            workspace.path().to_path_buf(),
+            storage_path.clone(),
+        )
         .expect("open project");
-
-    let timings = controller
+    controller
         .run_indexing_blocking_without_runtime_refresh(IndexMode::Full)
-        .expect("empty full publication");
+        .expect("publish compatible baseline");
+    fs::write(workspace.path().join("lib.rs"), "pub fn alpha_saved() {}\n").expect("save lib");
+    fs::write(
+        workspace.path().join("other.rs"),
+        "pub fn beta_pending() {}\n",
+    )
+    .expect("edit other");
+    let events = controller.events();
+    while events.try_recv().is_ok() {}
 
-    assert_eq!(
-        timings.full_refresh_chunk_target_bytes,
-        Some(8 * 1024 * 1024)
+    controller
+        .reindex_file("lib.rs")
+        .expect("reindex saved file");
+
+    let names = Storage::open(&storage_path)
+        .expect("open published storage")
+        .get_nodes()
+        .expect("nodes")
+        .into_iter()
+        .map(|node| node.serialized_name)
+        .collect::<Vec<_>>();
+    assert!(names.iter().any(|name| name.contains("alpha_saved")));
+    assert!(names.iter().any(|name| name.ends_with("beta")));
+    assert!(
+        !names.iter().any(|name| name.contains("beta_pending")),
+        "unsaved files wait for the next incremental refresh"
     );
-    assert_eq!(timings.full_refresh_chunk_target_nodes, Some(120_000));
-    assert_eq!(timings.full_refresh_chunk_file_ceiling, Some(512));
-    assert_eq!(timings.full_refresh_chunk_max_files, Some(0));
-    assert_eq!(timings.full_refresh_chunk_max_planned_bytes, Some(0));
-    assert_eq!(timings.full_refresh_chunk_max_nodes, Some(0));
-    assert_eq!(timings.full_refresh_chunk_budget_overruns, Some(0));
-    assert!(timings.full_refresh_chunk_planning_ms.is_some());
+    assert!(events.try_iter().any(|event| matches!(
+        event,
+        AppEventPayload::FileReindexed { ref path, .. } if path == "lib.rs"
+    )));
+    assert!(!controller.state.lock().is_indexing);
 }
 
 #[test]
-fn full_and_incremental_publications_advance_one_durable_generation() {
-    let assert_promotion_reconciles = |promotion: &CorePromotionTimings| {
-        let named_ms = promotion
-            .lock_recovery_ms
-            .saturating_add(promotion.candidate_validation_ms)
-            .saturating_add(promotion.previous_validation_ms)
-            .saturating_add(promotion.rollback_backup_copy_ms.unwrap_or_default())
-            .saturating_add(promotion.backup_validation_ms.unwrap_or_default())
-            .saturating_add(promotion.prepared_journal_write_ms)
-            .saturating_add(promotion.prepared_journal_file_sync_ms)
-            .saturating_add(promotion.prepared_journal_directory_sync_ms)
-            .saturating_add(promotion.staged_to_live_restore_ms)
-            .saturating_add(promotion.promoted_validation_ms)
-            .saturating_add(promotion.committed_journal_ms)
-            .saturating_add(promotion.cleanup_ms);
+fn reindex_file_removes_a_deleted_or_ignored_file_only() {
+    let workspace = tempdir().expect("workspace dir");
+    fs::write(workspace.path().join("lib.rs"), "pub fn alpha() {}\n").expect("write lib");
+    fs::write(workspace.path().join("other.rs"), "pub fn beta() {}\n").expect("write other");
+    fs::write(workspace.path().join("gen.rs"), "pub fn gamma() {}\n").expect("write gen");
+    let storage_path = workspace.path().join(".cache").join("codestory.db");
+    let controller = AppController::new();
+    controller
+        .open_project_summary_with_storage_path(
+            workspace.path().to_path_buf(),
+            storage_path.clone(),
+        )
+        .expect("open project");
+    controller
+        .run_indexing_blocking_without_runtime_refresh(IndexMode::Full)
+        .expect("publish compatible baseline");
+    fs::remove_file(workspace.path().join("lib.rs")).expect("delete lib");
+    fs::write(workspace.path().join(".gitignore"), "gen.rs\n").expect("ignore gen");
+
+    controller
+        .reindex_file("lib.rs")
+        .expect("reindex deleted file");
+    controller
+        .reindex_file("gen.rs")
+        .expect("reindex ignored file");
+
+    let names = Storage::open(&storage_path)
+        .expect("open published storage")
+        .get_nodes()
+        .expect("nodes")
+        .into_iter()
+        .map(|node| node.serialized_name)
+        .collect::<Vec<_>>();
+    assert!(!names.iter().any(|name| name.ends_with("alpha")));
+    assert!(!names.iter().any(|name| name.ends_with("gamma")));
+    assert!(names.iter().any(|name| name.ends_with("beta")));
+}
+
+#[test]
+fn node_occurrences_clamp_ranges_that_outlived_file_edits() {
+    let workspace = tempdir().expect("workspace dir");
+    let lib_path = workspace.path().join("lib.rs");
+    fs::write(&lib_path, "\n\n\n\npub fn alpha() {}\n").expect("write lib");
+    let storage_path = workspace.path().join(".cache").join("codestory.db");
+    let controller = AppController::new();
+    controller
+        .open_project_summary_with_storage_path(
+            workspace.path().to_path_buf(),
+            storage_path.clone(),
+        )
+        .expect("open project");
+    controller
+        .run_indexing_blocking_without_runtime_refresh(IndexMode::Full)
+        .expect("index project");
+    let alpha = Storage::open(&storage_path)
+        .expect("open published storage")
+        .get_nodes()
+        .expect("nodes")
+        .into_iter()
+        .find(|node| node.serialized_name.ends_with("alpha"))
+        .expect("alpha node");
+
+    fs::write(&lib_path, "fn a() {}\n").expect("shrink lib");
+    fs::File::options()
+        .write(true)
+        .open(&lib_path)
+        .expect("open lib")
+        .set_modified(std::time::UNIX_EPOCH + Duration::from_secs(1))
+        .expect("set lib mtime");
+    let events = controller.events();
+    while events.try_recv().is_ok() {}
+
+    let occurrences = controller
+        .node_occurrences(codestory_contracts::api::NodeOccurrencesRequest {
+            id: NodeId::from(alpha.id),
+            kind: None,
+            offset: None,
+            limit: None,
+        })
+        .expect("load occurrences");
+    assert!(!occurrences.is_empty());
+    for occurrence in &occurrences {
+        assert!(occurrence.stale, "{occurrence:?}");
+        assert!(occurrence.end_line <= 2, "{occurrence:?}");
+        assert!(occurrence.start_line <= occurrence.end_line);
+    }
+    assert!(events.try_iter().any(|event| matches!(
+        event,
+        AppEventPayload::ReindexSuggested { ref paths } if paths == &["lib.rs".to_string()]
+    )));
+}
+
+#[test]
+fn node_occurrences_mark_ranges_in_edited_files_stale_even_when_they_fit() {
+    let workspace = tempdir().expect("workspace dir");
+    let lib_path = workspace.path().join("lib.rs");
+    fs::write(&lib_path, "pub fn alpha() {}\n").expect("write lib");
+    let storage_path = workspace.path().join(".cache").join("codestory.db");
+    let controller = AppController::new();
+    controller
+        .open_project_summary_with_storage_path(
+            workspace.path().to_path_buf(),
+            storage_path.clone(),
+        )
+        .expect("open project");
+    controller
+        .run_indexing_blocking_without_runtime_refresh(IndexMode::Full)
+        .expect("index project");
+    let alpha = Storage::open(&storage_path)
+        .expect("open published storage")
+        .get_nodes()
+        .expect("nodes")
+        .into_iter()
+        .find(|node| node.serialized_name.ends_with("alpha"))
+        .expect("alpha node");
+    let request = || codestory_contracts::api::NodeOccurrencesRequest {
+        id: NodeId::from(alpha.id),
+        kind: None,
+        offset: None,
+        limit: None,
+    };
+    let indexed = controller
+        .node_occurrences(request())
+        .expect("load indexed occurrences");
+    assert!(!indexed.is_empty());
+    assert!(indexed.iter().all(|occurrence| !occurrence.stale));
+
+    fs::write(
+        &lib_path,
+        "pub fn alpha() { let renamed = 1; }\n// padding\n// padding\n",
+    )
+    .expect("edit lib");
+    fs::File::options()
+        .write(true)
+        .open(&lib_path)
+        .expect("open lib")
+        .set_modified(std::time::UNIX_EPOCH + Duration::from_secs(1))
+        .expect("set lib mtime");
+    let events = controller.events();
+    while events.try_recv().is_ok() {}
+
+    let edited = controller
+        .node_occurrences(request())
+        .expect("load edited occurrences");
+    assert_eq!(edited.len(), indexed.len());
+    for (before, after) in indexed.iter().zip(&edited) {
+        assert!(after.stale, "{after:?}");
         assert_eq!(
-            named_ms.saturating_add(promotion.unattributed_ms),
-            promotion.total_ms
+            (
+                after.start_line,
+                after.start_col,
+                after.end_line,
+                after.end_col
+            ),
+            (
+                before.start_line,
+                before.start_col,
+                before.end_line,
+                before.end_col
+            ),
+            "a range that still fits keeps its position"
         );
-    };
+    }
+    assert!(events.try_iter().any(|event| matches!(
+        event,
+        AppEventPayload::ReindexSuggested { ref paths } if paths == &["lib.rs".to_string()]
+    )));
+}
+
+#[test]
+fn indexing_reports_node_edge_and_file_deltas_per_run() {
     let workspace = tempdir().expect("workspace dir");
     fs::write(
         workspace.path().join("lib.rs"),
-        "pub fn first_value() -> i32 { 1 }\n",
+        "pub fn alpha() { beta(); }\npub fn beta() {}\n",
     )
-    .expect("write initial source");
-    let storage_path = workspace.path().join(".cache").join("codestory.db");
+    .expect("write lib");
+    fs::write(workspace.path().join("util.rs"), "pub fn gamma() {}\n").expect("write util");
     let controller = AppController::new();
     controller
         .open_project_summary_with_storage_path(
             workspace.path().to_path_buf(),
-            storage_path.clone(),
+            workspace.path().join(".cache").join("codestory.db"),
         )
         .expect("open project");
 
-    let full_timings = controller
-        .run_indexing_blocking_without_runtime_refresh(IndexMode::Full)
-        .expect("first full publication");
-    let parser_cache = full_timings
-        .parser_artifact_cache
-        .as_ref()
-        .expect("parser cache telemetry");
-    assert_eq!(parser_cache.policy, ArtifactCachePolicyDto::KnownEmpty);
-    assert_eq!(parser_cache.logical_lookups, 1);
-    assert_eq!(parser_cache.physical_queries, 0);
-    assert_eq!(parser_cache.hits, 0);
-    assert_eq!(parser_cache.misses, 1);
-    assert_eq!(parser_cache.reader_opens, 0);
-    assert_eq!(parser_cache.lookup_wall_ms, 0);
-    let structural_cache = full_timings
-        .structural_artifact_cache
-        .as_ref()
-        .expect("structural cache telemetry");
-    assert_eq!(structural_cache.policy, ArtifactCachePolicyDto::KnownEmpty);
-    assert_eq!(structural_cache.logical_lookups, 0);
-    assert_eq!(structural_cache.physical_queries, 0);
-    assert_eq!(structural_cache.reader_opens, 0);
-    assert_eq!(full_timings.full_refresh_queue_capacity, Some(1));
-    assert_eq!(full_timings.full_refresh_queue_high_water, Some(1));
-    assert_eq!(full_timings.full_refresh_chunks_produced, Some(1));
-    assert_eq!(full_timings.full_refresh_chunks_persisted, Some(1));
-    assert!(full_timings.full_refresh_producer_blocked_ms.is_some());
-    assert!(full_timings.full_refresh_writer_idle_ms.is_some());
-    assert_eq!(
-        full_timings.full_refresh_chunk_target_bytes,
-        Some(8 * 1024 * 1024)
+    let first = controller
+        .run_indexing_blocking(IndexMode::Full)
+        .expect("full index")
+        .run_delta
+        .expect("full run delta");
+    assert_eq!((first.files_added, first.files_removed), (2, 0));
+    assert!(
+        first.nodes_added >= 3 && first.edges_added >= 1,
+        "{first:?}"
     );
-    assert_eq!(full_timings.full_refresh_chunk_target_nodes, Some(120_000));
-    assert_eq!(full_timings.full_refresh_chunk_file_ceiling, Some(512));
-    assert_eq!(full_timings.full_refresh_chunk_max_files, Some(1));
-    assert!(full_timings.full_refresh_chunk_max_planned_bytes.is_some());
-    assert!(full_timings.full_refresh_chunk_max_nodes.is_some());
-    assert_eq!(full_timings.full_refresh_chunk_budget_overruns, Some(0));
-    assert!(full_timings.full_refresh_chunk_planning_ms.is_some());
-    let full_projection = full_timings
-        .projection_persistence
-        .as_ref()
-        .expect("full projection persistence telemetry");
-    assert_eq!(
-        Some(full_projection.transactions),
-        full_timings.projection_batch_transactions
+    assert_eq!((first.nodes_removed, first.edges_removed), (0, 0));
+    assert!(first.slowest_files.len() <= 2);
+    assert!(
+        first
+            .slowest_files
+            .iter()
+            .all(|file| file.path == "lib.rs" || file.path == "util.rs"),
+        "{first:?}"
     );
-    assert!(full_projection.row_attempts > 0);
-    assert!(full_projection.bound_bytes > 0);
-    assert!(full_projection.statement_executions > 0);
+
+    fs::write(workspace.path().join("lib.rs"), "pub fn alpha() {}\n").expect("rewrite lib");
+    fs::remove_file(workspace.path().join("util.rs")).expect("remove util");
+    let second = controller
+        .run_indexing_blocking(IndexMode::Incremental)
+        .expect("incremental index")
+        .run_delta
+        .expect("incremental run delta");
     assert_eq!(
-        full_projection.dirty_state.statement_executions,
-        u64::from(full_projection.transactions) * 4
+        (
+            second.files_added,
+            second.files_removed,
+            second.files_updated
+        ),
+        (0, 1, 1)
     );
-    assert_eq!(
-        full_timings.staged_sqlite_wal_autocheckpoint_bytes,
-        Some(64 * 1024 * 1024)
+    assert!(
+        second.nodes_removed >= 2 && second.edges_removed >= 1,
+        "{second:?}"
     );
-    assert!(full_timings.staged_sqlite_checkpoint_ms.is_some());
-    assert!(full_timings.staged_sqlite_sync_ms.is_some());
-    assert!(full_timings.staged_snapshot_copy.is_none());
-    let full_promotion = full_timings
-        .core_promotion
-        .as_ref()
-        .expect("first full promotion telemetry");
-    assert!(full_promotion.previous_live_bytes.is_none());
-    assert!(full_promotion.rollback_backup_copy_ms.is_none());
-    assert!(full_promotion.backup_validation_ms.is_none());
-    assert!(full_promotion.rollback_backup_bytes.is_none());
-    assert!(full_promotion.candidate_bytes > 0);
+}
+
+#[test]
+fn successful_indexing_archives_snapshots_within_retention() {
+    let workspace = tempdir().expect("workspace dir");
+    fs::write(workspace.path().join("lib.rs"), "pub fn archived() {}\n").expect("write lib");
+    let storage_path = workspace.path().join(".cache").join("codestory.db");
+    let controller = AppController::new();
+    controller
+        .open_project_summary_with_storage_path(
+            workspace.path().to_path_buf(),
+            storage_path.clone(),
+        )
+        .expect("open project");
+    controller
+        .run_indexing_blocking(IndexMode::Full)
+        .expect("index without archival");
+    assert!(
+        controller
+            .list_index_snapshots()
+            .expect("list snapshots")
+            .is_empty()
+    );
+
+    controller.set_snapshot_retention(2);
+    for _ in 0..3 {
+        controller
+            .run_indexing_blocking(IndexMode::Full)
+            .expect("index with archival");
+    }
+    let snapshots = controller.list_index_snapshots().expect("list snapshots");
+    assert_eq!(
+        snapshots.len(),
+        2,
+        "oldest snapshot is pruned: {snapshots:?}"
+    );
+    assert!(snapshots[0].created_at_epoch_ms > snapshots[1].created_at_epoch_ms);
+    assert!(snapshots.iter().all(|snapshot| snapshot.size_bytes > 0));
+    assert!(
+        Path::new(&snapshots[0].path)
+            .starts_with(workspace.path().join(".cache").join("snapshots"))
+    );
+
+    fs::write(workspace.path().join("lib.rs"), "pub fn refactored() {}\n").expect("edit lib");
+    controller
+        .run_indexing_blocking(IndexMode::Full)
+        .expect("index after refactor");
+    let archived_name = controller.list_index_snapshots().expect("list snapshots")[1]
+        .name
+        .clone();
+    let archived = controller
+        .open_index_snapshot(&archived_name)
+        .expect("open snapshot");
+    let archived_roots = archived
+        .list_root_symbols(ListRootSymbolsRequest { limit: None })
+        .expect("browse the snapshot although the workspace moved on")
+        .into_iter()
+        .map(|symbol| symbol.label)
+        .collect::<Vec<_>>();
+    assert!(
+        archived_roots
+            .iter()
+            .any(|label| label.contains("archived")),
+        "{archived_roots:?}"
+    );
+    assert!(
+        !archived_roots
+            .iter()
+            .any(|label| label.contains("refactored"))
+    );
+    assert_eq!(
+        controller.state.lock().storage_path.as_deref(),
+        Some(storage_path.as_path())
+    );
+    let err = controller
+        .open_index_snapshot("../codestory.db")
+        .expect_err("path escapes are rejected");
+    assert_eq!(err.code, "invalid_argument");
+}
+
+#[test]
+fn successful_indexing_writes_index_manifest_and_warns_on_incompatible_reopen() {
+    let workspace = tempdir().expect("workspace dir");
+    fs::write(workspace.path().join("lib.rs"), "pub fn manifest() {}\n").expect("write lib");
+    let storage_path = workspace.path().join(".cache").join("codestory.db");
+    let controller = AppController::new();
+    controller
+        .open_project_summary_with_storage_path(
+            workspace.path().to_path_buf(),
+            storage_path.clone(),
+        )
+        .expect("open project");
+    assert_eq!(controller.index_manifest().expect("read manifest"), None);
+    controller
+        .run_indexing_blocking(IndexMode::Full)
+        .expect("index project");
+
+    let manifest = controller
+        .index_manifest()
+        .expect("read manifest")
+        .expect("manifest written");
+    assert_eq!(manifest.tool_version, env!("CARGO_PKG_VERSION"));
+    assert_eq!(
+        manifest.schema_version,
+        codestory_store::CURRENT_SCHEMA_VERSION
+    );
+    assert_eq!(manifest.mode, IndexMode::Full);
+    assert_eq!(manifest.file_count, 1);
+    assert!(manifest.grammar_versions.contains_key("rust"));
+    assert_eq!(manifest.settings_hash.len(), 64);
+    let manifest_path = workspace
+        .path()
+        .join(".cache")
+        .join("codestory.index_manifest.json");
+    assert!(manifest_path.is_file());
+
+    let events = controller.events();
+    while events.try_recv().is_ok() {}
+    controller
+        .open_project_summary_with_storage_path(
+            workspace.path().to_path_buf(),
+            storage_path.clone(),
+        )
+        .expect("reopen project");
+    assert!(!events.try_iter().any(|event| matches!(
+        event,
+        AppEventPayload::StatusUpdate { ref message } if message.starts_with("Reindex recommended")
+    )));
+
+    let mut older = manifest;
+    older.schema_version -= 1;
+    fs::write(
+        &manifest_path,
+        serde_json::to_vec(&older).expect("encode manifest"),
+    )
+    .expect("rewrite manifest");
+    controller
+        .open_project_summary_with_storage_path(workspace.path().to_path_buf(), storage_path)
+        .expect("reopen project");
+    assert!(events.try_iter().any(|event| matches!(
+        event,
+        AppEventPayload::StatusUpdate { ref message } if message.starts_with("Reindex recommended")
+    )));
+}
+
+#[test]
+fn indexing_completes_on_a_single_thread_pool() {
+    let workspace = tempdir().expect("workspace dir");
+    fs::write(
+        workspace.path().join("lib.rs"),
+        "pub fn alpha() { beta(); }\npub fn beta() {}\n",
+    )
+    .expect("write lib");
+    let controller = AppController::new();
+    controller
+        .open_project_summary_with_storage_path(
+            workspace.path().to_path_buf(),
+            workspace.path().join(".cache").join("codestory.db"),
+        )
+        .expect("open project");
+    controller.set_indexing_max_threads(Some(1));
+
+    let delta = controller
+        .run_indexing_blocking(IndexMode::Full)
+        .expect("index on one thread")
+        .run_delta
+        .expect("full run delta");
+    assert_eq!(delta.files_added, 1);
+    assert!(
+        delta.nodes_added >= 3 && delta.edges_added >= 1,
+        "{delta:?}"
+    );
+}
+
+#[test]
+fn buffer_overlay_replaces_published_symbols_in_autocomplete_until_cleared() {
+    let workspace = tempdir().expect("workspace dir");
+    fs::write(workspace.path().join("lib.rs"), "pub fn alpha_saved() {}\n").expect("write lib");
+    let storage_path = workspace.path().join(".cache").join("codestory.db");
+    let controller = AppController::new();
+    controller
+        .open_project_summary_with_storage_path(
+            workspace.path().to_path_buf(),
+            storage_path.clone(),
+        )
+        .expect("open project");
+    controller
+        .run_indexing_blocking(IndexMode::Full)
+        .expect("index baseline");
+    let labels = |controller: &AppController| {
+        controller
+            .autocomplete("alpha", 10)
+            .expect("autocomplete")
+            .into_iter()
+            .map(|suggestion| suggestion.label)
+            .collect::<Vec<_>>()
+    };
+    assert!(
+        labels(&controller)
+            .iter()
+            .any(|label| label.contains("alpha_saved"))
+    );
+
+    let symbols = controller
+        .set_overlay("lib.rs", "pub fn alpha_unsaved() {}\n")
+        .expect("set overlay");
+    assert!(symbols >= 1);
+    let overlaid = labels(&controller);
+    assert!(overlaid.iter().any(|label| label.contains("alpha_unsaved")));
+    assert!(
+        !overlaid.iter().any(|label| label.contains("alpha_saved")),
+        "the overlay hides the published symbols of its file: {overlaid:?}"
+    );
+    assert!(
+        fs::read_to_string(workspace.path().join("lib.rs"))
+            .expect("read lib")
+            .contains("alpha_saved"),
+        "overlays never touch the file on disk"
+    );
+
+    assert!(controller.clear_overlay("lib.rs").expect("clear overlay"));
+    assert!(!controller.clear_overlay("lib.rs").expect("clear again"));
+    let restored = labels(&controller);
+    assert!(restored.iter().any(|label| label.contains("alpha_saved")));
+    assert!(!restored.iter().any(|label| label.contains("alpha_unsaved")));
+}
+
+#[test]
+fn open_files_are_scheduled_first_and_must_stay_inside_the_project() {
+    let workspace = tempdir().expect("workspace dir");
+    for name in ["a.rs", "b.rs", "c.rs"] {
+        fs::write(workspace.path().join(name), "pub fn f() {}\n").expect("write source");
+    }
+    let controller = AppController::new();
+    controller
+        .open_project_summary_with_storage_path(
+            workspace.path().to_path_buf(),
+            workspace.path().join(".cache").join("codestory.db"),
+        )
+        .expect("open project");
+
+    controller
+        .set_open_files(&["c.rs".to_string(), "./c.rs".to_string(), "b.rs".to_string()])
+        .expect("set open files");
+    let open_files = controller.state.lock().open_files.clone();
+    assert_eq!(
+        open_files,
+        vec![PathBuf::from("c.rs"), PathBuf::from("b.rs")]
+    );
+
+    let root = workspace.path().canonicalize().expect("canonical root");
+    let mut plan = RefreshExecutionPlan {
+        mode: RefreshMode::FullRefresh,
+        files_to_index: ["a.rs", "b.rs", "c.rs"]
+            .map(|name| root.join(name))
+            .to_vec(),
+        files_to_remove: Vec::new(),
+        existing_file_ids: HashMap::new(),
+    };
+    prioritize_open_files(&mut plan, workspace.path(), &open_files);
+    assert_eq!(
+        plan.files_to_index,
+        ["c.rs", "b.rs", "a.rs"]
+            .map(|name| root.join(name))
+            .to_vec()
+    );
+
+    let error = controller
+        .set_open_files(&["../outside.rs".to_string()])
+        .expect_err("paths outside the project are rejected");
+    assert_eq!(error.code, "invalid_argument");
+    assert_eq!(
+        controller.state.lock().open_files,
+        open_files,
+        "a rejected list leaves the open files as they were"
+    );
+}
+
+#[test]
+fn buffer_overlay_replaces_published_symbols_in_symbol_lookup() {
+    let workspace = tempdir().expect("workspace dir");
+    fs::write(workspace.path().join("lib.rs"), "pub fn alpha_saved() {}\n").expect("write lib");
+    let storage_path = workspace.path().join(".cache").join("codestory.db");
+    let controller = AppController::new();
+    controller
+        .open_project_summary_with_storage_path(
+            workspace.path().to_path_buf(),
+            storage_path.clone(),
+        )
+        .expect("open project");
+    controller
+        .run_indexing_blocking(IndexMode::Full)
+        .expect("index baseline");
+    let names = |controller: &AppController| {
+        controller
+            .resolve_indexed_symbol_candidates("alpha", 10)
+            .expect("symbol lookup")
+            .into_iter()
+            .map(|hit| (hit.display_name, hit.resolvable))
+            .collect::<Vec<_>>()
+    };
+
+    controller
+        .set_overlay("lib.rs", "pub fn alpha_unsaved() {}\n")
+        .expect("set overlay");
+    let overlaid = names(&controller);
+    assert!(
+        overlaid
+            .iter()
+            .any(|(name, resolvable)| name.contains("alpha_unsaved") && !resolvable),
+        "unsaved symbols are listed but not resolvable: {overlaid:?}"
+    );
+    assert!(
+        !overlaid
+            .iter()
+            .any(|(name, _)| name.contains("alpha_saved"))
+    );
+
+    controller.clear_overlay("lib.rs").expect("clear overlay");
+    let restored = names(&controller);
+    assert!(
+        restored
+            .iter()
+            .any(|(name, _)| name.contains("alpha_saved"))
+    );
+    assert!(
+        !restored
+            .iter()
+            .any(|(name, _)| name.contains("alpha_unsaved"))
+    );
+}
+
+#[test]
+fn indexing_queue_coalesces_by_mode_and_pops_highest_priority_first() {
+    let queued = |mode, priority| QueuedIndexing { mode, priority };
+    let mut queue = IndexingQueue::default();
+    queue.push(queued(IndexMode::Full, IndexingPriority::Rebuild));
+    queue.push(queued(IndexMode::Incremental, IndexingPriority::Watcher));
+    let (entry, pending) = queue.push(queued(
+        IndexMode::Incremental,
+        IndexingPriority::Interactive,
+    ));
+    assert_eq!(
+        (entry, pending),
+        (
+            queued(IndexMode::Incremental, IndexingPriority::Interactive),
+            2
+        )
+    );
+    let (entry, _) = queue.push(queued(IndexMode::Incremental, IndexingPriority::Rebuild));
+    assert_eq!(entry.priority, IndexingPriority::Interactive);
+
+    assert_eq!(
+        queue.pop(),
+        Some(queued(
+            IndexMode::Incremental,
+            IndexingPriority::Interactive
+        ))
+    );
+    assert_eq!(
+        queue.pop(),
+        Some(queued(IndexMode::Full, IndexingPriority::Rebuild))
+    );
+    assert_eq!(queue.pop(), None);
+
+    queue.push(queued(IndexMode::Incremental, IndexingPriority::Watcher));
+    queue.push(queued(IndexMode::Full, IndexingPriority::Watcher));
+    assert_eq!(
+        queue.pop().map(|entry| entry.mode),
+        Some(IndexMode::Incremental)
+    );
+}
+
+#[test]
+fn start_indexing_queues_requests_while_a_run_is_active() {
+    let workspace = tempdir().expect("workspace dir");
+    fs::write(
+        workspace.path().join("lib.rs"),
+        "pub fn value() -> i32 { 1 }\n",
+    )
+    .expect("write source");
+    let storage_path = workspace.path().join(".cache").join("codestory.db");
+    let controller = AppController::new();
+    controller
+        .open_project_summary_with_storage_path(
+            workspace.path().to_path_buf(),
+            storage_path.clone(),
+        )
+        .expect("open project");
+    controller
+        .run_indexing_blocking_without_runtime_refresh(IndexMode::Full)
+        .expect("publish compatible baseline");
+    let events = controller.events();
+    while events.try_recv().is_ok() {}
+
+    controller.state.lock().is_indexing = true;
+    for (mode, priority) in [
+        (IndexMode::Full, None),
+        (IndexMode::Incremental, None),
+        (IndexMode::Incremental, Some(IndexingPriority::Interactive)),
+    ] {
+        controller
+            .start_indexing(StartIndexingRequest {
+                mode,
+                priority,
+                max_threads: None,
+            })
+            .expect("queue while indexing");
+    }
+    let queued_events = events
+        .try_iter()
+        .filter_map(|event| match event {
+            AppEventPayload::IndexingQueued {
+                mode,
+                priority,
+                pending,
+            } => Some((mode, priority, pending)),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        queued_events,
+        vec![
+            (IndexMode::Full, IndexingPriority::Rebuild, 1),
+            (IndexMode::Incremental, IndexingPriority::Watcher, 2),
+            (IndexMode::Incremental, IndexingPriority::Interactive, 2),
+        ]
+    );
+    assert!(
+        controller.claim_queued_indexing().is_none(),
+        "queued runs wait for the active run"
+    );
+
+    controller.state.lock().is_indexing = false;
+    let (_, claimed_storage, mode) = controller
+        .claim_queued_indexing()
+        .expect("interactive run is claimed first");
+    assert_eq!(mode, IndexMode::Incremental);
+    assert_eq!(claimed_storage, storage_path);
+    assert!(controller.state.lock().is_indexing);
+    assert!(controller.claim_queued_indexing().is_none());
+
+    controller.state.lock().is_indexing = false;
+    assert_eq!(
+        controller.claim_queued_indexing().map(|(_, _, mode)| mode),
+        Some(IndexMode::Full)
+    );
+    controller.state.lock().is_indexing = false;
+    assert!(controller.claim_queued_indexing().is_none());
+}
+
+#[test]
+fn empty_full_refresh_reports_adaptive_chunk_config() {
+    let workspace = tempdir().expect("workspace dir");
+    let storage_path = workspace.path().join(".cache").join("codestory.db");
+    let controller = AppController::new();
+    controller
+        .open_project_summary_with_storage_path(workspace.path().to_path_buf(), storage_path)
+        .expect("open project");
+
+    let timings = controller
+        .run_indexing_blocking_without_runtime_refresh(IndexMode::Full)
+        .expect("empty full publication");
+
+    assert_eq!(
+        timings.full_refresh_chunk_target_bytes,
+        Some(8 * 1024 * 1024)
+    );
+    assert_eq!(timings.full_refresh_chunk_target_nodes, Some(120_000));
+    assert_eq!(timings.full_refresh_chunk_file_ceiling, Some(512));
+    assert_eq!(timings.full_refresh_chunk_max_files, Some(0));
+    assert_eq!(timings.full_refresh_chunk_max_planned_bytes, Some(0));
+    assert_eq!(timings.full_refresh_chunk_max_nodes, Some(0));
+    assert_eq!(timings.full_refresh_chunk_budget_overruns, Some(0));
+    assert!(timings.full_refresh_chunk_planning_ms.is_some());
+}
+
+#[test]
+fn full_and_incremental_publications_advance_one_durable_generation() {
+    let assert_promotion_reconciles = |promotion: &CorePromotionTimings| {
+        let named_ms = promotion
+            .lock_recovery_ms
+            .saturating_add(promotion.candidate_validation_ms)
+            .saturating_add(promotion.previous_validation_ms)
+            .saturating_add(promotion.rollback_backup_copy_ms.unwrap_or_default())
+            .saturating_add(promotion.backup_validation_ms.unwrap_or_default())
+            .saturating_add(promotion.prepared_journal_write_ms)
+            .saturating_add(promotion.prepared_journal_file_sync_ms)
+            .saturating_add(promotion.prepared_journal_directory_sync_ms)
+            .saturating_add(promotion.staged_to_live_restore_ms)
+            .saturating_add(promotion.promoted_validation_ms)
+            .saturating_add(promotion.committed_journal_ms)
+            .saturating_add(promotion.cleanup_ms);
+        assert_eq!(
+            named_ms.saturating_add(promotion.unattributed_ms),
+            promotion.total_ms
+        );
+    };
+    let workspace = tempdir().expect("workspace dir");
+    fs::write(
+        workspace.path().join("lib.rs"),
+        "pub fn first_value() -> i32 { 1 }\n",
+    )
+    .expect("write initial source");
+    let storage_path = workspace.path().join(".cache").join("codestory.db");
+    let controller = AppController::new();
+    controller
+        .open_project_summary_with_storage_path(
+            workspace.path().to_path_buf(),
+            storage_path.clone(),
+        )
+        .expect("open project");
+
+    let full_timings = controller
+        .run_indexing_blocking_without_runtime_refresh(IndexMode::Full)
+        .expect("first full publication");
+    let parser_cache = full_timings
+        .parser_artifact_cache
+        .as_ref()
+        .expect("parser cache telemetry");
+    assert_eq!(parser_cache.policy, ArtifactCachePolicyDto::KnownEmpty);
+    assert_eq!(parser_cache.logical_lookups, 1);
+    assert_eq!(parser_cache.physical_queries, 0);
+    assert_eq!(parser_cache.hits, 0);
+    assert_eq!(parser_cache.misses, 1);
+    assert_eq!(parser_cache.reader_opens, 0);
+    assert_eq!(parser_cache.lookup_wall_ms, 0);
+    let structural_cache = full_timings
+        .structural_artifact_cache
+        .as_ref()
+        .expect("structural cache telemetry");
+    assert_eq!(structural_cache.policy, ArtifactCachePolicyDto::KnownEmpty);
+    assert_eq!(structural_cache.logical_lookups, 0);
+    assert_eq!(structural_cache.physical_queries, 0);
+    assert_eq!(structural_cache.reader_opens, 0);
+    assert_eq!(full_timings.full_refresh_queue_capacity, Some(1));
+    assert_eq!(full_timings.full_refresh_queue_high_water, Some(1));
+    assert_eq!(full_timings.full_refresh_chunks_produced, Some(1));
+    assert_eq!(full_timings.full_refresh_chunks_persisted, Some(1));
+    assert!(full_timings.full_refresh_producer_blocked_ms.is_some());
+    assert!(full_timings.full_refresh_writer_idle_ms.is_some());
+    assert_eq!(
+        full_timings.full_refresh_chunk_target_bytes,
+        Some(8 * 1024 * 1024)
+    );
+    assert_eq!(full_timings.full_refresh_chunk_target_nodes, Some(120_000));
+    assert_eq!(full_timings.full_refresh_chunk_file_ceiling, Some(512));
+    assert_eq!(full_timings.full_refresh_chunk_max_files, Some(1));
+    assert!(full_timings.full_refresh_chunk_max_planned_bytes.is_some());
+    assert!(full_timings.full_refresh_chunk_max_nodes.is_some());
+    assert_eq!(full_timings.full_refresh_chunk_budget_overruns, Some(0));
+    assert!(full_timings.full_refresh_chunk_planning_ms.is_some());
+    let full_projection = full_timings
+        .projection_persistence
+        .as_ref()
+        .expect("full projection persistence telemetry");
+    assert_eq!(
+        Some(full_projection.transactions),
+        full_timings.projection_batch_transactions
+    );
+    assert!(full_projection.row_attempts > 0);
+    assert!(full_projection.bound_bytes > 0);
+    assert!(full_projection.statement_executions > 0);
+    assert_eq!(
+        full_projection.dirty_state.statement_executions,
+        u64::from(full_projection.transactions) * 4
+    );
+    assert_eq!(
+        full_timings.staged_sqlite_wal_autocheckpoint_bytes,
+        Some(64 * 1024 * 1024)
+    );
+    assert!(full_timings.staged_sqlite_checkpoint_ms.is_some());
+    assert!(full_timings.staged_sqlite_sync_ms.is_some());
+    assert!(full_timings.staged_snapshot_copy.is_none());
+    let full_promotion = full_timings
+        .core_promotion
+        .as_ref()
+        .expect("first full promotion telemetry");
+    assert!(full_promotion.previous_live_bytes.is_none());
+    assert!(full_promotion.rollback_backup_copy_ms.is_none());
+    assert!(full_promotion.backup_validation_ms.is_none());
+    assert!(full_promotion.rollback_backup_bytes.is_none());
+    assert!(full_promotion.candidate_bytes > 0);
     assert_promotion_reconciles(full_promotion);
     assert_eq!(full_timings.search_projection_rebuild_ms, Some(0));
     assert!(full_timings.search_symbol_stream_ms.is_some());
@@ -7059,9 +7866,7 @@ fn successful_index_reopen_failure_does_not_leave_indexing_stuck() {
     {
         let mut state = controller.state.lock();
         state.is_indexing = true;
-        state
-            .node_names
-            .insert(CoreNodeId(999), "stale_symbol".to_string());
+        Arc::make_mut(&mut state.node_names).insert(CoreNodeId(999), "stale_symbol".to_string());
         let engine = SearchEngine::new(None).expect("search engine");
         publish_search_engine(&mut state, engine, None);
     }
@@ -8465,6 +9270,122 @@ fn list_root_symbols_deduplicates_repeated_entries() {
     assert!(roots.iter().any(|symbol| symbol.label == "\"./app/types\""));
 }
 
+#[test]
+fn list_root_symbols_pins_entry_point_bookmarks_first() {
+    let temp = tempdir().expect("create temp dir");
+    let db_path = temp.path().join("codestory.db");
+
+    {
+        let mut storage = Storage::open(&db_path).expect("open storage");
+        storage
+            .insert_nodes_batch(&[
+                Node {
+                    id: CoreNodeId(111),
+                    kind: NodeKind::MODULE,
+                    serialized_name: "alpha".to_string(),
+                    ..Default::default()
+                },
+                Node {
+                    id: CoreNodeId(112),
+                    kind: NodeKind::MODULE,
+                    serialized_name: "zeta".to_string(),
+                    ..Default::default()
+                },
+                Node {
+                    id: CoreNodeId(113),
+                    kind: NodeKind::MODULE,
+                    serialized_name: "omega".to_string(),
+                    ..Default::default()
+                },
+            ])
+            .expect("insert root nodes");
+    }
+
+    let controller = AppController::new();
+    controller
+        .open_project(OpenProjectRequest {
+            path: temp.path().to_string_lossy().to_string(),
+        })
+        .expect("open project");
+    let category = controller
+        .create_bookmark_category(codestory_contracts::api::CreateBookmarkCategoryRequest {
+            name: "start HERE".to_string(),
+        })
+        .expect("create entry point category");
+    for node_id in ["113", "112"] {
+        controller
+            .create_bookmark(codestory_contracts::api::CreateBookmarkRequest {
+                category_id: category.id.clone(),
+                node_id: NodeId(node_id.to_string()),
+                comment: None,
+            })
+            .expect("bookmark entry point");
+    }
+
+    let roots = controller
+        .list_root_symbols(ListRootSymbolsRequest { limit: None })
+        .expect("load roots");
+    let listed = roots
+        .iter()
+        .map(|symbol| (symbol.label.as_str(), symbol.entry_point))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        listed,
+        vec![("omega", true), ("zeta", true), ("alpha", false)]
+    );
+
+    let limited = controller
+        .list_root_symbols(ListRootSymbolsRequest { limit: Some(1) })
+        .expect("load limited roots");
+    assert_eq!(limited.len(), 1);
+    assert!(limited[0].entry_point);
+}
+
+#[test]
+fn open_project_returns_before_caches_and_reports_when_they_are_ready() {
+    let temp = tempdir().expect("create temp dir");
+    let db_path = temp.path().join("codestory.db");
+    {
+        let mut storage = Storage::open(&db_path).expect("open storage");
+        storage
+            .insert_nodes_batch(&[Node {
+                id: CoreNodeId(201),
+                kind: NodeKind::FUNCTION,
+                serialized_name: "warm_cache_target".to_string(),
+                ..Default::default()
+            }])
+            .expect("insert node");
+    }
+
+    let controller = AppController::new();
+    let events = controller.events();
+    let summary = controller
+        .open_project(OpenProjectRequest {
+            path: temp.path().to_string_lossy().to_string(),
+        })
+        .expect("open project");
+    assert_eq!(summary.stats.node_count, 1);
+
+    let ready = loop {
+        match events
+            .recv_timeout(Duration::from_secs(30))
+            .expect("cache warm-up event")
+        {
+            AppEventPayload::CachesReady { symbol_count, .. } => break symbol_count,
+            AppEventPayload::StatusUpdate { message } if message.contains("not ready") => {
+                panic!("cache warm-up failed: {message}")
+            }
+            _ => {}
+        }
+    };
+    assert_eq!(ready, 1);
+    assert!(controller.state.lock().search_engine.is_some());
+    let hits = controller
+        .resolve_indexed_symbol_candidates("warm_cache_target", 5)
+        .expect("search after warm-up");
+    assert_eq!(hits[0].display_name, "warm_cache_target");
+}
+
 #[test]
 fn graph_neighborhood_member_includes_owner_inheritance_edges() {
     let temp = tempdir().expect("create temp dir");
@@ -8537,12 +9458,245 @@ fn graph_neighborhood_member_includes_owner_inheritance_edges() {
     );
     assert!(
         graph.canonical_layout.is_some(),
-        "Expected canonical_layout on neighborhood response"
+        "Expected canonical_layout on neighborhood response"
+    );
+}
+
+/// Nodes 1 and 2 both call node 3, so node 3 is the most central.
+fn write_centrality_fixture(db_path: &Path) {
+    let mut storage = Storage::open(db_path).expect("open storage");
+    storage
+        .insert_nodes_batch(
+            &(1..=3)
+                .map(|id| Node {
+                    id: CoreNodeId(id),
+                    kind: NodeKind::FUNCTION,
+                    serialized_name: format!("f{id}"),
+                    ..Default::default()
+                })
+                .collect::<Vec<_>>(),
+        )
+        .expect("insert nodes");
+    storage
+        .insert_edges_batch(&[
+            Edge {
+                id: EdgeId(11),
+                source: CoreNodeId(1),
+                target: CoreNodeId(3),
+                kind: EdgeKind::CALL,
+                ..Default::default()
+            },
+            Edge {
+                id: EdgeId(12),
+                source: CoreNodeId(2),
+                target: CoreNodeId(3),
+                kind: EdgeKind::CALL,
+                ..Default::default()
+            },
+        ])
+        .expect("insert edges");
+    storage
+        .refresh_node_centrality(codestory_store::CENTRALITY_DEFAULT_ITERATIONS)
+        .expect("score centrality");
+}
+
+#[test]
+fn graph_nodes_carry_stored_centrality() {
+    let temp = tempdir().expect("create temp dir");
+    write_centrality_fixture(&temp.path().join("codestory.db"));
+    let controller = AppController::new();
+    controller
+        .open_project(OpenProjectRequest {
+            path: temp.path().to_string_lossy().to_string(),
+        })
+        .expect("open project");
+
+    let graph = controller
+        .graph_neighborhood(GraphRequest {
+            center_id: codestory_contracts::api::NodeId("3".to_string()),
+            max_edges: None,
+        })
+        .expect("load graph neighborhood");
+    let centrality = |id: &str| {
+        graph
+            .nodes
+            .iter()
+            .find(|node| node.id.0 == id)
+            .and_then(|node| node.centrality)
+            .expect("scored node")
+    };
+    assert!(centrality("3") > centrality("1"));
+    assert!((centrality("1") - centrality("2")).abs() < 1e-6);
+
+    assert_eq!(
+        controller
+            .centrality_iterations()
+            .expect("default iterations"),
+        codestory_store::CENTRALITY_DEFAULT_ITERATIONS
+    );
+    assert_eq!(
+        controller
+            .set_centrality_iterations(40)
+            .expect("save iterations"),
+        40
+    );
+    assert_eq!(controller.centrality_iterations().expect("iterations"), 40);
+    let rejected = controller
+        .set_centrality_iterations(0)
+        .expect_err("zero iterations");
+    assert_eq!(rejected.code, "invalid_argument");
+}
+
+#[test]
+fn centrality_boost_raises_central_hits_by_a_bounded_share() {
+    let temp = tempdir().expect("create temp dir");
+    let db_path = temp.path().join("codestory.db");
+    write_centrality_fixture(&db_path);
+    let storage = Storage::open(&db_path).expect("open storage");
+    let scored = |id: &str| crate::HybridSearchScoredHit {
+        hit: SearchHit {
+            score_breakdown: Some(codestory_contracts::api::RetrievalScoreBreakdownDto {
+                lexical: 1.0,
+                semantic: 0.0,
+                graph: 0.0,
+                total: 1.0,
+                tier_cap: None,
+                boosts: Vec::new(),
+                dampening: Vec::new(),
+                final_rank_reason: None,
+                provenance: Vec::new(),
+            }),
+            ..search_plan_test_hit(
+                id,
+                &format!("f{id}"),
+                Path::new("src/lib.rs"),
+                1,
+                SearchHitOrigin::IndexedSymbol,
+                true,
+            )
+        },
+        lexical_score: 1.0,
+        semantic_score: 0.0,
+        graph_score: 0.0,
+        total_score: 1.0,
+    };
+    let mut hits = vec![scored("1"), scored("3"), scored("9")];
+
+    crate::search_scoring::apply_centrality_boost(&storage, &mut hits);
+
+    assert!(hits[1].total_score > hits[0].total_score);
+    assert!(hits[0].total_score > 1.0);
+    assert!(
+        (hits[1].total_score - 1.05).abs() < 1e-6,
+        "the most central hit gains the full boost: {}",
+        hits[1].total_score
+    );
+    assert_eq!(hits[1].hit.score, hits[1].total_score);
+    let breakdown = hits[1].hit.score_breakdown.as_ref().expect("breakdown");
+    assert_eq!(breakdown.total, hits[1].total_score);
+    assert!(breakdown.boosts[0].starts_with("centrality "));
+    assert_eq!(hits[2].total_score, 1.0, "unscored hits keep their score");
+    assert!(
+        hits[2]
+            .hit
+            .score_breakdown
+            .as_ref()
+            .unwrap()
+            .boosts
+            .is_empty()
+    );
+}
+
+#[test]
+fn graph_trail_includes_canonical_layout() {
+    let temp = tempdir().expect("create temp dir");
+    let db_path = temp.path().join("codestory.db");
+
+    {
+        let mut storage = Storage::open(&db_path).expect("open storage");
+        storage
+            .insert_nodes_batch(&[
+                Node {
+                    id: CoreNodeId(1),
+                    kind: NodeKind::CLASS,
+                    serialized_name: "Runner".to_string(),
+                    ..Default::default()
+                },
+                Node {
+                    id: CoreNodeId(2),
+                    kind: NodeKind::METHOD,
+                    serialized_name: "Runner::run".to_string(),
+                    ..Default::default()
+                },
+                Node {
+                    id: CoreNodeId(3),
+                    kind: NodeKind::METHOD,
+                    serialized_name: "Worker::execute".to_string(),
+                    ..Default::default()
+                },
+            ])
+            .expect("insert nodes");
+        storage
+            .insert_edges_batch(&[
+                Edge {
+                    id: EdgeId(11),
+                    source: CoreNodeId(1),
+                    target: CoreNodeId(2),
+                    kind: EdgeKind::MEMBER,
+                    ..Default::default()
+                },
+                Edge {
+                    id: EdgeId(12),
+                    source: CoreNodeId(2),
+                    target: CoreNodeId(3),
+                    kind: EdgeKind::CALL,
+                    ..Default::default()
+                },
+            ])
+            .expect("insert edges");
+    }
+
+    let controller = AppController::new();
+    controller
+        .open_project(OpenProjectRequest {
+            path: temp.path().to_string_lossy().to_string(),
+        })
+        .expect("open project");
+
+    let graph = controller
+        .graph_trail(TrailConfigDto {
+            root_id: codestory_contracts::api::NodeId("2".to_string()),
+            mode: codestory_contracts::api::TrailMode::Neighborhood,
+            target_id: None,
+            depth: Some(2),
+            direction: Some(codestory_contracts::api::TrailDirection::Both),
+            caller_scope: Some(codestory_contracts::api::TrailCallerScope::ProductionOnly),
+            edge_filter: vec![],
+            show_utility_calls: false,
+            hide_speculative: false,
+            story: false,
+            node_filter: vec![],
+            hide_trivial_accessors: false,
+            hide_generated: false,
+            entry_reachable_only: false,
+            max_paths: None,
+            edge_weight: Default::default(),
+            max_nodes: Some(128),
+            layout_direction: codestory_contracts::api::LayoutDirection::Horizontal,
+            graph_session: None,
+            timeout_ms: None,
+            filter_preset: None,
+        })
+        .expect("load graph trail");
+
+    assert!(
+        graph.canonical_layout.is_some(),
+        "Expected canonical_layout on trail response"
     );
 }
 
 #[test]
-fn graph_trail_includes_canonical_layout() {
+fn graph_trail_falls_back_to_project_trail_defaults() {
     let temp = tempdir().expect("create temp dir");
     let db_path = temp.path().join("codestory.db");
 
@@ -8596,28 +9750,180 @@ fn graph_trail_includes_canonical_layout() {
             path: temp.path().to_string_lossy().to_string(),
         })
         .expect("open project");
+    assert_eq!(
+        controller.trail_defaults().expect("load trail defaults"),
+        TrailDefaultsDto::default()
+    );
+    assert!(
+        controller
+            .set_trail_defaults(TrailDefaultsDto {
+                max_nodes: Some(5),
+                ..Default::default()
+            })
+            .is_err(),
+        "max_nodes below the trail clamp should be rejected"
+    );
 
-    let graph = controller
-        .graph_trail(TrailConfigDto {
+    let defaults = TrailDefaultsDto {
+        depth: Some(1),
+        direction: Some(codestory_contracts::api::TrailDirection::Outgoing),
+        caller_scope: None,
+        max_nodes: Some(40),
+        edge_filter: vec![codestory_contracts::api::EdgeKind::CALL],
+    };
+    controller
+        .set_trail_defaults(defaults.clone())
+        .expect("save trail defaults");
+    assert_eq!(
+        controller.trail_defaults().expect("load trail defaults"),
+        defaults
+    );
+
+    let context = controller
+        .trail_context(TrailConfigDto {
             root_id: codestory_contracts::api::NodeId("2".to_string()),
             mode: codestory_contracts::api::TrailMode::Neighborhood,
             target_id: None,
-            depth: 2,
-            direction: codestory_contracts::api::TrailDirection::Both,
-            caller_scope: codestory_contracts::api::TrailCallerScope::ProductionOnly,
+            depth: None,
+            direction: None,
+            caller_scope: None,
             edge_filter: vec![],
             show_utility_calls: false,
             hide_speculative: false,
-            story: false,
+            story: true,
             node_filter: vec![],
-            max_nodes: 128,
+            hide_trivial_accessors: false,
+            hide_generated: false,
+            entry_reachable_only: false,
+            max_paths: None,
+            edge_weight: Default::default(),
+            max_nodes: None,
             layout_direction: codestory_contracts::api::LayoutDirection::Horizontal,
+            graph_session: None,
+            timeout_ms: None,
+            filter_preset: None,
         })
-        .expect("load graph trail");
+        .expect("load trail with project defaults");
+    assert!(
+        context
+            .trail
+            .edges
+            .iter()
+            .any(|edge| edge.kind == codestory_contracts::api::EdgeKind::CALL)
+    );
+    assert!(
+        context
+            .trail
+            .edges
+            .iter()
+            .all(|edge| edge.kind != codestory_contracts::api::EdgeKind::MEMBER),
+        "the default edge filter should drop member edges"
+    );
+    let story = context.story.expect("story requested");
+    assert!(
+        story.summary.contains("direction=outgoing"),
+        "story should report the defaulted direction: {}",
+        story.summary
+    );
+
+    controller
+        .set_trail_defaults(TrailDefaultsDto::default())
+        .expect("reset trail defaults");
+    assert_eq!(
+        controller.trail_defaults().expect("load trail defaults"),
+        TrailDefaultsDto::default()
+    );
+}
+
+#[test]
+fn reachable_set_counts_reached_nodes_by_kind_and_file() {
+    let temp = tempdir().expect("create temp dir");
+    let db_path = temp.path().join("codestory.db");
+
+    {
+        let mut storage = Storage::open(&db_path).expect("open storage");
+        let function = |id, name: &str| Node {
+            id: CoreNodeId(id),
+            kind: NodeKind::FUNCTION,
+            serialized_name: name.to_string(),
+            file_node_id: Some(CoreNodeId(10)),
+            ..Default::default()
+        };
+        storage
+            .insert_nodes_batch(&[
+                Node {
+                    id: CoreNodeId(10),
+                    kind: NodeKind::FILE,
+                    serialized_name: "src/lib.rs".to_string(),
+                    ..Default::default()
+                },
+                function(1, "start"),
+                function(2, "step"),
+                function(3, "finish"),
+                function(4, "unrelated"),
+            ])
+            .expect("insert nodes");
+        let call = |id, source, target| Edge {
+            id: EdgeId(id),
+            source: CoreNodeId(source),
+            target: CoreNodeId(target),
+            kind: EdgeKind::CALL,
+            ..Default::default()
+        };
+        storage
+            .insert_edges_batch(&[call(21, 1, 2), call(22, 2, 3), call(23, 4, 1)])
+            .expect("insert edges");
+    }
+
+    let controller = AppController::new();
+    controller
+        .open_project(OpenProjectRequest {
+            path: temp.path().to_string_lossy().to_string(),
+        })
+        .expect("open project");
+    let request = |max_nodes| codestory_contracts::api::ReachableSetRequest {
+        id: codestory_contracts::api::NodeId("1".to_string()),
+        direction: codestory_contracts::api::TrailDirection::Outgoing,
+        edge_kinds: vec![codestory_contracts::api::EdgeKind::CALL],
+        max_nodes,
+    };
+
+    let reached = controller
+        .reachable_set(request(None))
+        .expect("reachable set");
+    let mut node_ids = reached
+        .node_ids
+        .iter()
+        .map(|id| id.0.as_str())
+        .collect::<Vec<_>>();
+    node_ids.sort_unstable();
+    assert_eq!(node_ids, vec!["2", "3"]);
+    assert_eq!(reached.max_depth, 2);
+    assert!(!reached.truncated);
+    assert_eq!(reached.kind_counts.len(), 1);
+    assert_eq!(
+        reached.kind_counts[0].kind,
+        codestory_contracts::api::NodeKind::FUNCTION
+    );
+    assert_eq!(reached.kind_counts[0].count, 2);
+    assert_eq!(reached.file_counts.len(), 1);
+    assert_eq!(reached.file_counts[0].path, "src/lib.rs");
+    assert_eq!(reached.file_counts[0].count, 2);
+
+    let capped = controller
+        .reachable_set(request(Some(1)))
+        .expect("capped reachable set");
+    assert_eq!(capped.node_ids.len(), 1);
+    assert!(capped.truncated);
 
     assert!(
-        graph.canonical_layout.is_some(),
-        "Expected canonical_layout on trail response"
+        controller
+            .reachable_set(codestory_contracts::api::ReachableSetRequest {
+                id: codestory_contracts::api::NodeId("not-a-node".to_string()),
+                ..request(None)
+            })
+            .is_err(),
+        "malformed ids are rejected"
     );
 }
 
@@ -8720,16 +10026,24 @@ fn graph_direct_references_returns_filtered_direct_incoming_edges() {
             root_id: codestory_contracts::api::NodeId("1".to_string()),
             mode: codestory_contracts::api::TrailMode::AllReferencing,
             target_id: None,
-            depth: 0,
-            direction: codestory_contracts::api::TrailDirection::Incoming,
-            caller_scope: codestory_contracts::api::TrailCallerScope::ProductionOnly,
+            depth: Some(0),
+            direction: Some(codestory_contracts::api::TrailDirection::Incoming),
+            caller_scope: Some(codestory_contracts::api::TrailCallerScope::ProductionOnly),
             edge_filter: vec![],
             show_utility_calls: false,
             hide_speculative: true,
             story: false,
             node_filter: vec![],
-            max_nodes: 10,
+            hide_trivial_accessors: false,
+            hide_generated: false,
+            entry_reachable_only: false,
+            max_paths: None,
+            edge_weight: Default::default(),
+            max_nodes: Some(10),
             layout_direction: codestory_contracts::api::LayoutDirection::Horizontal,
+            graph_session: None,
+            timeout_ms: None,
+            filter_preset: None,
         })
         .expect("load direct references");
 
@@ -8748,6 +10062,240 @@ fn graph_direct_references_returns_filtered_direct_incoming_edges() {
     assert!(graph.canonical_layout.is_none());
 }
 
+#[test]
+fn graph_direct_references_and_neighborhood_use_project_trail_defaults() {
+    let temp = tempdir().expect("create temp dir");
+    let db_path = temp.path().join("codestory.db");
+
+    {
+        let mut storage = Storage::open(&db_path).expect("open storage");
+        storage
+            .insert_nodes_batch(&[
+                Node {
+                    id: CoreNodeId(10),
+                    kind: NodeKind::FILE,
+                    serialized_name: "src/lib.rs".to_string(),
+                    ..Default::default()
+                },
+                Node {
+                    id: CoreNodeId(11),
+                    kind: NodeKind::FILE,
+                    serialized_name: "tests/lib_test.rs".to_string(),
+                    ..Default::default()
+                },
+                Node {
+                    id: CoreNodeId(1),
+                    kind: NodeKind::FUNCTION,
+                    serialized_name: "target".to_string(),
+                    file_node_id: Some(CoreNodeId(10)),
+                    ..Default::default()
+                },
+                Node {
+                    id: CoreNodeId(2),
+                    kind: NodeKind::FUNCTION,
+                    serialized_name: "prod_caller".to_string(),
+                    file_node_id: Some(CoreNodeId(10)),
+                    ..Default::default()
+                },
+                Node {
+                    id: CoreNodeId(3),
+                    kind: NodeKind::FUNCTION,
+                    serialized_name: "test_caller".to_string(),
+                    file_node_id: Some(CoreNodeId(11)),
+                    ..Default::default()
+                },
+                Node {
+                    id: CoreNodeId(4),
+                    kind: NodeKind::FUNCTION,
+                    serialized_name: "reader".to_string(),
+                    file_node_id: Some(CoreNodeId(10)),
+                    ..Default::default()
+                },
+            ])
+            .expect("insert nodes");
+        storage
+            .insert_edges_batch(&[
+                Edge {
+                    id: EdgeId(21),
+                    source: CoreNodeId(2),
+                    target: CoreNodeId(1),
+                    kind: EdgeKind::CALL,
+                    file_node_id: Some(CoreNodeId(10)),
+                    certainty: Some(ResolutionCertainty::Certain),
+                    ..Default::default()
+                },
+                Edge {
+                    id: EdgeId(22),
+                    source: CoreNodeId(3),
+                    target: CoreNodeId(1),
+                    kind: EdgeKind::CALL,
+                    file_node_id: Some(CoreNodeId(11)),
+                    certainty: Some(ResolutionCertainty::Certain),
+                    ..Default::default()
+                },
+                Edge {
+                    id: EdgeId(23),
+                    source: CoreNodeId(4),
+                    target: CoreNodeId(1),
+                    kind: EdgeKind::USAGE,
+                    file_node_id: Some(CoreNodeId(10)),
+                    certainty: Some(ResolutionCertainty::Certain),
+                    ..Default::default()
+                },
+            ])
+            .expect("insert edges");
+    }
+
+    let controller = AppController::new();
+    controller
+        .open_project(OpenProjectRequest {
+            path: temp.path().to_string_lossy().to_string(),
+        })
+        .expect("open project");
+    controller
+        .set_trail_defaults(codestory_contracts::api::TrailDefaultsDto {
+            caller_scope: Some(codestory_contracts::api::TrailCallerScope::IncludeTestsAndBenches),
+            edge_filter: vec![codestory_contracts::api::EdgeKind::CALL],
+            ..Default::default()
+        })
+        .expect("save trail defaults");
+
+    let references = controller
+        .graph_direct_references(TrailConfigDto {
+            root_id: codestory_contracts::api::NodeId("1".to_string()),
+            mode: codestory_contracts::api::TrailMode::AllReferencing,
+            target_id: None,
+            depth: None,
+            direction: None,
+            caller_scope: None,
+            edge_filter: vec![],
+            show_utility_calls: false,
+            hide_speculative: false,
+            story: false,
+            node_filter: vec![],
+            hide_trivial_accessors: false,
+            hide_generated: false,
+            entry_reachable_only: false,
+            max_paths: None,
+            edge_weight: Default::default(),
+            max_nodes: None,
+            layout_direction: codestory_contracts::api::LayoutDirection::Horizontal,
+            graph_session: None,
+            timeout_ms: None,
+            filter_preset: None,
+        })
+        .expect("load direct references");
+    let reference_sources = references
+        .edges
+        .iter()
+        .map(|edge| edge.source.0.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(reference_sources, vec!["2", "3"]);
+
+    let neighborhood = controller
+        .graph_neighborhood(GraphRequest {
+            center_id: codestory_contracts::api::NodeId("1".to_string()),
+            max_edges: None,
+        })
+        .expect("load neighborhood");
+    assert!(
+        neighborhood
+            .edges
+            .iter()
+            .all(|edge| edge.kind == codestory_contracts::api::EdgeKind::CALL),
+        "the default edge filter should drop the USAGE edge: {:?}",
+        neighborhood.edges
+    );
+    assert!(!neighborhood.nodes.iter().any(|node| node.id.0 == "4"));
+}
+
+#[test]
+fn graph_expand_returns_only_neighbors_and_edges_not_yet_shown() {
+    let temp = tempdir().expect("create temp dir");
+    let db_path = temp.path().join("codestory.db");
+
+    {
+        let mut storage = Storage::open(&db_path).expect("open storage");
+        let function = |id, name: &str| Node {
+            id: CoreNodeId(id),
+            kind: NodeKind::FUNCTION,
+            serialized_name: name.to_string(),
+            ..Default::default()
+        };
+        storage
+            .insert_nodes_batch(&[
+                function(1, "run"),
+                function(2, "helper"),
+                function(3, "worker"),
+                function(4, "main"),
+            ])
+            .expect("insert nodes");
+        let call = |id, source, target| Edge {
+            id: EdgeId(id),
+            source: CoreNodeId(source),
+            target: CoreNodeId(target),
+            kind: EdgeKind::CALL,
+            ..Default::default()
+        };
+        storage
+            .insert_edges_batch(&[call(21, 1, 2), call(22, 1, 3), call(23, 4, 1)])
+            .expect("insert edges");
+    }
+
+    let controller = AppController::new();
+    controller
+        .open_project(OpenProjectRequest {
+            path: temp.path().to_string_lossy().to_string(),
+        })
+        .expect("open project");
+
+    let graph = controller
+        .graph_expand(codestory_contracts::api::GraphExpandRequest {
+            node_id: codestory_contracts::api::NodeId("1".to_string()),
+            existing_node_ids: vec![
+                codestory_contracts::api::NodeId("1".to_string()),
+                codestory_contracts::api::NodeId("2".to_string()),
+            ],
+            existing_edge_ids: vec![codestory_contracts::api::EdgeId::from(EdgeId(21))],
+            direction: codestory_contracts::api::TrailDirection::Both,
+            caller_scope: codestory_contracts::api::TrailCallerScope::IncludeTestsAndBenches,
+            edge_filter: vec![],
+            node_filter: vec![],
+            show_utility_calls: true,
+            hide_speculative: false,
+            hide_trivial_accessors: false,
+            hide_generated: false,
+            max_nodes: 50,
+            graph_session: None,
+        })
+        .expect("expand node");
+
+    let mut node_ids = graph
+        .nodes
+        .iter()
+        .map(|node| node.id.0.as_str())
+        .collect::<Vec<_>>();
+    node_ids.sort_unstable();
+    assert_eq!(node_ids, vec!["3", "4"]);
+    let mut edges = graph
+        .edges
+        .iter()
+        .map(|edge| (edge.source.0.as_str(), edge.target.0.as_str()))
+        .collect::<Vec<_>>();
+    edges.sort_unstable();
+    assert_eq!(edges, vec![("1", "3"), ("4", "1")]);
+    let worker = graph
+        .nodes
+        .iter()
+        .find(|node| node.id.0 == "3")
+        .expect("worker node");
+    assert_eq!(
+        worker.edge_counts.map(|counts| counts.incoming_call_count),
+        Some(1)
+    );
+    assert!(graph.canonical_layout.is_none());
+}
+
 #[test]
 fn high_fanout_graph_trail_reports_truncation_at_max_nodes() {
     let temp = tempdir().expect("create temp dir");
@@ -8793,16 +10341,24 @@ fn high_fanout_graph_trail_reports_truncation_at_max_nodes() {
             root_id: codestory_contracts::api::NodeId("1".to_string()),
             mode: codestory_contracts::api::TrailMode::Neighborhood,
             target_id: None,
-            depth: 1,
-            direction: codestory_contracts::api::TrailDirection::Outgoing,
-            caller_scope: codestory_contracts::api::TrailCallerScope::ProductionOnly,
+            depth: Some(1),
+            direction: Some(codestory_contracts::api::TrailDirection::Outgoing),
+            caller_scope: Some(codestory_contracts::api::TrailCallerScope::ProductionOnly),
             edge_filter: vec![],
             show_utility_calls: true,
             hide_speculative: false,
             story: false,
             node_filter: vec![],
-            max_nodes: 10,
+            hide_trivial_accessors: false,
+            hide_generated: false,
+            entry_reachable_only: false,
+            max_paths: None,
+            edge_weight: Default::default(),
+            max_nodes: Some(10),
             layout_direction: codestory_contracts::api::LayoutDirection::Horizontal,
+            graph_session: None,
+            timeout_ms: None,
+            filter_preset: None,
         })
         .expect("load high fanout trail");
 
@@ -2097,6 +2097,8 @@ fn dedicated_openapi_coverage_requires_authenticated_file_owned_projection_evide
                 end_line: 1,
                 end_col: 2,
             },
+            edge_id: None,
+            notebook_cell: None,
         }])
         .expect("insert wrong-language definition occurrence");
     assert!(
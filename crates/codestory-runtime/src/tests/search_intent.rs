@@ -1,7 +1,8 @@
 use super::{
     Path, SearchHit, SearchIntentFilter, apply_search_intent_filters, extract_symbol_search_terms,
     indexed_file_matches_language_filter, language_filter_matches_path,
-    mixed_natural_language_query, parse_search_intent_query, should_expand_symbol_query,
+    mixed_natural_language_query, parse_search_intent_query, scoped_search_candidate_limit,
+    should_expand_symbol_query,
 };
 
 #[test]
@@ -26,6 +27,24 @@ fn parse_search_intent_query_extracts_supported_filters() {
     assert!(unknown_prefix.filters.is_empty());
 }
 
+#[test]
+fn scoped_search_keeps_fuzzy_remainder_and_widens_candidate_pool() {
+    let parsed = parse_search_intent_query("kind:function file:src/server lang:rust handler");
+
+    assert_eq!(parsed.effective_query, "handler");
+    assert_eq!(
+        parsed.filters,
+        vec![
+            SearchIntentFilter::Kind("function".to_string()),
+            SearchIntentFilter::Path("src/server".to_string()),
+            SearchIntentFilter::Language("rust".to_string()),
+        ]
+    );
+    assert_eq!(scoped_search_candidate_limit(10, &[]), 10);
+    assert_eq!(scoped_search_candidate_limit(10, &parsed.filters), 40);
+    assert_eq!(scoped_search_candidate_limit(50, &parsed.filters), 200);
+}
+
 #[test]
 fn search_intent_filters_hits_by_kind_path_name_and_language() {
     fn hit(
@@ -53,6 +72,7 @@ fn search_intent_filters_hits_by_kind_path_name_and_language() {
             source_excerpt: None,
             verification_targets: Vec::new(),
             score_breakdown: None,
+            highlight: None,
         }
     }
 
@@ -109,6 +109,8 @@ fn build_search_hit_prefers_declaration_coordinates_and_filters_unknown_nodes()
                 end_line: 87,
                 end_col: 20,
             },
+            edge_id: None,
+            notebook_cell: None,
         }])
         .expect("insert occurrences");
 
@@ -661,6 +663,7 @@ fn compare_search_hits_prefers_function_over_method_for_equal_symbol_matches() {
         source_excerpt: None,
         verification_targets: Vec::new(),
         score_breakdown: None,
+        highlight: None,
     };
     let method = SearchHit {
         node_id: NodeId("method".to_string()),
@@ -681,6 +684,7 @@ fn compare_search_hits_prefers_function_over_method_for_equal_symbol_matches() {
         source_excerpt: None,
         verification_targets: Vec::new(),
         score_breakdown: None,
+        highlight: None,
     };
 
     let mut hits = [method, function.clone()];
@@ -1603,6 +1607,7 @@ fn merge_search_hits_by_node_id_keeps_stronger_expanded_score() {
             source_excerpt: None,
             verification_targets: Vec::new(),
             score_breakdown: None,
+            highlight: None,
         },
         SearchHit {
             node_id: NodeId("secondary".to_string()),
@@ -1623,6 +1628,7 @@ fn merge_search_hits_by_node_id_keeps_stronger_expanded_score() {
             source_excerpt: None,
             verification_targets: Vec::new(),
             score_breakdown: None,
+            highlight: None,
         },
     ];
 
@@ -1647,6 +1653,7 @@ fn merge_search_hits_by_node_id_keeps_stronger_expanded_score() {
             source_excerpt: None,
             verification_targets: Vec::new(),
             score_breakdown: None,
+            highlight: None,
         }],
     );
 
@@ -1686,6 +1693,7 @@ fn inexact_search_results_deduplicate_repeated_display_keys() {
             source_excerpt: None,
             verification_targets: Vec::new(),
             score_breakdown: None,
+            highlight: None,
         },
         SearchHit {
             node_id: NodeId("embedding-engine-id-copy".to_string()),
@@ -1706,6 +1714,7 @@ fn inexact_search_results_deduplicate_repeated_display_keys() {
             source_excerpt: None,
             verification_targets: Vec::new(),
             score_breakdown: None,
+            highlight: None,
         },
         SearchHit {
             node_id: NodeId("other-helper".to_string()),
@@ -1726,6 +1735,7 @@ fn inexact_search_results_deduplicate_repeated_display_keys() {
             source_excerpt: None,
             verification_targets: Vec::new(),
             score_breakdown: None,
+            highlight: None,
         },
     ];
 
@@ -1768,6 +1778,7 @@ fn exact_search_results_keep_repeated_display_keys() {
             source_excerpt: None,
             verification_targets: Vec::new(),
             score_breakdown: None,
+            highlight: None,
         },
         SearchHit {
             node_id: NodeId("embedding-engine-id-copy".to_string()),
@@ -1788,6 +1799,7 @@ fn exact_search_results_keep_repeated_display_keys() {
             source_excerpt: None,
             verification_targets: Vec::new(),
             score_breakdown: None,
+            highlight: None,
         },
     ];
 
@@ -0,0 +1,226 @@
+use codestory_contracts::api::GraphResponse;
+
+/// Render a trail as a single HTML document with no external assets.
+///
+/// The graph is embedded as JSON and drawn by a small inline viewer: nodes are
+/// placed on the canonical layout ranks when present (trail depth otherwise),
+/// hovering highlights incident edges, clicking shows node details, and the
+/// filter box dims nodes whose label or path does not match.
+pub(crate) fn render_trail_html(title: &str, graph: &GraphResponse) -> serde_json::Result<String> {
+    let data = escape_script_json(&serde_json::to_string(graph)?);
+    let title = escape_html(title);
+    Ok(format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>{title}</title>\n<style>{TRAIL_HTML_STYLE}</style>\n</head>\n<body>\n\
+         <header><h1>{title}</h1><input id=\"filter\" type=\"search\" placeholder=\"Filter nodes\">\
+         <span id=\"summary\"></span></header>\n\
+         <main><svg id=\"graph\" xmlns=\"http://www.w3.org/2000/svg\"></svg>\
+         <aside id=\"details\">Select a node to see its details.</aside></main>\n\
+         <script type=\"application/json\" id=\"trail-data\">{data}</script>\n\
+         <script>{TRAIL_HTML_SCRIPT}</script>\n</body>\n</html>\n"
+    ))
+}
+
+/// Keep embedded JSON from closing the surrounding `<script>` element.
+fn escape_script_json(json: &str) -> String {
+    json.replace('<', "\\u003c")
+        .replace('>', "\\u003e")
+        .replace('&', "\\u0026")
+}
+
+fn escape_html(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    for ch in input.chars() {
+        match ch {
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '&' => out.push_str("&amp;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+const TRAIL_HTML_STYLE: &str = "\
+body{margin:0;font:13px system-ui,sans-serif;color:#1f2328;background:#f6f8fa}\
+header{display:flex;gap:12px;align-items:center;padding:8px 16px;background:#fff;border-bottom:1px solid #d0d7de}\
+h1{font-size:15px;margin:0}#summary{color:#656d76}\
+main{display:flex;height:calc(100vh - 50px)}\
+#graph{flex:1;background:#fff;cursor:grab}\
+#details{width:280px;padding:12px;overflow:auto;border-left:1px solid #d0d7de;white-space:pre-wrap}\
+.node rect{fill:#ddf4ff;stroke:#54aeff;rx:6}.node.center rect{fill:#fff8c5;stroke:#d4a72c}\
+.node text{font-size:12px;pointer-events:none}.node.dim{opacity:.2}\
+.edge{stroke:#8c959f;fill:none}.edge.hot{stroke:#cf222e;stroke-width:2}\
+.edge.uncertain{stroke-dasharray:4 3}";
+
+const TRAIL_HTML_SCRIPT: &str = r#"
+(() => {
+  const graph = JSON.parse(document.getElementById("trail-data").textContent);
+  const svgNs = "http://www.w3.org/2000/svg";
+  const svg = document.getElementById("graph");
+  const details = document.getElementById("details");
+  const layout = new Map();
+  for (const node of (graph.canonical_layout && graph.canonical_layout.nodes) || []) {
+    layout.set(node.id, { col: node.x_rank, row: node.y_rank });
+  }
+  const rows = new Map();
+  const pos = new Map();
+  for (const node of graph.nodes) {
+    let slot = layout.get(node.id);
+    if (!slot) {
+      const row = rows.get(node.depth) || 0;
+      rows.set(node.depth, row + 1);
+      slot = { col: node.depth, row };
+    }
+    pos.set(node.id, { x: 40 + slot.col * 240, y: 40 + slot.row * 56 });
+  }
+  const minX = Math.min(0, ...[...pos.values()].map((p) => p.x - 40));
+  for (const p of pos.values()) p.x -= minX;
+  const edgeEls = [];
+  const edgesRoot = document.createElementNS(svgNs, "g");
+  const nodesRoot = document.createElementNS(svgNs, "g");
+  svg.append(edgesRoot, nodesRoot);
+  for (const edge of graph.edges) {
+    const a = pos.get(edge.source);
+    const b = pos.get(edge.target);
+    if (!a || !b) continue;
+    const path = document.createElementNS(svgNs, "path");
+    const mid = (a.x + 180 + b.x) / 2;
+    path.setAttribute("d", `M${a.x + 180},${a.y + 16} C${mid},${a.y + 16} ${mid},${b.y + 16} ${b.x},${b.y + 16}`);
+    const uncertain = edge.certainty === "uncertain" || edge.resolution === "unresolved";
+    path.setAttribute("class", "edge" + (uncertain ? " uncertain" : ""));
+    path.appendChild(document.createElementNS(svgNs, "title")).textContent = edge.kind;
+    edgesRoot.appendChild(path);
+    edgeEls.push({ edge, path });
+  }
+  const nodeEls = [];
+  for (const node of graph.nodes) {
+    const p = pos.get(node.id);
+    const g = document.createElementNS(svgNs, "g");
+    g.setAttribute("class", "node" + (node.id === graph.center_id ? " center" : ""));
+    g.setAttribute("transform", `translate(${p.x},${p.y})`);
+    const rect = document.createElementNS(svgNs, "rect");
+    rect.setAttribute("width", 180);
+    rect.setAttribute("height", 32);
+    const text = document.createElementNS(svgNs, "text");
+    text.setAttribute("x", 8);
+    text.setAttribute("y", 20);
+    text.textContent = node.label.length > 26 ? node.label.slice(0, 25) + "…" : node.label;
+    g.append(rect, text);
+    g.addEventListener("mouseenter", () => highlight(node.id));
+    g.addEventListener("mouseleave", () => highlight(null));
+    g.addEventListener("click", () => {
+      details.textContent = [
+        node.label,
+        `kind: ${node.kind}`,
+        `depth: ${node.depth}`,
+        node.qualified_name ? `qualified: ${node.qualified_name}` : null,
+        node.file_path ? `file: ${node.file_path}` : null,
+      ].filter(Boolean).join("\n");
+    });
+    nodesRoot.appendChild(g);
+    nodeEls.push({ node, g });
+  }
+  function highlight(id) {
+    for (const { edge, path } of edgeEls) {
+      path.classList.toggle("hot", id !== null && (edge.source === id || edge.target === id));
+    }
+  }
+  document.getElementById("filter").addEventListener("input", (event) => {
+    const needle = event.target.value.trim().toLowerCase();
+    for (const { node, g } of nodeEls) {
+      const haystack = `${node.label} ${node.file_path || ""}`.toLowerCase();
+      g.classList.toggle("dim", needle !== "" && !haystack.includes(needle));
+    }
+  });
+  const notes = [`${graph.nodes.length} nodes`, `${graph.edges.length} edges`];
+  if (graph.truncated) {
+    notes.push(graph.node_limit ? `truncated at ${graph.node_limit} nodes` : "truncated");
+  }
+  if (graph.omitted_node_count) notes.push(`${graph.omitted_node_count} nodes omitted`);
+  if (graph.omitted_edge_count) notes.push(`${graph.omitted_edge_count} edges omitted`);
+  const pruned = (graph.pruned_test_node_count || 0) + (graph.pruned_accessor_node_count || 0)
+    + (graph.pruned_generated_node_count || 0);
+  if (pruned) notes.push(`${pruned} nodes pruned`);
+  document.getElementById("summary").textContent = notes.join(" · ");
+  const width = Math.max(...[...pos.values()].map((p) => p.x + 220), 400);
+  const height = Math.max(...[...pos.values()].map((p) => p.y + 72), 200);
+  let view = { x: 0, y: 0, w: width, h: height };
+  const apply = () => svg.setAttribute("viewBox", `${view.x} ${view.y} ${view.w} ${view.h}`);
+  apply();
+  svg.addEventListener("wheel", (event) => {
+    event.preventDefault();
+    const scale = event.deltaY > 0 ? 1.1 : 0.9;
+    view = { x: view.x, y: view.y, w: view.w * scale, h: view.h * scale };
+    apply();
+  });
+  let drag = null;
+  svg.addEventListener("mousedown", (event) => { drag = { x: event.clientX, y: event.clientY }; });
+  window.addEventListener("mouseup", () => { drag = null; });
+  window.addEventListener("mousemove", (event) => {
+    if (!drag) return;
+    const ratio = view.w / svg.clientWidth;
+    view.x -= (event.clientX - drag.x) * ratio;
+    view.y -= (event.clientY - drag.y) * ratio;
+    drag = { x: event.clientX, y: event.clientY };
+    apply();
+  });
+})();
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codestory_contracts::api::{
+        EdgeId, EdgeKind, GraphEdgeDto, GraphNodeDto, NodeId, NodeKind,
+    };
+
+    #[test]
+    fn trail_html_embeds_graph_without_breaking_out_of_script() {
+        let graph = GraphResponse {
+            center_id: NodeId("1".to_string()),
+            nodes: vec![GraphNodeDto {
+                id: NodeId("1".to_string()),
+                label: "render</script><b>".to_string(),
+                kind: NodeKind::FUNCTION,
+                depth: 0,
+                label_policy: None,
+                badge_visible_members: None,
+                badge_total_members: None,
+                merged_symbol_examples: Vec::new(),
+                file_path: Some("src/view.rs".to_string()),
+                qualified_name: None,
+                member_access: None,
+                edge_counts: None,
+                badges: Default::default(),
+                centrality: None,
+            }],
+            edges: vec![GraphEdgeDto::new(
+                EdgeId("7".to_string()),
+                NodeId("1".to_string()),
+                NodeId("1".to_string()),
+                EdgeKind::CALL,
+            )],
+            truncated: false,
+            truncated_by_timeout: false,
+            omitted_edge_count: 0,
+            pruned_test_node_count: 0,
+            pruned_accessor_node_count: 0,
+            pruned_generated_node_count: 0,
+            omitted_node_count: 0,
+            node_limit: None,
+            paths: Vec::new(),
+            canonical_layout: None,
+        };
+
+        let html = render_trail_html("Trail <render>", &graph).expect("render trail html");
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<title>Trail &lt;render&gt;</title>"));
+        assert_eq!(html.matches("</script>").count(), 2);
+        assert!(html.contains("render\\u003c/script\\u003e\\u003cb\\u003e"));
+        assert!(html.contains("src/view.rs"));
+    }
+}
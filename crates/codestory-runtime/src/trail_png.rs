@@ -0,0 +1,415 @@
+use codestory_contracts::api::{EdgeResolutionDto, GraphRenderTheme, GraphResponse};
+use std::collections::HashMap;
+use tiny_skia::{
+    Color, FillRule, Paint, Path, PathBuilder, Pixmap, Rect, Stroke, StrokeDash, Transform,
+};
+
+// Scene geometry, in the same units as the HTML trail viewer.
+const MARGIN: f32 = 40.0;
+const COLUMN_WIDTH: f32 = 240.0;
+const ROW_HEIGHT: f32 = 56.0;
+const NODE_WIDTH: f32 = 180.0;
+const NODE_HEIGHT: f32 = 32.0;
+const NODE_RADIUS: f32 = 6.0;
+const LABEL_INSET: f32 = 8.0;
+/// Scene units per glyph pixel of [`GLYPHS`].
+const GLYPH_PIXEL: f32 = 1.4;
+const LABEL_MAX_CHARS: usize = 19;
+
+/// Scale applied when the request names neither width nor height.
+const DEFAULT_SCALE: f32 = 2.0;
+const MIN_DIMENSION: u32 = 16;
+const MAX_DIMENSION: u32 = 4_096;
+
+struct Palette {
+    background: [u8; 3],
+    node_fill: [u8; 3],
+    node_stroke: [u8; 3],
+    center_fill: [u8; 3],
+    center_stroke: [u8; 3],
+    edge: [u8; 3],
+    text: [u8; 3],
+}
+
+const LIGHT: Palette = Palette {
+    background: [0xff, 0xff, 0xff],
+    node_fill: [0xdd, 0xf4, 0xff],
+    node_stroke: [0x54, 0xae, 0xff],
+    center_fill: [0xff, 0xf8, 0xc5],
+    center_stroke: [0xd4, 0xa7, 0x2c],
+    edge: [0x8c, 0x95, 0x9f],
+    text: [0x1f, 0x23, 0x28],
+};
+
+const DARK: Palette = Palette {
+    background: [0x0d, 0x11, 0x17],
+    node_fill: [0x12, 0x26, 0x3a],
+    node_stroke: [0x38, 0x8b, 0xfd],
+    center_fill: [0x3b, 0x2e, 0x0a],
+    center_stroke: [0xd2, 0x99, 0x22],
+    edge: [0x6e, 0x76, 0x81],
+    text: [0xe6, 0xed, 0xf3],
+};
+
+/// Rasterize a trail to PNG bytes.
+///
+/// Nodes sit on the canonical layout ranks when present (trail depth
+/// otherwise), exactly as in the HTML export, and the scene is scaled to fit
+/// the requested size with its aspect ratio kept. Labels use a built-in 5x7
+/// ASCII font, so no system fonts are needed.
+pub(crate) fn render_trail_png(
+    graph: &GraphResponse,
+    width: Option<u32>,
+    height: Option<u32>,
+    theme: GraphRenderTheme,
+) -> Result<Vec<u8>, String> {
+    let positions = node_positions(graph);
+    let scene_width = positions
+        .values()
+        .map(|(x, _)| x + NODE_WIDTH + MARGIN)
+        .fold(2.0 * MARGIN, f32::max);
+    let scene_height = positions
+        .values()
+        .map(|(_, y)| y + NODE_HEIGHT + MARGIN)
+        .fold(2.0 * MARGIN, f32::max);
+    let (image_width, image_height) = image_size(scene_width, scene_height, width, height);
+    let scale = (image_width as f32 / scene_width).min(image_height as f32 / scene_height);
+    let transform = Transform::from_scale(scale, scale).post_translate(
+        (image_width as f32 - scene_width * scale) / 2.0,
+        (image_height as f32 - scene_height * scale) / 2.0,
+    );
+
+    let palette = match theme {
+        GraphRenderTheme::Light => &LIGHT,
+        GraphRenderTheme::Dark => &DARK,
+    };
+    let mut pixmap = Pixmap::new(image_width, image_height)
+        .ok_or_else(|| format!("invalid image size {image_width}x{image_height}"))?;
+    let [r, g, b] = palette.background;
+    pixmap.fill(Color::from_rgba8(r, g, b, 0xff));
+
+    let edge_paint = paint(palette.edge);
+    for edge in &graph.edges {
+        let (Some(&(ax, ay)), Some(&(bx, by))) =
+            (positions.get(&edge.source.0), positions.get(&edge.target.0))
+        else {
+            continue;
+        };
+        let (start_x, start_y) = (ax + NODE_WIDTH, ay + NODE_HEIGHT / 2.0);
+        let (end_x, end_y) = (bx, by + NODE_HEIGHT / 2.0);
+        let mid = (start_x + end_x) / 2.0;
+        let mut builder = PathBuilder::new();
+        builder.move_to(start_x, start_y);
+        builder.cubic_to(mid, start_y, mid, end_y, end_x, end_y);
+        let Some(path) = builder.finish() else {
+            continue;
+        };
+        let stroke = Stroke {
+            width: 1.0,
+            dash: if edge.certainty.as_deref() == Some("uncertain")
+                || edge.resolution == EdgeResolutionDto::Unresolved
+            {
+                StrokeDash::new(vec![4.0, 3.0], 0.0)
+            } else {
+                None
+            },
+            ..Stroke::default()
+        };
+        pixmap.stroke_path(&path, &edge_paint, &stroke, transform, None);
+    }
+
+    let text_paint = paint(palette.text);
+    for node in &graph.nodes {
+        let Some(&(x, y)) = positions.get(&node.id.0) else {
+            continue;
+        };
+        let (fill, outline) = if node.id == graph.center_id {
+            (palette.center_fill, palette.center_stroke)
+        } else {
+            (palette.node_fill, palette.node_stroke)
+        };
+        if let Some(path) = rounded_rect(x, y, NODE_WIDTH, NODE_HEIGHT, NODE_RADIUS) {
+            pixmap.fill_path(&path, &paint(fill), FillRule::Winding, transform, None);
+            pixmap.stroke_path(&path, &paint(outline), &Stroke::default(), transform, None);
+        }
+        let text_y = y + (NODE_HEIGHT - 7.0 * GLYPH_PIXEL) / 2.0;
+        draw_text(
+            &mut pixmap,
+            &truncate_label(&node.label),
+            x + LABEL_INSET,
+            text_y,
+            &text_paint,
+            transform,
+        );
+    }
+
+    pixmap
+        .encode_png()
+        .map_err(|error| format!("failed to encode PNG: {error}"))
+}
+
+/// Top-left corner of each node in scene units, keyed by node id.
+fn node_positions(graph: &GraphResponse) -> HashMap<String, (f32, f32)> {
+    let ranks = graph
+        .canonical_layout
+        .iter()
+        .flat_map(|layout| &layout.nodes)
+        .map(|node| (node.id.0.as_str(), (node.x_rank as f32, node.y_rank as f32)))
+        .collect::<HashMap<_, _>>();
+    let mut rows_by_depth = HashMap::<u32, u32>::new();
+    let mut positions = graph
+        .nodes
+        .iter()
+        .map(|node| {
+            let (column, row) = ranks.get(node.id.0.as_str()).copied().unwrap_or_else(|| {
+                let row = rows_by_depth.entry(node.depth).or_default();
+                *row += 1;
+                (node.depth as f32, (*row - 1) as f32)
+            });
+            (
+                node.id.0.clone(),
+                (MARGIN + column * COLUMN_WIDTH, MARGIN + row * ROW_HEIGHT),
+            )
+        })
+        .collect::<HashMap<_, _>>();
+    // Canonical ranks put callers at negative columns.
+    let min_x = positions
+        .values()
+        .map(|(x, _)| x - MARGIN)
+        .fold(0.0, f32::min);
+    for (x, _) in positions.values_mut() {
+        *x -= min_x;
+    }
+    positions
+}
+
+fn image_size(
+    scene_width: f32,
+    scene_height: f32,
+    width: Option<u32>,
+    height: Option<u32>,
+) -> (u32, u32) {
+    let aspect = scene_width / scene_height;
+    let (width, height) = match (width, height) {
+        (Some(width), Some(height)) => (width as f32, height as f32),
+        (Some(width), None) => (width as f32, width as f32 / aspect),
+        (None, Some(height)) => (height as f32 * aspect, height as f32),
+        (None, None) => (scene_width * DEFAULT_SCALE, scene_height * DEFAULT_SCALE),
+    };
+    let clamp = |value: f32| (value.round() as u32).clamp(MIN_DIMENSION, MAX_DIMENSION);
+    (clamp(width), clamp(height))
+}
+
+fn paint(rgb: [u8; 3]) -> Paint<'static> {
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(rgb[0], rgb[1], rgb[2], 0xff);
+    paint.anti_alias = true;
+    paint
+}
+
+fn rounded_rect(x: f32, y: f32, width: f32, height: f32, radius: f32) -> Option<Path> {
+    let mut builder = PathBuilder::new();
+    builder.move_to(x + radius, y);
+    builder.line_to(x + width - radius, y);
+    builder.quad_to(x + width, y, x + width, y + radius);
+    builder.line_to(x + width, y + height - radius);
+    builder.quad_to(x + width, y + height, x + width - radius, y + height);
+    builder.line_to(x + radius, y + height);
+    builder.quad_to(x, y + height, x, y + height - radius);
+    builder.line_to(x, y + radius);
+    builder.quad_to(x, y, x + radius, y);
+    builder.close();
+    builder.finish()
+}
+
+/// `label` cut to what fits in a node, ending in `...` when shortened.
+fn truncate_label(label: &str) -> String {
+    if label.chars().count() <= LABEL_MAX_CHARS {
+        return label.to_string();
+    }
+    let mut truncated = label.chars().take(LABEL_MAX_CHARS - 3).collect::<String>();
+    truncated.push_str("...");
+    truncated
+}
+
+fn draw_text(pixmap: &mut Pixmap, text: &str, x: f32, y: f32, paint: &Paint, transform: Transform) {
+    for (index, ch) in text.chars().enumerate() {
+        let glyph = glyph(ch);
+        let glyph_x = x + index as f32 * 6.0 * GLYPH_PIXEL;
+        for (column, bits) in glyph.iter().enumerate() {
+            for row in 0..7 {
+                if bits & (1 << row) == 0 {
+                    continue;
+                }
+                if let Some(rect) = Rect::from_xywh(
+                    glyph_x + column as f32 * GLYPH_PIXEL,
+                    y + row as f32 * GLYPH_PIXEL,
+                    GLYPH_PIXEL,
+                    GLYPH_PIXEL,
+                ) {
+                    pixmap.fill_rect(rect, paint, transform, None);
+                }
+            }
+        }
+    }
+}
+
+/// Columns of `ch` in [`GLYPHS`], with `?` standing in for anything outside
+/// printable ASCII.
+fn glyph(ch: char) -> &'static [u8; 5] {
+    let index = match ch {
+        ' '..='~' => ch as usize - ' ' as usize,
+        _ => '?' as usize - ' ' as usize,
+    };
+    &GLYPHS[index]
+}
+
+/// A 5x7 font for printable ASCII: five columns per glyph, bit 0 the top row.
+const GLYPHS: [[u8; 5]; 95] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00], // ' '
+    [0x00, 0x00, 0x5f, 0x00, 0x00], // '!'
+    [0x00, 0x07, 0x00, 0x07, 0x00], // '"'
+    [0x14, 0x7f, 0x14, 0x7f, 0x14], // '#'
+    [0x24, 0x2a, 0x7f, 0x2a, 0x12], // '$'
+    [0x23, 0x13, 0x08, 0x64, 0x62], // '%'
+    [0x36, 0x49, 0x55, 0x22, 0x50], // '&'
+    [0x00, 0x05, 0x03, 0x00, 0x00], // '\''
+    [0x00, 0x1c, 0x22, 0x41, 0x00], // '('
+    [0x00, 0x41, 0x22, 0x1c, 0x00], // ')'
+    [0x08, 0x2a, 0x1c, 0x2a, 0x08], // '*'
+    [0x08, 0x08, 0x3e, 0x08, 0x08], // '+'
+    [0x00, 0x50, 0x30, 0x00, 0x00], // ','
+    [0x08, 0x08, 0x08, 0x08, 0x08], // '-'
+    [0x00, 0x60, 0x60, 0x00, 0x00], // '.'
+    [0x20, 0x10, 0x08, 0x04, 0x02], // '/'
+    [0x3e, 0x51, 0x49, 0x45, 0x3e], // '0'
+    [0x00, 0x42, 0x7f, 0x40, 0x00], // '1'
+    [0x42, 0x61, 0x51, 0x49, 0x46], // '2'
+    [0x21, 0x41, 0x45, 0x4b, 0x31], // '3'
+    [0x18, 0x14, 0x12, 0x7f, 0x10], // '4'
+    [0x27, 0x45, 0x45, 0x45, 0x39], // '5'
+    [0x3c, 0x4a, 0x49, 0x49, 0x30], // '6'
+    [0x01, 0x71, 0x09, 0x05, 0x03], // '7'
+    [0x36, 0x49, 0x49, 0x49, 0x36], // '8'
+    [0x06, 0x49, 0x49, 0x29, 0x1e], // '9'
+    [0x00, 0x36, 0x36, 0x00, 0x00], // ':'
+    [0x00, 0x56, 0x36, 0x00, 0x00], // ';'
+    [0x08, 0x14, 0x22, 0x41, 0x00], // '<'
+    [0x14, 0x14, 0x14, 0x14, 0x14], // '='
+    [0x00, 0x41, 0x22, 0x14, 0x08], // '>'
+    [0x02, 0x01, 0x51, 0x09, 0x06], // '?'
+    [0x32, 0x49, 0x79, 0x41, 0x3e], // '@'
+    [0x7e, 0x11, 0x11, 0x11, 0x7e], // 'A'
+    [0x7f, 0x49, 0x49, 0x49, 0x36], // 'B'
+    [0x3e, 0x41, 0x41, 0x41, 0x22], // 'C'
+    [0x7f, 0x41, 0x41, 0x22, 0x1c], // 'D'
+    [0x7f, 0x49, 0x49, 0x49, 0x41], // 'E'
+    [0x7f, 0x09, 0x09, 0x09, 0x01], // 'F'
+    [0x3e, 0x41, 0x49, 0x49, 0x7a], // 'G'
+    [0x7f, 0x08, 0x08, 0x08, 0x7f], // 'H'
+    [0x00, 0x41, 0x7f, 0x41, 0x00], // 'I'
+    [0x20, 0x40, 0x41, 0x3f, 0x01], // 'J'
+    [0x7f, 0x08, 0x14, 0x22, 0x41], // 'K'
+    [0x7f, 0x40, 0x40, 0x40, 0x40], // 'L'
+    [0x7f, 0x02, 0x0c, 0x02, 0x7f], // 'M'
+    [0x7f, 0x04, 0x08, 0x10, 0x7f], // 'N'
+    [0x3e, 0x41, 0x41, 0x41, 0x3e], // 'O'
+    [0x7f, 0x09, 0x09, 0x09, 0x06], // 'P'
+    [0x3e, 0x41, 0x51, 0x21, 0x5e], // 'Q'
+    [0x7f, 0x09, 0x19, 0x29, 0x46], // 'R'
+    [0x46, 0x49, 0x49, 0x49, 0x31], // 'S'
+    [0x01, 0x01, 0x7f, 0x01, 0x01], // 'T'
+    [0x3f, 0x40, 0x40, 0x40, 0x3f], // 'U'
+    [0x1f, 0x20, 0x40, 0x20, 0x1f], // 'V'
+    [0x3f, 0x40, 0x38, 0x40, 0x3f], // 'W'
+    [0x63, 0x14, 0x08, 0x14, 0x63], // 'X'
+    [0x07, 0x08, 0x70, 0x08, 0x07], // 'Y'
+    [0x61, 0x51, 0x49, 0x45, 0x43], // 'Z'
+    [0x00, 0x7f, 0x41, 0x41, 0x00], // '['
+    [0x02, 0x04, 0x08, 0x10, 0x20], // '\\'
+    [0x00, 0x41, 0x41, 0x7f, 0x00], // ']'
+    [0x04, 0x02, 0x01, 0x02, 0x04], // '^'
+    [0x40, 0x40, 0x40, 0x40, 0x40], // '_'
+    [0x00, 0x01, 0x02, 0x04, 0x00], // '`'
+    [0x20, 0x54, 0x54, 0x54, 0x78], // 'a'
+    [0x7f, 0x48, 0x44, 0x44, 0x38], // 'b'
+    [0x38, 0x44, 0x44, 0x44, 0x20], // 'c'
+    [0x38, 0x44, 0x44, 0x48, 0x7f], // 'd'
+    [0x38, 0x54, 0x54, 0x54, 0x18], // 'e'
+    [0x08, 0x7e, 0x09, 0x01, 0x02], // 'f'
+    [0x0c, 0x52, 0x52, 0x52, 0x3e], // 'g'
+    [0x7f, 0x08, 0x04, 0x04, 0x78], // 'h'
+    [0x00, 0x44, 0x7d, 0x40, 0x00], // 'i'
+    [0x20, 0x40, 0x44, 0x3d, 0x00], // 'j'
+    [0x7f, 0x10, 0x28, 0x44, 0x00], // 'k'
+    [0x00, 0x41, 0x7f, 0x40, 0x00], // 'l'
+    [0x7c, 0x04, 0x18, 0x04, 0x78], // 'm'
+    [0x7c, 0x08, 0x04, 0x04, 0x78], // 'n'
+    [0x38, 0x44, 0x44, 0x44, 0x38], // 'o'
+    [0x7c, 0x14, 0x14, 0x14, 0x08], // 'p'
+    [0x08, 0x14, 0x14, 0x18, 0x7c], // 'q'
+    [0x7c, 0x08, 0x04, 0x04, 0x08], // 'r'
+    [0x48, 0x54, 0x54, 0x54, 0x20], // 's'
+    [0x04, 0x3f, 0x44, 0x40, 0x20], // 't'
+    [0x3c, 0x40, 0x40, 0x20, 0x7c], // 'u'
+    [0x1c, 0x20, 0x40, 0x20, 0x1c], // 'v'
+    [0x3c, 0x40, 0x30, 0x40, 0x3c], // 'w'
+    [0x44, 0x28, 0x10, 0x28, 0x44], // 'x'
+    [0x0c, 0x50, 0x50, 0x50, 0x3c], // 'y'
+    [0x44, 0x64, 0x54, 0x4c, 0x44], // 'z'
+    [0x00, 0x08, 0x36, 0x41, 0x00], // '{'
+    [0x00, 0x00, 0x7f, 0x00, 0x00], // '|'
+    [0x00, 0x41, 0x36, 0x08, 0x00], // '}'
+    [0x02, 0x01, 0x02, 0x04, 0x02], // '~'
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codestory_contracts::api::{
+        EdgeId, EdgeKind, GraphEdgeDto, GraphNodeDto, NodeId, NodeKind,
+    };
+
+    fn node(id: &str, label: &str, depth: u32) -> GraphNodeDto {
+        GraphNodeDto::new(NodeId(id.to_string()), label, NodeKind::FUNCTION, depth)
+    }
+
+    #[test]
+    fn trail_png_is_a_png_of_the_requested_size() {
+        let mut edge = GraphEdgeDto::new(
+            EdgeId("10".to_string()),
+            NodeId("1".to_string()),
+            NodeId("2".to_string()),
+            EdgeKind::CALL,
+        );
+        edge.certainty = Some("uncertain".to_string());
+        let graph = GraphResponse::new(
+            NodeId("1".to_string()),
+            vec![
+                node("1", "main", 0),
+                node("2", "a_rather_long_callee_name", 1),
+            ],
+            vec![edge],
+        );
+
+        // Two columns by one row: a 500 x 112 scene.
+        let png = render_trail_png(&graph, Some(320), None, GraphRenderTheme::Dark)
+            .expect("render trail png");
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+        let sized = Pixmap::decode_png(&png).expect("decode png");
+        assert_eq!((sized.width(), sized.height()), (320, 72));
+
+        let natural = render_trail_png(&graph, None, None, GraphRenderTheme::Light)
+            .expect("render trail png");
+        let natural = Pixmap::decode_png(&natural).expect("decode png");
+        assert_eq!((natural.width(), natural.height()), (1_000, 224));
+    }
+
+    #[test]
+    fn long_labels_are_shortened_to_fit_the_node() {
+        assert_eq!(truncate_label("main"), "main");
+        let label = truncate_label("a_rather_long_callee_name");
+        assert_eq!(label, "a_rather_long_ca...");
+        assert_eq!(label.chars().count(), LABEL_MAX_CHARS);
+    }
+}
@@ -65,8 +65,8 @@ pub(crate) fn build_trail_story(
         trail.nodes.len(),
         trail.edges.len(),
         story_trail_mode(req.mode),
-        story_trail_direction(req.direction),
-        if req.caller_scope == TrailCallerScope::IncludeTestsAndBenches {
+        story_trail_direction(req.direction_or_default()),
+        if req.caller_scope_or_default() == TrailCallerScope::IncludeTestsAndBenches {
             "included"
         } else {
             "excluded"
@@ -149,9 +149,11 @@ enum StoryEdgeGroup {
 fn story_edge_group(kind: EdgeKind) -> StoryEdgeGroup {
     match kind {
         EdgeKind::CALL | EdgeKind::MACRO_USAGE => StoryEdgeGroup::Runtime,
-        EdgeKind::USAGE | EdgeKind::INCLUDE | EdgeKind::IMPORT | EdgeKind::ANNOTATION_USAGE => {
-            StoryEdgeGroup::Data
-        }
+        EdgeKind::USAGE
+        | EdgeKind::ASSIGNMENT
+        | EdgeKind::INCLUDE
+        | EdgeKind::IMPORT
+        | EdgeKind::ANNOTATION_USAGE => StoryEdgeGroup::Data,
         EdgeKind::TYPE_USAGE
         | EdgeKind::MEMBER
         | EdgeKind::INHERITANCE
@@ -329,6 +331,7 @@ fn story_relation(kind: EdgeKind) -> &'static str {
         EdgeKind::IMPORT => "imports",
         EdgeKind::MACRO_USAGE => "uses macro",
         EdgeKind::ANNOTATION_USAGE => "uses annotation",
+        EdgeKind::ASSIGNMENT => "assigns",
         EdgeKind::UNKNOWN => "relates to",
     }
 }
@@ -491,7 +494,7 @@ fn test_scope_for_story(
     test_nodes: &[&GraphNodeDto],
 ) -> Vec<String> {
     let mut scope = Vec::new();
-    if req.caller_scope == TrailCallerScope::IncludeTestsAndBenches {
+    if req.caller_scope_or_default() == TrailCallerScope::IncludeTestsAndBenches {
         scope.push("tests and benches included by request caller scope".to_string());
     } else {
         scope.push(
@@ -538,12 +541,14 @@ fn limits_for_story(
     if trail.truncated {
         limits.push(format!(
             "trail was truncated at max_nodes={} with omitted_edge_count={}",
-            req.max_nodes, trail.omitted_edge_count
+            req.max_nodes_or_default(),
+            trail.omitted_edge_count
         ));
     } else {
         limits.push(format!(
             "trail not truncated; max_nodes={} omitted_edge_count={}",
-            req.max_nodes, trail.omitted_edge_count
+            req.max_nodes_or_default(),
+            trail.omitted_edge_count
         ));
     }
     if trail.edges.is_empty() {
@@ -633,6 +638,9 @@ fn story_trail_mode(mode: TrailMode) -> &'static str {
         TrailMode::AllReferenced => "referenced",
         TrailMode::AllReferencing => "referencing",
         TrailMode::ToTargetSymbol => "to_target_symbol",
+        TrailMode::AllPaths => "all_paths",
+        TrailMode::WeightedPath => "weighted_path",
+        TrailMode::DataFlow => "data_flow",
     }
 }
 
@@ -666,19 +674,22 @@ mod trail_story_tests {
             file_path: (!file_path.is_empty()).then(|| file_path.to_string()),
             qualified_name: None,
             member_access: None,
+            edge_counts: None,
+            badges: Default::default(),
+            centrality: None,
         }
     }
 
     fn edge(id: usize, source: &str, target: &str, certainty: Option<&str>) -> GraphEdgeDto {
         GraphEdgeDto {
-            id: EdgeId(format!("edge-{id}")),
-            source: NodeId(source.to_string()),
-            target: NodeId(target.to_string()),
-            kind: EdgeKind::CALL,
             confidence: Some(0.99),
             certainty: certainty.map(ToOwned::to_owned),
-            callsite_identity: None,
-            candidate_targets: Vec::new(),
+            ..GraphEdgeDto::new(
+                EdgeId(format!("edge-{id}")),
+                NodeId(source.to_string()),
+                NodeId(target.to_string()),
+                EdgeKind::CALL,
+            )
         }
     }
 
@@ -690,14 +701,14 @@ mod trail_story_tests {
         certainty: Option<&str>,
     ) -> GraphEdgeDto {
         GraphEdgeDto {
-            id: EdgeId(format!("edge-{id}")),
-            source: NodeId(source.to_string()),
-            target: NodeId(target.to_string()),
-            kind,
             confidence: Some(0.99),
             certainty: certainty.map(ToOwned::to_owned),
-            callsite_identity: None,
-            candidate_targets: Vec::new(),
+            ..GraphEdgeDto::new(
+                EdgeId(format!("edge-{id}")),
+                NodeId(source.to_string()),
+                NodeId(target.to_string()),
+                kind,
+            )
         }
     }
 
@@ -706,16 +717,24 @@ mod trail_story_tests {
             root_id: NodeId("focus".to_string()),
             mode: TrailMode::Neighborhood,
             target_id: None,
-            depth: 2,
-            direction: TrailDirection::Both,
-            caller_scope: TrailCallerScope::ProductionOnly,
+            depth: Some(2),
+            direction: Some(TrailDirection::Both),
+            caller_scope: Some(TrailCallerScope::ProductionOnly),
             edge_filter: Vec::new(),
             show_utility_calls: false,
             hide_speculative: false,
             story,
             node_filter: Vec::new(),
-            max_nodes: 24,
+            hide_trivial_accessors: false,
+            hide_generated: false,
+            entry_reachable_only: false,
+            max_paths: None,
+            edge_weight: Default::default(),
+            max_nodes: Some(24),
             layout_direction: LayoutDirection::Horizontal,
+            graph_session: None,
+            timeout_ms: None,
+            filter_preset: None,
         }
     }
 
@@ -737,6 +756,7 @@ mod trail_story_tests {
             resolution_status: None,
             member_access: None,
             route_endpoint: None,
+            definitions: Vec::new(),
         }
     }
 
@@ -759,7 +779,14 @@ mod trail_story_tests {
             nodes,
             edges,
             truncated: false,
+            truncated_by_timeout: false,
             omitted_edge_count: 0,
+            pruned_test_node_count: 0,
+            pruned_accessor_node_count: 0,
+            pruned_generated_node_count: 0,
+            omitted_node_count: 0,
+            node_limit: None,
+            paths: Vec::new(),
             canonical_layout: None,
         };
 
@@ -805,7 +832,14 @@ mod trail_story_tests {
                 edge(4, "focus", "missing", None),
             ],
             truncated: false,
+            truncated_by_timeout: false,
             omitted_edge_count: 0,
+            pruned_test_node_count: 0,
+            pruned_accessor_node_count: 0,
+            pruned_generated_node_count: 0,
+            omitted_node_count: 0,
+            node_limit: None,
+            paths: Vec::new(),
             canonical_layout: None,
         };
 
@@ -844,7 +878,14 @@ mod trail_story_tests {
             nodes: vec![node("focus", "handle_request", "C:/repo/src/request.rs")],
             edges: Vec::new(),
             truncated: false,
+            truncated_by_timeout: false,
             omitted_edge_count: 0,
+            pruned_test_node_count: 0,
+            pruned_accessor_node_count: 0,
+            pruned_generated_node_count: 0,
+            omitted_node_count: 0,
+            node_limit: None,
+            paths: Vec::new(),
             canonical_layout: None,
         };
 
@@ -885,6 +926,7 @@ mod trail_story_tests {
             resolution_status: None,
             member_access: None,
             route_endpoint: None,
+            definitions: Vec::new(),
         };
         let trail = GraphResponse {
             center_id: NodeId("focus".to_string()),
@@ -900,7 +942,14 @@ mod trail_story_tests {
                 Some("certain"),
             )],
             truncated: false,
+            truncated_by_timeout: false,
             omitted_edge_count: 0,
+            pruned_test_node_count: 0,
+            pruned_accessor_node_count: 0,
+            pruned_generated_node_count: 0,
+            omitted_node_count: 0,
+            node_limit: None,
+            paths: Vec::new(),
             canonical_layout: None,
         };
 
@@ -946,6 +995,7 @@ mod trail_story_tests {
             resolution_status: None,
             member_access: None,
             route_endpoint: None,
+            definitions: Vec::new(),
         };
         let trail = GraphResponse {
             center_id: NodeId("focus".to_string()),
@@ -959,7 +1009,14 @@ mod trail_story_tests {
                 edge(2, "focus", "catalog", Some("certain")),
             ],
             truncated: false,
+            truncated_by_timeout: false,
             omitted_edge_count: 0,
+            pruned_test_node_count: 0,
+            pruned_accessor_node_count: 0,
+            pruned_generated_node_count: 0,
+            omitted_node_count: 0,
+            node_limit: None,
+            paths: Vec::new(),
             canonical_layout: None,
         };
 
@@ -997,7 +1054,14 @@ mod trail_story_tests {
                 edge(2, "focus", "business", Some("certain")),
             ],
             truncated: false,
+            truncated_by_timeout: false,
             omitted_edge_count: 0,
+            pruned_test_node_count: 0,
+            pruned_accessor_node_count: 0,
+            pruned_generated_node_count: 0,
+            omitted_node_count: 0,
+            node_limit: None,
+            paths: Vec::new(),
             canonical_layout: None,
         };
 
@@ -74,16 +74,24 @@ fn test_cli_app_indexer_smoke() -> anyhow::Result<()> {
             root_id: main_id,
             mode: TrailMode::Neighborhood,
             target_id: None,
-            depth: 15,
-            direction: TrailDirection::Outgoing,
-            caller_scope: TrailCallerScope::ProductionOnly,
+            depth: Some(15),
+            direction: Some(TrailDirection::Outgoing),
+            caller_scope: Some(TrailCallerScope::ProductionOnly),
             edge_filter: vec![],
             show_utility_calls: true,
             hide_speculative: false,
             story: false,
             node_filter: vec![],
-            max_nodes: 10,
+            hide_trivial_accessors: false,
+            hide_generated: false,
+            entry_reachable_only: false,
+            max_paths: None,
+            edge_weight: Default::default(),
+            max_nodes: Some(10),
             layout_direction: LayoutDirection::Horizontal,
+            graph_session: None,
+            timeout_ms: None,
+            filter_preset: None,
         })
         .unwrap();
 
@@ -18,24 +18,35 @@ pub use snapshot_store::{
     StagedSnapshotPublishStats,
 };
 pub use storage_impl::{
-    BUILD_EDGE_SEED_BATCH_SIZE, BuildNodeLookup, CURRENT_SCHEMA_VERSION,
-    CallerProjectionRemovalSummary, CorePromotionStats, DENSE_ANCHOR_MIGRATION_STATE_NATIVE,
+    BUILD_EDGE_SEED_BATCH_SIZE, BookmarkHealth, BookmarkHealthStatus, BuildNodeLookup,
+    CENTRALITY_DEFAULT_ITERATIONS, CURRENT_SCHEMA_VERSION, CallerProjectionRemovalSummary,
+    CentralityStats, CodeMarker, CodeMarkerFilter, CodeMarkerRecord, CodeMarkerScan,
+    CorePromotionStats, DENSE_ANCHOR_MIGRATION_STATE_NATIVE,
     DENSE_ANCHOR_PUBLICATION_SCHEMA_VERSION, DatabaseSnapshotCopyStats, DenseAnchorInput,
     DenseAnchorInputReuseMetadata, DenseAnchorPublicationManifest, DenseReasonCounts,
-    FileContentHash, FileInfo, FileProjectionRemovalSummary, FileRole, GroundingEdgeKindCount,
-    GroundingFileSummary, GroundingNodeRecord, GroundingSnapshotMetadata, GroundingSnapshotState,
-    IndexArtifactCacheReader, IndexArtifactCacheWrite, IndexPublicationMode,
-    IndexPublicationRecord, LlmSymbolDoc, LlmSymbolDocReuseMetadata, LlmSymbolDocStats,
-    ProjectionFlushBreakdown, ProjectionPersistenceFamilyStats, ProjectionPersistenceStats,
+    DependencyResolution, EntryPointDistances, FileContentHash, FileInfo,
+    FileProjectionRemovalSummary, FileRole, FileStats, GraphMatch, GraphMatchFilter, GraphMatches,
+    GraphMetadata, GroundingEdgeKindCount, GroundingFileSummary, GroundingNodeRecord,
+    GroundingSnapshotMetadata, GroundingSnapshotState, INDEX_HISTORY_RETAINED_RUNS,
+    IndexArtifactCacheReader, IndexArtifactCacheWrite, IndexContentDelta, IndexContentDigest,
+    IndexGraphAt, IndexHistoryEvent, IndexHistoryRun, IndexHistorySubject, IndexPublicationMode,
+    IndexPublicationRecord, JavaTypeLinks, LlmSymbolDoc, LlmSymbolDocReuseMetadata,
+    LlmSymbolDocStats, ManifestPackage, NodeAlias, NodeBadges, NodeCacheLimits, NodeCacheStats,
+    NodeDefinition, NodeEdgeCounts, NodePreview, PendingSourceScan, ProjectionFlushBreakdown,
+    ProjectionPersistenceFamilyStats, ProjectionPersistenceStats, ReachableFileCount, ReachableSet,
     RetrievalIndexManifest, RetrievalIndexRollbackRecord,
     SOURCE_POLICY_EXCLUSION_PUBLICATION_SCHEMA_VERSION, STRUCTURAL_TEXT_UNIT_DESCRIPTOR_VERSION,
     STRUCTURAL_TEXT_UNIT_MIGRATION_STATE_NATIVE, STRUCTURAL_TEXT_UNIT_PUBLICATION_SCHEMA_VERSION,
     SearchSymbolProjection, SearchSymbolProjectionDetail, SourcePolicyExclusionManifest,
     SourcePolicyExclusionPolicyIdentity, SourcePolicyExclusionRecord, Storage as Store,
-    StorageError, StorageOpenMode, StorageStats, StructuralTextArtifactCacheWrite,
+    StorageEncryptionKey, StorageError, StorageIndexUsage, StorageMaintenanceReport,
+    StorageOpenMode, StorageOpenOptions, StorageStats, StorageTableStats, StringLiteral,
+    StringLiteralRecord, StringLiteralScan, StructuralTextArtifactCacheWrite,
     StructuralTextProjection, StructuralTextPublicationCompatibility, StructuralTextUnit,
     StructuralTextUnitPublicationManifest, SymbolSearchDoc, SymbolSummaryRecord,
-    structural_text_unit_digest,
+    TEST_MAPPING_MAX_DEPTH, TestReach, TrailFilterPreset, TrailStop, WorkspaceSymbol,
+    WorkspaceSymbolFilter, WorkspaceSymbolGroup, enable_process_integrity_check,
+    encryption_supported, install_process_encryption_key, structural_text_unit_digest,
 };
 
 impl Store {
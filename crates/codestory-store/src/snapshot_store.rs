@@ -17,6 +17,9 @@ pub struct SnapshotRefreshStats {
 pub struct StagedSnapshotFinalizeStats {
     pub deferred_indexes_ms: u32,
     pub summary_snapshot_ms: u32,
+    /// Time spent refreshing planner statistics, when the graph grew enough
+    /// to need it.
+    pub analyze_ms: Option<u32>,
 }
 
 /// SQLite fence timings for a completed staged publication.
@@ -130,12 +133,21 @@ impl<'a> SnapshotStore<'a> {
             .complete_deferred_secondary_indexes_after_summary()?;
         let post_summary_indexes_duration = post_summary_indexes_started.elapsed();
 
-        Ok(staged_snapshot_finalize_stats(
-            pre_summary_indexes_duration,
-            summary_with_mid_index_duration,
-            node_file_rank_index_duration,
-            post_summary_indexes_duration,
-        ))
+        let analyze_started = Instant::now();
+        let analyze_ms = self
+            .storage
+            .analyze_if_stale()?
+            .then(|| clamp_u128_to_u32(analyze_started.elapsed().as_millis()));
+
+        Ok(StagedSnapshotFinalizeStats {
+            analyze_ms,
+            ..staged_snapshot_finalize_stats(
+                pre_summary_indexes_duration,
+                summary_with_mid_index_duration,
+                node_file_rank_index_duration,
+                post_summary_indexes_duration,
+            )
+        })
     }
 
     /// Return whether the summary snapshot is ready for reads.
@@ -296,6 +308,7 @@ fn staged_snapshot_finalize_stats(
     StagedSnapshotFinalizeStats {
         deferred_indexes_ms: clamp_u128_to_u32(deferred_indexes_duration.as_millis()),
         summary_snapshot_ms: clamp_u128_to_u32(summary_snapshot_duration.as_millis()),
+        analyze_ms: None,
     }
 }
 
@@ -57,36 +57,150 @@ pub(super) fn add_bookmark(
     Ok(conn.last_insert_rowid())
 }
 
+/// Bookmarks that still name a node, live or retired, ordered by id. Only
+/// the `window` (offset, limit) of them is read when one is given.
 pub(super) fn get_bookmarks(
     conn: &Connection,
     category_id: Option<i64>,
+    window: Option<(usize, usize)>,
 ) -> Result<Vec<Bookmark>, StorageError> {
-    let query = match category_id {
-        Some(_) => {
-            "SELECT id, category_id, node_id, comment FROM bookmark_node WHERE category_id = ?1"
-        }
-        None => "SELECT id, category_id, node_id, comment FROM bookmark_node",
-    };
-    let mut stmt = conn.prepare(query)?;
+    let (offset, limit) = window.unwrap_or((0, usize::MAX));
+    let mut stmt = conn.prepare(
+        "SELECT id, category_id, node_id, comment, retired_node_id FROM bookmark_node
+         WHERE (?1 IS NULL OR category_id = ?1)
+           AND (node_id IS NOT NULL OR retired_node_id IS NOT NULL)
+         ORDER BY id
+         LIMIT ?2 OFFSET ?3",
+    )?;
     let mut bookmarks = Vec::new();
 
-    let mut rows = if let Some(cat_id) = category_id {
-        stmt.query(params![cat_id])?
-    } else {
-        stmt.query([])?
-    };
+    let mut rows = stmt.query(params![
+        category_id,
+        limit.min(i64::MAX as usize) as i64,
+        offset.min(i64::MAX as usize) as i64
+    ])?;
 
     while let Some(row) = rows.next()? {
+        let node_id = match row.get::<_, Option<i64>>(2)? {
+            Some(node_id) => NodeId(node_id),
+            None => resolve_retired_node(conn, NodeId(row.get(4)?))?,
+        };
         bookmarks.push(Bookmark {
             id: row.get(0)?,
             category_id: row.get(1)?,
-            node_id: NodeId(row.get(2)?),
+            node_id,
             comment: row.get(3)?,
         });
     }
     Ok(bookmarks)
 }
 
+/// Longest successor chain followed before giving up on a retired node.
+const MAX_TOMBSTONE_HOPS: usize = 32;
+
+/// Follow tombstone successors from a retired node to the live node that
+/// replaced it. Returns the last id reached when the chain has no live end,
+/// so callers still see which symbol the bookmark referred to.
+pub(super) fn resolve_retired_node(
+    conn: &Connection,
+    retired: NodeId,
+) -> Result<NodeId, StorageError> {
+    let mut current = retired;
+    for _ in 0..MAX_TOMBSTONE_HOPS {
+        let successor = conn
+            .query_row(
+                "SELECT successor_node_id FROM node_tombstone WHERE node_id = ?1",
+                params![current.0],
+                |row| row.get::<_, Option<i64>>(0),
+            )
+            .optional()?
+            .flatten();
+        let Some(successor) = successor.map(NodeId) else {
+            break;
+        };
+        let live = conn
+            .query_row(
+                "SELECT 1 FROM node WHERE id = ?1",
+                params![successor.0],
+                |_| Ok(()),
+            )
+            .optional()?
+            .is_some();
+        if live || successor == current {
+            return Ok(successor);
+        }
+        current = successor;
+    }
+    Ok(current)
+}
+
+/// How long a bookmark may stay retired without its symbol reappearing.
+pub(super) const RETIRED_BOOKMARK_RETENTION_MS: i64 = 30 * 24 * 60 * 60 * 1000;
+
+/// Rebind retired bookmarks whose tombstone now leads to a live node, delete
+/// those retired for longer than [`RETIRED_BOOKMARK_RETENTION_MS`], and drop
+/// tombstones no remaining bookmark can reach. Returns how many bookmarks
+/// were deleted.
+pub(super) fn settle_retired_bookmarks(
+    conn: &Connection,
+    now_epoch_ms: i64,
+) -> Result<usize, StorageError> {
+    let retired = {
+        let mut stmt = conn.prepare(
+            "SELECT id, retired_node_id FROM bookmark_node
+             WHERE node_id IS NULL AND retired_node_id IS NOT NULL",
+        )?;
+        stmt.query_map([], |row| Ok((row.get::<_, i64>(0)?, NodeId(row.get(1)?))))?
+            .collect::<Result<Vec<_>, _>>()?
+    };
+    if !retired.is_empty() {
+        let mut rebind = conn.prepare(
+            "UPDATE bookmark_node SET node_id = ?1, retired_node_id = NULL WHERE id = ?2",
+        )?;
+        for (bookmark_id, retired_node_id) in retired {
+            let successor = resolve_retired_node(conn, retired_node_id)?;
+            let live = successor != retired_node_id
+                && conn
+                    .query_row(
+                        "SELECT 1 FROM node WHERE id = ?1",
+                        params![successor.0],
+                        |_| Ok(()),
+                    )
+                    .optional()?
+                    .is_some();
+            if live {
+                rebind.execute(params![successor.0, bookmark_id])?;
+            }
+        }
+    }
+
+    let expired = conn.execute(
+        "DELETE FROM bookmark_node
+         WHERE node_id IS NULL
+           AND retired_node_id IN (
+             SELECT node_id FROM node_tombstone WHERE retired_at_epoch_ms < ?1
+           )",
+        params![now_epoch_ms.saturating_sub(RETIRED_BOOKMARK_RETENTION_MS)],
+    )?;
+    // Keep every tombstone on a successor chain that starts at a retired
+    // bookmark; the rest are no longer needed to resolve anything.
+    conn.execute(
+        "WITH RECURSIVE reachable(node_id) AS (
+            SELECT retired_node_id FROM bookmark_node
+            WHERE node_id IS NULL AND retired_node_id IS NOT NULL
+            UNION
+            SELECT t.successor_node_id
+            FROM node_tombstone t
+            JOIN reachable r ON r.node_id = t.node_id
+            WHERE t.successor_node_id IS NOT NULL
+         )
+         DELETE FROM node_tombstone
+         WHERE node_id NOT IN (SELECT node_id FROM reachable)",
+        [],
+    )?;
+    Ok(expired)
+}
+
 pub(super) fn update_bookmark_comment(
     conn: &Connection,
     id: i64,
@@ -130,3 +244,140 @@ pub(super) fn delete_bookmark(conn: &Connection, id: i64) -> Result<(), StorageE
     conn.execute("DELETE FROM bookmark_node WHERE id = ?1", params![id])?;
     Ok(())
 }
+
+/// How a stored bookmark relates to the live node table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BookmarkHealthStatus {
+    /// Points at a live node.
+    Valid,
+    /// Its node is gone, but a live node with the same kind and name exists.
+    Resolvable,
+    /// Its node is gone and nothing matches it.
+    Orphaned,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BookmarkHealth {
+    pub bookmark_id: i64,
+    pub category_id: i64,
+    /// Node the bookmark row holds, live or retired.
+    pub stored_node_id: NodeId,
+    /// Live node the bookmark resolves to, if any.
+    pub resolved_node_id: Option<NodeId>,
+    /// Qualified or serialized name last recorded for the bookmarked symbol.
+    pub symbol_name: Option<String>,
+    pub status: BookmarkHealthStatus,
+}
+
+pub(super) fn bookmark_health(conn: &Connection) -> Result<Vec<BookmarkHealth>, StorageError> {
+    let mut stmt = conn.prepare(
+        "SELECT b.id, b.category_id, COALESCE(b.node_id, b.retired_node_id),
+                COALESCE(n.qualified_name, n.serialized_name)
+         FROM bookmark_node b
+         LEFT JOIN node n ON n.id = b.node_id
+         WHERE COALESCE(b.node_id, b.retired_node_id) IS NOT NULL
+         ORDER BY b.id",
+    )?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, i64>(1)?,
+                NodeId(row.get::<_, i64>(2)?),
+                row.get::<_, Option<String>>(3)?,
+            ))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut health = Vec::with_capacity(rows.len());
+    for (bookmark_id, category_id, stored_node_id, live_name) in rows {
+        let entry = if live_name.is_some() {
+            BookmarkHealth {
+                bookmark_id,
+                category_id,
+                stored_node_id,
+                resolved_node_id: Some(stored_node_id),
+                symbol_name: live_name,
+                status: BookmarkHealthStatus::Valid,
+            }
+        } else {
+            let (resolved_node_id, symbol_name) = resolve_missing_node(conn, stored_node_id)?;
+            BookmarkHealth {
+                bookmark_id,
+                category_id,
+                stored_node_id,
+                resolved_node_id,
+                symbol_name,
+                status: if resolved_node_id.is_some() {
+                    BookmarkHealthStatus::Resolvable
+                } else {
+                    BookmarkHealthStatus::Orphaned
+                },
+            }
+        };
+        health.push(entry);
+    }
+    Ok(health)
+}
+
+/// Live replacement for a node that no longer exists: its tombstone
+/// successor, or else a live node with the tombstone's kind and name.
+fn resolve_missing_node(
+    conn: &Connection,
+    missing: NodeId,
+) -> Result<(Option<NodeId>, Option<String>), StorageError> {
+    let is_live = |id: NodeId| -> Result<bool, StorageError> {
+        Ok(conn
+            .query_row(
+                "SELECT 1 FROM node WHERE id = ?1",
+                params![id.0],
+                |_| Ok(()),
+            )
+            .optional()?
+            .is_some())
+    };
+    let end = resolve_retired_node(conn, missing)?;
+    let tombstone = conn
+        .query_row(
+            "SELECT kind, COALESCE(qualified_name, serialized_name)
+             FROM node_tombstone WHERE node_id = ?1",
+            params![missing.0],
+            |row| Ok((row.get::<_, i32>(0)?, row.get::<_, String>(1)?)),
+        )
+        .optional()?;
+    if end != missing && is_live(end)? {
+        return Ok((Some(end), tombstone.map(|(_, name)| name)));
+    }
+    let Some((kind, name)) = tombstone else {
+        return Ok((None, None));
+    };
+    let matched = conn
+        .query_row(
+            "SELECT id FROM node
+             WHERE kind = ?1 AND COALESCE(qualified_name, serialized_name) = ?2
+             ORDER BY id
+             LIMIT 1",
+            params![kind, name],
+            |row| row.get::<_, i64>(0),
+        )
+        .optional()?
+        .map(NodeId);
+    Ok((matched, Some(name)))
+}
+
+/// Rebind every resolvable bookmark to its live node and return how many
+/// were rebound.
+pub(super) fn repair_bookmarks(conn: &Connection) -> Result<usize, StorageError> {
+    let mut stmt = conn
+        .prepare("UPDATE bookmark_node SET node_id = ?1, retired_node_id = NULL WHERE id = ?2")?;
+    let mut repaired = 0;
+    for entry in bookmark_health(conn)? {
+        if entry.status != BookmarkHealthStatus::Resolvable {
+            continue;
+        }
+        if let Some(node_id) = entry.resolved_node_id {
+            repaired += stmt.execute(params![node_id.0, entry.bookmark_id])?;
+        }
+    }
+    Ok(repaired)
+}
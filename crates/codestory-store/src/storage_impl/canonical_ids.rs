@@ -0,0 +1,23 @@
+use super::*;
+
+impl Storage {
+    /// Nodes whose `canonical_id` equals `canonical_id`, ordered by id.
+    ///
+    /// Unlike node ids, `sym:` canonical IDs do not depend on where the
+    /// workspace is checked out, so external tools can keep them across
+    /// re-indexing and look symbols up again here.
+    pub fn get_nodes_by_canonical_id(&self, canonical_id: &str) -> Result<Vec<Node>, StorageError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, kind, serialized_name, qualified_name, canonical_id, file_node_id, start_line, start_col, end_line, end_col
+             FROM node
+             WHERE canonical_id = ?1
+             ORDER BY id",
+        )?;
+        let mut rows = stmt.query(params![canonical_id])?;
+        let mut nodes = Vec::new();
+        while let Some(row) = rows.next()? {
+            nodes.push(Self::node_from_row(row)?);
+        }
+        Ok(nodes)
+    }
+}
@@ -0,0 +1,153 @@
+use super::*;
+
+/// PageRank iterations an indexing run computes when not configured.
+pub const CENTRALITY_DEFAULT_ITERATIONS: usize = 20;
+
+const CENTRALITY_DAMPING: f64 = 0.85;
+
+/// Edges a symbol's importance flows along: something that calls or uses a
+/// symbol lends it weight.
+const CENTRALITY_EDGE_KINDS: [EdgeKind; 3] =
+    [EdgeKind::CALL, EdgeKind::USAGE, EdgeKind::TYPE_USAGE];
+
+/// What one [`Storage::refresh_node_centrality`] pass scored.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CentralityStats {
+    pub node_count: usize,
+    pub edge_count: usize,
+    pub iterations: usize,
+}
+
+impl Storage {
+    /// Recompute the PageRank of every node on the call and usage graph,
+    /// replacing the stored scores.
+    ///
+    /// Edges are followed between their resolved endpoints. Scores are scaled
+    /// so the mean over scored nodes is `1.0`; nodes without call or usage
+    /// edges are not scored.
+    pub fn refresh_node_centrality(
+        &mut self,
+        iterations: usize,
+    ) -> Result<CentralityStats, StorageError> {
+        let mut edges = Vec::<(i64, i64)>::new();
+        {
+            let mut stmt = self.conn.prepare(
+                "SELECT COALESCE(resolved_source_node_id, source_node_id),
+                        COALESCE(resolved_target_node_id, target_node_id)
+                 FROM edge
+                 WHERE kind IN (?1, ?2, ?3)",
+            )?;
+            let kinds = CENTRALITY_EDGE_KINDS.map(|kind| kind as i32);
+            let mut rows = stmt.query(params![kinds[0], kinds[1], kinds[2]])?;
+            while let Some(row) = rows.next()? {
+                let (source, target) = (row.get::<_, i64>(0)?, row.get::<_, i64>(1)?);
+                if source != target {
+                    edges.push((source, target));
+                }
+            }
+        }
+        edges.sort_unstable();
+        edges.dedup();
+
+        let mut index_of = HashMap::<i64, usize>::new();
+        let mut node_ids = Vec::<i64>::new();
+        let mut links = Vec::<(usize, usize)>::with_capacity(edges.len());
+        for (source, target) in &edges {
+            let mut slot = |id: i64| {
+                *index_of.entry(id).or_insert_with(|| {
+                    node_ids.push(id);
+                    node_ids.len() - 1
+                })
+            };
+            let source = slot(*source);
+            let target = slot(*target);
+            links.push((source, target));
+        }
+
+        let node_count = node_ids.len();
+        let mut out_degree = vec![0usize; node_count];
+        for (source, _) in &links {
+            out_degree[*source] += 1;
+        }
+        let mut scores = vec![1.0 / node_count.max(1) as f64; node_count];
+        let mut next = vec![0.0; node_count];
+        for _ in 0..iterations {
+            // Nodes that call nothing spread their score evenly so the total
+            // stays constant.
+            let dangling = (0..node_count)
+                .filter(|index| out_degree[*index] == 0)
+                .map(|index| scores[index])
+                .sum::<f64>();
+            let base =
+                ((1.0 - CENTRALITY_DAMPING) + CENTRALITY_DAMPING * dangling) / node_count as f64;
+            next.fill(base);
+            for (source, target) in &links {
+                next[*target] += CENTRALITY_DAMPING * scores[*source] / out_degree[*source] as f64;
+            }
+            std::mem::swap(&mut scores, &mut next);
+        }
+
+        let tx = self.conn.transaction()?;
+        tx.execute("DELETE FROM node_centrality", [])?;
+        {
+            let mut insert =
+                tx.prepare("INSERT INTO node_centrality (node_id, score) VALUES (?1, ?2)")?;
+            for (id, score) in node_ids.iter().zip(&scores) {
+                insert.execute(params![id, score * node_count as f64])?;
+            }
+        }
+        tx.commit()?;
+        Ok(CentralityStats {
+            node_count,
+            edge_count: links.len(),
+            iterations,
+        })
+    }
+
+    /// Whether any node has a stored centrality score.
+    pub fn has_node_centrality(&self) -> Result<bool, StorageError> {
+        Ok(self
+            .conn
+            .query_row("SELECT EXISTS(SELECT 1 FROM node_centrality)", [], |row| {
+                row.get(0)
+            })?)
+    }
+
+    /// Stored centrality for each of `node_ids`. Unscored nodes are omitted.
+    pub fn node_centrality(
+        &self,
+        node_ids: &[NodeId],
+    ) -> Result<HashMap<NodeId, f32>, StorageError> {
+        let mut node_ids = node_ids.to_vec();
+        node_ids.sort_unstable_by_key(|id| id.0);
+        node_ids.dedup();
+        let mut scores = HashMap::new();
+        for chunk in node_ids.chunks(EDGE_NODE_LOOKUP_BATCH_SIZE) {
+            let query = format!(
+                "SELECT node_id, score FROM node_centrality WHERE node_id IN ({})",
+                numbered_placeholders(1, chunk.len())
+            );
+            let mut stmt = self.conn.prepare(&query)?;
+            let mut rows = stmt.query(params_from_iter(chunk.iter().map(|id| id.0)))?;
+            while let Some(row) = rows.next()? {
+                scores.insert(NodeId(row.get(0)?), row.get::<_, f64>(1)? as f32);
+            }
+        }
+        Ok(scores)
+    }
+
+    /// The `limit` highest-scored nodes, most central first.
+    pub fn most_central_nodes(&self, limit: usize) -> Result<Vec<(NodeId, f32)>, StorageError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT node_id, score FROM node_centrality
+             ORDER BY score DESC, node_id
+             LIMIT ?1",
+        )?;
+        let nodes = stmt
+            .query_map(params![limit as i64], |row| {
+                Ok((NodeId(row.get(0)?), row.get::<_, f64>(1)? as f32))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(nodes)
+    }
+}
@@ -0,0 +1,176 @@
+use super::*;
+
+/// One `TODO`, `FIXME`, or `HACK` comment in a source file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeMarker {
+    pub line: u32,
+    /// One-based column of the tag.
+    pub column: u32,
+    /// Upper-case tag, such as `TODO`.
+    pub tag: String,
+    /// Comment text after the tag.
+    pub text: String,
+}
+
+/// An indexed file whose markers, or other per-file scan, are missing or
+/// older than the file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingSourceScan {
+    pub file_id: i64,
+    pub path: PathBuf,
+    pub modification_time: Option<i64>,
+    pub content_hash: Option<String>,
+}
+
+/// The markers found in one file, and the file version they were read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeMarkerScan {
+    pub file_id: i64,
+    pub modification_time: Option<i64>,
+    pub content_hash: Option<String>,
+    pub markers: Vec<CodeMarker>,
+}
+
+/// Which markers [`Storage::code_markers`] returns. Unset fields match
+/// anything.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CodeMarkerFilter {
+    /// Tag, compared case-insensitively.
+    pub tag: Option<String>,
+    /// Substring of the file path.
+    pub path_contains: Option<String>,
+}
+
+/// A stored marker with the file it sits in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeMarkerRecord {
+    pub file_id: i64,
+    pub path: PathBuf,
+    pub marker: CodeMarker,
+}
+
+impl Storage {
+    /// Indexed files never scanned for markers, or changed since their scan.
+    pub fn code_marker_sources_pending(&self) -> Result<Vec<PendingSourceScan>, StorageError> {
+        self.pending_source_scans("code_marker_scan")
+    }
+
+    /// Files with no row in `scan_table`, or whose row records another file
+    /// version.
+    pub(super) fn pending_source_scans(
+        &self,
+        scan_table: &str,
+    ) -> Result<Vec<PendingSourceScan>, StorageError> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT f.id, f.path, f.modification_time, f.content_hash
+             FROM file f
+             LEFT JOIN {scan_table} s ON s.file_id = f.id
+             WHERE s.file_id IS NULL
+                OR s.modification_time IS NOT f.modification_time
+                OR s.content_hash IS NOT f.content_hash
+             ORDER BY f.id"
+        ))?;
+        let sources = stmt
+            .query_map([], |row| {
+                Ok(PendingSourceScan {
+                    file_id: row.get(0)?,
+                    path: PathBuf::from(row.get::<_, String>(1)?),
+                    modification_time: row.get(2)?,
+                    content_hash: row.get(3)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(sources)
+    }
+
+    /// Replace the markers of each scanned file, and drop markers of files no
+    /// longer in the index. Returns the number of markers written.
+    pub fn replace_code_markers(
+        &mut self,
+        scans: &[CodeMarkerScan],
+    ) -> Result<usize, StorageError> {
+        let tx = self.conn.transaction()?;
+        let mut written = 0;
+        {
+            let mut clear = tx.prepare("DELETE FROM code_marker WHERE file_id = ?1")?;
+            let mut insert = tx.prepare(
+                "INSERT OR REPLACE INTO code_marker (file_id, line, column, tag, text)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+            )?;
+            let mut record_scan = tx.prepare(
+                "INSERT OR REPLACE INTO code_marker_scan (file_id, modification_time, content_hash)
+                 VALUES (?1, ?2, ?3)",
+            )?;
+            for scan in scans {
+                clear.execute(params![scan.file_id])?;
+                for marker in &scan.markers {
+                    insert.execute(params![
+                        scan.file_id,
+                        marker.line,
+                        marker.column,
+                        marker.tag,
+                        marker.text
+                    ])?;
+                    written += 1;
+                }
+                record_scan.execute(params![
+                    scan.file_id,
+                    scan.modification_time,
+                    scan.content_hash
+                ])?;
+            }
+        }
+        tx.execute(
+            "DELETE FROM code_marker WHERE file_id NOT IN (SELECT id FROM file)",
+            [],
+        )?;
+        tx.execute(
+            "DELETE FROM code_marker_scan WHERE file_id NOT IN (SELECT id FROM file)",
+            [],
+        )?;
+        tx.commit()?;
+        Ok(written)
+    }
+
+    /// Stored markers matching `filter`, ordered by file path and line.
+    pub fn code_markers(
+        &self,
+        filter: &CodeMarkerFilter,
+        limit: usize,
+    ) -> Result<Vec<CodeMarkerRecord>, StorageError> {
+        let tag = filter
+            .tag
+            .as_deref()
+            .map(str::trim)
+            .filter(|tag| !tag.is_empty())
+            .map(str::to_ascii_uppercase);
+        let path = filter
+            .path_contains
+            .as_deref()
+            .filter(|path| !path.is_empty());
+        let mut stmt = self.conn.prepare(
+            "SELECT m.file_id, f.path, m.line, m.column, m.tag, m.text
+             FROM code_marker m
+             JOIN file f ON f.id = m.file_id
+             WHERE (?1 IS NULL OR m.tag = ?1)
+               AND (?2 IS NULL OR instr(f.path, ?2) > 0)
+             ORDER BY f.path, m.line, m.column
+             LIMIT ?3",
+        )?;
+        let records = stmt
+            .query_map(params![tag, path, limit as i64], |row| {
+                Ok(CodeMarkerRecord {
+                    file_id: row.get(0)?,
+                    path: PathBuf::from(row.get::<_, String>(1)?),
+                    marker: CodeMarker {
+                        line: row.get(2)?,
+                        column: row.get(3)?,
+                        tag: row.get(4)?,
+                        text: row.get(5)?,
+                    },
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(records)
+    }
+}
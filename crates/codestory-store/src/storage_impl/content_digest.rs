@@ -0,0 +1,174 @@
+use super::*;
+use std::cmp::Ordering;
+
+/// Sorted identities of everything one store generation holds.
+///
+/// Node and edge IDs are content-derived, so two digests taken around an
+/// indexing run diff into the run's additions and removals.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IndexContentDigest {
+    pub node_ids: Vec<i64>,
+    pub edge_ids: Vec<i64>,
+    /// `(file id, fingerprint)` pairs; the fingerprint is the content hash
+    /// when one was recorded and the modification time otherwise.
+    pub files: Vec<(i64, String)>,
+}
+
+/// What changed between two [`IndexContentDigest`]s.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct IndexContentDelta {
+    pub nodes_added: usize,
+    pub nodes_removed: usize,
+    pub edges_added: usize,
+    pub edges_removed: usize,
+    pub files_added: usize,
+    pub files_removed: usize,
+    pub files_updated: usize,
+}
+
+impl IndexContentDigest {
+    pub fn delta_to(&self, next: &IndexContentDigest) -> IndexContentDelta {
+        let (nodes_removed, nodes_added) = sorted_difference_counts(&self.node_ids, &next.node_ids);
+        let (edges_removed, edges_added) = sorted_difference_counts(&self.edge_ids, &next.edge_ids);
+        let mut delta = IndexContentDelta {
+            nodes_added,
+            nodes_removed,
+            edges_added,
+            edges_removed,
+            ..IndexContentDelta::default()
+        };
+        let (mut before, mut after) = (self.files.iter().peekable(), next.files.iter().peekable());
+        loop {
+            match (before.peek(), after.peek()) {
+                (Some((old_id, old_print)), Some((new_id, new_print))) => {
+                    match old_id.cmp(new_id) {
+                        Ordering::Less => {
+                            delta.files_removed += 1;
+                            before.next();
+                        }
+                        Ordering::Greater => {
+                            delta.files_added += 1;
+                            after.next();
+                        }
+                        Ordering::Equal => {
+                            if old_print != new_print {
+                                delta.files_updated += 1;
+                            }
+                            before.next();
+                            after.next();
+                        }
+                    }
+                }
+                (Some(_), None) => {
+                    delta.files_removed += before.count();
+                    break;
+                }
+                (None, Some(_)) => {
+                    delta.files_added += after.count();
+                    break;
+                }
+                (None, None) => break,
+            }
+        }
+        delta
+    }
+}
+
+/// `(only in left, only in right)` for two ascending, duplicate-free lists.
+fn sorted_difference_counts(left: &[i64], right: &[i64]) -> (usize, usize) {
+    let (mut i, mut j, mut shared) = (0, 0, 0);
+    while i < left.len() && j < right.len() {
+        match left[i].cmp(&right[j]) {
+            Ordering::Less => i += 1,
+            Ordering::Greater => j += 1,
+            Ordering::Equal => {
+                shared += 1;
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+    (left.len() - shared, right.len() - shared)
+}
+
+impl Storage {
+    /// Read the identity digest used to report per-run indexing deltas.
+    pub fn index_content_digest(&self) -> Result<IndexContentDigest, StorageError> {
+        self.read_index_content_digest(None)
+    }
+
+    /// Read the identity digest of the given files alone: their file rows,
+    /// the nodes and edges they own, and the file nodes themselves.
+    ///
+    /// Incremental runs only rewrite the files they touch, so diffing these
+    /// scoped digests reports the same delta without scanning every row.
+    /// Nodes no file owns, such as synthetic packages, are not counted.
+    pub fn index_content_digest_for_files(
+        &self,
+        file_ids: &[i64],
+    ) -> Result<IndexContentDigest, StorageError> {
+        let mut file_ids = file_ids.to_vec();
+        file_ids.sort_unstable();
+        file_ids.dedup();
+        self.read_index_content_digest(Some(&file_ids))
+    }
+
+    fn read_index_content_digest(
+        &self,
+        file_ids: Option<&[i64]>,
+    ) -> Result<IndexContentDigest, StorageError> {
+        let mut digest = IndexContentDigest::default();
+        let Some(file_ids) = file_ids else {
+            digest.node_ids = self.digest_ids("SELECT id FROM node ORDER BY id", &[])?;
+            digest.edge_ids = self.digest_ids("SELECT id FROM edge ORDER BY id", &[])?;
+            digest.files = self.digest_files("ORDER BY id", &[])?;
+            return Ok(digest);
+        };
+        for chunk in file_ids.chunks(400) {
+            let placeholders = question_placeholders(chunk.len());
+            digest.node_ids.extend(self.digest_ids(
+                &format!(
+                    "SELECT id FROM node
+                     WHERE file_node_id IN ({placeholders}) OR id IN ({placeholders})"
+                ),
+                &[chunk, chunk].concat(),
+            )?);
+            digest.edge_ids.extend(self.digest_ids(
+                &format!("SELECT id FROM edge WHERE file_node_id IN ({placeholders})"),
+                chunk,
+            )?);
+            digest
+                .files
+                .extend(self.digest_files(&format!("WHERE id IN ({placeholders})"), chunk)?);
+        }
+        digest.node_ids.sort_unstable();
+        digest.node_ids.dedup();
+        digest.edge_ids.sort_unstable();
+        digest.edge_ids.dedup();
+        digest.files.sort_unstable();
+        Ok(digest)
+    }
+
+    fn digest_ids(&self, sql: &str, params: &[i64]) -> Result<Vec<i64>, StorageError> {
+        let mut stmt = self.conn.prepare(sql)?;
+        let rows = stmt.query_map(params_from_iter(params), |row| row.get::<_, i64>(0))?;
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(StorageError::from)
+    }
+
+    fn digest_files(
+        &self,
+        filter: &str,
+        params: &[i64],
+    ) -> Result<Vec<(i64, String)>, StorageError> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT id, COALESCE(content_hash, CAST(modification_time AS TEXT), '')
+             FROM file {filter}"
+        ))?;
+        let rows = stmt.query_map(params_from_iter(params), |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+        })?;
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(StorageError::from)
+    }
+}
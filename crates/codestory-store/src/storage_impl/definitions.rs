@@ -0,0 +1,54 @@
+use super::*;
+
+/// One place a node is defined.
+///
+/// Partial classes, overloads sharing a qualified name, and re-exports index
+/// as a single node with several definition occurrences; each becomes one of
+/// these.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeDefinition {
+    pub file_node_id: NodeId,
+    pub file_path: String,
+    pub start_line: u32,
+    pub start_col: u32,
+    pub end_line: u32,
+    pub end_col: u32,
+}
+
+impl Storage {
+    /// Every distinct definition of `node_id`, ordered by file path, then
+    /// source position.
+    ///
+    /// Definitions of nodes merged into `node_id` are included.
+    pub fn get_node_definitions(
+        &self,
+        node_id: NodeId,
+    ) -> Result<Vec<NodeDefinition>, StorageError> {
+        let aliases = self.node_alias_map()?;
+        let node_ids = aliases.with_aliases(aliases.primary_of(node_id));
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT DISTINCT o.file_node_id, file_node.serialized_name,
+                    o.start_line, o.start_col, o.end_line, o.end_col
+             FROM occurrence o
+             JOIN node file_node ON file_node.id = o.file_node_id
+             WHERE o.kind = ?1 AND o.element_id IN ({})
+             ORDER BY file_node.serialized_name, o.start_line, o.start_col, o.end_line, o.end_col",
+            numbered_placeholders(2, node_ids.len())
+        ))?;
+        let params = std::iter::once(Value::from(OccurrenceKind::DEFINITION as i32))
+            .chain(node_ids.iter().map(|id| Value::from(id.0)));
+        let mut rows = stmt.query(params_from_iter(params))?;
+        let mut definitions = Vec::new();
+        while let Some(row) = rows.next()? {
+            definitions.push(NodeDefinition {
+                file_node_id: NodeId(row.get(0)?),
+                file_path: row.get(1)?,
+                start_line: row.get(2)?,
+                start_col: row.get(3)?,
+                end_line: row.get(4)?,
+                end_col: row.get(5)?,
+            });
+        }
+        Ok(definitions)
+    }
+}
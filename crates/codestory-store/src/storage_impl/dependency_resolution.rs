@@ -0,0 +1,148 @@
+use super::*;
+
+/// Confidence recorded on CALL edges resolved into dependency code.
+const DEPENDENCY_RESOLUTION_CONFIDENCE: f32 = 0.6;
+
+/// A CALL edge whose target was found in indexed dependency sources rather
+/// than in the workspace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DependencyResolution {
+    pub edge_id: EdgeId,
+    pub target: NodeId,
+    /// Dependency the target belongs to, e.g. `cargo:serde-1.0.200`.
+    pub dependency: String,
+}
+
+impl Storage {
+    /// Copy `targets` in from dependency storage and point each still
+    /// unresolved edge in `resolutions` at its target. Returns how many edges
+    /// were resolved.
+    ///
+    /// Resolutions whose edge was since removed or reset are dropped first,
+    /// so [`Storage::dependency_resolutions`] only reports live ones. The
+    /// copied nodes are recorded so
+    /// [`Storage::reset_dependency_resolutions`] can remove them again.
+    pub fn apply_dependency_resolutions(
+        &mut self,
+        targets: &[Node],
+        resolutions: &[DependencyResolution],
+    ) -> Result<usize, StorageError> {
+        if !targets.is_empty() {
+            self.insert_nodes_batch(targets)?;
+        }
+        let tx = self.conn.transaction()?;
+        tx.execute(
+            "DELETE FROM dependency_resolution
+             WHERE edge_id NOT IN (
+                 SELECT id FROM edge WHERE resolved_target_node_id IS NOT NULL
+             )",
+            [],
+        )?;
+        let mut applied = 0usize;
+        {
+            let mut copied =
+                tx.prepare("INSERT OR IGNORE INTO dependency_node (node_id) VALUES (?1)")?;
+            for target in targets {
+                copied.execute(params![target.id.0])?;
+            }
+            let mut update = tx.prepare(
+                "UPDATE edge
+                 SET resolved_target_node_id = ?2, confidence = ?3, certainty = ?4
+                 WHERE id = ?1 AND resolved_target_node_id IS NULL",
+            )?;
+            let mut record = tx.prepare(
+                "INSERT OR REPLACE INTO dependency_resolution (edge_id, dependency)
+                 VALUES (?1, ?2)",
+            )?;
+            for resolution in resolutions {
+                let changed = update.execute(params![
+                    resolution.edge_id.0,
+                    resolution.target.0,
+                    DEPENDENCY_RESOLUTION_CONFIDENCE,
+                    row_mapping::certainty_db_value(ResolutionCertainty::from_confidence(Some(
+                        DEPENDENCY_RESOLUTION_CONFIDENCE
+                    ))),
+                ])?;
+                if changed > 0 {
+                    record.execute(params![resolution.edge_id.0, resolution.dependency])?;
+                    applied += 1;
+                }
+            }
+        }
+        tx.commit()?;
+        if applied > 0 || !targets.is_empty() {
+            self.invalidate_grounding_snapshots()?;
+        }
+        Ok(applied)
+    }
+
+    /// Undo every dependency resolution and delete the nodes copied in for
+    /// them. The resolution pass calls this before resolving again, so
+    /// edges follow the current dependency storage and nodes of dependencies
+    /// that were removed do not linger. Returns how many edges were reset.
+    pub fn reset_dependency_resolutions(&mut self) -> Result<usize, StorageError> {
+        let tx = self.conn.transaction()?;
+        let reset = tx.execute(
+            "UPDATE edge
+             SET resolved_target_node_id = NULL, confidence = NULL, certainty = NULL
+             WHERE id IN (SELECT edge_id FROM dependency_resolution)
+               AND resolved_target_node_id IN (SELECT node_id FROM dependency_node)",
+            [],
+        )?;
+        tx.execute("DELETE FROM dependency_resolution", [])?;
+        // File nodes go last, once the symbols copied from them are gone.
+        let detached = {
+            let mut stmt = tx.prepare(
+                "SELECT copied.node_id
+                 FROM dependency_node copied
+                 JOIN node ON node.id = copied.node_id
+                 WHERE NOT EXISTS (
+                     SELECT 1 FROM edge
+                     WHERE edge.source_node_id = copied.node_id
+                        OR edge.target_node_id = copied.node_id
+                        OR edge.resolved_source_node_id = copied.node_id
+                        OR edge.resolved_target_node_id = copied.node_id
+                 )
+                 ORDER BY node.kind = ?1, copied.node_id",
+            )?;
+            stmt.query_map(params![NodeKind::FILE as i32], |row| row.get::<_, i64>(0))?
+                .collect::<Result<Vec<_>, _>>()?
+        };
+        let mut removed = Vec::with_capacity(detached.len());
+        for id in detached {
+            let still_holds_symbols: bool = tx.query_row(
+                "SELECT EXISTS(SELECT 1 FROM node WHERE file_node_id = ?1 AND id != ?1)",
+                params![id],
+                |row| row.get(0),
+            )?;
+            if !still_holds_symbols {
+                delete_detached_nodes_on(&tx, &[id])?;
+                removed.push(id);
+            }
+        }
+        tx.execute("DELETE FROM dependency_node", [])?;
+        tx.commit()?;
+        if !removed.is_empty() {
+            self.cache.nodes.lock().clear();
+        }
+        if reset > 0 || !removed.is_empty() {
+            self.invalidate_grounding_snapshots()?;
+        }
+        Ok(reset)
+    }
+
+    /// Dependency each edge resolved by
+    /// [`Storage::apply_dependency_resolutions`] points into.
+    pub fn dependency_resolutions(&self) -> Result<HashMap<EdgeId, String>, StorageError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT resolution.edge_id, resolution.dependency
+             FROM dependency_resolution resolution
+             JOIN edge ON edge.id = resolution.edge_id
+             WHERE edge.resolved_target_node_id IS NOT NULL",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok((EdgeId(row.get(0)?), row.get::<_, String>(1)?))
+        })?;
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+}
@@ -0,0 +1,68 @@
+use super::*;
+
+/// Whole-graph edge totals for one node, independent of any fetched subgraph.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NodeEdgeCounts {
+    pub outgoing_calls: u32,
+    pub incoming_calls: u32,
+    /// Outgoing `MEMBER` edges, i.e. declared members.
+    pub members: u32,
+}
+
+impl Storage {
+    /// Call and member counts for each of `node_ids`, following resolved
+    /// endpoints. Nodes without any counted edge are omitted.
+    pub fn node_edge_counts(
+        &self,
+        node_ids: &[NodeId],
+    ) -> Result<HashMap<NodeId, NodeEdgeCounts>, StorageError> {
+        let mut node_ids = node_ids.to_vec();
+        node_ids.sort_unstable_by_key(|id| id.0);
+        node_ids.dedup();
+        let mut counts = HashMap::<NodeId, NodeEdgeCounts>::new();
+        for chunk in node_ids.chunks(EDGE_NODE_LOOKUP_BATCH_SIZE) {
+            let placeholders = numbered_placeholders(1, chunk.len());
+            let call = chunk.len() + 1;
+            let member = chunk.len() + 2;
+            let query = format!(
+                "SELECT COALESCE(resolved_source_node_id, source_node_id), 0, kind, COUNT(*)
+                 FROM edge
+                 WHERE (source_node_id IN ({placeholders})
+                        OR resolved_source_node_id IN ({placeholders}))
+                   AND kind IN (?{call}, ?{member})
+                 GROUP BY 1, kind
+                 UNION ALL
+                 SELECT COALESCE(resolved_target_node_id, target_node_id), 1, kind, COUNT(*)
+                 FROM edge
+                 WHERE (target_node_id IN ({placeholders})
+                        OR resolved_target_node_id IN ({placeholders}))
+                   AND kind = ?{call}
+                 GROUP BY 1, kind"
+            );
+            let chunk_ids = chunk.iter().copied().collect::<HashSet<_>>();
+            let params = chunk
+                .iter()
+                .map(|id| id.0)
+                .chain([EdgeKind::CALL as i64, EdgeKind::MEMBER as i64]);
+            let mut stmt = self.conn.prepare(&query)?;
+            let mut rows = stmt.query(params_from_iter(params))?;
+            while let Some(row) = rows.next()? {
+                let node_id = NodeId(row.get(0)?);
+                if !chunk_ids.contains(&node_id) {
+                    continue;
+                }
+                let incoming = row.get::<_, i64>(1)? == 1;
+                let kind = EdgeKind::try_from(row.get::<_, i32>(2)?)?;
+                let count = clamp_i64_to_u32(row.get::<_, i64>(3)?);
+                let entry = counts.entry(node_id).or_default();
+                match (kind, incoming) {
+                    (EdgeKind::CALL, false) => entry.outgoing_calls += count,
+                    (EdgeKind::CALL, true) => entry.incoming_calls += count,
+                    (EdgeKind::MEMBER, false) => entry.members += count,
+                    _ => {}
+                }
+            }
+        }
+        Ok(counts)
+    }
+}
@@ -0,0 +1,131 @@
+use super::*;
+use std::fmt;
+use std::sync::OnceLock;
+
+static PROCESS_ENCRYPTION_KEY: OnceLock<StorageEncryptionKey> = OnceLock::new();
+
+/// Passphrase used to key an encrypted SQLite database.
+///
+/// The value is never printed through `Debug` so it cannot leak into traces
+/// or error reports that format open options.
+#[derive(Clone, PartialEq, Eq)]
+pub struct StorageEncryptionKey(String);
+
+impl StorageEncryptionKey {
+    /// Wrap a non-empty passphrase.
+    pub fn new(passphrase: impl Into<String>) -> Result<Self, StorageError> {
+        let passphrase = passphrase.into();
+        if passphrase.is_empty() {
+            return Err(StorageError::Other(
+                "Storage encryption key must not be empty".to_string(),
+            ));
+        }
+        Ok(Self(passphrase))
+    }
+
+    fn passphrase(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Debug for StorageEncryptionKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("StorageEncryptionKey(<redacted>)")
+    }
+}
+
+/// Options accepted by [`Storage::open_with_options`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StorageOpenOptions {
+    pub mode: StorageOpenMode,
+    /// Key for this connection. When absent, the process-wide key installed
+    /// with [`install_process_encryption_key`] applies, if any.
+    pub encryption_key: Option<StorageEncryptionKey>,
+    /// Run `PRAGMA integrity_check` before the store is initialized.
+    pub integrity_check: bool,
+}
+
+impl Default for StorageOpenOptions {
+    fn default() -> Self {
+        Self {
+            mode: StorageOpenMode::Live,
+            encryption_key: None,
+            integrity_check: false,
+        }
+    }
+}
+
+impl StorageOpenOptions {
+    pub fn with_mode(mut self, mode: StorageOpenMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn with_encryption_key(mut self, key: StorageEncryptionKey) -> Self {
+        self.encryption_key = Some(key);
+        self
+    }
+
+    pub fn with_integrity_check(mut self, integrity_check: bool) -> Self {
+        self.integrity_check = integrity_check;
+        self
+    }
+}
+
+/// Whether this build links SQLCipher and can open encrypted databases.
+pub const fn encryption_supported() -> bool {
+    cfg!(feature = "sqlcipher")
+}
+
+/// Install the key used by every file-backed connection this process opens.
+///
+/// Adapters call this once at startup after reading the key from their
+/// configured source. Staging, promotion, and observational opens all run
+/// against the same key, so a project database is either fully encrypted or
+/// not at all. Installing a different key a second time is rejected.
+pub fn install_process_encryption_key(key: StorageEncryptionKey) -> Result<(), StorageError> {
+    if !encryption_supported() {
+        return Err(StorageError::EncryptionUnavailable);
+    }
+    let installed = PROCESS_ENCRYPTION_KEY.get_or_init(|| key.clone());
+    if *installed != key {
+        return Err(StorageError::Other(
+            "A different storage encryption key is already installed for this process".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+pub(super) fn validate_explicit_key(
+    key: Option<&StorageEncryptionKey>,
+) -> Result<(), StorageError> {
+    if key.is_some() && !encryption_supported() {
+        return Err(StorageError::EncryptionUnavailable);
+    }
+    Ok(())
+}
+
+/// Key a freshly opened connection before any other statement touches it.
+///
+/// SQLCipher requires `PRAGMA key` to be the first operation on the handle;
+/// plain SQLite ignores the pragma, which is why keys are rejected up front
+/// when the feature is disabled.
+pub(super) fn apply_encryption_key(
+    conn: &Connection,
+    explicit: Option<&StorageEncryptionKey>,
+) -> rusqlite::Result<()> {
+    if let Some(key) = explicit.or_else(|| PROCESS_ENCRYPTION_KEY.get()) {
+        conn.pragma_update(None, "key", key.passphrase())?;
+    }
+    Ok(())
+}
+
+/// Open a file-backed connection and apply the process encryption key.
+pub(super) fn open_keyed_connection<P: AsRef<Path>>(
+    path: P,
+    flags: OpenFlags,
+) -> rusqlite::Result<Connection> {
+    let conn = Connection::open_with_flags(path, flags)?;
+    apply_encryption_key(&conn, None)?;
+    Ok(conn)
+}
@@ -0,0 +1,152 @@
+use super::*;
+
+/// Nodes one entry-point distance walk may reach before it stops expanding.
+const ENTRY_POINT_NODE_BUDGET: usize = 200_000;
+/// Annotation names (picocli, Spring Shell, click, Typer) that register a
+/// function or method as a command-line command.
+const CLI_COMMAND_ANNOTATIONS: &[&str] = &["Command", "ShellMethod", "command"];
+
+/// Call distance from the nearest entry point for every reachable node.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EntryPointDistances {
+    /// Entry points in ascending id order; each sits at distance zero.
+    pub entry_points: Vec<NodeId>,
+    pub distances: HashMap<NodeId, u32>,
+    /// Expansion stopped at the node budget before every call was followed.
+    pub truncated: bool,
+}
+
+impl EntryPointDistances {
+    pub fn distance(&self, id: NodeId) -> Option<u32> {
+        self.distances.get(&id).copied()
+    }
+}
+
+impl Storage {
+    /// Nodes execution can start from: functions and methods named `main`;
+    /// exported handlers, meaning framework route and OpenAPI endpoints and
+    /// functions named `handler`; CLI commands, meaning Tauri commands and
+    /// callables annotated as commands; and detected tests.
+    pub fn entry_point_ids(&self) -> Result<Vec<NodeId>, StorageError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id FROM node
+             WHERE (kind IN (?1, ?2)
+                    AND (serialized_name IN ('main', 'handler')
+                         OR serialized_name LIKE '%::main'
+                         OR serialized_name LIKE '%.main'
+                         OR serialized_name LIKE '%::handler'
+                         OR serialized_name LIKE '%.handler'))
+                OR canonical_id LIKE 'route_endpoint:%'
+                OR canonical_id LIKE 'openapi:endpoint:%'
+                OR canonical_id LIKE 'tauri:command:%'",
+        )?;
+        let mut ids = stmt
+            .query_map(
+                params![NodeKind::FUNCTION as i32, NodeKind::METHOD as i32],
+                |row| row.get::<_, i64>(0).map(NodeId),
+            )?
+            .collect::<Result<HashSet<_>, _>>()?;
+        ids.extend(self.annotated_node_ids(CLI_COMMAND_ANNOTATIONS)?);
+        ids.extend(self.test_function_ids()?);
+        let mut ids = ids.into_iter().collect::<Vec<_>>();
+        ids.sort_by_key(|id| id.0);
+        Ok(ids)
+    }
+
+    /// Call distance from the nearest entry point, as stored by the last
+    /// [`Storage::refresh_entry_point_distances`]. Stores without stored
+    /// distances are walked on the spot.
+    ///
+    /// Nodes missing from the result are not reached from any entry point.
+    pub fn entry_point_distances(&self) -> Result<EntryPointDistances, StorageError> {
+        let truncated = self
+            .conn
+            .query_row(
+                "SELECT truncated FROM entry_distance_scan WHERE id = 1",
+                [],
+                |row| row.get::<_, bool>(0),
+            )
+            .optional()?;
+        let Some(truncated) = truncated else {
+            return self.walk_entry_point_distances();
+        };
+        let mut stmt = self
+            .conn
+            .prepare("SELECT node_id, distance FROM node_entry_distance")?;
+        let distances = stmt
+            .query_map([], |row| Ok((NodeId(row.get(0)?), row.get::<_, u32>(1)?)))?
+            .collect::<Result<HashMap<_, _>, _>>()?;
+        let mut entry_points = distances
+            .iter()
+            .filter(|(_, distance)| **distance == 0)
+            .map(|(id, _)| *id)
+            .collect::<Vec<_>>();
+        entry_points.sort_by_key(|id| id.0);
+        Ok(EntryPointDistances {
+            entry_points,
+            distances,
+            truncated,
+        })
+    }
+
+    /// Walk the call graph from every entry point and store each reached
+    /// node's distance, replacing the stored distances.
+    pub fn refresh_entry_point_distances(&mut self) -> Result<EntryPointDistances, StorageError> {
+        let reach = self.walk_entry_point_distances()?;
+        let tx = self.conn.transaction()?;
+        tx.execute("DELETE FROM node_entry_distance", [])?;
+        {
+            let mut insert =
+                tx.prepare("INSERT INTO node_entry_distance (node_id, distance) VALUES (?1, ?2)")?;
+            for (id, distance) in &reach.distances {
+                insert.execute(params![id.0, distance])?;
+            }
+        }
+        tx.execute(
+            "INSERT OR REPLACE INTO entry_distance_scan (id, truncated) VALUES (1, ?1)",
+            params![reach.truncated],
+        )?;
+        tx.commit()?;
+        Ok(reach)
+    }
+
+    /// Breadth-first call distance from the nearest entry point.
+    fn walk_entry_point_distances(&self) -> Result<EntryPointDistances, StorageError> {
+        let entry_points = self.entry_point_ids()?;
+        let mut distances = entry_points
+            .iter()
+            .map(|id| (*id, 0))
+            .collect::<HashMap<_, _>>();
+        let mut frontier = entry_points.clone();
+        let mut distance = 0;
+        let mut truncated = false;
+        'expand: while !frontier.is_empty() {
+            distance += 1;
+            let mut next_frontier = Vec::new();
+            for chunk in frontier.chunks(EDGE_NODE_LOOKUP_BATCH_SIZE) {
+                for neighbor in trail::frontier_neighbors(
+                    self,
+                    chunk,
+                    TrailDirection::Outgoing,
+                    &[EdgeKind::CALL],
+                )? {
+                    if distances.contains_key(&neighbor) {
+                        continue;
+                    }
+                    if distances.len() >= ENTRY_POINT_NODE_BUDGET {
+                        truncated = true;
+                        break 'expand;
+                    }
+                    distances.insert(neighbor, distance);
+                    next_frontier.push(neighbor);
+                }
+            }
+            frontier = next_frontier;
+        }
+        Ok(EntryPointDistances {
+            entry_points,
+            distances,
+            truncated,
+        })
+    }
+}
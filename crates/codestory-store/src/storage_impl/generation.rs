@@ -0,0 +1,49 @@
+use super::*;
+
+impl Storage {
+    /// Counter that grows every time indexed graph data changes.
+    ///
+    /// Anything derived from the graph can be cached under this value and
+    /// reused for as long as it stays the same. Databases that never changed
+    /// report zero.
+    pub fn generation(&self) -> Result<u64, StorageError> {
+        let generation: Option<i64> = self
+            .conn
+            .query_row(
+                "SELECT generation FROM storage_meta WHERE id = 1",
+                [],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(generation.map_or(0, |generation| generation.max(0) as u64))
+    }
+
+    /// Move this database's generation past the one at `source_path`.
+    ///
+    /// A staged database that replaces the live one starts counting from
+    /// zero; carrying the live generation forward keeps the counter
+    /// increasing across the swap, so caches keyed by it see the change.
+    pub fn copy_generation_from(&mut self, source_path: &Path) -> Result<u64, StorageError> {
+        let source_generation = if source_path.exists() {
+            Storage::open(source_path)?.generation()?
+        } else {
+            0
+        };
+        let generation = self.generation()?.max(source_generation) + 1;
+        self.conn.execute(
+            "INSERT INTO storage_meta (id, generation) VALUES (1, ?1)
+             ON CONFLICT(id) DO UPDATE SET generation = excluded.generation",
+            params![generation as i64],
+        )?;
+        Ok(generation)
+    }
+}
+
+pub(super) fn bump_generation_on(conn: &Connection) -> Result<(), StorageError> {
+    conn.execute(
+        "INSERT INTO storage_meta (id, generation) VALUES (1, 1)
+         ON CONFLICT(id) DO UPDATE SET generation = generation + 1",
+        [],
+    )?;
+    Ok(())
+}
@@ -0,0 +1,126 @@
+use super::*;
+
+/// Which edges [`Storage::match_edges`] returns. Unset fields match anything.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GraphMatchFilter {
+    /// Leading segments of the source's qualified name, or of its serialized
+    /// name when it has none. `app::db` matches `app::db` and names under it
+    /// after a `::` or `.`, but not `app::dbx`.
+    pub from_prefix: Option<String>,
+    pub from_kind: Option<NodeKind>,
+    pub edge_kind: Option<EdgeKind>,
+    /// Leading segments of the target's qualified or serialized name.
+    pub to_prefix: Option<String>,
+    pub to_kind: Option<NodeKind>,
+}
+
+/// A source node with one of its matching edges.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GraphMatch {
+    pub source: NodeId,
+    pub source_name: String,
+    pub source_kind: NodeKind,
+    pub source_file: Option<String>,
+    pub source_line: Option<u32>,
+    pub target: NodeId,
+    pub target_name: String,
+    pub edge_kind: EdgeKind,
+    pub edge_line: Option<u32>,
+}
+
+/// The matches of [`Storage::match_edges`], and whether more sources matched
+/// than the limit allowed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GraphMatches {
+    pub matches: Vec<GraphMatch>,
+    pub truncated: bool,
+}
+
+impl Storage {
+    /// Source nodes with at least one edge matching `filter`, ordered by node
+    /// id, each reported once with its lowest-id matching edge. Edges are
+    /// matched on their resolved endpoints when they have them.
+    pub fn match_edges(
+        &self,
+        filter: &GraphMatchFilter,
+        limit: usize,
+    ) -> Result<GraphMatches, StorageError> {
+        let mut conditions = Vec::new();
+        let mut values = Vec::<rusqlite::types::Value>::new();
+        let mut bind = |value: rusqlite::types::Value| {
+            values.push(value);
+            values.len()
+        };
+        if let Some(kind) = filter.edge_kind {
+            conditions.push(format!("e.kind = ?{}", bind((kind as i64).into())));
+        }
+        for (alias, kind, prefix) in [
+            ("s", filter.from_kind, filter.from_prefix.as_deref()),
+            ("t", filter.to_kind, filter.to_prefix.as_deref()),
+        ] {
+            if let Some(kind) = kind {
+                conditions.push(format!("{alias}.kind = ?{}", bind((kind as i64).into())));
+            }
+            let prefix = prefix.map(|prefix| prefix.trim().trim_end_matches([':', '.']));
+            if let Some(prefix) = prefix.filter(|prefix| !prefix.is_empty()) {
+                // Ranges on the indexed name expression: the name itself, or
+                // a name continuing after `::` (`;` follows `:`) or `.` (`/`
+                // follows `.`).
+                let name = format!("COALESCE({alias}.qualified_name, {alias}.serialized_name)");
+                let exact = bind(prefix.to_string().into());
+                let ranges = [("::", ":;"), (".", "/")]
+                    .into_iter()
+                    .map(|(separator, end)| {
+                        let low = bind(format!("{prefix}{separator}").into());
+                        let high = bind(format!("{prefix}{end}").into());
+                        format!("({name} >= ?{low} AND {name} < ?{high})")
+                    })
+                    .collect::<Vec<_>>();
+                conditions.push(format!("({name} = ?{exact} OR {})", ranges.join(" OR ")));
+            }
+        }
+        let limit_param = bind((limit as i64 + 1).into());
+        let where_clause = if conditions.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", conditions.join(" AND "))
+        };
+        // SQLite takes the bare columns of a `MIN()` aggregate from the row
+        // holding the minimum, so each group reports its lowest-id edge. The
+        // endpoint joins are spelled out so the edge endpoint indexes apply.
+        let query = format!(
+            "SELECT s.id, COALESCE(s.qualified_name, s.serialized_name), s.kind,
+                    (SELECT path FROM file WHERE file.id = s.file_node_id), s.start_line,
+                    t.id, COALESCE(t.qualified_name, t.serialized_name),
+                    e.kind, e.line, MIN(e.id)
+             FROM edge e
+             JOIN node s ON (e.resolved_source_node_id = s.id
+                             OR (e.resolved_source_node_id IS NULL AND e.source_node_id = s.id))
+             JOIN node t ON (e.resolved_target_node_id = t.id
+                             OR (e.resolved_target_node_id IS NULL AND e.target_node_id = t.id))
+             {where_clause}
+             GROUP BY s.id
+             ORDER BY s.id
+             LIMIT ?{limit_param}"
+        );
+        let mut stmt = self.conn.prepare(&query)?;
+        let mut rows = stmt.query(params_from_iter(values))?;
+        let mut matches = Vec::new();
+        while let Some(row) = rows.next()? {
+            matches.push(GraphMatch {
+                source: NodeId(row.get(0)?),
+                source_name: row.get(1)?,
+                source_kind: NodeKind::try_from(row.get::<_, i32>(2)?)?,
+                source_file: row.get(3)?,
+                source_line: row.get(4)?,
+                target: NodeId(row.get(5)?),
+                target_name: row.get(6)?,
+                edge_kind: EdgeKind::try_from(row.get::<_, i32>(7)?)?,
+                edge_line: row.get(8)?,
+            });
+        }
+        let truncated = matches.len() > limit;
+        matches.truncate(limit);
+        Ok(GraphMatches { matches, truncated })
+    }
+}
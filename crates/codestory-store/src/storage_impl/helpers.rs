@@ -1,5 +1,6 @@
 use crate::StorageError;
 use codestory_contracts::graph::NodeId;
+use std::path::Path;
 
 const STORED_VECTOR_ENCODING_ENV: &str = "CODESTORY_STORED_VECTOR_ENCODING";
 const EMBEDDING_BLOB_MAGIC: &[u8; 4] = b"CSE1";
@@ -188,6 +189,74 @@ fn decode_float32_embedding_blob(blob: &[u8]) -> Result<Vec<f32>, StorageError>
     Ok(out)
 }
 
+/// Comparison key for stored file paths.
+///
+/// Separators become `/`, Windows verbatim prefixes (`\\?\C:`,
+/// `\\?\UNC\server`) are dropped, the drive letter is uppercased, and case is
+/// folded on platforms whose default filesystems ignore it.
+pub(crate) fn file_path_lookup_key(path: &str) -> String {
+    file_path_lookup_key_with_case(path, cfg!(any(windows, target_os = "macos")))
+}
+
+fn file_path_lookup_key_with_case(path: &str, fold_case: bool) -> String {
+    let mut key = path.replace('\\', "/");
+    if let Some(rest) = key.strip_prefix("//?/UNC/") {
+        key = format!("//{rest}");
+    } else if let Some(rest) = key.strip_prefix("//?/") {
+        key = rest.to_string();
+    }
+    if key.len() > 1 && key.ends_with('/') && !key.ends_with(":/") {
+        key.truncate(key.trim_end_matches('/').len().max(1));
+    }
+    if key.as_bytes().get(1) == Some(&b':') && key.as_bytes()[0].is_ascii_alphabetic() {
+        key[..1].make_ascii_uppercase();
+    }
+    if fold_case {
+        key = key.to_lowercase();
+    }
+    key
+}
+
+/// Directories between `root` and the file at `path`, outermost first, each
+/// as a `/`-joined prefix. A leading directory named in `package_roots` (such
+/// as `src`) is skipped. Paths outside `root` have no chain.
+pub(crate) fn directory_package_chain(
+    root: &Path,
+    path: &Path,
+    package_roots: &[String],
+) -> Vec<String> {
+    let Some(parent) = path.strip_prefix(root).ok().and_then(Path::parent) else {
+        return Vec::new();
+    };
+    let mut segments = parent
+        .components()
+        .filter_map(|component| match component {
+            std::path::Component::Normal(segment) => Some(segment.to_string_lossy().to_string()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    if segments
+        .first()
+        .is_some_and(|first| package_roots.iter().any(|root| root == first))
+    {
+        segments.remove(0);
+    }
+    (1..=segments.len())
+        .map(|len| segments[..len].join("/"))
+        .collect()
+}
+
+/// FNV-1a id for nodes and edges the store synthesizes itself, matching the
+/// indexer's id scheme.
+pub(crate) fn stable_synthetic_id(key: &str) -> i64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in key.as_bytes() {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    hash as i64
+}
+
 fn l2_normalize(values: &mut [f32]) {
     let norm = values.iter().map(|value| value * value).sum::<f32>().sqrt();
     if norm <= f32::EPSILON {
@@ -202,6 +271,37 @@ fn l2_normalize(values: &mut [f32]) {
 mod tests {
     use super::*;
 
+    #[test]
+    fn directory_package_chain_skips_package_roots() {
+        let root = Path::new("/repo");
+        let roots = vec!["src".to_string()];
+        let chain = |path: &str| directory_package_chain(root, Path::new(path), &roots);
+        assert_eq!(
+            chain("/repo/src/ui/forms/input.ts"),
+            vec!["ui".to_string(), "ui/forms".to_string()]
+        );
+        assert_eq!(chain("/repo/tools/build.js"), vec!["tools".to_string()]);
+        assert!(chain("/repo/src/index.ts").is_empty());
+        assert!(chain("/elsewhere/a/b.ts").is_empty());
+    }
+
+    #[test]
+    fn file_path_lookup_key_normalizes_separators_prefixes_and_case() {
+        let key = |path| file_path_lookup_key_with_case(path, false);
+        assert_eq!(key(r"c:\Repo\src\Main.rs"), "C:/Repo/src/Main.rs");
+        assert_eq!(key(r"\\?\C:\Repo\src\Main.rs"), "C:/Repo/src/Main.rs");
+        assert_eq!(key(r"\\?\UNC\server\share\lib.rs"), "//server/share/lib.rs");
+        assert_eq!(key(r"\\server\share\lib.rs"), "//server/share/lib.rs");
+        assert_eq!(key("/repo/src/"), "/repo/src");
+        assert_eq!(key("/"), "/");
+        assert_eq!(key("C:/"), "C:/");
+        assert_eq!(
+            file_path_lookup_key_with_case(r"C:\Repo\Main.rs", true),
+            file_path_lookup_key_with_case("c:/repo/main.rs", true)
+        );
+        assert_ne!(key("/repo/Main.rs"), key("/repo/main.rs"));
+    }
+
     #[test]
     fn test_decode_legacy_float32_embedding_blob() {
         let values = [0.25, -0.5, 0.75];
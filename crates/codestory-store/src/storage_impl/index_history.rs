@@ -0,0 +1,339 @@
+use super::*;
+
+/// Indexing runs whose changes are kept; older runs are pruned as new ones
+/// are recorded.
+pub const INDEX_HISTORY_RETAINED_RUNS: usize = 20;
+
+const HISTORY_ENTITY_NODE: i64 = 0;
+const HISTORY_ENTITY_EDGE: i64 = 1;
+
+/// One recorded indexing run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexHistoryRun {
+    pub run_id: String,
+    pub recorded_at_epoch_ms: i64,
+}
+
+/// What an [`IndexHistoryEvent`] added or removed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IndexHistorySubject {
+    Node {
+        id: NodeId,
+        kind: NodeKind,
+        /// Qualified name, or the serialized name when there is none.
+        name: String,
+    },
+    Edge {
+        id: EdgeId,
+        kind: EdgeKind,
+        source: NodeId,
+        /// The resolved target when the edge had one.
+        target: NodeId,
+    },
+}
+
+/// A node or edge that one indexing run added or removed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexHistoryEvent {
+    pub run_id: String,
+    pub recorded_at_epoch_ms: i64,
+    /// `false` when the run removed the subject.
+    pub added: bool,
+    pub subject: IndexHistorySubject,
+}
+
+/// Node and edge ids present right after one recorded run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexGraphAt {
+    pub run: IndexHistoryRun,
+    pub node_ids: Vec<NodeId>,
+    pub edge_ids: Vec<EdgeId>,
+}
+
+impl Storage {
+    /// Record the nodes and edges this store adds and removes relative to the
+    /// index at `previous_path` as run `run_id`.
+    ///
+    /// Call on a staged store before it replaces `previous_path`. When there
+    /// is no previous index the run is recorded without changes, as the
+    /// baseline later runs are diffed against. With `changed_file_ids`, as on
+    /// an incremental run, only those files, their nodes, and their edges are
+    /// diffed; nodes no file owns are then not tracked.
+    pub fn record_index_history(
+        &mut self,
+        previous_path: &Path,
+        run_id: &str,
+        recorded_at_epoch_ms: i64,
+        changed_file_ids: Option<&[i64]>,
+    ) -> Result<IndexHistoryRun, StorageError> {
+        let attached = previous_path.exists();
+        if attached {
+            let source = previous_path.to_string_lossy().to_string();
+            self.conn
+                .execute("ATTACH DATABASE ?1 AS history_source", params![source])?;
+        }
+        let record_result =
+            self.record_index_history_run(attached, run_id, recorded_at_epoch_ms, changed_file_ids);
+        let detach_result = if attached {
+            self.conn
+                .execute("DETACH DATABASE history_source", [])
+                .map(|_| ())
+        } else {
+            Ok(())
+        };
+        record_result?;
+        detach_result?;
+        Ok(IndexHistoryRun {
+            run_id: run_id.to_string(),
+            recorded_at_epoch_ms,
+        })
+    }
+
+    fn record_index_history_run(
+        &mut self,
+        diff_against_previous: bool,
+        run_id: &str,
+        recorded_at_epoch_ms: i64,
+        changed_file_ids: Option<&[i64]>,
+    ) -> Result<(), StorageError> {
+        let tx = self.conn.transaction()?;
+        let (node_scope, edge_scope) = match changed_file_ids {
+            Some(file_ids) => {
+                tx.execute(
+                    "CREATE TEMP TABLE IF NOT EXISTS index_history_scope (
+                        file_id INTEGER PRIMARY KEY
+                     )",
+                    [],
+                )?;
+                tx.execute("DELETE FROM temp.index_history_scope", [])?;
+                let mut stmt = tx.prepare(
+                    "INSERT OR IGNORE INTO temp.index_history_scope (file_id) VALUES (?1)",
+                )?;
+                for file_id in file_ids {
+                    stmt.execute(params![file_id])?;
+                }
+                (
+                    "AND (n.file_node_id IN (SELECT file_id FROM temp.index_history_scope)
+                          OR n.id IN (SELECT file_id FROM temp.index_history_scope))",
+                    "AND e.file_node_id IN (SELECT file_id FROM temp.index_history_scope)",
+                )
+            }
+            None => ("", ""),
+        };
+        tx.execute(
+            "DELETE FROM index_history_change
+             WHERE run_seq IN (SELECT seq FROM index_history_run WHERE run_id = ?1)",
+            params![run_id],
+        )?;
+        tx.execute(
+            "DELETE FROM index_history_run WHERE run_id = ?1",
+            params![run_id],
+        )?;
+        tx.execute(
+            "INSERT INTO index_history_run (run_id, recorded_at_epoch_ms) VALUES (?1, ?2)",
+            params![run_id, recorded_at_epoch_ms],
+        )?;
+        let seq = tx.last_insert_rowid();
+        if diff_against_previous {
+            for (added, present, absent) in
+                [(1, "main", "history_source"), (0, "history_source", "main")]
+            {
+                tx.execute(
+                    &format!(
+                        "INSERT INTO index_history_change (
+                            run_seq, entity, entity_id, added, kind, name
+                         )
+                         SELECT ?1, {HISTORY_ENTITY_NODE}, n.id, {added}, n.kind,
+                                COALESCE(n.qualified_name, n.serialized_name)
+                         FROM {present}.node n
+                         WHERE NOT EXISTS (SELECT 1 FROM {absent}.node o WHERE o.id = n.id)
+                         {node_scope}"
+                    ),
+                    params![seq],
+                )?;
+                tx.execute(
+                    &format!(
+                        "INSERT INTO index_history_change (
+                            run_seq, entity, entity_id, added, kind,
+                            source_node_id, target_node_id
+                         )
+                         SELECT ?1, {HISTORY_ENTITY_EDGE}, e.id, {added}, e.kind,
+                                COALESCE(e.resolved_source_node_id, e.source_node_id),
+                                COALESCE(e.resolved_target_node_id, e.target_node_id)
+                         FROM {present}.edge e
+                         WHERE NOT EXISTS (SELECT 1 FROM {absent}.edge o WHERE o.id = e.id)
+                         {edge_scope}"
+                    ),
+                    params![seq],
+                )?;
+            }
+        }
+        tx.execute(
+            "DELETE FROM index_history_run
+             WHERE seq <= (SELECT MAX(seq) FROM index_history_run) - ?1",
+            params![INDEX_HISTORY_RETAINED_RUNS as i64],
+        )?;
+        tx.execute(
+            "DELETE FROM index_history_change
+             WHERE run_seq NOT IN (SELECT seq FROM index_history_run)",
+            [],
+        )?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Copy recorded history from another index, such as the live index a
+    /// full refresh replaces. Returns the number of runs copied.
+    pub fn copy_index_history_from(&mut self, source_path: &Path) -> Result<usize, StorageError> {
+        if !source_path.exists() {
+            return Ok(0);
+        }
+        drop(Storage::open(source_path)?);
+        let source = source_path.to_string_lossy().to_string();
+        self.conn
+            .execute("ATTACH DATABASE ?1 AS source_snapshot", params![source])?;
+        let copy_result = (|| -> Result<usize, StorageError> {
+            let tx = self.conn.transaction()?;
+            tx.execute("DELETE FROM index_history_change", [])?;
+            tx.execute("DELETE FROM index_history_run", [])?;
+            let copied = tx.execute(
+                "INSERT INTO index_history_run (seq, run_id, recorded_at_epoch_ms)
+                 SELECT seq, run_id, recorded_at_epoch_ms
+                 FROM source_snapshot.index_history_run",
+                [],
+            )?;
+            tx.execute(
+                "INSERT INTO index_history_change
+                 SELECT * FROM source_snapshot.index_history_change",
+                [],
+            )?;
+            tx.commit()?;
+            Ok(copied)
+        })();
+        let detach_result = self.conn.execute("DETACH DATABASE source_snapshot", []);
+        let copied = copy_result?;
+        detach_result?;
+        Ok(copied)
+    }
+
+    /// Recorded runs, oldest first.
+    pub fn index_history_runs(&self) -> Result<Vec<IndexHistoryRun>, StorageError> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT run_id, recorded_at_epoch_ms FROM index_history_run ORDER BY seq")?;
+        let runs = stmt
+            .query_map([], |row| {
+                Ok(IndexHistoryRun {
+                    run_id: row.get(0)?,
+                    recorded_at_epoch_ms: row.get(1)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(runs)
+    }
+
+    /// When `node_id` and the edges touching it were added or removed, oldest
+    /// first, within the retained runs.
+    pub fn node_history(&self, node_id: NodeId) -> Result<Vec<IndexHistoryEvent>, StorageError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT r.run_id, r.recorded_at_epoch_ms, c.entity, c.entity_id, c.added, c.kind,
+                    c.name, c.source_node_id, c.target_node_id
+             FROM index_history_change c
+             JOIN index_history_run r ON r.seq = c.run_seq
+             WHERE (c.entity = ?1 AND c.entity_id = ?3)
+                OR (c.entity = ?2 AND (c.source_node_id = ?3 OR c.target_node_id = ?3))
+             ORDER BY c.run_seq, c.entity, c.entity_id",
+        )?;
+        let mut rows = stmt.query(params![HISTORY_ENTITY_NODE, HISTORY_ENTITY_EDGE, node_id.0])?;
+        let mut events = Vec::new();
+        while let Some(row) = rows.next()? {
+            let id = row.get::<_, i64>(3)?;
+            let kind = row.get::<_, i32>(5)?;
+            let subject = if row.get::<_, i64>(2)? == HISTORY_ENTITY_NODE {
+                IndexHistorySubject::Node {
+                    id: NodeId(id),
+                    kind: NodeKind::try_from(kind)?,
+                    name: row.get::<_, Option<String>>(6)?.unwrap_or_default(),
+                }
+            } else {
+                IndexHistorySubject::Edge {
+                    id: EdgeId(id),
+                    kind: EdgeKind::try_from(kind)?,
+                    source: NodeId(row.get(7)?),
+                    target: NodeId(row.get(8)?),
+                }
+            };
+            events.push(IndexHistoryEvent {
+                run_id: row.get(0)?,
+                recorded_at_epoch_ms: row.get(1)?,
+                added: row.get::<_, i64>(4)? != 0,
+                subject,
+            });
+        }
+        Ok(events)
+    }
+
+    /// The node and edge ids present right after run `run_id`, rebuilt by
+    /// undoing every later run. `None` when the run is not retained.
+    pub fn graph_at(&self, run_id: &str) -> Result<Option<IndexGraphAt>, StorageError> {
+        let run = self
+            .conn
+            .query_row(
+                "SELECT seq, recorded_at_epoch_ms FROM index_history_run WHERE run_id = ?1",
+                params![run_id],
+                |row| Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?)),
+            )
+            .optional()?;
+        let Some((seq, recorded_at_epoch_ms)) = run else {
+            return Ok(None);
+        };
+        let mut node_ids = self
+            .conn
+            .prepare("SELECT id FROM node")?
+            .query_map([], |row| row.get::<_, i64>(0))?
+            .collect::<Result<HashSet<_>, _>>()?;
+        let mut edge_ids = self
+            .conn
+            .prepare("SELECT id FROM edge")?
+            .query_map([], |row| row.get::<_, i64>(0))?
+            .collect::<Result<HashSet<_>, _>>()?;
+        // The first later change to each entity says whether it existed at
+        // `seq`: a later addition means it did not, a later removal that it did.
+        let mut first_later_change = HashMap::<(i64, i64), bool>::new();
+        let mut stmt = self.conn.prepare(
+            "SELECT entity, entity_id, added FROM index_history_change
+             WHERE run_seq > ?1
+             ORDER BY run_seq",
+        )?;
+        let mut rows = stmt.query(params![seq])?;
+        while let Some(row) = rows.next()? {
+            first_later_change
+                .entry((row.get(0)?, row.get(1)?))
+                .or_insert(row.get::<_, i64>(2)? != 0);
+        }
+        for ((entity, id), added_later) in first_later_change {
+            let ids = if entity == HISTORY_ENTITY_NODE {
+                &mut node_ids
+            } else {
+                &mut edge_ids
+            };
+            if added_later {
+                ids.remove(&id);
+            } else {
+                ids.insert(id);
+            }
+        }
+        let mut node_ids = node_ids.into_iter().map(NodeId).collect::<Vec<_>>();
+        node_ids.sort_unstable_by_key(|id| id.0);
+        let mut edge_ids = edge_ids.into_iter().map(EdgeId).collect::<Vec<_>>();
+        edge_ids.sort_unstable_by_key(|id| id.0);
+        Ok(Some(IndexGraphAt {
+            run: IndexHistoryRun {
+                run_id: run_id.to_string(),
+                recorded_at_epoch_ms,
+            },
+            node_ids,
+            edge_ids,
+        }))
+    }
+}
@@ -0,0 +1,590 @@
+use super::*;
+
+/// `canonical_id` prefix of the nodes standing in for Java types declared
+/// outside the project.
+pub(super) const EXTERNAL_JAVA_TYPE_CANONICAL_PREFIX: &str = "external_java_type:";
+
+/// Confidence of a link the file names unambiguously: a qualified name, a
+/// single-type import, the file's own package, or `java.lang`.
+const JAVA_TYPE_CERTAIN_CONFIDENCE: f32 = 0.95;
+
+/// Confidence of a link reached through a wildcard import or guessed for a
+/// type whose package the file never names.
+const JAVA_TYPE_PROBABLE_CONFIDENCE: f32 = 0.6;
+
+/// `java.lang` types visible in every file without an import.
+const JAVA_LANG_TYPES: &[&str] = &[
+    "AutoCloseable",
+    "CharSequence",
+    "Cloneable",
+    "Comparable",
+    "Enum",
+    "Error",
+    "Exception",
+    "Iterable",
+    "Number",
+    "Object",
+    "Record",
+    "Runnable",
+    "RuntimeException",
+    "Thread",
+    "Throwable",
+];
+
+/// Edges [`Storage::link_java_types`] pointed at a type.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct JavaTypeLinks {
+    /// Edges whose target is a type declared in the project.
+    pub project: usize,
+    /// Edges whose target is the shared node of a type outside the project.
+    pub external: usize,
+}
+
+#[derive(Default)]
+struct JavaFile {
+    path: PathBuf,
+    package: Option<String>,
+    /// Import names as written, without `static` or a trailing `.*`.
+    imports: Vec<String>,
+    /// Simple and qualified name of each type declared in the file.
+    declared: Vec<(String, String)>,
+}
+
+struct JavaEdge {
+    id: i64,
+    source: i64,
+    target: i64,
+    kind: i32,
+    /// File of the source node.
+    file_id: i64,
+    /// What the edge is linked to now, if anything.
+    resolved: Option<JavaEdgeTarget>,
+}
+
+enum JavaEdgeTarget {
+    /// The linked node no longer exists.
+    Removed,
+    /// A project node, with its file.
+    Declared(Option<i64>),
+    /// The shared node of an external type, by qualified name.
+    External(String),
+}
+
+struct JavaTypeLink {
+    edge_id: i64,
+    target: NodeId,
+    certain: bool,
+}
+
+impl Storage {
+    /// Resolve Java imports and `extends`/`implements` targets by fully
+    /// qualified name.
+    ///
+    /// A type's qualified name is its package plus its name, with nested
+    /// types under their enclosing type (`com.acme.Outer.Inner`). The package
+    /// is the file's `package` declaration or, without one, the file's
+    /// directory under the deepest of `source_roots` holding it. Supertypes
+    /// are looked up among the types the file declares, then through its
+    /// single-type imports, its own package, its wildcard imports, and
+    /// `java.lang`, in that order. Imports and supertypes naming no project
+    /// type point at one file-less node per qualified name, so every class
+    /// extending `java.io.Serializable` shares the same parent; those nodes
+    /// carry an `external_java_type:` canonical id and are badged external.
+    ///
+    /// With `changed_files`, as after an incremental run, only edges that
+    /// may link differently are revisited: those from the changed files,
+    /// those whose target was removed or lives in a changed file, and those
+    /// on an external type a changed file now declares. The returned counts
+    /// cover the revisited edges.
+    pub fn link_java_types(
+        &mut self,
+        source_roots: &[PathBuf],
+        changed_files: Option<&HashSet<i64>>,
+    ) -> Result<JavaTypeLinks, StorageError> {
+        let mut files = {
+            let mut stmt = self
+                .conn
+                .prepare("SELECT id, path FROM file WHERE language = 'java'")?;
+            let rows = stmt.query_map([], |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    JavaFile {
+                        path: PathBuf::from(row.get::<_, String>(1)?),
+                        ..Default::default()
+                    },
+                ))
+            })?;
+            rows.collect::<Result<HashMap<_, _>, _>>()?
+        };
+        if files.is_empty() {
+            self.replace_synthetic_packages(EXTERNAL_JAVA_TYPE_CANONICAL_PREFIX, &[], &[])?;
+            return Ok(JavaTypeLinks::default());
+        }
+
+        // (id, kind, name, file)
+        let nodes = {
+            let mut stmt = self.conn.prepare(
+                "SELECT node.id, node.kind, node.serialized_name, node.file_node_id
+                 FROM node JOIN file ON file.id = node.file_node_id
+                 WHERE file.language = 'java' AND node.kind IN (?1, ?2, ?3, ?4, ?5)
+                 ORDER BY node.id",
+            )?;
+            let rows = stmt.query_map(
+                params![
+                    NodeKind::MODULE as i32,
+                    NodeKind::CLASS as i32,
+                    NodeKind::INTERFACE as i32,
+                    NodeKind::ENUM as i32,
+                    NodeKind::ANNOTATION as i32,
+                ],
+                |row| {
+                    Ok((
+                        row.get::<_, i64>(0)?,
+                        row.get::<_, i32>(1)?,
+                        row.get::<_, String>(2)?,
+                        row.get::<_, i64>(3)?,
+                    ))
+                },
+            )?;
+            rows.collect::<Result<Vec<_>, _>>()?
+        };
+        let edges = {
+            let mut stmt = self.conn.prepare(
+                "SELECT edge.id, edge.source_node_id, edge.target_node_id, edge.kind,
+                        source.file_node_id, edge.resolved_target_node_id,
+                        resolved.id IS NOT NULL, resolved.file_node_id,
+                        resolved.canonical_id, resolved.serialized_name
+                 FROM edge
+                 JOIN node source ON source.id = edge.source_node_id
+                 JOIN file ON file.id = source.file_node_id
+                 LEFT JOIN node resolved ON resolved.id = edge.resolved_target_node_id
+                 WHERE file.language = 'java' AND edge.kind IN (?1, ?2, ?3, ?4)
+                 ORDER BY edge.id",
+            )?;
+            let rows = stmt.query_map(
+                params![
+                    EdgeKind::IMPORT as i32,
+                    EdgeKind::INHERITANCE as i32,
+                    EdgeKind::ANNOTATION_USAGE as i32,
+                    EdgeKind::MEMBER as i32,
+                ],
+                |row| {
+                    let resolved_id = row.get::<_, Option<i64>>(5)?;
+                    let resolved = match resolved_id {
+                        None => None,
+                        Some(_) if !row.get::<_, bool>(6)? => Some(JavaEdgeTarget::Removed),
+                        Some(_) => {
+                            let canonical_id = row.get::<_, Option<String>>(8)?;
+                            if canonical_id.as_deref().is_some_and(|canonical_id| {
+                                canonical_id.starts_with(EXTERNAL_JAVA_TYPE_CANONICAL_PREFIX)
+                            }) {
+                                Some(JavaEdgeTarget::External(row.get(9)?))
+                            } else {
+                                Some(JavaEdgeTarget::Declared(row.get(7)?))
+                            }
+                        }
+                    };
+                    Ok(JavaEdge {
+                        id: row.get(0)?,
+                        source: row.get(1)?,
+                        target: row.get(2)?,
+                        kind: row.get(3)?,
+                        file_id: row.get(4)?,
+                        resolved,
+                    })
+                },
+            )?;
+            rows.collect::<Result<Vec<_>, _>>()?
+        };
+
+        let imports = edges
+            .iter()
+            .filter(|edge| edge.kind == EdgeKind::IMPORT as i32 && edge.source == edge.target)
+            .map(|edge| edge.source)
+            .collect::<HashSet<_>>();
+        // Type nodes standing for a supertype or annotation use rather than a
+        // declaration.
+        let references = edges
+            .iter()
+            .filter_map(|edge| {
+                if edge.kind == EdgeKind::INHERITANCE as i32 {
+                    Some(edge.target)
+                } else {
+                    (edge.kind == EdgeKind::ANNOTATION_USAGE as i32).then_some(edge.source)
+                }
+            })
+            .collect::<HashSet<_>>();
+        let node_info = nodes
+            .iter()
+            .map(|(id, kind, name, file)| (*id, (*kind, name.as_str(), *file)))
+            .collect::<HashMap<_, _>>();
+        let is_declaration = |id: &i64| {
+            node_info
+                .get(id)
+                .is_some_and(|(kind, _, _)| *kind != NodeKind::MODULE as i32)
+                && !references.contains(id)
+        };
+        // Enclosing type of each nested type declaration.
+        let outer_of = edges
+            .iter()
+            .filter(|edge| {
+                edge.kind == EdgeKind::MEMBER as i32
+                    && is_declaration(&edge.source)
+                    && is_declaration(&edge.target)
+            })
+            .map(|edge| (edge.target, edge.source))
+            .collect::<HashMap<_, _>>();
+
+        for (id, kind, name, file_id) in &nodes {
+            let Some(file) = files.get_mut(file_id) else {
+                continue;
+            };
+            if *kind != NodeKind::MODULE as i32 {
+                continue;
+            }
+            if imports.contains(id) {
+                file.imports.push(name.trim().to_string());
+            } else if file.package.is_none() {
+                file.package = Some(name.trim().to_string());
+            }
+        }
+        let mut roots = source_roots.iter().collect::<Vec<_>>();
+        roots.sort_by_key(|root| std::cmp::Reverse(root.components().count()));
+        for file in files.values_mut() {
+            if file.package.is_none() {
+                file.package = roots
+                    .iter()
+                    .find_map(|root| file.path.parent()?.strip_prefix(root).ok())
+                    .map(|dir| {
+                        dir.components()
+                            .map(|part| part.as_os_str().to_string_lossy())
+                            .collect::<Vec<_>>()
+                            .join(".")
+                    });
+            }
+        }
+
+        let mut types = HashMap::<String, NodeId>::new();
+        let mut packages = HashSet::<String>::new();
+        // Simple names of the types the changed files declare.
+        let mut changed_type_names = HashSet::<&str>::new();
+        for (id, _, name, file_id) in &nodes {
+            if !is_declaration(id) {
+                continue;
+            }
+            let Some(file) = files.get_mut(file_id) else {
+                continue;
+            };
+            let mut path = vec![name.trim()];
+            let mut outer = outer_of.get(id);
+            while let Some(outer_id) = outer {
+                // Guard against a malformed graph that nests a type in itself.
+                if path.len() > outer_of.len() {
+                    break;
+                }
+                path.push(node_info[outer_id].1.trim());
+                outer = outer_of.get(outer_id);
+            }
+            path.reverse();
+            let package = file.package.clone().unwrap_or_default();
+            let qualified = qualify(&package, &path.join("."));
+            packages.insert(package);
+            file.declared
+                .push((name.trim().to_string(), qualified.clone()));
+            types.entry(qualified).or_insert(NodeId(*id));
+            if changed_files.is_some_and(|changed| changed.contains(file_id)) {
+                changed_type_names.insert(name.trim());
+            }
+        }
+
+        // Which edges to revisit: all of them on a full pass, otherwise the
+        // ones a change can affect.
+        let revisit = |edge: &JavaEdge| {
+            let Some(changed) = changed_files else {
+                return true;
+            };
+            changed.contains(&edge.file_id)
+                || match &edge.resolved {
+                    None => true,
+                    Some(JavaEdgeTarget::Removed) => true,
+                    Some(JavaEdgeTarget::Declared(file_id)) => {
+                        file_id.is_none_or(|file_id| changed.contains(&file_id))
+                    }
+                    Some(JavaEdgeTarget::External(name)) => {
+                        let simple = name.rsplit('.').next().unwrap_or_default();
+                        changed_type_names.contains(simple) || packages.contains(name.as_str())
+                    }
+                }
+        };
+
+        let mut externals = BTreeMap::<String, Node>::new();
+        if changed_files.is_some() {
+            // Externals outside the revisited edges stay; start from them so
+            // an `implements` seen earlier keeps its `INTERFACE` kind.
+            for node in self.external_java_type_nodes()? {
+                externals.insert(node.serialized_name.clone(), node);
+            }
+        }
+        let mut links = Vec::new();
+        let mut counts = JavaTypeLinks::default();
+        let mut external = |name: String, kind: NodeKind, certain: bool| {
+            let canonical_id = format!("{EXTERNAL_JAVA_TYPE_CANONICAL_PREFIX}{name}");
+            let node = externals.entry(name.clone()).or_insert_with(|| Node {
+                id: NodeId(stable_synthetic_id(&canonical_id)),
+                kind,
+                serialized_name: name.clone(),
+                qualified_name: Some(name),
+                canonical_id: Some(canonical_id),
+                ..Default::default()
+            });
+            // Imports only guess `CLASS`; an `implements` clause settles it.
+            if kind == NodeKind::INTERFACE {
+                node.kind = kind;
+            }
+            (node.id, certain)
+        };
+        for edge in edges.iter().filter(|edge| revisit(edge)) {
+            let (source, target, kind) = (&edge.source, &edge.target, &edge.kind);
+            let Some((_, name, file_id)) = node_info.get(target) else {
+                continue;
+            };
+            let Some(file) = files.get(file_id) else {
+                continue;
+            };
+            let resolved =
+                if *kind == EdgeKind::ANNOTATION_USAGE as i32 || *kind == EdgeKind::MEMBER as i32 {
+                    continue;
+                } else if *kind == EdgeKind::IMPORT as i32 {
+                    if source != target {
+                        continue;
+                    }
+                    let name = name.trim();
+                    match project_import_target(&types, name) {
+                        Some(target) => Ok((target, true)),
+                        // Wildcard import of a project package.
+                        None if packages.contains(name) => continue,
+                        None => {
+                            let (name, kind) = external_import_type(name);
+                            Err(external(name, kind, true))
+                        }
+                    }
+                } else {
+                    let Some((kind, _, _)) = node_info.get(target) else {
+                        continue;
+                    };
+                    let name = name.split('<').next().unwrap_or_default().trim();
+                    match project_supertype(&types, &packages, file, name) {
+                        Some(target) => Ok(target),
+                        None => {
+                            let (name, certain) = external_supertype(&packages, file, name);
+                            let kind = if *kind == NodeKind::INTERFACE as i32 {
+                                NodeKind::INTERFACE
+                            } else {
+                                NodeKind::CLASS
+                            };
+                            Err(external(name, kind, certain))
+                        }
+                    }
+                };
+            let (target, certain) = match resolved {
+                Ok(target) => {
+                    counts.project += 1;
+                    target
+                }
+                Err(target) => {
+                    counts.external += 1;
+                    target
+                }
+            };
+            links.push(JavaTypeLink {
+                edge_id: edge.id,
+                target,
+                certain,
+            });
+        }
+
+        let externals = externals.into_values().collect::<Vec<_>>();
+        if changed_files.is_none() {
+            self.replace_synthetic_packages(EXTERNAL_JAVA_TYPE_CANONICAL_PREFIX, &externals, &[])?;
+        } else {
+            self.insert_nodes_batch(&externals)?;
+        }
+        let tx = self.conn.transaction()?;
+        {
+            let mut update = tx.prepare(
+                "UPDATE edge
+                 SET resolved_target_node_id = ?2, confidence = ?3, certainty = ?4,
+                     candidate_target_node_ids = NULL
+                 WHERE id = ?1",
+            )?;
+            for link in &links {
+                let confidence = if link.certain {
+                    JAVA_TYPE_CERTAIN_CONFIDENCE
+                } else {
+                    JAVA_TYPE_PROBABLE_CONFIDENCE
+                };
+                update.execute(params![
+                    link.edge_id,
+                    link.target.0,
+                    confidence,
+                    row_mapping::certainty_db_value(ResolutionCertainty::from_confidence(Some(
+                        confidence
+                    ))),
+                ])?;
+            }
+        }
+        if changed_files.is_some() {
+            // Drop externals no edge points at any more.
+            let orphans = {
+                let mut stmt = tx.prepare(
+                    "SELECT id FROM node
+                     WHERE canonical_id LIKE ?1
+                       AND id NOT IN (
+                           SELECT resolved_target_node_id FROM edge
+                           WHERE resolved_target_node_id IS NOT NULL
+                       )",
+                )?;
+                stmt.query_map(
+                    params![format!("{EXTERNAL_JAVA_TYPE_CANONICAL_PREFIX}%")],
+                    |row| row.get::<_, i64>(0),
+                )?
+                .collect::<Result<Vec<_>, _>>()?
+            };
+            delete_detached_nodes_on(&tx, &orphans)?;
+        }
+        tx.commit()?;
+        if changed_files.is_some() {
+            self.cache.nodes.lock().clear();
+        }
+        if !links.is_empty() {
+            self.invalidate_grounding_snapshots()?;
+        }
+        Ok(counts)
+    }
+
+    /// Shared nodes of the Java types linked to outside the project.
+    fn external_java_type_nodes(&self) -> Result<Vec<Node>, StorageError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, kind, serialized_name, canonical_id FROM node
+             WHERE canonical_id LIKE ?1
+             ORDER BY id",
+        )?;
+        let rows = stmt.query_map(
+            params![format!("{EXTERNAL_JAVA_TYPE_CANONICAL_PREFIX}%")],
+            |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, i32>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, String>(3)?,
+                ))
+            },
+        )?;
+        let mut nodes = Vec::new();
+        for row in rows {
+            let (id, kind, name, canonical_id) = row?;
+            nodes.push(Node {
+                id: NodeId(id),
+                kind: NodeKind::try_from(kind)?,
+                serialized_name: name.clone(),
+                qualified_name: Some(name),
+                canonical_id: Some(canonical_id),
+                ..Default::default()
+            });
+        }
+        Ok(nodes)
+    }
+}
+
+fn qualify(package: &str, name: &str) -> String {
+    if package.is_empty() {
+        name.to_string()
+    } else {
+        format!("{package}.{name}")
+    }
+}
+
+/// Project type an import names, either directly or as the owner of a
+/// statically imported member.
+fn project_import_target(types: &HashMap<String, NodeId>, name: &str) -> Option<NodeId> {
+    types.get(name).copied().or_else(|| {
+        let (owner, _) = name.rsplit_once('.')?;
+        types.get(owner).copied()
+    })
+}
+
+/// Project type a supertype written as `name` refers to from `file`, and
+/// whether the file names it unambiguously.
+fn project_supertype(
+    types: &HashMap<String, NodeId>,
+    packages: &HashSet<String>,
+    file: &JavaFile,
+    name: &str,
+) -> Option<(NodeId, bool)> {
+    let package = file.package.as_deref().unwrap_or_default();
+    if let Some((_, qualified)) = file.declared.iter().find(|(simple, _)| simple == name) {
+        return types.get(qualified).map(|target| (*target, true));
+    }
+    if name.contains('.') {
+        return types
+            .get(name)
+            .or_else(|| types.get(&qualify(package, name)))
+            .map(|target| (*target, true));
+    }
+    let suffix = format!(".{name}");
+    if let Some(import) = file.imports.iter().find(|import| import.ends_with(&suffix)) {
+        return types.get(import).map(|target| (*target, true));
+    }
+    if let Some(target) = types.get(&qualify(package, name)) {
+        return Some((*target, true));
+    }
+    file.imports
+        .iter()
+        .filter(|import| packages.contains(*import))
+        .find_map(|import| types.get(&qualify(import, name)))
+        .map(|target| (*target, false))
+}
+
+/// Qualified name of a supertype declared outside the project, and whether
+/// the file names its package.
+fn external_supertype(packages: &HashSet<String>, file: &JavaFile, name: &str) -> (String, bool) {
+    if name.contains('.') {
+        return (name.to_string(), true);
+    }
+    let suffix = format!(".{name}");
+    if let Some(import) = file.imports.iter().find(|import| import.ends_with(&suffix)) {
+        return (import.clone(), true);
+    }
+    if JAVA_LANG_TYPES.contains(&name) {
+        return (format!("java.lang.{name}"), true);
+    }
+    let mut wildcards = file
+        .imports
+        .iter()
+        .filter(|import| !packages.contains(*import) && !is_type_name(import));
+    match (wildcards.next(), wildcards.next()) {
+        (Some(package), None) => (qualify(package, name), false),
+        _ => (name.to_string(), false),
+    }
+}
+
+/// Qualified name and kind of the external node an unresolved import points
+/// at. Static member imports point at their owning type and wildcard imports
+/// at their package.
+fn external_import_type(name: &str) -> (String, NodeKind) {
+    let parts = name.split('.').collect::<Vec<_>>();
+    match parts
+        .iter()
+        .rposition(|part| part.starts_with(|c: char| c.is_ascii_uppercase()))
+    {
+        Some(last_type) => (parts[..=last_type].join("."), NodeKind::CLASS),
+        None => (name.to_string(), NodeKind::PACKAGE),
+    }
+}
+
+fn is_type_name(import: &str) -> bool {
+    import
+        .rsplit('.')
+        .next()
+        .is_some_and(|last| last.starts_with(|c: char| c.is_ascii_uppercase()))
+}
@@ -0,0 +1,220 @@
+use super::*;
+use std::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
+
+static PROCESS_INTEGRITY_CHECK: AtomicBool = AtomicBool::new(false);
+
+/// Most problems `PRAGMA integrity_check` reports before it stops scanning.
+const INTEGRITY_CHECK_MAX_PROBLEMS: u32 = 20;
+/// Edge rows below which a store without planner statistics is left alone.
+const ANALYZE_MIN_EDGE_ROWS: u64 = 5_000;
+/// Share of free pages above which a vacuum is worth its rewrite cost.
+const VACUUM_FREELIST_RATIO: f64 = 0.25;
+/// Reclaimable bytes below which a vacuum is never recommended.
+const VACUUM_MIN_RECLAIMABLE_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Run `PRAGMA integrity_check` on every live open this process performs.
+///
+/// Adapters call this once at startup when the operator asked for it. The
+/// check reads the whole database, so it is off by default.
+pub fn enable_process_integrity_check() {
+    PROCESS_INTEGRITY_CHECK.store(true, AtomicOrdering::Relaxed);
+}
+
+pub(super) fn process_integrity_check_enabled() -> bool {
+    PROCESS_INTEGRITY_CHECK.load(AtomicOrdering::Relaxed)
+}
+
+/// Page usage and planner statistics for one store.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StorageMaintenanceReport {
+    pub page_size: u64,
+    pub page_count: u64,
+    pub freelist_pages: u64,
+    pub database_bytes: u64,
+    pub reclaimable_bytes: u64,
+    /// Free pages as a share of all pages, in `0.0..=1.0`.
+    pub fragmentation: f64,
+    pub vacuum_recommended: bool,
+    /// Whether `ANALYZE` has recorded planner statistics.
+    pub analyzed: bool,
+    pub indexes: Vec<StorageIndexUsage>,
+}
+
+/// Planner statistics for one index, as recorded by the last `ANALYZE`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageIndexUsage {
+    pub name: String,
+    pub table: String,
+    /// Rows in the table when statistics were gathered.
+    pub analyzed_rows: Option<u64>,
+    /// Average rows sharing one value of the leading column; lower is more
+    /// selective.
+    pub rows_per_leading_key: Option<u64>,
+}
+
+impl Storage {
+    /// Run SQLite's full consistency check and return the problems it found.
+    ///
+    /// An empty list means the database is intact.
+    pub fn integrity_check(&self) -> Result<Vec<String>, StorageError> {
+        integrity_problems(&self.conn)
+    }
+
+    /// Summarize free-page fragmentation and per-index planner statistics.
+    pub fn maintenance_report(&self) -> Result<StorageMaintenanceReport, StorageError> {
+        let pragma = |name: &str| -> Result<u64, StorageError> {
+            let value: i64 = self
+                .conn
+                .query_row(&format!("PRAGMA {name}"), [], |row| row.get(0))?;
+            Ok(value.max(0) as u64)
+        };
+        let page_size = pragma("page_size")?;
+        let page_count = pragma("page_count")?;
+        let freelist_pages = pragma("freelist_count")?;
+        let reclaimable_bytes = freelist_pages.saturating_mul(page_size);
+        let fragmentation = if page_count == 0 {
+            0.0
+        } else {
+            freelist_pages as f64 / page_count as f64
+        };
+
+        let index_stats = self.index_statistics()?;
+        let mut statement = self.conn.prepare(
+            "SELECT name, tbl_name FROM sqlite_master
+             WHERE type = 'index' AND tbl_name NOT LIKE 'sqlite_%'
+             ORDER BY tbl_name, name",
+        )?;
+        let indexes = statement
+            .query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })?
+            .map(|row| {
+                let (name, table) = row?;
+                let stat = index_stats.get(&name);
+                Ok(StorageIndexUsage {
+                    analyzed_rows: stat.and_then(|values| values.first().copied()),
+                    rows_per_leading_key: stat.and_then(|values| values.get(1).copied()),
+                    name,
+                    table,
+                })
+            })
+            .collect::<Result<Vec<_>, StorageError>>()?;
+
+        Ok(StorageMaintenanceReport {
+            page_size,
+            page_count,
+            freelist_pages,
+            database_bytes: page_count.saturating_mul(page_size),
+            reclaimable_bytes,
+            fragmentation,
+            vacuum_recommended: fragmentation >= VACUUM_FREELIST_RATIO
+                && reclaimable_bytes >= VACUUM_MIN_RECLAIMABLE_BYTES,
+            analyzed: self.has_planner_statistics()?,
+            indexes,
+        })
+    }
+
+    /// Refresh the query planner statistics for every table and index.
+    pub fn analyze(&self) -> Result<(), StorageError> {
+        self.conn.execute_batch("ANALYZE")?;
+        Ok(())
+    }
+
+    /// Run `ANALYZE` when the edge table has drifted far from the row count
+    /// the planner last saw, which happens after large indexing runs.
+    ///
+    /// Returns whether statistics were refreshed.
+    pub fn analyze_if_stale(&self) -> Result<bool, StorageError> {
+        let live_edges: i64 = self
+            .conn
+            .query_row("SELECT COUNT(*) FROM edge", [], |row| row.get(0))?;
+        let live_edges = live_edges.max(0) as u64;
+        let analyzed_edges = if self.has_planner_statistics()? {
+            self.conn
+                .query_row(
+                    "SELECT stat FROM sqlite_stat1 WHERE tbl = 'edge' LIMIT 1",
+                    [],
+                    |row| row.get::<_, String>(0),
+                )
+                .optional()?
+                .and_then(|stat| parse_stat_counts(&stat).first().copied())
+        } else {
+            None
+        };
+        let stale = match analyzed_edges {
+            None => live_edges >= ANALYZE_MIN_EDGE_ROWS,
+            Some(analyzed) => {
+                live_edges.abs_diff(analyzed) >= ANALYZE_MIN_EDGE_ROWS.max(analyzed / 5)
+            }
+        };
+        if stale {
+            self.analyze()?;
+        }
+        Ok(stale)
+    }
+
+    /// Rebuild the database file to return free pages to the filesystem.
+    ///
+    /// This rewrites every page and needs as much free disk as the database
+    /// itself; check [`StorageMaintenanceReport::vacuum_recommended`] first.
+    pub fn vacuum(&self) -> Result<(), StorageError> {
+        self.conn.execute_batch("VACUUM")?;
+        Ok(())
+    }
+
+    fn has_planner_statistics(&self) -> Result<bool, StorageError> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = 'sqlite_stat1'",
+            [],
+            |row| row.get(0),
+        )?;
+        Ok(count > 0)
+    }
+
+    fn index_statistics(&self) -> Result<HashMap<String, Vec<u64>>, StorageError> {
+        if !self.has_planner_statistics()? {
+            return Ok(HashMap::new());
+        }
+        let mut statement = self
+            .conn
+            .prepare("SELECT idx, stat FROM sqlite_stat1 WHERE idx IS NOT NULL")?;
+        let rows = statement
+            .query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })?
+            .map(|row| row.map(|(index, stat)| (index, parse_stat_counts(&stat))))
+            .collect::<Result<HashMap<_, _>, _>>()?;
+        Ok(rows)
+    }
+}
+
+/// Fail an open when the consistency check reports problems.
+pub(super) fn verify_integrity(conn: &Connection) -> Result<(), StorageError> {
+    let problems = integrity_problems(conn)?;
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(StorageError::IntegrityCheckFailed(problems))
+    }
+}
+
+fn integrity_problems(conn: &Connection) -> Result<Vec<String>, StorageError> {
+    let mut statement = conn.prepare(&format!(
+        "PRAGMA integrity_check({INTEGRITY_CHECK_MAX_PROBLEMS})"
+    ))?;
+    let messages = statement
+        .query_map([], |row| row.get::<_, String>(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(match messages.as_slice() {
+        [only] if only == "ok" => Vec::new(),
+        _ => messages,
+    })
+}
+
+/// Leading integers of a `sqlite_stat1.stat` value; trailing flags such as
+/// `unordered` are ignored.
+fn parse_stat_counts(stat: &str) -> Vec<u64> {
+    stat.split_whitespace()
+        .map_while(|value| value.parse().ok())
+        .collect()
+}
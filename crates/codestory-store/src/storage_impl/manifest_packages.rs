@@ -0,0 +1,108 @@
+use super::*;
+
+/// `canonical_id` prefix of package nodes built from build manifests.
+const MANIFEST_PACKAGE_CANONICAL_PREFIX: &str = "manifest_package:";
+
+/// A package declared by a build manifest such as `Cargo.toml`,
+/// `package.json`, or `pom.xml`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManifestPackage {
+    /// Manifest family, e.g. `cargo`, `npm`, or `maven`.
+    pub ecosystem: String,
+    pub name: String,
+    /// Directory holding the manifest. Files beneath it belong to the
+    /// package unless a package nested deeper claims them.
+    pub root: PathBuf,
+    /// Packages this one depends on, by declared name.
+    pub dependencies: Vec<String>,
+}
+
+impl Storage {
+    /// Replace the package nodes synthesized from build manifests.
+    ///
+    /// Each package becomes a `PACKAGE` node with its indexed files as
+    /// `MEMBER`s and an `IMPORT` edge to every dependency that is itself a
+    /// package of the same ecosystem in `packages`. Other dependencies are
+    /// external and left out. Returns the number of package nodes written.
+    pub fn rebuild_manifest_packages(
+        &mut self,
+        packages: &[ManifestPackage],
+    ) -> Result<usize, StorageError> {
+        let mut nodes = BTreeMap::<(String, String), Node>::new();
+        let mut roots = Vec::new();
+        for package in packages {
+            let key = (package.ecosystem.clone(), package.name.clone());
+            if nodes.contains_key(&key) {
+                continue;
+            }
+            let canonical_id = format!(
+                "{MANIFEST_PACKAGE_CANONICAL_PREFIX}{}:{}",
+                package.ecosystem, package.name
+            );
+            let id = NodeId(stable_synthetic_id(&canonical_id));
+            nodes.insert(
+                key,
+                Node {
+                    id,
+                    kind: NodeKind::PACKAGE,
+                    serialized_name: package.name.clone(),
+                    qualified_name: Some(package.name.clone()),
+                    canonical_id: Some(canonical_id),
+                    ..Default::default()
+                },
+            );
+            roots.push((package.root.as_path(), id));
+        }
+        // Deepest root first, so nested packages claim their own files.
+        roots.sort_by_key(|(root, _)| std::cmp::Reverse(root.components().count()));
+
+        let files = {
+            let mut stmt = self
+                .conn
+                .prepare("SELECT id, path FROM file ORDER BY path")?;
+            let rows = stmt.query_map([], |row| {
+                Ok((NodeId(row.get(0)?), PathBuf::from(row.get::<_, String>(1)?)))
+            })?;
+            rows.collect::<Result<Vec<_>, _>>()?
+        };
+        let mut edges = Vec::new();
+        for (file_id, path) in files {
+            if let Some((_, package_id)) = roots.iter().find(|(root, _)| path.starts_with(root)) {
+                edges.push(manifest_package_edge(
+                    *package_id,
+                    file_id,
+                    EdgeKind::MEMBER,
+                ));
+            }
+        }
+        for package in packages {
+            let source = nodes[&(package.ecosystem.clone(), package.name.clone())].id;
+            for dependency in &package.dependencies {
+                if let Some(target) = nodes.get(&(package.ecosystem.clone(), dependency.clone()))
+                    && target.id != source
+                {
+                    edges.push(manifest_package_edge(source, target.id, EdgeKind::IMPORT));
+                }
+            }
+        }
+        edges.sort_by_key(|edge| edge.id.0);
+        edges.dedup_by_key(|edge| edge.id.0);
+
+        let nodes = nodes.into_values().collect::<Vec<_>>();
+        self.replace_synthetic_packages(MANIFEST_PACKAGE_CANONICAL_PREFIX, &nodes, &edges)?;
+        Ok(nodes.len())
+    }
+}
+
+fn manifest_package_edge(source: NodeId, target: NodeId, kind: EdgeKind) -> Edge {
+    Edge {
+        id: EdgeId(stable_synthetic_id(&format!(
+            "{MANIFEST_PACKAGE_CANONICAL_PREFIX}{kind:?}:{}:{}",
+            source.0, target.0
+        ))),
+        source,
+        target,
+        kind,
+        ..Default::default()
+    }
+}
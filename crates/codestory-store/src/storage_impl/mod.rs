@@ -1,7 +1,8 @@
 use codestory_contracts::graph::{
     AccessKind, Bookmark, BookmarkCategory, CallableProjectionState, Edge, EdgeId, EdgeKind,
-    EnumConversionError, FileCoverageReason, Node, NodeId, NodeKind, Occurrence, OccurrenceKind,
-    ResolutionCertainty, TrailCallerScope, TrailConfig, TrailDirection, TrailMode, TrailResult,
+    EnumConversionError, FileCoverageReason, Node, NodeId, NodeKind, NotebookCellPosition,
+    Occurrence, OccurrenceKind, ResolutionCertainty, TrailCallerScope, TrailConfig, TrailDirection,
+    TrailMode, TrailResult,
 };
 use codestory_contracts::workspace::OversizedSourceExclusionCandidate;
 #[cfg(test)]
@@ -9,7 +10,7 @@ use codestory_contracts::workspace::{
     DEFAULT_SOURCE_FILE_BYTE_CAP, OVERSIZED_SOURCE_POLICY_VERSION,
 };
 use fs4::fs_std::FileExt;
-use parking_lot::RwLock;
+use parking_lot::Mutex;
 use rusqlite::{
     Connection, MAIN_DB, OpenFlags, OptionalExtension, Result, Row, limits::Limit, params,
     params_from_iter, types::Value,
@@ -26,18 +27,42 @@ use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use thiserror::Error;
 
 mod bookmarks;
+mod canonical_ids;
+mod centrality;
+mod code_markers;
+mod content_digest;
+mod definitions;
+mod dependency_resolution;
+mod edge_counts;
+mod encryption;
+mod entry_points;
+mod generation;
+mod graph_match;
 mod helpers;
+mod index_history;
+mod java_types;
+mod maintenance;
+mod manifest_packages;
+mod node_aliases;
+mod node_badges;
+mod node_cache;
+mod project_settings;
 mod retrieval_manifest;
 mod row_mapping;
 mod schema;
+mod string_literals;
+mod test_mapping;
 mod trail;
+mod trail_presets;
+mod workspace_symbols;
 
 use helpers::{
-    decode_embedding_blob, deserialize_candidate_targets, encode_embedding_blob,
-    numbered_placeholders, question_placeholders, serialize_candidate_targets,
+    decode_embedding_blob, deserialize_candidate_targets, directory_package_chain,
+    encode_embedding_blob, file_path_lookup_key, numbered_placeholders, question_placeholders,
+    serialize_candidate_targets, stable_synthetic_id,
 };
 
-const SCHEMA_VERSION: u32 = 30;
+const SCHEMA_VERSION: u32 = 51;
 // Reserved outside the sequential migration range so a future real schema version cannot
 // accidentally be treated as an interrupted run from this release.
 const INCOMPLETE_INCREMENTAL_SCHEMA_VERSION: u32 = 0x4353_0001;
@@ -47,7 +72,38 @@ const GROUNDING_SNAPSHOT_VERSION: i64 = 1;
 const GROUNDING_SNAPSHOT_STATE_DIRTY: i64 = 0;
 const GROUNDING_SNAPSHOT_STATE_BUILDING: i64 = 1;
 const GROUNDING_SNAPSHOT_STATE_READY: i64 = 2;
+/// `canonical_id` prefix of package nodes built from directories.
+const DIRECTORY_PACKAGE_CANONICAL_PREFIX: &str = "directory_package:";
+/// File languages without module declarations of their own, grouped into
+/// directory packages.
+const DIRECTORY_PACKAGE_LANGUAGES: &[&str] = &["javascript", "typescript"];
 const RELATED_NODE_SUBQUERY: &str = "SELECT id FROM node WHERE id = ?1 OR file_node_id = ?1";
+/// `distinct_children`: the children of node `?1` (`?2` MEMBER, `?3` FILE)
+/// with a `display_name`, keeping the first in source order of those that
+/// share a kind, display name and file.
+const CHILD_SYMBOLS_CTE: &str = "WITH children AS (
+        SELECT n.* FROM node n
+        JOIN edge e ON n.id = e.target_node_id
+        WHERE e.source_node_id = ?1 AND e.kind = ?2
+        UNION ALL
+        SELECT n.* FROM node n
+        WHERE n.file_node_id = ?1
+          AND n.kind != ?3
+          AND EXISTS (SELECT 1 FROM node parent WHERE parent.id = ?1 AND parent.kind = ?3)
+          AND n.id NOT IN (SELECT target_node_id FROM edge WHERE kind = ?2)
+    ),
+    distinct_children AS (
+        SELECT * FROM (
+            SELECT children.*,
+                   COALESCE(qualified_name, serialized_name) AS display_name,
+                   ROW_NUMBER() OVER (
+                       PARTITION BY kind, COALESCE(qualified_name, serialized_name), file_node_id
+                       ORDER BY start_line IS NULL, start_line, start_col, serialized_name, id
+                   ) AS duplicate_rank
+            FROM children
+        )
+        WHERE duplicate_rank = 1
+    )";
 const CALLER_CLEANUP_IDS_TABLE: &str = "caller_cleanup_ids";
 const RELATED_NODE_IDS_TABLE: &str = "related_node_ids";
 const EDGE_SELECT_BASE: &str = "SELECT e.id, e.source_node_id, e.target_node_id, e.kind, e.file_node_id, e.line, e.resolved_source_node_id, e.resolved_target_node_id, e.confidence, e.callsite_identity, e.certainty, e.candidate_target_node_ids, t.serialized_name, f.serialized_name
@@ -86,13 +142,13 @@ const PROMOTION_JOURNAL_VERSION: u32 = 5;
 // source-policy identity at schema 27, and journal v3 added structural-text
 // identity at schema 28. Journal v4 binds the structural-unit source-policy
 // identity added at schema 29. Journal v5 admits the semantic-projection
-// publication mode added at schema 30. Recovery runs before schema migration, so these
+// publication mode added at schema 30 and every later additive schema, which
+// keeps the v5 journal shape. Recovery runs before schema migration, so these
 // boundaries are part of the durable journal contract.
 const LEGACY_PROMOTION_MIN_SCHEMA_VERSION: u32 = 21;
 const SOURCE_POLICY_PROMOTION_MIN_SCHEMA_VERSION: u32 = 27;
 const STRUCTURAL_TEXT_PROMOTION_MIN_SCHEMA_VERSION: u32 = 28;
 const STRUCTURAL_POLICY_PROMOTION_MIN_SCHEMA_VERSION: u32 = 29;
-const SEMANTIC_PROJECTION_PROMOTION_MIN_SCHEMA_VERSION: u32 = 30;
 const DISPOSABLE_FULL_BUILD_WAL_AUTOCHECKPOINT_BYTES: u64 = 64 * 1024 * 1024;
 
 /// Successful SQLite backup timing and logical database-image sizes.
@@ -235,7 +291,7 @@ fn database_logical_bytes(connection: &Connection) -> Result<u64, StorageError>
 }
 
 fn database_logical_bytes_at_path(path: &Path) -> Result<u64, StorageError> {
-    let connection = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    let connection = open_keyed_connection(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
     database_logical_bytes(&connection)
 }
 
@@ -361,6 +417,36 @@ fn current_epoch_ms() -> i64 {
         .min(i64::MAX as u128) as i64
 }
 
+/// Delete `node_ids` and the per-node rows derived from them, retiring
+/// bookmarks on them. Edges touching the nodes must already be gone.
+fn delete_detached_nodes_on(conn: &Connection, node_ids: &[i64]) -> Result<(), StorageError> {
+    for id in node_ids {
+        conn.execute(
+            "UPDATE bookmark_node SET retired_node_id = node_id, node_id = NULL
+             WHERE node_id = ?1",
+            params![id],
+        )?;
+        for table in [
+            "symbol_summary",
+            "search_symbol_projection",
+            "symbol_search_doc",
+            "llm_symbol_doc",
+            "dense_anchor_input",
+            "node_centrality",
+            "node_entry_distance",
+            "symbol_fan_in_snapshot",
+            "shared_node_ref",
+        ] {
+            conn.execute(
+                &format!("DELETE FROM {table} WHERE node_id = ?1"),
+                params![id],
+            )?;
+        }
+        conn.execute("DELETE FROM node WHERE id = ?1", params![id])?;
+    }
+    Ok(())
+}
+
 fn compare_grounding_file_summaries(
     left: &GroundingFileSummary,
     right: &GroundingFileSummary,
@@ -427,8 +513,7 @@ impl RecoveryDatabaseContract {
                 schema_version == STRUCTURAL_POLICY_PROMOTION_MIN_SCHEMA_VERSION
             }
             Self::Journal(PROMOTION_JOURNAL_VERSION) => {
-                (STRUCTURAL_POLICY_PROMOTION_MIN_SCHEMA_VERSION
-                    ..=SEMANTIC_PROJECTION_PROMOTION_MIN_SCHEMA_VERSION)
+                (STRUCTURAL_POLICY_PROMOTION_MIN_SCHEMA_VERSION..=SCHEMA_VERSION)
                     .contains(&schema_version)
             }
             Self::Journal(_) => false,
@@ -493,7 +578,7 @@ fn read_source_policy_exclusion_rollback_identity(
     if !path.exists() {
         return Ok(None);
     }
-    let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    let conn = open_keyed_connection(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
     let table_exists: i64 = conn.query_row(
         "SELECT EXISTS(
             SELECT 1 FROM sqlite_master
@@ -689,7 +774,7 @@ fn read_structural_text_unit_rollback_identity(
     if !path.exists() {
         return Ok(None);
     }
-    let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    let conn = open_keyed_connection(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
     let table_exists: i64 = conn.query_row(
         "SELECT EXISTS(
             SELECT 1 FROM sqlite_master
@@ -870,7 +955,7 @@ fn inspect_promotion_database(path: &Path) -> Result<Option<(Connection, u32)>,
     if !path.exists() {
         return Ok(None);
     }
-    let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    let conn = open_keyed_connection(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
     let _ = conn.busy_timeout(Duration::from_millis(2_500));
     let quick_check: String = conn.query_row("PRAGMA quick_check", [], |row| row.get(0))?;
     if quick_check != "ok" {
@@ -1193,7 +1278,7 @@ fn recover_interrupted_promotion_locked(path: &Path) -> Result<(), StorageError>
 }
 
 fn restore_promotion_database(source_path: &Path, live_path: &Path) -> Result<(), StorageError> {
-    let mut live = Connection::open(live_path)?;
+    let mut live = open_keyed_connection(live_path, OpenFlags::default())?;
     let _ = live.busy_timeout(Duration::from_millis(2_500));
     live.restore(MAIN_DB, source_path, None::<fn(rusqlite::backup::Progress)>)?;
     Ok(())
@@ -1709,6 +1794,99 @@ GROUP BY
     f.complete,
     f.line_count";
 
+/// Point pending tombstones at a live node with the same kind and name.
+///
+/// With `candidates`, only those freshly written nodes are considered, which
+/// keeps projection flushes from scanning the node table. Without them, the
+/// whole table is searched; deletions use this because a moved symbol may have
+/// been written before its old file was removed.
+fn link_node_tombstone_successors(
+    conn: &Connection,
+    candidates: Option<&[Node]>,
+) -> Result<usize, StorageError> {
+    let mut pending = Vec::new();
+    {
+        let mut stmt = conn.prepare(
+            "SELECT node_id, kind, COALESCE(qualified_name, serialized_name)
+             FROM node_tombstone
+             WHERE successor_node_id IS NULL",
+        )?;
+        let mut rows = stmt.query([])?;
+        while let Some(row) = rows.next()? {
+            pending.push((
+                row.get::<_, i64>(0)?,
+                row.get::<_, i32>(1)?,
+                row.get::<_, String>(2)?,
+            ));
+        }
+    }
+    if pending.is_empty() {
+        return Ok(0);
+    }
+
+    let mut linked = 0;
+    match candidates {
+        Some(nodes) => {
+            let by_name = nodes
+                .iter()
+                .filter(|node| node.kind != NodeKind::FILE)
+                .map(|node| {
+                    let name = node
+                        .qualified_name
+                        .as_deref()
+                        .unwrap_or(node.serialized_name.as_str());
+                    ((node.kind as i32, name), node.id.0)
+                })
+                .collect::<HashMap<_, _>>();
+            let mut stmt = conn
+                .prepare("UPDATE node_tombstone SET successor_node_id = ?1 WHERE node_id = ?2")?;
+            for (tombstone_id, kind, name) in &pending {
+                if let Some(successor) = by_name.get(&(*kind, name.as_str())) {
+                    linked += stmt.execute(params![successor, tombstone_id])?;
+                }
+            }
+        }
+        None => {
+            linked = conn.execute(
+                "UPDATE node_tombstone
+                 SET successor_node_id = (
+                    SELECT n.id FROM node n
+                    WHERE n.kind = node_tombstone.kind
+                      AND COALESCE(n.qualified_name, n.serialized_name)
+                          = COALESCE(node_tombstone.qualified_name, node_tombstone.serialized_name)
+                    ORDER BY n.id
+                    LIMIT 1
+                 )
+                 WHERE successor_node_id IS NULL
+                   AND EXISTS (
+                    SELECT 1 FROM node n
+                    WHERE n.kind = node_tombstone.kind
+                      AND COALESCE(n.qualified_name, n.serialized_name)
+                          = COALESCE(node_tombstone.qualified_name, node_tombstone.serialized_name)
+                   )",
+                [],
+            )?;
+        }
+    }
+    Ok(linked)
+}
+
+/// Per-file symbol and edge counts computed from the live graph tables.
+///
+/// Symbols exclude the file node itself; edges are attributed to the file
+/// that recorded them, which is where the reference appears in source.
+fn live_file_stats_sql() -> String {
+    format!(
+        "SELECT
+            f.id AS file_id,
+            (SELECT COUNT(*) FROM node n
+             WHERE n.file_node_id = f.id AND n.kind != {file_kind}) AS symbol_count,
+            (SELECT COUNT(*) FROM edge e WHERE e.file_node_id = f.id) AS edge_count
+        FROM file f",
+        file_kind = NodeKind::FILE as i32,
+    )
+}
+
 fn outside_related_file_edge_predicate(file_param: &str) -> String {
     format!(
         "source_node_id NOT IN (SELECT node_id FROM {RELATED_NODE_IDS_TABLE})
@@ -1810,6 +1988,10 @@ pub enum StorageError {
     ResolutionSupportSnapshotTooBig,
     #[error("Invalid enum value: {0}")]
     EnumConversion(#[from] EnumConversionError),
+    #[error("Storage encryption requires a build with the `sqlcipher` feature")]
+    EncryptionUnavailable,
+    #[error("Storage integrity check failed: {}", .0.join("; "))]
+    IntegrityCheckFailed(Vec<String>),
     #[error("Other error: {0}")]
     Other(String),
 }
@@ -1835,6 +2017,13 @@ pub struct BuildNodeLookup {
 /// migration, projection replacement, retrieval manifest rows, and derived
 /// grounding snapshots. Callers must invalidate or rebuild derived snapshots
 /// after mutating graph/search projections.
+///
+/// List queries return rows in a documented, stable order rather than SQLite's
+/// scan order: whole-table node, edge, and error reads follow row id; files
+/// follow path; occurrences follow source position; symbol-tree reads follow
+/// name (roots) or declaration position (children); trails follow depth.
+/// Every ordering ends in an id tie-breaker, so equal inputs produce equal
+/// output across runs, query plans, and index rebuilds.
 pub struct Storage {
     conn: Connection,
     cache: StorageCache,
@@ -2258,8 +2447,8 @@ fn record_projection_statement(
 
 #[derive(Default)]
 struct StorageCache {
-    nodes:
-        Arc<RwLock<HashMap<codestory_contracts::graph::NodeId, codestory_contracts::graph::Node>>>,
+    nodes: Arc<Mutex<NodeCache>>,
+    aliases: node_aliases::NodeAliasCache,
 }
 
 /// Stored file row persisted with graph projections.
@@ -2280,6 +2469,67 @@ pub struct FileInfo {
     pub file_role: FileRole,
 }
 
+/// Symbol and edge totals for one indexed file.
+///
+/// Counts come from the grounding summary snapshot when it is ready and from
+/// the live graph tables otherwise.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FileStats {
+    pub file_id: i64,
+    pub path: PathBuf,
+    pub language: String,
+    pub line_count: u32,
+    pub symbol_count: u32,
+    pub edge_count: u32,
+}
+
+impl FileStats {
+    /// Symbols per source line; empty files count as one line.
+    pub fn symbol_density(&self) -> f64 {
+        f64::from(self.symbol_count) / f64::from(self.line_count.max(1))
+    }
+}
+
+/// Nodes reachable from one start node, as ids and group counts only.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReachableSet {
+    /// Reached nodes in breadth-first order, excluding the start node.
+    pub node_ids: Vec<NodeId>,
+    /// Reached nodes per kind, largest group first.
+    pub kind_counts: Vec<(NodeKind, u32)>,
+    /// Reached nodes per declaring file, largest group first. Nodes without a
+    /// file are not counted.
+    pub file_counts: Vec<ReachableFileCount>,
+    /// Hops from the start node to the farthest reached node.
+    pub max_depth: u32,
+    /// Expansion stopped at the node limit before the graph was exhausted.
+    pub truncated: bool,
+}
+
+/// Hover-sized summary of one node, read by [`Storage::get_node_preview`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodePreview {
+    pub node: Node,
+    /// Path of the declaring file node.
+    pub file_path: Option<String>,
+    /// First line of the newest generated summary.
+    pub summary: Option<String>,
+    pub access: Option<AccessKind>,
+    /// Distinct nodes calling this one.
+    pub caller_count: u32,
+    /// Distinct nodes this one calls.
+    pub callee_count: u32,
+    /// Distinct nodes declared as members of this one.
+    pub member_count: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReachableFileCount {
+    pub file_node_id: NodeId,
+    pub path: String,
+    pub count: u32,
+}
+
 /// Verified content identity for one parser-backed or structural file projection.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct FileContentHash {
@@ -2743,13 +2993,44 @@ impl FileRole {
         }
         Self::Source
     }
+
+    /// Classify a file by path, then mark otherwise primary code as
+    /// generated when its header carries a generator marker.
+    pub fn classify_source(path: &Path, source: &str) -> Self {
+        match Self::classify_path(path) {
+            Self::Source | Self::Entrypoint if has_generated_code_header(source) => Self::Generated,
+            role => role,
+        }
+    }
+}
+
+/// Whether one of the leading comment lines carries a code generator marker
+/// such as `@generated`, `DO NOT EDIT`, or `<auto-generated>`.
+fn has_generated_code_header(source: &str) -> bool {
+    source
+        .lines()
+        .take(GENERATED_HEADER_SCAN_LINES)
+        .map(str::trim)
+        .filter(|line| {
+            ["//", "#", "/*", "*", "--", "<!--", ";", "\"\"\""]
+                .iter()
+                .any(|prefix| line.starts_with(prefix))
+        })
+        .any(|line| {
+            let lower = line.to_ascii_lowercase();
+            lower.contains("@generated")
+                || lower.contains("do not edit")
+                || lower.contains("<auto-generated")
+        })
 }
 
+const GENERATED_HEADER_SCAN_LINES: usize = 20;
+
 /// Counts describing the effective store contents.
 ///
 /// When summary snapshots are ready, counts come from the snapshot read model;
-/// otherwise they are computed from live tables. Fatal errors are always counted
-/// from the error table.
+/// otherwise they are computed from live tables. Fatal errors and occurrences
+/// are always counted from their tables.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StorageStats {
     pub node_count: i64,
@@ -2757,6 +3038,35 @@ pub struct StorageStats {
     pub file_count: i64,
     pub error_count: i64,
     pub fatal_error_count: i64,
+    #[serde(default)]
+    pub occurrence_count: i64,
+    /// Size of the main database file, from its page count and page size.
+    #[serde(default)]
+    pub database_bytes: u64,
+    /// When the current complete index was published, if it has been.
+    #[serde(default)]
+    pub last_indexed_at_epoch_ms: Option<i64>,
+    /// Per-table usage, largest first. Only [`Storage::get_detailed_stats`]
+    /// fills it.
+    #[serde(default)]
+    pub tables: Vec<StorageTableStats>,
+}
+
+/// Rows and disk use of one table.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageTableStats {
+    pub name: String,
+    pub row_count: i64,
+    /// Bytes held by the table and its indexes, measured with the `dbstat`
+    /// virtual table. `None` when SQLite was built without it.
+    pub bytes: Option<u64>,
+}
+
+/// Per-kind node and edge counts, ordered by kind discriminant.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GraphMetadata {
+    pub node_kind_counts: Vec<(NodeKind, u64)>,
+    pub edge_kind_counts: Vec<(EdgeKind, u64)>,
 }
 
 /// Indexing mode that produced one durable core database generation.
@@ -2914,9 +3224,56 @@ fn is_framework_synthetic_node(node: &Node) -> bool {
             || canonical_id.starts_with("payload:collection:")
             || canonical_id.starts_with("route_endpoint:")
             || canonical_id.starts_with("openapi:endpoint:")
+            || canonical_id.starts_with("sql:table:")
+            || canonical_id.starts_with("proto:")
     })
 }
 
+/// Record every file that projects a shared framework node, so deleting the
+/// file that owns the stored row hands it to another referencing file
+/// instead of removing a node the others still use.
+fn record_shared_node_refs(conn: &Connection, nodes: &[Node]) -> Result<(), StorageError> {
+    let mut stmt = conn
+        .prepare("INSERT OR IGNORE INTO shared_node_ref (node_id, file_node_id) VALUES (?1, ?2)")?;
+    for node in nodes
+        .iter()
+        .filter(|node| is_framework_synthetic_node(node))
+    {
+        if let Some(file_node_id) = node.file_node_id {
+            stmt.execute(params![node.id.0, file_node_id.0])?;
+        }
+    }
+    Ok(())
+}
+
+/// Drop `file_node_id`'s shared node references and move the shared nodes
+/// it owns, that another file still references, to one of those files.
+/// Their location belonged to the removed file, so it is cleared until the
+/// new owner is next indexed.
+fn hand_over_shared_nodes(conn: &Connection, file_node_id: i64) -> Result<Vec<i64>, StorageError> {
+    conn.execute(
+        "DELETE FROM shared_node_ref WHERE file_node_id = ?1",
+        params![file_node_id],
+    )?;
+    let mut stmt = conn.prepare(
+        "UPDATE node
+         SET file_node_id = (
+                SELECT MIN(r.file_node_id) FROM shared_node_ref r WHERE r.node_id = node.id
+             ),
+             start_line = NULL,
+             start_col = NULL,
+             end_line = NULL,
+             end_col = NULL
+         WHERE file_node_id = ?1
+           AND id IN (SELECT node_id FROM shared_node_ref)
+         RETURNING id",
+    )?;
+    let handed_over = stmt
+        .query_map(params![file_node_id], |row| row.get::<_, i64>(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(handed_over)
+}
+
 fn is_endpoint_synthetic_node(node: &Node) -> bool {
     node.canonical_id.as_deref().is_some_and(|canonical_id| {
         canonical_id.starts_with("route_endpoint:") || canonical_id.starts_with("openapi:endpoint:")
@@ -2973,6 +3330,22 @@ fn framework_node_source_rank(
         return Ok(u8::from(!path.is_empty()));
     }
 
+    if canonical_id.starts_with("sql:table:") {
+        // Embedded queries only name the table; its DDL owns it.
+        if path.ends_with(".sql") {
+            return Ok(4);
+        }
+        return Ok(u8::from(node.start_line.is_some()));
+    }
+
+    if canonical_id.starts_with("proto:") {
+        // Generated code only names the definition; the `.proto` file owns it.
+        if path.ends_with(".proto") {
+            return Ok(4);
+        }
+        return Ok(u8::from(node.start_line.is_some()));
+    }
+
     if canonical_id.starts_with("route_endpoint:") || canonical_id.starts_with("openapi:endpoint:")
     {
         if !path.is_empty() && node.start_line.is_some() {
@@ -3121,6 +3494,9 @@ pub struct FileProjectionRemovalSummary {
     pub removed_occurrence_count: usize,
     pub removed_error_count: usize,
     pub removed_bookmark_node_count: usize,
+    /// Bookmarks detached from a retired node; they follow its successor.
+    #[serde(default)]
+    pub retired_bookmark_node_count: usize,
     pub removed_component_access_count: usize,
     pub removed_local_symbol_count: usize,
     pub removed_file_row_count: usize,
@@ -3657,7 +4033,7 @@ impl Storage {
     pub fn open_read_only<P: AsRef<Path>>(path: P) -> Result<Self, StorageError> {
         let path = path.as_ref();
         recover_interrupted_promotion(path)?;
-        let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+        let conn = open_keyed_connection(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
         conn.busy_timeout(Duration::from_millis(2_500))?;
         conn.pragma_update(None, "foreign_keys", "ON")?;
         let version: i64 = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
@@ -3736,7 +4112,7 @@ impl Storage {
         // may update transient reader marks inside the existing SHM wal-index;
         // durable database and WAL bytes remain observationally unchanged.
         let uri = observational_sqlite_uri(path, !wal_exists);
-        let conn = Connection::open_with_flags(
+        let conn = open_keyed_connection(
             uri,
             OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_URI,
         )?;
@@ -3819,6 +4195,8 @@ impl Storage {
             path,
             StorageOpenMode::Build,
             SqliteDurabilityProfile::DisposableFullBuild,
+            None,
+            false,
         )
     }
 
@@ -3827,19 +4205,46 @@ impl Storage {
         path: P,
         mode: StorageOpenMode,
     ) -> Result<Self, StorageError> {
-        Self::open_with_mode_and_durability(path, mode, SqliteDurabilityProfile::Durable)
+        Self::open_with_options(path, &StorageOpenOptions::default().with_mode(mode))
+    }
+
+    /// Open a store with explicit mode and an optional per-connection key.
+    ///
+    /// A key is rejected with [`StorageError::EncryptionUnavailable`] unless
+    /// the crate was built with the `sqlcipher` feature. When an integrity
+    /// check is requested, here or process-wide, a damaged live database fails
+    /// with [`StorageError::IntegrityCheckFailed`] before any migration runs.
+    pub fn open_with_options<P: AsRef<Path>>(
+        path: P,
+        options: &StorageOpenOptions,
+    ) -> Result<Self, StorageError> {
+        validate_explicit_key(options.encryption_key.as_ref())?;
+        Self::open_with_mode_and_durability(
+            path,
+            options.mode,
+            SqliteDurabilityProfile::Durable,
+            options.encryption_key.as_ref(),
+            options.integrity_check
+                || (options.mode == StorageOpenMode::Live && process_integrity_check_enabled()),
+        )
     }
 
     fn open_with_mode_and_durability<P: AsRef<Path>>(
         path: P,
         mode: StorageOpenMode,
         durability_profile: SqliteDurabilityProfile,
+        encryption_key: Option<&StorageEncryptionKey>,
+        integrity_check: bool,
     ) -> Result<Self, StorageError> {
         let path = path.as_ref();
         if matches!(mode, StorageOpenMode::Live) {
             recover_interrupted_promotion(path)?;
         }
         let conn = Connection::open(path)?;
+        apply_encryption_key(&conn, encryption_key)?;
+        if integrity_check {
+            verify_integrity(&conn)?;
+        }
         // Allow concurrent reads while indexing writes, and avoid flaky "database is locked" errors
         // in app shells when users query mid-index.
         conn.busy_timeout(Duration::from_millis(2_500))?;
@@ -3878,7 +4283,7 @@ impl Storage {
 
     pub fn database_schema_version(path: &Path) -> Result<u32, StorageError> {
         recover_interrupted_promotion(path)?;
-        let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+        let conn = open_keyed_connection(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
         let version: i64 = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
         Ok(version.max(0) as u32)
     }
@@ -3886,7 +4291,7 @@ impl Storage {
     /// Read the incomplete-run fence without migrating or otherwise mutating a live database.
     pub fn database_has_incomplete_incremental_run(path: &Path) -> Result<bool, StorageError> {
         recover_interrupted_promotion(path)?;
-        let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+        let conn = open_keyed_connection(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
         let version: i64 = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
         let version = version.max(0) as u32;
         if version != INCOMPLETE_INCREMENTAL_SCHEMA_VERSION && version > SCHEMA_VERSION {
@@ -3908,7 +4313,7 @@ impl Storage {
         path: &Path,
     ) -> Result<Option<IndexPublicationRecord>, StorageError> {
         recover_interrupted_promotion(path)?;
-        let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+        let conn = open_keyed_connection(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
         read_index_publication(&conn)
     }
 
@@ -3918,7 +4323,7 @@ impl Storage {
         path: &Path,
     ) -> Result<Option<IndexPublicationRecord>, StorageError> {
         recover_interrupted_promotion(path)?;
-        let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+        let conn = open_keyed_connection(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
         read_complete_index_publication(&conn)
     }
 
@@ -3935,12 +4340,12 @@ impl Storage {
                 ))
             })?;
         }
-        let source = Connection::open_with_flags(source_path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+        let source = open_keyed_connection(source_path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
         let source_bytes = database_logical_bytes(&source)?;
         let copy_started = Instant::now();
         source.backup(MAIN_DB, target_path, None::<fn(rusqlite::backup::Progress)>)?;
         let copy_ms = duration_ms(copy_started.elapsed());
-        let target = Connection::open_with_flags(target_path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+        let target = open_keyed_connection(target_path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
         let target_bytes = database_logical_bytes(&target)?;
         Ok(DatabaseSnapshotCopyStats {
             copy_ms,
@@ -3987,7 +4392,7 @@ impl Storage {
         else {
             return Ok(None);
         };
-        let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+        let conn = open_keyed_connection(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
         conn.busy_timeout(Duration::from_millis(2_500))?;
         conn.pragma_update(None, "query_only", "ON")?;
         let version: i64 = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
@@ -4390,6 +4795,7 @@ impl Storage {
             None,
         )?;
         Self::invalidate_resolution_support_snapshot_on(&self.conn)?;
+        generation::bump_generation_on(&self.conn)?;
         Ok(())
     }
 
@@ -4621,6 +5027,7 @@ impl Storage {
         )?;
         tx.execute("DELETE FROM grounding_repo_stats_snapshot", [])?;
         tx.execute("DELETE FROM grounding_file_snapshot", [])?;
+        tx.execute("DELETE FROM file_stats_snapshot", [])?;
         tx.execute("DELETE FROM grounding_node_snapshot", [])?;
         tx.execute("DELETE FROM grounding_node_summary_snapshot", [])?;
         tx.execute("DELETE FROM grounding_node_edge_digest_snapshot", [])?;
@@ -4654,6 +5061,13 @@ impl Storage {
             GROUNDING_FILE_SNAPSHOT_SELECT_SQL,
         );
         tx.execute(&file_snapshot_sql, [])?;
+        tx.execute(
+            &format!(
+                "INSERT INTO file_stats_snapshot (file_id, symbol_count, edge_count) {}",
+                live_file_stats_sql()
+            ),
+            [],
+        )?;
 
         tx.execute(
             "UPDATE grounding_snapshot_meta
@@ -4715,6 +5129,7 @@ impl Storage {
                     FROM occurrence o
                     JOIN snapshot_nodes snapshot_nodes
                       ON snapshot_nodes.node_id = o.element_id
+                    WHERE o.edge_id IS NULL
                     GROUP BY o.element_id
                 ) first_seen
                   ON first_seen.first_rowid = o.rowid
@@ -4818,19 +5233,22 @@ impl Storage {
                 node_count,
                 edge_count,
                 file_count,
-                error_count
+                error_count,
+                occurrence_count
              )
              SELECT
                 1,
                 (SELECT COUNT(*) FROM node),
                 (SELECT COUNT(*) FROM edge),
                 (SELECT COUNT(*) FROM grounding_file_snapshot),
-                (SELECT COUNT(*) FROM error)
+                (SELECT COUNT(*) FROM error),
+                (SELECT COUNT(*) FROM occurrence)
              ON CONFLICT(id) DO UPDATE SET
                 node_count = excluded.node_count,
                 edge_count = excluded.edge_count,
                 file_count = excluded.file_count,
-                error_count = excluded.error_count",
+                error_count = excluded.error_count,
+                occurrence_count = excluded.occurrence_count",
             [],
         )?;
         tx.execute(
@@ -4862,6 +5280,8 @@ impl Storage {
         tx.execute("DELETE FROM structural_text_projection", [])?;
         tx.execute("DELETE FROM structural_text_artifact_cache", [])?;
         tx.execute("DELETE FROM occurrence", [])?;
+        tx.execute("DELETE FROM dependency_resolution", [])?;
+        tx.execute("DELETE FROM dependency_node", [])?;
         tx.execute("DELETE FROM edge", [])?;
         tx.execute("DELETE FROM llm_symbol_doc", [])?;
         tx.execute("DELETE FROM dense_anchor_input", [])?;
@@ -4873,12 +5293,25 @@ impl Storage {
         tx.execute("DELETE FROM search_symbol_projection", [])?;
         tx.execute("DELETE FROM component_access", [])?;
         tx.execute("DELETE FROM bookmark_node", [])?;
+        tx.execute("DELETE FROM node_tombstone", [])?;
+        tx.execute("DELETE FROM shared_node_ref", [])?;
+        tx.execute("DELETE FROM symbol_fan_in_snapshot", [])?;
+        tx.execute("DELETE FROM symbol_fan_in_snapshot_meta", [])?;
+        tx.execute("DELETE FROM node_centrality", [])?;
+        tx.execute("DELETE FROM node_entry_distance", [])?;
+        tx.execute("DELETE FROM entry_distance_scan", [])?;
+        tx.execute("DELETE FROM code_marker", [])?;
+        tx.execute("DELETE FROM code_marker_scan", [])?;
+        tx.execute("DELETE FROM string_literal", [])?;
+        tx.execute("DELETE FROM string_literal_scan", [])?;
         tx.execute("DELETE FROM local_symbol", [])?;
         tx.execute("DELETE FROM error", [])?;
         tx.execute("DELETE FROM node", [])?;
         tx.execute("DELETE FROM file", [])?;
+        generation::bump_generation_on(&tx)?;
         tx.execute("DELETE FROM grounding_repo_stats_snapshot", [])?;
         tx.execute("DELETE FROM grounding_file_snapshot", [])?;
+        tx.execute("DELETE FROM file_stats_snapshot", [])?;
         tx.execute("DELETE FROM grounding_node_snapshot", [])?;
         tx.execute("DELETE FROM grounding_node_summary_snapshot", [])?;
         tx.execute("DELETE FROM grounding_node_edge_digest_snapshot", [])?;
@@ -4917,7 +5350,7 @@ impl Storage {
         )?;
         tx.commit()?;
 
-        self.cache.nodes.write().clear();
+        self.cache.nodes.lock().clear();
         Ok(())
     }
 
@@ -4931,7 +5364,7 @@ impl Storage {
         let mut updated = self.rebase_path_bound_text_columns(&source_root, &target_root)?;
         updated = updated.saturating_add(self.refresh_rebased_file_metadata()?);
         let invalidated_artifacts = self.clear_legacy_index_artifact_cache()?;
-        self.cache.nodes.write().clear();
+        self.cache.nodes.lock().clear();
         Ok((updated, invalidated_artifacts))
     }
 
@@ -5252,7 +5685,7 @@ impl Storage {
         let mut rollback_backup_bytes = None;
         if previous.is_some() {
             let rollback_backup_copy_started = Instant::now();
-            let live_conn = Connection::open(live_path)?;
+            let live_conn = open_keyed_connection(live_path, OpenFlags::default())?;
             let _ = live_conn.busy_timeout(Duration::from_millis(2_500));
             live_conn.backup(
                 MAIN_DB,
@@ -5313,7 +5746,7 @@ impl Storage {
         durations.prepared_journal_directory_sync = journal_write_stats.directory_sync;
 
         let staged_to_live_restore_started = Instant::now();
-        let mut live_conn = Connection::open(live_path)?;
+        let mut live_conn = open_keyed_connection(live_path, OpenFlags::default())?;
         let _ = live_conn.busy_timeout(Duration::from_millis(2_500));
         live_conn.pragma_update(None, "synchronous", "FULL")?;
 
@@ -5646,7 +6079,7 @@ impl Storage {
             ],
         )?;
         // Update cache
-        self.cache.nodes.write().insert(prepared.id, prepared);
+        self.cache.nodes.lock().insert(prepared);
         self.invalidate_grounding_snapshots()?;
         Ok(())
     }
@@ -5710,9 +6143,9 @@ impl Storage {
         tx.commit()?;
 
         // Update cache
-        let mut cache = self.cache.nodes.write();
+        let mut cache = self.cache.nodes.lock();
         for node in &prepared_nodes {
-            cache.insert(node.id, node.clone());
+            cache.insert(node.clone());
         }
 
         self.invalidate_grounding_snapshots()?;
@@ -5746,9 +6179,9 @@ impl Storage {
         }
         tx.commit()?;
 
-        let mut cache = self.cache.nodes.write();
+        let mut cache = self.cache.nodes.lock();
         for node in &prepared_nodes {
-            cache.insert(node.id, node.clone());
+            cache.insert(node.clone());
         }
 
         Ok(())
@@ -5874,7 +6307,7 @@ impl Storage {
         tx.execute("DROP TABLE temp.retrieval_artifact_keep", [])?;
         tx.commit()?;
 
-        let mut cache = self.cache.nodes.write();
+        let mut cache = self.cache.nodes.lock();
         for node_id in stale_node_ids {
             cache.remove(&NodeId(node_id));
         }
@@ -5928,6 +6361,9 @@ impl Storage {
         let detach_result = self.conn.execute("DETACH DATABASE source_snapshot", []);
         let copied = copy_result?;
         detach_result?;
+        if copied > 0 {
+            self.cache.nodes.lock().clear();
+        }
         Ok(copied)
     }
 
@@ -5967,7 +6403,7 @@ impl Storage {
         let tx = self.conn.transaction()?;
         {
             let mut stmt = tx.prepare(
-                "INSERT OR IGNORE INTO occurrence (element_id, kind, file_node_id, start_line, start_col, end_line, end_col) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)"
+                "INSERT OR IGNORE INTO occurrence (element_id, kind, file_node_id, start_line, start_col, end_line, end_col, edge_id, notebook_cell_index, notebook_cell_line) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)"
             )?;
             for occ in occurrences {
                 stmt.execute(params![
@@ -5978,6 +6414,9 @@ impl Storage {
                     occ.location.start_col,
                     occ.location.end_line,
                     occ.location.end_col,
+                    occ.edge_id.map(|id| id.0),
+                    occ.notebook_cell.map(|cell| cell.cell_index),
+                    occ.notebook_cell.map(|cell| cell.line),
                 ])?;
             }
         }
@@ -5997,10 +6436,11 @@ impl Storage {
         Ok(count)
     }
 
+    /// All nodes, ordered by node id.
     pub fn get_nodes(&self) -> Result<Vec<Node>, StorageError> {
         let mut stmt = self
             .conn
-            .prepare("SELECT id, kind, serialized_name, qualified_name, canonical_id, file_node_id, start_line, start_col, end_line, end_col FROM node")?;
+            .prepare("SELECT id, kind, serialized_name, qualified_name, canonical_id, file_node_id, start_line, start_col, end_line, end_col FROM node ORDER BY id")?;
         let mut nodes = Vec::new();
         let mut rows = stmt.query([])?;
         while let Some(row) = rows.next()? {
@@ -6078,10 +6518,11 @@ impl Storage {
         Ok(file_node_ids.into_iter().collect())
     }
 
+    /// All edges, ordered by edge id.
     pub fn get_edges(&self) -> Result<Vec<Edge>, StorageError> {
         let mut stmt = self
             .conn
-            .prepare("SELECT id, source_node_id, target_node_id, kind, file_node_id, line, resolved_source_node_id, resolved_target_node_id, confidence, callsite_identity, certainty, candidate_target_node_ids FROM edge")?;
+            .prepare("SELECT id, source_node_id, target_node_id, kind, file_node_id, line, resolved_source_node_id, resolved_target_node_id, confidence, callsite_identity, certainty, candidate_target_node_ids FROM edge ORDER BY id")?;
         let mut edges = Vec::new();
         let mut rows = stmt.query([])?;
         while let Some(row) = rows.next()? {
@@ -6283,6 +6724,119 @@ impl Storage {
         Ok(kinds)
     }
 
+    /// Count nodes and edges per kind in one read.
+    ///
+    /// Both histograms come from the same statement, so they describe one
+    /// database generation even while a writer is active.
+    pub fn get_graph_metadata(&self) -> Result<GraphMetadata, StorageError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT 0, kind, COUNT(*) FROM node GROUP BY kind
+             UNION ALL
+             SELECT 1, kind, COUNT(*) FROM edge GROUP BY kind
+             ORDER BY 1, 2",
+        )?;
+        let mut metadata = GraphMetadata::default();
+        let mut rows = stmt.query([])?;
+        while let Some(row) = rows.next()? {
+            let table: i32 = row.get(0)?;
+            let raw: i32 = row.get(1)?;
+            let count = row.get::<_, i64>(2)?.max(0) as u64;
+            if table == 0 {
+                if let Ok(kind) = NodeKind::try_from(raw) {
+                    metadata.node_kind_counts.push((kind, count));
+                }
+            } else if let Ok(kind) = EdgeKind::try_from(raw) {
+                metadata.edge_kind_counts.push((kind, count));
+            }
+        }
+        Ok(metadata)
+    }
+
+    /// Distinct referencing symbols per referenced node, through resolved
+    /// endpoints. Calls, usages, inheritance, imports, and macro or
+    /// annotation uses count; containment and file includes do not.
+    ///
+    /// The counts are kept with the generation they were computed at and
+    /// reused until the graph changes.
+    pub fn get_symbol_fan_in_counts(&self) -> Result<HashMap<NodeId, u32>, StorageError> {
+        let generation = self.generation()?;
+        let snapshot_generation: Option<i64> = self
+            .conn
+            .query_row(
+                "SELECT generation FROM symbol_fan_in_snapshot_meta WHERE id = 1",
+                [],
+                |row| row.get(0),
+            )
+            .optional()?;
+        if snapshot_generation == Some(generation as i64) {
+            let mut stmt = self
+                .conn
+                .prepare("SELECT node_id, fan_in FROM symbol_fan_in_snapshot")?;
+            let counts = stmt
+                .query_map([], |row| Ok((NodeId(row.get(0)?), row.get(1)?)))?
+                .collect::<Result<HashMap<_, _>, _>>()?;
+            return Ok(counts);
+        }
+
+        let counts = self.compute_symbol_fan_in_counts()?;
+        if !self.conn.is_readonly(rusqlite::MAIN_DB)? {
+            let tx = self.conn.unchecked_transaction()?;
+            tx.execute("DELETE FROM symbol_fan_in_snapshot", [])?;
+            {
+                let mut stmt = tx.prepare(
+                    "INSERT INTO symbol_fan_in_snapshot (node_id, fan_in) VALUES (?1, ?2)",
+                )?;
+                for (node_id, fan_in) in &counts {
+                    stmt.execute(params![node_id.0, fan_in])?;
+                }
+            }
+            tx.execute(
+                "INSERT INTO symbol_fan_in_snapshot_meta (id, generation) VALUES (1, ?1)
+                 ON CONFLICT(id) DO UPDATE SET generation = excluded.generation",
+                params![generation as i64],
+            )?;
+            tx.commit()?;
+        }
+        Ok(counts)
+    }
+
+    fn compute_symbol_fan_in_counts(&self) -> Result<HashMap<NodeId, u32>, StorageError> {
+        let kinds = [
+            EdgeKind::CALL,
+            EdgeKind::USAGE,
+            EdgeKind::TYPE_USAGE,
+            EdgeKind::TYPE_ARGUMENT,
+            EdgeKind::INHERITANCE,
+            EdgeKind::IMPORT,
+            EdgeKind::MACRO_USAGE,
+            EdgeKind::ANNOTATION_USAGE,
+        ]
+        .iter()
+        .map(|kind| (*kind as i32).to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT target_id, COUNT(DISTINCT source_id)
+             FROM (
+                SELECT COALESCE(resolved_source_node_id, source_node_id) AS source_id,
+                       COALESCE(resolved_target_node_id, target_node_id) AS target_id
+                FROM edge
+                WHERE kind IN ({kinds})
+             )
+             WHERE source_id != target_id
+             GROUP BY target_id"
+        ))?;
+        let counts = stmt
+            .query_map([], |row| {
+                Ok((
+                    NodeId(row.get(0)?),
+                    row.get::<_, i64>(1)?.clamp(0, i64::from(u32::MAX)) as u32,
+                ))
+            })?
+            .collect::<Result<HashMap<_, _>, _>>()?;
+        Ok(counts)
+    }
+
     pub fn insert_component_access_batch(
         &mut self,
         entries: &[(NodeId, AccessKind)],
@@ -6563,6 +7117,10 @@ impl Storage {
                 clamp_i64_to_u32(nodes_insert_started.elapsed().as_millis() as i64);
             breakdown.nodes_ms = nodes_prepare_ms.saturating_add(nodes_insert_ms);
             breakdown.persistence.nodes.wall_ms = nodes_insert_ms;
+            if link_node_tombstone_successors(&tx, Some(&prepared_nodes))? > 0 {
+                bookmarks::settle_retired_bookmarks(&tx, current_epoch_ms())?;
+            }
+            record_shared_node_refs(&tx, batch.nodes)?;
         }
 
         let structural_started = std::time::Instant::now();
@@ -6795,8 +7353,8 @@ impl Storage {
         if !batch.occurrences.is_empty() {
             let started = std::time::Instant::now();
             let mut stmt = tx.prepare(
-                "INSERT OR IGNORE INTO occurrence (element_id, kind, file_node_id, start_line, start_col, end_line, end_col)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                "INSERT OR IGNORE INTO occurrence (element_id, kind, file_node_id, start_line, start_col, end_line, end_col, edge_id, notebook_cell_index, notebook_cell_line)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
             )?;
             for occ in batch.occurrences {
                 stmt.execute(params![
@@ -6807,6 +7365,9 @@ impl Storage {
                     occ.location.start_col,
                     occ.location.end_line,
                     occ.location.end_col,
+                    occ.edge_id.map(|id| id.0),
+                    occ.notebook_cell.map(|cell| cell.cell_index),
+                    occ.notebook_cell.map(|cell| cell.line),
                 ])
                 .map_err(|err| {
                     StorageError::Other(format!(
@@ -6823,7 +7384,12 @@ impl Storage {
                 record_projection_statement(
                     &mut breakdown.persistence.occurrences,
                     1,
-                    projection_scalar_binds(7),
+                    projection_scalar_binds(7)
+                        .saturating_add(projection_optional_scalar_bind_bytes(occ.edge_id))
+                        .saturating_add(
+                            projection_optional_scalar_bind_bytes(occ.notebook_cell)
+                                .saturating_mul(2),
+                        ),
                 );
             }
             breakdown.occurrences_ms = clamp_i64_to_u32(started.elapsed().as_millis() as i64);
@@ -6976,9 +7542,9 @@ impl Storage {
             clamp_i64_to_u32(transaction_started.elapsed().as_millis() as i64);
 
         if !prepared_nodes.is_empty() {
-            let mut cache = self.cache.nodes.write();
+            let mut cache = self.cache.nodes.lock();
             for node in prepared_nodes {
-                cache.insert(node.id, node.clone());
+                cache.insert(node);
             }
         }
 
@@ -9457,9 +10023,11 @@ impl Storage {
         Ok(removed)
     }
 
+    /// All occurrences, ordered by file, then source position, then element.
     pub fn get_occurrences(&self) -> Result<Vec<Occurrence>, StorageError> {
         let mut stmt = self.conn.prepare(
-            "SELECT element_id, kind, file_node_id, start_line, start_col, end_line, end_col FROM occurrence"
+            "SELECT element_id, kind, file_node_id, start_line, start_col, end_line, end_col, edge_id, notebook_cell_index, notebook_cell_line FROM occurrence
+             ORDER BY file_node_id, start_line, start_col, end_line, end_col, element_id, kind"
         )?;
         let occ_iter = stmt.query_map([], Self::occurrence_from_row)?;
 
@@ -9470,12 +10038,14 @@ impl Storage {
         Ok(occurrences)
     }
 
+    /// Occurrences of one element, ordered by file, then source position.
     pub fn get_occurrences_for_element(
         &self,
         element_id: i64,
     ) -> Result<Vec<Occurrence>, StorageError> {
         let mut stmt = self.conn.prepare(
-            "SELECT element_id, kind, file_node_id, start_line, start_col, end_line, end_col FROM occurrence WHERE element_id = ?1"
+            "SELECT element_id, kind, file_node_id, start_line, start_col, end_line, end_col, edge_id, notebook_cell_index, notebook_cell_line FROM occurrence WHERE element_id = ?1
+             ORDER BY file_node_id, start_line, start_col, end_line, end_col, element_id, kind"
         )?;
         let occ_iter = stmt.query_map([element_id], Self::occurrence_from_row)?;
 
@@ -9536,8 +10106,8 @@ impl Storage {
     }
 
     pub fn get_node(&self, id: NodeId) -> Result<Option<Node>, StorageError> {
-        if let Some(node) = self.cache.nodes.read().get(&id) {
-            return Ok(Some(node.clone()));
+        if let Some(node) = self.cache.nodes.lock().get(&id) {
+            return Ok(Some(node));
         }
 
         let mut stmt = self
@@ -9547,7 +10117,7 @@ impl Storage {
 
         if let Some(row) = rows.next()? {
             let node = Self::node_from_row(row)?;
-            self.cache.nodes.write().insert(node.id, node.clone());
+            self.cache.nodes.lock().insert(node.clone());
             Ok(Some(node))
         } else {
             Ok(None)
@@ -9562,14 +10132,17 @@ impl Storage {
         let mut unique_ids = Vec::new();
         let mut seen_ids = HashSet::new();
         let mut nodes_by_id = HashMap::new();
-        for id in ids {
-            if !seen_ids.insert(*id) {
-                continue;
-            }
-            if let Some(node) = self.cache.nodes.read().get(id) {
-                nodes_by_id.insert(*id, node.clone());
-            } else {
-                unique_ids.push(*id);
+        {
+            let mut cache = self.cache.nodes.lock();
+            for id in ids {
+                if !seen_ids.insert(*id) {
+                    continue;
+                }
+                if let Some(node) = cache.get(id) {
+                    nodes_by_id.insert(*id, node);
+                } else {
+                    unique_ids.push(*id);
+                }
             }
         }
 
@@ -9581,10 +10154,10 @@ impl Storage {
             let params = chunk.iter().map(|id| Value::from(id.0));
             let mut stmt = self.conn.prepare(&query)?;
             let mut rows = stmt.query(params_from_iter(params))?;
-            let mut cache = self.cache.nodes.write();
+            let mut cache = self.cache.nodes.lock();
             while let Some(row) = rows.next()? {
                 let node = Self::node_from_row(row)?;
-                cache.insert(node.id, node.clone());
+                cache.insert(node.clone());
                 nodes_by_id.insert(node.id, node);
             }
         }
@@ -9659,7 +10232,7 @@ impl Storage {
         for chunk in unique_ids.chunks(OCCURRENCE_LOOKUP_BATCH_SIZE) {
             let placeholders = numbered_placeholders(1, chunk.len());
             let query = format!(
-                "SELECT element_id, kind, file_node_id, start_line, start_col, end_line, end_col FROM occurrence WHERE element_id IN ({placeholders})"
+                "SELECT element_id, kind, file_node_id, start_line, start_col, end_line, end_col, edge_id, notebook_cell_index, notebook_cell_line FROM occurrence WHERE element_id IN ({placeholders})"
             );
             let params = chunk.iter().map(|id| Value::from(id.0));
             let mut stmt = self.conn.prepare(&query)?;
@@ -9677,28 +10250,106 @@ impl Storage {
         Ok(occurrences_by_node)
     }
 
+    /// Occurrences of one node, ordered by file, then source position.
+    ///
+    /// Occurrences of nodes merged into `node_id` are included under its id.
     pub fn get_occurrences_for_node(
         &self,
         node_id: codestory_contracts::graph::NodeId,
     ) -> Result<Vec<Occurrence>, StorageError> {
-        let mut stmt = self.conn.prepare(
-            "SELECT element_id, kind, file_node_id, start_line, start_col, end_line, end_col FROM occurrence WHERE element_id = ?1"
+        let aliases = self.node_alias_map()?;
+        let node_id = aliases.primary_of(node_id);
+        let node_ids = aliases.with_aliases(node_id);
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT element_id, kind, file_node_id, start_line, start_col, end_line, end_col, edge_id, notebook_cell_index, notebook_cell_line FROM occurrence WHERE element_id IN ({})
+             ORDER BY file_node_id, start_line, start_col, end_line, end_col, element_id, kind",
+            numbered_placeholders(1, node_ids.len())
+        ))?;
+        let occ_iter = stmt.query_map(
+            params_from_iter(node_ids.iter().map(|id| id.0)),
+            Self::occurrence_from_row,
         )?;
-        let occ_iter = stmt.query_map(params![node_id.0], Self::occurrence_from_row)?;
 
         let mut occurrences = Vec::new();
         for occ in occ_iter {
-            occurrences.push(occ?);
+            let mut occ = occ?;
+            occ.element_id = node_id.0;
+            occurrences.push(occ);
+        }
+        Ok(occurrences)
+    }
+
+    /// Occurrences of `node_id` and its aliases with `kind`, if given,
+    /// ordered by file path, then source position, from `offset`, at most
+    /// `limit` of them. Occurrences whose file node is gone are left out.
+    pub fn get_occurrences_for_node_page(
+        &self,
+        node_id: codestory_contracts::graph::NodeId,
+        kind: Option<OccurrenceKind>,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<Occurrence>, StorageError> {
+        let aliases = self.node_alias_map()?;
+        let node_id = aliases.primary_of(node_id);
+        let node_ids = aliases.with_aliases(node_id);
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT o.element_id, o.kind, o.file_node_id, o.start_line, o.start_col, o.end_line, o.end_col, o.edge_id, o.notebook_cell_index, o.notebook_cell_line
+             FROM occurrence o
+             JOIN node f ON f.id = o.file_node_id
+             WHERE o.element_id IN ({}) AND (?1 IS NULL OR o.kind = ?1)
+             ORDER BY f.serialized_name, o.start_line, o.start_col, o.end_line, o.end_col, o.element_id, o.kind
+             LIMIT ?2 OFFSET ?3",
+            numbered_placeholders(4, node_ids.len())
+        ))?;
+        let mut values = vec![
+            rusqlite::types::Value::from(kind.map(|kind| kind as i64)),
+            rusqlite::types::Value::from(limit.min(i64::MAX as usize) as i64),
+            rusqlite::types::Value::from(offset.min(i64::MAX as usize) as i64),
+        ];
+        values.extend(node_ids.iter().map(|id| rusqlite::types::Value::from(id.0)));
+        let occ_iter = stmt.query_map(params_from_iter(values), Self::occurrence_from_row)?;
+
+        let mut occurrences = Vec::new();
+        for occ in occ_iter {
+            let mut occ = occ?;
+            occ.element_id = node_id.0;
+            occurrences.push(occ);
         }
         Ok(occurrences)
     }
 
+    /// How many occurrences [`Self::get_occurrences_for_node_page`] pages
+    /// through.
+    pub fn get_occurrence_count_for_node(
+        &self,
+        node_id: codestory_contracts::graph::NodeId,
+        kind: Option<OccurrenceKind>,
+    ) -> Result<usize, StorageError> {
+        let aliases = self.node_alias_map()?;
+        let node_ids = aliases.with_aliases(aliases.primary_of(node_id));
+        let query = format!(
+            "SELECT COUNT(*)
+             FROM occurrence o
+             JOIN node f ON f.id = o.file_node_id
+             WHERE o.element_id IN ({}) AND (?1 IS NULL OR o.kind = ?1)",
+            numbered_placeholders(2, node_ids.len())
+        );
+        let mut values = vec![rusqlite::types::Value::from(kind.map(|kind| kind as i64))];
+        values.extend(node_ids.iter().map(|id| rusqlite::types::Value::from(id.0)));
+        let count: i64 = self
+            .conn
+            .query_row(&query, params_from_iter(values), |row| row.get(0))?;
+        Ok(count as usize)
+    }
+
+    /// Occurrences in one file, ordered by source position, then element.
     pub fn get_occurrences_for_file(
         &self,
         file_node_id: codestory_contracts::graph::NodeId,
     ) -> Result<Vec<Occurrence>, StorageError> {
         let mut stmt = self.conn.prepare(
-            "SELECT element_id, kind, file_node_id, start_line, start_col, end_line, end_col FROM occurrence WHERE file_node_id = ?1"
+            "SELECT element_id, kind, file_node_id, start_line, start_col, end_line, end_col, edge_id, notebook_cell_index, notebook_cell_line FROM occurrence WHERE file_node_id = ?1
+             ORDER BY file_node_id, start_line, start_col, end_line, end_col, element_id, kind"
         )?;
         let occ_iter = stmt.query_map(params![file_node_id.0], Self::occurrence_from_row)?;
 
@@ -9709,6 +10360,25 @@ impl Storage {
         Ok(occurrences)
     }
 
+    /// Call-site occurrences linked to one edge, ordered by file, then source
+    /// position.
+    ///
+    /// Databases indexed before occurrences carried an edge link return no
+    /// rows here; callers fall back to [`Self::get_occurrences_for_element`].
+    pub fn get_callsites_for_edge(&self, edge_id: EdgeId) -> Result<Vec<Occurrence>, StorageError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT element_id, kind, file_node_id, start_line, start_col, end_line, end_col, edge_id, notebook_cell_index, notebook_cell_line FROM occurrence WHERE edge_id = ?1
+             ORDER BY file_node_id, start_line, start_col, end_line, end_col, element_id, kind"
+        )?;
+        let occ_iter = stmt.query_map(params![edge_id.0], Self::occurrence_from_row)?;
+
+        let mut occurrences = Vec::new();
+        for occ in occ_iter {
+            occurrences.push(occ?);
+        }
+        Ok(occurrences)
+    }
+
     pub fn insert_file(&self, info: &FileInfo) -> Result<(), StorageError> {
         self.conn.execute(
             "INSERT INTO file (id, path, language, modification_time, indexed, complete, line_count, file_role)
@@ -9772,9 +10442,11 @@ impl Storage {
         Ok(())
     }
 
+    /// All indexed files, ordered by path, then file id.
     pub fn get_files(&self) -> Result<Vec<FileInfo>, StorageError> {
         let mut stmt = self.conn.prepare(
-            "SELECT id, path, language, modification_time, indexed, complete, line_count, file_role FROM file",
+            "SELECT id, path, language, modification_time, indexed, complete, line_count, file_role FROM file
+             ORDER BY path, id",
         )?;
         let file_iter = stmt.query_map([], |row| {
             Ok(FileInfo {
@@ -9909,6 +10581,65 @@ impl Storage {
         Ok(clamp_i64_to_u32(self.effective_grounding_file_count()?))
     }
 
+    fn file_stats_source_sql(&self) -> Result<String, StorageError> {
+        if self.has_ready_grounding_summary_snapshots()? {
+            return Ok(
+                "SELECT file_id, symbol_count, edge_count FROM file_stats_snapshot".to_string(),
+            );
+        }
+        Ok(live_file_stats_sql())
+    }
+
+    /// Symbol and edge counts for one indexed file.
+    pub fn get_file_stats(&self, file_id: i64) -> Result<Option<FileStats>, StorageError> {
+        let query = format!(
+            "WITH stats AS ({})
+             SELECT f.id, f.path, f.language, f.line_count, s.symbol_count, s.edge_count
+             FROM file f
+             JOIN stats s ON s.file_id = f.id
+             WHERE f.id = ?1",
+            self.file_stats_source_sql()?
+        );
+        self.conn
+            .query_row(&query, params![file_id], row_mapping::file_stats_from_row)
+            .optional()
+            .map_err(StorageError::from)
+    }
+
+    /// Files with the most symbols per line, densest first.
+    ///
+    /// Files without symbols are omitted. Ties fall back to the larger symbol
+    /// count and then the path so the ordering is stable across runs.
+    pub fn get_files_by_symbol_density(
+        &self,
+        limit: usize,
+    ) -> Result<Vec<FileStats>, StorageError> {
+        if limit == 0 {
+            return Err(StorageError::InvalidBatchLimit(
+                "get_files_by_symbol_density",
+            ));
+        }
+        let query = format!(
+            "WITH stats AS ({})
+             SELECT f.id, f.path, f.language, f.line_count, s.symbol_count, s.edge_count
+             FROM file f
+             JOIN stats s ON s.file_id = f.id
+             WHERE s.symbol_count > 0
+             ORDER BY CAST(s.symbol_count AS REAL) / MAX(f.line_count, 1) DESC,
+                      s.symbol_count DESC,
+                      f.path ASC
+             LIMIT ?1",
+            self.file_stats_source_sql()?
+        );
+        let mut stmt = self.conn.prepare(&query)?;
+        let rows = stmt.query_map(
+            params![limit.min(i64::MAX as usize) as i64],
+            row_mapping::file_stats_from_row,
+        )?;
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(StorageError::from)
+    }
+
     pub fn get_grounding_file_summaries(&self) -> Result<Vec<GroundingFileSummary>, StorageError> {
         if self.has_ready_grounding_summary_snapshots()? {
             let mut stmt = self.conn.prepare(
@@ -10703,6 +11434,7 @@ impl Storage {
             "SELECT element_id, start_line
             FROM occurrence
             WHERE element_id IN ({placeholders})
+              AND edge_id IS NULL
             ORDER BY element_id ASC, rowid ASC"
         );
         let mut stmt = self.conn.prepare(&query)?;
@@ -10804,11 +11536,68 @@ impl Storage {
         Ok(counts)
     }
 
+    /// Look up a file row by path.
+    ///
+    /// An exact match wins. Otherwise the path is compared by lookup key, so a
+    /// different separator, drive-letter case, verbatim prefix, or (on Windows
+    /// and macOS) letter case still finds the stored row when exactly one row
+    /// matches. A path through a symlinked directory is finally retried as
+    /// its resolved target.
     pub fn get_file_by_path(&self, path: &Path) -> Result<Option<FileInfo>, StorageError> {
+        if let Some(file) = self.get_file_by_path_key(&path.to_string_lossy())? {
+            return Ok(Some(file));
+        }
+        match std::fs::canonicalize(path) {
+            Ok(resolved) if resolved != path => {
+                self.get_file_by_path_key(&resolved.to_string_lossy())
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn get_file_by_path_key(&self, raw: &str) -> Result<Option<FileInfo>, StorageError> {
+        if let Some(file) = self.get_file_by_exact_path(raw)? {
+            return Ok(Some(file));
+        }
+        let key = file_path_lookup_key(raw);
+        // Only rows ending in the same file name can share the key. LIKE
+        // ignores ASCII case, which covers the platforms that fold it.
+        let file_name = key.rsplit('/').next().unwrap_or(&key);
+        let escaped = file_name
+            .replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_");
+        let mut stmt = self
+            .conn
+            .prepare("SELECT path FROM file WHERE path LIKE ?1 ESCAPE '\\'")?;
+        let matches = stmt
+            .query_map(params![format!("%{escaped}")], |row| {
+                row.get::<_, String>(0)
+            })?
+            .filter(|stored| {
+                stored
+                    .as_ref()
+                    .map_or(true, |stored| file_path_lookup_key(stored) == key)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        match matches.as_slice() {
+            [] => Ok(None),
+            [stored] => self.get_file_by_exact_path(stored),
+            ambiguous => {
+                tracing::debug!(
+                    "file path {raw} matches {} stored paths by lookup key; none is picked",
+                    ambiguous.len()
+                );
+                Ok(None)
+            }
+        }
+    }
+
+    fn get_file_by_exact_path(&self, path: &str) -> Result<Option<FileInfo>, StorageError> {
         let mut stmt = self.conn.prepare(
             "SELECT id, path, language, modification_time, indexed, complete, line_count, file_role FROM file WHERE path = ?1",
         )?;
-        let mut rows = stmt.query(params![path.to_string_lossy()])?;
+        let mut rows = stmt.query(params![path])?;
 
         if let Some(row) = rows.next()? {
             Ok(Some(FileInfo {
@@ -10882,6 +11671,8 @@ impl Storage {
         Ok(node_kinds.into_values().collect())
     }
 
+    /// Nodes with an occurrence covering `line`, ordered by occurrence start,
+    /// then node id.
     pub fn get_nodes_for_file_line(
         &self,
         path: &str,
@@ -10891,7 +11682,8 @@ impl Storage {
             "SELECT n.id, n.kind, n.serialized_name, n.qualified_name, n.canonical_id, n.file_node_id, n.start_line, n.start_col, n.end_line, n.end_col FROM node n
              JOIN occurrence o ON n.id = o.element_id
              JOIN file f ON o.file_node_id = f.id
-             WHERE f.path = ?1 AND ?2 >= o.start_line AND ?2 <= o.end_line",
+             WHERE f.path = ?1 AND ?2 >= o.start_line AND ?2 <= o.end_line
+             ORDER BY o.start_line, o.start_col, n.id",
         )?;
         let mut nodes = Vec::new();
         let mut rows = stmt.query(params![path, line])?;
@@ -10953,17 +11745,28 @@ impl Storage {
     }
 
     /// Get symbols that have no parent (root namespaces, top-level classes, etc.)
+    ///
+    /// Files are left out, and so are the top-level symbols of files grouped
+    /// under a directory package, which are reached through the package.
+    /// Ordered by serialized name, then node id.
     pub fn get_root_symbols(&self) -> Result<Vec<Node>, StorageError> {
         let mut stmt = self.conn.prepare(
             "SELECT id, kind, serialized_name, qualified_name, canonical_id, file_node_id, start_line, start_col, end_line, end_col FROM node
              WHERE id NOT IN (SELECT target_node_id FROM edge WHERE kind = ?1)
-             AND kind != ?2", // Exclude files from symbol tree roots for now
+             AND kind != ?2
+             AND (file_node_id IS NULL OR file_node_id NOT IN (
+                SELECT e.target_node_id FROM edge e
+                JOIN node package ON package.id = e.source_node_id
+                WHERE e.kind = ?1 AND package.canonical_id LIKE ?3
+             ))
+             ORDER BY serialized_name, id", // Exclude files from symbol tree roots for now
         )?;
         let kind_member = codestory_contracts::graph::EdgeKind::MEMBER as i32;
         let kind_file = codestory_contracts::graph::NodeKind::FILE as i32;
+        let package_pattern = format!("{DIRECTORY_PACKAGE_CANONICAL_PREFIX}%");
 
         let mut nodes = Vec::new();
-        let mut rows = stmt.query(params![kind_member, kind_file])?;
+        let mut rows = stmt.query(params![kind_member, kind_file, package_pattern])?;
         while let Some(row) = rows.next()? {
             nodes.push(Self::node_from_row(row)?);
         }
@@ -10971,39 +11774,219 @@ impl Storage {
     }
 
     /// Get children symbols for a parent symbol (members of a class/namespace)
+    ///
+    /// A file's children are its members plus its top-level symbols. Ordered
+    /// by declaration position (members without a span last), then
+    /// serialized name, then node id.
     pub fn get_children_symbols(&self, parent_id: NodeId) -> Result<Vec<Node>, StorageError> {
         let mut stmt = self.conn.prepare(
-            "SELECT n.id, n.kind, n.serialized_name, n.qualified_name, n.canonical_id, n.file_node_id, n.start_line, n.start_col, n.end_line, n.end_col FROM node n
-             JOIN edge e ON n.id = e.target_node_id
-             WHERE e.source_node_id = ?1 AND e.kind = ?2",
+            "SELECT id, kind, serialized_name, qualified_name, canonical_id, file_node_id, start_line, start_col, end_line, end_col FROM (
+                SELECT n.* FROM node n
+                JOIN edge e ON n.id = e.target_node_id
+                WHERE e.source_node_id = ?1 AND e.kind = ?2
+                UNION ALL
+                SELECT n.* FROM node n
+                WHERE n.file_node_id = ?1
+                  AND n.kind != ?3
+                  AND EXISTS (SELECT 1 FROM node parent WHERE parent.id = ?1 AND parent.kind = ?3)
+                  AND n.id NOT IN (SELECT target_node_id FROM edge WHERE kind = ?2)
+             )
+             ORDER BY start_line IS NULL, start_line, start_col, serialized_name, id",
         )?;
         let kind_member = codestory_contracts::graph::EdgeKind::MEMBER as i32;
+        let kind_file = codestory_contracts::graph::NodeKind::FILE as i32;
 
         let mut nodes = Vec::new();
-        let mut rows = stmt.query(params![parent_id.0, kind_member])?;
+        let mut rows = stmt.query(params![parent_id.0, kind_member, kind_file])?;
         while let Some(row) = rows.next()? {
             nodes.push(Self::node_from_row(row)?);
         }
         Ok(nodes)
     }
 
+    /// Children of `parent_id`, as [`Self::get_children_symbols`] finds them,
+    /// ordered by display name, from `offset`, at most `limit` of them. Of
+    /// children sharing a kind, display name and file only the first in
+    /// source order is kept.
+    pub fn get_children_symbols_page(
+        &self,
+        parent_id: NodeId,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<Node>, StorageError> {
+        let mut stmt = self.conn.prepare(&format!(
+            "{CHILD_SYMBOLS_CTE}
+             SELECT id, kind, serialized_name, qualified_name, canonical_id, file_node_id, start_line, start_col, end_line, end_col
+             FROM distinct_children
+             ORDER BY display_name, start_line IS NULL, start_line, start_col, serialized_name, id
+             LIMIT ?4 OFFSET ?5"
+        ))?;
+        let mut nodes = Vec::new();
+        let mut rows = stmt.query(params![
+            parent_id.0,
+            codestory_contracts::graph::EdgeKind::MEMBER as i32,
+            codestory_contracts::graph::NodeKind::FILE as i32,
+            limit.min(i64::MAX as usize) as i64,
+            offset.min(i64::MAX as usize) as i64
+        ])?;
+        while let Some(row) = rows.next()? {
+            nodes.push(Self::node_from_row(row)?);
+        }
+        Ok(nodes)
+    }
+
+    /// How many children [`Self::get_children_symbols_page`] pages through.
+    pub fn get_children_symbol_count(&self, parent_id: NodeId) -> Result<usize, StorageError> {
+        let count: i64 = self.conn.query_row(
+            &format!("{CHILD_SYMBOLS_CTE} SELECT COUNT(*) FROM distinct_children"),
+            params![
+                parent_id.0,
+                codestory_contracts::graph::EdgeKind::MEMBER as i32,
+                codestory_contracts::graph::NodeKind::FILE as i32
+            ],
+            |row| row.get(0),
+        )?;
+        Ok(count as usize)
+    }
+
+    /// Replace the package nodes synthesized from directories.
+    ///
+    /// JavaScript and TypeScript files declare no namespaces, so each
+    /// directory under `root` holding such files becomes a `PACKAGE` node,
+    /// nested through `MEMBER` edges, with its files as members. A leading
+    /// directory named in `package_roots` is not a package of its own. Returns
+    /// the number of package nodes written.
+    pub fn rebuild_directory_packages(
+        &mut self,
+        root: &Path,
+        package_roots: &[String],
+    ) -> Result<usize, StorageError> {
+        let files = {
+            let placeholders = question_placeholders(DIRECTORY_PACKAGE_LANGUAGES.len());
+            let mut stmt = self.conn.prepare(&format!(
+                "SELECT id, path FROM file WHERE language IN ({placeholders}) ORDER BY path"
+            ))?;
+            let rows = stmt.query_map(
+                rusqlite::params_from_iter(DIRECTORY_PACKAGE_LANGUAGES.iter()),
+                |row| Ok((NodeId(row.get(0)?), PathBuf::from(row.get::<_, String>(1)?))),
+            )?;
+            rows.collect::<Result<Vec<_>, _>>()?
+        };
+
+        let mut packages = BTreeMap::<String, Node>::new();
+        let mut edges = Vec::new();
+        for (file_id, path) in files {
+            let chain = directory_package_chain(root, &path, package_roots);
+            let mut parent: Option<NodeId> = None;
+            for dir in &chain {
+                let canonical_id = format!("{DIRECTORY_PACKAGE_CANONICAL_PREFIX}{dir}");
+                let id = NodeId(stable_synthetic_id(&canonical_id));
+                if !packages.contains_key(dir) {
+                    packages.insert(
+                        dir.clone(),
+                        Node {
+                            id,
+                            kind: NodeKind::PACKAGE,
+                            serialized_name: dir.rsplit('/').next().unwrap_or(dir).to_string(),
+                            qualified_name: Some(dir.clone()),
+                            canonical_id: Some(canonical_id),
+                            ..Default::default()
+                        },
+                    );
+                    if let Some(parent) = parent {
+                        edges.push(directory_package_member_edge(parent, id));
+                    }
+                }
+                parent = Some(id);
+            }
+            if let Some(parent) = parent {
+                edges.push(directory_package_member_edge(parent, file_id));
+            }
+        }
+
+        let nodes = packages.into_values().collect::<Vec<_>>();
+        self.replace_synthetic_packages(DIRECTORY_PACKAGE_CANONICAL_PREFIX, &nodes, &edges)?;
+        Ok(nodes.len())
+    }
+
+    /// Swap the package nodes whose canonical id starts with
+    /// `canonical_prefix`, and the edges leaving them, for `nodes` and
+    /// `edges`. Bookmarks on dropped packages are retired.
+    pub(super) fn replace_synthetic_packages(
+        &mut self,
+        canonical_prefix: &str,
+        nodes: &[Node],
+        edges: &[Edge],
+    ) -> Result<(), StorageError> {
+        let package_pattern = format!("{canonical_prefix}%");
+        let keep = nodes.iter().map(|node| node.id.0).collect::<HashSet<_>>();
+        let tx = self.conn.transaction()?;
+        let stale = {
+            let mut stmt = tx.prepare("SELECT id FROM node WHERE canonical_id LIKE ?1")?;
+            let ids = stmt
+                .query_map(params![package_pattern], |row| row.get::<_, i64>(0))?
+                .collect::<Result<Vec<_>, _>>()?;
+            ids.into_iter()
+                .filter(|id| !keep.contains(id))
+                .collect::<Vec<_>>()
+        };
+        tx.execute(
+            "DELETE FROM edge WHERE source_node_id IN (
+                SELECT id FROM node WHERE canonical_id LIKE ?1
+             )",
+            params![package_pattern],
+        )?;
+        delete_detached_nodes_on(&tx, &stale)?;
+        tx.commit()?;
+        self.cache.nodes.lock().clear();
+
+        self.insert_nodes_batch(nodes)?;
+        self.insert_edges_batch(edges)?;
+        Ok(())
+    }
+
     /// Return store counts, preferring ready summary snapshots when available.
+    ///
+    /// Snapshots carry the counts taken when they were built, so reading
+    /// stats after an index run does not scan the graph tables again.
     pub fn get_stats(&self) -> Result<StorageStats, StorageError> {
         let fatal_error_count = self.fatal_error_count()?;
+        let count_occurrences = || -> Result<i64, StorageError> {
+            Ok(self
+                .conn
+                .query_row("SELECT count(*) FROM occurrence", [], |r| r.get(0))?)
+        };
+        let database_bytes: i64 = self.conn.query_row(
+            "SELECT page_count * page_size FROM pragma_page_count(), pragma_page_size()",
+            [],
+            |r| r.get(0),
+        )?;
+        let database_bytes = u64::try_from(database_bytes).unwrap_or(0);
+        let last_indexed_at_epoch_ms = self
+            .get_complete_index_publication()?
+            .map(|publication| publication.published_at_epoch_ms);
         if self.has_ready_grounding_summary_snapshots()? {
             let mut stmt = self.conn.prepare(
-                "SELECT node_count, edge_count, file_count, error_count
+                "SELECT node_count, edge_count, file_count, error_count, occurrence_count
                  FROM grounding_repo_stats_snapshot
                  WHERE id = 1",
             )?;
             let mut rows = stmt.query([])?;
             if let Some(row) = rows.next()? {
+                let occurrence_count = match row.get::<_, Option<i64>>(4)? {
+                    Some(count) => count,
+                    None => count_occurrences()?,
+                };
                 return Ok(StorageStats {
                     node_count: row.get(0)?,
                     edge_count: row.get(1)?,
                     file_count: row.get(2)?,
                     error_count: row.get(3)?,
                     fatal_error_count,
+                    occurrence_count,
+                    database_bytes,
+                    last_indexed_at_epoch_ms,
+                    tables: Vec::new(),
                 });
             }
         }
@@ -11025,9 +12008,78 @@ impl Storage {
             file_count,
             error_count,
             fatal_error_count,
+            occurrence_count: count_occurrences()?,
+            database_bytes,
+            last_indexed_at_epoch_ms,
+            tables: Vec::new(),
         })
     }
 
+    /// [`Storage::get_stats`] plus the row count and disk use of every table.
+    ///
+    /// Every table is counted, so this scans each one; it is meant for
+    /// diagnostics rather than routine summaries.
+    pub fn get_detailed_stats(&self) -> Result<StorageStats, StorageError> {
+        let mut stats = self.get_stats()?;
+        let table_names = {
+            let mut stmt = self.conn.prepare(
+                "SELECT name FROM sqlite_master
+                 WHERE type = 'table' AND name NOT LIKE 'sqlite_%'",
+            )?;
+            stmt.query_map([], |row| row.get::<_, String>(0))?
+                .collect::<Result<Vec<_>, _>>()?
+        };
+        let bytes_by_table = self.table_bytes_from_dbstat();
+        for name in table_names {
+            let row_count: i64 = self.conn.query_row(
+                &format!("SELECT count(*) FROM \"{}\"", name.replace('"', "\"\"")),
+                [],
+                |r| r.get(0),
+            )?;
+            let bytes = bytes_by_table
+                .as_ref()
+                .map(|bytes| bytes.get(&name).copied().unwrap_or(0));
+            stats.tables.push(StorageTableStats {
+                name,
+                row_count,
+                bytes,
+            });
+        }
+        stats.tables.sort_by(|left, right| {
+            right
+                .bytes
+                .cmp(&left.bytes)
+                .then_with(|| right.row_count.cmp(&left.row_count))
+                .then_with(|| left.name.cmp(&right.name))
+        });
+        Ok(stats)
+    }
+
+    /// Bytes per table, counting each index under its table, or `None` when
+    /// the `dbstat` virtual table is unavailable.
+    fn table_bytes_from_dbstat(&self) -> Option<HashMap<String, u64>> {
+        let mut stmt = self
+            .conn
+            .prepare(
+                "SELECT COALESCE(m.tbl_name, d.name), SUM(d.pgsize)
+                 FROM (SELECT name, pgsize FROM dbstat WHERE aggregate = TRUE) d
+                 LEFT JOIN sqlite_master m ON m.name = d.name
+                 GROUP BY 1",
+            )
+            .ok()?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
+            })
+            .ok()?;
+        let mut bytes = HashMap::new();
+        for row in rows {
+            let (name, size) = row.ok()?;
+            bytes.insert(name, u64::try_from(size).unwrap_or(0));
+        }
+        Some(bytes)
+    }
+
     fn fatal_error_count(&self) -> Result<i64, StorageError> {
         self.conn
             .query_row("SELECT count(*) FROM error WHERE fatal = 1", [], |r| {
@@ -11048,6 +12100,7 @@ impl Storage {
              );
              DELETE FROM related_node_ids;",
         )?;
+        let handed_over_node_ids = hand_over_shared_nodes(&tx, file_node_id)?;
         tx.execute(
             &format!(
                 "INSERT INTO {RELATED_NODE_IDS_TABLE} (node_id)
@@ -11110,9 +12163,36 @@ impl Storage {
             params![file_node_id],
         )?;
 
-        let removed_bookmarks = tx.execute(
+        // Bookmarked nodes, and nodes that already succeed an earlier
+        // tombstone, are retired rather than forgotten so a symbol that moved
+        // to another file keeps its bookmarks.
+        tx.execute(
             &format!(
-                "DELETE FROM bookmark_node WHERE node_id IN (SELECT node_id FROM {RELATED_NODE_IDS_TABLE})"
+                "INSERT INTO node_tombstone (
+                    node_id, kind, serialized_name, qualified_name,
+                    successor_node_id, retired_at_epoch_ms
+                 )
+                 SELECT n.id, n.kind, n.serialized_name, n.qualified_name, NULL, ?1
+                 FROM node n
+                 WHERE n.id IN (SELECT node_id FROM {RELATED_NODE_IDS_TABLE})
+                   AND (
+                     EXISTS (SELECT 1 FROM bookmark_node b WHERE b.node_id = n.id)
+                     OR EXISTS (SELECT 1 FROM node_tombstone t WHERE t.successor_node_id = n.id)
+                   )
+                 ON CONFLICT(node_id) DO UPDATE SET
+                    kind = excluded.kind,
+                    serialized_name = excluded.serialized_name,
+                    qualified_name = excluded.qualified_name,
+                    successor_node_id = NULL,
+                    retired_at_epoch_ms = excluded.retired_at_epoch_ms"
+            ),
+            params![current_epoch_ms()],
+        )?;
+        let retired_bookmarks = tx.execute(
+            &format!(
+                "UPDATE bookmark_node
+                 SET retired_node_id = node_id, node_id = NULL
+                 WHERE node_id IN (SELECT node_id FROM {RELATED_NODE_IDS_TABLE})"
             ),
             [],
         )?;
@@ -11197,12 +12277,23 @@ impl Storage {
             [],
         )?;
 
+        tx.execute(
+            &format!(
+                "DELETE FROM shared_node_ref
+                 WHERE node_id IN (SELECT node_id FROM {RELATED_NODE_IDS_TABLE})"
+            ),
+            [],
+        )?;
+
         // Remove any node references in other projection tables.
         let removed_nodes = tx.execute(
             &format!("DELETE FROM node WHERE id IN (SELECT node_id FROM {RELATED_NODE_IDS_TABLE})"),
             [],
         )?;
 
+        link_node_tombstone_successors(&tx, None)?;
+        let removed_bookmarks = bookmarks::settle_retired_bookmarks(&tx, current_epoch_ms())?;
+
         let removed_errors = tx.execute(
             "DELETE FROM error WHERE file_id = ?1",
             params![file_node_id],
@@ -11215,8 +12306,8 @@ impl Storage {
         self.invalidate_grounding_snapshots()?;
 
         {
-            let mut nodes = self.cache.nodes.write();
-            for node_id in related_node_ids {
+            let mut nodes = self.cache.nodes.lock();
+            for node_id in related_node_ids.into_iter().chain(handed_over_node_ids) {
                 nodes.remove(&NodeId(node_id));
             }
         }
@@ -11228,6 +12319,7 @@ impl Storage {
             removed_occurrence_count: removed_occurrences,
             removed_error_count: removed_errors,
             removed_bookmark_node_count: removed_bookmarks,
+            retired_bookmark_node_count: retired_bookmarks,
             removed_component_access_count: removed_component_access,
             removed_local_symbol_count: removed_local_symbols,
             removed_file_row_count: removed_file_rows,
@@ -11253,15 +12345,40 @@ impl Storage {
     // Error Management
     // ========================================================================
 
-    /// Get all errors with optional filtering
+    /// Get all errors with optional filtering, ordered by error id.
     pub fn get_errors(
         &self,
         filter: Option<&codestory_contracts::graph::ErrorFilter>,
     ) -> Result<Vec<codestory_contracts::graph::ErrorInfo>, StorageError> {
-        let base_query =
-            "SELECT id, message, file_id, line, column, fatal, indexed, coverage_reason FROM error";
-        let mut conditions = Vec::new();
+        self.query_errors(filter, 0, usize::MAX)
+    }
+
+    /// The errors [`Self::get_errors`] lists, from `offset`, at most `limit`
+    /// of them.
+    pub fn get_errors_page(
+        &self,
+        filter: Option<&codestory_contracts::graph::ErrorFilter>,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<codestory_contracts::graph::ErrorInfo>, StorageError> {
+        self.query_errors(filter, offset, limit)
+    }
+
+    /// How many errors [`Self::get_errors`] lists.
+    pub fn get_error_count(
+        &self,
+        filter: Option<&codestory_contracts::graph::ErrorFilter>,
+    ) -> Result<usize, StorageError> {
+        let query = format!(
+            "SELECT COUNT(*) FROM error{}",
+            Self::error_filter_clause(filter)
+        );
+        let count: i64 = self.conn.query_row(&query, [], |row| row.get(0))?;
+        Ok(count as usize)
+    }
 
+    fn error_filter_clause(filter: Option<&codestory_contracts::graph::ErrorFilter>) -> String {
+        let mut conditions = Vec::new();
         if let Some(f) = filter {
             if f.fatal_only {
                 conditions.push("fatal = 1");
@@ -11270,16 +12387,32 @@ impl Storage {
                 conditions.push("indexed = 1");
             }
         }
-
-        let query = if conditions.is_empty() {
-            base_query.to_string()
+        if conditions.is_empty() {
+            String::new()
         } else {
-            format!("{} WHERE {}", base_query, conditions.join(" AND "))
-        };
+            format!(" WHERE {}", conditions.join(" AND "))
+        }
+    }
+
+    fn query_errors(
+        &self,
+        filter: Option<&codestory_contracts::graph::ErrorFilter>,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<codestory_contracts::graph::ErrorInfo>, StorageError> {
+        let query = format!(
+            "SELECT id, message, file_id, line, column, fatal, indexed, coverage_reason FROM error{}
+             ORDER BY id
+             LIMIT ?1 OFFSET ?2",
+            Self::error_filter_clause(filter)
+        );
 
         let mut stmt = self.conn.prepare(&query)?;
         let mut errors = Vec::new();
-        let mut rows = stmt.query([])?;
+        let mut rows = stmt.query(params![
+            limit.min(i64::MAX as usize) as i64,
+            offset.min(i64::MAX as usize) as i64
+        ])?;
 
         while let Some(row) = rows.next()? {
             let fatal: i32 = row.get(5)?;
@@ -11362,6 +12495,38 @@ impl Storage {
         Ok(())
     }
 
+    /// Replace the errors not tied to any file, which describe a whole
+    /// indexing run, such as rule compile failures.
+    pub fn replace_run_errors(
+        &mut self,
+        errors: &[codestory_contracts::graph::ErrorInfo],
+    ) -> Result<(), StorageError> {
+        debug_assert!(errors.iter().all(|error| error.file_id.is_none()));
+        let tx = self.conn.transaction()?;
+        let removed_error_count = tx.execute("DELETE FROM error WHERE file_id IS NULL", [])?;
+        {
+            let mut insert = tx.prepare(
+                "INSERT INTO error (message, file_id, line, column, fatal, indexed, coverage_reason)
+                 VALUES (?1, NULL, ?2, ?3, ?4, ?5, ?6)",
+            )?;
+            for error in errors {
+                insert.execute(params![
+                    error.message,
+                    error.line,
+                    error.column,
+                    error.is_fatal as i32,
+                    (error.index_step == codestory_contracts::graph::IndexStep::Indexing) as i32,
+                    error.coverage_reason.map(FileCoverageReason::as_str),
+                ])?;
+            }
+        }
+        tx.commit()?;
+        if removed_error_count > 0 || !errors.is_empty() {
+            self.invalidate_grounding_snapshots()?;
+        }
+        Ok(())
+    }
+
     // ========================================================================
     // Bookmark Management
     // ========================================================================
@@ -11398,7 +12563,30 @@ impl Storage {
 
     /// Get bookmarks, optionally filtered by category
     pub fn get_bookmarks(&self, category_id: Option<i64>) -> Result<Vec<Bookmark>, StorageError> {
-        bookmarks::get_bookmarks(&self.conn, category_id)
+        bookmarks::get_bookmarks(&self.conn, category_id, None)
+    }
+
+    /// The bookmarks [`Self::get_bookmarks`] lists, from `offset`, at most
+    /// `limit` of them.
+    pub fn get_bookmarks_page(
+        &self,
+        category_id: Option<i64>,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<Bookmark>, StorageError> {
+        bookmarks::get_bookmarks(&self.conn, category_id, Some((offset, limit)))
+    }
+
+    /// How many bookmarks [`Self::get_bookmarks`] lists.
+    pub fn get_bookmark_count(&self, category_id: Option<i64>) -> Result<usize, StorageError> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM bookmark_node
+             WHERE (?1 IS NULL OR category_id = ?1)
+               AND (node_id IS NOT NULL OR retired_node_id IS NOT NULL)",
+            params![category_id],
+            |row| row.get(0),
+        )?;
+        Ok(count as usize)
     }
 
     /// Update a bookmark's comment
@@ -11421,13 +12609,110 @@ impl Storage {
         bookmarks::delete_bookmark(&self.conn, id)
     }
 
+    /// Classify every bookmark as valid, resolvable, or orphaned.
+    pub fn bookmark_health(&self) -> Result<Vec<BookmarkHealth>, StorageError> {
+        bookmarks::bookmark_health(&self.conn)
+    }
+
+    /// Rebind resolvable bookmarks to their live nodes.
+    pub fn repair_bookmarks(&self) -> Result<usize, StorageError> {
+        bookmarks::repair_bookmarks(&self.conn)
+    }
+
     // ========================================================================
     // Trail Query (BFS-based subgraph exploration)
     // ========================================================================
 
     /// Get a trail (subgraph) starting from a root node up to a certain depth
+    ///
+    /// Nodes are ordered by trail depth, then node id, so the root comes
+    /// first; edges are ordered by edge id.
     pub fn get_trail(&self, config: &TrailConfig) -> Result<TrailResult, StorageError> {
-        trail::get_trail(self, config)
+        trail::get_trail(self, config, &TrailStop::default())
+    }
+
+    /// [`Storage::get_trail`] that gives up once `stop` trips, returning the
+    /// partial trail with `truncated_by_timeout` set.
+    pub fn get_trail_until(
+        &self,
+        config: &TrailConfig,
+        stop: &TrailStop,
+    ) -> Result<TrailResult, StorageError> {
+        trail::get_trail(self, config, stop)
+    }
+
+    /// Collect every node reachable from `node_id` without materializing a
+    /// trail.
+    ///
+    /// Expansion is breadth-first with one batched edge query per frontier
+    /// chunk, and stops after `max_nodes` reached nodes. An empty
+    /// `edge_kinds` follows every edge kind.
+    pub fn reachable_set(
+        &self,
+        node_id: NodeId,
+        direction: TrailDirection,
+        edge_kinds: &[EdgeKind],
+        max_nodes: usize,
+    ) -> Result<ReachableSet, StorageError> {
+        trail::reachable_set(self, node_id, direction, edge_kinds, max_nodes)
+    }
+
+    /// Node row, declaring file, summary, and caller/callee/member counts in
+    /// one query, for hover previews.
+    pub fn get_node_preview(&self, id: NodeId) -> Result<Option<NodePreview>, StorageError> {
+        let sql = format!(
+            "WITH edge_effective AS (
+                SELECT kind,
+                       COALESCE(resolved_source_node_id, source_node_id) AS source_id,
+                       COALESCE(resolved_target_node_id, target_node_id) AS target_id
+                FROM edge
+                WHERE source_node_id = ?1
+                   OR target_node_id = ?1
+                   OR resolved_source_node_id = ?1
+                   OR resolved_target_node_id = ?1
+            )
+            SELECT n.id, n.kind, n.serialized_name, n.qualified_name, n.canonical_id,
+                   n.file_node_id, n.start_line, n.start_col, n.end_line, n.end_col,
+                   file_node.serialized_name,
+                   (SELECT summary FROM symbol_summary
+                    WHERE node_id = n.id
+                    ORDER BY updated_at_epoch_ms DESC
+                    LIMIT 1),
+                   (SELECT type FROM component_access WHERE node_id = n.id),
+                   (SELECT COUNT(DISTINCT source_id) FROM edge_effective
+                    WHERE kind = {call_kind} AND target_id = n.id AND source_id != n.id),
+                   (SELECT COUNT(DISTINCT target_id) FROM edge_effective
+                    WHERE kind = {call_kind} AND source_id = n.id AND target_id != n.id),
+                   (SELECT COUNT(DISTINCT target_id) FROM edge_effective
+                    WHERE kind = {member_kind} AND source_id = n.id AND target_id != n.id)
+            FROM node n
+            LEFT JOIN node file_node ON file_node.id = n.file_node_id
+            WHERE n.id = ?1",
+            call_kind = EdgeKind::CALL as i32,
+            member_kind = EdgeKind::MEMBER as i32,
+        );
+        let mut stmt = self.conn.prepare(&sql)?;
+        let mut rows = stmt.query(params![id.0])?;
+        let Some(row) = rows.next()? else {
+            return Ok(None);
+        };
+        Ok(Some(NodePreview {
+            node: Self::node_from_row(row)?,
+            file_path: row.get(10)?,
+            summary: row.get::<_, Option<String>>(11)?.and_then(|summary| {
+                summary
+                    .lines()
+                    .map(str::trim)
+                    .find(|line| !line.is_empty())
+                    .map(ToOwned::to_owned)
+            }),
+            access: row
+                .get::<_, Option<i32>>(12)?
+                .map(row_mapping::access_kind_from_db),
+            caller_count: row.get(13)?,
+            callee_count: row.get(14)?,
+            member_count: row.get(15)?,
+        }))
     }
 
     /// Helper: Get edges for a node in a specific direction
@@ -11548,6 +12833,19 @@ fn neighbor_for_direction(
     }
 }
 
+fn directory_package_member_edge(source: NodeId, target: NodeId) -> Edge {
+    Edge {
+        id: codestory_contracts::graph::EdgeId(stable_synthetic_id(&format!(
+            "{DIRECTORY_PACKAGE_CANONICAL_PREFIX}member:{}:{}",
+            source.0, target.0
+        ))),
+        source,
+        target,
+        kind: EdgeKind::MEMBER,
+        ..Default::default()
+    }
+}
+
 fn apply_trail_node_filter(result: &mut TrailResult, config: &TrailConfig) {
     if config.node_filter.is_empty() {
         return;
@@ -11566,7 +12864,9 @@ fn apply_trail_node_filter(result: &mut TrailResult, config: &TrailConfig) {
         allowed.insert(target);
     }
 
+    let before = result.nodes.len();
     result.nodes.retain(|node| allowed.contains(&node.id));
+    result.omitted_node_count += (before - result.nodes.len()) as u32;
     result.edges.retain(|edge| {
         let (s, t) = edge.effective_endpoints();
         allowed.contains(&s) && allowed.contains(&t)
@@ -11597,11 +12897,47 @@ fn is_test_or_bench_path(path: &str) -> bool {
         || normalized.contains("/__test__/")
         || normalized.contains("/benches/")
         || normalized.contains("/bench/")
-        || normalized.ends_with("_test.rs")
+        || normalized.starts_with("spec/")
+        || normalized.contains("/spec/")
+        || normalized.contains("_test.")
+        || normalized.contains("_spec.")
         || normalized.contains(".test.")
         || normalized.contains(".spec.")
 }
 
+/// Longest body, in lines, that still counts as a trivial accessor.
+const TRIVIAL_ACCESSOR_MAX_LINES: u32 = 3;
+
+/// Short `get_x`/`setX`/`is_x`-style functions and methods.
+///
+/// Nodes without a recorded span are never treated as trivial.
+fn is_trivial_accessor(node: &Node) -> bool {
+    if !matches!(node.kind, NodeKind::FUNCTION | NodeKind::METHOD) {
+        return false;
+    }
+    let (Some(start), Some(end)) = (node.start_line, node.end_line) else {
+        return false;
+    };
+    if end.saturating_sub(start) + 1 > TRIVIAL_ACCESSOR_MAX_LINES {
+        return false;
+    }
+    let name = node
+        .qualified_name
+        .as_deref()
+        .unwrap_or(&node.serialized_name);
+    let name = name.rsplit([':', '.']).next().unwrap_or(name);
+    ["get", "set", "is", "has"].iter().any(|prefix| {
+        name.strip_prefix(prefix).is_some_and(|rest| {
+            let mut chars = rest.chars();
+            match chars.next() {
+                Some('_') => chars.next().is_some(),
+                Some(first) => first.is_ascii_uppercase(),
+                None => false,
+            }
+        })
+    })
+}
+
 fn should_ignore_call_resolution(
     target_symbol: &str,
     certainty: Option<ResolutionCertainty>,
@@ -12040,7 +13376,41 @@ mod grounding_snapshot_fast_path_tests {
     }
 }
 
+pub use bookmarks::{BookmarkHealth, BookmarkHealthStatus};
+pub use centrality::{CENTRALITY_DEFAULT_ITERATIONS, CentralityStats};
+pub use code_markers::{
+    CodeMarker, CodeMarkerFilter, CodeMarkerRecord, CodeMarkerScan, PendingSourceScan,
+};
+pub use content_digest::{IndexContentDelta, IndexContentDigest};
+pub use definitions::NodeDefinition;
+pub use dependency_resolution::DependencyResolution;
+pub use edge_counts::NodeEdgeCounts;
+pub use encryption::{
+    StorageEncryptionKey, StorageOpenOptions, encryption_supported, install_process_encryption_key,
+};
+use encryption::{apply_encryption_key, open_keyed_connection, validate_explicit_key};
+pub use entry_points::EntryPointDistances;
+pub use graph_match::{GraphMatch, GraphMatchFilter, GraphMatches};
+pub use index_history::{
+    INDEX_HISTORY_RETAINED_RUNS, IndexGraphAt, IndexHistoryEvent, IndexHistoryRun,
+    IndexHistorySubject,
+};
+pub use java_types::JavaTypeLinks;
+pub use maintenance::{
+    StorageIndexUsage, StorageMaintenanceReport, enable_process_integrity_check,
+};
+use maintenance::{process_integrity_check_enabled, verify_integrity};
+pub use manifest_packages::ManifestPackage;
+pub use node_aliases::NodeAlias;
+pub use node_badges::NodeBadges;
+use node_cache::NodeCache;
+pub use node_cache::{NodeCacheLimits, NodeCacheStats};
 pub use retrieval_manifest::{RetrievalIndexManifest, RetrievalIndexRollbackRecord};
+pub use string_literals::{StringLiteral, StringLiteralRecord, StringLiteralScan};
+pub use test_mapping::{TEST_MAPPING_MAX_DEPTH, TestReach};
+pub use trail::TrailStop;
+pub use trail_presets::TrailFilterPreset;
+pub use workspace_symbols::{WorkspaceSymbol, WorkspaceSymbolFilter, WorkspaceSymbolGroup};
 
 #[cfg(test)]
 mod tests;
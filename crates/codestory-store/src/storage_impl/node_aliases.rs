@@ -0,0 +1,293 @@
+use super::*;
+
+/// A node merged into another as a duplicate.
+///
+/// Both sides are recorded by kind and canonical name (the qualified name, or
+/// the serialized name when there is none), so the merge outlives the node
+/// ids of any one index run and rebinds to whichever nodes carry those names
+/// after re-indexing. A merge of same-name duplicates records the name as its
+/// own primary: every live node carrying it folds into the lowest-id one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeAlias {
+    pub kind: NodeKind,
+    pub name: String,
+    pub primary_kind: NodeKind,
+    pub primary_name: String,
+    /// Live node currently carrying `kind` and `name`, if any, other than
+    /// the primary.
+    pub node_id: Option<NodeId>,
+    /// Live node currently carrying `primary_kind` and `primary_name`, if any.
+    pub primary_node_id: Option<NodeId>,
+}
+
+/// Live alias bindings, used to redirect query results onto primary nodes.
+#[derive(Debug, Default)]
+pub(super) struct NodeAliasMap {
+    primary_by_alias: HashMap<NodeId, NodeId>,
+    /// Aliases of each primary, lowest id first.
+    aliases_by_primary: HashMap<NodeId, Vec<NodeId>>,
+}
+
+impl NodeAliasMap {
+    pub(super) fn primary_of(&self, id: NodeId) -> NodeId {
+        self.primary_by_alias.get(&id).copied().unwrap_or(id)
+    }
+
+    /// `primary` followed by every live node merged into it.
+    pub(super) fn with_aliases(&self, primary: NodeId) -> Vec<NodeId> {
+        let mut ids = vec![primary];
+        if let Some(aliases) = self.aliases_by_primary.get(&primary) {
+            ids.extend_from_slice(aliases);
+        }
+        ids
+    }
+
+    /// Point every endpoint of `edge` that is an alias at its primary.
+    pub(super) fn redirect_edge(&self, edge: &mut Edge) {
+        if self.primary_by_alias.is_empty() {
+            return;
+        }
+        edge.source = self.primary_of(edge.source);
+        edge.target = self.primary_of(edge.target);
+        edge.resolved_source = edge.resolved_source.map(|id| self.primary_of(id));
+        edge.resolved_target = edge.resolved_target.map(|id| self.primary_of(id));
+    }
+}
+
+type AliasKey = (i32, String);
+
+fn node_alias_key(conn: &Connection, id: NodeId) -> Result<Option<AliasKey>, StorageError> {
+    Ok(conn
+        .query_row(
+            "SELECT kind, COALESCE(qualified_name, serialized_name) FROM node WHERE id = ?1",
+            params![id.0],
+            |row| Ok((row.get::<_, i32>(0)?, row.get::<_, String>(1)?)),
+        )
+        .optional()?)
+}
+
+/// Live nodes with `kind` and canonical `name`, lowest id first.
+fn live_nodes_for_key(
+    conn: &Connection,
+    kind: i32,
+    name: &str,
+) -> Result<Vec<NodeId>, StorageError> {
+    let mut stmt = conn.prepare_cached(
+        "SELECT id FROM node
+         WHERE kind = ?1
+           AND (qualified_name = ?2 OR (qualified_name IS NULL AND serialized_name = ?2))
+         ORDER BY id",
+    )?;
+    let ids = stmt
+        .query_map(params![kind, name], |row| row.get::<_, i64>(0))?
+        .map(|id| id.map(NodeId))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(ids)
+}
+
+/// Each recorded merge with the live nodes bound to its alias side and the
+/// live node bound to its primary side.
+fn load_bound_aliases(conn: &Connection) -> Result<Vec<(NodeAlias, Vec<NodeId>)>, StorageError> {
+    let rows = {
+        let mut stmt = conn.prepare(
+            "SELECT alias_kind, alias_name, primary_kind, primary_name
+             FROM node_alias
+             ORDER BY primary_name, primary_kind, alias_name, alias_kind",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, i32>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, i32>(2)?,
+                row.get::<_, String>(3)?,
+            ))
+        })?;
+        rows.collect::<Result<Vec<_>, _>>()?
+    };
+    rows.into_iter()
+        .map(|(kind, name, primary_kind, primary_name)| {
+            let primary_node_id = live_nodes_for_key(conn, primary_kind, &primary_name)?
+                .first()
+                .copied();
+            let node_ids = live_nodes_for_key(conn, kind, &name)?
+                .into_iter()
+                .filter(|id| Some(*id) != primary_node_id)
+                .collect::<Vec<_>>();
+            let alias = NodeAlias {
+                node_id: node_ids.first().copied(),
+                primary_node_id,
+                kind: NodeKind::try_from(kind)?,
+                name,
+                primary_kind: NodeKind::try_from(primary_kind)?,
+                primary_name,
+            };
+            Ok((alias, node_ids))
+        })
+        .collect()
+}
+
+pub(super) fn load_node_aliases(conn: &Connection) -> Result<Vec<NodeAlias>, StorageError> {
+    Ok(load_bound_aliases(conn)?
+        .into_iter()
+        .map(|(alias, _)| alias)
+        .collect())
+}
+
+fn load_node_alias_map(conn: &Connection) -> Result<NodeAliasMap, StorageError> {
+    let mut primary_by_alias = HashMap::new();
+    for (alias, node_ids) in load_bound_aliases(conn)? {
+        let Some(primary) = alias.primary_node_id else {
+            continue;
+        };
+        primary_by_alias.extend(node_ids.into_iter().map(|id| (id, primary)));
+    }
+    let mut aliases_by_primary = HashMap::<NodeId, Vec<NodeId>>::new();
+    for (alias, primary) in &primary_by_alias {
+        aliases_by_primary.entry(*primary).or_default().push(*alias);
+    }
+    for aliases in aliases_by_primary.values_mut() {
+        aliases.sort_by_key(|id| id.0);
+    }
+    Ok(NodeAliasMap {
+        primary_by_alias,
+        aliases_by_primary,
+    })
+}
+
+/// Database state an alias map was loaded at: the commits seen from other
+/// connections and the rows changed through this one. Either moving means
+/// the merges or the nodes they bind to may have changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct AliasMapVersion {
+    data_version: i64,
+    total_changes: u64,
+}
+
+/// The alias map of one store, kept until the database changes.
+#[derive(Default)]
+pub(super) struct NodeAliasCache {
+    loaded: Mutex<Option<(AliasMapVersion, Arc<NodeAliasMap>)>>,
+}
+
+impl Storage {
+    /// Live alias bindings, loaded once and reused until the database
+    /// changes, so trail searches and definition lookups that consult them
+    /// for every node read the `node_alias` table only once.
+    pub(super) fn node_alias_map(&self) -> Result<Arc<NodeAliasMap>, StorageError> {
+        let version = AliasMapVersion {
+            data_version: self
+                .conn
+                .query_row("PRAGMA data_version", [], |row| row.get(0))?,
+            total_changes: self.conn.total_changes(),
+        };
+        let mut loaded = self.cache.aliases.loaded.lock();
+        if let Some((loaded_version, map)) = loaded.as_ref()
+            && *loaded_version == version
+        {
+            return Ok(Arc::clone(map));
+        }
+        let map = Arc::new(load_node_alias_map(&self.conn)?);
+        *loaded = Some((version, Arc::clone(&map)));
+        Ok(map)
+    }
+
+    /// Merge `duplicates` into `primary`: queries for the primary also return
+    /// the duplicates' edges and occurrences, with the duplicates' ids
+    /// replaced by the primary's. Returns the number of duplicates recorded.
+    ///
+    /// A primary that was itself merged into another node forwards the
+    /// duplicates there, and nodes already merged into a duplicate move to
+    /// the new primary, so merges never chain. A duplicate with the same kind
+    /// and name as the primary merges every node carrying that name into the
+    /// lowest-id one.
+    pub fn merge_nodes(
+        &self,
+        primary: NodeId,
+        duplicates: &[NodeId],
+    ) -> Result<usize, StorageError> {
+        let missing = |id: NodeId| StorageError::Other(format!("Node {} not found", id.0));
+        let mut primary_key =
+            node_alias_key(&self.conn, primary)?.ok_or_else(|| missing(primary))?;
+        if let Some(forwarded) = self
+            .conn
+            .query_row(
+                "SELECT primary_kind, primary_name FROM node_alias
+                 WHERE alias_kind = ?1 AND alias_name = ?2",
+                params![primary_key.0, primary_key.1],
+                |row| Ok((row.get::<_, i32>(0)?, row.get::<_, String>(1)?)),
+            )
+            .optional()?
+        {
+            primary_key = forwarded;
+        }
+        let mut duplicate_keys = Vec::new();
+        for id in duplicates.iter().filter(|id| **id != primary) {
+            let key = node_alias_key(&self.conn, *id)?.ok_or_else(|| missing(*id))?;
+            if !duplicate_keys.contains(&key) {
+                duplicate_keys.push(key);
+            }
+        }
+
+        let tx = self.conn.unchecked_transaction()?;
+        let mut recorded = 0;
+        for key in &duplicate_keys {
+            tx.execute(
+                "UPDATE node_alias SET primary_kind = ?1, primary_name = ?2
+                 WHERE primary_kind = ?3 AND primary_name = ?4",
+                params![primary_key.0, primary_key.1, key.0, key.1],
+            )?;
+            recorded += tx.execute(
+                "INSERT OR REPLACE INTO node_alias (alias_kind, alias_name, primary_kind, primary_name)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![key.0, key.1, primary_key.0, primary_key.1],
+            )?;
+        }
+        tx.commit()?;
+        Ok(recorded)
+    }
+
+    /// Undo the merge of `node_id` into its primary. Returns whether it had
+    /// been merged.
+    pub fn unmerge_node(&self, node_id: NodeId) -> Result<bool, StorageError> {
+        let Some((kind, name)) = node_alias_key(&self.conn, node_id)? else {
+            return Ok(false);
+        };
+        let removed = self.conn.execute(
+            "DELETE FROM node_alias WHERE alias_kind = ?1 AND alias_name = ?2",
+            params![kind, name],
+        )?;
+        Ok(removed > 0)
+    }
+
+    /// Every recorded merge, bound to the live nodes that match it now.
+    pub fn node_aliases(&self) -> Result<Vec<NodeAlias>, StorageError> {
+        load_node_aliases(&self.conn)
+    }
+
+    /// The primary `node_id` was merged into, or `node_id` itself.
+    pub fn resolve_node_alias(&self, node_id: NodeId) -> Result<NodeId, StorageError> {
+        Ok(self.node_alias_map()?.primary_of(node_id))
+    }
+
+    /// Copy recorded merges from another index, such as the live index a
+    /// full refresh replaces. Returns the number of merges copied.
+    pub fn copy_node_aliases_from(&mut self, source_path: &Path) -> Result<usize, StorageError> {
+        if !source_path.exists() {
+            return Ok(0);
+        }
+        drop(Storage::open(source_path)?);
+        let source = source_path.to_string_lossy().to_string();
+        self.conn
+            .execute("ATTACH DATABASE ?1 AS source_snapshot", params![source])?;
+        let copy_result = self.conn.execute(
+            "INSERT OR REPLACE INTO node_alias (alias_kind, alias_name, primary_kind, primary_name)
+             SELECT alias_kind, alias_name, primary_kind, primary_name
+             FROM source_snapshot.node_alias",
+            [],
+        );
+        let detach_result = self.conn.execute("DETACH DATABASE source_snapshot", []);
+        let copied = copy_result?;
+        detach_result?;
+        Ok(copied)
+    }
+}
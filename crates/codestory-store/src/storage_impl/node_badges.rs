@@ -0,0 +1,87 @@
+use super::*;
+
+/// Per-node decorations graph views draw without fetching more data.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NodeBadges {
+    /// The node's file, or the node itself when it is a file, has indexing
+    /// errors.
+    pub has_errors: bool,
+    pub is_bookmarked: bool,
+    /// Some bookmark on the node carries a non-empty comment.
+    pub has_annotation: bool,
+    /// `TODO`, `FIXME`, and `HACK` markers in a file node, or within the span
+    /// of a function or method node.
+    pub marker_count: u32,
+    /// The node stands for a type declared outside the project, such as
+    /// the shared node Java supertypes from libraries link to.
+    pub is_external: bool,
+}
+
+impl Storage {
+    /// Badges for each of `node_ids`. Nodes without any badge are omitted.
+    pub fn node_badges(
+        &self,
+        node_ids: &[NodeId],
+    ) -> Result<HashMap<NodeId, NodeBadges>, StorageError> {
+        let mut node_ids = node_ids.to_vec();
+        node_ids.sort_unstable_by_key(|id| id.0);
+        node_ids.dedup();
+        let mut badges = HashMap::<NodeId, NodeBadges>::new();
+        for chunk in node_ids.chunks(EDGE_NODE_LOOKUP_BATCH_SIZE) {
+            let placeholders = numbered_placeholders(1, chunk.len());
+            let query = format!(
+                "SELECT node.id, 1, 0
+                 FROM node
+                 WHERE node.id IN ({placeholders})
+                   AND EXISTS (
+                       SELECT 1 FROM error
+                       WHERE error.file_id = COALESCE(node.file_node_id, node.id)
+                   )
+                 UNION ALL
+                 SELECT node_id, 2, MAX(COALESCE(TRIM(comment), '') != '')
+                 FROM bookmark_node
+                 WHERE node_id IN ({placeholders})
+                 GROUP BY node_id
+                 UNION ALL
+                 SELECT node.id, 3, COUNT(*)
+                 FROM node
+                 JOIN code_marker marker
+                   ON marker.file_id = COALESCE(node.file_node_id, node.id)
+                 WHERE node.id IN ({placeholders})
+                   AND (
+                       node.kind = {file_kind}
+                       OR (
+                           node.kind IN ({function_kind}, {method_kind})
+                           AND marker.line BETWEEN node.start_line
+                               AND COALESCE(node.end_line, node.start_line)
+                       )
+                   )
+                 GROUP BY node.id
+                 UNION ALL
+                 SELECT node.id, 4, 0
+                 FROM node
+                 WHERE node.id IN ({placeholders})
+                   AND node.canonical_id LIKE '{external_prefix}%'",
+                external_prefix = java_types::EXTERNAL_JAVA_TYPE_CANONICAL_PREFIX,
+                file_kind = NodeKind::FILE as i32,
+                function_kind = NodeKind::FUNCTION as i32,
+                method_kind = NodeKind::METHOD as i32,
+            );
+            let mut stmt = self.conn.prepare(&query)?;
+            let mut rows = stmt.query(params_from_iter(chunk.iter().map(|id| id.0)))?;
+            while let Some(row) = rows.next()? {
+                let entry = badges.entry(NodeId(row.get(0)?)).or_default();
+                match row.get::<_, i64>(1)? {
+                    1 => entry.has_errors = true,
+                    2 => {
+                        entry.is_bookmarked = true;
+                        entry.has_annotation = row.get::<_, i64>(2)? != 0;
+                    }
+                    3 => entry.marker_count = row.get(2)?,
+                    _ => entry.is_external = true,
+                }
+            }
+        }
+        Ok(badges)
+    }
+}
@@ -0,0 +1,232 @@
+use super::*;
+use codestory_contracts::graph::StringInterner;
+
+/// Bounds for the per-connection node cache.
+///
+/// Whichever bound is reached first evicts the least recently used nodes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NodeCacheLimits {
+    pub max_entries: usize,
+    /// Approximate memory held by cached nodes, counting each distinct string
+    /// once.
+    pub max_bytes: usize,
+}
+
+impl Default for NodeCacheLimits {
+    fn default() -> Self {
+        Self {
+            max_entries: 100_000,
+            max_bytes: 64 * 1024 * 1024,
+        }
+    }
+}
+
+/// Node cache occupancy and lookup counters since the store was opened.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NodeCacheStats {
+    pub entries: usize,
+    pub bytes: usize,
+    /// Distinct names shared by cached nodes.
+    #[serde(default)]
+    pub interned_strings: usize,
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+}
+
+impl NodeCacheStats {
+    /// Share of lookups answered from the cache, or `None` before any lookup.
+    pub fn hit_rate(&self) -> Option<f64> {
+        let lookups = self.hits.saturating_add(self.misses);
+        (lookups > 0).then(|| self.hits as f64 / lookups as f64)
+    }
+}
+
+impl Storage {
+    /// Rebound the node cache, evicting least recently used nodes that no
+    /// longer fit.
+    pub fn set_node_cache_limits(&self, limits: NodeCacheLimits) {
+        self.cache.nodes.lock().set_limits(limits);
+    }
+
+    /// Node cache occupancy and hit counters for diagnostics.
+    pub fn node_cache_stats(&self) -> NodeCacheStats {
+        self.cache.nodes.lock().stats()
+    }
+}
+
+/// Least recently used map of nodes read from or written through the store.
+///
+/// Names are interned, so nodes sharing a serialized name, qualified name, or
+/// canonical id hold one copy of it between them.
+#[derive(Default)]
+pub(super) struct NodeCache {
+    limits: NodeCacheLimits,
+    entries: HashMap<NodeId, CachedNode>,
+    /// Entries keyed by the tick of their last use, oldest first.
+    recency: BTreeMap<u64, NodeId>,
+    strings: StringInterner,
+    tick: u64,
+    /// Memory held by entries, excluding the interned strings.
+    bytes: usize,
+    hits: u64,
+    misses: u64,
+    evictions: u64,
+}
+
+struct CachedNode {
+    kind: NodeKind,
+    serialized_name: Arc<str>,
+    qualified_name: Option<Arc<str>>,
+    canonical_id: Option<Arc<str>>,
+    file_node_id: Option<NodeId>,
+    start_line: Option<u32>,
+    start_col: Option<u32>,
+    end_line: Option<u32>,
+    end_col: Option<u32>,
+    tick: u64,
+}
+
+impl CachedNode {
+    fn to_node(&self, id: NodeId) -> Node {
+        Node {
+            id,
+            kind: self.kind,
+            serialized_name: self.serialized_name.to_string(),
+            qualified_name: self.qualified_name.as_deref().map(str::to_string),
+            canonical_id: self.canonical_id.as_deref().map(str::to_string),
+            file_node_id: self.file_node_id,
+            start_line: self.start_line,
+            start_col: self.start_col,
+            end_line: self.end_line,
+            end_col: self.end_col,
+        }
+    }
+
+    /// Release names one at a time, so a name shared by two fields leaves the
+    /// pool once both handles are gone.
+    fn release_strings(self, strings: &mut StringInterner) {
+        let names = [
+            Some(self.serialized_name),
+            self.qualified_name,
+            self.canonical_id,
+        ];
+        for name in names.into_iter().flatten() {
+            strings.release(&name);
+        }
+    }
+}
+
+impl NodeCache {
+    /// Look up a node and mark it as recently used.
+    pub(super) fn get(&mut self, id: &NodeId) -> Option<Node> {
+        self.tick += 1;
+        let Some(entry) = self.entries.get_mut(id) else {
+            self.misses += 1;
+            return None;
+        };
+        self.recency.remove(&entry.tick);
+        entry.tick = self.tick;
+        self.recency.insert(self.tick, *id);
+        self.hits += 1;
+        Some(entry.to_node(*id))
+    }
+
+    #[cfg(test)]
+    pub(super) fn contains_key(&self, id: &NodeId) -> bool {
+        self.entries.contains_key(id)
+    }
+
+    #[cfg(test)]
+    pub(super) fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[cfg(test)]
+    pub(super) fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Cache a node, replacing any stale copy with the same id.
+    pub(super) fn insert(&mut self, node: Node) {
+        self.remove(&node.id);
+        self.tick += 1;
+        self.bytes += CACHED_NODE_BYTES;
+        self.recency.insert(self.tick, node.id);
+        let entry = CachedNode {
+            kind: node.kind,
+            serialized_name: self.strings.intern(&node.serialized_name),
+            qualified_name: node
+                .qualified_name
+                .as_deref()
+                .map(|value| self.strings.intern(value)),
+            canonical_id: node
+                .canonical_id
+                .as_deref()
+                .map(|value| self.strings.intern(value)),
+            file_node_id: node.file_node_id,
+            start_line: node.start_line,
+            start_col: node.start_col,
+            end_line: node.end_line,
+            end_col: node.end_col,
+            tick: self.tick,
+        };
+        self.entries.insert(node.id, entry);
+        self.evict_to_limits();
+    }
+
+    pub(super) fn remove(&mut self, id: &NodeId) {
+        if let Some(entry) = self.entries.remove(id) {
+            self.recency.remove(&entry.tick);
+            self.bytes -= CACHED_NODE_BYTES;
+            entry.release_strings(&mut self.strings);
+        }
+    }
+
+    pub(super) fn clear(&mut self) {
+        self.entries.clear();
+        self.recency.clear();
+        self.strings.clear();
+        self.bytes = 0;
+    }
+
+    pub(super) fn set_limits(&mut self, limits: NodeCacheLimits) {
+        self.limits = limits;
+        self.evict_to_limits();
+    }
+
+    pub(super) fn stats(&self) -> NodeCacheStats {
+        NodeCacheStats {
+            entries: self.entries.len(),
+            bytes: self.total_bytes(),
+            interned_strings: self.strings.len(),
+            hits: self.hits,
+            misses: self.misses,
+            evictions: self.evictions,
+        }
+    }
+
+    fn total_bytes(&self) -> usize {
+        self.bytes + self.strings.bytes()
+    }
+
+    fn evict_to_limits(&mut self) {
+        while self.entries.len() > self.limits.max_entries
+            || self.total_bytes() > self.limits.max_bytes
+        {
+            let Some((_, id)) = self.recency.pop_first() else {
+                break;
+            };
+            if let Some(entry) = self.entries.remove(&id) {
+                self.bytes -= CACHED_NODE_BYTES;
+                entry.release_strings(&mut self.strings);
+                self.evictions += 1;
+            }
+        }
+    }
+}
+
+/// Per-entry footprint: the entry itself plus its map and recency slots.
+pub(super) const CACHED_NODE_BYTES: usize = std::mem::size_of::<CachedNode>()
+    + std::mem::size_of::<NodeId>() * 2
+    + std::mem::size_of::<u64>();
@@ -0,0 +1,56 @@
+use super::*;
+
+impl Storage {
+    /// The value stored for the project setting `key`, if any.
+    pub fn project_setting(&self, key: &str) -> Result<Option<String>, StorageError> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT value FROM project_setting WHERE key = ?1")?;
+        let mut rows = stmt.query(params![key])?;
+        match rows.next()? {
+            Some(row) => Ok(Some(row.get(0)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Store `value` under `key`, replacing any previous value.
+    pub fn set_project_setting(&self, key: &str, value: &str) -> Result<(), StorageError> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO project_setting (key, value) VALUES (?1, ?2)",
+            params![key, value],
+        )?;
+        Ok(())
+    }
+
+    /// Remove the setting `key`. Returns whether it existed.
+    pub fn delete_project_setting(&self, key: &str) -> Result<bool, StorageError> {
+        let deleted = self
+            .conn
+            .execute("DELETE FROM project_setting WHERE key = ?1", params![key])?;
+        Ok(deleted > 0)
+    }
+
+    /// Copy project settings from the database at `source_path`, so they
+    /// survive a full re-index.
+    pub fn copy_project_settings_from(
+        &mut self,
+        source_path: &Path,
+    ) -> Result<usize, StorageError> {
+        if !source_path.exists() {
+            return Ok(0);
+        }
+        drop(Storage::open(source_path)?);
+        let source = source_path.to_string_lossy().to_string();
+        self.conn
+            .execute("ATTACH DATABASE ?1 AS source_snapshot", params![source])?;
+        let copy_result = self.conn.execute(
+            "INSERT OR REPLACE INTO project_setting (key, value)
+             SELECT key, value FROM source_snapshot.project_setting",
+            [],
+        );
+        let detach_result = self.conn.execute("DETACH DATABASE source_snapshot", []);
+        let copied = copy_result?;
+        detach_result?;
+        Ok(copied)
+    }
+}
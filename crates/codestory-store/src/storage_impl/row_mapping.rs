@@ -52,6 +52,11 @@ pub(super) fn occurrence_from_row(row: &Row) -> rusqlite::Result<Occurrence> {
             end_line: row.get(5)?,
             end_col: row.get(6)?,
         },
+        edge_id: row.get::<_, Option<i64>>(7)?.map(EdgeId),
+        notebook_cell: match (row.get::<_, Option<u32>>(8)?, row.get::<_, Option<u32>>(9)?) {
+            (Some(cell_index), Some(line)) => Some(NotebookCellPosition { cell_index, line }),
+            _ => None,
+        },
     })
 }
 
@@ -76,3 +81,14 @@ pub(super) fn access_kind_from_db(value: i32) -> AccessKind {
         _ => AccessKind::Public,
     }
 }
+
+pub(super) fn file_stats_from_row(row: &Row) -> rusqlite::Result<FileStats> {
+    Ok(FileStats {
+        file_id: row.get(0)?,
+        path: PathBuf::from(row.get::<_, String>(1)?),
+        language: row.get::<_, Option<String>>(2)?.unwrap_or_default(),
+        line_count: clamp_i64_to_u32(row.get::<_, Option<i64>>(3)?.unwrap_or(0)),
+        symbol_count: clamp_i64_to_u32(row.get(4)?),
+        edge_count: clamp_i64_to_u32(row.get(5)?),
+    })
+}
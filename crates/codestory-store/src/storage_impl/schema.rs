@@ -40,7 +40,10 @@ const TABLE_STATEMENTS: &[&str] = &[
          start_line INTEGER NOT NULL,
          start_col INTEGER NOT NULL,
          end_line INTEGER NOT NULL,
-         end_col INTEGER NOT NULL
+         end_col INTEGER NOT NULL,
+         edge_id INTEGER,
+         notebook_cell_index INTEGER,
+         notebook_cell_line INTEGER
     )",
     "CREATE TABLE IF NOT EXISTS file (
         id INTEGER PRIMARY KEY,
@@ -152,9 +155,18 @@ const TABLE_STATEMENTS: &[&str] = &[
         category_id INTEGER,
         node_id INTEGER,
         comment TEXT,
+        retired_node_id INTEGER,
         FOREIGN KEY(category_id) REFERENCES bookmark_category(id),
         FOREIGN KEY(node_id) REFERENCES node(id)
     )",
+    "CREATE TABLE IF NOT EXISTS node_tombstone (
+        node_id INTEGER PRIMARY KEY,
+        kind INTEGER NOT NULL,
+        serialized_name TEXT NOT NULL,
+        qualified_name TEXT,
+        successor_node_id INTEGER,
+        retired_at_epoch_ms INTEGER NOT NULL
+    )",
     "CREATE TABLE IF NOT EXISTS llm_symbol_doc (
         node_id INTEGER PRIMARY KEY,
         file_node_id INTEGER,
@@ -281,6 +293,112 @@ const TABLE_STATEMENTS: &[&str] = &[
         FOREIGN KEY(file_id) REFERENCES file(id),
         FOREIGN KEY(node_id) REFERENCES node(id)
     )",
+    "CREATE TABLE IF NOT EXISTS storage_meta (
+        id INTEGER PRIMARY KEY CHECK (id = 1),
+        generation INTEGER NOT NULL
+    )",
+    "CREATE TABLE IF NOT EXISTS dependency_resolution (
+        edge_id INTEGER PRIMARY KEY,
+        dependency TEXT NOT NULL
+    )",
+    "CREATE TABLE IF NOT EXISTS node_alias (
+        alias_kind INTEGER NOT NULL,
+        alias_name TEXT NOT NULL,
+        primary_kind INTEGER NOT NULL,
+        primary_name TEXT NOT NULL,
+        PRIMARY KEY(alias_kind, alias_name)
+    )",
+    "CREATE TABLE IF NOT EXISTS trail_filter_preset (
+        name TEXT PRIMARY KEY,
+        edge_kinds TEXT NOT NULL,
+        node_kinds TEXT NOT NULL
+    )",
+    "CREATE TABLE IF NOT EXISTS project_setting (
+        key TEXT PRIMARY KEY,
+        value TEXT NOT NULL
+    )",
+    "CREATE TABLE IF NOT EXISTS shared_node_ref (
+        node_id INTEGER NOT NULL,
+        file_node_id INTEGER NOT NULL,
+        PRIMARY KEY (node_id, file_node_id)
+    ) WITHOUT ROWID",
+    "CREATE TABLE IF NOT EXISTS symbol_fan_in_snapshot (
+        node_id INTEGER PRIMARY KEY,
+        fan_in INTEGER NOT NULL
+    )",
+    "CREATE TABLE IF NOT EXISTS symbol_fan_in_snapshot_meta (
+        id INTEGER PRIMARY KEY CHECK (id = 1),
+        generation INTEGER NOT NULL
+    )",
+    "CREATE TABLE IF NOT EXISTS index_history_run (
+        seq INTEGER PRIMARY KEY,
+        run_id TEXT NOT NULL UNIQUE,
+        recorded_at_epoch_ms INTEGER NOT NULL
+    )",
+    "CREATE TABLE IF NOT EXISTS index_history_change (
+        run_seq INTEGER NOT NULL,
+        entity INTEGER NOT NULL,
+        entity_id INTEGER NOT NULL,
+        added INTEGER NOT NULL,
+        kind INTEGER NOT NULL,
+        name TEXT,
+        source_node_id INTEGER,
+        target_node_id INTEGER
+    )",
+    "CREATE TABLE IF NOT EXISTS node_centrality (
+        node_id INTEGER PRIMARY KEY,
+        score REAL NOT NULL
+    )",
+    "CREATE TABLE IF NOT EXISTS code_marker (
+        file_id INTEGER NOT NULL,
+        line INTEGER NOT NULL,
+        column INTEGER NOT NULL,
+        tag TEXT NOT NULL,
+        text TEXT NOT NULL,
+        PRIMARY KEY(file_id, line, column)
+    )",
+    "CREATE TABLE IF NOT EXISTS code_marker_scan (
+        file_id INTEGER PRIMARY KEY,
+        modification_time INTEGER,
+        content_hash TEXT
+    )",
+    "CREATE TABLE IF NOT EXISTS string_literal (
+        file_id INTEGER NOT NULL,
+        line INTEGER NOT NULL,
+        column INTEGER NOT NULL,
+        name TEXT,
+        value TEXT NOT NULL,
+        PRIMARY KEY(file_id, line, column)
+    )",
+    "CREATE TABLE IF NOT EXISTS string_literal_scan (
+        file_id INTEGER PRIMARY KEY,
+        modification_time INTEGER,
+        content_hash TEXT
+    )",
+    "CREATE TABLE IF NOT EXISTS node_entry_distance (
+        node_id INTEGER PRIMARY KEY,
+        distance INTEGER NOT NULL
+    )",
+    "CREATE TABLE IF NOT EXISTS entry_distance_scan (
+        id INTEGER PRIMARY KEY CHECK (id = 1),
+        truncated INTEGER NOT NULL
+    )",
+    "CREATE TABLE IF NOT EXISTS dependency_node (
+        node_id INTEGER PRIMARY KEY
+    )",
+    "CREATE VIRTUAL TABLE IF NOT EXISTS string_literal_fts USING fts5(
+        name,
+        value,
+        content = 'string_literal',
+        content_rowid = 'rowid'
+    )",
+    "CREATE TRIGGER IF NOT EXISTS string_literal_fts_insert AFTER INSERT ON string_literal BEGIN
+        INSERT INTO string_literal_fts(rowid, name, value) VALUES (new.rowid, new.name, new.value);
+    END",
+    "CREATE TRIGGER IF NOT EXISTS string_literal_fts_delete AFTER DELETE ON string_literal BEGIN
+        INSERT INTO string_literal_fts(string_literal_fts, rowid, name, value)
+        VALUES ('delete', old.rowid, old.name, old.value);
+    END",
     "CREATE TABLE IF NOT EXISTS grounding_snapshot_meta (
         id INTEGER PRIMARY KEY CHECK (id = 1),
         snapshot_version INTEGER NOT NULL,
@@ -294,7 +412,8 @@ const TABLE_STATEMENTS: &[&str] = &[
         node_count INTEGER NOT NULL,
         edge_count INTEGER NOT NULL,
         file_count INTEGER NOT NULL,
-        error_count INTEGER NOT NULL
+        error_count INTEGER NOT NULL,
+        occurrence_count INTEGER
     )",
     "CREATE TABLE IF NOT EXISTS grounding_file_snapshot (
         file_id INTEGER PRIMARY KEY,
@@ -307,6 +426,11 @@ const TABLE_STATEMENTS: &[&str] = &[
         symbol_count INTEGER NOT NULL,
         best_node_rank INTEGER NOT NULL
     )",
+    "CREATE TABLE IF NOT EXISTS file_stats_snapshot (
+        file_id INTEGER PRIMARY KEY,
+        symbol_count INTEGER NOT NULL,
+        edge_count INTEGER NOT NULL
+    )",
     "CREATE TABLE IF NOT EXISTS grounding_node_snapshot (
         node_id INTEGER PRIMARY KEY,
         kind INTEGER NOT NULL,
@@ -384,9 +508,18 @@ const TABLE_STATEMENTS: &[&str] = &[
 ];
 
 const LOAD_TIME_INDEX_STATEMENTS: &[&str] = &[
-    "CREATE UNIQUE INDEX IF NOT EXISTS idx_occurrence_unique
-     ON occurrence(element_id, file_node_id, start_line, start_col, end_line, end_col)",
+    // Call sites at one location stay distinct when they link different edges.
+    "CREATE UNIQUE INDEX IF NOT EXISTS idx_occurrence_callsite_unique
+     ON occurrence(element_id, file_node_id, start_line, start_col, end_line, end_col, IFNULL(edge_id, 0))",
     "CREATE UNIQUE INDEX IF NOT EXISTS idx_component_access_node ON component_access(node_id)",
+    "CREATE INDEX IF NOT EXISTS idx_shared_node_ref_file ON shared_node_ref(file_node_id)",
+    "CREATE INDEX IF NOT EXISTS idx_error_file ON error(file_id)",
+    "CREATE INDEX IF NOT EXISTS idx_index_history_change_entity
+     ON index_history_change(entity_id, entity)",
+    "CREATE INDEX IF NOT EXISTS idx_index_history_change_source
+     ON index_history_change(source_node_id)",
+    "CREATE INDEX IF NOT EXISTS idx_index_history_change_target
+     ON index_history_change(target_node_id)",
 ];
 
 const SEMANTIC_CONTEXT_ENDPOINT_INDEX_STATEMENTS: &[&str] = &[
@@ -400,6 +533,7 @@ const PRE_SUMMARY_SECONDARY_INDEX_STATEMENTS: &[&str] = &[
     "CREATE INDEX IF NOT EXISTS idx_occurrence_element ON occurrence(element_id)",
     "CREATE INDEX IF NOT EXISTS idx_occurrence_element_start_line ON occurrence(element_id, start_line)",
     "CREATE INDEX IF NOT EXISTS idx_occurrence_file ON occurrence(file_node_id)",
+    "CREATE INDEX IF NOT EXISTS idx_occurrence_edge ON occurrence(edge_id) WHERE edge_id IS NOT NULL",
     "CREATE INDEX IF NOT EXISTS idx_edge_file ON edge(file_node_id)",
     "CREATE INDEX IF NOT EXISTS idx_edge_scope_unresolved
      ON edge(kind, resolved_target_node_id, source_node_id, file_node_id)",
@@ -407,6 +541,9 @@ const PRE_SUMMARY_SECONDARY_INDEX_STATEMENTS: &[&str] = &[
     "CREATE INDEX IF NOT EXISTS idx_node_file_kind_line ON node(file_node_id, kind, start_line)",
     "CREATE INDEX IF NOT EXISTS idx_node_file_kind_name ON node(file_node_id, kind, qualified_name, serialized_name)",
     "CREATE INDEX IF NOT EXISTS idx_node_qualified_name ON node(qualified_name)",
+    "CREATE INDEX IF NOT EXISTS idx_node_match_name
+     ON node(COALESCE(qualified_name, serialized_name))",
+    "CREATE INDEX IF NOT EXISTS idx_node_canonical_id ON node(canonical_id)",
     "CREATE INDEX IF NOT EXISTS idx_bookmark_node_category ON bookmark_node(category_id)",
     "CREATE INDEX IF NOT EXISTS idx_bookmark_node_node ON bookmark_node(node_id)",
     "CREATE INDEX IF NOT EXISTS idx_node_kind_serialized_name ON node(kind, serialized_name)",
@@ -699,6 +836,90 @@ pub(super) fn apply_schema_migrations(storage: &Storage) -> Result<(), StorageEr
     if stored_version < 30 {
         storage.set_schema_version(30)?;
     }
+    if stored_version < 31 {
+        migrate_v31_file_stats_snapshot(&storage.conn)?;
+        storage.set_schema_version(31)?;
+    }
+    if stored_version < 32 {
+        migrate_v32_node_tombstones(&storage.conn)?;
+        storage.set_schema_version(32)?;
+    }
+    if stored_version < 33 {
+        migrate_v33_occurrence_edge_id(&storage.conn)?;
+        storage.set_schema_version(33)?;
+    }
+    if stored_version < 34 {
+        migrate_v34_occurrence_notebook_cell(&storage.conn)?;
+        storage.set_schema_version(34)?;
+    }
+    if stored_version < 35 {
+        migrate_v35_storage_meta(&storage.conn)?;
+        storage.set_schema_version(35)?;
+    }
+    if stored_version < 36 {
+        migrate_v36_dependency_resolution(&storage.conn)?;
+        storage.set_schema_version(36)?;
+    }
+    if stored_version < 37 {
+        migrate_v37_node_aliases(&storage.conn)?;
+        storage.set_schema_version(37)?;
+    }
+    if stored_version < 38 {
+        migrate_v38_index_history(&storage.conn)?;
+        storage.set_schema_version(38)?;
+    }
+    if stored_version < 39 {
+        migrate_v39_node_centrality(&storage.conn)?;
+        storage.set_schema_version(39)?;
+    }
+    if stored_version < 40 {
+        migrate_v40_code_markers(&storage.conn)?;
+        storage.set_schema_version(40)?;
+    }
+    if stored_version < 41 {
+        migrate_v41_string_literals(&storage.conn)?;
+        storage.set_schema_version(41)?;
+    }
+    if stored_version < 42 {
+        migrate_v42_trail_filter_presets(&storage.conn)?;
+        storage.set_schema_version(42)?;
+    }
+    if stored_version < 43 {
+        migrate_v43_project_settings(&storage.conn)?;
+        storage.set_schema_version(43)?;
+    }
+    if stored_version < 44 {
+        migrate_v44_occurrence_callsite_unique(&storage.conn)?;
+        storage.set_schema_version(44)?;
+    }
+    if stored_version < 45 {
+        migrate_v45_shared_node_refs(&storage.conn)?;
+        storage.set_schema_version(45)?;
+    }
+    if stored_version < 46 {
+        migrate_v46_symbol_fan_in_snapshot(&storage.conn)?;
+        storage.set_schema_version(46)?;
+    }
+    if stored_version < 47 {
+        migrate_v47_entry_distances(&storage.conn)?;
+        storage.set_schema_version(47)?;
+    }
+    if stored_version < 48 {
+        migrate_v48_dependency_nodes(&storage.conn)?;
+        storage.set_schema_version(48)?;
+    }
+    if stored_version < 49 {
+        migrate_v49_occurrence_count_snapshot(&storage.conn)?;
+        storage.set_schema_version(49)?;
+    }
+    if stored_version < 50 {
+        migrate_v50_error_file_index(&storage.conn)?;
+        storage.set_schema_version(50)?;
+    }
+    if stored_version < 51 {
+        migrate_v51_node_match_name_index(&storage.conn)?;
+        storage.set_schema_version(51)?;
+    }
     create_llm_symbol_doc_reuse_index(&storage.conn)?;
     create_symbol_summary_indexes(&storage.conn)?;
 
@@ -1183,6 +1404,277 @@ pub(super) fn migrate_v29_structural_policy_exclusions(
     Ok(())
 }
 
+/// Graph matches range over the name a node is matched by.
+pub(super) fn migrate_v51_node_match_name_index(conn: &Connection) -> Result<(), StorageError> {
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_node_match_name
+         ON node(COALESCE(qualified_name, serialized_name))",
+        [],
+    )?;
+    Ok(())
+}
+
+/// Node badges look up each node's file errors by `error.file_id`.
+pub(super) fn migrate_v50_error_file_index(conn: &Connection) -> Result<(), StorageError> {
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_error_file ON error(file_id)",
+        [],
+    )?;
+    Ok(())
+}
+
+/// Snapshots built before this version have no occurrence count; stats
+/// count the table until the next snapshot build fills it in.
+pub(super) fn migrate_v49_occurrence_count_snapshot(conn: &Connection) -> Result<(), StorageError> {
+    try_add_column(
+        conn,
+        "grounding_repo_stats_snapshot",
+        "occurrence_count INTEGER",
+    )
+}
+
+pub(super) fn migrate_v48_dependency_nodes(conn: &Connection) -> Result<(), StorageError> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS dependency_node (
+            node_id INTEGER PRIMARY KEY
+        )",
+        [],
+    )?;
+    Ok(())
+}
+
+pub(super) fn migrate_v47_entry_distances(conn: &Connection) -> Result<(), StorageError> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS node_entry_distance (
+            node_id INTEGER PRIMARY KEY,
+            distance INTEGER NOT NULL
+        );
+        CREATE TABLE IF NOT EXISTS entry_distance_scan (
+            id INTEGER PRIMARY KEY CHECK (id = 1),
+            truncated INTEGER NOT NULL
+        );",
+    )?;
+    Ok(())
+}
+
+pub(super) fn migrate_v46_symbol_fan_in_snapshot(conn: &Connection) -> Result<(), StorageError> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS symbol_fan_in_snapshot (
+            node_id INTEGER PRIMARY KEY,
+            fan_in INTEGER NOT NULL
+        );
+        CREATE TABLE IF NOT EXISTS symbol_fan_in_snapshot_meta (
+            id INTEGER PRIMARY KEY CHECK (id = 1),
+            generation INTEGER NOT NULL
+        );",
+    )?;
+    Ok(())
+}
+
+pub(super) fn migrate_v45_shared_node_refs(conn: &Connection) -> Result<(), StorageError> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS shared_node_ref (
+            node_id INTEGER NOT NULL,
+            file_node_id INTEGER NOT NULL,
+            PRIMARY KEY (node_id, file_node_id)
+        ) WITHOUT ROWID",
+        [],
+    )?;
+    Ok(())
+}
+
+pub(super) fn migrate_v44_occurrence_callsite_unique(
+    conn: &Connection,
+) -> Result<(), StorageError> {
+    // Replaced by idx_occurrence_callsite_unique, which load-time index
+    // creation builds next.
+    conn.execute("DROP INDEX IF EXISTS idx_occurrence_unique", [])?;
+    Ok(())
+}
+
+pub(super) fn migrate_v43_project_settings(conn: &Connection) -> Result<(), StorageError> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS project_setting (
+            key TEXT PRIMARY KEY,
+            value TEXT NOT NULL
+        )",
+        [],
+    )?;
+    Ok(())
+}
+
+pub(super) fn migrate_v42_trail_filter_presets(conn: &Connection) -> Result<(), StorageError> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS trail_filter_preset (
+            name TEXT PRIMARY KEY,
+            edge_kinds TEXT NOT NULL,
+            node_kinds TEXT NOT NULL
+        )",
+        [],
+    )?;
+    Ok(())
+}
+
+pub(super) fn migrate_v41_string_literals(conn: &Connection) -> Result<(), StorageError> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS string_literal (
+            file_id INTEGER NOT NULL,
+            line INTEGER NOT NULL,
+            column INTEGER NOT NULL,
+            name TEXT,
+            value TEXT NOT NULL,
+            PRIMARY KEY(file_id, line, column)
+        );
+        CREATE TABLE IF NOT EXISTS string_literal_scan (
+            file_id INTEGER PRIMARY KEY,
+            modification_time INTEGER,
+            content_hash TEXT
+        );
+        CREATE VIRTUAL TABLE IF NOT EXISTS string_literal_fts USING fts5(
+            name,
+            value,
+            content = 'string_literal',
+            content_rowid = 'rowid'
+        );
+        CREATE TRIGGER IF NOT EXISTS string_literal_fts_insert AFTER INSERT ON string_literal BEGIN
+            INSERT INTO string_literal_fts(rowid, name, value) VALUES (new.rowid, new.name, new.value);
+        END;
+        CREATE TRIGGER IF NOT EXISTS string_literal_fts_delete AFTER DELETE ON string_literal BEGIN
+            INSERT INTO string_literal_fts(string_literal_fts, rowid, name, value)
+            VALUES ('delete', old.rowid, old.name, old.value);
+        END;",
+    )?;
+    Ok(())
+}
+
+pub(super) fn migrate_v40_code_markers(conn: &Connection) -> Result<(), StorageError> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS code_marker (
+            file_id INTEGER NOT NULL,
+            line INTEGER NOT NULL,
+            column INTEGER NOT NULL,
+            tag TEXT NOT NULL,
+            text TEXT NOT NULL,
+            PRIMARY KEY(file_id, line, column)
+        );
+        CREATE TABLE IF NOT EXISTS code_marker_scan (
+            file_id INTEGER PRIMARY KEY,
+            modification_time INTEGER,
+            content_hash TEXT
+        );",
+    )?;
+    Ok(())
+}
+
+pub(super) fn migrate_v39_node_centrality(conn: &Connection) -> Result<(), StorageError> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS node_centrality (
+            node_id INTEGER PRIMARY KEY,
+            score REAL NOT NULL
+        )",
+        [],
+    )?;
+    Ok(())
+}
+
+pub(super) fn migrate_v38_index_history(conn: &Connection) -> Result<(), StorageError> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS index_history_run (
+            seq INTEGER PRIMARY KEY,
+            run_id TEXT NOT NULL UNIQUE,
+            recorded_at_epoch_ms INTEGER NOT NULL
+        );
+        CREATE TABLE IF NOT EXISTS index_history_change (
+            run_seq INTEGER NOT NULL,
+            entity INTEGER NOT NULL,
+            entity_id INTEGER NOT NULL,
+            added INTEGER NOT NULL,
+            kind INTEGER NOT NULL,
+            name TEXT,
+            source_node_id INTEGER,
+            target_node_id INTEGER
+        )",
+    )?;
+    Ok(())
+}
+
+pub(super) fn migrate_v37_node_aliases(conn: &Connection) -> Result<(), StorageError> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS node_alias (
+            alias_kind INTEGER NOT NULL,
+            alias_name TEXT NOT NULL,
+            primary_kind INTEGER NOT NULL,
+            primary_name TEXT NOT NULL,
+            PRIMARY KEY(alias_kind, alias_name)
+        )",
+        [],
+    )?;
+    Ok(())
+}
+
+pub(super) fn migrate_v36_dependency_resolution(conn: &Connection) -> Result<(), StorageError> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS dependency_resolution (
+            edge_id INTEGER PRIMARY KEY,
+            dependency TEXT NOT NULL
+        )",
+        [],
+    )?;
+    Ok(())
+}
+
+pub(super) fn migrate_v35_storage_meta(conn: &Connection) -> Result<(), StorageError> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS storage_meta (
+            id INTEGER PRIMARY KEY CHECK (id = 1),
+            generation INTEGER NOT NULL
+        )",
+        [],
+    )?;
+    Ok(())
+}
+
+pub(super) fn migrate_v34_occurrence_notebook_cell(conn: &Connection) -> Result<(), StorageError> {
+    try_add_column(conn, "occurrence", "notebook_cell_index INTEGER")?;
+    try_add_column(conn, "occurrence", "notebook_cell_line INTEGER")?;
+    Ok(())
+}
+
+pub(super) fn migrate_v33_occurrence_edge_id(conn: &Connection) -> Result<(), StorageError> {
+    // The partial index on edge_id is created with the other secondary
+    // occurrence indexes so build-mode opens can keep deferring it.
+    try_add_column(conn, "occurrence", "edge_id INTEGER")?;
+    Ok(())
+}
+
+pub(super) fn migrate_v32_node_tombstones(conn: &Connection) -> Result<(), StorageError> {
+    try_add_column(conn, "bookmark_node", "retired_node_id INTEGER")?;
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS node_tombstone (
+            node_id INTEGER PRIMARY KEY,
+            kind INTEGER NOT NULL,
+            serialized_name TEXT NOT NULL,
+            qualified_name TEXT,
+            successor_node_id INTEGER,
+            retired_at_epoch_ms INTEGER NOT NULL
+        )",
+        [],
+    )?;
+    Ok(())
+}
+
+pub(super) fn migrate_v31_file_stats_snapshot(conn: &Connection) -> Result<(), StorageError> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS file_stats_snapshot (
+            file_id INTEGER PRIMARY KEY,
+            symbol_count INTEGER NOT NULL,
+            edge_count INTEGER NOT NULL
+        )",
+        [],
+    )?;
+    Ok(())
+}
+
 pub(super) fn migrate_v30_semantic_projection_publication_mode(
     conn: &Connection,
 ) -> Result<(), StorageError> {
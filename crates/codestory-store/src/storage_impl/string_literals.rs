@@ -0,0 +1,147 @@
+use super::*;
+
+/// A string literal, or the value of a named constant, in a source file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StringLiteral {
+    pub line: u32,
+    /// One-based column of the opening quote, or of a constant's name.
+    pub column: u32,
+    /// Set for a named constant, whose `value` is its initializer as written.
+    pub name: Option<String>,
+    /// Literal text between the quotes, escapes as written.
+    pub value: String,
+}
+
+/// The literals found in one file, and the file version they were read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StringLiteralScan {
+    pub file_id: i64,
+    pub modification_time: Option<i64>,
+    pub content_hash: Option<String>,
+    pub literals: Vec<StringLiteral>,
+}
+
+/// A stored literal matching a search, with its file and the innermost
+/// symbol whose span holds it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StringLiteralRecord {
+    pub file_id: i64,
+    pub path: PathBuf,
+    pub literal: StringLiteral,
+    pub symbol_id: Option<NodeId>,
+}
+
+impl Storage {
+    /// Indexed files never scanned for literals, or changed since their scan.
+    pub fn string_literal_sources_pending(&self) -> Result<Vec<PendingSourceScan>, StorageError> {
+        self.pending_source_scans("string_literal_scan")
+    }
+
+    /// Replace the literals of each scanned file, and drop literals of files
+    /// no longer in the index. Returns the number of literals written.
+    pub fn replace_string_literals(
+        &mut self,
+        scans: &[StringLiteralScan],
+    ) -> Result<usize, StorageError> {
+        let tx = self.conn.transaction()?;
+        let mut written = 0;
+        {
+            let mut clear = tx.prepare("DELETE FROM string_literal WHERE file_id = ?1")?;
+            let mut insert = tx.prepare(
+                "INSERT OR IGNORE INTO string_literal (file_id, line, column, name, value)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+            )?;
+            let mut record_scan = tx.prepare(
+                "INSERT OR REPLACE INTO string_literal_scan (file_id, modification_time, content_hash)
+                 VALUES (?1, ?2, ?3)",
+            )?;
+            for scan in scans {
+                clear.execute(params![scan.file_id])?;
+                for literal in &scan.literals {
+                    written += insert.execute(params![
+                        scan.file_id,
+                        literal.line,
+                        literal.column,
+                        literal.name,
+                        literal.value
+                    ])?;
+                }
+                record_scan.execute(params![
+                    scan.file_id,
+                    scan.modification_time,
+                    scan.content_hash
+                ])?;
+            }
+        }
+        tx.execute(
+            "DELETE FROM string_literal WHERE file_id NOT IN (SELECT id FROM file)",
+            [],
+        )?;
+        tx.execute(
+            "DELETE FROM string_literal_scan WHERE file_id NOT IN (SELECT id FROM file)",
+            [],
+        )?;
+        tx.commit()?;
+        Ok(written)
+    }
+
+    /// Drop every stored literal, for indexing runs that do not record them.
+    pub fn clear_string_literals(&mut self) -> Result<(), StorageError> {
+        self.conn.execute_batch(
+            "DELETE FROM string_literal;
+             DELETE FROM string_literal_scan;",
+        )?;
+        Ok(())
+    }
+
+    /// Literals and constants holding every word of `query`, best matches
+    /// first.
+    pub fn search_string_literals(
+        &self,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<StringLiteralRecord>, StorageError> {
+        let terms = query
+            .split(|ch: char| !ch.is_alphanumeric() && ch != '_')
+            .filter(|term| !term.is_empty())
+            .map(|term| format!("\"{term}\""))
+            .collect::<Vec<_>>();
+        if terms.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT s.file_id, f.path, s.line, s.column, s.name, s.value,
+                    (SELECT n.id FROM node n
+                     WHERE n.file_node_id = s.file_id
+                       AND n.kind != {file_kind}
+                       AND n.start_line <= s.line
+                       AND COALESCE(n.end_line, n.start_line) >= s.line
+                     ORDER BY COALESCE(n.end_line, n.start_line) - n.start_line,
+                              n.start_line DESC, n.id
+                     LIMIT 1)
+             FROM string_literal_fts
+             JOIN string_literal s ON s.rowid = string_literal_fts.rowid
+             JOIN file f ON f.id = s.file_id
+             WHERE string_literal_fts MATCH ?1
+             ORDER BY bm25(string_literal_fts), f.path, s.line, s.column
+             LIMIT ?2",
+            file_kind = NodeKind::FILE as i32,
+        ))?;
+        let records = stmt
+            .query_map(params![terms.join(" "), limit as i64], |row| {
+                Ok(StringLiteralRecord {
+                    file_id: row.get(0)?,
+                    path: PathBuf::from(row.get::<_, String>(1)?),
+                    literal: StringLiteral {
+                        line: row.get(2)?,
+                        column: row.get(3)?,
+                        name: row.get(4)?,
+                        value: row.get(5)?,
+                    },
+                    symbol_id: row.get::<_, Option<i64>>(6)?.map(NodeId),
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(records)
+    }
+}
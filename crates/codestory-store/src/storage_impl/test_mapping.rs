@@ -0,0 +1,177 @@
+use super::*;
+
+/// Annotation names (JUnit, TestNG, xUnit, MSTest, Rust test attributes)
+/// that mark a function or method as a test.
+const TEST_ANNOTATIONS: &[&str] = &[
+    "test",
+    "rstest",
+    "Test",
+    "ParameterizedTest",
+    "RepeatedTest",
+    "TestFactory",
+    "TestTemplate",
+    "Fact",
+    "Theory",
+    "TestMethod",
+];
+/// Call hops followed between a test and the code it exercises.
+pub const TEST_MAPPING_MAX_DEPTH: u32 = 6;
+/// Nodes one test-mapping walk may visit before it stops expanding.
+const TEST_MAPPING_NODE_BUDGET: usize = 20_000;
+
+/// One end of a test-to-code link, found by following calls.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TestReach {
+    pub node_id: NodeId,
+    /// Call hops between the test and the production code.
+    pub distance: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CallableTestRole {
+    Test,
+    /// Helpers and fixtures declared in test files.
+    TestSupport,
+    Production,
+}
+
+impl Storage {
+    /// Functions and methods detected as tests.
+    ///
+    /// A callable is a test when it carries a test annotation such as
+    /// `@Test` or `#[test]`, is named `test_*`, or is named `test*`/`Test*`
+    /// inside a test file.
+    pub fn test_function_ids(&self) -> Result<HashSet<NodeId>, StorageError> {
+        Ok(self
+            .callable_test_roles()?
+            .into_iter()
+            .filter(|(_, role)| *role == CallableTestRole::Test)
+            .map(|(id, _)| id)
+            .collect())
+    }
+
+    /// Tests whose calls reach `node_id`, nearest first.
+    pub fn tests_covering(&self, node_id: NodeId) -> Result<Vec<TestReach>, StorageError> {
+        let roles = self.callable_test_roles()?;
+        self.test_mapping_walk(node_id, TrailDirection::Incoming, |id| {
+            roles.get(&id) == Some(&CallableTestRole::Test)
+        })
+    }
+
+    /// Production functions and methods `test_id` reaches through calls,
+    /// nearest first. Test helpers on the way are walked through but not
+    /// listed.
+    pub fn code_tested_by(&self, test_id: NodeId) -> Result<Vec<TestReach>, StorageError> {
+        let roles = self.callable_test_roles()?;
+        self.test_mapping_walk(test_id, TrailDirection::Outgoing, |id| {
+            roles.get(&id) == Some(&CallableTestRole::Production)
+        })
+    }
+
+    fn test_mapping_walk(
+        &self,
+        start_id: NodeId,
+        direction: TrailDirection,
+        include: impl Fn(NodeId) -> bool,
+    ) -> Result<Vec<TestReach>, StorageError> {
+        let mut visited = HashSet::from([start_id]);
+        let mut frontier = vec![start_id];
+        let mut reached = Vec::new();
+        let mut distance = 0;
+        while !frontier.is_empty() && distance < TEST_MAPPING_MAX_DEPTH {
+            distance += 1;
+            let mut next_frontier = Vec::new();
+            for chunk in frontier.chunks(EDGE_NODE_LOOKUP_BATCH_SIZE) {
+                for neighbor in
+                    trail::frontier_neighbors(self, chunk, direction, &[EdgeKind::CALL])?
+                {
+                    if visited.len() >= TEST_MAPPING_NODE_BUDGET || !visited.insert(neighbor) {
+                        continue;
+                    }
+                    if include(neighbor) {
+                        reached.push(TestReach {
+                            node_id: neighbor,
+                            distance,
+                        });
+                    }
+                    next_frontier.push(neighbor);
+                }
+            }
+            frontier = next_frontier;
+        }
+        reached.sort_by_key(|reach| (reach.distance, reach.node_id.0));
+        Ok(reached)
+    }
+
+    fn callable_test_roles(&self) -> Result<HashMap<NodeId, CallableTestRole>, StorageError> {
+        let annotated = self.annotated_node_ids(TEST_ANNOTATIONS)?;
+        let mut stmt = self.conn.prepare(
+            "SELECT n.id, n.serialized_name, f.file_role,
+                    COALESCE(f.path, file_node.serialized_name)
+             FROM node n
+             LEFT JOIN file f ON f.id = n.file_node_id
+             LEFT JOIN node file_node ON file_node.id = n.file_node_id
+             WHERE n.kind IN (?1, ?2)",
+        )?;
+        let rows = stmt.query_map(
+            params![NodeKind::FUNCTION as i32, NodeKind::METHOD as i32],
+            |row| {
+                Ok((
+                    NodeId(row.get::<_, i64>(0)?),
+                    row.get::<_, String>(1)?,
+                    row.get::<_, Option<String>>(2)?,
+                    row.get::<_, Option<String>>(3)?,
+                ))
+            },
+        )?;
+        let mut roles = HashMap::new();
+        for row in rows {
+            let (id, name, file_role, path) = row?;
+            let in_test_file = file_role
+                .is_some_and(|role| FileRole::from_db_value(&role) == FileRole::Test)
+                || path.as_deref().is_some_and(is_test_or_bench_path);
+            let role = if annotated.contains(&id) || is_test_function_name(&name, in_test_file) {
+                CallableTestRole::Test
+            } else if in_test_file {
+                CallableTestRole::TestSupport
+            } else {
+                CallableTestRole::Production
+            };
+            roles.insert(id, role);
+        }
+        Ok(roles)
+    }
+
+    /// Nodes carrying an annotation whose last name segment is in `names`.
+    pub(super) fn annotated_node_ids(
+        &self,
+        names: &[&str],
+    ) -> Result<HashSet<NodeId>, StorageError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT COALESCE(e.resolved_source_node_id, e.source_node_id), a.serialized_name
+             FROM edge e
+             JOIN node a ON a.id = COALESCE(e.resolved_target_node_id, e.target_node_id)
+             WHERE e.kind = ?1",
+        )?;
+        let rows = stmt.query_map(params![EdgeKind::ANNOTATION_USAGE as i32], |row| {
+            Ok((NodeId(row.get::<_, i64>(0)?), row.get::<_, String>(1)?))
+        })?;
+        let mut annotated = HashSet::new();
+        for row in rows {
+            let (id, annotation) = row?;
+            if names.contains(&last_name_segment(annotation.trim_start_matches('@'))) {
+                annotated.insert(id);
+            }
+        }
+        Ok(annotated)
+    }
+}
+
+fn is_test_function_name(name: &str, in_test_file: bool) -> bool {
+    let name = last_name_segment(name);
+    name.starts_with("test_") || (in_test_file && name.to_ascii_lowercase().starts_with("test"))
+}
+
+fn last_name_segment(name: &str) -> &str {
+    name.rsplit(['.', ':']).next().unwrap_or(name)
+}
@@ -1,4 +1,5 @@
 use super::*;
+use codestory_contracts::graph::TrailEdgeWeight;
 use rusqlite::OptionalExtension;
 
 #[test]
@@ -20,6 +21,30 @@ fn file_role_classification_ignores_materialized_repo_cache_prefix() {
         FileRole::Generated
     );
 }
+
+#[test]
+fn file_role_classification_reads_generator_markers_from_header_comments() {
+    let path = Path::new("src/api/client.go");
+    assert_eq!(
+        FileRole::classify_source(
+            path,
+            "// Code generated by protoc-gen-go. DO NOT EDIT.\npackage api\n"
+        ),
+        FileRole::Generated
+    );
+    assert_eq!(
+        FileRole::classify_source(Path::new("src/lib.rs"), "// @generated by build.rs\n"),
+        FileRole::Generated
+    );
+    assert_eq!(
+        FileRole::classify_source(path, "package api\n\nconst note = \"DO NOT EDIT\"\n"),
+        FileRole::Source
+    );
+    assert_eq!(
+        FileRole::classify_source(Path::new("tests/fixture.rs"), "// @generated\n"),
+        FileRole::Test
+    );
+}
 use codestory_contracts::graph::{
     AccessKind, CallableProjectionState, Edge, EdgeId, EdgeKind, ErrorInfo, FileCoverageReason,
     IndexStep, Node, NodeId, NodeKind, Occurrence, OccurrenceKind, ResolutionCertainty,
@@ -1384,6 +1409,56 @@ fn insert_file_row(storage: &Storage, id: i64, path: &str) -> Result<(), Storage
     })
 }
 
+#[test]
+fn get_file_by_path_falls_back_to_the_normalized_lookup_key() -> Result<(), StorageError> {
+    let storage = Storage::new_in_memory()?;
+    insert_file_row(&storage, 1, r"C:\Repo\src\main.ts")?;
+    insert_file_row(&storage, 2, "/repo/src/app.ts")?;
+
+    let found = |path: &str| -> Result<Option<i64>, StorageError> {
+        Ok(storage
+            .get_file_by_path(Path::new(path))?
+            .map(|file| file.id))
+    };
+    assert_eq!(found(r"C:\Repo\src\main.ts")?, Some(1));
+    assert_eq!(found("c:/Repo/src/main.ts")?, Some(1));
+    assert_eq!(found(r"\\?\C:\Repo\src\main.ts")?, Some(1));
+    assert_eq!(found("/repo/src/app.ts")?, Some(2));
+    assert_eq!(found("/repo/src/missing.ts")?, None);
+
+    insert_file_row(&storage, 3, "C:/Repo/src/main.ts")?;
+    assert_eq!(
+        found("c:/Repo/src/main.ts")?,
+        None,
+        "an ambiguous normalized match must not pick a row"
+    );
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn get_file_by_path_resolves_symlinked_roots() -> Result<(), StorageError> {
+    let temp = tempfile::tempdir().map_err(|error| StorageError::Other(error.to_string()))?;
+    let real_root = temp.path().join("real");
+    std::fs::create_dir_all(real_root.join("src"))
+        .and_then(|()| std::fs::write(real_root.join("src/main.ts"), "export {}\n"))
+        .and_then(|()| std::os::unix::fs::symlink(&real_root, temp.path().join("linked")))
+        .map_err(|error| StorageError::Other(error.to_string()))?;
+    let stored = std::fs::canonicalize(real_root.join("src/main.ts"))
+        .map_err(|error| StorageError::Other(error.to_string()))?;
+
+    let storage = Storage::new_in_memory()?;
+    insert_file_row(&storage, 1, &stored.to_string_lossy())?;
+    let found = storage.get_file_by_path(&temp.path().join("linked/src/main.ts"))?;
+    assert_eq!(found.map(|file| file.id), Some(1));
+    assert!(
+        storage
+            .get_file_by_path(&temp.path().join("linked/src/missing.ts"))?
+            .is_none()
+    );
+    Ok(())
+}
+
 #[test]
 fn openapi_endpoint_projection_requires_file_owned_graph_evidence() -> Result<(), StorageError> {
     let mut storage = Storage::new_in_memory()?;
@@ -1474,6 +1549,8 @@ fn openapi_endpoint_projection_requires_file_owned_graph_evidence() -> Result<()
                     end_line: 1,
                     end_col: 2,
                 },
+                edge_id: None,
+                notebook_cell: None,
             })
             .collect::<Vec<_>>(),
     )?;
@@ -1684,6 +1761,102 @@ fn endpoint_synthetic_node_source_metadata_is_stable_for_duplicate_routes()
     Ok(())
 }
 
+#[test]
+fn protobuf_definition_node_prefers_proto_source_over_generated_code() -> Result<(), StorageError> {
+    let mut storage = Storage::new_in_memory()?;
+    insert_file_row(&storage, 20, "gen/greeter_grpc.pb.go")?;
+    insert_file_row(&storage, 21, "proto/greeter.proto")?;
+
+    let generated_file = file_node(20, "gen/greeter_grpc.pb.go");
+    let proto_file = file_node(21, "proto/greeter.proto");
+    let linked = Node {
+        id: NodeId(950),
+        kind: NodeKind::INTERFACE,
+        serialized_name: "Greeter".to_string(),
+        qualified_name: Some("Greeter".to_string()),
+        canonical_id: Some("proto:service:Greeter".to_string()),
+        file_node_id: Some(NodeId(20)),
+        ..Default::default()
+    };
+    let defined = Node {
+        file_node_id: Some(NodeId(21)),
+        start_line: Some(6),
+        start_col: Some(9),
+        end_line: Some(6),
+        end_col: Some(15),
+        ..linked.clone()
+    };
+
+    storage.insert_nodes_batch(&[proto_file.clone(), defined])?;
+    storage.insert_nodes_batch(&[generated_file.clone(), linked])?;
+    let stored = storage.get_node(NodeId(950))?.expect("proto service node");
+    assert_eq!(stored.file_node_id, Some(NodeId(21)));
+    assert_eq!(stored.start_line, Some(6));
+
+    Ok(())
+}
+
+#[test]
+fn protobuf_placeholders_neither_replace_nor_delete_definitions() -> Result<(), StorageError> {
+    let mut storage = Storage::new_in_memory()?;
+    insert_file_row(&storage, 20, "gen/greeter_pb2.py")?;
+    insert_file_row(&storage, 21, "proto/greeter.proto")?;
+    let placeholder = Node {
+        id: NodeId(951),
+        kind: NodeKind::CLASS,
+        serialized_name: "HelloRequest".to_string(),
+        qualified_name: Some("HelloRequest".to_string()),
+        canonical_id: Some("proto:message:HelloRequest".to_string()),
+        file_node_id: Some(NodeId(20)),
+        ..Default::default()
+    };
+    let definition = Node {
+        file_node_id: Some(NodeId(21)),
+        start_line: Some(4),
+        start_col: Some(9),
+        ..placeholder.clone()
+    };
+    let flush = |storage: &mut Storage, nodes: &[Node]| {
+        storage.flush_projection_batch(ProjectionBatch {
+            files: &[],
+            file_content_hashes: &[],
+            nodes,
+            structural_text_units: &[],
+            structural_text_projections: &[],
+            structural_text_cache_writes: &[],
+            edges: &[],
+            occurrences: &[],
+            component_access: &[],
+            callable_projection_states: &[],
+            file_errors: &[],
+        })
+    };
+    let generated = [file_node(20, "gen/greeter_pb2.py"), placeholder];
+    let proto = [file_node(21, "proto/greeter.proto"), definition];
+    let stored = |storage: &Storage| -> Result<_, StorageError> {
+        Ok(storage
+            .get_node(NodeId(951))?
+            .map(|node| (node.file_node_id, node.start_line)))
+    };
+
+    flush(&mut storage, &proto)?;
+    flush(&mut storage, &generated)?;
+    assert_eq!(stored(&storage)?, Some((Some(NodeId(21)), Some(4))));
+
+    // Re-indexing the generated file leaves the definition alone.
+    storage.delete_file_projection(20)?;
+    assert_eq!(stored(&storage)?, Some((Some(NodeId(21)), Some(4))));
+    flush(&mut storage, &generated)?;
+
+    // Re-indexing the proto file keeps the node for the generated code until
+    // the definition comes back.
+    storage.delete_file_projection(21)?;
+    assert_eq!(stored(&storage)?, Some((Some(NodeId(20)), None)));
+    flush(&mut storage, &proto)?;
+    assert_eq!(stored(&storage)?, Some((Some(NodeId(21)), Some(4))));
+    Ok(())
+}
+
 #[test]
 fn projection_flush_prefers_framework_definition_over_usage() -> Result<(), StorageError> {
     let mut storage = Storage::new_in_memory()?;
@@ -1742,6 +1915,54 @@ fn projection_flush_prefers_framework_definition_over_usage() -> Result<(), Stor
     Ok(())
 }
 
+#[test]
+fn shared_framework_nodes_outlive_the_file_that_owns_them() -> Result<(), StorageError> {
+    let mut storage = Storage::new_in_memory()?;
+    insert_file_row(&storage, 1, "src/orders.ts")?;
+    insert_file_row(&storage, 2, "src/report.ts")?;
+    let table = |file_id: i64, line: u32| Node {
+        id: NodeId(50),
+        kind: NodeKind::CLASS,
+        serialized_name: "orders".to_string(),
+        canonical_id: Some("sql:table:orders".to_string()),
+        file_node_id: Some(NodeId(file_id)),
+        start_line: Some(line),
+        start_col: Some(10),
+        ..Default::default()
+    };
+    for (file_id, path, line) in [(1, "src/orders.ts", 3), (2, "src/report.ts", 8)] {
+        storage.flush_projection_batch(ProjectionBatch {
+            files: &[],
+            file_content_hashes: &[],
+            nodes: &[file_node(file_id, path), table(file_id, line)],
+            structural_text_units: &[],
+            structural_text_projections: &[],
+            structural_text_cache_writes: &[],
+            edges: &[],
+            occurrences: &[],
+            component_access: &[],
+            callable_projection_states: &[],
+            file_errors: &[],
+        })?;
+    }
+    assert_eq!(
+        storage
+            .get_node(NodeId(50))?
+            .and_then(|node| node.file_node_id),
+        Some(NodeId(1))
+    );
+
+    let removal = storage.delete_file_projection(1)?;
+    assert_eq!(removal.removed_node_count, 1, "only the file node goes");
+    let table = storage.get_node(NodeId(50))?.expect("still referenced");
+    assert_eq!(table.file_node_id, Some(NodeId(2)));
+    assert_eq!(table.start_line, None);
+
+    storage.delete_file_projection(2)?;
+    assert!(storage.get_node(NodeId(50))?.is_none());
+    Ok(())
+}
+
 #[test]
 fn test_resolution_indexes_are_created() -> Result<(), StorageError> {
     let storage = Storage::new_in_memory()?;
@@ -2750,6 +2971,8 @@ fn flush_projection_persistence_fixture(
             end_line: 3,
             end_col: 2,
         },
+        edge_id: None,
+        notebook_cell: None,
     }];
     let component_access = [(NodeId(2), AccessKind::Public)];
     let callable_projection_states = [CallableProjectionState {
@@ -2913,6 +3136,66 @@ fn projection_batch_family_and_commit_failures_roll_back_everything() -> Result<
     Ok(())
 }
 
+#[test]
+fn test_open_with_options_rejects_keys_without_sqlcipher() -> Result<(), StorageError> {
+    let temp = tempfile::tempdir().expect("create temp dir");
+    let path = temp.path().join("codestory.db");
+    let key = StorageEncryptionKey::new("correct horse battery staple")?;
+    assert_eq!(format!("{key:?}"), "StorageEncryptionKey(<redacted>)");
+    assert!(StorageEncryptionKey::new("").is_err());
+
+    let storage = Storage::open_with_options(&path, &StorageOpenOptions::default())?;
+    assert_eq!(storage.get_stats()?.node_count, 0);
+    drop(storage);
+
+    let keyed = StorageOpenOptions::default().with_encryption_key(key.clone());
+    if encryption_supported() {
+        return Ok(());
+    }
+    assert!(matches!(
+        Storage::open_with_options(&path, &keyed),
+        Err(StorageError::EncryptionUnavailable)
+    ));
+    assert!(matches!(
+        install_process_encryption_key(key),
+        Err(StorageError::EncryptionUnavailable)
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_maintenance_report_tracks_planner_statistics_after_integrity_checked_open()
+-> Result<(), StorageError> {
+    let temp = tempfile::tempdir().expect("create temp dir");
+    let path = temp.path().join("codestory.db");
+    let options = StorageOpenOptions::default().with_integrity_check(true);
+    drop(Storage::open_with_options(&path, &options)?);
+    let storage = Storage::open_with_options(&path, &options)?;
+    assert!(storage.integrity_check()?.is_empty());
+
+    let report = storage.maintenance_report()?;
+    assert!(report.page_count > 0);
+    assert_eq!(report.database_bytes, report.page_count * report.page_size);
+    assert!((0.0..=1.0).contains(&report.fragmentation));
+    assert!(!report.vacuum_recommended);
+    assert!(!report.analyzed);
+    assert!(report.indexes.iter().any(|index| index.table == "edge"));
+    assert!(
+        report
+            .indexes
+            .iter()
+            .all(|index| index.analyzed_rows.is_none())
+    );
+
+    assert!(!storage.analyze_if_stale()?, "small stores skip ANALYZE");
+    storage.analyze()?;
+    let report = storage.maintenance_report()?;
+    assert!(report.analyzed);
+    storage.vacuum()?;
+    assert!(storage.integrity_check()?.is_empty());
+    Ok(())
+}
+
 #[test]
 fn test_present_kind_queries() -> Result<(), StorageError> {
     let mut storage = Storage::new_in_memory()?;
@@ -2953,6 +3236,86 @@ fn test_present_kind_queries() -> Result<(), StorageError> {
     assert!(node_kinds.contains(&NodeKind::METHOD));
     assert!(edge_kinds.contains(&EdgeKind::MEMBER));
     assert!(edge_kinds.contains(&EdgeKind::CALL));
+
+    let metadata = storage.get_graph_metadata()?;
+    assert_eq!(
+        metadata
+            .node_kind_counts
+            .iter()
+            .map(|(kind, _)| *kind)
+            .collect::<Vec<_>>(),
+        node_kinds
+    );
+    assert!(metadata.node_kind_counts.contains(&(NodeKind::CLASS, 1)));
+    assert!(metadata.edge_kind_counts.contains(&(EdgeKind::CALL, 1)));
+    assert_eq!(metadata.edge_kind_counts.len(), 2);
+    Ok(())
+}
+
+#[test]
+fn test_file_stats_rank_files_by_symbol_density() -> Result<(), StorageError> {
+    let mut storage = Storage::new_in_memory()?;
+    for (id, path, line_count) in [(1, "src/dense.rs", 4), (2, "src/sparse.rs", 100)] {
+        storage.insert_file(&FileInfo {
+            id,
+            path: PathBuf::from(path),
+            language: "rust".to_string(),
+            modification_time: 1,
+            indexed: true,
+            complete: true,
+            line_count,
+            file_role: FileRole::Source,
+        })?;
+    }
+    let symbol = |id: i64, file: i64| Node {
+        id: NodeId(id),
+        kind: NodeKind::FUNCTION,
+        serialized_name: format!("f{id}"),
+        file_node_id: Some(NodeId(file)),
+        ..Default::default()
+    };
+    storage.insert_nodes_batch(&[
+        Node {
+            id: NodeId(1),
+            kind: NodeKind::FILE,
+            serialized_name: "src/dense.rs".to_string(),
+            ..Default::default()
+        },
+        Node {
+            id: NodeId(2),
+            kind: NodeKind::FILE,
+            serialized_name: "src/sparse.rs".to_string(),
+            ..Default::default()
+        },
+        symbol(10, 1),
+        symbol(11, 1),
+        symbol(20, 2),
+    ])?;
+    storage.insert_edges_batch(&[Edge {
+        id: EdgeId(1),
+        source: NodeId(10),
+        target: NodeId(20),
+        kind: EdgeKind::CALL,
+        file_node_id: Some(NodeId(1)),
+        ..Default::default()
+    }])?;
+
+    let live = storage.get_files_by_symbol_density(10)?;
+    assert_eq!(
+        live.iter().map(|stats| stats.file_id).collect::<Vec<_>>(),
+        vec![1, 2]
+    );
+    assert_eq!((live[0].symbol_count, live[0].edge_count), (2, 1));
+    assert!((live[0].symbol_density() - 0.5).abs() < f64::EPSILON);
+
+    storage.refresh_grounding_summary_snapshots()?;
+    assert_eq!(storage.get_files_by_symbol_density(10)?, live);
+    assert_eq!(storage.get_file_stats(2)?, Some(live[1].clone()));
+    assert_eq!(storage.get_files_by_symbol_density(1)?.len(), 1);
+    assert!(matches!(
+        storage.get_files_by_symbol_density(0),
+        Err(StorageError::InvalidBatchLimit(_))
+    ));
     Ok(())
 }
 
@@ -3793,7 +4156,7 @@ fn staged_build_node_pages_filter_and_order_across_keyset_boundaries() -> Result
             ..Default::default()
         },
     ])?;
-    storage.cache.nodes.write().clear();
+    storage.cache.nodes.lock().clear();
 
     let accepted_kinds = [
         NodeKind::METHOD,
@@ -3832,7 +4195,7 @@ fn staged_build_node_pages_filter_and_order_across_keyset_boundaries() -> Result
             .is_empty()
     );
     assert!(
-        storage.cache.nodes.read().is_empty(),
+        storage.cache.nodes.lock().is_empty(),
         "build node scans must not populate StorageCache"
     );
 
@@ -3935,15 +4298,15 @@ fn staged_build_node_lookup_batches_duplicates_without_touching_cache() -> Resul
         "UPDATE node SET serialized_name = 'database-1' WHERE id = 1",
         [],
     )?;
-    let cache_len_before = storage.cache.nodes.read().len();
+    let cache_len_before = storage.cache.nodes.lock().len();
     assert_eq!(
         storage
             .cache
             .nodes
-            .read()
+            .lock()
             .get(&NodeId(1))
-            .map(|node| node.serialized_name.as_str()),
-        Some("cached-1")
+            .map(|node| node.serialized_name),
+        Some("cached-1".to_string())
     );
 
     let mut requested_ids = (1_i64..=205).map(NodeId).collect::<Vec<_>>();
@@ -3960,15 +4323,15 @@ fn staged_build_node_lookup_batches_duplicates_without_touching_cache() -> Resul
         "uncached lookup read a stale cached node"
     );
     assert!(!lookup.nodes.contains_key(&NodeId(999)));
-    assert_eq!(storage.cache.nodes.read().len(), cache_len_before);
+    assert_eq!(storage.cache.nodes.lock().len(), cache_len_before);
     assert_eq!(
         storage
             .cache
             .nodes
-            .read()
+            .lock()
             .get(&NodeId(1))
-            .map(|node| node.serialized_name.as_str()),
-        Some("cached-1"),
+            .map(|node| node.serialized_name),
+        Some("cached-1".to_string()),
         "uncached lookup mutated StorageCache"
     );
     let empty = storage.get_nodes_by_ids_no_cache_for_build(&[])?;
@@ -4022,7 +4385,7 @@ fn staged_build_edge_batches_match_incident_edge_order_and_remain_bounded()
         ..Default::default()
     }));
     storage.insert_edges_batch(&edges)?;
-    storage.cache.nodes.write().clear();
+    storage.cache.nodes.lock().clear();
 
     let expected = storage
         .get_edges_for_node_ids(&[NodeId(1)])?
@@ -4047,7 +4410,7 @@ fn staged_build_edge_batches_match_incident_edge_order_and_remain_bounded()
         "streamed lookup must retain ignored CALL-resolution behavior"
     );
     assert!(
-        storage.cache.nodes.read().is_empty(),
+        storage.cache.nodes.lock().is_empty(),
         "edge streaming must not populate StorageCache"
     );
     assert!(matches!(
@@ -4203,6 +4566,8 @@ fn test_clear_removes_fk_dependents_and_cache() -> Result<(), StorageError> {
             end_line: 1,
             end_col: 4,
         },
+        edge_id: None,
+        notebook_cell: None,
     }])?;
     storage.insert_component_access_batch(&[(function_node.id, AccessKind::Public)])?;
     storage.upsert_callable_projection_states(&[CallableProjectionState {
@@ -4530,6 +4895,8 @@ fn test_delete_projection_for_callers_removes_callable_scoped_data() -> Result<(
                 end_line: 3,
                 end_col: 1,
             },
+            edge_id: None,
+            notebook_cell: None,
         },
         Occurrence {
             element_id: caller_b.id.0,
@@ -4541,6 +4908,8 @@ fn test_delete_projection_for_callers_removes_callable_scoped_data() -> Result<(
                 end_line: 12,
                 end_col: 1,
             },
+            edge_id: None,
+            notebook_cell: None,
         },
         Occurrence {
             element_id: NodeId(903).0,
@@ -4552,6 +4921,8 @@ fn test_delete_projection_for_callers_removes_callable_scoped_data() -> Result<(
                 end_line: 2,
                 end_col: 10,
             },
+            edge_id: None,
+            notebook_cell: None,
         },
         Occurrence {
             element_id: NodeId(903).0,
@@ -4563,6 +4934,8 @@ fn test_delete_projection_for_callers_removes_callable_scoped_data() -> Result<(
                 end_line: 11,
                 end_col: 10,
             },
+            edge_id: None,
+            notebook_cell: None,
         },
     ])?;
     storage.upsert_callable_projection_states(&[
@@ -7412,6 +7785,8 @@ fn test_occurrence_insert() -> Result<(), StorageError> {
             end_line: 1,
             end_col: 10,
         },
+        edge_id: None,
+        notebook_cell: None,
     }];
     storage.insert_occurrences_batch(&occurrences)?;
     let mut stmt = storage.conn.prepare("SELECT count(*) FROM occurrence")?;
@@ -7470,6 +7845,8 @@ fn batched_nodes_and_occurrences_match_single_node_lookup() -> Result<(), Storag
             end_line: 10,
             end_col: 4,
         },
+        edge_id: None,
+        notebook_cell: None,
     }])?;
 
     let batched_nodes = storage.get_nodes_by_ids(&[NodeId(1), NodeId(2)])?;
@@ -7496,6 +7873,157 @@ fn batched_nodes_and_occurrences_match_single_node_lookup() -> Result<(), Storag
     Ok(())
 }
 
+#[test]
+fn callsite_occurrences_resolve_to_their_edge() -> Result<(), StorageError> {
+    let mut storage = Storage::new_in_memory()?;
+    let file_id = NodeId(1);
+    let callee = NodeId(4);
+    storage.insert_nodes_batch(&[
+        Node {
+            id: file_id,
+            kind: NodeKind::FILE,
+            serialized_name: "src/main.rs".to_string(),
+            ..Default::default()
+        },
+        Node {
+            id: NodeId(2),
+            kind: NodeKind::FUNCTION,
+            serialized_name: "first".to_string(),
+            file_node_id: Some(file_id),
+            ..Default::default()
+        },
+        Node {
+            id: NodeId(3),
+            kind: NodeKind::FUNCTION,
+            serialized_name: "second".to_string(),
+            file_node_id: Some(file_id),
+            ..Default::default()
+        },
+        Node {
+            id: callee,
+            kind: NodeKind::FUNCTION,
+            serialized_name: "helper".to_string(),
+            file_node_id: Some(file_id),
+            ..Default::default()
+        },
+    ])?;
+    storage.insert_edges_batch(&[
+        Edge {
+            id: EdgeId(10),
+            source: NodeId(2),
+            target: callee,
+            kind: EdgeKind::CALL,
+            file_node_id: Some(file_id),
+            line: Some(3),
+            ..Default::default()
+        },
+        Edge {
+            id: EdgeId(11),
+            source: NodeId(3),
+            target: callee,
+            kind: EdgeKind::CALL,
+            file_node_id: Some(file_id),
+            line: Some(8),
+            ..Default::default()
+        },
+    ])?;
+    let callsite = |edge_id: i64, line: u32| Occurrence {
+        element_id: callee.0,
+        kind: OccurrenceKind::REFERENCE,
+        location: SourceLocation {
+            file_node_id: file_id,
+            start_line: line,
+            start_col: 5,
+            end_line: line,
+            end_col: 11,
+        },
+        edge_id: Some(EdgeId(edge_id)),
+        notebook_cell: None,
+    };
+    // Edge 11 also calls from line 3, where edge 10 does; sharing a location
+    // must not drop either call site, while exact repeats still collapse.
+    storage.insert_occurrences_batch(&[
+        callsite(11, 8),
+        callsite(10, 3),
+        callsite(11, 9),
+        callsite(11, 3),
+        callsite(10, 3),
+    ])?;
+
+    let first = storage.get_callsites_for_edge(EdgeId(10))?;
+    assert_eq!(first.len(), 1);
+    assert_eq!(first[0].location.start_line, 3);
+    assert_eq!(first[0].element_id, callee.0);
+
+    let second = storage.get_callsites_for_edge(EdgeId(11))?;
+    assert_eq!(
+        second
+            .iter()
+            .map(|occurrence| occurrence.location.start_line)
+            .collect::<Vec<_>>(),
+        vec![3, 8, 9]
+    );
+    assert!(
+        second
+            .iter()
+            .all(|occurrence| occurrence.edge_id == Some(EdgeId(11)))
+    );
+
+    let by_callee = storage.get_occurrences_for_node(callee)?;
+    assert_eq!(by_callee.len(), 4);
+    assert!(storage.get_callsites_for_edge(EdgeId(12))?.is_empty());
+    Ok(())
+}
+
+#[test]
+fn notebook_cell_positions_round_trip_through_occurrences() -> Result<(), StorageError> {
+    let mut storage = Storage::new_in_memory()?;
+    let file_id = NodeId(1);
+    storage.insert_occurrences_batch(&[
+        Occurrence {
+            element_id: 2,
+            kind: OccurrenceKind::DEFINITION,
+            location: SourceLocation {
+                file_node_id: file_id,
+                start_line: 7,
+                start_col: 1,
+                end_line: 8,
+                end_col: 12,
+            },
+            edge_id: None,
+            notebook_cell: Some(NotebookCellPosition {
+                cell_index: 3,
+                line: 2,
+            }),
+        },
+        Occurrence {
+            element_id: 3,
+            kind: OccurrenceKind::DEFINITION,
+            location: SourceLocation {
+                file_node_id: file_id,
+                start_line: 1,
+                start_col: 1,
+                end_line: 1,
+                end_col: 4,
+            },
+            edge_id: None,
+            notebook_cell: None,
+        },
+    ])?;
+
+    let occurrences = storage.get_occurrences_for_file(file_id)?;
+    assert_eq!(occurrences.len(), 2);
+    assert_eq!(occurrences[0].notebook_cell, None);
+    assert_eq!(
+        occurrences[1].notebook_cell,
+        Some(NotebookCellPosition {
+            cell_index: 3,
+            line: 2,
+        })
+    );
+    Ok(())
+}
+
 #[test]
 fn batched_edges_for_node_ids_matches_single_node_lookup() -> Result<(), StorageError> {
     let mut storage = Storage::new_in_memory()?;
@@ -7657,21 +8185,171 @@ fn test_error_storage_round_trips_coverage_reason() -> Result<(), StorageError>
 }
 
 #[test]
-fn test_node_cache() -> Result<(), StorageError> {
-    let storage = Storage::new_in_memory()?;
-    let node = Node {
+fn test_detailed_stats_report_occurrences_size_and_tables() -> Result<(), StorageError> {
+    let mut storage = Storage::new_in_memory()?;
+    storage.insert_nodes_batch(&[Node {
         id: NodeId(1),
         kind: NodeKind::FUNCTION,
-        serialized_name: "test_node".to_string(),
+        serialized_name: "main".to_string(),
         ..Default::default()
-    };
-    storage.insert_node(&node)?;
-    {
-        let cache = storage.cache.nodes.read();
-        assert!(cache.contains_key(&NodeId(1)));
-    }
-    let fetched = storage.get_node(NodeId(1))?.unwrap();
-    assert_eq!(fetched.serialized_name, "test_node");
+    }])?;
+    storage.insert_occurrences_batch(&[Occurrence {
+        element_id: 1,
+        kind: OccurrenceKind::DEFINITION,
+        location: SourceLocation {
+            file_node_id: NodeId(1),
+            start_line: 1,
+            start_col: 1,
+            end_line: 1,
+            end_col: 5,
+        },
+        edge_id: None,
+        notebook_cell: None,
+    }])?;
+
+    let stats = storage.get_stats()?;
+    assert_eq!(stats.occurrence_count, 1);
+    assert!(stats.database_bytes > 0);
+    assert_eq!(stats.last_indexed_at_epoch_ms, None);
+    assert!(stats.tables.is_empty());
+
+    let detailed = storage.get_detailed_stats()?;
+    let node_table = detailed
+        .tables
+        .iter()
+        .find(|table| table.name == "node")
+        .expect("node table");
+    assert_eq!(node_table.row_count, 1);
+    if let Some(bytes) = node_table.bytes {
+        assert!(bytes > 0);
+    }
+    assert!(
+        detailed
+            .tables
+            .iter()
+            .any(|table| table.name == "occurrence")
+    );
+
+    // A ready snapshot serves the occurrence count it was built with.
+    storage.refresh_grounding_snapshots()?;
+    storage.conn.execute("DELETE FROM occurrence", [])?;
+    assert_eq!(storage.get_stats()?.occurrence_count, 1);
+    storage.invalidate_grounding_snapshots()?;
+    assert_eq!(storage.get_stats()?.occurrence_count, 0);
+    Ok(())
+}
+
+#[test]
+fn test_node_cache() -> Result<(), StorageError> {
+    let storage = Storage::new_in_memory()?;
+    let node = Node {
+        id: NodeId(1),
+        kind: NodeKind::FUNCTION,
+        serialized_name: "test_node".to_string(),
+        ..Default::default()
+    };
+    storage.insert_node(&node)?;
+    {
+        let cache = storage.cache.nodes.lock();
+        assert!(cache.contains_key(&NodeId(1)));
+    }
+    let fetched = storage.get_node(NodeId(1))?.unwrap();
+    assert_eq!(fetched.serialized_name, "test_node");
+    Ok(())
+}
+
+#[test]
+fn test_symbol_fan_in_counts_distinct_referencing_symbols() -> Result<(), StorageError> {
+    let mut storage = Storage::new_in_memory()?;
+    let nodes = ["Widget", "main", "helper", "render", "Widget::draw"]
+        .into_iter()
+        .zip(1..)
+        .map(|(name, id)| Node {
+            id: NodeId(id),
+            kind: NodeKind::FUNCTION,
+            serialized_name: name.to_string(),
+            ..Default::default()
+        })
+        .collect::<Vec<_>>();
+    storage.insert_nodes_batch(&nodes)?;
+    let edge = |id, source, target, kind| Edge {
+        id: EdgeId(id),
+        source: NodeId(source),
+        target: NodeId(target),
+        kind,
+        ..Default::default()
+    };
+    storage.insert_edges_batch(&[
+        edge(1, 2, 1, EdgeKind::TYPE_USAGE),
+        edge(2, 2, 1, EdgeKind::CALL),
+        edge(3, 3, 1, EdgeKind::CALL),
+        edge(4, 1, 5, EdgeKind::MEMBER),
+        edge(5, 4, 4, EdgeKind::CALL),
+        Edge {
+            resolved_target: Some(NodeId(1)),
+            ..edge(6, 4, 3, EdgeKind::CALL)
+        },
+    ])?;
+
+    let fan_in = storage.get_symbol_fan_in_counts()?;
+    assert_eq!(fan_in.get(&NodeId(1)), Some(&3));
+    assert_eq!(fan_in.get(&NodeId(3)), None);
+    assert_eq!(fan_in.get(&NodeId(4)), None);
+    assert_eq!(fan_in.get(&NodeId(5)), None);
+
+    // Counts are reused until the generation moves.
+    storage.conn.execute(
+        "INSERT INTO edge (id, source_node_id, target_node_id, kind) VALUES (7, 5, 1, ?1)",
+        params![EdgeKind::CALL as i32],
+    )?;
+    assert_eq!(storage.get_symbol_fan_in_counts()?, fan_in);
+    storage.insert_edges_batch(&[edge(8, 5, 3, EdgeKind::CALL)])?;
+    let fan_in = storage.get_symbol_fan_in_counts()?;
+    assert_eq!(fan_in.get(&NodeId(1)), Some(&4));
+    assert_eq!(fan_in.get(&NodeId(3)), Some(&1));
+    Ok(())
+}
+
+#[test]
+fn test_node_cache_evicts_least_recently_used_nodes() -> Result<(), StorageError> {
+    let mut storage = Storage::new_in_memory()?;
+    storage.set_node_cache_limits(NodeCacheLimits {
+        max_entries: 2,
+        ..NodeCacheLimits::default()
+    });
+    let nodes = (1..=3)
+        .map(|id| Node {
+            id: NodeId(id),
+            kind: NodeKind::FUNCTION,
+            serialized_name: format!("node_{id}"),
+            ..Default::default()
+        })
+        .collect::<Vec<_>>();
+    storage.insert_nodes_batch(&nodes[..2])?;
+    assert!(storage.get_node(NodeId(1))?.is_some());
+    storage.insert_nodes_batch(&nodes[2..])?;
+    {
+        let cache = storage.cache.nodes.lock();
+        assert_eq!(cache.len(), 2);
+        assert!(cache.contains_key(&NodeId(1)));
+        assert!(!cache.contains_key(&NodeId(2)));
+    }
+
+    assert_eq!(
+        storage
+            .get_node(NodeId(2))?
+            .map(|node| node.serialized_name),
+        Some("node_2".to_string())
+    );
+    let stats = storage.node_cache_stats();
+    assert_eq!(stats.entries, 2);
+    assert_eq!((stats.hits, stats.misses), (1, 1));
+    assert_eq!(stats.evictions, 2);
+    assert_eq!(stats.hit_rate(), Some(0.5));
+
+    storage.clear()?;
+    assert!(storage.cache.nodes.lock().is_empty());
+    assert_eq!(storage.node_cache_stats().bytes, 0);
     Ok(())
 }
 
@@ -7731,6 +8409,8 @@ fn test_delete_file_projection() -> Result<(), StorageError> {
             end_line: 1,
             end_col: 3,
         },
+        edge_id: None,
+        notebook_cell: None,
     }])?;
 
     storage.insert_error(&codestory_contracts::graph::ErrorInfo {
@@ -7812,9 +8492,13 @@ fn test_delete_file_projection() -> Result<(), StorageError> {
             .is_empty()
     );
     assert!(storage.get_errors(None)?.is_empty());
-    assert!(storage.get_bookmarks(Some(category_id))?.is_empty());
+    // Bookmarks survive as retired references until a successor appears.
+    assert_eq!(summary.retired_bookmark_node_count, 1);
+    let bookmarks = storage.get_bookmarks(Some(category_id))?;
+    assert_eq!(bookmarks.len(), 1);
+    assert_eq!(bookmarks[0].node_id, func_node.id);
 
-    let cache = storage.cache.nodes.read();
+    let cache = storage.cache.nodes.lock();
     assert!(!cache.contains_key(&NodeId(file_node_id)));
     assert!(!cache.contains_key(&NodeId(2_001)));
 
@@ -8032,6 +8716,325 @@ fn test_update_bookmark_tri_state_comment_patch() -> Result<(), StorageError> {
     Ok(())
 }
 
+#[test]
+fn test_bookmarks_follow_tombstone_successors_across_file_moves() -> Result<(), StorageError> {
+    fn file_with_symbol(file_id: i64, symbol_id: i64) -> (FileInfo, Vec<Node>) {
+        let path = format!("src/file_{file_id}.rs");
+        let file = FileInfo {
+            id: file_id,
+            path: PathBuf::from(&path),
+            language: "rust".to_string(),
+            modification_time: 1,
+            indexed: true,
+            complete: true,
+            line_count: 10,
+            file_role: FileRole::Source,
+        };
+        let nodes = vec![
+            Node {
+                id: NodeId(file_id),
+                kind: NodeKind::FILE,
+                serialized_name: path,
+                ..Default::default()
+            },
+            Node {
+                id: NodeId(symbol_id),
+                kind: NodeKind::FUNCTION,
+                serialized_name: "moved".to_string(),
+                qualified_name: Some("crate::moved".to_string()),
+                file_node_id: Some(NodeId(file_id)),
+                ..Default::default()
+            },
+        ];
+        (file, nodes)
+    }
+    fn flush(storage: &mut Storage, file: &FileInfo, nodes: &[Node]) -> Result<(), StorageError> {
+        storage
+            .flush_projection_batch(ProjectionBatch {
+                files: std::slice::from_ref(file),
+                file_content_hashes: &[],
+                nodes,
+                structural_text_units: &[],
+                structural_text_projections: &[],
+                structural_text_cache_writes: &[],
+                edges: &[],
+                occurrences: &[],
+                component_access: &[],
+                callable_projection_states: &[],
+                file_errors: &[],
+            })
+            .map(|_| ())
+    }
+
+    let mut storage = Storage::new_in_memory()?;
+    let category_id = storage.create_bookmark_category("Moves")?;
+    let (first_file, first_nodes) = file_with_symbol(1, 10);
+    flush(&mut storage, &first_file, &first_nodes)?;
+    storage.add_bookmark(category_id, NodeId(10), Some("keep me"))?;
+
+    // The successor lands before its old file is removed.
+    let (second_file, second_nodes) = file_with_symbol(2, 20);
+    flush(&mut storage, &second_file, &second_nodes)?;
+    let removal = storage.delete_file_projection(1)?;
+    assert_eq!(removal.retired_bookmark_node_count, 1);
+    let bookmarks = storage.get_bookmarks(Some(category_id))?;
+    assert_eq!(bookmarks.len(), 1);
+    assert_eq!(bookmarks[0].node_id, NodeId(20));
+    assert_eq!(bookmarks[0].comment.as_deref(), Some("keep me"));
+
+    // The successor moves again, this time before its replacement is written.
+    storage.delete_file_projection(2)?;
+    assert_eq!(storage.get_bookmarks(None)?[0].node_id, NodeId(20));
+    let (third_file, third_nodes) = file_with_symbol(3, 30);
+    flush(&mut storage, &third_file, &third_nodes)?;
+    assert_eq!(storage.get_bookmarks(None)?[0].node_id, NodeId(30));
+    Ok(())
+}
+
+#[test]
+fn test_retired_bookmarks_expire_and_unused_tombstones_are_pruned() -> Result<(), StorageError> {
+    let mut storage = Storage::new_in_memory()?;
+    let file = FileInfo {
+        id: 1,
+        path: PathBuf::from("src/lib.rs"),
+        language: "rust".to_string(),
+        modification_time: 1,
+        indexed: true,
+        complete: true,
+        line_count: 10,
+        file_role: FileRole::Source,
+    };
+    storage.insert_file(&file)?;
+    storage.insert_nodes_batch(&[Node {
+        id: NodeId(1),
+        kind: NodeKind::FILE,
+        serialized_name: "src/lib.rs".to_string(),
+        ..Default::default()
+    }])?;
+    let category_id = storage.create_bookmark_category("Old")?;
+    // One bookmark retired long ago, one recently; a third tombstone is
+    // referenced by no bookmark at all.
+    for (retired, retired_at) in [(10, 0), (11, current_epoch_ms()), (12, 0)] {
+        storage.conn.execute(
+            "INSERT INTO node_tombstone (
+                node_id, kind, serialized_name, qualified_name,
+                successor_node_id, retired_at_epoch_ms
+             ) VALUES (?1, ?2, 'gone', NULL, NULL, ?3)",
+            params![retired, NodeKind::FUNCTION as i32, retired_at],
+        )?;
+    }
+    for retired in [10, 11] {
+        storage.conn.execute(
+            "INSERT INTO bookmark_node (category_id, node_id, comment, retired_node_id)
+             VALUES (?1, NULL, NULL, ?2)",
+            params![category_id, retired],
+        )?;
+    }
+
+    let removal = storage.delete_file_projection(1)?;
+    assert_eq!(removal.removed_bookmark_node_count, 1);
+    let bookmarks = storage.get_bookmarks(Some(category_id))?;
+    assert_eq!(bookmarks.len(), 1);
+    assert_eq!(bookmarks[0].node_id, NodeId(11));
+    let tombstones = storage
+        .conn
+        .prepare("SELECT node_id FROM node_tombstone ORDER BY node_id")?
+        .query_map([], |row| row.get::<_, i64>(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(tombstones, vec![11]);
+    Ok(())
+}
+
+#[test]
+fn test_bookmark_health_classifies_and_repairs_by_symbol_name() -> Result<(), StorageError> {
+    let mut storage = Storage::new_in_memory()?;
+    let function = |id, name: &str| Node {
+        id: NodeId(id),
+        kind: NodeKind::FUNCTION,
+        serialized_name: name.to_string(),
+        qualified_name: Some(format!("crate::{name}")),
+        ..Default::default()
+    };
+    storage.insert_nodes_batch(&[function(1, "kept"), function(3, "moved")])?;
+    let category_id = storage.create_bookmark_category("Health")?;
+    let kept = storage.add_bookmark(category_id, NodeId(1), None)?;
+    for (retired, name) in [(2, "crate::moved"), (4, "crate::gone")] {
+        storage.conn.execute(
+            "INSERT INTO node_tombstone (
+                node_id, kind, serialized_name, qualified_name,
+                successor_node_id, retired_at_epoch_ms
+             ) VALUES (?1, ?2, ?3, ?3, NULL, 0)",
+            params![retired, NodeKind::FUNCTION as i32, name],
+        )?;
+        storage.conn.execute(
+            "INSERT INTO bookmark_node (category_id, node_id, comment, retired_node_id)
+             VALUES (?1, NULL, NULL, ?2)",
+            params![category_id, retired],
+        )?;
+    }
+
+    let health = storage.bookmark_health()?;
+    let statuses = health
+        .iter()
+        .map(|entry| (entry.stored_node_id.0, entry.status, entry.resolved_node_id))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        statuses,
+        vec![
+            (1, BookmarkHealthStatus::Valid, Some(NodeId(1))),
+            (2, BookmarkHealthStatus::Resolvable, Some(NodeId(3))),
+            (4, BookmarkHealthStatus::Orphaned, None),
+        ]
+    );
+    assert_eq!(health[0].bookmark_id, kept);
+    assert_eq!(health[2].symbol_name.as_deref(), Some("crate::gone"));
+
+    assert_eq!(storage.repair_bookmarks()?, 1);
+    let repaired = storage.bookmark_health()?;
+    assert_eq!(repaired[1].status, BookmarkHealthStatus::Valid);
+    assert_eq!(repaired[1].stored_node_id, NodeId(3));
+    assert_eq!(repaired[2].status, BookmarkHealthStatus::Orphaned);
+    assert_eq!(storage.repair_bookmarks()?, 0);
+    Ok(())
+}
+
+#[test]
+fn test_listing_pages_are_read_in_sql_with_matching_counts() -> Result<(), StorageError> {
+    let mut storage = Storage::new_in_memory()?;
+    let node = |id: i64, kind: NodeKind, name: &str, file: Option<i64>| Node {
+        id: NodeId(id),
+        kind,
+        serialized_name: name.to_string(),
+        file_node_id: file.map(NodeId),
+        start_line: file.map(|_| id as u32),
+        ..Default::default()
+    };
+    storage.insert_nodes_batch(&[
+        node(1, NodeKind::FILE, "src/b.rs", None),
+        node(2, NodeKind::FILE, "src/a.rs", None),
+        node(10, NodeKind::CLASS, "Widget", Some(1)),
+        node(11, NodeKind::METHOD, "draw", Some(1)),
+        node(12, NodeKind::METHOD, "close", Some(1)),
+        node(13, NodeKind::METHOD, "draw", Some(1)),
+    ])?;
+    let member = |id: i64, target: i64| Edge {
+        id: EdgeId(id),
+        source: NodeId(10),
+        target: NodeId(target),
+        kind: EdgeKind::MEMBER,
+        ..Default::default()
+    };
+    storage.insert_edges_batch(&[member(20, 11), member(21, 12), member(22, 13)])?;
+
+    // The second `draw` repeats the first and is left out of pages and counts.
+    let names = |nodes: Vec<Node>| {
+        nodes
+            .into_iter()
+            .map(|node| node.serialized_name)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(storage.get_children_symbol_count(NodeId(10))?, 2);
+    assert_eq!(
+        names(storage.get_children_symbols_page(NodeId(10), 0, 10)?),
+        vec!["close", "draw"]
+    );
+    assert_eq!(
+        storage.get_children_symbols_page(NodeId(10), 1, 10)?[0].id,
+        NodeId(11)
+    );
+
+    let occurrence = |file: i64, line: u32, kind: OccurrenceKind| Occurrence {
+        element_id: 11,
+        kind,
+        location: SourceLocation {
+            file_node_id: NodeId(file),
+            start_line: line,
+            start_col: 0,
+            end_line: line,
+            end_col: 4,
+        },
+        edge_id: None,
+        notebook_cell: None,
+    };
+    storage.insert_occurrences_batch(&[
+        occurrence(1, 3, OccurrenceKind::DEFINITION),
+        occurrence(2, 9, OccurrenceKind::REFERENCE),
+        occurrence(2, 4, OccurrenceKind::REFERENCE),
+        occurrence(1, 1, OccurrenceKind::REFERENCE),
+    ])?;
+    let lines = |occurrences: Vec<Occurrence>| {
+        occurrences
+            .into_iter()
+            .map(|occurrence| {
+                (
+                    occurrence.location.file_node_id.0,
+                    occurrence.location.start_line,
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(storage.get_occurrence_count_for_node(NodeId(11), None)?, 4);
+    assert_eq!(
+        storage.get_occurrence_count_for_node(NodeId(11), Some(OccurrenceKind::REFERENCE))?,
+        3
+    );
+    assert_eq!(
+        lines(storage.get_occurrences_for_node_page(NodeId(11), None, 1, 2)?),
+        vec![(2, 9), (1, 1)]
+    );
+    assert_eq!(
+        lines(storage.get_occurrences_for_node_page(
+            NodeId(11),
+            Some(OccurrenceKind::DEFINITION),
+            0,
+            10
+        )?),
+        vec![(1, 3)]
+    );
+
+    let category = storage.create_bookmark_category("Paged")?;
+    for id in [10, 11, 12] {
+        storage.add_bookmark(category, NodeId(id), None)?;
+    }
+    assert_eq!(storage.get_bookmark_count(Some(category))?, 3);
+    assert_eq!(
+        storage
+            .get_bookmarks_page(Some(category), 1, 1)?
+            .iter()
+            .map(|bookmark| bookmark.node_id)
+            .collect::<Vec<_>>(),
+        vec![NodeId(11)]
+    );
+
+    for (message, is_fatal) in [("first", true), ("second", false), ("third", true)] {
+        storage.insert_error(&codestory_contracts::graph::ErrorInfo {
+            message: message.to_string(),
+            file_id: None,
+            line: None,
+            column: None,
+            is_fatal,
+            index_step: codestory_contracts::graph::IndexStep::Indexing,
+            coverage_reason: None,
+        })?;
+    }
+    let fatal = codestory_contracts::graph::ErrorFilter {
+        fatal_only: true,
+        indexed_only: false,
+    };
+    assert_eq!(storage.get_error_count(None)?, 3);
+    assert_eq!(storage.get_error_count(Some(&fatal))?, 2);
+    assert_eq!(
+        storage
+            .get_errors_page(Some(&fatal), 1, 5)?
+            .into_iter()
+            .map(|error| error.message)
+            .collect::<Vec<_>>(),
+        vec!["third"]
+    );
+
+    Ok(())
+}
+
 #[test]
 fn test_get_errors() -> Result<(), StorageError> {
     let storage = Storage::new_in_memory()?;
@@ -8085,6 +9088,43 @@ fn test_get_errors() -> Result<(), StorageError> {
     Ok(())
 }
 
+#[test]
+fn test_replace_run_errors_keeps_file_errors() -> Result<(), StorageError> {
+    let mut storage = Storage::new_in_memory()?;
+    storage.insert_nodes_batch(&[Node {
+        id: NodeId(5),
+        kind: NodeKind::FILE,
+        serialized_name: "src/a.ts".to_string(),
+        ..Default::default()
+    }])?;
+    insert_file_row(&storage, 5, "src/a.ts")?;
+    let error = |message: &str, file_id: Option<NodeId>| codestory_contracts::graph::ErrorInfo {
+        message: message.to_string(),
+        file_id,
+        line: None,
+        column: None,
+        is_fatal: file_id.is_none(),
+        index_step: codestory_contracts::graph::IndexStep::Indexing,
+        coverage_reason: None,
+    };
+    storage.insert_error(&error("file failed", Some(NodeId(5))))?;
+
+    storage.replace_run_errors(&[error("rules failed", None)])?;
+    storage.replace_run_errors(&[error("rules still failing", None)])?;
+
+    let mut messages = storage
+        .get_errors(None)?
+        .into_iter()
+        .map(|error| error.message)
+        .collect::<Vec<_>>();
+    messages.sort();
+    assert_eq!(messages, ["file failed", "rules still failing"]);
+
+    storage.replace_run_errors(&[])?;
+    assert_eq!(storage.get_errors(None)?.len(), 1);
+    Ok(())
+}
+
 #[test]
 fn test_trail_query() -> Result<(), StorageError> {
     let mut storage = Storage::new_in_memory()?;
@@ -8141,6 +9181,11 @@ fn test_trail_query() -> Result<(), StorageError> {
         edge_filter: vec![],
         show_utility_calls: true,
         node_filter: Vec::new(),
+        hide_trivial_accessors: false,
+        hide_generated: false,
+        entry_reachable_only: false,
+        max_paths: None,
+        edge_weight: Default::default(),
         max_nodes: 100,
     };
     let result = storage.get_trail(&config)?;
@@ -8158,6 +9203,11 @@ fn test_trail_query() -> Result<(), StorageError> {
         edge_filter: vec![],
         show_utility_calls: true,
         node_filter: Vec::new(),
+        hide_trivial_accessors: false,
+        hide_generated: false,
+        entry_reachable_only: false,
+        max_paths: None,
+        edge_weight: Default::default(),
         max_nodes: 100,
     };
     let result = storage.get_trail(&config)?;
@@ -8174,6 +9224,11 @@ fn test_trail_query() -> Result<(), StorageError> {
         edge_filter: vec![],
         show_utility_calls: true,
         node_filter: Vec::new(),
+        hide_trivial_accessors: false,
+        hide_generated: false,
+        entry_reachable_only: false,
+        max_paths: None,
+        edge_weight: Default::default(),
         max_nodes: 100,
     };
     let result = storage.get_trail(&config)?;
@@ -8183,11 +9238,66 @@ fn test_trail_query() -> Result<(), StorageError> {
 }
 
 #[test]
-fn test_trail_to_target_symbol_simple_path() -> Result<(), StorageError> {
+fn test_symbol_tree_and_trail_results_have_stable_order() -> Result<(), StorageError> {
     let mut storage = Storage::new_in_memory()?;
 
-    let nodes = vec![
-        Node {
+    let node = |id: i64, kind: NodeKind, name: &str, line: Option<u32>| Node {
+        id: NodeId(id),
+        kind,
+        serialized_name: name.to_string(),
+        start_line: line,
+        start_col: line.map(|_| 1),
+        ..Default::default()
+    };
+    // Inserted out of every order the queries promise.
+    storage.insert_nodes_batch(&[
+        node(9, NodeKind::METHOD, "zeta", None),
+        node(8, NodeKind::METHOD, "beta", Some(30)),
+        node(7, NodeKind::METHOD, "alpha", Some(10)),
+        node(6, NodeKind::METHOD, "alpha", Some(10)),
+        node(5, NodeKind::CLASS, "Widget", Some(1)),
+        node(4, NodeKind::CLASS, "Gadget", Some(1)),
+        node(3, NodeKind::CLASS, "Gadget", Some(40)),
+    ])?;
+    let member = |id: i64, target: i64| Edge {
+        id: EdgeId(id),
+        source: NodeId(5),
+        target: NodeId(target),
+        kind: EdgeKind::MEMBER,
+        ..Default::default()
+    };
+    storage.insert_edges_batch(&[member(24, 9), member(23, 8), member(22, 7), member(21, 6)])?;
+
+    let ids = |nodes: Vec<Node>| nodes.into_iter().map(|node| node.id.0).collect::<Vec<_>>();
+    assert_eq!(ids(storage.get_root_symbols()?), vec![3, 4, 5]);
+    assert_eq!(
+        ids(storage.get_children_symbols(NodeId(5))?),
+        vec![6, 7, 8, 9]
+    );
+    assert_eq!(ids(storage.get_nodes()?), vec![3, 4, 5, 6, 7, 8, 9]);
+
+    let trail = storage.get_trail(&TrailConfig {
+        root_id: NodeId(5),
+        depth: 1,
+        direction: TrailDirection::Outgoing,
+        caller_scope: TrailCallerScope::IncludeTestsAndBenches,
+        ..TrailConfig::default()
+    })?;
+    assert_eq!(ids(trail.nodes), vec![5, 6, 7, 8, 9]);
+    assert_eq!(
+        trail.edges.iter().map(|edge| edge.id.0).collect::<Vec<_>>(),
+        vec![21, 22, 23, 24]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_trail_to_target_symbol_simple_path() -> Result<(), StorageError> {
+    let mut storage = Storage::new_in_memory()?;
+
+    let nodes = vec![
+        Node {
             id: NodeId(1),
             kind: NodeKind::FUNCTION,
             serialized_name: "A".to_string(),
@@ -8235,6 +9345,11 @@ fn test_trail_to_target_symbol_simple_path() -> Result<(), StorageError> {
         edge_filter: vec![],
         show_utility_calls: true,
         node_filter: Vec::new(),
+        hide_trivial_accessors: false,
+        hide_generated: false,
+        entry_reachable_only: false,
+        max_paths: None,
+        edge_weight: Default::default(),
         max_nodes: 100,
     })?;
 
@@ -8245,6 +9360,485 @@ fn test_trail_to_target_symbol_simple_path() -> Result<(), StorageError> {
     Ok(())
 }
 
+#[test]
+fn test_trail_all_paths_lists_simple_paths_shortest_first() -> Result<(), StorageError> {
+    let mut storage = Storage::new_in_memory()?;
+    let nodes = ["A", "B", "C", "D", "E"]
+        .into_iter()
+        .zip(1..)
+        .map(|(name, id)| Node {
+            id: NodeId(id),
+            kind: NodeKind::FUNCTION,
+            serialized_name: name.to_string(),
+            ..Default::default()
+        })
+        .collect::<Vec<_>>();
+    storage.insert_nodes_batch(&nodes)?;
+    let call = |id, source, target| Edge {
+        id: EdgeId(id),
+        source: NodeId(source),
+        target: NodeId(target),
+        kind: EdgeKind::CALL,
+        ..Default::default()
+    };
+    // A -> D directly, through B, through C, and through B then C. C -> A
+    // closes a cycle and D -> E leads past the target.
+    storage.insert_edges_batch(&[
+        call(1, 1, 2),
+        call(2, 1, 3),
+        call(3, 1, 4),
+        call(4, 2, 4),
+        call(5, 3, 4),
+        call(6, 2, 3),
+        call(7, 3, 1),
+        call(8, 4, 5),
+    ])?;
+    let config = TrailConfig {
+        root_id: NodeId(1),
+        mode: TrailMode::AllPaths,
+        target_id: Some(NodeId(4)),
+        depth: 0,
+        caller_scope: TrailCallerScope::IncludeTestsAndBenches,
+        show_utility_calls: true,
+        ..TrailConfig::default()
+    };
+
+    let result = storage.get_trail(&config)?;
+    let paths = result
+        .paths
+        .iter()
+        .map(|path| path.node_ids.iter().map(|id| id.0).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        paths,
+        vec![vec![1, 4], vec![1, 2, 4], vec![1, 3, 4], vec![1, 2, 3, 4]]
+    );
+    assert_eq!(
+        result.paths[3].edge_ids,
+        vec![EdgeId(1), EdgeId(6), EdgeId(5)]
+    );
+    assert!(!result.truncated);
+    assert_eq!(result.nodes.len(), 4);
+    assert_eq!(result.edges.len(), 6);
+
+    let limited = storage.get_trail(&TrailConfig {
+        max_paths: Some(2),
+        ..config.clone()
+    })?;
+    assert_eq!(limited.paths.len(), 2);
+    assert!(limited.truncated);
+
+    let short = storage.get_trail(&TrailConfig { depth: 2, ..config })?;
+    assert_eq!(short.paths.len(), 3);
+    assert!(!short.truncated);
+    Ok(())
+}
+
+#[test]
+fn test_trail_weighted_path_prefers_confident_edges() -> Result<(), StorageError> {
+    let mut storage = Storage::new_in_memory()?;
+    let nodes = ["A", "B", "C", "D"]
+        .into_iter()
+        .zip(1..)
+        .map(|(name, id)| Node {
+            id: NodeId(id),
+            kind: NodeKind::FUNCTION,
+            serialized_name: name.to_string(),
+            ..Default::default()
+        })
+        .collect::<Vec<_>>();
+    storage.insert_nodes_batch(&nodes)?;
+    let call = |id, source, target, confidence| Edge {
+        id: EdgeId(id),
+        source: NodeId(source),
+        target: NodeId(target),
+        kind: EdgeKind::CALL,
+        confidence,
+        ..Default::default()
+    };
+    // A guessed A -> D hop competes with A -> B -> D, where B -> D is well
+    // resolved, and with the longer certain chain A -> B -> C -> D.
+    storage.insert_edges_batch(&[
+        call(1, 1, 4, Some(0.2)),
+        call(2, 1, 2, None),
+        call(3, 2, 4, Some(0.9)),
+        call(4, 2, 3, None),
+        call(5, 3, 4, None),
+    ])?;
+    let config = TrailConfig {
+        root_id: NodeId(1),
+        mode: TrailMode::WeightedPath,
+        target_id: Some(NodeId(4)),
+        depth: 0,
+        caller_scope: TrailCallerScope::IncludeTestsAndBenches,
+        show_utility_calls: true,
+        ..TrailConfig::default()
+    };
+    let node_ids = |result: &TrailResult| {
+        result.paths[0]
+            .node_ids
+            .iter()
+            .map(|id| id.0)
+            .collect::<Vec<_>>()
+    };
+
+    let weighted = storage.get_trail(&config)?;
+    assert_eq!(weighted.paths.len(), 1);
+    assert_eq!(node_ids(&weighted), vec![1, 2, 4]);
+    assert_eq!(weighted.paths[0].edge_ids, vec![EdgeId(2), EdgeId(3)]);
+    assert_eq!(weighted.nodes.len(), 3);
+    assert_eq!(weighted.edges.len(), 2);
+    assert_eq!(weighted.depth_map.get(&NodeId(4)), Some(&2));
+
+    let product = storage.get_trail(&TrailConfig {
+        edge_weight: TrailEdgeWeight::NegativeLogConfidence,
+        ..config.clone()
+    })?;
+    assert_eq!(node_ids(&product), vec![1, 2, 3, 4]);
+
+    let one_hop = storage.get_trail(&TrailConfig { depth: 1, ..config })?;
+    assert_eq!(node_ids(&one_hop), vec![1, 4]);
+    Ok(())
+}
+
+#[test]
+fn test_trail_data_flow_follows_writes_then_reads() -> Result<(), StorageError> {
+    let mut storage = Storage::new_in_memory()?;
+    let node = |id, name: &str, kind| Node {
+        id: NodeId(id),
+        kind,
+        serialized_name: name.to_string(),
+        ..Default::default()
+    };
+    storage.insert_nodes_batch(&[
+        node(1, "load", NodeKind::FUNCTION),
+        node(2, "Cache::entries", NodeKind::FIELD),
+        node(3, "summarize", NodeKind::FUNCTION),
+        node(4, "Report::total", NodeKind::FIELD),
+        node(5, "render", NodeKind::FUNCTION),
+        node(6, "log", NodeKind::FUNCTION),
+        node(7, "reset", NodeKind::FUNCTION),
+    ])?;
+    let edge = |id, source, target, kind| Edge {
+        id: EdgeId(id),
+        source: NodeId(source),
+        target: NodeId(target),
+        kind,
+        ..Default::default()
+    };
+    // load writes entries, summarize reads entries and writes total, render
+    // reads total. The call from load and the second writer of entries are
+    // not data flow out of load.
+    storage.insert_edges_batch(&[
+        edge(1, 1, 2, EdgeKind::ASSIGNMENT),
+        edge(2, 3, 2, EdgeKind::USAGE),
+        edge(3, 3, 4, EdgeKind::ASSIGNMENT),
+        edge(4, 5, 4, EdgeKind::USAGE),
+        edge(5, 1, 6, EdgeKind::CALL),
+        edge(6, 7, 2, EdgeKind::ASSIGNMENT),
+    ])?;
+
+    let result = storage.get_trail(&TrailConfig {
+        root_id: NodeId(1),
+        mode: TrailMode::DataFlow,
+        depth: 0,
+        caller_scope: TrailCallerScope::IncludeTestsAndBenches,
+        show_utility_calls: true,
+        ..TrailConfig::default()
+    })?;
+    let mut node_ids = result
+        .nodes
+        .iter()
+        .map(|node| node.id.0)
+        .collect::<Vec<_>>();
+    node_ids.sort_unstable();
+    assert_eq!(node_ids, vec![1, 2, 3, 4, 5]);
+    let mut edge_ids = result
+        .edges
+        .iter()
+        .map(|edge| edge.id.0)
+        .collect::<Vec<_>>();
+    edge_ids.sort_unstable();
+    assert_eq!(edge_ids, vec![1, 2, 3, 4]);
+    assert_eq!(result.depth_map.get(&NodeId(5)), Some(&4));
+    Ok(())
+}
+
+#[test]
+fn test_tests_and_covered_code_are_linked_through_calls() -> Result<(), StorageError> {
+    let mut storage = Storage::new_in_memory()?;
+    let node = |id, name: &str, kind, file| Node {
+        id: NodeId(id),
+        kind,
+        serialized_name: name.to_string(),
+        file_node_id: Some(NodeId(file)),
+        ..Default::default()
+    };
+    let files = [
+        (10, "src/parser.rs"),
+        (11, "tests/common.rs"),
+        (12, "src/test/java/ParserTest.java"),
+    ];
+    for (id, path) in files {
+        storage.insert_nodes_batch(&[node(id, path, NodeKind::FILE, id)])?;
+        insert_file_row(&storage, id, path)?;
+    }
+    storage.insert_nodes_batch(&[
+        node(1, "test_parses_header", NodeKind::FUNCTION, 10),
+        node(2, "build_fixture", NodeKind::FUNCTION, 11),
+        node(3, "parse", NodeKind::FUNCTION, 10),
+        node(4, "tokenize", NodeKind::FUNCTION, 10),
+        node(5, "ParserTest.rejectsEmptyInput", NodeKind::METHOD, 12),
+        node(6, "org.junit.Test", NodeKind::ANNOTATION, 12),
+        node(7, "untested", NodeKind::FUNCTION, 10),
+        node(8, "parser::tests::header_roundtrip", NodeKind::FUNCTION, 10),
+        node(9, "tokio::test", NodeKind::ANNOTATION, 10),
+    ])?;
+    let edge = |id, source, target, kind| Edge {
+        id: EdgeId(id),
+        source: NodeId(source),
+        target: NodeId(target),
+        kind,
+        ..Default::default()
+    };
+    storage.insert_edges_batch(&[
+        edge(1, 1, 2, EdgeKind::CALL),
+        edge(2, 2, 3, EdgeKind::CALL),
+        edge(3, 3, 4, EdgeKind::CALL),
+        edge(4, 5, 4, EdgeKind::CALL),
+        edge(5, 5, 6, EdgeKind::ANNOTATION_USAGE),
+        edge(6, 8, 9, EdgeKind::ANNOTATION_USAGE),
+        edge(7, 8, 3, EdgeKind::CALL),
+    ])?;
+    let reaches = |reached: Vec<TestReach>| {
+        reached
+            .into_iter()
+            .map(|reach| (reach.node_id.0, reach.distance))
+            .collect::<Vec<_>>()
+    };
+
+    let mut tests = storage.test_function_ids()?.into_iter().collect::<Vec<_>>();
+    tests.sort_by_key(|id| id.0);
+    assert_eq!(tests, vec![NodeId(1), NodeId(5), NodeId(8)]);
+    // The helper in tests/common.rs is walked through but is not production code.
+    assert_eq!(
+        reaches(storage.code_tested_by(NodeId(1))?),
+        vec![(3, 2), (4, 3)]
+    );
+    assert_eq!(
+        reaches(storage.tests_covering(NodeId(4))?),
+        vec![(5, 1), (8, 2), (1, 3)]
+    );
+    assert!(storage.tests_covering(NodeId(7))?.is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_entry_point_distances_follow_calls_from_main_routes_and_tests() -> Result<(), StorageError>
+{
+    let mut storage = Storage::new_in_memory()?;
+    let node = |id, name: &str, canonical_id: Option<&str>| Node {
+        id: NodeId(id),
+        kind: NodeKind::FUNCTION,
+        serialized_name: name.to_string(),
+        canonical_id: canonical_id.map(str::to_string),
+        ..Default::default()
+    };
+    storage.insert_nodes_batch(&[
+        node(1, "app::main", None),
+        node(2, "run", None),
+        node(3, "load_config", None),
+        node(4, "GET /health", Some("route_endpoint:GET /health")),
+        node(5, "health", None),
+        node(6, "test_load_config", None),
+        node(7, "dead_code", None),
+        node(8, "also_dead", None),
+        node(9, "lambda::handler", None),
+        node(10, "tauri command save", Some("tauri:command:save")),
+        node(11, "Cli.run", None),
+        Node {
+            id: NodeId(12),
+            kind: NodeKind::ANNOTATION,
+            serialized_name: "picocli.CommandLine.Command".to_string(),
+            ..Default::default()
+        },
+    ])?;
+    let call = |id, source, target| Edge {
+        id: EdgeId(id),
+        source: NodeId(source),
+        target: NodeId(target),
+        kind: EdgeKind::CALL,
+        ..Default::default()
+    };
+    storage.insert_edges_batch(&[
+        call(1, 1, 2),
+        call(2, 2, 3),
+        call(3, 4, 5),
+        call(4, 6, 3),
+        call(5, 7, 8),
+        call(6, 8, 3),
+        Edge {
+            kind: EdgeKind::ANNOTATION_USAGE,
+            ..call(7, 11, 12)
+        },
+    ])?;
+
+    let reach = storage.entry_point_distances()?;
+    assert_eq!(
+        reach.entry_points,
+        [1, 4, 6, 9, 10, 11].map(NodeId).to_vec()
+    );
+    assert_eq!(reach.distance(NodeId(3)), Some(1));
+    assert_eq!(reach.distance(NodeId(2)), Some(1));
+    assert_eq!(reach.distance(NodeId(5)), Some(1));
+    assert_eq!(reach.distance(NodeId(7)), None);
+    assert!(!reach.truncated);
+
+    let trail = storage.get_trail(&TrailConfig {
+        root_id: NodeId(3),
+        direction: TrailDirection::Incoming,
+        depth: 0,
+        caller_scope: TrailCallerScope::IncludeTestsAndBenches,
+        show_utility_calls: true,
+        entry_reachable_only: true,
+        ..TrailConfig::default()
+    })?;
+    let mut node_ids = trail.nodes.iter().map(|node| node.id.0).collect::<Vec<_>>();
+    node_ids.sort_unstable();
+    assert_eq!(node_ids, vec![1, 2, 3, 6]);
+    assert_eq!(trail.omitted_node_count, 1);
+
+    // Stored distances are served until the next refresh.
+    assert_eq!(storage.refresh_entry_point_distances()?, reach);
+    storage.conn.execute("DELETE FROM edge WHERE id = 3", [])?;
+    assert_eq!(storage.entry_point_distances()?, reach);
+    storage.refresh_entry_point_distances()?;
+    assert_eq!(storage.entry_point_distances()?.distance(NodeId(5)), None);
+    Ok(())
+}
+
+#[test]
+fn test_node_edge_counts_group_calls_and_members_by_direction() -> Result<(), StorageError> {
+    let mut storage = Storage::new_in_memory()?;
+    let node = |id, kind, name: &str| Node {
+        id: NodeId(id),
+        kind,
+        serialized_name: name.to_string(),
+        ..Default::default()
+    };
+    storage.insert_nodes_batch(&[
+        node(1, NodeKind::CLASS, "Service"),
+        node(2, NodeKind::METHOD, "Service::run"),
+        node(3, NodeKind::METHOD, "Service::stop"),
+        node(4, NodeKind::FUNCTION, "main"),
+        node(5, NodeKind::UNKNOWN, "run"),
+    ])?;
+    let edge = |id, source, target, kind| Edge {
+        id: EdgeId(id),
+        source: NodeId(source),
+        target: NodeId(target),
+        kind,
+        ..Default::default()
+    };
+    storage.insert_edges_batch(&[
+        edge(1, 1, 2, EdgeKind::MEMBER),
+        edge(2, 1, 3, EdgeKind::MEMBER),
+        edge(3, 2, 3, EdgeKind::CALL),
+        edge(4, 4, 2, EdgeKind::CALL),
+        Edge {
+            resolved_target: Some(NodeId(2)),
+            ..edge(5, 4, 5, EdgeKind::CALL)
+        },
+    ])?;
+
+    let counts = storage.node_edge_counts(&[NodeId(1), NodeId(2), NodeId(2), NodeId(5)])?;
+
+    assert_eq!(
+        counts.get(&NodeId(1)),
+        Some(&NodeEdgeCounts {
+            members: 2,
+            ..NodeEdgeCounts::default()
+        })
+    );
+    assert_eq!(
+        counts.get(&NodeId(2)),
+        Some(&NodeEdgeCounts {
+            outgoing_calls: 1,
+            incoming_calls: 2,
+            members: 0,
+        })
+    );
+    assert!(
+        !counts.contains_key(&NodeId(5)),
+        "resolved calls count at the resolved target"
+    );
+    assert!(
+        !counts.contains_key(&NodeId(4)),
+        "only requested nodes are counted"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_node_badges_report_file_errors_and_bookmarks() -> Result<(), StorageError> {
+    let mut storage = Storage::new_in_memory()?;
+    let node = |id, kind, file: Option<i64>| Node {
+        id: NodeId(id),
+        kind,
+        serialized_name: format!("n{id}"),
+        file_node_id: file.map(NodeId),
+        ..Default::default()
+    };
+    storage.insert_nodes_batch(&[
+        node(1, NodeKind::FILE, None),
+        node(2, NodeKind::FUNCTION, Some(1)),
+        node(3, NodeKind::FILE, None),
+        node(4, NodeKind::FUNCTION, Some(3)),
+        node(5, NodeKind::FUNCTION, Some(3)),
+    ])?;
+    insert_file_row(&storage, 1, "src/broken.ts")?;
+    insert_file_row(&storage, 3, "src/clean.ts")?;
+    storage.insert_error(&codestory_contracts::graph::ErrorInfo {
+        message: "unexpected token".to_string(),
+        file_id: Some(NodeId(1)),
+        line: Some(3),
+        column: Some(1),
+        is_fatal: false,
+        index_step: codestory_contracts::graph::IndexStep::Indexing,
+        coverage_reason: None,
+    })?;
+    let category = storage.create_bookmark_category("review")?;
+    storage.add_bookmark(category, NodeId(4), Some("  "))?;
+    storage.add_bookmark(category, NodeId(5), None)?;
+    storage.add_bookmark(category, NodeId(5), Some("check the retry"))?;
+
+    let badges = storage.node_badges(&(1..=5).map(NodeId).collect::<Vec<_>>())?;
+
+    let errors = NodeBadges {
+        has_errors: true,
+        ..NodeBadges::default()
+    };
+    assert_eq!(badges.get(&NodeId(1)), Some(&errors));
+    assert_eq!(badges.get(&NodeId(2)), Some(&errors));
+    assert!(!badges.contains_key(&NodeId(3)));
+    assert_eq!(
+        badges.get(&NodeId(4)),
+        Some(&NodeBadges {
+            is_bookmarked: true,
+            ..NodeBadges::default()
+        })
+    );
+    assert_eq!(
+        badges.get(&NodeId(5)),
+        Some(&NodeBadges {
+            is_bookmarked: true,
+            has_annotation: true,
+            ..NodeBadges::default()
+        })
+    );
+    Ok(())
+}
+
 #[test]
 fn test_trail_to_target_symbol_prunes_unreachable_incoming_fanout() -> Result<(), StorageError> {
     let mut storage = Storage::new_in_memory()?;
@@ -8329,6 +9923,11 @@ fn test_trail_to_target_symbol_prunes_unreachable_incoming_fanout() -> Result<()
         edge_filter: vec![],
         show_utility_calls: true,
         node_filter: Vec::new(),
+        hide_trivial_accessors: false,
+        hide_generated: false,
+        entry_reachable_only: false,
+        max_paths: None,
+        edge_weight: Default::default(),
         max_nodes: 4,
     })?;
 
@@ -8341,446 +9940,1747 @@ fn test_trail_to_target_symbol_prunes_unreachable_incoming_fanout() -> Result<()
         vec![EdgeId(1), EdgeId(2), EdgeId(3)]
     );
     assert!(!result.truncated);
+    assert_eq!(result.omitted_node_count, 0);
 
     Ok(())
 }
 
 #[test]
-fn test_trail_to_target_symbol_no_path_returns_endpoints() -> Result<(), StorageError> {
+fn test_trail_counts_nodes_cut_by_max_nodes() -> Result<(), StorageError> {
     let mut storage = Storage::new_in_memory()?;
-
-    storage.insert_nodes_batch(&[
-        Node {
-            id: NodeId(1),
-            kind: NodeKind::FUNCTION,
-            serialized_name: "A".to_string(),
-            ..Default::default()
-        },
-        Node {
-            id: NodeId(2),
+    let nodes = (1..=20)
+        .map(|id| Node {
+            id: NodeId(id),
             kind: NodeKind::FUNCTION,
-            serialized_name: "B".to_string(),
+            serialized_name: format!("f{id}"),
             ..Default::default()
-        },
-        Node {
-            id: NodeId(3),
-            kind: NodeKind::FUNCTION,
-            serialized_name: "C".to_string(),
+        })
+        .collect::<Vec<_>>();
+    storage.insert_nodes_batch(&nodes)?;
+    let edges = (2..=20)
+        .map(|id| Edge {
+            id: EdgeId(id),
+            source: NodeId(1),
+            target: NodeId(id),
+            kind: EdgeKind::CALL,
             ..Default::default()
-        },
-    ])?;
-    storage.insert_edges_batch(&[Edge {
-        id: EdgeId(1),
-        source: NodeId(1),
-        target: NodeId(2),
-        kind: EdgeKind::CALL,
-        ..Default::default()
-    }])?;
+        })
+        .collect::<Vec<_>>();
+    storage.insert_edges_batch(&edges)?;
 
     let result = storage.get_trail(&TrailConfig {
         root_id: NodeId(1),
-        mode: TrailMode::ToTargetSymbol,
-        target_id: Some(NodeId(3)),
-        depth: 0,
+        depth: 1,
         direction: TrailDirection::Outgoing,
-        caller_scope: TrailCallerScope::IncludeTestsAndBenches,
-        edge_filter: vec![],
         show_utility_calls: true,
-        node_filter: Vec::new(),
-        max_nodes: 100,
+        max_paths: None,
+        max_nodes: 10,
+        ..TrailConfig::default()
     })?;
 
-    assert_eq!(
-        result.nodes.iter().map(|node| node.id).collect::<Vec<_>>(),
-        vec![NodeId(1), NodeId(3)]
+    assert!(result.truncated);
+    assert_eq!(result.nodes.len(), 10);
+    assert_eq!(result.omitted_node_count, 10);
+    assert!(
+        result
+            .nodes
+            .iter()
+            .all(|node| result.depth_map.contains_key(&node.id))
     );
-    assert!(result.edges.is_empty());
-    assert!(!result.truncated);
 
     Ok(())
 }
 
 #[test]
-fn test_trail_ignores_ambiguous_call_resolutions() -> Result<(), StorageError> {
+fn test_trail_returns_each_relationship_once() -> Result<(), StorageError> {
     let mut storage = Storage::new_in_memory()?;
-
-    let caller = Node {
-        id: NodeId(1),
-        kind: NodeKind::FUNCTION,
-        serialized_name: "caller".to_string(),
-        qualified_name: Some("caller".to_string()),
-        ..Default::default()
-    };
-    let call_symbol = Node {
-        id: NodeId(10),
-        kind: NodeKind::UNKNOWN,
-        serialized_name: "add".to_string(),
-        ..Default::default()
-    };
-    let resolved = Node {
-        id: NodeId(3),
-        kind: NodeKind::METHOD,
-        serialized_name: "SomeType::add".to_string(),
-        qualified_name: Some("SomeType::add".to_string()),
-        ..Default::default()
-    };
-
-    storage.insert_nodes_batch(&[caller.clone(), call_symbol.clone(), resolved.clone()])?;
-    storage.insert_edges_batch(&[Edge {
-        id: EdgeId(100),
-        source: caller.id,
-        target: call_symbol.id,
+    storage.insert_nodes_batch(&[
+        Node {
+            id: NodeId(1),
+            kind: NodeKind::FUNCTION,
+            serialized_name: "caller".to_string(),
+            ..Default::default()
+        },
+        Node {
+            id: NodeId(2),
+            kind: NodeKind::FUNCTION,
+            serialized_name: "callee".to_string(),
+            ..Default::default()
+        },
+        Node {
+            id: NodeId(3),
+            kind: NodeKind::UNKNOWN,
+            serialized_name: "callee".to_string(),
+            ..Default::default()
+        },
+    ])?;
+    let call = |id: i64, source: i64, target: i64, line: u32| Edge {
+        id: EdgeId(id),
+        source: NodeId(source),
+        target: NodeId(target),
         kind: EdgeKind::CALL,
-        resolved_target: Some(resolved.id),
-        confidence: Some(0.6),
+        line: Some(line),
         ..Default::default()
-    }])?;
+    };
+    storage.insert_edges_batch(&[
+        call(1, 1, 2, 5),
+        // The same call extracted against a placeholder and resolved later.
+        Edge {
+            resolved_target: Some(NodeId(2)),
+            confidence: Some(0.9),
+            certainty: Some(ResolutionCertainty::Certain),
+            ..call(2, 1, 3, 5)
+        },
+        call(3, 2, 1, 9),
+    ])?;
 
-    // Exploring from the resolved target should not traverse this edge.
     let result = storage.get_trail(&TrailConfig {
-        root_id: resolved.id,
-        mode: TrailMode::Neighborhood,
-        target_id: None,
-        depth: 1,
-        direction: TrailDirection::Incoming,
-        caller_scope: TrailCallerScope::IncludeTestsAndBenches,
-        edge_filter: vec![EdgeKind::CALL],
+        root_id: NodeId(1),
+        depth: 2,
+        direction: TrailDirection::Both,
         show_utility_calls: true,
-        node_filter: Vec::new(),
-        max_nodes: 50,
+        ..TrailConfig::default()
     })?;
 
-    assert!(result.edges.is_empty());
-    assert_eq!(result.nodes.len(), 1);
-    assert_eq!(result.nodes[0].id, resolved.id);
+    let edge_ids = |result: &TrailResult| {
+        result
+            .edges
+            .iter()
+            .map(|edge| edge.id.0)
+            .collect::<Vec<_>>()
+    };
+    // The resolved edge is kept over the unscored raw one.
+    assert_eq!(edge_ids(&result), vec![2, 3]);
+    assert_eq!(
+        result.collapsed_edge_ids,
+        HashMap::from([(EdgeId(2), vec![EdgeId(1)])])
+    );
+    assert_eq!(result.omitted_edge_count, 0);
 
+    // Every mode folds the duplicate, not only the breadth-first ones.
+    let to_target = storage.get_trail(&TrailConfig {
+        root_id: NodeId(1),
+        target_id: Some(NodeId(2)),
+        mode: TrailMode::ToTargetSymbol,
+        depth: 2,
+        direction: TrailDirection::Outgoing,
+        show_utility_calls: true,
+        ..TrailConfig::default()
+    })?;
+    assert_eq!(edge_ids(&to_target), vec![2]);
+    assert_eq!(
+        to_target.collapsed_edge_ids,
+        HashMap::from([(EdgeId(2), vec![EdgeId(1)])])
+    );
     Ok(())
 }
 
 #[test]
-fn test_trail_production_scope_excludes_test_callers() -> Result<(), StorageError> {
+fn test_dependency_resolutions_point_unresolved_calls_into_dependency_nodes()
+-> Result<(), StorageError> {
     let mut storage = Storage::new_in_memory()?;
-
-    let file_prod = Node {
-        id: NodeId(100),
-        kind: NodeKind::FILE,
-        serialized_name: "src/lib.rs".to_string(),
-        ..Default::default()
-    };
-    let file_test = Node {
-        id: NodeId(101),
-        kind: NodeKind::FILE,
-        serialized_name: "tests/integration.rs".to_string(),
+    storage.insert_nodes_batch(&[
+        Node {
+            id: NodeId(10),
+            kind: NodeKind::FUNCTION,
+            serialized_name: "main".to_string(),
+            ..Default::default()
+        },
+        Node {
+            id: NodeId(20),
+            kind: NodeKind::UNKNOWN,
+            serialized_name: "from_str".to_string(),
+            ..Default::default()
+        },
+    ])?;
+    storage.insert_edges_batch(&[Edge {
+        id: EdgeId(1),
+        source: NodeId(10),
+        target: NodeId(20),
+        kind: EdgeKind::CALL,
         ..Default::default()
-    };
-    let prod_target = Node {
-        id: NodeId(1),
+    }])?;
+    let library_fn = Node {
+        id: NodeId(900),
         kind: NodeKind::FUNCTION,
-        serialized_name: "target".to_string(),
-        file_node_id: Some(file_prod.id),
+        serialized_name: "serde_json::from_str".to_string(),
         ..Default::default()
     };
-    let test_caller = Node {
-        id: NodeId(2),
-        kind: NodeKind::FUNCTION,
-        serialized_name: "test_caller".to_string(),
-        file_node_id: Some(file_test.id),
-        ..Default::default()
+    let resolution = DependencyResolution {
+        edge_id: EdgeId(1),
+        target: NodeId(900),
+        dependency: "cargo:serde_json-1.0.120".to_string(),
     };
-    let unresolved_target = Node {
-        id: NodeId(3),
-        kind: NodeKind::UNKNOWN,
-        serialized_name: "target".to_string(),
-        file_node_id: Some(file_test.id),
+
+    let applied = storage.apply_dependency_resolutions(
+        std::slice::from_ref(&library_fn),
+        std::slice::from_ref(&resolution),
+    )?;
+    assert_eq!(applied, 1);
+    assert!(storage.get_node(NodeId(900))?.is_some());
+    let edge = storage.get_edges()?.remove(0);
+    assert_eq!(edge.resolved_target, Some(NodeId(900)));
+    assert_eq!(edge.certainty, Some(ResolutionCertainty::Probable));
+    assert_eq!(
+        storage
+            .dependency_resolutions()?
+            .get(&EdgeId(1))
+            .map(String::as_str),
+        Some("cargo:serde_json-1.0.120")
+    );
+
+    assert_eq!(
+        storage.apply_dependency_resolutions(&[], std::slice::from_ref(&resolution))?,
+        0,
+        "edges that already resolve are left alone"
+    );
+
+    assert_eq!(storage.reset_dependency_resolutions()?, 1);
+    assert!(
+        storage.get_node(NodeId(900))?.is_none(),
+        "copied dependency nodes go with their resolutions"
+    );
+    let edge = storage.get_edges()?.remove(0);
+    assert_eq!(edge.resolved_target, None);
+    assert_eq!(edge.certainty, None);
+    assert!(storage.dependency_resolutions()?.is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_merged_nodes_redirect_edges_and_occurrences_across_reindexing() -> Result<(), StorageError>
+{
+    let mut storage = Storage::new_in_memory()?;
+    let module = |id, name: &str| Node {
+        id: NodeId(id),
+        kind: NodeKind::MODULE,
+        serialized_name: name.to_string(),
         ..Default::default()
     };
-
     storage.insert_nodes_batch(&[
-        file_prod,
-        file_test,
-        prod_target,
-        test_caller,
-        unresolved_target,
+        module(1, "react"),
+        module(2, "\"react\""),
+        Node {
+            id: NodeId(3),
+            kind: NodeKind::FUNCTION,
+            serialized_name: "render".to_string(),
+            ..Default::default()
+        },
     ])?;
     storage.insert_edges_batch(&[Edge {
-        id: EdgeId(1),
-        source: NodeId(2),
-        target: NodeId(3),
-        kind: EdgeKind::CALL,
-        resolved_target: Some(NodeId(1)),
-        file_node_id: Some(NodeId(101)),
+        id: EdgeId(10),
+        source: NodeId(3),
+        target: NodeId(2),
+        kind: EdgeKind::IMPORT,
         ..Default::default()
     }])?;
+    let occurrence = |element_id| Occurrence {
+        element_id,
+        kind: OccurrenceKind::REFERENCE,
+        location: SourceLocation {
+            file_node_id: NodeId(3),
+            start_line: 1,
+            start_col: 1,
+            end_line: 1,
+            end_col: 8,
+        },
+        edge_id: None,
+        notebook_cell: None,
+    };
+    storage.insert_occurrences_batch(&[occurrence(2)])?;
 
-    let production_only = storage.get_trail(&TrailConfig {
-        root_id: NodeId(1),
-        mode: TrailMode::Neighborhood,
-        target_id: None,
-        depth: 1,
-        direction: TrailDirection::Incoming,
-        caller_scope: TrailCallerScope::ProductionOnly,
-        edge_filter: vec![EdgeKind::CALL],
-        show_utility_calls: true,
-        node_filter: Vec::new(),
-        max_nodes: 50,
-    })?;
-    assert!(production_only.edges.is_empty());
-
-    let include_tests = storage.get_trail(&TrailConfig {
-        root_id: NodeId(1),
-        mode: TrailMode::Neighborhood,
-        target_id: None,
-        depth: 1,
-        direction: TrailDirection::Incoming,
-        caller_scope: TrailCallerScope::IncludeTestsAndBenches,
-        edge_filter: vec![EdgeKind::CALL],
-        show_utility_calls: true,
-        node_filter: Vec::new(),
-        max_nodes: 50,
-    })?;
-    assert_eq!(include_tests.edges.len(), 1);
+    assert_eq!(storage.merge_nodes(NodeId(1), &[NodeId(2), NodeId(1)])?, 1);
+    assert_eq!(storage.resolve_node_alias(NodeId(2))?, NodeId(1));
+    let edges = storage.get_edges_for_node_id(NodeId(1))?;
+    assert_eq!(edges.len(), 1);
+    assert_eq!(edges[0].target, NodeId(1));
+    let occurrences = storage.get_occurrences_for_node(NodeId(1))?;
+    assert_eq!(occurrences.len(), 1);
+    assert_eq!(occurrences[0].element_id, 1);
+
+    // Re-indexing gives the duplicate a new id; the merge follows its name.
+    storage.conn.execute("DELETE FROM edge", [])?;
+    storage.conn.execute("DELETE FROM node WHERE id = 2", [])?;
+    storage.insert_nodes_batch(&[module(22, "\"react\"")])?;
+    storage.insert_edges_batch(&[Edge {
+        id: EdgeId(11),
+        source: NodeId(3),
+        target: NodeId(22),
+        kind: EdgeKind::IMPORT,
+        ..Default::default()
+    }])?;
+    let aliases = storage.node_aliases()?;
+    assert_eq!(aliases.len(), 1);
+    assert_eq!(aliases[0].node_id, Some(NodeId(22)));
+    assert_eq!(aliases[0].primary_node_id, Some(NodeId(1)));
+    assert_eq!(
+        storage.get_edges_for_node_id(NodeId(22))?[0].target,
+        NodeId(1)
+    );
 
+    assert!(storage.unmerge_node(NodeId(22))?);
+    assert!(storage.node_aliases()?.is_empty());
+    assert!(storage.get_edges_for_node_id(NodeId(1))?.is_empty());
     Ok(())
 }
 
 #[test]
-fn test_trail_can_hide_utility_calls() -> Result<(), StorageError> {
+fn test_same_name_duplicates_merge_into_the_lowest_id_node() -> Result<(), StorageError> {
     let mut storage = Storage::new_in_memory()?;
-
-    let caller = Node {
-        id: NodeId(1),
+    let function = |id| Node {
+        id: NodeId(id),
         kind: NodeKind::FUNCTION,
-        serialized_name: "caller".to_string(),
+        serialized_name: "render".to_string(),
         ..Default::default()
     };
-    let utility_symbol = Node {
-        id: NodeId(2),
-        kind: NodeKind::UNKNOWN,
-        serialized_name: "len".to_string(),
-        ..Default::default()
-    };
-
-    storage.insert_nodes_batch(&[caller, utility_symbol])?;
+    storage.insert_nodes_batch(&[function(1), function(2)])?;
     storage.insert_edges_batch(&[Edge {
         id: EdgeId(10),
-        source: NodeId(1),
+        source: NodeId(2),
         target: NodeId(2),
         kind: EdgeKind::CALL,
         ..Default::default()
     }])?;
+    // Loaded before the merge, so the merge must invalidate the cached map.
+    assert_eq!(storage.resolve_node_alias(NodeId(2))?, NodeId(2));
+
+    assert_eq!(storage.merge_nodes(NodeId(1), &[NodeId(2)])?, 1);
+    assert_eq!(storage.resolve_node_alias(NodeId(2))?, NodeId(1));
+    let aliases = storage.node_aliases()?;
+    assert_eq!(aliases.len(), 1);
+    assert_eq!(aliases[0].node_id, Some(NodeId(2)));
+    assert_eq!(aliases[0].primary_node_id, Some(NodeId(1)));
+    let edges = storage.get_edges_for_node_id(NodeId(1))?;
+    assert_eq!(edges.len(), 1);
+    assert_eq!((edges[0].source, edges[0].target), (NodeId(1), NodeId(1)));
 
-    let hidden = storage.get_trail(&TrailConfig {
-        root_id: NodeId(1),
-        mode: TrailMode::Neighborhood,
-        target_id: None,
-        depth: 1,
-        direction: TrailDirection::Outgoing,
-        caller_scope: TrailCallerScope::IncludeTestsAndBenches,
-        edge_filter: vec![EdgeKind::CALL],
-        show_utility_calls: false,
-        node_filter: Vec::new(),
-        max_nodes: 50,
-    })?;
-    assert!(hidden.edges.is_empty());
-
-    let shown = storage.get_trail(&TrailConfig {
-        root_id: NodeId(1),
-        mode: TrailMode::Neighborhood,
-        target_id: None,
-        depth: 1,
-        direction: TrailDirection::Outgoing,
-        caller_scope: TrailCallerScope::IncludeTestsAndBenches,
-        edge_filter: vec![EdgeKind::CALL],
-        show_utility_calls: true,
-        node_filter: Vec::new(),
-        max_nodes: 50,
-    })?;
-    assert_eq!(shown.edges.len(), 1);
+    // A third node with the name joins the merge without another call.
+    storage.insert_nodes_batch(&[function(3)])?;
+    assert_eq!(storage.resolve_node_alias(NodeId(3))?, NodeId(1));
 
+    assert!(storage.unmerge_node(NodeId(2))?);
+    assert_eq!(storage.resolve_node_alias(NodeId(2))?, NodeId(2));
     Ok(())
 }
 
 #[test]
-fn test_helper_calls_are_not_suppressed_as_ambiguous() {
-    assert!(!should_ignore_call_resolution(
-        "Self::flush_projection_batch",
-        Some(ResolutionCertainty::Uncertain),
-        Some(0.40)
-    ));
-    assert!(!should_ignore_call_resolution(
-        "WorkspaceIndexer::seed_symbol_table",
-        Some(ResolutionCertainty::Probable),
-        Some(0.70)
-    ));
+fn test_get_nodes_by_canonical_id_matches_whole_ids_only() -> Result<(), StorageError> {
+    let mut storage = Storage::new_in_memory()?;
+    let node = |id, canonical_id: &str| Node {
+        id: NodeId(id),
+        kind: NodeKind::METHOD,
+        serialized_name: "Counter::bump".to_string(),
+        canonical_id: Some(canonical_id.to_string()),
+        ..Default::default()
+    };
+    storage.insert_nodes_batch(&[
+        node(2, "sym:rust:Counter::bump#00000000000000aa"),
+        node(1, "sym:rust:Counter::bump#00000000000000aa"),
+        node(3, "sym:rust:Counter::bump#00000000000000bb"),
+    ])?;
+
+    let ids = storage
+        .get_nodes_by_canonical_id("sym:rust:Counter::bump#00000000000000aa")?
+        .into_iter()
+        .map(|node| node.id)
+        .collect::<Vec<_>>();
+    assert_eq!(ids, vec![NodeId(1), NodeId(2)]);
+    assert!(
+        storage
+            .get_nodes_by_canonical_id("sym:rust:Counter::bump")?
+            .is_empty()
+    );
+    Ok(())
 }
 
 #[test]
-fn test_safe_enum_conversion() -> Result<(), StorageError> {
+fn test_generation_advances_on_graph_writes_only() -> Result<(), StorageError> {
     let mut storage = Storage::new_in_memory()?;
+    assert_eq!(storage.generation()?, 0);
 
-    // Test that we can round-trip all NodeKind variants
-    let node = Node {
+    storage.insert_nodes_batch(&[Node {
         id: NodeId(1),
-        kind: NodeKind::ENUM_CONSTANT,
-        serialized_name: "test".to_string(),
+        kind: NodeKind::FUNCTION,
+        serialized_name: "run".to_string(),
         ..Default::default()
-    };
-    storage.insert_nodes_batch(&[node])?;
+    }])?;
+    let after_insert = storage.generation()?;
+    assert!(after_insert > 0);
 
-    let nodes = storage.get_nodes()?;
-    assert_eq!(nodes[0].kind, NodeKind::ENUM_CONSTANT);
+    storage.get_trail(&TrailConfig {
+        root_id: NodeId(1),
+        ..TrailConfig::default()
+    })?;
+    assert_eq!(storage.generation()?, after_insert);
 
-    // Test that we can round-trip all EdgeKind variants
-    let edges = vec![Edge {
-        id: codestory_contracts::graph::EdgeId(1),
-        source: NodeId(1),
-        target: NodeId(1),
-        kind: EdgeKind::ANNOTATION_USAGE,
-        ..Default::default()
-    }];
-    storage.insert_edges_batch(&edges)?;
+    storage.clear()?;
+    assert!(storage.generation()? > after_insert);
+    Ok(())
+}
 
-    let edges = storage.get_edges()?;
-    assert_eq!(edges[0].kind, EdgeKind::ANNOTATION_USAGE);
+#[test]
+fn test_staged_generation_moves_past_the_live_generation() -> Result<(), StorageError> {
+    let live_path = unique_temp_db_path("generation-live");
+    let live = Storage::open(&live_path)?;
+    for _ in 0..3 {
+        live.invalidate_grounding_snapshots()?;
+    }
+    let live_generation = live.generation()?;
+    drop(live);
 
+    let mut staged = Storage::open(unique_temp_db_path("generation-staged"))?;
+    staged.invalidate_grounding_snapshots()?;
+    assert!(staged.generation()? < live_generation);
+    let carried = staged.copy_generation_from(&live_path)?;
+    assert!(carried > live_generation);
+    assert_eq!(staged.generation()?, carried);
+
+    let mut fresh = Storage::new_in_memory()?;
+    assert_eq!(
+        fresh.copy_generation_from(&unique_temp_db_path("generation-missing"))?,
+        1
+    );
     Ok(())
 }
 
 #[test]
-fn grounding_node_snapshot_preserves_columns_rank_and_member_root_direction()
--> Result<(), StorageError> {
-    #[derive(Debug, PartialEq)]
-    struct SnapshotRow {
-        node_id: i64,
-        kind: i32,
-        serialized_name: String,
-        qualified_name: Option<String>,
-        canonical_id: Option<String>,
-        file_node_id: Option<i64>,
-        start_line: Option<i64>,
-        start_col: Option<i64>,
-        end_line: Option<i64>,
-        end_col: Option<i64>,
-        display_name: String,
-        file_path: Option<String>,
-        node_rank: i64,
-        sort_start_line: i64,
-        is_root: i64,
-        file_symbol_rank: Option<i64>,
+fn test_clear_drops_centrality_marker_and_literal_rows() -> Result<(), StorageError> {
+    let storage = Storage::new_in_memory()?;
+    storage.conn.execute_batch(
+        "INSERT INTO node_centrality (node_id, score) VALUES (1, 0.5);
+         INSERT INTO code_marker (file_id, line, column, tag, text) VALUES (1, 1, 1, 'TODO', 'x');
+         INSERT INTO string_literal (file_id, line, column, name, value) VALUES (1, 1, 1, NULL, 'x');",
+    )?;
+
+    storage.clear()?;
+
+    for table in ["node_centrality", "code_marker", "string_literal"] {
+        let rows: i64 =
+            storage
+                .conn
+                .query_row(&format!("SELECT COUNT(*) FROM {table}"), [], |row| {
+                    row.get(0)
+                })?;
+        assert_eq!(rows, 0, "{table} survived clear");
     }
+    Ok(())
+}
 
+#[test]
+fn test_trail_until_returns_partial_result_once_stopped() -> Result<(), StorageError> {
     let mut storage = Storage::new_in_memory()?;
-    storage.insert_file(&FileInfo {
-        id: 100,
-        path: PathBuf::from("src/lib.rs"),
-        language: "rust".to_string(),
-        modification_time: 0,
-        indexed: true,
-        complete: true,
-        line_count: 20,
-        file_role: FileRole::Source,
-    })?;
-    storage.insert_nodes_batch(&[
-        Node {
-            id: NodeId(100),
-            kind: NodeKind::FILE,
-            serialized_name: "source-node-path.rs".to_string(),
+    let nodes = (1..=4)
+        .map(|id| Node {
+            id: NodeId(id),
+            kind: NodeKind::FUNCTION,
+            serialized_name: format!("f{id}"),
             ..Default::default()
-        },
-        Node {
-            id: NodeId(200),
-            kind: NodeKind::FILE,
-            serialized_name: "generated/fallback.ts".to_string(),
+        })
+        .collect::<Vec<_>>();
+    storage.insert_nodes_batch(&nodes)?;
+    let edges = (2..=4)
+        .map(|id| Edge {
+            id: EdgeId(id),
+            source: NodeId(id - 1),
+            target: NodeId(id),
+            kind: EdgeKind::CALL,
             ..Default::default()
+        })
+        .collect::<Vec<_>>();
+    storage.insert_edges_batch(&edges)?;
+    let config = TrailConfig {
+        root_id: NodeId(1),
+        depth: 0,
+        direction: TrailDirection::Outgoing,
+        show_utility_calls: true,
+        max_nodes: 10,
+        ..TrailConfig::default()
+    };
+
+    let unbounded = storage.get_trail_until(&config, &TrailStop::default())?;
+    assert_eq!(unbounded.nodes.len(), 4);
+    assert!(!unbounded.truncated_by_timeout);
+
+    let expired = TrailStop::new(Some(std::time::Instant::now()), None);
+    let result = storage.get_trail_until(&config, &expired)?;
+    assert!(result.truncated);
+    assert!(result.truncated_by_timeout);
+    assert_eq!(
+        result.nodes.iter().map(|node| node.id).collect::<Vec<_>>(),
+        vec![NodeId(1)]
+    );
+
+    let cancelled = Arc::new(std::sync::atomic::AtomicBool::new(true));
+    let result = storage.get_trail_until(
+        &TrailConfig {
+            mode: TrailMode::AllPaths,
+            target_id: Some(NodeId(4)),
+            ..config
         },
+        &TrailStop::new(None, Some(cancelled)),
+    )?;
+    assert!(result.truncated_by_timeout);
+    assert!(result.paths.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_trail_to_target_symbol_no_path_returns_endpoints() -> Result<(), StorageError> {
+    let mut storage = Storage::new_in_memory()?;
+
+    storage.insert_nodes_batch(&[
         Node {
-            id: NodeId(101),
-            kind: NodeKind::STRUCT,
-            serialized_name: "Widget".to_string(),
-            qualified_name: Some("crate::Widget".to_string()),
-            canonical_id: Some("rust:struct:Widget".to_string()),
-            file_node_id: Some(NodeId(100)),
-            start_line: Some(2),
-            start_col: Some(3),
-            end_line: Some(8),
-            end_col: Some(1),
-        },
-        Node {
-            id: NodeId(102),
+            id: NodeId(1),
             kind: NodeKind::FUNCTION,
-            serialized_name: "run".to_string(),
-            file_node_id: Some(NodeId(100)),
-            start_line: Some(10),
-            start_col: Some(1),
-            end_line: Some(12),
-            end_col: Some(2),
-            ..Default::default()
-        },
-        Node {
-            id: NodeId(103),
-            kind: NodeKind::MODULE,
-            serialized_name: "\"./types\"".to_string(),
-            file_node_id: Some(NodeId(100)),
-            start_line: Some(1),
+            serialized_name: "A".to_string(),
             ..Default::default()
         },
         Node {
-            id: NodeId(201),
-            kind: NodeKind::CLASS,
-            serialized_name: "Fallback".to_string(),
-            file_node_id: Some(NodeId(200)),
+            id: NodeId(2),
+            kind: NodeKind::FUNCTION,
+            serialized_name: "B".to_string(),
             ..Default::default()
         },
         Node {
-            id: NodeId(202),
-            kind: NodeKind::UNKNOWN,
-            serialized_name: "excluded".to_string(),
-            file_node_id: Some(NodeId(200)),
+            id: NodeId(3),
+            kind: NodeKind::FUNCTION,
+            serialized_name: "C".to_string(),
             ..Default::default()
         },
     ])?;
     storage.insert_edges_batch(&[Edge {
         id: EdgeId(1),
-        source: NodeId(101),
-        target: NodeId(102),
-        kind: EdgeKind::MEMBER,
-        file_node_id: Some(NodeId(100)),
+        source: NodeId(1),
+        target: NodeId(2),
+        kind: EdgeKind::CALL,
         ..Default::default()
     }])?;
 
-    storage.refresh_grounding_summary_snapshots()?;
-    let mut stmt = storage.conn.prepare(
-        "SELECT
-            node_id,
-            kind,
-            serialized_name,
-            qualified_name,
-            canonical_id,
-            file_node_id,
-            start_line,
-            start_col,
-            end_line,
-            end_col,
-            display_name,
-            file_path,
-            node_rank,
-            sort_start_line,
-            is_root,
-            file_symbol_rank
-         FROM grounding_node_snapshot
-         ORDER BY node_id",
-    )?;
-    let actual = stmt
-        .query_map([], |row| {
-            Ok(SnapshotRow {
-                node_id: row.get(0)?,
-                kind: row.get(1)?,
-                serialized_name: row.get(2)?,
-                qualified_name: row.get(3)?,
-                canonical_id: row.get(4)?,
-                file_node_id: row.get(5)?,
-                start_line: row.get(6)?,
-                start_col: row.get(7)?,
+    let result = storage.get_trail(&TrailConfig {
+        root_id: NodeId(1),
+        mode: TrailMode::ToTargetSymbol,
+        target_id: Some(NodeId(3)),
+        depth: 0,
+        direction: TrailDirection::Outgoing,
+        caller_scope: TrailCallerScope::IncludeTestsAndBenches,
+        edge_filter: vec![],
+        show_utility_calls: true,
+        node_filter: Vec::new(),
+        hide_trivial_accessors: false,
+        hide_generated: false,
+        entry_reachable_only: false,
+        max_paths: None,
+        edge_weight: Default::default(),
+        max_nodes: 100,
+    })?;
+
+    assert_eq!(
+        result.nodes.iter().map(|node| node.id).collect::<Vec<_>>(),
+        vec![NodeId(1), NodeId(3)]
+    );
+    assert!(result.edges.is_empty());
+    assert!(!result.truncated);
+
+    Ok(())
+}
+
+#[test]
+fn test_trail_ignores_ambiguous_call_resolutions() -> Result<(), StorageError> {
+    let mut storage = Storage::new_in_memory()?;
+
+    let caller = Node {
+        id: NodeId(1),
+        kind: NodeKind::FUNCTION,
+        serialized_name: "caller".to_string(),
+        qualified_name: Some("caller".to_string()),
+        ..Default::default()
+    };
+    let call_symbol = Node {
+        id: NodeId(10),
+        kind: NodeKind::UNKNOWN,
+        serialized_name: "add".to_string(),
+        ..Default::default()
+    };
+    let resolved = Node {
+        id: NodeId(3),
+        kind: NodeKind::METHOD,
+        serialized_name: "SomeType::add".to_string(),
+        qualified_name: Some("SomeType::add".to_string()),
+        ..Default::default()
+    };
+
+    storage.insert_nodes_batch(&[caller.clone(), call_symbol.clone(), resolved.clone()])?;
+    storage.insert_edges_batch(&[Edge {
+        id: EdgeId(100),
+        source: caller.id,
+        target: call_symbol.id,
+        kind: EdgeKind::CALL,
+        resolved_target: Some(resolved.id),
+        confidence: Some(0.6),
+        ..Default::default()
+    }])?;
+
+    // Exploring from the resolved target should not traverse this edge.
+    let result = storage.get_trail(&TrailConfig {
+        root_id: resolved.id,
+        mode: TrailMode::Neighborhood,
+        target_id: None,
+        depth: 1,
+        direction: TrailDirection::Incoming,
+        caller_scope: TrailCallerScope::IncludeTestsAndBenches,
+        edge_filter: vec![EdgeKind::CALL],
+        show_utility_calls: true,
+        node_filter: Vec::new(),
+        hide_trivial_accessors: false,
+        hide_generated: false,
+        entry_reachable_only: false,
+        max_paths: None,
+        edge_weight: Default::default(),
+        max_nodes: 50,
+    })?;
+
+    assert!(result.edges.is_empty());
+    assert_eq!(result.nodes.len(), 1);
+    assert_eq!(result.nodes[0].id, resolved.id);
+
+    Ok(())
+}
+
+#[test]
+fn test_trail_production_scope_excludes_test_callers() -> Result<(), StorageError> {
+    let mut storage = Storage::new_in_memory()?;
+
+    let file_prod = Node {
+        id: NodeId(100),
+        kind: NodeKind::FILE,
+        serialized_name: "src/lib.rs".to_string(),
+        ..Default::default()
+    };
+    let file_test = Node {
+        id: NodeId(101),
+        kind: NodeKind::FILE,
+        serialized_name: "tests/integration.rs".to_string(),
+        ..Default::default()
+    };
+    let prod_target = Node {
+        id: NodeId(1),
+        kind: NodeKind::FUNCTION,
+        serialized_name: "target".to_string(),
+        file_node_id: Some(file_prod.id),
+        ..Default::default()
+    };
+    let test_caller = Node {
+        id: NodeId(2),
+        kind: NodeKind::FUNCTION,
+        serialized_name: "test_caller".to_string(),
+        file_node_id: Some(file_test.id),
+        ..Default::default()
+    };
+    let unresolved_target = Node {
+        id: NodeId(3),
+        kind: NodeKind::UNKNOWN,
+        serialized_name: "target".to_string(),
+        file_node_id: Some(file_test.id),
+        ..Default::default()
+    };
+
+    storage.insert_nodes_batch(&[
+        file_prod,
+        file_test,
+        prod_target,
+        test_caller,
+        unresolved_target,
+    ])?;
+    storage.insert_edges_batch(&[Edge {
+        id: EdgeId(1),
+        source: NodeId(2),
+        target: NodeId(3),
+        kind: EdgeKind::CALL,
+        resolved_target: Some(NodeId(1)),
+        file_node_id: Some(NodeId(101)),
+        ..Default::default()
+    }])?;
+
+    let production_only = storage.get_trail(&TrailConfig {
+        root_id: NodeId(1),
+        mode: TrailMode::Neighborhood,
+        target_id: None,
+        depth: 1,
+        direction: TrailDirection::Incoming,
+        caller_scope: TrailCallerScope::ProductionOnly,
+        edge_filter: vec![EdgeKind::CALL],
+        show_utility_calls: true,
+        node_filter: Vec::new(),
+        hide_trivial_accessors: false,
+        hide_generated: false,
+        entry_reachable_only: false,
+        max_paths: None,
+        edge_weight: Default::default(),
+        max_nodes: 50,
+    })?;
+    assert!(production_only.edges.is_empty());
+
+    let include_tests = storage.get_trail(&TrailConfig {
+        root_id: NodeId(1),
+        mode: TrailMode::Neighborhood,
+        target_id: None,
+        depth: 1,
+        direction: TrailDirection::Incoming,
+        caller_scope: TrailCallerScope::IncludeTestsAndBenches,
+        edge_filter: vec![EdgeKind::CALL],
+        show_utility_calls: true,
+        node_filter: Vec::new(),
+        hide_trivial_accessors: false,
+        hide_generated: false,
+        entry_reachable_only: false,
+        max_paths: None,
+        edge_weight: Default::default(),
+        max_nodes: 50,
+    })?;
+    assert_eq!(include_tests.edges.len(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_trail_can_hide_utility_calls() -> Result<(), StorageError> {
+    let mut storage = Storage::new_in_memory()?;
+
+    let caller = Node {
+        id: NodeId(1),
+        kind: NodeKind::FUNCTION,
+        serialized_name: "caller".to_string(),
+        ..Default::default()
+    };
+    let utility_symbol = Node {
+        id: NodeId(2),
+        kind: NodeKind::UNKNOWN,
+        serialized_name: "len".to_string(),
+        ..Default::default()
+    };
+
+    storage.insert_nodes_batch(&[caller, utility_symbol])?;
+    storage.insert_edges_batch(&[Edge {
+        id: EdgeId(10),
+        source: NodeId(1),
+        target: NodeId(2),
+        kind: EdgeKind::CALL,
+        ..Default::default()
+    }])?;
+
+    let hidden = storage.get_trail(&TrailConfig {
+        root_id: NodeId(1),
+        mode: TrailMode::Neighborhood,
+        target_id: None,
+        depth: 1,
+        direction: TrailDirection::Outgoing,
+        caller_scope: TrailCallerScope::IncludeTestsAndBenches,
+        edge_filter: vec![EdgeKind::CALL],
+        show_utility_calls: false,
+        node_filter: Vec::new(),
+        hide_trivial_accessors: false,
+        hide_generated: false,
+        entry_reachable_only: false,
+        max_paths: None,
+        edge_weight: Default::default(),
+        max_nodes: 50,
+    })?;
+    assert!(hidden.edges.is_empty());
+
+    let shown = storage.get_trail(&TrailConfig {
+        root_id: NodeId(1),
+        mode: TrailMode::Neighborhood,
+        target_id: None,
+        depth: 1,
+        direction: TrailDirection::Outgoing,
+        caller_scope: TrailCallerScope::IncludeTestsAndBenches,
+        edge_filter: vec![EdgeKind::CALL],
+        show_utility_calls: true,
+        node_filter: Vec::new(),
+        hide_trivial_accessors: false,
+        hide_generated: false,
+        entry_reachable_only: false,
+        max_paths: None,
+        edge_weight: Default::default(),
+        max_nodes: 50,
+    })?;
+    assert_eq!(shown.edges.len(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_reachable_set_counts_reached_nodes_by_kind_and_file() -> Result<(), StorageError> {
+    let mut storage = Storage::new_in_memory()?;
+    let node = |id: i64, kind: NodeKind, name: &str, file: Option<i64>| Node {
+        id: NodeId(id),
+        kind,
+        serialized_name: name.to_string(),
+        file_node_id: file.map(NodeId),
+        ..Default::default()
+    };
+    storage.insert_nodes_batch(&[
+        node(100, NodeKind::FILE, "src/api.rs", None),
+        node(101, NodeKind::FILE, "src/db.rs", None),
+        node(1, NodeKind::FUNCTION, "handle", Some(100)),
+        node(2, NodeKind::FUNCTION, "load", Some(101)),
+        node(3, NodeKind::FUNCTION, "query", Some(101)),
+        node(4, NodeKind::STRUCT, "Row", Some(101)),
+        node(5, NodeKind::FUNCTION, "caller", Some(100)),
+    ])?;
+    let edge = |id: i64, source: i64, target: i64, kind: EdgeKind| Edge {
+        id: EdgeId(id),
+        source: NodeId(source),
+        target: NodeId(target),
+        kind,
+        ..Default::default()
+    };
+    storage.insert_edges_batch(&[
+        edge(1, 1, 2, EdgeKind::CALL),
+        edge(2, 2, 3, EdgeKind::CALL),
+        edge(3, 3, 4, EdgeKind::TYPE_USAGE),
+        edge(4, 3, 1, EdgeKind::CALL),
+        edge(5, 5, 1, EdgeKind::CALL),
+    ])?;
+
+    let reached = storage.reachable_set(NodeId(1), TrailDirection::Outgoing, &[], 100)?;
+    assert_eq!(reached.node_ids, vec![NodeId(2), NodeId(3), NodeId(4)]);
+    assert_eq!(reached.max_depth, 3);
+    assert!(!reached.truncated);
+    assert_eq!(
+        reached.kind_counts,
+        vec![(NodeKind::FUNCTION, 2), (NodeKind::STRUCT, 1)]
+    );
+    assert_eq!(
+        reached.file_counts,
+        vec![ReachableFileCount {
+            file_node_id: NodeId(101),
+            path: "src/db.rs".to_string(),
+            count: 3,
+        }]
+    );
+
+    let calls_only =
+        storage.reachable_set(NodeId(1), TrailDirection::Outgoing, &[EdgeKind::CALL], 100)?;
+    assert_eq!(calls_only.node_ids, vec![NodeId(2), NodeId(3)]);
+
+    let callers = storage.reachable_set(NodeId(1), TrailDirection::Incoming, &[], 100)?;
+    assert_eq!(callers.node_ids.len(), 3);
+    assert!(callers.node_ids.contains(&NodeId(5)));
+
+    let capped = storage.reachable_set(NodeId(1), TrailDirection::Both, &[], 2)?;
+    assert_eq!(capped.node_ids.len(), 2);
+    assert!(capped.truncated);
+    Ok(())
+}
+
+#[test]
+fn test_directory_packages_group_script_files_in_symbol_tree() -> Result<(), StorageError> {
+    let mut storage = Storage::new_in_memory()?;
+    for (id, path, language) in [
+        (100, "/repo/src/ui/button.ts", "typescript"),
+        (101, "/repo/src/ui/forms/input.ts", "typescript"),
+        (102, "/repo/src/main.ts", "typescript"),
+        (103, "/repo/src/lib.rs", "rust"),
+    ] {
+        storage.insert_file(&FileInfo {
+            id,
+            path: PathBuf::from(path),
+            language: language.to_string(),
+            modification_time: 0,
+            indexed: true,
+            complete: true,
+            line_count: 10,
+            file_role: FileRole::Source,
+        })?;
+    }
+    let node = |id: i64, kind: NodeKind, name: &str, file: Option<i64>| Node {
+        id: NodeId(id),
+        kind,
+        serialized_name: name.to_string(),
+        file_node_id: file.map(NodeId),
+        start_line: file.map(|_| 1),
+        ..Default::default()
+    };
+    storage.insert_nodes_batch(&[
+        node(100, NodeKind::FILE, "/repo/src/ui/button.ts", None),
+        node(101, NodeKind::FILE, "/repo/src/ui/forms/input.ts", None),
+        node(102, NodeKind::FILE, "/repo/src/main.ts", None),
+        node(103, NodeKind::FILE, "/repo/src/lib.rs", None),
+        node(1, NodeKind::CLASS, "Button", Some(100)),
+        node(2, NodeKind::FUNCTION, "Input", Some(101)),
+        node(3, NodeKind::FUNCTION, "main", Some(102)),
+        node(4, NodeKind::FUNCTION, "run", Some(103)),
+    ])?;
+
+    assert_eq!(
+        storage.rebuild_directory_packages(Path::new("/repo"), &["src".to_string()])?,
+        2
+    );
+    // Rebuilding replaces rather than duplicates.
+    assert_eq!(
+        storage.rebuild_directory_packages(Path::new("/repo"), &["src".to_string()])?,
+        2
+    );
+
+    let names = |nodes: Vec<Node>| {
+        nodes
+            .into_iter()
+            .map(|node| node.serialized_name)
+            .collect::<Vec<_>>()
+    };
+    let roots = storage.get_root_symbols()?;
+    let ui = roots
+        .iter()
+        .find(|node| node.kind == NodeKind::PACKAGE)
+        .expect("ui package is a root")
+        .clone();
+    assert_eq!(ui.serialized_name, "ui");
+    assert_eq!(names(roots), vec!["main", "run", "ui"]);
+
+    let ui_children = storage.get_children_symbols(ui.id)?;
+    let forms = ui_children
+        .iter()
+        .find(|node| node.kind == NodeKind::PACKAGE)
+        .expect("forms package nested under ui")
+        .clone();
+    assert_eq!(forms.qualified_name.as_deref(), Some("ui/forms"));
+    assert!(ui_children.iter().any(|node| node.id == NodeId(100)));
+    assert_eq!(
+        names(storage.get_children_symbols(forms.id)?),
+        vec!["/repo/src/ui/forms/input.ts"]
+    );
+    assert_eq!(
+        names(storage.get_children_symbols(NodeId(100))?),
+        vec!["Button"]
+    );
+
+    // Packages left without files are dropped on the next rebuild.
+    storage.delete_files_batch(&[101])?;
+    assert_eq!(
+        storage.rebuild_directory_packages(Path::new("/repo"), &["src".to_string()])?,
+        1
+    );
+    assert!(storage.get_node(forms.id)?.is_none());
+    Ok(())
+}
+
+#[test]
+fn test_manifest_packages_own_their_files_and_import_sibling_packages() -> Result<(), StorageError>
+{
+    let mut storage = Storage::new_in_memory()?;
+    for (id, path) in [
+        (100, "/repo/crates/app/src/main.rs"),
+        (101, "/repo/crates/core/src/lib.rs"),
+        (102, "/repo/crates/core/benches/bench.rs"),
+        (103, "/repo/scripts/build.sh"),
+    ] {
+        storage.insert_file(&FileInfo {
+            id,
+            path: PathBuf::from(path),
+            language: "rust".to_string(),
+            modification_time: 0,
+            indexed: true,
+            complete: true,
+            line_count: 10,
+            file_role: FileRole::Source,
+        })?;
+        storage.insert_nodes_batch(&[Node {
+            id: NodeId(id),
+            kind: NodeKind::FILE,
+            serialized_name: path.to_string(),
+            ..Default::default()
+        }])?;
+    }
+    let package = |name: &str, root: &str, dependencies: &[&str]| ManifestPackage {
+        ecosystem: "cargo".to_string(),
+        name: name.to_string(),
+        root: PathBuf::from(root),
+        dependencies: dependencies.iter().map(|name| name.to_string()).collect(),
+    };
+    let packages = [
+        package("workspace", "/repo", &[]),
+        package("app", "/repo/crates/app", &["core", "serde"]),
+        package("core", "/repo/crates/core", &[]),
+    ];
+
+    assert_eq!(storage.rebuild_manifest_packages(&packages)?, 3);
+    assert_eq!(storage.rebuild_manifest_packages(&packages)?, 3);
+
+    let package_id = |name: &str| -> Result<NodeId, StorageError> {
+        Ok(storage
+            .get_nodes()?
+            .into_iter()
+            .find(|node| node.kind == NodeKind::PACKAGE && node.serialized_name == name)
+            .expect("package node")
+            .id)
+    };
+    let (app, core, workspace) = (
+        package_id("app")?,
+        package_id("core")?,
+        package_id("workspace")?,
+    );
+    let edges = storage.get_edges()?;
+    let targets = |source: NodeId, kind: EdgeKind| {
+        let mut targets = edges
+            .iter()
+            .filter(|edge| edge.source == source && edge.kind == kind)
+            .map(|edge| edge.target.0)
+            .collect::<Vec<_>>();
+        targets.sort();
+        targets
+    };
+    assert_eq!(targets(app, EdgeKind::MEMBER), vec![100]);
+    assert_eq!(targets(core, EdgeKind::MEMBER), vec![101, 102]);
+    assert_eq!(targets(workspace, EdgeKind::MEMBER), vec![103]);
+    assert_eq!(targets(app, EdgeKind::IMPORT), vec![core.0]);
+
+    // Packages whose manifest disappeared are dropped on the next rebuild.
+    assert_eq!(storage.rebuild_manifest_packages(&packages[1..])?, 2);
+    assert!(storage.get_node(workspace)?.is_none());
+    Ok(())
+}
+
+#[test]
+fn test_java_types_link_by_qualified_name_and_share_external_supertypes() -> Result<(), StorageError>
+{
+    let mut storage = Storage::new_in_memory()?;
+    let root = "/repo/src/main/java";
+    for (id, path) in [
+        (100, "com/acme/Base.java"),
+        (200, "com/acme/shapes/Circle.java"),
+        (300, "com/acme/shapes/Square.java"),
+    ] {
+        let path = format!("{root}/{path}");
+        storage.insert_file(&FileInfo {
+            id,
+            path: PathBuf::from(&path),
+            language: "java".to_string(),
+            modification_time: 0,
+            indexed: true,
+            complete: true,
+            line_count: 10,
+            file_role: FileRole::Source,
+        })?;
+        storage.insert_nodes_batch(&[Node {
+            id: NodeId(id),
+            kind: NodeKind::FILE,
+            serialized_name: path,
+            ..Default::default()
+        }])?;
+    }
+    let node = |id: i64, kind: NodeKind, name: &str, file: i64| Node {
+        id: NodeId(id),
+        kind,
+        serialized_name: name.to_string(),
+        file_node_id: Some(NodeId(file)),
+        ..Default::default()
+    };
+    storage.insert_nodes_batch(&[
+        node(1001, NodeKind::MODULE, "com.acme", 100),
+        node(1002, NodeKind::CLASS, "Base", 100),
+        node(2001, NodeKind::MODULE, "com.acme.shapes", 200),
+        node(2002, NodeKind::MODULE, "com.acme.Base", 200),
+        node(2003, NodeKind::MODULE, "java.io.Serializable", 200),
+        node(2004, NodeKind::CLASS, "Circle", 200),
+        node(2005, NodeKind::CLASS, "Base", 200),
+        node(2006, NodeKind::INTERFACE, "Serializable", 200),
+        node(2007, NodeKind::INTERFACE, "Comparable<Circle>", 200),
+        // No package declaration: the package comes from the source root.
+        node(3001, NodeKind::CLASS, "Square", 300),
+        node(3002, NodeKind::MODULE, "java.io.Serializable", 300),
+        node(3003, NodeKind::CLASS, "Circle", 300),
+        node(3004, NodeKind::INTERFACE, "Serializable", 300),
+    ])?;
+    let edge = |id: i64, source: i64, target: i64, kind: EdgeKind| Edge {
+        id: EdgeId(id),
+        source: NodeId(source),
+        target: NodeId(target),
+        kind,
+        ..Default::default()
+    };
+    storage.insert_edges_batch(&[
+        edge(1, 2002, 2002, EdgeKind::IMPORT),
+        edge(2, 2003, 2003, EdgeKind::IMPORT),
+        edge(3, 2004, 2005, EdgeKind::INHERITANCE),
+        edge(4, 2004, 2006, EdgeKind::INHERITANCE),
+        edge(5, 2004, 2007, EdgeKind::INHERITANCE),
+        edge(6, 3002, 3002, EdgeKind::IMPORT),
+        edge(7, 3001, 3003, EdgeKind::INHERITANCE),
+        edge(8, 3001, 3004, EdgeKind::INHERITANCE),
+    ])?;
+
+    let links = JavaTypeLinks {
+        project: 3,
+        external: 5,
+    };
+    assert_eq!(
+        storage.link_java_types(&[PathBuf::from(root)], None)?,
+        links
+    );
+    assert_eq!(
+        storage.link_java_types(&[PathBuf::from(root)], None)?,
+        links
+    );
+
+    let externals = storage
+        .get_nodes()?
+        .into_iter()
+        .filter(|node| node.file_node_id.is_none() && node.kind != NodeKind::FILE)
+        .map(|node| (node.serialized_name, (node.kind, node.id)))
+        .collect::<BTreeMap<_, _>>();
+    assert_eq!(externals.len(), 2);
+    let external = |name: &str| externals[name].1;
+    assert_eq!(externals["java.io.Serializable"].0, NodeKind::INTERFACE);
+    let resolved = storage
+        .get_edges()?
+        .into_iter()
+        .map(|edge| (edge.id.0, edge.resolved_target))
+        .collect::<HashMap<_, _>>();
+    assert_eq!(resolved[&1], Some(NodeId(1002)));
+    assert_eq!(resolved[&3], Some(NodeId(1002)));
+    assert_eq!(resolved[&7], Some(NodeId(2004)));
+    for id in [2, 4, 6, 8] {
+        assert_eq!(resolved[&id], Some(external("java.io.Serializable")));
+    }
+    assert_eq!(resolved[&5], Some(external("java.lang.Comparable")));
+    let badges = storage.node_badges(&[external("java.lang.Comparable"), NodeId(1002)])?;
+    assert!(badges[&external("java.lang.Comparable")].is_external);
+    assert!(!badges.contains_key(&NodeId(1002)));
+    Ok(())
+}
+
+#[test]
+fn test_java_nested_types_link_under_their_outer_type_and_relink_only_changed_files()
+-> Result<(), StorageError> {
+    let mut storage = Storage::new_in_memory()?;
+    for (id, path) in [(100, "/repo/a/A.java"), (200, "/repo/b/B.java")] {
+        storage.insert_file(&FileInfo {
+            id,
+            path: PathBuf::from(path),
+            language: "java".to_string(),
+            modification_time: 0,
+            indexed: true,
+            complete: true,
+            line_count: 10,
+            file_role: FileRole::Source,
+        })?;
+        storage.insert_nodes_batch(&[Node {
+            id: NodeId(id),
+            kind: NodeKind::FILE,
+            serialized_name: path.to_string(),
+            ..Default::default()
+        }])?;
+    }
+    let node = |id: i64, kind: NodeKind, name: &str, file: i64| Node {
+        id: NodeId(id),
+        kind,
+        serialized_name: name.to_string(),
+        file_node_id: Some(NodeId(file)),
+        ..Default::default()
+    };
+    let edge = |id: i64, source: i64, target: i64, kind: EdgeKind| Edge {
+        id: EdgeId(id),
+        source: NodeId(source),
+        target: NodeId(target),
+        kind,
+        ..Default::default()
+    };
+    // Two outer classes in one package each declare a nested `Node`.
+    storage.insert_nodes_batch(&[
+        node(1001, NodeKind::MODULE, "acme", 100),
+        node(1002, NodeKind::CLASS, "Tree", 100),
+        node(1003, NodeKind::CLASS, "Node", 100),
+        node(1004, NodeKind::CLASS, "Graph", 100),
+        node(1005, NodeKind::CLASS, "Node", 100),
+        node(2001, NodeKind::MODULE, "acme", 200),
+        node(2002, NodeKind::MODULE, "acme.Graph.Node", 200),
+        node(2003, NodeKind::CLASS, "Leaf", 200),
+        node(2004, NodeKind::CLASS, "Node", 200),
+        node(2005, NodeKind::CLASS, "Widget", 200),
+    ])?;
+    storage.insert_edges_batch(&[
+        edge(1, 1002, 1003, EdgeKind::MEMBER),
+        edge(2, 1004, 1005, EdgeKind::MEMBER),
+        edge(3, 2002, 2002, EdgeKind::IMPORT),
+        edge(4, 2003, 2004, EdgeKind::INHERITANCE),
+        edge(5, 2003, 2005, EdgeKind::INHERITANCE),
+    ])?;
+
+    let roots = [PathBuf::from("/repo")];
+    assert_eq!(
+        storage.link_java_types(&roots, None)?,
+        JavaTypeLinks {
+            project: 2,
+            external: 1,
+        }
+    );
+    let resolved = |storage: &Storage| -> Result<HashMap<i64, Option<NodeId>>, StorageError> {
+        Ok(storage
+            .get_edges()?
+            .into_iter()
+            .map(|edge| (edge.id.0, edge.resolved_target))
+            .collect())
+    };
+    let before = resolved(&storage)?;
+    assert_eq!(before[&3], Some(NodeId(1005)));
+    assert_eq!(before[&4], Some(NodeId(1005)));
+    let widget = before[&5].expect("external widget");
+
+    // Nothing changed: no edge is revisited.
+    let changed = HashSet::from([300]);
+    assert_eq!(
+        storage.link_java_types(&roots, Some(&changed))?,
+        JavaTypeLinks::default()
+    );
+    assert_eq!(resolved(&storage)?, before);
+
+    // A changed file that declares `Widget` takes over the external link;
+    // the links into `Graph.Node` are revisited because its file changed.
+    storage.insert_nodes_batch(&[node(1006, NodeKind::CLASS, "Widget", 100)])?;
+    let changed = HashSet::from([100]);
+    assert_eq!(
+        storage.link_java_types(&roots, Some(&changed))?,
+        JavaTypeLinks {
+            project: 3,
+            external: 0,
+        }
+    );
+    let after = resolved(&storage)?;
+    assert_eq!(after[&5], Some(NodeId(1006)));
+    assert_eq!(after[&3], before[&3]);
+    assert!(storage.get_node(widget)?.is_none());
+    Ok(())
+}
+
+#[test]
+fn test_index_content_digest_diffs_nodes_edges_and_files() -> Result<(), StorageError> {
+    let mut storage = Storage::new_in_memory()?;
+    let node = |id: i64| Node {
+        id: NodeId(id),
+        kind: NodeKind::FUNCTION,
+        serialized_name: format!("f{id}"),
+        ..Default::default()
+    };
+    let edge = |id: i64, source: i64, target: i64| Edge {
+        id: EdgeId(id),
+        source: NodeId(source),
+        target: NodeId(target),
+        kind: EdgeKind::CALL,
+        ..Default::default()
+    };
+    storage.insert_nodes_batch(&[node(1), node(2), node(3)])?;
+    storage.insert_edges_batch(&[edge(1, 1, 2), edge(2, 2, 3)])?;
+    insert_file_row(&storage, 10, "src/kept.ts")?;
+    insert_file_row(&storage, 11, "src/changed.ts")?;
+    insert_file_row(&storage, 12, "src/removed.ts")?;
+    let before = storage.index_content_digest()?;
+
+    storage.conn.execute("DELETE FROM edge WHERE id = 2", [])?;
+    storage.conn.execute("DELETE FROM node WHERE id = 3", [])?;
+    storage.insert_nodes_batch(&[node(4), node(5)])?;
+    storage.insert_edges_batch(&[edge(3, 1, 4), edge(4, 4, 5)])?;
+    storage.insert_file(&FileInfo {
+        id: 11,
+        path: PathBuf::from("src/changed.ts"),
+        language: "typescript".to_string(),
+        modification_time: 2,
+        indexed: true,
+        complete: true,
+        line_count: 1,
+        file_role: FileRole::Source,
+    })?;
+    storage.delete_files_batch(&[12])?;
+    insert_file_row(&storage, 13, "src/added.ts")?;
+    let after = storage.index_content_digest()?;
+
+    assert_eq!(
+        before.delta_to(&after),
+        IndexContentDelta {
+            nodes_added: 2,
+            nodes_removed: 1,
+            edges_added: 2,
+            edges_removed: 1,
+            files_added: 1,
+            files_removed: 1,
+            files_updated: 1,
+        }
+    );
+    assert_eq!(after.delta_to(&after), IndexContentDelta::default());
+    Ok(())
+}
+
+#[test]
+fn test_index_content_digest_for_files_reads_only_the_given_files() -> Result<(), StorageError> {
+    let mut storage = Storage::new_in_memory()?;
+    let node = |id: i64, file: Option<i64>| Node {
+        id: NodeId(id),
+        kind: NodeKind::FUNCTION,
+        serialized_name: format!("f{id}"),
+        file_node_id: file.map(NodeId),
+        ..Default::default()
+    };
+    let edge = |id: i64, source: i64, target: i64, file: i64| Edge {
+        id: EdgeId(id),
+        source: NodeId(source),
+        target: NodeId(target),
+        kind: EdgeKind::CALL,
+        file_node_id: Some(NodeId(file)),
+        ..Default::default()
+    };
+    storage.insert_nodes_batch(&[
+        node(10, Some(10)),
+        node(11, Some(11)),
+        node(1, Some(10)),
+        node(2, Some(11)),
+        node(3, None),
+    ])?;
+    storage.insert_edges_batch(&[edge(1, 1, 3, 10), edge(2, 2, 1, 11)])?;
+    insert_file_row(&storage, 10, "src/kept.ts")?;
+    insert_file_row(&storage, 11, "src/changed.ts")?;
+
+    let scoped = storage.index_content_digest_for_files(&[11, 11, 99])?;
+    assert_eq!(scoped.node_ids, vec![2, 11]);
+    assert_eq!(scoped.edge_ids, vec![2]);
+    assert_eq!(
+        scoped.files.iter().map(|(id, _)| *id).collect::<Vec<_>>(),
+        vec![11]
+    );
+
+    storage.conn.execute("DELETE FROM edge WHERE id = 2", [])?;
+    storage.conn.execute("DELETE FROM node WHERE id = 2", [])?;
+    storage.insert_nodes_batch(&[node(4, Some(11))])?;
+    let rescoped = storage.index_content_digest_for_files(&[11])?;
+    assert_eq!(
+        scoped.delta_to(&rescoped),
+        IndexContentDelta {
+            nodes_added: 1,
+            nodes_removed: 1,
+            edges_removed: 1,
+            ..IndexContentDelta::default()
+        }
+    );
+    Ok(())
+}
+
+#[test]
+fn test_node_preview_counts_neighbors_and_reads_summary() -> Result<(), StorageError> {
+    let mut storage = Storage::new_in_memory()?;
+    let node = |id: i64, kind: NodeKind, name: &str, file: Option<i64>| Node {
+        id: NodeId(id),
+        kind,
+        serialized_name: name.to_string(),
+        file_node_id: file.map(NodeId),
+        start_line: file.map(|_| 3),
+        ..Default::default()
+    };
+    storage.insert_nodes_batch(&[
+        node(100, NodeKind::FILE, "src/store.rs", None),
+        node(1, NodeKind::STRUCT, "Store", Some(100)),
+        node(2, NodeKind::METHOD, "Store::get", Some(100)),
+        node(3, NodeKind::METHOD, "Store::put", Some(100)),
+        node(4, NodeKind::FUNCTION, "main", Some(100)),
+        node(5, NodeKind::FUNCTION, "helper", Some(100)),
+    ])?;
+    let edge = |id: i64, source: i64, target: i64, kind: EdgeKind| Edge {
+        id: EdgeId(id),
+        source: NodeId(source),
+        target: NodeId(target),
+        kind,
+        ..Default::default()
+    };
+    storage.insert_edges_batch(&[
+        edge(1, 1, 2, EdgeKind::MEMBER),
+        edge(2, 1, 3, EdgeKind::MEMBER),
+        edge(3, 4, 2, EdgeKind::CALL),
+        edge(4, 3, 2, EdgeKind::CALL),
+        edge(5, 4, 2, EdgeKind::CALL),
+        edge(6, 2, 5, EdgeKind::CALL),
+    ])?;
+    storage.insert_component_access_batch(&[(NodeId(2), AccessKind::Public)])?;
+    storage.upsert_symbol_summaries_batch(&[SymbolSummaryRecord {
+        node_id: NodeId(2),
+        content_hash: "hash".to_string(),
+        summary: "\nLooks up one key.\nReturns None when missing.".to_string(),
+        model: "test".to_string(),
+        updated_at_epoch_ms: 1,
+    }])?;
+
+    let preview = storage
+        .get_node_preview(NodeId(2))?
+        .expect("preview for stored node");
+    assert_eq!(preview.node.serialized_name, "Store::get");
+    assert_eq!(preview.file_path.as_deref(), Some("src/store.rs"));
+    assert_eq!(preview.summary.as_deref(), Some("Looks up one key."));
+    assert_eq!(preview.access, Some(AccessKind::Public));
+    assert_eq!(preview.caller_count, 2);
+    assert_eq!(preview.callee_count, 1);
+    assert_eq!(preview.member_count, 0);
+
+    let owner = storage
+        .get_node_preview(NodeId(1))?
+        .expect("preview for stored node");
+    assert_eq!(owner.member_count, 2);
+    assert_eq!(owner.summary, None);
+    assert!(storage.get_node_preview(NodeId(99))?.is_none());
+    Ok(())
+}
+
+#[test]
+fn test_trail_hide_generated_prunes_symbols_from_generated_files() -> Result<(), StorageError> {
+    let mut storage = Storage::new_in_memory()?;
+    for (id, path, file_role) in [
+        (100, "src/service.rs", FileRole::Source),
+        (101, "src/client.rs", FileRole::Generated),
+    ] {
+        storage.insert_file(&FileInfo {
+            id,
+            path: PathBuf::from(path),
+            language: "rust".to_string(),
+            modification_time: 0,
+            indexed: true,
+            complete: true,
+            line_count: 10,
+            file_role,
+        })?;
+    }
+    let symbol = |id: i64, name: &str, file: i64| Node {
+        id: NodeId(id),
+        kind: NodeKind::FUNCTION,
+        serialized_name: name.to_string(),
+        file_node_id: Some(NodeId(file)),
+        start_line: Some(1),
+        end_line: Some(8),
+        ..Default::default()
+    };
+    let file = |id: i64, path: &str| Node {
+        id: NodeId(id),
+        kind: NodeKind::FILE,
+        serialized_name: path.to_string(),
+        ..Default::default()
+    };
+    storage.insert_nodes_batch(&[
+        file(100, "src/service.rs"),
+        file(101, "src/client.rs"),
+        symbol(1, "serve", 100),
+        symbol(2, "encode_request", 101),
+        symbol(3, "validate", 100),
+    ])?;
+    storage.insert_edges_batch(&[
+        Edge {
+            id: EdgeId(10),
+            source: NodeId(1),
+            target: NodeId(2),
+            kind: EdgeKind::CALL,
+            ..Default::default()
+        },
+        Edge {
+            id: EdgeId(11),
+            source: NodeId(1),
+            target: NodeId(3),
+            kind: EdgeKind::CALL,
+            ..Default::default()
+        },
+    ])?;
+
+    let config = TrailConfig {
+        root_id: NodeId(1),
+        depth: 1,
+        direction: TrailDirection::Outgoing,
+        show_utility_calls: true,
+        hide_generated: true,
+        entry_reachable_only: false,
+        max_paths: None,
+        ..TrailConfig::default()
+    };
+    let pruned = storage.get_trail(&config)?;
+    let mut ids = pruned
+        .nodes
+        .iter()
+        .map(|node| node.id.0)
+        .collect::<Vec<_>>();
+    ids.sort_unstable();
+    assert_eq!(ids, vec![1, 3]);
+    assert_eq!(pruned.pruned_generated_node_count, 1);
+
+    let shown = storage.get_trail(&TrailConfig {
+        hide_generated: false,
+        entry_reachable_only: false,
+        max_paths: None,
+        ..config
+    })?;
+    assert_eq!(shown.nodes.len(), 3);
+    assert_eq!(shown.pruned_generated_node_count, 0);
+
+    Ok(())
+}
+
+#[test]
+fn test_trail_prunes_test_nodes_and_trivial_accessors() -> Result<(), StorageError> {
+    let mut storage = Storage::new_in_memory()?;
+
+    let file_prod = Node {
+        id: NodeId(100),
+        kind: NodeKind::FILE,
+        serialized_name: "src/user.rs".to_string(),
+        ..Default::default()
+    };
+    let file_spec = Node {
+        id: NodeId(101),
+        kind: NodeKind::FILE,
+        serialized_name: "src/user_spec.rs".to_string(),
+        ..Default::default()
+    };
+    let symbol = |id: i64, name: &str, file: NodeId, lines: (u32, u32)| Node {
+        id: NodeId(id),
+        kind: NodeKind::METHOD,
+        serialized_name: name.to_string(),
+        qualified_name: Some(format!("User::{name}")),
+        file_node_id: Some(file),
+        start_line: Some(lines.0),
+        end_line: Some(lines.1),
+        ..Default::default()
+    };
+    let edge = |id: i64, source: i64, target: i64| Edge {
+        id: EdgeId(id),
+        source: NodeId(source),
+        target: NodeId(target),
+        kind: EdgeKind::USAGE,
+        ..Default::default()
+    };
+
+    storage.insert_nodes_batch(&[
+        file_prod.clone(),
+        file_spec.clone(),
+        symbol(1, "render", file_prod.id, (1, 20)),
+        symbol(2, "get_name", file_prod.id, (22, 24)),
+        symbol(3, "setDisplayName", file_prod.id, (26, 26)),
+        symbol(4, "get_or_load_profile", file_prod.id, (28, 60)),
+        symbol(5, "render_fixture", file_spec.id, (1, 10)),
+        symbol(6, "normalize", file_prod.id, (62, 70)),
+    ])?;
+    storage.insert_edges_batch(&[
+        edge(10, 1, 2),
+        edge(11, 1, 3),
+        edge(12, 1, 4),
+        edge(13, 1, 5),
+        edge(14, 2, 6),
+    ])?;
+
+    let config = TrailConfig {
+        root_id: NodeId(1),
+        depth: 2,
+        direction: TrailDirection::Outgoing,
+        hide_trivial_accessors: true,
+        ..TrailConfig::default()
+    };
+    let pruned = storage.get_trail(&config)?;
+    let mut ids = pruned
+        .nodes
+        .iter()
+        .map(|node| node.id.0)
+        .collect::<Vec<_>>();
+    ids.sort_unstable();
+    assert_eq!(ids, vec![1, 4]);
+    assert_eq!(pruned.pruned_accessor_node_count, 2);
+    assert_eq!(pruned.pruned_test_node_count, 1);
+    assert_eq!(pruned.edges.len(), 1);
+    assert_eq!(pruned.omitted_edge_count, 3);
+
+    let unpruned = storage.get_trail(&TrailConfig {
+        caller_scope: TrailCallerScope::IncludeTestsAndBenches,
+        hide_trivial_accessors: false,
+        ..config
+    })?;
+    assert_eq!(unpruned.nodes.len(), 6);
+    assert_eq!(unpruned.pruned_accessor_node_count, 0);
+    assert_eq!(unpruned.pruned_test_node_count, 0);
+
+    Ok(())
+}
+
+#[test]
+fn test_helper_calls_are_not_suppressed_as_ambiguous() {
+    assert!(!should_ignore_call_resolution(
+        "Self::flush_projection_batch",
+        Some(ResolutionCertainty::Uncertain),
+        Some(0.40)
+    ));
+    assert!(!should_ignore_call_resolution(
+        "WorkspaceIndexer::seed_symbol_table",
+        Some(ResolutionCertainty::Probable),
+        Some(0.70)
+    ));
+}
+
+#[test]
+fn test_safe_enum_conversion() -> Result<(), StorageError> {
+    let mut storage = Storage::new_in_memory()?;
+
+    // Test that we can round-trip all NodeKind variants
+    let node = Node {
+        id: NodeId(1),
+        kind: NodeKind::ENUM_CONSTANT,
+        serialized_name: "test".to_string(),
+        ..Default::default()
+    };
+    storage.insert_nodes_batch(&[node])?;
+
+    let nodes = storage.get_nodes()?;
+    assert_eq!(nodes[0].kind, NodeKind::ENUM_CONSTANT);
+
+    // Test that we can round-trip all EdgeKind variants
+    let edges = vec![Edge {
+        id: codestory_contracts::graph::EdgeId(1),
+        source: NodeId(1),
+        target: NodeId(1),
+        kind: EdgeKind::ANNOTATION_USAGE,
+        ..Default::default()
+    }];
+    storage.insert_edges_batch(&edges)?;
+
+    let edges = storage.get_edges()?;
+    assert_eq!(edges[0].kind, EdgeKind::ANNOTATION_USAGE);
+
+    Ok(())
+}
+
+#[test]
+fn grounding_node_snapshot_preserves_columns_rank_and_member_root_direction()
+-> Result<(), StorageError> {
+    #[derive(Debug, PartialEq)]
+    struct SnapshotRow {
+        node_id: i64,
+        kind: i32,
+        serialized_name: String,
+        qualified_name: Option<String>,
+        canonical_id: Option<String>,
+        file_node_id: Option<i64>,
+        start_line: Option<i64>,
+        start_col: Option<i64>,
+        end_line: Option<i64>,
+        end_col: Option<i64>,
+        display_name: String,
+        file_path: Option<String>,
+        node_rank: i64,
+        sort_start_line: i64,
+        is_root: i64,
+        file_symbol_rank: Option<i64>,
+    }
+
+    let mut storage = Storage::new_in_memory()?;
+    storage.insert_file(&FileInfo {
+        id: 100,
+        path: PathBuf::from("src/lib.rs"),
+        language: "rust".to_string(),
+        modification_time: 0,
+        indexed: true,
+        complete: true,
+        line_count: 20,
+        file_role: FileRole::Source,
+    })?;
+    storage.insert_nodes_batch(&[
+        Node {
+            id: NodeId(100),
+            kind: NodeKind::FILE,
+            serialized_name: "source-node-path.rs".to_string(),
+            ..Default::default()
+        },
+        Node {
+            id: NodeId(200),
+            kind: NodeKind::FILE,
+            serialized_name: "generated/fallback.ts".to_string(),
+            ..Default::default()
+        },
+        Node {
+            id: NodeId(101),
+            kind: NodeKind::STRUCT,
+            serialized_name: "Widget".to_string(),
+            qualified_name: Some("crate::Widget".to_string()),
+            canonical_id: Some("rust:struct:Widget".to_string()),
+            file_node_id: Some(NodeId(100)),
+            start_line: Some(2),
+            start_col: Some(3),
+            end_line: Some(8),
+            end_col: Some(1),
+        },
+        Node {
+            id: NodeId(102),
+            kind: NodeKind::FUNCTION,
+            serialized_name: "run".to_string(),
+            file_node_id: Some(NodeId(100)),
+            start_line: Some(10),
+            start_col: Some(1),
+            end_line: Some(12),
+            end_col: Some(2),
+            ..Default::default()
+        },
+        Node {
+            id: NodeId(103),
+            kind: NodeKind::MODULE,
+            serialized_name: "\"./types\"".to_string(),
+            file_node_id: Some(NodeId(100)),
+            start_line: Some(1),
+            ..Default::default()
+        },
+        Node {
+            id: NodeId(201),
+            kind: NodeKind::CLASS,
+            serialized_name: "Fallback".to_string(),
+            file_node_id: Some(NodeId(200)),
+            ..Default::default()
+        },
+        Node {
+            id: NodeId(202),
+            kind: NodeKind::UNKNOWN,
+            serialized_name: "excluded".to_string(),
+            file_node_id: Some(NodeId(200)),
+            ..Default::default()
+        },
+    ])?;
+    storage.insert_edges_batch(&[Edge {
+        id: EdgeId(1),
+        source: NodeId(101),
+        target: NodeId(102),
+        kind: EdgeKind::MEMBER,
+        file_node_id: Some(NodeId(100)),
+        ..Default::default()
+    }])?;
+
+    storage.refresh_grounding_summary_snapshots()?;
+    let mut stmt = storage.conn.prepare(
+        "SELECT
+            node_id,
+            kind,
+            serialized_name,
+            qualified_name,
+            canonical_id,
+            file_node_id,
+            start_line,
+            start_col,
+            end_line,
+            end_col,
+            display_name,
+            file_path,
+            node_rank,
+            sort_start_line,
+            is_root,
+            file_symbol_rank
+         FROM grounding_node_snapshot
+         ORDER BY node_id",
+    )?;
+    let actual = stmt
+        .query_map([], |row| {
+            Ok(SnapshotRow {
+                node_id: row.get(0)?,
+                kind: row.get(1)?,
+                serialized_name: row.get(2)?,
+                qualified_name: row.get(3)?,
+                canonical_id: row.get(4)?,
+                file_node_id: row.get(5)?,
+                start_line: row.get(6)?,
+                start_col: row.get(7)?,
                 end_line: row.get(8)?,
                 end_col: row.get(9)?,
                 display_name: row.get(10)?,
@@ -8790,309 +11690,1171 @@ fn grounding_node_snapshot_preserves_columns_rank_and_member_root_direction()
                 is_root: row.get(14)?,
                 file_symbol_rank: row.get(15)?,
             })
-        })?
-        .collect::<rusqlite::Result<Vec<_>>>()?;
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    assert_eq!(
+        actual,
+        vec![
+            SnapshotRow {
+                node_id: 101,
+                kind: NodeKind::STRUCT as i32,
+                serialized_name: "Widget".to_string(),
+                qualified_name: Some("crate::Widget".to_string()),
+                canonical_id: Some("rust:struct:Widget".to_string()),
+                file_node_id: Some(100),
+                start_line: Some(2),
+                start_col: Some(3),
+                end_line: Some(8),
+                end_col: Some(1),
+                display_name: "crate::Widget".to_string(),
+                file_path: Some("src/lib.rs".to_string()),
+                node_rank: 0,
+                sort_start_line: 2,
+                is_root: 1,
+                file_symbol_rank: Some(1),
+            },
+            SnapshotRow {
+                node_id: 102,
+                kind: NodeKind::FUNCTION as i32,
+                serialized_name: "run".to_string(),
+                qualified_name: None,
+                canonical_id: None,
+                file_node_id: Some(100),
+                start_line: Some(10),
+                start_col: Some(1),
+                end_line: Some(12),
+                end_col: Some(2),
+                display_name: "run".to_string(),
+                file_path: Some("src/lib.rs".to_string()),
+                node_rank: 1,
+                sort_start_line: 10,
+                is_root: 0,
+                file_symbol_rank: Some(2),
+            },
+            SnapshotRow {
+                node_id: 103,
+                kind: NodeKind::MODULE as i32,
+                serialized_name: "\"./types\"".to_string(),
+                qualified_name: None,
+                canonical_id: None,
+                file_node_id: Some(100),
+                start_line: Some(1),
+                start_col: None,
+                end_line: None,
+                end_col: None,
+                display_name: "\"./types\"".to_string(),
+                file_path: Some("src/lib.rs".to_string()),
+                node_rank: 5,
+                sort_start_line: 1,
+                is_root: 1,
+                file_symbol_rank: Some(3),
+            },
+            SnapshotRow {
+                node_id: 201,
+                kind: NodeKind::CLASS as i32,
+                serialized_name: "Fallback".to_string(),
+                qualified_name: None,
+                canonical_id: None,
+                file_node_id: Some(200),
+                start_line: None,
+                start_col: None,
+                end_line: None,
+                end_col: None,
+                display_name: "Fallback".to_string(),
+                file_path: Some("generated/fallback.ts".to_string()),
+                node_rank: 0,
+                sort_start_line: 2_147_483_647,
+                is_root: 1,
+                file_symbol_rank: Some(1),
+            },
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_grounding_queries_rank_symbols_and_roots() -> Result<(), StorageError> {
+    let mut storage = Storage::new_in_memory()?;
+
+    storage.insert_file(&FileInfo {
+        id: 100,
+        path: PathBuf::from("src/a.rs"),
+        language: "rust".to_string(),
+        modification_time: 0,
+        indexed: true,
+        complete: true,
+        line_count: 10,
+        file_role: FileRole::Source,
+    })?;
+    storage.insert_file(&FileInfo {
+        id: 200,
+        path: PathBuf::from("src/b.rs"),
+        language: "rust".to_string(),
+        modification_time: 0,
+        indexed: true,
+        complete: true,
+        line_count: 10,
+        file_role: FileRole::Source,
+    })?;
+    storage.insert_nodes_batch(&[
+        codestory_contracts::graph::Node {
+            id: NodeId(100),
+            kind: NodeKind::FILE,
+            serialized_name: "src/a.rs".to_string(),
+            ..Default::default()
+        },
+        codestory_contracts::graph::Node {
+            id: NodeId(200),
+            kind: NodeKind::FILE,
+            serialized_name: "src/b.rs".to_string(),
+            ..Default::default()
+        },
+        codestory_contracts::graph::Node {
+            id: NodeId(101),
+            kind: NodeKind::FUNCTION,
+            serialized_name: "zeta".to_string(),
+            file_node_id: Some(NodeId(100)),
+            start_line: Some(8),
+            ..Default::default()
+        },
+        codestory_contracts::graph::Node {
+            id: NodeId(102),
+            kind: NodeKind::STRUCT,
+            serialized_name: "Alpha".to_string(),
+            file_node_id: Some(NodeId(100)),
+            start_line: Some(2),
+            ..Default::default()
+        },
+        codestory_contracts::graph::Node {
+            id: NodeId(201),
+            kind: NodeKind::MODULE,
+            serialized_name: "\"./types\"".to_string(),
+            file_node_id: Some(NodeId(200)),
+            start_line: Some(1),
+            ..Default::default()
+        },
+        codestory_contracts::graph::Node {
+            id: NodeId(202),
+            kind: NodeKind::CLASS,
+            serialized_name: "Widget".to_string(),
+            file_node_id: Some(NodeId(200)),
+            start_line: Some(2),
+            ..Default::default()
+        },
+    ])?;
+
+    let summaries = storage.get_grounding_file_summaries()?;
+    assert_eq!(summaries.len(), 2);
+    assert_eq!(summaries[0].file.id, 100);
+    assert_eq!(summaries[0].symbol_count, 2);
+    assert_eq!(summaries[0].best_node_rank, 0);
+
+    let top = storage.get_grounding_top_symbols_for_files(&[100, 200], 1)?;
+    assert_eq!(top.len(), 2);
+    assert_eq!(top[0].node.id, NodeId(102));
+    assert_eq!(top[1].node.id, NodeId(202));
+
+    let roots = storage.get_grounding_root_symbol_candidates(2, 0)?;
+    assert_eq!(roots.len(), 2);
+    assert_eq!(roots[0].node.id, NodeId(102));
+    assert_eq!(roots[1].node.id, NodeId(202));
+
+    Ok(())
+}
+
+#[test]
+fn test_grounding_member_counts_and_occurrence_lines() -> Result<(), StorageError> {
+    let mut storage = Storage::new_in_memory()?;
+
+    storage.insert_nodes_batch(&[
+        codestory_contracts::graph::Node {
+            id: NodeId(1),
+            kind: NodeKind::STRUCT,
+            serialized_name: "Widget".to_string(),
+            ..Default::default()
+        },
+        codestory_contracts::graph::Node {
+            id: NodeId(2),
+            kind: NodeKind::FIELD,
+            serialized_name: "title".to_string(),
+            ..Default::default()
+        },
+        codestory_contracts::graph::Node {
+            id: NodeId(3),
+            kind: NodeKind::FIELD,
+            serialized_name: "count".to_string(),
+            ..Default::default()
+        },
+        codestory_contracts::graph::Node {
+            id: NodeId(10),
+            kind: NodeKind::FILE,
+            serialized_name: "src/lib.rs".to_string(),
+            ..Default::default()
+        },
+        codestory_contracts::graph::Node {
+            id: NodeId(11),
+            kind: NodeKind::FUNCTION,
+            serialized_name: "render".to_string(),
+            file_node_id: Some(NodeId(10)),
+            start_line: None,
+            ..Default::default()
+        },
+    ])?;
+    storage.insert_edges_batch(&[
+        Edge {
+            id: EdgeId(1),
+            source: NodeId(1),
+            target: NodeId(2),
+            kind: EdgeKind::MEMBER,
+            ..Default::default()
+        },
+        Edge {
+            id: EdgeId(2),
+            source: NodeId(1),
+            target: NodeId(3),
+            kind: EdgeKind::MEMBER,
+            ..Default::default()
+        },
+    ])?;
+    storage.insert_occurrences_batch(&[
+        codestory_contracts::graph::Occurrence {
+            element_id: 11,
+            kind: codestory_contracts::graph::OccurrenceKind::REFERENCE,
+            location: SourceLocation {
+                file_node_id: NodeId(10),
+                start_line: 20,
+                start_col: 1,
+                end_line: 20,
+                end_col: 5,
+            },
+            edge_id: None,
+            notebook_cell: None,
+        },
+        codestory_contracts::graph::Occurrence {
+            element_id: 11,
+            kind: codestory_contracts::graph::OccurrenceKind::REFERENCE,
+            location: SourceLocation {
+                file_node_id: NodeId(10),
+                start_line: 5,
+                start_col: 1,
+                end_line: 5,
+                end_col: 5,
+            },
+            edge_id: None,
+            notebook_cell: None,
+        },
+    ])?;
+
+    let member_counts = storage.get_grounding_member_counts(&[NodeId(1)])?;
+    assert_eq!(member_counts.get(&NodeId(1)), Some(&2));
+
+    let fallback_lines = storage.get_grounding_min_occurrence_lines(&[NodeId(11)])?;
+    assert_eq!(fallback_lines.get(&NodeId(11)), Some(&20));
+
+    Ok(())
+}
+
+#[test]
+fn test_grounding_edge_digests_ignore_ambiguous_resolved_targets() -> Result<(), StorageError> {
+    let mut storage = Storage::new_in_memory()?;
+
+    storage.insert_nodes_batch(&[
+        codestory_contracts::graph::Node {
+            id: NodeId(1),
+            kind: NodeKind::FUNCTION,
+            serialized_name: "caller".to_string(),
+            ..Default::default()
+        },
+        codestory_contracts::graph::Node {
+            id: NodeId(2),
+            kind: NodeKind::UNKNOWN,
+            serialized_name: "len".to_string(),
+            ..Default::default()
+        },
+        codestory_contracts::graph::Node {
+            id: NodeId(3),
+            kind: NodeKind::FUNCTION,
+            serialized_name: "Vec::len".to_string(),
+            ..Default::default()
+        },
+    ])?;
+    storage.insert_edges_batch(&[Edge {
+        id: EdgeId(10),
+        source: NodeId(1),
+        target: NodeId(2),
+        kind: EdgeKind::CALL,
+        resolved_target: Some(NodeId(3)),
+        certainty: Some(ResolutionCertainty::Uncertain),
+        ..Default::default()
+    }])?;
+
+    let counts = storage.get_grounding_edge_digest_counts(&[NodeId(1), NodeId(2), NodeId(3)])?;
+    assert!(counts.iter().any(|entry| {
+        entry.node_id == NodeId(1) && entry.kind == EdgeKind::CALL && entry.count == 1
+    }));
+    assert!(counts.iter().any(|entry| {
+        entry.node_id == NodeId(2) && entry.kind == EdgeKind::CALL && entry.count == 1
+    }));
+    assert!(!counts.iter().any(|entry| entry.node_id == NodeId(3)));
+
+    Ok(())
+}
+
+#[test]
+fn test_index_history_answers_when_nodes_and_edges_appeared() -> Result<(), StorageError> {
+    let node = |id| Node {
+        id: NodeId(id),
+        kind: NodeKind::FUNCTION,
+        serialized_name: format!("n{id}"),
+        ..Default::default()
+    };
+    let edge = |id, source, target, kind| Edge {
+        id: EdgeId(id),
+        source: NodeId(source),
+        target: NodeId(target),
+        kind,
+        ..Default::default()
+    };
+    let generations = [
+        (
+            "run-1",
+            vec![node(1), node(2)],
+            vec![edge(10, 1, 2, EdgeKind::CALL)],
+        ),
+        (
+            "run-2",
+            vec![node(1), node(2), node(3)],
+            vec![
+                edge(10, 1, 2, EdgeKind::CALL),
+                edge(11, 1, 3, EdgeKind::IMPORT),
+            ],
+        ),
+        (
+            "run-3",
+            vec![node(1), node(3)],
+            vec![edge(11, 1, 3, EdgeKind::IMPORT)],
+        ),
+    ];
+    let mut paths = Vec::new();
+    for (index, (run_id, nodes, edges)) in generations.into_iter().enumerate() {
+        let path = unique_temp_db_path(&format!("index-history-{index}"));
+        let previous = paths
+            .last()
+            .cloned()
+            .unwrap_or_else(|| unique_temp_db_path("index-history-missing"));
+        let mut storage = Storage::open(&path)?;
+        storage.copy_index_history_from(&previous)?;
+        storage.insert_nodes_batch(&nodes)?;
+        storage.insert_edges_batch(&edges)?;
+        storage.record_index_history(&previous, run_id, 1_000 * (index as i64 + 1), None)?;
+        paths.push(path);
+    }
+    let storage = Storage::open(paths.last().expect("latest generation"))?;
+
+    assert_eq!(
+        storage
+            .index_history_runs()?
+            .into_iter()
+            .map(|run| (run.run_id, run.recorded_at_epoch_ms))
+            .collect::<Vec<_>>(),
+        vec![
+            ("run-1".to_string(), 1_000),
+            ("run-2".to_string(), 2_000),
+            ("run-3".to_string(), 3_000),
+        ]
+    );
+    let summarize = |events: Vec<IndexHistoryEvent>| {
+        events
+            .into_iter()
+            .map(|event| {
+                let subject = match event.subject {
+                    IndexHistorySubject::Node { id, .. } => format!("node {}", id.0),
+                    IndexHistorySubject::Edge { id, .. } => format!("edge {}", id.0),
+                };
+                (event.run_id, event.added, subject)
+            })
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        summarize(storage.node_history(NodeId(3))?),
+        vec![
+            ("run-2".to_string(), true, "node 3".to_string()),
+            ("run-2".to_string(), true, "edge 11".to_string()),
+        ]
+    );
+    assert_eq!(
+        summarize(storage.node_history(NodeId(2))?),
+        vec![
+            ("run-3".to_string(), false, "node 2".to_string()),
+            ("run-3".to_string(), false, "edge 10".to_string()),
+        ]
+    );
+
+    let at_first = storage.graph_at("run-1")?.expect("first run retained");
+    assert_eq!(at_first.node_ids, vec![NodeId(1), NodeId(2)]);
+    assert_eq!(at_first.edge_ids, vec![EdgeId(10)]);
+    let at_second = storage.graph_at("run-2")?.expect("second run retained");
+    assert_eq!(at_second.node_ids, vec![NodeId(1), NodeId(2), NodeId(3)]);
+    assert_eq!(at_second.edge_ids, vec![EdgeId(10), EdgeId(11)]);
+    assert_eq!(at_second.run.recorded_at_epoch_ms, 2_000);
+    assert!(storage.graph_at("run-unknown")?.is_none());
+
+    drop(storage);
+    for path in paths {
+        let _ = fs::remove_file(path);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_index_history_diffs_only_the_changed_files() -> Result<(), StorageError> {
+    let file = |id| Node {
+        id: NodeId(id),
+        kind: NodeKind::FILE,
+        serialized_name: format!("file{id}.rs"),
+        ..Default::default()
+    };
+    let node = |id, file_id| Node {
+        id: NodeId(id),
+        kind: NodeKind::FUNCTION,
+        serialized_name: format!("n{id}"),
+        file_node_id: Some(NodeId(file_id)),
+        ..Default::default()
+    };
+    let edge = |id, source, target, file_id| Edge {
+        id: EdgeId(id),
+        source: NodeId(source),
+        target: NodeId(target),
+        kind: EdgeKind::CALL,
+        file_node_id: Some(NodeId(file_id)),
+        ..Default::default()
+    };
+    let previous = unique_temp_db_path("index-history-scope-previous");
+    {
+        let mut storage = Storage::open(&previous)?;
+        storage.insert_nodes_batch(&[file(100), file(200), node(1, 100), node(2, 200)])?;
+        storage.insert_edges_batch(&[edge(10, 1, 2, 100), edge(20, 2, 1, 200)])?;
+    }
+    // Both files differ, but the run only rewrote file 100.
+    let mut storage = Storage::new_in_memory()?;
+    storage.insert_nodes_batch(&[file(100), file(200), node(3, 100), node(4, 200)])?;
+    storage.insert_edges_batch(&[edge(11, 3, 4, 100)])?;
+    storage.record_index_history(&previous, "run-scoped", 1_000, Some(&[100]))?;
 
+    let subjects = |node_id| -> Result<Vec<(bool, String)>, StorageError> {
+        Ok(storage
+            .node_history(NodeId(node_id))?
+            .into_iter()
+            .map(|event| {
+                let subject = match event.subject {
+                    IndexHistorySubject::Node { id, .. } => format!("node {}", id.0),
+                    IndexHistorySubject::Edge { id, .. } => format!("edge {}", id.0),
+                };
+                (event.added, subject)
+            })
+            .collect())
+    };
     assert_eq!(
-        actual,
+        subjects(1)?,
         vec![
-            SnapshotRow {
-                node_id: 101,
-                kind: NodeKind::STRUCT as i32,
-                serialized_name: "Widget".to_string(),
-                qualified_name: Some("crate::Widget".to_string()),
-                canonical_id: Some("rust:struct:Widget".to_string()),
-                file_node_id: Some(100),
-                start_line: Some(2),
-                start_col: Some(3),
-                end_line: Some(8),
-                end_col: Some(1),
-                display_name: "crate::Widget".to_string(),
-                file_path: Some("src/lib.rs".to_string()),
-                node_rank: 0,
-                sort_start_line: 2,
-                is_root: 1,
-                file_symbol_rank: Some(1),
-            },
-            SnapshotRow {
-                node_id: 102,
-                kind: NodeKind::FUNCTION as i32,
-                serialized_name: "run".to_string(),
-                qualified_name: None,
-                canonical_id: None,
-                file_node_id: Some(100),
-                start_line: Some(10),
-                start_col: Some(1),
-                end_line: Some(12),
-                end_col: Some(2),
-                display_name: "run".to_string(),
-                file_path: Some("src/lib.rs".to_string()),
-                node_rank: 1,
-                sort_start_line: 10,
-                is_root: 0,
-                file_symbol_rank: Some(2),
-            },
-            SnapshotRow {
-                node_id: 103,
-                kind: NodeKind::MODULE as i32,
-                serialized_name: "\"./types\"".to_string(),
-                qualified_name: None,
-                canonical_id: None,
-                file_node_id: Some(100),
-                start_line: Some(1),
-                start_col: None,
-                end_line: None,
-                end_col: None,
-                display_name: "\"./types\"".to_string(),
-                file_path: Some("src/lib.rs".to_string()),
-                node_rank: 5,
-                sort_start_line: 1,
-                is_root: 1,
-                file_symbol_rank: Some(3),
-            },
-            SnapshotRow {
-                node_id: 201,
-                kind: NodeKind::CLASS as i32,
-                serialized_name: "Fallback".to_string(),
-                qualified_name: None,
-                canonical_id: None,
-                file_node_id: Some(200),
-                start_line: None,
-                start_col: None,
-                end_line: None,
-                end_col: None,
-                display_name: "Fallback".to_string(),
-                file_path: Some("generated/fallback.ts".to_string()),
-                node_rank: 0,
-                sort_start_line: 2_147_483_647,
-                is_root: 1,
-                file_symbol_rank: Some(1),
-            },
+            (false, "node 1".to_string()),
+            (false, "edge 10".to_string())
         ]
     );
+    assert_eq!(
+        subjects(3)?,
+        vec![(true, "node 3".to_string()), (true, "edge 11".to_string())]
+    );
+    // File 200 was not diffed, so only the edges from file 100 show up.
+    assert_eq!(subjects(2)?, vec![(false, "edge 10".to_string())]);
+    assert_eq!(subjects(4)?, vec![(true, "edge 11".to_string())]);
+
+    drop(storage);
+    let _ = fs::remove_file(previous);
     Ok(())
 }
 
 #[test]
-fn test_grounding_queries_rank_symbols_and_roots() -> Result<(), StorageError> {
+fn test_index_history_keeps_a_bounded_number_of_runs() -> Result<(), StorageError> {
     let mut storage = Storage::new_in_memory()?;
+    let missing = unique_temp_db_path("index-history-retention");
+    let total = INDEX_HISTORY_RETAINED_RUNS + 3;
+    for run in 0..total {
+        storage.record_index_history(&missing, &format!("run-{run}"), run as i64, None)?;
+    }
 
+    let runs = storage.index_history_runs()?;
+    assert_eq!(runs.len(), INDEX_HISTORY_RETAINED_RUNS);
+    assert_eq!(runs[0].run_id, "run-3");
+    assert!(storage.graph_at("run-0")?.is_none());
+    assert!(storage.graph_at(&format!("run-{}", total - 1))?.is_some());
+    Ok(())
+}
+
+#[test]
+fn test_match_edges_filters_both_endpoints_by_name_prefix_and_kind() -> Result<(), StorageError> {
+    let mut storage = Storage::new_in_memory()?;
+    let node = |id, kind, name: &str| Node {
+        id: NodeId(id),
+        kind,
+        serialized_name: name.rsplit("::").next().unwrap_or(name).to_string(),
+        qualified_name: Some(name.to_string()),
+        ..Default::default()
+    };
+    storage.insert_nodes_batch(&[
+        node(1, NodeKind::FUNCTION, "app::billing::charge"),
+        node(2, NodeKind::FUNCTION, "app::billing::refund"),
+        node(3, NodeKind::FUNCTION, "app::db::insert"),
+        node(4, NodeKind::STRUCT, "app::db::Row"),
+        node(5, NodeKind::FUNCTION, "app::ui::render"),
+        node(7, NodeKind::FUNCTION, "app::billingx::audit"),
+        node(8, NodeKind::FUNCTION, "app.billing.export"),
+        Node {
+            id: NodeId(6),
+            kind: NodeKind::UNKNOWN,
+            serialized_name: "insert".to_string(),
+            ..Default::default()
+        },
+    ])?;
+    let edge = |id, source, target, kind| Edge {
+        id: EdgeId(id),
+        source: NodeId(source),
+        target: NodeId(target),
+        kind,
+        line: Some(id as u32),
+        ..Default::default()
+    };
+    storage.insert_edges_batch(&[
+        edge(10, 1, 5, EdgeKind::CALL),
+        edge(11, 1, 3, EdgeKind::CALL),
+        edge(12, 1, 3, EdgeKind::CALL),
+        edge(13, 2, 4, EdgeKind::TYPE_USAGE),
+        Edge {
+            resolved_target: Some(NodeId(3)),
+            ..edge(14, 2, 6, EdgeKind::CALL)
+        },
+        edge(15, 5, 3, EdgeKind::CALL),
+        edge(16, 7, 3, EdgeKind::CALL),
+        edge(17, 8, 3, EdgeKind::CALL),
+    ])?;
+
+    let billing_calls_into_db = GraphMatchFilter {
+        from_prefix: Some("app::billing".to_string()),
+        from_kind: Some(NodeKind::FUNCTION),
+        edge_kind: Some(EdgeKind::CALL),
+        to_prefix: Some("app::db".to_string()),
+        to_kind: None,
+    };
+    let found = storage.match_edges(&billing_calls_into_db, 10)?;
+    assert!(!found.truncated);
+    let matches = found.matches;
+    assert_eq!(
+        matches
+            .iter()
+            .map(|found| (found.source.0, found.target.0, found.edge_line))
+            .collect::<Vec<_>>(),
+        vec![(1, 3, Some(11)), (2, 3, Some(14))],
+        "`app::billing` must not match `app::billingx`"
+    );
+    assert_eq!(matches[0].source_name, "app::billing::charge");
+    assert_eq!(matches[0].target_name, "app::db::insert");
+
+    let dotted = GraphMatchFilter {
+        from_prefix: Some("app.billing.".to_string()),
+        ..GraphMatchFilter::default()
+    };
+    assert_eq!(
+        storage
+            .match_edges(&dotted, 10)?
+            .matches
+            .iter()
+            .map(|found| found.source.0)
+            .collect::<Vec<_>>(),
+        vec![8]
+    );
+    let partial_segment = GraphMatchFilter {
+        from_prefix: Some("app::bill".to_string()),
+        ..GraphMatchFilter::default()
+    };
+    assert!(
+        storage
+            .match_edges(&partial_segment, 10)?
+            .matches
+            .is_empty()
+    );
+
+    let structs_only = GraphMatchFilter {
+        to_kind: Some(NodeKind::STRUCT),
+        ..GraphMatchFilter::default()
+    };
+    let matches = storage.match_edges(&structs_only, 10)?.matches;
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].edge_kind, EdgeKind::TYPE_USAGE);
+
+    let capped = storage.match_edges(&GraphMatchFilter::default(), 2)?;
+    assert!(capped.truncated);
+    assert_eq!(
+        capped
+            .matches
+            .iter()
+            .map(|found| found.source.0)
+            .collect::<Vec<_>>(),
+        vec![1, 2]
+    );
+    assert!(
+        !storage
+            .match_edges(&GraphMatchFilter::default(), 6)?
+            .truncated
+    );
+    Ok(())
+}
+
+#[test]
+fn test_workspace_symbols_groups_matches_by_kind_with_counts() -> Result<(), StorageError> {
+    let mut storage = Storage::new_in_memory()?;
     storage.insert_file(&FileInfo {
-        id: 100,
-        path: PathBuf::from("src/a.rs"),
-        language: "rust".to_string(),
-        modification_time: 0,
-        indexed: true,
-        complete: true,
-        line_count: 10,
-        file_role: FileRole::Source,
-    })?;
-    storage.insert_file(&FileInfo {
-        id: 200,
-        path: PathBuf::from("src/b.rs"),
+        id: 1,
+        path: PathBuf::from("src/billing.rs"),
         language: "rust".to_string(),
-        modification_time: 0,
+        modification_time: 1,
         indexed: true,
         complete: true,
-        line_count: 10,
+        line_count: 40,
         file_role: FileRole::Source,
     })?;
+    let node = |id, kind, name: &str| Node {
+        id: NodeId(id),
+        kind,
+        serialized_name: name.rsplit("::").next().unwrap_or(name).to_string(),
+        qualified_name: Some(name.to_string()),
+        file_node_id: Some(NodeId(1)),
+        start_line: Some(id as u32),
+        ..Default::default()
+    };
     storage.insert_nodes_batch(&[
-        codestory_contracts::graph::Node {
-            id: NodeId(100),
-            kind: NodeKind::FILE,
-            serialized_name: "src/a.rs".to_string(),
-            ..Default::default()
-        },
-        codestory_contracts::graph::Node {
-            id: NodeId(200),
-            kind: NodeKind::FILE,
-            serialized_name: "src/b.rs".to_string(),
-            ..Default::default()
-        },
-        codestory_contracts::graph::Node {
-            id: NodeId(101),
-            kind: NodeKind::FUNCTION,
-            serialized_name: "zeta".to_string(),
-            file_node_id: Some(NodeId(100)),
-            start_line: Some(8),
-            ..Default::default()
+        node(1, NodeKind::FILE, "src/billing.rs"),
+        node(2, NodeKind::FUNCTION, "billing::charge"),
+        node(3, NodeKind::FUNCTION, "billing::refund"),
+        node(4, NodeKind::FUNCTION, "billing::charge_later"),
+        node(5, NodeKind::STRUCT, "billing::Charge"),
+        node(6, NodeKind::STRUCT, "billing::Invoice"),
+        node(7, NodeKind::FUNCTION, "billing::charge%fee"),
+    ])?;
+    let summary = |groups: &[WorkspaceSymbolGroup]| {
+        groups
+            .iter()
+            .map(|group| {
+                let ids = group.symbols.iter().map(|symbol| symbol.id.0).collect();
+                (group.kind, group.count, ids)
+            })
+            .collect::<Vec<(NodeKind, u32, Vec<i64>)>>()
+    };
+
+    let charges = storage.workspace_symbols(&WorkspaceSymbolFilter {
+        name_contains: Some("CHARGE".to_string()),
+        kinds: Vec::new(),
+        limit_per_kind: 2,
+    })?;
+    assert_eq!(
+        summary(&charges),
+        vec![
+            (NodeKind::STRUCT, 1, vec![5]),
+            (NodeKind::FUNCTION, 3, vec![2, 7]),
+        ]
+    );
+    assert_eq!(charges[0].symbols[0].name, "billing::Charge");
+    assert_eq!(
+        charges[0].symbols[0].file_path.as_deref(),
+        Some("src/billing.rs")
+    );
+    assert_eq!(charges[0].symbols[0].line, Some(5));
+
+    let literal_percent = storage.workspace_symbols(&WorkspaceSymbolFilter {
+        name_contains: Some("charge%".to_string()),
+        kinds: vec![NodeKind::FUNCTION],
+        limit_per_kind: 10,
+    })?;
+    assert_eq!(
+        summary(&literal_percent),
+        vec![(NodeKind::FUNCTION, 1, vec![7])]
+    );
+
+    let files = storage.workspace_symbols(&WorkspaceSymbolFilter {
+        kinds: vec![NodeKind::FILE],
+        limit_per_kind: 10,
+        ..WorkspaceSymbolFilter::default()
+    })?;
+    assert_eq!(summary(&files), vec![(NodeKind::FILE, 1, vec![1])]);
+    Ok(())
+}
+
+#[test]
+fn test_refresh_node_centrality_ranks_widely_called_nodes_first() -> Result<(), StorageError> {
+    let mut storage = Storage::new_in_memory()?;
+    storage.insert_nodes_batch(
+        &(1..=6)
+            .map(|id| Node {
+                id: NodeId(id),
+                kind: NodeKind::FUNCTION,
+                serialized_name: format!("f{id}"),
+                ..Default::default()
+            })
+            .collect::<Vec<_>>(),
+    )?;
+    let edge = |id, source, target, kind| Edge {
+        id: EdgeId(id),
+        source: NodeId(source),
+        target: NodeId(target),
+        kind,
+        ..Default::default()
+    };
+    storage.insert_edges_batch(&[
+        edge(10, 1, 4, EdgeKind::CALL),
+        edge(11, 2, 4, EdgeKind::CALL),
+        edge(12, 3, 4, EdgeKind::USAGE),
+        edge(13, 4, 5, EdgeKind::CALL),
+        edge(14, 1, 5, EdgeKind::MEMBER),
+        Edge {
+            resolved_target: Some(NodeId(4)),
+            ..edge(15, 5, 6, EdgeKind::CALL)
         },
-        codestory_contracts::graph::Node {
-            id: NodeId(102),
-            kind: NodeKind::STRUCT,
-            serialized_name: "Alpha".to_string(),
-            file_node_id: Some(NodeId(100)),
-            start_line: Some(2),
+    ])?;
+
+    assert!(!storage.has_node_centrality()?);
+    let stats = storage.refresh_node_centrality(CENTRALITY_DEFAULT_ITERATIONS)?;
+    assert!(storage.has_node_centrality()?);
+    assert_eq!(stats.node_count, 5);
+    assert_eq!(stats.edge_count, 5);
+
+    let ranked = storage.most_central_nodes(10)?;
+    assert_eq!(ranked.len(), 5);
+    assert_eq!(ranked[0].0, NodeId(4));
+    assert_eq!(ranked[1].0, NodeId(5));
+    let mean = ranked.iter().map(|(_, score)| score).sum::<f32>() / ranked.len() as f32;
+    assert!((mean - 1.0).abs() < 1e-3, "mean centrality {mean}");
+
+    let scores = storage.node_centrality(&[NodeId(1), NodeId(4), NodeId(6)])?;
+    assert!(scores[&NodeId(4)] > scores[&NodeId(1)]);
+    assert!(!scores.contains_key(&NodeId(6)));
+
+    storage.refresh_node_centrality(0)?;
+    let uniform = storage.most_central_nodes(1)?;
+    assert!((uniform[0].1 - 1.0).abs() < 1e-6);
+    Ok(())
+}
+
+#[test]
+fn test_code_markers_replace_filter_and_badge_nodes() -> Result<(), StorageError> {
+    let mut storage = Storage::new_in_memory()?;
+    storage.insert_nodes_batch(&[
+        Node {
+            id: NodeId(1),
+            kind: NodeKind::FILE,
+            serialized_name: "src/app.ts".to_string(),
             ..Default::default()
         },
-        codestory_contracts::graph::Node {
-            id: NodeId(201),
-            kind: NodeKind::MODULE,
-            serialized_name: "\"./types\"".to_string(),
-            file_node_id: Some(NodeId(200)),
-            start_line: Some(1),
+        Node {
+            id: NodeId(2),
+            kind: NodeKind::FUNCTION,
+            serialized_name: "load".to_string(),
+            file_node_id: Some(NodeId(1)),
+            start_line: Some(10),
+            end_line: Some(20),
             ..Default::default()
         },
-        codestory_contracts::graph::Node {
-            id: NodeId(202),
+        Node {
+            id: NodeId(3),
             kind: NodeKind::CLASS,
-            serialized_name: "Widget".to_string(),
-            file_node_id: Some(NodeId(200)),
-            start_line: Some(2),
+            serialized_name: "App".to_string(),
+            file_node_id: Some(NodeId(1)),
+            start_line: Some(1),
+            end_line: Some(30),
             ..Default::default()
         },
     ])?;
+    insert_file_row(&storage, 1, "src/app.ts")?;
+    insert_file_row(&storage, 5, "src/util.ts")?;
+    let marker = |line, tag: &str, text: &str| CodeMarker {
+        line,
+        column: 4,
+        tag: tag.to_string(),
+        text: text.to_string(),
+    };
+    assert_eq!(storage.code_marker_sources_pending()?.len(), 2);
 
-    let summaries = storage.get_grounding_file_summaries()?;
-    assert_eq!(summaries.len(), 2);
-    assert_eq!(summaries[0].file.id, 100);
-    assert_eq!(summaries[0].symbol_count, 2);
-    assert_eq!(summaries[0].best_node_rank, 0);
+    let written = storage.replace_code_markers(&[
+        CodeMarkerScan {
+            file_id: 1,
+            modification_time: Some(1),
+            content_hash: None,
+            markers: vec![
+                marker(3, "HACK", "shim for old browsers"),
+                marker(12, "TODO", "retry on timeout"),
+            ],
+        },
+        CodeMarkerScan {
+            file_id: 5,
+            modification_time: Some(1),
+            content_hash: None,
+            markers: vec![marker(7, "TODO", "drop once callers migrate")],
+        },
+    ])?;
+    assert_eq!(written, 3);
+    assert!(storage.code_marker_sources_pending()?.is_empty());
 
-    let top = storage.get_grounding_top_symbols_for_files(&[100, 200], 1)?;
-    assert_eq!(top.len(), 2);
-    assert_eq!(top[0].node.id, NodeId(102));
-    assert_eq!(top[1].node.id, NodeId(202));
+    let todos = storage.code_markers(
+        &CodeMarkerFilter {
+            tag: Some("todo".to_string()),
+            ..CodeMarkerFilter::default()
+        },
+        10,
+    )?;
+    assert_eq!(
+        todos
+            .iter()
+            .map(|record| (record.file_id, record.marker.line))
+            .collect::<Vec<_>>(),
+        vec![(1, 12), (5, 7)]
+    );
+    let in_util = storage.code_markers(
+        &CodeMarkerFilter {
+            path_contains: Some("util".to_string()),
+            ..CodeMarkerFilter::default()
+        },
+        10,
+    )?;
+    assert_eq!(in_util.len(), 1);
+    assert_eq!(in_util[0].marker.text, "drop once callers migrate");
 
-    let roots = storage.get_grounding_root_symbol_candidates(2, 0)?;
-    assert_eq!(roots.len(), 2);
-    assert_eq!(roots[0].node.id, NodeId(102));
-    assert_eq!(roots[1].node.id, NodeId(202));
+    let badges = storage.node_badges(&[NodeId(1), NodeId(2), NodeId(3)])?;
+    assert_eq!(badges[&NodeId(1)].marker_count, 2);
+    assert_eq!(badges[&NodeId(2)].marker_count, 1);
+    assert!(!badges.contains_key(&NodeId(3)));
 
+    storage.conn.execute("DELETE FROM file WHERE id = 5", [])?;
+    storage.replace_code_markers(&[])?;
+    assert_eq!(
+        storage
+            .code_markers(&CodeMarkerFilter::default(), 10)?
+            .len(),
+        2
+    );
     Ok(())
 }
 
 #[test]
-fn test_grounding_member_counts_and_occurrence_lines() -> Result<(), StorageError> {
+fn test_string_literals_search_and_resolve_enclosing_symbol() -> Result<(), StorageError> {
     let mut storage = Storage::new_in_memory()?;
-
     storage.insert_nodes_batch(&[
-        codestory_contracts::graph::Node {
+        Node {
             id: NodeId(1),
-            kind: NodeKind::STRUCT,
-            serialized_name: "Widget".to_string(),
+            kind: NodeKind::FILE,
+            serialized_name: "src/net.rs".to_string(),
             ..Default::default()
         },
-        codestory_contracts::graph::Node {
+        Node {
             id: NodeId(2),
-            kind: NodeKind::FIELD,
-            serialized_name: "title".to_string(),
+            kind: NodeKind::MODULE,
+            serialized_name: "net".to_string(),
+            file_node_id: Some(NodeId(1)),
+            start_line: Some(1),
+            end_line: Some(40),
             ..Default::default()
         },
-        codestory_contracts::graph::Node {
+        Node {
             id: NodeId(3),
-            kind: NodeKind::FIELD,
-            serialized_name: "count".to_string(),
-            ..Default::default()
-        },
-        codestory_contracts::graph::Node {
-            id: NodeId(10),
-            kind: NodeKind::FILE,
-            serialized_name: "src/lib.rs".to_string(),
-            ..Default::default()
-        },
-        codestory_contracts::graph::Node {
-            id: NodeId(11),
             kind: NodeKind::FUNCTION,
-            serialized_name: "render".to_string(),
-            file_node_id: Some(NodeId(10)),
-            start_line: None,
-            ..Default::default()
-        },
-    ])?;
-    storage.insert_edges_batch(&[
-        Edge {
-            id: EdgeId(1),
-            source: NodeId(1),
-            target: NodeId(2),
-            kind: EdgeKind::MEMBER,
-            ..Default::default()
-        },
-        Edge {
-            id: EdgeId(2),
-            source: NodeId(1),
-            target: NodeId(3),
-            kind: EdgeKind::MEMBER,
+            serialized_name: "connect".to_string(),
+            file_node_id: Some(NodeId(1)),
+            start_line: Some(10),
+            end_line: Some(20),
             ..Default::default()
         },
     ])?;
-    storage.insert_occurrences_batch(&[
-        codestory_contracts::graph::Occurrence {
-            element_id: 11,
-            kind: codestory_contracts::graph::OccurrenceKind::REFERENCE,
-            location: SourceLocation {
-                file_node_id: NodeId(10),
-                start_line: 20,
-                start_col: 1,
-                end_line: 20,
-                end_col: 5,
-            },
+    insert_file_row(&storage, 1, "src/net.rs")?;
+    insert_file_row(&storage, 5, "src/limits.rs")?;
+    let literal = |line, name: Option<&str>, value: &str| StringLiteral {
+        line,
+        column: 5,
+        name: name.map(str::to_string),
+        value: value.to_string(),
+    };
+    assert_eq!(storage.string_literal_sources_pending()?.len(), 2);
+
+    let written = storage.replace_string_literals(&[
+        StringLiteralScan {
+            file_id: 1,
+            modification_time: Some(1),
+            content_hash: None,
+            literals: vec![
+                literal(3, Some("CONNECT_TIMEOUT_MS"), "4096"),
+                literal(12, None, "connection refused by peer"),
+            ],
         },
-        codestory_contracts::graph::Occurrence {
-            element_id: 11,
-            kind: codestory_contracts::graph::OccurrenceKind::REFERENCE,
-            location: SourceLocation {
-                file_node_id: NodeId(10),
-                start_line: 5,
-                start_col: 1,
-                end_line: 5,
-                end_col: 5,
-            },
+        StringLiteralScan {
+            file_id: 5,
+            modification_time: Some(1),
+            content_hash: None,
+            literals: vec![literal(2, None, "peer limit reached")],
         },
     ])?;
+    assert_eq!(written, 3);
+    assert!(storage.string_literal_sources_pending()?.is_empty());
 
-    let member_counts = storage.get_grounding_member_counts(&[NodeId(1)])?;
-    assert_eq!(member_counts.get(&NodeId(1)), Some(&2));
+    let refused = storage.search_string_literals("Connection refused", 10)?;
+    assert_eq!(refused.len(), 1);
+    assert_eq!(refused[0].literal.line, 12);
+    assert_eq!(refused[0].symbol_id, Some(NodeId(3)));
 
-    let fallback_lines = storage.get_grounding_min_occurrence_lines(&[NodeId(11)])?;
-    assert_eq!(fallback_lines.get(&NodeId(11)), Some(&20));
+    let by_value = storage.search_string_literals("4096", 10)?;
+    assert_eq!(by_value.len(), 1);
+    assert_eq!(
+        by_value[0].literal.name.as_deref(),
+        Some("CONNECT_TIMEOUT_MS")
+    );
+    assert_eq!(by_value[0].symbol_id, Some(NodeId(2)));
+    assert_eq!(
+        storage
+            .search_string_literals("connect_timeout_ms", 10)?
+            .len(),
+        1
+    );
+    assert_eq!(storage.search_string_literals("peer", 10)?.len(), 2);
+    assert!(storage.search_string_literals("\"*(", 10)?.is_empty());
 
+    storage.replace_string_literals(&[StringLiteralScan {
+        file_id: 1,
+        modification_time: Some(2),
+        content_hash: None,
+        literals: Vec::new(),
+    }])?;
+    assert!(storage.search_string_literals("refused", 10)?.is_empty());
+    let peer = storage.search_string_literals("peer", 10)?;
+    assert_eq!(peer.len(), 1);
+    assert_eq!(peer[0].symbol_id, None);
+
+    storage.clear_string_literals()?;
+    assert!(storage.search_string_literals("peer", 10)?.is_empty());
+    assert_eq!(storage.string_literal_sources_pending()?.len(), 2);
     Ok(())
 }
 
 #[test]
-fn test_grounding_edge_digests_ignore_ambiguous_resolved_targets() -> Result<(), StorageError> {
-    let mut storage = Storage::new_in_memory()?;
+fn test_trail_filter_presets_round_trip_and_copy_forward() -> Result<(), StorageError> {
+    let live_path = unique_temp_db_path("trail-presets-live");
+    let live = Storage::open(&live_path)?;
+    let architecture = TrailFilterPreset {
+        name: "Only architecture edges".to_string(),
+        edge_kinds: vec![EdgeKind::INHERITANCE, EdgeKind::IMPORT],
+        node_kinds: vec![NodeKind::CLASS, NodeKind::MODULE],
+    };
+    let calls = TrailFilterPreset {
+        name: "Calls+Overrides".to_string(),
+        edge_kinds: vec![EdgeKind::CALL, EdgeKind::OVERRIDE],
+        node_kinds: Vec::new(),
+    };
+    live.save_trail_filter_preset(&architecture)?;
+    live.save_trail_filter_preset(&calls)?;
+    assert_eq!(
+        live.trail_filter_presets()?,
+        vec![calls.clone(), architecture.clone()]
+    );
+
+    let narrowed = TrailFilterPreset {
+        edge_kinds: vec![EdgeKind::CALL],
+        ..calls.clone()
+    };
+    live.save_trail_filter_preset(&narrowed)?;
+    assert_eq!(
+        live.trail_filter_preset("Calls+Overrides")?,
+        Some(narrowed.clone())
+    );
+    assert_eq!(live.trail_filter_preset("missing")?, None);
+    drop(live);
+
+    let mut rebuilt = Storage::open(unique_temp_db_path("trail-presets-rebuilt"))?;
+    assert_eq!(rebuilt.copy_trail_filter_presets_from(&live_path)?, 2);
+    assert_eq!(
+        rebuilt.trail_filter_presets()?,
+        vec![narrowed, architecture]
+    );
+    assert!(rebuilt.delete_trail_filter_preset("Calls+Overrides")?);
+    assert!(!rebuilt.delete_trail_filter_preset("Calls+Overrides")?);
+    assert_eq!(rebuilt.trail_filter_presets()?.len(), 1);
+    Ok(())
+}
 
+#[test]
+fn test_node_definitions_list_each_defining_location() -> Result<(), StorageError> {
+    let mut storage = Storage::new_in_memory()?;
+    let file = |id, path: &str| Node {
+        id: NodeId(id),
+        kind: NodeKind::FILE,
+        serialized_name: path.to_string(),
+        ..Default::default()
+    };
     storage.insert_nodes_batch(&[
-        codestory_contracts::graph::Node {
-            id: NodeId(1),
-            kind: NodeKind::FUNCTION,
-            serialized_name: "caller".to_string(),
+        file(1, "src/Widget.Layout.cs"),
+        file(2, "src/Widget.cs"),
+        Node {
+            id: NodeId(3),
+            kind: NodeKind::CLASS,
+            serialized_name: "Widget".to_string(),
+            file_node_id: Some(NodeId(2)),
             ..Default::default()
         },
-        codestory_contracts::graph::Node {
-            id: NodeId(2),
-            kind: NodeKind::UNKNOWN,
-            serialized_name: "len".to_string(),
-            ..Default::default()
+    ])?;
+    let occurrence = |kind, file_id, line| Occurrence {
+        element_id: 3,
+        kind,
+        location: SourceLocation {
+            file_node_id: NodeId(file_id),
+            start_line: line,
+            start_col: 1,
+            end_line: line + 10,
+            end_col: 2,
         },
-        codestory_contracts::graph::Node {
-            id: NodeId(3),
+        edge_id: None,
+        notebook_cell: None,
+    };
+    storage.insert_occurrences_batch(&[
+        occurrence(OccurrenceKind::DEFINITION, 2, 5),
+        occurrence(OccurrenceKind::DEFINITION, 1, 3),
+        occurrence(OccurrenceKind::DEFINITION, 2, 5),
+        occurrence(OccurrenceKind::REFERENCE, 2, 40),
+    ])?;
+
+    let definitions = storage.get_node_definitions(NodeId(3))?;
+    assert_eq!(
+        definitions
+            .iter()
+            .map(|definition| (definition.file_path.as_str(), definition.start_line))
+            .collect::<Vec<_>>(),
+        vec![("src/Widget.Layout.cs", 3), ("src/Widget.cs", 5)]
+    );
+    assert_eq!(definitions[0].file_node_id, NodeId(1));
+    assert_eq!(definitions[0].end_line, 13);
+    assert!(storage.get_node_definitions(NodeId(99))?.is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_node_cache_shares_repeated_names() -> Result<(), StorageError> {
+    let mut storage = Storage::new_in_memory()?;
+    let nodes = ["alpha", "beta", "gamma"]
+        .into_iter()
+        .zip(1..)
+        .map(|(module, id)| Node {
+            id: NodeId(id),
             kind: NodeKind::FUNCTION,
-            serialized_name: "Vec::len".to_string(),
+            serialized_name: "new".to_string(),
+            qualified_name: Some(format!("{module}::new")),
+            canonical_id: Some(format!("{module}::new")),
             ..Default::default()
-        },
-    ])?;
-    storage.insert_edges_batch(&[Edge {
-        id: EdgeId(10),
-        source: NodeId(1),
-        target: NodeId(2),
-        kind: EdgeKind::CALL,
-        resolved_target: Some(NodeId(3)),
-        certainty: Some(ResolutionCertainty::Uncertain),
-        ..Default::default()
-    }])?;
+        })
+        .collect::<Vec<_>>();
+    storage.insert_nodes_batch(&nodes)?;
+    let stats = storage.node_cache_stats();
+    assert_eq!(stats.entries, 3);
+    assert_eq!(stats.interned_strings, 4);
+    assert_eq!(
+        storage
+            .get_node(NodeId(2))?
+            .and_then(|node| node.canonical_id),
+        Some("beta::new".to_string())
+    );
+
+    storage.set_node_cache_limits(NodeCacheLimits {
+        max_entries: 1,
+        ..NodeCacheLimits::default()
+    });
+    let stats = storage.node_cache_stats();
+    assert_eq!((stats.entries, stats.interned_strings), (1, 2));
+    storage.set_node_cache_limits(NodeCacheLimits {
+        max_entries: 0,
+        ..NodeCacheLimits::default()
+    });
+    let stats = storage.node_cache_stats();
+    assert_eq!(
+        (stats.entries, stats.interned_strings, stats.bytes),
+        (0, 0, 0)
+    );
+    Ok(())
+}
 
-    let counts = storage.get_grounding_edge_digest_counts(&[NodeId(1), NodeId(2), NodeId(3)])?;
-    assert!(counts.iter().any(|entry| {
-        entry.node_id == NodeId(1) && entry.kind == EdgeKind::CALL && entry.count == 1
-    }));
-    assert!(counts.iter().any(|entry| {
-        entry.node_id == NodeId(2) && entry.kind == EdgeKind::CALL && entry.count == 1
-    }));
-    assert!(!counts.iter().any(|entry| entry.node_id == NodeId(3)));
+#[test]
+fn test_node_cache_interning_cuts_retained_bytes_on_a_large_graph() -> Result<(), StorageError> {
+    // Common method names across many modules, with canonical ids equal to
+    // qualified names as the indexer writes them.
+    const NAMES: [&str; 8] = [
+        "new", "fmt", "from", "clone", "default", "eq", "hash", "drop",
+    ];
+    let mut storage = Storage::new_in_memory()?;
+    let nodes = (0..60_000usize)
+        .map(|index| {
+            let name = NAMES[index % NAMES.len()];
+            let qualified = format!("crate::module_{}::{name}", index / NAMES.len());
+            Node {
+                id: NodeId(index as i64 + 1),
+                kind: NodeKind::FUNCTION,
+                serialized_name: name.to_string(),
+                qualified_name: Some(qualified.clone()),
+                canonical_id: Some(qualified),
+                ..Default::default()
+            }
+        })
+        .collect::<Vec<_>>();
+    let owned_string_bytes = nodes
+        .iter()
+        .map(|node| {
+            node.serialized_name.len()
+                + node.qualified_name.as_ref().map_or(0, String::len)
+                + node.canonical_id.as_ref().map_or(0, String::len)
+        })
+        .sum::<usize>();
+    storage.insert_nodes_batch(&nodes)?;
+
+    let stats = storage.node_cache_stats();
+    assert_eq!(stats.entries, nodes.len());
+    let entry_bytes = stats.entries * node_cache::CACHED_NODE_BYTES;
+    let retained_string_bytes = stats.bytes - entry_bytes;
+    assert!(
+        retained_string_bytes * 2 < owned_string_bytes,
+        "interned names should retain under half the bytes of owned copies: \
+         {retained_string_bytes} vs {owned_string_bytes}"
+    );
+    assert!(
+        stats.bytes * 10 < (entry_bytes + owned_string_bytes) * 9,
+        "the whole cache should retain at least a tenth less than owned copies: \
+         {} vs {}",
+        stats.bytes,
+        entry_bytes + owned_string_bytes
+    );
+    Ok(())
+}
 
+#[test]
+fn test_project_settings_round_trip_and_copy_forward() -> Result<(), StorageError> {
+    let live_path = unique_temp_db_path("project-settings-live");
+    let live = Storage::open(&live_path)?;
+    assert_eq!(live.project_setting("trail_defaults")?, None);
+    live.set_project_setting("trail_defaults", r#"{"depth":3}"#)?;
+    live.set_project_setting("trail_defaults", r#"{"depth":4}"#)?;
+    assert_eq!(
+        live.project_setting("trail_defaults")?.as_deref(),
+        Some(r#"{"depth":4}"#)
+    );
+    drop(live);
+
+    let mut rebuilt = Storage::open(unique_temp_db_path("project-settings-rebuilt"))?;
+    assert_eq!(rebuilt.copy_project_settings_from(&live_path)?, 1);
+    assert_eq!(
+        rebuilt.project_setting("trail_defaults")?.as_deref(),
+        Some(r#"{"depth":4}"#)
+    );
+    assert!(rebuilt.delete_project_setting("trail_defaults")?);
+    assert!(!rebuilt.delete_project_setting("trail_defaults")?);
+    assert_eq!(rebuilt.project_setting("trail_defaults")?, None);
     Ok(())
 }
@@ -1,6 +1,65 @@
 use super::*;
+use codestory_contracts::graph::{TrailEdgeWeight, TrailPath};
+use std::cell::Cell;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
+use std::time::Instant;
+
+/// Paths `TrailMode::AllPaths` returns when the config sets no limit.
+const DEFAULT_ALL_PATHS_LIMIT: usize = 32;
+/// Hop limit for `TrailMode::AllPaths` when the config depth is unbounded.
+const DEFAULT_ALL_PATHS_MAX_LENGTH: u32 = 12;
+/// Edge steps one all-paths search may take before it reports truncation.
+const ALL_PATHS_STEP_BUDGET: usize = 200_000;
+/// Partial paths one weighted-path search may queue before it gives up.
+const WEIGHTED_PATH_LABEL_BUDGET: usize = 200_000;
+/// Lowest confidence an edge is weighted at, which keeps weights finite.
+const MIN_EDGE_CONFIDENCE: f32 = 0.01;
+/// `TrailMode::DataFlow` leaves a function through the data it writes...
+const DATA_FLOW_WRITE_EDGES: &[EdgeKind] = &[EdgeKind::ASSIGNMENT];
+/// ...and leaves a field or variable through the functions that read it.
+const DATA_FLOW_READ_EDGES: &[EdgeKind] = &[EdgeKind::USAGE];
+
+/// Deadline and cancellation flag a trail query checks as it expands.
+///
+/// Once either trips, the query stops expanding and returns what it has found
+/// so far with `TrailResult::truncated_by_timeout` set.
+#[derive(Debug, Default)]
+pub struct TrailStop {
+    deadline: Option<Instant>,
+    cancelled: Option<Arc<AtomicBool>>,
+    stopped: Cell<bool>,
+}
+
+impl TrailStop {
+    pub fn new(deadline: Option<Instant>, cancelled: Option<Arc<AtomicBool>>) -> Self {
+        Self {
+            deadline,
+            cancelled,
+            stopped: Cell::new(false),
+        }
+    }
+
+    fn should_stop(&self) -> bool {
+        if self.stopped.get() {
+            return true;
+        }
+        let stop = self
+            .deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+            || self
+                .cancelled
+                .as_ref()
+                .is_some_and(|cancelled| cancelled.load(AtomicOrdering::Acquire));
+        self.stopped.set(stop);
+        stop
+    }
+}
 
 struct BfsTraversalOptions<'a> {
+    stop: &'a TrailStop,
     edge_filter: &'a [EdgeKind],
     caller_scope: TrailCallerScope,
     show_utility_calls: bool,
@@ -14,22 +73,221 @@ struct EdgeFetchResult {
     truncated: bool,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TrailPruneReason {
+    TestOrBench,
+    TrivialAccessor,
+    Generated,
+    Unreachable,
+}
+
+/// Node-level pruning applied while a trail is built.
+///
+/// Edge-level caller scoping only drops calls made from test files; this also
+/// keeps test and bench symbols reached through other edges out of a
+/// production trail. File test-ness and generated-ness are cached per file
+/// node; entry-point reachability is computed once, on first use.
+struct TrailNodePruner {
+    prune_tests: bool,
+    hide_trivial_accessors: bool,
+    hide_generated: bool,
+    entry_reachable_only: bool,
+    keep: HashSet<NodeId>,
+    test_files: HashMap<NodeId, bool>,
+    generated_files: HashMap<NodeId, bool>,
+    entry_distances: Option<EntryPointDistances>,
+}
+
+impl TrailNodePruner {
+    fn new(config: &TrailConfig) -> Self {
+        let mut keep = HashSet::from([config.root_id]);
+        keep.extend(config.target_id);
+        Self {
+            prune_tests: config.caller_scope == TrailCallerScope::ProductionOnly,
+            hide_trivial_accessors: config.hide_trivial_accessors,
+            hide_generated: config.hide_generated,
+            entry_reachable_only: config.entry_reachable_only,
+            keep,
+            test_files: HashMap::new(),
+            generated_files: HashMap::new(),
+            entry_distances: None,
+        }
+    }
+
+    fn prune_reason(
+        &mut self,
+        storage: &Storage,
+        node: &Node,
+    ) -> Result<Option<TrailPruneReason>, StorageError> {
+        if self.keep.contains(&node.id) {
+            return Ok(None);
+        }
+        if self.hide_trivial_accessors && super::is_trivial_accessor(node) {
+            return Ok(Some(TrailPruneReason::TrivialAccessor));
+        }
+        if self.prune_tests
+            && let Some(file_node_id) = node.file_node_id
+            && self.is_test_file(storage, file_node_id)?
+        {
+            return Ok(Some(TrailPruneReason::TestOrBench));
+        }
+        if self.hide_generated
+            && let Some(file_node_id) = node.file_node_id
+            && self.is_generated_file(storage, file_node_id)?
+        {
+            return Ok(Some(TrailPruneReason::Generated));
+        }
+        if self.entry_reachable_only {
+            if self.entry_distances.is_none() {
+                self.entry_distances = Some(storage.entry_point_distances()?);
+            }
+            if self
+                .entry_distances
+                .as_ref()
+                .is_some_and(|reach| reach.distance(node.id).is_none())
+            {
+                return Ok(Some(TrailPruneReason::Unreachable));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Generated-ness comes from the stored file role, which also reflects
+    /// generator markers found in the file header at index time.
+    fn is_generated_file(
+        &mut self,
+        storage: &Storage,
+        file_node_id: NodeId,
+    ) -> Result<bool, StorageError> {
+        if let Some(is_generated) = self.generated_files.get(&file_node_id) {
+            return Ok(*is_generated);
+        }
+        let file_role: Option<String> = storage
+            .conn
+            .query_row(
+                "SELECT file_role FROM file WHERE id = ?1",
+                params![file_node_id.0],
+                |row| row.get(0),
+            )
+            .optional()?;
+        let is_generated =
+            file_role.is_some_and(|role| FileRole::from_db_value(&role) == FileRole::Generated);
+        self.generated_files.insert(file_node_id, is_generated);
+        Ok(is_generated)
+    }
+
+    fn is_test_file(
+        &mut self,
+        storage: &Storage,
+        file_node_id: NodeId,
+    ) -> Result<bool, StorageError> {
+        if let Some(is_test) = self.test_files.get(&file_node_id) {
+            return Ok(*is_test);
+        }
+        let path: Option<String> = storage
+            .conn
+            .query_row(
+                "SELECT COALESCE(
+                    (SELECT path FROM file WHERE id = ?1),
+                    (SELECT serialized_name FROM node WHERE id = ?1)
+                 )",
+                params![file_node_id.0],
+                |row| row.get(0),
+            )
+            .optional()?
+            .flatten();
+        let is_test = path.as_deref().is_some_and(super::is_test_or_bench_path);
+        self.test_files.insert(file_node_id, is_test);
+        Ok(is_test)
+    }
+
+    /// Drop pruned nodes from a finished result, leaving edge cleanup to the
+    /// caller's endpoint pass so those edges count as omitted.
+    fn prune_result(
+        &mut self,
+        storage: &Storage,
+        result: &mut TrailResult,
+    ) -> Result<(), StorageError> {
+        let mut kept = Vec::with_capacity(result.nodes.len());
+        for node in std::mem::take(&mut result.nodes) {
+            match self.prune_reason(storage, &node)? {
+                Some(reason) => {
+                    record_pruned(result, reason);
+                    result.depth_map.remove(&node.id);
+                }
+                None => kept.push(node),
+            }
+        }
+        result.nodes = kept;
+        Ok(())
+    }
+}
+
+fn record_pruned(result: &mut TrailResult, reason: TrailPruneReason) {
+    match reason {
+        TrailPruneReason::TestOrBench => {
+            result.pruned_test_node_count = result.pruned_test_node_count.saturating_add(1);
+        }
+        TrailPruneReason::TrivialAccessor => {
+            result.pruned_accessor_node_count = result.pruned_accessor_node_count.saturating_add(1);
+        }
+        TrailPruneReason::Generated => {
+            result.pruned_generated_node_count =
+                result.pruned_generated_node_count.saturating_add(1);
+        }
+        TrailPruneReason::Unreachable => {
+            result.omitted_node_count = result.omitted_node_count.saturating_add(1);
+        }
+    }
+}
+
 pub(super) fn get_trail(
     storage: &Storage,
     config: &TrailConfig,
+    stop: &TrailStop,
 ) -> Result<TrailResult, StorageError> {
-    match config.mode {
-        TrailMode::ToTargetSymbol => get_trail_to_target(storage, config),
-        _ => get_trail_bfs(storage, config),
+    // Trails over merged nodes start and end at their primaries.
+    let aliases = storage.node_alias_map()?;
+    let redirected;
+    let config = if aliases.primary_of(config.root_id) != config.root_id
+        || config
+            .target_id
+            .is_some_and(|target| aliases.primary_of(target) != target)
+    {
+        redirected = TrailConfig {
+            root_id: aliases.primary_of(config.root_id),
+            target_id: config.target_id.map(|target| aliases.primary_of(target)),
+            ..config.clone()
+        };
+        &redirected
+    } else {
+        config
+    };
+    let mut result = match config.mode {
+        TrailMode::ToTargetSymbol => get_trail_to_target(storage, config, stop),
+        TrailMode::AllPaths => get_trail_all_paths(storage, config, stop),
+        TrailMode::WeightedPath => get_trail_weighted_path(storage, config, stop),
+        _ => get_trail_bfs(storage, config, stop),
+    }?;
+    collapse_duplicate_edges(&mut result);
+    if stop.stopped.get() {
+        result.truncated = true;
+        result.truncated_by_timeout = true;
     }
+    Ok(result)
 }
 
 pub(super) fn get_trail_bfs(
     storage: &Storage,
     config: &TrailConfig,
+    stop: &TrailStop,
 ) -> Result<TrailResult, StorageError> {
     let mut result = TrailResult::default();
+    let mut pruner = TrailNodePruner::new(config);
     let mut visited: HashSet<NodeId> = HashSet::new();
+    let mut pruned: HashSet<NodeId> = HashSet::new();
+    let mut fetched: HashMap<NodeId, Node> = HashMap::new();
+    let mut seen_edges: HashSet<EdgeId> = HashSet::new();
     let mut queue: VecDeque<(NodeId, u32)> = VecDeque::new();
     let max_edges = config.max_nodes.saturating_mul(3).max(128);
     let max_depth = if config.depth == 0 {
@@ -49,12 +307,24 @@ pub(super) fn get_trail_bfs(
     result.depth_map.insert(config.root_id, 0);
 
     while let Some((current_id, depth)) = queue.pop_front() {
-        if result.nodes.len() >= config.max_nodes {
+        // The root is always kept so a stopped trail still has its anchor.
+        if result.nodes.len() >= config.max_nodes
+            || (!result.nodes.is_empty() && stop.should_stop())
+        {
             result.truncated = true;
             break;
         }
 
-        if let Some(node) = storage.get_node(current_id)? {
+        let node = match fetched.remove(&current_id) {
+            Some(node) => Some(node),
+            None => storage.get_node(current_id)?,
+        };
+        let (direction, edge_filter) = if config.mode == TrailMode::DataFlow {
+            data_flow_step(node.as_ref().map(|node| node.kind))
+        } else {
+            (direction, config.edge_filter.as_slice())
+        };
+        if let Some(node) = node {
             result.nodes.push(node);
         }
 
@@ -68,7 +338,7 @@ pub(super) fn get_trail_bfs(
                 storage,
                 current_id,
                 &direction,
-                &config.edge_filter,
+                edge_filter,
                 config.caller_scope,
                 config.show_utility_calls,
                 Some(remaining_edges),
@@ -82,17 +352,35 @@ pub(super) fn get_trail_bfs(
                     result.truncated = true;
                     break;
                 }
-                result.edges.push(edge.clone());
+                // Expanding the node at either end fetches the same edge.
+                if !seen_edges.insert(edge.id) {
+                    continue;
+                }
                 let Some(neighbor_id) = super::neighbor_for_direction(current_id, direction, &edge)
                 else {
+                    result.edges.push(edge);
                     continue;
                 };
+                if pruned.contains(&neighbor_id) {
+                    result.omitted_edge_count += 1;
+                    continue;
+                }
 
                 if !visited.contains(&neighbor_id) {
+                    if let Some(node) = storage.get_node(neighbor_id)? {
+                        if let Some(reason) = pruner.prune_reason(storage, &node)? {
+                            record_pruned(&mut result, reason);
+                            pruned.insert(neighbor_id);
+                            result.omitted_edge_count += 1;
+                            continue;
+                        }
+                        fetched.insert(neighbor_id, node);
+                    }
                     visited.insert(neighbor_id);
                     result.depth_map.insert(neighbor_id, depth + 1);
                     queue.push_back((neighbor_id, depth + 1));
                 }
+                result.edges.push(edge);
             }
 
             if result.truncated {
@@ -100,6 +388,8 @@ pub(super) fn get_trail_bfs(
             }
         }
     }
+    // Visited nodes still waiting in the queue were cut by a limit.
+    result.omitted_node_count += visited.len().saturating_sub(result.nodes.len()) as u32;
 
     super::apply_trail_node_filter(&mut result, config);
     let selected_ids: HashSet<NodeId> = result.nodes.iter().map(|n| n.id).collect();
@@ -113,12 +403,73 @@ pub(super) fn get_trail_bfs(
         }
     }
 
+    sort_trail_result(&mut result);
     Ok(result)
 }
 
+/// Fold edges of one kind and line that share their effective endpoints, such
+/// as a raw edge and a resolved edge for the same call, into one: a resolved
+/// edge over a raw one, then the most confident, then the lowest id. The
+/// folded ids are kept in `TrailResult::collapsed_edge_ids`, and edges stay
+/// in id order.
+fn collapse_duplicate_edges(result: &mut TrailResult) {
+    let mut groups: Vec<Vec<Edge>> = Vec::new();
+    let mut group_of = HashMap::new();
+    for edge in std::mem::take(&mut result.edges) {
+        let (source, target) = edge.effective_endpoints();
+        let index = *group_of
+            .entry((source, target, edge.kind, edge.line))
+            .or_insert_with(|| {
+                groups.push(Vec::new());
+                groups.len() - 1
+            });
+        groups[index].push(edge);
+    }
+    for mut group in groups {
+        let resolved =
+            |edge: &Edge| edge.resolved_source.is_some() || edge.resolved_target.is_some();
+        group.sort_by(|a, b| {
+            resolved(b)
+                .cmp(&resolved(a))
+                .then(
+                    b.confidence
+                        .unwrap_or(0.0)
+                        .total_cmp(&a.confidence.unwrap_or(0.0)),
+                )
+                .then(a.id.0.cmp(&b.id.0))
+        });
+        let mut group = group.into_iter();
+        let Some(kept) = group.next() else {
+            continue;
+        };
+        let folded = group.map(|edge| edge.id).collect::<Vec<_>>();
+        if !folded.is_empty() {
+            result.collapsed_edge_ids.insert(kept.id, folded);
+        }
+        result.edges.push(kept);
+    }
+    result.edges.sort_by_key(|edge| edge.id.0);
+}
+
+/// Which way a data-flow trail continues from a node: out through writes for
+/// code, in through reads for data. The configured edge filter is ignored.
+fn data_flow_step(kind: Option<NodeKind>) -> (TrailDirection, &'static [EdgeKind]) {
+    match kind {
+        Some(
+            NodeKind::FIELD
+            | NodeKind::VARIABLE
+            | NodeKind::GLOBAL_VARIABLE
+            | NodeKind::CONSTANT
+            | NodeKind::ENUM_CONSTANT,
+        ) => (TrailDirection::Incoming, DATA_FLOW_READ_EDGES),
+        _ => (TrailDirection::Outgoing, DATA_FLOW_WRITE_EDGES),
+    }
+}
+
 pub(super) fn get_trail_to_target(
     storage: &Storage,
     config: &TrailConfig,
+    stop: &TrailStop,
 ) -> Result<TrailResult, StorageError> {
     let target_id = config.target_id.ok_or_else(|| {
         StorageError::Other("TrailMode::ToTargetSymbol requires TrailConfig.target_id".to_string())
@@ -136,6 +487,7 @@ pub(super) fn get_trail_to_target(
         .min(100_000);
 
     let traversal_options = BfsTraversalOptions {
+        stop,
         edge_filter: &config.edge_filter,
         caller_scope: config.caller_scope,
         show_utility_calls: config.show_utility_calls,
@@ -152,19 +504,7 @@ pub(super) fn get_trail_to_target(
     )?;
 
     if !dist_from_root.contains_key(&target_id) {
-        let mut result = TrailResult::default();
-        if let Some(node) = storage.get_node(config.root_id)? {
-            result.nodes.push(node);
-            result.depth_map.insert(config.root_id, 0);
-        }
-        if target_id != config.root_id
-            && let Some(node) = storage.get_node(target_id)?
-        {
-            result.nodes.push(node);
-        }
-        result.truncated = truncated_from_root;
-        super::apply_trail_node_filter(&mut result, config);
-        return Ok(result);
+        return unreachable_target_trail(storage, config, target_id, truncated_from_root);
     }
 
     let (dist_to_target, truncated_to_target) = bfs_distances_to_target_through_root_reachable(
@@ -273,6 +613,7 @@ pub(super) fn get_trail_to_target(
         || selected.len() < included.len();
     let mut result = TrailResult {
         truncated,
+        omitted_node_count: included.len().saturating_sub(selected.len()) as u32,
         ..TrailResult::default()
     };
 
@@ -328,6 +669,7 @@ pub(super) fn get_trail_to_target(
     }
     result.edges.sort_by_key(|e| e.id.0);
 
+    TrailNodePruner::new(config).prune_result(storage, &mut result)?;
     super::apply_trail_node_filter(&mut result, config);
     let selected_ids: HashSet<NodeId> = result.nodes.iter().map(|n| n.id).collect();
     let old_edges = std::mem::take(&mut result.edges);
@@ -340,9 +682,670 @@ pub(super) fn get_trail_to_target(
         }
     }
 
+    sort_trail_result(&mut result);
+    Ok(result)
+}
+
+/// Trail holding only the root and target when no path joins them.
+fn unreachable_target_trail(
+    storage: &Storage,
+    config: &TrailConfig,
+    target_id: NodeId,
+    truncated: bool,
+) -> Result<TrailResult, StorageError> {
+    let mut result = TrailResult::default();
+    if let Some(node) = storage.get_node(config.root_id)? {
+        result.nodes.push(node);
+        result.depth_map.insert(config.root_id, 0);
+    }
+    if target_id != config.root_id
+        && let Some(node) = storage.get_node(target_id)?
+    {
+        result.nodes.push(node);
+    }
+    result.truncated = truncated;
+    TrailNodePruner::new(config).prune_result(storage, &mut result)?;
+    super::apply_trail_node_filter(&mut result, config);
+    sort_trail_result(&mut result);
+    Ok(result)
+}
+
+/// Enumerate distinct simple paths from the root to the target.
+///
+/// Paths are searched one length at a time, so the returned list holds the
+/// shortest paths first. The hop limit is `config.depth` (a default when
+/// unbounded) and the path limit is `config.max_paths`; hitting either the
+/// path limit or the search budget marks the result truncated. Result nodes
+/// and edges are the union of the returned paths, capped at `max_nodes`.
+pub(super) fn get_trail_all_paths(
+    storage: &Storage,
+    config: &TrailConfig,
+    stop: &TrailStop,
+) -> Result<TrailResult, StorageError> {
+    let target_id = config.target_id.ok_or_else(|| {
+        StorageError::Other("TrailMode::AllPaths requires TrailConfig.target_id".to_string())
+    })?;
+    let max_length = if config.depth == 0 {
+        DEFAULT_ALL_PATHS_MAX_LENGTH
+    } else {
+        config.depth
+    };
+    let traversal_options = BfsTraversalOptions {
+        stop,
+        edge_filter: &config.edge_filter,
+        caller_scope: config.caller_scope,
+        show_utility_calls: config.show_utility_calls,
+        max_depth: max_length,
+        max_nodes: config
+            .max_nodes
+            .saturating_mul(4)
+            .max(config.max_nodes)
+            .min(100_000),
+        max_edges_per_node: config.max_nodes.saturating_mul(3).max(128),
+    };
+
+    let (dist_from_root, truncated_from_root) = bfs_distances(
+        storage,
+        config.root_id,
+        TrailDirection::Outgoing,
+        &traversal_options,
+    )?;
+    if !dist_from_root.contains_key(&target_id) {
+        return unreachable_target_trail(storage, config, target_id, truncated_from_root);
+    }
+    let (dist_to_target, truncated_to_target) = bfs_distances_to_target_through_root_reachable(
+        storage,
+        target_id,
+        &dist_from_root,
+        &traversal_options,
+    )?;
+
+    let mut result = TrailResult::default();
+    let mut search = AllPathsSearch {
+        storage,
+        config,
+        stop,
+        target_id,
+        dist_to_target: &dist_to_target,
+        max_edges_per_node: traversal_options.max_edges_per_node,
+        max_paths: config
+            .max_paths
+            .map_or(DEFAULT_ALL_PATHS_LIMIT, |limit| limit.max(1) as usize),
+        pruner: TrailNodePruner::new(config),
+        pruned: HashSet::new(),
+        hops: HashMap::new(),
+        edges: HashMap::new(),
+        steps: 0,
+        paths: Vec::new(),
+        truncated: false,
+    };
+    let shortest = dist_to_target.get(&config.root_id).copied().unwrap_or(0);
+    for length in shortest..=max_length {
+        let mut path = TrailPath {
+            node_ids: vec![config.root_id],
+            edge_ids: Vec::new(),
+        };
+        search.walk(&mut result, &mut path, length)?;
+        if search.truncated {
+            break;
+        }
+    }
+
+    let mut included: HashSet<NodeId> = HashSet::new();
+    let mut dropped: HashSet<NodeId> = HashSet::new();
+    let mut edge_ids: HashSet<EdgeId> = HashSet::new();
+    let mut capped = false;
+    for path in std::mem::take(&mut search.paths) {
+        if capped {
+            dropped.extend(path.node_ids.iter().copied());
+            continue;
+        }
+        let new_nodes = path
+            .node_ids
+            .iter()
+            .filter(|id| !included.contains(id))
+            .count();
+        if included.len() + new_nodes > config.max_nodes {
+            result.truncated = true;
+            capped = true;
+            dropped.extend(path.node_ids.iter().copied());
+            continue;
+        }
+        included.extend(path.node_ids.iter().copied());
+        edge_ids.extend(path.edge_ids.iter().copied());
+        result.paths.push(path);
+    }
+    result.omitted_node_count += dropped.difference(&included).count() as u32;
+    result.truncated |= search.truncated || truncated_from_root || truncated_to_target;
+
+    let mut node_ids: Vec<NodeId> = included.into_iter().collect();
+    node_ids.sort_by_key(|id| id.0);
+    for id in node_ids {
+        if let Some(node) = storage.get_node(id)? {
+            result.nodes.push(node);
+        }
+        result
+            .depth_map
+            .insert(id, dist_from_root.get(&id).copied().unwrap_or(0));
+    }
+    result.edges = search
+        .edges
+        .into_values()
+        .filter(|edge| edge_ids.contains(&edge.id))
+        .collect();
+
+    super::apply_trail_node_filter(&mut result, config);
+    sort_trail_result(&mut result);
     Ok(result)
 }
 
+/// Depth-first state for [`get_trail_all_paths`].
+struct AllPathsSearch<'a> {
+    storage: &'a Storage,
+    config: &'a TrailConfig,
+    stop: &'a TrailStop,
+    target_id: NodeId,
+    /// Fewest hops from each node to the target; nodes absent here cannot
+    /// reach it within the hop limit.
+    dist_to_target: &'a HashMap<NodeId, u32>,
+    max_edges_per_node: usize,
+    max_paths: usize,
+    pruner: TrailNodePruner,
+    pruned: HashSet<NodeId>,
+    /// Outgoing hops per node, one edge per neighbor, nearest to the target
+    /// first.
+    hops: HashMap<NodeId, Vec<(NodeId, EdgeId)>>,
+    edges: HashMap<EdgeId, Edge>,
+    steps: usize,
+    paths: Vec<TrailPath>,
+    truncated: bool,
+}
+
+impl AllPathsSearch<'_> {
+    /// Extend `path` to every simple path of exactly `length` hops that ends
+    /// at the target.
+    fn walk(
+        &mut self,
+        result: &mut TrailResult,
+        path: &mut TrailPath,
+        length: u32,
+    ) -> Result<(), StorageError> {
+        let current = *path.node_ids.last().expect("path starts at the root");
+        let hops_taken = path.edge_ids.len() as u32;
+        if current == self.target_id {
+            if hops_taken == length {
+                if self.paths.len() == self.max_paths {
+                    self.truncated = true;
+                } else {
+                    self.paths.push(path.clone());
+                }
+            }
+            return Ok(());
+        }
+        let remaining = length - hops_taken;
+        for (next, edge_id) in self.hops(result, current)? {
+            if self.truncated {
+                break;
+            }
+            if self
+                .dist_to_target
+                .get(&next)
+                .is_none_or(|distance| *distance >= remaining)
+                || path.node_ids.contains(&next)
+            {
+                continue;
+            }
+            self.steps += 1;
+            if self.steps > ALL_PATHS_STEP_BUDGET || self.stop.should_stop() {
+                self.truncated = true;
+                break;
+            }
+            path.node_ids.push(next);
+            path.edge_ids.push(edge_id);
+            self.walk(result, path, length)?;
+            path.node_ids.pop();
+            path.edge_ids.pop();
+        }
+        Ok(())
+    }
+
+    fn hops(
+        &mut self,
+        result: &mut TrailResult,
+        node_id: NodeId,
+    ) -> Result<Vec<(NodeId, EdgeId)>, StorageError> {
+        if let Some(hops) = self.hops.get(&node_id) {
+            return Ok(hops.clone());
+        }
+        let edge_fetch = get_edges_for_node_limited(
+            self.storage,
+            node_id,
+            &TrailDirection::Outgoing,
+            &self.config.edge_filter,
+            self.config.caller_scope,
+            self.config.show_utility_calls,
+            Some(self.max_edges_per_node),
+        )?;
+        if edge_fetch.truncated {
+            result.truncated = true;
+        }
+        let mut hops: Vec<(NodeId, EdgeId)> = Vec::new();
+        for edge in edge_fetch.edges {
+            let (src, dst) = edge.effective_endpoints();
+            if src != node_id
+                || dst == node_id
+                || !self.dist_to_target.contains_key(&dst)
+                || hops.iter().any(|(next, _)| *next == dst)
+            {
+                continue;
+            }
+            if self.pruned.contains(&dst) {
+                result.omitted_edge_count += 1;
+                continue;
+            }
+            if let Some(node) = self.storage.get_node(dst)?
+                && let Some(reason) = self.pruner.prune_reason(self.storage, &node)?
+            {
+                record_pruned(result, reason);
+                self.pruned.insert(dst);
+                result.omitted_edge_count += 1;
+                continue;
+            }
+            hops.push((dst, edge.id));
+            self.edges.insert(edge.id, edge);
+        }
+        hops.sort_by_key(|(next, _)| (self.dist_to_target[next], next.0));
+        self.hops.insert(node_id, hops.clone());
+        Ok(hops)
+    }
+}
+
+/// Find the root-to-target path with the lowest total edge weight.
+///
+/// Hops are weighted by `config.edge_weight` from each edge's confidence, and
+/// the search is Dijkstra's over (node, hops) labels so the `depth` limit
+/// still holds: a label is dropped when the node was already settled in as
+/// few hops, since that earlier label was also cheaper. The result holds only
+/// the nodes and edges of the chosen path, which is also its single entry in
+/// `paths`.
+pub(super) fn get_trail_weighted_path(
+    storage: &Storage,
+    config: &TrailConfig,
+    stop: &TrailStop,
+) -> Result<TrailResult, StorageError> {
+    let target_id = config.target_id.ok_or_else(|| {
+        StorageError::Other("TrailMode::WeightedPath requires TrailConfig.target_id".to_string())
+    })?;
+    let max_depth = if config.depth == 0 {
+        u32::MAX
+    } else {
+        config.depth
+    };
+    let traversal_options = BfsTraversalOptions {
+        stop,
+        edge_filter: &config.edge_filter,
+        caller_scope: config.caller_scope,
+        show_utility_calls: config.show_utility_calls,
+        max_depth,
+        max_nodes: config
+            .max_nodes
+            .saturating_mul(4)
+            .max(config.max_nodes)
+            .min(100_000),
+        max_edges_per_node: config.max_nodes.saturating_mul(3).max(128),
+    };
+
+    let (dist_from_root, truncated_from_root) = bfs_distances(
+        storage,
+        config.root_id,
+        TrailDirection::Outgoing,
+        &traversal_options,
+    )?;
+    if !dist_from_root.contains_key(&target_id) {
+        return unreachable_target_trail(storage, config, target_id, truncated_from_root);
+    }
+    let (dist_to_target, truncated_to_target) = bfs_distances_to_target_through_root_reachable(
+        storage,
+        target_id,
+        &dist_from_root,
+        &traversal_options,
+    )?;
+
+    let mut result = TrailResult::default();
+    let mut pruner = TrailNodePruner::new(config);
+    let mut pruned: HashSet<NodeId> = HashSet::new();
+    let mut kept: HashSet<NodeId> = HashSet::new();
+    let mut edges: HashMap<EdgeId, Edge> = HashMap::new();
+    // Each label is a node reached through an edge from a parent label.
+    let mut labels: Vec<(NodeId, Option<(EdgeId, usize)>)> = vec![(config.root_id, None)];
+    let mut settled_hops: HashMap<NodeId, u32> = HashMap::new();
+    let mut queue = BinaryHeap::from([Reverse(WeightedLabel {
+        cost: 0.0,
+        hops: 0,
+        node_id: config.root_id,
+        label: 0,
+    })]);
+    let mut found = None;
+    while let Some(Reverse(entry)) = queue.pop() {
+        if stop.should_stop() {
+            result.truncated = true;
+            break;
+        }
+        if settled_hops
+            .get(&entry.node_id)
+            .is_some_and(|hops| *hops <= entry.hops)
+        {
+            continue;
+        }
+        settled_hops.insert(entry.node_id, entry.hops);
+        if entry.node_id == target_id {
+            found = Some(entry.label);
+            break;
+        }
+        let edge_fetch = get_edges_for_node_limited(
+            storage,
+            entry.node_id,
+            &TrailDirection::Outgoing,
+            &config.edge_filter,
+            config.caller_scope,
+            config.show_utility_calls,
+            Some(traversal_options.max_edges_per_node),
+        )?;
+        if edge_fetch.truncated {
+            result.truncated = true;
+        }
+        let hops = entry.hops + 1;
+        for edge in edge_fetch.edges {
+            let (src, dst) = edge.effective_endpoints();
+            if src != entry.node_id || dst == entry.node_id {
+                continue;
+            }
+            let Some(&d_to) = dist_to_target.get(&dst) else {
+                continue;
+            };
+            if max_depth != u32::MAX && hops as u64 + d_to as u64 > max_depth as u64 {
+                continue;
+            }
+            if settled_hops
+                .get(&dst)
+                .is_some_and(|settled| *settled <= hops)
+                || pruned.contains(&dst)
+            {
+                continue;
+            }
+            if !kept.contains(&dst) {
+                if let Some(node) = storage.get_node(dst)?
+                    && let Some(reason) = pruner.prune_reason(storage, &node)?
+                {
+                    record_pruned(&mut result, reason);
+                    pruned.insert(dst);
+                    continue;
+                }
+                kept.insert(dst);
+            }
+            if labels.len() >= WEIGHTED_PATH_LABEL_BUDGET {
+                result.truncated = true;
+                break;
+            }
+            queue.push(Reverse(WeightedLabel {
+                cost: entry.cost + weighted_edge_cost(&edge, config.edge_weight),
+                hops,
+                node_id: dst,
+                label: labels.len(),
+            }));
+            labels.push((dst, Some((edge.id, entry.label))));
+            edges.entry(edge.id).or_insert(edge);
+        }
+    }
+    result.truncated |= truncated_from_root || truncated_to_target;
+
+    let Some(mut label) = found else {
+        return unreachable_target_trail(storage, config, target_id, result.truncated);
+    };
+    let mut path = TrailPath::default();
+    loop {
+        let (node_id, parent) = labels[label];
+        path.node_ids.push(node_id);
+        let Some((edge_id, parent_label)) = parent else {
+            break;
+        };
+        path.edge_ids.push(edge_id);
+        label = parent_label;
+    }
+    path.node_ids.reverse();
+    path.edge_ids.reverse();
+
+    for (depth, id) in path.node_ids.iter().enumerate() {
+        if let Some(node) = storage.get_node(*id)? {
+            result.nodes.push(node);
+        }
+        result.depth_map.insert(*id, depth as u32);
+    }
+    result.edges = path
+        .edge_ids
+        .iter()
+        .filter_map(|edge_id| edges.remove(edge_id))
+        .collect();
+    result.paths.push(path);
+
+    super::apply_trail_node_filter(&mut result, config);
+    sort_trail_result(&mut result);
+    Ok(result)
+}
+
+/// Queue entry for [`get_trail_weighted_path`], ordered by cost, then hops,
+/// then node id.
+struct WeightedLabel {
+    cost: f64,
+    hops: u32,
+    node_id: NodeId,
+    label: usize,
+}
+
+impl WeightedLabel {
+    fn key(&self) -> (f64, u32, i64) {
+        (self.cost, self.hops, self.node_id.0)
+    }
+}
+
+impl PartialEq for WeightedLabel {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for WeightedLabel {}
+
+impl PartialOrd for WeightedLabel {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for WeightedLabel {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let (cost, hops, id) = self.key();
+        let (other_cost, other_hops, other_id) = other.key();
+        cost.total_cmp(&other_cost)
+            .then(hops.cmp(&other_hops))
+            .then(id.cmp(&other_id))
+    }
+}
+
+fn weighted_edge_cost(edge: &Edge, weight: TrailEdgeWeight) -> f64 {
+    let confidence = f64::from(
+        edge.confidence
+            .unwrap_or(1.0)
+            .clamp(MIN_EDGE_CONFIDENCE, 1.0),
+    );
+    match weight {
+        TrailEdgeWeight::InverseConfidence => 1.0 / confidence,
+        TrailEdgeWeight::NegativeLogConfidence => -confidence.ln(),
+    }
+}
+
+pub(super) fn reachable_set(
+    storage: &Storage,
+    start_id: NodeId,
+    direction: TrailDirection,
+    edge_kinds: &[EdgeKind],
+    max_nodes: usize,
+) -> Result<ReachableSet, StorageError> {
+    let mut visited = HashSet::from([start_id]);
+    let mut node_ids = Vec::new();
+    let mut frontier = vec![start_id];
+    let mut level = 0;
+    let mut max_depth = 0;
+    let mut truncated = false;
+    'expand: while !frontier.is_empty() {
+        level += 1;
+        let mut next_frontier = Vec::new();
+        for chunk in frontier.chunks(EDGE_NODE_LOOKUP_BATCH_SIZE) {
+            for neighbor in frontier_neighbors(storage, chunk, direction, edge_kinds)? {
+                if !visited.insert(neighbor) {
+                    continue;
+                }
+                if node_ids.len() >= max_nodes {
+                    truncated = true;
+                    break 'expand;
+                }
+                node_ids.push(neighbor);
+                next_frontier.push(neighbor);
+                max_depth = level;
+            }
+        }
+        frontier = next_frontier;
+    }
+
+    let mut reached = ReachableSet {
+        node_ids,
+        kind_counts: Vec::new(),
+        file_counts: Vec::new(),
+        max_depth,
+        truncated,
+    };
+    count_reachable_groups(storage, &mut reached)?;
+    Ok(reached)
+}
+
+/// Neighbors of one frontier chunk, read with a single edge query.
+pub(super) fn frontier_neighbors(
+    storage: &Storage,
+    chunk: &[NodeId],
+    direction: TrailDirection,
+    edge_kinds: &[EdgeKind],
+) -> Result<Vec<NodeId>, StorageError> {
+    let placeholders = numbered_placeholders(1, chunk.len());
+    let where_clause = match direction {
+        TrailDirection::Outgoing => format!(
+            "e.source_node_id IN ({placeholders}) OR e.resolved_source_node_id IN ({placeholders})"
+        ),
+        TrailDirection::Incoming => format!(
+            "e.target_node_id IN ({placeholders}) OR e.resolved_target_node_id IN ({placeholders})"
+        ),
+        TrailDirection::Both => format!(
+            "e.source_node_id IN ({placeholders}) OR e.resolved_source_node_id IN ({placeholders})
+             OR e.target_node_id IN ({placeholders}) OR e.resolved_target_node_id IN ({placeholders})"
+        ),
+    };
+    let query = format!("{} WHERE {where_clause}", super::EDGE_SELECT_BASE);
+    let chunk_ids = chunk.iter().copied().collect::<HashSet<_>>();
+    let mut stmt = storage.conn.prepare(&query)?;
+    let mut rows = stmt.query(params_from_iter(chunk.iter().map(|id| id.0)))?;
+    let mut neighbors = Vec::new();
+    while let Some(row) = rows.next()? {
+        let mut edge = Storage::edge_from_row(row)?;
+        if !edge_kinds.is_empty() && !edge_kinds.contains(&edge.kind) {
+            continue;
+        }
+        let target_symbol: String = row.get(12)?;
+        if edge.kind == EdgeKind::CALL
+            && edge.resolved_target.is_some()
+            && super::should_ignore_call_resolution(&target_symbol, edge.certainty, edge.confidence)
+        {
+            edge.resolved_target = None;
+        }
+        let endpoints = [
+            edge.effective_source(),
+            edge.effective_target(),
+            edge.source,
+            edge.target,
+        ];
+        for current in endpoints {
+            if chunk_ids.contains(&current)
+                && let Some(neighbor) = super::neighbor_for_direction(current, direction, &edge)
+            {
+                neighbors.push(neighbor);
+            }
+        }
+    }
+    Ok(neighbors)
+}
+
+/// Group reached nodes by kind and by declaring file.
+fn count_reachable_groups(
+    storage: &Storage,
+    reached: &mut ReachableSet,
+) -> Result<(), StorageError> {
+    let mut kinds: HashMap<NodeKind, u32> = HashMap::new();
+    let mut files: HashMap<NodeId, u32> = HashMap::new();
+    for chunk in reached.node_ids.chunks(NODE_LOOKUP_BATCH_SIZE) {
+        let query = format!(
+            "SELECT kind, file_node_id FROM node WHERE id IN ({})",
+            numbered_placeholders(1, chunk.len())
+        );
+        let mut stmt = storage.conn.prepare(&query)?;
+        let mut rows = stmt.query(params_from_iter(chunk.iter().map(|id| id.0)))?;
+        while let Some(row) = rows.next()? {
+            let kind = NodeKind::try_from(row.get::<_, i32>(0)?)?;
+            *kinds.entry(kind).or_default() += 1;
+            if let Some(file_id) = row.get::<_, Option<i64>>(1)? {
+                *files.entry(NodeId(file_id)).or_default() += 1;
+            }
+        }
+    }
+
+    let file_ids = files.keys().copied().collect::<Vec<_>>();
+    let file_counts = &mut reached.file_counts;
+    for chunk in file_ids.chunks(NODE_LOOKUP_BATCH_SIZE) {
+        let query = format!(
+            "SELECT n.id, COALESCE(f.path, n.serialized_name)
+             FROM node n LEFT JOIN file f ON f.id = n.id
+             WHERE n.id IN ({})",
+            numbered_placeholders(1, chunk.len())
+        );
+        let mut stmt = storage.conn.prepare(&query)?;
+        let mut rows = stmt.query(params_from_iter(chunk.iter().map(|id| id.0)))?;
+        while let Some(row) = rows.next()? {
+            let file_node_id = NodeId(row.get(0)?);
+            file_counts.push(ReachableFileCount {
+                file_node_id,
+                path: row.get(1)?,
+                count: files[&file_node_id],
+            });
+        }
+    }
+
+    file_counts.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.path.cmp(&b.path)));
+    reached.kind_counts = kinds.into_iter().collect();
+    reached
+        .kind_counts
+        .sort_by_key(|(kind, count)| (std::cmp::Reverse(*count), *kind as i32));
+    Ok(())
+}
+
+/// Order trail output by depth, then id, independent of traversal order.
+fn sort_trail_result(result: &mut TrailResult) {
+    let depth_map = &result.depth_map;
+    result.nodes.sort_by_key(|node| {
+        (
+            depth_map.get(&node.id).copied().unwrap_or(u32::MAX),
+            node.id.0,
+        )
+    });
+    result.edges.sort_by_key(|edge| edge.id.0);
+}
+
 fn bfs_distances(
     storage: &Storage,
     start: NodeId,
@@ -357,7 +1360,7 @@ fn bfs_distances(
     queue.push_back((start, 0));
 
     while let Some((current_id, depth)) = queue.pop_front() {
-        if dist.len() >= options.max_nodes {
+        if dist.len() >= options.max_nodes || options.stop.should_stop() {
             truncated = true;
             break;
         }
@@ -407,7 +1410,7 @@ fn bfs_distances_to_target_through_root_reachable(
     queue.push_back((target_id, 0));
 
     while let Some((current_id, depth)) = queue.pop_front() {
-        if dist.len() >= options.max_nodes {
+        if dist.len() >= options.max_nodes || options.stop.should_stop() {
             truncated = true;
             break;
         }
@@ -461,12 +1464,21 @@ fn get_edges_for_node_limited(
     show_utility_calls: bool,
     edge_budget: Option<usize>,
 ) -> Result<EdgeFetchResult, StorageError> {
+    // Edges of nodes merged into this one count as its own.
+    let aliases = storage.node_alias_map()?;
+    let node_id = aliases.primary_of(node_id);
+    let node_ids = aliases.with_aliases(node_id);
+    let ids = super::numbered_placeholders(1, node_ids.len());
     let where_clause = match direction {
-        TrailDirection::Outgoing => "e.source_node_id = ?1 OR e.resolved_source_node_id = ?1",
-        TrailDirection::Incoming => "e.target_node_id = ?1 OR e.resolved_target_node_id = ?1",
-        TrailDirection::Both => {
-            "e.source_node_id = ?1 OR e.target_node_id = ?1 OR e.resolved_source_node_id = ?1 OR e.resolved_target_node_id = ?1"
+        TrailDirection::Outgoing => {
+            format!("e.source_node_id IN ({ids}) OR e.resolved_source_node_id IN ({ids})")
+        }
+        TrailDirection::Incoming => {
+            format!("e.target_node_id IN ({ids}) OR e.resolved_target_node_id IN ({ids})")
         }
+        TrailDirection::Both => format!(
+            "e.source_node_id IN ({ids}) OR e.target_node_id IN ({ids}) OR e.resolved_source_node_id IN ({ids}) OR e.resolved_target_node_id IN ({ids})"
+        ),
     };
     let query = format!(
         "{} WHERE {where_clause} ORDER BY e.id",
@@ -482,10 +1494,11 @@ fn get_edges_for_node_limited(
     let mut stmt = storage.conn.prepare(&query)?;
     let mut edges = Vec::new();
     let mut truncated = false;
-    let mut rows = stmt.query(params![node_id.0])?;
+    let mut rows = stmt.query(params_from_iter(node_ids.iter().map(|id| id.0)))?;
 
     while let Some(row) = rows.next()? {
         let mut edge = Storage::edge_from_row(row)?;
+        aliases.redirect_edge(&mut edge);
         let target_symbol: String = row.get(12)?;
         let caller_file_path: Option<String> = row.get(13)?;
 
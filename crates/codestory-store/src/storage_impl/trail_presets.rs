@@ -0,0 +1,119 @@
+use super::*;
+
+/// Named edge and node kind filters saved for reuse across trail requests.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrailFilterPreset {
+    pub name: String,
+    pub edge_kinds: Vec<EdgeKind>,
+    pub node_kinds: Vec<NodeKind>,
+}
+
+fn kinds_db_value(kinds: impl IntoIterator<Item = i32>) -> String {
+    kinds
+        .into_iter()
+        .map(|kind| kind.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn kinds_from_db<K>(value: &str) -> Result<Vec<K>, StorageError>
+where
+    K: TryFrom<i32>,
+    StorageError: From<K::Error>,
+{
+    value
+        .split(',')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let kind = part.parse::<i32>().map_err(|error| {
+                StorageError::Other(format!("Invalid trail preset kind `{part}`: {error}"))
+            })?;
+            Ok(K::try_from(kind)?)
+        })
+        .collect()
+}
+
+fn trail_filter_preset_from_row(row: &Row) -> Result<TrailFilterPreset, StorageError> {
+    Ok(TrailFilterPreset {
+        name: row.get(0)?,
+        edge_kinds: kinds_from_db(&row.get::<_, String>(1)?)?,
+        node_kinds: kinds_from_db(&row.get::<_, String>(2)?)?,
+    })
+}
+
+impl Storage {
+    /// Every saved trail filter preset, ordered by name.
+    pub fn trail_filter_presets(&self) -> Result<Vec<TrailFilterPreset>, StorageError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT name, edge_kinds, node_kinds FROM trail_filter_preset ORDER BY name",
+        )?;
+        let mut rows = stmt.query([])?;
+        let mut presets = Vec::new();
+        while let Some(row) = rows.next()? {
+            presets.push(trail_filter_preset_from_row(row)?);
+        }
+        Ok(presets)
+    }
+
+    pub fn trail_filter_preset(
+        &self,
+        name: &str,
+    ) -> Result<Option<TrailFilterPreset>, StorageError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT name, edge_kinds, node_kinds FROM trail_filter_preset WHERE name = ?1",
+        )?;
+        let mut rows = stmt.query(params![name])?;
+        match rows.next()? {
+            Some(row) => Ok(Some(trail_filter_preset_from_row(row)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Save `preset`, replacing any preset with the same name.
+    pub fn save_trail_filter_preset(&self, preset: &TrailFilterPreset) -> Result<(), StorageError> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO trail_filter_preset (name, edge_kinds, node_kinds)
+             VALUES (?1, ?2, ?3)",
+            params![
+                preset.name,
+                kinds_db_value(preset.edge_kinds.iter().map(|kind| *kind as i32)),
+                kinds_db_value(preset.node_kinds.iter().map(|kind| *kind as i32)),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Delete the preset called `name`. Returns whether it existed.
+    pub fn delete_trail_filter_preset(&self, name: &str) -> Result<bool, StorageError> {
+        let deleted = self.conn.execute(
+            "DELETE FROM trail_filter_preset WHERE name = ?1",
+            params![name],
+        )?;
+        Ok(deleted > 0)
+    }
+
+    /// Copy saved presets from the database at `source_path`, so they survive
+    /// a full re-index.
+    pub fn copy_trail_filter_presets_from(
+        &mut self,
+        source_path: &Path,
+    ) -> Result<usize, StorageError> {
+        if !source_path.exists() {
+            return Ok(0);
+        }
+        drop(Storage::open(source_path)?);
+        let source = source_path.to_string_lossy().to_string();
+        self.conn
+            .execute("ATTACH DATABASE ?1 AS source_snapshot", params![source])?;
+        let copy_result = self.conn.execute(
+            "INSERT OR REPLACE INTO trail_filter_preset (name, edge_kinds, node_kinds)
+             SELECT name, edge_kinds, node_kinds
+             FROM source_snapshot.trail_filter_preset",
+            [],
+        );
+        let detach_result = self.conn.execute("DETACH DATABASE source_snapshot", []);
+        let copied = copy_result?;
+        detach_result?;
+        Ok(copied)
+    }
+}
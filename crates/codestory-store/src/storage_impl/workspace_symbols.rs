@@ -0,0 +1,111 @@
+use super::*;
+
+/// Which nodes [`Storage::workspace_symbols`] lists.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WorkspaceSymbolFilter {
+    /// Case-insensitive substring of the qualified name, or of the serialized
+    /// name when there is none.
+    pub name_contains: Option<String>,
+    /// Kinds to list. Empty lists every kind but files and unknown nodes.
+    pub kinds: Vec<NodeKind>,
+    /// Most symbols listed per kind.
+    pub limit_per_kind: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkspaceSymbol {
+    pub id: NodeId,
+    pub name: String,
+    pub file_path: Option<String>,
+    pub line: Option<u32>,
+}
+
+/// The listed symbols of one kind, and how many matched in all.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkspaceSymbolGroup {
+    pub kind: NodeKind,
+    pub count: u32,
+    pub symbols: Vec<WorkspaceSymbol>,
+}
+
+impl Storage {
+    /// Nodes matching `filter`, grouped by kind in kind order, each group
+    /// ordered by name, then node id.
+    pub fn workspace_symbols(
+        &self,
+        filter: &WorkspaceSymbolFilter,
+    ) -> Result<Vec<WorkspaceSymbolGroup>, StorageError> {
+        let mut values = Vec::<rusqlite::types::Value>::new();
+        let mut bind = |value: rusqlite::types::Value| {
+            values.push(value);
+            values.len()
+        };
+        let (kind_operator, kinds) = if filter.kinds.is_empty() {
+            ("NOT IN", vec![NodeKind::FILE, NodeKind::UNKNOWN])
+        } else {
+            ("IN", filter.kinds.clone())
+        };
+        let kind_params = kinds
+            .iter()
+            .map(|kind| format!("?{}", bind((*kind as i64).into())))
+            .collect::<Vec<_>>()
+            .join(",");
+        let mut conditions = vec![format!("n.kind {kind_operator} ({kind_params})")];
+        if let Some(needle) = filter
+            .name_contains
+            .as_deref()
+            .map(str::trim)
+            .filter(|needle| !needle.is_empty())
+        {
+            let escaped = needle
+                .replace('\\', "\\\\")
+                .replace('%', "\\%")
+                .replace('_', "\\_");
+            let param = bind(format!("%{escaped}%").into());
+            conditions.push(format!(
+                "COALESCE(n.qualified_name, n.serialized_name) LIKE ?{param} ESCAPE '\\'"
+            ));
+        }
+        let limit_param = bind((filter.limit_per_kind.max(1) as i64).into());
+        // One pass counts every match per kind and ranks it within the kind;
+        // rows past the limit only feed the count.
+        let query = format!(
+            "SELECT id, kind, name, path, start_line, kind_count FROM (
+                SELECT n.id, n.kind, COALESCE(n.qualified_name, n.serialized_name) AS name,
+                       f.path, n.start_line,
+                       COUNT(*) OVER (PARTITION BY n.kind) AS kind_count,
+                       ROW_NUMBER() OVER (
+                           PARTITION BY n.kind
+                           ORDER BY COALESCE(n.qualified_name, n.serialized_name), n.id
+                       ) AS kind_rank
+                FROM node n
+                LEFT JOIN file f ON f.id = n.file_node_id
+                WHERE {}
+             )
+             WHERE kind_rank <= ?{limit_param}
+             ORDER BY kind, kind_rank",
+            conditions.join(" AND ")
+        );
+        let mut stmt = self.conn.prepare(&query)?;
+        let mut rows = stmt.query(params_from_iter(values))?;
+        let mut groups = Vec::<WorkspaceSymbolGroup>::new();
+        while let Some(row) = rows.next()? {
+            let kind = NodeKind::try_from(row.get::<_, i32>(1)?)?;
+            let symbol = WorkspaceSymbol {
+                id: NodeId(row.get(0)?),
+                name: row.get(2)?,
+                file_path: row.get(3)?,
+                line: row.get(4)?,
+            };
+            match groups.last_mut() {
+                Some(group) if group.kind == kind => group.symbols.push(symbol),
+                _ => groups.push(WorkspaceSymbolGroup {
+                    kind,
+                    count: row.get(5)?,
+                    symbols: vec![symbol],
+                }),
+            }
+        }
+        Ok(groups)
+    }
+}
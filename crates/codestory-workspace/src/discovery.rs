@@ -0,0 +1,72 @@
+//! Directory walking shared by every CodeStory file discovery pass.
+//!
+//! Workspace inventory and auxiliary walks in the indexer build their walkers
+//! here so they agree on which files exist: `.gitignore` applies with or
+//! without an enclosing git repository, `.ignore` and `.codestoryignore` apply
+//! per directory, hidden entries are skipped, and symlinks are followed.
+
+use ignore::gitignore::GitignoreBuilder;
+use std::path::Path;
+
+/// Per-directory ignore file honored by discovery in addition to `.gitignore`
+/// and `.ignore`. Patterns use gitignore syntax and apply to the directory
+/// holding the file and everything below it.
+pub const CODESTORY_IGNORE_FILE_NAME: &str = ".codestoryignore";
+
+/// Walker over `root` with CodeStory's ignore semantics. Callers add their
+/// own `filter_entry` for excludes and language filters.
+pub fn walk_builder(root: &Path) -> ignore::WalkBuilder {
+    let mut builder = ignore::WalkBuilder::new(root);
+    builder.follow_links(true);
+    builder.require_git(false);
+    builder.add_custom_ignore_filename(CODESTORY_IGNORE_FILE_NAME);
+    builder
+}
+
+/// Whether a [`walk_builder`] walk of `root` would skip `path`, which lies
+/// below `root`, because an entry on the way down is hidden or ignored.
+/// Lets callers check one file without walking its directory.
+pub fn is_ignored_below(root: &Path, path: &Path) -> bool {
+    let Ok(relative) = path.strip_prefix(root) else {
+        return true;
+    };
+    let components = relative.components().collect::<Vec<_>>();
+    let mut entry = root.to_path_buf();
+    for (index, component) in components.iter().enumerate() {
+        entry.push(component);
+        if component.as_os_str().to_string_lossy().starts_with('.') {
+            return true;
+        }
+        if ignore_files_exclude(&entry, index + 1 < components.len()) {
+            return true;
+        }
+    }
+    false
+}
+
+/// Verdict of the nearest ignore file with a rule for `entry`. Within one
+/// directory `.codestoryignore` wins over `.ignore`, which wins over
+/// `.gitignore`.
+fn ignore_files_exclude(entry: &Path, is_dir: bool) -> bool {
+    for dir in entry.ancestors().skip(1) {
+        for name in [CODESTORY_IGNORE_FILE_NAME, ".ignore", ".gitignore"] {
+            let file = dir.join(name);
+            if !file.is_file() {
+                continue;
+            }
+            let mut builder = GitignoreBuilder::new(dir);
+            let _ = builder.add(&file);
+            let Ok(matcher) = builder.build() else {
+                continue;
+            };
+            let verdict = matcher.matched(entry, is_dir);
+            if verdict.is_ignore() {
+                return true;
+            }
+            if verdict.is_whitelist() {
+                return false;
+            }
+        }
+    }
+    false
+}
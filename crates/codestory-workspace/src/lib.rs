@@ -27,8 +27,12 @@ use std::path::{Component, Path, PathBuf};
 use uuid::Uuid;
 
 pub mod atomic_file;
+pub mod discovery;
 pub mod owned_deletion;
+mod presets;
 mod repository_identity;
+pub use discovery::CODESTORY_IGNORE_FILE_NAME;
+pub use presets::WorkspacePreset;
 pub use repository_identity::{
     PROJECT_IDENTITY_SCHEMA_VERSION, PROJECT_IDENTITY_V3_SCHEMA_VERSION, ProjectIdentityV2,
     ProjectIdentityV3, REPOSITORY_IDENTITY_SCHEMA_VERSION, REPOSITORY_IDENTITY_V2_SCHEMA_VERSION,
@@ -63,6 +67,7 @@ pub enum Language {
     Dart,
     Lua,
     Sql,
+    Protobuf,
     Html,
     Css,
     Bash,
@@ -138,12 +143,20 @@ pub enum LanguageSpecificSettings {
         precompiled_header: Option<PathBuf>,
     },
     Java {
+        /// Classpath entries; directories among them are source roots.
         classpath: Vec<PathBuf>,
+        /// Maven build whose `src/main/java` and `src/test/java` are source
+        /// roots, given as the `pom.xml` or the directory holding it.
         maven_path: Option<PathBuf>,
+        /// Gradle build whose conventional source directories are source
+        /// roots, given as the build file or the directory holding it.
         gradle_path: Option<PathBuf>,
     },
     Python {
+        /// Extra directory searched for absolute imports before the source
+        /// paths, like a `PYTHONPATH` entry.
         python_path: Option<PathBuf>,
+        /// Virtual environment whose installed packages are third-party.
         virtual_env: Option<PathBuf>,
     },
     Other,
@@ -589,6 +602,17 @@ impl WorkspaceManifest {
         WorkspaceDiscovery.build_refresh_outcome_with_index_policy(self, inputs, policy)
     }
 
+    /// Build a policy-aware refresh outcome for one file without walking the
+    /// workspace.
+    pub fn build_file_execution_outcome_with_policy(
+        &self,
+        inputs: &RefreshInputs,
+        path: &Path,
+        policy: &SourceIndexPolicy,
+    ) -> Result<WorkspacePolicyRefreshOutcome> {
+        WorkspaceDiscovery.build_file_refresh_outcome_with_index_policy(self, inputs, path, policy)
+    }
+
     /// Build a policy-aware refresh outcome with the ordinary current-file discovery bound.
     pub fn build_execution_outcome_bounded_with_oversized_policy(
         &self,
@@ -770,82 +794,140 @@ impl WorkspaceDiscovery {
             bail!("bounded source policy requires non-zero caps and a non-empty version");
         }
         let inventory = self.source_inventory_inner(manifest, max_files)?;
-        if !inventory.outcome.is_complete() {
-            return Ok(WorkspacePolicyFileInventory {
-                files: inventory.files,
-                policy_exclusions: Vec::new(),
-                outcome: inventory.outcome,
-                issues: inventory.issues,
-            });
-        }
+        Ok(classify_source_policy(
+            manifest,
+            inventory,
+            byte_cap,
+            policy_version,
+            structural_unit_cap,
+        ))
+    }
 
-        let root = workspace_root(manifest);
-        let mut files = Vec::with_capacity(inventory.files.len());
-        let mut policy_exclusions = Vec::new();
-        let mut issues = inventory.issues;
-        for path in inventory.files {
-            let metadata = match fs::metadata(&path) {
-                Ok(metadata) => metadata,
-                Err(error) => {
-                    issues.push(WorkspaceInventoryIssue {
-                        path: path.clone(),
-                        message: format!("failed to inspect policy candidate: {error}"),
-                    });
-                    files.push(path);
-                    continue;
-                }
-            };
-            if metadata.len() <= byte_cap {
-                files.push(path);
-                continue;
-            }
-            let normalized_path = match normalized_policy_path(&root, &path) {
-                Ok(path) => path,
-                Err(error) => {
-                    issues.push(WorkspaceInventoryIssue {
-                        path: path.clone(),
-                        message: error.to_string(),
-                    });
-                    files.push(path);
+    /// Whether full discovery of `manifest` would list `path`, decided
+    /// without walking any source directory.
+    pub fn source_file_is_member(&self, manifest: &WorkspaceManifest, path: &Path) -> Result<bool> {
+        let path = normalize_lexical_path(path);
+        if !fs::metadata(&path).is_ok_and(|metadata| metadata.is_file()) {
+            return Ok(false);
+        }
+        let discovery_exclusions = observe_discovery_exclusions(manifest).map_err(|error| {
+            anyhow::anyhow!(
+                "failed to observe caller-owned discovery exclusion {}: {}",
+                error.path.display(),
+                error.error
+            )
+        })?;
+        if discovery_exclusions.file_is_excluded(&path)? {
+            return Ok(false);
+        }
+        let workspace_root = workspace_root(manifest);
+        let filter_by_language = manifest.should_filter_source_group_language();
+        for group in &manifest.settings.source_groups {
+            let exclude_patterns = compile_exclude_patterns(&group.exclude_patterns)?;
+            for source_path in &group.source_paths {
+                let full_path = resolve_manifest_source_path(manifest, source_path)?;
+                if discovery_exclusions.directory_contains(&full_path)
+                    || workspace_structural_source_exclusion(&workspace_root, &full_path).is_some()
+                {
                     continue;
                 }
-            };
-            let (content_hash, observed_size) = match current_content_identity(&path) {
-                Ok(identity) => identity,
-                Err(error) => {
-                    issues.push(WorkspaceInventoryIssue {
-                        path: path.clone(),
-                        message: format!("failed to verify oversized policy candidate: {error}"),
-                    });
-                    files.push(path);
-                    continue;
+                let source_root = discovery_root(&full_path);
+                let path_filter = DiscoveryPathFilter {
+                    workspace_root: &workspace_root,
+                    source_root: &source_root,
+                    filter_by_language,
+                    language: &group.language,
+                    exclude_patterns: &exclude_patterns,
+                    discovery_exclusions: &discovery_exclusions,
+                };
+                // The walker lists files under the spelling of the source
+                // path, which may differ from `path` by a symlinked root.
+                let walked = match path.strip_prefix(&source_root) {
+                    Ok(relative) if !path.starts_with(&full_path) => full_path.join(relative),
+                    _ => path.clone(),
+                };
+                let reachable = full_path == walked
+                    || (full_path.is_dir()
+                        && walked.starts_with(&full_path)
+                        && walked
+                            .ancestors()
+                            .skip(1)
+                            .take_while(|dir| *dir != full_path)
+                            .all(|dir| should_include_discovered_path(dir, true, &path_filter))
+                        && !discovery::is_ignored_below(&full_path, &walked));
+                if reachable && should_include_discovered_path(&walked, false, &path_filter) {
+                    return Ok(true);
                 }
-            };
-            if observed_size <= byte_cap {
-                files.push(path);
-                continue;
             }
-            policy_exclusions.push(OversizedSourceExclusionCandidate {
-                normalized_path,
-                content_hash,
-                observed_size,
-                observed_unit_count: 0,
-                policy_version: policy_version.to_string(),
-                byte_cap,
-                structural_unit_cap,
-            });
         }
-        policy_exclusions.sort_by(|left, right| left.normalized_path.cmp(&right.normalized_path));
-        let outcome = if issues.is_empty() {
-            WorkspaceInventoryOutcome::Complete
+        Ok(false)
+    }
+
+    /// Build a policy-aware refresh outcome for `path` alone.
+    ///
+    /// Only `path` is checked against the manifest: it is indexed when
+    /// discovery would list it and its stored row is removed when it would
+    /// not. Policy exclusions of every other file are carried forward.
+    pub fn build_file_refresh_outcome_with_index_policy(
+        &self,
+        manifest: &WorkspaceManifest,
+        inputs: &RefreshInputs,
+        path: &Path,
+        policy: &SourceIndexPolicy,
+    ) -> Result<WorkspacePolicyRefreshOutcome> {
+        let root = manifest.root_dir();
+        let key = normalized_compare_key(&root, path);
+        let files = if self.source_file_is_member(manifest, path)? {
+            vec![normalize_lexical_path(path)]
         } else {
-            WorkspaceInventoryOutcome::Partial
+            Vec::new()
         };
-        Ok(WorkspacePolicyFileInventory {
-            files,
+        let mut inventory = classify_source_policy(
+            manifest,
+            WorkspaceFileInventory {
+                files,
+                outcome: WorkspaceInventoryOutcome::Complete,
+                issues: Vec::new(),
+            },
+            policy.byte_cap,
+            &policy.policy_version,
+            policy.structural_unit_cap,
+        );
+        let (file_exclusions, other_exclusions): (Vec<_>, Vec<_>) = inputs
+            .policy_exclusions
+            .iter()
+            .cloned()
+            .partition(|candidate| {
+                normalized_compare_key(&root, &root.join(&candidate.normalized_path)) == key
+            });
+        let file_inputs = RefreshInputs {
+            stored_files: inputs
+                .inventory_map()
+                .into_values()
+                .filter(|file| normalized_compare_key(&root, &file.path) == key)
+                .collect(),
+            policy_exclusions: file_exclusions,
+            inventory: WorkspaceInventory::default(),
+        };
+        self.carry_forward_verified_policy_exclusions(
+            manifest,
+            &file_inputs,
+            policy,
+            &mut inventory,
+        );
+        let refresh = build_refresh_outcome_from_inventory(
+            manifest,
+            &file_inputs,
+            inventory.files,
+            inventory.outcome,
+            inventory.issues,
+        )?;
+        let mut policy_exclusions = other_exclusions;
+        policy_exclusions.extend(inventory.policy_exclusions);
+        policy_exclusions.sort_by(|left, right| left.normalized_path.cmp(&right.normalized_path));
+        Ok(WorkspacePolicyRefreshOutcome {
+            refresh,
             policy_exclusions,
-            outcome,
-            issues,
         })
     }
 
@@ -950,9 +1032,7 @@ impl WorkspaceDiscovery {
                     continue;
                 }
                 if metadata.is_dir() {
-                    let mut builder = ignore::WalkBuilder::new(&full_path);
-                    builder.follow_links(true);
-                    builder.require_git(false);
+                    let mut builder = discovery::walk_builder(&full_path);
                     let workspace_root_for_filter = workspace_root.clone();
                     let source_root_for_filter = source_root.clone();
                     let exclude_patterns = exclude_patterns.clone();
@@ -1242,6 +1322,94 @@ impl WorkspaceDiscovery {
     }
 }
 
+/// Split a discovered inventory into parser candidates and files over the
+/// byte cap. Incomplete inventories are returned without classification.
+fn classify_source_policy(
+    manifest: &WorkspaceManifest,
+    inventory: WorkspaceFileInventory,
+    byte_cap: u64,
+    policy_version: &str,
+    structural_unit_cap: u64,
+) -> WorkspacePolicyFileInventory {
+    if !inventory.outcome.is_complete() {
+        return WorkspacePolicyFileInventory {
+            files: inventory.files,
+            policy_exclusions: Vec::new(),
+            outcome: inventory.outcome,
+            issues: inventory.issues,
+        };
+    }
+
+    let root = workspace_root(manifest);
+    let mut files = Vec::with_capacity(inventory.files.len());
+    let mut policy_exclusions = Vec::new();
+    let mut issues = inventory.issues;
+    for path in inventory.files {
+        let metadata = match fs::metadata(&path) {
+            Ok(metadata) => metadata,
+            Err(error) => {
+                issues.push(WorkspaceInventoryIssue {
+                    path: path.clone(),
+                    message: format!("failed to inspect policy candidate: {error}"),
+                });
+                files.push(path);
+                continue;
+            }
+        };
+        if metadata.len() <= byte_cap {
+            files.push(path);
+            continue;
+        }
+        let normalized_path = match normalized_policy_path(&root, &path) {
+            Ok(path) => path,
+            Err(error) => {
+                issues.push(WorkspaceInventoryIssue {
+                    path: path.clone(),
+                    message: error.to_string(),
+                });
+                files.push(path);
+                continue;
+            }
+        };
+        let (content_hash, observed_size) = match current_content_identity(&path) {
+            Ok(identity) => identity,
+            Err(error) => {
+                issues.push(WorkspaceInventoryIssue {
+                    path: path.clone(),
+                    message: format!("failed to verify oversized policy candidate: {error}"),
+                });
+                files.push(path);
+                continue;
+            }
+        };
+        if observed_size <= byte_cap {
+            files.push(path);
+            continue;
+        }
+        policy_exclusions.push(OversizedSourceExclusionCandidate {
+            normalized_path,
+            content_hash,
+            observed_size,
+            observed_unit_count: 0,
+            policy_version: policy_version.to_string(),
+            byte_cap,
+            structural_unit_cap,
+        });
+    }
+    policy_exclusions.sort_by(|left, right| left.normalized_path.cmp(&right.normalized_path));
+    let outcome = if issues.is_empty() {
+        WorkspaceInventoryOutcome::Complete
+    } else {
+        WorkspaceInventoryOutcome::Partial
+    };
+    WorkspacePolicyFileInventory {
+        files,
+        policy_exclusions,
+        outcome,
+        issues,
+    }
+}
+
 fn build_refresh_outcome_from_inventory(
     manifest: &WorkspaceManifest,
     inputs: &RefreshInputs,
@@ -1766,6 +1934,7 @@ fn source_group_accepts_registry_language(language: &Language, registry_language
             | (&Language::Swift, "swift")
             | (&Language::Dart, "dart")
             | (&Language::Sql, "sql")
+            | (&Language::Protobuf, "protobuf")
             | (&Language::Html, "html")
             | (&Language::Css, "css")
             | (&Language::Bash, "bash")
@@ -1783,6 +1952,7 @@ fn compatibility_extension_matches_source_group(extension: &str, language: &Lang
         (language, extension),
         (&Language::JavaScript, "svelte" | "vue" | "astro")
             | (&Language::TypeScript, "svelte" | "vue" | "astro")
+            | (&Language::Python, "ipynb")
             | (&Language::CSharp, "cshtml")
             | (&Language::Lua, "lua")
             | (&Language::Css, "scss" | "sass" | "less")
@@ -1975,6 +2145,48 @@ mod tests {
     use std::path::Path;
     use tempfile::tempdir;
 
+    #[test]
+    fn preset_manifest_filters_by_language_and_refuses_to_overwrite() -> Result<()> {
+        let project = tempdir()?;
+        fs::create_dir_all(project.path().join("src"))?;
+        fs::create_dir_all(project.path().join("target/debug"))?;
+        fs::write(
+            project.path().join("Cargo.toml"),
+            "[package]\nname = \"demo\"\n",
+        )?;
+        fs::write(project.path().join("src/lib.rs"), "pub fn demo() {}\n")?;
+        fs::write(project.path().join("src/helper.py"), "def helper(): pass\n")?;
+        fs::write(
+            project.path().join("target/debug/build.rs"),
+            "fn main() {}\n",
+        )?;
+
+        let manifest = WorkspaceManifest::create(
+            project.path().to_path_buf(),
+            WorkspacePreset::RustWorkspace,
+        )?;
+        assert!(project.path().join("codestory_project.json").is_file());
+        let files = WorkspaceManifest::open(project.path().to_path_buf())?
+            .source_files()?
+            .into_iter()
+            .filter_map(|path| workspace_relative_path(&manifest.root_dir(), &path))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            files,
+            vec![PathBuf::from("Cargo.toml"), PathBuf::from("src/lib.rs")]
+        );
+
+        assert!(
+            WorkspaceManifest::create(project.path().to_path_buf(), WorkspacePreset::PythonPackage)
+                .is_err()
+        );
+        assert_eq!(
+            WorkspacePreset::parse("python_package"),
+            Some(WorkspacePreset::PythonPackage)
+        );
+        Ok(())
+    }
+
     #[test]
     fn exact_project_path_resolution_distinguishes_existing_missing_and_outside() -> Result<()> {
         let project = tempdir()?;
@@ -2136,6 +2348,72 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn single_file_refresh_checks_membership_without_touching_other_rows() -> Result<()> {
+        let temp = tempdir()?;
+        let root = temp.path().join("repo");
+        fs::create_dir_all(root.join("src"))?;
+        fs::create_dir_all(root.join("generated"))?;
+        fs::write(root.join(".gitignore"), "generated/\n")?;
+        let saved = root.join("src").join("lib.rs");
+        let other = root.join("src").join("other.rs");
+        let ignored = root.join("generated").join("out.rs");
+        for path in [&saved, &other, &ignored] {
+            fs::write(path, "pub fn f() {}\n")?;
+        }
+        let stored = |id: i64, path: &Path| StoredFileState {
+            id,
+            path: path.to_path_buf(),
+            modification_time: 0,
+            content_hash: None,
+            indexed: true,
+            complete: true,
+            retry_required: false,
+        };
+        let inputs = RefreshInputs {
+            stored_files: vec![
+                stored(1, &saved),
+                stored(2, &other),
+                stored(3, &ignored),
+                stored(4, &root.join("src").join("gone.rs")),
+            ],
+            policy_exclusions: Vec::new(),
+            inventory: WorkspaceInventory::default(),
+        };
+        let manifest = WorkspaceManifest::open(root.clone())?;
+        let policy = SourceIndexPolicy::default();
+
+        assert!(WorkspaceDiscovery.source_file_is_member(&manifest, &saved)?);
+        assert!(!WorkspaceDiscovery.source_file_is_member(&manifest, &ignored)?);
+
+        let saved_plan = manifest
+            .build_file_execution_outcome_with_policy(&inputs, &saved, &policy)?
+            .refresh
+            .plan;
+        assert_eq!(saved_plan.files_to_index, vec![saved.clone()]);
+        assert!(saved_plan.files_to_remove.is_empty());
+        assert_eq!(saved_plan.existing_file_ids.get(&saved), Some(&1));
+
+        let ignored_plan = manifest
+            .build_file_execution_outcome_with_policy(&inputs, &ignored, &policy)?
+            .refresh
+            .plan;
+        assert!(ignored_plan.files_to_index.is_empty());
+        assert_eq!(ignored_plan.files_to_remove, vec![3]);
+
+        let gone_plan = manifest
+            .build_file_execution_outcome_with_policy(
+                &inputs,
+                &root.join("src").join("gone.rs"),
+                &policy,
+            )?
+            .refresh
+            .plan;
+        assert!(gone_plan.files_to_index.is_empty());
+        assert_eq!(gone_plan.files_to_remove, vec![4]);
+        Ok(())
+    }
+
     #[test]
     fn structural_exclusion_uses_only_workspace_relative_descendants() -> Result<()> {
         let temp = tempdir()?;
@@ -2751,6 +3029,65 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn codestoryignore_files_prune_their_own_directory_subtree() -> Result<()> {
+        let temp = tempdir()?;
+        let root = temp.path().join("repo");
+        let vendored = root.join("pkg").join("vendored");
+        fs::create_dir_all(&vendored)?;
+        fs::write(root.join("lib.rs"), "pub fn indexed() {}\n")?;
+        fs::write(root.join("pkg").join("api.rs"), "pub fn api() {}\n")?;
+        fs::write(
+            root.join("pkg").join("api_gen.rs"),
+            "pub fn generated() {}\n",
+        )?;
+        fs::write(vendored.join("dep.rs"), "pub fn dep() {}\n")?;
+        fs::write(root.join("top_gen.rs"), "pub fn kept() {}\n")?;
+        fs::write(
+            root.join("pkg").join(CODESTORY_IGNORE_FILE_NAME),
+            "vendored/\n*_gen.rs\n",
+        )?;
+
+        let files = WorkspaceManifest::open(root.clone())?.source_files()?;
+
+        assert!(files.contains(&root.join("lib.rs")));
+        assert!(files.contains(&root.join("pkg").join("api.rs")));
+        assert!(files.contains(&root.join("top_gen.rs")));
+        assert!(!files.contains(&root.join("pkg").join("api_gen.rs")));
+        assert!(!files.contains(&vendored.join("dep.rs")));
+        Ok(())
+    }
+
+    #[test]
+    fn shared_walker_matches_inventory_ignore_semantics_outside_git() -> Result<()> {
+        let temp = tempdir()?;
+        let root = temp.path().join("repo");
+        fs::create_dir_all(root.join("generated"))?;
+        fs::create_dir_all(root.join(".cache"))?;
+        fs::write(root.join(".gitignore"), "generated/\n")?;
+        fs::write(root.join("lib.rs"), "pub fn kept() {}\n")?;
+        fs::write(
+            root.join("generated").join("out.rs"),
+            "pub fn ignored() {}\n",
+        )?;
+        fs::write(
+            root.join(".cache").join("hidden.rs"),
+            "pub fn hidden() {}\n",
+        )?;
+
+        let walked = discovery::walk_builder(&root)
+            .build()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_some_and(|kind| kind.is_file()))
+            .map(|entry| entry.into_path())
+            .collect::<Vec<_>>();
+        let inventory = WorkspaceManifest::open(root.clone())?.source_files()?;
+
+        assert_eq!(walked, vec![root.join("lib.rs")]);
+        assert_eq!(inventory, walked);
+        Ok(())
+    }
+
     #[test]
     fn caller_owned_generated_roots_exclude_descendants_without_hiding_siblings() -> Result<()> {
         let temp = tempdir()?;
@@ -3014,6 +3351,7 @@ mod tests {
             Language::Swift,
             Language::Dart,
             Language::Sql,
+            Language::Protobuf,
             Language::Html,
             Language::Css,
             Language::Bash,
@@ -3046,6 +3384,7 @@ mod tests {
         }
 
         let compatibility_only = [
+            ("ipynb", Language::Python),
             ("cshtml", Language::CSharp),
             ("svelte", Language::JavaScript),
             ("svelte", Language::TypeScript),
@@ -0,0 +1,190 @@
+use super::*;
+
+/// Starting configuration for a new project manifest.
+///
+/// Each preset writes explicit source groups, so discovery filters files by
+/// language instead of keeping everything the way a synthetic default does.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum WorkspacePreset {
+    /// Cargo workspace: Rust sources and manifests, without `target`.
+    RustWorkspace,
+    /// JavaScript/TypeScript monorepo: every package, without installed
+    /// dependencies or bundler output.
+    NodeMonorepo,
+    /// CMake C/C++ project, using `compile_commands.json` from the build
+    /// directory when one exists.
+    CxxCmake,
+    /// Python package, using the project virtual environment when one exists.
+    PythonPackage,
+}
+
+const VCS_EXCLUDES: &[&str] = &["**/.git/**", "**/.hg/**", "**/.svn/**"];
+
+impl WorkspacePreset {
+    pub const ALL: [Self; 4] = [
+        Self::RustWorkspace,
+        Self::NodeMonorepo,
+        Self::CxxCmake,
+        Self::PythonPackage,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::RustWorkspace => "rust_workspace",
+            Self::NodeMonorepo => "node_monorepo",
+            Self::CxxCmake => "cxx_cmake",
+            Self::PythonPackage => "python_package",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|preset| preset.as_str() == value)
+    }
+
+    /// Source groups for a project rooted at `root`. Paths are relative to
+    /// `root`; `root` is only inspected to find optional build metadata.
+    pub fn source_groups(self, root: &Path) -> Vec<SourceGroupSettings> {
+        match self {
+            Self::RustWorkspace => vec![preset_group(
+                Language::Rust,
+                LanguageStandard::Default,
+                &["**/target/**"],
+                LanguageSpecificSettings::Other,
+            )],
+            Self::NodeMonorepo => {
+                let excludes = [
+                    "**/node_modules/**",
+                    "**/dist/**",
+                    "**/build/**",
+                    "**/coverage/**",
+                    "**/.next/**",
+                    "**/.turbo/**",
+                    "**/*.min.js",
+                ];
+                vec![
+                    preset_group(
+                        Language::TypeScript,
+                        LanguageStandard::Default,
+                        &excludes,
+                        LanguageSpecificSettings::Other,
+                    ),
+                    preset_group(
+                        Language::JavaScript,
+                        LanguageStandard::Default,
+                        &excludes,
+                        LanguageSpecificSettings::Other,
+                    ),
+                ]
+            }
+            Self::CxxCmake => {
+                let cdb_path = ["build", "out/build", "cmake-build-debug"]
+                    .iter()
+                    .map(|dir| Path::new(dir).join("compile_commands.json"))
+                    .chain([PathBuf::from("compile_commands.json")])
+                    .find(|path| root.join(path).is_file());
+                let header_paths = ["include"]
+                    .iter()
+                    .map(PathBuf::from)
+                    .filter(|path| root.join(path).is_dir())
+                    .collect();
+                vec![preset_group(
+                    Language::Cxx,
+                    LanguageStandard::Cxx17,
+                    &[
+                        "**/build/**",
+                        "**/out/**",
+                        "**/cmake-build-*/**",
+                        "**/CMakeFiles/**",
+                        "**/_deps/**",
+                    ],
+                    LanguageSpecificSettings::Cxx {
+                        cdb_path,
+                        header_paths,
+                        precompiled_header: None,
+                    },
+                )]
+            }
+            Self::PythonPackage => {
+                let virtual_env = [".venv", "venv"]
+                    .iter()
+                    .map(PathBuf::from)
+                    .find(|path| root.join(path).join("pyvenv.cfg").is_file());
+                vec![preset_group(
+                    Language::Python,
+                    LanguageStandard::Default,
+                    &[
+                        "**/.venv/**",
+                        "**/venv/**",
+                        "**/__pycache__/**",
+                        "**/.tox/**",
+                        "**/.mypy_cache/**",
+                        "**/build/**",
+                        "**/dist/**",
+                        "**/*.egg-info/**",
+                    ],
+                    LanguageSpecificSettings::Python {
+                        python_path: None,
+                        virtual_env,
+                    },
+                )]
+            }
+        }
+    }
+}
+
+fn preset_group(
+    language: Language,
+    standard: LanguageStandard,
+    excludes: &[&str],
+    language_specific: LanguageSpecificSettings,
+) -> SourceGroupSettings {
+    SourceGroupSettings {
+        id: Uuid::new_v4(),
+        language,
+        standard,
+        source_paths: vec![PathBuf::from(".")],
+        exclude_patterns: VCS_EXCLUDES
+            .iter()
+            .chain(excludes)
+            .map(|pattern| pattern.to_string())
+            .collect(),
+        include_paths: Vec::new(),
+        defines: HashMap::new(),
+        language_specific,
+    }
+}
+
+impl WorkspaceManifest {
+    /// Write a new `codestory_project.json` under `root_path` configured by
+    /// `preset`, and return it loaded.
+    ///
+    /// Fails if `root_path` already has a project or workspace manifest, so an
+    /// existing configuration is never overwritten.
+    pub fn create(root_path: PathBuf, preset: WorkspacePreset) -> Result<Self> {
+        if !root_path.is_dir() {
+            bail!("project root `{}` is not a directory", root_path.display());
+        }
+        for existing in ["codestory_workspace.json", "codestory_project.json"] {
+            if root_path.join(existing).exists() {
+                bail!(
+                    "`{}` already has a `{existing}` manifest",
+                    root_path.display()
+                );
+            }
+        }
+        let mut manifest = Self::new(
+            root_path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("Project")
+                .to_string(),
+            root_path.join("codestory_project.json"),
+        );
+        manifest.settings.source_groups = preset.source_groups(&root_path);
+        manifest.save()?;
+        Ok(manifest)
+    }
+}